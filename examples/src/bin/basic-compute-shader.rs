@@ -14,13 +14,10 @@
 // GPU", or *GPGPU*. This is what this example demonstrates.
 
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer},
+    buffer::{Buffer, BufferLocation, BufferUsage},
     command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
     descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet},
-    device::{
-        physical::{PhysicalDevice, PhysicalDeviceType},
-        Device, DeviceCreateInfo, DeviceExtensions, QueueCreateInfo,
-    },
+    device::{Device, DeviceExtensions, DeviceSelector, QueueRequirement},
     instance::Instance,
     pipeline::{ComputePipeline, Pipeline, PipelineBindPoint},
     sync::{self, GpuFuture},
@@ -35,23 +32,17 @@ fn main() {
         khr_storage_buffer_storage_class: true,
         ..DeviceExtensions::none()
     };
-    let (physical_device, queue_family) = PhysicalDevice::enumerate(&instance)
-        .filter(|&p| p.supported_extensions().is_superset_of(&device_extensions))
-        .filter_map(|p| {
-            // The Vulkan specs guarantee that a compliant implementation must provide at least one queue
-            // that supports compute operations.
-            p.queue_families()
-                .find(|&q| q.supports_compute())
-                .map(|q| (p, q))
-        })
-        .min_by_key(|(p, _)| match p.properties().device_type {
-            PhysicalDeviceType::DiscreteGpu => 0,
-            PhysicalDeviceType::IntegratedGpu => 1,
-            PhysicalDeviceType::VirtualGpu => 2,
-            PhysicalDeviceType::Cpu => 3,
-            PhysicalDeviceType::Other => 4,
+    let selected = DeviceSelector::new()
+        .required_extensions(device_extensions)
+        // The Vulkan specs guarantee that a compliant implementation must provide at least one queue
+        // that supports compute operations.
+        .queue(QueueRequirement {
+            compute: true,
+            ..Default::default()
         })
+        .select(&instance)
         .unwrap();
+    let physical_device = selected.physical_device;
 
     println!(
         "Using device: {} (type: {:?})",
@@ -60,17 +51,7 @@ fn main() {
     );
 
     // Now initializing the device.
-    let (device, mut queues) = Device::new(
-        physical_device,
-        DeviceCreateInfo {
-            enabled_extensions: physical_device
-                .required_extensions()
-                .union(&device_extensions),
-            queue_create_infos: vec![QueueCreateInfo::family(queue_family)],
-            ..Default::default()
-        },
-    )
-    .unwrap();
+    let (device, mut queues) = Device::new(physical_device, selected.device_create_info()).unwrap();
 
     // Since we can request multiple queues, the `queues` variable is in fact an iterator. In this
     // example we use only one queue, so we just retrieve the first and only element of the
@@ -131,13 +112,13 @@ fn main() {
         // Iterator that produces the data.
         let data_iter = (0..65536u32).map(|n| n);
         // Builds the buffer and fills it with this iterator.
-        CpuAccessibleBuffer::from_iter(
+        Buffer::from_iter(
             device.clone(),
             BufferUsage {
                 storage_buffer: true,
                 ..BufferUsage::none()
             },
-            false,
+            BufferLocation::Upload,
             data_iter,
         )
         .unwrap()
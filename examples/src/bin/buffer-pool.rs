@@ -9,15 +9,9 @@
 
 // BufferPool Example
 //
-// Modified triangle example to show BufferPool
-// Using a pool allows multiple buffers to be "in-flight" simultaneously
+// Modified triangle example to show SubbufferAllocator
+// Using an allocator allows multiple subbuffers to be "in-flight" simultaneously
 //  and is suited to highly dynamic, similar sized chunks of data
-//
-// NOTE:(jdnewman85) ATM (5/4/2020) CpuBufferPool.next() and .chunk() have identical documentation
-//      I was unable to get next() to work. The compiler complained that the resulting buffer
-//      didn't implement VertexSource. Similar issues have been reported.
-//      See: https://github.com/vulkano-rs/vulkano/issues/1221
-//      Finally, I have not profiled CpuBufferPool against CpuAccessibleBuffer
 
 use bytemuck::{Pod, Zeroable};
 use std::{
@@ -25,7 +19,10 @@ use std::{
     time::{SystemTime, UNIX_EPOCH},
 };
 use vulkano::{
-    buffer::CpuBufferPool,
+    buffer::{
+        allocator::{SubbufferAllocator, SubbufferAllocatorCreateInfo},
+        BufferUsage,
+    },
     command_buffer::{
         AutoCommandBufferBuilder, CommandBufferUsage, RenderPassBeginInfo, SubpassContents,
     },
@@ -148,7 +145,13 @@ fn main() {
     };
 
     // Vertex Buffer Pool
-    let buffer_pool: CpuBufferPool<Vertex> = CpuBufferPool::vertex_buffer(device.clone());
+    let buffer_allocator = SubbufferAllocator::new(
+        device.clone(),
+        SubbufferAllocatorCreateInfo {
+            usage: BufferUsage::vertex_buffer(),
+            ..Default::default()
+        },
+    );
 
     mod vs {
         vulkano_shaders::shader! {
@@ -307,8 +310,8 @@ fn main() {
                 ];
                 let num_vertices = data.len() as u32;
 
-                // Allocate a new chunk from buffer_pool
-                let buffer = buffer_pool.chunk(data.to_vec()).unwrap();
+                // Allocate a new subbuffer from buffer_allocator
+                let buffer = buffer_allocator.from_iter(data).unwrap();
                 let mut builder = AutoCommandBufferBuilder::primary(
                     device.clone(),
                     queue.family(),
@@ -18,7 +18,7 @@ use vulkano::{
     instance::{
         debug::{
             DebugUtilsMessageSeverity, DebugUtilsMessageType, DebugUtilsMessenger,
-            DebugUtilsMessengerCreateInfo,
+            DebugUtilsMessengerCreateInfo, ValidationFeatureEnable,
         },
         layers_list, Instance, InstanceCreateInfo, InstanceExtensions,
     },
@@ -62,10 +62,28 @@ fn main() {
     #[cfg(target_os = "macos")]
     let layers = vec!["VK_LAYER_KHRONOS_validation".to_owned()];
 
+    // The validation layer can also perform additional checks, which are disabled by default
+    // because they are slower. They are enabled with the VK_EXT_validation_features extension,
+    // which is provided by the layer itself. Here we turn on synchronization validation in debug
+    // builds, if the layer supports it.
+    let mut extensions = extensions;
+    let mut enabled_validation_features = Vec::new();
+
+    if cfg!(debug_assertions)
+        && layers.iter().any(|layer| {
+            InstanceExtensions::supported_by_layer(layer)
+                .map_or(false, |supported| supported.ext_validation_features)
+        })
+    {
+        extensions.ext_validation_features = true;
+        enabled_validation_features.push(ValidationFeatureEnable::SynchronizationValidation);
+    }
+
     // Important: pass the extension(s) and layer(s) when creating the vulkano instance
     let instance = Instance::new(InstanceCreateInfo {
         enabled_extensions: extensions,
         enabled_layers: layers,
+        enabled_validation_features,
         ..Default::default()
     })
     .expect("failed to create Vulkan instance");
@@ -7,177 +7,46 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use bytemuck::{Pod, Zeroable};
-use std::sync::Arc;
-use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer, TypedBufferAccess},
-    command_buffer::{
-        AutoCommandBufferBuilder, CommandBufferInheritanceInfo, CommandBufferUsage,
-        SecondaryAutoCommandBuffer,
-    },
-    descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet},
-    device::Queue,
-    image::ImageViewAbstract,
-    impl_vertex,
-    pipeline::{
-        graphics::{
-            color_blend::{AttachmentBlend, BlendFactor, BlendOp, ColorBlendState},
-            input_assembly::InputAssemblyState,
-            vertex_input::BuffersDefinition,
-            viewport::{Viewport, ViewportState},
-        },
-        GraphicsPipeline, Pipeline, PipelineBindPoint,
-    },
-    render_pass::Subpass,
+use vulkano::{device::DeviceOwned, pipeline::Pipeline};
+use vulkano_util::{
+    deferred::{FrameSystem, LightingPass},
+    fullscreen_pass::FullscreenPass,
 };
 
 /// Allows applying an ambient lighting to a scene.
 pub struct AmbientLightingSystem {
-    gfx_queue: Arc<Queue>,
-    vertex_buffer: Arc<CpuAccessibleBuffer<[Vertex]>>,
-    subpass: Subpass,
-    pipeline: Arc<GraphicsPipeline>,
+    pass: FullscreenPass,
 }
 
 impl AmbientLightingSystem {
     /// Initializes the ambient lighting system.
-    pub fn new(gfx_queue: Arc<Queue>, subpass: Subpass) -> AmbientLightingSystem {
-        // TODO: vulkano doesn't allow us to draw without a vertex buffer, otherwise we could
-        //       hard-code these values in the shader
-        let vertices = [
-            Vertex {
-                position: [-1.0, -1.0],
-            },
-            Vertex {
-                position: [-1.0, 3.0],
-            },
-            Vertex {
-                position: [3.0, -1.0],
-            },
-        ];
-        let vertex_buffer = {
-            CpuAccessibleBuffer::from_iter(
-                gfx_queue.device().clone(),
-                BufferUsage::all(),
-                false,
-                vertices,
-            )
-            .expect("failed to create buffer")
-        };
-
-        let pipeline = {
-            let vs = vs::load(gfx_queue.device().clone()).expect("failed to create shader module");
-            let fs = fs::load(gfx_queue.device().clone()).expect("failed to create shader module");
-
-            GraphicsPipeline::start()
-                .vertex_input_state(BuffersDefinition::new().vertex::<Vertex>())
-                .vertex_shader(vs.entry_point("main").unwrap(), ())
-                .input_assembly_state(InputAssemblyState::new())
-                .viewport_state(ViewportState::viewport_dynamic_scissor_irrelevant())
-                .fragment_shader(fs.entry_point("main").unwrap(), ())
-                .color_blend_state(ColorBlendState::new(subpass.num_color_attachments()).blend(
-                    AttachmentBlend {
-                        color_op: BlendOp::Add,
-                        color_source: BlendFactor::One,
-                        color_destination: BlendFactor::One,
-                        alpha_op: BlendOp::Max,
-                        alpha_source: BlendFactor::One,
-                        alpha_destination: BlendFactor::One,
-                    },
-                ))
-                .render_pass(subpass.clone())
-                .build(gfx_queue.device().clone())
-                .unwrap()
-        };
+    pub fn new(frame_system: &FrameSystem) -> AmbientLightingSystem {
+        let fs = fs::load(frame_system.device().clone()).expect("failed to create shader module");
+        let pass = frame_system
+            .lighting_pass(fs.entry_point("main").unwrap())
+            .unwrap();
 
-        AmbientLightingSystem {
-            gfx_queue,
-            vertex_buffer,
-            subpass,
-            pipeline,
-        }
+        AmbientLightingSystem { pass }
     }
 
-    /// Builds a secondary command buffer that applies ambient lighting.
+    /// Applies ambient lighting.
     ///
-    /// This secondary command buffer will read `color_input`, multiply it with `ambient_color`
-    /// and write the output to the current framebuffer with additive blending (in other words
-    /// the value will be added to the existing value in the framebuffer, and not replace the
-    /// existing value).
+    /// The fragment shader reads the albedo of each object of the scene from the G-buffer,
+    /// multiplies it with `ambient_color`, and the result is added to the existing value in the
+    /// final image.
     ///
-    /// - `viewport_dimensions` contains the dimensions of the current framebuffer.
-    /// - `color_input` is an image containing the albedo of each object of the scene. It is the
-    ///   result of the deferred pass.
+    /// - `lighting` is the lighting pass of the current frame.
     /// - `ambient_color` is the color to apply.
     ///
-    pub fn draw(
-        &self,
-        viewport_dimensions: [u32; 2],
-        color_input: Arc<dyn ImageViewAbstract + 'static>,
-        ambient_color: [f32; 3],
-    ) -> SecondaryAutoCommandBuffer {
+    pub fn draw(&self, lighting: &mut LightingPass, ambient_color: [f32; 3]) {
         let push_constants = fs::ty::PushConstants {
             color: [ambient_color[0], ambient_color[1], ambient_color[2], 1.0],
         };
 
-        let layout = self.pipeline.layout().set_layouts().get(0).unwrap();
-        let descriptor_set = PersistentDescriptorSet::new(
-            layout.clone(),
-            [WriteDescriptorSet::image_view(0, color_input)],
-        )
-        .unwrap();
-
-        let viewport = Viewport {
-            origin: [0.0, 0.0],
-            dimensions: [viewport_dimensions[0] as f32, viewport_dimensions[1] as f32],
-            depth_range: 0.0..1.0,
-        };
-
-        let mut builder = AutoCommandBufferBuilder::secondary(
-            self.gfx_queue.device().clone(),
-            self.gfx_queue.family(),
-            CommandBufferUsage::MultipleSubmit,
-            CommandBufferInheritanceInfo {
-                render_pass: Some(self.subpass.clone().into()),
-                ..Default::default()
-            },
-        )
-        .unwrap();
-        builder
-            .set_viewport(0, [viewport.clone()])
-            .bind_pipeline_graphics(self.pipeline.clone())
-            .bind_descriptor_sets(
-                PipelineBindPoint::Graphics,
-                self.pipeline.layout().clone(),
-                0,
-                descriptor_set,
-            )
-            .push_constants(self.pipeline.layout().clone(), 0, push_constants)
-            .bind_vertex_buffers(0, self.vertex_buffer.clone())
-            .draw(self.vertex_buffer.len() as u32, 1, 0, 0)
-            .unwrap();
-        builder.build().unwrap()
-    }
-}
-
-#[repr(C)]
-#[derive(Clone, Copy, Debug, Default, Zeroable, Pod)]
-struct Vertex {
-    position: [f32; 2],
-}
-impl_vertex!(Vertex, position);
-
-mod vs {
-    vulkano_shaders::shader! {
-        ty: "vertex",
-        src: "
-#version 450
-
-layout(location = 0) in vec2 position;
-
-void main() {
-    gl_Position = vec4(position, 0.0, 1.0);
-}"
+        lighting
+            .builder()
+            .push_constants(self.pass.pipeline().layout().clone(), 0, push_constants);
+        lighting.draw(&self.pass, ()).unwrap();
     }
 }
 
@@ -187,7 +56,7 @@ mod fs {
         src: "
 #version 450
 
-// The `color_input` parameter of the `draw` method.
+// The albedo of the objects, from the G-buffer.
 layout(input_attachment_index = 0, set = 0, binding = 0) uniform subpassInput u_diffuse;
 
 layout(push_constant) uniform PushConstants {
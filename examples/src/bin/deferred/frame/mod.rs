@@ -7,14 +7,15 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-// This module exposes what is needed in order to draw with a deferred rendering system.
-//
-// The main code is in the `system` module, while the other modules implement the different kinds
-// of lighting sources.
+// This module implements the different kinds of lighting sources, which are drawn in the lighting
+// pass of the `FrameSystem` of the `deferred` module of vulkano-util.
 
-pub use self::system::{DrawPass, Frame, FrameSystem, LightingPass, Pass};
+pub use self::{
+    ambient_lighting_system::AmbientLightingSystem,
+    directional_lighting_system::DirectionalLightingSystem,
+    point_lighting_system::PointLightingSystem,
+};
 
 mod ambient_lighting_system;
 mod directional_lighting_system;
 mod point_lighting_system;
-mod system;
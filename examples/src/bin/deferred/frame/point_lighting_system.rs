@@ -7,205 +7,65 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use bytemuck::{Pod, Zeroable};
 use cgmath::{Matrix4, Vector3};
-use std::sync::Arc;
-use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer, TypedBufferAccess},
-    command_buffer::{
-        AutoCommandBufferBuilder, CommandBufferInheritanceInfo, CommandBufferUsage,
-        SecondaryAutoCommandBuffer,
-    },
-    descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet},
-    device::Queue,
-    image::ImageViewAbstract,
-    impl_vertex,
-    pipeline::{
-        graphics::{
-            color_blend::{AttachmentBlend, BlendFactor, BlendOp, ColorBlendState},
-            input_assembly::InputAssemblyState,
-            vertex_input::BuffersDefinition,
-            viewport::{Viewport, ViewportState},
-        },
-        GraphicsPipeline, Pipeline, PipelineBindPoint,
-    },
-    render_pass::Subpass,
+use vulkano::{device::DeviceOwned, pipeline::Pipeline};
+use vulkano_util::{
+    deferred::{FrameSystem, LightingPass},
+    fullscreen_pass::FullscreenPass,
 };
 
+/// Allows applying a point light source to a scene.
 pub struct PointLightingSystem {
-    gfx_queue: Arc<Queue>,
-    vertex_buffer: Arc<CpuAccessibleBuffer<[Vertex]>>,
-    subpass: Subpass,
-    pipeline: Arc<GraphicsPipeline>,
+    pass: FullscreenPass,
 }
 
 impl PointLightingSystem {
     /// Initializes the point lighting system.
-    pub fn new(gfx_queue: Arc<Queue>, subpass: Subpass) -> PointLightingSystem {
-        // TODO: vulkano doesn't allow us to draw without a vertex buffer, otherwise we could
-        //       hard-code these values in the shader
-        let vertices = [
-            Vertex {
-                position: [-1.0, -1.0],
-            },
-            Vertex {
-                position: [-1.0, 3.0],
-            },
-            Vertex {
-                position: [3.0, -1.0],
-            },
-        ];
-        let vertex_buffer = {
-            CpuAccessibleBuffer::from_iter(
-                gfx_queue.device().clone(),
-                BufferUsage::all(),
-                false,
-                vertices,
-            )
-            .expect("failed to create buffer")
-        };
-
-        let pipeline = {
-            let vs = vs::load(gfx_queue.device().clone()).expect("failed to create shader module");
-            let fs = fs::load(gfx_queue.device().clone()).expect("failed to create shader module");
-
-            GraphicsPipeline::start()
-                .vertex_input_state(BuffersDefinition::new().vertex::<Vertex>())
-                .vertex_shader(vs.entry_point("main").unwrap(), ())
-                .input_assembly_state(InputAssemblyState::new())
-                .viewport_state(ViewportState::viewport_dynamic_scissor_irrelevant())
-                .fragment_shader(fs.entry_point("main").unwrap(), ())
-                .color_blend_state(ColorBlendState::new(subpass.num_color_attachments()).blend(
-                    AttachmentBlend {
-                        color_op: BlendOp::Add,
-                        color_source: BlendFactor::One,
-                        color_destination: BlendFactor::One,
-                        alpha_op: BlendOp::Max,
-                        alpha_source: BlendFactor::One,
-                        alpha_destination: BlendFactor::One,
-                    },
-                ))
-                .render_pass(subpass.clone())
-                .build(gfx_queue.device().clone())
-                .unwrap()
-        };
+    pub fn new(frame_system: &FrameSystem) -> PointLightingSystem {
+        let fs = fs::load(frame_system.device().clone()).expect("failed to create shader module");
+        let pass = frame_system
+            .lighting_pass(fs.entry_point("main").unwrap())
+            .unwrap();
 
-        PointLightingSystem {
-            gfx_queue,
-            vertex_buffer,
-            subpass,
-            pipeline,
-        }
+        PointLightingSystem { pass }
     }
 
-    /// Builds a secondary command buffer that applies a point lighting.
-    ///
-    /// This secondary command buffer will read `depth_input` and rebuild the world position of the
-    /// pixel currently being processed (modulo rounding errors). It will then compare this
-    /// position with `position`, and process the lighting based on the distance and orientation
-    /// (similar to the directional lighting system).
+    /// Applies a point lighting.
     ///
-    /// It then writes the output to the current framebuffer with additive blending (in other words
-    /// the value will be added to the existing value in the framebuffer, and not replace the
-    /// existing value).
+    /// The fragment shader reads the depth of each pixel from the G-buffer and rebuilds the world
+    /// position of the pixel currently being processed (modulo rounding errors). It then compares
+    /// this position with `position`, and processes the lighting based on the distance and
+    /// orientation (similar to the directional lighting system). The result is added to the
+    /// existing value in the final image.
     ///
     /// Note that in a real-world application, you probably want to pass additional parameters
     /// such as some way to indicate the distance at which the lighting decrease. In this example
     /// this value is hardcoded in the shader.
     ///
-    /// - `viewport_dimensions` contains the dimensions of the current framebuffer.
-    /// - `color_input` is an image containing the albedo of each object of the scene. It is the
-    ///   result of the deferred pass.
-    /// - `normals_input` is an image containing the normals of each object of the scene. It is the
-    ///   result of the deferred pass.
-    /// - `depth_input` is an image containing the depth value of each pixel of the scene. It is
-    ///   the result of the deferred pass.
+    /// - `lighting` is the lighting pass of the current frame.
     /// - `screen_to_world` is a matrix that turns coordinates from framebuffer space into world
-    ///   space. This matrix is used alongside with `depth_input` to determine the world
-    ///   coordinates of each pixel being processed.
+    ///   space. This matrix is used alongside with the depth of the G-buffer to determine the
+    ///   world coordinates of each pixel being processed.
     /// - `position` is the position of the spot light in world coordinates.
     /// - `color` is the color of the light.
     ///
     pub fn draw(
         &self,
-        viewport_dimensions: [u32; 2],
-        color_input: Arc<dyn ImageViewAbstract + 'static>,
-        normals_input: Arc<dyn ImageViewAbstract + 'static>,
-        depth_input: Arc<dyn ImageViewAbstract + 'static>,
+        lighting: &mut LightingPass,
         screen_to_world: Matrix4<f32>,
         position: Vector3<f32>,
         color: [f32; 3],
-    ) -> SecondaryAutoCommandBuffer {
+    ) {
         let push_constants = fs::ty::PushConstants {
             screen_to_world: screen_to_world.into(),
             color: [color[0], color[1], color[2], 1.0],
             position: position.extend(0.0).into(),
         };
 
-        let layout = self.pipeline.layout().set_layouts().get(0).unwrap();
-        let descriptor_set = PersistentDescriptorSet::new(
-            layout.clone(),
-            [
-                WriteDescriptorSet::image_view(0, color_input),
-                WriteDescriptorSet::image_view(1, normals_input),
-                WriteDescriptorSet::image_view(2, depth_input),
-            ],
-        )
-        .unwrap();
-
-        let viewport = Viewport {
-            origin: [0.0, 0.0],
-            dimensions: [viewport_dimensions[0] as f32, viewport_dimensions[1] as f32],
-            depth_range: 0.0..1.0,
-        };
-
-        let mut builder = AutoCommandBufferBuilder::secondary(
-            self.gfx_queue.device().clone(),
-            self.gfx_queue.family(),
-            CommandBufferUsage::MultipleSubmit,
-            CommandBufferInheritanceInfo {
-                render_pass: Some(self.subpass.clone().into()),
-                ..Default::default()
-            },
-        )
-        .unwrap();
-        builder
-            .set_viewport(0, [viewport.clone()])
-            .bind_pipeline_graphics(self.pipeline.clone())
-            .bind_descriptor_sets(
-                PipelineBindPoint::Graphics,
-                self.pipeline.layout().clone(),
-                0,
-                descriptor_set,
-            )
-            .push_constants(self.pipeline.layout().clone(), 0, push_constants)
-            .bind_vertex_buffers(0, self.vertex_buffer.clone())
-            .draw(self.vertex_buffer.len() as u32, 1, 0, 0)
-            .unwrap();
-        builder.build().unwrap()
-    }
-}
-
-#[repr(C)]
-#[derive(Clone, Copy, Debug, Default, Zeroable, Pod)]
-struct Vertex {
-    position: [f32; 2],
-}
-impl_vertex!(Vertex, position);
-
-mod vs {
-    vulkano_shaders::shader! {
-        ty: "vertex",
-        src: "
-#version 450
-
-layout(location = 0) in vec2 position;
-layout(location = 0) out vec2 v_screen_coords;
-
-void main() {
-    v_screen_coords = position;
-    gl_Position = vec4(position, 0.0, 1.0);
-}"
+        lighting
+            .builder()
+            .push_constants(self.pass.pipeline().layout().clone(), 0, push_constants);
+        lighting.draw(&self.pass, ()).unwrap();
     }
 }
 
@@ -215,11 +75,11 @@ mod fs {
         src: "
 #version 450
 
-// The `color_input` parameter of the `draw` method.
+// The albedo of the objects, from the G-buffer.
 layout(input_attachment_index = 0, set = 0, binding = 0) uniform subpassInput u_diffuse;
-// The `normals_input` parameter of the `draw` method.
+// The normals of the objects in world coordinates, from the G-buffer.
 layout(input_attachment_index = 1, set = 0, binding = 1) uniform subpassInput u_normals;
-// The `depth_input` parameter of the `draw` method.
+// The depth of the objects, from the G-buffer.
 layout(input_attachment_index = 2, set = 0, binding = 2) uniform subpassInput u_depth;
 
 layout(push_constant) uniform PushConstants {
@@ -231,7 +91,8 @@ layout(push_constant) uniform PushConstants {
     vec4 position;
 } push_constants;
 
-layout(location = 0) in vec2 v_screen_coords;
+// The coordinates of the pixel in the final image, from `0.0` to `1.0`.
+layout(location = 0) in vec2 tex_coords;
 layout(location = 0) out vec4 f_color;
 
 void main() {
@@ -242,7 +103,8 @@ void main() {
         discard;
     }
     // Find the world coordinates of the current pixel.
-    vec4 world = push_constants.screen_to_world * vec4(v_screen_coords, in_depth, 1.0);
+    vec2 screen_coords = tex_coords * 2.0 - 1.0;
+    vec4 world = push_constants.screen_to_world * vec4(screen_coords, in_depth, 1.0);
     world /= world.w;
 
     vec3 in_normal = normalize(subpassLoad(u_normals).rgb);
@@ -26,22 +26,25 @@
 // drawn after the lighting, and that the whole process consumes more memory.
 
 use crate::{
-    frame::{FrameSystem, Pass},
+    frame::{AmbientLightingSystem, DirectionalLightingSystem, PointLightingSystem},
     triangle_draw_system::TriangleDrawSystem,
 };
 use cgmath::{Matrix4, SquareMatrix, Vector3};
 use vulkano::{
+    command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage, SubpassContents},
     device::{
         physical::{PhysicalDevice, PhysicalDeviceType},
         Device, DeviceCreateInfo, DeviceExtensions, QueueCreateInfo,
     },
-    image::{view::ImageView, ImageUsage},
+    image::{view::ImageView, ImageUsage, SampleCount},
     instance::{Instance, InstanceCreateInfo},
     swapchain::{
         acquire_next_image, AcquireError, Swapchain, SwapchainCreateInfo, SwapchainCreationError,
     },
     sync::{self, FlushError, GpuFuture},
+    Version,
 };
+use vulkano_util::deferred::{FrameSystem, FrameSystemCreateInfo, Pass};
 use vulkano_win::VkSurfaceBuild;
 use winit::{
     event::{Event, WindowEvent},
@@ -93,12 +96,27 @@ fn main() {
         physical_device.properties().device_type
     );
 
+    // The frame system needs to resolve depth images to use multisampling. This is part of
+    // Vulkan 1.2, and is otherwise provided by an extension that is enabled if it is supported.
+    let optional_extensions = DeviceExtensions {
+        khr_create_renderpass2: true,
+        khr_depth_stencil_resolve: true,
+        khr_maintenance2: true,
+        khr_multiview: true,
+        ..DeviceExtensions::none()
+    };
+
     let (device, mut queues) = Device::new(
         physical_device,
         DeviceCreateInfo {
             enabled_extensions: physical_device
                 .required_extensions()
-                .union(&device_extensions),
+                .union(&device_extensions)
+                .union(
+                    &physical_device
+                        .supported_extensions()
+                        .intersection(&optional_extensions),
+                ),
             queue_create_infos: vec![QueueCreateInfo::family(queue_family)],
             ..Default::default()
         },
@@ -141,10 +159,42 @@ fn main() {
         (swapchain, images)
     };
 
-    // Here is the basic initialization for the deferred system.
-    let mut frame_system = FrameSystem::new(queue.clone(), swapchain.image_format());
-    let triangle_draw_system =
-        TriangleDrawSystem::new(queue.clone(), frame_system.deferred_subpass());
+    // Draw the objects of the scene with 4 samples per pixel if the device supports it.
+    let properties = physical_device.properties();
+    let samples = if properties.framebuffer_color_sample_counts.sample4
+        && properties.framebuffer_depth_sample_counts.sample4
+        && (device.api_version() >= Version::V1_2
+            || device.enabled_extensions().khr_depth_stencil_resolve)
+    {
+        SampleCount::Sample4
+    } else {
+        SampleCount::Sample1
+    };
+
+    // Here is the basic initialization for the deferred system. The depth of the objects is drawn
+    // in a pre-pass, so that the G-buffer is only written once per pixel. This pays off in scenes
+    // where many objects overlap.
+    let mut frame_system = FrameSystem::new(
+        device.clone(),
+        FrameSystemCreateInfo {
+            samples,
+            depth_prepass: true,
+            ..FrameSystemCreateInfo::output_format(swapchain.image_format())
+        },
+    )
+    .unwrap();
+    let triangle_draw_system = TriangleDrawSystem::new(queue.clone(), &frame_system);
+
+    // The lights are drawn in the lighting pass of the frame system, with fragment shaders that
+    // read the G-buffer.
+    let ambient_lighting_system = AmbientLightingSystem::new(&frame_system);
+    let directional_lighting_system = DirectionalLightingSystem::new(&frame_system);
+    let point_lighting_system = PointLightingSystem::new(&frame_system);
+
+    // The matrix that turns world coordinates into 2D coordinates on the framebuffer, and its
+    // inverse, which the point lights use to find the world coordinates of the pixels.
+    let world_to_framebuffer = Matrix4::<f32>::identity();
+    let screen_to_world = world_to_framebuffer.invert().unwrap();
 
     let mut recreate_swapchain = false;
     let mut previous_frame_end = Some(sync::now(device.clone()).boxed());
@@ -203,30 +253,67 @@ fn main() {
                 recreate_swapchain = true;
             }
 
-            let future = previous_frame_end.take().unwrap().join(acquire_future);
-            let mut frame =
-                frame_system.frame(future, images[image_num].clone(), Matrix4::identity());
-            let mut after_future = None;
-            while let Some(pass) = frame.next_pass() {
+            let mut builder = AutoCommandBufferBuilder::primary(
+                device.clone(),
+                queue.family(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+
+            // The objects are drawn with secondary command buffers, while the lights are drawn
+            // inline.
+            let mut frame = frame_system
+                .frame(
+                    &mut builder,
+                    images[image_num].clone(),
+                    SubpassContents::SecondaryCommandBuffers,
+                )
+                .unwrap();
+            while let Some(pass) = frame.next_pass().unwrap() {
                 match pass {
+                    Pass::DepthPrepass(mut draw_pass) => {
+                        let cb = triangle_draw_system.draw_depth(draw_pass.viewport_dimensions());
+                        draw_pass.execute(cb).unwrap();
+                    }
                     Pass::Deferred(mut draw_pass) => {
                         let cb = triangle_draw_system.draw(draw_pass.viewport_dimensions());
-                        draw_pass.execute(cb);
+                        draw_pass.execute(cb).unwrap();
                     }
                     Pass::Lighting(mut lighting) => {
-                        lighting.ambient_light([0.1, 0.1, 0.1]);
-                        lighting.directional_light(Vector3::new(0.2, -0.1, -0.7), [0.6, 0.6, 0.6]);
-                        lighting.point_light(Vector3::new(0.5, -0.5, -0.1), [1.0, 0.0, 0.0]);
-                        lighting.point_light(Vector3::new(-0.9, 0.2, -0.15), [0.0, 1.0, 0.0]);
-                        lighting.point_light(Vector3::new(0.0, 0.5, -0.05), [0.0, 0.0, 1.0]);
-                    }
-                    Pass::Finished(af) => {
-                        after_future = Some(af);
+                        ambient_lighting_system.draw(&mut lighting, [0.1, 0.1, 0.1]);
+                        directional_lighting_system.draw(
+                            &mut lighting,
+                            Vector3::new(0.2, -0.1, -0.7),
+                            [0.6, 0.6, 0.6],
+                        );
+                        point_lighting_system.draw(
+                            &mut lighting,
+                            screen_to_world,
+                            Vector3::new(0.5, -0.5, -0.1),
+                            [1.0, 0.0, 0.0],
+                        );
+                        point_lighting_system.draw(
+                            &mut lighting,
+                            screen_to_world,
+                            Vector3::new(-0.9, 0.2, -0.15),
+                            [0.0, 1.0, 0.0],
+                        );
+                        point_lighting_system.draw(
+                            &mut lighting,
+                            screen_to_world,
+                            Vector3::new(0.0, 0.5, -0.05),
+                            [0.0, 0.0, 1.0],
+                        );
                     }
                 }
             }
+            let command_buffer = builder.build().unwrap();
 
-            let future = after_future
+            let future = previous_frame_end
+                .take()
+                .unwrap()
+                .join(acquire_future)
+                .then_execute(queue.clone(), command_buffer)
                 .unwrap()
                 .then_swapchain_present(queue.clone(), swapchain.clone(), image_num)
                 .then_signal_fence_and_flush();
@@ -10,7 +10,7 @@
 use bytemuck::{Pod, Zeroable};
 use std::sync::Arc;
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer, TypedBufferAccess},
+    buffer::{Buffer, BufferLocation, BufferUsage, Subbuffer},
     command_buffer::{
         AutoCommandBufferBuilder, CommandBufferInheritanceInfo, CommandBufferUsage,
         SecondaryAutoCommandBuffer,
@@ -19,8 +19,8 @@ use vulkano::{
     impl_vertex,
     pipeline::{
         graphics::{
-            depth_stencil::DepthStencilState,
             input_assembly::InputAssemblyState,
+            multisample::MultisampleState,
             vertex_input::BuffersDefinition,
             viewport::{Viewport, ViewportState},
         },
@@ -28,17 +28,23 @@ use vulkano::{
     },
     render_pass::Subpass,
 };
+use vulkano_util::deferred::FrameSystem;
 
 pub struct TriangleDrawSystem {
     gfx_queue: Arc<Queue>,
-    vertex_buffer: Arc<CpuAccessibleBuffer<[Vertex]>>,
+    vertex_buffer: Arc<Subbuffer<[Vertex]>>,
     subpass: Subpass,
     pipeline: Arc<GraphicsPipeline>,
+    // The subpass and the pipeline that draw the depth of the triangle, if the frame system has a
+    // depth pre-pass.
+    depth_prepass: Option<(Subpass, Arc<GraphicsPipeline>)>,
 }
 
 impl TriangleDrawSystem {
     /// Initializes a triangle drawing system.
-    pub fn new(gfx_queue: Arc<Queue>, subpass: Subpass) -> TriangleDrawSystem {
+    pub fn new(gfx_queue: Arc<Queue>, frame_system: &FrameSystem) -> TriangleDrawSystem {
+        let subpass = frame_system.deferred_subpass();
+
         let vertices = [
             Vertex {
                 position: [-0.5, -0.25],
@@ -51,17 +57,17 @@ impl TriangleDrawSystem {
             },
         ];
         let vertex_buffer = {
-            CpuAccessibleBuffer::from_iter(
+            Buffer::from_iter(
                 gfx_queue.device().clone(),
                 BufferUsage::all(),
-                false,
+                BufferLocation::Upload,
                 vertices,
             )
             .expect("failed to create buffer")
         };
 
+        let vs = vs::load(gfx_queue.device().clone()).expect("failed to create shader module");
         let pipeline = {
-            let vs = vs::load(gfx_queue.device().clone()).expect("failed to create shader module");
             let fs = fs::load(gfx_queue.device().clone()).expect("failed to create shader module");
 
             GraphicsPipeline::start()
@@ -70,28 +76,69 @@ impl TriangleDrawSystem {
                 .input_assembly_state(InputAssemblyState::new())
                 .viewport_state(ViewportState::viewport_dynamic_scissor_irrelevant())
                 .fragment_shader(fs.entry_point("main").unwrap(), ())
-                .depth_stencil_state(DepthStencilState::simple_depth_test())
+                .depth_stencil_state(frame_system.deferred_depth_stencil_state())
+                // The number of samples must match the attachments of the subpass.
+                .multisample_state(MultisampleState {
+                    rasterization_samples: subpass.num_samples().unwrap(),
+                    ..Default::default()
+                })
                 .render_pass(subpass.clone())
                 .build(gfx_queue.device().clone())
                 .unwrap()
         };
 
+        // The depth pre-pass uses the same vertex input and vertex shader, so that the triangle
+        // has the same depth in both passes.
+        let depth_prepass = frame_system.depth_prepass_subpass().map(|subpass| {
+            let pipeline = frame_system
+                .depth_prepass_pipeline(
+                    BuffersDefinition::new().vertex::<Vertex>(),
+                    vs.entry_point("main").unwrap(),
+                )
+                .unwrap();
+            (subpass, pipeline)
+        });
+
         TriangleDrawSystem {
             gfx_queue,
             vertex_buffer,
             subpass,
             pipeline,
+            depth_prepass,
         }
     }
 
     /// Builds a secondary command buffer that draws the triangle on the current subpass.
     pub fn draw(&self, viewport_dimensions: [u32; 2]) -> SecondaryAutoCommandBuffer {
+        self.draw_with(&self.subpass, &self.pipeline, viewport_dimensions)
+    }
+
+    /// Builds a secondary command buffer that draws the depth of the triangle in the depth
+    /// pre-pass.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the frame system has no depth pre-pass.
+    pub fn draw_depth(&self, viewport_dimensions: [u32; 2]) -> SecondaryAutoCommandBuffer {
+        let (subpass, pipeline) = self
+            .depth_prepass
+            .as_ref()
+            .expect("the frame system has no depth pre-pass");
+        self.draw_with(subpass, pipeline, viewport_dimensions)
+    }
+
+    fn draw_with(
+        &self,
+        subpass: &Subpass,
+        pipeline: &Arc<GraphicsPipeline>,
+        viewport_dimensions: [u32; 2],
+    ) -> SecondaryAutoCommandBuffer {
         let mut builder = AutoCommandBufferBuilder::secondary(
             self.gfx_queue.device().clone(),
             self.gfx_queue.family(),
             CommandBufferUsage::MultipleSubmit,
             CommandBufferInheritanceInfo {
-                render_pass: Some(self.subpass.clone().into()),
+                render_pass: Some(subpass.clone().into()),
                 ..Default::default()
             },
         )
@@ -105,7 +152,7 @@ impl TriangleDrawSystem {
                     depth_range: 0.0..1.0,
                 }],
             )
-            .bind_pipeline_graphics(self.pipeline.clone())
+            .bind_pipeline_graphics(pipeline.clone())
             .bind_vertex_buffers(0, self.vertex_buffer.clone())
             .draw(self.vertex_buffer.len() as u32, 1, 0, 0)
             .unwrap();
@@ -128,6 +175,10 @@ mod vs {
 
 layout(location = 0) in vec2 position;
 
+// The position must be computed in exactly the same way in the depth pre-pass and in the
+// deferred subpass.
+invariant gl_Position;
+
 void main() {
     gl_Position = vec4(position, 0.0, 1.0);
 }"
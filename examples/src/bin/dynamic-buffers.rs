@@ -16,7 +16,7 @@
 
 use std::mem::size_of;
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer},
+    buffer::{Buffer, BufferLocation, BufferUsage},
     command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
     descriptor_set::{
         layout::DescriptorType, DescriptorSet, PersistentDescriptorSet, WriteDescriptorSet,
@@ -145,18 +145,18 @@ fn main() {
         aligned_data
     };
 
-    let input_buffer = CpuAccessibleBuffer::from_iter(
+    let input_buffer = Buffer::from_iter(
         device.clone(),
         BufferUsage::all(),
-        false,
+        BufferLocation::Upload,
         aligned_data.into_iter(),
     )
     .unwrap();
 
-    let output_buffer = CpuAccessibleBuffer::from_iter(
+    let output_buffer = Buffer::from_iter(
         device.clone(),
         BufferUsage::all(),
-        false,
+        BufferLocation::Upload,
         (0..12).map(|_| 0u32),
     )
     .unwrap();
@@ -16,7 +16,7 @@
 
 use std::{fs::File, io::BufWriter, path::Path};
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer},
+    buffer::{Buffer, BufferLocation, BufferUsage},
     command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage, CopyImageToBufferInfo},
     descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet},
     device::{
@@ -202,10 +202,10 @@ fn main() {
     )
     .unwrap();
 
-    let buf = CpuAccessibleBuffer::from_iter(
+    let buf = Buffer::from_iter(
         device.clone(),
         BufferUsage::all(),
-        false,
+        BufferLocation::Upload,
         (0..1024 * 1024 * 4).map(|_| 0u8),
     )
     .unwrap();
@@ -13,7 +13,7 @@ mod linux {
         time::Instant,
     };
     use vulkano::{
-        buffer::{BufferUsage, CpuAccessibleBuffer, TypedBufferAccess},
+        buffer::{Buffer, BufferLocation, BufferUsage, Subbuffer},
         command_buffer::{
             submit::SubmitCommandBufferBuilder, AutoCommandBufferBuilder, CommandBufferUsage,
             RenderPassBeginInfo, SubpassContents,
@@ -376,7 +376,7 @@ mod linux {
         Vec<Arc<Framebuffer>>,
         Arc<vulkano::sampler::Sampler>,
         Arc<GraphicsPipeline>,
-        Arc<CpuAccessibleBuffer<[Vertex]>>,
+        Arc<Subbuffer<[Vertex]>>,
     ) {
         let required_extensions = vulkano_win::required_extensions();
 
@@ -514,10 +514,10 @@ mod linux {
                 position: [0.5, 0.5],
             },
         ];
-        let vertex_buffer = CpuAccessibleBuffer::<[Vertex]>::from_iter(
+        let vertex_buffer = Buffer::from_iter(
             device.clone(),
             BufferUsage::all(),
-            false,
+            BufferLocation::Upload,
             vertices,
         )
         .unwrap();
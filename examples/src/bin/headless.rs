@@ -0,0 +1,222 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Headless rendering example.
+//!
+//! This example renders a triangle without creating a window, a surface or a swapchain, and saves
+//! the result to a PNG file. This is useful to run a renderer on machines that have no display,
+//! for example to compare its output with reference images in continuous integration.
+//!
+//! The path of the PNG file can be passed as the first argument, it defaults to `headless.png`.
+
+use bytemuck::{Pod, Zeroable};
+use std::{fs::File, io::BufWriter};
+use vulkano::{
+    buffer::{Buffer, BufferLocation, BufferUsage},
+    command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage, PrimaryCommandBuffer},
+    device::{
+        physical::{PhysicalDevice, PhysicalDeviceType},
+        Device, DeviceCreateInfo, QueueCreateInfo,
+    },
+    image::SampleCount,
+    impl_vertex,
+    instance::{Instance, InstanceCreateInfo},
+    pipeline::{
+        graphics::{
+            depth_stencil::DepthStencilState, multisample::MultisampleState,
+            vertex_input::BuffersDefinition, viewport::ViewportState,
+        },
+        GraphicsPipeline,
+    },
+    sync::GpuFuture,
+};
+use vulkano_util::render_target::{RenderTarget, RenderTargetCreateInfo};
+
+const WIDTH: u32 = 1024;
+const HEIGHT: u32 = 768;
+
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "headless.png".to_owned());
+
+    // No extensions are needed, as nothing is presented.
+    let instance = Instance::new(InstanceCreateInfo::default()).unwrap();
+
+    let (physical_device, queue_family) = PhysicalDevice::enumerate(&instance)
+        .filter_map(|p| {
+            p.queue_families()
+                .find(|&q| q.supports_graphics())
+                .map(|q| (p, q))
+        })
+        .min_by_key(|(p, _)| match p.properties().device_type {
+            PhysicalDeviceType::DiscreteGpu => 0,
+            PhysicalDeviceType::IntegratedGpu => 1,
+            PhysicalDeviceType::VirtualGpu => 2,
+            PhysicalDeviceType::Cpu => 3,
+            PhysicalDeviceType::Other => 4,
+        })
+        .unwrap();
+
+    println!(
+        "Using device: {} (type: {:?})",
+        physical_device.properties().device_name,
+        physical_device.properties().device_type
+    );
+
+    let (device, mut queues) = Device::new(
+        physical_device,
+        DeviceCreateInfo {
+            enabled_extensions: physical_device.required_extensions().clone(),
+            queue_create_infos: vec![QueueCreateInfo::family(queue_family)],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let queue = queues.next().unwrap();
+
+    // The render target takes the place of the swapchain. It owns the color and depth images, and
+    // resolves the multisampled color image into the single-sampled image that is read back.
+    let render_target = RenderTarget::new(
+        device.clone(),
+        RenderTargetCreateInfo {
+            samples: SampleCount::Sample4,
+            ..RenderTargetCreateInfo::extent([WIDTH, HEIGHT])
+        },
+    )
+    .unwrap();
+
+    mod vs {
+        vulkano_shaders::shader! {
+            ty: "vertex",
+            src: "
+                #version 450
+
+                layout(location = 0) in vec2 position;
+                layout(location = 1) in vec3 color;
+
+                layout(location = 0) out vec3 v_color;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                    v_color = color;
+                }
+            "
+        }
+    }
+
+    mod fs {
+        vulkano_shaders::shader! {
+            ty: "fragment",
+            src: "
+                #version 450
+
+                layout(location = 0) in vec3 v_color;
+
+                layout(location = 0) out vec4 f_color;
+
+                void main() {
+                    f_color = vec4(v_color, 1.0);
+                }
+            "
+        }
+    }
+
+    let vs = vs::load(device.clone()).unwrap();
+    let fs = fs::load(device.clone()).unwrap();
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default, Zeroable, Pod)]
+    struct Vertex {
+        position: [f32; 2],
+        color: [f32; 3],
+    }
+    impl_vertex!(Vertex, position, color);
+
+    let vertices = [
+        Vertex {
+            position: [-0.5, 0.5],
+            color: [1.0, 0.0, 0.0],
+        },
+        Vertex {
+            position: [0.5, 0.5],
+            color: [0.0, 1.0, 0.0],
+        },
+        Vertex {
+            position: [0.0, -0.5],
+            color: [0.0, 0.0, 1.0],
+        },
+    ];
+    let vertex_buffer = Buffer::from_iter(
+        device.clone(),
+        BufferUsage::all(),
+        BufferLocation::Upload,
+        vertices,
+    )
+    .unwrap();
+
+    // The pipeline is created for the subpass of the render target, like it would be for the
+    // render pass of a swapchain.
+    let subpass = render_target.subpass();
+    let pipeline = GraphicsPipeline::start()
+        .vertex_input_state(BuffersDefinition::new().vertex::<Vertex>())
+        .vertex_shader(vs.entry_point("main").unwrap(), ())
+        .viewport_state(ViewportState::viewport_dynamic_scissor_irrelevant())
+        .fragment_shader(fs.entry_point("main").unwrap(), ())
+        .depth_stencil_state(DepthStencilState::simple_depth_test())
+        .multisample_state(MultisampleState {
+            rasterization_samples: subpass.num_samples().unwrap(),
+            ..Default::default()
+        })
+        .render_pass(subpass)
+        .build(device.clone())
+        .unwrap();
+
+    let mut builder = AutoCommandBufferBuilder::primary(
+        device.clone(),
+        queue.family(),
+        CommandBufferUsage::OneTimeSubmit,
+    )
+    .unwrap();
+
+    // `record` begins the render pass and sets the viewport. The closure records the drawing.
+    render_target
+        .record(&mut builder, [0.0, 0.0, 0.0, 1.0].into(), |builder| {
+            builder
+                .bind_pipeline_graphics(pipeline.clone())
+                .bind_vertex_buffers(0, vertex_buffer.clone())
+                .draw(vertex_buffer.len() as u32, 1, 0, 0)
+                .unwrap();
+        })
+        .unwrap();
+
+    // Copy the resolved image to a buffer that the CPU can read.
+    render_target.copy_color_to_buffer(&mut builder).unwrap();
+
+    let command_buffer = builder.build().unwrap();
+    command_buffer
+        .execute(queue.clone())
+        .unwrap()
+        .then_signal_fence_and_flush()
+        .unwrap()
+        .wait(None)
+        .unwrap();
+
+    // The content of the image is tightly packed RGBA8 pixels, which is what the PNG encoder
+    // expects.
+    let pixels = render_target.read_color().unwrap();
+    let file = File::create(&path).unwrap();
+    let mut encoder = png::Encoder::new(BufWriter::new(file), WIDTH, HEIGHT);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(&pixels).unwrap();
+
+    println!("Saved to {}", path);
+}
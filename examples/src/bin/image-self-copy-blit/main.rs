@@ -10,7 +10,7 @@
 use bytemuck::{Pod, Zeroable};
 use std::{io::Cursor, sync::Arc};
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer, TypedBufferAccess},
+    buffer::{Buffer, BufferLocation, BufferUsage},
     command_buffer::{
         AutoCommandBufferBuilder, BlitImageInfo, BufferImageCopy, ClearColorImageInfo,
         CommandBufferUsage, CopyBufferToImageInfo, CopyImageInfo, ImageBlit, ImageCopy,
@@ -157,10 +157,10 @@ fn main() {
             position: [0.5, 0.5],
         },
     ];
-    let vertex_buffer = CpuAccessibleBuffer::<[Vertex]>::from_iter(
+    let vertex_buffer = Buffer::from_iter(
         device.clone(),
         BufferUsage::all(),
-        false,
+        BufferLocation::Upload,
         vertices,
     )
     .unwrap();
@@ -208,10 +208,10 @@ fn main() {
         )
         .unwrap();
 
-        let buffer = CpuAccessibleBuffer::from_iter(
+        let buffer = Buffer::from_iter(
             device.clone(),
             BufferUsage::transfer_src(),
-            false,
+            BufferLocation::Upload,
             image_data,
         )
         .unwrap();
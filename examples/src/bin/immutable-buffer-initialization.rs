@@ -10,7 +10,7 @@
 // This example demonstrates how to initialize immutable buffers.
 
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer, ImmutableBuffer},
+    buffer::{Buffer, BufferLocation, BufferUsage, ImmutableBuffer},
     command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
     descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet},
     device::{
@@ -105,8 +105,13 @@ void main() {
 
     let data_buffer = {
         let data_iter = (0..65536u32).map(|n| n);
-        CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(), false, data_iter)
-            .unwrap()
+        Buffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            BufferLocation::Upload,
+            data_iter,
+        )
+        .unwrap()
     };
 
     // Create immutable buffer and initialize it
@@ -19,7 +19,7 @@
 use bytemuck::{Pod, Zeroable};
 use std::{io::Cursor, sync::Arc};
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer, TypedBufferAccess},
+    buffer::{Buffer, BufferLocation, BufferUsage},
     command_buffer::{
         AutoCommandBufferBuilder, CommandBufferUsage, RenderPassBeginInfo, SubpassContents,
     },
@@ -161,10 +161,10 @@ fn main() {
             position: [0.5, 0.5],
         },
     ];
-    let vertex_buffer = CpuAccessibleBuffer::<[Vertex]>::from_iter(
+    let vertex_buffer = Buffer::from_iter(
         device.clone(),
         BufferUsage::all(),
-        false,
+        BufferLocation::Upload,
         vertices,
     )
     .unwrap();
@@ -27,7 +27,10 @@
 use bytemuck::{Pod, Zeroable};
 use std::sync::Arc;
 use vulkano::{
-    buffer::{BufferUsage, CpuBufferPool},
+    buffer::{
+        allocator::{SubbufferAllocator, SubbufferAllocatorCreateInfo},
+        BufferUsage,
+    },
     command_buffer::{
         AutoCommandBufferBuilder, CommandBufferUsage, DrawIndirectCommand, RenderPassBeginInfo,
         SubpassContents,
@@ -233,9 +236,20 @@ fn main() {
 
     // Each frame we generate a new set of vertices and each frame we need a new DrawIndirectCommand struct to
     // set the number of vertices to draw
-    let indirect_args_pool: CpuBufferPool<DrawIndirectCommand> =
-        CpuBufferPool::new(device.clone(), BufferUsage::all());
-    let vertex_pool: CpuBufferPool<Vertex> = CpuBufferPool::new(device.clone(), BufferUsage::all());
+    let indirect_args_pool = SubbufferAllocator::new(
+        device.clone(),
+        SubbufferAllocatorCreateInfo {
+            usage: BufferUsage::all(),
+            ..Default::default()
+        },
+    );
+    let vertex_pool = SubbufferAllocator::new(
+        device.clone(),
+        SubbufferAllocatorCreateInfo {
+            usage: BufferUsage::all(),
+            ..Default::default()
+        },
+    );
 
     let compute_pipeline = ComputePipeline::new(
         device.clone(),
@@ -346,12 +360,12 @@ fn main() {
                     first_vertex: 0,
                     first_instance: 0,
                 }];
-                let indirect_buffer = indirect_args_pool.chunk(indirect_commands).unwrap();
+                let indirect_buffer = indirect_args_pool.from_iter(indirect_commands).unwrap();
 
                 // Allocate a GPU buffer to hold this frames vertices. This needs to be large enough to hold
                 // the worst case number of vertices generated by the compute shader
                 let vertices = vertex_pool
-                    .chunk((0..(6 * 16)).map(|_| Vertex { position: [0.0; 2] }))
+                    .from_iter((0..(6 * 16)).map(|_| Vertex { position: [0.0; 2] }))
                     .unwrap();
 
                 // Pass the two buffers to the compute shader
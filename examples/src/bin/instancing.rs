@@ -15,7 +15,7 @@
 use bytemuck::{Pod, Zeroable};
 use std::sync::Arc;
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer, TypedBufferAccess},
+    buffer::{Buffer, BufferLocation, BufferUsage},
     command_buffer::{
         AutoCommandBufferBuilder, CommandBufferUsage, RenderPassBeginInfo, SubpassContents,
     },
@@ -167,7 +167,13 @@ fn main() {
         },
     ];
     let vertex_buffer = {
-        CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(), false, vertices).unwrap()
+        Buffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            BufferLocation::Upload,
+            vertices,
+        )
+        .unwrap()
     };
 
     // Now we create another buffer that will store the unique data per instance.
@@ -193,9 +199,13 @@ fn main() {
         }
         data
     };
-    let instance_buffer =
-        CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(), false, instances)
-            .unwrap();
+    let instance_buffer = Buffer::from_iter(
+        device.clone(),
+        BufferUsage::all(),
+        BufferLocation::Upload,
+        instances,
+    )
+    .unwrap();
 
     mod vs {
         vulkano_shaders::shader! {
@@ -12,7 +12,7 @@ use cgmath::Vector2;
 use rand::Rng;
 use std::sync::Arc;
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer},
+    buffer::{Buffer, BufferLocation, BufferUsage, Subbuffer},
     command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage, PrimaryCommandBuffer},
     descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet},
     device::Queue,
@@ -24,7 +24,7 @@ use vulkano::{
 pub struct FractalComputePipeline {
     gfx_queue: Arc<Queue>,
     pipeline: Arc<ComputePipeline>,
-    palette: Arc<CpuAccessibleBuffer<[[f32; 4]]>>,
+    palette: Arc<Subbuffer<[[f32; 4]]>>,
     palette_size: i32,
     end_color: [f32; 4],
 }
@@ -41,10 +41,10 @@ impl FractalComputePipeline {
             [1.0, 0.0, 1.0, 1.0],
         ];
         let palette_size = colors.len() as i32;
-        let palette = CpuAccessibleBuffer::from_iter(
+        let palette = Buffer::from_iter(
             gfx_queue.device().clone(),
             BufferUsage::all(),
-            false,
+            BufferLocation::Upload,
             colors,
         )
         .unwrap();
@@ -80,10 +80,10 @@ impl FractalComputePipeline {
             let a = rand::thread_rng().gen::<f32>();
             colors.push([r, g, b, a]);
         }
-        self.palette = CpuAccessibleBuffer::from_iter(
+        self.palette = Buffer::from_iter(
             self.gfx_queue.device().clone(),
             BufferUsage::all(),
-            false,
+            BufferLocation::Upload,
             colors.into_iter(),
         )
         .unwrap();
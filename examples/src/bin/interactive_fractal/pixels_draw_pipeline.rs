@@ -10,7 +10,7 @@
 use bytemuck::{Pod, Zeroable};
 use std::sync::Arc;
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer, TypedBufferAccess},
+    buffer::{Buffer, BufferLocation, BufferUsage, Subbuffer},
     command_buffer::{
         AutoCommandBufferBuilder, CommandBufferInheritanceInfo, CommandBufferUsage,
         SecondaryAutoCommandBuffer,
@@ -69,24 +69,24 @@ pub struct PixelsDrawPipeline {
     gfx_queue: Arc<Queue>,
     subpass: Subpass,
     pipeline: Arc<GraphicsPipeline>,
-    vertices: Arc<CpuAccessibleBuffer<[TexturedVertex]>>,
-    indices: Arc<CpuAccessibleBuffer<[u32]>>,
+    vertices: Arc<Subbuffer<[TexturedVertex]>>,
+    indices: Arc<Subbuffer<[u32]>>,
 }
 
 impl PixelsDrawPipeline {
     pub fn new(gfx_queue: Arc<Queue>, subpass: Subpass) -> PixelsDrawPipeline {
         let (vertices, indices) = textured_quad(2.0, 2.0);
-        let vertex_buffer = CpuAccessibleBuffer::<[TexturedVertex]>::from_iter(
+        let vertex_buffer = Buffer::from_iter(
             gfx_queue.device().clone(),
             BufferUsage::vertex_buffer(),
-            false,
+            BufferLocation::Upload,
             vertices,
         )
         .unwrap();
-        let index_buffer = CpuAccessibleBuffer::<[u32]>::from_iter(
+        let index_buffer = Buffer::from_iter(
             gfx_queue.device().clone(),
             BufferUsage::index_buffer(),
-            false,
+            BufferLocation::Upload,
             indices,
         )
         .unwrap();
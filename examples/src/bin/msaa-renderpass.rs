@@ -67,7 +67,7 @@
 use bytemuck::{Pod, Zeroable};
 use std::{fs::File, io::BufWriter, path::Path};
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer, TypedBufferAccess},
+    buffer::{Buffer, BufferLocation, BufferUsage},
     command_buffer::{
         AutoCommandBufferBuilder, CommandBufferUsage, CopyImageToBufferInfo, PrimaryCommandBuffer,
         RenderPassBeginInfo, SubpassContents,
@@ -272,9 +272,13 @@ fn main() {
             position: [0.5, -0.25],
         },
     ];
-    let vertex_buffer =
-        CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(), false, vertices)
-            .unwrap();
+    let vertex_buffer = Buffer::from_iter(
+        device.clone(),
+        BufferUsage::all(),
+        BufferLocation::Upload,
+        vertices,
+    )
+    .unwrap();
 
     let subpass = Subpass::from(render_pass.clone(), 0).unwrap();
     let pipeline = GraphicsPipeline::start()
@@ -296,10 +300,10 @@ fn main() {
         depth_range: 0.0..1.0,
     };
 
-    let buf = CpuAccessibleBuffer::from_iter(
+    let buf = Buffer::from_iter(
         device.clone(),
         BufferUsage::all(),
-        false,
+        BufferLocation::Upload,
         (0..1024 * 1024 * 4).map(|_| 0u8),
     )
     .unwrap();
@@ -19,7 +19,7 @@
 use bytemuck::{Pod, Zeroable};
 use std::{collections::HashMap, sync::Arc};
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer, TypedBufferAccess},
+    buffer::{Buffer, BufferLocation, BufferUsage},
     command_buffer::{
         AutoCommandBufferBuilder, CommandBufferUsage, RenderPassBeginInfo, SubpassContents,
     },
@@ -180,9 +180,13 @@ fn main() {
             position: [0.25, -0.1],
         },
     ];
-    let vertex_buffer =
-        CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(), false, vertices)
-            .unwrap();
+    let vertex_buffer = Buffer::from_iter(
+        device.clone(),
+        BufferUsage::all(),
+        BufferLocation::Upload,
+        vertices,
+    )
+    .unwrap();
 
     mod vs {
         vulkano_shaders::shader! {
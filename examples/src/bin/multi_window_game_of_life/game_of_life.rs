@@ -12,7 +12,7 @@ use cgmath::Vector2;
 use rand::Rng;
 use std::sync::Arc;
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer},
+    buffer::{Buffer, BufferLocation, BufferUsage, Subbuffer},
     command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage, PrimaryAutoCommandBuffer},
     descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet},
     device::Queue,
@@ -30,16 +30,16 @@ use vulkano::{
 pub struct GameOfLifeComputePipeline {
     compute_queue: Arc<Queue>,
     compute_life_pipeline: Arc<ComputePipeline>,
-    life_in: Arc<CpuAccessibleBuffer<[u32]>>,
-    life_out: Arc<CpuAccessibleBuffer<[u32]>>,
+    life_in: Arc<Subbuffer<[u32]>>,
+    life_out: Arc<Subbuffer<[u32]>>,
     image: DeviceImageView,
 }
 
-fn rand_grid(compute_queue: &Arc<Queue>, size: [u32; 2]) -> Arc<CpuAccessibleBuffer<[u32]>> {
-    CpuAccessibleBuffer::from_iter(
+fn rand_grid(compute_queue: &Arc<Queue>, size: [u32; 2]) -> Arc<Subbuffer<[u32]>> {
+    Buffer::from_iter(
         compute_queue.device().clone(),
         BufferUsage::all(),
-        false,
+        BufferLocation::Upload,
         (0..(size[0] * size[1]))
             .map(|_| rand::thread_rng().gen_range(0u32..=1))
             .collect::<Vec<u32>>(),
@@ -16,7 +16,7 @@
 use bytemuck::{Pod, Zeroable};
 use std::{fs::File, io::BufWriter, path::Path, sync::Arc};
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer, TypedBufferAccess},
+    buffer::{Buffer, BufferLocation, BufferUsage, Subbuffer},
     command_buffer::{
         AutoCommandBufferBuilder, BufferImageCopy, CommandBufferUsage, CopyImageToBufferInfo,
         RenderPassBeginInfo, SubpassContents,
@@ -157,9 +157,13 @@ fn main() {
             position: [0.25, -0.1],
         },
     ];
-    let vertex_buffer =
-        CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(), false, vertices)
-            .unwrap();
+    let vertex_buffer = Buffer::from_iter(
+        device.clone(),
+        BufferUsage::all(),
+        BufferLocation::Upload,
+        vertices,
+    )
+    .unwrap();
 
     // Note the `#extension GL_EXT_multiview : enable` that enables the multiview extension
     // for the shader and the use of `gl_ViewIndex` which contains a value based on which
@@ -261,10 +265,10 @@ fn main() {
         .unwrap();
 
     let create_buffer = || {
-        CpuAccessibleBuffer::from_iter(
+        Buffer::from_iter(
             device.clone(),
             BufferUsage::all(),
-            false,
+            BufferLocation::Upload,
             (0..image.dimensions().width() * image.dimensions().height() * 4).map(|_| 0u8),
         )
         .unwrap()
@@ -352,12 +356,7 @@ fn main() {
     );
 }
 
-fn write_image_buffer_to_file(
-    buffer: Arc<CpuAccessibleBuffer<[u8]>>,
-    path: &str,
-    width: u32,
-    height: u32,
-) {
+fn write_image_buffer_to_file(buffer: Arc<Subbuffer<[u8]>>, path: &str, width: u32, height: u32) {
     let buffer_content = buffer.read().unwrap();
     let path = Path::new(path);
     let file = File::create(path).unwrap();
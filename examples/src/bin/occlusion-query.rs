@@ -14,7 +14,7 @@
 use bytemuck::{Pod, Zeroable};
 use std::sync::Arc;
 use vulkano::{
-    buffer::{BufferAccess, BufferUsage, CpuAccessibleBuffer},
+    buffer::{Buffer, BufferAccess, BufferLocation, BufferUsage},
     command_buffer::{
         AutoCommandBufferBuilder, CommandBufferUsage, RenderPassBeginInfo, SubpassContents,
     },
@@ -185,9 +185,13 @@ fn main() {
             color: [0.0, 1.0, 0.0],
         },
     ];
-    let vertex_buffer =
-        CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(), false, vertices)
-            .unwrap();
+    let vertex_buffer = Buffer::from_iter(
+        device.clone(),
+        BufferUsage::all(),
+        BufferLocation::Upload,
+        vertices,
+    )
+    .unwrap();
 
     // Create three buffer slices, one for each triangle.
     let triangle1 = vertex_buffer.slice::<Vertex>(0..3).unwrap();
@@ -10,7 +10,7 @@
 // TODO: Give a paragraph about what push constants are and what problems they solve
 
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer},
+    buffer::{Buffer, BufferLocation, BufferUsage},
     command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
     descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet},
     device::{
@@ -105,8 +105,13 @@ fn main() {
 
     let data_buffer = {
         let data_iter = (0..65536u32).map(|n| n);
-        CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(), false, data_iter)
-            .unwrap()
+        Buffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            BufferLocation::Upload,
+            data_iter,
+        )
+        .unwrap()
     };
 
     let layout = pipeline.layout().set_layouts().get(0).unwrap();
@@ -11,7 +11,7 @@
 // and then we use `copy_buffer_dimensions` to copy the first half of the input buffer to the second half.
 
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer},
+    buffer::{Buffer, BufferLocation, BufferUsage},
     command_buffer::{
         AutoCommandBufferBuilder, BufferCopy, CommandBufferUsage, CopyBufferInfoTyped,
     },
@@ -102,7 +102,7 @@ fn main() {
     let data_buffer = {
         // we intitialize half of the array and leave the other half to 0, we will use copy later to fill it
         let data_iter = (0..65536u32).map(|n| if n < 65536 / 2 { n } else { 0 });
-        CpuAccessibleBuffer::from_iter(
+        Buffer::from_iter(
             device.clone(),
             BufferUsage {
                 storage_buffer: true,
@@ -110,7 +110,7 @@ fn main() {
                 transfer_dst: true,
                 ..BufferUsage::none()
             },
-            false,
+            BufferLocation::Upload,
             data_iter,
         )
         .unwrap()
@@ -12,7 +12,7 @@
 // most of the boilerplate is explained.
 
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer},
+    buffer::{Buffer, BufferLocation, BufferUsage},
     command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
     descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet},
     device::{
@@ -107,8 +107,13 @@ fn main() {
 
     let data_buffer = {
         let data_iter = (0..65536u32).map(|n| n);
-        CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(), false, data_iter)
-            .unwrap()
+        Buffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            BufferLocation::Upload,
+            data_iter,
+        )
+        .unwrap()
     };
 
     let layout = pipeline.layout().set_layouts().get(0).unwrap();
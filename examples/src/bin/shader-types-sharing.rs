@@ -29,7 +29,7 @@
 
 use std::sync::Arc;
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer},
+    buffer::{Buffer, BufferLocation, BufferUsage, Subbuffer},
     command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
     descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet},
     device::{
@@ -172,7 +172,7 @@ fn main() {
     fn run_shader(
         pipeline: Arc<ComputePipeline>,
         queue: Arc<Queue>,
-        data_buffer: Arc<CpuAccessibleBuffer<[u32]>>,
+        data_buffer: Arc<Subbuffer<[u32]>>,
         parameters: shaders::ty::Parameters,
     ) {
         let layout = pipeline.layout().set_layouts().get(0).unwrap();
@@ -213,8 +213,13 @@ fn main() {
     // Preparing test data array `[0, 1, 2, 3....]`
     let data_buffer = {
         let data_iter = (0..65536u32).map(|n| n);
-        CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(), false, data_iter)
-            .unwrap()
+        Buffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            BufferLocation::Upload,
+            data_iter,
+        )
+        .unwrap()
     };
 
     // Loading the first shader, and creating a Pipeline for the shader
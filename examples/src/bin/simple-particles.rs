@@ -7,16 +7,16 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-//! A minimal particle-sandbox to demonstrate a reasonable use-case for a `DeviceLocalBuffer`.
+//! A minimal particle-sandbox to demonstrate a reasonable use-case for a device-local `Buffer`.
 //! We gain significant runtime performance by copying the inital vertex values to the GPU using
-//! a `CpuAccessibleBuffer` and then moving the data to a `DeviceLocalBuffer` to be accessed soley
-//! by the GPU through the compute shader and as a vertex array.
+//! a host-accessible `Buffer` and then moving the data to a device-local `Buffer` to be accessed
+//! soley by the GPU through the compute shader and as a vertex array.
 //!
 
 use bytemuck::{Pod, Zeroable};
 use std::{sync::Arc, time::SystemTime};
 use vulkano::{
-    buffer::{BufferUsage, CpuAccessibleBuffer, DeviceLocalBuffer},
+    buffer::{Buffer, BufferLocation, BufferUsage},
     command_buffer::{
         AutoCommandBufferBuilder, CommandBufferUsage, CopyBufferInfo, PrimaryCommandBuffer,
         RenderPassBeginInfo, SubpassContents,
@@ -187,7 +187,7 @@ fn main() {
                     vec2 vel;
                 };
                 
-                // Storage buffer binding, which we optimize by using a DeviceLocalBuffer.
+                // Storage buffer binding, which we optimize by using a device-local buffer.
                 layout (binding = 0) buffer VertexBuffer {
                     VertexData verticies[];
                 };
@@ -299,7 +299,7 @@ fn main() {
     }
     impl_vertex!(Vertex, pos, vel);
 
-    // Apply scoped logic to create `DeviceLocalBuffer` initialized with vertex data.
+    // Apply scoped logic to create a device-local `Buffer` initialized with vertex data.
     let vertex_buffer = {
         // Initialize vertex data as an iterator.
         let vertices = (0..PARTICLE_COUNT).map(|i| {
@@ -311,20 +311,20 @@ fn main() {
         });
 
         // Create a CPU accessible buffer initialized with the vertex data.
-        let temporary_accessible_buffer = CpuAccessibleBuffer::from_iter(
+        let temporary_accessible_buffer = Buffer::from_iter(
             device.clone(),
             BufferUsage::transfer_src(), // Specify this buffer will be used as a transfer source.
-            false,
+            BufferLocation::Upload,      // Specify this buffer will be written by the CPU.
             vertices,
         )
         .unwrap();
 
         // Create a buffer array on the GPU with enough space for `PARTICLE_COUNT` number of `Vertex`.
-        let device_local_buffer = DeviceLocalBuffer::<[Vertex]>::array(
+        let device_local_buffer = Buffer::new_slice::<Vertex>(
             device.clone(),
-            PARTICLE_COUNT as vulkano::DeviceSize,
             BufferUsage::storage_buffer() | BufferUsage::vertex_buffer_transfer_dst(), // Specify use as a storage buffer, vertex buffer, and transfer destination.
-            device.active_queue_families(),
+            BufferLocation::Device, // Specify this buffer will only be accessed by the GPU.
+            PARTICLE_COUNT as vulkano::DeviceSize,
         )
         .unwrap();
 
@@ -373,7 +373,7 @@ fn main() {
             .unwrap()
             .clone(),
         [
-            // 0 is the binding of the data in this set. We bind the `DeviceLocalBuffer` of vertices here.
+            // 0 is the binding of the data in this set. We bind the device-local `Buffer` of vertices here.
             WriteDescriptorSet::buffer(0, vertex_buffer.clone()),
         ],
     )
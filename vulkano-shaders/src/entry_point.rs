@@ -35,6 +35,7 @@ pub(super) fn write_entry_point(
         write_specialization_constant_requirements(&info.specialization_constant_requirements);
     let input_interface = write_interface(&info.input_interface);
     let output_interface = write_interface(&info.output_interface);
+    let uses_view_index = info.uses_view_index;
 
     quote! {
         (
@@ -47,6 +48,7 @@ pub(super) fn write_entry_point(
                 specialization_constant_requirements: #specialization_constant_requirements.into_iter().collect(),
                 input_interface: #input_interface,
                 output_interface: #output_interface,
+                uses_view_index: #uses_view_index,
             },
         ),
     }
@@ -71,7 +73,17 @@ fn write_shader_execution(execution: &ShaderExecution) -> TokenStream {
                 )
             }
         }
-        ShaderExecution::Fragment => quote! { ::vulkano::shader::ShaderExecution::Fragment },
+        ShaderExecution::Fragment(::vulkano::shader::FragmentShaderExecution {
+            depth_replacing,
+        }) => {
+            quote! {
+                ::vulkano::shader::ShaderExecution::Fragment(
+                    ::vulkano::shader::FragmentShaderExecution {
+                        depth_replacing: #depth_replacing,
+                    }
+                )
+            }
+        }
         ShaderExecution::Compute => quote! { ::vulkano::shader::ShaderExecution::Compute },
         ShaderExecution::RayGeneration => {
             quote! { ::vulkano::shader::ShaderExecution::RayGeneration}
@@ -96,6 +108,7 @@ fn write_descriptor_requirements(
             image_multisampled,
             image_scalar_type,
             image_view_type,
+            input_attachment_index,
             sampler_compare,
             sampler_no_unnormalized_coordinates,
             sampler_no_ycbcr_conversion,
@@ -131,6 +144,10 @@ fn write_descriptor_requirements(
             }
             None => quote! { None },
         };
+        let input_attachment_index = match input_attachment_index {
+            Some(input_attachment_index) => quote! { Some(#input_attachment_index) },
+            None => quote! { None },
+        };
         let sampler_compare = sampler_compare.iter();
         let sampler_no_unnormalized_coordinates = sampler_no_unnormalized_coordinates.iter();
         let sampler_no_ycbcr_conversion = sampler_no_ycbcr_conversion.iter();
@@ -206,6 +223,7 @@ fn write_descriptor_requirements(
                     image_multisampled: #image_multisampled,
                     image_scalar_type: #image_scalar_type,
                     image_view_type: #image_view_type,
+                    input_attachment_index: #input_attachment_index,
                     sampler_compare: [#(#sampler_compare),*].into_iter().collect(),
                     sampler_no_unnormalized_coordinates: [#(#sampler_no_unnormalized_coordinates),*].into_iter().collect(),
                     sampler_no_ycbcr_conversion: [#(#sampler_no_ycbcr_conversion),*].into_iter().collect(),
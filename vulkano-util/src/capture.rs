@@ -0,0 +1,458 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Reading back the contents of swapchain images, for example to take screenshots.
+//!
+//! The [`capture_image`] function copies the contents of an image to host memory and returns the
+//! pixels as tightly packed 8-bit RGBA values, whatever the format of the image is.
+//!
+//! ```no_run
+//! use vulkano_util::capture::capture_image;
+//! use vulkano::sync::GpuFuture;
+//! # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+//! # let image: std::sync::Arc<vulkano::image::SwapchainImage<()>> = return;
+//! # let render_future: Box<dyn GpuFuture> = return;
+//!
+//! // `render_future` is the future of the commands that draw to the acquired swapchain image.
+//! let captured = capture_image(queue, image, render_future).unwrap();
+//! println!("{:?} pixels in {:?}", captured.extent, captured.format);
+//! ```
+
+use std::{error, fmt, sync::Arc};
+use vulkano::{
+    buffer::{Buffer, BufferCreationError, BufferLocation, BufferUsage},
+    command_buffer::{
+        AutoCommandBufferBuilder, BlitImageInfo, BuildError, CommandBufferBeginError,
+        CommandBufferExecError, CommandBufferUsage, CopyImageToBufferInfo,
+        PrimaryAutoCommandBuffer, RenderPassBeginInfo, SubpassContents,
+    },
+    descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet},
+    device::{DeviceOwned, Queue},
+    format::{Format, NumericType},
+    image::{
+        view::{ImageView, ImageViewCreateInfo, ImageViewCreationError, ImageViewType},
+        AttachmentImage, ImageAccess, ImageCreationError, ImageDimensions, ImageSubresourceRange,
+        ImageUsage,
+    },
+    memory::DeviceMemoryAllocationError,
+    pipeline::{
+        graphics::viewport::{Viewport, ViewportState},
+        GraphicsPipeline, Pipeline, PipelineBindPoint,
+    },
+    render_pass::{Framebuffer, FramebufferCreateInfo, Subpass},
+    sync::{FlushError, GpuFuture},
+    DeviceSize, OomError,
+};
+
+/// The pixels of an image that was read back with [`capture_image`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedImage {
+    /// The width and height of the image, in pixels.
+    pub extent: [u32; 2],
+
+    /// The format of `data`.
+    ///
+    /// This is [`Format::R8G8B8A8_SRGB`] if the captured image had an sRGB format, so that the
+    /// values are still sRGB-encoded, and [`Format::R8G8B8A8_UNORM`] otherwise.
+    pub format: Format,
+
+    /// The pixels of the image, row by row starting from the top left corner, with four bytes
+    /// per pixel in RGBA order.
+    pub data: Vec<u8>,
+}
+
+/// Copies the contents of `image` to host memory, and returns its pixels.
+///
+/// The copy is executed on `queue` after `before`, which should be the future of the commands
+/// that write to the image. For a swapchain image, this is typically the future of the rendering
+/// commands of a frame, before it is presented. This function waits for the copy to finish
+/// before returning; to present the image afterwards, start a new future with
+/// [`sync::now`](vulkano::sync::now).
+///
+/// Only the first mip level and array layer of the image are captured. The pixels are converted
+/// to 8-bit RGBA:
+/// - If the image was created with the `transfer_src` usage, it is copied directly if its format
+///   is already 8-bit RGBA, and otherwise blitted to an intermediate image of the right format.
+/// - If the image was not created with the `transfer_src` usage, which swapchains are not
+///   required to support, but with the `sampled` usage, it is drawn to an intermediate image
+///   with a full-screen triangle instead. This creates a render pass and a graphics pipeline on
+///   every call, so it is not meant to be used on every frame.
+///
+/// # Panics
+///
+/// - Panics if `image` is not a two-dimensional image.
+/// - Panics if `queue` does not support graphics operations.
+pub fn capture_image<I, F>(
+    queue: Arc<Queue>,
+    image: Arc<I>,
+    before: F,
+) -> Result<CapturedImage, CaptureError>
+where
+    I: ImageAccess + 'static,
+    F: GpuFuture + 'static,
+{
+    let device = image.device().clone();
+    assert_eq!(&device, queue.device());
+
+    let extent = match image.dimensions() {
+        ImageDimensions::Dim2d { width, height, .. } => [width, height],
+        _ => panic!("the image must be a two-dimensional image"),
+    };
+    let src_format = image.format();
+    let dst_format = if src_format.type_color() == Some(NumericType::SRGB) {
+        Format::R8G8B8A8_SRGB
+    } else {
+        Format::R8G8B8A8_UNORM
+    };
+
+    let usage = *image.usage();
+    let format_features = image.format_features();
+    let direct_copy = usage.transfer_src && src_format == dst_format;
+    let blit = usage.transfer_src && format_features.blit_src;
+    let draw = usage.sampled && format_features.sampled_image;
+
+    if !(direct_copy || blit || draw) {
+        return Err(CaptureError::ImageNotReadable);
+    }
+
+    let buffer = Buffer::new_slice::<u8>(
+        device.clone(),
+        BufferUsage::transfer_dst(),
+        BufferLocation::Download,
+        extent[0] as DeviceSize * extent[1] as DeviceSize * 4,
+    )?;
+
+    let mut cbb = AutoCommandBufferBuilder::primary(
+        device.clone(),
+        queue.family(),
+        CommandBufferUsage::OneTimeSubmit,
+    )?;
+
+    if direct_copy {
+        let mut copy_info = CopyImageToBufferInfo::image_buffer(image, buffer.clone());
+        copy_info.regions[0].image_subresource.array_layers = 0..1;
+        cbb.copy_image_to_buffer(copy_info).unwrap();
+    } else {
+        let intermediate = AttachmentImage::with_usage(
+            device.clone(),
+            extent,
+            dst_format,
+            ImageUsage {
+                transfer_src: true,
+                transfer_dst: true,
+                ..ImageUsage::none()
+            },
+        )?;
+
+        if blit {
+            // The blit takes care of the conversion between the two formats.
+            cbb.blit_image(BlitImageInfo::images(image, intermediate.clone()))
+                .unwrap();
+        } else {
+            record_draw(&mut cbb, image, intermediate.clone(), extent, dst_format)?;
+        }
+
+        cbb.copy_image_to_buffer(CopyImageToBufferInfo::image_buffer(
+            intermediate,
+            buffer.clone(),
+        ))
+        .unwrap();
+    }
+
+    let cb = cbb.build()?;
+    let future = before
+        .then_execute(queue, cb)?
+        .then_signal_fence_and_flush()?;
+    future.wait(None)?;
+
+    let data = buffer.read().unwrap().to_vec();
+
+    Ok(CapturedImage {
+        extent,
+        format: dst_format,
+        data,
+    })
+}
+
+// Draws the first layer of `image` to `intermediate`, by fetching each texel of `image` in the
+// fragment shader. The sRGB conversions done when reading and writing cancel each other out.
+fn record_draw<I>(
+    cbb: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+    image: Arc<I>,
+    intermediate: Arc<AttachmentImage>,
+    extent: [u32; 2],
+    dst_format: Format,
+) -> Result<(), CaptureError>
+where
+    I: ImageAccess + 'static,
+{
+    let device = image.device().clone();
+
+    let src_view = ImageView::new(
+        image.clone(),
+        ImageViewCreateInfo {
+            view_type: ImageViewType::Dim2d,
+            subresource_range: ImageSubresourceRange {
+                mip_levels: 0..1,
+                array_layers: 0..1,
+                ..image.subresource_range()
+            },
+            ..ImageViewCreateInfo::from_image(&image)
+        },
+    )?;
+    let dst_view = ImageView::new_default(intermediate)?;
+
+    let render_pass = vulkano::single_pass_renderpass!(device.clone(),
+        attachments: {
+            color: {
+                load: DontCare,
+                store: Store,
+                format: dst_format,
+                samples: 1,
+            }
+        },
+        pass: {
+            color: [color],
+            depth_stencil: {}
+        }
+    )
+    .unwrap();
+    let framebuffer = Framebuffer::new(
+        render_pass.clone(),
+        FramebufferCreateInfo {
+            attachments: vec![dst_view],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let vs = vs::load(device.clone()).unwrap();
+    let fs = fs::load(device.clone()).unwrap();
+    let pipeline = GraphicsPipeline::start()
+        .vertex_shader(vs.entry_point("main").unwrap(), ())
+        .viewport_state(ViewportState::viewport_fixed_scissor_irrelevant([
+            Viewport {
+                origin: [0.0, 0.0],
+                dimensions: [extent[0] as f32, extent[1] as f32],
+                depth_range: 0.0..1.0,
+            },
+        ]))
+        .fragment_shader(fs.entry_point("main").unwrap(), ())
+        .render_pass(Subpass::from(render_pass, 0).unwrap())
+        .build(device)
+        .unwrap();
+
+    let set = PersistentDescriptorSet::new(
+        pipeline.layout().set_layouts()[0].clone(),
+        [WriteDescriptorSet::image_view(0, src_view)],
+    )
+    .unwrap();
+
+    cbb.begin_render_pass(
+        RenderPassBeginInfo {
+            clear_values: vec![None],
+            ..RenderPassBeginInfo::framebuffer(framebuffer)
+        },
+        SubpassContents::Inline,
+    )
+    .unwrap()
+    .bind_pipeline_graphics(pipeline.clone())
+    .bind_descriptor_sets(
+        PipelineBindPoint::Graphics,
+        pipeline.layout().clone(),
+        0,
+        set,
+    )
+    .draw(3, 1, 0, 0)
+    .unwrap()
+    .end_render_pass()
+    .unwrap();
+
+    Ok(())
+}
+
+/// Error that can happen when capturing an image.
+#[derive(Clone, Debug)]
+pub enum CaptureError {
+    ImageCreationError(ImageCreationError),
+    ImageViewCreationError(ImageViewCreationError),
+    BufferCreationError(BufferCreationError),
+    DeviceMemoryAllocationError(DeviceMemoryAllocationError),
+    CommandBufferBeginError(CommandBufferBeginError),
+    BuildError(BuildError),
+    CommandBufferExecError(CommandBufferExecError),
+    FlushError(FlushError),
+
+    /// The image can neither be copied from nor sampled, so its contents can't be read.
+    ///
+    /// Copying requires the `transfer_src` usage, and also the `blit_src` format feature if the
+    /// format is not 8-bit RGBA. Sampling requires the `sampled` usage and the `sampled_image`
+    /// format feature.
+    ImageNotReadable,
+}
+
+impl error::Error for CaptureError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::ImageCreationError(err) => Some(err),
+            Self::ImageViewCreationError(err) => Some(err),
+            Self::BufferCreationError(err) => Some(err),
+            Self::DeviceMemoryAllocationError(err) => Some(err),
+            Self::CommandBufferBeginError(err) => Some(err),
+            Self::BuildError(err) => Some(err),
+            Self::CommandBufferExecError(err) => Some(err),
+            Self::FlushError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CaptureError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ImageCreationError(err) => err.fmt(f),
+            Self::ImageViewCreationError(err) => err.fmt(f),
+            Self::BufferCreationError(err) => err.fmt(f),
+            Self::DeviceMemoryAllocationError(err) => err.fmt(f),
+            Self::CommandBufferBeginError(err) => err.fmt(f),
+            Self::BuildError(err) => err.fmt(f),
+            Self::CommandBufferExecError(err) => err.fmt(f),
+            Self::FlushError(err) => err.fmt(f),
+            Self::ImageNotReadable => write!(
+                f,
+                "the image was created without the usages or format features needed to read it",
+            ),
+        }
+    }
+}
+
+impl From<ImageCreationError> for CaptureError {
+    #[inline]
+    fn from(err: ImageCreationError) -> Self {
+        Self::ImageCreationError(err)
+    }
+}
+
+impl From<ImageViewCreationError> for CaptureError {
+    #[inline]
+    fn from(err: ImageViewCreationError) -> Self {
+        Self::ImageViewCreationError(err)
+    }
+}
+
+impl From<BufferCreationError> for CaptureError {
+    #[inline]
+    fn from(err: BufferCreationError) -> Self {
+        Self::BufferCreationError(err)
+    }
+}
+
+impl From<DeviceMemoryAllocationError> for CaptureError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocationError) -> Self {
+        Self::DeviceMemoryAllocationError(err)
+    }
+}
+
+impl From<OomError> for CaptureError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::DeviceMemoryAllocationError(err.into())
+    }
+}
+
+impl From<CommandBufferBeginError> for CaptureError {
+    #[inline]
+    fn from(err: CommandBufferBeginError) -> Self {
+        Self::CommandBufferBeginError(err)
+    }
+}
+
+impl From<BuildError> for CaptureError {
+    #[inline]
+    fn from(err: BuildError) -> Self {
+        Self::BuildError(err)
+    }
+}
+
+impl From<CommandBufferExecError> for CaptureError {
+    #[inline]
+    fn from(err: CommandBufferExecError) -> Self {
+        Self::CommandBufferExecError(err)
+    }
+}
+
+impl From<FlushError> for CaptureError {
+    #[inline]
+    fn from(err: FlushError) -> Self {
+        Self::FlushError(err)
+    }
+}
+
+mod vs {
+    vulkano_shaders::shader! {
+        ty: "vertex",
+        src: "
+            #version 450
+
+            void main() {
+                vec2 position = vec2((gl_VertexIndex << 1) & 2, gl_VertexIndex & 2);
+                gl_Position = vec4(position * 2.0 - 1.0, 0.0, 1.0);
+            }
+        "
+    }
+}
+
+mod fs {
+    vulkano_shaders::shader! {
+        ty: "fragment",
+        src: "
+            #version 450
+            #extension GL_EXT_samplerless_texture_functions : require
+
+            layout(set = 0, binding = 0) uniform texture2D src;
+
+            layout(location = 0) out vec4 color;
+
+            void main() {
+                color = texelFetch(src, ivec2(gl_FragCoord.xy), 0);
+            }
+        "
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{capture_image, CaptureError};
+    use vulkano::{
+        format::Format,
+        image::{AttachmentImage, ImageUsage},
+        sync,
+    };
+
+    #[test]
+    fn not_readable() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let image = AttachmentImage::with_usage(
+            device.clone(),
+            [32, 32],
+            Format::R8G8B8A8_UNORM,
+            ImageUsage::none(),
+        )
+        .unwrap();
+
+        match capture_image(queue, image, sync::now(device)) {
+            Err(CaptureError::ImageNotReadable) => (),
+            _ => panic!(),
+        }
+    }
+}
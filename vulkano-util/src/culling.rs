@@ -0,0 +1,1205 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Culling objects on the device before drawing them.
+//!
+//! When a scene contains many objects, testing each of them against the view frustum on the host
+//! and recording one draw command for each visible object becomes a bottleneck. A
+//! [`FrustumCuller`] moves this work to the device: a compute shader tests the bounding sphere of
+//! every object against the frustum, and writes the draw commands of the objects that are visible
+//! into a compacted buffer, along with their number. The draws are then recorded with a single
+//! [`draw_indexed_indirect_count`](vulkano::command_buffer::AutoCommandBufferBuilder::draw_indexed_indirect_count)
+//! command.
+//!
+//! The inputs are two buffers with one element per object:
+//!
+//! - A buffer of [`BoundingSphere`]s, in world space.
+//! - A buffer of [`DrawIndexedIndirectCommand`]s, the draw command of each object. The draw
+//!   command of a visible object is copied as is to the output, so its `first_instance` can be
+//!   used to find the per-object data in the shaders.
+//!
+//! Both buffers must have been created with the `storage_buffer` usage.
+//!
+//! The barriers between the compute shader and the draw command are inserted by the command
+//! buffer builder, as for any other command.
+//!
+//! Instead of the buffers of the culler, the output can be written to an
+//! [`IndirectDrawBuffer`](vulkano::indirect::IndirectDrawBuffer) with
+//! [`cull_into`](FrustumCuller::cull_into), which keeps the per-instance data of the objects in
+//! the same buffer.
+//!
+//! # Example
+//!
+//! ```
+//! use vulkano_util::culling::{FrustumCuller, FrustumCullerCreateInfo};
+//!
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! # let bounds: std::sync::Arc<vulkano::buffer::Subbuffer<[vulkano_util::culling::BoundingSphere]>> = return;
+//! # let draw_commands: std::sync::Arc<vulkano::buffer::Subbuffer<[vulkano::command_buffer::DrawIndexedIndirectCommand]>> = return;
+//! # let view_projection: [[f32; 4]; 4] = return;
+//! # let mut builder: vulkano::command_buffer::AutoCommandBufferBuilder<vulkano::command_buffer::PrimaryAutoCommandBuffer> = return;
+//! let culler = FrustumCuller::new(
+//!     device.clone(),
+//!     FrustumCullerCreateInfo {
+//!         capacity: 4096,
+//!         ..Default::default()
+//!     },
+//! )
+//! .unwrap();
+//!
+//! // Each frame, outside of a render pass:
+//! culler
+//!     .cull(&mut builder, bounds.clone(), draw_commands.clone(), view_projection)
+//!     .unwrap();
+//!
+//! // Then inside the render pass, with the graphics pipeline and the index buffer bound:
+//! culler.draw(&mut builder).unwrap();
+//! ```
+//!
+//! # Occlusion culling
+//!
+//! Objects that are inside the frustum may still be hidden behind other objects. An
+//! [`OcclusionCuller`] finds them with one occlusion query per object, and copies the results of
+//! the queries to a buffer on the device at the end of each frame. In the next frame, the draw
+//! commands of each object are wrapped in
+//! [conditional rendering](vulkano::command_buffer::AutoCommandBufferBuilder::begin_conditional_rendering)
+//! that reads this buffer, so that only the objects that were visible in the previous frame are
+//! drawn, without waiting for the results on the host.
+//!
+//! An object that is not drawn can't become visible again by itself. Instead, a cheap proxy of the
+//! object, such as its bounding box, is drawn under the inverse condition with color and depth
+//! writes disabled, and its query tells whether the object must be drawn in the next frame.
+//!
+//! ```
+//! use vulkano_util::culling::{OcclusionCuller, OcclusionCullerCreateInfo};
+//!
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! # let mut builder: vulkano::command_buffer::AutoCommandBufferBuilder<vulkano::command_buffer::PrimaryAutoCommandBuffer> = return;
+//! # let object_count = 0;
+//! let mut culler = OcclusionCuller::new(
+//!     device.clone(),
+//!     OcclusionCullerCreateInfo {
+//!         capacity: 4096,
+//!         frames_in_flight: 2,
+//!         ..Default::default()
+//!     },
+//! )
+//! .unwrap();
+//!
+//! // Each frame, outside of a render pass:
+//! culler.begin_frame(&mut builder).unwrap();
+//!
+//! // Inside the render pass, ideally from front to back:
+//! for index in 0..object_count {
+//!     culler.begin_object(&mut builder, index).unwrap();
+//!     // Draw the object.
+//!     culler.begin_proxy(&mut builder).unwrap();
+//!     // Draw the bounding box of the object.
+//!     culler.end_object(&mut builder).unwrap();
+//! }
+//!
+//! // After the render pass:
+//! culler.end_frame(&mut builder).unwrap();
+//! ```
+
+use bytemuck::{Pod, Zeroable};
+use std::{error, fmt, mem::size_of, ops::Range, sync::Arc};
+use vulkano::{
+    buffer::{
+        Buffer, BufferAccess, BufferCreationError, BufferLocation, BufferUsage, Subbuffer,
+        TypedBufferAccess,
+    },
+    command_buffer::{
+        AutoCommandBufferBuilder, BeginQueryError, ConditionalRenderingBeginInfo,
+        ConditionalRenderingError, CopyError, CopyQueryPoolResultsError, DispatchError,
+        DrawIndexedIndirectCommand, DrawIndexedIndirectError, EndQueryError, FillBufferInfo,
+        ResetQueryPoolError,
+    },
+    descriptor_set::{DescriptorSetCreationError, PersistentDescriptorSet, WriteDescriptorSet},
+    device::{Device, DeviceOwned},
+    indirect::IndirectDrawBuffer,
+    pipeline::{ComputePipeline, ComputePipelineCreationError, Pipeline, PipelineBindPoint},
+    query::{
+        QueryControlFlags, QueryPool, QueryPoolCreateInfo, QueryPoolCreationError,
+        QueryResultFlags, QueryType,
+    },
+    shader::ShaderCreationError,
+    DeviceSize,
+};
+
+/// A sphere that contains an object, used to test whether the object is visible.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq)]
+pub struct BoundingSphere {
+    pub center: [f32; 3],
+    pub radius: f32,
+}
+
+/// A compute pipeline that culls objects against the view frustum, and the buffers that it writes
+/// the draw commands of the visible objects to.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug)]
+pub struct FrustumCuller {
+    pipeline: Arc<ComputePipeline>,
+    capacity: u32,
+    draw_buffer: Arc<Subbuffer<[DrawIndexedIndirectCommand]>>,
+    count_buffer: Arc<Subbuffer<u32>>,
+}
+
+impl FrustumCuller {
+    /// Creates a new `FrustumCuller`.
+    ///
+    /// The compute shader requires the device to support Vulkan 1.1 or to have the
+    /// [`khr_storage_buffer_storage_class`](vulkano::device::DeviceExtensions::khr_storage_buffer_storage_class)
+    /// extension enabled.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.capacity` is 0.
+    pub fn new(
+        device: Arc<Device>,
+        create_info: FrustumCullerCreateInfo,
+    ) -> Result<FrustumCuller, FrustumCullerCreationError> {
+        let FrustumCullerCreateInfo { capacity, _ne: _ } = create_info;
+
+        assert!(capacity != 0, "the capacity must not be 0");
+
+        let shader = cs::load(device.clone())?;
+        let pipeline = ComputePipeline::new(
+            device.clone(),
+            shader.entry_point("main").unwrap(),
+            &(),
+            None,
+            |_| {},
+        )?;
+
+        let draw_buffer = Buffer::new_slice(
+            device.clone(),
+            BufferUsage {
+                storage_buffer: true,
+                indirect_buffer: true,
+                ..BufferUsage::none()
+            },
+            BufferLocation::Device,
+            capacity as DeviceSize,
+        )?;
+        let count_buffer = Buffer::new_sized(
+            device.clone(),
+            BufferUsage {
+                transfer_dst: true,
+                storage_buffer: true,
+                indirect_buffer: true,
+                ..BufferUsage::none()
+            },
+            BufferLocation::Device,
+        )?;
+
+        Ok(FrustumCuller {
+            pipeline,
+            capacity,
+            draw_buffer,
+            count_buffer,
+        })
+    }
+
+    /// Returns the compute pipeline that culls the objects.
+    #[inline]
+    pub fn pipeline(&self) -> &Arc<ComputePipeline> {
+        &self.pipeline
+    }
+
+    /// Returns the maximum number of objects that can be culled at once.
+    #[inline]
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Returns the buffer that the draw commands of the visible objects are written to.
+    ///
+    /// Only the first [`count_buffer`](FrustumCuller::count_buffer) elements are written by the
+    /// last culling.
+    #[inline]
+    pub fn draw_buffer(&self) -> &Arc<Subbuffer<[DrawIndexedIndirectCommand]>> {
+        &self.draw_buffer
+    }
+
+    /// Returns the buffer that the number of visible objects is written to.
+    #[inline]
+    pub fn count_buffer(&self) -> &Arc<Subbuffer<u32>> {
+        &self.count_buffer
+    }
+
+    /// Records the culling of the objects described by `bounds` and `draw_commands` into
+    /// `builder`, which must be outside of a render pass.
+    ///
+    /// `view_projection` is the matrix that transforms world space to clip space, in column-major
+    /// order. The objects whose bounding sphere is entirely outside of the frustum that it
+    /// describes are culled.
+    ///
+    /// This binds the compute pipeline of the culler, its descriptor set and its push constants,
+    /// replacing the ones that were previously bound for compute.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `bounds` and `draw_commands` don't have the same length.
+    /// - Panics if the length of `bounds` is greater than the capacity of the culler.
+    pub fn cull<L, P, Bb, Db>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+        bounds: Arc<Bb>,
+        draw_commands: Arc<Db>,
+        view_projection: [[f32; 4]; 4],
+    ) -> Result<(), FrustumCullError>
+    where
+        Bb: TypedBufferAccess<Content = [BoundingSphere]> + 'static,
+        Db: TypedBufferAccess<Content = [DrawIndexedIndirectCommand]> + 'static,
+    {
+        assert_eq!(
+            bounds.len(),
+            draw_commands.len(),
+            "bounds and draw_commands must have the same length"
+        );
+        assert!(
+            bounds.len() <= self.capacity as DeviceSize,
+            "the number of objects must not be greater than the capacity of the culler"
+        );
+
+        self.record_cull(
+            builder,
+            bounds,
+            draw_commands,
+            view_projection,
+            self.draw_buffer.clone(),
+            self.count_buffer.clone(),
+        )
+    }
+
+    /// Same as [`cull`](FrustumCuller::cull), but writes the draw commands of the visible objects
+    /// and their number to `output` instead of the buffers of the culler.
+    ///
+    /// The objects can then be drawn with [`IndirectDrawBuffer::draw`], and their per-instance
+    /// data is available to the shaders in [`IndirectDrawBuffer::instances`].
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `bounds` and `draw_commands` don't have the same length.
+    /// - Panics if the length of `bounds` is greater than the command capacity of `output`.
+    pub fn cull_into<L, P, Bb, Db, I>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+        bounds: Arc<Bb>,
+        draw_commands: Arc<Db>,
+        view_projection: [[f32; 4]; 4],
+        output: &IndirectDrawBuffer<DrawIndexedIndirectCommand, I>,
+    ) -> Result<(), FrustumCullError>
+    where
+        Bb: TypedBufferAccess<Content = [BoundingSphere]> + 'static,
+        Db: TypedBufferAccess<Content = [DrawIndexedIndirectCommand]> + 'static,
+        I: Pod + Send + Sync,
+    {
+        assert_eq!(
+            bounds.len(),
+            draw_commands.len(),
+            "bounds and draw_commands must have the same length"
+        );
+        assert!(
+            bounds.len() <= output.command_capacity() as DeviceSize,
+            "the number of objects must not be greater than the command capacity of the output"
+        );
+
+        self.record_cull(
+            builder,
+            bounds,
+            draw_commands,
+            view_projection,
+            output.commands().clone(),
+            output.count().clone(),
+        )
+    }
+
+    fn record_cull<L, P, Bb, Db>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+        bounds: Arc<Bb>,
+        draw_commands: Arc<Db>,
+        view_projection: [[f32; 4]; 4],
+        draw_buffer: Arc<dyn BufferAccess>,
+        count_buffer: Arc<dyn BufferAccess>,
+    ) -> Result<(), FrustumCullError>
+    where
+        Bb: TypedBufferAccess<Content = [BoundingSphere]> + 'static,
+        Db: TypedBufferAccess<Content = [DrawIndexedIndirectCommand]> + 'static,
+    {
+        let object_count = bounds.len() as u32;
+
+        let descriptor_set = PersistentDescriptorSet::new(
+            self.pipeline.layout().set_layouts()[0].clone(),
+            [
+                WriteDescriptorSet::buffer(0, bounds),
+                WriteDescriptorSet::buffer(1, draw_commands),
+                WriteDescriptorSet::buffer(2, draw_buffer),
+                WriteDescriptorSet::buffer(3, count_buffer.clone()),
+            ],
+        )?;
+
+        builder.fill_buffer(FillBufferInfo::dst_buffer(count_buffer))?;
+
+        if object_count == 0 {
+            return Ok(());
+        }
+
+        builder
+            .bind_pipeline_compute(self.pipeline.clone())
+            .bind_descriptor_sets(
+                PipelineBindPoint::Compute,
+                self.pipeline.layout().clone(),
+                0,
+                descriptor_set,
+            )
+            .push_constants(
+                self.pipeline.layout().clone(),
+                0,
+                cs::ty::PushConstants {
+                    planes: frustum_planes(view_projection),
+                    object_count: [object_count, 0, 0, 0],
+                },
+            )
+            .dispatch([(object_count + WORKGROUP_SIZE - 1) / WORKGROUP_SIZE, 1, 1])?;
+
+        Ok(())
+    }
+
+    /// Records the drawing of the objects that were found visible by the last call to
+    /// [`cull`](FrustumCuller::cull), with the graphics pipeline and index buffer that are bound.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if neither the
+    ///   [`draw_indirect_count`](vulkano::device::Features::draw_indirect_count) feature nor the
+    ///   [`khr_draw_indirect_count`](vulkano::device::DeviceExtensions::khr_draw_indirect_count)
+    ///   extension is enabled on the device.
+    #[inline]
+    pub fn draw<L, P>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+    ) -> Result<(), DrawIndexedIndirectError> {
+        builder.draw_indexed_indirect_count(self.draw_buffer.clone(), self.count_buffer.clone())?;
+
+        Ok(())
+    }
+}
+
+unsafe impl DeviceOwned for FrustumCuller {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.pipeline.device()
+    }
+}
+
+/// Parameters to create a new `FrustumCuller`.
+#[derive(Clone, Debug)]
+pub struct FrustumCullerCreateInfo {
+    /// The maximum number of objects that can be culled at once. This is the length of the
+    /// buffer that the draw commands are written to.
+    ///
+    /// The default value is `1024`.
+    pub capacity: u32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for FrustumCullerCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Error that can happen when creating a `FrustumCuller`.
+#[derive(Clone, Debug)]
+pub enum FrustumCullerCreationError {
+    ComputePipelineCreationError(ComputePipelineCreationError),
+    BufferCreationError(BufferCreationError),
+    ShaderCreationError(ShaderCreationError),
+}
+
+impl error::Error for FrustumCullerCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::ComputePipelineCreationError(err) => Some(err),
+            Self::BufferCreationError(err) => Some(err),
+            Self::ShaderCreationError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for FrustumCullerCreationError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::ComputePipelineCreationError(_) => write!(f, "creating the pipeline failed"),
+            Self::BufferCreationError(_) => write!(f, "creating a buffer failed"),
+            Self::ShaderCreationError(_) => write!(f, "creating the compute shader failed"),
+        }
+    }
+}
+
+impl From<ComputePipelineCreationError> for FrustumCullerCreationError {
+    #[inline]
+    fn from(err: ComputePipelineCreationError) -> Self {
+        Self::ComputePipelineCreationError(err)
+    }
+}
+
+impl From<BufferCreationError> for FrustumCullerCreationError {
+    #[inline]
+    fn from(err: BufferCreationError) -> Self {
+        Self::BufferCreationError(err)
+    }
+}
+
+impl From<ShaderCreationError> for FrustumCullerCreationError {
+    #[inline]
+    fn from(err: ShaderCreationError) -> Self {
+        Self::ShaderCreationError(err)
+    }
+}
+
+/// Error that can happen when recording the culling with [`FrustumCuller::cull`].
+#[derive(Clone, Debug)]
+pub enum FrustumCullError {
+    CopyError(CopyError),
+    DescriptorSetCreationError(DescriptorSetCreationError),
+    DispatchError(DispatchError),
+}
+
+impl error::Error for FrustumCullError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::CopyError(err) => Some(err),
+            Self::DescriptorSetCreationError(err) => Some(err),
+            Self::DispatchError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for FrustumCullError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::CopyError(_) => write!(f, "resetting the count buffer failed"),
+            Self::DescriptorSetCreationError(_) => {
+                write!(f, "creating the descriptor set failed")
+            }
+            Self::DispatchError(_) => write!(f, "recording the dispatch command failed"),
+        }
+    }
+}
+
+impl From<CopyError> for FrustumCullError {
+    #[inline]
+    fn from(err: CopyError) -> Self {
+        Self::CopyError(err)
+    }
+}
+
+impl From<DescriptorSetCreationError> for FrustumCullError {
+    #[inline]
+    fn from(err: DescriptorSetCreationError) -> Self {
+        Self::DescriptorSetCreationError(err)
+    }
+}
+
+impl From<DispatchError> for FrustumCullError {
+    #[inline]
+    fn from(err: DispatchError) -> Self {
+        Self::DispatchError(err)
+    }
+}
+
+/// Occlusion queries and a visibility buffer that draw objects only if they were visible in the
+/// previous frame.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug)]
+pub struct OcclusionCuller {
+    query_pool: Arc<QueryPool>,
+    capacity: u32,
+    frames_in_flight: u32,
+    visibility_buffer: Arc<Subbuffer<[u32]>>,
+    visibility_initialized: bool,
+
+    // The index of the frame in flight whose queries are used by the current frame.
+    frame: u32,
+    frame_begun: bool,
+    // Whether the query of each object was issued in the current frame.
+    queried: Vec<bool>,
+    // The object between `begin_object` and `end_object`, and whether its proxy is being drawn.
+    active_object: Option<(u32, bool)>,
+}
+
+impl OcclusionCuller {
+    /// Creates a new `OcclusionCuller`.
+    ///
+    /// The
+    /// [`ext_conditional_rendering`](vulkano::device::DeviceExtensions::ext_conditional_rendering)
+    /// extension and the
+    /// [`conditional_rendering`](vulkano::device::Features::conditional_rendering) feature must be
+    /// enabled on the device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.capacity` or `create_info.frames_in_flight` is 0.
+    pub fn new(
+        device: Arc<Device>,
+        create_info: OcclusionCullerCreateInfo,
+    ) -> Result<OcclusionCuller, OcclusionCullerCreationError> {
+        let OcclusionCullerCreateInfo {
+            capacity,
+            frames_in_flight,
+            _ne: _,
+        } = create_info;
+
+        assert!(capacity != 0, "the capacity must not be 0");
+        assert!(frames_in_flight != 0, "frames_in_flight must not be 0");
+
+        if !device.enabled_extensions().ext_conditional_rendering {
+            return Err(OcclusionCullerCreationError::ExtensionNotEnabled {
+                extension: "ext_conditional_rendering",
+                reason: "created an OcclusionCuller",
+            });
+        }
+
+        if !device.enabled_features().conditional_rendering {
+            return Err(OcclusionCullerCreationError::FeatureNotEnabled {
+                feature: "conditional_rendering",
+                reason: "created an OcclusionCuller",
+            });
+        }
+
+        let query_pool = QueryPool::new(
+            device.clone(),
+            QueryPoolCreateInfo {
+                query_count: capacity
+                    .checked_mul(frames_in_flight)
+                    .expect("capacity * frames_in_flight must fit in a u32"),
+                ..QueryPoolCreateInfo::query_type(QueryType::Occlusion)
+            },
+        )?;
+        let visibility_buffer = Buffer::new_slice(
+            device.clone(),
+            BufferUsage {
+                transfer_dst: true,
+                conditional_rendering: true,
+                ..BufferUsage::none()
+            },
+            BufferLocation::Device,
+            capacity as DeviceSize,
+        )?;
+
+        Ok(OcclusionCuller {
+            query_pool,
+            capacity,
+            frames_in_flight,
+            visibility_buffer,
+            visibility_initialized: false,
+
+            frame: 0,
+            frame_begun: false,
+            queried: vec![false; capacity as usize],
+            active_object: None,
+        })
+    }
+
+    /// Returns the query pool that contains the queries of all the frames in flight.
+    #[inline]
+    pub fn query_pool(&self) -> &Arc<QueryPool> {
+        &self.query_pool
+    }
+
+    /// Returns the maximum number of objects that can be queried in a frame.
+    #[inline]
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Returns the number of frames that can be in flight at the same time.
+    #[inline]
+    pub fn frames_in_flight(&self) -> u32 {
+        self.frames_in_flight
+    }
+
+    /// Returns the buffer that contains, for each object, a non-zero value if the object was
+    /// visible the last time it was queried.
+    #[inline]
+    pub fn visibility_buffer(&self) -> &Arc<Subbuffer<[u32]>> {
+        &self.visibility_buffer
+    }
+
+    /// Records the start of a new frame into `builder`, which must be outside of a render pass.
+    ///
+    /// This resets the queries of the current frame in flight. The first time it is called, all
+    /// objects are also marked as visible, so that they are all drawn in the first frame.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the previous frame was not ended with [`end_frame`](Self::end_frame).
+    pub fn begin_frame<L, P>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+    ) -> Result<(), OcclusionCullError> {
+        assert!(!self.frame_begun, "the previous frame must be ended first");
+
+        if !self.visibility_initialized {
+            builder.fill_buffer(FillBufferInfo {
+                data: 1,
+                ..FillBufferInfo::dst_buffer(self.visibility_buffer.clone())
+            })?;
+            self.visibility_initialized = true;
+        }
+
+        unsafe {
+            // The queries of a frame are only active while its command buffer is being recorded,
+            // and a command buffer can't end with an active query.
+            builder.reset_query_pool(self.query_pool.clone(), self.frame_queries())?;
+        }
+
+        self.queried.iter_mut().for_each(|queried| *queried = false);
+        self.frame_begun = true;
+
+        Ok(())
+    }
+
+    /// Begins the query of the object at `index`, and the conditional rendering that skips its
+    /// draw commands if it was not visible the last time it was queried.
+    ///
+    /// The draw commands of the object must be recorded after this call, inside a render pass.
+    /// Their samples that pass the depth and stencil tests are counted by the query.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no frame was begun with [`begin_frame`](Self::begin_frame).
+    /// - Panics if another object was begun and not ended with [`end_object`](Self::end_object).
+    /// - Panics if `index` is not less than the capacity of the culler.
+    /// - Panics if the object at `index` was already queried in the current frame.
+    pub fn begin_object<L, P>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+        index: u32,
+    ) -> Result<(), OcclusionCullError> {
+        assert!(self.frame_begun, "a frame must be begun first");
+        assert!(
+            self.active_object.is_none(),
+            "the previous object must be ended first"
+        );
+        assert!(
+            index < self.capacity,
+            "the index must be less than the capacity of the culler"
+        );
+        assert!(
+            !self.queried[index as usize],
+            "an object can only be queried once per frame"
+        );
+
+        unsafe {
+            // The query was reset in `begin_frame`.
+            builder.begin_query(
+                self.query_pool.clone(),
+                self.frame_queries().start + index,
+                QueryControlFlags::default(),
+            )?;
+        }
+        builder.begin_conditional_rendering(self.condition(index, false))?;
+
+        self.active_object = Some((index, false));
+
+        Ok(())
+    }
+
+    /// Ends the conditional rendering of the current object, and begins one that skips the draw
+    /// commands that follow if the object *was* visible the last time it was queried.
+    ///
+    /// The draw commands recorded after this call should draw a cheap proxy of the object, such
+    /// as its bounding box, with color and depth writes disabled. They are drawn only when the
+    /// object itself is not, so that the query can find out when the object becomes visible
+    /// again.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no object was begun with [`begin_object`](Self::begin_object).
+    /// - Panics if the proxy of the current object was already begun.
+    pub fn begin_proxy<L, P>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+    ) -> Result<(), OcclusionCullError> {
+        let (index, proxy) = self
+            .active_object
+            .as_mut()
+            .expect("an object must be begun first");
+        assert!(!*proxy, "the proxy of the object was already begun");
+        *proxy = true;
+        let index = *index;
+
+        builder
+            .end_conditional_rendering()?
+            .begin_conditional_rendering(self.condition(index, true))?;
+
+        Ok(())
+    }
+
+    /// Ends the conditional rendering and the query of the current object.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no object was begun with [`begin_object`](Self::begin_object).
+    pub fn end_object<L, P>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+    ) -> Result<(), OcclusionCullError> {
+        let (index, _) = self
+            .active_object
+            .take()
+            .expect("an object must be begun first");
+
+        builder
+            .end_conditional_rendering()?
+            .end_query(self.query_pool.clone(), self.frame_queries().start + index)?;
+        self.queried[index as usize] = true;
+
+        Ok(())
+    }
+
+    /// Records the end of the frame into `builder`, which must be outside of a render pass.
+    ///
+    /// The results of the queries of the objects that were begun in this frame are copied to the
+    /// visibility buffer, where they decide whether the objects are drawn in the next frame. The
+    /// visibility of the other objects is left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no frame was begun with [`begin_frame`](Self::begin_frame).
+    /// - Panics if an object was begun and not ended with [`end_object`](Self::end_object).
+    pub fn end_frame<L, P>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+    ) -> Result<(), OcclusionCullError> {
+        assert!(self.frame_begun, "a frame must be begun first");
+        assert!(
+            self.active_object.is_none(),
+            "the current object must be ended first"
+        );
+
+        let first_query = self.frame_queries().start;
+        let mut index = 0;
+
+        // Copy each run of consecutive queried objects with a single command.
+        while index < self.capacity {
+            if !self.queried[index as usize] {
+                index += 1;
+                continue;
+            }
+
+            let start = index;
+
+            while index < self.capacity && self.queried[index as usize] {
+                index += 1;
+            }
+
+            builder.copy_query_pool_results(
+                self.query_pool.clone(),
+                first_query + start..first_query + index,
+                self.visibility_buffer
+                    .slice(start as DeviceSize..index as DeviceSize)
+                    .unwrap(),
+                QueryResultFlags {
+                    wait: true,
+                    ..Default::default()
+                },
+            )?;
+        }
+
+        self.frame = (self.frame + 1) % self.frames_in_flight;
+        self.frame_begun = false;
+
+        Ok(())
+    }
+
+    // The range of queries of the current frame in flight.
+    fn frame_queries(&self) -> Range<u32> {
+        let start = self.frame * self.capacity;
+        start..start + self.capacity
+    }
+
+    fn condition(&self, index: u32, inverted: bool) -> ConditionalRenderingBeginInfo {
+        ConditionalRenderingBeginInfo {
+            offset: index as DeviceSize * size_of::<u32>() as DeviceSize,
+            inverted,
+            ..ConditionalRenderingBeginInfo::buffer(self.visibility_buffer.clone())
+        }
+    }
+}
+
+unsafe impl DeviceOwned for OcclusionCuller {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.query_pool.device()
+    }
+}
+
+/// Parameters to create a new `OcclusionCuller`.
+#[derive(Clone, Debug)]
+pub struct OcclusionCullerCreateInfo {
+    /// The maximum number of objects that can be queried in a frame. Objects are identified by
+    /// their index, which must be less than this value.
+    ///
+    /// The default value is `1024`.
+    pub capacity: u32,
+
+    /// The number of frames that can be in flight at the same time. Each of them uses its own set
+    /// of queries, so that a frame can be recorded while the previous ones are still executing.
+    ///
+    /// The default value is `2`.
+    pub frames_in_flight: u32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for OcclusionCullerCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            frames_in_flight: 2,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Error that can happen when creating an `OcclusionCuller`.
+#[derive(Clone, Debug)]
+pub enum OcclusionCullerCreationError {
+    BufferCreationError(BufferCreationError),
+    QueryPoolCreationError(QueryPoolCreationError),
+
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl error::Error for OcclusionCullerCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::BufferCreationError(err) => Some(err),
+            Self::QueryPoolCreationError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for OcclusionCullerCreationError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::BufferCreationError(_) => write!(f, "creating a buffer failed"),
+            Self::QueryPoolCreationError(_) => write!(f, "creating the query pool failed"),
+            Self::ExtensionNotEnabled { extension, reason } => {
+                write!(f, "the extension {} must be enabled: {}", extension, reason)
+            }
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(f, "the feature {} must be enabled: {}", feature, reason)
+            }
+        }
+    }
+}
+
+impl From<BufferCreationError> for OcclusionCullerCreationError {
+    #[inline]
+    fn from(err: BufferCreationError) -> Self {
+        Self::BufferCreationError(err)
+    }
+}
+
+impl From<QueryPoolCreationError> for OcclusionCullerCreationError {
+    #[inline]
+    fn from(err: QueryPoolCreationError) -> Self {
+        Self::QueryPoolCreationError(err)
+    }
+}
+
+/// Error that can happen when recording the commands of an [`OcclusionCuller`].
+#[derive(Clone, Debug)]
+pub enum OcclusionCullError {
+    BeginQueryError(BeginQueryError),
+    ConditionalRenderingError(ConditionalRenderingError),
+    CopyError(CopyError),
+    CopyQueryPoolResultsError(CopyQueryPoolResultsError),
+    EndQueryError(EndQueryError),
+    ResetQueryPoolError(ResetQueryPoolError),
+}
+
+impl error::Error for OcclusionCullError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::BeginQueryError(err) => Some(err),
+            Self::ConditionalRenderingError(err) => Some(err),
+            Self::CopyError(err) => Some(err),
+            Self::CopyQueryPoolResultsError(err) => Some(err),
+            Self::EndQueryError(err) => Some(err),
+            Self::ResetQueryPoolError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for OcclusionCullError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::BeginQueryError(_) => write!(f, "beginning the query failed"),
+            Self::ConditionalRenderingError(_) => {
+                write!(f, "recording the conditional rendering failed")
+            }
+            Self::CopyError(_) => write!(f, "initializing the visibility buffer failed"),
+            Self::CopyQueryPoolResultsError(_) => {
+                write!(f, "copying the query results failed")
+            }
+            Self::EndQueryError(_) => write!(f, "ending the query failed"),
+            Self::ResetQueryPoolError(_) => write!(f, "resetting the queries failed"),
+        }
+    }
+}
+
+impl From<BeginQueryError> for OcclusionCullError {
+    #[inline]
+    fn from(err: BeginQueryError) -> Self {
+        Self::BeginQueryError(err)
+    }
+}
+
+impl From<ConditionalRenderingError> for OcclusionCullError {
+    #[inline]
+    fn from(err: ConditionalRenderingError) -> Self {
+        Self::ConditionalRenderingError(err)
+    }
+}
+
+impl From<CopyError> for OcclusionCullError {
+    #[inline]
+    fn from(err: CopyError) -> Self {
+        Self::CopyError(err)
+    }
+}
+
+impl From<CopyQueryPoolResultsError> for OcclusionCullError {
+    #[inline]
+    fn from(err: CopyQueryPoolResultsError) -> Self {
+        Self::CopyQueryPoolResultsError(err)
+    }
+}
+
+impl From<EndQueryError> for OcclusionCullError {
+    #[inline]
+    fn from(err: EndQueryError) -> Self {
+        Self::EndQueryError(err)
+    }
+}
+
+impl From<ResetQueryPoolError> for OcclusionCullError {
+    #[inline]
+    fn from(err: ResetQueryPoolError) -> Self {
+        Self::ResetQueryPoolError(err)
+    }
+}
+
+// Must match the `local_size_x` of the shader.
+const WORKGROUP_SIZE: u32 = 64;
+
+// Extracts the planes of the frustum from the rows of the matrix, for a clip space whose depth
+// goes from 0 to 1. The normals point inwards, so that a point is inside if its signed distance
+// to all planes is positive.
+fn frustum_planes(m: [[f32; 4]; 4]) -> [[f32; 4]; 6] {
+    let row = |r: usize| [m[0][r], m[1][r], m[2][r], m[3][r]];
+    let add = |a: [f32; 4], b: [f32; 4]| [a[0] + b[0], a[1] + b[1], a[2] + b[2], a[3] + b[3]];
+    let sub = |a: [f32; 4], b: [f32; 4]| [a[0] - b[0], a[1] - b[1], a[2] - b[2], a[3] - b[3]];
+    let normalize = |p: [f32; 4]| {
+        let length = (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt();
+        [p[0] / length, p[1] / length, p[2] / length, p[3] / length]
+    };
+
+    let [x, y, z, w] = [row(0), row(1), row(2), row(3)];
+
+    [
+        normalize(add(w, x)),
+        normalize(sub(w, x)),
+        normalize(add(w, y)),
+        normalize(sub(w, y)),
+        normalize(z),
+        normalize(sub(w, z)),
+    ]
+}
+
+mod cs {
+    vulkano_shaders::shader! {
+        ty: "compute",
+        src: "
+            #version 450
+
+            layout(local_size_x = 64, local_size_y = 1, local_size_z = 1) in;
+
+            struct DrawCommand {
+                uint index_count;
+                uint instance_count;
+                uint first_index;
+                int vertex_offset;
+                uint first_instance;
+            };
+
+            layout(set = 0, binding = 0) readonly buffer Bounds {
+                vec4 bounds[];
+            };
+            layout(set = 0, binding = 1) readonly buffer Templates {
+                DrawCommand templates[];
+            };
+            layout(set = 0, binding = 2) buffer Draws {
+                DrawCommand draws[];
+            };
+            layout(set = 0, binding = 3) buffer Count {
+                uint draw_count;
+            };
+
+            layout(push_constant) uniform PushConstants {
+                vec4 planes[6];
+                uvec4 object_count;
+            } pc;
+
+            void main() {
+                uint index = gl_GlobalInvocationID.x;
+                if (index >= pc.object_count.x) {
+                    return;
+                }
+
+                vec4 sphere = bounds[index];
+                for (int i = 0; i < 6; i++) {
+                    if (dot(pc.planes[i].xyz, sphere.xyz) + pc.planes[i].w < -sphere.w) {
+                        return;
+                    }
+                }
+
+                uint slot = atomicAdd(draw_count, 1u);
+                draws[slot] = templates[index];
+            }
+        "
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        frustum_planes, BoundingSphere, FrustumCuller, FrustumCullerCreateInfo, OcclusionCuller,
+        OcclusionCullerCreateInfo, OcclusionCullerCreationError,
+    };
+    use vulkano::{
+        buffer::{Buffer, BufferLocation, BufferUsage},
+        command_buffer::{
+            AutoCommandBufferBuilder, CommandBufferUsage, DrawIndexedIndirectCommand,
+        },
+    };
+
+    #[test]
+    fn record_cull() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let culler = FrustumCuller::new(
+            device.clone(),
+            FrustumCullerCreateInfo {
+                capacity: 16,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let usage = BufferUsage {
+            storage_buffer: true,
+            ..BufferUsage::none()
+        };
+        let bounds = Buffer::from_iter(
+            device.clone(),
+            usage,
+            BufferLocation::Upload,
+            [BoundingSphere {
+                center: [0.0, 0.0, 0.5],
+                radius: 1.0,
+            }; 4],
+        )
+        .unwrap();
+        let draw_commands = Buffer::from_iter(
+            device.clone(),
+            usage,
+            BufferLocation::Upload,
+            [DrawIndexedIndirectCommand::default(); 4],
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        let identity = [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        culler
+            .cull(&mut builder, bounds, draw_commands, identity)
+            .unwrap();
+        builder.build().unwrap();
+
+        // With the identity matrix, the frustum is the clip space volume itself.
+        assert_eq!(
+            frustum_planes(identity),
+            [
+                [1.0, 0.0, 0.0, 1.0],
+                [-1.0, 0.0, 0.0, 1.0],
+                [0.0, 1.0, 0.0, 1.0],
+                [0.0, -1.0, 0.0, 1.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, -1.0, 1.0],
+            ]
+        );
+    }
+
+    #[test]
+    fn occlusion_culler_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match OcclusionCuller::new(device, OcclusionCullerCreateInfo::default()) {
+            Err(OcclusionCullerCreationError::ExtensionNotEnabled {
+                extension: "ext_conditional_rendering",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+}
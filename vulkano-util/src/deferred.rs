@@ -0,0 +1,1217 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Deferred rendering.
+//!
+//! With deferred rendering, the objects of the scene are not lit while they are drawn. Instead,
+//! their properties, such as their albedo and their normal, are written to a set of images called
+//! the G-buffer. Lighting is then applied in a second step, by running a fragment shader over the
+//! whole image for each light source, which reads the G-buffer and adds the contribution of the
+//! light to the final image. The cost of lighting thus depends on the number of pixels and not on
+//! the number of objects.
+//!
+//! A [`FrameSystem`] owns the render pass and the G-buffer images, and guides the recording of
+//! each frame through its passes:
+//!
+//! - An optional depth pre-pass, where only the depth of the objects is drawn, so that the
+//!   fragment shaders of the G-buffer pass run only once per pixel.
+//! - The deferred pass, where the objects are drawn to the G-buffer, whose color formats are
+//!   chosen when the `FrameSystem` is created. With multisampling, the objects are drawn to
+//!   multisampled images which are resolved into the G-buffer at the end of the pass.
+//! - The lighting pass, where lights are drawn with the [`FullscreenPass`]es returned by
+//!   [`FrameSystem::lighting_pass`], with fragment shaders that are provided by the user.
+//!
+//! The fragment shaders of the lighting pass read the G-buffer as input attachments in the
+//! descriptor set 0. The color images are bound in the order of their formats, starting at
+//! binding 0, and the depth image follows them. With the default G-buffer formats:
+//!
+//! ```glsl
+//! layout(input_attachment_index = 0, set = 0, binding = 0) uniform subpassInput u_diffuse;
+//! layout(input_attachment_index = 1, set = 0, binding = 1) uniform subpassInput u_normals;
+//! layout(input_attachment_index = 2, set = 0, binding = 2) uniform subpassInput u_depth;
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use vulkano::{command_buffer::SubpassContents, format::Format};
+//! use vulkano_util::deferred::{FrameSystem, FrameSystemCreateInfo, Pass};
+//!
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! # let lighting_fs: std::sync::Arc<vulkano::shader::ShaderModule> = return;
+//! # let swapchain_image: std::sync::Arc<dyn vulkano::image::ImageViewAbstract> = return;
+//! # let mut builder: vulkano::command_buffer::AutoCommandBufferBuilder<vulkano::command_buffer::PrimaryAutoCommandBuffer> = return;
+//! let mut frame_system = FrameSystem::new(
+//!     device.clone(),
+//!     FrameSystemCreateInfo::output_format(Format::B8G8R8A8_SRGB),
+//! )
+//! .unwrap();
+//! let light = frame_system
+//!     .lighting_pass(lighting_fs.entry_point("main").unwrap())
+//!     .unwrap();
+//!
+//! // Create the pipelines that draw the objects with `frame_system.deferred_subpass()`.
+//!
+//! // Each frame:
+//! let mut frame = frame_system
+//!     .frame(&mut builder, swapchain_image.clone(), SubpassContents::Inline)
+//!     .unwrap();
+//!
+//! while let Some(pass) = frame.next_pass().unwrap() {
+//!     match pass {
+//!         Pass::DepthPrepass(_) => unreachable!(),
+//!         Pass::Deferred(mut draw_pass) => {
+//!             // Draw the objects with `draw_pass.builder()`.
+//!         }
+//!         Pass::Lighting(mut lighting) => {
+//!             lighting.draw(&light, ()).unwrap();
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::fullscreen_pass::{
+    FullscreenPass, FullscreenPassCreateInfo, FullscreenPassCreationError,
+};
+use std::{error, fmt, sync::Arc};
+use vulkano::{
+    command_buffer::{
+        AutoCommandBufferBuilder, DrawError, ExecuteCommandsError, PrimaryAutoCommandBuffer,
+        RenderPassBeginInfo, RenderPassError, SecondaryCommandBuffer, SubpassContents,
+    },
+    descriptor_set::{
+        DescriptorSetCreationError, DescriptorSetsCollection, PersistentDescriptorSet,
+        WriteDescriptorSet,
+    },
+    device::{Device, DeviceOwned},
+    format::{ClearValue, Format},
+    image::{
+        view::{ImageView, ImageViewCreationError},
+        AttachmentImage, AttachmentImageCreateInfo, ImageCreationError, ImageUsage,
+        ImageViewAbstract, SampleCount,
+    },
+    pipeline::{
+        graphics::{
+            color_blend::{AttachmentBlend, BlendFactor, BlendOp},
+            depth_stencil::{CompareOp, DepthState, DepthStencilState},
+            input_assembly::InputAssemblyState,
+            multisample::MultisampleState,
+            vertex_input::VertexDefinition,
+            viewport::{Viewport, ViewportState},
+            GraphicsPipelineCreationError,
+        },
+        GraphicsPipeline, Pipeline, StateMode,
+    },
+    render_pass::{
+        AttachmentDescription, Framebuffer, FramebufferCreateInfo, FramebufferCreationError,
+        LoadOp, RenderPass, RenderPassBuilder, RenderPassCreationError, ResolveMode, StoreOp,
+        Subpass, SubpassBuilder, SubpassDependency,
+    },
+    shader::EntryPoint,
+    sync::PipelineStages,
+    Version,
+};
+
+/// The render pass and the G-buffer of a deferred renderer.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug)]
+pub struct FrameSystem {
+    render_pass: Arc<RenderPass>,
+    g_buffer_formats: Vec<Format>,
+    depth_format: Format,
+    samples: SampleCount,
+    depth_prepass: bool,
+
+    // The extent of the images below. Zero until the first frame.
+    extent: [u32; 2],
+    // The images that the lighting subpass reads, in the order of `g_buffer_formats` followed by
+    // the depth image.
+    g_buffer: Vec<Arc<ImageView<AttachmentImage>>>,
+    // When multisampling, the images that the objects are drawn to, in the same order as
+    // `g_buffer`. They are resolved into `g_buffer` at the end of the deferred subpass. Empty
+    // otherwise.
+    multisampled_g_buffer: Vec<Arc<ImageView<AttachmentImage>>>,
+}
+
+impl FrameSystem {
+    /// Creates a new `FrameSystem`.
+    ///
+    /// The G-buffer images are created by the first call to [`frame`](FrameSystem::frame), with
+    /// the extent of the final image.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.output_format` is `None`.
+    /// - Panics if an element of `create_info.g_buffer_formats` does not have a color aspect, or
+    ///   is compressed.
+    /// - Panics if `create_info.depth_format` does not have a depth aspect.
+    pub fn new(
+        device: Arc<Device>,
+        create_info: FrameSystemCreateInfo,
+    ) -> Result<FrameSystem, FrameSystemCreationError> {
+        let FrameSystemCreateInfo {
+            output_format,
+            g_buffer_formats,
+            depth_format,
+            samples,
+            depth_prepass,
+            _ne: _,
+        } = create_info;
+
+        let output_format = output_format.expect("`create_info.output_format` must be set");
+
+        for format in &g_buffer_formats {
+            assert!(
+                format.aspects().color && format.compression().is_none(),
+                "the G-buffer formats must be uncompressed color formats"
+            );
+        }
+
+        assert!(
+            depth_format.aspects().depth,
+            "the depth format of the G-buffer must have a depth aspect"
+        );
+
+        let multisampled = samples != SampleCount::Sample1;
+
+        // The multisampled depth image is resolved into the depth image that the lighting
+        // subpass reads.
+        if multisampled
+            && !(device.api_version() >= Version::V1_2
+                || device.enabled_extensions().khr_depth_stencil_resolve)
+        {
+            return Err(FrameSystemCreationError::ExtensionNotEnabled {
+                extension: "khr_depth_stencil_resolve",
+                reason: "`create_info.samples` was not `SampleCount::Sample1`",
+            });
+        }
+
+        // Attachment 0 is the final image. It is followed by the multisampled images if
+        // multisampling, then by the images that the lighting subpass reads. Each group contains
+        // the color images in the order of `g_buffer_formats`, then the depth image.
+        let formats: Vec<Format> = g_buffer_formats
+            .iter()
+            .copied()
+            .chain([depth_format])
+            .collect();
+        let stencil = depth_format.aspects().stencil;
+        let mut builder = RenderPassBuilder::new().attachment(AttachmentDescription {
+            format: Some(output_format),
+            load_op: LoadOp::Clear,
+            store_op: StoreOp::Store,
+            ..Default::default()
+        });
+
+        if multisampled {
+            for &format in &formats {
+                builder = builder.attachment(AttachmentDescription {
+                    format: Some(format),
+                    samples,
+                    load_op: LoadOp::Clear,
+                    stencil_load_op: if stencil {
+                        LoadOp::Clear
+                    } else {
+                        LoadOp::DontCare
+                    },
+                    ..Default::default()
+                });
+            }
+        }
+
+        // When multisampling, these are entirely overwritten by the resolve.
+        for &format in &formats {
+            let load_op = if multisampled {
+                LoadOp::DontCare
+            } else {
+                LoadOp::Clear
+            };
+            builder = builder.attachment(AttachmentDescription {
+                format: Some(format),
+                load_op,
+                stencil_load_op: if stencil { load_op } else { LoadOp::DontCare },
+                ..Default::default()
+            });
+        }
+
+        let count = formats.len() as u32;
+        let draw_first = 1;
+        let read_first = if multisampled { 1 + count } else { 1 };
+        let draw_depth = draw_first + count - 1;
+        let read_depth = read_first + count - 1;
+
+        if depth_prepass {
+            builder = builder.subpass(SubpassBuilder::new().depth_stencil_attachment(draw_depth));
+        }
+
+        // The depth attachment is resolved by taking its first sample, which is the only mode
+        // that all devices support.
+        let mut deferred_subpass = SubpassBuilder::new().depth_stencil_attachment(draw_depth);
+
+        for attachment in draw_first..draw_depth {
+            deferred_subpass = deferred_subpass.color_attachment(attachment);
+        }
+
+        if multisampled {
+            for attachment in read_first..read_depth {
+                deferred_subpass = deferred_subpass.resolve_attachment(attachment);
+            }
+
+            deferred_subpass = deferred_subpass.depth_stencil_resolve_attachment(
+                read_depth,
+                Some(ResolveMode::SampleZero),
+                stencil.then(|| ResolveMode::SampleZero),
+            );
+        }
+
+        builder = builder.subpass(deferred_subpass);
+
+        let mut lighting_subpass = SubpassBuilder::new().color_attachment(0);
+
+        for attachment in read_first..=read_depth {
+            lighting_subpass = lighting_subpass.input_attachment(attachment);
+        }
+
+        builder = builder.subpass(lighting_subpass);
+
+        // Each subpass uses the attachments written by the previous one.
+        for id in 0..builder.num_subpasses() - 1 {
+            let stages = PipelineStages {
+                all_graphics: true,
+                ..PipelineStages::none()
+            };
+            builder = builder.dependency(SubpassDependency {
+                source_subpass: Some(id),
+                destination_subpass: Some(id + 1),
+                source_stages: stages,
+                destination_stages: stages,
+                source_access: stages.supported_access(),
+                destination_access: stages.supported_access(),
+                by_region: true,
+                ..Default::default()
+            });
+        }
+
+        let render_pass = builder.build(device)?;
+
+        Ok(FrameSystem {
+            render_pass,
+            g_buffer_formats,
+            depth_format,
+            samples,
+            depth_prepass,
+            extent: [0, 0],
+            g_buffer: Vec::new(),
+            multisampled_g_buffer: Vec::new(),
+        })
+    }
+
+    /// Returns the render pass of the frame system.
+    #[inline]
+    pub fn render_pass(&self) -> &Arc<RenderPass> {
+        &self.render_pass
+    }
+
+    /// Returns the formats of the color images of the G-buffer.
+    #[inline]
+    pub fn g_buffer_formats(&self) -> &[Format] {
+        &self.g_buffer_formats
+    }
+
+    /// Returns the format of the depth image of the G-buffer.
+    #[inline]
+    pub fn depth_format(&self) -> Format {
+        self.depth_format
+    }
+
+    /// Returns the number of samples per pixel that the objects are drawn with.
+    #[inline]
+    pub fn samples(&self) -> SampleCount {
+        self.samples
+    }
+
+    /// Returns the subpass where the depth of the objects is drawn, if the frame system was
+    /// created with a depth pre-pass.
+    ///
+    /// The subpass has no color attachment, only the depth attachment.
+    #[inline]
+    pub fn depth_prepass_subpass(&self) -> Option<Subpass> {
+        if self.depth_prepass {
+            Some(self.render_pass.clone().first_subpass())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the subpass where the objects are drawn to the G-buffer.
+    ///
+    /// The subpass has a color attachment for each of the G-buffer formats, in the same order,
+    /// and the depth attachment.
+    #[inline]
+    pub fn deferred_subpass(&self) -> Subpass {
+        Subpass::from(self.render_pass.clone(), self.depth_prepass as u32).unwrap()
+    }
+
+    /// Returns the subpass where the lighting is applied.
+    ///
+    /// The subpass has the final image as its only color attachment, and reads the G-buffer as
+    /// input attachments.
+    #[inline]
+    pub fn lighting_subpass(&self) -> Subpass {
+        Subpass::from(self.render_pass.clone(), self.depth_prepass as u32 + 1).unwrap()
+    }
+
+    /// Returns the multisample state that the pipelines drawing in the depth pre-pass and in the
+    /// deferred subpass must use.
+    #[inline]
+    pub fn multisample_state(&self) -> MultisampleState {
+        MultisampleState {
+            rasterization_samples: self.samples,
+            ..MultisampleState::new()
+        }
+    }
+
+    /// Returns the depth state that the pipelines drawing in the deferred subpass should use.
+    ///
+    /// With a depth pre-pass, the depth buffer already contains the depth of the closest objects,
+    /// so only the fragments at exactly that depth are drawn, and the depth is not written again.
+    /// For the depths to be equal, the vertex shaders of both passes must compute the positions
+    /// in exactly the same way, which is what the `invariant` qualifier on `gl_Position`
+    /// guarantees.
+    pub fn deferred_depth_stencil_state(&self) -> DepthStencilState {
+        if self.depth_prepass {
+            DepthStencilState {
+                depth: Some(DepthState {
+                    enable_dynamic: false,
+                    compare_op: StateMode::Fixed(CompareOp::Equal),
+                    write_enable: StateMode::Fixed(false),
+                }),
+                ..DepthStencilState::disabled()
+            }
+        } else {
+            DepthStencilState::simple_depth_test()
+        }
+    }
+
+    /// Creates the pipeline that draws objects in the depth pre-pass, from the vertex input and
+    /// the vertex shader of the pipeline that draws them in the deferred subpass.
+    ///
+    /// The pipeline has no fragment shader, as only the depth of the objects is written.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the frame system was created without a depth pre-pass.
+    pub fn depth_prepass_pipeline<Vdef>(
+        &self,
+        vertex_input_state: Vdef,
+        vertex_shader: EntryPoint,
+    ) -> Result<Arc<GraphicsPipeline>, GraphicsPipelineCreationError>
+    where
+        Vdef: VertexDefinition,
+    {
+        let subpass = self
+            .depth_prepass_subpass()
+            .expect("the frame system has no depth pre-pass");
+
+        GraphicsPipeline::start()
+            .vertex_input_state(vertex_input_state)
+            .vertex_shader(vertex_shader, ())
+            .input_assembly_state(InputAssemblyState::new())
+            .viewport_state(ViewportState::viewport_dynamic_scissor_irrelevant())
+            .depth_stencil_state(DepthStencilState::simple_depth_test())
+            .multisample_state(self.multisample_state())
+            .render_pass(subpass)
+            .build(self.device().clone())
+    }
+
+    /// Creates a pass that draws a light with `fragment_shader` in the lighting subpass.
+    ///
+    /// The output of the shader is added to the final image. To blend it differently, create a
+    /// [`FullscreenPass`] with the [`lighting_subpass`](FrameSystem::lighting_subpass) instead.
+    pub fn lighting_pass(
+        &self,
+        fragment_shader: EntryPoint,
+    ) -> Result<FullscreenPass, FullscreenPassCreationError> {
+        FullscreenPass::new(
+            self.device().clone(),
+            fragment_shader,
+            FullscreenPassCreateInfo {
+                blend: Some(AttachmentBlend {
+                    color_op: BlendOp::Add,
+                    color_source: BlendFactor::One,
+                    color_destination: BlendFactor::One,
+                    alpha_op: BlendOp::Max,
+                    alpha_source: BlendFactor::One,
+                    alpha_destination: BlendFactor::One,
+                }),
+                ..FullscreenPassCreateInfo::subpass(self.lighting_subpass())
+            },
+        )
+    }
+
+    /// Begins the render pass of a frame that renders to `final_image`, and returns a [`Frame`]
+    /// to record its passes.
+    ///
+    /// The G-buffer images are recreated if their extent is not the extent of `final_image`.
+    /// `final_image` is cleared to zero, then the contributions of the lights are added to it.
+    /// The depth pre-pass and the deferred subpass are begun with `contents`, while the lighting
+    /// subpass is always recorded inline.
+    pub fn frame<'a>(
+        &'a mut self,
+        builder: &'a mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+        final_image: Arc<dyn ImageViewAbstract>,
+        contents: SubpassContents,
+    ) -> Result<Frame<'a>, FrameError> {
+        let extent = final_image.dimensions().width_height();
+
+        if self.extent != extent {
+            self.create_g_buffer(extent)?;
+        }
+
+        let mut attachments = vec![final_image];
+        attachments.extend(
+            self.multisampled_g_buffer
+                .iter()
+                .chain(&self.g_buffer)
+                .map(|image| image.clone() as Arc<_>),
+        );
+        let framebuffer = Framebuffer::new(
+            self.render_pass.clone(),
+            FramebufferCreateInfo {
+                attachments,
+                ..Default::default()
+            },
+        )?;
+
+        // The images that the objects are drawn to are cleared. When multisampling, the resolved
+        // images are not.
+        let depth_clear_value = if self.depth_format.aspects().stencil {
+            ClearValue::DepthStencil((1.0, 0))
+        } else {
+            ClearValue::Depth(1.0)
+        };
+        let mut clear_values = vec![Some([0.0, 0.0, 0.0, 0.0].into())];
+        clear_values.extend(
+            self.g_buffer_formats
+                .iter()
+                .map(|_| Some([0.0, 0.0, 0.0, 0.0].into())),
+        );
+        clear_values.push(Some(depth_clear_value));
+
+        if self.samples != SampleCount::Sample1 {
+            clear_values.extend(self.g_buffer.iter().map(|_| None));
+        }
+
+        // The first subpass is either the depth pre-pass or the deferred subpass.
+        builder.begin_render_pass(
+            RenderPassBeginInfo {
+                clear_values,
+                ..RenderPassBeginInfo::framebuffer(framebuffer)
+            },
+            contents,
+        )?;
+
+        Ok(Frame {
+            system: self,
+            builder,
+            contents,
+            extent,
+            num_pass: 0,
+        })
+    }
+
+    // Creates the images of the G-buffer with the given extent.
+    fn create_g_buffer(&mut self, extent: [u32; 2]) -> Result<(), FrameError> {
+        // The images are transient: their content is only defined within the render pass, so
+        // implementations can avoid allocating memory for them.
+        let create_image = |format, samples, input_attachment| {
+            Ok::<_, FrameError>(ImageView::new_default(AttachmentImage::with_create_info(
+                self.device().clone(),
+                AttachmentImageCreateInfo {
+                    samples,
+                    usage: ImageUsage {
+                        transient_attachment: true,
+                        input_attachment,
+                        ..ImageUsage::none()
+                    },
+                    ..AttachmentImageCreateInfo::dimensions_format(extent, format)
+                },
+            )?)?)
+        };
+        let formats = self
+            .g_buffer_formats
+            .iter()
+            .copied()
+            .chain([self.depth_format]);
+
+        let g_buffer = formats
+            .clone()
+            .map(|format| create_image(format, SampleCount::Sample1, true))
+            .collect::<Result<_, _>>()?;
+        let multisampled_g_buffer = if self.samples != SampleCount::Sample1 {
+            formats
+                .map(|format| create_image(format, self.samples, false))
+                .collect::<Result<_, _>>()?
+        } else {
+            Vec::new()
+        };
+
+        self.extent = extent;
+        self.g_buffer = g_buffer;
+        self.multisampled_g_buffer = multisampled_g_buffer;
+
+        Ok(())
+    }
+}
+
+unsafe impl DeviceOwned for FrameSystem {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.render_pass.device()
+    }
+}
+
+/// Parameters to create a new `FrameSystem`.
+#[derive(Clone, Debug)]
+pub struct FrameSystemCreateInfo {
+    /// The format of the final images that frames are rendered to. If the format of the final
+    /// images ever changes, a new `FrameSystem` must be created.
+    ///
+    /// The default value is `None`, which must be overridden.
+    pub output_format: Option<Format>,
+
+    /// The formats of the color images of the G-buffer, that the fragment shaders of the deferred
+    /// subpass write to, in the order of their locations.
+    ///
+    /// The default value is `[Format::A2B10G10R10_UNORM_PACK32, Format::R16G16B16A16_SFLOAT]`,
+    /// for the albedo and the normal of the objects.
+    pub g_buffer_formats: Vec<Format>,
+
+    /// The format of the depth image of the G-buffer.
+    ///
+    /// The default value is [`Format::D16_UNORM`].
+    pub depth_format: Format,
+
+    /// The number of samples per pixel that the objects are drawn with. If it is not `Sample1`,
+    /// the objects are drawn to multisampled images that are resolved into the G-buffer, so the
+    /// lighting is computed once per pixel. This requires the device API version to be at least
+    /// 1.2, or the [`khr_depth_stencil_resolve`] extension to be enabled on the device.
+    ///
+    /// The default value is [`SampleCount::Sample1`].
+    ///
+    /// [`khr_depth_stencil_resolve`]: vulkano::device::DeviceExtensions::khr_depth_stencil_resolve
+    pub samples: SampleCount,
+
+    /// Whether the depth of the objects is drawn in a subpass of its own before the objects are
+    /// drawn to the G-buffer. The fragment shaders of the deferred subpass then run only once per
+    /// pixel, which saves a lot of work in scenes where many objects overlap.
+    ///
+    /// The default value is `false`.
+    pub depth_prepass: bool,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for FrameSystemCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            output_format: None,
+            g_buffer_formats: vec![
+                Format::A2B10G10R10_UNORM_PACK32,
+                Format::R16G16B16A16_SFLOAT,
+            ],
+            depth_format: Format::D16_UNORM,
+            samples: SampleCount::Sample1,
+            depth_prepass: false,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl FrameSystemCreateInfo {
+    /// Returns a `FrameSystemCreateInfo` for frames that are rendered to images of `format`.
+    #[inline]
+    pub fn output_format(format: Format) -> Self {
+        Self {
+            output_format: Some(format),
+            ..Default::default()
+        }
+    }
+}
+
+/// The recording of a frame, returned by [`FrameSystem::frame`].
+///
+/// The passes of the frame are recorded one after the other by calling
+/// [`next_pass`](Frame::next_pass) until it returns `None`, at which point the render pass has
+/// ended.
+pub struct Frame<'a> {
+    system: &'a FrameSystem,
+    builder: &'a mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+    contents: SubpassContents,
+    extent: [u32; 2],
+
+    // The pass that the next call to `next_pass` returns.
+    // - 0 is the depth pre-pass, which is skipped if the frame system doesn't have one.
+    // - 1 is the deferred pass.
+    // - 2 is the lighting pass.
+    // - 3 ends the render pass, after which the frame is finished.
+    num_pass: u8,
+}
+
+impl<'a> Frame<'a> {
+    /// Returns the next pass of the frame, or `None` once the render pass has ended.
+    pub fn next_pass(&mut self) -> Result<Option<Pass<'_, 'a>>, RenderPassError> {
+        if self.num_pass == 0 && !self.system.depth_prepass {
+            self.num_pass = 1;
+        }
+
+        let current_pass = self.num_pass;
+        self.num_pass = self.num_pass.saturating_add(1);
+
+        Ok(match current_pass {
+            // The render pass was begun in the depth pre-pass by `FrameSystem::frame`.
+            0 => Some(Pass::DepthPrepass(DrawPass { frame: self })),
+            1 => {
+                if self.system.depth_prepass {
+                    self.builder.next_subpass(self.contents)?;
+                }
+
+                Some(Pass::Deferred(DrawPass { frame: self }))
+            }
+            2 => {
+                self.builder.next_subpass(SubpassContents::Inline)?;
+
+                Some(Pass::Lighting(LightingPass { frame: self }))
+            }
+            3 => {
+                self.builder.end_render_pass()?;
+
+                None
+            }
+            _ => None,
+        })
+    }
+
+    /// Returns a viewport that covers the whole final image.
+    #[inline]
+    pub fn viewport(&self) -> Viewport {
+        Viewport {
+            origin: [0.0, 0.0],
+            dimensions: [self.extent[0] as f32, self.extent[1] as f32],
+            depth_range: 0.0..1.0,
+        }
+    }
+}
+
+/// A pass of a [`Frame`].
+pub enum Pass<'f, 'a> {
+    /// The pass where the depth of the objects is drawn, if the frame system was created with a
+    /// depth pre-pass. The objects are drawn with the pipelines returned by
+    /// [`FrameSystem::depth_prepass_pipeline`].
+    DepthPrepass(DrawPass<'f, 'a>),
+
+    /// The pass where the objects are drawn to the G-buffer.
+    Deferred(DrawPass<'f, 'a>),
+
+    /// The pass where the lighting is applied to the final image.
+    Lighting(LightingPass<'f, 'a>),
+}
+
+/// Allows drawing the objects of the scene in the depth pre-pass or the deferred subpass.
+pub struct DrawPass<'f, 'a> {
+    frame: &'f mut Frame<'a>,
+}
+
+impl<'f, 'a> DrawPass<'f, 'a> {
+    /// Returns the command buffer builder, to record drawing commands if the pass was begun with
+    /// `SubpassContents::Inline`.
+    #[inline]
+    pub fn builder(&mut self) -> &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer> {
+        self.frame.builder
+    }
+
+    /// Records the execution of a secondary command buffer that draws objects, if the pass was
+    /// begun with `SubpassContents::SecondaryCommandBuffers`.
+    #[inline]
+    pub fn execute<C>(&mut self, command_buffer: C) -> Result<(), ExecuteCommandsError>
+    where
+        C: SecondaryCommandBuffer + 'static,
+    {
+        self.frame.builder.execute_commands(command_buffer)?;
+
+        Ok(())
+    }
+
+    /// Returns a viewport that covers the whole final image.
+    #[inline]
+    pub fn viewport(&self) -> Viewport {
+        self.frame.viewport()
+    }
+
+    /// Returns the width and height of the final image.
+    #[inline]
+    pub fn viewport_dimensions(&self) -> [u32; 2] {
+        self.frame.extent
+    }
+}
+
+/// Allows drawing lights in the lighting subpass.
+pub struct LightingPass<'f, 'a> {
+    frame: &'f mut Frame<'a>,
+}
+
+impl<'f, 'a> LightingPass<'f, 'a> {
+    /// Returns the command buffer builder, for example to push the constants of a light before
+    /// it is drawn.
+    #[inline]
+    pub fn builder(&mut self) -> &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer> {
+        self.frame.builder
+    }
+
+    /// Returns a viewport that covers the whole final image.
+    #[inline]
+    pub fn viewport(&self) -> Viewport {
+        self.frame.viewport()
+    }
+
+    /// Returns the width and height of the final image.
+    #[inline]
+    pub fn viewport_dimensions(&self) -> [u32; 2] {
+        self.frame.extent
+    }
+
+    /// Records the drawing of a light with `light`, which must have been created for the lighting
+    /// subpass, for example with [`FrameSystem::lighting_pass`].
+    ///
+    /// The G-buffer images that the fragment shader of `light` reads are bound to the descriptor
+    /// set 0. `descriptor_sets` are bound starting at set number 1.
+    pub fn draw(
+        &mut self,
+        light: &FullscreenPass,
+        descriptor_sets: impl DescriptorSetsCollection,
+    ) -> Result<(), LightingError> {
+        let mut sets = Vec::new();
+
+        if let Some(layout) = light.pipeline().layout().set_layouts().first() {
+            let writes = self
+                .frame
+                .system
+                .g_buffer
+                .iter()
+                .enumerate()
+                .map(|(binding, image)| (binding as u32, image))
+                .filter(|(binding, _)| layout.bindings().contains_key(binding))
+                .map(|(binding, image)| WriteDescriptorSet::image_view(binding, image.clone()));
+            sets.push(PersistentDescriptorSet::new(layout.clone(), writes)?.into());
+        }
+
+        sets.extend(descriptor_sets.into_vec());
+        light.draw(self.frame.builder, self.frame.viewport(), sets)?;
+
+        Ok(())
+    }
+}
+
+/// Error that can happen when creating a `FrameSystem`.
+#[derive(Clone, Debug)]
+pub enum FrameSystemCreationError {
+    RenderPassCreationError(RenderPassCreationError),
+
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl error::Error for FrameSystemCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::RenderPassCreationError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FrameSystemCreationError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::RenderPassCreationError(_) => write!(f, "creating the render pass failed"),
+            Self::ExtensionNotEnabled { extension, reason } => {
+                write!(f, "the extension {} must be enabled: {}", extension, reason)
+            }
+        }
+    }
+}
+
+impl From<RenderPassCreationError> for FrameSystemCreationError {
+    #[inline]
+    fn from(err: RenderPassCreationError) -> Self {
+        Self::RenderPassCreationError(err)
+    }
+}
+
+/// Error that can happen when beginning a frame with [`FrameSystem::frame`].
+#[derive(Clone, Debug)]
+pub enum FrameError {
+    FramebufferCreationError(FramebufferCreationError),
+    ImageCreationError(ImageCreationError),
+    ImageViewCreationError(ImageViewCreationError),
+    RenderPassError(RenderPassError),
+}
+
+impl error::Error for FrameError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::FramebufferCreationError(err) => Some(err),
+            Self::ImageCreationError(err) => Some(err),
+            Self::ImageViewCreationError(err) => Some(err),
+            Self::RenderPassError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for FrameError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::FramebufferCreationError(_) => write!(f, "creating the framebuffer failed"),
+            Self::ImageCreationError(_) => write!(f, "creating a G-buffer image failed"),
+            Self::ImageViewCreationError(_) => {
+                write!(f, "creating the view of a G-buffer image failed")
+            }
+            Self::RenderPassError(_) => write!(f, "beginning the render pass failed"),
+        }
+    }
+}
+
+impl From<FramebufferCreationError> for FrameError {
+    #[inline]
+    fn from(err: FramebufferCreationError) -> Self {
+        Self::FramebufferCreationError(err)
+    }
+}
+
+impl From<ImageCreationError> for FrameError {
+    #[inline]
+    fn from(err: ImageCreationError) -> Self {
+        Self::ImageCreationError(err)
+    }
+}
+
+impl From<ImageViewCreationError> for FrameError {
+    #[inline]
+    fn from(err: ImageViewCreationError) -> Self {
+        Self::ImageViewCreationError(err)
+    }
+}
+
+impl From<RenderPassError> for FrameError {
+    #[inline]
+    fn from(err: RenderPassError) -> Self {
+        Self::RenderPassError(err)
+    }
+}
+
+/// Error that can happen when drawing a light with [`LightingPass::draw`].
+#[derive(Clone, Debug)]
+pub enum LightingError {
+    DescriptorSetCreationError(DescriptorSetCreationError),
+    DrawError(DrawError),
+}
+
+impl error::Error for LightingError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::DescriptorSetCreationError(err) => Some(err),
+            Self::DrawError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for LightingError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::DescriptorSetCreationError(_) => {
+                write!(f, "creating the descriptor set of the G-buffer failed")
+            }
+            Self::DrawError(_) => write!(f, "recording the draw command failed"),
+        }
+    }
+}
+
+impl From<DescriptorSetCreationError> for LightingError {
+    #[inline]
+    fn from(err: DescriptorSetCreationError) -> Self {
+        Self::DescriptorSetCreationError(err)
+    }
+}
+
+impl From<DrawError> for LightingError {
+    #[inline]
+    fn from(err: DrawError) -> Self {
+        Self::DrawError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FrameSystem, FrameSystemCreateInfo, FrameSystemCreationError, Pass};
+    use std::sync::Arc;
+    use vulkano::{
+        command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage, SubpassContents},
+        device::{DeviceOwned, Queue},
+        format::Format,
+        image::{view::ImageView, AttachmentImage, ImageUsage, SampleCount},
+        pipeline::{
+            graphics::{depth_stencil::CompareOp, vertex_input::BuffersDefinition},
+            StateMode,
+        },
+        Version,
+    };
+
+    // Outputs the color of the first image of the G-buffer.
+    mod lighting_fs {
+        vulkano_shaders::shader! {
+            ty: "fragment",
+            src: "
+                #version 450
+
+                layout(input_attachment_index = 0, set = 0, binding = 0)
+                    uniform subpassInput u_diffuse;
+
+                layout(location = 0) out vec4 f_color;
+
+                void main() {
+                    f_color = subpassLoad(u_diffuse);
+                }
+            "
+        }
+    }
+
+    // Places every vertex at the origin.
+    mod vs {
+        vulkano_shaders::shader! {
+            ty: "vertex",
+            src: "
+                #version 450
+
+                void main() {
+                    gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+                }
+            "
+        }
+    }
+
+    // Records a frame of `frame_system` that draws a light with `lighting_fs`, and returns the
+    // passes of the frame.
+    fn record_frame(frame_system: &mut FrameSystem, queue: &Arc<Queue>) -> Vec<&'static str> {
+        let device = frame_system.device().clone();
+        let fragment_shader = lighting_fs::load(device.clone()).unwrap();
+        let light = frame_system
+            .lighting_pass(fragment_shader.entry_point("main").unwrap())
+            .unwrap();
+
+        let target = ImageView::new_default(
+            AttachmentImage::with_usage(
+                device.clone(),
+                [64, 32],
+                Format::R8G8B8A8_UNORM,
+                ImageUsage::none(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        let mut frame = frame_system
+            .frame(&mut builder, target, SubpassContents::Inline)
+            .unwrap();
+        let mut passes = Vec::new();
+
+        while let Some(pass) = frame.next_pass().unwrap() {
+            match pass {
+                Pass::DepthPrepass(_) => passes.push("depth prepass"),
+                Pass::Deferred(draw_pass) => {
+                    assert_eq!(draw_pass.viewport_dimensions(), [64, 32]);
+                    passes.push("deferred");
+                }
+                Pass::Lighting(mut lighting) => {
+                    lighting.draw(&light, ()).unwrap();
+                    passes.push("lighting");
+                }
+            }
+        }
+
+        assert!(frame.next_pass().unwrap().is_none());
+        builder.build().unwrap();
+
+        passes
+    }
+
+    #[test]
+    fn render_frame() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let mut frame_system = FrameSystem::new(
+            device,
+            FrameSystemCreateInfo::output_format(Format::R8G8B8A8_UNORM),
+        )
+        .unwrap();
+
+        // Without a depth pre-pass, the deferred subpass does a regular depth test.
+        assert!(frame_system.depth_prepass_subpass().is_none());
+        let depth = frame_system.deferred_depth_stencil_state().depth.unwrap();
+        assert_eq!(depth.compare_op, StateMode::Fixed(CompareOp::Less));
+
+        assert_eq!(
+            record_frame(&mut frame_system, &queue),
+            ["deferred", "lighting"]
+        );
+    }
+
+    #[test]
+    fn multisampling_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        // Resolving depth images is core in Vulkan 1.2.
+        if device.api_version() >= Version::V1_2 {
+            return;
+        }
+
+        match FrameSystem::new(
+            device,
+            FrameSystemCreateInfo {
+                samples: SampleCount::Sample4,
+                ..FrameSystemCreateInfo::output_format(Format::R8G8B8A8_UNORM)
+            },
+        ) {
+            Err(FrameSystemCreationError::ExtensionNotEnabled {
+                extension: "khr_depth_stencil_resolve",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn render_multisampled_frame() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        if device.api_version() < Version::V1_2 {
+            return;
+        }
+
+        let properties = device.physical_device().properties();
+
+        if !(properties.framebuffer_color_sample_counts.sample4
+            && properties.framebuffer_depth_sample_counts.sample4)
+        {
+            return;
+        }
+
+        let mut frame_system = FrameSystem::new(
+            device,
+            FrameSystemCreateInfo {
+                samples: SampleCount::Sample4,
+                ..FrameSystemCreateInfo::output_format(Format::R8G8B8A8_UNORM)
+            },
+        )
+        .unwrap();
+
+        // The objects are drawn with 4 samples, and the lighting reads the resolved G-buffer.
+        assert_eq!(frame_system.samples(), SampleCount::Sample4);
+        assert_eq!(
+            frame_system.multisample_state().rasterization_samples,
+            SampleCount::Sample4
+        );
+        assert_eq!(
+            frame_system.deferred_subpass().num_samples(),
+            Some(SampleCount::Sample4)
+        );
+        assert_eq!(
+            frame_system.lighting_subpass().num_samples(),
+            Some(SampleCount::Sample1)
+        );
+
+        assert_eq!(
+            record_frame(&mut frame_system, &queue),
+            ["deferred", "lighting"]
+        );
+    }
+
+    #[test]
+    fn depth_prepass() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let mut frame_system = FrameSystem::new(
+            device.clone(),
+            FrameSystemCreateInfo {
+                depth_prepass: true,
+                ..FrameSystemCreateInfo::output_format(Format::R8G8B8A8_UNORM)
+            },
+        )
+        .unwrap();
+
+        let depth_prepass_subpass = frame_system.depth_prepass_subpass().unwrap();
+        assert_eq!(depth_prepass_subpass.index(), 0);
+        assert_eq!(depth_prepass_subpass.num_color_attachments(), 0);
+        assert!(depth_prepass_subpass.has_depth());
+        assert_eq!(frame_system.deferred_subpass().index(), 1);
+        assert_eq!(frame_system.lighting_subpass().index(), 2);
+
+        // The deferred subpass only draws the fragments that passed the depth pre-pass.
+        let depth = frame_system.deferred_depth_stencil_state().depth.unwrap();
+        assert_eq!(depth.compare_op, StateMode::Fixed(CompareOp::Equal));
+        assert_eq!(depth.write_enable, StateMode::Fixed(false));
+
+        let vertex_shader = vs::load(device).unwrap();
+        frame_system
+            .depth_prepass_pipeline(
+                BuffersDefinition::new(),
+                vertex_shader.entry_point("main").unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            record_frame(&mut frame_system, &queue),
+            ["depth prepass", "deferred", "lighting"]
+        );
+    }
+
+    #[test]
+    fn depth_prepass_pipeline_without_prepass() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let frame_system = FrameSystem::new(
+            device.clone(),
+            FrameSystemCreateInfo::output_format(Format::R8G8B8A8_UNORM),
+        )
+        .unwrap();
+
+        let vertex_shader = vs::load(device).unwrap();
+
+        assert_should_panic!("the frame system has no depth pre-pass", {
+            let _ = frame_system.depth_prepass_pipeline(
+                BuffersDefinition::new(),
+                vertex_shader.entry_point("main").unwrap(),
+            );
+        });
+    }
+}
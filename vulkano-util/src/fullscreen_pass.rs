@@ -0,0 +1,562 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Running a fragment shader over a whole image.
+//!
+//! Post-processing effects such as tone mapping, anti-aliasing or bloom are implemented by
+//! running a fragment shader once for each pixel of their target. A [`FullscreenPass`] takes care
+//! of what these passes have in common: it provides the vertex shader, creates the graphics
+//! pipeline, and draws a single triangle that covers the viewport. No vertex buffer is needed.
+//!
+//! The vertex shader passes the coordinates of the pixel in the target, normalized to the
+//! `0.0..1.0` range, to the fragment shader at location 0:
+//!
+//! ```glsl
+//! layout(location = 0) in vec2 tex_coords;
+//! ```
+//!
+//! The inputs of the pass are read either as input attachments, if the pass is a subpass of a
+//! render pass that produces them, or by sampling images, in which case the
+//! [`sampler`](FullscreenPass::sampler) of the pass can be used.
+//!
+//! # Example
+//!
+//! ```
+//! use vulkano::format::Format;
+//! use vulkano_util::fullscreen_pass::{FullscreenPass, FullscreenPassCreateInfo};
+//!
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! # let tone_map_fs: std::sync::Arc<vulkano::shader::ShaderModule> = return;
+//! # let hdr_image: std::sync::Arc<dyn vulkano::image::ImageViewAbstract> = return;
+//! # let swapchain_image: std::sync::Arc<dyn vulkano::image::ImageViewAbstract> = return;
+//! # let mut builder: vulkano::command_buffer::AutoCommandBufferBuilder<vulkano::command_buffer::PrimaryAutoCommandBuffer> = return;
+//! let tone_map = FullscreenPass::new(
+//!     device.clone(),
+//!     tone_map_fs.entry_point("main").unwrap(),
+//!     FullscreenPassCreateInfo::output_format(Format::B8G8R8A8_SRGB),
+//! )
+//! .unwrap();
+//!
+//! // Each frame:
+//! let descriptor_set = tone_map
+//!     .descriptor_set(0, [tone_map.sampled_input(0, hdr_image.clone())])
+//!     .unwrap();
+//! tone_map
+//!     .render(&mut builder, swapchain_image.clone(), descriptor_set)
+//!     .unwrap();
+//! ```
+
+use std::{error, fmt, sync::Arc};
+use vulkano::{
+    command_buffer::{
+        AutoCommandBufferBuilder, DrawError, PrimaryAutoCommandBuffer, RenderPassBeginInfo,
+        RenderPassError, SubpassContents,
+    },
+    descriptor_set::{
+        DescriptorSetCreationError, DescriptorSetsCollection, PersistentDescriptorSet,
+        WriteDescriptorSet,
+    },
+    device::{Device, DeviceOwned},
+    format::Format,
+    image::{ImageViewAbstract, SampleCount},
+    pipeline::{
+        graphics::{
+            color_blend::{AttachmentBlend, ColorBlendState},
+            multisample::MultisampleState,
+            viewport::{Viewport, ViewportState},
+            GraphicsPipelineCreationError,
+        },
+        GraphicsPipeline, Pipeline, PipelineBindPoint,
+    },
+    render_pass::{
+        AttachmentDescription, Framebuffer, FramebufferCreateInfo, FramebufferCreationError,
+        LoadOp, RenderPass, RenderPassBuilder, RenderPassCreationError, StoreOp, Subpass,
+        SubpassBuilder,
+    },
+    sampler::{Sampler, SamplerAddressMode, SamplerCreateInfo, SamplerCreationError},
+    shader::{EntryPoint, ShaderCreationError},
+};
+
+/// A graphics pipeline that runs a fragment shader over its whole target.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug)]
+pub struct FullscreenPass {
+    pipeline: Arc<GraphicsPipeline>,
+    subpass: Subpass,
+    // The render pass created by the `FullscreenPass`, if no subpass was provided.
+    render_pass: Option<Arc<RenderPass>>,
+    sampler: Arc<Sampler>,
+}
+
+impl FullscreenPass {
+    /// Creates a new `FullscreenPass` that runs `fragment_shader`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if both or neither of `create_info.subpass` and `create_info.output_format` are
+    ///   set.
+    /// - Panics if `create_info.load_op` is `LoadOp::Clear`.
+    pub fn new(
+        device: Arc<Device>,
+        fragment_shader: EntryPoint,
+        create_info: FullscreenPassCreateInfo,
+    ) -> Result<FullscreenPass, FullscreenPassCreationError> {
+        let FullscreenPassCreateInfo {
+            subpass,
+            output_format,
+            load_op,
+            blend,
+            _ne: _,
+        } = create_info;
+
+        // The whole target is overwritten, so clearing it first would be wasted work.
+        assert!(load_op != LoadOp::Clear);
+
+        let (subpass, render_pass) = match (subpass, output_format) {
+            (Some(subpass), None) => (subpass, None),
+            (None, Some(format)) => {
+                let render_pass = RenderPassBuilder::new()
+                    .attachment(AttachmentDescription {
+                        format: Some(format),
+                        load_op,
+                        store_op: StoreOp::Store,
+                        ..Default::default()
+                    })
+                    .subpass(SubpassBuilder::new().color_attachment(0))
+                    .build(device.clone())?;
+
+                (render_pass.clone().first_subpass(), Some(render_pass))
+            }
+            _ => panic!("exactly one of `subpass` and `output_format` must be set"),
+        };
+
+        let vertex_shader = vs::load(device.clone())?;
+
+        let color_blend_state = match blend {
+            Some(blend) => ColorBlendState::new(subpass.num_color_attachments()).blend(blend),
+            None => ColorBlendState::new(subpass.num_color_attachments()),
+        };
+
+        let pipeline = GraphicsPipeline::start()
+            .vertex_shader(vertex_shader.entry_point("main").unwrap(), ())
+            .viewport_state(ViewportState::viewport_dynamic_scissor_irrelevant())
+            .fragment_shader(fragment_shader, ())
+            .multisample_state(MultisampleState {
+                rasterization_samples: subpass.num_samples().unwrap_or(SampleCount::Sample1),
+                ..MultisampleState::new()
+            })
+            .color_blend_state(color_blend_state)
+            .render_pass(subpass.clone())
+            .build(device.clone())?;
+
+        let sampler = Sampler::new(
+            device,
+            SamplerCreateInfo {
+                address_mode: [SamplerAddressMode::ClampToEdge; 3],
+                ..SamplerCreateInfo::simple_repeat_linear_no_mipmap()
+            },
+        )?;
+
+        Ok(FullscreenPass {
+            pipeline,
+            subpass,
+            render_pass,
+            sampler,
+        })
+    }
+
+    /// Returns the graphics pipeline of the pass.
+    #[inline]
+    pub fn pipeline(&self) -> &Arc<GraphicsPipeline> {
+        &self.pipeline
+    }
+
+    /// Returns the subpass that the pass is drawn in.
+    #[inline]
+    pub fn subpass(&self) -> &Subpass {
+        &self.subpass
+    }
+
+    /// Returns a sampler with linear filtering that clamps to the edges of images, to sample the
+    /// inputs of the pass with.
+    #[inline]
+    pub fn sampler(&self) -> &Arc<Sampler> {
+        &self.sampler
+    }
+
+    /// Returns a write that binds `image_view` to `binding` as a combined image sampler, using the
+    /// [`sampler`](FullscreenPass::sampler) of the pass.
+    #[inline]
+    pub fn sampled_input(
+        &self,
+        binding: u32,
+        image_view: Arc<dyn ImageViewAbstract>,
+    ) -> WriteDescriptorSet {
+        WriteDescriptorSet::image_view_sampler(binding, image_view, self.sampler.clone())
+    }
+
+    /// Creates a descriptor set for the set number `set` of the pipeline layout.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the pipeline layout does not have a set number `set`.
+    pub fn descriptor_set(
+        &self,
+        set: u32,
+        descriptor_writes: impl IntoIterator<Item = WriteDescriptorSet>,
+    ) -> Result<Arc<PersistentDescriptorSet>, DescriptorSetCreationError> {
+        let layout = self.pipeline.layout().set_layouts()[set as usize].clone();
+        PersistentDescriptorSet::new(layout, descriptor_writes)
+    }
+
+    /// Records the drawing of the pass into `builder`, which must be inside the subpass of the
+    /// pass.
+    ///
+    /// `descriptor_sets` are bound starting at set number 0. The fragment shader is run for every
+    /// pixel of `viewport`.
+    pub fn draw<L, P>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+        viewport: Viewport,
+        descriptor_sets: impl DescriptorSetsCollection,
+    ) -> Result<(), DrawError> {
+        builder
+            .bind_pipeline_graphics(self.pipeline.clone())
+            .set_viewport(0, [viewport]);
+
+        let descriptor_sets = descriptor_sets.into_vec();
+
+        if !descriptor_sets.is_empty() {
+            builder.bind_descriptor_sets(
+                PipelineBindPoint::Graphics,
+                self.pipeline.layout().clone(),
+                0,
+                descriptor_sets,
+            );
+        }
+
+        builder.draw(3, 1, 0, 0)?;
+
+        Ok(())
+    }
+
+    /// Records a render pass that draws the pass over the whole of `target`.
+    ///
+    /// The content of `target` is kept if `load_op` was `LoadOp::Load` when the pass was created,
+    /// so that the pass can blend with it.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the pass was created with a `subpass`.
+    pub fn render(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+        target: Arc<dyn ImageViewAbstract>,
+        descriptor_sets: impl DescriptorSetsCollection,
+    ) -> Result<(), FullscreenPassRenderError> {
+        let render_pass = self
+            .render_pass
+            .as_ref()
+            .expect("the pass must have been created with an `output_format` to be rendered");
+
+        let [width, height] = target.dimensions().width_height();
+        let framebuffer = Framebuffer::new(
+            render_pass.clone(),
+            FramebufferCreateInfo {
+                attachments: vec![target],
+                ..Default::default()
+            },
+        )?;
+
+        builder.begin_render_pass(
+            RenderPassBeginInfo {
+                clear_values: vec![None],
+                ..RenderPassBeginInfo::framebuffer(framebuffer)
+            },
+            SubpassContents::Inline,
+        )?;
+        self.draw(
+            builder,
+            Viewport {
+                origin: [0.0, 0.0],
+                dimensions: [width as f32, height as f32],
+                depth_range: 0.0..1.0,
+            },
+            descriptor_sets,
+        )?;
+        builder.end_render_pass()?;
+
+        Ok(())
+    }
+}
+
+unsafe impl DeviceOwned for FullscreenPass {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.pipeline.device()
+    }
+}
+
+/// Parameters to create a new `FullscreenPass`.
+///
+/// Exactly one of `subpass` and `output_format` must be set.
+#[derive(Clone, Debug)]
+pub struct FullscreenPassCreateInfo {
+    /// The subpass that the pass is drawn in.
+    ///
+    /// This is needed to read input attachments, or to draw the pass in the same render pass as
+    /// other passes. The pass is then recorded with [`FullscreenPass::draw`].
+    ///
+    /// The default value is `None`.
+    pub subpass: Option<Subpass>,
+
+    /// The format of the image that the pass renders to.
+    ///
+    /// If set, the `FullscreenPass` creates a render pass with a single color attachment of this
+    /// format. The pass is then recorded with [`FullscreenPass::render`].
+    ///
+    /// The default value is `None`.
+    pub output_format: Option<Format>,
+
+    /// What happens to the previous content of the target, if the `FullscreenPass` creates the
+    /// render pass. `LoadOp::Clear` is not allowed.
+    ///
+    /// The default value is [`LoadOp::DontCare`].
+    pub load_op: LoadOp,
+
+    /// How the output of the fragment shader is blended with the content of the color attachments
+    /// of the subpass. If `None`, the content is replaced.
+    ///
+    /// The default value is `None`.
+    pub blend: Option<AttachmentBlend>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for FullscreenPassCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            subpass: None,
+            output_format: None,
+            load_op: LoadOp::DontCare,
+            blend: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl FullscreenPassCreateInfo {
+    /// Returns a `FullscreenPassCreateInfo` for a pass that is drawn in `subpass`.
+    #[inline]
+    pub fn subpass(subpass: Subpass) -> Self {
+        Self {
+            subpass: Some(subpass),
+            ..Default::default()
+        }
+    }
+
+    /// Returns a `FullscreenPassCreateInfo` for a pass that renders to images of `format`.
+    #[inline]
+    pub fn output_format(format: Format) -> Self {
+        Self {
+            output_format: Some(format),
+            ..Default::default()
+        }
+    }
+}
+
+/// Error that can happen when creating a `FullscreenPass`.
+#[derive(Clone, Debug)]
+pub enum FullscreenPassCreationError {
+    GraphicsPipelineCreationError(GraphicsPipelineCreationError),
+    RenderPassCreationError(RenderPassCreationError),
+    SamplerCreationError(SamplerCreationError),
+    ShaderCreationError(ShaderCreationError),
+}
+
+impl error::Error for FullscreenPassCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::GraphicsPipelineCreationError(err) => Some(err),
+            Self::RenderPassCreationError(err) => Some(err),
+            Self::SamplerCreationError(err) => Some(err),
+            Self::ShaderCreationError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for FullscreenPassCreationError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::GraphicsPipelineCreationError(_) => write!(f, "creating the pipeline failed"),
+            Self::RenderPassCreationError(_) => write!(f, "creating the render pass failed"),
+            Self::SamplerCreationError(_) => write!(f, "creating the sampler failed"),
+            Self::ShaderCreationError(_) => write!(f, "creating the vertex shader failed"),
+        }
+    }
+}
+
+impl From<GraphicsPipelineCreationError> for FullscreenPassCreationError {
+    #[inline]
+    fn from(err: GraphicsPipelineCreationError) -> Self {
+        Self::GraphicsPipelineCreationError(err)
+    }
+}
+
+impl From<RenderPassCreationError> for FullscreenPassCreationError {
+    #[inline]
+    fn from(err: RenderPassCreationError) -> Self {
+        Self::RenderPassCreationError(err)
+    }
+}
+
+impl From<SamplerCreationError> for FullscreenPassCreationError {
+    #[inline]
+    fn from(err: SamplerCreationError) -> Self {
+        Self::SamplerCreationError(err)
+    }
+}
+
+impl From<ShaderCreationError> for FullscreenPassCreationError {
+    #[inline]
+    fn from(err: ShaderCreationError) -> Self {
+        Self::ShaderCreationError(err)
+    }
+}
+
+/// Error that can happen when recording a `FullscreenPass` with [`FullscreenPass::render`].
+#[derive(Clone, Debug)]
+pub enum FullscreenPassRenderError {
+    DrawError(DrawError),
+    FramebufferCreationError(FramebufferCreationError),
+    RenderPassError(RenderPassError),
+}
+
+impl error::Error for FullscreenPassRenderError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::DrawError(err) => Some(err),
+            Self::FramebufferCreationError(err) => Some(err),
+            Self::RenderPassError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for FullscreenPassRenderError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::DrawError(_) => write!(f, "recording the draw command failed"),
+            Self::FramebufferCreationError(_) => write!(f, "creating the framebuffer failed"),
+            Self::RenderPassError(_) => write!(f, "recording a render pass command failed"),
+        }
+    }
+}
+
+impl From<DrawError> for FullscreenPassRenderError {
+    #[inline]
+    fn from(err: DrawError) -> Self {
+        Self::DrawError(err)
+    }
+}
+
+impl From<FramebufferCreationError> for FullscreenPassRenderError {
+    #[inline]
+    fn from(err: FramebufferCreationError) -> Self {
+        Self::FramebufferCreationError(err)
+    }
+}
+
+impl From<RenderPassError> for FullscreenPassRenderError {
+    #[inline]
+    fn from(err: RenderPassError) -> Self {
+        Self::RenderPassError(err)
+    }
+}
+
+mod vs {
+    vulkano_shaders::shader! {
+        ty: "vertex",
+        src: "
+            #version 450
+
+            layout(location = 0) out vec2 tex_coords;
+
+            void main() {
+                tex_coords = vec2(float((gl_VertexIndex << 1) & 2), float(gl_VertexIndex & 2));
+                gl_Position = vec4(tex_coords * 2.0 - 1.0, 0.0, 1.0);
+            }
+        "
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FullscreenPass, FullscreenPassCreateInfo};
+    use vulkano::{
+        command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
+        format::Format,
+        image::{view::ImageView, AttachmentImage, ImageUsage},
+    };
+
+    mod fs {
+        vulkano_shaders::shader! {
+            ty: "fragment",
+            src: "
+                #version 450
+
+                layout(location = 0) in vec2 tex_coords;
+                layout(location = 0) out vec4 f_color;
+
+                void main() {
+                    f_color = vec4(tex_coords, 0.0, 1.0);
+                }
+            "
+        }
+    }
+
+    #[test]
+    fn render_to_image() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let fragment_shader = fs::load(device.clone()).unwrap();
+
+        let pass = FullscreenPass::new(
+            device.clone(),
+            fragment_shader.entry_point("main").unwrap(),
+            FullscreenPassCreateInfo::output_format(Format::R8G8B8A8_UNORM),
+        )
+        .unwrap();
+
+        let target = ImageView::new_default(
+            AttachmentImage::with_usage(
+                device.clone(),
+                [64, 32],
+                Format::R8G8B8A8_UNORM,
+                ImageUsage::none(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        pass.render(&mut builder, target, ()).unwrap();
+        builder.build().unwrap();
+    }
+}
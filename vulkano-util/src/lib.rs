@@ -0,0 +1,37 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+#![doc(html_logo_url = "https://raw.githubusercontent.com/vulkano-rs/vulkano/master/logo.png")]
+//! Rendering helpers built on top of vulkano.
+//!
+//! Vulkano itself only wraps the Vulkan API. This crate contains higher-level building blocks
+//! that are common to many renderers, such as full-screen passes or render targets. Unlike
+//! vulkano, it can use shaders, which are written in GLSL and compiled with the
+//! [`shader!`](vulkano_shaders::shader) macro.
+
+#[cfg(test)]
+#[macro_use]
+mod tests;
+
+pub mod capture;
+pub mod culling;
+pub mod deferred;
+pub mod fullscreen_pass;
+pub mod output_conversion;
+pub mod render_target;
+pub mod shadow_map;
+pub mod stereo;
+
+/// A helper type for non-exhaustive structs.
+///
+/// This type cannot be constructed outside this crate. Structures with a field of this type can
+/// only be constructed by calling a constructor function or `Default::default()`. This is the
+/// same as [`vulkano::NonExhaustive`], for the structures of this crate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NonExhaustive(pub(crate) ());
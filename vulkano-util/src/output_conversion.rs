@@ -0,0 +1,590 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Converting a linear HDR image to the color space of a swapchain.
+//!
+//! Applications that render in high dynamic range usually do so into an offscreen image with a
+//! floating-point format, whose values are linear and relative to a "paper white" of `1.0`. As the
+//! last pass of a frame, these values must be converted to what the swapchain expects, which
+//! depends on its color space:
+//!
+//! - [`SrgbNonLinear`](ColorSpace::SrgbNonLinear): the values are tone mapped to the `0.0..1.0`
+//!   range and encoded with the sRGB transfer function. The encoding is done by the hardware if
+//!   the format of the swapchain is an sRGB format, and by the shader otherwise.
+//! - [`ExtendedSrgbLinear`](ColorSpace::ExtendedSrgbLinear) (scRGB): the values stay linear, but
+//!   are scaled so that `1.0` is 80 nits. Values above `1.0` are brighter than standard white.
+//! - [`Hdr10St2084`](ColorSpace::Hdr10St2084) (HDR10): the values are converted from the BT.709
+//!   primaries to the BT.2020 primaries, and encoded with the PQ transfer function, for which
+//!   `1.0` is 10000 nits.
+//!
+//! For the HDR color spaces, the paper white of the input is mapped to
+//! [`paper_white`](OutputConversionPassCreateInfo::paper_white) nits.
+//!
+//! An [`OutputConversionPass`] is a [`FullscreenPass`] that does this conversion.
+//!
+//! # Example
+//!
+//! ```
+//! use vulkano_util::output_conversion::{OutputConversionPass, OutputConversionPassCreateInfo};
+//!
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! # let swapchain: std::sync::Arc<vulkano::swapchain::Swapchain<()>> = return;
+//! # let hdr_image: std::sync::Arc<dyn vulkano::image::ImageViewAbstract> = return;
+//! # let swapchain_image: std::sync::Arc<dyn vulkano::image::ImageViewAbstract> = return;
+//! # let mut builder: vulkano::command_buffer::AutoCommandBufferBuilder<vulkano::command_buffer::PrimaryAutoCommandBuffer> = return;
+//! let output_conversion = OutputConversionPass::new(
+//!     device.clone(),
+//!     OutputConversionPassCreateInfo::new(swapchain.image_format(), swapchain.image_color_space()),
+//! )
+//! .unwrap();
+//!
+//! // Each frame, after rendering to `hdr_image`:
+//! output_conversion
+//!     .render(&mut builder, hdr_image.clone(), swapchain_image.clone())
+//!     .unwrap();
+//! ```
+
+use crate::fullscreen_pass::{
+    FullscreenPass, FullscreenPassCreateInfo, FullscreenPassCreationError,
+    FullscreenPassRenderError,
+};
+use std::{error, fmt, sync::Arc};
+use vulkano::{
+    command_buffer::{AutoCommandBufferBuilder, PrimaryAutoCommandBuffer},
+    descriptor_set::{DescriptorSetCreationError, WriteDescriptorSet},
+    device::{Device, DeviceOwned},
+    format::{Format, NumericType},
+    image::ImageViewAbstract,
+    pipeline::Pipeline,
+    shader::ShaderCreationError,
+    swapchain::ColorSpace,
+};
+
+/// A pass that converts a linear HDR image to the color space of a swapchain.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug)]
+pub struct OutputConversionPass {
+    pass: FullscreenPass,
+    format: Format,
+    color_space: ColorSpace,
+    encoding: Encoding,
+    tone_mapping: ToneMapping,
+    paper_white: f32,
+    exposure: f32,
+}
+
+impl OutputConversionPass {
+    /// Creates a new `OutputConversionPass` that renders to images of `create_info.format` in
+    /// `create_info.color_space`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.paper_white` or `create_info.exposure` is not greater than `0.0`.
+    pub fn new(
+        device: Arc<Device>,
+        create_info: OutputConversionPassCreateInfo,
+    ) -> Result<OutputConversionPass, OutputConversionPassCreationError> {
+        let OutputConversionPassCreateInfo {
+            format,
+            color_space,
+            tone_mapping,
+            paper_white,
+            exposure,
+            _ne: _,
+        } = create_info;
+
+        assert!(paper_white > 0.0);
+        assert!(exposure > 0.0);
+
+        let encoding = Encoding::new(format, color_space)
+            .ok_or(OutputConversionPassCreationError::ColorSpaceNotSupported { color_space })?;
+
+        let fragment_shader = fs::load(device.clone())?;
+        let pass = FullscreenPass::new(
+            device,
+            fragment_shader.entry_point("main").unwrap(),
+            FullscreenPassCreateInfo::output_format(format),
+        )?;
+
+        Ok(OutputConversionPass {
+            pass,
+            format,
+            color_space,
+            encoding,
+            tone_mapping,
+            paper_white,
+            exposure,
+        })
+    }
+
+    /// Returns the format of the images that the pass renders to.
+    #[inline]
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the color space that the pass converts to.
+    #[inline]
+    pub fn color_space(&self) -> ColorSpace {
+        self.color_space
+    }
+
+    /// Returns the tone mapping that is applied to the input.
+    #[inline]
+    pub fn tone_mapping(&self) -> ToneMapping {
+        self.tone_mapping
+    }
+
+    /// Sets the tone mapping that is applied to the input.
+    #[inline]
+    pub fn set_tone_mapping(&mut self, tone_mapping: ToneMapping) {
+        self.tone_mapping = tone_mapping;
+    }
+
+    /// Returns the brightness in nits that the paper white of the input is shown with, for the
+    /// HDR color spaces.
+    #[inline]
+    pub fn paper_white(&self) -> f32 {
+        self.paper_white
+    }
+
+    /// Sets the brightness in nits that the paper white of the input is shown with, for the HDR
+    /// color spaces.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `paper_white` is not greater than `0.0`.
+    #[inline]
+    pub fn set_paper_white(&mut self, paper_white: f32) {
+        assert!(paper_white > 0.0);
+        self.paper_white = paper_white;
+    }
+
+    /// Returns the factor that the input is multiplied with before tone mapping.
+    #[inline]
+    pub fn exposure(&self) -> f32 {
+        self.exposure
+    }
+
+    /// Sets the factor that the input is multiplied with before tone mapping.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `exposure` is not greater than `0.0`.
+    #[inline]
+    pub fn set_exposure(&mut self, exposure: f32) {
+        assert!(exposure > 0.0);
+        self.exposure = exposure;
+    }
+
+    /// Returns the underlying `FullscreenPass`.
+    #[inline]
+    pub fn fullscreen_pass(&self) -> &FullscreenPass {
+        &self.pass
+    }
+
+    /// Records a render pass that converts `input` to the color space of the pass, and writes
+    /// the result to the whole of `target`.
+    ///
+    /// `input` is sampled with linear filtering, so it doesn't need to have the same size as
+    /// `target`. It must have been created with the `sampled` usage.
+    pub fn render(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+        input: Arc<dyn ImageViewAbstract>,
+        target: Arc<dyn ImageViewAbstract>,
+    ) -> Result<(), OutputConversionRenderError> {
+        let descriptor_set = self.pass.descriptor_set(
+            0,
+            [
+                WriteDescriptorSet::image_view(0, input),
+                WriteDescriptorSet::sampler(1, self.pass.sampler().clone()),
+            ],
+        )?;
+
+        let scale = match self.encoding {
+            Encoding::Linear | Encoding::Srgb => 1.0,
+            Encoding::ScRgb => self.paper_white / SCRGB_WHITE_NITS,
+            Encoding::Pq => self.paper_white / PQ_MAX_NITS,
+        };
+
+        builder.push_constants(
+            self.pass.pipeline().layout().clone(),
+            0,
+            fs::ty::PushConstants {
+                exposure: self.exposure,
+                scale,
+                tone_mapping: self.tone_mapping as u32,
+                encoding: self.encoding.shader_value(),
+            },
+        );
+        self.pass.render(builder, target, descriptor_set)?;
+
+        Ok(())
+    }
+}
+
+unsafe impl DeviceOwned for OutputConversionPass {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.pass.device()
+    }
+}
+
+/// Parameters to create a new `OutputConversionPass`.
+#[derive(Clone, Debug)]
+pub struct OutputConversionPassCreateInfo {
+    /// The format of the images that the pass renders to, usually the image format of the
+    /// swapchain.
+    ///
+    /// The default value is `Format::B8G8R8A8_SRGB`.
+    pub format: Format,
+
+    /// The color space to convert to, usually the image color space of the swapchain. It must be
+    /// `SrgbNonLinear`, `ExtendedSrgbLinear` or `Hdr10St2084`.
+    ///
+    /// The default value is [`ColorSpace::SrgbNonLinear`].
+    pub color_space: ColorSpace,
+
+    /// The tone mapping that is applied to the input. Tone mapping compresses the values to the
+    /// `0.0..1.0` range, so for the HDR color spaces it limits the output to the paper white.
+    ///
+    /// The default value is [`ToneMapping::Aces`].
+    pub tone_mapping: ToneMapping,
+
+    /// The brightness in nits that the paper white of the input is shown with, for the HDR color
+    /// spaces.
+    ///
+    /// The default value is `200.0`.
+    pub paper_white: f32,
+
+    /// The factor that the input is multiplied with before tone mapping.
+    ///
+    /// The default value is `1.0`.
+    pub exposure: f32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for OutputConversionPassCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            format: Format::B8G8R8A8_SRGB,
+            color_space: ColorSpace::SrgbNonLinear,
+            tone_mapping: ToneMapping::Aces,
+            paper_white: 200.0,
+            exposure: 1.0,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl OutputConversionPassCreateInfo {
+    /// Returns an `OutputConversionPassCreateInfo` for images of `format` in `color_space`.
+    #[inline]
+    pub fn new(format: Format, color_space: ColorSpace) -> Self {
+        Self {
+            format,
+            color_space,
+            ..Default::default()
+        }
+    }
+}
+
+/// The operator that compresses the values of the input to the `0.0..1.0` range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ToneMapping {
+    /// The values are not compressed. Values outside the range of the output are clamped.
+    None = 0,
+
+    /// `x / (1 + x)`, which keeps the dark values mostly unchanged.
+    Reinhard = 1,
+
+    /// An approximation of the filmic curve of the Academy Color Encoding System.
+    Aces = 2,
+}
+
+// How the shader encodes its output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Encoding {
+    // The values are written as they are, and encoded by the hardware for sRGB formats.
+    Linear,
+    // The values are encoded with the sRGB transfer function.
+    Srgb,
+    // The values are scaled for scRGB.
+    ScRgb,
+    // The values are converted to BT.2020 and encoded with the PQ transfer function.
+    Pq,
+}
+
+impl Encoding {
+    fn new(format: Format, color_space: ColorSpace) -> Option<Self> {
+        match color_space {
+            ColorSpace::SrgbNonLinear if format.type_color() == Some(NumericType::SRGB) => {
+                Some(Encoding::Linear)
+            }
+            ColorSpace::SrgbNonLinear => Some(Encoding::Srgb),
+            ColorSpace::ExtendedSrgbLinear => Some(Encoding::ScRgb),
+            ColorSpace::Hdr10St2084 => Some(Encoding::Pq),
+            _ => None,
+        }
+    }
+
+    // Must match the `encoding` values of the shader.
+    fn shader_value(self) -> u32 {
+        match self {
+            Encoding::Linear | Encoding::ScRgb => 0,
+            Encoding::Srgb => 1,
+            Encoding::Pq => 2,
+        }
+    }
+}
+
+// The brightness of a value of 1.0 in scRGB.
+const SCRGB_WHITE_NITS: f32 = 80.0;
+
+// The brightness of a value of 1.0 before PQ encoding.
+const PQ_MAX_NITS: f32 = 10000.0;
+
+/// Error that can happen when creating an `OutputConversionPass`.
+#[derive(Clone, Debug)]
+pub enum OutputConversionPassCreationError {
+    FullscreenPassCreationError(FullscreenPassCreationError),
+    ShaderCreationError(ShaderCreationError),
+
+    /// The color space can't be converted to.
+    ColorSpaceNotSupported {
+        color_space: ColorSpace,
+    },
+}
+
+impl error::Error for OutputConversionPassCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::FullscreenPassCreationError(err) => Some(err),
+            Self::ShaderCreationError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for OutputConversionPassCreationError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::FullscreenPassCreationError(_) => {
+                write!(f, "creating the fullscreen pass failed")
+            }
+            Self::ShaderCreationError(_) => write!(f, "creating the fragment shader failed"),
+            Self::ColorSpaceNotSupported { color_space } => {
+                write!(f, "the color space {:?} can't be converted to", color_space,)
+            }
+        }
+    }
+}
+
+impl From<FullscreenPassCreationError> for OutputConversionPassCreationError {
+    #[inline]
+    fn from(err: FullscreenPassCreationError) -> Self {
+        Self::FullscreenPassCreationError(err)
+    }
+}
+
+impl From<ShaderCreationError> for OutputConversionPassCreationError {
+    #[inline]
+    fn from(err: ShaderCreationError) -> Self {
+        Self::ShaderCreationError(err)
+    }
+}
+
+/// Error that can happen when recording an `OutputConversionPass`.
+#[derive(Clone, Debug)]
+pub enum OutputConversionRenderError {
+    DescriptorSetCreationError(DescriptorSetCreationError),
+    FullscreenPassRenderError(FullscreenPassRenderError),
+}
+
+impl error::Error for OutputConversionRenderError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::DescriptorSetCreationError(err) => Some(err),
+            Self::FullscreenPassRenderError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for OutputConversionRenderError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::DescriptorSetCreationError(_) => {
+                write!(f, "creating the descriptor set failed")
+            }
+            Self::FullscreenPassRenderError(_) => write!(f, "recording the pass failed"),
+        }
+    }
+}
+
+impl From<DescriptorSetCreationError> for OutputConversionRenderError {
+    #[inline]
+    fn from(err: DescriptorSetCreationError) -> Self {
+        Self::DescriptorSetCreationError(err)
+    }
+}
+
+impl From<FullscreenPassRenderError> for OutputConversionRenderError {
+    #[inline]
+    fn from(err: FullscreenPassRenderError) -> Self {
+        Self::FullscreenPassRenderError(err)
+    }
+}
+
+mod fs {
+    vulkano_shaders::shader! {
+        ty: "fragment",
+        src: "
+            #version 450
+
+            layout(location = 0) in vec2 tex_coords;
+            layout(location = 0) out vec4 f_color;
+
+            layout(set = 0, binding = 0) uniform texture2D hdr_input;
+            layout(set = 0, binding = 1) uniform sampler hdr_sampler;
+
+            layout(push_constant) uniform PushConstants {
+                float exposure;
+                float scale;
+                uint tone_mapping;
+                uint encoding;
+            } pc;
+
+            vec3 tone_map(vec3 color) {
+                if (pc.tone_mapping == 1u) {
+                    return color / (1.0 + color);
+                } else if (pc.tone_mapping == 2u) {
+                    return clamp(
+                        (color * (2.51 * color + 0.03)) / (color * (2.43 * color + 0.59) + 0.14),
+                        0.0,
+                        1.0
+                    );
+                } else {
+                    return color;
+                }
+            }
+
+            vec3 srgb_encode(vec3 color) {
+                color = clamp(color, 0.0, 1.0);
+                vec3 low = color * 12.92;
+                vec3 high = 1.055 * pow(color, vec3(1.0 / 2.4)) - 0.055;
+                return mix(low, high, step(vec3(0.0031308), color));
+            }
+
+            vec3 pq_encode(vec3 color) {
+                vec3 p = pow(clamp(color, 0.0, 1.0), vec3(0.1593017578125));
+                return pow((0.8359375 + 18.8515625 * p) / (1.0 + 18.6875 * p), vec3(78.84375));
+            }
+
+            void main() {
+                vec3 hdr = texture(sampler2D(hdr_input, hdr_sampler), tex_coords).rgb;
+                vec3 color = tone_map(max(hdr * pc.exposure, 0.0));
+
+                if (pc.encoding == 1u) {
+                    color = srgb_encode(color);
+                } else if (pc.encoding == 2u) {
+                    mat3 bt709_to_bt2020 = mat3(
+                        0.6274, 0.0691, 0.0164,
+                        0.3293, 0.9195, 0.0880,
+                        0.0433, 0.0114, 0.8956
+                    );
+                    color = pq_encode(bt709_to_bt2020 * color * pc.scale);
+                } else {
+                    color = color * pc.scale;
+                }
+
+                f_color = vec4(color, 1.0);
+            }
+        "
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Encoding, OutputConversionPass, OutputConversionPassCreateInfo};
+    use vulkano::{
+        command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
+        format::Format,
+        image::{view::ImageView, AttachmentImage, ImageUsage},
+        swapchain::ColorSpace,
+    };
+
+    #[test]
+    fn encoding() {
+        assert_eq!(
+            Encoding::new(Format::B8G8R8A8_SRGB, ColorSpace::SrgbNonLinear),
+            Some(Encoding::Linear)
+        );
+        assert_eq!(
+            Encoding::new(Format::B8G8R8A8_UNORM, ColorSpace::SrgbNonLinear),
+            Some(Encoding::Srgb)
+        );
+        assert_eq!(
+            Encoding::new(Format::R16G16B16A16_SFLOAT, ColorSpace::ExtendedSrgbLinear),
+            Some(Encoding::ScRgb)
+        );
+        assert_eq!(
+            Encoding::new(Format::A2B10G10R10_UNORM_PACK32, ColorSpace::Hdr10St2084),
+            Some(Encoding::Pq)
+        );
+        assert_eq!(
+            Encoding::new(Format::B8G8R8A8_UNORM, ColorSpace::DisplayP3NonLinear),
+            None
+        );
+    }
+
+    #[test]
+    fn render_to_image() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pass = OutputConversionPass::new(
+            device.clone(),
+            OutputConversionPassCreateInfo::new(Format::B8G8R8A8_UNORM, ColorSpace::SrgbNonLinear),
+        )
+        .unwrap();
+
+        let input = ImageView::new_default(
+            AttachmentImage::sampled(device.clone(), [64, 32], Format::R16G16B16A16_SFLOAT)
+                .unwrap(),
+        )
+        .unwrap();
+        let target = ImageView::new_default(
+            AttachmentImage::with_usage(
+                device.clone(),
+                [64, 32],
+                Format::B8G8R8A8_UNORM,
+                ImageUsage::none(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        pass.render(&mut builder, input, target).unwrap();
+        builder.build().unwrap();
+    }
+}
@@ -0,0 +1,558 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Rendering to images that are read back by the CPU, without a surface.
+//!
+//! A [`RenderTarget`] owns a color image, an optional depth image, and a render pass and
+//! framebuffer that draw to them. If multisampling is enabled, the scene is drawn to a
+//! multisampled color image that is resolved into the color image at the end of the render pass.
+//!
+//! After the scene has been drawn, the color image can be copied to a buffer that the CPU can
+//! read. This makes it possible to render without a window, for example to compare the output of
+//! a renderer with reference images in automated tests.
+//!
+//! # Example
+//!
+//! ```
+//! use vulkano::{
+//!     command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage, PrimaryCommandBuffer},
+//!     image::SampleCount,
+//!     sync::GpuFuture,
+//! };
+//! use vulkano_util::render_target::{RenderTarget, RenderTargetCreateInfo};
+//!
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+//! let render_target = RenderTarget::new(
+//!     device.clone(),
+//!     RenderTargetCreateInfo {
+//!         samples: SampleCount::Sample4,
+//!         ..RenderTargetCreateInfo::extent([1024, 768])
+//!     },
+//! )
+//! .unwrap();
+//!
+//! // Create the pipelines with `render_target.subpass()`.
+//!
+//! let mut builder = AutoCommandBufferBuilder::primary(
+//!     device.clone(),
+//!     queue.family(),
+//!     CommandBufferUsage::OneTimeSubmit,
+//! )
+//! .unwrap();
+//! render_target
+//!     .record(&mut builder, [0.0, 0.0, 0.0, 1.0].into(), |builder| {
+//!         // Draw the scene.
+//!     })
+//!     .unwrap();
+//! render_target.copy_color_to_buffer(&mut builder).unwrap();
+//!
+//! builder
+//!     .build()
+//!     .unwrap()
+//!     .execute(queue.clone())
+//!     .unwrap()
+//!     .then_signal_fence_and_flush()
+//!     .unwrap()
+//!     .wait(None)
+//!     .unwrap();
+//!
+//! // Tightly packed rows of pixels, in the color format of the target.
+//! let pixels = render_target.read_color().unwrap();
+//! ```
+
+use std::{error, fmt, sync::Arc};
+use vulkano::{
+    buffer::{
+        cpu_access::ReadLockError, Buffer, BufferCreationError, BufferLocation, BufferUsage,
+        Subbuffer,
+    },
+    command_buffer::{
+        AutoCommandBufferBuilder, CopyError, CopyImageToBufferInfo, PrimaryAutoCommandBuffer,
+        RenderPassBeginInfo, RenderPassError, SubpassContents,
+    },
+    device::{Device, DeviceOwned},
+    format::{ClearValue, Format},
+    image::{
+        view::{ImageView, ImageViewCreationError},
+        AttachmentImage, AttachmentImageCreateInfo, ImageCreationError, ImageUsage,
+        ImageViewAbstract, SampleCount,
+    },
+    pipeline::graphics::viewport::Viewport,
+    render_pass::{
+        AttachmentDescription, Framebuffer, FramebufferCreateInfo, FramebufferCreationError,
+        LoadOp, RenderPass, RenderPassBuilder, RenderPassCreationError, StoreOp, Subpass,
+        SubpassBuilder,
+    },
+    DeviceSize,
+};
+
+/// Color and depth images that are rendered to without a surface, and read back by the CPU.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug)]
+pub struct RenderTarget {
+    render_pass: Arc<RenderPass>,
+    framebuffer: Arc<Framebuffer>,
+    // The single-sampled image that holds the final color, resolved if multisampling is used.
+    color_image: Arc<ImageView<AttachmentImage>>,
+    depth_image: Option<Arc<ImageView<AttachmentImage>>>,
+    color_buffer: Arc<Subbuffer<[u8]>>,
+    extent: [u32; 2],
+    samples: SampleCount,
+}
+
+impl RenderTarget {
+    /// Creates a new `RenderTarget`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.extent` contains zero.
+    /// - Panics if `create_info.color_format` does not have a color aspect, or is compressed.
+    /// - Panics if `create_info.depth_format` is `Some` and does not have a depth aspect.
+    pub fn new(
+        device: Arc<Device>,
+        create_info: RenderTargetCreateInfo,
+    ) -> Result<RenderTarget, RenderTargetCreationError> {
+        let RenderTargetCreateInfo {
+            extent,
+            color_format,
+            depth_format,
+            samples,
+            _ne: _,
+        } = create_info;
+
+        assert!(extent[0] != 0 && extent[1] != 0);
+        assert!(
+            color_format.aspects().color && color_format.compression().is_none(),
+            "the color format of a render target must be an uncompressed color format"
+        );
+
+        if let Some(depth_format) = depth_format {
+            assert!(
+                depth_format.aspects().depth,
+                "the depth format of a render target must have a depth aspect"
+            );
+        }
+
+        let multisampled = samples != SampleCount::Sample1;
+        let mut render_pass_builder = RenderPassBuilder::new();
+        let mut subpass_builder = SubpassBuilder::new().color_attachment(0);
+
+        // The multisampled color image only lives during the render pass.
+        if multisampled {
+            render_pass_builder = render_pass_builder
+                .attachment(AttachmentDescription {
+                    format: Some(color_format),
+                    samples,
+                    load_op: LoadOp::Clear,
+                    store_op: StoreOp::DontCare,
+                    ..Default::default()
+                })
+                .attachment(AttachmentDescription {
+                    format: Some(color_format),
+                    load_op: LoadOp::DontCare,
+                    store_op: StoreOp::Store,
+                    ..Default::default()
+                });
+            subpass_builder = subpass_builder.resolve_attachment(1);
+        } else {
+            render_pass_builder = render_pass_builder.attachment(AttachmentDescription {
+                format: Some(color_format),
+                load_op: LoadOp::Clear,
+                store_op: StoreOp::Store,
+                ..Default::default()
+            });
+        }
+
+        if let Some(depth_format) = depth_format {
+            let depth_attachment = if multisampled { 2 } else { 1 };
+            render_pass_builder = render_pass_builder.attachment(AttachmentDescription {
+                format: Some(depth_format),
+                samples,
+                load_op: LoadOp::Clear,
+                store_op: StoreOp::DontCare,
+                stencil_load_op: LoadOp::Clear,
+                stencil_store_op: StoreOp::DontCare,
+                ..Default::default()
+            });
+            subpass_builder = subpass_builder.depth_stencil_attachment(depth_attachment);
+        }
+
+        let render_pass = render_pass_builder
+            .subpass(subpass_builder)
+            .build(device.clone())?;
+
+        let transient_usage = ImageUsage {
+            transient_attachment: true,
+            ..ImageUsage::none()
+        };
+        let mut attachments: Vec<Arc<dyn ImageViewAbstract>> = Vec::new();
+
+        if multisampled {
+            attachments.push(ImageView::new_default(AttachmentImage::with_create_info(
+                device.clone(),
+                AttachmentImageCreateInfo {
+                    samples,
+                    usage: transient_usage,
+                    ..AttachmentImageCreateInfo::dimensions_format(extent, color_format)
+                },
+            )?)?);
+        }
+
+        let color_image = ImageView::new_default(AttachmentImage::with_create_info(
+            device.clone(),
+            AttachmentImageCreateInfo {
+                usage: ImageUsage {
+                    transfer_src: true,
+                    ..ImageUsage::none()
+                },
+                ..AttachmentImageCreateInfo::dimensions_format(extent, color_format)
+            },
+        )?)?;
+        attachments.push(color_image.clone());
+
+        let depth_image = match depth_format {
+            Some(depth_format) => {
+                let depth_image = ImageView::new_default(AttachmentImage::with_create_info(
+                    device.clone(),
+                    AttachmentImageCreateInfo {
+                        samples,
+                        usage: transient_usage,
+                        ..AttachmentImageCreateInfo::dimensions_format(extent, depth_format)
+                    },
+                )?)?;
+                attachments.push(depth_image.clone());
+                Some(depth_image)
+            }
+            None => None,
+        };
+
+        let framebuffer = Framebuffer::new(
+            render_pass.clone(),
+            FramebufferCreateInfo {
+                attachments,
+                ..Default::default()
+            },
+        )?;
+
+        let color_buffer_len =
+            extent[0] as DeviceSize * extent[1] as DeviceSize * color_format.block_size().unwrap();
+        let color_buffer = Buffer::new_slice(
+            device,
+            BufferUsage {
+                transfer_dst: true,
+                ..BufferUsage::none()
+            },
+            BufferLocation::Download,
+            color_buffer_len,
+        )?;
+
+        Ok(RenderTarget {
+            render_pass,
+            framebuffer,
+            color_image,
+            depth_image,
+            color_buffer,
+            extent,
+            samples,
+        })
+    }
+
+    /// Returns the subpass that the scene is drawn in. The pipelines that draw the scene must be
+    /// created for this subpass.
+    #[inline]
+    pub fn subpass(&self) -> Subpass {
+        self.render_pass.clone().first_subpass()
+    }
+
+    /// Returns the width and height of the images.
+    #[inline]
+    pub fn extent(&self) -> [u32; 2] {
+        self.extent
+    }
+
+    /// Returns the number of samples per pixel that the scene is drawn with.
+    #[inline]
+    pub fn samples(&self) -> SampleCount {
+        self.samples
+    }
+
+    /// Returns a viewport that covers the whole target.
+    #[inline]
+    pub fn viewport(&self) -> Viewport {
+        Viewport {
+            origin: [0.0, 0.0],
+            dimensions: [self.extent[0] as f32, self.extent[1] as f32],
+            depth_range: 0.0..1.0,
+        }
+    }
+
+    /// Returns the single-sampled color image, that holds the result of the rendering.
+    #[inline]
+    pub fn color_image(&self) -> &Arc<ImageView<AttachmentImage>> {
+        &self.color_image
+    }
+
+    /// Returns the depth image, if the target has one.
+    #[inline]
+    pub fn depth_image(&self) -> Option<&Arc<ImageView<AttachmentImage>>> {
+        self.depth_image.as_ref()
+    }
+
+    /// Returns the buffer that [`copy_color_to_buffer`](RenderTarget::copy_color_to_buffer)
+    /// copies the color image to.
+    #[inline]
+    pub fn color_buffer(&self) -> &Arc<Subbuffer<[u8]>> {
+        &self.color_buffer
+    }
+
+    /// Records the rendering of a scene into `builder`.
+    ///
+    /// The color image is cleared with `clear_color` and the depth image with a depth of `1.0`.
+    /// The viewport 0 is set to [`viewport`](RenderTarget::viewport), then `draw` is called to
+    /// record the drawing of the scene.
+    pub fn record<F>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+        clear_color: ClearValue,
+        draw: F,
+    ) -> Result<(), RenderPassError>
+    where
+        F: FnOnce(&mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>),
+    {
+        let mut clear_values = vec![Some(clear_color)];
+
+        if self.samples != SampleCount::Sample1 {
+            clear_values.push(None);
+        }
+
+        if let Some(depth_image) = &self.depth_image {
+            let format = depth_image.format().unwrap();
+            clear_values.push(Some(if format.aspects().stencil {
+                ClearValue::DepthStencil((1.0, 0))
+            } else {
+                ClearValue::Depth(1.0)
+            }));
+        }
+
+        builder.begin_render_pass(
+            RenderPassBeginInfo {
+                clear_values,
+                ..RenderPassBeginInfo::framebuffer(self.framebuffer.clone())
+            },
+            SubpassContents::Inline,
+        )?;
+        builder.set_viewport(0, [self.viewport()]);
+        draw(builder);
+        builder.end_render_pass()?;
+
+        Ok(())
+    }
+
+    /// Records a copy of the color image to the [`color_buffer`](RenderTarget::color_buffer).
+    #[inline]
+    pub fn copy_color_to_buffer<L, P>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+    ) -> Result<(), CopyError> {
+        builder.copy_image_to_buffer(CopyImageToBufferInfo::image_buffer(
+            self.color_image.image().clone(),
+            self.color_buffer.clone(),
+        ))?;
+
+        Ok(())
+    }
+
+    /// Returns the content of the color image, as copied by the last execution of
+    /// [`copy_color_to_buffer`](RenderTarget::copy_color_to_buffer).
+    ///
+    /// The rows of pixels are tightly packed, from top to bottom, in the color format.
+    #[inline]
+    pub fn read_color(&self) -> Result<Vec<u8>, ReadLockError> {
+        Ok(self.color_buffer.read()?.to_vec())
+    }
+}
+
+unsafe impl DeviceOwned for RenderTarget {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.render_pass.device()
+    }
+}
+
+/// Parameters to create a new `RenderTarget`.
+#[derive(Clone, Debug)]
+pub struct RenderTargetCreateInfo {
+    /// The width and height of the images.
+    ///
+    /// The default value is `[0, 0]`, which must be overridden.
+    pub extent: [u32; 2],
+
+    /// The format of the color image.
+    ///
+    /// The default value is [`Format::R8G8B8A8_UNORM`].
+    pub color_format: Format,
+
+    /// The format of the depth image, or `None` to render without a depth image.
+    ///
+    /// The default value is `Some(Format::D16_UNORM)`.
+    pub depth_format: Option<Format>,
+
+    /// The number of samples per pixel. If it is not `Sample1`, the scene is drawn to a
+    /// multisampled image that is resolved into the color image.
+    ///
+    /// The default value is [`SampleCount::Sample1`].
+    pub samples: SampleCount,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for RenderTargetCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            extent: [0, 0],
+            color_format: Format::R8G8B8A8_UNORM,
+            depth_format: Some(Format::D16_UNORM),
+            samples: SampleCount::Sample1,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl RenderTargetCreateInfo {
+    /// Returns a `RenderTargetCreateInfo` with the specified `extent`.
+    #[inline]
+    pub fn extent(extent: [u32; 2]) -> Self {
+        Self {
+            extent,
+            ..Default::default()
+        }
+    }
+}
+
+/// Error that can happen when creating a `RenderTarget`.
+#[derive(Clone, Debug)]
+pub enum RenderTargetCreationError {
+    BufferCreationError(BufferCreationError),
+    FramebufferCreationError(FramebufferCreationError),
+    ImageCreationError(ImageCreationError),
+    ImageViewCreationError(ImageViewCreationError),
+    RenderPassCreationError(RenderPassCreationError),
+}
+
+impl error::Error for RenderTargetCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::BufferCreationError(err) => Some(err),
+            Self::FramebufferCreationError(err) => Some(err),
+            Self::ImageCreationError(err) => Some(err),
+            Self::ImageViewCreationError(err) => Some(err),
+            Self::RenderPassCreationError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for RenderTargetCreationError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::BufferCreationError(_) => write!(f, "allocating the buffer failed"),
+            Self::FramebufferCreationError(_) => write!(f, "creating the framebuffer failed"),
+            Self::ImageCreationError(_) => write!(f, "creating an image failed"),
+            Self::ImageViewCreationError(_) => write!(f, "creating an image view failed"),
+            Self::RenderPassCreationError(_) => write!(f, "creating the render pass failed"),
+        }
+    }
+}
+
+impl From<BufferCreationError> for RenderTargetCreationError {
+    #[inline]
+    fn from(err: BufferCreationError) -> Self {
+        Self::BufferCreationError(err)
+    }
+}
+
+impl From<FramebufferCreationError> for RenderTargetCreationError {
+    #[inline]
+    fn from(err: FramebufferCreationError) -> Self {
+        Self::FramebufferCreationError(err)
+    }
+}
+
+impl From<ImageCreationError> for RenderTargetCreationError {
+    #[inline]
+    fn from(err: ImageCreationError) -> Self {
+        Self::ImageCreationError(err)
+    }
+}
+
+impl From<ImageViewCreationError> for RenderTargetCreationError {
+    #[inline]
+    fn from(err: ImageViewCreationError) -> Self {
+        Self::ImageViewCreationError(err)
+    }
+}
+
+impl From<RenderPassCreationError> for RenderTargetCreationError {
+    #[inline]
+    fn from(err: RenderPassCreationError) -> Self {
+        Self::RenderPassCreationError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RenderTarget, RenderTargetCreateInfo};
+    use vulkano::{
+        command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage, PrimaryCommandBuffer},
+        image::SampleCount,
+        sync::GpuFuture,
+    };
+
+    #[test]
+    fn read_back_resolved_clear() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let render_target = RenderTarget::new(
+            device.clone(),
+            RenderTargetCreateInfo {
+                samples: SampleCount::Sample4,
+                ..RenderTargetCreateInfo::extent([8, 4])
+            },
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        render_target
+            .record(&mut builder, [1.0, 0.0, 0.0, 1.0].into(), |_| ())
+            .unwrap();
+        render_target.copy_color_to_buffer(&mut builder).unwrap();
+        builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        let pixels = render_target.read_color().unwrap();
+        assert_eq!(pixels.len(), 8 * 4 * 4);
+        assert!(pixels.chunks(4).all(|pixel| pixel == [255, 0, 0, 255]));
+    }
+}
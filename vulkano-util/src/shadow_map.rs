@@ -0,0 +1,879 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Rendering cascaded shadow maps for directional lights.
+//!
+//! A [`ShadowMap`] owns what is needed to render the depth of a scene from the point of view of a
+//! light, and to sample it from lighting shaders:
+//!
+//! - A depth-only render pass. The pipelines that draw the shadow casters must be created for its
+//!   [`subpass`](ShadowMap::subpass), with a dynamic viewport.
+//! - A depth image that holds one shadow map per cascade, either as the layers of an array image
+//!   or as the tiles of an atlas. See [`ShadowMapLayout`].
+//! - A sampler that performs depth comparison, so that lighting shaders can sample the image with
+//!   a `sampler2DArrayShadow` or a `sampler2DShadow`.
+//!
+//! The view frustum of the camera is split into slices along its depth, one per cascade.
+//! [`ShadowMap::update_cascades`] computes the split depths and, for each cascade, the matrix that
+//! the shadow casters are rendered with, and the matrix that lighting shaders transform world
+//! positions with to sample the shadow map. Matrices are column-major, like the `mat4` type of
+//! GLSL and the matrix types of most math libraries.
+//!
+//! # Example
+//!
+//! ```
+//! use vulkano_util::shadow_map::{CascadeUpdateInfo, ShadowMap, ShadowMapCreateInfo};
+//!
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! # let mut builder: vulkano::command_buffer::AutoCommandBufferBuilder<vulkano::command_buffer::PrimaryAutoCommandBuffer> = return;
+//! # let inverse_view_projection: [[f32; 4]; 4] = return;
+//! let mut shadow_map = ShadowMap::new(device.clone(), ShadowMapCreateInfo::default()).unwrap();
+//!
+//! // Create the depth-only pipelines with `shadow_map.subpass()`.
+//!
+//! // Each frame:
+//! shadow_map.update_cascades(CascadeUpdateInfo {
+//!     camera_inverse_view_projection: inverse_view_projection,
+//!     camera_near: 0.1,
+//!     camera_far: 100.0,
+//!     light_direction: [-0.3, -1.0, 0.2],
+//!     ..Default::default()
+//! });
+//!
+//! shadow_map
+//!     .record(&mut builder, |builder, cascade_index, cascade| {
+//!         // Draw the shadow casters with `cascade.light_view_projection`.
+//!     })
+//!     .unwrap();
+//!
+//! // Bind `shadow_map.descriptor(binding)` in the lighting pass, and upload the
+//! // `texture_matrix` and `split_depth` of each cascade.
+//! ```
+
+use std::{error, fmt, sync::Arc};
+use vulkano::{
+    command_buffer::{
+        AutoCommandBufferBuilder, PrimaryAutoCommandBuffer, RenderPassBeginInfo, RenderPassError,
+        SubpassContents,
+    },
+    descriptor_set::WriteDescriptorSet,
+    device::{Device, DeviceOwned},
+    format::{ClearValue, Format},
+    image::{
+        view::{ImageView, ImageViewCreateInfo, ImageViewCreationError, ImageViewType},
+        AttachmentImage, AttachmentImageCreateInfo, ImageAccess, ImageAspects, ImageCreationError,
+        ImageSubresourceRange, ImageUsage,
+    },
+    pipeline::graphics::{depth_stencil::CompareOp, viewport::Viewport},
+    render_pass::{
+        AttachmentDescription, Framebuffer, FramebufferCreateInfo, FramebufferCreationError,
+        LoadOp, RenderPass, RenderPassBuilder, RenderPassCreationError, StoreOp, Subpass,
+        SubpassBuilder,
+    },
+    sampler::{
+        BorderColor, Filter, Sampler, SamplerAddressMode, SamplerCreateInfo, SamplerCreationError,
+    },
+};
+
+/// A depth image with one shadow map per cascade, and the objects needed to render and sample it.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug)]
+pub struct ShadowMap {
+    render_pass: Arc<RenderPass>,
+    image_view: Arc<ImageView<AttachmentImage>>,
+    // One framebuffer per cascade for `ShadowMapLayout::Array`, a single one for
+    // `ShadowMapLayout::Atlas`.
+    framebuffers: Vec<Arc<Framebuffer>>,
+    sampler: Arc<Sampler>,
+    clear_value: ClearValue,
+    layout: ShadowMapLayout,
+    resolution: u32,
+    cascades: Vec<ShadowCascade>,
+}
+
+impl ShadowMap {
+    /// Creates a new `ShadowMap`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.format` does not have a depth aspect.
+    /// - Panics if `create_info.resolution` or `create_info.num_cascades` is zero.
+    /// - Panics if `create_info.layout` is `ShadowMapLayout::Atlas` and `columns` is zero.
+    pub fn new(
+        device: Arc<Device>,
+        create_info: ShadowMapCreateInfo,
+    ) -> Result<ShadowMap, ShadowMapCreationError> {
+        let ShadowMapCreateInfo {
+            format,
+            resolution,
+            num_cascades,
+            layout,
+            compare_op,
+            filter,
+            _ne: _,
+        } = create_info;
+
+        assert!(
+            format.aspects().depth,
+            "the format of a shadow map must have a depth aspect"
+        );
+        assert!(resolution != 0);
+        assert!(num_cascades != 0);
+
+        let (dimensions, array_layers) = match layout {
+            ShadowMapLayout::Array => ([resolution, resolution], num_cascades),
+            ShadowMapLayout::Atlas { columns } => {
+                assert!(columns != 0);
+                let rows = (num_cascades + columns - 1) / columns;
+                (
+                    [resolution * columns.min(num_cascades), resolution * rows],
+                    1,
+                )
+            }
+        };
+
+        let render_pass = RenderPassBuilder::new()
+            .attachment(AttachmentDescription {
+                format: Some(format),
+                load_op: LoadOp::Clear,
+                store_op: StoreOp::Store,
+                stencil_load_op: LoadOp::DontCare,
+                stencil_store_op: StoreOp::DontCare,
+                ..Default::default()
+            })
+            .subpass(SubpassBuilder::new().depth_stencil_attachment(0))
+            .build(device.clone())?;
+
+        let image = AttachmentImage::with_create_info(
+            device.clone(),
+            AttachmentImageCreateInfo {
+                array_layers,
+                usage: ImageUsage {
+                    sampled: true,
+                    ..ImageUsage::none()
+                },
+                ..AttachmentImageCreateInfo::dimensions_format(dimensions, format)
+            },
+        )?;
+
+        // Only the depth aspect can be sampled.
+        let image_view = ImageView::new(
+            image.clone(),
+            ImageViewCreateInfo {
+                view_type: match layout {
+                    ShadowMapLayout::Array => ImageViewType::Dim2dArray,
+                    ShadowMapLayout::Atlas { .. } => ImageViewType::Dim2d,
+                },
+                subresource_range: ImageSubresourceRange {
+                    aspects: ImageAspects {
+                        depth: true,
+                        ..ImageAspects::none()
+                    },
+                    ..image.subresource_range()
+                },
+                ..ImageViewCreateInfo::from_image(&*image)
+            },
+        )?;
+
+        let framebuffers = (0..array_layers)
+            .map(|array_layer| {
+                let attachment = ImageView::new(
+                    image.clone(),
+                    ImageViewCreateInfo {
+                        view_type: ImageViewType::Dim2d,
+                        subresource_range: ImageSubresourceRange {
+                            array_layers: array_layer..array_layer + 1,
+                            ..image.subresource_range()
+                        },
+                        ..ImageViewCreateInfo::from_image(&*image)
+                    },
+                )?;
+
+                Ok(Framebuffer::new(
+                    render_pass.clone(),
+                    FramebufferCreateInfo {
+                        attachments: vec![attachment],
+                        ..Default::default()
+                    },
+                )?)
+            })
+            .collect::<Result<Vec<_>, ShadowMapCreationError>>()?;
+
+        let sampler = Sampler::new(
+            device,
+            SamplerCreateInfo {
+                mag_filter: filter,
+                min_filter: filter,
+                address_mode: [SamplerAddressMode::ClampToBorder; 3],
+                border_color: BorderColor::FloatOpaqueWhite,
+                compare: Some(compare_op),
+                ..Default::default()
+            },
+        )?;
+
+        let cascades = (0..num_cascades)
+            .map(|cascade_index| {
+                let (array_layer, tile) = match layout {
+                    ShadowMapLayout::Array => (cascade_index, [0, 0]),
+                    ShadowMapLayout::Atlas { columns } => {
+                        (0, [cascade_index % columns, cascade_index / columns])
+                    }
+                };
+
+                ShadowCascade {
+                    light_view_projection: IDENTITY,
+                    texture_matrix: IDENTITY,
+                    split_depth: 0.0,
+                    array_layer,
+                    viewport: Viewport {
+                        origin: [(tile[0] * resolution) as f32, (tile[1] * resolution) as f32],
+                        dimensions: [resolution as f32; 2],
+                        depth_range: 0.0..1.0,
+                    },
+                }
+            })
+            .collect();
+
+        Ok(ShadowMap {
+            render_pass,
+            image_view,
+            framebuffers,
+            sampler,
+            clear_value: if format.aspects().stencil {
+                ClearValue::DepthStencil((1.0, 0))
+            } else {
+                ClearValue::Depth(1.0)
+            },
+            layout,
+            resolution,
+            cascades,
+        })
+    }
+
+    /// Returns the subpass that the shadow casters are drawn in.
+    ///
+    /// The pipelines that draw the shadow casters must be created for this subpass, and must use a
+    /// dynamic viewport.
+    #[inline]
+    pub fn subpass(&self) -> Subpass {
+        Subpass::from(self.render_pass.clone(), 0).unwrap()
+    }
+
+    /// Returns the view of the depth aspect of the shadow map image, that lighting shaders sample.
+    ///
+    /// For [`ShadowMapLayout::Array`], this is a 2D array view with one layer per cascade. For
+    /// [`ShadowMapLayout::Atlas`], this is a 2D view of the whole atlas.
+    #[inline]
+    pub fn image_view(&self) -> &Arc<ImageView<AttachmentImage>> {
+        &self.image_view
+    }
+
+    /// Returns the sampler that lighting shaders sample the shadow map with. Depth comparison is
+    /// enabled, and positions outside of the shadow map are not shadowed.
+    #[inline]
+    pub fn sampler(&self) -> &Arc<Sampler> {
+        &self.sampler
+    }
+
+    /// Returns a descriptor write that binds the shadow map and its sampler as a combined image
+    /// sampler at `binding`.
+    #[inline]
+    pub fn descriptor(&self, binding: u32) -> WriteDescriptorSet {
+        WriteDescriptorSet::image_view_sampler(
+            binding,
+            self.image_view.clone(),
+            self.sampler.clone(),
+        )
+    }
+
+    /// Returns the layout of the cascades in the shadow map image.
+    #[inline]
+    pub fn layout(&self) -> ShadowMapLayout {
+        self.layout
+    }
+
+    /// Returns the width and height in pixels of the shadow map of each cascade.
+    #[inline]
+    pub fn resolution(&self) -> u32 {
+        self.resolution
+    }
+
+    /// Returns the cascades, from the nearest to the camera to the farthest.
+    ///
+    /// The matrices are the identity until [`update_cascades`](ShadowMap::update_cascades) is
+    /// called.
+    #[inline]
+    pub fn cascades(&self) -> &[ShadowCascade] {
+        &self.cascades
+    }
+
+    /// Splits the view frustum of the camera into one slice per cascade, and fits the matrices of
+    /// each cascade around its slice.
+    ///
+    /// The matrices are stabilized: the projection of each cascade has a size that only depends
+    /// on the size of its slice, and is moved in increments of whole texels, so that the edges
+    /// of shadows don't flicker when the camera moves.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `info.camera_near` is not positive, or is not smaller than `info.camera_far`.
+    /// - Panics if `info.light_direction` is zero.
+    pub fn update_cascades(&mut self, info: CascadeUpdateInfo) {
+        let CascadeUpdateInfo {
+            camera_inverse_view_projection,
+            camera_near,
+            camera_far,
+            light_direction,
+            split_lambda,
+            _ne: _,
+        } = info;
+
+        assert!(camera_near > 0.0 && camera_near < camera_far);
+
+        let light_direction = normalize(light_direction);
+        let up = if light_direction[1].abs() > 0.99 {
+            [0.0, 0.0, 1.0]
+        } else {
+            [0.0, 1.0, 0.0]
+        };
+
+        // The corners of the view frustum in world space, at the near and far planes.
+        let corners: Vec<([f32; 3], [f32; 3])> =
+            [[-1.0, -1.0], [1.0, -1.0], [-1.0, 1.0], [1.0, 1.0]]
+                .iter()
+                .map(|&[x, y]| {
+                    (
+                        transform_point(&camera_inverse_view_projection, [x, y, 0.0]),
+                        transform_point(&camera_inverse_view_projection, [x, y, 1.0]),
+                    )
+                })
+                .collect();
+
+        let num_cascades = self.cascades.len();
+        let (atlas_columns, atlas_rows) = match self.layout {
+            ShadowMapLayout::Array => (1, 1),
+            ShadowMapLayout::Atlas { columns } => {
+                let columns = columns.min(num_cascades as u32);
+                (columns, (num_cascades as u32 + columns - 1) / columns)
+            }
+        };
+        let mut slice_near = camera_near;
+
+        for (cascade_index, cascade) in self.cascades.iter_mut().enumerate() {
+            // Blend between a logarithmic and a uniform split scheme.
+            let p = (cascade_index + 1) as f32 / num_cascades as f32;
+            let logarithmic = camera_near * (camera_far / camera_near).powf(p);
+            let uniform = camera_near + (camera_far - camera_near) * p;
+            let slice_far = split_lambda * logarithmic + (1.0 - split_lambda) * uniform;
+
+            // Points along a corner edge of the frustum have a view depth that varies linearly
+            // with their world position.
+            let lerp = |(near, far): &([f32; 3], [f32; 3]), depth: f32| {
+                let t = (depth - camera_near) / (camera_far - camera_near);
+                [
+                    near[0] + (far[0] - near[0]) * t,
+                    near[1] + (far[1] - near[1]) * t,
+                    near[2] + (far[2] - near[2]) * t,
+                ]
+            };
+            let slice_corners: Vec<[f32; 3]> = corners
+                .iter()
+                .flat_map(|edge| [lerp(edge, slice_near), lerp(edge, slice_far)])
+                .collect();
+
+            // Fit a sphere around the slice, so that the projection doesn't change size when the
+            // camera rotates.
+            let mut center = [0.0; 3];
+            for corner in &slice_corners {
+                center = add(center, scale(*corner, 1.0 / slice_corners.len() as f32));
+            }
+            let radius = slice_corners
+                .iter()
+                .map(|&corner| length(sub(corner, center)))
+                .fold(0.0f32, f32::max);
+            let radius = (radius * 16.0).ceil() / 16.0;
+
+            let eye = sub(center, scale(light_direction, radius));
+            let view = look_at(eye, center, up);
+            let mut light_view_projection = mul(
+                &orthographic(-radius, radius, -radius, radius, 0.0, 2.0 * radius),
+                &view,
+            );
+
+            // Snap the origin of the world to a texel of the shadow map.
+            let half_resolution = self.resolution as f32 / 2.0;
+            let origin = transform_point(&light_view_projection, [0.0; 3]);
+            for (axis, value) in light_view_projection[3][..2].iter_mut().enumerate() {
+                let texels = origin[axis] * half_resolution;
+                *value += (texels.round() - texels) / half_resolution;
+            }
+
+            // Map clip space to the texture coordinates of the cascade's part of the image.
+            let (scale_u, scale_v) = (0.5 / atlas_columns as f32, 0.5 / atlas_rows as f32);
+            let (offset_u, offset_v) = match self.layout {
+                ShadowMapLayout::Array => (0.5, 0.5),
+                ShadowMapLayout::Atlas { .. } => {
+                    let column = cascade_index as u32 % atlas_columns;
+                    let row = cascade_index as u32 / atlas_columns;
+                    (
+                        (2 * column + 1) as f32 * scale_u,
+                        (2 * row + 1) as f32 * scale_v,
+                    )
+                }
+            };
+            let clip_to_texture = [
+                [scale_u, 0.0, 0.0, 0.0],
+                [0.0, scale_v, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [offset_u, offset_v, 0.0, 1.0],
+            ];
+
+            cascade.texture_matrix = mul(&clip_to_texture, &light_view_projection);
+            cascade.light_view_projection = light_view_projection;
+            cascade.split_depth = slice_far;
+            slice_near = slice_far;
+        }
+    }
+
+    /// Records the rendering of the shadow maps of all cascades into `builder`.
+    ///
+    /// For each cascade, the shadow map is cleared, the viewport 0 is set to the viewport of the
+    /// cascade, and `draw` is called with the index of the cascade to record the drawing of the
+    /// shadow casters.
+    pub fn record<F>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+        mut draw: F,
+    ) -> Result<(), RenderPassError>
+    where
+        F: FnMut(&mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>, u32, &ShadowCascade),
+    {
+        match self.layout {
+            ShadowMapLayout::Array => {
+                for (cascade_index, cascade) in self.cascades.iter().enumerate() {
+                    self.begin_render_pass(builder, cascade.array_layer)?;
+                    builder.set_viewport(0, [cascade.viewport.clone()]);
+                    draw(builder, cascade_index as u32, cascade);
+                    builder.end_render_pass()?;
+                }
+            }
+            ShadowMapLayout::Atlas { .. } => {
+                self.begin_render_pass(builder, 0)?;
+
+                for (cascade_index, cascade) in self.cascades.iter().enumerate() {
+                    builder.set_viewport(0, [cascade.viewport.clone()]);
+                    draw(builder, cascade_index as u32, cascade);
+                }
+
+                builder.end_render_pass()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Begins the render pass on the framebuffer of `array_layer`, clearing it.
+    fn begin_render_pass(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+        array_layer: u32,
+    ) -> Result<(), RenderPassError> {
+        builder.begin_render_pass(
+            RenderPassBeginInfo {
+                clear_values: vec![Some(self.clear_value)],
+                ..RenderPassBeginInfo::framebuffer(self.framebuffers[array_layer as usize].clone())
+            },
+            SubpassContents::Inline,
+        )?;
+
+        Ok(())
+    }
+}
+
+unsafe impl DeviceOwned for ShadowMap {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.render_pass.device()
+    }
+}
+
+/// Parameters to create a new `ShadowMap`.
+#[derive(Clone, Debug)]
+pub struct ShadowMapCreateInfo {
+    /// The format of the shadow map image.
+    ///
+    /// The format must have a depth aspect. If `filter` is `Linear`, the format must support
+    /// linear filtering.
+    ///
+    /// The default value is [`Format::D16_UNORM`].
+    pub format: Format,
+
+    /// The width and height in pixels of the shadow map of each cascade.
+    ///
+    /// The default value is `2048`.
+    pub resolution: u32,
+
+    /// The number of cascades that the view frustum is split into.
+    ///
+    /// The default value is `4`.
+    pub num_cascades: u32,
+
+    /// How the shadow maps of the cascades are laid out in the image.
+    ///
+    /// The default value is [`ShadowMapLayout::Array`].
+    pub layout: ShadowMapLayout,
+
+    /// The comparison that the sampler performs between the reference depth and the depth in the
+    /// shadow map. A position is lit if the comparison passes.
+    ///
+    /// The default value is [`CompareOp::LessOrEqual`].
+    pub compare_op: CompareOp,
+
+    /// The filter of the sampler. With `Linear`, the results of the comparisons of neighboring
+    /// texels are blended, which softens the edges of shadows.
+    ///
+    /// The default value is [`Filter::Linear`].
+    pub filter: Filter,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for ShadowMapCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            format: Format::D16_UNORM,
+            resolution: 2048,
+            num_cascades: 4,
+            layout: ShadowMapLayout::Array,
+            compare_op: CompareOp::LessOrEqual,
+            filter: Filter::Linear,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// How the shadow maps of the cascades are laid out in the shadow map image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShadowMapLayout {
+    /// Each cascade has its own layer of an array image. Lighting shaders sample it with a
+    /// `sampler2DArrayShadow`, using the index of the cascade as the layer.
+    Array,
+
+    /// The cascades are tiles of a single image, `columns` tiles wide, in row-major order.
+    /// Lighting shaders sample it with a `sampler2DShadow`, and the texture matrix of each
+    /// cascade maps to its tile.
+    Atlas { columns: u32 },
+}
+
+/// Parameters to update the cascades of a `ShadowMap`.
+#[derive(Clone, Debug)]
+pub struct CascadeUpdateInfo {
+    /// The inverse of the view-projection matrix of the camera, which transforms clip space
+    /// positions into world space.
+    ///
+    /// The projection must map the near plane to a depth of `0.0`, and the far plane to a depth
+    /// of `1.0`.
+    ///
+    /// The default value is the identity matrix.
+    pub camera_inverse_view_projection: [[f32; 4]; 4],
+
+    /// The distance of the near plane of the camera.
+    ///
+    /// The default value is `0.1`.
+    pub camera_near: f32,
+
+    /// The distance of the far plane of the camera. Shadows are only rendered up to this
+    /// distance, so it can be smaller than the actual far plane.
+    ///
+    /// The default value is `100.0`.
+    pub camera_far: f32,
+
+    /// The direction that the light travels in, in world space.
+    ///
+    /// The default value is `[0.0, -1.0, 0.0]`.
+    pub light_direction: [f32; 3],
+
+    /// How the split depths are chosen, between `0.0` for splits that are evenly spaced, and
+    /// `1.0` for splits that are spaced logarithmically, which gives more resolution near the
+    /// camera.
+    ///
+    /// The default value is `0.75`.
+    pub split_lambda: f32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for CascadeUpdateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            camera_inverse_view_projection: IDENTITY,
+            camera_near: 0.1,
+            camera_far: 100.0,
+            light_direction: [0.0, -1.0, 0.0],
+            split_lambda: 0.75,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// A cascade of a `ShadowMap`.
+#[derive(Clone, Debug)]
+pub struct ShadowCascade {
+    /// The matrix that transforms world space positions into the clip space of the light, which
+    /// the shadow casters of the cascade are drawn with.
+    pub light_view_projection: [[f32; 4]; 4],
+
+    /// The matrix that transforms world space positions into the texture coordinates of the
+    /// cascade in the shadow map image. The `x` and `y` components are the coordinates to sample,
+    /// and `z` is the reference depth to compare with.
+    pub texture_matrix: [[f32; 4]; 4],
+
+    /// The view depth where the slice of the view frustum that the cascade covers ends. Lighting
+    /// shaders select the first cascade whose split depth is larger than the view depth of the
+    /// shaded position.
+    pub split_depth: f32,
+
+    /// The array layer of the shadow map image that holds the cascade.
+    pub array_layer: u32,
+
+    /// The viewport that covers the cascade in its array layer.
+    pub viewport: Viewport,
+}
+
+/// Error that can happen when creating a `ShadowMap`.
+#[derive(Clone, Debug)]
+pub enum ShadowMapCreationError {
+    FramebufferCreationError(FramebufferCreationError),
+    ImageCreationError(ImageCreationError),
+    ImageViewCreationError(ImageViewCreationError),
+    RenderPassCreationError(RenderPassCreationError),
+    SamplerCreationError(SamplerCreationError),
+}
+
+impl error::Error for ShadowMapCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::FramebufferCreationError(err) => Some(err),
+            Self::ImageCreationError(err) => Some(err),
+            Self::ImageViewCreationError(err) => Some(err),
+            Self::RenderPassCreationError(err) => Some(err),
+            Self::SamplerCreationError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for ShadowMapCreationError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FramebufferCreationError(_) => write!(f, "creating a framebuffer failed"),
+            Self::ImageCreationError(_) => write!(f, "creating the shadow map image failed"),
+            Self::ImageViewCreationError(_) => write!(f, "creating an image view failed"),
+            Self::RenderPassCreationError(_) => write!(f, "creating the render pass failed"),
+            Self::SamplerCreationError(_) => write!(f, "creating the sampler failed"),
+        }
+    }
+}
+
+impl From<FramebufferCreationError> for ShadowMapCreationError {
+    #[inline]
+    fn from(err: FramebufferCreationError) -> Self {
+        Self::FramebufferCreationError(err)
+    }
+}
+
+impl From<ImageCreationError> for ShadowMapCreationError {
+    #[inline]
+    fn from(err: ImageCreationError) -> Self {
+        Self::ImageCreationError(err)
+    }
+}
+
+impl From<ImageViewCreationError> for ShadowMapCreationError {
+    #[inline]
+    fn from(err: ImageViewCreationError) -> Self {
+        Self::ImageViewCreationError(err)
+    }
+}
+
+impl From<RenderPassCreationError> for ShadowMapCreationError {
+    #[inline]
+    fn from(err: RenderPassCreationError) -> Self {
+        Self::RenderPassCreationError(err)
+    }
+}
+
+impl From<SamplerCreationError> for ShadowMapCreationError {
+    #[inline]
+    fn from(err: SamplerCreationError) -> Self {
+        Self::SamplerCreationError(err)
+    }
+}
+
+// Column-major matrix and vector helpers.
+
+const IDENTITY: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+fn mul(a: &[[f32; 4]; 4], b: &[[f32; 4]; 4]) -> [[f32; 4]; 4] {
+    let mut result = [[0.0; 4]; 4];
+
+    for (column, result_column) in result.iter_mut().enumerate() {
+        for (row, value) in result_column.iter_mut().enumerate() {
+            *value = (0..4).map(|k| a[k][row] * b[column][k]).sum();
+        }
+    }
+
+    result
+}
+
+fn transform_point(m: &[[f32; 4]; 4], p: [f32; 3]) -> [f32; 3] {
+    let [x, y, z, w] =
+        [0, 1, 2, 3].map(|row| m[0][row] * p[0] + m[1][row] * p[1] + m[2][row] * p[2] + m[3][row]);
+
+    [x / w, y / w, z / w]
+}
+
+fn add(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: [f32; 3], s: f32) -> [f32; 3] {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn length(a: [f32; 3]) -> f32 {
+    dot(a, a).sqrt()
+}
+
+fn normalize(a: [f32; 3]) -> [f32; 3] {
+    let length = length(a);
+    assert!(length != 0.0);
+    scale(a, 1.0 / length)
+}
+
+// Right-handed view matrix, looking down the negative Z axis.
+fn look_at(eye: [f32; 3], target: [f32; 3], up: [f32; 3]) -> [[f32; 4]; 4] {
+    let f = normalize(sub(target, eye));
+    let s = normalize(cross(f, up));
+    let u = cross(s, f);
+
+    [
+        [s[0], u[0], -f[0], 0.0],
+        [s[1], u[1], -f[1], 0.0],
+        [s[2], u[2], -f[2], 0.0],
+        [-dot(s, eye), -dot(u, eye), dot(f, eye), 1.0],
+    ]
+}
+
+// Orthographic projection for a right-handed view space, with a depth range of `0.0..1.0`.
+fn orthographic(
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+    near: f32,
+    far: f32,
+) -> [[f32; 4]; 4] {
+    [
+        [2.0 / (right - left), 0.0, 0.0, 0.0],
+        [0.0, 2.0 / (top - bottom), 0.0, 0.0],
+        [0.0, 0.0, -1.0 / (far - near), 0.0],
+        [
+            -(right + left) / (right - left),
+            -(top + bottom) / (top - bottom),
+            -near / (far - near),
+            1.0,
+        ],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        transform_point, CascadeUpdateInfo, ShadowMap, ShadowMapCreateInfo, ShadowMapLayout,
+    };
+
+    #[test]
+    fn cascades_cover_frustum() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let mut shadow_map = ShadowMap::new(
+            device,
+            ShadowMapCreateInfo {
+                resolution: 256,
+                num_cascades: 3,
+                layout: ShadowMapLayout::Atlas { columns: 2 },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // A camera at the origin looking down the negative Z axis, with a 90 degree field of
+        // view, near = 1 and far = 10.
+        let (near, far) = (1.0f32, 10.0f32);
+        let inverse_view_projection = [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, (near - far) / (far * near)],
+            [0.0, 0.0, -1.0, 1.0 / near],
+        ];
+        shadow_map.update_cascades(CascadeUpdateInfo {
+            camera_inverse_view_projection: inverse_view_projection,
+            camera_near: near,
+            camera_far: far,
+            ..Default::default()
+        });
+
+        let cascades = shadow_map.cascades();
+        assert_eq!(cascades.len(), 3);
+        assert!((cascades[2].split_depth - far).abs() < 1e-4);
+        assert_eq!(cascades[1].viewport.origin, [256.0, 0.0]);
+        assert_eq!(cascades[2].viewport.origin, [0.0, 256.0]);
+
+        for (index, cascade) in cascades.iter().enumerate() {
+            // A point in the middle of the cascade's slice is inside its tile of the atlas.
+            let slice_near = if index == 0 {
+                near
+            } else {
+                cascades[index - 1].split_depth
+            };
+            let depth = (slice_near + cascade.split_depth) / 2.0;
+            let [u, v, z] = transform_point(&cascade.texture_matrix, [0.0, 0.0, -depth]);
+            let (column, row) = ((index % 2) as f32, (index / 2) as f32);
+            assert!(u > column / 2.0 && u < (column + 1.0) / 2.0);
+            assert!(v > row / 2.0 && v < (row + 1.0) / 2.0);
+            assert!(z > 0.0 && z < 1.0);
+        }
+    }
+}
@@ -0,0 +1,539 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Rendering both eyes of a stereo view at once, for virtual reality.
+//!
+//! A [`StereoRenderTarget`] owns a color image and an optional depth image with two array layers,
+//! one per eye, and a render pass that uses multiview to draw to both layers with a single set of
+//! draw commands. The [`multiview`](vulkano::device::Features::multiview) feature must be enabled
+//! on the device.
+//!
+//! Every draw command is executed once per eye. The shaders read the built-in `gl_ViewIndex`
+//! variable to know which eye they are drawing, which is `0` for the left eye and `1` for the
+//! right eye, and typically use it to select the view and projection matrices of that eye:
+//!
+//! ```glsl
+//! #version 450
+//! #extension GL_EXT_multiview : require
+//!
+//! layout(set = 0, binding = 0) uniform Eyes {
+//!     mat4 view_projection[2];
+//! };
+//!
+//! layout(location = 0) in vec3 position;
+//!
+//! void main() {
+//!     gl_Position = view_projection[gl_ViewIndex] * vec4(position, 1.0);
+//! }
+//! ```
+//!
+//! Both eyes have the same extent, so the same viewport is used for both. After rendering, the
+//! image of each eye can be accessed with [`eye_image`](StereoRenderTarget::eye_image), to be
+//! sampled or copied into the images of a VR compositor.
+//!
+//! # Example
+//!
+//! ```
+//! use vulkano::command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage};
+//! use vulkano_util::stereo::{Eye, StereoRenderTarget, StereoRenderTargetCreateInfo};
+//!
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+//! let stereo_target = StereoRenderTarget::new(
+//!     device.clone(),
+//!     StereoRenderTargetCreateInfo::extent([1440, 1600]),
+//! )
+//! .unwrap();
+//!
+//! // Create the pipelines with `stereo_target.subpass()`.
+//!
+//! let mut builder = AutoCommandBufferBuilder::primary(
+//!     device.clone(),
+//!     queue.family(),
+//!     CommandBufferUsage::OneTimeSubmit,
+//! )
+//! .unwrap();
+//! stereo_target
+//!     .record(&mut builder, [0.0, 0.0, 0.0, 1.0].into(), |builder| {
+//!         // Draw the scene once, it is rendered for both eyes.
+//!     })
+//!     .unwrap();
+//!
+//! let left_eye = stereo_target.eye_image(Eye::Left);
+//! let right_eye = stereo_target.eye_image(Eye::Right);
+//! ```
+
+use std::{error, fmt, sync::Arc};
+use vulkano::{
+    command_buffer::{
+        AutoCommandBufferBuilder, PrimaryAutoCommandBuffer, RenderPassBeginInfo, RenderPassError,
+        SubpassContents,
+    },
+    device::{Device, DeviceOwned},
+    format::{ClearValue, Format},
+    image::{
+        view::{ImageView, ImageViewCreateInfo, ImageViewCreationError, ImageViewType},
+        AttachmentImage, AttachmentImageCreateInfo, ImageCreationError, ImageUsage,
+        ImageViewAbstract, SampleCount,
+    },
+    pipeline::graphics::viewport::Viewport,
+    render_pass::{
+        AttachmentDescription, Framebuffer, FramebufferCreateInfo, FramebufferCreationError,
+        LoadOp, RenderPass, RenderPassBuilder, RenderPassCreationError, StoreOp, Subpass,
+        SubpassBuilder,
+    },
+};
+
+// Each bit is a view, and therefore an array layer of the attachments.
+const VIEW_MASK: u32 = 0b11;
+
+/// One of the two eyes of a stereo view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum Eye {
+    /// The left eye, drawn with a `gl_ViewIndex` of `0` into the array layer 0.
+    Left = 0,
+    /// The right eye, drawn with a `gl_ViewIndex` of `1` into the array layer 1.
+    Right = 1,
+}
+
+/// Color and depth images with one array layer per eye, that are rendered to with multiview.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug)]
+pub struct StereoRenderTarget {
+    render_pass: Arc<RenderPass>,
+    framebuffer: Arc<Framebuffer>,
+    // The single-sampled array image that holds the final color, resolved if multisampling is
+    // used.
+    color_image: Arc<ImageView<AttachmentImage>>,
+    eye_images: [Arc<ImageView<AttachmentImage>>; 2],
+    depth_image: Option<Arc<ImageView<AttachmentImage>>>,
+    extent: [u32; 2],
+    samples: SampleCount,
+}
+
+impl StereoRenderTarget {
+    /// Creates a new `StereoRenderTarget`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.extent` contains zero.
+    /// - Panics if `create_info.color_format` does not have a color aspect.
+    /// - Panics if `create_info.depth_format` is `Some` and does not have a depth aspect.
+    pub fn new(
+        device: Arc<Device>,
+        create_info: StereoRenderTargetCreateInfo,
+    ) -> Result<StereoRenderTarget, StereoRenderTargetCreationError> {
+        let StereoRenderTargetCreateInfo {
+            extent,
+            color_format,
+            depth_format,
+            samples,
+            _ne: _,
+        } = create_info;
+
+        assert!(extent[0] != 0 && extent[1] != 0);
+        assert!(
+            color_format.aspects().color,
+            "the color format of a stereo render target must have a color aspect"
+        );
+
+        if let Some(depth_format) = depth_format {
+            assert!(
+                depth_format.aspects().depth,
+                "the depth format of a stereo render target must have a depth aspect"
+            );
+        }
+
+        let multisampled = samples != SampleCount::Sample1;
+        // The two eyes look at nearly the same scene, which lets the implementation share work
+        // between them.
+        let mut render_pass_builder = RenderPassBuilder::new().correlated_view_mask(VIEW_MASK);
+        let mut subpass_builder = SubpassBuilder::new()
+            .view_mask(VIEW_MASK)
+            .color_attachment(0);
+
+        // The multisampled color image only lives during the render pass.
+        if multisampled {
+            render_pass_builder = render_pass_builder
+                .attachment(AttachmentDescription {
+                    format: Some(color_format),
+                    samples,
+                    load_op: LoadOp::Clear,
+                    store_op: StoreOp::DontCare,
+                    ..Default::default()
+                })
+                .attachment(AttachmentDescription {
+                    format: Some(color_format),
+                    load_op: LoadOp::DontCare,
+                    store_op: StoreOp::Store,
+                    ..Default::default()
+                });
+            subpass_builder = subpass_builder.resolve_attachment(1);
+        } else {
+            render_pass_builder = render_pass_builder.attachment(AttachmentDescription {
+                format: Some(color_format),
+                load_op: LoadOp::Clear,
+                store_op: StoreOp::Store,
+                ..Default::default()
+            });
+        }
+
+        if let Some(depth_format) = depth_format {
+            let depth_attachment = if multisampled { 2 } else { 1 };
+            render_pass_builder = render_pass_builder.attachment(AttachmentDescription {
+                format: Some(depth_format),
+                samples,
+                load_op: LoadOp::Clear,
+                store_op: StoreOp::DontCare,
+                stencil_load_op: LoadOp::Clear,
+                stencil_store_op: StoreOp::DontCare,
+                ..Default::default()
+            });
+            subpass_builder = subpass_builder.depth_stencil_attachment(depth_attachment);
+        }
+
+        let render_pass = render_pass_builder
+            .subpass(subpass_builder)
+            .build(device.clone())?;
+
+        let transient_usage = ImageUsage {
+            transient_attachment: true,
+            ..ImageUsage::none()
+        };
+        let mut attachments: Vec<Arc<dyn ImageViewAbstract>> = Vec::new();
+
+        if multisampled {
+            attachments.push(ImageView::new_default(AttachmentImage::with_create_info(
+                device.clone(),
+                AttachmentImageCreateInfo {
+                    array_layers: 2,
+                    samples,
+                    usage: transient_usage,
+                    ..AttachmentImageCreateInfo::dimensions_format(extent, color_format)
+                },
+            )?)?);
+        }
+
+        let color_attachment_image = AttachmentImage::with_create_info(
+            device.clone(),
+            AttachmentImageCreateInfo {
+                array_layers: 2,
+                usage: ImageUsage {
+                    sampled: true,
+                    transfer_src: true,
+                    ..ImageUsage::none()
+                },
+                ..AttachmentImageCreateInfo::dimensions_format(extent, color_format)
+            },
+        )?;
+        let color_image = ImageView::new_default(color_attachment_image.clone())?;
+        attachments.push(color_image.clone());
+
+        let eye_image = |eye: Eye| {
+            let mut create_info = ImageViewCreateInfo::from_image(&*color_attachment_image);
+            create_info.view_type = ImageViewType::Dim2d;
+            create_info.subresource_range.array_layers = eye as u32..eye as u32 + 1;
+            ImageView::new(color_attachment_image.clone(), create_info)
+        };
+        let eye_images = [eye_image(Eye::Left)?, eye_image(Eye::Right)?];
+
+        let depth_image = match depth_format {
+            Some(depth_format) => {
+                let depth_image = ImageView::new_default(AttachmentImage::with_create_info(
+                    device,
+                    AttachmentImageCreateInfo {
+                        array_layers: 2,
+                        samples,
+                        usage: transient_usage,
+                        ..AttachmentImageCreateInfo::dimensions_format(extent, depth_format)
+                    },
+                )?)?;
+                attachments.push(depth_image.clone());
+                Some(depth_image)
+            }
+            None => None,
+        };
+
+        let framebuffer = Framebuffer::new(
+            render_pass.clone(),
+            FramebufferCreateInfo {
+                attachments,
+                ..Default::default()
+            },
+        )?;
+
+        Ok(StereoRenderTarget {
+            render_pass,
+            framebuffer,
+            color_image,
+            eye_images,
+            depth_image,
+            extent,
+            samples,
+        })
+    }
+
+    /// Returns the subpass that the scene is drawn in. The pipelines that draw the scene must be
+    /// created for this subpass.
+    #[inline]
+    pub fn subpass(&self) -> Subpass {
+        self.render_pass.clone().first_subpass()
+    }
+
+    /// Returns the width and height of the image of each eye.
+    #[inline]
+    pub fn extent(&self) -> [u32; 2] {
+        self.extent
+    }
+
+    /// Returns the number of samples per pixel that the scene is drawn with.
+    #[inline]
+    pub fn samples(&self) -> SampleCount {
+        self.samples
+    }
+
+    /// Returns a viewport that covers the whole image of an eye. It is used for both eyes.
+    #[inline]
+    pub fn viewport(&self) -> Viewport {
+        Viewport {
+            origin: [0.0, 0.0],
+            dimensions: [self.extent[0] as f32, self.extent[1] as f32],
+            depth_range: 0.0..1.0,
+        }
+    }
+
+    /// Returns the single-sampled color image, with the left eye in array layer 0 and the right
+    /// eye in array layer 1.
+    #[inline]
+    pub fn color_image(&self) -> &Arc<ImageView<AttachmentImage>> {
+        &self.color_image
+    }
+
+    /// Returns a view of the color image that only contains the array layer of `eye`.
+    #[inline]
+    pub fn eye_image(&self, eye: Eye) -> &Arc<ImageView<AttachmentImage>> {
+        &self.eye_images[eye as usize]
+    }
+
+    /// Returns the depth image, if the target has one.
+    #[inline]
+    pub fn depth_image(&self) -> Option<&Arc<ImageView<AttachmentImage>>> {
+        self.depth_image.as_ref()
+    }
+
+    /// Records the rendering of a scene for both eyes into `builder`.
+    ///
+    /// The color image is cleared with `clear_color` and the depth image with a depth of `1.0`.
+    /// The viewport 0 is set to [`viewport`](StereoRenderTarget::viewport), then `draw` is called
+    /// to record the drawing of the scene.
+    pub fn record<F>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+        clear_color: ClearValue,
+        draw: F,
+    ) -> Result<(), RenderPassError>
+    where
+        F: FnOnce(&mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>),
+    {
+        let mut clear_values = vec![Some(clear_color)];
+
+        if self.samples != SampleCount::Sample1 {
+            clear_values.push(None);
+        }
+
+        if let Some(depth_image) = &self.depth_image {
+            let format = depth_image.format().unwrap();
+            clear_values.push(Some(if format.aspects().stencil {
+                ClearValue::DepthStencil((1.0, 0))
+            } else {
+                ClearValue::Depth(1.0)
+            }));
+        }
+
+        builder.begin_render_pass(
+            RenderPassBeginInfo {
+                clear_values,
+                ..RenderPassBeginInfo::framebuffer(self.framebuffer.clone())
+            },
+            SubpassContents::Inline,
+        )?;
+        builder.set_viewport(0, [self.viewport()]);
+        draw(builder);
+        builder.end_render_pass()?;
+
+        Ok(())
+    }
+}
+
+unsafe impl DeviceOwned for StereoRenderTarget {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.render_pass.device()
+    }
+}
+
+/// Parameters to create a new `StereoRenderTarget`.
+#[derive(Clone, Debug)]
+pub struct StereoRenderTargetCreateInfo {
+    /// The width and height of the image of each eye.
+    ///
+    /// The default value is `[0, 0]`, which must be overridden.
+    pub extent: [u32; 2],
+
+    /// The format of the color image.
+    ///
+    /// The default value is [`Format::R8G8B8A8_SRGB`].
+    pub color_format: Format,
+
+    /// The format of the depth image, or `None` to render without a depth image.
+    ///
+    /// The default value is `Some(Format::D16_UNORM)`.
+    pub depth_format: Option<Format>,
+
+    /// The number of samples per pixel. If it is not `Sample1`, the scene is drawn to a
+    /// multisampled image that is resolved into the color image.
+    ///
+    /// The default value is [`SampleCount::Sample1`].
+    pub samples: SampleCount,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for StereoRenderTargetCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            extent: [0, 0],
+            color_format: Format::R8G8B8A8_SRGB,
+            depth_format: Some(Format::D16_UNORM),
+            samples: SampleCount::Sample1,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl StereoRenderTargetCreateInfo {
+    /// Returns a `StereoRenderTargetCreateInfo` with the specified `extent`.
+    #[inline]
+    pub fn extent(extent: [u32; 2]) -> Self {
+        Self {
+            extent,
+            ..Default::default()
+        }
+    }
+}
+
+/// Error that can happen when creating a `StereoRenderTarget`.
+#[derive(Clone, Debug)]
+pub enum StereoRenderTargetCreationError {
+    FramebufferCreationError(FramebufferCreationError),
+    ImageCreationError(ImageCreationError),
+    ImageViewCreationError(ImageViewCreationError),
+    RenderPassCreationError(RenderPassCreationError),
+}
+
+impl error::Error for StereoRenderTargetCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::FramebufferCreationError(err) => Some(err),
+            Self::ImageCreationError(err) => Some(err),
+            Self::ImageViewCreationError(err) => Some(err),
+            Self::RenderPassCreationError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for StereoRenderTargetCreationError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::FramebufferCreationError(_) => write!(f, "creating the framebuffer failed"),
+            Self::ImageCreationError(_) => write!(f, "creating an image failed"),
+            Self::ImageViewCreationError(_) => write!(f, "creating an image view failed"),
+            Self::RenderPassCreationError(_) => write!(f, "creating the render pass failed"),
+        }
+    }
+}
+
+impl From<FramebufferCreationError> for StereoRenderTargetCreationError {
+    #[inline]
+    fn from(err: FramebufferCreationError) -> Self {
+        Self::FramebufferCreationError(err)
+    }
+}
+
+impl From<ImageCreationError> for StereoRenderTargetCreationError {
+    #[inline]
+    fn from(err: ImageCreationError) -> Self {
+        Self::ImageCreationError(err)
+    }
+}
+
+impl From<ImageViewCreationError> for StereoRenderTargetCreationError {
+    #[inline]
+    fn from(err: ImageViewCreationError) -> Self {
+        Self::ImageViewCreationError(err)
+    }
+}
+
+impl From<RenderPassCreationError> for StereoRenderTargetCreationError {
+    #[inline]
+    fn from(err: RenderPassCreationError) -> Self {
+        Self::RenderPassCreationError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Eye, StereoRenderTarget, StereoRenderTargetCreateInfo, StereoRenderTargetCreationError,
+    };
+    use vulkano::{
+        image::{ImageViewAbstract, SampleCount},
+        render_pass::RenderPassCreationError,
+    };
+
+    #[test]
+    fn create() {
+        let (device, _) = gfx_dev_and_queue!(multiview);
+
+        let stereo_target = StereoRenderTarget::new(
+            device,
+            StereoRenderTargetCreateInfo {
+                samples: SampleCount::Sample4,
+                ..StereoRenderTargetCreateInfo::extent([16, 16])
+            },
+        )
+        .unwrap();
+
+        assert_eq!(stereo_target.subpass().render_pass().views_used(), 2);
+        assert_eq!(
+            stereo_target
+                .eye_image(Eye::Right)
+                .subresource_range()
+                .array_layers,
+            1..2
+        );
+    }
+
+    #[test]
+    fn multiview_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match StereoRenderTarget::new(device, StereoRenderTargetCreateInfo::extent([16, 16])) {
+            Err(StereoRenderTargetCreationError::RenderPassCreationError(
+                RenderPassCreationError::FeatureNotEnabled { .. },
+            )) => (),
+            _ => panic!(),
+        }
+    }
+}
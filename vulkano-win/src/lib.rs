@@ -15,3 +15,8 @@ pub use crate::raw_window_handle::*;
 mod winit;
 #[cfg(feature = "winit_")]
 pub use crate::winit::*;
+
+#[cfg(feature = "winit_")]
+mod window_renderer;
+#[cfg(feature = "winit_")]
+pub use crate::window_renderer::*;
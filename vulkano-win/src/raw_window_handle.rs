@@ -1,4 +1,4 @@
-use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+use raw_window_handle::HasRawWindowHandle;
 use std::sync::Arc;
 use vulkano::instance::Instance;
 use vulkano::swapchain::Surface;
@@ -9,6 +9,9 @@ use vulkano::swapchain::SurfaceCreationError;
 /// - Note that if you wish to use this function with MacOS, you will need to ensure that the
 /// `CAMetalLayer` is set to the ns_view. An example of how one might do that can be found in
 /// `vulkano_win::set_ca_metal_layer_to_winit`
+///
+/// > **Note**: This is just a shortcut for the `Surface::from_window()` function.
+#[inline]
 pub fn create_surface_from_handle<W>(
     window: W,
     instance: Arc<Instance>,
@@ -16,21 +19,5 @@ pub fn create_surface_from_handle<W>(
 where
     W: HasRawWindowHandle,
 {
-    unsafe {
-        match window.raw_window_handle() {
-            RawWindowHandle::AndroidNdk(h) => {
-                Surface::from_android(instance, h.a_native_window, window)
-            }
-            RawWindowHandle::UiKit(h) => Surface::from_ios(instance, h.ui_view, window),
-            RawWindowHandle::AppKit(h) => Surface::from_mac_os(instance, h.ns_view, window),
-            RawWindowHandle::Wayland(h) => {
-                Surface::from_wayland(instance, h.display, h.surface, window)
-            }
-            RawWindowHandle::Win32(h) => Surface::from_win32(instance, h.hinstance, h.hwnd, window),
-            RawWindowHandle::Xcb(h) => Surface::from_xcb(instance, h.connection, h.window, window),
-            RawWindowHandle::Xlib(h) => Surface::from_xlib(instance, h.display, h.window, window),
-            RawWindowHandle::Web(_) => unimplemented!(),
-            _ => unimplemented!(),
-        }
-    }
+    Surface::from_window(instance, window)
 }
@@ -0,0 +1,523 @@
+use std::collections::hash_map::{HashMap, Iter, IterMut};
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+
+use vulkano::device::{Device, DeviceOwned, Queue};
+use vulkano::image::{ImageView, SwapchainImage};
+use vulkano::swapchain::managed::{ManagedSwapchain, ManagedSwapchainError};
+use vulkano::swapchain::{Surface, SwapchainCreateInfo};
+use vulkano::sync::{self, FlushError, GpuFuture};
+use winit::{
+    event::WindowEvent,
+    event_loop::EventLoopWindowTarget,
+    window::{Window, WindowBuilder, WindowId},
+};
+
+use crate::{create_surface_from_winit, CreationError};
+
+/// The windows of an application that are rendered to with the same device, each with its own
+/// surface, swapchain and state.
+///
+/// `T` is the state that the application keeps for each window, such as its framebuffers or
+/// camera.
+///
+/// ```no_run
+/// use vulkano::image::ImageUsage;
+/// use vulkano::swapchain::SwapchainCreateInfo;
+/// use vulkano_win::WindowRenderers;
+/// use winit::event::{Event, WindowEvent};
+/// use winit::window::WindowBuilder;
+/// # let event_loop: winit::event_loop::EventLoop<()> = return;
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+///
+/// let mut windows = WindowRenderers::new(device);
+/// let swapchain_create_info = SwapchainCreateInfo {
+///     image_usage: ImageUsage::color_attachment(),
+///     ..Default::default()
+/// };
+/// windows
+///     .create_window(&event_loop, WindowBuilder::new(), swapchain_create_info, ())
+///     .unwrap();
+///
+/// event_loop.run(move |event, _, _| match event {
+///     Event::WindowEvent { window_id, event } => {
+///         if let WindowEvent::CloseRequested = event {
+///             windows.remove(window_id);
+///         } else {
+///             windows.handle_window_event(window_id, &event);
+///         }
+///     }
+///     Event::RedrawEventsCleared => {
+///         for (_, renderer) in windows.iter_mut() {
+///             if let Some(acquired) = renderer.acquire().unwrap() {
+///                 // let future = acquired.future.then_execute(...);
+///                 renderer
+///                     .present(queue.clone(), acquired.image_index, acquired.future)
+///                     .unwrap();
+///             }
+///         }
+///     }
+///     _ => (),
+/// });
+/// ```
+pub struct WindowRenderers<T> {
+    device: Arc<Device>,
+    renderers: HashMap<WindowId, WindowRenderer<T>>,
+}
+
+impl<T> WindowRenderers<T> {
+    /// Creates an empty set of windows, that will be rendered to with `device`.
+    #[inline]
+    pub fn new(device: Arc<Device>) -> WindowRenderers<T> {
+        WindowRenderers {
+            device,
+            renderers: HashMap::new(),
+        }
+    }
+
+    /// Returns the device that the windows are rendered to with.
+    #[inline]
+    pub fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+
+    /// Creates a window, with a surface and a swapchain for it.
+    ///
+    /// `swapchain_create_info.image_extent` is replaced with the size of the window.
+    pub fn create_window<E>(
+        &mut self,
+        event_loop: &EventLoopWindowTarget<E>,
+        window_builder: WindowBuilder,
+        swapchain_create_info: SwapchainCreateInfo,
+        state: T,
+    ) -> Result<WindowId, WindowRendererCreationError> {
+        let window = window_builder
+            .build(event_loop)
+            .map_err(CreationError::from)?;
+        let surface = create_surface_from_winit(window, self.device.instance().clone())
+            .map_err(CreationError::from)?;
+        let window_id = surface.window().id();
+
+        let swapchain = ManagedSwapchain::new(
+            self.device.clone(),
+            surface.clone(),
+            SwapchainCreateInfo {
+                image_extent: surface.window().inner_size().into(),
+                ..swapchain_create_info
+            },
+        )?;
+
+        self.renderers.insert(
+            window_id,
+            WindowRenderer {
+                surface,
+                swapchain,
+                previous_frame_end: Some(sync::now(self.device.clone()).boxed()),
+                state,
+            },
+        );
+
+        Ok(window_id)
+    }
+
+    /// Removes a window, and returns it. The window is closed when the returned value is dropped.
+    #[inline]
+    pub fn remove(&mut self, window_id: WindowId) -> Option<WindowRenderer<T>> {
+        self.renderers.remove(&window_id)
+    }
+
+    /// Returns the window with the given ID.
+    #[inline]
+    pub fn get(&self, window_id: WindowId) -> Option<&WindowRenderer<T>> {
+        self.renderers.get(&window_id)
+    }
+
+    /// Returns the window with the given ID.
+    #[inline]
+    pub fn get_mut(&mut self, window_id: WindowId) -> Option<&mut WindowRenderer<T>> {
+        self.renderers.get_mut(&window_id)
+    }
+
+    /// Returns the number of windows.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.renderers.len()
+    }
+
+    /// Returns whether there are no windows.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.renderers.is_empty()
+    }
+
+    /// Iterates over the windows, in an unspecified order.
+    #[inline]
+    pub fn iter(&self) -> Iter<WindowId, WindowRenderer<T>> {
+        self.renderers.iter()
+    }
+
+    /// Iterates over the windows, in an unspecified order. This can be used to render a frame
+    /// to each window.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<WindowId, WindowRenderer<T>> {
+        self.renderers.iter_mut()
+    }
+
+    /// Updates the window that an event was received for. The swapchain of the window is
+    /// recreated when the window is resized.
+    pub fn handle_window_event(&mut self, window_id: WindowId, event: &WindowEvent) {
+        let renderer = match self.renderers.get_mut(&window_id) {
+            Some(renderer) => renderer,
+            None => return,
+        };
+
+        match event {
+            WindowEvent::Resized(size) => renderer.swapchain.resize((*size).into()),
+            WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
+                renderer.swapchain.resize((**new_inner_size).into())
+            }
+            _ => (),
+        }
+    }
+}
+
+/// A window that is rendered to, with its surface, swapchain and state.
+pub struct WindowRenderer<T> {
+    surface: Arc<Surface<Window>>,
+    swapchain: ManagedSwapchain<Window>,
+    previous_frame_end: Option<Box<dyn GpuFuture>>,
+    state: T,
+}
+
+impl<T> WindowRenderer<T> {
+    /// Returns the window.
+    #[inline]
+    pub fn window(&self) -> &Window {
+        self.surface.window()
+    }
+
+    /// Returns the surface of the window.
+    #[inline]
+    pub fn surface(&self) -> &Arc<Surface<Window>> {
+        &self.surface
+    }
+
+    /// Returns the swapchain of the window.
+    #[inline]
+    pub fn swapchain(&self) -> &ManagedSwapchain<Window> {
+        &self.swapchain
+    }
+
+    /// Returns the swapchain of the window.
+    #[inline]
+    pub fn swapchain_mut(&mut self) -> &mut ManagedSwapchain<Window> {
+        &mut self.swapchain
+    }
+
+    /// Returns the views of the images of the swapchain of the window.
+    #[inline]
+    pub fn image_views(&self) -> &[Arc<ImageView<SwapchainImage<Window>>>] {
+        self.swapchain.image_views()
+    }
+
+    /// Returns the state of the window.
+    #[inline]
+    pub fn state(&self) -> &T {
+        &self.state
+    }
+
+    /// Returns the state of the window.
+    #[inline]
+    pub fn state_mut(&mut self) -> &mut T {
+        &mut self.state
+    }
+
+    /// Acquires the next image of the swapchain of the window.
+    ///
+    /// Returns `None` if the window is minimized. See [`ManagedSwapchain::acquire`] for more
+    /// information.
+    pub fn acquire(&mut self) -> Result<Option<AcquiredWindowImage>, ManagedSwapchainError> {
+        if let Some(previous_frame_end) = self.previous_frame_end.as_mut() {
+            previous_frame_end.cleanup_finished();
+        }
+
+        let acquired = match self.swapchain.acquire(None)? {
+            Some(acquired) => acquired,
+            None => return Ok(None),
+        };
+
+        let future = self
+            .previous_frame_end
+            .take()
+            .unwrap()
+            .join(acquired.future)
+            .boxed();
+
+        Ok(Some(AcquiredWindowImage {
+            image_index: acquired.image_index,
+            recreated: acquired.recreated,
+            future,
+        }))
+    }
+
+    /// Presents the image that was acquired with [`acquire`](Self::acquire) on `queue`, after
+    /// `after`, and flushes the future.
+    ///
+    /// The image is presented with the [present mode](ManagedSwapchain::present_mode) of the
+    /// swapchain. If the swapchain is out of date, it is recreated at the next acquire and no
+    /// error is returned.
+    pub fn present(
+        &mut self,
+        queue: Arc<Queue>,
+        image_index: usize,
+        after: Box<dyn GpuFuture>,
+    ) -> Result<(), FlushError> {
+        let swapchain = self.swapchain.swapchain().clone();
+        let present_mode = self.swapchain.present_mode();
+
+        let result = if present_mode == swapchain.present_mode() {
+            after
+                .then_swapchain_present(queue, swapchain, image_index)
+                .then_signal_fence_and_flush()
+                .map(|future| future.boxed())
+        } else {
+            after
+                .then_swapchain_present_with_mode(queue, swapchain, image_index, present_mode)
+                .then_signal_fence_and_flush()
+                .map(|future| future.boxed())
+        };
+
+        match result {
+            Ok(future) => {
+                self.previous_frame_end = Some(future);
+                Ok(())
+            }
+            Err(err) => {
+                self.previous_frame_end =
+                    Some(sync::now(self.swapchain.swapchain().device().clone()).boxed());
+
+                match err {
+                    FlushError::OutOfDate => {
+                        self.swapchain.invalidate();
+                        Ok(())
+                    }
+                    err => Err(err),
+                }
+            }
+        }
+    }
+}
+
+/// An image that was acquired by [`WindowRenderer::acquire`].
+pub struct AcquiredWindowImage {
+    /// The index of the image in [`WindowRenderer::image_views`].
+    pub image_index: usize,
+
+    /// Whether the swapchain was recreated before the image was acquired. If so, the objects in
+    /// the state of the window that were created from the previous images, such as framebuffers,
+    /// must be created again.
+    pub recreated: bool,
+
+    /// The future that represents the moment when the image is available and the previous frame
+    /// of the window has finished.
+    pub future: Box<dyn GpuFuture>,
+}
+
+/// Error that can happen when creating a window with `WindowRenderers`.
+#[derive(Debug)]
+pub enum WindowRendererCreationError {
+    /// Error when creating the window or its surface.
+    CreationError(CreationError),
+    /// Error when creating the swapchain.
+    ManagedSwapchainError(ManagedSwapchainError),
+}
+
+impl error::Error for WindowRendererCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            WindowRendererCreationError::CreationError(ref err) => Some(err),
+            WindowRendererCreationError::ManagedSwapchainError(ref err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for WindowRendererCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            fmt,
+            "{}",
+            match *self {
+                WindowRendererCreationError::CreationError(_) => {
+                    "error while creating the window or its surface"
+                }
+                WindowRendererCreationError::ManagedSwapchainError(_) => {
+                    "error while creating the swapchain"
+                }
+            }
+        )
+    }
+}
+
+impl From<CreationError> for WindowRendererCreationError {
+    #[inline]
+    fn from(err: CreationError) -> WindowRendererCreationError {
+        WindowRendererCreationError::CreationError(err)
+    }
+}
+
+impl From<ManagedSwapchainError> for WindowRendererCreationError {
+    #[inline]
+    fn from(err: ManagedSwapchainError) -> WindowRendererCreationError {
+        WindowRendererCreationError::ManagedSwapchainError(err)
+    }
+}
+
+#[cfg(all(
+    test,
+    any(
+        windows,
+        all(unix, not(target_os = "android"), not(target_os = "macos"))
+    )
+))]
+mod tests {
+    use super::{WindowRendererCreationError, WindowRenderers};
+    use std::panic;
+    use vulkano::device::physical::PhysicalDevice;
+    use vulkano::device::{Device, DeviceCreateInfo, DeviceExtensions, QueueCreateInfo};
+    use vulkano::image::ImageUsage;
+    use vulkano::instance::{Instance, InstanceCreateInfo};
+    use vulkano::swapchain::managed::ManagedSwapchainError;
+    use vulkano::swapchain::{SwapchainCreateInfo, SwapchainCreationError};
+    use winit::dpi::PhysicalSize;
+    use winit::event::WindowEvent;
+    use winit::event_loop::EventLoop;
+    use winit::window::WindowBuilder;
+
+    // Creates an event loop outside of the main thread, or returns `None` if there is no display.
+    fn event_loop() -> Option<EventLoop<()>> {
+        #[cfg(unix)]
+        use winit::platform::unix::EventLoopExtUnix;
+        #[cfg(windows)]
+        use winit::platform::windows::EventLoopExtWindows;
+
+        panic::catch_unwind(EventLoop::<()>::new_any_thread).ok()
+    }
+
+    // All the windows are created in a single test, because winit doesn't support multiple event
+    // loops at the same time.
+    #[test]
+    fn window_renderers() {
+        let event_loop = match event_loop() {
+            Some(e) => e,
+            None => return,
+        };
+        let instance = match Instance::new(InstanceCreateInfo {
+            enabled_extensions: crate::required_extensions(),
+            ..Default::default()
+        }) {
+            Ok(i) => i,
+            Err(_) => return,
+        };
+        let (physical_device, queue_family) = match PhysicalDevice::enumerate(&instance)
+            .filter(|p| p.supported_extensions().khr_swapchain)
+            .find_map(|p| {
+                p.queue_families()
+                    .find(|q| q.supports_graphics())
+                    .map(|q| (p, q))
+            }) {
+            Some(x) => x,
+            None => return,
+        };
+        let create_device = |enabled_extensions| {
+            Device::new(
+                physical_device,
+                DeviceCreateInfo {
+                    enabled_extensions,
+                    queue_create_infos: vec![QueueCreateInfo::family(queue_family)],
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+        let window_builder = || {
+            WindowBuilder::new()
+                .with_visible(false)
+                .with_inner_size(PhysicalSize::new(64, 64))
+        };
+        let swapchain_create_info = || SwapchainCreateInfo {
+            image_usage: ImageUsage::color_attachment(),
+            ..Default::default()
+        };
+
+        // The swapchain can't be created without the `khr_swapchain` extension.
+        let (device, _) = create_device(DeviceExtensions::none());
+        let mut windows = WindowRenderers::new(device);
+
+        match windows.create_window(&event_loop, window_builder(), swapchain_create_info(), ()) {
+            Err(WindowRendererCreationError::ManagedSwapchainError(
+                ManagedSwapchainError::SwapchainCreationError(
+                    SwapchainCreationError::ExtensionNotEnabled {
+                        extension: "khr_swapchain",
+                        ..
+                    },
+                ),
+            )) => (),
+            _ => panic!(),
+        }
+        assert!(windows.is_empty());
+
+        let (device, mut queues) = create_device(DeviceExtensions {
+            khr_swapchain: true,
+            ..DeviceExtensions::none()
+        });
+        let queue = queues.next().unwrap();
+        let mut windows = WindowRenderers::new(device);
+
+        let window_id = match windows.create_window(
+            &event_loop,
+            window_builder(),
+            swapchain_create_info(),
+            0u32,
+        ) {
+            Ok(id) => id,
+            // The surface may not support the default parameters of the swapchain, such as the
+            // composite alpha.
+            Err(WindowRendererCreationError::ManagedSwapchainError(_)) => return,
+            Err(err) => panic!("{}", err),
+        };
+        assert_eq!(windows.len(), 1);
+
+        let renderer = windows.get_mut(window_id).unwrap();
+        assert_eq!(renderer.window().id(), window_id);
+        assert_eq!(
+            renderer.image_views().len(),
+            renderer.swapchain().images().len()
+        );
+        *renderer.state_mut() += 1;
+
+        windows.handle_window_event(window_id, &WindowEvent::Resized(PhysicalSize::new(32, 48)));
+
+        let renderer = windows.get_mut(window_id).unwrap();
+        assert_eq!(*renderer.state(), 1);
+
+        if !queue_family.supports_surface(renderer.surface()).unwrap() {
+            return;
+        }
+
+        if let Some(acquired) = renderer.acquire().unwrap() {
+            assert!(acquired.recreated);
+            renderer
+                .present(queue, acquired.image_index, acquired.future)
+                .unwrap();
+        }
+
+        let renderer = windows.remove(window_id).unwrap();
+        assert_eq!(renderer.window().id(), window_id);
+        assert!(windows.get(window_id).is_none());
+        assert!(windows.is_empty());
+    }
+}
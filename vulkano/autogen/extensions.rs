@@ -172,6 +172,13 @@ fn device_extensions_output(members: &[ExtensionsMember]) -> TokenStream {
         }
     });
 
+    let disable_items = members.iter().map(|ExtensionsMember { name, .. }| {
+        let name_string = name.to_string();
+        quote! {
+            #name_string => &mut self.#name,
+        }
+    });
+
     let required_if_supported_extensions_items = members.iter().map(
         |ExtensionsMember {
              name,
@@ -200,6 +207,17 @@ fn device_extensions_output(members: &[ExtensionsMember]) -> TokenStream {
                 Ok(())
             }
 
+            /// Disables the extension with the given name, as in
+            /// `ExtensionRestrictionError::extension`. Returns whether it was enabled.
+            pub(crate) fn disable(&mut self, name: &str) -> bool {
+                let enabled = match name {
+                    #(#disable_items)*
+                    _ => return false,
+                };
+
+                std::mem::take(enabled)
+            }
+
             pub(crate) fn required_if_supported_extensions() -> Self {
                 Self {
                     #(#required_if_supported_extensions_items)*
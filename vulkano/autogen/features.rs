@@ -26,6 +26,7 @@ fn requires_features(name: &str) -> &'static [&'static str] {
         "sparseImageFloat32Atomics" => &["shaderImageFloat32Atomics"],
         "sparseImageFloat32AtomicAdd" => &["shaderImageFloat32AtomicAdd"],
         "sparseImageFloat32AtomicMinMax" => &["shaderImageFloat32AtomicMinMax"],
+        "robustBufferAccess2" => &["robustBufferAccess"],
         _ => &[],
     }
 }
@@ -63,7 +64,7 @@ fn required_by_extensions(name: &str) -> &'static [(&'static str, &'static str)]
 }
 
 pub fn write(vk_data: &VkRegistryData) {
-    let features_output = features_output(&features_members(&vk_data.types));
+    let features_output = features_output(&features_members(&vk_data.types, &vk_data.extensions));
     let features_ffi_output =
         features_ffi_output(&features_ffi_members(&vk_data.types, &vk_data.extensions));
     write_file(
@@ -88,9 +89,18 @@ struct FeaturesMember {
     requires_features: Vec<Ident>,
     conflicts_features: Vec<Ident>,
     required_by_extensions: Vec<(Ident, Ident)>,
+    provided_by: Vec<FeatureProvidedBy>,
     optional: bool,
 }
 
+// A Vulkan version or extension that provides a feature structure that contains a feature.
+#[derive(Clone, Debug, PartialEq)]
+enum FeatureProvidedBy {
+    Version(u32, u32),
+    DeviceExtension(Ident),
+    InstanceExtension(Ident),
+}
+
 fn features_output(members: &[FeaturesMember]) -> TokenStream {
     let struct_items = members.iter().map(|FeaturesMember { name, doc, .. }| {
         quote! {
@@ -160,6 +170,72 @@ fn features_output(members: &[FeaturesMember]) -> TokenStream {
         },
     );
 
+    let provided_items = members.iter().map(
+        |FeaturesMember {
+             name, provided_by, ..
+         }| {
+            let provided_by_items = provided_by_items(provided_by);
+            quote! {
+                #name: #(#provided_by_items)||*,
+            }
+        },
+    );
+
+    let unavailable_items = members.iter().map(
+        |FeaturesMember {
+             name, provided_by, ..
+         }| {
+            let name_string = name.to_string();
+            let provided_by_items = provided_by_items(provided_by);
+            // Only the lowest version matters.
+            let api_version_items = provided_by
+                .iter()
+                .filter_map(|provided_by| match provided_by {
+                    FeatureProvidedBy::Version(major, minor) => Some((*major, *minor)),
+                    _ => None,
+                })
+                .min()
+                .map(|(major, minor)| {
+                    let version = format_ident!("V{}_{}", major, minor);
+                    quote! { Some(Version::#version) }
+                })
+                .unwrap_or_else(|| quote! { None });
+            let device_extensions_items =
+                provided_by
+                    .iter()
+                    .filter_map(|provided_by| match provided_by {
+                        FeatureProvidedBy::DeviceExtension(ext) => Some(ext.to_string()),
+                        _ => None,
+                    });
+            let instance_extensions_items =
+                provided_by
+                    .iter()
+                    .filter_map(|provided_by| match provided_by {
+                        FeatureProvidedBy::InstanceExtension(ext) => Some(ext.to_string()),
+                        _ => None,
+                    });
+            quote! {
+                if self.#name {
+                    if !supported.#name {
+                        unavailable.push(FeatureRestrictionError {
+                            feature: #name_string,
+                            restriction: FeatureRestriction::NotSupported,
+                        });
+                    } else if !(#(#provided_by_items)||*) {
+                        unavailable.push(FeatureRestrictionError {
+                            feature: #name_string,
+                            restriction: FeatureRestriction::Requires(OneOfRequirements {
+                                api_version: #api_version_items,
+                                device_extensions: &[#(#device_extensions_items),*],
+                                instance_extensions: &[#(#instance_extensions_items),*],
+                            }),
+                        });
+                    }
+                }
+            }
+        },
+    );
+
     let none_items = members.iter().map(|FeaturesMember { name, .. }| {
         quote! {
             #name: false,
@@ -306,6 +382,34 @@ fn features_output(members: &[FeaturesMember]) -> TokenStream {
                 Ok(())
             }
 
+            /// Returns the features whose feature structure is provided by the given device
+            /// version and extensions. They can be enabled if they are also supported.
+            pub(crate) fn provided(
+                api_version: Version,
+                device_extensions: &DeviceExtensions,
+                instance_extensions: &InstanceExtensions,
+            ) -> Features {
+                Features {
+                    #(#provided_items)*
+                    _ne: crate::NonExhaustive(()),
+                }
+            }
+
+            /// Returns why each feature of `self` that can't be enabled isn't available, either
+            /// because it isn't supported, or because its feature structure isn't provided by the
+            /// given device version and extensions.
+            pub(crate) fn unavailable(
+                &self,
+                supported: &Features,
+                api_version: Version,
+                device_extensions: &DeviceExtensions,
+                instance_extensions: &InstanceExtensions,
+            ) -> Vec<FeatureRestrictionError> {
+                let mut unavailable = Vec::new();
+                #(#unavailable_items)*
+                unavailable
+            }
+
             /// Builds a `Features` object with all values to false.
             pub const fn none() -> Features {
                 Features {
@@ -373,7 +477,24 @@ fn features_output(members: &[FeaturesMember]) -> TokenStream {
     }
 }
 
-fn features_members(types: &HashMap<&str, (&Type, Vec<&str>)>) -> Vec<FeaturesMember> {
+fn provided_by_items(provided_by: &[FeatureProvidedBy]) -> Vec<TokenStream> {
+    provided_by
+        .iter()
+        .map(|provided_by| match provided_by {
+            FeatureProvidedBy::Version(major, minor) => {
+                let version = format_ident!("V{}_{}", major, minor);
+                quote! { api_version >= Version::#version }
+            }
+            FeatureProvidedBy::DeviceExtension(ext) => quote! { device_extensions.#ext },
+            FeatureProvidedBy::InstanceExtension(ext) => quote! { instance_extensions.#ext },
+        })
+        .collect()
+}
+
+fn features_members(
+    types: &HashMap<&str, (&Type, Vec<&str>)>,
+    extensions: &IndexMap<&str, &Extension>,
+) -> Vec<FeaturesMember> {
     let mut features = HashMap::new();
     std::iter::once(&types["VkPhysicalDeviceFeatures"])
         .chain(sorted_structs(types).into_iter())
@@ -382,9 +503,39 @@ fn features_members(types: &HashMap<&str, (&Type, Vec<&str>)>) -> Vec<FeaturesMe
                 || ty.structextends.as_ref().map(|s| s.as_str())
                     == Some("VkPhysicalDeviceFeatures2,VkDeviceCreateInfo")
         })
-        .for_each(|(ty, _)| {
+        .for_each(|(ty, provided_by)| {
             let vulkan_ty_name = ty.name.as_ref().unwrap();
 
+            let provided_by: Vec<_> = if vulkan_ty_name == "VkPhysicalDeviceFeatures" {
+                vec![FeatureProvidedBy::Version(1, 0)]
+            } else {
+                provided_by
+                    .iter()
+                    .map(|provided_by| {
+                        if let Some(version) = provided_by.strip_prefix("VK_VERSION_") {
+                            let (major, minor) = version.split_once('_').unwrap();
+                            FeatureProvidedBy::Version(
+                                major.parse().unwrap(),
+                                minor.parse().unwrap(),
+                            )
+                        } else {
+                            let name = format_ident!(
+                                "{}",
+                                provided_by
+                                    .strip_prefix("VK_")
+                                    .unwrap()
+                                    .to_ascii_lowercase(),
+                            );
+
+                            match extensions[provided_by].ext_type.as_ref().unwrap().as_str() {
+                                "instance" => FeatureProvidedBy::InstanceExtension(name),
+                                _ => FeatureProvidedBy::DeviceExtension(name),
+                            }
+                        }
+                    })
+                    .collect()
+            };
+
             let (ty_name, optional) = if vulkan_ty_name == "VkPhysicalDeviceFeatures" {
                 (
                     (format_ident!("features_vulkan10"), quote! { .features }),
@@ -429,13 +580,21 @@ fn features_members(types: &HashMap<&str, (&Type, Vec<&str>)>) -> Vec<FeaturesMe
                                     (version, name)
                                 })
                                 .collect(),
+                            provided_by: provided_by.clone(),
                             optional,
                         };
                         make_doc(&mut member, vulkan_ty_name);
                         entry.insert(member);
                     }
                     Entry::Occupied(entry) => {
-                        entry.into_mut().ffi_members.push(ty_name.clone());
+                        let member = entry.into_mut();
+                        member.ffi_members.push(ty_name.clone());
+
+                        for provided_by in &provided_by {
+                            if !member.provided_by.contains(provided_by) {
+                                member.provided_by.push(provided_by.clone());
+                            }
+                        }
                     }
                 };
             });
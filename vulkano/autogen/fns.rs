@@ -21,7 +21,10 @@ pub fn write(vk_data: &VkRegistryData) {
         "Raw Vulkan global entry point-level functions.\n\nTo use these, you need to include the Ash crate, using the same version Vulkano uses.",
     );
     let instance_fns_output = fns_output(
-        &extension_fns_members("instance", &vk_data.extensions),
+        &extension_fns_members("instance", &vk_data.extensions)
+            .into_iter()
+            .chain(physical_device_fns_members(&vk_data.extensions))
+            .collect::<Vec<_>>(),
         "Instance",
         "Raw Vulkan instance-level functions.\n\nTo use these, you need to include the Ash crate, using the same version Vulkano uses.",
     );
@@ -121,3 +124,33 @@ fn extension_fns_members(ty: &str, extensions: &IndexMap<&str, &Extension>) -> V
         })
         .collect()
 }
+
+// Device extensions can have commands that are called on a physical device. These are loaded with
+// the instance, because there's no device yet when they are called.
+fn physical_device_fns_members(extensions: &IndexMap<&str, &Extension>) -> Vec<FnsMember> {
+    extensions
+        .values()
+        .filter(|ext| ext.ext_type.as_ref().unwrap() == "device")
+        .filter(|ext| {
+            ext.children.iter().any(|ch| {
+                if let ExtensionChild::Require { items, .. } = ch {
+                    items.iter().any(|i| match i {
+                        InterfaceItem::Command { name, .. } => {
+                            name.starts_with("vkGetPhysicalDevice")
+                                || name.starts_with("vkEnumeratePhysicalDevice")
+                        }
+                        _ => false,
+                    })
+                } else {
+                    false
+                }
+            })
+        })
+        .map(|ext| {
+            let base = ext.name.strip_prefix("VK_").unwrap().to_snake_case();
+            let name = format_ident!("{}", base);
+            let fn_struct = format_ident!("{}Fn", base.to_upper_camel_case());
+            FnsMember { name, fn_struct }
+        })
+        .collect()
+}
@@ -36,6 +36,13 @@ mod spirv_reqs;
 mod version;
 
 pub fn autogen() {
+    // vk.xml is the 1.3.209 registry, with VK_EXT_image_2d_view_of_3d,
+    // VK_EXT_image_compression_control, VK_EXT_attachment_feedback_loop_layout,
+    // VK_EXT_attachment_feedback_loop_dynamic_state, VK_EXT_swapchain_maintenance1,
+    // VK_EXT_surface_maintenance1, VK_EXT_layer_settings, VK_EXT_device_fault and
+    // VK_EXT_mesh_shader ported in by hand. It should be replaced by the upstream 1.3.251
+    // registry, which matches the headers of ash 0.37.3. Extensions that are newer than that
+    // registry, such as VK_EXT_layer_settings, have to be carried over by hand again.
     let registry = get_vk_registry("vk.xml");
     let vk_data = VkRegistryData::new(&registry);
     let spirv_grammar = get_spirv_grammar("spirv.core.grammar.json");
@@ -0,0 +1,361 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Suballocation of many short-lived subbuffers from a few large buffers.
+//!
+//! A [`SubbufferAllocator`] allocates subbuffers from large buffers that it creates as needed,
+//! called arenas. Allocating a subbuffer only advances an offset in the current arena, which is
+//! much cheaper than creating a new buffer. Once every subbuffer of an arena has been dropped,
+//! including the ones held by command buffers that the device may still be executing, the arena
+//! is reused.
+//!
+//! This is suited to data that is uploaded at every frame, such as uniform buffers:
+//!
+//! ```
+//! use vulkano::buffer::{
+//!     allocator::{SubbufferAllocator, SubbufferAllocatorCreateInfo},
+//!     BufferUsage,
+//! };
+//!
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! let allocator = SubbufferAllocator::new(
+//!     device.clone(),
+//!     SubbufferAllocatorCreateInfo {
+//!         usage: BufferUsage::uniform_buffer(),
+//!         ..Default::default()
+//!     },
+//! );
+//!
+//! for n in 0..25u32 {
+//!     // Each iteration allocates a new subbuffer and stores `data` in it.
+//!     let data: [f32; 4] = [1.0, 0.5, n as f32 / 24.0, 0.0];
+//!     let subbuffer = allocator.from_data(data).unwrap();
+//!
+//!     // `subbuffer` can then be used in commands like any other buffer.
+//! }
+//! ```
+
+use super::{
+    subbuffer::{Buffer, BufferCreateInfo, BufferLocation, Subbuffer},
+    BufferContents, BufferCreationError, BufferUsage,
+};
+use crate::{
+    device::{Device, DeviceOwned},
+    DeviceSize,
+};
+use std::{
+    cmp::max,
+    mem::{align_of, size_of},
+    sync::{Arc, Mutex},
+};
+
+/// Allocates subbuffers from larger buffers that it manages.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug)]
+pub struct SubbufferAllocator {
+    device: Arc<Device>,
+    arena_size: DeviceSize,
+    usage: BufferUsage,
+    location: BufferLocation,
+    // The alignment that every subbuffer must have, because of the usage of the buffers.
+    min_alignment: DeviceSize,
+    state: Mutex<SubbufferAllocatorState>,
+}
+
+#[derive(Debug, Default)]
+struct SubbufferAllocatorState {
+    // The arena that subbuffers are currently allocated from, and the start of its free space.
+    current: Option<(Arc<Buffer>, DeviceSize)>,
+    // Arenas that were full. They can be reused once they are no longer referenced by a
+    // subbuffer, which is when the allocator holds their only reference.
+    reserve: Vec<Arc<Buffer>>,
+}
+
+impl SubbufferAllocator {
+    /// Creates a new `SubbufferAllocator`. No memory is allocated until the first subbuffer is.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.arena_size` is zero.
+    pub fn new(device: Arc<Device>, create_info: SubbufferAllocatorCreateInfo) -> Self {
+        let SubbufferAllocatorCreateInfo {
+            arena_size,
+            usage,
+            location,
+            _ne: _,
+        } = create_info;
+
+        assert!(arena_size != 0, "the arena size must not be zero");
+
+        let properties = device.physical_device().properties();
+        let mut min_alignment = 1;
+
+        if usage.uniform_buffer {
+            min_alignment = max(
+                min_alignment,
+                properties.min_uniform_buffer_offset_alignment,
+            );
+        }
+
+        if usage.storage_buffer {
+            min_alignment = max(
+                min_alignment,
+                properties.min_storage_buffer_offset_alignment,
+            );
+        }
+
+        if usage.uniform_texel_buffer || usage.storage_texel_buffer {
+            min_alignment = max(min_alignment, properties.min_texel_buffer_offset_alignment);
+        }
+
+        SubbufferAllocator {
+            device,
+            arena_size,
+            usage,
+            location,
+            min_alignment,
+            state: Mutex::new(Default::default()),
+        }
+    }
+
+    /// Returns the usage of the buffers that subbuffers are allocated from.
+    #[inline]
+    pub fn usage(&self) -> &BufferUsage {
+        &self.usage
+    }
+
+    /// Returns the location of the buffers that subbuffers are allocated from.
+    #[inline]
+    pub fn location(&self) -> BufferLocation {
+        self.location
+    }
+
+    /// Returns the number of buffers that the allocator created so far.
+    #[inline]
+    pub fn arena_count(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.reserve.len() + state.current.is_some() as usize
+    }
+
+    /// Allocates a subbuffer that can hold a `T`. Its content is unspecified.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    pub fn allocate_sized<T>(&self) -> Result<Arc<Subbuffer<T>>, BufferCreationError>
+    where
+        T: BufferContents,
+    {
+        assert!(size_of::<T>() != 0, "`T` must not have zero size");
+
+        self.allocate(size_of::<T>() as DeviceSize, align_of::<T>() as DeviceSize)
+    }
+
+    /// Allocates a subbuffer that can hold `len` elements of `T`. Its content is unspecified.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    /// - Panics if `len` is zero.
+    pub fn allocate_slice<T>(
+        &self,
+        len: DeviceSize,
+    ) -> Result<Arc<Subbuffer<[T]>>, BufferCreationError>
+    where
+        [T]: BufferContents,
+    {
+        assert!(size_of::<T>() != 0, "`T` must not have zero size");
+        assert!(len != 0, "`len` must not be zero");
+
+        self.allocate(
+            len * size_of::<T>() as DeviceSize,
+            align_of::<T>() as DeviceSize,
+        )
+    }
+
+    /// Allocates a subbuffer that contains `data`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    /// - Panics if the location of the allocator is [`BufferLocation::Device`].
+    pub fn from_data<T>(&self, data: T) -> Result<Arc<Subbuffer<T>>, BufferCreationError>
+    where
+        T: BufferContents,
+    {
+        assert!(
+            self.location.is_host_accessible(),
+            "the allocator must be host-accessible to allocate subbuffers with data"
+        );
+
+        let subbuffer = self.allocate_sized()?;
+        *subbuffer.write().unwrap() = data;
+
+        Ok(subbuffer)
+    }
+
+    /// Allocates a subbuffer that contains the elements of `iter`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    /// - Panics if `iter` is empty.
+    /// - Panics if the location of the allocator is [`BufferLocation::Device`].
+    pub fn from_iter<T, I>(&self, iter: I) -> Result<Arc<Subbuffer<[T]>>, BufferCreationError>
+    where
+        [T]: BufferContents,
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        assert!(
+            self.location.is_host_accessible(),
+            "the allocator must be host-accessible to allocate subbuffers with data"
+        );
+
+        let iter = iter.into_iter();
+        let subbuffer = self.allocate_slice(iter.len() as DeviceSize)?;
+
+        {
+            let mut mapping = subbuffer.write().unwrap();
+
+            for (o, i) in mapping.iter_mut().zip(iter) {
+                *o = i;
+            }
+        }
+
+        Ok(subbuffer)
+    }
+
+    fn allocate<T>(
+        &self,
+        size: DeviceSize,
+        alignment: DeviceSize,
+    ) -> Result<Arc<Subbuffer<T>>, BufferCreationError>
+    where
+        T: ?Sized,
+    {
+        let alignment = max(alignment, self.min_alignment);
+        let mut state = self.state.lock().unwrap();
+
+        if let Some((arena, free_start)) = &mut state.current {
+            let offset = (*free_start + alignment - 1) / alignment * alignment;
+
+            if offset + size <= arena.size() {
+                *free_start = offset + size;
+                return Ok(Subbuffer::from_range(arena.clone(), offset, size));
+            }
+        }
+
+        if let Some((arena, _)) = state.current.take() {
+            state.reserve.push(arena);
+        }
+
+        let reusable = state
+            .reserve
+            .iter()
+            .position(|arena| Arc::strong_count(arena) == 1 && arena.size() >= size);
+        let arena = match reusable {
+            Some(index) => state.reserve.swap_remove(index),
+            None => Buffer::new(
+                self.device.clone(),
+                BufferCreateInfo {
+                    location: self.location,
+                    size: max(self.arena_size, size),
+                    usage: self.usage,
+                    ..Default::default()
+                },
+            )?,
+        };
+
+        // The start of an arena is suitably aligned for any subbuffer.
+        state.current = Some((arena.clone(), size));
+
+        Ok(Subbuffer::from_range(arena, 0, size))
+    }
+}
+
+unsafe impl DeviceOwned for SubbufferAllocator {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+/// Parameters to create a new `SubbufferAllocator`.
+#[derive(Clone, Debug)]
+pub struct SubbufferAllocatorCreateInfo {
+    /// The size in bytes of the buffers that subbuffers are allocated from. A subbuffer that is
+    /// larger than this gets a buffer of its own size.
+    ///
+    /// The default value is `65536`.
+    pub arena_size: DeviceSize,
+
+    /// How the buffers are going to be used.
+    ///
+    /// The default value is [`BufferUsage::none()`], which must be overridden.
+    pub usage: BufferUsage,
+
+    /// Where the memory of the buffers is allocated.
+    ///
+    /// The default value is [`BufferLocation::Upload`].
+    pub location: BufferLocation,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for SubbufferAllocatorCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            arena_size: 65536,
+            usage: BufferUsage::none(),
+            location: BufferLocation::Upload,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SubbufferAllocator, SubbufferAllocatorCreateInfo};
+    use crate::buffer::BufferUsage;
+
+    #[test]
+    fn reuse_arenas() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let allocator = SubbufferAllocator::new(
+            device,
+            SubbufferAllocatorCreateInfo {
+                arena_size: 64,
+                usage: BufferUsage::transfer_src(),
+                ..Default::default()
+            },
+        );
+
+        let first = allocator.from_iter([1u32; 12]).unwrap();
+        let second = allocator.from_data(2u64).unwrap();
+        assert!(first.buffer() == second.buffer());
+        assert_eq!(second.offset(), 48);
+        assert_eq!(allocator.arena_count(), 1);
+
+        // Doesn't fit in the rest of the first arena.
+        let third = allocator.from_iter([3u32; 4]).unwrap();
+        assert!(third.buffer() != first.buffer());
+        assert_eq!(allocator.arena_count(), 2);
+
+        // The first arena is reused once its subbuffers are dropped.
+        drop((first, second));
+        let fourth = allocator.from_iter([4u32; 16]).unwrap();
+        assert!(fourth.buffer() != third.buffer());
+        assert_eq!(allocator.arena_count(), 2);
+        assert_eq!(&*fourth.read().unwrap(), &[4; 16]);
+    }
+}
@@ -16,8 +16,12 @@
 //! You can read the buffer multiple times simultaneously. Trying to read and write simultaneously,
 //! or write and write simultaneously will block.
 
+// The deprecated types of this module are still implemented and tested here.
+#![allow(deprecated)]
+
 use super::{
     sys::UnsafeBuffer, BufferAccess, BufferAccessObject, BufferContents, BufferInner, BufferUsage,
+    HeaderSlice,
 };
 use crate::{
     buffer::{sys::UnsafeBufferCreateInfo, BufferCreationError, TypedBufferAccess},
@@ -32,6 +36,7 @@ use crate::{
     sync::Sharing,
     DeviceSize,
 };
+use bytemuck::Pod;
 use smallvec::SmallVec;
 use std::{
     error, fmt,
@@ -50,6 +55,7 @@ use std::{
 /// memory caches GPU data on the CPU side. This can be more performant in cases where
 /// the cpu needs to read data coming off the GPU.
 #[derive(Debug)]
+#[deprecated(since = "0.30", note = "Use `Buffer` and `Subbuffer` instead")]
 pub struct CpuAccessibleBuffer<T, A = PotentialDedicatedAllocation<StdMemoryPoolAlloc>>
 where
     T: BufferContents + ?Sized,
@@ -201,6 +207,81 @@ where
     }
 }
 
+impl<H, T> CpuAccessibleBuffer<HeaderSlice<H, T>>
+where
+    H: Pod + Send + Sync,
+    T: Pod + Send + Sync,
+{
+    /// Builds a new buffer that contains a header followed by an array of `T`. The initial data
+    /// of the array comes from an iterator that produces that list of Ts.
+    ///
+    /// If the alignment of the whole object requires it, the array is padded at the end with
+    /// zeroed elements. See [`HeaderSlice`] for details.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    /// - Panics if the size of `H` is not a multiple of the alignment of `T`.
+    pub fn from_header_iter<I>(
+        device: Arc<Device>,
+        usage: BufferUsage,
+        host_cached: bool,
+        header: H,
+        data: I,
+    ) -> Result<Arc<CpuAccessibleBuffer<HeaderSlice<H, T>>>, DeviceMemoryAllocationError>
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let data = data.into_iter();
+
+        unsafe {
+            let uninitialized = CpuAccessibleBuffer::uninitialized_header_slice(
+                device,
+                data.len() as DeviceSize,
+                usage,
+                host_cached,
+            )?;
+
+            {
+                let mut mapping = uninitialized.write().unwrap();
+                mapping.header = header;
+
+                let mut data = data.fuse();
+
+                for o in mapping.slice.iter_mut() {
+                    *o = data.next().unwrap_or_else(T::zeroed);
+                }
+            }
+
+            Ok(uninitialized)
+        }
+    }
+
+    /// Builds a new uninitialized buffer that contains a header followed by an array of at least
+    /// `len` elements of `T`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    /// - Panics if the size of `H` is not a multiple of the alignment of `T`.
+    #[inline]
+    pub unsafe fn uninitialized_header_slice(
+        device: Arc<Device>,
+        len: DeviceSize,
+        usage: BufferUsage,
+        host_cached: bool,
+    ) -> Result<Arc<CpuAccessibleBuffer<HeaderSlice<H, T>>>, DeviceMemoryAllocationError> {
+        CpuAccessibleBuffer::raw(
+            device,
+            HeaderSlice::<H, T>::size_for_len(len),
+            usage,
+            host_cached,
+            [],
+        )
+    }
+}
+
 impl<T> CpuAccessibleBuffer<T>
 where
     T: BufferContents + ?Sized,
@@ -630,7 +711,7 @@ impl fmt::Display for WriteLockError {
 
 #[cfg(test)]
 mod tests {
-    use crate::buffer::{BufferUsage, CpuAccessibleBuffer};
+    use crate::buffer::{BufferUsage, CpuAccessibleBuffer, HeaderSlice};
 
     #[test]
     fn create_empty_buffer() {
@@ -645,4 +726,30 @@ mod tests {
                 .unwrap();
         });
     }
+
+    #[test]
+    fn header_slice() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer = CpuAccessibleBuffer::<HeaderSlice<[u32; 2], u32>>::from_header_iter(
+            device,
+            BufferUsage::all(),
+            false,
+            [3, 7],
+            [10u32, 20, 30],
+        )
+        .unwrap();
+
+        let content = buffer.read().unwrap();
+        assert_eq!(content.header, [3, 7]);
+        assert_eq!(&content.slice, &[10, 20, 30]);
+    }
+
+    #[test]
+    fn header_slice_padded_len() {
+        // The header is aligned to 8 bytes, so an odd number of `u32`s gets padded.
+        assert_eq!(HeaderSlice::<u64, u32>::size_for_len(3), 24);
+        assert_eq!(HeaderSlice::<u64, u32>::size_for_len(4), 24);
+        assert_eq!(HeaderSlice::<[u32; 3], u32>::size_for_len(2), 20);
+    }
 }
@@ -7,6 +7,9 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+// The deprecated types of this module are still implemented and tested here.
+#![allow(deprecated)]
+
 use super::{
     sys::{UnsafeBuffer, UnsafeBufferCreateInfo},
     BufferAccess, BufferAccessObject, BufferContents, BufferCreationError, BufferInner,
@@ -91,6 +94,7 @@ use std::{
 /// }
 /// ```
 ///
+#[deprecated(since = "0.30", note = "Use `SubbufferAllocator` instead")]
 pub struct CpuBufferPool<T, A = Arc<StdMemoryPool>>
 where
     [T]: BufferContents,
@@ -14,6 +14,9 @@
 //! write simultaneously, or write and write simultaneously will block with a semaphore.
 //!
 
+// The deprecated types of this module are still implemented here.
+#![allow(deprecated)]
+
 use super::{
     sys::{UnsafeBuffer, UnsafeBufferCreateInfo},
     BufferAccess, BufferAccessObject, BufferContents, BufferCreationError, BufferInner,
@@ -116,6 +119,7 @@ use std::{
 /// ```
 ///
 #[derive(Debug)]
+#[deprecated(since = "0.30", note = "Use `Buffer` and `Subbuffer` instead")]
 pub struct DeviceLocalBuffer<T, A = PotentialDedicatedAllocation<StdMemoryPoolAlloc>>
 where
     T: BufferContents + ?Sized,
@@ -0,0 +1,391 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Buffer that is larger than a single memory allocation.
+//!
+//! Implementations limit the size of a single allocation of device memory, both explicitly with
+//! the [`max_memory_allocation_size`](crate::device::Properties::max_memory_allocation_size)
+//! property, and implicitly with the size of the memory heaps. A [`HugeBuffer`] is a sparse
+//! buffer whose memory is split into several allocations, called chunks, that are bound one after
+//! the other. From the point of view of commands and shaders, it is a single buffer with a
+//! contiguous range of addresses, and it can be used like any other buffer.
+//!
+//! Creating a `HugeBuffer` requires the
+//! [`sparse_binding`](crate::device::Features::sparse_binding) feature, and a queue whose family
+//! supports sparse binding to bind the chunks with.
+
+use super::{
+    sys::{UnsafeBuffer, UnsafeBufferCreateInfo},
+    BufferAccess, BufferAccessObject, BufferCreationError, BufferInner, BufferUsage, SparseLevel,
+    TypedBufferAccess,
+};
+use crate::{
+    command_buffer::submit::{
+        SubmitBindSparseBatchBuilder, SubmitBindSparseBufferBindBuilder, SubmitBindSparseBuilder,
+        SubmitBindSparseError,
+    },
+    device::{Device, DeviceOwned, Queue},
+    memory::{DeviceMemory, DeviceMemoryAllocationError, MemoryAllocateInfo},
+    sync::{Fence, FenceWaitError, Sharing},
+    DeviceSize, OomError,
+};
+use smallvec::SmallVec;
+use std::{
+    cmp::min,
+    error, fmt,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+/// A sparse buffer whose memory is made of several allocations.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug)]
+pub struct HugeBuffer {
+    inner: Arc<UnsafeBuffer>,
+    chunks: Vec<DeviceMemory>,
+    chunk_size: DeviceSize,
+}
+
+impl HugeBuffer {
+    /// Creates a new `HugeBuffer`, allocates its chunks and binds them to it using `queue`.
+    ///
+    /// This function blocks until the chunks are bound.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.size` is zero.
+    /// - Panics if `create_info.max_chunk_size` is `Some`, and is smaller than the alignment of
+    ///   the buffer's memory.
+    /// - Panics if the queue family of `queue` doesn't support sparse binding.
+    pub fn new(
+        queue: Arc<Queue>,
+        create_info: HugeBufferCreateInfo,
+    ) -> Result<Arc<HugeBuffer>, HugeBufferCreationError> {
+        let HugeBufferCreateInfo {
+            sharing,
+            size,
+            usage,
+            max_chunk_size,
+            _ne: _,
+        } = create_info;
+
+        let device = queue.device().clone();
+        let buffer = UnsafeBuffer::new(
+            device.clone(),
+            UnsafeBufferCreateInfo {
+                sharing,
+                size,
+                usage,
+                sparse: Some(SparseLevel::none()),
+                ..Default::default()
+            },
+        )?;
+        let mem_reqs = buffer.memory_requirements();
+
+        let memory_type = device
+            .physical_device()
+            .memory_types()
+            .filter(|t| mem_reqs.memory_type_bits & (1 << t.id()) != 0)
+            .filter(|t| !t.is_lazily_allocated() && !t.is_protected())
+            .min_by_key(|t| !t.is_device_local())
+            .ok_or(HugeBufferCreationError::NoSuitableMemoryType)?;
+
+        // The size of each chunk must be a multiple of the sparse block size, which is given by
+        // the alignment of the memory requirements.
+        let chunk_size = {
+            let mut max_size = memory_type.heap().size();
+
+            if let Some(max_memory_allocation_size) = device
+                .physical_device()
+                .properties()
+                .max_memory_allocation_size
+            {
+                max_size = min(max_size, max_memory_allocation_size);
+            }
+
+            if let Some(max_chunk_size) = max_chunk_size {
+                max_size = min(max_size, max_chunk_size);
+            }
+
+            let chunk_size = max_size / mem_reqs.alignment * mem_reqs.alignment;
+            assert!(chunk_size != 0);
+            chunk_size
+        };
+
+        let chunks = (0..mem_reqs.size)
+            .step_by(chunk_size as usize)
+            .map(|offset| {
+                DeviceMemory::allocate(
+                    device.clone(),
+                    MemoryAllocateInfo {
+                        allocation_size: min(chunk_size, mem_reqs.size - offset),
+                        memory_type_index: memory_type.id(),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        assert!(
+            queue.family().supports_sparse_binding(),
+            "the queue family of the queue must support sparse binding"
+        );
+
+        unsafe {
+            let mut buffer_binds = SubmitBindSparseBufferBindBuilder::new(&buffer);
+
+            for (index, memory) in chunks.iter().enumerate() {
+                buffer_binds.add_bind(
+                    index as DeviceSize * chunk_size,
+                    memory.allocation_size(),
+                    memory,
+                    0,
+                );
+            }
+
+            let mut batch = SubmitBindSparseBatchBuilder::new();
+            batch.add_buffer(buffer_binds);
+
+            let fence = Fence::from_pool(device.clone())?;
+            let mut builder = SubmitBindSparseBuilder::new();
+            builder.add(batch);
+            builder.set_fence_signal(&fence);
+            builder.submit(&queue)?;
+            fence.wait(None)?;
+        }
+
+        Ok(Arc::new(HugeBuffer {
+            inner: buffer,
+            chunks,
+            chunk_size,
+        }))
+    }
+
+    /// Returns the size of the buffer in bytes.
+    #[inline]
+    pub fn size(&self) -> DeviceSize {
+        self.inner.size()
+    }
+
+    /// Returns the usage the buffer was created with.
+    #[inline]
+    pub fn usage(&self) -> &BufferUsage {
+        self.inner.usage()
+    }
+
+    /// Returns the size in bytes of each chunk of memory. The last chunk may be smaller.
+    #[inline]
+    pub fn chunk_size(&self) -> DeviceSize {
+        self.chunk_size
+    }
+
+    /// Returns the chunks of memory of the buffer, in the order of the ranges of the buffer that
+    /// they are bound to.
+    #[inline]
+    pub fn chunks(&self) -> &[DeviceMemory] {
+        &self.chunks
+    }
+}
+
+unsafe impl DeviceOwned for HugeBuffer {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.inner.device()
+    }
+}
+
+unsafe impl BufferAccess for HugeBuffer {
+    #[inline]
+    fn inner(&self) -> BufferInner {
+        BufferInner {
+            buffer: &self.inner,
+            offset: 0,
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> DeviceSize {
+        self.inner.size()
+    }
+}
+
+impl BufferAccessObject for Arc<HugeBuffer> {
+    #[inline]
+    fn as_buffer_access_object(&self) -> Arc<dyn BufferAccess> {
+        self.clone()
+    }
+}
+
+unsafe impl TypedBufferAccess for HugeBuffer {
+    type Content = [u8];
+}
+
+impl PartialEq for HugeBuffer {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl Eq for HugeBuffer {}
+
+impl Hash for HugeBuffer {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+    }
+}
+
+/// Parameters to create a new `HugeBuffer`.
+#[derive(Clone, Debug)]
+pub struct HugeBufferCreateInfo {
+    /// Whether the buffer can be shared across multiple queues, or is limited to a single queue.
+    ///
+    /// The default value is [`Sharing::Exclusive`].
+    pub sharing: Sharing<SmallVec<[u32; 4]>>,
+
+    /// The size in bytes of the buffer.
+    ///
+    /// The default value is `0`, which must be overridden.
+    pub size: DeviceSize,
+
+    /// How the buffer is going to be used.
+    ///
+    /// The default value is [`BufferUsage::none()`], which must be overridden.
+    pub usage: BufferUsage,
+
+    /// The maximum size in bytes of each chunk of memory. It is rounded down to a multiple of the
+    /// alignment of the buffer's memory.
+    ///
+    /// If `None`, the largest size that the implementation allows for the chosen memory type is
+    /// used.
+    ///
+    /// The default value is `None`.
+    pub max_chunk_size: Option<DeviceSize>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for HugeBufferCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            sharing: Sharing::Exclusive,
+            size: 0,
+            usage: BufferUsage::none(),
+            max_chunk_size: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Error that can happen when creating a `HugeBuffer`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HugeBufferCreationError {
+    BufferCreationError(BufferCreationError),
+    DeviceMemoryAllocationError(DeviceMemoryAllocationError),
+    SubmitBindSparseError(SubmitBindSparseError),
+    FenceWaitError(FenceWaitError),
+
+    /// None of the memory types that the buffer can be bound to are suitable.
+    NoSuitableMemoryType,
+}
+
+impl error::Error for HugeBufferCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::BufferCreationError(err) => Some(err),
+            Self::DeviceMemoryAllocationError(err) => Some(err),
+            Self::SubmitBindSparseError(err) => Some(err),
+            Self::FenceWaitError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for HugeBufferCreationError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferCreationError(err) => err.fmt(f),
+            Self::DeviceMemoryAllocationError(err) => err.fmt(f),
+            Self::SubmitBindSparseError(err) => err.fmt(f),
+            Self::FenceWaitError(err) => err.fmt(f),
+            Self::NoSuitableMemoryType => write!(
+                f,
+                "none of the memory types that the buffer can be bound to are suitable",
+            ),
+        }
+    }
+}
+
+impl From<BufferCreationError> for HugeBufferCreationError {
+    #[inline]
+    fn from(err: BufferCreationError) -> Self {
+        Self::BufferCreationError(err)
+    }
+}
+
+impl From<DeviceMemoryAllocationError> for HugeBufferCreationError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocationError) -> Self {
+        Self::DeviceMemoryAllocationError(err)
+    }
+}
+
+impl From<OomError> for HugeBufferCreationError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::DeviceMemoryAllocationError(err.into())
+    }
+}
+
+impl From<SubmitBindSparseError> for HugeBufferCreationError {
+    #[inline]
+    fn from(err: SubmitBindSparseError) -> Self {
+        Self::SubmitBindSparseError(err)
+    }
+}
+
+impl From<FenceWaitError> for HugeBufferCreationError {
+    #[inline]
+    fn from(err: FenceWaitError) -> Self {
+        Self::FenceWaitError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HugeBuffer, HugeBufferCreateInfo, HugeBufferCreationError};
+    use crate::buffer::{BufferCreationError, BufferUsage};
+
+    #[test]
+    fn missing_feature_sparse_binding() {
+        let (_device, queue) = gfx_dev_and_queue!();
+
+        match HugeBuffer::new(
+            queue,
+            HugeBufferCreateInfo {
+                size: 1024,
+                usage: BufferUsage::transfer_dst(),
+                ..Default::default()
+            },
+        ) {
+            Err(HugeBufferCreationError::BufferCreationError(
+                BufferCreationError::FeatureNotEnabled {
+                    feature: "sparse_binding",
+                    ..
+                },
+            )) => (),
+            _ => panic!(),
+        }
+    }
+}
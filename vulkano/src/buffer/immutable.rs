@@ -19,8 +19,8 @@
 //!
 
 use super::{
-    sys::UnsafeBuffer, BufferAccess, BufferAccessObject, BufferContents, BufferInner, BufferUsage,
-    CpuAccessibleBuffer,
+    sys::UnsafeBuffer, Buffer, BufferAccess, BufferAccessObject, BufferContents, BufferInner,
+    BufferLocation, BufferUsage,
 };
 use crate::{
     buffer::{sys::UnsafeBufferCreateInfo, BufferCreationError, TypedBufferAccess},
@@ -150,10 +150,10 @@ where
         (Arc<ImmutableBuffer<T>>, ImmutableBufferFromBufferFuture),
         ImmutableBufferCreationError,
     > {
-        let source = CpuAccessibleBuffer::from_data(
+        let source = Buffer::from_data(
             queue.device().clone(),
             BufferUsage::transfer_src(),
-            false,
+            BufferLocation::Upload,
             data,
         )?;
         ImmutableBuffer::from_buffer(source, usage, queue)
@@ -218,10 +218,10 @@ where
         D: IntoIterator<Item = T>,
         D::IntoIter: ExactSizeIterator,
     {
-        let source = CpuAccessibleBuffer::from_iter(
+        let source = Buffer::from_iter(
             queue.device().clone(),
             BufferUsage::transfer_src(),
-            false,
+            BufferLocation::Upload,
             data,
         )?;
         ImmutableBuffer::from_buffer(source, usage, queue)
@@ -578,6 +578,7 @@ where
 
 #[derive(Clone, Debug)]
 pub enum ImmutableBufferCreationError {
+    BufferCreationError(BufferCreationError),
     DeviceMemoryAllocationError(DeviceMemoryAllocationError),
     CommandBufferBeginError(CommandBufferBeginError),
 }
@@ -586,6 +587,7 @@ impl error::Error for ImmutableBufferCreationError {
     #[inline]
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
+            Self::BufferCreationError(err) => Some(err),
             Self::DeviceMemoryAllocationError(err) => Some(err),
             Self::CommandBufferBeginError(err) => Some(err),
         }
@@ -596,12 +598,20 @@ impl fmt::Display for ImmutableBufferCreationError {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Self::BufferCreationError(err) => err.fmt(f),
             Self::DeviceMemoryAllocationError(err) => err.fmt(f),
             Self::CommandBufferBeginError(err) => err.fmt(f),
         }
     }
 }
 
+impl From<BufferCreationError> for ImmutableBufferCreationError {
+    #[inline]
+    fn from(err: BufferCreationError) -> Self {
+        Self::BufferCreationError(err)
+    }
+}
+
 impl From<DeviceMemoryAllocationError> for ImmutableBufferCreationError {
     #[inline]
     fn from(err: DeviceMemoryAllocationError) -> Self {
@@ -635,8 +645,13 @@ mod tests {
         let (buffer, _) =
             ImmutableBuffer::from_data(12u32, BufferUsage::all(), queue.clone()).unwrap();
 
-        let destination =
-            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, 0).unwrap();
+        let destination = Buffer::from_data(
+            device.clone(),
+            BufferUsage::all(),
+            BufferLocation::Upload,
+            0,
+        )
+        .unwrap();
 
         let mut cbb = AutoCommandBufferBuilder::primary(
             device.clone(),
@@ -669,10 +684,10 @@ mod tests {
         )
         .unwrap();
 
-        let destination = CpuAccessibleBuffer::from_iter(
+        let destination = Buffer::from_iter(
             device.clone(),
             BufferUsage::all(),
-            false,
+            BufferLocation::Upload,
             (0..512).map(|_| 0u32),
         )
         .unwrap();
@@ -707,8 +722,13 @@ mod tests {
             ImmutableBuffer::<u32>::uninitialized(device.clone(), BufferUsage::all()).unwrap()
         };
 
-        let source =
-            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, 0).unwrap();
+        let source = Buffer::from_data(
+            device.clone(),
+            BufferUsage::all(),
+            BufferLocation::Upload,
+            0,
+        )
+        .unwrap();
 
         let mut cbb = AutoCommandBufferBuilder::primary(
             device.clone(),
@@ -738,8 +758,13 @@ mod tests {
             ImmutableBuffer::<u32>::uninitialized(device.clone(), BufferUsage::all()).unwrap()
         };
 
-        let source =
-            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, 0).unwrap();
+        let source = Buffer::from_data(
+            device.clone(),
+            BufferUsage::all(),
+            BufferLocation::Upload,
+            0,
+        )
+        .unwrap();
 
         let mut cbb = AutoCommandBufferBuilder::primary(
             device.clone(),
@@ -26,36 +26,44 @@
 //! This type makes it possible to use all the features that Vulkan is capable of, but as its name
 //! tells it is unsafe to use.
 //!
-//! Instead you are encouraged to use one of the high-level wrappers that vulkano provides. Which
-//! wrapper to use depends on the way you are going to use the buffer:
-//!
-//! - A [`DeviceLocalBuffer`](crate::buffer::device_local::DeviceLocalBuffer) designates a buffer
-//!   usually located in video memory and whose content can't be directly accessed by your
-//!   application. Accessing this buffer from the GPU is generally faster compared to accessing a
-//!   CPU-accessible buffer.
-//! - An [`ImmutableBuffer`](crate::buffer::immutable::ImmutableBuffer) designates a buffer in video
-//!   memory and whose content can only be written at creation. Compared to `DeviceLocalBuffer`,
-//!   this buffer requires less CPU processing because we don't need to keep track of the reads
-//!   and writes.
-//! - A [`CpuBufferPool`](crate::buffer::cpu_pool::CpuBufferPool) is a ring buffer that can be used to
-//!   transfer data between the CPU and the GPU at a high rate.
-//! - A [`CpuAccessibleBuffer`](crate::buffer::cpu_access::CpuAccessibleBuffer) is a simple buffer that
-//!   can be used to prototype. It may be removed from vulkano in the far future.
+//! Instead you are encouraged to use one of the high-level wrappers that vulkano provides.
+//!
+//! A [`Buffer`](crate::buffer::subbuffer::Buffer) is a buffer whose memory location, on the
+//! device or accessible from the host, is chosen with a
+//! [`BufferLocation`](crate::buffer::subbuffer::BufferLocation) when it's created. It is accessed
+//! through [`Subbuffer`](crate::buffer::subbuffer::Subbuffer)s, which are typed ranges of it that
+//! can be used in any command. Since one buffer can be split into many subbuffers, this makes it
+//! easy to suballocate many small objects from one buffer.
+//!
+//! A [`SubbufferAllocator`](crate::buffer::allocator::SubbufferAllocator) allocates many
+//! short-lived subbuffers from buffers that it reuses, which suits data that is transferred
+//! between the CPU and the GPU at a high rate, such as the uniform data of every frame.
+//!
+//! An [`ImmutableBuffer`](crate::buffer::immutable::ImmutableBuffer) designates a buffer in video
+//! memory and whose content can only be written at creation. It requires less CPU processing than
+//! a `Buffer` because we don't need to keep track of the reads and writes.
 //!
 //! Here is a quick way to choose which buffer to use. Do you often need to read or write
-//! the content of the buffer? If so, use a `CpuBufferPool`. Otherwise, do you need to be able to
-//! modify the content of the buffer after its initialization? If so, use a `DeviceLocalBuffer`.
-//! If no to both questions, use an `ImmutableBuffer`.
+//! the content of the buffer? If so, use a `SubbufferAllocator`. Otherwise, do you need to be
+//! able to modify the content of the buffer after its initialization? If so, use a `Buffer`
+//! located on the device, and update it with
+//! [`Subbuffer::update`](crate::buffer::subbuffer::Subbuffer::update). If no to both questions,
+//! use an `ImmutableBuffer`.
 //!
 //! When deciding how your buffer is going to be used, don't forget that sometimes the best
 //! solution is to manipulate multiple buffers instead. For example if you need to update a buffer's
 //! content only from time to time, it may be a good idea to simply recreate a new `ImmutableBuffer`
 //! every time.
 //! Another example: if a buffer is under constant access by the GPU but you need to
-//! read its content on the CPU from time to time, it may be a good idea to use a
-//! `DeviceLocalBuffer` as the main buffer and a `CpuBufferPool` for when you need to read it.
-//! Then whenever you need to read the main buffer, ask the GPU to copy from the device-local
-//! buffer to the CPU buffer pool, and read the CPU buffer pool instead.
+//! read its content on the CPU from time to time, it may be a good idea to use a `Buffer` located
+//! on the device as the main buffer, and a subbuffer located for download for when you need to
+//! read it. Then whenever you need to read the main buffer, ask the GPU to copy from the
+//! device-local buffer to the downloaded subbuffer, and read that subbuffer instead.
+//!
+//! The older [`CpuAccessibleBuffer`](crate::buffer::cpu_access::CpuAccessibleBuffer),
+//! [`DeviceLocalBuffer`](crate::buffer::device_local::DeviceLocalBuffer) and
+//! [`CpuBufferPool`](crate::buffer::cpu_pool::CpuBufferPool) types are deprecated in favor of
+//! `Buffer`, `Subbuffer` and `SubbufferAllocator`.
 //!
 //! # Buffers usage
 //!
@@ -84,17 +92,21 @@
 //!
 
 pub use self::{
-    cpu_access::CpuAccessibleBuffer,
-    cpu_pool::CpuBufferPool,
-    device_local::DeviceLocalBuffer,
+    allocator::{SubbufferAllocator, SubbufferAllocatorCreateInfo},
+    huge::{HugeBuffer, HugeBufferCreateInfo, HugeBufferCreationError},
     immutable::ImmutableBuffer,
     slice::BufferSlice,
+    subbuffer::{Buffer, BufferCreateInfo, BufferLocation, BufferUpdateError, Subbuffer},
     sys::{BufferCreationError, SparseLevel},
     traits::{
         BufferAccess, BufferAccessObject, BufferDeviceAddressError, BufferInner, TypedBufferAccess,
     },
     usage::BufferUsage,
 };
+#[allow(deprecated)]
+pub use self::{
+    cpu_access::CpuAccessibleBuffer, cpu_pool::CpuBufferPool, device_local::DeviceLocalBuffer,
+};
 use crate::{
     memory::{ExternalMemoryHandleType, ExternalMemoryProperties},
     DeviceSize,
@@ -103,12 +115,19 @@ use bytemuck::{
     bytes_of, cast_slice, try_cast_slice, try_cast_slice_mut, try_from_bytes, try_from_bytes_mut,
     Pod, PodCastError,
 };
-use std::mem::size_of;
+use std::{
+    cmp::max,
+    mem::{align_of, size_of, size_of_val},
+    ptr, slice,
+};
 
+pub mod allocator;
 pub mod cpu_access;
 pub mod cpu_pool;
 pub mod device_local;
+pub mod huge;
 pub mod immutable;
+pub mod subbuffer;
 pub mod sys;
 pub mod view;
 
@@ -182,6 +201,114 @@ where
     }
 }
 
+/// Buffer contents made of a sized header, followed by a runtime-sized array of elements.
+///
+/// This corresponds to a shader storage block whose last member is an array without a size, such
+/// as `buffer Data { uint count; vec4 items[]; };`. The header can be any plain-old-data type,
+/// including a struct that contains other structs. The fields are laid out as with `#[repr(C)]`,
+/// so `slice` starts directly after `header`, and the whole object is aligned to the greater of
+/// the alignments of `H` and `T`.
+///
+/// The contents may not contain padding bytes, so the size of `H` must be a multiple of the
+/// alignment of `T`. If the alignment of `H` is greater than that of `T`, the total size must
+/// also be a multiple of the alignment of `H`, which is why the slice can end up with a few more
+/// elements than requested.
+#[derive(Debug)]
+#[repr(C)]
+pub struct HeaderSlice<H, T> {
+    pub header: H,
+    pub slice: [T],
+}
+
+impl<H, T> HeaderSlice<H, T>
+where
+    H: Pod,
+    T: Pod,
+{
+    /// Returns the size in bytes of a `HeaderSlice` whose slice has at least `len` elements,
+    /// rounded up to the alignment of the whole object.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    /// - Panics if the size of `H` is not a multiple of the alignment of `T`.
+    #[inline]
+    pub fn size_for_len(len: DeviceSize) -> DeviceSize {
+        let align = Self::align() as DeviceSize;
+        let size = Self::slice_offset() as DeviceSize + len * size_of::<T>() as DeviceSize;
+
+        (size + align - 1) / align * align
+    }
+
+    #[inline]
+    fn slice_offset() -> usize {
+        assert!(
+            size_of::<T>() != 0,
+            "the slice element type must not have zero size"
+        );
+        assert!(
+            size_of::<H>() % align_of::<T>() == 0,
+            "the size of the header must be a multiple of the alignment of the slice elements"
+        );
+
+        size_of::<H>()
+    }
+
+    #[inline]
+    fn align() -> usize {
+        max(align_of::<H>(), align_of::<T>())
+    }
+
+    // Returns the number of slice elements for an object of `size` bytes starting at `ptr`.
+    fn slice_len(ptr: *const u8, size: usize) -> Result<usize, PodCastError> {
+        let slice_offset = Self::slice_offset();
+
+        if size < slice_offset
+            || (size - slice_offset) % size_of::<T>() != 0
+            || size % Self::align() != 0
+        {
+            return Err(PodCastError::SizeMismatch);
+        }
+
+        if ptr as usize % Self::align() != 0 {
+            return Err(PodCastError::TargetAlignmentGreaterAndInputNotAligned);
+        }
+
+        Ok((size - slice_offset) / size_of::<T>())
+    }
+}
+
+unsafe impl<H, T> BufferContents for HeaderSlice<H, T>
+where
+    H: Pod + Send + Sync,
+    T: Pod + Send + Sync,
+{
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        // `from_bytes` guarantees that there is no padding.
+        unsafe { slice::from_raw_parts(self as *const Self as *const u8, size_of_val(self)) }
+    }
+
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> Result<&Self, PodCastError> {
+        let len = Self::slice_len(bytes.as_ptr(), bytes.len())?;
+
+        unsafe { Ok(&*(ptr::slice_from_raw_parts(bytes.as_ptr(), len) as *const Self)) }
+    }
+
+    #[inline]
+    fn from_bytes_mut(bytes: &mut [u8]) -> Result<&mut Self, PodCastError> {
+        let len = Self::slice_len(bytes.as_ptr(), bytes.len())?;
+
+        unsafe { Ok(&mut *(ptr::slice_from_raw_parts_mut(bytes.as_mut_ptr(), len) as *mut Self)) }
+    }
+
+    #[inline]
+    fn size_of_element() -> DeviceSize {
+        1
+    }
+}
+
 /// The buffer configuration to query in
 /// [`PhysicalDevice::external_buffer_properties`](crate::device::physical::PhysicalDevice::external_buffer_properties).
 #[derive(Clone, Debug)]
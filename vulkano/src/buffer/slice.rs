@@ -30,7 +30,7 @@ use std::{
 ///
 /// ```ignore       // FIXME: unignore
 /// use vulkano::buffer::BufferSlice;
-/// # let buffer: std::sync::Arc<vulkano::buffer::DeviceLocalBuffer<[u8]>> = return;
+/// # let buffer: std::sync::Arc<vulkano::buffer::Subbuffer<[u8]>> = return;
 /// let _slice = BufferSlice::from(&buffer);
 /// ```
 ///
@@ -38,7 +38,7 @@ use std::{
 ///
 /// ```ignore       // FIXME: unignore
 /// use vulkano::buffer::BufferSlice;
-/// # let buffer: std::sync::Arc<vulkano::buffer::DeviceLocalBuffer<[u8]>> = return;
+/// # let buffer: std::sync::Arc<vulkano::buffer::Subbuffer<[u8]>> = return;
 /// let _slice = BufferSlice::from(&buffer).slice(12 .. 14).unwrap();
 /// ```
 ///
@@ -0,0 +1,1274 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A buffer whose memory location is chosen at creation, and typed subranges of it.
+//!
+//! A [`Buffer`] owns a Vulkan buffer and the memory bound to it. Whether that memory lives on the
+//! device or is accessible from the host is a parameter of its creation, given by a
+//! [`BufferLocation`], rather than a different type.
+//!
+//! A [`Subbuffer`] is a typed range of a `Buffer`. Every `Buffer` constructor that takes content
+//! returns a `Subbuffer` covering the whole buffer, and subbuffers can be further split or cast to
+//! other types. Many small objects can therefore be suballocated from one large buffer, instead of
+//! allocating a separate buffer for each of them. Whatever the location of the buffer it comes
+//! from, a `Subbuffer<T>` is the same type, and it can be passed to any command that takes a
+//! buffer.
+//!
+//! Host-accessible subbuffers can be read and written with [`Subbuffer::read`] and
+//! [`Subbuffer::write`]. Only the range of the subbuffer is locked, so separate subbuffers of the
+//! same buffer can be accessed by the host and the device at the same time. Subbuffers that are
+//! not host-accessible are written with [`Subbuffer::update`] and [`Subbuffer::update_range`],
+//! which copy the data through an intermediate buffer.
+//!
+//! To allocate many short-lived subbuffers, for example the uniform data of every frame, use a
+//! [`SubbufferAllocator`](crate::buffer::allocator::SubbufferAllocator).
+
+use super::{
+    sys::{UnsafeBuffer, UnsafeBufferCreateInfo},
+    BufferAccess, BufferAccessObject, BufferContents, BufferCreationError, BufferInner,
+    BufferUsage, HeaderSlice, TypedBufferAccess,
+};
+use crate::{
+    buffer::cpu_access::{ReadLockError, WriteLockError},
+    command_buffer::{
+        AutoCommandBufferBuilder, BufferCopy, BuildError, CommandBufferBeginError,
+        CommandBufferExecError, CommandBufferExecFuture, CommandBufferUsage, CopyBufferInfo,
+        CopyBufferInfoTyped, CopyError, PrimaryAutoCommandBuffer, PrimaryCommandBuffer,
+    },
+    device::{Device, DeviceOwned, Queue},
+    memory::{
+        pool::{
+            AllocFromRequirementsFilter, AllocLayout, MappingRequirement, MemoryPoolAlloc,
+            MemoryTypePolicy, PotentialDedicatedAllocation, StdMemoryPoolAlloc,
+        },
+        DedicatedAllocation, MemoryPool,
+    },
+    sync::{NowFuture, Sharing},
+    DeviceSize, OomError,
+};
+use bytemuck::Pod;
+use smallvec::{smallvec, SmallVec};
+use std::{
+    cmp::min,
+    error, fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    mem::{align_of, size_of},
+    ops::{Deref, DerefMut, Range},
+    sync::Arc,
+};
+
+/// A buffer, together with the memory that is bound to it.
+#[derive(Debug)]
+pub struct Buffer {
+    inner: Arc<UnsafeBuffer>,
+    memory: PotentialDedicatedAllocation<StdMemoryPoolAlloc>,
+    location: BufferLocation,
+}
+
+impl Buffer {
+    /// Creates a new `Buffer` and allocates memory for it.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.size` is zero.
+    pub fn new(
+        device: Arc<Device>,
+        create_info: BufferCreateInfo,
+    ) -> Result<Arc<Buffer>, BufferCreationError> {
+        let BufferCreateInfo {
+            location,
+            sharing,
+            size,
+            usage,
+            memory_type_policy,
+            _ne: _,
+        } = create_info;
+
+        let buffer = UnsafeBuffer::new(
+            device.clone(),
+            UnsafeBufferCreateInfo {
+                sharing,
+                size,
+                usage,
+                ..Default::default()
+            },
+        )?;
+        let mem_reqs = buffer.memory_requirements();
+
+        let map = if location.is_host_accessible() {
+            MappingRequirement::Map
+        } else {
+            MappingRequirement::DoNotMap
+        };
+        let memory = match memory_type_policy {
+            Some(policy) => MemoryPool::alloc_with_policy(
+                &Device::standard_pool(&device),
+                &mem_reqs,
+                AllocLayout::Linear,
+                map,
+                Some(DedicatedAllocation::Buffer(&buffer)),
+                &policy,
+            ),
+            None => MemoryPool::alloc_from_requirements(
+                &Device::standard_pool(&device),
+                &mem_reqs,
+                AllocLayout::Linear,
+                map,
+                Some(DedicatedAllocation::Buffer(&buffer)),
+                |t| {
+                    let preferred = match location {
+                        BufferLocation::Device => t.is_device_local(),
+                        BufferLocation::Upload => !t.is_host_cached(),
+                        BufferLocation::Download => t.is_host_cached(),
+                    };
+
+                    if preferred {
+                        AllocFromRequirementsFilter::Preferred
+                    } else {
+                        AllocFromRequirementsFilter::Allowed
+                    }
+                },
+            ),
+        }
+        .map_err(BufferCreationError::AllocError)?;
+        debug_assert!((memory.offset() % mem_reqs.alignment) == 0);
+        debug_assert!(!location.is_host_accessible() || memory.mapped_memory().is_some());
+
+        unsafe {
+            buffer.bind_memory(memory.memory(), memory.offset())?;
+        }
+
+        Ok(Arc::new(Buffer {
+            inner: buffer,
+            memory,
+            location,
+        }))
+    }
+
+    /// Creates a new buffer containing `data`, and returns a subbuffer covering all of it.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    /// - Panics if `location` is [`BufferLocation::Device`].
+    pub fn from_data<T>(
+        device: Arc<Device>,
+        usage: BufferUsage,
+        location: BufferLocation,
+        data: T,
+    ) -> Result<Arc<Subbuffer<T>>, BufferCreationError>
+    where
+        T: BufferContents,
+    {
+        assert!(
+            location.is_host_accessible(),
+            "the buffer must be host-accessible to be created with data"
+        );
+
+        let subbuffer = Buffer::new_sized::<T>(device, usage, location)?;
+        *subbuffer.write().unwrap() = data;
+
+        Ok(subbuffer)
+    }
+
+    /// Creates a new buffer containing the elements of `iter`, and returns a subbuffer covering
+    /// all of it.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    /// - Panics if `iter` is empty.
+    /// - Panics if `location` is [`BufferLocation::Device`].
+    pub fn from_iter<T, I>(
+        device: Arc<Device>,
+        usage: BufferUsage,
+        location: BufferLocation,
+        iter: I,
+    ) -> Result<Arc<Subbuffer<[T]>>, BufferCreationError>
+    where
+        [T]: BufferContents,
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        assert!(
+            location.is_host_accessible(),
+            "the buffer must be host-accessible to be created with data"
+        );
+
+        let iter = iter.into_iter();
+        let subbuffer = Buffer::new_slice::<T>(device, usage, location, iter.len() as DeviceSize)?;
+
+        {
+            let mut mapping = subbuffer.write().unwrap();
+
+            for (o, i) in mapping.iter_mut().zip(iter) {
+                *o = i;
+            }
+        }
+
+        Ok(subbuffer)
+    }
+
+    /// Creates a new buffer that can hold a `T`, and returns a subbuffer covering all of it.
+    ///
+    /// If the buffer is host-accessible, its content is zeroed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    pub fn new_sized<T>(
+        device: Arc<Device>,
+        usage: BufferUsage,
+        location: BufferLocation,
+    ) -> Result<Arc<Subbuffer<T>>, BufferCreationError>
+    where
+        T: BufferContents,
+    {
+        let buffer = Buffer::new_zeroed(device, size_of::<T>() as DeviceSize, usage, location)?;
+
+        Ok(Arc::new(Subbuffer::from_buffer(buffer)))
+    }
+
+    /// Creates a new buffer that can hold `len` elements of `T`, and returns a subbuffer covering
+    /// all of it.
+    ///
+    /// If the buffer is host-accessible, its content is zeroed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    /// - Panics if `len` is zero.
+    pub fn new_slice<T>(
+        device: Arc<Device>,
+        usage: BufferUsage,
+        location: BufferLocation,
+        len: DeviceSize,
+    ) -> Result<Arc<Subbuffer<[T]>>, BufferCreationError>
+    where
+        [T]: BufferContents,
+    {
+        let buffer =
+            Buffer::new_zeroed(device, len * size_of::<T>() as DeviceSize, usage, location)?;
+
+        Ok(Arc::new(Subbuffer::from_buffer(buffer)))
+    }
+
+    /// Creates a new buffer containing `header` followed by the elements of `iter`, and returns a
+    /// subbuffer covering all of it.
+    ///
+    /// If the alignment of the whole object requires it, the array is padded at the end with
+    /// zeroed elements. See [`HeaderSlice`] for details.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    /// - Panics if the size of `H` is not a multiple of the alignment of `T`.
+    /// - Panics if `location` is [`BufferLocation::Device`].
+    pub fn from_header_iter<H, T, I>(
+        device: Arc<Device>,
+        usage: BufferUsage,
+        location: BufferLocation,
+        header: H,
+        iter: I,
+    ) -> Result<Arc<Subbuffer<HeaderSlice<H, T>>>, BufferCreationError>
+    where
+        H: Pod + Send + Sync,
+        T: Pod + Send + Sync,
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        assert!(
+            location.is_host_accessible(),
+            "the buffer must be host-accessible to be created with data"
+        );
+
+        let iter = iter.into_iter();
+        let buffer = Buffer::new_zeroed(
+            device,
+            HeaderSlice::<H, T>::size_for_len(iter.len() as DeviceSize),
+            usage,
+            location,
+        )?;
+        let subbuffer = Arc::new(Subbuffer::from_buffer(buffer));
+
+        {
+            let mut mapping = subbuffer.write().unwrap();
+            mapping.header = header;
+
+            for (o, i) in mapping.slice.iter_mut().zip(iter) {
+                *o = i;
+            }
+        }
+
+        Ok(subbuffer)
+    }
+
+    fn new_zeroed(
+        device: Arc<Device>,
+        size: DeviceSize,
+        usage: BufferUsage,
+        location: BufferLocation,
+    ) -> Result<Arc<Buffer>, BufferCreationError> {
+        let buffer = Buffer::new(
+            device,
+            BufferCreateInfo {
+                location,
+                size,
+                usage,
+                ..Default::default()
+            },
+        )?;
+
+        if location.is_host_accessible() {
+            let bytes = Subbuffer::<[u8]>::from_buffer(buffer.clone());
+            bytes.write().unwrap().fill(0);
+        }
+
+        Ok(buffer)
+    }
+
+    /// Returns the location of the memory of the buffer.
+    #[inline]
+    pub fn location(&self) -> BufferLocation {
+        self.location
+    }
+
+    /// Returns the size of the buffer in bytes.
+    #[inline]
+    pub fn size(&self) -> DeviceSize {
+        self.inner.size()
+    }
+
+    /// Returns the usage the buffer was created with.
+    #[inline]
+    pub fn usage(&self) -> &BufferUsage {
+        self.inner.usage()
+    }
+
+    /// Sets or removes the tag of the memory allocation of the buffer, which is used to group
+    /// allocations in a [`StdMemoryPool::report`](crate::memory::pool::StdMemoryPool::report).
+    ///
+    /// Dedicated allocations are not part of the pool's report, so the tag is ignored if the
+    /// buffer has one.
+    #[inline]
+    pub fn set_memory_tag(&self, tag: Option<&str>) {
+        if let PotentialDedicatedAllocation::Generic(alloc) = &self.memory {
+            alloc.set_tag(tag);
+        }
+    }
+
+    /// Returns a subbuffer of bytes covering the whole buffer.
+    #[inline]
+    pub fn subbuffer(self: &Arc<Self>) -> Arc<Subbuffer<[u8]>> {
+        Arc::new(Subbuffer::from_buffer(self.clone()))
+    }
+
+    // Returns the range of the mapped memory that must be invalidated or flushed to access
+    // `range` of the buffer. It is extended to multiples of the non-coherent atom size.
+    fn memory_range(&self, range: &Range<DeviceSize>) -> Range<DeviceSize> {
+        let offset = self.memory.offset();
+        let atom_size = self
+            .device()
+            .physical_device()
+            .properties()
+            .non_coherent_atom_size;
+
+        let start = (offset + range.start) / atom_size * atom_size;
+        let end = min(
+            (offset + range.end + atom_size - 1) / atom_size * atom_size,
+            self.memory.memory().allocation_size(),
+        );
+
+        start..end
+    }
+}
+
+unsafe impl DeviceOwned for Buffer {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.inner.device()
+    }
+}
+
+impl PartialEq for Buffer {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl Eq for Buffer {}
+
+impl Hash for Buffer {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+    }
+}
+
+/// Parameters to create a new `Buffer`.
+#[derive(Clone, Debug)]
+pub struct BufferCreateInfo {
+    /// Where the memory of the buffer is allocated.
+    ///
+    /// The default value is [`BufferLocation::Device`].
+    pub location: BufferLocation,
+
+    /// Whether the buffer can be shared across multiple queues, or is limited to a single queue.
+    ///
+    /// The default value is [`Sharing::Exclusive`].
+    pub sharing: Sharing<SmallVec<[u32; 4]>>,
+
+    /// The size in bytes of the buffer.
+    ///
+    /// The default value is `0`, which must be overridden.
+    pub size: DeviceSize,
+
+    /// How the buffer is going to be used.
+    ///
+    /// The default value is [`BufferUsage::none()`], which must be overridden.
+    pub usage: BufferUsage,
+
+    /// How the memory type of the buffer's memory is chosen.
+    ///
+    /// If `None`, the preferred memory type depends on `location`. If `Some`, the policy replaces
+    /// those preferences. For example, on devices with unified memory, it can be used to require
+    /// a host-visible memory type for a buffer whose location is [`BufferLocation::Device`]. If
+    /// `location` is host-accessible, then non-host-visible memory types are always excluded.
+    ///
+    /// The default value is `None`.
+    pub memory_type_policy: Option<MemoryTypePolicy>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for BufferCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            location: BufferLocation::Device,
+            sharing: Sharing::Exclusive,
+            size: 0,
+            usage: BufferUsage::none(),
+            memory_type_policy: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Where the memory of a buffer is allocated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BufferLocation {
+    /// The memory is preferably device-local, and can't be accessed by the host. This gives the
+    /// fastest access from the device, but the content must be transferred from another buffer.
+    Device,
+
+    /// The memory is host-accessible, and preferably not host-cached. This is suited to data that
+    /// is written by the host and read by the device.
+    Upload,
+
+    /// The memory is host-accessible, and preferably host-cached. This is suited to data that is
+    /// written by the device and read back by the host.
+    Download,
+}
+
+impl BufferLocation {
+    /// Returns whether the memory can be accessed by the host.
+    #[inline]
+    pub fn is_host_accessible(&self) -> bool {
+        !matches!(self, BufferLocation::Device)
+    }
+}
+
+/// A typed range of a [`Buffer`].
+///
+/// Subbuffers are cheap to create, and they only hold a reference to the buffer that they come
+/// from. They can be used in commands like any other buffer.
+#[derive(Debug)]
+pub struct Subbuffer<T: ?Sized> {
+    marker: PhantomData<Box<T>>,
+    buffer: Arc<Buffer>,
+    offset: DeviceSize,
+    size: DeviceSize,
+}
+
+// We need to implement `Clone` manually, otherwise the derive adds a `T: Clone` requirement.
+impl<T: ?Sized> Clone for Subbuffer<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Subbuffer {
+            marker: PhantomData,
+            buffer: self.buffer.clone(),
+            offset: self.offset,
+            size: self.size,
+        }
+    }
+}
+
+impl<T: ?Sized> Subbuffer<T> {
+    #[inline]
+    fn from_buffer(buffer: Arc<Buffer>) -> Self {
+        let size = buffer.size();
+
+        Subbuffer {
+            marker: PhantomData,
+            buffer,
+            offset: 0,
+            size,
+        }
+    }
+
+    #[inline]
+    pub(super) fn from_range(
+        buffer: Arc<Buffer>,
+        offset: DeviceSize,
+        size: DeviceSize,
+    ) -> Arc<Self> {
+        debug_assert!(offset + size <= buffer.size());
+
+        Arc::new(Subbuffer {
+            marker: PhantomData,
+            buffer,
+            offset,
+            size,
+        })
+    }
+
+    #[inline]
+    fn subrange<U: ?Sized>(&self, offset: DeviceSize, size: DeviceSize) -> Arc<Subbuffer<U>> {
+        debug_assert!(offset + size <= self.size);
+
+        Arc::new(Subbuffer {
+            marker: PhantomData,
+            buffer: self.buffer.clone(),
+            offset: self.offset + offset,
+            size,
+        })
+    }
+
+    /// Returns the buffer that this subbuffer belongs to.
+    #[inline]
+    pub fn buffer(&self) -> &Arc<Buffer> {
+        &self.buffer
+    }
+
+    /// Returns the offset in bytes of the subbuffer within the buffer.
+    #[inline]
+    pub fn offset(&self) -> DeviceSize {
+        self.offset
+    }
+
+    /// Returns the size in bytes of the subbuffer.
+    #[inline]
+    pub fn size(&self) -> DeviceSize {
+        self.size
+    }
+
+    /// Returns the same range of the buffer as a subbuffer of bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> Arc<Subbuffer<[u8]>> {
+        self.subrange(0, self.size)
+    }
+}
+
+impl<T> Subbuffer<T>
+where
+    T: BufferContents + ?Sized,
+{
+    /// Locks the subbuffer in order to read its content from the host.
+    ///
+    /// If the range of the subbuffer is currently written by the device or locked for writing by
+    /// the host, this function will return an error. Other ranges of the same buffer are
+    /// unaffected.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the buffer is not host-accessible.
+    /// - Panics if the content of the subbuffer is not a valid `T`, for example if it was cast to
+    ///   a type of a different size.
+    pub fn read(&self) -> Result<SubbufferReadGuard<T>, ReadLockError> {
+        assert!(
+            self.buffer.location.is_host_accessible(),
+            "the buffer must be host-accessible to be read by the host"
+        );
+
+        let range = self.offset..self.offset + self.size;
+
+        {
+            let mut state = self.buffer.inner.state();
+
+            unsafe {
+                state.check_cpu_read(range.clone())?;
+                state.cpu_read_lock(range.clone());
+            }
+        }
+
+        let mapped_memory = self.buffer.memory.mapped_memory().unwrap();
+        let memory_range = self.buffer.memory_range(&range);
+        let start = self.buffer.memory.offset() + self.offset;
+
+        let bytes = unsafe {
+            // See `CpuAccessibleBuffer::read` for why it's fine to invalidate with other read
+            // locks being held.
+            mapped_memory
+                .invalidate_range(memory_range.clone())
+                .unwrap();
+            mapped_memory.read(start..start + self.size).unwrap()
+        };
+
+        Ok(SubbufferReadGuard {
+            subbuffer: self,
+            range,
+            data: T::from_bytes(bytes).unwrap(),
+        })
+    }
+
+    /// Locks the subbuffer in order to write its content from the host.
+    ///
+    /// If the range of the subbuffer is currently in use by the device or locked by the host, this
+    /// function will return an error. Other ranges of the same buffer are unaffected.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the buffer is not host-accessible.
+    /// - Panics if the content of the subbuffer is not a valid `T`, for example if it was cast to
+    ///   a type of a different size.
+    pub fn write(&self) -> Result<SubbufferWriteGuard<T>, WriteLockError> {
+        assert!(
+            self.buffer.location.is_host_accessible(),
+            "the buffer must be host-accessible to be written by the host"
+        );
+
+        let range = self.offset..self.offset + self.size;
+
+        {
+            let mut state = self.buffer.inner.state();
+
+            unsafe {
+                state.check_cpu_write(range.clone())?;
+                state.cpu_write_lock(range.clone());
+            }
+        }
+
+        let mapped_memory = self.buffer.memory.mapped_memory().unwrap();
+        let memory_range = self.buffer.memory_range(&range);
+        let start = self.buffer.memory.offset() + self.offset;
+
+        let bytes = unsafe {
+            mapped_memory
+                .invalidate_range(memory_range.clone())
+                .unwrap();
+            mapped_memory.write(start..start + self.size).unwrap()
+        };
+
+        Ok(SubbufferWriteGuard {
+            subbuffer: self,
+            range,
+            memory_range,
+            data: T::from_bytes_mut(bytes).unwrap(),
+        })
+    }
+}
+
+/// The future returned by [`Subbuffer::update`] and [`Subbuffer::update_range`].
+pub type SubbufferUpdateFuture = CommandBufferExecFuture<NowFuture, PrimaryAutoCommandBuffer>;
+
+impl<T> Subbuffer<T>
+where
+    T: BufferContents,
+{
+    /// Replaces the content of the subbuffer with `data`.
+    ///
+    /// This function builds a host-accessible intermediate buffer, writes the data to it, builds
+    /// a command buffer that copies from this intermediate buffer to `self`, and finally submits
+    /// the command buffer as a future. Operations that use the new content must be submitted
+    /// after this future. Contrary to [`write`](Subbuffer::write), this works whatever the
+    /// location of the buffer.
+    ///
+    /// The buffer must have been created with the `transfer_dst` usage.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    pub fn update(
+        self: &Arc<Self>,
+        data: T,
+        queue: Arc<Queue>,
+    ) -> Result<SubbufferUpdateFuture, BufferUpdateError> {
+        let source = Buffer::from_data(
+            self.device().clone(),
+            BufferUsage::transfer_src(),
+            BufferLocation::Upload,
+            data,
+        )?;
+
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            self.device().clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )?;
+        cbb.copy_buffer(CopyBufferInfo::buffers(source, self.clone()))?;
+        let cb = cbb.build()?;
+
+        Ok(cb.execute(queue)?)
+    }
+}
+
+impl<T> Subbuffer<[T]>
+where
+    [T]: BufferContents,
+{
+    /// Replaces the elements of the subbuffer starting at `first_element` with the elements of
+    /// `data`. The rest of the subbuffer is left untouched.
+    ///
+    /// This works like [`update`](Subbuffer::update), but the intermediate buffer only holds
+    /// `data`.
+    ///
+    /// The buffer must have been created with the `transfer_dst` usage, and the range must fit
+    /// inside the subbuffer.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    /// - Panics if `data` is empty.
+    pub fn update_range<D>(
+        self: &Arc<Self>,
+        first_element: DeviceSize,
+        data: D,
+        queue: Arc<Queue>,
+    ) -> Result<SubbufferUpdateFuture, BufferUpdateError>
+    where
+        D: IntoIterator<Item = T>,
+        D::IntoIter: ExactSizeIterator,
+    {
+        let source = Buffer::from_iter(
+            self.device().clone(),
+            BufferUsage::transfer_src(),
+            BufferLocation::Upload,
+            data,
+        )?;
+        let len = source.len();
+
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            self.device().clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )?;
+        cbb.copy_buffer(CopyBufferInfoTyped {
+            regions: smallvec![BufferCopy {
+                src_offset: 0,
+                dst_offset: first_element,
+                size: len,
+                ..Default::default()
+            }],
+            ..CopyBufferInfoTyped::buffers(source, self.clone())
+        })?;
+        let cb = cbb.build()?;
+
+        Ok(cb.execute(queue)?)
+    }
+}
+
+impl<T> Subbuffer<[T]> {
+    /// Returns the number of elements in the subbuffer.
+    #[inline]
+    pub fn len(&self) -> DeviceSize {
+        debug_assert_eq!(self.size % size_of::<T>() as DeviceSize, 0);
+        self.size / size_of::<T>() as DeviceSize
+    }
+
+    /// Reduces the subbuffer to just one element of the array.
+    ///
+    /// Returns `None` if out of range.
+    #[inline]
+    pub fn index(&self, index: DeviceSize) -> Option<Arc<Subbuffer<T>>> {
+        if index >= self.len() {
+            return None;
+        }
+
+        Some(self.subrange(
+            index * size_of::<T>() as DeviceSize,
+            size_of::<T>() as DeviceSize,
+        ))
+    }
+
+    /// Reduces the subbuffer to just a range of the array.
+    ///
+    /// Returns `None` if out of range or if the range is empty.
+    #[inline]
+    pub fn slice(&self, range: Range<DeviceSize>) -> Option<Arc<Subbuffer<[T]>>> {
+        if range.start >= range.end || range.end > self.len() {
+            return None;
+        }
+
+        Some(self.subrange(
+            range.start * size_of::<T>() as DeviceSize,
+            (range.end - range.start) * size_of::<T>() as DeviceSize,
+        ))
+    }
+
+    /// Splits the subbuffer into two at an index. The first subbuffer contains the elements
+    /// `[0, mid)` and the second contains `[mid, len)`.
+    ///
+    /// Returns `None` if `mid` is zero or not less than the length, as the subbuffers can't be
+    /// empty.
+    #[inline]
+    pub fn split_at(&self, mid: DeviceSize) -> Option<(Arc<Subbuffer<[T]>>, Arc<Subbuffer<[T]>>)> {
+        if mid == 0 || mid >= self.len() {
+            return None;
+        }
+
+        Some((self.slice(0..mid)?, self.slice(mid..self.len())?))
+    }
+}
+
+impl Subbuffer<[u8]> {
+    /// Casts the subbuffer to a single `T`.
+    ///
+    /// Returns `None` if the size of the subbuffer is not the size of `T`, or if the offset of the
+    /// subbuffer within the buffer is not a multiple of the alignment of `T`.
+    #[inline]
+    pub fn cast_sized<T>(&self) -> Option<Arc<Subbuffer<T>>>
+    where
+        T: Pod,
+    {
+        if self.size != size_of::<T>() as DeviceSize
+            || self.offset % align_of::<T>() as DeviceSize != 0
+        {
+            return None;
+        }
+
+        Some(self.subrange(0, self.size))
+    }
+
+    /// Casts the subbuffer to an array of `T`.
+    ///
+    /// Returns `None` if the size of the subbuffer is not a multiple of the size of `T`, or if the
+    /// offset of the subbuffer within the buffer is not a multiple of the alignment of `T`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    #[inline]
+    pub fn cast_slice<T>(&self) -> Option<Arc<Subbuffer<[T]>>>
+    where
+        T: Pod,
+    {
+        assert!(size_of::<T>() != 0);
+
+        if self.size % size_of::<T>() as DeviceSize != 0
+            || self.offset % align_of::<T>() as DeviceSize != 0
+        {
+            return None;
+        }
+
+        Some(self.subrange(0, self.size))
+    }
+}
+
+unsafe impl<T> BufferAccess for Subbuffer<T>
+where
+    T: Send + Sync + ?Sized,
+{
+    #[inline]
+    fn inner(&self) -> BufferInner {
+        BufferInner {
+            buffer: &self.buffer.inner,
+            offset: self.offset,
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> DeviceSize {
+        self.size
+    }
+}
+
+impl<T> BufferAccessObject for Arc<Subbuffer<T>>
+where
+    T: Send + Sync + ?Sized + 'static,
+{
+    #[inline]
+    fn as_buffer_access_object(&self) -> Arc<dyn BufferAccess> {
+        self.clone()
+    }
+}
+
+unsafe impl<T> TypedBufferAccess for Subbuffer<T>
+where
+    T: BufferContents + ?Sized,
+{
+    type Content = T;
+}
+
+unsafe impl<T: ?Sized> DeviceOwned for Subbuffer<T> {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.buffer.device()
+    }
+}
+
+impl<T: ?Sized> PartialEq for Subbuffer<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.buffer == other.buffer && self.offset == other.offset && self.size == other.size
+    }
+}
+
+impl<T: ?Sized> Eq for Subbuffer<T> {}
+
+impl<T: ?Sized> Hash for Subbuffer<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.buffer.hash(state);
+        self.offset.hash(state);
+        self.size.hash(state);
+    }
+}
+
+/// Object that can be used to read the content of a `Subbuffer`.
+///
+/// While this object exists, the range of the subbuffer can't be written by the host or the
+/// device.
+#[derive(Debug)]
+pub struct SubbufferReadGuard<'a, T>
+where
+    T: BufferContents + ?Sized,
+{
+    subbuffer: &'a Subbuffer<T>,
+    range: Range<DeviceSize>,
+    data: &'a T,
+}
+
+impl<'a, T> Drop for SubbufferReadGuard<'a, T>
+where
+    T: BufferContents + ?Sized,
+{
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let mut state = self.subbuffer.buffer.inner.state();
+            state.cpu_read_unlock(self.range.clone());
+        }
+    }
+}
+
+impl<'a, T> Deref for SubbufferReadGuard<'a, T>
+where
+    T: BufferContents + ?Sized,
+{
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.data
+    }
+}
+
+/// Object that can be used to read or write the content of a `Subbuffer`.
+///
+/// While this object exists, the range of the subbuffer can't be accessed by the device or by
+/// other host locks.
+#[derive(Debug)]
+pub struct SubbufferWriteGuard<'a, T>
+where
+    T: BufferContents + ?Sized,
+{
+    subbuffer: &'a Subbuffer<T>,
+    range: Range<DeviceSize>,
+    memory_range: Range<DeviceSize>,
+    data: &'a mut T,
+}
+
+impl<'a, T> Drop for SubbufferWriteGuard<'a, T>
+where
+    T: BufferContents + ?Sized,
+{
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            self.subbuffer
+                .buffer
+                .memory
+                .mapped_memory()
+                .unwrap()
+                .flush_range(self.memory_range.clone())
+                .unwrap();
+
+            let mut state = self.subbuffer.buffer.inner.state();
+            state.cpu_write_unlock(self.range.clone());
+        }
+    }
+}
+
+impl<'a, T> Deref for SubbufferWriteGuard<'a, T>
+where
+    T: BufferContents + ?Sized,
+{
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.data
+    }
+}
+
+impl<'a, T> DerefMut for SubbufferWriteGuard<'a, T>
+where
+    T: BufferContents + ?Sized,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        self.data
+    }
+}
+
+/// Error that can happen when updating the content of a subbuffer from the host.
+#[derive(Clone, Debug)]
+pub enum BufferUpdateError {
+    BufferCreationError(BufferCreationError),
+    CommandBufferBeginError(CommandBufferBeginError),
+    CopyError(CopyError),
+    BuildError(BuildError),
+    CommandBufferExecError(CommandBufferExecError),
+}
+
+impl error::Error for BufferUpdateError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::BufferCreationError(err) => Some(err),
+            Self::CommandBufferBeginError(err) => Some(err),
+            Self::CopyError(err) => Some(err),
+            Self::BuildError(err) => Some(err),
+            Self::CommandBufferExecError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for BufferUpdateError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferCreationError(err) => err.fmt(f),
+            Self::CommandBufferBeginError(err) => err.fmt(f),
+            Self::CopyError(err) => err.fmt(f),
+            Self::BuildError(err) => err.fmt(f),
+            Self::CommandBufferExecError(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<BufferCreationError> for BufferUpdateError {
+    #[inline]
+    fn from(err: BufferCreationError) -> Self {
+        Self::BufferCreationError(err)
+    }
+}
+
+impl From<OomError> for BufferUpdateError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::BufferCreationError(err.into())
+    }
+}
+
+impl From<CommandBufferBeginError> for BufferUpdateError {
+    #[inline]
+    fn from(err: CommandBufferBeginError) -> Self {
+        Self::CommandBufferBeginError(err)
+    }
+}
+
+impl From<CopyError> for BufferUpdateError {
+    #[inline]
+    fn from(err: CopyError) -> Self {
+        Self::CopyError(err)
+    }
+}
+
+impl From<BuildError> for BufferUpdateError {
+    #[inline]
+    fn from(err: BuildError) -> Self {
+        Self::BuildError(err)
+    }
+}
+
+impl From<CommandBufferExecError> for BufferUpdateError {
+    #[inline]
+    fn from(err: CommandBufferExecError) -> Self {
+        Self::CommandBufferExecError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Buffer, BufferCreateInfo, BufferLocation, BufferUpdateError};
+    use crate::{
+        buffer::{BufferUsage, HeaderSlice, TypedBufferAccess},
+        command_buffer::{
+            AutoCommandBufferBuilder, CommandBufferUsage, CopyBufferInfo, PrimaryCommandBuffer,
+        },
+        memory::pool::{MemoryPoolAlloc, MemoryPropertyFlags, MemoryTypePolicy},
+        sync::GpuFuture,
+    };
+
+    #[test]
+    fn split_and_write() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let subbuffer = Buffer::from_iter(
+            device,
+            BufferUsage::all(),
+            BufferLocation::Upload,
+            [1u32, 2, 3, 4],
+        )
+        .unwrap();
+
+        let (first, second) = subbuffer.split_at(1).unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(second.len(), 3);
+        assert_eq!(second.offset(), 4);
+
+        // Disjoint ranges of the same buffer can be locked at the same time.
+        let read = first.read().unwrap();
+        second.write().unwrap()[0] = 5;
+        assert_eq!(&*read, &[1]);
+        drop(read);
+
+        assert!(subbuffer.split_at(0).is_none());
+        assert_eq!(&*subbuffer.read().unwrap(), &[1, 5, 3, 4]);
+    }
+
+    #[test]
+    fn cast_bytes() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let subbuffer =
+            Buffer::new_slice::<u8>(device, BufferUsage::all(), BufferLocation::Download, 16)
+                .unwrap();
+
+        assert!(subbuffer.cast_sized::<[u32; 4]>().is_some());
+        assert!(subbuffer.cast_sized::<u32>().is_none());
+        assert_eq!(subbuffer.cast_slice::<u32>().unwrap().len(), 4);
+        assert!(subbuffer
+            .slice(1..15)
+            .unwrap()
+            .cast_slice::<u32>()
+            .is_none());
+        assert_eq!(&*subbuffer.read().unwrap(), &[0; 16]);
+    }
+
+    #[test]
+    fn memory_type_policy() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let buffer = Buffer::new(
+            device,
+            BufferCreateInfo {
+                location: BufferLocation::Device,
+                size: 16,
+                usage: BufferUsage::all(),
+                memory_type_policy: Some(MemoryTypePolicy {
+                    required_flags: MemoryPropertyFlags {
+                        host_visible: true,
+                        ..MemoryPropertyFlags::none()
+                    },
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(buffer.memory.memory().memory_type().is_host_visible());
+    }
+
+    #[test]
+    fn from_header_iter() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let subbuffer = Buffer::from_header_iter(
+            device,
+            BufferUsage::all(),
+            BufferLocation::Upload,
+            [7u32; 2],
+            [1u32, 2, 3],
+        )
+        .unwrap();
+
+        let data: &HeaderSlice<[u32; 2], u32> = &subbuffer.read().unwrap();
+        assert_eq!(data.header, [7, 7]);
+        assert_eq!(&data.slice, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn update_range() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let subbuffer = Buffer::new_slice::<u32>(
+            device.clone(),
+            BufferUsage::all(),
+            BufferLocation::Device,
+            8,
+        )
+        .unwrap();
+
+        subbuffer
+            .update_range(0, [0u32; 8], queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+        subbuffer
+            .update_range(2, [5u32, 6, 7], queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        let destination = Buffer::new_slice::<u32>(
+            device.clone(),
+            BufferUsage::all(),
+            BufferLocation::Download,
+            8,
+        )
+        .unwrap();
+
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        cbb.copy_buffer(CopyBufferInfo::buffers(
+            subbuffer.clone(),
+            destination.clone(),
+        ))
+        .unwrap();
+        cbb.build()
+            .unwrap()
+            .execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        assert_eq!(&*destination.read().unwrap(), &[0, 0, 5, 6, 7, 0, 0, 0]);
+
+        assert!(matches!(
+            subbuffer.update_range(6, [1u32, 2, 3], queue),
+            Err(BufferUpdateError::CopyError(_))
+        ));
+    }
+}
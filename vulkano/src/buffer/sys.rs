@@ -30,7 +30,11 @@ use super::{
 };
 use crate::{
     check_errors,
-    device::{Device, DeviceOwned},
+    device::{
+        object_tracker::{TrackedObject, TrackedObjectType},
+        Device, DeviceOwned,
+    },
+    extension_chain::ExtensionChain,
     memory::{DeviceMemory, DeviceMemoryAllocationError, MemoryRequirements},
     sync::{AccessError, CurrentAccess, Sharing},
     DeviceSize, Error, OomError, Version, VulkanObject,
@@ -56,8 +60,10 @@ pub struct UnsafeBuffer {
 
     size: DeviceSize,
     usage: BufferUsage,
+    protected: bool,
 
     state: Mutex<BufferState>,
+    tracked: TrackedObject,
 }
 
 impl UnsafeBuffer {
@@ -78,6 +84,8 @@ impl UnsafeBuffer {
             size,
             sparse,
             usage,
+            protected,
+            extensions,
             _ne: _,
         } = create_info;
 
@@ -119,6 +127,23 @@ impl UnsafeBuffer {
             flags |= sparse_level.into();
         }
 
+        if protected {
+            // VUID-VkBufferCreateInfo-flags-01887
+            if !device.enabled_features().protected_memory {
+                return Err(BufferCreationError::FeatureNotEnabled {
+                    feature: "protected_memory",
+                    reason: "protected was set",
+                });
+            }
+
+            // VUID-VkBufferCreateInfo-None-01888
+            if sparse.is_some() {
+                return Err(BufferCreationError::SparseProtected);
+            }
+
+            flags |= ash::vk::BufferCreateFlags::PROTECTED;
+        }
+
         // Check sharing mode and queue families
         let (sharing_mode, queue_family_indices) = match &mut sharing {
             Sharing::Exclusive => (ash::vk::SharingMode::EXCLUSIVE, &[] as _),
@@ -150,33 +175,41 @@ impl UnsafeBuffer {
         }
 
         // Everything now ok. Creating the buffer.
-        let create_info = ash::vk::BufferCreateInfo::builder()
+        let mut create_info = ash::vk::BufferCreateInfo::builder()
             .flags(flags)
             .size(size)
             .usage(usage.into())
             .sharing_mode(sharing_mode)
-            .queue_family_indices(queue_family_indices);
+            .queue_family_indices(queue_family_indices)
+            .build();
 
         let handle = unsafe {
+            let _extensions_vk = extensions.link(&mut create_info.p_next);
+
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
             check_errors((fns.v1_0.create_buffer)(
                 device.internal_object(),
-                &create_info.build(),
+                &create_info,
                 ptr::null(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
+        let tracked = device.track_object(TrackedObjectType::Buffer);
         let buffer = UnsafeBuffer {
             handle,
             device,
 
             size,
             usage,
+            protected,
 
             state: Mutex::new(BufferState::new(size)),
+            tracked,
         };
 
         Ok(Arc::new(buffer))
@@ -254,7 +287,7 @@ impl UnsafeBuffer {
         if self.usage.uniform_texel_buffer || self.usage.storage_texel_buffer {
             memory_requirements.alignment = align(
                 memory_requirements.alignment,
-                properties.min_texel_buffer_offset_alignment,
+                self.texel_buffer_offset_alignment(),
             );
         }
 
@@ -298,11 +331,15 @@ impl UnsafeBuffer {
                 && mem_reqs.memory_type_bits & (1 << memory.memory_type().id()) != 0
         });
 
+        // VUID-vkBindBufferMemory-None-01898
+        // VUID-vkBindBufferMemory-None-01899
+        debug_assert!(memory.memory_type().is_protected() == self.protected);
+
         // Check for alignment correctness.
         {
             let properties = self.device().physical_device().properties();
             if self.usage().uniform_texel_buffer || self.usage().storage_texel_buffer {
-                debug_assert!(offset % properties.min_texel_buffer_offset_alignment == 0);
+                debug_assert!(offset % self.texel_buffer_offset_alignment() == 0);
             }
             if self.usage().storage_buffer {
                 debug_assert!(offset % properties.min_storage_buffer_offset_alignment == 0);
@@ -321,6 +358,39 @@ impl UnsafeBuffer {
         Ok(())
     }
 
+    // The offset alignment that buffer views of this buffer need. If the `texel_buffer_alignment`
+    // feature is enabled, the relaxed per-usage alignments are used instead of the coarse
+    // `min_texel_buffer_offset_alignment` limit.
+    fn texel_buffer_offset_alignment(&self) -> DeviceSize {
+        let properties = self.device.physical_device().properties();
+
+        if self.device.api_version() >= Version::V1_3
+            || self.device.enabled_features().texel_buffer_alignment
+        {
+            let mut alignment = 1;
+
+            if self.usage.storage_texel_buffer {
+                alignment = alignment.max(
+                    properties
+                        .storage_texel_buffer_offset_alignment_bytes
+                        .unwrap(),
+                );
+            }
+
+            if self.usage.uniform_texel_buffer {
+                alignment = alignment.max(
+                    properties
+                        .uniform_texel_buffer_offset_alignment_bytes
+                        .unwrap(),
+                );
+            }
+
+            alignment
+        } else {
+            properties.min_texel_buffer_offset_alignment
+        }
+    }
+
     pub(crate) fn state(&self) -> MutexGuard<BufferState> {
         self.state.lock()
     }
@@ -337,6 +407,12 @@ impl UnsafeBuffer {
         &self.usage
     }
 
+    /// Returns whether the buffer is a protected buffer.
+    #[inline]
+    pub fn protected(&self) -> bool {
+        self.protected
+    }
+
     /// Returns a key unique to each `UnsafeBuffer`. Can be used for the `conflicts_key` method.
     #[inline]
     pub fn key(&self) -> u64 {
@@ -410,6 +486,22 @@ pub struct UnsafeBufferCreateInfo {
     /// The default value is [`BufferUsage::none()`], which must be overridden.
     pub usage: BufferUsage,
 
+    /// Whether the buffer is a protected buffer, whose contents can only be accessed by protected
+    /// queue operations. It must be bound to memory of a protected memory type.
+    ///
+    /// If set to `true`, the [`protected_memory`](crate::device::Features::protected_memory)
+    /// feature must be enabled on the device, and `sparse` must be `None`.
+    ///
+    /// The default value is `false`.
+    pub protected: bool,
+
+    /// Extension structures that vulkano doesn't support, to add to the create info of the
+    /// buffer. See the [`extension_chain`](crate::extension_chain) module for more information.
+    /// If it is not empty, it can only be used to create one object.
+    ///
+    /// The default value is empty.
+    pub extensions: ExtensionChain<UnsafeBuffer>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -421,6 +513,8 @@ impl Default for UnsafeBufferCreateInfo {
             size: 0,
             sparse: None,
             usage: BufferUsage::none(),
+            protected: false,
+            extensions: ExtensionChain::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -447,6 +541,9 @@ pub enum BufferCreationError {
     /// The sharing mode was set to `Concurrent`, but one of the specified queue family ids was not
     /// valid.
     SharingInvalidQueueFamilyId { id: u32 },
+
+    /// The buffer was set to be both sparse and protected.
+    SparseProtected,
 }
 
 impl error::Error for BufferCreationError {
@@ -479,6 +576,9 @@ impl fmt::Display for BufferCreationError {
             Self::SharingInvalidQueueFamilyId { id } => {
                 write!(fmt, "the sharing mode was set to `Concurrent`, but one of the specified queue family ids was not valid")
             }
+            Self::SparseProtected => {
+                write!(fmt, "the buffer was set to be both sparse and protected")
+            }
         }
     }
 }
@@ -765,8 +865,14 @@ mod tests {
     use super::SparseLevel;
     use super::UnsafeBuffer;
     use super::UnsafeBufferCreateInfo;
+    use crate::device::physical::PhysicalDevice;
     use crate::device::Device;
+    use crate::device::DeviceCreateInfo;
+    use crate::device::DeviceExtensions;
     use crate::device::DeviceOwned;
+    use crate::device::Features;
+    use crate::device::QueueCreateInfo;
+    use crate::Version;
 
     #[test]
     fn create() {
@@ -855,6 +961,106 @@ mod tests {
         }
     }
 
+    #[test]
+    fn missing_feature_protected_memory() {
+        let (device, _) = gfx_dev_and_queue!();
+        match UnsafeBuffer::new(
+            device,
+            UnsafeBufferCreateInfo {
+                size: 128,
+                usage: BufferUsage::all(),
+                protected: true,
+                ..Default::default()
+            },
+        ) {
+            Err(BufferCreationError::FeatureNotEnabled {
+                feature: "protected_memory",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn texel_buffer_alignment_coarse() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device.api_version() >= Version::V1_3 {
+            return;
+        }
+
+        let buf = UnsafeBuffer::new(
+            device.clone(),
+            UnsafeBufferCreateInfo {
+                size: 128,
+                usage: BufferUsage {
+                    uniform_texel_buffer: true,
+                    ..BufferUsage::none()
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let min_alignment = device
+            .physical_device()
+            .properties()
+            .min_texel_buffer_offset_alignment;
+        assert_eq!(buf.texel_buffer_offset_alignment(), min_alignment);
+        assert_eq!(buf.memory_requirements().alignment % min_alignment, 0);
+    }
+
+    #[test]
+    fn texel_buffer_alignment_relaxed() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).find(|p| {
+            p.supported_extensions().ext_texel_buffer_alignment
+                && p.supported_features().texel_buffer_alignment
+        }) {
+            Some(p) => p,
+            None => return,
+        };
+
+        let (device, _) = Device::new(
+            physical,
+            DeviceCreateInfo {
+                enabled_extensions: DeviceExtensions {
+                    ext_texel_buffer_alignment: true,
+                    ..DeviceExtensions::none()
+                },
+                enabled_features: Features {
+                    texel_buffer_alignment: true,
+                    ..Features::none()
+                },
+                queue_create_infos: vec![QueueCreateInfo::family(
+                    physical.queue_families().next().unwrap(),
+                )],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let buf = UnsafeBuffer::new(
+            device,
+            UnsafeBufferCreateInfo {
+                size: 128,
+                usage: BufferUsage {
+                    storage_texel_buffer: true,
+                    ..BufferUsage::none()
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let alignment = physical
+            .properties()
+            .storage_texel_buffer_offset_alignment_bytes
+            .unwrap();
+        assert_eq!(buf.texel_buffer_offset_alignment(), alignment);
+        assert_eq!(buf.memory_requirements().alignment % alignment, 0);
+    }
+
     #[test]
     fn create_empty_buffer() {
         let (device, _) = gfx_dev_and_queue!();
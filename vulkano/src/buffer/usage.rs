@@ -27,6 +27,7 @@ pub struct BufferUsage {
     pub vertex_buffer: bool,
     pub indirect_buffer: bool,
     pub device_address: bool,
+    pub conditional_rendering: bool,
     pub _ne: crate::NonExhaustive,
 }
 
@@ -44,6 +45,7 @@ impl Default for BufferUsage {
             vertex_buffer: false,
             indirect_buffer: false,
             device_address: false,
+            conditional_rendering: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -64,6 +66,7 @@ impl BufferUsage {
             vertex_buffer: false,
             indirect_buffer: false,
             device_address: false,
+            conditional_rendering: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -82,6 +85,7 @@ impl BufferUsage {
             vertex_buffer: true,
             indirect_buffer: true,
             device_address: true,
+            conditional_rendering: true,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -200,6 +204,15 @@ impl BufferUsage {
             ..BufferUsage::none()
         }
     }
+
+    /// Builds a `BufferUsage` with `conditional_rendering` set to true and the rest to false.
+    #[inline]
+    pub const fn conditional_rendering() -> BufferUsage {
+        BufferUsage {
+            conditional_rendering: true,
+            ..BufferUsage::none()
+        }
+    }
 }
 
 impl From<BufferUsage> for ash::vk::BufferUsageFlags {
@@ -235,6 +248,9 @@ impl From<BufferUsage> for ash::vk::BufferUsageFlags {
         if val.device_address {
             result |= ash::vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS;
         }
+        if val.conditional_rendering {
+            result |= ash::vk::BufferUsageFlags::CONDITIONAL_RENDERING_EXT;
+        }
         result
     }
 }
@@ -255,6 +271,7 @@ impl BitOr for BufferUsage {
             vertex_buffer: self.vertex_buffer || rhs.vertex_buffer,
             indirect_buffer: self.indirect_buffer || rhs.indirect_buffer,
             device_address: self.device_address || rhs.device_address,
+            conditional_rendering: self.conditional_rendering || rhs.conditional_rendering,
             _ne: crate::NonExhaustive(()),
         }
     }
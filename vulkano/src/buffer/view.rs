@@ -216,6 +216,8 @@ where
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         Ok(Arc::new(BufferView {
             handle,
             buffer,
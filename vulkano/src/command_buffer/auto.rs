@@ -60,7 +60,7 @@ use std::{
 /// is built, however, it *does* implement `Send` and `Sync`.
 pub struct AutoCommandBufferBuilder<L, P = StandardCommandPoolBuilder> {
     pub(super) inner: SyncCommandBufferBuilder,
-    pool_builder_alloc: P, // Safety: must be dropped after `inner`
+    pub(super) pool_builder_alloc: P, // Safety: must be dropped after `inner`
 
     // The queue family that this command buffer is being created for.
     queue_family_id: u32,
@@ -75,9 +75,22 @@ pub struct AutoCommandBufferBuilder<L, P = StandardCommandPoolBuilder> {
     // If we're inside a render pass, contains the render pass state.
     pub(super) render_pass_state: Option<RenderPassState>,
 
+    // If the last render pass instance begun with `begin_rendering` was suspended, contains its
+    // state until it is resumed.
+    pub(super) suspended_rendering_state: Option<BeginRenderingState>,
+
     // If any queries are active, this hashmap contains their state.
     pub(super) query_state: HashMap<ash::vk::QueryType, QueryState>,
 
+    // If conditional rendering is active, contains its state.
+    pub(super) conditional_rendering_state: Option<ConditionalRenderingState>,
+
+    // Whether the profiling lock of the device was held when recording began.
+    pub(super) profiling_lock_held: bool,
+
+    // The performance query pool that has been used in the command buffer, if any.
+    pub(super) performance_query_pool: Option<ash::vk::QueryPool>,
+
     _data: PhantomData<L>,
 }
 
@@ -119,6 +132,7 @@ pub(super) struct BeginRenderingState {
     pub(super) color_attachments: Vec<Option<RenderingAttachmentInfo>>,
     pub(super) depth_attachment: Option<RenderingAttachmentInfo>,
     pub(super) stencil_attachment: Option<RenderingAttachmentInfo>,
+    pub(super) suspending: bool,
 }
 
 // The state of an active query.
@@ -130,6 +144,13 @@ pub(super) struct QueryState {
     pub(super) in_subpass: bool,
 }
 
+// The state of active conditional rendering.
+pub(super) struct ConditionalRenderingState {
+    // The index of the subpass in which conditional rendering was begun, or `None` if it was
+    // begun outside a render pass.
+    pub(super) render_pass_subpass: Option<u32>,
+}
+
 impl AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, StandardCommandPoolBuilder> {
     /// Starts recording a primary command buffer.
     #[inline]
@@ -151,6 +172,36 @@ impl AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, StandardCommandPoolBuild
                     inheritance_info: None,
                     _ne: crate::NonExhaustive(()),
                 },
+                false,
+            )
+        }
+    }
+
+    /// Starts recording a protected primary command buffer, which can access protected resources.
+    ///
+    /// The [`protected_memory`](crate::device::Features::protected_memory) feature must be
+    /// enabled on the device. The command buffer is submitted as a protected submission, so it
+    /// can only be executed on a [protected-capable](crate::device::Queue::is_protected) queue.
+    #[inline]
+    pub fn primary_protected(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        usage: CommandBufferUsage,
+    ) -> Result<
+        AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, StandardCommandPoolBuilder>,
+        CommandBufferBeginError,
+    > {
+        unsafe {
+            AutoCommandBufferBuilder::begin(
+                device,
+                queue_family,
+                CommandBufferLevel::Primary,
+                CommandBufferBeginInfo {
+                    usage,
+                    inheritance_info: None,
+                    _ne: crate::NonExhaustive(()),
+                },
+                true,
             )
         }
     }
@@ -178,6 +229,38 @@ impl AutoCommandBufferBuilder<SecondaryAutoCommandBuffer, StandardCommandPoolBui
                     inheritance_info: Some(inheritance_info),
                     _ne: crate::NonExhaustive(()),
                 },
+                false,
+            )?)
+        }
+    }
+
+    /// Starts recording a protected secondary command buffer, which can access protected
+    /// resources.
+    ///
+    /// The [`protected_memory`](crate::device::Features::protected_memory) feature must be
+    /// enabled on the device. The command buffer can only be executed by a protected primary
+    /// command buffer.
+    #[inline]
+    pub fn secondary_protected(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        usage: CommandBufferUsage,
+        inheritance_info: CommandBufferInheritanceInfo,
+    ) -> Result<
+        AutoCommandBufferBuilder<SecondaryAutoCommandBuffer, StandardCommandPoolBuilder>,
+        CommandBufferBeginError,
+    > {
+        unsafe {
+            Ok(AutoCommandBufferBuilder::begin(
+                device,
+                queue_family,
+                CommandBufferLevel::Secondary,
+                CommandBufferBeginInfo {
+                    usage,
+                    inheritance_info: Some(inheritance_info),
+                    _ne: crate::NonExhaustive(()),
+                },
+                true,
             )?)
         }
     }
@@ -192,9 +275,12 @@ impl<L> AutoCommandBufferBuilder<L, StandardCommandPoolBuilder> {
         queue_family: QueueFamily,
         level: CommandBufferLevel,
         begin_info: CommandBufferBeginInfo,
+        protected: bool,
     ) -> Result<AutoCommandBufferBuilder<L, StandardCommandPoolBuilder>, CommandBufferBeginError>
     {
-        Self::validate_begin(&device, &queue_family, level, &begin_info)?;
+        trace_span!("begin_command_buffer", ?level);
+
+        Self::validate_begin(&device, &queue_family, level, &begin_info, protected)?;
 
         let &CommandBufferBeginInfo {
             usage,
@@ -239,10 +325,16 @@ impl<L> AutoCommandBufferBuilder<L, StandardCommandPoolBuilder> {
             }
         }
 
-        let pool_builder_alloc = Device::standard_command_pool(&device, queue_family)
+        let pool = if protected {
+            Device::standard_protected_command_pool(&device, queue_family)
+        } else {
+            Device::standard_command_pool(&device, queue_family)
+        };
+        let pool_builder_alloc = pool
             .allocate(level, 1)?
             .next()
             .expect("Requested one command buffer from the command pool, but got zero.");
+        let profiling_lock_held = device.is_profiling_lock_held();
         let inner = SyncCommandBufferBuilder::new(pool_builder_alloc.inner(), begin_info)?;
 
         Ok(AutoCommandBufferBuilder {
@@ -250,7 +342,11 @@ impl<L> AutoCommandBufferBuilder<L, StandardCommandPoolBuilder> {
             pool_builder_alloc,
             queue_family_id: queue_family.id(),
             render_pass_state,
+            suspended_rendering_state: None,
             query_state: HashMap::default(),
+            conditional_rendering_state: None,
+            profiling_lock_held,
+            performance_query_pool: None,
             inheritance_info,
             usage,
             _data: PhantomData,
@@ -262,10 +358,19 @@ impl<L> AutoCommandBufferBuilder<L, StandardCommandPoolBuilder> {
         queue_family: &QueueFamily,
         level: CommandBufferLevel,
         begin_info: &CommandBufferBeginInfo,
+        protected: bool,
     ) -> Result<(), CommandBufferBeginError> {
         let physical_device = device.physical_device();
         let properties = physical_device.properties();
 
+        // VUID-VkCommandPoolCreateInfo-flags-02860
+        if protected && !device.enabled_features().protected_memory {
+            return Err(CommandBufferBeginError::FeatureNotEnabled {
+                feature: "protected_memory",
+                reason: "tried to record a protected command buffer",
+            });
+        }
+
         let &CommandBufferBeginInfo {
             usage,
             ref inheritance_info,
@@ -545,6 +650,8 @@ where
     /// Builds the command buffer.
     #[inline]
     pub fn build(self) -> Result<PrimaryAutoCommandBuffer<P::Alloc>, BuildError> {
+        trace_span!("build_command_buffer", level = "primary");
+
         if self.render_pass_state.is_some() {
             return Err(AutoCommandBufferBuilderContextError::ForbiddenInsideRenderPass.into());
         }
@@ -553,6 +660,11 @@ where
             return Err(AutoCommandBufferBuilderContextError::QueryIsActive.into());
         }
 
+        // VUID-vkEndCommandBuffer-None-01978
+        if self.conditional_rendering_state.is_some() {
+            return Err(AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive.into());
+        }
+
         let submit_state = match self.usage {
             CommandBufferUsage::MultipleSubmit => SubmitState::ExclusiveUse {
                 in_use: AtomicBool::new(false),
@@ -578,10 +690,17 @@ where
     /// Builds the command buffer.
     #[inline]
     pub fn build(self) -> Result<SecondaryAutoCommandBuffer<P::Alloc>, BuildError> {
+        trace_span!("build_command_buffer", level = "secondary");
+
         if !self.query_state.is_empty() {
             return Err(AutoCommandBufferBuilderContextError::QueryIsActive.into());
         }
 
+        // VUID-vkEndCommandBuffer-None-01978
+        if self.conditional_rendering_state.is_some() {
+            return Err(AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive.into());
+        }
+
         let submit_state = match self.usage {
             CommandBufferUsage::MultipleSubmit => SubmitState::ExclusiveUse {
                 in_use: AtomicBool::new(false),
@@ -704,7 +823,7 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
     }
 
     #[inline]
-    pub(super) fn queue_family(&self) -> QueueFamily {
+    pub(crate) fn queue_family(&self) -> QueueFamily {
         self.device()
             .physical_device()
             .queue_family_by_id(self.queue_family_id)
@@ -1128,6 +1247,8 @@ pub enum AutoCommandBufferBuilderContextError {
     },
     /// A query is active that conflicts with the current operation.
     QueryIsActive,
+    /// Conditional rendering is active, and must be ended first.
+    ConditionalRenderingIsActive,
     /// This query was not active.
     QueryNotActive,
     /// A query is active that is not included in the `inheritance` of the secondary command buffer.
@@ -1173,6 +1294,9 @@ impl fmt::Display for AutoCommandBufferBuilderContextError {
                 AutoCommandBufferBuilderContextError::QueryIsActive => {
                     "a query is active that conflicts with the current operation"
                 }
+                AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive => {
+                    "conditional rendering is active, and must be ended first"
+                }
                 AutoCommandBufferBuilderContextError::QueryNotActive => {
                     "this query was not active"
                 }
@@ -1197,7 +1321,7 @@ impl fmt::Display for AutoCommandBufferBuilderContextError {
 mod tests {
     use super::*;
     use crate::{
-        buffer::{BufferUsage, CpuAccessibleBuffer},
+        buffer::{Buffer, BufferLocation, BufferUsage},
         command_buffer::{BufferCopy, CopyBufferInfoTyped, CopyError, ExecuteCommandsError},
         device::{physical::PhysicalDevice, DeviceCreateInfo, QueueCreateInfo},
     };
@@ -1227,18 +1351,18 @@ mod tests {
 
         let queue = queues.next().unwrap();
 
-        let source = CpuAccessibleBuffer::from_iter(
+        let source = Buffer::from_iter(
             device.clone(),
             BufferUsage::all(),
-            true,
+            BufferLocation::Download,
             [1_u32, 2].iter().copied(),
         )
         .unwrap();
 
-        let destination = CpuAccessibleBuffer::from_iter(
+        let destination = Buffer::from_iter(
             device.clone(),
             BufferUsage::all(),
-            true,
+            BufferLocation::Download,
             [0_u32, 10, 20, 3, 4].iter().copied(),
         )
         .unwrap();
@@ -1352,10 +1476,10 @@ mod tests {
     fn buffer_self_copy_overlapping() {
         let (device, queue) = gfx_dev_and_queue!();
 
-        let source = CpuAccessibleBuffer::from_iter(
+        let source = Buffer::from_iter(
             device.clone(),
             BufferUsage::all(),
-            true,
+            BufferLocation::Download,
             [0_u32, 1, 2, 3].iter().copied(),
         )
         .unwrap();
@@ -1398,10 +1522,10 @@ mod tests {
     fn buffer_self_copy_not_overlapping() {
         let (device, queue) = gfx_dev_and_queue!();
 
-        let source = CpuAccessibleBuffer::from_iter(
+        let source = Buffer::from_iter(
             device.clone(),
             BufferUsage::all(),
-            true,
+            BufferLocation::Download,
             [0_u32, 1, 2, 3].iter().copied(),
         )
         .unwrap();
@@ -1413,20 +1537,37 @@ mod tests {
         )
         .unwrap();
 
+        match builder.copy_buffer(CopyBufferInfoTyped {
+            regions: [BufferCopy {
+                src_offset: 0,
+                dst_offset: 1,
+                size: 2,
+                ..Default::default()
+            }]
+            .into(),
+            ..CopyBufferInfoTyped::buffers(source.clone(), source.clone())
+        }) {
+            Err(CopyError::ValidationError(err)) => {
+                assert_eq!(err.code, "overlapping_regions");
+                assert!(err.has_vuid("VUID-VkCopyBufferInfo2-pRegions-00117"));
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn protected_requires_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
         assert!(matches!(
-            builder.copy_buffer(CopyBufferInfoTyped {
-                regions: [BufferCopy {
-                    src_offset: 0,
-                    dst_offset: 1,
-                    size: 2,
-                    ..Default::default()
-                }]
-                .into(),
-                ..CopyBufferInfoTyped::buffers(source.clone(), source.clone())
-            }),
-            Err(CopyError::OverlappingRegions {
-                src_region_index: 0,
-                dst_region_index: 0,
+            AutoCommandBufferBuilder::primary_protected(
+                device,
+                queue.family(),
+                CommandBufferUsage::OneTimeSubmit,
+            ),
+            Err(CommandBufferBeginError::FeatureNotEnabled {
+                feature: "protected_memory",
+                ..
             })
         ));
     }
@@ -0,0 +1,132 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    command_buffer::{
+        synced::{Command, SyncCommandBufferBuilder},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder,
+    },
+    device::DeviceOwned,
+};
+use std::{error, fmt};
+
+/// # Commands for diagnosing device loss.
+///
+/// These commands require the
+/// [`nv_device_diagnostic_checkpoints`](crate::device::DeviceExtensions::nv_device_diagnostic_checkpoints)
+/// extension to be enabled on the device.
+impl<L, P> AutoCommandBufferBuilder<L, P> {
+    /// Inserts a checkpoint with the value `marker`.
+    ///
+    /// If the device is lost, [`Queue::checkpoint_data`](crate::device::Queue::checkpoint_data)
+    /// returns the last checkpoints that the device reached on the queue, which tells which
+    /// commands were being executed.
+    #[inline]
+    pub fn set_checkpoint(&mut self, marker: u64) -> Result<&mut Self, CheckpointError> {
+        self.validate_set_checkpoint(marker)?;
+
+        unsafe {
+            self.inner.set_checkpoint(marker);
+        }
+
+        Ok(self)
+    }
+
+    fn validate_set_checkpoint(&self, marker: u64) -> Result<(), CheckpointError> {
+        if !self
+            .device()
+            .enabled_extensions()
+            .nv_device_diagnostic_checkpoints
+        {
+            return Err(CheckpointError::ExtensionNotEnabled {
+                extension: "nv_device_diagnostic_checkpoints",
+                reason: "tried to record a checkpoint",
+            });
+        }
+
+        // VUID-vkCmdSetCheckpointNV-commandBuffer-cmdpool
+        let queue_family = self.queue_family();
+        if !(queue_family.supports_graphics()
+            || queue_family.supports_compute()
+            || queue_family.explicitly_supports_transfers())
+        {
+            return Err(CheckpointError::NotSupportedByQueueFamily);
+        }
+
+        Ok(())
+    }
+}
+
+impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdSetCheckpointNV` on the builder.
+    #[inline]
+    pub unsafe fn set_checkpoint(&mut self, marker: u64) {
+        struct Cmd {
+            marker: u64,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_checkpoint"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_checkpoint(self.marker);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { marker }));
+    }
+}
+
+impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdSetCheckpointNV` on the builder.
+    #[inline]
+    pub unsafe fn set_checkpoint(&mut self, marker: u64) {
+        let fns = self.device.fns();
+        // The marker is an opaque pointer, that the device returns unchanged.
+        (fns.nv_device_diagnostic_checkpoints.cmd_set_checkpoint_nv)(
+            self.handle,
+            marker as usize as *const _,
+        );
+    }
+}
+
+/// Error that can happen when recording or reading checkpoints.
+#[derive(Clone, Debug)]
+pub enum CheckpointError {
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+
+    /// The checkpoint data of a queue was requested, but the device is not lost.
+    DeviceNotLost,
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+}
+
+impl error::Error for CheckpointError {}
+
+impl fmt::Display for CheckpointError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::ExtensionNotEnabled { extension, reason } => {
+                write!(f, "the extension {} must be enabled: {}", extension, reason)
+            }
+            Self::DeviceNotLost => write!(f, "the device is not lost"),
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+        }
+    }
+}
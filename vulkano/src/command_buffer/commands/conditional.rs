@@ -0,0 +1,429 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    buffer::BufferAccess,
+    command_buffer::{
+        auto::{ConditionalRenderingState, RenderPassStateType},
+        synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder,
+    },
+    device::DeviceOwned,
+    sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
+    DeviceSize, VulkanObject,
+};
+use std::{error, fmt, sync::Arc};
+
+/// # Commands for conditional rendering.
+///
+/// These commands require the
+/// [`ext_conditional_rendering`](crate::device::DeviceExtensions::ext_conditional_rendering)
+/// extension and the
+/// [`conditional_rendering`](crate::device::Features::conditional_rendering) feature to be
+/// enabled on the device.
+impl<L, P> AutoCommandBufferBuilder<L, P> {
+    /// Begins a region in which drawing and dispatch commands are only executed if a 32-bit value
+    /// in a buffer is not zero.
+    ///
+    /// The value is read when the command buffer is executed, so it can be written by the device,
+    /// for example by [`copy_query_pool_results`](Self::copy_query_pool_results).
+    ///
+    /// If conditional rendering is begun inside a render pass, it must be ended in the same
+    /// subpass. If it is begun outside, it must be ended outside of any render pass.
+    #[inline]
+    pub fn begin_conditional_rendering(
+        &mut self,
+        begin_info: ConditionalRenderingBeginInfo,
+    ) -> Result<&mut Self, ConditionalRenderingError> {
+        self.validate_begin_conditional_rendering(&begin_info)?;
+
+        unsafe {
+            self.inner.begin_conditional_rendering(begin_info)?;
+        }
+
+        self.conditional_rendering_state = Some(ConditionalRenderingState {
+            render_pass_subpass: self.current_subpass_index(),
+        });
+
+        Ok(self)
+    }
+
+    fn validate_begin_conditional_rendering(
+        &self,
+        begin_info: &ConditionalRenderingBeginInfo,
+    ) -> Result<(), ConditionalRenderingError> {
+        self.validate_conditional_rendering_command()?;
+
+        // VUID-vkCmdBeginConditionalRenderingEXT-None-01980
+        if self.conditional_rendering_state.is_some() {
+            return Err(ConditionalRenderingError::ConditionalRenderingActive);
+        }
+
+        let &ConditionalRenderingBeginInfo {
+            ref buffer,
+            offset,
+            inverted: _,
+            _ne: _,
+        } = begin_info;
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-commonparent
+        assert_eq!(
+            self.device().internal_object(),
+            buffer.device().internal_object(),
+        );
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-buffer-01982
+        if !buffer.inner().buffer.usage().conditional_rendering {
+            return Err(ConditionalRenderingError::MissingUsage);
+        }
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-offset-01984
+        if offset % 4 != 0 {
+            return Err(ConditionalRenderingError::OffsetNotAligned { offset });
+        }
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-offset-01983
+        if offset + 4 > buffer.size() {
+            return Err(ConditionalRenderingError::OffsetOutOfRange {
+                offset,
+                buffer_size: buffer.size(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Ends the region begun with
+    /// [`begin_conditional_rendering`](Self::begin_conditional_rendering).
+    #[inline]
+    pub fn end_conditional_rendering(&mut self) -> Result<&mut Self, ConditionalRenderingError> {
+        self.validate_end_conditional_rendering()?;
+
+        unsafe {
+            self.inner.end_conditional_rendering();
+        }
+
+        self.conditional_rendering_state = None;
+
+        Ok(self)
+    }
+
+    fn validate_end_conditional_rendering(&self) -> Result<(), ConditionalRenderingError> {
+        self.validate_conditional_rendering_command()?;
+
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01985
+        let state = self
+            .conditional_rendering_state
+            .as_ref()
+            .ok_or(ConditionalRenderingError::ConditionalRenderingNotActive)?;
+
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01986
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01987
+        if state.render_pass_subpass != self.current_subpass_index() {
+            return Err(ConditionalRenderingError::RenderPassMismatch);
+        }
+
+        Ok(())
+    }
+
+    fn validate_conditional_rendering_command(&self) -> Result<(), ConditionalRenderingError> {
+        if !self.device().enabled_extensions().ext_conditional_rendering {
+            return Err(ConditionalRenderingError::ExtensionNotEnabled {
+                extension: "ext_conditional_rendering",
+                reason: "tried to record a conditional rendering command",
+            });
+        }
+
+        // The pipeline barriers that protect the buffer use the conditional rendering stage.
+        // VUID-vkCmdPipelineBarrier-srcStageMask-04091
+        if !self.device().enabled_features().conditional_rendering {
+            return Err(ConditionalRenderingError::FeatureNotEnabled {
+                feature: "conditional_rendering",
+                reason: "tried to record a conditional rendering command",
+            });
+        }
+
+        // VUID-vkCmdBeginConditionalRenderingEXT-commandBuffer-cmdpool
+        // VUID-vkCmdEndConditionalRenderingEXT-commandBuffer-cmdpool
+        if !(self.queue_family().supports_graphics() || self.queue_family().supports_compute()) {
+            return Err(ConditionalRenderingError::NotSupportedByQueueFamily);
+        }
+
+        Ok(())
+    }
+
+    // Returns `None` outside of a render pass, and the index of the current subpass inside one.
+    // Render passes begun with `begin_rendering` or inherited have a single subpass.
+    fn current_subpass_index(&self) -> Option<u32> {
+        self.render_pass_state
+            .as_ref()
+            .map(|state| match &state.render_pass {
+                RenderPassStateType::BeginRenderPass(state) => state.subpass.index(),
+                RenderPassStateType::BeginRendering(_) | RenderPassStateType::Inherited => 0,
+            })
+    }
+}
+
+impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdBeginConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn begin_conditional_rendering(
+        &mut self,
+        begin_info: ConditionalRenderingBeginInfo,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            begin_info: ConditionalRenderingBeginInfo,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "begin_conditional_rendering"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.begin_conditional_rendering(&self.begin_info);
+            }
+        }
+
+        let &ConditionalRenderingBeginInfo {
+            ref buffer, offset, ..
+        } = &begin_info;
+
+        let resources = [(
+            "buffer".into(),
+            Resource::Buffer {
+                buffer: buffer.clone(),
+                range: offset..offset + 4,
+                memory: PipelineMemoryAccess {
+                    stages: PipelineStages {
+                        conditional_rendering: true,
+                        ..PipelineStages::none()
+                    },
+                    access: AccessFlags {
+                        conditional_rendering_read: true,
+                        ..AccessFlags::none()
+                    },
+                    exclusive: false,
+                },
+            },
+        )];
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd { begin_info }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdEndConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_conditional_rendering(&mut self) {
+        struct Cmd {}
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "end_conditional_rendering"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.end_conditional_rendering();
+            }
+        }
+
+        self.commands.push(Box::new(Cmd {}));
+    }
+}
+
+impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdBeginConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn begin_conditional_rendering(
+        &mut self,
+        begin_info: &ConditionalRenderingBeginInfo,
+    ) {
+        let &ConditionalRenderingBeginInfo {
+            ref buffer,
+            offset,
+            inverted,
+            _ne: _,
+        } = begin_info;
+
+        let buffer_inner = buffer.inner();
+
+        let mut flags = ash::vk::ConditionalRenderingFlagsEXT::empty();
+        if inverted {
+            flags |= ash::vk::ConditionalRenderingFlagsEXT::INVERTED;
+        }
+
+        let begin_info = ash::vk::ConditionalRenderingBeginInfoEXT {
+            buffer: buffer_inner.buffer.internal_object(),
+            offset: buffer_inner.offset + offset,
+            flags,
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+        (fns.ext_conditional_rendering
+            .cmd_begin_conditional_rendering_ext)(self.handle, &begin_info);
+    }
+
+    /// Calls `vkCmdEndConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_conditional_rendering(&mut self) {
+        let fns = self.device.fns();
+        (fns.ext_conditional_rendering
+            .cmd_end_conditional_rendering_ext)(self.handle);
+    }
+}
+
+/// Parameters to begin conditional rendering.
+#[derive(Clone, Debug)]
+pub struct ConditionalRenderingBeginInfo {
+    /// The buffer that contains the value that determines whether commands are executed.
+    ///
+    /// The buffer must have been created with the
+    /// [`conditional_rendering`](crate::buffer::BufferUsage::conditional_rendering) usage.
+    ///
+    /// There is no default value.
+    pub buffer: Arc<dyn BufferAccess>,
+
+    /// The offset in bytes of the 32-bit value in `buffer`. Must be a multiple of 4.
+    ///
+    /// The default value is `0`.
+    pub offset: DeviceSize,
+
+    /// If `true`, the commands are executed if the value is zero, instead of if it is not zero.
+    ///
+    /// The default value is `false`.
+    pub inverted: bool,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl ConditionalRenderingBeginInfo {
+    /// Returns a `ConditionalRenderingBeginInfo` with the specified `buffer`.
+    #[inline]
+    pub fn buffer(buffer: Arc<dyn BufferAccess>) -> Self {
+        Self {
+            buffer,
+            offset: 0,
+            inverted: false,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Error that can happen when recording a conditional rendering command.
+#[derive(Clone, Debug)]
+pub enum ConditionalRenderingError {
+    SyncCommandBufferBuilderError(SyncCommandBufferBuilderError),
+
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// Conditional rendering is already active.
+    ConditionalRenderingActive,
+
+    /// Conditional rendering is not active.
+    ConditionalRenderingNotActive,
+
+    /// The buffer was not created with the `conditional_rendering` usage.
+    MissingUsage,
+
+    /// The offset is not a multiple of 4.
+    OffsetNotAligned {
+        offset: DeviceSize,
+    },
+
+    /// The 32-bit value at the offset is not inside the buffer.
+    OffsetOutOfRange {
+        offset: DeviceSize,
+        buffer_size: DeviceSize,
+    },
+
+    /// Conditional rendering was begun inside a different subpass or render pass instance, or
+    /// was begun outside of a render pass and is ended inside one, or the reverse.
+    RenderPassMismatch,
+}
+
+impl error::Error for ConditionalRenderingError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::SyncCommandBufferBuilderError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ConditionalRenderingError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::SyncCommandBufferBuilderError(_) => write!(f, "a SyncCommandBufferBuilderError"),
+            Self::ExtensionNotEnabled { extension, reason } => {
+                write!(f, "the extension {} must be enabled: {}", extension, reason)
+            }
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(f, "the feature {} must be enabled: {}", feature, reason)
+            }
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::ConditionalRenderingActive => {
+                write!(f, "conditional rendering is already active")
+            }
+            Self::ConditionalRenderingNotActive => write!(f, "conditional rendering is not active"),
+            Self::MissingUsage => write!(
+                f,
+                "the buffer was not created with the conditional_rendering usage",
+            ),
+            Self::OffsetNotAligned { offset } => {
+                write!(f, "the offset {} is not a multiple of 4", offset)
+            }
+            Self::OffsetOutOfRange {
+                offset,
+                buffer_size,
+            } => write!(
+                f,
+                "the 32-bit value at offset {} is not inside the buffer of size {}",
+                offset, buffer_size,
+            ),
+            Self::RenderPassMismatch => write!(
+                f,
+                "conditional rendering must be ended in the same subpass that it was begun in, or \
+                outside of a render pass if it was begun outside",
+            ),
+        }
+    }
+}
+
+impl From<SyncCommandBufferBuilderError> for ConditionalRenderingError {
+    #[inline]
+    fn from(err: SyncCommandBufferBuilderError) -> Self {
+        Self::SyncCommandBufferBuilderError(err)
+    }
+}
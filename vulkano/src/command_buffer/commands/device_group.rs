@@ -0,0 +1,212 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    command_buffer::{
+        synced::{Command, SyncCommandBufferBuilder},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder,
+    },
+    device::DeviceOwned,
+    ValidationError, Version,
+};
+use std::{error, fmt};
+
+/// # Commands for devices that were created from several physical devices.
+///
+/// These commands require the device API version to be at least 1.1, or the
+/// [`khr_device_group`](crate::device::DeviceExtensions::khr_device_group) extension to be
+/// enabled on the device.
+impl<L, P> AutoCommandBufferBuilder<L, P> {
+    /// Sets the physical devices that execute the following commands, as a bit mask of their
+    /// device indices in [`Device::physical_devices`](crate::device::Device::physical_devices).
+    ///
+    /// At the start of the command buffer, all the physical devices of the device execute the
+    /// commands.
+    #[inline]
+    pub fn set_device_mask(&mut self, device_mask: u32) -> Result<&mut Self, DeviceGroupError> {
+        self.validate_set_device_mask(device_mask)?;
+
+        unsafe {
+            self.inner.set_device_mask(device_mask);
+        }
+
+        Ok(self)
+    }
+
+    fn validate_set_device_mask(&self, device_mask: u32) -> Result<(), DeviceGroupError> {
+        let device = self.device();
+
+        if !(device.api_version() >= Version::V1_1 || device.enabled_extensions().khr_device_group)
+        {
+            return Err(DeviceGroupError::ExtensionNotEnabled {
+                extension: "khr_device_group",
+                reason: "tried to record a device group command",
+            });
+        }
+
+        if device_mask == 0 {
+            return Err(DeviceGroupError::ValidationError(ValidationError {
+                code: "device_mask_zero",
+                context: "set_device_mask",
+                problem: "device_mask is zero".into(),
+                vuids: &["VUID-vkCmdSetDeviceMask-deviceMask-00109"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCmdSetDeviceMask-deviceMask-00110
+        // VUID-vkCmdSetDeviceMask-deviceMask-00111
+        // The device masks of the render pass and of the command buffer are never restricted, so
+        // they contain all the physical devices.
+        if !device.is_valid_device_mask(device_mask) {
+            return Err(DeviceGroupError::ValidationError(ValidationError {
+                code: "device_mask_out_of_range",
+                context: "set_device_mask",
+                problem: format!(
+                    "device_mask ({:#b}) contains bits for physical devices that the device was \
+                    not created from (the device has {} physical devices)",
+                    device_mask,
+                    device.physical_devices().len(),
+                )
+                .into(),
+                vuids: &["VUID-vkCmdSetDeviceMask-deviceMask-00108"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+}
+
+impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdSetDeviceMask` on the builder.
+    #[inline]
+    pub unsafe fn set_device_mask(&mut self, device_mask: u32) {
+        struct Cmd {
+            device_mask: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_device_mask"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_device_mask(self.device_mask);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { device_mask }));
+    }
+}
+
+impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdSetDeviceMask` on the builder.
+    #[inline]
+    pub unsafe fn set_device_mask(&mut self, device_mask: u32) {
+        let fns = self.device.fns();
+
+        if self.device.api_version() >= Version::V1_1 {
+            (fns.v1_1.cmd_set_device_mask)(self.handle, device_mask);
+        } else {
+            (fns.khr_device_group.cmd_set_device_mask_khr)(self.handle, device_mask);
+        }
+    }
+}
+
+/// Error that can happen when recording a device group command.
+#[derive(Clone, Debug)]
+pub enum DeviceGroupError {
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+
+    /// A parameter broke a rule of the Vulkan specification, such as a device mask that was zero
+    /// or that contained bits for physical devices that the device was not created from.
+    ValidationError(ValidationError),
+}
+
+impl error::Error for DeviceGroupError {}
+
+impl fmt::Display for DeviceGroupError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::ExtensionNotEnabled { extension, reason } => {
+                write!(f, "the extension {} must be enabled: {}", extension, reason)
+            }
+            Self::ValidationError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<ValidationError> for DeviceGroupError {
+    #[inline]
+    fn from(err: ValidationError) -> Self {
+        Self::ValidationError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeviceGroupError;
+    use crate::{
+        command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
+        ValidationError,
+    };
+
+    #[test]
+    fn device_mask_invalid() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        match builder.set_device_mask(0) {
+            Err(DeviceGroupError::ValidationError(err)) => {
+                assert_eq!(err.code, "device_mask_zero");
+                assert!(err.has_vuid("VUID-vkCmdSetDeviceMask-deviceMask-00109"));
+            }
+            Err(DeviceGroupError::ExtensionNotEnabled { .. }) => (),
+            _ => panic!(),
+        }
+
+        match builder.set_device_mask(1 << device.physical_devices().len()) {
+            Err(DeviceGroupError::ValidationError(err)) => {
+                assert_eq!(err.code, "device_mask_out_of_range");
+                assert!(err.has_vuid("VUID-vkCmdSetDeviceMask-deviceMask-00108"));
+            }
+            Err(DeviceGroupError::ExtensionNotEnabled { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn validation_error_display() {
+        let err = DeviceGroupError::from(ValidationError {
+            code: "device_mask_zero",
+            context: "set_device_mask",
+            problem: "device_mask is zero".into(),
+            vuids: &["VUID-vkCmdSetDeviceMask-deviceMask-00109"],
+            ..Default::default()
+        });
+
+        assert_eq!(
+            err.to_string(),
+            "set_device_mask: device_mask is zero (error code: device_mask_zero, Vulkan VUIDs: \
+            VUID-vkCmdSetDeviceMask-deviceMask-00109)",
+        );
+    }
+}
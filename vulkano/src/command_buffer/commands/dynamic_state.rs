@@ -14,6 +14,7 @@ use crate::{
         AutoCommandBufferBuilder,
     },
     device::DeviceOwned,
+    image::ImageAspects,
     pipeline::{
         graphics::{
             color_blend::LogicOp,
@@ -41,6 +42,64 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             .unwrap_or(false)
     }
 
+    /// Sets the image aspects for which attachment feedback loops are enabled for future draw
+    /// calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`attachment_feedback_loop_dynamic_state`](crate::device::Features::attachment_feedback_loop_dynamic_state)
+    ///   feature is not enabled on the device.
+    /// - Panics if `aspects` contains aspects other than `color`, `depth` and `stencil`.
+    /// - Panics if `aspects` is not empty and the
+    ///   [`attachment_feedback_loop_layout`](crate::device::Features::attachment_feedback_loop_layout)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    #[inline]
+    pub fn set_attachment_feedback_loop_enable(&mut self, aspects: ImageAspects) -> &mut Self {
+        assert!(
+            self.queue_family().supports_graphics(),
+            "the queue family of the command buffer must support graphics operations"
+        );
+        // VUID-vkCmdSetAttachmentFeedbackLoopEnableEXT-attachmentFeedbackLoopDynamicState-08862
+        assert!(
+            self.device()
+                .enabled_features()
+                .attachment_feedback_loop_dynamic_state,
+            "the attachment_feedback_loop_dynamic_state feature must be enabled on the device"
+        );
+        // VUID-vkCmdSetAttachmentFeedbackLoopEnableEXT-aspectMask-08863
+        assert!(
+            ImageAspects {
+                color: false,
+                depth: false,
+                stencil: false,
+                ..aspects
+            } == ImageAspects::none(),
+            "aspects must only contain color, depth and stencil"
+        );
+        // VUID-vkCmdSetAttachmentFeedbackLoopEnableEXT-attachmentFeedbackLoopLayout-08864
+        assert!(
+            aspects == ImageAspects::none()
+                || self
+                    .device()
+                    .enabled_features()
+                    .attachment_feedback_loop_layout,
+            "if aspects is not empty, the attachment_feedback_loop_layout feature must be enabled on the device"
+        );
+        assert!(
+            !self.has_fixed_state(DynamicState::AttachmentFeedbackLoopEnable),
+            "the currently bound graphics pipeline must not contain this state internally"
+        );
+
+        unsafe {
+            self.inner.set_attachment_feedback_loop_enable(aspects);
+        }
+
+        self
+    }
+
     /// Sets the dynamic blend constants for future draw calls.
     ///
     /// # Panics
@@ -1086,6 +1145,27 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
 }
 
 impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdSetAttachmentFeedbackLoopEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_attachment_feedback_loop_enable(&mut self, aspects: ImageAspects) {
+        struct Cmd {
+            aspects: ImageAspects,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_attachment_feedback_loop_enable"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_attachment_feedback_loop_enable(self.aspects);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { aspects }));
+        self.current_state.attachment_feedback_loop_enable = Some(aspects);
+    }
+
     /// Calls `vkCmdSetBlendConstants` on the builder.
     #[inline]
     pub unsafe fn set_blend_constants(&mut self, constants: [f32; 4]) {
@@ -1837,6 +1917,20 @@ impl SyncCommandBufferBuilder {
 }
 
 impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdSetAttachmentFeedbackLoopEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_attachment_feedback_loop_enable(&mut self, aspects: ImageAspects) {
+        debug_assert!(
+            self.device
+                .enabled_extensions()
+                .ext_attachment_feedback_loop_dynamic_state
+        );
+
+        let fns = self.device.fns();
+        (fns.ext_attachment_feedback_loop_dynamic_state
+            .cmd_set_attachment_feedback_loop_enable_ext)(self.handle, aspects.into());
+    }
+
     /// Calls `vkCmdSetBlendConstants` on the builder.
     #[inline]
     pub unsafe fn set_blend_constants(&mut self, constants: [f32; 4]) {
@@ -2363,3 +2457,30 @@ impl UnsafeCommandBufferBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
+        image::ImageAspects,
+    };
+
+    #[test]
+    fn attachment_feedback_loop_enable_feature_not_enabled() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert_should_panic!(
+            "the attachment_feedback_loop_dynamic_state feature must be enabled on the device",
+            {
+                builder.set_attachment_feedback_loop_enable(ImageAspects::none());
+            }
+        );
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
     command_buffer::{
         synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
         sys::UnsafeCommandBufferBuilder,
-        AutoCommandBufferBuilder, CopyError, CopyErrorResource,
+        AutoCommandBufferBuilder, CopyError,
     },
     device::DeviceOwned,
     format::{ClearColorValue, ClearDepthStencilValue, NumericType},
@@ -21,7 +21,7 @@ use crate::{
     },
     sampler::Filter,
     sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
-    Version, VulkanObject,
+    ValidationError, Version, VulkanObject,
 };
 use smallvec::{smallvec, SmallVec};
 use std::{
@@ -113,65 +113,97 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         let src_image_type = src_image.dimensions().image_type();
         let dst_image_type = dst_image.dimensions().image_type();
 
-        // VUID-VkBlitImageInfo2-srcImage-00219
         if !src_image.usage().transfer_src {
-            return Err(CopyError::MissingUsage {
-                resource: CopyErrorResource::Source,
-                usage: "transfer_src",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_usage",
+                context: "blit_image",
+                problem: "the source resource did not have the required usage transfer_src enabled"
+                    .into(),
+                vuids: &["VUID-VkBlitImageInfo2-srcImage-00219"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkBlitImageInfo2-dstImage-00224
         if !dst_image.usage().transfer_dst {
-            return Err(CopyError::MissingUsage {
-                resource: CopyErrorResource::Destination,
-                usage: "transfer_dst",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_usage",
+                context: "blit_image",
+                problem: "the destination resource did not have the required usage transfer_dst \
+                    enabled"
+                    .into(),
+                vuids: &["VUID-VkBlitImageInfo2-dstImage-00224"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkBlitImageInfo2-srcImage-01999
         if !src_image.format_features().blit_src {
-            return Err(CopyError::MissingFormatFeature {
-                resource: CopyErrorResource::Source,
-                format_feature: "blit_src",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_format_feature",
+                context: "blit_image",
+                problem: "the source image does not have the required format feature blit_src"
+                    .into(),
+                vuids: &["VUID-VkBlitImageInfo2-srcImage-01999"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkBlitImageInfo2-dstImage-02000
         if !dst_image.format_features().blit_dst {
-            return Err(CopyError::MissingFormatFeature {
-                resource: CopyErrorResource::Destination,
-                format_feature: "blit_dst",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_format_feature",
+                context: "blit_image",
+                problem: "the destination image does not have the required format feature blit_dst"
+                    .into(),
+                vuids: &["VUID-VkBlitImageInfo2-dstImage-02000"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkBlitImageInfo2-srcImage-06421
         if src_image.format().ycbcr_chroma_sampling().is_some() {
-            return Err(CopyError::FormatNotSupported {
-                resource: CopyErrorResource::Source,
-                format: src_image.format(),
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "format_not_supported",
+                context: "blit_image",
+                problem: format!(
+                    "the format of the source image ({:?}) is not supported for this operation",
+                    src_image.format(),
+                )
+                .into(),
+                vuids: &["VUID-VkBlitImageInfo2-srcImage-06421"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkBlitImageInfo2-dstImage-06422
         if dst_image.format().ycbcr_chroma_sampling().is_some() {
-            return Err(CopyError::FormatNotSupported {
-                resource: CopyErrorResource::Destination,
-                format: src_image.format(),
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "format_not_supported",
+                context: "blit_image",
+                problem: format!(
+                    "the format of the destination image ({:?}) is not supported for this \
+                    operation",
+                    dst_image.format(),
+                )
+                .into(),
+                vuids: &["VUID-VkBlitImageInfo2-dstImage-06422"],
+                ..Default::default()
+            }));
         }
 
         if !(src_image_aspects.color && dst_image_aspects.color) {
-            // VUID-VkBlitImageInfo2-srcImage-00231
             if src_image.format() != dst_image.format() {
-                return Err(CopyError::FormatsMismatch {
-                    src_format: src_image.format(),
-                    dst_format: dst_image.format(),
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "formats_mismatch",
+                    context: "blit_image",
+                    problem: format!(
+                        "the format of the source image ({:?}) does not match the format of the \
+                        destination image ({:?})",
+                        src_image.format(),
+                        dst_image.format(),
+                    )
+                    .into(),
+                    vuids: &["VUID-VkBlitImageInfo2-srcImage-00231"],
+                    ..Default::default()
+                }));
             }
         } else {
-            // VUID-VkBlitImageInfo2-srcImage-00229
-            // VUID-VkBlitImageInfo2-srcImage-00230
             if !matches!(
                 (
                     src_image.format().type_color().unwrap(),
@@ -195,80 +227,131 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 ) | (NumericType::SINT, NumericType::SINT)
                     | (NumericType::UINT, NumericType::UINT)
             ) {
-                return Err(CopyError::FormatsNotCompatible {
-                    src_format: src_image.format(),
-                    dst_format: dst_image.format(),
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "formats_not_compatible",
+                    context: "blit_image",
+                    problem: format!(
+                        "the format of the source image subresource ({:?}) is not compatible with \
+                        the format of the destination image subresource ({:?})",
+                        src_image.format(),
+                        dst_image.format(),
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkBlitImageInfo2-srcImage-00229",
+                        "VUID-VkBlitImageInfo2-srcImage-00230",
+                    ],
+                    ..Default::default()
+                }));
             }
         }
 
-        // VUID-VkBlitImageInfo2-srcImage-00233
         if src_image.samples() != SampleCount::Sample1 {
-            return Err(CopyError::SampleCountInvalid {
-                resource: CopyErrorResource::Destination,
-                sample_count: dst_image.samples(),
-                allowed_sample_counts: SampleCounts {
-                    sample1: true,
-                    sample2: false,
-                    sample4: false,
-                    sample8: false,
-                    sample16: false,
-                    sample32: false,
-                    sample64: false,
-                },
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "sample_count_invalid",
+                context: "blit_image",
+                problem: format!(
+                    "the source image has a sample count ({:?}) that is not valid for this \
+                    operation ({:?})",
+                    src_image.samples(),
+                    SampleCounts {
+                        sample1: true,
+                        sample2: false,
+                        sample4: false,
+                        sample8: false,
+                        sample16: false,
+                        sample32: false,
+                        sample64: false,
+                    },
+                )
+                .into(),
+                vuids: &["VUID-VkBlitImageInfo2-srcImage-00233"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkBlitImageInfo2-dstImage-00234
         if dst_image.samples() != SampleCount::Sample1 {
-            return Err(CopyError::SampleCountInvalid {
-                resource: CopyErrorResource::Destination,
-                sample_count: dst_image.samples(),
-                allowed_sample_counts: SampleCounts {
-                    sample1: true,
-                    sample2: false,
-                    sample4: false,
-                    sample8: false,
-                    sample16: false,
-                    sample32: false,
-                    sample64: false,
-                },
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "sample_count_invalid",
+                context: "blit_image",
+                problem: format!(
+                    "the destination image has a sample count ({:?}) that is not valid for this \
+                    operation ({:?})",
+                    dst_image.samples(),
+                    SampleCounts {
+                        sample1: true,
+                        sample2: false,
+                        sample4: false,
+                        sample8: false,
+                        sample16: false,
+                        sample32: false,
+                        sample64: false,
+                    },
+                )
+                .into(),
+                vuids: &["VUID-VkBlitImageInfo2-dstImage-00234"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkBlitImageInfo2-srcImageLayout-01398
         if !matches!(
             src_image_layout,
             ImageLayout::TransferSrcOptimal | ImageLayout::General
         ) {
-            return Err(CopyError::ImageLayoutInvalid {
-                resource: CopyErrorResource::Source,
-                image_layout: src_image_layout,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "image_layout_invalid",
+                context: "blit_image",
+                problem: format!(
+                    "the specified source image layout {:?} is not valid for this operation",
+                    src_image_layout,
+                )
+                .into(),
+                vuids: &["VUID-VkBlitImageInfo2-srcImageLayout-01398"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkBlitImageInfo2-dstImageLayout-01399
         if !matches!(
             dst_image_layout,
             ImageLayout::TransferDstOptimal | ImageLayout::General
         ) {
-            return Err(CopyError::ImageLayoutInvalid {
-                resource: CopyErrorResource::Destination,
-                image_layout: dst_image_layout,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "image_layout_invalid",
+                context: "blit_image",
+                problem: format!(
+                    "the specified destination image layout {:?} is not valid for this operation",
+                    dst_image_layout,
+                )
+                .into(),
+                vuids: &["VUID-VkBlitImageInfo2-dstImageLayout-01399"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkBlitImageInfo2-srcImage-00232
         if !src_image_aspects.color && filter != Filter::Nearest {
-            return Err(CopyError::FilterNotSupportedByFormat);
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "filter_not_supported_by_format",
+                context: "blit_image",
+                problem: "the chosen filter is not supported by the format of the source image"
+                    .into(),
+                vuids: &["VUID-VkBlitImageInfo2-srcImage-00232"],
+                ..Default::default()
+            }));
         }
 
         match filter {
             Filter::Nearest => (),
             Filter::Linear => {
-                // VUID-VkBlitImageInfo2-filter-02001
                 if !src_image.format_features().sampled_image_filter_linear {
-                    return Err(CopyError::FilterNotSupportedByFormat);
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "filter_not_supported_by_format",
+                        context: "blit_image",
+                        problem: "the chosen filter is not supported by the format of the source \
+                            image"
+                            .into(),
+                        vuids: &["VUID-VkBlitImageInfo2-filter-02001"],
+                        ..Default::default()
+                    }));
                 }
             }
             Filter::Cubic => {
@@ -279,14 +362,27 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                     });
                 }
 
-                // VUID-VkBlitImageInfo2-filter-02002
                 if !src_image.format_features().sampled_image_filter_cubic {
-                    return Err(CopyError::FilterNotSupportedByFormat);
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "filter_not_supported_by_format",
+                        context: "blit_image",
+                        problem: "the chosen filter is not supported by the format of the source \
+                            image"
+                            .into(),
+                        vuids: &["VUID-VkBlitImageInfo2-filter-02002"],
+                        ..Default::default()
+                    }));
                 }
 
-                // VUID-VkBlitImageInfo2-filter-00237
                 if !matches!(src_image.dimensions(), ImageDimensions::Dim2d { .. }) {
-                    return Err(CopyError::FilterNotSupportedForImageType);
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "filter_not_supported_for_image_type",
+                        context: "blit_image",
+                        problem: "the chosen filter is not supported for the source image type"
+                            .into(),
+                        vuids: &["VUID-VkBlitImageInfo2-filter-00237"],
+                        ..Default::default()
+                    }));
                 }
             }
         }
@@ -304,49 +400,81 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 _ne: _,
             } = region;
 
-            let check_subresource = |resource: CopyErrorResource,
+            let check_subresource = |resource: &'static str,
                                      image: &dyn ImageAccess,
                                      image_aspects: &ImageAspects,
                                      subresource: &ImageSubresourceLayers|
              -> Result<_, CopyError> {
-                // VUID-VkBlitImageInfo2-srcSubresource-01705
-                // VUID-VkBlitImageInfo2-dstSubresource-01706
                 if subresource.mip_level >= image.mip_levels() {
-                    return Err(CopyError::MipLevelsOutOfRange {
-                        resource,
-                        region_index,
-                        mip_levels_range_end: subresource.mip_level + 1,
-                        image_mip_levels: image.mip_levels(),
-                    });
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "mip_levels_out_of_range",
+                        context: "blit_image",
+                        problem: format!(
+                            "the end of the range of accessed mip levels ({}) of the {} \
+                            subresource range of region {} is not less than the number of mip \
+                            levels in the {} image ({})",
+                            subresource.mip_level + 1,
+                            resource,
+                            region_index,
+                            resource,
+                            image.mip_levels(),
+                        )
+                        .into(),
+                        vuids: &[
+                            "VUID-VkBlitImageInfo2-srcSubresource-01705",
+                            "VUID-VkBlitImageInfo2-dstSubresource-01706",
+                        ],
+                        ..Default::default()
+                    }));
                 }
 
                 // VUID-VkImageSubresourceLayers-layerCount-01700
                 assert!(!subresource.array_layers.is_empty());
 
-                // VUID-VkBlitImageInfo2-srcSubresource-01707
-                // VUID-VkBlitImageInfo2-dstSubresource-01708
-                // VUID-VkBlitImageInfo2-srcImage-00240
                 if subresource.array_layers.end > image.dimensions().array_layers() {
-                    return Err(CopyError::ArrayLayersOutOfRange {
-                        resource,
-                        region_index,
-                        array_layers_range_end: subresource.array_layers.end,
-                        image_array_layers: image.dimensions().array_layers(),
-                    });
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "array_layers_out_of_range",
+                        context: "blit_image",
+                        problem: format!(
+                            "the end of the range of accessed array layers ({}) of the {} \
+                            subresource range of region {} is greater than the number of array \
+                            layers in the {} image ({})",
+                            subresource.array_layers.end,
+                            resource,
+                            region_index,
+                            resource,
+                            image.dimensions().array_layers(),
+                        )
+                        .into(),
+                        vuids: &[
+                            "VUID-VkBlitImageInfo2-srcSubresource-01707",
+                            "VUID-VkBlitImageInfo2-dstSubresource-01708",
+                            "VUID-VkBlitImageInfo2-srcImage-00240",
+                        ],
+                        ..Default::default()
+                    }));
                 }
 
                 // VUID-VkImageSubresourceLayers-aspectMask-requiredbitmask
                 assert!(subresource.aspects != ImageAspects::none());
 
-                // VUID-VkBlitImageInfo2-aspectMask-00241
-                // VUID-VkBlitImageInfo2-aspectMask-00242
                 if !image_aspects.contains(&subresource.aspects) {
-                    return Err(CopyError::AspectsNotAllowed {
-                        resource,
-                        region_index,
-                        aspects: subresource.aspects,
-                        allowed_aspects: *image_aspects,
-                    });
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "aspects_not_allowed",
+                        context: "blit_image",
+                        problem: format!(
+                            "the aspects ({:?}) of the {} subresource range of region {} contain \
+                            aspects that are not present in the {} image, or that are not \
+                            allowed ({:?})",
+                            subresource.aspects, resource, region_index, resource, *image_aspects,
+                        )
+                        .into(),
+                        vuids: &[
+                            "VUID-VkBlitImageInfo2-aspectMask-00241",
+                            "VUID-VkBlitImageInfo2-aspectMask-00242",
+                        ],
+                        ..Default::default()
+                    }));
                 }
 
                 Ok(image
@@ -356,26 +484,28 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                     .width_height_depth())
             };
 
-            let src_subresource_extent = check_subresource(
-                CopyErrorResource::Source,
-                src_image,
-                &src_image_aspects,
-                src_subresource,
-            )?;
+            let src_subresource_extent =
+                check_subresource("source", src_image, &src_image_aspects, src_subresource)?;
             let dst_subresource_extent = check_subresource(
-                CopyErrorResource::Destination,
+                "destination",
                 dst_image,
                 &dst_image_aspects,
                 dst_subresource,
             )?;
 
-            // VUID-VkImageBlit2-aspectMask-00238
             if src_subresource.aspects != dst_subresource.aspects {
-                return Err(CopyError::AspectsMismatch {
-                    region_index,
-                    src_aspects: src_subresource.aspects,
-                    dst_aspects: dst_subresource.aspects,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "aspects_mismatch",
+                    context: "blit_image",
+                    problem: format!(
+                        "the aspects of the source and destination subresource ranges of region \
+                        {} do not match (source: {:?}; destination: {:?})",
+                        region_index, src_subresource.aspects, dst_subresource.aspects,
+                    )
+                    .into(),
+                    vuids: &["VUID-VkImageBlit2-aspectMask-00238"],
+                    ..Default::default()
+                }));
             }
 
             let src_layer_count =
@@ -383,52 +513,93 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             let dst_layer_count =
                 dst_subresource.array_layers.end - dst_subresource.array_layers.start;
 
-            // VUID-VkImageBlit2-layerCount-00239
-            // VUID-VkBlitImageInfo2-srcImage-00240
             if src_layer_count != dst_layer_count {
-                return Err(CopyError::ArrayLayerCountMismatch {
-                    region_index,
-                    src_layer_count,
-                    dst_layer_count,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "array_layer_count_mismatch",
+                    context: "blit_image",
+                    problem: format!(
+                        "the array layer counts of the source and destination subresource ranges \
+                        of region {} do not match (source: {}; destination: {})",
+                        region_index, src_layer_count, dst_layer_count,
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkImageBlit2-layerCount-00239",
+                        "VUID-VkBlitImageInfo2-srcImage-00240",
+                    ],
+                    ..Default::default()
+                }));
             }
 
-            let check_offset_extent = |resource: CopyErrorResource,
+            let check_offset_extent = |resource: &'static str,
                                        image_type: ImageType,
                                        subresource_extent: [u32; 3],
                                        offsets: [[u32; 3]; 2]|
              -> Result<_, CopyError> {
                 match image_type {
                     ImageType::Dim1d => {
-                        // VUID-VkBlitImageInfo2-srcImage-00245
-                        // VUID-VkBlitImageInfo2-dstImage-00250
                         if !(offsets[0][1] == 0 && offsets[1][1] == 1) {
-                            return Err(CopyError::OffsetsInvalidForImageType {
-                                resource,
-                                region_index,
-                                offsets: [offsets[0][1], offsets[1][1]],
-                            });
+                            return Err(CopyError::ValidationError(ValidationError {
+                                code: "offsets_invalid_for_image_type",
+                                context: "blit_image",
+                                problem: format!(
+                                    "the {} image offsets ({:?}) of region {} are not the values \
+                                    required for that axis ([0, 1]) for the type of the {} image",
+                                    resource,
+                                    [offsets[0][1], offsets[1][1]],
+                                    region_index,
+                                    resource,
+                                )
+                                .into(),
+                                vuids: &[
+                                    "VUID-VkBlitImageInfo2-srcImage-00245",
+                                    "VUID-VkBlitImageInfo2-dstImage-00250",
+                                ],
+                                ..Default::default()
+                            }));
                         }
 
-                        // VUID-VkBlitImageInfo2-srcImage-00247
-                        // VUID-VkBlitImageInfo2-dstImage-00252
                         if !(offsets[0][2] == 0 && offsets[1][2] == 1) {
-                            return Err(CopyError::OffsetsInvalidForImageType {
-                                resource,
-                                region_index,
-                                offsets: [offsets[0][2], offsets[1][2]],
-                            });
+                            return Err(CopyError::ValidationError(ValidationError {
+                                code: "offsets_invalid_for_image_type",
+                                context: "blit_image",
+                                problem: format!(
+                                    "the {} image offsets ({:?}) of region {} are not the values \
+                                    required for that axis ([0, 1]) for the type of the {} image",
+                                    resource,
+                                    [offsets[0][2], offsets[1][2]],
+                                    region_index,
+                                    resource,
+                                )
+                                .into(),
+                                vuids: &[
+                                    "VUID-VkBlitImageInfo2-srcImage-00247",
+                                    "VUID-VkBlitImageInfo2-dstImage-00252",
+                                ],
+                                ..Default::default()
+                            }));
                         }
                     }
                     ImageType::Dim2d => {
-                        // VUID-VkBlitImageInfo2-srcImage-00247
-                        // VUID-VkBlitImageInfo2-dstImage-00252
                         if !(offsets[0][2] == 0 && offsets[1][2] == 1) {
-                            return Err(CopyError::OffsetsInvalidForImageType {
-                                resource,
-                                region_index,
-                                offsets: [offsets[0][2], offsets[1][2]],
-                            });
+                            return Err(CopyError::ValidationError(ValidationError {
+                                code: "offsets_invalid_for_image_type",
+                                context: "blit_image",
+                                problem: format!(
+                                    "the {} image offsets ({:?}) of region {} are not the values \
+                                    required for that axis ([0, 1]) for the type of the {} image",
+                                    resource,
+                                    [offsets[0][2], offsets[1][2]],
+                                    region_index,
+                                    resource,
+                                )
+                                .into(),
+                                vuids: &[
+                                    "VUID-VkBlitImageInfo2-srcImage-00247",
+                                    "VUID-VkBlitImageInfo2-dstImage-00252",
+                                ],
+                                ..Default::default()
+                            }));
                         }
                     }
                     ImageType::Dim3d => (),
@@ -441,19 +612,31 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 ];
 
                 for i in 0..3 {
-                    // VUID-VkBlitImageInfo2-srcOffset-00243
-                    // VUID-VkBlitImageInfo2-srcOffset-00244
-                    // VUID-VkBlitImageInfo2-srcOffset-00246
-                    // VUID-VkBlitImageInfo2-dstOffset-00248
-                    // VUID-VkBlitImageInfo2-dstOffset-00249
-                    // VUID-VkBlitImageInfo2-dstOffset-00251
                     if offset_range_end[i] > subresource_extent[i] {
-                        return Err(CopyError::RegionOutOfImageBounds {
-                            resource,
-                            region_index,
-                            offset_range_end,
-                            subresource_extent,
-                        });
+                        return Err(CopyError::ValidationError(ValidationError {
+                            code: "region_out_of_image_bounds",
+                            context: "blit_image",
+                            problem: format!(
+                                "the end of the range of accessed {} texel offsets ({:?}) of \
+                                region {} is greater than the extent of the selected subresource \
+                                of the {} image ({:?})",
+                                resource,
+                                offset_range_end,
+                                region_index,
+                                resource,
+                                subresource_extent,
+                            )
+                            .into(),
+                            vuids: &[
+                                "VUID-VkBlitImageInfo2-srcOffset-00243",
+                                "VUID-VkBlitImageInfo2-srcOffset-00244",
+                                "VUID-VkBlitImageInfo2-srcOffset-00246",
+                                "VUID-VkBlitImageInfo2-dstOffset-00248",
+                                "VUID-VkBlitImageInfo2-dstOffset-00249",
+                                "VUID-VkBlitImageInfo2-dstOffset-00251",
+                            ],
+                            ..Default::default()
+                        }));
                     }
                 }
 
@@ -461,13 +644,13 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             };
 
             check_offset_extent(
-                CopyErrorResource::Source,
+                "source",
                 src_image_type,
                 src_subresource_extent,
                 src_offsets,
             )?;
             check_offset_extent(
-                CopyErrorResource::Destination,
+                "destination",
                 dst_image_type,
                 dst_subresource_extent,
                 dst_offsets,
@@ -542,24 +725,39 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             }
         }
 
-        // VUID-VkBlitImageInfo2-pRegions-00217
         if let Some((src_region_index, dst_region_index)) = overlap_extent_indices {
-            return Err(CopyError::OverlappingRegions {
-                src_region_index,
-                dst_region_index,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "overlapping_regions",
+                context: "blit_image",
+                problem: format!(
+                    "the source bounds of region {} overlap with the destination bounds of region \
+                    {}",
+                    src_region_index, dst_region_index,
+                )
+                .into(),
+                vuids: &["VUID-VkBlitImageInfo2-pRegions-00217"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkBlitImageInfo2-srcImageLayout-00221
-        // VUID-VkBlitImageInfo2-dstImageLayout-00226
         if let Some((src_region_index, dst_region_index)) = overlap_subresource_indices {
             if src_image_layout != dst_image_layout {
-                return Err(CopyError::OverlappingSubresourcesLayoutMismatch {
-                    src_region_index,
-                    dst_region_index,
-                    src_image_layout,
-                    dst_image_layout,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "overlapping_subresources_layout_mismatch",
+                    context: "blit_image",
+                    problem: format!(
+                        "the source subresources of region {} overlap with the destination \
+                        subresources of region {}, but the source image layout ({:?}) does not \
+                        equal the destination image layout ({:?})",
+                        src_region_index, dst_region_index, src_image_layout, dst_image_layout,
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkBlitImageInfo2-srcImageLayout-00221",
+                        "VUID-VkBlitImageInfo2-dstImageLayout-00226",
+                    ],
+                    ..Default::default()
+                }));
             }
         }
 
@@ -607,101 +805,162 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         // VUID-vkCmdClearColorImage-commonparent
         assert_eq!(device, image.device());
 
-        // VUID-vkCmdClearColorImage-image-00002
         if !image.usage().transfer_dst {
-            return Err(CopyError::MissingUsage {
-                resource: CopyErrorResource::Destination,
-                usage: "transfer_dst",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_usage",
+                context: "clear_color_image",
+                problem: "the destination resource did not have the required usage transfer_dst \
+                    enabled"
+                    .into(),
+                vuids: &["VUID-vkCmdClearColorImage-image-00002"],
+                ..Default::default()
+            }));
         }
 
         if device.api_version() >= Version::V1_1 || device.enabled_extensions().khr_maintenance1 {
-            // VUID-vkCmdClearColorImage-image-01993
             if !image.format_features().transfer_dst {
-                return Err(CopyError::MissingFormatFeature {
-                    resource: CopyErrorResource::Destination,
-                    format_feature: "transfer_dst",
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "missing_format_feature",
+                    context: "clear_color_image",
+                    problem: "the destination image does not have the required format feature \
+                        transfer_dst"
+                        .into(),
+                    vuids: &["VUID-vkCmdClearColorImage-image-01993"],
+                    ..Default::default()
+                }));
             }
         }
 
         let image_aspects = image.format().aspects();
 
-        // VUID-vkCmdClearColorImage-image-00007
         if image_aspects.depth || image_aspects.stencil {
-            return Err(CopyError::FormatNotSupported {
-                resource: CopyErrorResource::Destination,
-                format: image.format(),
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "format_not_supported",
+                context: "clear_color_image",
+                problem: format!(
+                    "the format of the destination image ({:?}) is not supported for this \
+                    operation",
+                    image.format(),
+                )
+                .into(),
+                vuids: &["VUID-vkCmdClearColorImage-image-00007"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-vkCmdClearColorImage-image-00007
         if image.format().compression().is_some() {
-            return Err(CopyError::FormatNotSupported {
-                resource: CopyErrorResource::Destination,
-                format: image.format(),
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "format_not_supported",
+                context: "clear_color_image",
+                problem: format!(
+                    "the format of the destination image ({:?}) is not supported for this \
+                    operation",
+                    image.format(),
+                )
+                .into(),
+                vuids: &["VUID-vkCmdClearColorImage-image-00007"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-vkCmdClearColorImage-image-01545
         if image.format().ycbcr_chroma_sampling().is_some() {
-            return Err(CopyError::FormatNotSupported {
-                resource: CopyErrorResource::Destination,
-                format: image.format(),
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "format_not_supported",
+                context: "clear_color_image",
+                problem: format!(
+                    "the format of the destination image ({:?}) is not supported for this \
+                    operation",
+                    image.format(),
+                )
+                .into(),
+                vuids: &["VUID-vkCmdClearColorImage-image-01545"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-vkCmdClearColorImage-imageLayout-01394
         if !matches!(
             image_layout,
             ImageLayout::TransferDstOptimal | ImageLayout::General
         ) {
-            return Err(CopyError::ImageLayoutInvalid {
-                resource: CopyErrorResource::Destination,
-                image_layout,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "image_layout_invalid",
+                context: "clear_color_image",
+                problem: format!(
+                    "the specified destination image layout {:?} is not valid for this operation",
+                    image_layout,
+                )
+                .into(),
+                vuids: &["VUID-vkCmdClearColorImage-imageLayout-01394"],
+                ..Default::default()
+            }));
         }
 
         for (region_index, subresource_range) in regions.iter().enumerate() {
             // VUID-VkImageSubresourceRange-aspectMask-requiredbitmask
             assert!(subresource_range.aspects != ImageAspects::none());
 
-            // VUID-vkCmdClearColorImage-aspectMask-02498
             if !image_aspects.contains(&subresource_range.aspects) {
-                return Err(CopyError::AspectsNotAllowed {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    aspects: subresource_range.aspects,
-                    allowed_aspects: image_aspects,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "aspects_not_allowed",
+                    context: "clear_color_image",
+                    problem: format!(
+                        "the aspects ({:?}) of the destination subresource range of region {} \
+                        contain aspects that are not present in the destination image, or that \
+                        are not allowed ({:?})",
+                        subresource_range.aspects, region_index, image_aspects,
+                    )
+                    .into(),
+                    vuids: &["VUID-vkCmdClearColorImage-aspectMask-02498"],
+                    ..Default::default()
+                }));
             }
 
             // VUID-VkImageSubresourceRange-levelCount-01720
             assert!(!subresource_range.mip_levels.is_empty());
 
-            // VUID-vkCmdClearColorImage-baseMipLevel-01470
-            // VUID-vkCmdClearColorImage-pRanges-01692
             if subresource_range.mip_levels.end > image.mip_levels() {
-                return Err(CopyError::MipLevelsOutOfRange {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    mip_levels_range_end: subresource_range.mip_levels.end,
-                    image_mip_levels: image.dimensions().array_layers(),
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "mip_levels_out_of_range",
+                    context: "clear_color_image",
+                    problem: format!(
+                        "the end of the range of accessed mip levels ({}) of the destination \
+                        subresource range of region {} is not less than the number of mip levels \
+                        in the destination image ({})",
+                        subresource_range.mip_levels.end,
+                        region_index,
+                        image.dimensions().array_layers(),
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-vkCmdClearColorImage-baseMipLevel-01470",
+                        "VUID-vkCmdClearColorImage-pRanges-01692",
+                    ],
+                    ..Default::default()
+                }));
             }
 
             // VUID-VkImageSubresourceRange-layerCount-01721
             assert!(!subresource_range.array_layers.is_empty());
 
-            // VUID-vkCmdClearDepthStencilImage-baseArrayLayer-01476
-            // VUID-vkCmdClearDepthStencilImage-pRanges-01695
             if subresource_range.array_layers.end > image.dimensions().array_layers() {
-                return Err(CopyError::ArrayLayersOutOfRange {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    array_layers_range_end: subresource_range.array_layers.end,
-                    image_array_layers: image.dimensions().array_layers(),
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "array_layers_out_of_range",
+                    context: "clear_color_image",
+                    problem: format!(
+                        "the end of the range of accessed array layers ({}) of the destination \
+                        subresource range of region {} is greater than the number of array \
+                        layers in the destination image ({})",
+                        subresource_range.array_layers.end,
+                        region_index,
+                        image.dimensions().array_layers(),
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-vkCmdClearDepthStencilImage-baseArrayLayer-01476",
+                        "VUID-vkCmdClearDepthStencilImage-pRanges-01695",
+                    ],
+                    ..Default::default()
+                }));
             }
         }
 
@@ -749,46 +1008,67 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         // VUID-vkCmdClearDepthStencilImage-commonparent
         assert_eq!(device, image.device());
 
-        // VUID-vkCmdClearDepthStencilImage-pRanges-02659
-        // VUID-vkCmdClearDepthStencilImage-pRanges-02660
         if !image.usage().transfer_dst {
-            if !image.usage().transfer_dst {
-                return Err(CopyError::MissingUsage {
-                    resource: CopyErrorResource::Destination,
-                    usage: "transfer_dst",
-                });
-            }
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_usage",
+                context: "clear_depth_stencil_image",
+                problem: "the destination resource did not have the required usage transfer_dst \
+                    enabled"
+                    .into(),
+                vuids: &[
+                    "VUID-vkCmdClearDepthStencilImage-pRanges-02659",
+                    "VUID-vkCmdClearDepthStencilImage-pRanges-02660",
+                ],
+                ..Default::default()
+            }));
         }
 
         if device.api_version() >= Version::V1_1 || device.enabled_extensions().khr_maintenance1 {
-            // VUID-vkCmdClearDepthStencilImage-image-01994
             if !image.format_features().transfer_dst {
-                return Err(CopyError::MissingFormatFeature {
-                    resource: CopyErrorResource::Destination,
-                    format_feature: "transfer_dst",
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "missing_format_feature",
+                    context: "clear_depth_stencil_image",
+                    problem: "the destination image does not have the required format feature \
+                        transfer_dst"
+                        .into(),
+                    vuids: &["VUID-vkCmdClearDepthStencilImage-image-01994"],
+                    ..Default::default()
+                }));
             }
         }
 
         let image_aspects = image.format().aspects();
 
-        // VUID-vkCmdClearDepthStencilImage-image-00014
         if !(image_aspects.depth || image_aspects.stencil) {
-            return Err(CopyError::FormatNotSupported {
-                resource: CopyErrorResource::Destination,
-                format: image.format(),
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "format_not_supported",
+                context: "clear_depth_stencil_image",
+                problem: format!(
+                    "the format of the destination image ({:?}) is not supported for this \
+                    operation",
+                    image.format(),
+                )
+                .into(),
+                vuids: &["VUID-vkCmdClearDepthStencilImage-image-00014"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-vkCmdClearDepthStencilImage-imageLayout-00012
         if !matches!(
             image_layout,
             ImageLayout::TransferDstOptimal | ImageLayout::General
         ) {
-            return Err(CopyError::ImageLayoutInvalid {
-                resource: CopyErrorResource::Destination,
-                image_layout,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "image_layout_invalid",
+                context: "clear_depth_stencil_image",
+                problem: format!(
+                    "the specified destination image layout {:?} is not valid for this operation",
+                    image_layout,
+                )
+                .into(),
+                vuids: &["VUID-vkCmdClearDepthStencilImage-imageLayout-00012"],
+                ..Default::default()
+            }));
         }
 
         // VUID-VkClearDepthStencilValue-depth-00022
@@ -805,44 +1085,72 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             // VUID-VkImageSubresourceRange-aspectMask-requiredbitmask
             assert!(subresource_range.aspects != ImageAspects::none());
 
-            // VUID-vkCmdClearDepthStencilImage-aspectMask-02824
-            // VUID-vkCmdClearDepthStencilImage-image-02825
-            // VUID-vkCmdClearDepthStencilImage-image-02826
             if !image_aspects.contains(&subresource_range.aspects) {
-                return Err(CopyError::AspectsNotAllowed {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    aspects: subresource_range.aspects,
-                    allowed_aspects: image_aspects,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "aspects_not_allowed",
+                    context: "clear_depth_stencil_image",
+                    problem: format!(
+                        "the aspects ({:?}) of the destination subresource range of region {} \
+                        contain aspects that are not present in the destination image, or that \
+                        are not allowed ({:?})",
+                        subresource_range.aspects, region_index, image_aspects,
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-vkCmdClearDepthStencilImage-aspectMask-02824",
+                        "VUID-vkCmdClearDepthStencilImage-image-02825",
+                        "VUID-vkCmdClearDepthStencilImage-image-02826",
+                    ],
+                    ..Default::default()
+                }));
             }
 
             // VUID-VkImageSubresourceRange-levelCount-01720
             assert!(!subresource_range.mip_levels.is_empty());
 
-            // VUID-vkCmdClearDepthStencilImage-baseMipLevel-01474
-            // VUID-vkCmdClearDepthStencilImage-pRanges-01694
             if subresource_range.mip_levels.end > image.mip_levels() {
-                return Err(CopyError::MipLevelsOutOfRange {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    mip_levels_range_end: subresource_range.mip_levels.end,
-                    image_mip_levels: image.dimensions().array_layers(),
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "mip_levels_out_of_range",
+                    context: "clear_depth_stencil_image",
+                    problem: format!(
+                        "the end of the range of accessed mip levels ({}) of the destination \
+                        subresource range of region {} is not less than the number of mip levels \
+                        in the destination image ({})",
+                        subresource_range.mip_levels.end,
+                        region_index,
+                        image.dimensions().array_layers(),
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-vkCmdClearDepthStencilImage-baseMipLevel-01474",
+                        "VUID-vkCmdClearDepthStencilImage-pRanges-01694",
+                    ],
+                    ..Default::default()
+                }));
             }
 
             // VUID-VkImageSubresourceRange-layerCount-01721
             assert!(!subresource_range.array_layers.is_empty());
 
-            // VUID-vkCmdClearDepthStencilImage-baseArrayLayer-01476
-            // VUID-vkCmdClearDepthStencilImage-pRanges-01695
             if subresource_range.array_layers.end > image.dimensions().array_layers() {
-                return Err(CopyError::ArrayLayersOutOfRange {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    array_layers_range_end: subresource_range.array_layers.end,
-                    image_array_layers: image.dimensions().array_layers(),
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "array_layers_out_of_range",
+                    context: "clear_depth_stencil_image",
+                    problem: format!(
+                        "the end of the range of accessed array layers ({}) of the destination \
+                        subresource range of region {} is greater than the number of array \
+                        layers in the destination image ({})",
+                        subresource_range.array_layers.end,
+                        region_index,
+                        image.dimensions().array_layers(),
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-vkCmdClearDepthStencilImage-baseArrayLayer-01476",
+                        "VUID-vkCmdClearDepthStencilImage-pRanges-01695",
+                    ],
+                    ..Default::default()
+                }));
             }
         }
 
@@ -900,76 +1208,114 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         let src_image_type = src_image.dimensions().image_type();
         let dst_image_type = dst_image.dimensions().image_type();
 
-        // VUID-VkResolveImageInfo2-srcImage-00257
         if src_image.samples() == SampleCount::Sample1 {
-            return Err(CopyError::SampleCountInvalid {
-                resource: CopyErrorResource::Source,
-                sample_count: dst_image.samples(),
-                allowed_sample_counts: SampleCounts {
-                    sample1: false,
-                    sample2: true,
-                    sample4: true,
-                    sample8: true,
-                    sample16: true,
-                    sample32: true,
-                    sample64: true,
-                },
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "sample_count_invalid",
+                context: "resolve_image",
+                problem: format!(
+                    "the source image has a sample count ({:?}) that is not valid for this \
+                    operation ({:?})",
+                    dst_image.samples(),
+                    SampleCounts {
+                        sample1: false,
+                        sample2: true,
+                        sample4: true,
+                        sample8: true,
+                        sample16: true,
+                        sample32: true,
+                        sample64: true,
+                    },
+                )
+                .into(),
+                vuids: &["VUID-VkResolveImageInfo2-srcImage-00257"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkResolveImageInfo2-dstImage-00259
         if dst_image.samples() != SampleCount::Sample1 {
-            return Err(CopyError::SampleCountInvalid {
-                resource: CopyErrorResource::Destination,
-                sample_count: dst_image.samples(),
-                allowed_sample_counts: SampleCounts {
-                    sample1: true,
-                    sample2: false,
-                    sample4: false,
-                    sample8: false,
-                    sample16: false,
-                    sample32: false,
-                    sample64: false,
-                },
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "sample_count_invalid",
+                context: "resolve_image",
+                problem: format!(
+                    "the destination image has a sample count ({:?}) that is not valid for this \
+                    operation ({:?})",
+                    dst_image.samples(),
+                    SampleCounts {
+                        sample1: true,
+                        sample2: false,
+                        sample4: false,
+                        sample8: false,
+                        sample16: false,
+                        sample32: false,
+                        sample64: false,
+                    },
+                )
+                .into(),
+                vuids: &["VUID-VkResolveImageInfo2-dstImage-00259"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkResolveImageInfo2-dstImage-02003
         if !dst_image.format_features().color_attachment {
-            return Err(CopyError::MissingFormatFeature {
-                resource: CopyErrorResource::Destination,
-                format_feature: "color_attachment",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_format_feature",
+                context: "resolve_image",
+                problem: "the destination image does not have the required format feature \
+                    color_attachment"
+                    .into(),
+                vuids: &["VUID-VkResolveImageInfo2-dstImage-02003"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkResolveImageInfo2-srcImage-01386
         if src_image.format() != dst_image.format() {
-            return Err(CopyError::FormatsMismatch {
-                src_format: src_image.format(),
-                dst_format: dst_image.format(),
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "formats_mismatch",
+                context: "resolve_image",
+                problem: format!(
+                    "the format of the source image ({:?}) does not match the format of the \
+                    destination image ({:?})",
+                    src_image.format(),
+                    dst_image.format(),
+                )
+                .into(),
+                vuids: &["VUID-VkResolveImageInfo2-srcImage-01386"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkResolveImageInfo2-srcImageLayout-01400
         if !matches!(
             src_image_layout,
             ImageLayout::TransferSrcOptimal | ImageLayout::General
         ) {
-            return Err(CopyError::ImageLayoutInvalid {
-                resource: CopyErrorResource::Source,
-                image_layout: src_image_layout,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "image_layout_invalid",
+                context: "resolve_image",
+                problem: format!(
+                    "the specified source image layout {:?} is not valid for this operation",
+                    src_image_layout,
+                )
+                .into(),
+                vuids: &["VUID-VkResolveImageInfo2-srcImageLayout-01400"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkResolveImageInfo2-dstImageLayout-01401
         if !matches!(
             dst_image_layout,
             ImageLayout::TransferDstOptimal | ImageLayout::General
         ) {
-            return Err(CopyError::ImageLayoutInvalid {
-                resource: CopyErrorResource::Destination,
-                image_layout: dst_image_layout,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "image_layout_invalid",
+                context: "resolve_image",
+                problem: format!(
+                    "the specified destination image layout {:?} is not valid for this operation",
+                    dst_image_layout,
+                )
+                .into(),
+                vuids: &["VUID-VkResolveImageInfo2-dstImageLayout-01401"],
+                ..Default::default()
+            }));
         }
 
         // Should be guaranteed by the requirement that formats match, and that the destination
@@ -986,19 +1332,31 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 _ne: _,
             } = region;
 
-            let check_subresource = |resource: CopyErrorResource,
+            let check_subresource = |resource: &'static str,
                                      image: &dyn ImageAccess,
                                      subresource: &ImageSubresourceLayers|
              -> Result<_, CopyError> {
-                // VUID-VkResolveImageInfo2-srcSubresource-01709
-                // VUID-VkResolveImageInfo2-dstSubresource-01710
                 if subresource.mip_level >= image.mip_levels() {
-                    return Err(CopyError::MipLevelsOutOfRange {
-                        resource,
-                        region_index,
-                        mip_levels_range_end: subresource.mip_level + 1,
-                        image_mip_levels: image.mip_levels(),
-                    });
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "mip_levels_out_of_range",
+                        context: "resolve_image",
+                        problem: format!(
+                            "the end of the range of accessed mip levels ({}) of the {} \
+                            subresource range of region {} is not less than the number of mip \
+                            levels in the {} image ({})",
+                            subresource.mip_level + 1,
+                            resource,
+                            region_index,
+                            resource,
+                            image.mip_levels(),
+                        )
+                        .into(),
+                        vuids: &[
+                            "VUID-VkResolveImageInfo2-srcSubresource-01709",
+                            "VUID-VkResolveImageInfo2-dstSubresource-01710",
+                        ],
+                        ..Default::default()
+                    }));
                 }
 
                 // VUID-VkImageSubresourceLayers-layerCount-01700
@@ -1006,36 +1364,58 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 // VUID-VkResolveImageInfo2-srcImage-04447
                 assert!(!subresource.array_layers.is_empty());
 
-                // VUID-VkResolveImageInfo2-srcSubresource-01711
-                // VUID-VkResolveImageInfo2-dstSubresource-01712
-                // VUID-VkResolveImageInfo2-srcImage-04446
-                // VUID-VkResolveImageInfo2-srcImage-04447
                 if subresource.array_layers.end > image.dimensions().array_layers() {
-                    return Err(CopyError::ArrayLayersOutOfRange {
-                        resource: CopyErrorResource::Destination,
-                        region_index,
-                        array_layers_range_end: subresource.array_layers.end,
-                        image_array_layers: image.dimensions().array_layers(),
-                    });
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "array_layers_out_of_range",
+                        context: "resolve_image",
+                        problem: format!(
+                            "the end of the range of accessed array layers ({}) of the \
+                            destination subresource range of region {} is greater than the \
+                            number of array layers in the destination image ({})",
+                            subresource.array_layers.end,
+                            region_index,
+                            image.dimensions().array_layers(),
+                        )
+                        .into(),
+                        vuids: &[
+                            "VUID-VkResolveImageInfo2-srcSubresource-01711",
+                            "VUID-VkResolveImageInfo2-dstSubresource-01712",
+                            "VUID-VkResolveImageInfo2-srcImage-04446",
+                            "VUID-VkResolveImageInfo2-srcImage-04447",
+                        ],
+                        ..Default::default()
+                    }));
                 }
 
-                // VUID-VkImageSubresourceLayers-aspectMask-requiredbitmask
-                // VUID-VkImageResolve2-aspectMask-00266
                 if subresource.aspects
                     != (ImageAspects {
                         color: true,
                         ..ImageAspects::none()
                     })
                 {
-                    return Err(CopyError::AspectsNotAllowed {
-                        resource,
-                        region_index,
-                        aspects: subresource.aspects,
-                        allowed_aspects: ImageAspects {
-                            color: true,
-                            ..ImageAspects::none()
-                        },
-                    });
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "aspects_not_allowed",
+                        context: "resolve_image",
+                        problem: format!(
+                            "the aspects ({:?}) of the {} subresource range of region {} contain \
+                            aspects that are not present in the {} image, or that are not \
+                            allowed ({:?})",
+                            subresource.aspects,
+                            resource,
+                            region_index,
+                            resource,
+                            ImageAspects {
+                                color: true,
+                                ..ImageAspects::none()
+                            },
+                        )
+                        .into(),
+                        vuids: &[
+                            "VUID-VkImageSubresourceLayers-aspectMask-requiredbitmask",
+                            "VUID-VkImageResolve2-aspectMask-00266",
+                        ],
+                        ..Default::default()
+                    }));
                 }
 
                 Ok(image
@@ -1045,31 +1425,38 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                     .width_height_depth())
             };
 
-            let src_subresource_extent =
-                check_subresource(CopyErrorResource::Source, src_image, src_subresource)?;
+            let src_subresource_extent = check_subresource("source", src_image, src_subresource)?;
             let dst_subresource_extent =
-                check_subresource(CopyErrorResource::Destination, dst_image, dst_subresource)?;
+                check_subresource("destination", dst_image, dst_subresource)?;
 
             let src_layer_count =
                 src_subresource.array_layers.end - src_subresource.array_layers.start;
             let dst_layer_count =
                 dst_subresource.array_layers.end - dst_subresource.array_layers.start;
 
-            // VUID-VkImageResolve2-layerCount-00267
-            // VUID-VkResolveImageInfo2-srcImage-04446
-            // VUID-VkResolveImageInfo2-srcImage-04447
             if src_layer_count != dst_layer_count {
-                return Err(CopyError::ArrayLayerCountMismatch {
-                    region_index,
-                    src_layer_count,
-                    dst_layer_count,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "array_layer_count_mismatch",
+                    context: "resolve_image",
+                    problem: format!(
+                        "the array layer counts of the source and destination subresource ranges \
+                        of region {} do not match (source: {}; destination: {})",
+                        region_index, src_layer_count, dst_layer_count,
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkImageResolve2-layerCount-00267",
+                        "VUID-VkResolveImageInfo2-srcImage-04446",
+                        "VUID-VkResolveImageInfo2-srcImage-04447",
+                    ],
+                    ..Default::default()
+                }));
             }
 
             // No VUID, but it makes sense?
             assert!(extent[0] != 0 && extent[1] != 0 && extent[2] != 0);
 
-            let check_offset_extent = |resource: CopyErrorResource,
+            let check_offset_extent = |resource: &'static str,
                                        image_type: ImageType,
                                        subresource_extent: [u32; 3],
                                        offset: [u32; 3]|
@@ -1078,37 +1465,44 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                     // No VUID, but makes sense?
                     assert!(extent[i] != 0);
 
-                    // VUID-VkResolveImageInfo2-srcOffset-00269
-                    // VUID-VkResolveImageInfo2-srcOffset-00270
-                    // VUID-VkResolveImageInfo2-srcOffset-00272
-                    // VUID-VkResolveImageInfo2-dstOffset-00274
-                    // VUID-VkResolveImageInfo2-dstOffset-00275
-                    // VUID-VkResolveImageInfo2-dstOffset-00277
                     if offset[i] + extent[i] > subresource_extent[i] {
-                        return Err(CopyError::RegionOutOfImageBounds {
-                            resource,
-                            region_index,
-                            offset_range_end: [
-                                offset[0] + extent[0],
-                                offset[1] + extent[1],
-                                offset[2] + extent[2],
+                        return Err(CopyError::ValidationError(ValidationError {
+                            code: "region_out_of_image_bounds",
+                            context: "resolve_image",
+                            problem: format!(
+                                "the end of the range of accessed {} texel offsets ({:?}) of \
+                                region {} is greater than the extent of the selected subresource \
+                                of the {} image ({:?})",
+                                resource,
+                                [
+                                    offset[0] + extent[0],
+                                    offset[1] + extent[1],
+                                    offset[2] + extent[2],
+                                ],
+                                region_index,
+                                resource,
+                                subresource_extent,
+                            )
+                            .into(),
+                            vuids: &[
+                                "VUID-VkResolveImageInfo2-srcOffset-00269",
+                                "VUID-VkResolveImageInfo2-srcOffset-00270",
+                                "VUID-VkResolveImageInfo2-srcOffset-00272",
+                                "VUID-VkResolveImageInfo2-dstOffset-00274",
+                                "VUID-VkResolveImageInfo2-dstOffset-00275",
+                                "VUID-VkResolveImageInfo2-dstOffset-00277",
                             ],
-                            subresource_extent,
-                        });
+                            ..Default::default()
+                        }));
                     }
                 }
 
                 Ok(())
             };
 
+            check_offset_extent("source", src_image_type, src_subresource_extent, src_offset)?;
             check_offset_extent(
-                CopyErrorResource::Source,
-                src_image_type,
-                src_subresource_extent,
-                src_offset,
-            )?;
-            check_offset_extent(
-                CopyErrorResource::Destination,
+                "destination",
                 dst_image_type,
                 dst_subresource_extent,
                 dst_offset,
@@ -20,7 +20,7 @@ use crate::{
         DrawIndexedIndirectCommand, DrawIndexedIndirectError, DrawIndirectCommand,
         DrawIndirectError,
     },
-    descriptor_set::{layout::DescriptorType, DescriptorBindingResources},
+    descriptor_set::{layout::DescriptorType, DescriptorBindingResources, DescriptorSetResources},
     device::{Device, DeviceOwned},
     format::Format,
     image::{
@@ -38,7 +38,7 @@ use crate::{
     sampler::{Sampler, SamplerImageViewIncompatibleError},
     shader::{DescriptorRequirements, ShaderScalarType, ShaderStage},
     sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
-    DeviceSize, VulkanObject,
+    DeviceSize, Version, VulkanObject,
 };
 use std::{borrow::Cow, error, fmt, mem::size_of, ops::Range, sync::Arc};
 
@@ -199,6 +199,63 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         Ok(self)
     }
 
+    /// Perform multiple draw operations using a graphics pipeline, reading the number of draws
+    /// from a buffer.
+    ///
+    /// This is the same as [`draw_indirect`](Self::draw_indirect), except that the number of
+    /// [`DrawIndirectCommand`] structs to read from `indirect_buffer` is read from `count_buffer`
+    /// when the command is executed. If that number is greater than the length of
+    /// `indirect_buffer`, the length is used instead.
+    ///
+    /// This allows the draw commands and their number to be written by the device, for example by
+    /// a compute shader that culls objects.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if neither the
+    ///   [`draw_indirect_count`](crate::device::Features::draw_indirect_count) feature nor the
+    ///   [`khr_draw_indirect_count`](crate::device::DeviceExtensions::khr_draw_indirect_count)
+    ///   extension is enabled on the device.
+    #[inline]
+    pub fn draw_indirect_count<Inb, Cb>(
+        &mut self,
+        indirect_buffer: Arc<Inb>,
+        count_buffer: Arc<Cb>,
+    ) -> Result<&mut Self, DrawIndirectError>
+    where
+        Inb: TypedBufferAccess<Content = [DrawIndirectCommand]> + Send + Sync + 'static,
+        Cb: TypedBufferAccess<Content = u32> + Send + Sync + 'static,
+    {
+        // VUID-vkCmdDrawIndirectCount-None-04445
+        assert!(
+            self.device().enabled_features().draw_indirect_count
+                || self.device().enabled_extensions().khr_draw_indirect_count,
+            "the draw_indirect_count feature or the khr_draw_indirect_count extension must be enabled on the device"
+        );
+
+        let pipeline = check_pipeline_graphics(self.state())?;
+        self.ensure_inside_render_pass_inline(pipeline)?;
+        check_dynamic_state_validity(self.state(), pipeline)?;
+        check_descriptor_sets_validity(self.state(), pipeline, pipeline.descriptor_requirements())?;
+        check_push_constants_validity(self.state(), pipeline.layout())?;
+        check_vertex_buffers(self.state(), pipeline, None, None)?;
+        check_indirect_buffer(self.device(), indirect_buffer.as_ref())?;
+        check_indirect_buffer(self.device(), count_buffer.as_ref())?;
+
+        let max_draw_count = indirect_buffer.len() as u32;
+
+        unsafe {
+            self.inner.draw_indirect_count(
+                indirect_buffer,
+                count_buffer,
+                max_draw_count,
+                size_of::<DrawIndirectCommand>() as u32,
+            )?;
+        }
+
+        Ok(self)
+    }
+
     /// Perform a single draw operation using a graphics pipeline, using an index buffer.
     ///
     /// The parameters specify the first index and the number of indices in the index buffer that
@@ -317,6 +374,64 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
 
         Ok(self)
     }
+
+    /// Perform multiple draw operations using a graphics pipeline, using an index buffer, and
+    /// reading the number of draws from a buffer.
+    ///
+    /// This is the same as [`draw_indexed_indirect`](Self::draw_indexed_indirect), except that
+    /// the number of [`DrawIndexedIndirectCommand`] structs to read from `indirect_buffer` is read
+    /// from `count_buffer` when the command is executed. If that number is greater than the
+    /// length of `indirect_buffer`, the length is used instead.
+    ///
+    /// This allows the draw commands and their number to be written by the device, for example by
+    /// a compute shader that culls objects.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if neither the
+    ///   [`draw_indirect_count`](crate::device::Features::draw_indirect_count) feature nor the
+    ///   [`khr_draw_indirect_count`](crate::device::DeviceExtensions::khr_draw_indirect_count)
+    ///   extension is enabled on the device.
+    #[inline]
+    pub fn draw_indexed_indirect_count<Inb, Cb>(
+        &mut self,
+        indirect_buffer: Arc<Inb>,
+        count_buffer: Arc<Cb>,
+    ) -> Result<&mut Self, DrawIndexedIndirectError>
+    where
+        Inb: TypedBufferAccess<Content = [DrawIndexedIndirectCommand]> + 'static,
+        Cb: TypedBufferAccess<Content = u32> + 'static,
+    {
+        // VUID-vkCmdDrawIndexedIndirectCount-None-04445
+        assert!(
+            self.device().enabled_features().draw_indirect_count
+                || self.device().enabled_extensions().khr_draw_indirect_count,
+            "the draw_indirect_count feature or the khr_draw_indirect_count extension must be enabled on the device"
+        );
+
+        let pipeline = check_pipeline_graphics(self.state())?;
+        self.ensure_inside_render_pass_inline(pipeline)?;
+        check_dynamic_state_validity(self.state(), pipeline)?;
+        check_descriptor_sets_validity(self.state(), pipeline, pipeline.descriptor_requirements())?;
+        check_push_constants_validity(self.state(), pipeline.layout())?;
+        check_vertex_buffers(self.state(), pipeline, None, None)?;
+        check_index_buffer(self.state(), None)?;
+        check_indirect_buffer(self.device(), indirect_buffer.as_ref())?;
+        check_indirect_buffer(self.device(), count_buffer.as_ref())?;
+
+        let max_draw_count = indirect_buffer.len() as u32;
+
+        unsafe {
+            self.inner.draw_indexed_indirect_count(
+                indirect_buffer,
+                count_buffer,
+                max_draw_count,
+                size_of::<DrawIndexedIndirectCommand>() as u32,
+            )?;
+        }
+
+        Ok(self)
+    }
 }
 
 fn check_pipeline_compute(
@@ -617,28 +732,64 @@ fn check_descriptor_sets_validity<'a, P: Pipeline>(
 
         match binding_resources {
             DescriptorBindingResources::None(elements) => {
-                check_resources(set_num, binding_num, reqs, elements, check_none)?;
+                check_resources(
+                    set_num,
+                    binding_num,
+                    set_resources,
+                    reqs,
+                    elements,
+                    check_none,
+                )?;
             }
             DescriptorBindingResources::Buffer(elements) => {
-                check_resources(set_num, binding_num, reqs, elements, check_buffer)?;
+                check_resources(
+                    set_num,
+                    binding_num,
+                    set_resources,
+                    reqs,
+                    elements,
+                    check_buffer,
+                )?;
             }
             DescriptorBindingResources::BufferView(elements) => {
-                check_resources(set_num, binding_num, reqs, elements, check_buffer_view)?;
+                check_resources(
+                    set_num,
+                    binding_num,
+                    set_resources,
+                    reqs,
+                    elements,
+                    check_buffer_view,
+                )?;
             }
             DescriptorBindingResources::ImageView(elements) => {
-                check_resources(set_num, binding_num, reqs, elements, check_image_view)?;
+                check_resources(
+                    set_num,
+                    binding_num,
+                    set_resources,
+                    reqs,
+                    elements,
+                    check_image_view,
+                )?;
             }
             DescriptorBindingResources::ImageViewSampler(elements) => {
                 check_resources(
                     set_num,
                     binding_num,
+                    set_resources,
                     reqs,
                     elements,
                     check_image_view_sampler,
                 )?;
             }
             DescriptorBindingResources::Sampler(elements) => {
-                check_resources(set_num, binding_num, reqs, elements, check_sampler)?;
+                check_resources(
+                    set_num,
+                    binding_num,
+                    set_resources,
+                    reqs,
+                    elements,
+                    check_sampler,
+                )?;
             }
         }
     }
@@ -700,6 +851,7 @@ impl fmt::Display for CheckDescriptorSetsValidityError {
 fn check_resources<T>(
     set_num: u32,
     binding_num: u32,
+    set_resources: &DescriptorSetResources,
     reqs: &DescriptorRequirements,
     elements: &[Option<T>],
     mut extra_check: impl FnMut(u32, &T) -> Result<(), InvalidDescriptorResource>,
@@ -713,6 +865,8 @@ fn check_resources<T>(
         // VUID-vkCmdDispatch-None-02699
         let element = match element {
             Some(x) => x,
+            // Null descriptors are valid to access, and have no resource to check.
+            None if set_resources.is_null(binding_num, index) => continue,
             None => {
                 return Err(
                     CheckDescriptorSetsValidityError::InvalidDescriptorResource {
@@ -924,6 +1078,11 @@ fn check_dynamic_state_validity(
         .map(|(s, _)| s)
     {
         match dynamic_state {
+            DynamicState::AttachmentFeedbackLoopEnable => {
+                if current_state.attachment_feedback_loop_enable().is_none() {
+                    return Err(CheckDynamicStateValidityError::NotSet { dynamic_state });
+                }
+            }
             DynamicState::BlendConstants => {
                 if current_state.blend_constants().is_none() {
                     return Err(CheckDynamicStateValidityError::NotSet { dynamic_state });
@@ -1977,6 +2136,129 @@ impl SyncCommandBufferBuilder {
         Ok(())
     }
 
+    /// Calls `vkCmdDrawIndirectCount` on the builder.
+    #[inline]
+    pub unsafe fn draw_indirect_count(
+        &mut self,
+        indirect_buffer: Arc<dyn BufferAccess>,
+        count_buffer: Arc<dyn BufferAccess>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            indirect_buffer: Arc<dyn BufferAccess>,
+            count_buffer: Arc<dyn BufferAccess>,
+            max_draw_count: u32,
+            stride: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "draw_indirect_count"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_indirect_count(
+                    self.indirect_buffer.as_ref(),
+                    self.count_buffer.as_ref(),
+                    self.max_draw_count,
+                    self.stride,
+                );
+            }
+        }
+
+        let pipeline = self.current_state.pipeline_graphics.as_ref().unwrap();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_set_resources(
+            &mut resources,
+            PipelineBindPoint::Graphics,
+            pipeline.descriptor_requirements(),
+        );
+        self.add_vertex_buffer_resources(&mut resources, pipeline.vertex_input_state());
+        self.add_indirect_buffer_resources(&mut resources, &indirect_buffer);
+        self.add_indirect_count_buffer_resources(&mut resources, &count_buffer);
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            indirect_buffer,
+            count_buffer,
+            max_draw_count,
+            stride,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdDrawIndexedIndirectCount` on the builder.
+    #[inline]
+    pub unsafe fn draw_indexed_indirect_count(
+        &mut self,
+        indirect_buffer: Arc<dyn BufferAccess>,
+        count_buffer: Arc<dyn BufferAccess>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            indirect_buffer: Arc<dyn BufferAccess>,
+            count_buffer: Arc<dyn BufferAccess>,
+            max_draw_count: u32,
+            stride: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "draw_indexed_indirect_count"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_indexed_indirect_count(
+                    self.indirect_buffer.as_ref(),
+                    self.count_buffer.as_ref(),
+                    self.max_draw_count,
+                    self.stride,
+                );
+            }
+        }
+
+        let pipeline = self.current_state.pipeline_graphics.as_ref().unwrap();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_set_resources(
+            &mut resources,
+            PipelineBindPoint::Graphics,
+            pipeline.descriptor_requirements(),
+        );
+        self.add_vertex_buffer_resources(&mut resources, pipeline.vertex_input_state());
+        self.add_index_buffer_resources(&mut resources);
+        self.add_indirect_buffer_resources(&mut resources, &indirect_buffer);
+        self.add_indirect_count_buffer_resources(&mut resources, &count_buffer);
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            indirect_buffer,
+            count_buffer,
+            max_draw_count,
+            stride,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
     fn add_descriptor_set_resources<'a>(
         &self,
         resources: &mut Vec<(Cow<'static, str>, Resource)>,
@@ -2221,6 +2503,31 @@ impl SyncCommandBufferBuilder {
             },
         ));
     }
+
+    fn add_indirect_count_buffer_resources(
+        &self,
+        resources: &mut Vec<(Cow<'static, str>, Resource)>,
+        count_buffer: &Arc<dyn BufferAccess>,
+    ) {
+        resources.push((
+            "count buffer".into(),
+            Resource::Buffer {
+                buffer: count_buffer.clone(),
+                range: 0..count_buffer.size(),
+                memory: PipelineMemoryAccess {
+                    stages: PipelineStages {
+                        draw_indirect: true,
+                        ..PipelineStages::none()
+                    },
+                    access: AccessFlags {
+                        indirect_command_read: true,
+                        ..AccessFlags::none()
+                    },
+                    exclusive: false,
+                },
+            },
+        ));
+    }
 }
 
 impl UnsafeCommandBufferBuilder {
@@ -2351,6 +2658,107 @@ impl UnsafeCommandBufferBuilder {
             stride,
         );
     }
+
+    /// Calls `vkCmdDrawIndirectCount` on the builder.
+    #[inline]
+    pub unsafe fn draw_indirect_count(
+        &mut self,
+        buffer: &dyn BufferAccess,
+        count_buffer: &dyn BufferAccess,
+        max_draw_count: u32,
+        stride: u32,
+    ) {
+        let fns = self.device.fns();
+
+        debug_assert!(
+            max_draw_count == 0
+                || ((stride % 4) == 0)
+                    && stride as usize >= size_of::<ash::vk::DrawIndirectCommand>()
+        );
+
+        let inner = buffer.inner();
+        debug_assert!(inner.offset < inner.buffer.size());
+        debug_assert!(inner.buffer.usage().indirect_buffer);
+
+        let count_inner = count_buffer.inner();
+        debug_assert!(count_inner.offset < count_inner.buffer.size());
+        debug_assert!(count_inner.offset % 4 == 0);
+        debug_assert!(count_inner.buffer.usage().indirect_buffer);
+
+        if self.device.api_version() >= Version::V1_2 {
+            (fns.v1_2.cmd_draw_indirect_count)(
+                self.handle,
+                inner.buffer.internal_object(),
+                inner.offset,
+                count_inner.buffer.internal_object(),
+                count_inner.offset,
+                max_draw_count,
+                stride,
+            );
+        } else {
+            debug_assert!(self.device.enabled_extensions().khr_draw_indirect_count);
+            (fns.khr_draw_indirect_count.cmd_draw_indirect_count_khr)(
+                self.handle,
+                inner.buffer.internal_object(),
+                inner.offset,
+                count_inner.buffer.internal_object(),
+                count_inner.offset,
+                max_draw_count,
+                stride,
+            );
+        }
+    }
+
+    /// Calls `vkCmdDrawIndexedIndirectCount` on the builder.
+    #[inline]
+    pub unsafe fn draw_indexed_indirect_count(
+        &mut self,
+        buffer: &dyn BufferAccess,
+        count_buffer: &dyn BufferAccess,
+        max_draw_count: u32,
+        stride: u32,
+    ) {
+        let fns = self.device.fns();
+
+        debug_assert!(
+            max_draw_count == 0
+                || ((stride % 4) == 0)
+                    && stride as usize >= size_of::<ash::vk::DrawIndexedIndirectCommand>()
+        );
+
+        let inner = buffer.inner();
+        debug_assert!(inner.offset < inner.buffer.size());
+        debug_assert!(inner.buffer.usage().indirect_buffer);
+
+        let count_inner = count_buffer.inner();
+        debug_assert!(count_inner.offset < count_inner.buffer.size());
+        debug_assert!(count_inner.offset % 4 == 0);
+        debug_assert!(count_inner.buffer.usage().indirect_buffer);
+
+        if self.device.api_version() >= Version::V1_2 {
+            (fns.v1_2.cmd_draw_indexed_indirect_count)(
+                self.handle,
+                inner.buffer.internal_object(),
+                inner.offset,
+                count_inner.buffer.internal_object(),
+                count_inner.offset,
+                max_draw_count,
+                stride,
+            );
+        } else {
+            debug_assert!(self.device.enabled_extensions().khr_draw_indirect_count);
+            (fns.khr_draw_indirect_count
+                .cmd_draw_indexed_indirect_count_khr)(
+                self.handle,
+                inner.buffer.internal_object(),
+                inner.offset,
+                count_inner.buffer.internal_object(),
+                count_inner.offset,
+                max_draw_count,
+                stride,
+            );
+        }
+    }
 }
 
 #[cfg(test)]
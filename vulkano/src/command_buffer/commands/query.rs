@@ -18,8 +18,8 @@ use crate::{
     },
     device::{physical::QueueFamily, Device, DeviceOwned},
     query::{
-        GetResultsError, QueriesRange, Query, QueryControlFlags, QueryPool, QueryResultElement,
-        QueryResultFlags, QueryType,
+        GetResultsError, PerformanceCounterScope, QueriesRange, Query, QueryControlFlags,
+        QueryPool, QueryResultElement, QueryResultFlags, QueryType,
     },
     sync::{AccessFlags, PipelineMemoryAccess, PipelineStage, PipelineStages},
     DeviceSize, VulkanObject,
@@ -32,8 +32,13 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
     ///
     /// The query will be active until [`end_query`](Self::end_query) is called for the same query.
     ///
+    /// For performance queries, the
+    /// [profiling lock](crate::device::Device::acquire_profiling_lock) must have been held when
+    /// recording of the command buffer began.
+    ///
     /// # Safety
     /// The query must be unavailable, ensured by calling [`reset_query_pool`](Self::reset_query_pool).
+    /// For performance queries, the reset must not be recorded in the same command buffer.
     pub unsafe fn begin_query(
         &mut self,
         query_pool: Arc<QueryPool>,
@@ -43,7 +48,7 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         check_begin_query(self.device(), &query_pool, query, flags)?;
 
         match query_pool.query_type() {
-            QueryType::Occlusion => {
+            QueryType::Occlusion | QueryType::MeshPrimitivesGenerated => {
                 if !self.queue_family().supports_graphics() {
                     return Err(
                         AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into(),
@@ -60,6 +65,54 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 }
             }
             QueryType::Timestamp => unreachable!(),
+            QueryType::PerformanceQuery => {
+                // The queries measure the counters of a single queue family.
+                if query_pool.performance_queue_family_index() != self.queue_family().id() {
+                    return Err(
+                        AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into(),
+                    );
+                }
+
+                // The profiling lock must have been held before the command buffer began.
+                if !self.profiling_lock_held {
+                    return Err(CheckBeginQueryError::ProfilingLockNotHeld.into());
+                }
+
+                let raw_query_pool = query_pool.internal_object();
+
+                if self
+                    .performance_query_pool
+                    .map_or(false, |pool| pool != raw_query_pool)
+                    && !self
+                        .device()
+                        .enabled_features()
+                        .performance_counter_multiple_query_pools
+                {
+                    return Err(
+                        CheckBeginQueryError::PerformanceCounterMultipleQueryPoolsFeatureNotEnabled
+                            .into(),
+                    );
+                }
+
+                for counter in query_pool.performance_counters() {
+                    match counter.scope {
+                        PerformanceCounterScope::CommandBuffer => {
+                            if !self.inner.commands.is_empty() {
+                                return Err(CheckBeginQueryError::NotFirstCommand.into());
+                            }
+                        }
+                        PerformanceCounterScope::RenderPass => {
+                            if self.render_pass_state.is_some() {
+                                return Err(
+                                    AutoCommandBufferBuilderContextError::ForbiddenInsideRenderPass
+                                        .into(),
+                                );
+                            }
+                        }
+                        PerformanceCounterScope::Command => (),
+                    }
+                }
+            }
         }
 
         let ty = query_pool.query_type();
@@ -71,6 +124,11 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
 
         // TODO: validity checks
         self.inner.begin_query(query_pool, query, flags);
+
+        if let QueryType::PerformanceQuery = ty {
+            self.performance_query_pool = Some(raw_query_pool);
+        }
+
         self.query_state.insert(
             raw_ty,
             QueryState {
@@ -147,6 +205,9 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
     /// [`QueryResultFlags::with_availability`] is enabled.
     /// The provided buffer must be large enough to hold the data.
     ///
+    /// This is not permitted for performance queries, whose results must be retrieved with
+    /// [`get_performance_results`](crate::query::QueriesRange::get_performance_results).
+    ///
     /// See also [`get_results`](crate::query::QueriesRange::get_results).
     pub fn copy_query_pool_results<D, T>(
         &mut self,
@@ -232,12 +293,17 @@ fn check_begin_query(
                 return Err(CheckBeginQueryError::OcclusionQueryPreciseFeatureNotEnabled);
             }
         }
-        QueryType::PipelineStatistics(_) => {
+        QueryType::PipelineStatistics(_) | QueryType::MeshPrimitivesGenerated => {
             if flags.precise {
                 return Err(CheckBeginQueryError::InvalidFlags);
             }
         }
         QueryType::Timestamp => return Err(CheckBeginQueryError::NotPermitted),
+        QueryType::PerformanceQuery => {
+            if flags.precise {
+                return Err(CheckBeginQueryError::InvalidFlags);
+            }
+        }
     }
 
     Ok(())
@@ -248,12 +314,21 @@ fn check_begin_query(
 pub enum CheckBeginQueryError {
     /// The provided flags are not allowed for this type of query.
     InvalidFlags,
+    /// The query measures a counter with the command buffer scope, but it is not the first command
+    /// of the command buffer.
+    NotFirstCommand,
     /// This operation is not permitted on this query type.
     NotPermitted,
     /// `QueryControlFlags::precise` was requested, but the `occlusion_query_precise` feature was not enabled.
     OcclusionQueryPreciseFeatureNotEnabled,
     /// The provided query index is not valid for this pool.
     OutOfRange,
+    /// Another performance query pool was used in the command buffer, but the
+    /// `performance_counter_multiple_query_pools` feature was not enabled.
+    PerformanceCounterMultipleQueryPoolsFeatureNotEnabled,
+    /// A performance query was begun, but the profiling lock of the device was not held when
+    /// recording of the command buffer began.
+    ProfilingLockNotHeld,
 }
 
 impl error::Error for CheckBeginQueryError {}
@@ -268,6 +343,9 @@ impl fmt::Display for CheckBeginQueryError {
                 Self::InvalidFlags => {
                     "the provided flags are not allowed for this type of query"
                 }
+                Self::NotFirstCommand => {
+                    "the query measures a counter with the command buffer scope, but it is not the first command of the command buffer"
+                }
                 Self::NotPermitted => {
                     "this operation is not permitted on this query type"
                 }
@@ -277,6 +355,12 @@ impl fmt::Display for CheckBeginQueryError {
                 Self::OutOfRange => {
                     "the provided query index is not valid for this pool"
                 }
+                Self::PerformanceCounterMultipleQueryPoolsFeatureNotEnabled => {
+                    "another performance query pool was used in the command buffer, but the performance_counter_multiple_query_pools feature was not enabled"
+                }
+                Self::ProfilingLockNotHeld => {
+                    "a performance query was begun, but the profiling lock of the device was not held when recording of the command buffer began"
+                }
             }
         )
     }
@@ -482,6 +566,8 @@ pub enum CheckCopyQueryPoolResultsError {
     DestinationMissingTransferUsage,
     /// The provided flags are not allowed for this type of query.
     InvalidFlags,
+    /// This operation is not permitted on this query type.
+    NotPermitted,
     /// The provided queries range is not valid for this pool.
     OutOfRange,
 }
@@ -498,6 +584,7 @@ impl From<GetResultsError> for CheckCopyQueryPoolResultsError {
                 actual_len,
             },
             GetResultsError::InvalidFlags => CheckCopyQueryPoolResultsError::InvalidFlags,
+            GetResultsError::NotPermitted => CheckCopyQueryPoolResultsError::NotPermitted,
             GetResultsError::DeviceLost | GetResultsError::OomError(_) => unreachable!(),
         }
     }
@@ -521,6 +608,9 @@ impl fmt::Display for CheckCopyQueryPoolResultsError {
                 Self::InvalidFlags => {
                     "the provided flags are not allowed for this type of query"
                 }
+                Self::NotPermitted => {
+                    "this operation is not permitted on this query type"
+                }
                 Self::OutOfRange => {
                     "the provided queries range is not valid for this pool"
                 }
@@ -9,7 +9,10 @@
 
 use crate::{
     command_buffer::{
-        auto::{BeginRenderPassState, BeginRenderingState, RenderPassState, RenderPassStateType},
+        auto::{
+            BeginRenderPassState, BeginRenderingState, ConditionalRenderingState, RenderPassState,
+            RenderPassStateType,
+        },
         pool::CommandPoolBuilderAlloc,
         synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
         sys::UnsafeCommandBufferBuilder,
@@ -98,6 +101,11 @@ where
             return Err(RenderPassError::ForbiddenInsideRenderPass);
         }
 
+        // VUID-VkSubmitInfo-pCommandBuffers-06015
+        if self.suspended_rendering_state.is_some() {
+            return Err(RenderPassError::SuspendedRenderPassNotResumed);
+        }
+
         let &mut RenderPassBeginInfo {
             ref render_pass,
             ref framebuffer,
@@ -185,6 +193,15 @@ where
                             });
                         }
                     }
+                    ImageLayout::AttachmentFeedbackLoopOptimal => {
+                        // VUID-vkCmdBeginRenderPass2-initialLayout-07002
+                        if !image_view.usage().attachment_feedback_loop {
+                            return Err(RenderPassError::AttachmentImageMissingUsage {
+                                attachment_index,
+                                usage: "attachment_feedback_loop",
+                            });
+                        }
+                    }
                     _ => (),
                 }
             }
@@ -197,14 +214,20 @@ where
                 ref color_attachments,
                 ref resolve_attachments,
                 ref depth_stencil_attachment,
+                ref depth_stencil_resolve_attachment,
+                depth_resolve_mode: _,
+                stencil_resolve_mode: _,
                 preserve_attachments: _,
+                rasterization_order_color_attachment_access: _,
+                rasterization_order_depth_attachment_access: _,
+                rasterization_order_stencil_attachment_access: _,
                 _ne: _,
             } = subpass_desc;
 
             for atch_ref in (input_attachments.iter())
                 .chain(color_attachments)
                 .chain(resolve_attachments)
-                .chain([depth_stencil_attachment])
+                .chain([depth_stencil_attachment, depth_stencil_resolve_attachment])
                 .flatten()
             {
                 let image_view = &framebuffer.attachments()[atch_ref.attachment as usize];
@@ -256,6 +279,15 @@ where
                             });
                         }
                     }
+                    ImageLayout::AttachmentFeedbackLoopOptimal => {
+                        // VUID-vkCmdBeginRenderPass2-initialLayout-07002
+                        if !image_view.usage().attachment_feedback_loop {
+                            return Err(RenderPassError::AttachmentImageMissingUsage {
+                                attachment_index: atch_ref.attachment,
+                                usage: "attachment_feedback_loop",
+                            });
+                        }
+                    }
                     _ => (),
                 }
             }
@@ -434,6 +466,16 @@ where
             return Err(RenderPassError::QueryIsActive);
         }
 
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01986
+        if matches!(
+            self.conditional_rendering_state,
+            Some(ConditionalRenderingState {
+                render_pass_subpass: Some(_),
+            })
+        ) {
+            return Err(RenderPassError::ConditionalRenderingIsActive);
+        }
+
         // VUID-vkCmdNextSubpass2-commandBuffer-cmdpool
         debug_assert!(self.queue_family().supports_graphics());
 
@@ -491,6 +533,16 @@ where
             return Err(RenderPassError::QueryIsActive);
         }
 
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01986
+        if matches!(
+            self.conditional_rendering_state,
+            Some(ConditionalRenderingState {
+                render_pass_subpass: Some(_),
+            })
+        ) {
+            return Err(RenderPassError::ConditionalRenderingIsActive);
+        }
+
         // VUID-vkCmdEndRenderPass2-commandBuffer-cmdpool
         debug_assert!(self.queue_family().supports_graphics());
 
@@ -519,6 +571,8 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 ref depth_attachment,
                 ref stencil_attachment,
                 contents,
+                resuming: _,
+                suspending: _,
                 _ne: _,
             } = &mut rendering_info;
 
@@ -603,6 +657,8 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 ref depth_attachment,
                 ref stencil_attachment,
                 contents,
+                resuming: _,
+                suspending,
                 _ne: _,
             } = &rendering_info;
 
@@ -615,6 +671,7 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                     color_attachments: color_attachments.clone(),
                     depth_attachment: depth_attachment.clone(),
                     stencil_attachment: stencil_attachment.clone(),
+                    suspending,
                 }
                 .into(),
             };
@@ -622,6 +679,7 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             self.inner.begin_rendering(rendering_info)?;
 
             self.render_pass_state = Some(render_pass_state);
+            self.suspended_rendering_state = None;
         }
 
         Ok(self)
@@ -661,6 +719,8 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             ref depth_attachment,
             ref stencil_attachment,
             contents,
+            resuming,
+            suspending: _,
             _ne: _,
         } = rendering_info;
 
@@ -669,6 +729,29 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             return Err(RenderPassError::ContentsForbiddenInSecondaryCommandBuffer);
         }
 
+        if let Some(suspended_state) = &self.suspended_rendering_state {
+            // VUID-VkSubmitInfo-pCommandBuffers-06015
+            if !resuming {
+                return Err(RenderPassError::SuspendedRenderPassNotResumed);
+            }
+
+            // VUID-VkSubmitInfo-pCommandBuffers-06016
+            if view_mask != suspended_state.view_mask
+                || color_attachments.len() != suspended_state.color_attachments.len()
+                || !color_attachments
+                    .iter()
+                    .zip(&suspended_state.color_attachments)
+                    .all(|(a, b)| same_rendering_attachment(a, b))
+                || !same_rendering_attachment(depth_attachment, &suspended_state.depth_attachment)
+                || !same_rendering_attachment(
+                    stencil_attachment,
+                    &suspended_state.stencil_attachment,
+                )
+            {
+                return Err(RenderPassError::ResumedRenderPassMismatch);
+            }
+        }
+
         // No VUID, but for sanity it makes sense to treat this the same as in framebuffers.
         if view_mask != 0 && layer_count != 1 {
             return Err(RenderPassError::MultiviewLayersInvalid);
@@ -723,6 +806,13 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 _ne: _,
             } = attachment_info;
 
+            if load_op == LoadOp::None && !device.enabled_extensions().ext_load_store_op_none {
+                return Err(RenderPassError::ExtensionNotEnabled {
+                    extension: "ext_load_store_op_none",
+                    reason: "an attachment used `LoadOp::None`",
+                });
+            }
+
             // VUID-VkRenderingInfo-colorAttachmentCount-06087
             if !image_view.usage().color_attachment {
                 return Err(RenderPassError::ColorAttachmentMissingUsage { attachment_index });
@@ -857,6 +947,13 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 _ne: _,
             } = attachment_info;
 
+            if load_op == LoadOp::None && !device.enabled_extensions().ext_load_store_op_none {
+                return Err(RenderPassError::ExtensionNotEnabled {
+                    extension: "ext_load_store_op_none",
+                    reason: "an attachment used `LoadOp::None`",
+                });
+            }
+
             let image_aspects = image_view.format().unwrap().aspects();
 
             // VUID-VkRenderingInfo-pDepthAttachment-06547
@@ -967,6 +1064,13 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 _ne: _,
             } = attachment_info;
 
+            if load_op == LoadOp::None && !device.enabled_extensions().ext_load_store_op_none {
+                return Err(RenderPassError::ExtensionNotEnabled {
+                    extension: "ext_load_store_op_none",
+                    reason: "an attachment used `LoadOp::None`",
+                });
+            }
+
             let image_aspects = image_view.format().unwrap().aspects();
 
             // VUID-VkRenderingInfo-pStencilAttachment-06548
@@ -1116,7 +1220,16 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
 
         unsafe {
             self.inner.end_rendering();
-            self.render_pass_state = None;
+
+            if let Some(RenderPassState {
+                render_pass: RenderPassStateType::BeginRendering(state),
+                ..
+            }) = self.render_pass_state.take()
+            {
+                if state.suspending {
+                    self.suspended_rendering_state = Some(state);
+                }
+            }
         }
 
         Ok(self)
@@ -1142,6 +1255,15 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 return Err(RenderPassError::ForbiddenWithInheritedRenderPass)
             }
         }
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01986
+        if matches!(
+            self.conditional_rendering_state,
+            Some(ConditionalRenderingState {
+                render_pass_subpass: Some(_),
+            })
+        ) {
+            return Err(RenderPassError::ConditionalRenderingIsActive);
+        }
 
         // VUID-vkCmdEndRendering-commandBuffer-cmdpool
         debug_assert!(self.queue_family().supports_graphics());
@@ -1660,6 +1782,8 @@ impl SyncCommandBufferBuilder {
             ref depth_attachment,
             ref stencil_attachment,
             contents,
+            resuming: _,
+            suspending: _,
             _ne,
         } = &rendering_info;
 
@@ -2082,6 +2206,8 @@ impl UnsafeCommandBufferBuilder {
             ref depth_attachment,
             ref stencil_attachment,
             contents,
+            resuming,
+            suspending,
             _ne: _,
         } = rendering_info;
 
@@ -2142,8 +2268,18 @@ impl UnsafeCommandBufferBuilder {
         let depth_attachment = map_attachment_info(depth_attachment);
         let stencil_attachment = map_attachment_info(stencil_attachment);
 
+        let mut flags = ash::vk::RenderingFlags::from(contents);
+
+        if resuming {
+            flags |= ash::vk::RenderingFlags::RESUMING;
+        }
+
+        if suspending {
+            flags |= ash::vk::RenderingFlags::SUSPENDING;
+        }
+
         let rendering_info = ash::vk::RenderingInfo {
-            flags: contents.into(),
+            flags,
             render_area: ash::vk::Rect2D {
                 offset: ash::vk::Offset2D {
                     x: render_area_offset[0] as i32,
@@ -2362,6 +2498,22 @@ pub struct RenderingInfo {
     /// The default value is [`SubpassContents::Inline`].
     pub contents: SubpassContents,
 
+    /// Whether the render pass instance resumes a render pass instance that was suspended.
+    ///
+    /// The suspended render pass instance must be the one that immediately precedes this one in
+    /// submission order, and the two must have identical parameters other than `contents`,
+    /// `resuming` and `suspending`. No action or synchronization commands may be recorded between
+    /// them, which allows a single render pass instance to span several command buffers.
+    ///
+    /// The default value is `false`.
+    pub resuming: bool,
+
+    /// Whether the render pass instance is suspended when `end_rendering` is called, to be resumed
+    /// by the next render pass instance in submission order, which must have `resuming` set.
+    ///
+    /// The default value is `false`.
+    pub suspending: bool,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -2377,11 +2529,38 @@ impl Default for RenderingInfo {
             depth_attachment: None,
             stencil_attachment: None,
             contents: SubpassContents::Inline,
+            resuming: false,
+            suspending: false,
             _ne: crate::NonExhaustive(()),
         }
     }
 }
 
+// Returns whether two attachments of a render pass instance refer to the same image views, used
+// in the same way.
+fn same_rendering_attachment(
+    a: &Option<RenderingAttachmentInfo>,
+    b: &Option<RenderingAttachmentInfo>,
+) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => {
+            a.image_view.internal_object() == b.image_view.internal_object()
+                && a.image_layout == b.image_layout
+                && match (&a.resolve_info, &b.resolve_info) {
+                    (None, None) => true,
+                    (Some(a), Some(b)) => {
+                        a.mode == b.mode
+                            && a.image_view.internal_object() == b.image_view.internal_object()
+                            && a.image_layout == b.image_layout
+                    }
+                    _ => false,
+                }
+        }
+        _ => false,
+    }
+}
+
 /// Parameters to specify properties of an attachment.
 #[derive(Clone, Debug)]
 pub struct RenderingAttachmentInfo {
@@ -2564,6 +2743,11 @@ pub struct ClearRect {
 pub enum RenderPassError {
     SyncCommandBufferBuilderError(SyncCommandBufferBuilderError),
 
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+
     FeatureNotEnabled {
         feature: &'static str,
         reason: &'static str,
@@ -2652,6 +2836,9 @@ pub enum RenderPassError {
         attachment_index: u32,
     },
 
+    /// Conditional rendering was begun in the current subpass, and must be ended first.
+    ConditionalRenderingIsActive,
+
     /// The contents `SubpassContents::SecondaryCommandBuffers` is not allowed inside a secondary command buffer.
     ContentsForbiddenInSecondaryCommandBuffer,
 
@@ -2774,6 +2961,10 @@ pub enum RenderPassError {
     /// The render area's `offset` and `extent` are outside the extent of the framebuffer.
     RenderAreaOutOfBounds,
 
+    /// A render pass instance that resumes a suspended render pass instance has different
+    /// parameters from it.
+    ResumedRenderPassMismatch,
+
     /// The stencil attachment has a format that does not support that usage.
     StencilAttachmentFormatUsageNotSupported,
 
@@ -2809,6 +3000,10 @@ pub enum RenderPassError {
         current_subpass: u32,
         remaining_subpasses: u32,
     },
+
+    /// Tried to begin a render pass instance that does not resume the suspended render pass
+    /// instance that precedes it.
+    SuspendedRenderPassNotResumed,
 }
 
 impl error::Error for RenderPassError {
@@ -2827,6 +3022,9 @@ impl fmt::Display for RenderPassError {
         match self {
             Self::SyncCommandBufferBuilderError(_) => write!(f, "a SyncCommandBufferBuilderError"),
 
+            Self::ExtensionNotEnabled { extension, reason } => {
+                write!(f, "the extension {} must be enabled: {}", extension, reason)
+            }
             Self::FeatureNotEnabled { feature, reason } => {
                 write!(f, "the feature {} must be enabled: {}", feature, reason)
             }
@@ -2933,6 +3131,10 @@ impl fmt::Display for RenderPassError {
                 "color attachment {} with a resolve attachment has a `samples` value of `SampleCount::Sample1`",
                 attachment_index,
             ),
+            Self::ConditionalRenderingIsActive => write!(
+                f,
+                "conditional rendering was begun in the current subpass, and must be ended first",
+            ),
             Self::ContentsForbiddenInSecondaryCommandBuffer => write!(
                 f,
                 "the contents `SubpassContents::SecondaryCommandBuffers` is not allowed inside a secondary command buffer",
@@ -3065,6 +3267,10 @@ impl fmt::Display for RenderPassError {
                 f,
                 "the render area's `offset` and `extent` are outside the extent of the framebuffer",
             ),
+            Self::ResumedRenderPassMismatch => write!(
+                f,
+                "a render pass instance that resumes a suspended render pass instance has different parameters from it",
+            ),
             Self::StencilAttachmentFormatUsageNotSupported => write!(
                 f,
                 "the stencil attachment has a format that does not support that usage",
@@ -3109,6 +3315,10 @@ impl fmt::Display for RenderPassError {
                 "tried to end a render pass at subpass {}, with {} subpasses still remaining in the render pass",
                 current_subpass, remaining_subpasses,
             ),
+            Self::SuspendedRenderPassNotResumed => write!(
+                f,
+                "tried to begin a render pass instance that does not resume the suspended render pass instance that precedes it",
+            ),
         }
     }
 }
@@ -3119,3 +3329,99 @@ impl From<SyncCommandBufferBuilderError> for RenderPassError {
         Self::SyncCommandBufferBuilderError(err)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{RenderPassError, RenderingAttachmentInfo, RenderingInfo};
+    use crate::{
+        command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
+        format::Format,
+        image::{view::ImageView, AttachmentImage},
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn begin_rendering_feature_not_enabled() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        match builder.begin_rendering(Default::default()) {
+            Err(RenderPassError::FeatureNotEnabled {
+                feature: "dynamic_rendering",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn suspend_resume() {
+        let (device, queue) = gfx_dev_and_queue!(dynamic_rendering);
+
+        let new_view = || {
+            ImageView::new_default(
+                AttachmentImage::new(device.clone(), [32, 32], Format::R8G8B8A8_UNORM).unwrap(),
+            )
+            .unwrap()
+        };
+        let first = new_view();
+        let second = new_view();
+
+        fn rendering_info(
+            view: &Arc<ImageView<AttachmentImage>>,
+            resuming: bool,
+            suspending: bool,
+        ) -> RenderingInfo {
+            RenderingInfo {
+                color_attachments: vec![Some(RenderingAttachmentInfo::image_view(view.clone()))],
+                resuming,
+                suspending,
+                ..Default::default()
+            }
+        }
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder
+            .begin_rendering(rendering_info(&first, false, true))
+            .unwrap()
+            .end_rendering()
+            .unwrap();
+
+        // The next render pass instance must resume the suspended one.
+        match builder.begin_rendering(rendering_info(&first, false, false)) {
+            Err(RenderPassError::SuspendedRenderPassNotResumed) => (),
+            _ => panic!(),
+        }
+
+        // And it must use the same attachments.
+        match builder.begin_rendering(rendering_info(&second, true, false)) {
+            Err(RenderPassError::ResumedRenderPassMismatch) => (),
+            _ => panic!(),
+        }
+
+        builder
+            .begin_rendering(rendering_info(&first, true, false))
+            .unwrap()
+            .end_rendering()
+            .unwrap();
+
+        // Nothing is suspended anymore.
+        builder
+            .begin_rendering(rendering_info(&second, false, false))
+            .unwrap()
+            .end_rendering()
+            .unwrap();
+    }
+}
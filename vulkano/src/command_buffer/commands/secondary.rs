@@ -122,6 +122,17 @@ where
         // TODO:
         // VUID-vkCmdExecuteCommands-pCommandBuffers-00094
 
+        // VUID-vkCmdExecuteCommands-commandBuffer-01820
+        // VUID-vkCmdExecuteCommands-commandBuffer-01821
+        let protected = command_buffer.inner().protected();
+
+        if protected != self.pool_builder_alloc.inner().protected() {
+            return Err(ExecuteCommandsError::ProtectedMismatch {
+                command_buffer_index,
+                protected,
+            });
+        }
+
         if let Some(render_pass_state) = &self.render_pass_state {
             // VUID-vkCmdExecuteCommands-contents-06018
             // VUID-vkCmdExecuteCommands-flags-06024
@@ -360,7 +371,9 @@ where
                         );
                     }
                 }
-                QueryType::Timestamp => (),
+                QueryType::Timestamp
+                | QueryType::PerformanceQuery
+                | QueryType::MeshPrimitivesGenerated => (),
             }
         }
 
@@ -590,6 +603,13 @@ pub enum ExecuteCommandsError {
         inherited_flags: QueryPipelineStatisticFlags,
     },
 
+    /// A command buffer is a protected command buffer and the primary command buffer is not, or
+    /// the other way around.
+    ProtectedMismatch {
+        command_buffer_index: u32,
+        protected: bool,
+    },
+
     /// The inherited color attachment count of a command buffer does not match the current
     /// attachment count.
     RenderPassColorAttachmentCountMismatch {
@@ -742,6 +762,16 @@ impl fmt::Display for ExecuteCommandsError {
                 "the inherited pipeline statistics query flags ({:?}) of command buffer {} are not a superset of the currently active flags ({:?})",
                 inherited_flags, command_buffer_index, required_flags,
             ),
+            Self::ProtectedMismatch {
+                command_buffer_index,
+                protected,
+            } => write!(
+                f,
+                "command buffer {} is {}a protected command buffer, but the primary command buffer is {}",
+                command_buffer_index,
+                if *protected { "" } else { "not " },
+                if *protected { "not" } else { "protected" },
+            ),
             Self::RenderPassColorAttachmentCountMismatch {
                 command_buffer_index,
                 required_count,
@@ -11,6 +11,7 @@ use crate::{
     command_buffer::{
         synced::{Command, SyncCommandBufferBuilder},
         sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, AutoCommandBufferBuilderContextError,
     },
     image::ImageLayout,
     sync::{
@@ -22,6 +23,45 @@ use crate::{
 use smallvec::SmallVec;
 use std::sync::Arc;
 
+/// # Commands to synchronize resources.
+impl<L, P> AutoCommandBufferBuilder<L, P> {
+    /// Inserts a global memory barrier between all commands recorded so far and all commands
+    /// recorded afterwards.
+    ///
+    /// Vulkano automatically synchronizes accesses to the same buffer or image, but it does not
+    /// know when two different resources are bound to overlapping ranges of memory. When such
+    /// aliased resources are used one after the other, for example transient attachments that
+    /// share memory, this barrier must be used to order the accesses to the memory.
+    ///
+    /// # Safety
+    ///
+    /// - `barrier` must cover all accesses to the aliased memory made by the commands before it,
+    ///   and all accesses made by the commands after it.
+    /// - When an image is used after another resource has written to its memory, its contents
+    ///   are undefined. It must therefore not be used in a way that expects its previous contents
+    ///   or layout to be preserved.
+    #[inline]
+    pub unsafe fn aliasing_barrier(
+        &mut self,
+        barrier: MemoryBarrier,
+    ) -> Result<&mut Self, AutoCommandBufferBuilderContextError> {
+        self.ensure_outside_render_pass()?;
+
+        debug_assert!(barrier
+            .source_stages
+            .supported_access()
+            .contains(&barrier.source_access));
+        debug_assert!(barrier
+            .destination_stages
+            .supported_access()
+            .contains(&barrier.destination_access));
+
+        self.inner.aliasing_barrier(barrier);
+
+        Ok(self)
+    }
+}
+
 impl SyncCommandBufferBuilder {
     /// Calls `vkCmdSetEvent` on the builder.
     #[inline]
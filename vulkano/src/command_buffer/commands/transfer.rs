@@ -12,16 +12,16 @@ use crate::{
     command_buffer::{
         synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
         sys::UnsafeCommandBufferBuilder,
-        AutoCommandBufferBuilder, CopyError, CopyErrorResource,
+        AutoCommandBufferBuilder, CopyError,
     },
-    device::DeviceOwned,
+    device::{physical::QueueFamily, DeviceOwned},
     format::Format,
     image::{
         ImageAccess, ImageAspects, ImageLayout, ImageSubresourceLayers, ImageType, SampleCount,
         SampleCounts,
     },
     sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
-    DeviceSize, SafeDeref, Version, VulkanObject,
+    DeviceSize, SafeDeref, ValidationError, Version, VulkanObject,
 };
 use smallvec::{smallvec, SmallVec};
 use std::{
@@ -85,20 +85,27 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         assert_eq!(device, src_buffer.device());
         assert_eq!(device, dst_buffer.device());
 
-        // VUID-VkCopyBufferInfo2-srcBuffer-00118
         if !src_buffer.usage().transfer_src {
-            return Err(CopyError::MissingUsage {
-                resource: CopyErrorResource::Source,
-                usage: "transfer_src",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_usage",
+                context: "copy_buffer",
+                problem: "the source resource did not have the required usage transfer_src enabled"
+                    .into(),
+                vuids: &["VUID-VkCopyBufferInfo2-srcBuffer-00118"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkCopyBufferInfo2-dstBuffer-00120
         if !dst_buffer.usage().transfer_dst {
-            return Err(CopyError::MissingUsage {
-                resource: CopyErrorResource::Destination,
-                usage: "transfer_dst",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_usage",
+                context: "copy_buffer",
+                problem: "the destination resource did not have the required usage transfer_dst \
+                    enabled"
+                    .into(),
+                vuids: &["VUID-VkCopyBufferInfo2-dstBuffer-00120"],
+                ..Default::default()
+            }));
         }
 
         let same_buffer = src_buffer_inner.buffer == dst_buffer_inner.buffer;
@@ -115,26 +122,44 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             // VUID-VkBufferCopy2-size-01988
             assert!(size != 0);
 
-            // VUID-VkCopyBufferInfo2-srcOffset-00113
-            // VUID-VkCopyBufferInfo2-size-00115
             if src_offset + size > src_buffer.size() {
-                return Err(CopyError::RegionOutOfBufferBounds {
-                    resource: CopyErrorResource::Source,
-                    region_index,
-                    offset_range_end: src_offset + size,
-                    buffer_size: src_buffer.size(),
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "region_out_of_buffer_bounds",
+                    context: "copy_buffer",
+                    problem: format!(
+                        "the end of the range of accessed source byte offsets ({}) of region {} \
+                        is greater than the size of the source buffer ({})",
+                        src_offset + size,
+                        region_index,
+                        src_buffer.size(),
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkCopyBufferInfo2-srcOffset-00113",
+                        "VUID-VkCopyBufferInfo2-size-00115",
+                    ],
+                    ..Default::default()
+                }));
             }
 
-            // VUID-VkCopyBufferInfo2-dstOffset-00114
-            // VUID-VkCopyBufferInfo2-size-00116
             if dst_offset + size > dst_buffer.size() {
-                return Err(CopyError::RegionOutOfBufferBounds {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    offset_range_end: dst_offset + size,
-                    buffer_size: dst_buffer.size(),
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "region_out_of_buffer_bounds",
+                    context: "copy_buffer",
+                    problem: format!(
+                        "the end of the range of accessed destination byte offsets ({}) of region \
+                        {} is greater than the size of the destination buffer ({})",
+                        dst_offset + size,
+                        region_index,
+                        dst_buffer.size(),
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkCopyBufferInfo2-dstOffset-00114",
+                        "VUID-VkCopyBufferInfo2-size-00116",
+                    ],
+                    ..Default::default()
+                }));
             }
 
             // VUID-VkCopyBufferInfo2-pRegions-00117
@@ -159,12 +184,19 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             }
         }
 
-        // VUID-VkCopyBufferInfo2-pRegions-00117
         if let Some((src_region_index, dst_region_index)) = overlap_indices {
-            return Err(CopyError::OverlappingRegions {
-                src_region_index,
-                dst_region_index,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "overlapping_regions",
+                context: "copy_buffer",
+                problem: format!(
+                    "the source bounds of region {} overlap with the destination bounds of region \
+                    {}",
+                    src_region_index, dst_region_index,
+                )
+                .into(),
+                vuids: &["VUID-VkCopyBufferInfo2-pRegions-00117"],
+                ..Default::default()
+            }));
         }
 
         Ok(())
@@ -239,104 +271,137 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         let mut src_image_aspects = src_image.format().aspects();
         let mut dst_image_aspects = dst_image.format().aspects();
 
-        // VUID-VkCopyImageInfo2-aspect-06662
         if !src_image.usage().transfer_src {
-            return Err(CopyError::MissingUsage {
-                resource: CopyErrorResource::Source,
-                usage: "transfer_src",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_usage",
+                context: "copy_image",
+                problem: "the source resource did not have the required usage transfer_src enabled"
+                    .into(),
+                vuids: &["VUID-VkCopyImageInfo2-aspect-06662"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkCopyImageInfo2-aspect-06663
         if !dst_image.usage().transfer_dst {
-            return Err(CopyError::MissingUsage {
-                resource: CopyErrorResource::Destination,
-                usage: "transfer_dst",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_usage",
+                context: "copy_image",
+                problem: "the destination resource did not have the required usage transfer_dst \
+                    enabled"
+                    .into(),
+                vuids: &["VUID-VkCopyImageInfo2-aspect-06663"],
+                ..Default::default()
+            }));
         }
 
         if device.api_version() >= Version::V1_1 || device.enabled_extensions().khr_maintenance1 {
-            // VUID-VkCopyImageInfo2-srcImage-01995
             if !src_image.format_features().transfer_src {
-                return Err(CopyError::MissingFormatFeature {
-                    resource: CopyErrorResource::Source,
-                    format_feature: "transfer_src",
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "missing_format_feature",
+                    context: "copy_image",
+                    problem: "the source image does not have the required format feature \
+                        transfer_src"
+                        .into(),
+                    vuids: &["VUID-VkCopyImageInfo2-srcImage-01995"],
+                    ..Default::default()
+                }));
             }
 
-            // VUID-VkCopyImageInfo2-dstImage-01996
             if !dst_image.format_features().transfer_dst {
-                return Err(CopyError::MissingFormatFeature {
-                    resource: CopyErrorResource::Destination,
-                    format_feature: "transfer_dst",
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "missing_format_feature",
+                    context: "copy_image",
+                    problem: "the destination image does not have the required format feature \
+                        transfer_dst"
+                        .into(),
+                    vuids: &["VUID-VkCopyImageInfo2-dstImage-01996"],
+                    ..Default::default()
+                }));
             }
         }
 
-        // VUID-VkCopyImageInfo2-srcImage-00136
         if src_image.samples() != dst_image.samples() {
-            return Err(CopyError::SampleCountMismatch {
-                src_sample_count: src_image.samples(),
-                dst_sample_count: dst_image.samples(),
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "sample_count_mismatch",
+                context: "copy_image",
+                problem: format!(
+                    "the source image has a different sample count ({:?}) than the destination \
+                    image ({:?})",
+                    src_image.samples(),
+                    dst_image.samples(),
+                )
+                .into(),
+                vuids: &["VUID-VkCopyImageInfo2-srcImage-00136"],
+                ..Default::default()
+            }));
         }
 
         if !(src_image_aspects.color || dst_image_aspects.color) {
-            // VUID-VkCopyImageInfo2-srcImage-01548
             if src_image.format() != dst_image.format() {
-                return Err(CopyError::FormatsMismatch {
-                    src_format: src_image.format(),
-                    dst_format: dst_image.format(),
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "formats_mismatch",
+                    context: "copy_image",
+                    problem: format!(
+                        "the format of the source image ({:?}) does not match the format of the \
+                        destination image ({:?})",
+                        src_image.format(),
+                        dst_image.format(),
+                    )
+                    .into(),
+                    vuids: &["VUID-VkCopyImageInfo2-srcImage-01548"],
+                    ..Default::default()
+                }));
             }
         }
 
-        // VUID-VkCopyImageInfo2-srcImageLayout-01917
         if !matches!(
             src_image_layout,
             ImageLayout::TransferSrcOptimal | ImageLayout::General
         ) {
-            return Err(CopyError::ImageLayoutInvalid {
-                resource: CopyErrorResource::Source,
-                image_layout: src_image_layout,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "image_layout_invalid",
+                context: "copy_image",
+                problem: format!(
+                    "the specified source image layout {:?} is not valid for this operation",
+                    src_image_layout,
+                )
+                .into(),
+                vuids: &["VUID-VkCopyImageInfo2-srcImageLayout-01917"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkCopyImageInfo2-dstImageLayout-01395
         if !matches!(
             dst_image_layout,
             ImageLayout::TransferDstOptimal | ImageLayout::General
         ) {
-            return Err(CopyError::ImageLayoutInvalid {
-                resource: CopyErrorResource::Destination,
-                image_layout: dst_image_layout,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "image_layout_invalid",
+                context: "copy_image",
+                problem: format!(
+                    "the specified destination image layout {:?} is not valid for this operation",
+                    dst_image_layout,
+                )
+                .into(),
+                vuids: &["VUID-VkCopyImageInfo2-dstImageLayout-01395"],
+                ..Default::default()
+            }));
         }
 
         let extent_alignment = match self.queue_family().min_image_transfer_granularity() {
             [0, 0, 0] => None,
-            min_image_transfer_granularity => {
-                let granularity = move |block_extent: [u32; 3], is_multi_plane: bool| {
-                    if is_multi_plane {
-                        // Assume planes always have 1x1 blocks
-                        min_image_transfer_granularity
-                    } else {
-                        // "The value returned in minImageTransferGranularity has a unit of
-                        // compressed texel blocks for images having a block-compressed format, and
-                        // a unit of texels otherwise."
-                        [
-                            min_image_transfer_granularity[0] * block_extent[0],
-                            min_image_transfer_granularity[1] * block_extent[1],
-                            min_image_transfer_granularity[2] * block_extent[2],
-                        ]
-                    }
-                };
-
-                Some((
-                    granularity(src_image.format().block_extent(), src_image_aspects.plane0),
-                    granularity(dst_image.format().block_extent(), dst_image_aspects.plane0),
-                ))
-            }
+            min_image_transfer_granularity => Some((
+                image_transfer_granularity(
+                    min_image_transfer_granularity,
+                    src_image.format(),
+                    src_image_aspects.plane0,
+                ),
+                image_transfer_granularity(
+                    min_image_transfer_granularity,
+                    dst_image.format(),
+                    dst_image_aspects.plane0,
+                ),
+            )),
         };
 
         if src_image_aspects.plane0 {
@@ -365,63 +430,103 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 _ne,
             } = region;
 
-            let check_subresource = |resource: CopyErrorResource,
+            let check_subresource = |resource: &'static str,
                                      image: &dyn ImageAccess,
                                      image_aspects: &ImageAspects,
                                      subresource: &ImageSubresourceLayers|
              -> Result<_, CopyError> {
-                // VUID-VkCopyImageInfo2-srcSubresource-01696
-                // VUID-VkCopyImageInfo2-dstSubresource-01697
                 if subresource.mip_level >= image.mip_levels() {
-                    return Err(CopyError::MipLevelsOutOfRange {
-                        resource,
-                        region_index,
-                        mip_levels_range_end: subresource.mip_level + 1,
-                        image_mip_levels: image.mip_levels(),
-                    });
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "mip_levels_out_of_range",
+                        context: "copy_image",
+                        problem: format!(
+                            "the end of the range of accessed mip levels ({}) of the {} \
+                            subresource range of region {} is not less than the number of mip \
+                            levels in the {} image ({})",
+                            subresource.mip_level + 1,
+                            resource,
+                            region_index,
+                            resource,
+                            image.mip_levels(),
+                        )
+                        .into(),
+                        vuids: &[
+                            "VUID-VkCopyImageInfo2-srcSubresource-01696",
+                            "VUID-VkCopyImageInfo2-dstSubresource-01697",
+                        ],
+                        ..Default::default()
+                    }));
                 }
 
                 // VUID-VkImageSubresourceLayers-layerCount-01700
                 assert!(!subresource.array_layers.is_empty());
 
-                // VUID-VkCopyImageInfo2-srcSubresource-01698
-                // VUID-VkCopyImageInfo2-dstSubresource-01699
-                // VUID-VkCopyImageInfo2-srcImage-04443
-                // VUID-VkCopyImageInfo2-dstImage-04444
                 if subresource.array_layers.end > image.dimensions().array_layers() {
-                    return Err(CopyError::ArrayLayersOutOfRange {
-                        resource,
-                        region_index,
-                        array_layers_range_end: subresource.array_layers.end,
-                        image_array_layers: image.dimensions().array_layers(),
-                    });
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "array_layers_out_of_range",
+                        context: "copy_image",
+                        problem: format!(
+                            "the end of the range of accessed array layers ({}) of the {} \
+                            subresource range of region {} is greater than the number of array \
+                            layers in the {} image ({})",
+                            subresource.array_layers.end,
+                            resource,
+                            region_index,
+                            resource,
+                            image.dimensions().array_layers(),
+                        )
+                        .into(),
+                        vuids: &[
+                            "VUID-VkCopyImageInfo2-srcSubresource-01698",
+                            "VUID-VkCopyImageInfo2-dstSubresource-01699",
+                            "VUID-VkCopyImageInfo2-srcImage-04443",
+                            "VUID-VkCopyImageInfo2-dstImage-04444",
+                        ],
+                        ..Default::default()
+                    }));
                 }
 
                 // VUID-VkImageSubresourceLayers-aspectMask-requiredbitmask
                 assert!(subresource.aspects != ImageAspects::none());
 
-                // VUID-VkCopyImageInfo2-aspectMask-00142
-                // VUID-VkCopyImageInfo2-aspectMask-00143
                 if !image_aspects.contains(&subresource.aspects) {
-                    return Err(CopyError::AspectsNotAllowed {
-                        resource,
-                        region_index,
-                        aspects: subresource.aspects,
-                        allowed_aspects: *image_aspects,
-                    });
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "aspects_not_allowed",
+                        context: "copy_image",
+                        problem: format!(
+                            "the aspects ({:?}) of the {} subresource range of region {} contain \
+                            aspects that are not present in the {} image, or that are not \
+                            allowed ({:?})",
+                            subresource.aspects, resource, region_index, resource, *image_aspects,
+                        )
+                        .into(),
+                        vuids: &[
+                            "VUID-VkCopyImageInfo2-aspectMask-00142",
+                            "VUID-VkCopyImageInfo2-aspectMask-00143",
+                        ],
+                        ..Default::default()
+                    }));
                 }
 
                 let (subresource_format, subresource_extent) = if image_aspects.plane0 {
-                    // VUID-VkCopyImageInfo2-srcImage-01552
-                    // VUID-VkCopyImageInfo2-srcImage-01553
-                    // VUID-VkCopyImageInfo2-dstImage-01554
-                    // VUID-VkCopyImageInfo2-dstImage-01555
                     if subresource.aspects.iter().count() != 1 {
-                        return Err(CopyError::MultipleAspectsNotAllowed {
-                            resource,
-                            region_index,
-                            aspects: subresource.aspects,
-                        });
+                        return Err(CopyError::ValidationError(ValidationError {
+                            code: "multiple_aspects_not_allowed",
+                            context: "copy_image",
+                            problem: format!(
+                                "the {} subresource range of region {} specifies multiple aspects \
+                                ({:?}), but only one aspect can be selected for the {} image",
+                                resource, region_index, subresource.aspects, resource,
+                            )
+                            .into(),
+                            vuids: &[
+                                "VUID-VkCopyImageInfo2-srcImage-01552",
+                                "VUID-VkCopyImageInfo2-srcImage-01553",
+                                "VUID-VkCopyImageInfo2-dstImage-01554",
+                                "VUID-VkCopyImageInfo2-dstImage-01555",
+                            ],
+                            ..Default::default()
+                        }));
                     }
 
                     if subresource.aspects.plane0 {
@@ -462,45 +567,77 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 Ok((subresource_format, subresource_extent))
             };
 
-            let (src_subresource_format, src_subresource_extent) = check_subresource(
-                CopyErrorResource::Source,
-                src_image,
-                &src_image_aspects,
-                src_subresource,
-            )?;
+            let (src_subresource_format, src_subresource_extent) =
+                check_subresource("source", src_image, &src_image_aspects, src_subresource)?;
             let (dst_subresource_format, dst_subresource_extent) = check_subresource(
-                CopyErrorResource::Destination,
+                "destination",
                 dst_image,
                 &dst_image_aspects,
                 dst_subresource,
             )?;
 
             if !(src_image_aspects.plane0 || dst_image_aspects.plane0) {
-                // VUID-VkCopyImageInfo2-srcImage-01551
                 if src_subresource.aspects != dst_subresource.aspects {
-                    return Err(CopyError::AspectsMismatch {
-                        region_index,
-                        src_aspects: src_subresource.aspects,
-                        dst_aspects: dst_subresource.aspects,
-                    });
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "aspects_mismatch",
+                        context: "copy_image",
+                        problem: format!(
+                            "the aspects of the source and destination subresource ranges of \
+                            region {} do not match (source: {:?}; destination: {:?})",
+                            region_index, src_subresource.aspects, dst_subresource.aspects,
+                        )
+                        .into(),
+                        vuids: &["VUID-VkCopyImageInfo2-srcImage-01551"],
+                        ..Default::default()
+                    }));
                 }
             }
 
-            // VUID-VkCopyImageInfo2-srcImage-01548
-            // VUID-VkCopyImageInfo2-None-01549
             // Color formats must be size-compatible.
             if src_subresource_format.block_size() != dst_subresource_format.block_size() {
-                return Err(CopyError::FormatsNotCompatible {
-                    src_format: src_subresource_format,
-                    dst_format: dst_subresource_format,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "formats_not_compatible",
+                    context: "copy_image",
+                    problem: format!(
+                        "the format of the source image subresource ({:?}) is not compatible with \
+                        the format of the destination image subresource ({:?})",
+                        src_subresource_format, dst_subresource_format,
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkCopyImageInfo2-srcImage-01548",
+                        "VUID-VkCopyImageInfo2-None-01549",
+                    ],
+                    ..Default::default()
+                }));
             }
 
-            // TODO:
             // "When copying between compressed and uncompressed formats the extent members
             // represent the texel dimensions of the source image and not the destination."
             let mut src_extent = extent;
             let mut dst_extent = extent;
+            let src_block_extent = src_subresource_format.block_extent();
+            let dst_block_extent = dst_subresource_format.block_extent();
+
+            if src_block_extent != dst_block_extent {
+                for i in 0..3 {
+                    // Scale from source texels to texel blocks, rounding up for a partial block
+                    // at the edge of the source subresource, then to destination texels.
+                    let block_count = (extent[i] + src_block_extent[i] - 1) / src_block_extent[i];
+                    dst_extent[i] = block_count * dst_block_extent[i];
+
+                    // A partial block at the edge of the destination subresource is clamped to
+                    // the edge, like the source extent would be.
+                    let dst_remaining = dst_subresource_extent[i].saturating_sub(dst_offset[i]);
+
+                    if dst_extent[i] > dst_remaining
+                        && dst_extent[i] - dst_remaining < dst_block_extent[i]
+                    {
+                        dst_extent[i] = dst_remaining;
+                    }
+                }
+            }
+
             let src_layer_count =
                 src_subresource.array_layers.end - src_subresource.array_layers.start;
             let dst_layer_count =
@@ -514,51 +651,78 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                     (ImageType::Dim2d, ImageType::Dim3d) => {
                         src_extent[2] = 1;
 
-                        // VUID-vkCmdCopyImage-srcImage-01791
                         if dst_extent[2] != src_layer_count {
-                            return Err(CopyError::ArrayLayerCountMismatch {
-                                region_index,
-                                src_layer_count,
-                                dst_layer_count: dst_extent[2],
-                            });
+                            return Err(CopyError::ValidationError(ValidationError {
+                                code: "array_layer_count_mismatch",
+                                context: "copy_image",
+                                problem: format!(
+                                    "the array layer counts of the source and destination \
+                                    subresource ranges of region {} do not match (source: {}; \
+                                    destination: {})",
+                                    region_index, src_layer_count, dst_extent[2],
+                                )
+                                .into(),
+                                vuids: &["VUID-vkCmdCopyImage-srcImage-01791"],
+                                ..Default::default()
+                            }));
                         }
                     }
                     (ImageType::Dim3d, ImageType::Dim2d) => {
                         dst_extent[2] = 1;
 
-                        // VUID-vkCmdCopyImage-dstImage-01792
                         if src_extent[2] != dst_layer_count {
-                            return Err(CopyError::ArrayLayerCountMismatch {
-                                region_index,
-                                src_layer_count: src_extent[2],
-                                dst_layer_count,
-                            });
+                            return Err(CopyError::ValidationError(ValidationError {
+                                code: "array_layer_count_mismatch",
+                                context: "copy_image",
+                                problem: format!(
+                                    "the array layer counts of the source and destination \
+                                    subresource ranges of region {} do not match (source: {}; \
+                                    destination: {})",
+                                    region_index, src_extent[2], dst_layer_count,
+                                )
+                                .into(),
+                                vuids: &["VUID-vkCmdCopyImage-dstImage-01792"],
+                                ..Default::default()
+                            }));
                         }
                     }
                     _ => {
-                        // VUID-VkImageCopy2-extent-00140
                         if src_layer_count != dst_layer_count {
-                            return Err(CopyError::ArrayLayerCountMismatch {
-                                region_index,
-                                src_layer_count,
-                                dst_layer_count,
-                            });
+                            return Err(CopyError::ValidationError(ValidationError {
+                                code: "array_layer_count_mismatch",
+                                context: "copy_image",
+                                problem: format!(
+                                    "the array layer counts of the source and destination \
+                                    subresource ranges of region {} do not match (source: {}; \
+                                    destination: {})",
+                                    region_index, src_layer_count, dst_layer_count,
+                                )
+                                .into(),
+                                vuids: &["VUID-VkImageCopy2-extent-00140"],
+                                ..Default::default()
+                            }));
                         }
                     }
                 }
             } else {
-                // VUID-VkImageCopy2-extent-00140
                 if src_layer_count != dst_layer_count {
-                    return Err(CopyError::ArrayLayerCountMismatch {
-                        region_index,
-                        src_layer_count,
-                        dst_layer_count,
-                    });
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "array_layer_count_mismatch",
+                        context: "copy_image",
+                        problem: format!(
+                            "the array layer counts of the source and destination subresource \
+                            ranges of region {} do not match (source: {}; destination: {})",
+                            region_index, src_layer_count, dst_layer_count,
+                        )
+                        .into(),
+                        vuids: &["VUID-VkImageCopy2-extent-00140"],
+                        ..Default::default()
+                    }));
                 }
             };
 
             if let Some((src_extent_alignment, dst_extent_alignment)) = extent_alignment {
-                let check_offset_extent = |resource: CopyErrorResource,
+                let check_offset_extent = |resource: &'static str,
                                            extent_alignment: [u32; 3],
                                            subresource_extent: [u32; 3],
                                            offset: [u32; 3],
@@ -570,53 +734,79 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                         // VUID-VkImageCopy2-extent-06670
                         assert!(extent[i] != 0);
 
-                        // VUID-VkCopyImageInfo2-srcOffset-00144
-                        // VUID-VkCopyImageInfo2-srcOffset-00145
-                        // VUID-VkCopyImageInfo2-srcOffset-00147
-                        // VUID-VkCopyImageInfo2-dstOffset-00150
-                        // VUID-VkCopyImageInfo2-dstOffset-00151
-                        // VUID-VkCopyImageInfo2-dstOffset-00153
                         if offset[i] + extent[i] > subresource_extent[i] {
-                            return Err(CopyError::RegionOutOfImageBounds {
-                                resource,
-                                region_index,
-                                offset_range_end: [
-                                    offset[0] + extent[0],
-                                    offset[1] + extent[1],
-                                    offset[2] + extent[2],
+                            return Err(CopyError::ValidationError(ValidationError {
+                                code: "region_out_of_image_bounds",
+                                context: "copy_image",
+                                problem: format!(
+                                    "the end of the range of accessed {} texel offsets ({:?}) of \
+                                    region {} is greater than the extent of the selected \
+                                    subresource of the {} image ({:?})",
+                                    resource,
+                                    [
+                                        offset[0] + extent[0],
+                                        offset[1] + extent[1],
+                                        offset[2] + extent[2],
+                                    ],
+                                    region_index,
+                                    resource,
+                                    subresource_extent,
+                                )
+                                .into(),
+                                vuids: &[
+                                    "VUID-VkCopyImageInfo2-srcOffset-00144",
+                                    "VUID-VkCopyImageInfo2-srcOffset-00145",
+                                    "VUID-VkCopyImageInfo2-srcOffset-00147",
+                                    "VUID-VkCopyImageInfo2-dstOffset-00150",
+                                    "VUID-VkCopyImageInfo2-dstOffset-00151",
+                                    "VUID-VkCopyImageInfo2-dstOffset-00153",
                                 ],
-                                subresource_extent,
-                            });
+                                ..Default::default()
+                            }));
                         }
 
-                        // VUID-VkCopyImageInfo2-srcImage-01727
-                        // VUID-VkCopyImageInfo2-dstImage-01731
-                        // VUID-VkCopyImageInfo2-srcOffset-01783
-                        // VUID-VkCopyImageInfo2-dstOffset-01784
                         if offset[i] % extent_alignment[i] != 0 {
-                            return Err(CopyError::OffsetNotAlignedForImage {
-                                resource,
-                                region_index,
-                                offset,
-                                required_alignment: extent_alignment,
-                            });
+                            return Err(CopyError::ValidationError(ValidationError {
+                                code: "offset_not_aligned_for_image",
+                                context: "copy_image",
+                                problem: format!(
+                                    "the {} image offset ({:?}) of region {} is not a multiple of \
+                                    the required {} image alignment ({:?})",
+                                    resource, offset, region_index, resource, extent_alignment,
+                                )
+                                .into(),
+                                vuids: &[
+                                    "VUID-VkCopyImageInfo2-srcImage-01727",
+                                    "VUID-VkCopyImageInfo2-dstImage-01731",
+                                    "VUID-VkCopyImageInfo2-srcOffset-01783",
+                                    "VUID-VkCopyImageInfo2-dstOffset-01784",
+                                ],
+                                ..Default::default()
+                            }));
                         }
 
-                        // VUID-VkCopyImageInfo2-srcImage-01728
-                        // VUID-VkCopyImageInfo2-srcImage-01729
-                        // VUID-VkCopyImageInfo2-srcImage-01730
-                        // VUID-VkCopyImageInfo2-dstImage-01732
-                        // VUID-VkCopyImageInfo2-dstImage-01733
-                        // VUID-VkCopyImageInfo2-dstImage-01734
                         if offset[i] + extent[i] != subresource_extent[i]
                             && extent[i] % extent_alignment[i] != 0
                         {
-                            return Err(CopyError::ExtentNotAlignedForImage {
-                                resource,
-                                region_index,
-                                extent,
-                                required_alignment: extent_alignment,
-                            });
+                            return Err(CopyError::ValidationError(ValidationError {
+                                code: "extent_not_aligned_for_image",
+                                context: "copy_image",
+                                problem: format!(
+                                    "the {} image extent ({:?}) of region {} is not a multiple of \
+                                    the required {} image alignment ({:?})",
+                                    resource, extent, region_index, resource, extent_alignment,
+                                )
+                                .into(),
+                                vuids: &[
+                                    "VUID-VkCopyImageInfo2-srcImage-01728",
+                                    "VUID-VkCopyImageInfo2-srcImage-01729",
+                                    "VUID-VkCopyImageInfo2-srcImage-01730",
+                                    "VUID-VkCopyImageInfo2-dstImage-01732",
+                                    "VUID-VkCopyImageInfo2-dstImage-01733",
+                                    "VUID-VkCopyImageInfo2-dstImage-01734",
+                                ],
+                                ..Default::default()
+                            }));
                         }
                     }
 
@@ -624,14 +814,14 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 };
 
                 check_offset_extent(
-                    CopyErrorResource::Source,
+                    "source",
                     src_extent_alignment,
                     src_subresource_extent,
                     src_offset,
                     src_extent,
                 )?;
                 check_offset_extent(
-                    CopyErrorResource::Destination,
+                    "destination",
                     dst_extent_alignment,
                     dst_subresource_extent,
                     dst_offset,
@@ -708,50 +898,59 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 }
             } else {
                 // If granularity is `None`, then we can only copy whole subresources.
-                let check_offset_extent = |resource: CopyErrorResource,
+                let check_offset_extent = |resource: &'static str,
                                            subresource_extent: [u32; 3],
                                            offset: [u32; 3],
                                            extent: [u32; 3]|
                  -> Result<_, CopyError> {
-                    // VUID-VkCopyImageInfo2-srcImage-01727
-                    // VUID-VkCopyImageInfo2-dstImage-01731
-                    // VUID-vkCmdCopyImage-srcOffset-01783
-                    // VUID-vkCmdCopyImage-dstOffset-01784
                     if offset != [0, 0, 0] {
-                        return Err(CopyError::OffsetNotAlignedForImage {
-                            resource,
-                            region_index,
-                            offset,
-                            required_alignment: subresource_extent,
-                        });
+                        return Err(CopyError::ValidationError(ValidationError {
+                            code: "offset_not_aligned_for_image",
+                            context: "copy_image",
+                            problem: format!(
+                                "the {} image offset ({:?}) of region {} is not a multiple of the \
+                                required {} image alignment ({:?})",
+                                resource, offset, region_index, resource, subresource_extent,
+                            )
+                            .into(),
+                            vuids: &[
+                                "VUID-VkCopyImageInfo2-srcImage-01727",
+                                "VUID-VkCopyImageInfo2-dstImage-01731",
+                                "VUID-vkCmdCopyImage-srcOffset-01783",
+                                "VUID-vkCmdCopyImage-dstOffset-01784",
+                            ],
+                            ..Default::default()
+                        }));
                     }
 
-                    // VUID-VkCopyImageInfo2-srcImage-01728
-                    // VUID-VkCopyImageInfo2-srcImage-01729
-                    // VUID-VkCopyImageInfo2-srcImage-01730
-                    // VUID-VkCopyImageInfo2-dstImage-01732
-                    // VUID-VkCopyImageInfo2-dstImage-01733
-                    // VUID-VkCopyImageInfo2-dstImage-01734
                     if extent != subresource_extent {
-                        return Err(CopyError::ExtentNotAlignedForImage {
-                            resource,
-                            region_index,
-                            extent,
-                            required_alignment: subresource_extent,
-                        });
+                        return Err(CopyError::ValidationError(ValidationError {
+                            code: "extent_not_aligned_for_image",
+                            context: "copy_image",
+                            problem: format!(
+                                "the {} image extent ({:?}) of region {} is not a multiple of the \
+                                required {} image alignment ({:?})",
+                                resource, extent, region_index, resource, subresource_extent,
+                            )
+                            .into(),
+                            vuids: &[
+                                "VUID-VkCopyImageInfo2-srcImage-01728",
+                                "VUID-VkCopyImageInfo2-srcImage-01729",
+                                "VUID-VkCopyImageInfo2-srcImage-01730",
+                                "VUID-VkCopyImageInfo2-dstImage-01732",
+                                "VUID-VkCopyImageInfo2-dstImage-01733",
+                                "VUID-VkCopyImageInfo2-dstImage-01734",
+                            ],
+                            ..Default::default()
+                        }));
                     }
 
                     Ok(())
                 };
 
+                check_offset_extent("source", src_subresource_extent, src_offset, src_extent)?;
                 check_offset_extent(
-                    CopyErrorResource::Source,
-                    src_subresource_extent,
-                    src_offset,
-                    src_extent,
-                )?;
-                check_offset_extent(
-                    CopyErrorResource::Destination,
+                    "destination",
                     dst_subresource_extent,
                     dst_offset,
                     dst_extent,
@@ -803,24 +1002,39 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             }
         }
 
-        // VUID-VkCopyImageInfo2-pRegions-00124
         if let Some((src_region_index, dst_region_index)) = overlap_extent_indices {
-            return Err(CopyError::OverlappingRegions {
-                src_region_index,
-                dst_region_index,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "overlapping_regions",
+                context: "copy_image",
+                problem: format!(
+                    "the source bounds of region {} overlap with the destination bounds of region \
+                    {}",
+                    src_region_index, dst_region_index,
+                )
+                .into(),
+                vuids: &["VUID-VkCopyImageInfo2-pRegions-00124"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkCopyImageInfo2-srcImageLayout-00128
-        // VUID-VkCopyImageInfo2-dstImageLayout-00133
         if let Some((src_region_index, dst_region_index)) = overlap_subresource_indices {
             if src_image_layout != dst_image_layout {
-                return Err(CopyError::OverlappingSubresourcesLayoutMismatch {
-                    src_region_index,
-                    dst_region_index,
-                    src_image_layout,
-                    dst_image_layout,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "overlapping_subresources_layout_mismatch",
+                    context: "copy_image",
+                    problem: format!(
+                        "the source subresources of region {} overlap with the destination \
+                        subresources of region {}, but the source image layout ({:?}) does not \
+                        equal the destination image layout ({:?})",
+                        src_region_index, dst_region_index, src_image_layout, dst_image_layout,
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkCopyImageInfo2-srcImageLayout-00128",
+                        "VUID-VkCopyImageInfo2-dstImageLayout-00133",
+                    ],
+                    ..Default::default()
+                }));
             }
         }
 
@@ -875,89 +1089,103 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         let buffer_inner = buffer.inner();
         let mut image_aspects = image.format().aspects();
 
-        // VUID-VkCopyBufferToImageInfo2-commandBuffer-04477
         if !self.queue_family().supports_graphics() && !image_aspects.color {
-            return Err(CopyError::DepthStencilNotSupportedByQueueFamily);
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "depth_stencil_not_supported_by_queue_family",
+                context: "copy_buffer_to_image",
+                problem: "depth/stencil images are not supported by the queue family of this \
+                    command buffer; a graphics queue family is required"
+                    .into(),
+                vuids: &["VUID-VkCopyBufferToImageInfo2-commandBuffer-04477"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkCopyBufferToImageInfo2-srcBuffer-00174
         if !buffer.usage().transfer_src {
-            return Err(CopyError::MissingUsage {
-                resource: CopyErrorResource::Source,
-                usage: "transfer_src",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_usage",
+                context: "copy_buffer_to_image",
+                problem: "the source resource did not have the required usage transfer_src enabled"
+                    .into(),
+                vuids: &["VUID-VkCopyBufferToImageInfo2-srcBuffer-00174"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkCopyBufferToImageInfo2-dstImage-00177
         if !image.usage().transfer_dst {
-            return Err(CopyError::MissingUsage {
-                resource: CopyErrorResource::Destination,
-                usage: "transfer_dst",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_usage",
+                context: "copy_buffer_to_image",
+                problem: "the destination resource did not have the required usage transfer_dst \
+                    enabled"
+                    .into(),
+                vuids: &["VUID-VkCopyBufferToImageInfo2-dstImage-00177"],
+                ..Default::default()
+            }));
         }
 
         if device.api_version() >= Version::V1_1 || device.enabled_extensions().khr_maintenance1 {
-            // VUID-VkCopyBufferToImageInfo2-dstImage-01997
             if !image.format_features().transfer_dst {
-                return Err(CopyError::MissingFormatFeature {
-                    resource: CopyErrorResource::Destination,
-                    format_feature: "transfer_dst",
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "missing_format_feature",
+                    context: "copy_buffer_to_image",
+                    problem: "the destination image does not have the required format feature \
+                        transfer_dst"
+                        .into(),
+                    vuids: &["VUID-VkCopyBufferToImageInfo2-dstImage-01997"],
+                    ..Default::default()
+                }));
             }
         }
 
-        // VUID-VkCopyBufferToImageInfo2-dstImage-00179
         if image.samples() != SampleCount::Sample1 {
-            return Err(CopyError::SampleCountInvalid {
-                resource: CopyErrorResource::Destination,
-                sample_count: image.samples(),
-                allowed_sample_counts: SampleCounts {
-                    sample1: true,
-                    sample2: false,
-                    sample4: false,
-                    sample8: false,
-                    sample16: false,
-                    sample32: false,
-                    sample64: false,
-                },
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "sample_count_invalid",
+                context: "copy_buffer_to_image",
+                problem: format!(
+                    "the destination image has a sample count ({:?}) that is not valid for this \
+                    operation ({:?})",
+                    image.samples(),
+                    SampleCounts {
+                        sample1: true,
+                        sample2: false,
+                        sample4: false,
+                        sample8: false,
+                        sample16: false,
+                        sample32: false,
+                        sample64: false,
+                    },
+                )
+                .into(),
+                vuids: &["VUID-VkCopyBufferToImageInfo2-dstImage-00179"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkCopyBufferToImageInfo2-dstImageLayout-01396
         if !matches!(
             image_layout,
             ImageLayout::TransferDstOptimal | ImageLayout::General
         ) {
-            return Err(CopyError::ImageLayoutInvalid {
-                resource: CopyErrorResource::Destination,
-                image_layout,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "image_layout_invalid",
+                context: "copy_buffer_to_image",
+                problem: format!(
+                    "the specified destination image layout {:?} is not valid for this operation",
+                    image_layout,
+                )
+                .into(),
+                vuids: &["VUID-VkCopyBufferToImageInfo2-dstImageLayout-01396"],
+                ..Default::default()
+            }));
         }
 
         let extent_alignment = match self.queue_family().min_image_transfer_granularity() {
             [0, 0, 0] => None,
-            min_image_transfer_granularity => {
-                let granularity = move |block_extent: [u32; 3], is_multi_plane: bool| {
-                    if is_multi_plane {
-                        // Assume planes always have 1x1 blocks
-                        min_image_transfer_granularity
-                    } else {
-                        // "The value returned in minImageTransferGranularity has a unit of
-                        // compressed texel blocks for images having a block-compressed format, and
-                        // a unit of texels otherwise."
-                        [
-                            min_image_transfer_granularity[0] * block_extent[0],
-                            min_image_transfer_granularity[1] * block_extent[1],
-                            min_image_transfer_granularity[2] * block_extent[2],
-                        ]
-                    }
-                };
-
-                Some(granularity(
-                    image.format().block_extent(),
-                    image_aspects.plane0,
-                ))
-            }
+            min_image_transfer_granularity => Some(image_transfer_granularity(
+                min_image_transfer_granularity,
+                image.format(),
+                image_aspects.plane0,
+            )),
         };
 
         if image_aspects.plane0 {
@@ -976,52 +1204,85 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 _ne: _,
             } = region;
 
-            // VUID-VkCopyBufferToImageInfo2-imageSubresource-01701
             if image_subresource.mip_level >= image.mip_levels() {
-                return Err(CopyError::MipLevelsOutOfRange {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    mip_levels_range_end: image_subresource.mip_level + 1,
-                    image_mip_levels: image.mip_levels(),
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "mip_levels_out_of_range",
+                    context: "copy_buffer_to_image",
+                    problem: format!(
+                        "the end of the range of accessed mip levels ({}) of the destination \
+                        subresource range of region {} is not less than the number of mip levels \
+                        in the destination image ({})",
+                        image_subresource.mip_level + 1,
+                        region_index,
+                        image.mip_levels(),
+                    )
+                    .into(),
+                    vuids: &["VUID-VkCopyBufferToImageInfo2-imageSubresource-01701"],
+                    ..Default::default()
+                }));
             }
 
             // VUID-VkImageSubresourceLayers-layerCount-01700
             // VUID-VkCopyBufferToImageInfo2-baseArrayLayer-00213
             assert!(!image_subresource.array_layers.is_empty());
 
-            // VUID-VkCopyBufferToImageInfo2-imageSubresource-01702
-            // VUID-VkCopyBufferToImageInfo2-baseArrayLayer-00213
             if image_subresource.array_layers.end > image.dimensions().array_layers() {
-                return Err(CopyError::ArrayLayersOutOfRange {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    array_layers_range_end: image_subresource.array_layers.end,
-                    image_array_layers: image.dimensions().array_layers(),
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "array_layers_out_of_range",
+                    context: "copy_buffer_to_image",
+                    problem: format!(
+                        "the end of the range of accessed array layers ({}) of the destination \
+                        subresource range of region {} is greater than the number of array \
+                        layers in the destination image ({})",
+                        image_subresource.array_layers.end,
+                        region_index,
+                        image.dimensions().array_layers(),
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkCopyBufferToImageInfo2-imageSubresource-01702",
+                        "VUID-VkCopyBufferToImageInfo2-baseArrayLayer-00213",
+                    ],
+                    ..Default::default()
+                }));
             }
 
             // VUID-VkImageSubresourceLayers-aspectMask-requiredbitmask
             assert!(image_subresource.aspects != ImageAspects::none());
 
-            // VUID-VkCopyBufferToImageInfo2-aspectMask-00211
             if !image_aspects.contains(&image_subresource.aspects) {
-                return Err(CopyError::AspectsNotAllowed {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    aspects: image_subresource.aspects,
-                    allowed_aspects: image_aspects,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "aspects_not_allowed",
+                    context: "copy_buffer_to_image",
+                    problem: format!(
+                        "the aspects ({:?}) of the destination subresource range of region {} \
+                        contain aspects that are not present in the destination image, or that \
+                        are not allowed ({:?})",
+                        image_subresource.aspects, region_index, image_aspects,
+                    )
+                    .into(),
+                    vuids: &["VUID-VkCopyBufferToImageInfo2-aspectMask-00211"],
+                    ..Default::default()
+                }));
             }
 
-            // VUID-VkBufferImageCopy2-aspectMask-00212
-            // VUID-VkCopyBufferToImageInfo2-aspectMask-01560
             if image_subresource.aspects.iter().count() != 1 {
-                return Err(CopyError::MultipleAspectsNotAllowed {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    aspects: image_subresource.aspects,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "multiple_aspects_not_allowed",
+                    context: "copy_buffer_to_image",
+                    problem: format!(
+                        "the destination subresource range of region {} specifies multiple \
+                        aspects ({:?}), but only one aspect can be selected for the destination \
+                        image",
+                        region_index, image_subresource.aspects,
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkBufferImageCopy2-aspectMask-00212",
+                        "VUID-VkCopyBufferToImageInfo2-aspectMask-01560",
+                    ],
+                    ..Default::default()
+                }));
             }
 
             let (image_subresource_format, image_subresource_extent) = if image_aspects.plane0 {
@@ -1067,112 +1328,166 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                     // VUID-VkBufferImageCopy2-imageExtent-06661
                     assert!(image_extent[i] != 0);
 
-                    // VUID-VkCopyBufferToImageInfo2-pRegions-06223
-                    // VUID-VkCopyBufferToImageInfo2-pRegions-06224
-                    // VUID-VkCopyBufferToImageInfo2-imageOffset-00200
                     if image_offset[i] + image_extent[i] > image_subresource_extent[i] {
-                        return Err(CopyError::RegionOutOfImageBounds {
-                            resource: CopyErrorResource::Destination,
-                            region_index,
-                            offset_range_end: [
-                                image_offset[0] + image_extent[0],
-                                image_offset[1] + image_extent[1],
-                                image_offset[2] + image_extent[2],
+                        return Err(CopyError::ValidationError(ValidationError {
+                            code: "region_out_of_image_bounds",
+                            context: "copy_buffer_to_image",
+                            problem: format!(
+                                "the end of the range of accessed destination texel offsets \
+                                ({:?}) of region {} is greater than the extent of the selected \
+                                subresource of the destination image ({:?})",
+                                [
+                                    image_offset[0] + image_extent[0],
+                                    image_offset[1] + image_extent[1],
+                                    image_offset[2] + image_extent[2],
+                                ],
+                                region_index,
+                                image_subresource_extent,
+                            )
+                            .into(),
+                            vuids: &[
+                                "VUID-VkCopyBufferToImageInfo2-pRegions-06223",
+                                "VUID-VkCopyBufferToImageInfo2-pRegions-06224",
+                                "VUID-VkCopyBufferToImageInfo2-imageOffset-00200",
                             ],
-                            subresource_extent: image_subresource_extent,
-                        });
+                            ..Default::default()
+                        }));
                     }
 
-                    // VUID-VkCopyBufferToImageInfo2-imageOffset-01793
-                    // VUID-VkCopyBufferToImageInfo2-imageOffset-00205
                     if image_offset[i] % extent_alignment[i] != 0 {
-                        return Err(CopyError::OffsetNotAlignedForImage {
-                            resource: CopyErrorResource::Destination,
-                            region_index,
-                            offset: image_offset,
-                            required_alignment: extent_alignment,
-                        });
+                        return Err(CopyError::ValidationError(ValidationError {
+                            code: "offset_not_aligned_for_image",
+                            context: "copy_buffer_to_image",
+                            problem: format!(
+                                "the destination image offset ({:?}) of region {} is not a \
+                                multiple of the required destination image alignment ({:?})",
+                                image_offset, region_index, extent_alignment,
+                            )
+                            .into(),
+                            vuids: &[
+                                "VUID-VkCopyBufferToImageInfo2-imageOffset-01793",
+                                "VUID-VkCopyBufferToImageInfo2-imageOffset-00205",
+                            ],
+                            ..Default::default()
+                        }));
                     }
 
-                    // VUID-VkCopyBufferToImageInfo2-imageOffset-01793
-                    // VUID-VkCopyBufferToImageInfo2-imageExtent-00207
-                    // VUID-VkCopyBufferToImageInfo2-imageExtent-00208
-                    // VUID-VkCopyBufferToImageInfo2-imageExtent-00209
                     if image_offset[i] + image_extent[i] != image_subresource_extent[i]
                         && image_extent[i] % extent_alignment[i] != 0
                     {
-                        return Err(CopyError::ExtentNotAlignedForImage {
-                            resource: CopyErrorResource::Destination,
-                            region_index,
-                            extent: image_extent,
-                            required_alignment: extent_alignment,
-                        });
+                        return Err(CopyError::ValidationError(ValidationError {
+                            code: "extent_not_aligned_for_image",
+                            context: "copy_buffer_to_image",
+                            problem: format!(
+                                "the destination image extent ({:?}) of region {} is not a \
+                                multiple of the required destination image alignment ({:?})",
+                                image_extent, region_index, extent_alignment,
+                            )
+                            .into(),
+                            vuids: &[
+                                "VUID-VkCopyBufferToImageInfo2-imageOffset-01793",
+                                "VUID-VkCopyBufferToImageInfo2-imageExtent-00207",
+                                "VUID-VkCopyBufferToImageInfo2-imageExtent-00208",
+                                "VUID-VkCopyBufferToImageInfo2-imageExtent-00209",
+                            ],
+                            ..Default::default()
+                        }));
                     }
                 }
             } else {
                 // If granularity is `None`, then we can only copy whole subresources.
 
-                // VUID-VkCopyBufferToImageInfo2-imageOffset-01793
                 if image_offset != [0, 0, 0] {
-                    return Err(CopyError::OffsetNotAlignedForImage {
-                        resource: CopyErrorResource::Destination,
-                        region_index,
-                        offset: image_offset,
-                        required_alignment: image_subresource_extent,
-                    });
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "offset_not_aligned_for_image",
+                        context: "copy_buffer_to_image",
+                        problem: format!(
+                            "the destination image offset ({:?}) of region {} is not a multiple \
+                            of the required destination image alignment ({:?})",
+                            image_offset, region_index, image_subresource_extent,
+                        )
+                        .into(),
+                        vuids: &["VUID-VkCopyBufferToImageInfo2-imageOffset-01793"],
+                        ..Default::default()
+                    }));
                 }
 
-                // VUID-VkCopyBufferToImageInfo2-imageOffset-01793
                 if image_extent != image_subresource_extent {
-                    return Err(CopyError::ExtentNotAlignedForImage {
-                        resource: CopyErrorResource::Destination,
-                        region_index,
-                        extent: image_extent,
-                        required_alignment: image_subresource_extent,
-                    });
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "extent_not_aligned_for_image",
+                        context: "copy_buffer_to_image",
+                        problem: format!(
+                            "the destination image extent ({:?}) of region {} is not a multiple \
+                            of the required destination image alignment ({:?})",
+                            image_extent, region_index, image_subresource_extent,
+                        )
+                        .into(),
+                        vuids: &["VUID-VkCopyBufferToImageInfo2-imageOffset-01793"],
+                        ..Default::default()
+                    }));
                 }
             }
 
-            // VUID-VkBufferImageCopy2-bufferRowLength-00195
             if !(buffer_row_length == 0 || buffer_row_length >= image_extent[0]) {
-                return Err(CopyError::BufferRowLengthTooSmall {
-                    resource: CopyErrorResource::Source,
-                    region_index,
-                    row_length: buffer_row_length,
-                    min: image_extent[0],
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "buffer_row_length_too_small",
+                    context: "copy_buffer_to_image",
+                    problem: format!(
+                        "the source buffer row length length ({}) of region {} is smaller than \
+                        the source image extent width ({})",
+                        buffer_row_length, region_index, image_extent[0],
+                    )
+                    .into(),
+                    vuids: &["VUID-VkBufferImageCopy2-bufferRowLength-00195"],
+                    ..Default::default()
+                }));
             }
 
-            // VUID-VkBufferImageCopy2-bufferImageHeight-00196
             if !(buffer_image_height == 0 || buffer_image_height >= image_extent[1]) {
-                return Err(CopyError::BufferImageHeightTooSmall {
-                    resource: CopyErrorResource::Source,
-                    region_index,
-                    image_height: buffer_image_height,
-                    min: image_extent[1],
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "buffer_image_height_too_small",
+                    context: "copy_buffer_to_image",
+                    problem: format!(
+                        "the source buffer image height ({}) of region {} is smaller than the \
+                        source image extent height ({})",
+                        buffer_image_height, region_index, image_extent[1],
+                    )
+                    .into(),
+                    vuids: &["VUID-VkBufferImageCopy2-bufferImageHeight-00196"],
+                    ..Default::default()
+                }));
             }
 
             let image_subresource_block_extent = image_subresource_format.block_extent();
 
-            // VUID-VkCopyBufferToImageInfo2-bufferRowLength-00203
             if buffer_row_length % image_subresource_block_extent[0] != 0 {
-                return Err(CopyError::BufferRowLengthNotAligned {
-                    resource: CopyErrorResource::Source,
-                    region_index,
-                    row_length: buffer_row_length,
-                    required_alignment: image_subresource_block_extent[0],
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "buffer_row_length_not_aligned",
+                    context: "copy_buffer_to_image",
+                    problem: format!(
+                        "the source buffer row length ({}) of region {} is not a multiple of the \
+                        required source buffer alignment ({})",
+                        buffer_row_length, region_index, image_subresource_block_extent[0],
+                    )
+                    .into(),
+                    vuids: &["VUID-VkCopyBufferToImageInfo2-bufferRowLength-00203"],
+                    ..Default::default()
+                }));
             }
 
-            // VUID-VkCopyBufferToImageInfo2-bufferImageHeight-00204
             if buffer_image_height % image_subresource_block_extent[1] != 0 {
-                return Err(CopyError::BufferImageHeightNotAligned {
-                    resource: CopyErrorResource::Source,
-                    region_index,
-                    image_height: buffer_image_height,
-                    required_alignment: image_subresource_block_extent[1],
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "buffer_image_height_not_aligned",
+                    context: "copy_buffer_to_image",
+                    problem: format!(
+                        "the source buffer image height ({}) of region {} is not a multiple of \
+                        the required source buffer alignment ({})",
+                        buffer_image_height, region_index, image_subresource_block_extent[1],
+                    )
+                    .into(),
+                    vuids: &["VUID-VkCopyBufferToImageInfo2-bufferImageHeight-00204"],
+                    ..Default::default()
+                }));
             }
 
             // https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/VkBufferImageCopy.html#_description
@@ -1191,17 +1506,25 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 image_subresource_format.block_size().unwrap()
             };
 
-            // VUID-VkCopyBufferToImageInfo2-pRegions-04725
-            // VUID-VkCopyBufferToImageInfo2-pRegions-04726
             if (buffer_row_length / image_subresource_block_extent[0]) as DeviceSize
                 * image_subresource_block_size
                 > 0x7FFFFFFF
             {
-                return Err(CopyError::BufferRowLengthTooLarge {
-                    resource: CopyErrorResource::Source,
-                    region_index,
-                    buffer_row_length,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "buffer_row_length_too_large",
+                    context: "copy_buffer_to_image",
+                    problem: format!(
+                        "the source buffer row length ({}) of region {} specifies a row of texels \
+                        that is greater than 0x7FFFFFFF bytes in size",
+                        buffer_row_length, region_index,
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkCopyBufferToImageInfo2-pRegions-04725",
+                        "VUID-VkCopyBufferToImageInfo2-pRegions-04726",
+                    ],
+                    ..Default::default()
+                }));
             }
 
             let buffer_offset_alignment = if image_aspects.depth || image_aspects.stencil {
@@ -1226,29 +1549,45 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 buffer_offset_alignment
             };
 
-            // VUID-VkCopyBufferToImageInfo2-bufferOffset-00206
-            // VUID-VkCopyBufferToImageInfo2-bufferOffset-01558
-            // VUID-VkCopyBufferToImageInfo2-bufferOffset-01559
-            // VUID-VkCopyBufferToImageInfo2-srcImage-04053
             if (buffer_inner.offset + buffer_offset) % buffer_offset_alignment != 0 {
-                return Err(CopyError::OffsetNotAlignedForBuffer {
-                    resource: CopyErrorResource::Source,
-                    region_index,
-                    offset: buffer_inner.offset + buffer_offset,
-                    required_alignment: buffer_offset_alignment,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "offset_not_aligned_for_buffer",
+                    context: "copy_buffer_to_image",
+                    problem: format!(
+                        "the source buffer offset ({}) of region {} is not a multiple of the \
+                        required source buffer alignment ({})",
+                        buffer_inner.offset + buffer_offset,
+                        region_index,
+                        buffer_offset_alignment,
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkCopyBufferToImageInfo2-bufferOffset-00206",
+                        "VUID-VkCopyBufferToImageInfo2-bufferOffset-01558",
+                        "VUID-VkCopyBufferToImageInfo2-bufferOffset-01559",
+                        "VUID-VkCopyBufferToImageInfo2-srcImage-04053",
+                    ],
+                    ..Default::default()
+                }));
             }
 
             let buffer_copy_size = region.buffer_copy_size(image_subresource_format);
 
-            // VUID-VkCopyBufferToImageInfo2-pRegions-00171
             if buffer_offset + buffer_copy_size > buffer.size() {
-                return Err(CopyError::RegionOutOfBufferBounds {
-                    resource: CopyErrorResource::Source,
-                    region_index,
-                    offset_range_end: buffer_offset + buffer_copy_size,
-                    buffer_size: buffer.size(),
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "region_out_of_buffer_bounds",
+                    context: "copy_buffer_to_image",
+                    problem: format!(
+                        "the end of the range of accessed source byte offsets ({}) of region {} \
+                        is greater than the size of the source buffer ({})",
+                        buffer_offset + buffer_copy_size,
+                        region_index,
+                        buffer.size(),
+                    )
+                    .into(),
+                    vuids: &["VUID-VkCopyBufferToImageInfo2-pRegions-00171"],
+                    ..Default::default()
+                }));
             }
         }
 
@@ -1306,84 +1645,91 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         let buffer_inner = buffer.inner();
         let mut image_aspects = image.format().aspects();
 
-        // VUID-VkCopyImageToBufferInfo2-srcImage-00186
         if !image.usage().transfer_src {
-            return Err(CopyError::MissingUsage {
-                resource: CopyErrorResource::Source,
-                usage: "transfer_src",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_usage",
+                context: "copy_image_to_buffer",
+                problem: "the source resource did not have the required usage transfer_src enabled"
+                    .into(),
+                vuids: &["VUID-VkCopyImageToBufferInfo2-srcImage-00186"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkCopyImageToBufferInfo2-dstBuffer-00191
         if !buffer.usage().transfer_dst {
-            return Err(CopyError::MissingUsage {
-                resource: CopyErrorResource::Destination,
-                usage: "transfer_dst",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_usage",
+                context: "copy_image_to_buffer",
+                problem: "the destination resource did not have the required usage transfer_dst \
+                    enabled"
+                    .into(),
+                vuids: &["VUID-VkCopyImageToBufferInfo2-dstBuffer-00191"],
+                ..Default::default()
+            }));
         }
 
         if device.api_version() >= Version::V1_1 || device.enabled_extensions().khr_maintenance1 {
-            // VUID-VkCopyImageToBufferInfo2-srcImage-01998
             if !image.format_features().transfer_src {
-                return Err(CopyError::MissingFormatFeature {
-                    resource: CopyErrorResource::Source,
-                    format_feature: "transfer_src",
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "missing_format_feature",
+                    context: "copy_image_to_buffer",
+                    problem: "the source image does not have the required format feature \
+                        transfer_src"
+                        .into(),
+                    vuids: &["VUID-VkCopyImageToBufferInfo2-srcImage-01998"],
+                    ..Default::default()
+                }));
             }
         }
 
-        // VUID-VkCopyImageToBufferInfo2-srcImage-00188
         if image.samples() != SampleCount::Sample1 {
-            return Err(CopyError::SampleCountInvalid {
-                resource: CopyErrorResource::Source,
-                sample_count: image.samples(),
-                allowed_sample_counts: SampleCounts {
-                    sample1: true,
-                    sample2: false,
-                    sample4: false,
-                    sample8: false,
-                    sample16: false,
-                    sample32: false,
-                    sample64: false,
-                },
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "sample_count_invalid",
+                context: "copy_image_to_buffer",
+                problem: format!(
+                    "the source image has a sample count ({:?}) that is not valid for this \
+                    operation ({:?})",
+                    image.samples(),
+                    SampleCounts {
+                        sample1: true,
+                        sample2: false,
+                        sample4: false,
+                        sample8: false,
+                        sample16: false,
+                        sample32: false,
+                        sample64: false,
+                    },
+                )
+                .into(),
+                vuids: &["VUID-VkCopyImageToBufferInfo2-srcImage-00188"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkCopyImageToBufferInfo2-srcImageLayout-01397
         if !matches!(
             image_layout,
             ImageLayout::TransferSrcOptimal | ImageLayout::General
         ) {
-            return Err(CopyError::ImageLayoutInvalid {
-                resource: CopyErrorResource::Source,
-                image_layout,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "image_layout_invalid",
+                context: "copy_image_to_buffer",
+                problem: format!(
+                    "the specified source image layout {:?} is not valid for this operation",
+                    image_layout,
+                )
+                .into(),
+                vuids: &["VUID-VkCopyImageToBufferInfo2-srcImageLayout-01397"],
+                ..Default::default()
+            }));
         }
 
         let extent_alignment = match self.queue_family().min_image_transfer_granularity() {
             [0, 0, 0] => None,
-            min_image_transfer_granularity => {
-                let granularity = move |block_extent: [u32; 3], is_multi_plane: bool| {
-                    if is_multi_plane {
-                        // Assume planes always have 1x1 blocks
-                        min_image_transfer_granularity
-                    } else {
-                        // "The value returned in minImageTransferGranularity has a unit of
-                        // compressed texel blocks for images having a block-compressed format, and
-                        // a unit of texels otherwise."
-                        [
-                            min_image_transfer_granularity[0] * block_extent[0],
-                            min_image_transfer_granularity[1] * block_extent[1],
-                            min_image_transfer_granularity[2] * block_extent[2],
-                        ]
-                    }
-                };
-
-                Some(granularity(
-                    image.format().block_extent(),
-                    image_aspects.plane0,
-                ))
-            }
+            min_image_transfer_granularity => Some(image_transfer_granularity(
+                min_image_transfer_granularity,
+                image.format(),
+                image_aspects.plane0,
+            )),
         };
 
         if image_aspects.plane0 {
@@ -1402,50 +1748,80 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 _ne: _,
             } = region;
 
-            // VUID-VkCopyImageToBufferInfo2-imageSubresource-01703
             if image_subresource.mip_level >= image.mip_levels() {
-                return Err(CopyError::MipLevelsOutOfRange {
-                    resource: CopyErrorResource::Source,
-                    region_index,
-                    mip_levels_range_end: image_subresource.mip_level + 1,
-                    image_mip_levels: image.mip_levels(),
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "mip_levels_out_of_range",
+                    context: "copy_image_to_buffer",
+                    problem: format!(
+                        "the end of the range of accessed mip levels ({}) of the source \
+                        subresource range of region {} is not less than the number of mip levels \
+                        in the source image ({})",
+                        image_subresource.mip_level + 1,
+                        region_index,
+                        image.mip_levels(),
+                    )
+                    .into(),
+                    vuids: &["VUID-VkCopyImageToBufferInfo2-imageSubresource-01703"],
+                    ..Default::default()
+                }));
             }
 
             // VUID-VkImageSubresourceLayers-layerCount-01700
             assert!(!image_subresource.array_layers.is_empty());
 
-            // VUID-VkCopyImageToBufferInfo2-imageSubresource-01704
-            // VUID-VkCopyImageToBufferInfo2-baseArrayLayer-00213
             if image_subresource.array_layers.end > image.dimensions().array_layers() {
-                return Err(CopyError::ArrayLayersOutOfRange {
-                    resource: CopyErrorResource::Source,
-                    region_index,
-                    array_layers_range_end: image_subresource.array_layers.end,
-                    image_array_layers: image.dimensions().array_layers(),
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "array_layers_out_of_range",
+                    context: "copy_image_to_buffer",
+                    problem: format!(
+                        "the end of the range of accessed array layers ({}) of the source \
+                        subresource range of region {} is greater than the number of array \
+                        layers in the source image ({})",
+                        image_subresource.array_layers.end,
+                        region_index,
+                        image.dimensions().array_layers(),
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkCopyImageToBufferInfo2-imageSubresource-01704",
+                        "VUID-VkCopyImageToBufferInfo2-baseArrayLayer-00213",
+                    ],
+                    ..Default::default()
+                }));
             }
 
             // VUID-VkImageSubresourceLayers-aspectMask-requiredbitmask
             assert!(image_subresource.aspects != ImageAspects::none());
 
-            // VUID-VkCopyImageToBufferInfo2-aspectMask-00211
             if !image_aspects.contains(&image_subresource.aspects) {
-                return Err(CopyError::AspectsNotAllowed {
-                    resource: CopyErrorResource::Source,
-                    region_index,
-                    aspects: image_subresource.aspects,
-                    allowed_aspects: image_aspects,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "aspects_not_allowed",
+                    context: "copy_image_to_buffer",
+                    problem: format!(
+                        "the aspects ({:?}) of the source subresource range of region {} contain \
+                        aspects that are not present in the source image, or that are not \
+                        allowed ({:?})",
+                        image_subresource.aspects, region_index, image_aspects,
+                    )
+                    .into(),
+                    vuids: &["VUID-VkCopyImageToBufferInfo2-aspectMask-00211"],
+                    ..Default::default()
+                }));
             }
 
-            // VUID-VkBufferImageCopy2-aspectMask-00212
             if image_subresource.aspects.iter().count() != 1 {
-                return Err(CopyError::MultipleAspectsNotAllowed {
-                    resource: CopyErrorResource::Source,
-                    region_index,
-                    aspects: image_subresource.aspects,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "multiple_aspects_not_allowed",
+                    context: "copy_image_to_buffer",
+                    problem: format!(
+                        "the source subresource range of region {} specifies multiple aspects \
+                        ({:?}), but only one aspect can be selected for the source image",
+                        region_index, image_subresource.aspects,
+                    )
+                    .into(),
+                    vuids: &["VUID-VkBufferImageCopy2-aspectMask-00212"],
+                    ..Default::default()
+                }));
             }
 
             let (image_subresource_format, image_subresource_extent) = if image_aspects.plane0 {
@@ -1491,112 +1867,166 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                     // VUID-VkBufferImageCopy2-imageExtent-06661
                     assert!(image_extent[i] != 0);
 
-                    // VUID-VkCopyImageToBufferInfo2-imageOffset-00197
-                    // VUID-VkCopyImageToBufferInfo2-imageOffset-00198
-                    // VUID-VkCopyImageToBufferInfo2-imageOffset-00200
                     if image_offset[i] + image_extent[i] > image_subresource_extent[i] {
-                        return Err(CopyError::RegionOutOfImageBounds {
-                            resource: CopyErrorResource::Source,
-                            region_index,
-                            offset_range_end: [
-                                image_offset[0] + image_extent[0],
-                                image_offset[1] + image_extent[1],
-                                image_offset[2] + image_extent[2],
+                        return Err(CopyError::ValidationError(ValidationError {
+                            code: "region_out_of_image_bounds",
+                            context: "copy_image_to_buffer",
+                            problem: format!(
+                                "the end of the range of accessed source texel offsets ({:?}) of \
+                                region {} is greater than the extent of the selected subresource \
+                                of the source image ({:?})",
+                                [
+                                    image_offset[0] + image_extent[0],
+                                    image_offset[1] + image_extent[1],
+                                    image_offset[2] + image_extent[2],
+                                ],
+                                region_index,
+                                image_subresource_extent,
+                            )
+                            .into(),
+                            vuids: &[
+                                "VUID-VkCopyImageToBufferInfo2-imageOffset-00197",
+                                "VUID-VkCopyImageToBufferInfo2-imageOffset-00198",
+                                "VUID-VkCopyImageToBufferInfo2-imageOffset-00200",
                             ],
-                            subresource_extent: image_subresource_extent,
-                        });
+                            ..Default::default()
+                        }));
                     }
 
-                    // VUID-VkCopyImageToBufferInfo2-imageOffset-01794
-                    // VUID-VkCopyImageToBufferInfo2-imageOffset-00205
                     if image_offset[i] % extent_alignment[i] != 0 {
-                        return Err(CopyError::OffsetNotAlignedForImage {
-                            resource: CopyErrorResource::Source,
-                            region_index,
-                            offset: image_offset,
-                            required_alignment: extent_alignment,
-                        });
+                        return Err(CopyError::ValidationError(ValidationError {
+                            code: "offset_not_aligned_for_image",
+                            context: "copy_image_to_buffer",
+                            problem: format!(
+                                "the source image offset ({:?}) of region {} is not a multiple of \
+                                the required source image alignment ({:?})",
+                                image_offset, region_index, extent_alignment,
+                            )
+                            .into(),
+                            vuids: &[
+                                "VUID-VkCopyImageToBufferInfo2-imageOffset-01794",
+                                "VUID-VkCopyImageToBufferInfo2-imageOffset-00205",
+                            ],
+                            ..Default::default()
+                        }));
                     }
 
-                    // VUID-VkCopyImageToBufferInfo2-imageOffset-01794
-                    // VUID-VkCopyImageToBufferInfo2-imageExtent-00207
-                    // VUID-VkCopyImageToBufferInfo2-imageExtent-00208
-                    // VUID-VkCopyImageToBufferInfo2-imageExtent-00209
                     if image_offset[i] + image_extent[i] != image_subresource_extent[i]
                         && image_extent[i] % extent_alignment[i] != 0
                     {
-                        return Err(CopyError::ExtentNotAlignedForImage {
-                            resource: CopyErrorResource::Source,
-                            region_index,
-                            extent: image_extent,
-                            required_alignment: extent_alignment,
-                        });
+                        return Err(CopyError::ValidationError(ValidationError {
+                            code: "extent_not_aligned_for_image",
+                            context: "copy_image_to_buffer",
+                            problem: format!(
+                                "the source image extent ({:?}) of region {} is not a multiple of \
+                                the required source image alignment ({:?})",
+                                image_extent, region_index, extent_alignment,
+                            )
+                            .into(),
+                            vuids: &[
+                                "VUID-VkCopyImageToBufferInfo2-imageOffset-01794",
+                                "VUID-VkCopyImageToBufferInfo2-imageExtent-00207",
+                                "VUID-VkCopyImageToBufferInfo2-imageExtent-00208",
+                                "VUID-VkCopyImageToBufferInfo2-imageExtent-00209",
+                            ],
+                            ..Default::default()
+                        }));
                     }
                 }
             } else {
                 // If granularity is `None`, then we can only copy whole subresources.
 
-                // VUID-VkCopyBufferToImageInfo2-imageOffset-01793
                 if image_offset != [0, 0, 0] {
-                    return Err(CopyError::OffsetNotAlignedForImage {
-                        resource: CopyErrorResource::Source,
-                        region_index,
-                        offset: image_offset,
-                        required_alignment: image_subresource_extent,
-                    });
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "offset_not_aligned_for_image",
+                        context: "copy_image_to_buffer",
+                        problem: format!(
+                            "the source image offset ({:?}) of region {} is not a multiple of the \
+                            required source image alignment ({:?})",
+                            image_offset, region_index, image_subresource_extent,
+                        )
+                        .into(),
+                        vuids: &["VUID-VkCopyBufferToImageInfo2-imageOffset-01793"],
+                        ..Default::default()
+                    }));
                 }
 
-                // VUID-VkCopyBufferToImageInfo2-imageOffset-01793
                 if image_extent != image_subresource_extent {
-                    return Err(CopyError::ExtentNotAlignedForImage {
-                        resource: CopyErrorResource::Source,
-                        region_index,
-                        extent: image_extent,
-                        required_alignment: image_subresource_extent,
-                    });
+                    return Err(CopyError::ValidationError(ValidationError {
+                        code: "extent_not_aligned_for_image",
+                        context: "copy_image_to_buffer",
+                        problem: format!(
+                            "the source image extent ({:?}) of region {} is not a multiple of the \
+                            required source image alignment ({:?})",
+                            image_extent, region_index, image_subresource_extent,
+                        )
+                        .into(),
+                        vuids: &["VUID-VkCopyBufferToImageInfo2-imageOffset-01793"],
+                        ..Default::default()
+                    }));
                 }
             }
 
-            // VUID-VkBufferImageCopy2-bufferRowLength-00195
             if !(buffer_row_length == 0 || buffer_row_length >= image_extent[0]) {
-                return Err(CopyError::BufferRowLengthTooSmall {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    row_length: buffer_row_length,
-                    min: image_extent[0],
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "buffer_row_length_too_small",
+                    context: "copy_image_to_buffer",
+                    problem: format!(
+                        "the destination buffer row length length ({}) of region {} is smaller \
+                        than the destination image extent width ({})",
+                        buffer_row_length, region_index, image_extent[0],
+                    )
+                    .into(),
+                    vuids: &["VUID-VkBufferImageCopy2-bufferRowLength-00195"],
+                    ..Default::default()
+                }));
             }
 
-            // VUID-VkBufferImageCopy2-bufferImageHeight-00196
             if !(buffer_image_height == 0 || buffer_image_height >= image_extent[1]) {
-                return Err(CopyError::BufferImageHeightTooSmall {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    image_height: buffer_image_height,
-                    min: image_extent[1],
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "buffer_image_height_too_small",
+                    context: "copy_image_to_buffer",
+                    problem: format!(
+                        "the destination buffer image height ({}) of region {} is smaller than \
+                        the destination image extent height ({})",
+                        buffer_image_height, region_index, image_extent[1],
+                    )
+                    .into(),
+                    vuids: &["VUID-VkBufferImageCopy2-bufferImageHeight-00196"],
+                    ..Default::default()
+                }));
             }
 
             let image_subresource_block_extent = image_subresource_format.block_extent();
 
-            // VUID-VkCopyImageToBufferInfo2-bufferRowLength-00203
             if buffer_row_length % image_subresource_block_extent[0] != 0 {
-                return Err(CopyError::BufferRowLengthNotAligned {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    row_length: buffer_row_length,
-                    required_alignment: image_subresource_block_extent[0],
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "buffer_row_length_not_aligned",
+                    context: "copy_image_to_buffer",
+                    problem: format!(
+                        "the destination buffer row length ({}) of region {} is not a multiple of \
+                        the required destination buffer alignment ({})",
+                        buffer_row_length, region_index, image_subresource_block_extent[0],
+                    )
+                    .into(),
+                    vuids: &["VUID-VkCopyImageToBufferInfo2-bufferRowLength-00203"],
+                    ..Default::default()
+                }));
             }
 
-            // VUID-VkCopyImageToBufferInfo2-bufferImageHeight-00204
             if buffer_image_height % image_subresource_block_extent[1] != 0 {
-                return Err(CopyError::BufferImageHeightNotAligned {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    image_height: buffer_image_height,
-                    required_alignment: image_subresource_block_extent[1],
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "buffer_image_height_not_aligned",
+                    context: "copy_image_to_buffer",
+                    problem: format!(
+                        "the destination buffer image height ({}) of region {} is not a multiple \
+                        of the required destination buffer alignment ({})",
+                        buffer_image_height, region_index, image_subresource_block_extent[1],
+                    )
+                    .into(),
+                    vuids: &["VUID-VkCopyImageToBufferInfo2-bufferImageHeight-00204"],
+                    ..Default::default()
+                }));
             }
 
             // https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/VkBufferImageCopy.html#_description
@@ -1615,17 +2045,25 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 image_subresource_format.block_size().unwrap()
             };
 
-            // VUID-VkCopyImageToBufferInfo2-pRegions-04725
-            // VUID-VkCopyImageToBufferInfo2-pRegions-04726
             if (buffer_row_length / image_subresource_block_extent[0]) as DeviceSize
                 * image_subresource_block_size
                 > 0x7FFFFFFF
             {
-                return Err(CopyError::BufferRowLengthTooLarge {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    buffer_row_length,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "buffer_row_length_too_large",
+                    context: "copy_image_to_buffer",
+                    problem: format!(
+                        "the destination buffer row length ({}) of region {} specifies a row of \
+                        texels that is greater than 0x7FFFFFFF bytes in size",
+                        buffer_row_length, region_index,
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkCopyImageToBufferInfo2-pRegions-04725",
+                        "VUID-VkCopyImageToBufferInfo2-pRegions-04726",
+                    ],
+                    ..Default::default()
+                }));
             }
 
             let buffer_offset_alignment = if image_aspects.depth || image_aspects.stencil {
@@ -1650,29 +2088,45 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                 buffer_offset_alignment
             };
 
-            // VUID-VkCopyImageToBufferInfo2-bufferOffset-01558
-            // VUID-VkCopyImageToBufferInfo2-bufferOffset-01559
-            // VUID-VkCopyImageToBufferInfo2-bufferOffset-00206
-            // VUID-VkCopyImageToBufferInfo2-srcImage-04053
             if (buffer_inner.offset + buffer_offset) % buffer_offset_alignment != 0 {
-                return Err(CopyError::OffsetNotAlignedForBuffer {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    offset: buffer_inner.offset + buffer_offset,
-                    required_alignment: buffer_offset_alignment,
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "offset_not_aligned_for_buffer",
+                    context: "copy_image_to_buffer",
+                    problem: format!(
+                        "the destination buffer offset ({}) of region {} is not a multiple of the \
+                        required destination buffer alignment ({})",
+                        buffer_inner.offset + buffer_offset,
+                        region_index,
+                        buffer_offset_alignment,
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkCopyImageToBufferInfo2-bufferOffset-01558",
+                        "VUID-VkCopyImageToBufferInfo2-bufferOffset-01559",
+                        "VUID-VkCopyImageToBufferInfo2-bufferOffset-00206",
+                        "VUID-VkCopyImageToBufferInfo2-srcImage-04053",
+                    ],
+                    ..Default::default()
+                }));
             }
 
             let buffer_copy_size = region.buffer_copy_size(image_subresource_format);
 
-            // VUID-VkCopyImageToBufferInfo2-pRegions-00183
             if buffer_offset + buffer_copy_size > buffer.size() {
-                return Err(CopyError::RegionOutOfBufferBounds {
-                    resource: CopyErrorResource::Destination,
-                    region_index,
-                    offset_range_end: buffer_offset + buffer_copy_size,
-                    buffer_size: buffer.size(),
-                });
+                return Err(CopyError::ValidationError(ValidationError {
+                    code: "region_out_of_buffer_bounds",
+                    context: "copy_image_to_buffer",
+                    problem: format!(
+                        "the end of the range of accessed destination byte offsets ({}) of region \
+                        {} is greater than the size of the destination buffer ({})",
+                        buffer_offset + buffer_copy_size,
+                        region_index,
+                        buffer.size(),
+                    )
+                    .into(),
+                    vuids: &["VUID-VkCopyImageToBufferInfo2-pRegions-00183"],
+                    ..Default::default()
+                }));
             }
         }
 
@@ -1744,43 +2198,68 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         // VUID-vkCmdFillBuffer-size-00026
         assert!(size != 0);
 
-        // VUID-vkCmdFillBuffer-dstBuffer-00029
         if !dst_buffer.usage().transfer_dst {
-            return Err(CopyError::MissingUsage {
-                resource: CopyErrorResource::Destination,
-                usage: "transfer_dst",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_usage",
+                context: "fill_buffer",
+                problem: "the destination resource did not have the required usage transfer_dst \
+                    enabled"
+                    .into(),
+                vuids: &["VUID-vkCmdFillBuffer-dstBuffer-00029"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-vkCmdFillBuffer-dstOffset-00024
-        // VUID-vkCmdFillBuffer-size-00027
         if dst_offset + size > dst_buffer.size() {
-            return Err(CopyError::RegionOutOfBufferBounds {
-                resource: CopyErrorResource::Destination,
-                region_index: 0,
-                offset_range_end: dst_offset + size,
-                buffer_size: dst_buffer.size(),
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "region_out_of_buffer_bounds",
+                context: "fill_buffer",
+                problem: format!(
+                    "the end of the range of accessed destination byte offsets ({}) of region {} \
+                    is greater than the size of the destination buffer ({})",
+                    dst_offset + size,
+                    0,
+                    dst_buffer.size(),
+                )
+                .into(),
+                vuids: &[
+                    "VUID-vkCmdFillBuffer-dstOffset-00024",
+                    "VUID-vkCmdFillBuffer-size-00027",
+                ],
+                ..Default::default()
+            }));
         }
 
-        // VUID-vkCmdFillBuffer-dstOffset-00025
         if (dst_buffer_inner.offset + dst_offset) % 4 != 0 {
-            return Err(CopyError::OffsetNotAlignedForBuffer {
-                resource: CopyErrorResource::Destination,
-                region_index: 0,
-                offset: dst_buffer_inner.offset + dst_offset,
-                required_alignment: 4,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "offset_not_aligned_for_buffer",
+                context: "fill_buffer",
+                problem: format!(
+                    "the destination buffer offset ({}) of region {} is not a multiple of the \
+                    required destination buffer alignment ({})",
+                    dst_buffer_inner.offset + dst_offset,
+                    0,
+                    4,
+                )
+                .into(),
+                vuids: &["VUID-vkCmdFillBuffer-dstOffset-00025"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-vkCmdFillBuffer-size-00028
         if size % 4 != 0 {
-            return Err(CopyError::SizeNotAlignedForBuffer {
-                resource: CopyErrorResource::Destination,
-                region_index: 0,
-                size,
-                required_alignment: 4,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "size_not_aligned_for_buffer",
+                context: "fill_buffer",
+                problem: format!(
+                    "the destination buffer size ({}) of region {} is not a multiple of the \
+                    required destination buffer alignment ({})",
+                    size, 0, 4,
+                )
+                .into(),
+                vuids: &["VUID-vkCmdFillBuffer-size-00028"],
+                ..Default::default()
+            }));
         }
 
         Ok(())
@@ -1844,51 +2323,85 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         // VUID-vkCmdUpdateBuffer-dataSize-arraylength
         assert!(size_of_val(data) != 0);
 
-        // VUID-vkCmdUpdateBuffer-dstBuffer-00034
         if !dst_buffer.usage().transfer_dst {
-            return Err(CopyError::MissingUsage {
-                resource: CopyErrorResource::Destination,
-                usage: "transfer_dst",
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "missing_usage",
+                context: "update_buffer",
+                problem: "the destination resource did not have the required usage transfer_dst \
+                    enabled"
+                    .into(),
+                vuids: &["VUID-vkCmdUpdateBuffer-dstBuffer-00034"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-vkCmdUpdateBuffer-dstOffset-00032
-        // VUID-vkCmdUpdateBuffer-dataSize-00033
         if dst_offset + size_of_val(data) as DeviceSize > dst_buffer.size() {
-            return Err(CopyError::RegionOutOfBufferBounds {
-                resource: CopyErrorResource::Destination,
-                region_index: 0,
-                offset_range_end: dst_offset + size_of_val(data) as DeviceSize,
-                buffer_size: dst_buffer.size(),
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "region_out_of_buffer_bounds",
+                context: "update_buffer",
+                problem: format!(
+                    "the end of the range of accessed destination byte offsets ({}) of region {} \
+                    is greater than the size of the destination buffer ({})",
+                    dst_offset + size_of_val(data) as DeviceSize,
+                    0,
+                    dst_buffer.size(),
+                )
+                .into(),
+                vuids: &[
+                    "VUID-vkCmdUpdateBuffer-dstOffset-00032",
+                    "VUID-vkCmdUpdateBuffer-dataSize-00033",
+                ],
+                ..Default::default()
+            }));
         }
 
-        // VUID-vkCmdUpdateBuffer-dstOffset-00036
         if (dst_buffer_inner.offset + dst_offset) % 4 != 0 {
-            return Err(CopyError::OffsetNotAlignedForBuffer {
-                resource: CopyErrorResource::Destination,
-                region_index: 0,
-                offset: dst_buffer_inner.offset + dst_offset,
-                required_alignment: 4,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "offset_not_aligned_for_buffer",
+                context: "update_buffer",
+                problem: format!(
+                    "the destination buffer offset ({}) of region {} is not a multiple of the \
+                    required destination buffer alignment ({})",
+                    dst_buffer_inner.offset + dst_offset,
+                    0,
+                    4,
+                )
+                .into(),
+                vuids: &["VUID-vkCmdUpdateBuffer-dstOffset-00036"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-vkCmdUpdateBuffer-dataSize-00037
         if size_of_val(data) > 65536 {
-            return Err(CopyError::DataTooLarge {
-                size: size_of_val(data) as DeviceSize,
-                max: 65536,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "data_too_large",
+                context: "update_buffer",
+                problem: format!(
+                    "the provided data has a size ({}) greater than the maximum allowed ({})",
+                    size_of_val(data) as DeviceSize,
+                    65536,
+                )
+                .into(),
+                vuids: &["VUID-vkCmdUpdateBuffer-dataSize-00037"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-vkCmdUpdateBuffer-dataSize-00038
         if size_of_val(data) % 4 != 0 {
-            return Err(CopyError::SizeNotAlignedForBuffer {
-                resource: CopyErrorResource::Destination,
-                region_index: 0,
-                size: size_of_val(data) as DeviceSize,
-                required_alignment: 4,
-            });
+            return Err(CopyError::ValidationError(ValidationError {
+                code: "size_not_aligned_for_buffer",
+                context: "update_buffer",
+                problem: format!(
+                    "the destination buffer size ({}) of region {} is not a multiple of the \
+                    required destination buffer alignment ({})",
+                    size_of_val(data) as DeviceSize,
+                    0,
+                    4,
+                )
+                .into(),
+                vuids: &["VUID-vkCmdUpdateBuffer-dataSize-00038"],
+                ..Default::default()
+            }));
         }
 
         Ok(())
@@ -1914,6 +2427,64 @@ fn required_size_for_format(format: Format, extent: [u32; 3], layer_count: u32)
     num_blocks * block_size
 }
 
+/// Returns the alignment of the offsets and extents of image regions in copy commands, for an
+/// image with the given format on a queue family with the given
+/// `min_image_transfer_granularity`.
+fn image_transfer_granularity(
+    min_image_transfer_granularity: [u32; 3],
+    format: Format,
+    is_multi_plane: bool,
+) -> [u32; 3] {
+    if is_multi_plane {
+        // Assume planes always have 1x1 blocks
+        min_image_transfer_granularity
+    } else {
+        // "The value returned in minImageTransferGranularity has a unit of
+        // compressed texel blocks for images having a block-compressed format, and
+        // a unit of texels otherwise."
+        let block_extent = format.block_extent();
+        [
+            min_image_transfer_granularity[0] * block_extent[0],
+            min_image_transfer_granularity[1] * block_extent[1],
+            min_image_transfer_granularity[2] * block_extent[2],
+        ]
+    }
+}
+
+/// Grows the region starting at `offset` with size `extent` outwards to multiples of `alignment`,
+/// and clamps it to `subresource_extent`. If `alignment` is `None`, the whole subresource is
+/// returned.
+fn fit_image_region(
+    offset: [u32; 3],
+    extent: [u32; 3],
+    subresource_extent: [u32; 3],
+    alignment: Option<[u32; 3]>,
+) -> ([u32; 3], [u32; 3]) {
+    let alignment = match alignment {
+        Some(alignment) => alignment,
+        None => return ([0; 3], subresource_extent),
+    };
+
+    let mut fitted_offset = [0; 3];
+    let mut fitted_extent = [0; 3];
+
+    for i in 0..3 {
+        assert!(extent[i] != 0);
+        assert!(offset[i] < subresource_extent[i]);
+
+        let start = offset[i] / alignment[i] * alignment[i];
+        let end = min(
+            (offset[i] + extent[i] + alignment[i] - 1) / alignment[i] * alignment[i],
+            subresource_extent[i],
+        );
+
+        fitted_offset[i] = start;
+        fitted_extent[i] = end - start;
+    }
+
+    (fitted_offset, fitted_extent)
+}
+
 impl SyncCommandBufferBuilder {
     /// Calls `vkCmdCopyBuffer` on the builder.
     ///
@@ -3253,6 +3824,9 @@ pub struct ImageCopy {
 
     /// The extent of texels to copy.
     ///
+    /// When copying between a block-compressed and an uncompressed format, this is measured in
+    /// texels of `src_image`. The extent in `dst_image` covers the same number of texel blocks.
+    ///
     /// The default value is `[0; 3]`, which must be overridden.
     pub extent: [u32; 3],
 
@@ -3401,14 +3975,16 @@ pub struct BufferImageCopy {
 
     /// The number of texels between successive rows of image data in the buffer.
     ///
-    /// If set to `0`, the width of the image is used.
+    /// If set to `0`, the width of the image is used. For block-compressed formats, this is still
+    /// measured in texels, and must be a multiple of the width of a texel block.
     ///
     /// The default value is `0`.
     pub buffer_row_length: u32,
 
     /// The number of rows between successive depth slices of image data in the buffer.
     ///
-    /// If set to `0`, the height of the image is used.
+    /// If set to `0`, the height of the image is used. For block-compressed formats, this is
+    /// still measured in texels, and must be a multiple of the height of a texel block.
     ///
     /// The default value is `0`.
     pub buffer_image_height: u32,
@@ -3451,6 +4027,71 @@ impl Default for BufferImageCopy {
 }
 
 impl BufferImageCopy {
+    /// Returns a `BufferImageCopy` that covers at least the region of `image_subresource` of
+    /// `image` starting at `image_offset` with size `image_extent`, adjusted so that it is valid
+    /// for copy commands recorded for `queue_family`.
+    ///
+    /// The region is grown outwards to the nearest multiples of the required image alignment,
+    /// which depends on the block extent of the format and on the
+    /// [`min_image_transfer_granularity`](QueueFamily::min_image_transfer_granularity) of the
+    /// queue family, and is clamped to the extent of the subresource. If the queue family can
+    /// only copy whole subresources, the whole subresource is selected. The buffer offset and
+    /// layout are left at their default values, so the returned `image_offset` and
+    /// `image_extent` must be used to address the data in the buffer.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `image_subresource` does not select exactly one aspect, or selects a mip level
+    ///   that `image` does not have.
+    /// - Panics if `image_extent` is zero in any dimension, or if `image_offset` is outside the
+    ///   subresource.
+    pub fn fitted(
+        queue_family: QueueFamily,
+        image: &dyn ImageAccess,
+        image_subresource: ImageSubresourceLayers,
+        image_offset: [u32; 3],
+        image_extent: [u32; 3],
+    ) -> Self {
+        assert!(image_subresource.aspects.iter().count() == 1);
+
+        let format = image.format();
+        let image_aspects = format.aspects();
+        let subresource_extent =
+            if image_subresource.aspects.plane1 || image_subresource.aspects.plane2 {
+                format
+                    .ycbcr_chroma_sampling()
+                    .unwrap()
+                    .subsampled_extent(image.dimensions().width_height_depth())
+            } else if image_subresource.aspects.plane0 {
+                image.dimensions().width_height_depth()
+            } else {
+                image
+                    .dimensions()
+                    .mip_level_dimensions(image_subresource.mip_level)
+                    .unwrap()
+                    .width_height_depth()
+            };
+
+        let alignment = match queue_family.min_image_transfer_granularity() {
+            [0, 0, 0] => None,
+            min_image_transfer_granularity => Some(image_transfer_granularity(
+                min_image_transfer_granularity,
+                format,
+                image_aspects.plane0,
+            )),
+        };
+
+        let (image_offset, image_extent) =
+            fit_image_region(image_offset, image_extent, subresource_extent, alignment);
+
+        Self {
+            image_subresource,
+            image_offset,
+            image_extent,
+            ..Default::default()
+        }
+    }
+
     // Following
     // https://www.khronos.org/registry/vulkan/specs/1.3-extensions/html/chap20.html#copies-buffers-images-addressing
     pub(crate) fn buffer_copy_size(&self, format: Format) -> DeviceSize {
@@ -3603,4 +4244,58 @@ mod tests {
             29584
         );
     }
+
+    #[test]
+    fn fit_image_region_aligned() {
+        // Grown outwards to the alignment.
+        assert_eq!(
+            fit_image_region([5, 9, 0], [6, 2, 1], [64, 64, 1], Some([4, 4, 1])),
+            ([4, 8, 0], [8, 4, 1])
+        );
+
+        // Clamped to the subresource, even if that is not aligned.
+        assert_eq!(
+            fit_image_region([60, 0, 0], [3, 1, 1], [62, 64, 1], Some([8, 8, 1])),
+            ([56, 0, 0], [6, 8, 1])
+        );
+
+        // Only whole subresources can be copied.
+        assert_eq!(
+            fit_image_region([5, 9, 0], [6, 2, 1], [64, 64, 1], None),
+            ([0, 0, 0], [64, 64, 1])
+        );
+    }
+
+    #[test]
+    fn buffer_copy_size_compressed() {
+        let region = BufferImageCopy {
+            image_subresource: ImageSubresourceLayers {
+                aspects: ImageAspects {
+                    color: true,
+                    ..ImageAspects::none()
+                },
+                mip_level: 0,
+                array_layers: 0..1,
+            },
+            image_extent: [6, 6, 1],
+            ..Default::default()
+        };
+
+        // A partial block at the edge still takes up a whole block.
+        assert_eq!(
+            region.buffer_copy_size(Format::BC1_RGB_UNORM_BLOCK),
+            2 * 2 * 8
+        );
+
+        let region = BufferImageCopy {
+            buffer_row_length: 16,
+            ..region
+        };
+
+        // The row length is measured in texels, and covers 4 blocks per row.
+        assert_eq!(
+            region.buffer_copy_size(Format::BC1_RGB_UNORM_BLOCK),
+            (4 + 2) * 8
+        );
+    }
 }
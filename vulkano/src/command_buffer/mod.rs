@@ -77,7 +77,10 @@
 //! information.
 
 pub use self::commands::{
+    checkpoint::CheckpointError,
+    conditional::{ConditionalRenderingBeginInfo, ConditionalRenderingError},
     debug::DebugUtilsError,
+    device_group::DeviceGroupError,
     image::{
         BlitImageInfo, ClearColorImageInfo, ClearDepthStencilImageInfo, ImageBlit, ImageResolve,
         ResolveImageInfo,
@@ -100,7 +103,7 @@ pub use self::commands::{
         BufferCopy, BufferImageCopy, CopyBufferInfo, CopyBufferInfoTyped, CopyBufferToImageInfo,
         CopyImageInfo, CopyImageToBufferInfo, FillBufferInfo, ImageCopy,
     },
-    CopyError, CopyErrorResource,
+    CopyError,
 };
 pub use self::{
     auto::{
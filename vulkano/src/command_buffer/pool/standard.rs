@@ -44,6 +44,9 @@ pub struct StandardCommandPool {
     // Identifier of the queue family.
     queue_family: u32,
 
+    // Whether the command buffers are protected command buffers.
+    protected: bool,
+
     // For each thread, we store thread-specific info.
     per_thread: Mutex<HashMap<thread::ThreadId, Weak<StandardCommandPoolPerThread>>>,
 }
@@ -77,9 +80,36 @@ impl StandardCommandPool {
         StandardCommandPool {
             device: device,
             queue_family: queue_family.id(),
+            protected: false,
             per_thread: Mutex::new(Default::default()),
         }
     }
+
+    /// Builds a new pool of protected command buffers, which can access protected resources and
+    /// must be submitted to a [protected-capable](crate::device::Queue::is_protected) queue.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the device and the queue family don't belong to the same physical device.
+    /// - Panics if the [`protected_memory`](crate::device::Features::protected_memory) feature is
+    ///   not enabled on the device.
+    ///
+    pub fn new_protected(device: Arc<Device>, queue_family: QueueFamily) -> StandardCommandPool {
+        // VUID-VkCommandPoolCreateInfo-flags-02860
+        assert!(device.enabled_features().protected_memory);
+
+        StandardCommandPool {
+            protected: true,
+            ..StandardCommandPool::new(device, queue_family)
+        }
+    }
+
+    /// Returns whether the command buffers allocated from this pool are protected command
+    /// buffers.
+    #[inline]
+    pub fn protected(&self) -> bool {
+        self.protected
+    }
 }
 
 unsafe impl CommandPool for Arc<StandardCommandPool> {
@@ -108,6 +138,7 @@ unsafe impl CommandPool for Arc<StandardCommandPool> {
                 UnsafeCommandPoolCreateInfo {
                     queue_family_index: self.queue_family().id(),
                     reset_command_buffer: true,
+                    protected: self.protected,
                     ..Default::default()
                 },
             )
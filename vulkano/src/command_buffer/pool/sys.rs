@@ -41,6 +41,7 @@ pub struct UnsafeCommandPool {
     queue_family_index: u32,
     transient: bool,
     reset_command_buffer: bool,
+    protected: bool,
 }
 
 unsafe impl Send for UnsafeCommandPool {}
@@ -58,6 +59,7 @@ impl UnsafeCommandPool {
             queue_family_index,
             transient,
             reset_command_buffer,
+            protected,
             _ne: _,
         } = create_info;
 
@@ -69,6 +71,7 @@ impl UnsafeCommandPool {
             queue_family_index,
             transient,
             reset_command_buffer,
+            protected,
         })
     }
 
@@ -80,6 +83,7 @@ impl UnsafeCommandPool {
             queue_family_index,
             transient,
             reset_command_buffer,
+            protected,
             _ne: _,
         } = create_info;
 
@@ -95,6 +99,14 @@ impl UnsafeCommandPool {
             });
         }
 
+        // VUID-VkCommandPoolCreateInfo-flags-02860
+        if protected && !device.enabled_features().protected_memory {
+            return Err(UnsafeCommandPoolCreationError::FeatureNotEnabled {
+                feature: "protected_memory",
+                reason: "protected was set",
+            });
+        }
+
         Ok(())
     }
 
@@ -106,6 +118,7 @@ impl UnsafeCommandPool {
             queue_family_index,
             transient,
             reset_command_buffer,
+            protected,
             _ne: _,
         } = create_info;
 
@@ -119,6 +132,10 @@ impl UnsafeCommandPool {
             flags |= ash::vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER;
         }
 
+        if protected {
+            flags |= ash::vk::CommandPoolCreateFlags::PROTECTED;
+        }
+
         let create_info = ash::vk::CommandPoolCreateInfo {
             flags,
             queue_family_index,
@@ -137,6 +154,8 @@ impl UnsafeCommandPool {
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         Ok(handle)
     }
 
@@ -199,15 +218,20 @@ impl UnsafeCommandPool {
             }
         };
 
+        for &handle in &out {
+            self.device.set_automatic_debug_name(handle);
+        }
+
         let device = self.device.clone();
+        let protected = self.protected;
 
         Ok(out
             .into_iter()
             .map(move |command_buffer| UnsafeCommandPoolAlloc {
                 handle: command_buffer,
                 device: device.clone(),
-
                 level,
+                protected,
             }))
     }
 
@@ -278,6 +302,13 @@ impl UnsafeCommandPool {
             .queue_family_by_id(self.queue_family_index)
             .unwrap()
     }
+
+    /// Returns whether the command buffers allocated from this pool are protected command
+    /// buffers.
+    #[inline]
+    pub fn protected(&self) -> bool {
+        self.protected
+    }
 }
 
 impl Drop for UnsafeCommandPool {
@@ -333,6 +364,11 @@ pub enum UnsafeCommandPoolCreationError {
     /// Not enough memory.
     OomError(OomError),
 
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+
     /// The provided `queue_family_index` was not less than the number of queue families in the
     /// physical device.
     QueueFamilyIndexOutOfRange {
@@ -356,6 +392,9 @@ impl fmt::Display for UnsafeCommandPoolCreationError {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match *self {
             Self::OomError(_) => write!(fmt, "not enough memory",),
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
             Self::QueueFamilyIndexOutOfRange {
                 queue_family_index,
                 queue_family_count,
@@ -398,6 +437,15 @@ pub struct UnsafeCommandPoolCreateInfo {
     /// The default value is `false`.
     pub reset_command_buffer: bool,
 
+    /// Whether the command buffers allocated from this pool are protected command buffers, which
+    /// can access protected resources and must be submitted as a protected submission.
+    ///
+    /// If set to `true`, the [`protected_memory`](crate::device::Features::protected_memory)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `false`.
+    pub protected: bool,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -408,6 +456,7 @@ impl Default for UnsafeCommandPoolCreateInfo {
             queue_family_index: u32::MAX,
             transient: false,
             reset_command_buffer: false,
+            protected: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -446,6 +495,7 @@ pub struct UnsafeCommandPoolAlloc {
     handle: ash::vk::CommandBuffer,
     device: Arc<Device>,
     level: CommandBufferLevel,
+    protected: bool,
 }
 
 impl UnsafeCommandPoolAlloc {
@@ -454,6 +504,13 @@ impl UnsafeCommandPoolAlloc {
     pub fn level(&self) -> CommandBufferLevel {
         self.level
     }
+
+    /// Returns whether the command buffer is a protected command buffer, which was allocated
+    /// from a protected command pool.
+    #[inline]
+    pub fn protected(&self) -> bool {
+        self.protected
+    }
 }
 
 unsafe impl VulkanObject for UnsafeCommandPoolAlloc {
@@ -573,6 +630,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn protected_requires_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        match UnsafeCommandPool::new(
+            device,
+            UnsafeCommandPoolCreateInfo {
+                queue_family_index: queue.family().id(),
+                protected: true,
+                ..Default::default()
+            },
+        ) {
+            Err(UnsafeCommandPoolCreationError::FeatureNotEnabled {
+                feature: "protected_memory",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn check_maintenance_when_trim() {
         let (device, queue) = gfx_dev_and_queue!();
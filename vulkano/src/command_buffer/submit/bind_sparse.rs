@@ -8,7 +8,6 @@
 // according to those terms.
 
 use crate::buffer::sys::UnsafeBuffer;
-use crate::check_errors;
 use crate::device::Queue;
 use crate::image::sys::UnsafeImage;
 use crate::memory::DeviceMemory;
@@ -137,10 +136,18 @@ impl<'a> SubmitBindSparseBuilder<'a> {
 
     /// Submits the command. Calls `vkQueueBindSparse`.
     pub fn submit(self, queue: &Queue) -> Result<(), SubmitBindSparseError> {
+        trace_span!("queue_bind_sparse", batches = self.infos.len());
+
         unsafe {
             debug_assert!(queue.family().supports_sparse_binding());
 
-            let fns = queue.device().fns();
+            let device = queue.device();
+
+            if device.is_lost() {
+                return Err(SubmitBindSparseError::DeviceLost);
+            }
+
+            let fns = device.fns();
             let queue = queue.internal_object_guard();
 
             // We start by storing all the `VkSparseBufferMemoryBindInfo`s of the whole command
@@ -239,7 +246,7 @@ impl<'a> SubmitBindSparseBuilder<'a> {
             };
 
             // Finally executing the command.
-            check_errors((fns.v1_0.queue_bind_sparse)(
+            device.check_errors((fns.v1_0.queue_bind_sparse)(
                 *queue,
                 bs_infos.len() as u32,
                 bs_infos.as_ptr(),
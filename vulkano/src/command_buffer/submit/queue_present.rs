@@ -11,15 +11,19 @@ use smallvec::SmallVec;
 use std::error;
 use std::fmt;
 use std::marker::PhantomData;
+use std::num::NonZeroU64;
 use std::ptr;
 
 use crate::device::DeviceOwned;
 use crate::device::Queue;
+use crate::swapchain::DeviceGroupPresentMode;
+use crate::swapchain::PresentMode;
 use crate::swapchain::PresentRegion;
+use crate::swapchain::PresentTime;
 use crate::swapchain::Swapchain;
+use crate::sync::Fence;
 use crate::sync::Semaphore;
 
-use crate::check_errors;
 use crate::Error;
 use crate::OomError;
 use crate::SynchronizedVulkanObject;
@@ -31,6 +35,14 @@ pub struct SubmitPresentBuilder<'a> {
     wait_semaphores: SmallVec<[ash::vk::Semaphore; 8]>,
     swapchains: SmallVec<[ash::vk::SwapchainKHR; 4]>,
     image_indices: SmallVec<[u32; 4]>,
+    present_ids: SmallVec<[u64; 4]>,
+    present_times: SmallVec<[ash::vk::PresentTimeGOOGLE; 4]>,
+    present_modes: SmallVec<[ash::vk::PresentModeKHR; 4]>,
+    // True if a present mode was provided for any of the swapchains.
+    present_modes_provided: bool,
+    device_masks: SmallVec<[u32; 4]>,
+    device_group_present_mode: Option<DeviceGroupPresentMode>,
+    fences: SmallVec<[ash::vk::Fence; 4]>,
     present_regions: SmallVec<[ash::vk::PresentRegionKHR; 4]>,
     rect_layers: SmallVec<[ash::vk::RectLayerKHR; 4]>,
     marker: PhantomData<&'a ()>,
@@ -44,6 +56,13 @@ impl<'a> SubmitPresentBuilder<'a> {
             wait_semaphores: SmallVec::new(),
             swapchains: SmallVec::new(),
             image_indices: SmallVec::new(),
+            present_ids: SmallVec::new(),
+            present_times: SmallVec::new(),
+            present_modes: SmallVec::new(),
+            present_modes_provided: false,
+            device_masks: SmallVec::new(),
+            device_group_present_mode: None,
+            fences: SmallVec::new(),
             present_regions: SmallVec::new(),
             rect_layers: SmallVec::new(),
             marker: PhantomData,
@@ -76,10 +95,23 @@ impl<'a> SubmitPresentBuilder<'a> {
     ///
     /// Allows to specify a present region.
     /// Areas outside the present region *can* be ignored by the Vulkan implementation for
-    /// optimizations purposes.
+    /// optimizations purposes. The parts of the rectangles of the region that are outside of the
+    /// images of the swapchain are removed.
     ///
     /// If `VK_KHR_incremental_present` is not enabled, the `present_region` parameter is ignored.
     ///
+    /// Also allows to specify a present ID, that can be waited for with
+    /// [`Swapchain::wait_for_present`](crate::swapchain::Swapchain::wait_for_present).
+    ///
+    /// If `VK_GOOGLE_display_timing` is not enabled, the `present_time` parameter is ignored.
+    ///
+    /// Also allows to specify the present mode to use for this present, instead of the present
+    /// mode of the previous present.
+    ///
+    /// Also allows to specify the device mask of the physical devices that present the image,
+    /// and the device group present mode, for a device that was created from several physical
+    /// devices.
+    ///
     /// # Safety
     ///
     /// - If you submit this builder, the swapchain must be kept alive until you are
@@ -87,12 +119,29 @@ impl<'a> SubmitPresentBuilder<'a> {
     ///
     /// - The swapchains and semaphores must all belong to the same device.
     ///
+    /// - If `present_id` is `Some`, the [`present_id`](crate::device::Features::present_id)
+    ///   feature must be enabled on the device, and the ID must be greater than the IDs that were
+    ///   previously provided for the swapchain.
+    ///
+    /// - If `present_mode` is `Some`, the mode must be one of the
+    ///   [`present_modes`](crate::swapchain::Swapchain::present_modes) of the swapchain.
+    ///
+    /// - If `device_group_present` is `Some`, it must be `Some` with the same mode for all the
+    ///   swapchains of the builder, the device mask and the mode must be valid for the device, and
+    ///   the mode must be one of the
+    ///   [`device_group_present_modes`](crate::swapchain::Swapchain::device_group_present_modes)
+    ///   of the swapchain.
+    ///
     #[inline]
     pub unsafe fn add_swapchain<W>(
         &mut self,
         swapchain: &'a Swapchain<W>,
         image_num: u32,
         present_region: Option<&'a PresentRegion>,
+        present_id: Option<NonZeroU64>,
+        present_time: Option<PresentTime>,
+        present_mode: Option<PresentMode>,
+        device_group_present: Option<(u32, DeviceGroupPresentMode)>,
     ) {
         debug_assert!(image_num < swapchain.image_count());
 
@@ -103,12 +152,22 @@ impl<'a> SubmitPresentBuilder<'a> {
         {
             let vk_present_region = match present_region {
                 Some(present_region) => {
-                    assert!(present_region.is_compatible_with(swapchain));
-                    for rectangle in &present_region.rectangles {
-                        self.rect_layers.push(rectangle.into());
-                    }
+                    let rect_layers_len = self.rect_layers.len();
+                    self.rect_layers.extend(
+                        present_region
+                            .rectangles
+                            .iter()
+                            .filter_map(|rectangle| {
+                                rectangle.clipped_to(
+                                    swapchain.image_extent(),
+                                    swapchain.image_array_layers(),
+                                )
+                            })
+                            .map(|rectangle| (&rectangle).into()),
+                    );
+                    // If no rectangle is left, the count is zero and the whole image is presented.
                     ash::vk::PresentRegionKHR {
-                        rectangle_count: present_region.rectangles.len() as u32,
+                        rectangle_count: (self.rect_layers.len() - rect_layers_len) as u32,
                         // Set this to null for now; in submit fill it with self.rect_layers
                         p_rectangles: ptr::null(),
                     }
@@ -123,6 +182,60 @@ impl<'a> SubmitPresentBuilder<'a> {
 
         self.swapchains.push(swapchain.internal_object());
         self.image_indices.push(image_num);
+        self.present_ids.push(present_id.map_or(0, NonZeroU64::get));
+        self.fences.push(ash::vk::Fence::null());
+
+        // If another swapchain of the same present switches its present mode, this swapchain
+        // uses the mode that it was created with.
+        self.present_modes.push(
+            present_mode
+                .unwrap_or_else(|| swapchain.present_mode())
+                .into(),
+        );
+        self.present_modes_provided |= present_mode.is_some();
+
+        if let Some((device_mask, mode)) = device_group_present {
+            debug_assert!(self
+                .device_group_present_mode
+                .map_or(self.device_masks.is_empty(), |m| m == mode));
+            self.device_masks.push(device_mask);
+            self.device_group_present_mode = Some(mode);
+        }
+
+        if swapchain
+            .device()
+            .enabled_extensions()
+            .google_display_timing
+        {
+            self.present_times
+                .push(present_time.unwrap_or_default().into());
+        }
+    }
+
+    /// Sets a fence that is signaled when the presentation engine no longer uses the resources
+    /// of the present of the swapchain that was added last, such as the semaphores that the
+    /// present waits on.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if no swapchain image has been added to the builder.
+    ///
+    /// # Safety
+    ///
+    /// - If you submit this builder, the fence must be kept alive until it is signaled.
+    ///
+    /// - The fence must be unsignaled, and must not be associated with any other operation that
+    ///   has not completed yet.
+    ///
+    /// - The [`swapchain_maintenance1`](crate::device::Features::swapchain_maintenance1) feature
+    ///   must be enabled on the device.
+    ///
+    #[inline]
+    pub unsafe fn set_fence(&mut self, fence: &'a Fence) {
+        *self
+            .fences
+            .last_mut()
+            .expect("Tried to set a present fence without any swapchain") = fence.internal_object();
     }
 
     /// Submits the command. Calls `vkQueuePresentKHR`.
@@ -132,6 +245,8 @@ impl<'a> SubmitPresentBuilder<'a> {
     /// Panics if no swapchain image has been added to the builder.
     ///
     pub fn submit(mut self, queue: &Queue) -> Result<(), SubmitPresentError> {
+        trace_span!("queue_present", swapchains = self.swapchains.len());
+
         unsafe {
             debug_assert_eq!(self.swapchains.len(), self.image_indices.len());
             assert!(
@@ -139,7 +254,25 @@ impl<'a> SubmitPresentBuilder<'a> {
                 "Tried to submit a present command without any swapchain"
             );
 
-            let present_regions = {
+            let device = queue.device();
+
+            if device.is_lost() {
+                return Err(SubmitPresentError::DeviceLost);
+            }
+
+            // A present ID of 0 means that the image has no ID.
+            let present_ids = if self.present_ids.iter().any(|&id| id != 0) {
+                debug_assert!(queue.device().enabled_features().present_id);
+                Some(ash::vk::PresentIdKHR {
+                    swapchain_count: self.present_ids.len() as u32,
+                    p_present_ids: self.present_ids.as_ptr(),
+                    ..Default::default()
+                })
+            } else {
+                None
+            };
+
+            let mut present_regions = {
                 if !self.present_regions.is_empty() {
                     debug_assert!(queue.device().enabled_extensions().khr_incremental_present);
                     debug_assert_eq!(self.swapchains.len(), self.present_regions.len());
@@ -158,16 +291,96 @@ impl<'a> SubmitPresentBuilder<'a> {
                 }
             };
 
+            // A desired present time of 0 means that the image has no timing constraint.
+            let mut present_times = if !self.present_times.is_empty() {
+                debug_assert!(queue.device().enabled_extensions().google_display_timing);
+                debug_assert_eq!(self.swapchains.len(), self.present_times.len());
+                Some(ash::vk::PresentTimesInfoGOOGLE {
+                    swapchain_count: self.present_times.len() as u32,
+                    p_times: self.present_times.as_ptr(),
+                    ..Default::default()
+                })
+            } else {
+                None
+            };
+
+            let mut present_modes = if self.present_modes_provided {
+                debug_assert!(queue.device().enabled_features().swapchain_maintenance1);
+                Some(ash::vk::SwapchainPresentModeInfoEXT {
+                    swapchain_count: self.present_modes.len() as u32,
+                    p_present_modes: self.present_modes.as_ptr(),
+                    ..Default::default()
+                })
+            } else {
+                None
+            };
+
+            let mut device_group_info = if let Some(mode) = self.device_group_present_mode {
+                debug_assert_eq!(self.swapchains.len(), self.device_masks.len());
+                Some(ash::vk::DeviceGroupPresentInfoKHR {
+                    swapchain_count: self.device_masks.len() as u32,
+                    p_device_masks: self.device_masks.as_ptr(),
+                    mode: mode.into(),
+                    ..Default::default()
+                })
+            } else {
+                None
+            };
+
+            // A null fence means that no fence is signaled for the swapchain.
+            let mut fences = if self
+                .fences
+                .iter()
+                .any(|&fence| fence != ash::vk::Fence::null())
+            {
+                debug_assert!(queue.device().enabled_features().swapchain_maintenance1);
+                Some(ash::vk::SwapchainPresentFenceInfoEXT {
+                    swapchain_count: self.fences.len() as u32,
+                    p_fences: self.fences.as_ptr(),
+                    ..Default::default()
+                })
+            } else {
+                None
+            };
+
+            let mut p_next: *const _ = ptr::null();
+
+            if let Some(present_ids) = &present_ids {
+                p_next = present_ids as *const ash::vk::PresentIdKHR as *const _;
+            }
+
+            if let Some(present_times) = &mut present_times {
+                present_times.p_next = p_next;
+                p_next = present_times as *const ash::vk::PresentTimesInfoGOOGLE as *const _;
+            }
+
+            if let Some(present_regions) = &mut present_regions {
+                present_regions.p_next = p_next;
+                p_next = present_regions as *const ash::vk::PresentRegionsKHR as *const _;
+            }
+
+            if let Some(present_modes) = &mut present_modes {
+                present_modes.p_next = p_next;
+                p_next = present_modes as *const ash::vk::SwapchainPresentModeInfoEXT as *const _;
+            }
+
+            if let Some(device_group_info) = &mut device_group_info {
+                device_group_info.p_next = p_next;
+                p_next = device_group_info as *const ash::vk::DeviceGroupPresentInfoKHR as *const _;
+            }
+
+            if let Some(fences) = &mut fences {
+                fences.p_next = p_next;
+                p_next = fences as *const ash::vk::SwapchainPresentFenceInfoEXT as *const _;
+            }
+
             let mut results = vec![ash::vk::Result::SUCCESS; self.swapchains.len()];
 
-            let fns = queue.device().fns();
+            let fns = device.fns();
             let queue = queue.internal_object_guard();
 
             let infos = ash::vk::PresentInfoKHR {
-                p_next: present_regions
-                    .as_ref()
-                    .map(|pr| pr as *const ash::vk::PresentRegionsKHR as *const _)
-                    .unwrap_or(ptr::null()),
+                p_next,
                 wait_semaphore_count: self.wait_semaphores.len() as u32,
                 p_wait_semaphores: self.wait_semaphores.as_ptr(),
                 swapchain_count: self.swapchains.len() as u32,
@@ -177,10 +390,10 @@ impl<'a> SubmitPresentBuilder<'a> {
                 ..Default::default()
             };
 
-            check_errors((fns.khr_swapchain.queue_present_khr)(*queue, &infos))?;
+            device.check_errors((fns.khr_swapchain.queue_present_khr)(*queue, &infos))?;
 
             for result in results {
-                check_errors(result)?;
+                device.check_errors(result)?;
             }
 
             Ok(())
@@ -194,6 +407,7 @@ impl<'a> fmt::Debug for SubmitPresentBuilder<'a> {
             .field("wait_semaphores", &self.wait_semaphores)
             .field("swapchains", &self.swapchains)
             .field("image_indices", &self.image_indices)
+            .field("present_ids", &self.present_ids)
             .finish()
     }
 }
@@ -7,7 +7,6 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use crate::check_errors;
 use crate::command_buffer::sys::UnsafeCommandBuffer;
 use crate::device::Queue;
 use crate::sync::Fence;
@@ -31,6 +30,8 @@ pub struct SubmitCommandBufferBuilder<'a> {
     signal_semaphores: SmallVec<[ash::vk::Semaphore; 16]>,
     command_buffers: SmallVec<[ash::vk::CommandBuffer; 4]>,
     fence: ash::vk::Fence,
+    protected: bool,
+    counter_pass_index: Option<u32>,
     marker: PhantomData<&'a ()>,
 }
 
@@ -44,6 +45,8 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
             signal_semaphores: SmallVec::new(),
             command_buffers: SmallVec::new(),
             fence: ash::vk::Fence::null(),
+            protected: false,
+            counter_pass_index: None,
             marker: PhantomData,
         }
     }
@@ -166,6 +169,51 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
         self.command_buffers.push(command_buffer.internal_object());
     }
 
+    /// Sets whether this is a protected submission.
+    ///
+    /// # Safety
+    ///
+    /// - If `protected` is `true`, the queue that this builder is submitted to must be a
+    ///   [protected-capable](crate::device::Queue::is_protected) queue.
+    ///
+    /// - If `protected` is `true`, all the command buffers must have been allocated from a
+    ///   [protected](crate::command_buffer::pool::UnsafeCommandPool::protected) command pool.
+    ///   Otherwise, none of them must have been.
+    ///
+    #[inline]
+    pub unsafe fn set_protected(&mut self, protected: bool) {
+        self.protected = protected;
+    }
+
+    /// Returns whether this is a protected submission.
+    #[inline]
+    pub fn protected(&self) -> bool {
+        self.protected
+    }
+
+    /// Sets the counter pass index of the performance queries in the command buffers.
+    ///
+    /// # Safety
+    ///
+    /// - If `counter_pass_index` is `Some`, the
+    ///   [`khr_performance_query`](crate::device::DeviceExtensions::khr_performance_query)
+    ///   extension must be enabled on the device.
+    ///
+    /// - If `counter_pass_index` is `Some`, it must be less than the number of
+    ///   [passes](crate::query::QueryPool::performance_query_passes) of every performance query
+    ///   pool that is used by the command buffers.
+    ///
+    #[inline]
+    pub unsafe fn set_counter_pass_index(&mut self, counter_pass_index: Option<u32>) {
+        self.counter_pass_index = counter_pass_index;
+    }
+
+    /// Returns the counter pass index of the performance queries in the command buffers.
+    #[inline]
+    pub fn counter_pass_index(&self) -> Option<u32> {
+        self.counter_pass_index
+    }
+
     /// Returns the number of semaphores to signal.
     ///
     /// In other words, this is the number of times `add_signal_semaphore` has been called.
@@ -197,13 +245,47 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     /// > possible together and avoid submitting them one by one.
     ///
     pub fn submit(self, queue: &Queue) -> Result<(), SubmitCommandBufferError> {
+        trace_span!(
+            "queue_submit",
+            command_buffers = self.command_buffers.len(),
+            wait_semaphores = self.wait_semaphores.len(),
+            signal_semaphores = self.signal_semaphores.len(),
+        );
+
         unsafe {
-            let fns = queue.device().fns();
+            let device = queue.device();
+
+            if device.is_lost() {
+                return Err(SubmitCommandBufferError::DeviceLost);
+            }
+
+            let fns = device.fns();
+
+            // VUID-VkSubmitInfo-pNext-04148
+            debug_assert!(!self.protected || queue.is_protected());
+
+            device.record_submission(
+                queue,
+                &self.command_buffers,
+                self.wait_semaphores.len(),
+                self.signal_semaphores.len(),
+            );
+
             let queue = queue.internal_object_guard();
 
             debug_assert_eq!(self.wait_semaphores.len(), self.destination_stages.len());
 
-            let batch = ash::vk::SubmitInfo {
+            let mut protected_submit_info = ash::vk::ProtectedSubmitInfo {
+                protected_submit: ash::vk::TRUE,
+                ..Default::default()
+            };
+
+            let mut performance_query_submit_info = ash::vk::PerformanceQuerySubmitInfoKHR {
+                counter_pass_index: self.counter_pass_index.unwrap_or(0),
+                ..Default::default()
+            };
+
+            let mut batch = ash::vk::SubmitInfo {
                 wait_semaphore_count: self.wait_semaphores.len() as u32,
                 p_wait_semaphores: self.wait_semaphores.as_ptr(),
                 p_wait_dst_stage_mask: self.destination_stages.as_ptr(),
@@ -214,7 +296,17 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
                 ..Default::default()
             };
 
-            check_errors((fns.v1_0.queue_submit)(*queue, 1, &batch, self.fence))?;
+            if self.protected {
+                protected_submit_info.p_next = batch.p_next;
+                batch.p_next = &protected_submit_info as *const _ as *const _;
+            }
+
+            if self.counter_pass_index.is_some() {
+                performance_query_submit_info.p_next = batch.p_next;
+                batch.p_next = &performance_query_submit_info as *const _ as *const _;
+            }
+
+            device.check_errors((fns.v1_0.queue_submit)(*queue, 1, &batch, self.fence))?;
             Ok(())
         }
     }
@@ -224,12 +316,22 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     /// # Panic
     ///
     /// Panics if both builders have a fence already set.
+    /// Panics if only one of the builders is a protected submission.
+    /// Panics if the builders have different counter pass indices.
     // TODO: create multiple batches instead
     pub fn merge(mut self, other: Self) -> Self {
         assert!(
             self.fence == ash::vk::Fence::null() || other.fence == ash::vk::Fence::null(),
             "Can't merge two queue submits that both have a fence"
         );
+        assert_eq!(
+            self.protected, other.protected,
+            "Can't merge a protected queue submit with an unprotected one"
+        );
+        assert_eq!(
+            self.counter_pass_index, other.counter_pass_index,
+            "Can't merge queue submits with different counter pass indices"
+        );
 
         self.wait_semaphores.extend(other.wait_semaphores);
         self.destination_stages.extend(other.destination_stages); // TODO: meh? will be solved if we submit multiple batches
@@ -24,7 +24,7 @@ use crate::{
     },
     descriptor_set::{DescriptorSetResources, DescriptorSetWithOffsets},
     device::{Device, DeviceOwned},
-    image::{sys::UnsafeImage, ImageAccess, ImageLayout, ImageSubresourceRange},
+    image::{sys::UnsafeImage, ImageAccess, ImageAspects, ImageLayout, ImageSubresourceRange},
     pipeline::{
         graphics::{
             color_blend::LogicOp,
@@ -37,8 +37,8 @@ use crate::{
     },
     range_set::RangeSet,
     sync::{
-        AccessFlags, BufferMemoryBarrier, DependencyInfo, ImageMemoryBarrier, PipelineMemoryAccess,
-        PipelineStages,
+        AccessFlags, BufferMemoryBarrier, DependencyInfo, ImageMemoryBarrier, MemoryBarrier,
+        PipelineMemoryAccess, PipelineStages,
     },
     DeviceSize, OomError, VulkanObject,
 };
@@ -695,6 +695,34 @@ impl SyncCommandBufferBuilder {
         }
     }
 
+    /// Inserts a global memory barrier between all commands that were added so far and all
+    /// commands that will be added afterwards.
+    ///
+    /// Resource states are tracked per buffer and per image, so the builder can't know that two
+    /// different resources are bound to overlapping memory. This allows the user to order
+    /// accesses to such aliased resources manually.
+    ///
+    /// # Safety
+    ///
+    /// - The builder must not be inside a render pass.
+    /// - `barrier` must cover all accesses to the aliased memory that were made by the previous
+    ///   commands and that will be made by the following commands.
+    pub unsafe fn aliasing_barrier(&mut self, barrier: MemoryBarrier) {
+        debug_assert!(self.latest_render_pass_enter.is_none());
+
+        // Flush all pending commands, so that the barrier ends up after them.
+        self.inner.pipeline_barrier(&self.pending_barrier);
+        self.pending_barrier.clear();
+        self.barriers.push(self.first_unflushed); // Track inserted barriers
+
+        for command in &mut self.commands[self.first_unflushed..] {
+            command.send(&mut self.inner);
+        }
+
+        self.first_unflushed = self.commands.len();
+        self.pending_barrier.memory_barriers.push(barrier);
+    }
+
     /// Builds the command buffer and turns it into a `SyncCommandBuffer`.
     #[inline]
     pub fn build(mut self) -> Result<SyncCommandBuffer, OomError> {
@@ -846,7 +874,16 @@ impl fmt::Display for SyncCommandBufferBuilderError {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match self {
-            SyncCommandBufferBuilderError::Conflict { .. } => write!(fmt, "unsolvable conflict"),
+            SyncCommandBufferBuilderError::Conflict {
+                command_param,
+                previous_command_name,
+                previous_command_offset,
+                previous_command_param,
+            } => write!(
+                fmt,
+                "unsolvable conflict: the resource used as `{}` is also used as `{}` by the previous command {} at index {}",
+                command_param, previous_command_param, previous_command_name, previous_command_offset,
+            ),
             SyncCommandBufferBuilderError::ExecError(err) => err.fmt(fmt),
         }
     }
@@ -909,6 +946,7 @@ pub(in crate::command_buffer) struct CurrentState {
     pub(in crate::command_buffer) push_constants: RangeSet<u32>,
     pub(in crate::command_buffer) push_constants_pipeline_layout: Option<Arc<PipelineLayout>>,
 
+    pub(in crate::command_buffer) attachment_feedback_loop_enable: Option<ImageAspects>,
     pub(in crate::command_buffer) blend_constants: Option<[f32; 4]>,
     pub(in crate::command_buffer) color_write_enable: Option<SmallVec<[bool; 4]>>,
     pub(in crate::command_buffer) cull_mode: Option<CullMode>,
@@ -946,6 +984,9 @@ impl CurrentState {
     ) {
         for state in states {
             match state {
+                DynamicState::AttachmentFeedbackLoopEnable => {
+                    self.attachment_feedback_loop_enable = None
+                }
                 DynamicState::BlendConstants => self.blend_constants = None,
                 DynamicState::ColorWriteEnable => self.color_write_enable = None,
                 DynamicState::CullMode => self.cull_mode = None,
@@ -1144,6 +1185,13 @@ impl<'a> CommandBufferState<'a> {
         self.current_state.push_constants_pipeline_layout.as_ref()
     }
 
+    /// Returns the image aspects for which an attachment feedback loop is currently enabled, or
+    /// `None` if nothing has been set yet.
+    #[inline]
+    pub fn attachment_feedback_loop_enable(&self) -> Option<ImageAspects> {
+        self.current_state.attachment_feedback_loop_enable
+    }
+
     /// Returns the current blend constants, or `None` if nothing has been set yet.
     #[inline]
     pub fn blend_constants(&self) -> Option<[f32; 4]> {
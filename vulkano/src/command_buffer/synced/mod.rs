@@ -523,7 +523,7 @@ impl std::fmt::Debug for dyn Command {
 mod tests {
     use super::*;
     use crate::{
-        buffer::{BufferUsage, CpuAccessibleBuffer, ImmutableBuffer},
+        buffer::{Buffer, BufferLocation, BufferUsage, ImmutableBuffer},
         command_buffer::{
             pool::{CommandPool, CommandPoolBuilderAlloc},
             sys::CommandBufferBeginInfo,
@@ -673,8 +673,8 @@ mod tests {
                 },
             )
             .unwrap();
-            let buf =
-                CpuAccessibleBuffer::from_data(device, BufferUsage::all(), false, 0u32).unwrap();
+            let buf = Buffer::from_data(device, BufferUsage::all(), BufferLocation::Upload, 0u32)
+                .unwrap();
             let mut buf_builder = sync.bind_vertex_buffers();
             buf_builder.add(buf);
             buf_builder.submit(1);
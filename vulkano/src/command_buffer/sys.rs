@@ -42,6 +42,7 @@ pub struct UnsafeCommandBufferBuilder {
     pub(super) handle: ash::vk::CommandBuffer,
     pub(super) device: Arc<Device>,
     usage: CommandBufferUsage,
+    protected: bool,
 }
 
 impl UnsafeCommandBufferBuilder {
@@ -188,6 +189,7 @@ impl UnsafeCommandBufferBuilder {
             handle: pool_alloc.internal_object(),
             device,
             usage,
+            protected: pool_alloc.protected(),
         })
     }
 
@@ -202,6 +204,7 @@ impl UnsafeCommandBufferBuilder {
                 command_buffer: self.handle,
                 device: self.device.clone(),
                 usage: self.usage,
+                protected: self.protected,
             })
         }
     }
@@ -262,6 +265,7 @@ pub struct UnsafeCommandBuffer {
     command_buffer: ash::vk::CommandBuffer,
     device: Arc<Device>,
     usage: CommandBufferUsage,
+    protected: bool,
 }
 
 impl UnsafeCommandBuffer {
@@ -269,6 +273,13 @@ impl UnsafeCommandBuffer {
     pub fn usage(&self) -> CommandBufferUsage {
         self.usage
     }
+
+    /// Returns whether this is a protected command buffer, which must be submitted as a
+    /// protected submission.
+    #[inline]
+    pub fn protected(&self) -> bool {
+        self.protected
+    }
 }
 
 unsafe impl DeviceOwned for UnsafeCommandBuffer {
@@ -14,7 +14,7 @@ use super::{
 };
 use crate::{
     buffer::{sys::UnsafeBuffer, BufferAccess},
-    device::{Device, DeviceOwned, Queue},
+    device::{Device, DeviceOwned, Queue, WaitIdleError},
     image::{sys::UnsafeImage, ImageAccess, ImageLayout, ImageSubresourceRange},
     sync::{
         now, AccessCheckError, AccessError, AccessFlags, FlushError, GpuFuture, NowFuture,
@@ -105,7 +105,11 @@ pub unsafe trait PrimaryCommandBuffer: DeviceOwned + Send + Sync {
     ///
     /// # Panic
     ///
-    /// Panics if the device of the command buffer is not the same as the device of the future.
+    /// - Panics if the device of the command buffer is not the same as the device of the future.
+    /// - Panics if the future must be executed on another queue than `queue`. This includes the
+    ///   other queues of the same family. Use
+    ///   [`then_signal_semaphore`](crate::sync::GpuFuture::then_signal_semaphore) to wait for the
+    ///   future on `queue`.
     #[inline]
     fn execute_after<F>(
         self,
@@ -121,8 +125,17 @@ pub unsafe trait PrimaryCommandBuffer: DeviceOwned + Send + Sync {
             future.device().internal_object()
         );
 
+        // VUID-VkSubmitInfo-pNext-04148
+        if self.inner().protected() && !queue.is_protected() {
+            return Err(CommandBufferExecError::ProtectedQueueRequired);
+        }
+
         if !future.queue_change_allowed() {
-            assert!(future.queue().unwrap() == queue);
+            assert!(
+                future.queue().unwrap() == queue,
+                "the future must be executed on another queue; signal a semaphore to wait for it \
+                on this queue",
+            );
         }
 
         self.lock_submit(&future, &queue)?;
@@ -131,6 +144,7 @@ pub unsafe trait PrimaryCommandBuffer: DeviceOwned + Send + Sync {
             previous: future,
             command_buffer: self,
             queue,
+            counter_pass_index: None,
             submitted: Mutex::new(false),
             finished: AtomicBool::new(false),
         })
@@ -329,6 +343,7 @@ where
     previous: F,
     command_buffer: Cb,
     queue: Arc<Queue>,
+    counter_pass_index: Option<u32>,
     // True if the command buffer has already been submitted.
     // If flush is called multiple times, we want to block so that only one flushing is executed.
     // Therefore we use a `Mutex<bool>` and not an `AtomicBool`.
@@ -341,17 +356,63 @@ where
     F: GpuFuture,
     Cb: PrimaryCommandBuffer,
 {
+    /// Sets the counter pass index of the performance queries in the command buffer, for this
+    /// execution.
+    ///
+    /// The results of a performance query pool are only complete once the command buffers that
+    /// use it have been executed for each of its
+    /// [`performance_query_passes`](crate::query::QueryPool::performance_query_passes), with
+    /// each counter pass index from `0` to the number of passes minus one. Without a counter pass
+    /// index, the command buffer is executed for no particular pass.
+    ///
+    /// # Safety
+    ///
+    /// - `counter_pass_index` must be less than the number of passes of every performance query
+    ///   pool that is used by the command buffer.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the future was already flushed.
+    #[inline]
+    pub unsafe fn counter_pass_index(mut self, counter_pass_index: u32) -> Self {
+        assert!(!*self.submitted.lock());
+        self.counter_pass_index = Some(counter_pass_index);
+        self
+    }
+
     // Implementation of `build_submission`. Doesn't check whenever the future was already flushed.
     // You must make sure to not submit same command buffer multiple times.
     unsafe fn build_submission_impl(&self) -> Result<SubmitAnyBuilder, FlushError> {
+        let protected = self.command_buffer.inner().protected();
+        let counter_pass_index = self.counter_pass_index;
+
         Ok(match self.previous.build_submission()? {
             SubmitAnyBuilder::Empty => {
                 let mut builder = SubmitCommandBufferBuilder::new();
+                builder.set_protected(protected);
+                builder.set_counter_pass_index(counter_pass_index);
                 builder.add_command_buffer(self.command_buffer.inner());
                 SubmitAnyBuilder::CommandBuffer(builder)
             }
             SubmitAnyBuilder::SemaphoresWait(sem) => {
                 let mut builder: SubmitCommandBufferBuilder = sem.into();
+                builder.set_protected(protected);
+                builder.set_counter_pass_index(counter_pass_index);
+                builder.add_command_buffer(self.command_buffer.inner());
+                SubmitAnyBuilder::CommandBuffer(builder)
+            }
+            SubmitAnyBuilder::CommandBuffer(builder)
+                if builder.protected() != protected
+                    || builder.counter_pass_index() != counter_pass_index =>
+            {
+                // Protected and unprotected command buffers, or command buffers for different
+                // counter passes, can't be in the same submission, so the previous one is
+                // submitted first.
+                builder.submit(&self.queue)?;
+
+                let mut builder = SubmitCommandBufferBuilder::new();
+                builder.set_protected(protected);
+                builder.set_counter_pass_index(counter_pass_index);
                 builder.add_command_buffer(self.command_buffer.inner());
                 SubmitAnyBuilder::CommandBuffer(builder)
             }
@@ -498,9 +559,19 @@ where
         unsafe {
             if !*self.finished.get_mut() {
                 // TODO: handle errors?
-                self.flush().unwrap();
-                // Block until the queue finished.
-                self.queue.wait().unwrap();
+                // If the device was lost, its queues no longer execute anything, so the resources
+                // can be released without waiting.
+                match self.flush() {
+                    Ok(()) => {
+                        // Block until the queue finished.
+                        match self.queue.wait() {
+                            Ok(()) | Err(WaitIdleError::DeviceLost) => (),
+                            Err(err) => panic!("{}", err),
+                        }
+                    }
+                    Err(FlushError::DeviceLost) => (),
+                    Err(err) => panic!("{}", err),
+                }
                 self.command_buffer.unlock();
                 self.previous.signal_finished();
             }
@@ -526,6 +597,10 @@ pub enum CommandBufferExecError {
     /// The command buffer or one of the secondary command buffers it executes is already in use by
     /// the GPU and was not created with the "concurrent" flag.
     ExclusiveAlreadyInUse,
+
+    /// The command buffer is a protected command buffer, but the queue is not a
+    /// protected-capable queue.
+    ProtectedQueueRequired,
     // TODO: missing entries (eg. wrong queue family, secondary command buffer)
 }
 
@@ -557,6 +632,10 @@ impl fmt::Display for CommandBufferExecError {
                     "the command buffer or one of the secondary command buffers it executes is \
                  already in use was not created with the \"concurrent\" flag"
                 }
+                CommandBufferExecError::ProtectedQueueRequired => {
+                    "the command buffer is a protected command buffer, but the queue is not a \
+                 protected-capable queue"
+                }
             }
         )
     }
@@ -313,6 +313,8 @@ impl DescriptorSetLayout {
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         Ok(handle)
     }
 
@@ -667,6 +669,7 @@ impl DescriptorSetLayoutBinding {
             image_multisampled,
             image_scalar_type,
             image_view_type,
+            input_attachment_index: _,
             sampler_compare,
             sampler_no_unnormalized_coordinates,
             sampler_no_ycbcr_conversion,
@@ -83,13 +83,14 @@ pub use self::update::{DescriptorSetUpdateError, WriteDescriptorSet, WriteDescri
 use crate::buffer::view::BufferViewAbstract;
 use crate::buffer::BufferAccess;
 use crate::descriptor_set::layout::DescriptorType;
+use crate::device::object_tracker::{TrackedObject, TrackedObjectType};
 use crate::device::DeviceOwned;
 use crate::image::view::ImageViewAbstract;
 use crate::sampler::Sampler;
 use crate::OomError;
 use crate::VulkanObject;
 use smallvec::{smallvec, SmallVec};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::ptr;
@@ -149,6 +150,7 @@ pub(crate) struct DescriptorSetInner {
     layout: Arc<DescriptorSetLayout>,
     variable_descriptor_count: u32,
     resources: DescriptorSetResources,
+    tracked: TrackedObject,
 }
 
 impl DescriptorSetInner {
@@ -224,6 +226,9 @@ impl DescriptorSetInner {
 
         Ok(DescriptorSetInner {
             handle,
+            tracked: layout
+                .device()
+                .track_object(TrackedObjectType::DescriptorSet),
             layout,
             variable_descriptor_count,
             resources,
@@ -247,6 +252,8 @@ impl DescriptorSetInner {
 #[derive(Clone)]
 pub struct DescriptorSetResources {
     binding_resources: HashMap<u32, DescriptorBindingResources>,
+    // The `(binding, index)` pairs of descriptors that were written as null descriptors.
+    null_descriptors: HashSet<(u32, u32)>,
 }
 
 impl DescriptorSetResources {
@@ -305,7 +312,10 @@ impl DescriptorSetResources {
             })
             .collect();
 
-        Self { binding_resources }
+        Self {
+            binding_resources,
+            null_descriptors: HashSet::default(),
+        }
     }
 
     /// Applies a descriptor write to the resources.
@@ -315,10 +325,26 @@ impl DescriptorSetResources {
     /// - Panics if the binding number of a write does not exist in the resources.
     /// - See also [`DescriptorBindingResources::update`].
     pub fn update<'a>(&mut self, write: &WriteDescriptorSet) {
-        self.binding_resources
+        let binding_resources = self
+            .binding_resources
             .get_mut(&write.binding())
-            .expect("descriptor write has invalid binding number")
-            .update(write)
+            .expect("descriptor write has invalid binding number");
+        let is_null = matches!(write.elements(), WriteDescriptorSetElements::None(_))
+            && !matches!(binding_resources, DescriptorBindingResources::None(_));
+
+        binding_resources.update(write);
+
+        let indices =
+            write.first_array_element()..write.first_array_element() + write.elements().len();
+
+        if is_null {
+            self.null_descriptors
+                .extend(indices.map(|index| (write.binding(), index)));
+        } else {
+            for index in indices {
+                self.null_descriptors.remove(&(write.binding(), index));
+            }
+        }
     }
 
     /// Returns a reference to the bound resources for `binding`. Returns `None` if the binding
@@ -327,6 +353,16 @@ impl DescriptorSetResources {
     pub fn binding(&self, binding: u32) -> Option<&DescriptorBindingResources> {
         self.binding_resources.get(&binding)
     }
+
+    /// Returns whether the descriptor at `index` in `binding` was written as a null descriptor,
+    /// using [`WriteDescriptorSet::none`] or [`WriteDescriptorSet::none_array`] with the
+    /// [`null_descriptor`](crate::device::Features::null_descriptor) feature.
+    ///
+    /// The resources of a null descriptor are `None`.
+    #[inline]
+    pub fn is_null(&self, binding: u32, index: u32) -> bool {
+        self.null_descriptors.contains(&(binding, index))
+    }
 }
 
 /// The resources that are bound to a single descriptor set binding.
@@ -345,11 +381,24 @@ type Elements<T> = SmallVec<[Option<T>; 1]>;
 impl DescriptorBindingResources {
     /// Applies a descriptor write to the resources.
     ///
+    /// A write of [`WriteDescriptorSetElements::None`] to a binding that holds resources sets the
+    /// written elements to `None`.
+    ///
     /// # Panics
     ///
     /// - Panics if the resource types do not match.
     /// - Panics if the write goes out of bounds.
     pub fn update(&mut self, write: &WriteDescriptorSet) {
+        fn clear_resources<T>(first: usize, resources: &mut [Option<T>], num_elements: u32) {
+            resources
+                .get_mut(first..first + num_elements as usize)
+                .expect("descriptor write for binding out of bounds")
+                .iter_mut()
+                .for_each(|resource| {
+                    *resource = None;
+                });
+        }
+
         fn write_resources<T: Clone>(first: usize, resources: &mut [Option<T>], elements: &[T]) {
             resources
                 .get_mut(first..first + elements.len())
@@ -376,6 +425,18 @@ impl DescriptorBindingResources {
                         *resource = Some(());
                     });
             }
+            (
+                DescriptorBindingResources::Buffer(resources),
+                &WriteDescriptorSetElements::None(num_elements),
+            ) => clear_resources(first, resources, num_elements),
+            (
+                DescriptorBindingResources::BufferView(resources),
+                &WriteDescriptorSetElements::None(num_elements),
+            ) => clear_resources(first, resources, num_elements),
+            (
+                DescriptorBindingResources::ImageView(resources),
+                &WriteDescriptorSetElements::None(num_elements),
+            ) => clear_resources(first, resources, num_elements),
             (
                 DescriptorBindingResources::Buffer(resources),
                 WriteDescriptorSetElements::Buffer(elements),
@@ -496,7 +557,7 @@ where
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum DescriptorSetCreationError {
     DescriptorSetUpdateError(DescriptorSetUpdateError),
     OomError(OomError),
@@ -537,3 +598,75 @@ impl From<OomError> for DescriptorSetCreationError {
         Self::OomError(err)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::descriptor_set::layout::{
+        DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
+        DescriptorType,
+    };
+    use crate::descriptor_set::{
+        DescriptorSetCreationError, DescriptorSetUpdateError, PersistentDescriptorSet,
+        WriteDescriptorSet,
+    };
+    use crate::shader::ShaderStages;
+
+    #[test]
+    fn null_descriptor_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::all_graphics(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::StorageBuffer)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        match PersistentDescriptorSet::new(layout, [WriteDescriptorSet::none(0)]) {
+            Err(DescriptorSetCreationError::DescriptorSetUpdateError(
+                DescriptorSetUpdateError::FeatureNotEnabled { .. },
+            )) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn invalid_binding() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::all_graphics(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::StorageBuffer)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        match PersistentDescriptorSet::new(layout, [WriteDescriptorSet::none(1)]) {
+            Err(DescriptorSetCreationError::DescriptorSetUpdateError(
+                DescriptorSetUpdateError::ValidationError(err),
+            )) => {
+                assert_eq!(err.code, "invalid_binding");
+                assert!(err.has_vuid("VUID-VkWriteDescriptorSet-dstBinding-00315"));
+            }
+            _ => panic!(),
+        }
+    }
+}
@@ -106,6 +106,8 @@ impl UnsafeDescriptorPool {
             }
         };
 
+        device.set_automatic_debug_name(handle);
+
         Ok(UnsafeDescriptorPool {
             handle,
             device,
@@ -237,6 +239,10 @@ impl UnsafeDescriptorPool {
             output
         };
 
+        for &handle in &output {
+            self.device.set_automatic_debug_name(handle);
+        }
+
         Ok(output
             .into_iter()
             .map(|handle| UnsafeDescriptorSet::new(handle)))
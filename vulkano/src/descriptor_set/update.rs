@@ -12,8 +12,8 @@ use crate::{
     buffer::{view::BufferViewAbstract, BufferAccess, BufferInner},
     device::DeviceOwned,
     image::{view::ImageViewType, ImageType, ImageViewAbstract},
-    sampler::{Sampler, SamplerImageViewIncompatibleError},
-    DeviceSize, VulkanObject,
+    sampler::Sampler,
+    DeviceSize, ValidationError, VulkanObject,
 };
 use smallvec::SmallVec;
 use std::{ptr, sync::Arc};
@@ -50,6 +50,12 @@ impl WriteDescriptorSet {
     ///
     /// For regular descriptor sets, the data for such descriptors is automatically valid, and dummy
     /// writes are not allowed.
+    ///
+    /// If the [`null_descriptor`](crate::device::Features::null_descriptor) feature is enabled on
+    /// the device, this can also be used to write null descriptors to buffer, texel buffer,
+    /// sampled image and storage image bindings, and to combined image sampler bindings that have
+    /// immutable samplers. Shaders can access null descriptors; reads return zero, and writes are
+    /// discarded.
     #[inline]
     pub fn none_array(binding: u32, first_array_element: u32, num_elements: u32) -> Self {
         assert!(num_elements != 0);
@@ -194,18 +200,45 @@ impl WriteDescriptorSet {
 
     pub(crate) fn to_vulkan_info(&self, descriptor_type: DescriptorType) -> DescriptorWriteInfo {
         match &self.elements {
-            WriteDescriptorSetElements::None(num_elements) => {
-                debug_assert!(matches!(descriptor_type, DescriptorType::Sampler));
-                DescriptorWriteInfo::Image(
-                    std::iter::repeat_with(|| ash::vk::DescriptorImageInfo {
-                        sampler: ash::vk::Sampler::null(),
-                        image_view: ash::vk::ImageView::null(),
-                        image_layout: ash::vk::ImageLayout::UNDEFINED,
+            WriteDescriptorSetElements::None(num_elements) => match descriptor_type {
+                DescriptorType::UniformBuffer
+                | DescriptorType::StorageBuffer
+                | DescriptorType::UniformBufferDynamic
+                | DescriptorType::StorageBufferDynamic => DescriptorWriteInfo::Buffer(
+                    std::iter::repeat_with(|| ash::vk::DescriptorBufferInfo {
+                        buffer: ash::vk::Buffer::null(),
+                        offset: 0,
+                        range: ash::vk::WHOLE_SIZE,
                     })
                     .take(*num_elements as usize)
                     .collect(),
-                )
-            }
+                ),
+                DescriptorType::UniformTexelBuffer | DescriptorType::StorageTexelBuffer => {
+                    DescriptorWriteInfo::BufferView(
+                        std::iter::repeat(ash::vk::BufferView::null())
+                            .take(*num_elements as usize)
+                            .collect(),
+                    )
+                }
+                _ => {
+                    debug_assert!(matches!(
+                        descriptor_type,
+                        DescriptorType::Sampler
+                            | DescriptorType::CombinedImageSampler
+                            | DescriptorType::SampledImage
+                            | DescriptorType::StorageImage
+                    ));
+                    DescriptorWriteInfo::Image(
+                        std::iter::repeat_with(|| ash::vk::DescriptorImageInfo {
+                            sampler: ash::vk::Sampler::null(),
+                            image_view: ash::vk::ImageView::null(),
+                            image_layout: ash::vk::ImageLayout::UNDEFINED,
+                        })
+                        .take(*num_elements as usize)
+                        .collect(),
+                    )
+                }
+            },
             WriteDescriptorSetElements::Buffer(elements) => {
                 debug_assert!(matches!(
                     descriptor_type,
@@ -401,9 +434,17 @@ pub(crate) fn check_descriptor_write<'a>(
     let layout_binding = match layout.bindings().get(&write.binding()) {
         Some(binding) => binding,
         None => {
-            return Err(DescriptorSetUpdateError::InvalidBinding {
-                binding: write.binding(),
-            })
+            return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                code: "invalid_binding",
+                context: "WriteDescriptorSet.binding",
+                problem: format!(
+                    "tried to write to a nonexistent binding {}",
+                    write.binding(),
+                )
+                .into(),
+                vuids: &["VUID-VkWriteDescriptorSet-dstBinding-00315"],
+                ..Default::default()
+            }))
         }
     };
 
@@ -421,21 +462,68 @@ pub(crate) fn check_descriptor_write<'a>(
     let descriptor_range_end = descriptor_range_start + num_elements;
 
     if descriptor_range_end > max_descriptor_count {
-        return Err(DescriptorSetUpdateError::ArrayIndexOutOfBounds {
-            binding: write.binding(),
-            available_count: max_descriptor_count,
-            written_count: descriptor_range_end,
-        });
+        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+            code: "array_index_out_of_bounds",
+            context: "WriteDescriptorSet.first_array_element",
+            problem: format!(
+                "tried to write up to element {} to binding {}, but only {} descriptors are \
+                available",
+                descriptor_range_end,
+                write.binding(),
+                max_descriptor_count,
+            )
+            .into(),
+            vuids: &["VUID-VkWriteDescriptorSet-dstArrayElement-00321"],
+            ..Default::default()
+        }));
     }
 
     match elements {
-        WriteDescriptorSetElements::None(num_elements) => match layout_binding.descriptor_type {
+        WriteDescriptorSetElements::None(_) => match layout_binding.descriptor_type {
             DescriptorType::Sampler
                 if layout.push_descriptor() && !layout_binding.immutable_samplers.is_empty() => {}
+            DescriptorType::UniformBuffer
+            | DescriptorType::StorageBuffer
+            | DescriptorType::UniformBufferDynamic
+            | DescriptorType::StorageBufferDynamic
+            | DescriptorType::UniformTexelBuffer
+            | DescriptorType::StorageTexelBuffer
+            | DescriptorType::SampledImage
+            | DescriptorType::StorageImage => {
+                // VUID-VkDescriptorBufferInfo-buffer-02998
+                // VUID-VkWriteDescriptorSet-descriptorType-02995
+                // VUID-VkWriteDescriptorSet-descriptorType-02997
+                if !layout.device().enabled_features().null_descriptor {
+                    return Err(DescriptorSetUpdateError::FeatureNotEnabled {
+                        feature: "null_descriptor",
+                        reason: "a null descriptor was written",
+                    });
+                }
+            }
+            DescriptorType::CombinedImageSampler
+                if !layout_binding.immutable_samplers.is_empty() =>
+            {
+                // VUID-VkWriteDescriptorSet-descriptorType-02997
+                if !layout.device().enabled_features().null_descriptor {
+                    return Err(DescriptorSetUpdateError::FeatureNotEnabled {
+                        feature: "null_descriptor",
+                        reason: "a null descriptor was written",
+                    });
+                }
+            }
             _ => {
-                return Err(DescriptorSetUpdateError::IncompatibleDescriptorType {
-                    binding: write.binding(),
-                })
+                return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                    code: "incompatible_descriptor_type",
+                    context: "WriteDescriptorSet.elements",
+                    problem: format!(
+                        "tried to write a resource to binding {} whose type was not compatible \
+                        with the descriptor type",
+                        write.binding(),
+                    )
+                    .into(),
+                    vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00319"],
+                    ..Default::default()
+                }))
             }
         },
         WriteDescriptorSetElements::Buffer(elements) => {
@@ -448,11 +536,21 @@ pub(crate) fn check_descriptor_write<'a>(
                         );
 
                         if !buffer.inner().buffer.usage().storage_buffer {
-                            return Err(DescriptorSetUpdateError::MissingUsage {
-                                binding: write.binding(),
-                                index: descriptor_range_start + index as u32,
-                                usage: "storage_buffer",
-                            });
+                            return Err(DescriptorSetUpdateError::ValidationError(
+                                ValidationError {
+                                    code: "missing_usage",
+                                    context: "WriteDescriptorSet.elements",
+                                    problem: format!(
+                                        "tried to write a resource to binding {} index {} that \
+                                        did not have the required usage `storage_buffer` enabled",
+                                        write.binding(),
+                                        descriptor_range_start + index as u32,
+                                    )
+                                    .into(),
+                                    vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00331"],
+                                    ..Default::default()
+                                },
+                            ));
                         }
                     }
                 }
@@ -464,18 +562,37 @@ pub(crate) fn check_descriptor_write<'a>(
                         );
 
                         if !buffer.inner().buffer.usage().uniform_buffer {
-                            return Err(DescriptorSetUpdateError::MissingUsage {
-                                binding: write.binding(),
-                                index: descriptor_range_start + index as u32,
-                                usage: "uniform_buffer",
-                            });
+                            return Err(DescriptorSetUpdateError::ValidationError(
+                                ValidationError {
+                                    code: "missing_usage",
+                                    context: "WriteDescriptorSet.elements",
+                                    problem: format!(
+                                        "tried to write a resource to binding {} index {} that \
+                                        did not have the required usage `uniform_buffer` enabled",
+                                        write.binding(),
+                                        descriptor_range_start + index as u32,
+                                    )
+                                    .into(),
+                                    vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00330"],
+                                    ..Default::default()
+                                },
+                            ));
                         }
                     }
                 }
                 _ => {
-                    return Err(DescriptorSetUpdateError::IncompatibleDescriptorType {
-                        binding: write.binding(),
-                    })
+                    return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                        code: "incompatible_descriptor_type",
+                        context: "WriteDescriptorSet.elements",
+                        problem: format!(
+                            "tried to write a resource to binding {} whose type was not \
+                            compatible with the descriptor type",
+                            write.binding(),
+                        )
+                        .into(),
+                        vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00319"],
+                        ..Default::default()
+                    }))
                 }
             }
 
@@ -507,11 +624,22 @@ pub(crate) fn check_descriptor_write<'a>(
                             .usage()
                             .storage_texel_buffer
                         {
-                            return Err(DescriptorSetUpdateError::MissingUsage {
-                                binding: write.binding(),
-                                index: descriptor_range_start + index as u32,
-                                usage: "storage_texel_buffer",
-                            });
+                            return Err(DescriptorSetUpdateError::ValidationError(
+                                ValidationError {
+                                    code: "missing_usage",
+                                    context: "WriteDescriptorSet.elements",
+                                    problem: format!(
+                                        "tried to write a resource to binding {} index {} that \
+                                        did not have the required usage `storage_texel_buffer` \
+                                        enabled",
+                                        write.binding(),
+                                        descriptor_range_start + index as u32,
+                                    )
+                                    .into(),
+                                    vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00335"],
+                                    ..Default::default()
+                                },
+                            ));
                         }
                     }
                 }
@@ -529,18 +657,38 @@ pub(crate) fn check_descriptor_write<'a>(
                             .usage()
                             .uniform_texel_buffer
                         {
-                            return Err(DescriptorSetUpdateError::MissingUsage {
-                                binding: write.binding(),
-                                index: descriptor_range_start + index as u32,
-                                usage: "uniform_texel_buffer",
-                            });
+                            return Err(DescriptorSetUpdateError::ValidationError(
+                                ValidationError {
+                                    code: "missing_usage",
+                                    context: "WriteDescriptorSet.elements",
+                                    problem: format!(
+                                        "tried to write a resource to binding {} index {} that \
+                                        did not have the required usage `uniform_texel_buffer` \
+                                        enabled",
+                                        write.binding(),
+                                        descriptor_range_start + index as u32,
+                                    )
+                                    .into(),
+                                    vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00334"],
+                                    ..Default::default()
+                                },
+                            ));
                         }
                     }
                 }
                 _ => {
-                    return Err(DescriptorSetUpdateError::IncompatibleDescriptorType {
-                        binding: write.binding(),
-                    })
+                    return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                        code: "incompatible_descriptor_type",
+                        context: "WriteDescriptorSet.elements",
+                        problem: format!(
+                            "tried to write a resource to binding {} whose type was not \
+                            compatible with the descriptor type",
+                            write.binding(),
+                        )
+                        .into(),
+                        vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00319"],
+                        ..Default::default()
+                    }))
                 }
             }
         }
@@ -559,44 +707,96 @@ pub(crate) fn check_descriptor_write<'a>(
                         layout.device().internal_object(),
                     );
 
-                    // VUID-VkWriteDescriptorSet-descriptorType-00337
                     if !image_view.usage().sampled {
-                        return Err(DescriptorSetUpdateError::MissingUsage {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                            usage: "sampled",
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "missing_usage",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write a resource to binding {} index {} that did not \
+                                have the required usage `sampled` enabled",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00337"],
+                            ..Default::default()
+                        }));
                     }
 
-                    // VUID-VkDescriptorImageInfo-imageView-00343
-                    if matches!(
-                        image_view.view_type(),
-                        ImageViewType::Dim2d | ImageViewType::Dim2dArray
-                    ) && image_view.image().inner().image.dimensions().image_type()
+                    if image_view.image().inner().image.dimensions().image_type()
                         == ImageType::Dim3d
                     {
-                        return Err(DescriptorSetUpdateError::ImageView2dFrom3d {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                        });
+                        let image_inner = image_view.image().inner().image;
+                        let allowed = match image_view.view_type() {
+                            ImageViewType::Dim2d => {
+                                image_inner
+                                    .device()
+                                    .enabled_features()
+                                    .sampler2_d_view_of3_d
+                                    && image_inner.view_2d_compatible()
+                            }
+                            ImageViewType::Dim2dArray => false,
+                            _ => true,
+                        };
+
+                        if !allowed {
+                            return Err(DescriptorSetUpdateError::ValidationError(
+                                ValidationError {
+                                    code: "image_view_2d_from_3d",
+                                    context: "WriteDescriptorSet.elements",
+                                    problem: format!(
+                                        "tried to write an image view to binding {} index {} with \
+                                        a 2D type and a 3D underlying image",
+                                        write.binding(),
+                                        descriptor_range_start + index as u32,
+                                    )
+                                    .into(),
+                                    vuids: &[
+                                        "VUID-VkDescriptorImageInfo-imageView-00343",
+                                        "VUID-VkDescriptorImageInfo-imageView-06712",
+                                        "VUID-VkDescriptorImageInfo-descriptorType-06714",
+                                        "VUID-VkWriteDescriptorSet-descriptorType-06711",
+                                    ],
+                                    ..Default::default()
+                                },
+                            ));
+                        }
                     }
 
-                    // VUID-VkDescriptorImageInfo-imageView-01976
                     if image_view.subresource_range().aspects.depth
                         && image_view.subresource_range().aspects.stencil
                     {
-                        return Err(DescriptorSetUpdateError::ImageViewDepthAndStencil {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "image_view_depth_and_stencil",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write an image view to binding {} index {} that has \
+                                both the `depth` and `stencil` aspects",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkDescriptorImageInfo-imageView-01976"],
+                            ..Default::default()
+                        }));
                     }
 
                     if let Err(error) = sampler.check_can_sample(image_view.as_ref()) {
-                        return Err(DescriptorSetUpdateError::ImageViewIncompatibleSampler {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                            error,
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "image_view_incompatible_sampler",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write an image view to binding {} index {} that was not \
+                                compatible with the sampler that was provided as part of the \
+                                update or immutably in the layout: {}",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                                error,
+                            )
+                            .into(),
+                            vuids: &[],
+                            ..Default::default()
+                        }));
                     }
                 }
             }
@@ -607,46 +807,95 @@ pub(crate) fn check_descriptor_write<'a>(
                         layout.device().internal_object(),
                     );
 
-                    // VUID-VkWriteDescriptorSet-descriptorType-00337
                     if !image_view.usage().sampled {
-                        return Err(DescriptorSetUpdateError::MissingUsage {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                            usage: "sampled",
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "missing_usage",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write a resource to binding {} index {} that did not \
+                                have the required usage `sampled` enabled",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00337"],
+                            ..Default::default()
+                        }));
                     }
 
-                    // VUID-VkDescriptorImageInfo-imageView-00343
-                    if matches!(
-                        image_view.view_type(),
-                        ImageViewType::Dim2d | ImageViewType::Dim2dArray
-                    ) && image_view.image().inner().image.dimensions().image_type()
+                    if image_view.image().inner().image.dimensions().image_type()
                         == ImageType::Dim3d
                     {
-                        return Err(DescriptorSetUpdateError::ImageView2dFrom3d {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                        });
+                        let image_inner = image_view.image().inner().image;
+                        let allowed = match image_view.view_type() {
+                            ImageViewType::Dim2d => {
+                                image_inner
+                                    .device()
+                                    .enabled_features()
+                                    .sampler2_d_view_of3_d
+                                    && image_inner.view_2d_compatible()
+                            }
+                            ImageViewType::Dim2dArray => false,
+                            _ => true,
+                        };
+
+                        if !allowed {
+                            return Err(DescriptorSetUpdateError::ValidationError(
+                                ValidationError {
+                                    code: "image_view_2d_from_3d",
+                                    context: "WriteDescriptorSet.elements",
+                                    problem: format!(
+                                        "tried to write an image view to binding {} index {} with \
+                                        a 2D type and a 3D underlying image",
+                                        write.binding(),
+                                        descriptor_range_start + index as u32,
+                                    )
+                                    .into(),
+                                    vuids: &[
+                                        "VUID-VkDescriptorImageInfo-imageView-00343",
+                                        "VUID-VkDescriptorImageInfo-imageView-06712",
+                                        "VUID-VkDescriptorImageInfo-descriptorType-06714",
+                                        "VUID-VkWriteDescriptorSet-descriptorType-06711",
+                                    ],
+                                    ..Default::default()
+                                },
+                            ));
+                        }
                     }
 
-                    // VUID-VkDescriptorImageInfo-imageView-01976
                     if image_view.subresource_range().aspects.depth
                         && image_view.subresource_range().aspects.stencil
                     {
-                        return Err(DescriptorSetUpdateError::ImageViewDepthAndStencil {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "image_view_depth_and_stencil",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write an image view to binding {} index {} that has \
+                                both the `depth` and `stencil` aspects",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkDescriptorImageInfo-imageView-01976"],
+                            ..Default::default()
+                        }));
                     }
 
-                    // VUID-VkWriteDescriptorSet-descriptorType-01946
                     if image_view.sampler_ycbcr_conversion().is_some() {
-                        return Err(
-                            DescriptorSetUpdateError::ImageViewHasSamplerYcbcrConversion {
-                                binding: write.binding(),
-                                index: descriptor_range_start + index as u32,
-                            },
-                        );
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "image_view_has_sampler_ycbcr_conversion",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write an image view to binding {} index {} with an \
+                                attached sampler YCbCr conversion to a binding that does not \
+                                support it",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkWriteDescriptorSet-descriptorType-01946"],
+                            ..Default::default()
+                        }));
                     }
                 }
             }
@@ -657,54 +906,110 @@ pub(crate) fn check_descriptor_write<'a>(
                         layout.device().internal_object(),
                     );
 
-                    // VUID-VkWriteDescriptorSet-descriptorType-00339
                     if !image_view.usage().storage {
-                        return Err(DescriptorSetUpdateError::MissingUsage {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                            usage: "storage",
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "missing_usage",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write a resource to binding {} index {} that did not \
+                                have the required usage `storage` enabled",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00339"],
+                            ..Default::default()
+                        }));
                     }
 
-                    // VUID-VkDescriptorImageInfo-imageView-00343
-                    if matches!(
-                        image_view.view_type(),
-                        ImageViewType::Dim2d | ImageViewType::Dim2dArray
-                    ) && image_view.image().inner().image.dimensions().image_type()
+                    if image_view.image().inner().image.dimensions().image_type()
                         == ImageType::Dim3d
                     {
-                        return Err(DescriptorSetUpdateError::ImageView2dFrom3d {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                        });
+                        let image_inner = image_view.image().inner().image;
+                        let allowed = match image_view.view_type() {
+                            ImageViewType::Dim2d => {
+                                image_inner.device().enabled_features().image2_d_view_of3_d
+                                    && image_inner.view_2d_compatible()
+                            }
+                            ImageViewType::Dim2dArray => false,
+                            _ => true,
+                        };
+
+                        if !allowed {
+                            return Err(DescriptorSetUpdateError::ValidationError(
+                                ValidationError {
+                                    code: "image_view_2d_from_3d",
+                                    context: "WriteDescriptorSet.elements",
+                                    problem: format!(
+                                        "tried to write an image view to binding {} index {} with \
+                                        a 2D type and a 3D underlying image",
+                                        write.binding(),
+                                        descriptor_range_start + index as u32,
+                                    )
+                                    .into(),
+                                    vuids: &[
+                                        "VUID-VkDescriptorImageInfo-imageView-00343",
+                                        "VUID-VkDescriptorImageInfo-imageView-06712",
+                                        "VUID-VkDescriptorImageInfo-descriptorType-06713",
+                                        "VUID-VkWriteDescriptorSet-descriptorType-06710",
+                                    ],
+                                    ..Default::default()
+                                },
+                            ));
+                        }
                     }
 
-                    // VUID-VkDescriptorImageInfo-imageView-01976
                     if image_view.subresource_range().aspects.depth
                         && image_view.subresource_range().aspects.stencil
                     {
-                        return Err(DescriptorSetUpdateError::ImageViewDepthAndStencil {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "image_view_depth_and_stencil",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write an image view to binding {} index {} that has \
+                                both the `depth` and `stencil` aspects",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkDescriptorImageInfo-imageView-01976"],
+                            ..Default::default()
+                        }));
                     }
 
-                    // VUID-VkWriteDescriptorSet-descriptorType-00336
                     if !image_view.component_mapping().is_identity() {
-                        return Err(DescriptorSetUpdateError::ImageViewNotIdentitySwizzled {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "image_view_not_identity_swizzled",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write an image view with non-identity swizzling to \
+                                binding {} index {}, but this binding has a descriptor type that \
+                                requires it to be identity swizzled",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00336"],
+                            ..Default::default()
+                        }));
                     }
 
                     // VUID??
                     if image_view.sampler_ycbcr_conversion().is_some() {
-                        return Err(
-                            DescriptorSetUpdateError::ImageViewHasSamplerYcbcrConversion {
-                                binding: write.binding(),
-                                index: descriptor_range_start + index as u32,
-                            },
-                        );
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "image_view_has_sampler_ycbcr_conversion",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write an image view to binding {} index {} with an \
+                                attached sampler YCbCr conversion to a binding that does not \
+                                support it",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &[],
+                            ..Default::default()
+                        }));
                     }
                 }
             }
@@ -715,69 +1020,128 @@ pub(crate) fn check_descriptor_write<'a>(
                         layout.device().internal_object(),
                     );
 
-                    // VUID-VkWriteDescriptorSet-descriptorType-00338
                     if !image_view.usage().input_attachment {
-                        return Err(DescriptorSetUpdateError::MissingUsage {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                            usage: "input_attachment",
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "missing_usage",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write a resource to binding {} index {} that did not \
+                                have the required usage `input_attachment` enabled",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00338"],
+                            ..Default::default()
+                        }));
                     }
 
-                    // VUID-VkDescriptorImageInfo-imageView-00343
                     if matches!(
                         image_view.view_type(),
                         ImageViewType::Dim2d | ImageViewType::Dim2dArray
                     ) && image_view.image().inner().image.dimensions().image_type()
                         == ImageType::Dim3d
                     {
-                        return Err(DescriptorSetUpdateError::ImageView2dFrom3d {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "image_view_2d_from_3d",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write an image view to binding {} index {} with a 2D \
+                                type and a 3D underlying image",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkDescriptorImageInfo-imageView-00343"],
+                            ..Default::default()
+                        }));
                     }
 
-                    // VUID-VkDescriptorImageInfo-imageView-01976
                     if image_view.subresource_range().aspects.depth
                         && image_view.subresource_range().aspects.stencil
                     {
-                        return Err(DescriptorSetUpdateError::ImageViewDepthAndStencil {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "image_view_depth_and_stencil",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write an image view to binding {} index {} that has \
+                                both the `depth` and `stencil` aspects",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkDescriptorImageInfo-imageView-01976"],
+                            ..Default::default()
+                        }));
                     }
 
-                    // VUID-VkWriteDescriptorSet-descriptorType-00336
                     if !image_view.component_mapping().is_identity() {
-                        return Err(DescriptorSetUpdateError::ImageViewNotIdentitySwizzled {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "image_view_not_identity_swizzled",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write an image view with non-identity swizzling to \
+                                binding {} index {}, but this binding has a descriptor type that \
+                                requires it to be identity swizzled",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00336"],
+                            ..Default::default()
+                        }));
                     }
 
                     // VUID??
                     if image_view.sampler_ycbcr_conversion().is_some() {
-                        return Err(
-                            DescriptorSetUpdateError::ImageViewHasSamplerYcbcrConversion {
-                                binding: write.binding(),
-                                index: descriptor_range_start + index as u32,
-                            },
-                        );
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "image_view_has_sampler_ycbcr_conversion",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write an image view to binding {} index {} with an \
+                                attached sampler YCbCr conversion to a binding that does not \
+                                support it",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &[],
+                            ..Default::default()
+                        }));
                     }
 
                     // VUID??
                     if image_view.view_type().is_arrayed() {
-                        return Err(DescriptorSetUpdateError::ImageViewIsArrayed {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "image_view_is_arrayed",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write an image view of an arrayed type to binding {} \
+                                index {}, but this binding has a descriptor type that does not \
+                                support arrayed image views",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &[],
+                            ..Default::default()
+                        }));
                     }
                 }
             }
             _ => {
-                return Err(DescriptorSetUpdateError::IncompatibleDescriptorType {
-                    binding: write.binding(),
-                })
+                return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                    code: "incompatible_descriptor_type",
+                    context: "WriteDescriptorSet.elements",
+                    problem: format!(
+                        "tried to write a resource to binding {} whose type was not compatible \
+                        with the descriptor type",
+                        write.binding(),
+                    )
+                    .into(),
+                    vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00319"],
+                    ..Default::default()
+                }))
             }
         },
         WriteDescriptorSetElements::ImageViewSampler(elements) => match layout_binding
@@ -785,9 +1149,18 @@ pub(crate) fn check_descriptor_write<'a>(
         {
             DescriptorType::CombinedImageSampler => {
                 if !layout_binding.immutable_samplers.is_empty() {
-                    return Err(DescriptorSetUpdateError::SamplerIsImmutable {
-                        binding: write.binding(),
-                    });
+                    return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                        code: "sampler_is_immutable",
+                        context: "WriteDescriptorSet.elements",
+                        problem: format!(
+                            "tried to write a sampler to binding {}, which already contains \
+                            immutable samplers in the descriptor set layout",
+                            write.binding(),
+                        )
+                        .into(),
+                        vuids: &[],
+                        ..Default::default()
+                    }));
                 }
 
                 for (index, (image_view, sampler)) in elements.iter().enumerate() {
@@ -800,75 +1173,162 @@ pub(crate) fn check_descriptor_write<'a>(
                         layout.device().internal_object(),
                     );
 
-                    // VUID-VkWriteDescriptorSet-descriptorType-00337
                     if !image_view.usage().sampled {
-                        return Err(DescriptorSetUpdateError::MissingUsage {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                            usage: "sampled",
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "missing_usage",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write a resource to binding {} index {} that did not \
+                                have the required usage `sampled` enabled",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00337"],
+                            ..Default::default()
+                        }));
                     }
 
-                    // VUID-VkDescriptorImageInfo-imageView-00343
-                    if matches!(
-                        image_view.view_type(),
-                        ImageViewType::Dim2d | ImageViewType::Dim2dArray
-                    ) && image_view.image().inner().image.dimensions().image_type()
+                    if image_view.image().inner().image.dimensions().image_type()
                         == ImageType::Dim3d
                     {
-                        return Err(DescriptorSetUpdateError::ImageView2dFrom3d {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                        });
+                        let image_inner = image_view.image().inner().image;
+                        let allowed = match image_view.view_type() {
+                            ImageViewType::Dim2d => {
+                                image_inner
+                                    .device()
+                                    .enabled_features()
+                                    .sampler2_d_view_of3_d
+                                    && image_inner.view_2d_compatible()
+                            }
+                            ImageViewType::Dim2dArray => false,
+                            _ => true,
+                        };
+
+                        if !allowed {
+                            return Err(DescriptorSetUpdateError::ValidationError(
+                                ValidationError {
+                                    code: "image_view_2d_from_3d",
+                                    context: "WriteDescriptorSet.elements",
+                                    problem: format!(
+                                        "tried to write an image view to binding {} index {} with \
+                                        a 2D type and a 3D underlying image",
+                                        write.binding(),
+                                        descriptor_range_start + index as u32,
+                                    )
+                                    .into(),
+                                    vuids: &[
+                                        "VUID-VkDescriptorImageInfo-imageView-00343",
+                                        "VUID-VkDescriptorImageInfo-imageView-06712",
+                                        "VUID-VkDescriptorImageInfo-descriptorType-06714",
+                                        "VUID-VkWriteDescriptorSet-descriptorType-06711",
+                                    ],
+                                    ..Default::default()
+                                },
+                            ));
+                        }
                     }
 
-                    // VUID-VkDescriptorImageInfo-imageView-01976
                     if image_view.subresource_range().aspects.depth
                         && image_view.subresource_range().aspects.stencil
                     {
-                        return Err(DescriptorSetUpdateError::ImageViewDepthAndStencil {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "image_view_depth_and_stencil",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write an image view to binding {} index {} that has \
+                                both the `depth` and `stencil` aspects",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkDescriptorImageInfo-imageView-01976"],
+                            ..Default::default()
+                        }));
                     }
 
                     if image_view.sampler_ycbcr_conversion().is_some() {
-                        return Err(
-                            DescriptorSetUpdateError::ImageViewHasSamplerYcbcrConversion {
-                                binding: write.binding(),
-                                index: descriptor_range_start + index as u32,
-                            },
-                        );
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "image_view_has_sampler_ycbcr_conversion",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write an image view to binding {} index {} with an \
+                                attached sampler YCbCr conversion to a binding that does not \
+                                support it",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &[],
+                            ..Default::default()
+                        }));
                     }
 
                     if sampler.sampler_ycbcr_conversion().is_some() {
-                        return Err(DescriptorSetUpdateError::SamplerHasSamplerYcbcrConversion {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "sampler_has_sampler_ycbcr_conversion",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write a sampler to binding {} index {} that has an \
+                                attached sampler YCbCr conversion",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &[],
+                            ..Default::default()
+                        }));
                     }
 
                     if let Err(error) = sampler.check_can_sample(image_view.as_ref()) {
-                        return Err(DescriptorSetUpdateError::ImageViewIncompatibleSampler {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                            error,
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "image_view_incompatible_sampler",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write an image view to binding {} index {} that was not \
+                                compatible with the sampler that was provided as part of the \
+                                update or immutably in the layout: {}",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                                error,
+                            )
+                            .into(),
+                            vuids: &[],
+                            ..Default::default()
+                        }));
                     }
                 }
             }
             _ => {
-                return Err(DescriptorSetUpdateError::IncompatibleDescriptorType {
-                    binding: write.binding(),
-                })
+                return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                    code: "incompatible_descriptor_type",
+                    context: "WriteDescriptorSet.elements",
+                    problem: format!(
+                        "tried to write a resource to binding {} whose type was not compatible \
+                        with the descriptor type",
+                        write.binding(),
+                    )
+                    .into(),
+                    vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00319"],
+                    ..Default::default()
+                }))
             }
         },
         WriteDescriptorSetElements::Sampler(elements) => match layout_binding.descriptor_type {
             DescriptorType::Sampler => {
                 if !layout_binding.immutable_samplers.is_empty() {
-                    return Err(DescriptorSetUpdateError::SamplerIsImmutable {
-                        binding: write.binding(),
-                    });
+                    return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                        code: "sampler_is_immutable",
+                        context: "WriteDescriptorSet.elements",
+                        problem: format!(
+                            "tried to write a sampler to binding {}, which already contains \
+                            immutable samplers in the descriptor set layout",
+                            write.binding(),
+                        )
+                        .into(),
+                        vuids: &[],
+                        ..Default::default()
+                    }));
                 }
 
                 for (index, sampler) in elements.iter().enumerate() {
@@ -878,17 +1338,35 @@ pub(crate) fn check_descriptor_write<'a>(
                     );
 
                     if sampler.sampler_ycbcr_conversion().is_some() {
-                        return Err(DescriptorSetUpdateError::SamplerHasSamplerYcbcrConversion {
-                            binding: write.binding(),
-                            index: descriptor_range_start + index as u32,
-                        });
+                        return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                            code: "sampler_has_sampler_ycbcr_conversion",
+                            context: "WriteDescriptorSet.elements",
+                            problem: format!(
+                                "tried to write a sampler to binding {} index {} that has an \
+                                attached sampler YCbCr conversion",
+                                write.binding(),
+                                descriptor_range_start + index as u32,
+                            )
+                            .into(),
+                            vuids: &[],
+                            ..Default::default()
+                        }));
                     }
                 }
             }
             _ => {
-                return Err(DescriptorSetUpdateError::IncompatibleDescriptorType {
-                    binding: write.binding(),
-                })
+                return Err(DescriptorSetUpdateError::ValidationError(ValidationError {
+                    code: "incompatible_descriptor_type",
+                    context: "WriteDescriptorSet.elements",
+                    problem: format!(
+                        "tried to write a resource to binding {} whose type was not compatible \
+                        with the descriptor type",
+                        write.binding(),
+                    )
+                    .into(),
+                    vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00319"],
+                    ..Default::default()
+                }))
             }
         },
     }
@@ -896,146 +1374,34 @@ pub(crate) fn check_descriptor_write<'a>(
     Ok(layout_binding)
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum DescriptorSetUpdateError {
-    /// Tried to write more elements than were available in a binding.
-    ArrayIndexOutOfBounds {
-        /// Binding that is affected.
-        binding: u32,
-        /// Number of available descriptors in the binding.
-        available_count: u32,
-        /// The number of descriptors that were in the update.
-        written_count: u32,
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
     },
 
-    /// Tried to write an image view with a 2D type and a 3D underlying image.
-    ImageView2dFrom3d { binding: u32, index: u32 },
-
-    /// Tried to write an image view that has both the `depth` and `stencil` aspects.
-    ImageViewDepthAndStencil { binding: u32, index: u32 },
-
-    /// Tried to write an image view with an attached sampler YCbCr conversion to a binding that
-    /// does not support it.
-    ImageViewHasSamplerYcbcrConversion { binding: u32, index: u32 },
-
-    /// Tried to write an image view of an arrayed type to a descriptor type that does not support
-    /// it.
-    ImageViewIsArrayed { binding: u32, index: u32 },
-
-    /// Tried to write an image view that was not compatible with the sampler that was provided as
-    /// part of the update or immutably in the layout.
-    ImageViewIncompatibleSampler {
-        binding: u32,
-        index: u32,
-        error: SamplerImageViewIncompatibleError,
-    },
-
-    /// Tried to write an image view to a descriptor type that requires it to be identity swizzled,
-    /// but it was not.
-    ImageViewNotIdentitySwizzled { binding: u32, index: u32 },
-
-    /// Tried to write an element type that was not compatible with the descriptor type in the
-    /// layout.
-    IncompatibleDescriptorType { binding: u32 },
-
-    /// Tried to write to a nonexistent binding.
-    InvalidBinding { binding: u32 },
-
-    /// A resource was missing a usage flag that was required.
-    MissingUsage {
-        binding: u32,
-        index: u32,
-        usage: &'static str,
-    },
-
-    /// Tried to write a sampler that has an attached sampler YCbCr conversion.
-    SamplerHasSamplerYcbcrConversion { binding: u32, index: u32 },
-
-    /// Tried to write a sampler to a binding with immutable samplers.
-    SamplerIsImmutable { binding: u32 },
+    /// A write did not meet the requirements of the Vulkan specification.
+    ValidationError(ValidationError),
 }
 
-impl std::error::Error for DescriptorSetUpdateError {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        match self {
-            Self::ImageViewIncompatibleSampler { error, .. } => Some(error),
-            _ => None,
-        }
-    }
-}
+impl std::error::Error for DescriptorSetUpdateError {}
 
 impl std::fmt::Display for DescriptorSetUpdateError {
     #[inline]
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         match self {
-            Self::ArrayIndexOutOfBounds {
-                binding,
-                available_count,
-                written_count,
-            } => write!(
-                fmt,
-                "tried to write up to element {} to binding {}, but only {} descriptors are available",
-                written_count, binding, available_count,
-            ),
-            Self::ImageView2dFrom3d { binding, index } => write!(
-                fmt,
-                "tried to write an image view to binding {} index {} with a 2D type and a 3D underlying image",
-                binding, index,
-            ),
-            Self::ImageViewDepthAndStencil { binding, index } => write!(
-                fmt,
-                "tried to write an image view to binding {} index {} that has both the `depth` and `stencil` aspects",
-                binding, index,
-            ),
-            Self::ImageViewHasSamplerYcbcrConversion { binding, index } => write!(
-                fmt,
-                "tried to write an image view to binding {} index {} with an attached sampler YCbCr conversion to binding that does not support it",
-                binding, index,
-            ),
-            Self::ImageViewIsArrayed { binding, index } => write!(
-                fmt,
-                "tried to write an image view of an arrayed type to binding {} index {}, but this binding has a descriptor type that does not support arrayed image views",
-                binding, index,
-            ),
-            Self::ImageViewIncompatibleSampler { binding, index, .. } => write!(
-                fmt,
-                "tried to write an image view to binding {} index {}, that was not compatible with the sampler that was provided as part of the update or immutably in the layout",
-                binding, index,
-            ),
-            Self::ImageViewNotIdentitySwizzled { binding, index } => write!(
-                fmt,
-                "tried to write an image view with non-identity swizzling to binding {} index {}, but this binding has a descriptor type that requires it to be identity swizzled",
-                binding, index,
-            ),
-            Self::IncompatibleDescriptorType { binding } => write!(
-                fmt,
-                "tried to write a resource to binding {} whose type was not compatible with the descriptor type",
-                binding,
-            ),
-            Self::InvalidBinding { binding } => write!(
-                fmt,
-                "tried to write to a nonexistent binding {}",
-                binding,
-            ),
-            Self::MissingUsage {
-                binding,
-                index,
-                usage,
-            } => write!(
-                fmt,
-                "tried to write a resource to binding {} index {} that did not have the required usage {} enabled",
-                binding, index, usage,
-            ),
-            Self::SamplerHasSamplerYcbcrConversion { binding, index } => write!(
-                fmt,
-                "tried to write a sampler to binding {} index {} that has an attached sampler YCbCr conversion",
-                binding, index,
-            ),
-            Self::SamplerIsImmutable { binding } => write!(
-                fmt,
-                "tried to write a sampler to binding {}, which already contains immutable samplers in the descriptor set layout",
-                binding,
-            ),
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
+            Self::ValidationError(err) => write!(fmt, "{}", err),
         }
     }
 }
+
+impl From<ValidationError> for DescriptorSetUpdateError {
+    #[inline]
+    fn from(err: ValidationError) -> Self {
+        Self::ValidationError(err)
+    }
+}
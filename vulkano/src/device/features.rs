@@ -7,11 +7,14 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use crate::device::DeviceExtensions;
+use crate::device::{Device, DeviceExtensions};
+use crate::extension_chain::{ExtendsCreateInfo, ExtensionChain};
+use crate::extensions::OneOfRequirements;
 use crate::instance::InstanceExtensions;
 use crate::Version;
 use std::error;
 use std::fmt;
+use std::ptr;
 
 // Generated by build.rs
 include!(concat!(env!("OUT_DIR"), "/features.rs"));
@@ -48,6 +51,9 @@ pub enum FeatureRestriction {
     ConflictsFeature(&'static str),
     /// An extension requires this feature to be enabled.
     RequiredByExtension(&'static str),
+    /// Requires one of the following, which provide the feature structure that contains the
+    /// feature.
+    Requires(OneOfRequirements),
 }
 
 impl fmt::Display for FeatureRestriction {
@@ -66,6 +72,225 @@ impl fmt::Display for FeatureRestriction {
             FeatureRestriction::RequiredByExtension(ext) => {
                 write!(fmt, "required to be enabled by extension {}", ext)
             }
+            FeatureRestriction::Requires(requires) => {
+                if requires.has_multiple() {
+                    write!(fmt, "requires one of: {}", requires)
+                } else {
+                    write!(fmt, "requires: {}", requires)
+                }
+            }
+        }
+    }
+}
+
+/// Feature structures of extensions that are not included in [`Features`], to enable when
+/// creating a device.
+///
+/// The structures are the raw structures of the [`ash`] crate, such as
+/// `ash::vk::PhysicalDeviceMeshShaderFeaturesNV`. The features that a physical device supports can
+/// be queried with
+/// [`PhysicalDevice::extension_features`](crate::device::physical::PhysicalDevice::extension_features).
+///
+/// ```
+/// use vulkano::{ash, device::ExtensionFeatures};
+///
+/// let mut extension_features = ExtensionFeatures::new();
+///
+/// unsafe {
+///     extension_features.set(ash::vk::PhysicalDeviceMeshShaderFeaturesNV {
+///         mesh_shader: ash::vk::TRUE,
+///         task_shader: ash::vk::TRUE,
+///         ..Default::default()
+///     });
+/// }
+///
+/// let mesh_shader = extension_features
+///     .get::<ash::vk::PhysicalDeviceMeshShaderFeaturesNV>()
+///     .unwrap();
+/// assert_eq!(mesh_shader.mesh_shader, ash::vk::TRUE);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ExtensionFeatures {
+    structs: ExtensionChain<Device>,
+}
+
+impl ExtensionFeatures {
+    /// Returns an empty `ExtensionFeatures`.
+    #[inline]
+    pub fn new() -> Self {
+        ExtensionFeatures {
+            structs: ExtensionChain::new(),
+        }
+    }
+
+    /// Adds a feature structure, replacing the structure of the same type if there is one.
+    /// Its `p_next` member is ignored.
+    ///
+    /// # Safety
+    ///
+    /// - The device extension or Vulkan version that provides `S` must be enabled on the device.
+    pub unsafe fn set<S>(&mut self, features: S) -> &mut Self
+    where
+        S: ExtensionFeatureStruct,
+    {
+        self.structs.remove(s_type_of::<S>());
+        self.structs.push(features);
+        self
+    }
+
+    /// Returns the feature structure of type `S`, if it was added.
+    pub fn get<S>(&self) -> Option<S>
+    where
+        S: ExtensionFeatureStruct,
+    {
+        unsafe {
+            self.structs.get::<S>(s_type_of::<S>()).map(|mut features| {
+                (*(&mut features as *mut S as *mut ash::vk::BaseOutStructure)).p_next =
+                    ptr::null_mut();
+                features
+            })
+        }
+    }
+
+    /// Returns the number of feature structures.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.structs.len()
+    }
+
+    /// Returns whether no feature structure was added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.structs.is_empty()
+    }
+
+    #[inline]
+    pub(crate) fn chain(&self) -> &ExtensionChain<Device> {
+        &self.structs
+    }
+}
+
+/// A raw Vulkan structure that contains features of a device, which can be added to
+/// [`ExtensionFeatures`].
+///
+/// # Safety
+///
+/// The structure must begin with the `s_type` and `p_next` members, and its `Default`
+/// implementation must set `s_type`. It must extend both `VkPhysicalDeviceFeatures2` and
+/// `VkDeviceCreateInfo`. This trait is implemented for the structures of [`ash`] that do.
+pub unsafe trait ExtensionFeatureStruct: ExtendsCreateInfo<Device> + Copy + Default {}
+
+unsafe impl<S> ExtensionFeatureStruct for S where
+    S: ash::vk::ExtendsPhysicalDeviceFeatures2 + ash::vk::ExtendsDeviceCreateInfo + Copy + Default
+{
+}
+
+// Returns the `s_type` of a feature structure.
+pub(crate) fn s_type_of<S>() -> ash::vk::StructureType
+where
+    S: ExtensionFeatureStruct,
+{
+    let features = S::default();
+    unsafe { *(&features as *const S as *const ash::vk::StructureType) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExtensionFeatures, FeatureRestriction, FeatureRestrictionError, Features};
+    use crate::{device::DeviceExtensions, instance::InstanceExtensions, Version};
+
+    #[test]
+    fn extension_features_replace() {
+        let mut extension_features = ExtensionFeatures::new();
+
+        unsafe {
+            extension_features
+                .set(ash::vk::PhysicalDeviceMeshShaderFeaturesNV {
+                    mesh_shader: ash::vk::TRUE,
+                    ..Default::default()
+                })
+                .set(ash::vk::PhysicalDeviceMeshShaderFeaturesNV {
+                    task_shader: ash::vk::TRUE,
+                    ..Default::default()
+                });
+        }
+
+        assert_eq!(extension_features.len(), 1);
+
+        let mesh_shader = extension_features
+            .get::<ash::vk::PhysicalDeviceMeshShaderFeaturesNV>()
+            .unwrap();
+        assert_eq!(mesh_shader.mesh_shader, ash::vk::FALSE);
+        assert_eq!(mesh_shader.task_shader, ash::vk::TRUE);
+
+        assert!(extension_features
+            .get::<ash::vk::PhysicalDeviceShaderClockFeaturesKHR>()
+            .is_none());
+    }
+
+    #[test]
+    fn provided() {
+        let instance_extensions = InstanceExtensions::none();
+        let extensions = DeviceExtensions {
+            khr_dynamic_rendering: true,
+            ..DeviceExtensions::none()
+        };
+
+        let provided = Features::provided(
+            Version::V1_0,
+            &DeviceExtensions::none(),
+            &instance_extensions,
+        );
+        assert!(provided.geometry_shader);
+        assert!(!provided.dynamic_rendering);
+
+        let provided = Features::provided(Version::V1_0, &extensions, &instance_extensions);
+        assert!(provided.dynamic_rendering);
+
+        let provided = Features::provided(
+            Version::V1_3,
+            &DeviceExtensions::none(),
+            &instance_extensions,
+        );
+        assert!(provided.dynamic_rendering);
+    }
+
+    #[test]
+    fn unavailable() {
+        let features = Features {
+            geometry_shader: true,
+            dynamic_rendering: true,
+            ..Features::none()
+        };
+
+        let unavailable = features.unavailable(
+            &Features::none(),
+            Version::V1_3,
+            &DeviceExtensions::none(),
+            &InstanceExtensions::none(),
+        );
+        assert_eq!(unavailable.len(), 2);
+        assert!(unavailable
+            .iter()
+            .all(|err| matches!(err.restriction, FeatureRestriction::NotSupported)));
+
+        let unavailable = features.unavailable(
+            &Features::all(),
+            Version::V1_2,
+            &DeviceExtensions::none(),
+            &InstanceExtensions::none(),
+        );
+        match unavailable[..] {
+            [FeatureRestrictionError {
+                feature: "dynamic_rendering",
+                restriction: FeatureRestriction::Requires(requires),
+            }] => {
+                assert_eq!(requires.api_version, Some(Version::V1_3));
+                assert!(requires
+                    .device_extensions
+                    .contains(&"khr_dynamic_rendering"));
+            }
+            _ => panic!(),
         }
     }
 }
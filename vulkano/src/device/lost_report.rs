@@ -0,0 +1,453 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Reports of what the device was doing when it was lost.
+//!
+//! When a device is lost, for example because a command buffer hung, it is hard to find out which
+//! commands were responsible, especially on the machines of end users. A [`DeviceLostReport`]
+//! gathers what vulkano and the device know into one value, that can be printed or, with the
+//! `serde` feature, serialized and attached to a bug report:
+//!
+//! - The recent submissions to the queues of the device, if
+//!   [`DeviceCreateInfo::submission_history_len`](crate::device::DeviceCreateInfo::submission_history_len)
+//!   is not 0. Each submission records the command buffers that were submitted and the queue debug
+//!   labels that were open at the time.
+//! - The queue debug labels that are open.
+//! - The last checkpoints that the device reached on each queue, if the
+//!   [`nv_device_diagnostic_checkpoints`](crate::device::DeviceExtensions::nv_device_diagnostic_checkpoints)
+//!   extension is enabled. Checkpoints are inserted into command buffers with
+//!   [`set_checkpoint`](crate::command_buffer::AutoCommandBufferBuilder::set_checkpoint).
+//! - The fault that caused the loss of the device, as reported by the implementation, if the
+//!   [`ext_device_fault`](crate::device::DeviceExtensions::ext_device_fault) extension and the
+//!   [`device_fault`](crate::device::Features::device_fault) feature are enabled. See
+//!   [`Device::fault_info`].
+//!
+//! ```
+//! use vulkano::device::lost_report::DeviceLostReport;
+//!
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+//! if device.is_lost() {
+//!     let report = DeviceLostReport::new(&device, [&queue]);
+//!     eprintln!("{}", report);
+//! }
+//! ```
+
+use crate::{
+    device::{Device, Queue},
+    DeviceSize,
+};
+use std::{fmt, sync::Arc, time::Duration};
+
+/// What vulkano and the device know about the state of the device, to diagnose its loss.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct DeviceLostReport {
+    /// The name of the physical device.
+    pub device_name: String,
+
+    /// The vendor ID of the physical device.
+    pub vendor_id: u32,
+
+    /// The device ID of the physical device.
+    pub device_id: u32,
+
+    /// The version of the driver, in a vendor-specific format.
+    pub driver_version: u32,
+
+    /// The Vulkan version of the device.
+    pub api_version: String,
+
+    /// Whether the device was lost when the report was made.
+    pub lost: bool,
+
+    /// The time between the creation of the device and the report.
+    pub time: Duration,
+
+    /// The most recent submissions, oldest first.
+    pub submissions: Vec<SubmissionRecord>,
+
+    /// The state of the queues that were given to [`DeviceLostReport::new`].
+    pub queues: Vec<QueueReport>,
+
+    /// The fault that caused the loss of the device, if the device is lost and the
+    /// implementation could report it.
+    pub fault_info: Option<DeviceFaultInfo>,
+}
+
+impl DeviceLostReport {
+    /// Makes a report of the state of `device` and of `queues`.
+    ///
+    /// The checkpoints of the queues are only available once the device is lost.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if one of `queues` doesn't belong to `device`.
+    pub fn new<'a>(
+        device: &Device,
+        queues: impl IntoIterator<Item = &'a Arc<Queue>>,
+    ) -> DeviceLostReport {
+        let properties = device.physical_device().properties();
+
+        let queues = queues
+            .into_iter()
+            .map(|queue| {
+                assert!(
+                    &**queue.device() as *const Device == device as *const Device,
+                    "the queues must belong to the device"
+                );
+
+                QueueReport {
+                    queue_family_index: queue.family().id(),
+                    queue_index: queue.id_within_family(),
+                    labels: queue.debug_utils_labels(),
+                    // Not available if the extension is not enabled or the device is not lost.
+                    checkpoints: queue.checkpoint_data().unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        DeviceLostReport {
+            device_name: properties.device_name.clone(),
+            vendor_id: properties.vendor_id,
+            device_id: properties.device_id,
+            driver_version: properties.driver_version,
+            api_version: device.api_version().to_string(),
+            lost: device.is_lost(),
+            time: device.time_since_creation(),
+            submissions: device.submission_history(),
+            queues,
+            // Not available if the extension is not enabled or the device is not lost.
+            fault_info: device.fault_info().ok(),
+        }
+    }
+}
+
+impl fmt::Display for DeviceLostReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        writeln!(
+            f,
+            "device: {} (vendor {:#x}, device {:#x}, driver {:#x}, Vulkan {})",
+            self.device_name, self.vendor_id, self.device_id, self.driver_version, self.api_version,
+        )?;
+        writeln!(f, "lost: {}, at {:?}", self.lost, self.time)?;
+
+        writeln!(f, "submissions:")?;
+        for submission in &self.submissions {
+            writeln!(
+                f,
+                "  at {:?} to queue {}.{}: command buffers {:x?}, {} wait semaphores, {} signal \
+                semaphores, labels {:?}",
+                submission.time,
+                submission.queue_family_index,
+                submission.queue_index,
+                submission.command_buffers,
+                submission.wait_semaphore_count,
+                submission.signal_semaphore_count,
+                submission.queue_labels,
+            )?;
+        }
+
+        for queue in &self.queues {
+            writeln!(
+                f,
+                "queue {}.{}: labels {:?}",
+                queue.queue_family_index, queue.queue_index, queue.labels,
+            )?;
+
+            for checkpoint in &queue.checkpoints {
+                writeln!(
+                    f,
+                    "  checkpoint {:#x} reached {}",
+                    checkpoint.marker, checkpoint.stage,
+                )?;
+            }
+        }
+
+        if let Some(fault_info) = &self.fault_info {
+            writeln!(
+                f,
+                "fault: {} ({} addresses, {} vendor infos, {} bytes of vendor binary data)",
+                fault_info.description,
+                fault_info.address_infos.len(),
+                fault_info.vendor_infos.len(),
+                fault_info.vendor_binary_data.len(),
+            )?;
+
+            for address_info in &fault_info.address_infos {
+                writeln!(
+                    f,
+                    "  {:?} at {:#x} (precision {:#x})",
+                    address_info.address_type,
+                    address_info.reported_address,
+                    address_info.address_precision,
+                )?;
+            }
+
+            for vendor_info in &fault_info.vendor_infos {
+                writeln!(
+                    f,
+                    "  {}: code {:#x}, data {:#x}",
+                    vendor_info.description,
+                    vendor_info.vendor_fault_code,
+                    vendor_info.vendor_fault_data,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A submission of command buffers to a queue, as recorded by the device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct SubmissionRecord {
+    /// The time between the creation of the device and the submission.
+    pub time: Duration,
+
+    /// The index of the queue family of the queue.
+    pub queue_family_index: u32,
+
+    /// The index of the queue within its family.
+    pub queue_index: u32,
+
+    /// The raw handles of the command buffers, in the order in which they were submitted. These
+    /// are the handles that the validation layers and the debug names refer to.
+    pub command_buffers: Vec<u64>,
+
+    /// The number of semaphores that the submission waited on.
+    pub wait_semaphore_count: usize,
+
+    /// The number of semaphores that the submission signaled.
+    pub signal_semaphore_count: usize,
+
+    /// The queue debug labels that were open at the time of the submission, outermost first.
+    pub queue_labels: Vec<String>,
+}
+
+/// The state of a queue in a [`DeviceLostReport`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct QueueReport {
+    /// The index of the queue family of the queue.
+    pub queue_family_index: u32,
+
+    /// The index of the queue within its family.
+    pub queue_index: u32,
+
+    /// The queue debug labels that are open, outermost first.
+    pub labels: Vec<String>,
+
+    /// The last checkpoints that the device reached on the queue.
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+/// A checkpoint that the device reached on a queue, returned by
+/// [`Queue::checkpoint_data`](crate::device::Queue::checkpoint_data).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Checkpoint {
+    /// The marker that was given to
+    /// [`set_checkpoint`](crate::command_buffer::AutoCommandBufferBuilder::set_checkpoint).
+    pub marker: u64,
+
+    /// The name of the pipeline stage that the device reached for the checkpoint, such as
+    /// `TOP_OF_PIPE` or `BOTTOM_OF_PIPE`.
+    pub stage: String,
+}
+
+/// The fault that caused the loss of a device, returned by
+/// [`Device::fault_info`](crate::device::Device::fault_info).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct DeviceFaultInfo {
+    /// A description of the fault, chosen by the implementation.
+    pub description: String,
+
+    /// The addresses that were accessed by the faulting operations, or of the instructions that
+    /// caused the fault.
+    pub address_infos: Vec<DeviceFaultAddressInfo>,
+
+    /// Vendor-specific information about the fault.
+    pub vendor_infos: Vec<DeviceFaultVendorInfo>,
+
+    /// Vendor-specific binary crash dump data, that can be given to the vendor of the device. It
+    /// starts with a header whose layout is described by
+    /// `VkDeviceFaultVendorBinaryHeaderVersionOneEXT`.
+    ///
+    /// This is only returned if the
+    /// [`device_fault_vendor_binary`](crate::device::Features::device_fault_vendor_binary)
+    /// feature is enabled on the device.
+    pub vendor_binary_data: Vec<u8>,
+}
+
+/// An address related to the fault that caused the loss of a device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DeviceFaultAddressInfo {
+    /// How the address is related to the fault.
+    pub address_type: DeviceFaultAddressType,
+
+    /// The address that was reported by the implementation.
+    pub reported_address: DeviceSize,
+
+    /// The precision of `reported_address`. The actual address lies within the range of addresses
+    /// that are aligned to this value and contain `reported_address`. It is a power of two.
+    pub address_precision: DeviceSize,
+}
+
+/// How an address is related to the fault that caused the loss of a device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+#[repr(i32)]
+pub enum DeviceFaultAddressType {
+    /// The address has no meaning.
+    None = ash::vk::DeviceFaultAddressTypeEXT::NONE.as_raw(),
+
+    /// The address was read, but was not valid for reading.
+    ReadInvalid = ash::vk::DeviceFaultAddressTypeEXT::READ_INVALID.as_raw(),
+
+    /// The address was written, but was not valid for writing.
+    WriteInvalid = ash::vk::DeviceFaultAddressTypeEXT::WRITE_INVALID.as_raw(),
+
+    /// The address was executed, but was not valid for executing.
+    ExecuteInvalid = ash::vk::DeviceFaultAddressTypeEXT::EXECUTE_INVALID.as_raw(),
+
+    /// The address is the address of an instruction that was executing when the fault happened,
+    /// but that isn't known to be related to the fault.
+    InstructionPointerUnknown =
+        ash::vk::DeviceFaultAddressTypeEXT::INSTRUCTION_POINTER_UNKNOWN.as_raw(),
+
+    /// The address is the address of an instruction that was not valid.
+    InstructionPointerInvalid =
+        ash::vk::DeviceFaultAddressTypeEXT::INSTRUCTION_POINTER_INVALID.as_raw(),
+
+    /// The address is the address of the instruction that caused the fault.
+    InstructionPointerFault =
+        ash::vk::DeviceFaultAddressTypeEXT::INSTRUCTION_POINTER_FAULT.as_raw(),
+}
+
+impl TryFrom<ash::vk::DeviceFaultAddressTypeEXT> for DeviceFaultAddressType {
+    type Error = ();
+
+    #[inline]
+    fn try_from(val: ash::vk::DeviceFaultAddressTypeEXT) -> Result<Self, Self::Error> {
+        Ok(match val {
+            ash::vk::DeviceFaultAddressTypeEXT::NONE => Self::None,
+            ash::vk::DeviceFaultAddressTypeEXT::READ_INVALID => Self::ReadInvalid,
+            ash::vk::DeviceFaultAddressTypeEXT::WRITE_INVALID => Self::WriteInvalid,
+            ash::vk::DeviceFaultAddressTypeEXT::EXECUTE_INVALID => Self::ExecuteInvalid,
+            ash::vk::DeviceFaultAddressTypeEXT::INSTRUCTION_POINTER_UNKNOWN => {
+                Self::InstructionPointerUnknown
+            }
+            ash::vk::DeviceFaultAddressTypeEXT::INSTRUCTION_POINTER_INVALID => {
+                Self::InstructionPointerInvalid
+            }
+            ash::vk::DeviceFaultAddressTypeEXT::INSTRUCTION_POINTER_FAULT => {
+                Self::InstructionPointerFault
+            }
+            _ => return Err(()),
+        })
+    }
+}
+
+/// Vendor-specific information about the fault that caused the loss of a device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DeviceFaultVendorInfo {
+    /// A description of the fault, chosen by the vendor.
+    pub description: String,
+
+    /// A vendor-specific fault code.
+    pub vendor_fault_code: u64,
+
+    /// Vendor-specific data associated with the fault.
+    pub vendor_fault_data: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeviceFaultAddressType, DeviceLostReport};
+    use crate::device::{Device, DeviceCreateInfo, QueueCreateInfo};
+
+    #[test]
+    fn report() {
+        use ash::vk::Handle;
+
+        let (device, queue) = gfx_dev_and_queue!();
+        let (device, mut queues) = Device::new(
+            device.physical_device(),
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo::family(queue.family())],
+                submission_history_len: 4,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let queue = queues.next().unwrap();
+
+        device.record_submission(&queue, &[ash::vk::CommandBuffer::from_raw(0x42)], 1, 2);
+
+        let report = DeviceLostReport::new(&device, [&queue]);
+        let properties = device.physical_device().properties();
+
+        assert_eq!(report.device_name, properties.device_name);
+        assert_eq!(report.vendor_id, properties.vendor_id);
+        assert_eq!(report.api_version, device.api_version().to_string());
+        assert!(!report.lost);
+        assert_eq!(report.submissions, device.submission_history());
+        assert_eq!(report.queues.len(), 1);
+        assert_eq!(report.queues[0].queue_family_index, queue.family().id());
+        assert_eq!(report.queues[0].queue_index, queue.id_within_family());
+
+        // Only available once the device is lost.
+        assert!(report.queues[0].checkpoints.is_empty());
+        assert!(report.fault_info.is_none());
+
+        let text = report.to_string();
+        assert!(text.contains(&properties.device_name));
+        assert!(text.contains("command buffers [42], 1 wait semaphores, 2 signal semaphores"));
+    }
+
+    #[test]
+    fn report_queue_of_other_device() {
+        let (device, _) = gfx_dev_and_queue!();
+        let (_, queue) = gfx_dev_and_queue!();
+
+        assert_should_panic!("the queues must belong to the device", {
+            DeviceLostReport::new(&device, [&queue]);
+        });
+    }
+
+    #[test]
+    fn fault_address_type() {
+        assert_eq!(
+            DeviceFaultAddressType::try_from(ash::vk::DeviceFaultAddressTypeEXT::WRITE_INVALID),
+            Ok(DeviceFaultAddressType::WriteInvalid)
+        );
+        assert_eq!(
+            DeviceFaultAddressType::try_from(
+                ash::vk::DeviceFaultAddressTypeEXT::INSTRUCTION_POINTER_FAULT
+            ),
+            Ok(DeviceFaultAddressType::InstructionPointerFault)
+        );
+        assert_eq!(
+            DeviceFaultAddressType::try_from(ash::vk::DeviceFaultAddressTypeEXT::from_raw(100)),
+            Err(())
+        );
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Reports of the device memory that the implementation allocates and frees.
+//!
+//! Besides the memory that you allocate explicitly, the implementation can allocate device memory
+//! on its own for objects such as pipelines or descriptor pools. With the
+//! [`ext_device_memory_report`](crate::device::DeviceExtensions::ext_device_memory_report)
+//! extension, you can register callbacks when creating a device, that are called with a
+//! [`DeviceMemoryReportEvent`] every time device memory is allocated, freed, imported or
+//! unimported, or an allocation fails.
+//!
+//! The callbacks are called from inside Vulkan commands, so they must not make any calls to the
+//! Vulkan API themselves. To process the events elsewhere, send them over a channel:
+//!
+//! ```no_run
+//! use std::sync::{mpsc, Arc, Mutex};
+//! use vulkano::device::{
+//!     memory_report::{DeviceMemoryReportCallback, DeviceMemoryReportEvent},
+//!     Device, DeviceCreateInfo, DeviceExtensions, Features,
+//! };
+//! # let physical_device: vulkano::device::physical::PhysicalDevice = return;
+//! # let create_info: DeviceCreateInfo = return;
+//!
+//! let (sender, receiver) = mpsc::channel::<DeviceMemoryReportEvent>();
+//! let sender = Mutex::new(sender);
+//! let callback: DeviceMemoryReportCallback = Arc::new(move |event| {
+//!     let _ = sender.lock().unwrap().send(event.clone());
+//! });
+//!
+//! let (device, queues) = unsafe {
+//!     Device::with_memory_report_callbacks(
+//!         physical_device,
+//!         DeviceCreateInfo {
+//!             enabled_extensions: DeviceExtensions {
+//!                 ext_device_memory_report: true,
+//!                 ..create_info.enabled_extensions
+//!             },
+//!             enabled_features: Features {
+//!                 device_memory_report: true,
+//!                 ..create_info.enabled_features
+//!             },
+//!             ..create_info
+//!         },
+//!         [callback],
+//!     )
+//!     .unwrap()
+//! };
+//!
+//! for event in receiver.try_iter() {
+//!     println!("{:?}", event);
+//! }
+//! ```
+
+use crate::DeviceSize;
+use std::{
+    ffi::c_void,
+    fmt,
+    panic::{catch_unwind, AssertUnwindSafe, RefUnwindSafe},
+    sync::Arc,
+};
+
+/// A callback that is called with device memory report events.
+///
+/// The callback is provided inside an `Arc` so that it can be shared across multiple devices.
+pub type DeviceMemoryReportCallback =
+    Arc<dyn Fn(&DeviceMemoryReportEvent) + RefUnwindSafe + Send + Sync>;
+
+/// An event reported by a device memory report callback.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DeviceMemoryReportEvent {
+    /// What happened to the memory.
+    pub ty: DeviceMemoryReportEventType,
+
+    /// A unique identifier of the memory object. The same identifier is used for the events of
+    /// the same memory object, for example when it is allocated and then freed.
+    pub memory_object_id: u64,
+
+    /// The size of the memory object in bytes. This is `0` for free and unimport events.
+    pub size: DeviceSize,
+
+    /// The Vulkan type of the object that the memory is associated with.
+    pub object_type: ash::vk::ObjectType,
+
+    /// The raw handle of the object that the memory is associated with, or `0` if there is no
+    /// handle, or the handle is not known.
+    pub object_handle: u64,
+
+    /// The index of the memory heap that the memory was allocated from. This is only meaningful
+    /// for allocate, import and allocation failed events.
+    pub heap_index: u32,
+}
+
+/// The kind of event reported by a device memory report callback.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum DeviceMemoryReportEventType {
+    /// Memory was allocated.
+    Allocate = ash::vk::DeviceMemoryReportEventTypeEXT::ALLOCATE.as_raw(),
+
+    /// Memory was freed.
+    Free = ash::vk::DeviceMemoryReportEventTypeEXT::FREE.as_raw(),
+
+    /// External memory was imported.
+    Import = ash::vk::DeviceMemoryReportEventTypeEXT::IMPORT.as_raw(),
+
+    /// Imported external memory was released.
+    Unimport = ash::vk::DeviceMemoryReportEventTypeEXT::UNIMPORT.as_raw(),
+
+    /// Allocating memory failed.
+    AllocationFailed = ash::vk::DeviceMemoryReportEventTypeEXT::ALLOCATION_FAILED.as_raw(),
+}
+
+impl TryFrom<ash::vk::DeviceMemoryReportEventTypeEXT> for DeviceMemoryReportEventType {
+    type Error = ();
+
+    #[inline]
+    fn try_from(val: ash::vk::DeviceMemoryReportEventTypeEXT) -> Result<Self, Self::Error> {
+        Ok(match val {
+            ash::vk::DeviceMemoryReportEventTypeEXT::ALLOCATE => Self::Allocate,
+            ash::vk::DeviceMemoryReportEventTypeEXT::FREE => Self::Free,
+            ash::vk::DeviceMemoryReportEventTypeEXT::IMPORT => Self::Import,
+            ash::vk::DeviceMemoryReportEventTypeEXT::UNIMPORT => Self::Unimport,
+            ash::vk::DeviceMemoryReportEventTypeEXT::ALLOCATION_FAILED => Self::AllocationFailed,
+            _ => return Err(()),
+        })
+    }
+}
+
+// The callbacks registered on a device. They are boxed so that their address, which is given to
+// the implementation as user data, stays the same for the lifetime of the device.
+pub(super) struct MemoryReportCallbacks(pub(super) Vec<Box<DeviceMemoryReportCallback>>);
+
+impl fmt::Debug for MemoryReportCallbacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("MemoryReportCallbacks")
+            .field("len", &self.0.len())
+            .finish_non_exhaustive()
+    }
+}
+
+pub(super) unsafe extern "system" fn trampoline(
+    callback_data: *const ash::vk::DeviceMemoryReportCallbackDataEXT,
+    user_data: *mut c_void,
+) {
+    // Since we box the closure, the type system doesn't detect that the `UnwindSafe`
+    // bound is enforced. Therefore we enforce it manually.
+    let _ = catch_unwind(AssertUnwindSafe(move || {
+        let user_callback = user_data as *mut DeviceMemoryReportCallback as *const _;
+        let user_callback: &DeviceMemoryReportCallback = &*user_callback;
+        let callback_data = &*callback_data;
+
+        // Event types added by later versions of the extension are skipped.
+        let ty = match DeviceMemoryReportEventType::try_from(callback_data.ty) {
+            Ok(ty) => ty,
+            Err(()) => return,
+        };
+
+        let event = DeviceMemoryReportEvent {
+            ty,
+            memory_object_id: callback_data.memory_object_id,
+            size: callback_data.size,
+            object_type: callback_data.object_type,
+            object_handle: callback_data.object_handle,
+            heap_index: callback_data.heap_index,
+        };
+
+        user_callback(&event);
+    }));
+}
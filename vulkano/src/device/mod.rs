@@ -95,51 +95,128 @@
 //!
 //! TODO: write
 
-use self::physical::{PhysicalDevice, QueueFamily};
+use self::lost_report::{
+    Checkpoint, DeviceFaultAddressInfo, DeviceFaultInfo, DeviceFaultVendorInfo, SubmissionRecord,
+};
+use self::memory_report::{DeviceMemoryReportCallback, MemoryReportCallbacks};
+use self::object_tracker::{ObjectTracker, TrackedObject, TrackedObjectType};
+use self::physical::{PhysicalDevice, PhysicalDeviceGroup, QueueFamily, SurfacePropertiesError};
 pub(crate) use self::{features::FeaturesFfi, properties::PropertiesFfi};
 pub use self::{
-    features::{FeatureRestriction, FeatureRestrictionError, Features},
+    features::{
+        ExtensionFeatureStruct, ExtensionFeatures, FeatureRestriction, FeatureRestrictionError,
+        Features,
+    },
     properties::Properties,
+    selector::{DeviceSelectionError, DeviceSelector, QueueRequirement, SelectedDevice},
 };
 use crate::{
     check_errors,
-    command_buffer::pool::StandardCommandPool,
+    command_buffer::{pool::StandardCommandPool, CheckpointError},
     descriptor_set::pool::StdDescriptorPool,
-    instance::{debug::DebugUtilsLabel, Instance},
+    extension_chain::ExtensionChain,
+    instance::{
+        debug::DebugUtilsLabel,
+        host_allocator::{callbacks_ptr, HostAllocationCallbacks, HostAllocator},
+        Instance,
+    },
     memory::{pool::StdMemoryPool, ExternalMemoryHandleType},
-    Error, OomError, SynchronizedVulkanObject, Version, VulkanObject,
+    swapchain::{DeviceGroupPresentCapabilities, DeviceGroupPresentModes, Surface},
+    Error, OomError, Success, SynchronizedVulkanObject, Version, VulkanObject,
 };
 pub use crate::{
     device::extensions::DeviceExtensions,
-    extensions::{ExtensionRestriction, ExtensionRestrictionError, SupportedExtensionsError},
+    extensions::{
+        ExtensionRestriction, ExtensionRestrictionError, OneOfRequirements,
+        SupportedExtensionsError,
+    },
     fns::DeviceFunctions,
 };
 use ash::vk::Handle;
-use smallvec::SmallVec;
+use smallvec::{smallvec, SmallVec};
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, VecDeque},
     error,
-    ffi::CString,
+    ffi::{c_void, CStr, CString},
     fmt,
     fs::File,
     hash::{Hash, Hasher},
     mem::{self, MaybeUninit},
     ops::Deref,
     ptr,
-    sync::{Arc, Mutex, MutexGuard, Weak},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard, Weak,
+    },
+    time::{Duration, Instant},
 };
 
 pub(crate) mod extensions;
 pub(crate) mod features;
+pub mod lost_report;
+pub mod memory_report;
+pub mod object_tracker;
 pub mod physical;
 pub(crate) mod properties;
+mod selector;
 
 /// Represents a Vulkan context.
+///
+/// # Device loss
+///
+/// A device can be lost at any time, for example if the driver resets the GPU after a command
+/// took too long to execute, or if the GPU is removed. Operations that execute work on the device
+/// or wait for it, such as flushing futures, waiting for fences or acquiring swapchain images,
+/// then return a `DeviceLost` error, and [`is_lost`](Device::is_lost) returns `true`. From then
+/// on, submitting more work to the queues of the device returns `DeviceLost` immediately, and
+/// dropping a future no longer waits for the work that it was waiting for.
+///
+/// A lost device can't be used anymore. It must be dropped, along with every object that was
+/// created from it. These objects, such as queues, buffers, images, pipelines, command buffers
+/// and futures, keep the device alive with an `Arc<Device>`, which they return from
+/// [`DeviceOwned::device`]. [`Arc::strong_count`] on the device tells how many references to it
+/// are still alive.
+///
+/// If the device was created with
+/// [`object_tracking`](DeviceCreateInfo::object_tracking), its
+/// [`object_tracker`](Device::object_tracker) enumerates the objects that are still alive on it,
+/// such as buffers, images, memory allocations, pipelines and synchronization primitives, by
+/// type and by the [tag](object_tracker::tag_scope) that they were created with. This tells which
+/// parts of the application still hold objects of the lost device.
+///
+/// [`recreate`](Device::recreate) creates a new device with the same parameters, from which the
+/// objects can be created again:
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use vulkano::{device::{Device, Queue}, extension_chain::ExtensionChain, sync::FlushError};
+/// # fn draw_frame(device: &Arc<Device>, queue: &Arc<Queue>) -> Result<(), FlushError> {
+/// #     unimplemented!()
+/// # }
+/// # let (mut device, mut queue): (Arc<Device>, Arc<Queue>) = unimplemented!();
+/// loop {
+///     match draw_frame(&device, &queue) {
+///         Err(FlushError::DeviceLost) => {
+///             // Drop the objects created from the old device. If it was created with object
+///             // tracking, the objects that are still alive can be listed with
+///             // `device.object_tracker().unwrap().snapshot()`.
+///
+///             let (new_device, mut queues) = device.recreate(ExtensionChain::new()).unwrap();
+///             device = new_device;
+///             queue = queues.next().unwrap();
+///
+///             // Create the objects again from `device`.
+///         }
+///         result => result.unwrap(),
+///     }
+/// }
+/// ```
 #[derive(Debug)]
 pub struct Device {
     handle: ash::vk::Device,
     instance: Arc<Instance>,
     physical_device: usize,
+    physical_devices: SmallVec<[usize; 2]>,
 
     // The highest version that is supported for this device.
     // This is the minimum of Instance::max_api_version and PhysicalDevice::api_version.
@@ -148,14 +225,36 @@ pub struct Device {
     fns: DeviceFunctions,
     standard_pool: Mutex<Weak<StdMemoryPool>>,
     standard_descriptor_pool: Mutex<Weak<StdDescriptorPool>>,
-    standard_command_pools: Mutex<HashMap<u32, Weak<StandardCommandPool>>>,
+    standard_command_pools: Mutex<HashMap<(u32, bool), Weak<StandardCommandPool>>>,
     enabled_extensions: DeviceExtensions,
     enabled_features: Features,
+    enabled_extension_features: ExtensionFeatures,
     active_queue_families: SmallVec<[u32; 2]>,
     allocation_count: Mutex<u32>,
     fence_pool: Mutex<Vec<ash::vk::Fence>>,
     semaphore_pool: Mutex<Vec<ash::vk::Semaphore>>,
     event_pool: Mutex<Vec<ash::vk::Event>>,
+    host_allocator: Option<HostAllocationCallbacks>,
+    memory_report_callbacks: MemoryReportCallbacks,
+    automatic_debug_names: bool,
+    object_tracker: Option<Arc<ObjectTracker>>,
+    created: Instant,
+    submission_history: Mutex<VecDeque<SubmissionRecord>>,
+    submission_history_len: usize,
+    lost: AtomicBool,
+    profiling_lock: AtomicBool,
+
+    // The parameters that are not kept elsewhere, to recreate the device.
+    queue_family_params: SmallVec<[QueueFamilyParams; 2]>,
+}
+
+// The parameters that a queue family was given in `DeviceCreateInfo::queue_create_infos`.
+#[derive(Clone, Debug)]
+struct QueueFamilyParams {
+    family: u32,
+    queues: Vec<f32>,
+    protected: bool,
+    global_priority: Option<QueueGlobalPriority>,
 }
 
 // The `StandardCommandPool` type doesn't implement Send/Sync, so we have to manually reimplement
@@ -166,6 +265,10 @@ unsafe impl Sync for Device {}
 impl Device {
     /// Creates a new `Device`.
     ///
+    /// Along with the device, returns the queues that were created, in the order of
+    /// `create_info.queue_create_infos`, and for each of them in the order of
+    /// [`QueueCreateInfo::queues`].
+    ///
     /// # Panics
     ///
     /// - Panics if `create_info.queues` is empty.
@@ -175,14 +278,107 @@ impl Device {
     /// - Panics if `create_info.queues` contains an element where `queues` is empty.
     /// - Panics if `create_info.queues` contains an element where `queues` contains a value that is
     ///   not between 0.0 and 1.0 inclusive.
+    /// - Panics if `create_info.physical_devices` contains the same physical device more than
+    ///   once, or a physical device of another instance.
+    #[inline]
     pub fn new(
         physical_device: PhysicalDevice,
         create_info: DeviceCreateInfo,
+    ) -> Result<(Arc<Device>, impl ExactSizeIterator<Item = Arc<Queue>>), DeviceCreationError> {
+        unsafe { Self::with_memory_report_callbacks(physical_device, create_info, []) }
+    }
+
+    /// Creates a new `Device`, with callbacks that are called when the implementation allocates
+    /// or frees device memory. See the [`memory_report`] module for more information.
+    ///
+    /// If `memory_report_callbacks` is not empty, the `ext_device_memory_report` extension and
+    /// the [`device_memory_report`](crate::device::Features::device_memory_report) feature must
+    /// be enabled in `create_info`.
+    ///
+    /// # Safety
+    ///
+    /// - The callbacks in `memory_report_callbacks` must not make any calls to the Vulkan API.
+    ///
+    /// # Panics
+    ///
+    /// - Panics for the same reasons as [`Device::new`].
+    pub unsafe fn with_memory_report_callbacks(
+        physical_device: PhysicalDevice,
+        create_info: DeviceCreateInfo,
+        memory_report_callbacks: impl IntoIterator<Item = DeviceMemoryReportCallback>,
+    ) -> Result<(Arc<Device>, impl ExactSizeIterator<Item = Arc<Queue>>), DeviceCreationError> {
+        let fns_i = physical_device.instance().fns();
+
+        Self::new_inner(
+            physical_device,
+            create_info,
+            memory_report_callbacks,
+            |create_info, allocator, output| {
+                (fns_i.v1_0.create_device)(
+                    physical_device.internal_object(),
+                    create_info,
+                    allocator,
+                    output,
+                )
+            },
+        )
+    }
+
+    /// Creates a new `Device`, calling `create_fn` instead of `vkCreateDevice` to create the
+    /// Vulkan device.
+    ///
+    /// This is needed when the device must be created by another library, such as an OpenXR
+    /// runtime with `xrCreateVulkanDeviceKHR`. `create_fn` receives the parameters that would
+    /// have been passed to `vkCreateDevice` after the physical device, and must return its
+    /// result.
+    ///
+    /// # Panics
+    ///
+    /// - Panics for the same reasons as [`Device::new`].
+    ///
+    /// # Safety
+    ///
+    /// - `create_fn` must create the device for `physical_device`, from the
+    ///   `ash::vk::DeviceCreateInfo` it is given. It may enable additional extensions and
+    ///   features, which vulkano will not know about.
+    /// - If `create_fn` returns `SUCCESS`, it must have written a valid device handle to the
+    ///   output pointer.
+    pub unsafe fn with_create_fn<F>(
+        physical_device: PhysicalDevice,
+        create_info: DeviceCreateInfo,
+        create_fn: F,
+    ) -> Result<(Arc<Device>, impl ExactSizeIterator<Item = Arc<Queue>>), DeviceCreationError>
+    where
+        F: FnOnce(
+            &ash::vk::DeviceCreateInfo,
+            *const ash::vk::AllocationCallbacks,
+            *mut ash::vk::Device,
+        ) -> ash::vk::Result,
+    {
+        Self::new_inner(physical_device, create_info, [], create_fn)
+    }
+
+    unsafe fn new_inner(
+        physical_device: PhysicalDevice,
+        create_info: DeviceCreateInfo,
+        memory_report_callbacks: impl IntoIterator<Item = DeviceMemoryReportCallback>,
+        create_fn: impl FnOnce(
+            &ash::vk::DeviceCreateInfo,
+            *const ash::vk::AllocationCallbacks,
+            *mut ash::vk::Device,
+        ) -> ash::vk::Result,
     ) -> Result<(Arc<Device>, impl ExactSizeIterator<Item = Arc<Queue>>), DeviceCreationError> {
         let DeviceCreateInfo {
-            enabled_extensions,
+            mut enabled_extensions,
             mut enabled_features,
+            extension_features,
+            physical_devices,
+            extensions,
+            host_allocator,
             queue_create_infos,
+            automatic_debug_names,
+            object_tracking,
+            submission_history_len,
             _ne: _,
         } = create_info;
 
@@ -197,6 +393,9 @@ impl Device {
         struct QueueToGet {
             family: u32,
             id: u32,
+            priority: f32,
+            global_priority: Option<QueueGlobalPriority>,
+            protected: bool,
         }
 
         // VUID-VkDeviceCreateInfo-queueCreateInfoCount-arraylength
@@ -207,10 +406,16 @@ impl Device {
         let mut active_queue_families: SmallVec<[_; 2]> =
             SmallVec::with_capacity(queue_create_infos.len());
         let mut queues_to_get: SmallVec<[_; 2]> = SmallVec::with_capacity(queue_create_infos.len());
+        let mut global_priority_create_infos_vk: SmallVec<[_; 2]> =
+            SmallVec::with_capacity(queue_create_infos.len());
+        let mut queue_family_params: SmallVec<[_; 2]> =
+            SmallVec::with_capacity(queue_create_infos.len());
 
-        for QueueCreateInfo {
+        for &QueueCreateInfo {
             family,
-            queues,
+            ref queues,
+            protected,
+            global_priority,
             _ne: _,
         } in &queue_create_infos
         {
@@ -223,7 +428,7 @@ impl Device {
             assert!(
                 queue_create_infos
                     .iter()
-                    .filter(|qc2| qc2.family == *family)
+                    .filter(|qc2| qc2.family == family)
                     .count()
                     == 1
             );
@@ -240,16 +445,80 @@ impl Device {
                 return Err(DeviceCreationError::TooManyQueuesForFamily);
             }
 
+            let mut flags = ash::vk::DeviceQueueCreateFlags::empty();
+
+            if protected {
+                // VUID-VkDeviceQueueCreateInfo-flags-06449
+                if !family.supports_protected() {
+                    return Err(DeviceCreationError::QueueFamilyProtectedNotSupported);
+                }
+
+                flags |= ash::vk::DeviceQueueCreateFlags::PROTECTED;
+            }
+
+            if let Some(global_priority) = global_priority {
+                if !(enabled_extensions.khr_global_priority
+                    || enabled_extensions.ext_global_priority)
+                {
+                    return Err(DeviceCreationError::ExtensionNotEnabled {
+                        extension: "khr_global_priority",
+                        reason: "one of queue_create_infos had global_priority set",
+                    });
+                }
+
+                // With this feature, the implementation must support creating queues with any of
+                // the priorities that it reports.
+                if enabled_features.global_priority_query
+                    && !family
+                        .supported_global_priorities()
+                        .contains(&global_priority)
+                {
+                    return Err(DeviceCreationError::QueueFamilyGlobalPriorityNotSupported {
+                        global_priority,
+                    });
+                }
+            }
+
+            global_priority_create_infos_vk.push(global_priority.map(|global_priority| {
+                ash::vk::DeviceQueueGlobalPriorityCreateInfoKHR {
+                    global_priority: global_priority.into(),
+                    ..Default::default()
+                }
+            }));
+
             let family = family.id();
+            queue_family_params.push(QueueFamilyParams {
+                family,
+                queues: queues.clone(),
+                protected,
+                global_priority,
+            });
             queue_create_infos_vk.push(ash::vk::DeviceQueueCreateInfo {
-                flags: ash::vk::DeviceQueueCreateFlags::empty(),
+                flags,
                 queue_family_index: family,
                 queue_count: queues.len() as u32,
                 p_queue_priorities: queues.as_ptr(), // borrows from queue_create
                 ..Default::default()
             });
             active_queue_families.push(family);
-            queues_to_get.extend((0..queues.len() as u32).map(move |id| QueueToGet { family, id }));
+            queues_to_get.extend(queues.iter().enumerate().map(move |(id, &priority)| {
+                QueueToGet {
+                    family,
+                    id: id as u32,
+                    priority,
+                    global_priority,
+                    protected,
+                }
+            }));
+        }
+
+        for (queue_create_info_vk, global_priority_create_info_vk) in queue_create_infos_vk
+            .iter_mut()
+            .zip(global_priority_create_infos_vk.iter())
+        {
+            if let Some(global_priority_create_info_vk) = global_priority_create_info_vk {
+                queue_create_info_vk.p_next = global_priority_create_info_vk as *const _ as _;
+            }
         }
 
         active_queue_families.sort_unstable();
@@ -259,9 +528,20 @@ impl Device {
             Extensions
         */
 
+        // VUID-VkDeviceCreateInfo-pProperties-04451
+        // The extensions that must be enabled if they are supported, such as
+        // `khr_portability_subset`, are enabled automatically.
+        enabled_extensions = enabled_extensions.union(physical_device.required_extensions());
+
+        // The extensions of promoted features are enabled automatically as well.
+        enabled_extensions = enabled_extensions.union(&promoted_feature_extensions(
+            &enabled_features,
+            api_version,
+            physical_device.supported_extensions(),
+        ));
+
         // VUID-VkDeviceCreateInfo-ppEnabledExtensionNames-01840
         // VUID-VkDeviceCreateInfo-ppEnabledExtensionNames-03328
-        // VUID-VkDeviceCreateInfo-pProperties-04451
         enabled_extensions.check_requirements(
             physical_device.supported_extensions(),
             api_version,
@@ -319,6 +599,73 @@ impl Device {
             &enabled_extensions,
         )?;
 
+        /*
+            Device group
+        */
+
+        let mut physical_devices_vk: SmallVec<[_; 2]> = SmallVec::new();
+
+        if !physical_devices.is_empty() {
+            if !(instance.api_version() >= Version::V1_1
+                || instance.enabled_extensions().khr_device_group_creation)
+            {
+                return Err(DeviceCreationError::ExtensionNotEnabled {
+                    extension: "khr_device_group_creation",
+                    reason: "physical_devices was not empty",
+                });
+            }
+
+            for (index, device) in physical_devices.iter().enumerate() {
+                // VUID-VkDeviceGroupDeviceCreateInfo-commonparent
+                assert!(Arc::ptr_eq(device.instance(), instance));
+
+                // VUID-VkDeviceGroupDeviceCreateInfo-pPhysicalDevices-00375
+                assert!(!physical_devices[..index]
+                    .iter()
+                    .any(|other| other.index() == device.index()));
+            }
+
+            // VUID-VkDeviceGroupDeviceCreateInfo-physicalDeviceCount-00377
+            if !physical_devices
+                .iter()
+                .any(|device| device.index() == physical_device.index())
+            {
+                return Err(DeviceCreationError::PhysicalDeviceNotInPhysicalDevices);
+            }
+
+            // VUID-VkDeviceGroupDeviceCreateInfo-pPhysicalDevices-00376
+            let in_same_group = |group: &PhysicalDeviceGroup| {
+                physical_devices.iter().all(|device| {
+                    group
+                        .physical_devices()
+                        .iter()
+                        .any(|other| other.index() == device.index())
+                })
+            };
+
+            if !PhysicalDevice::enumerate_groups(instance)?
+                .iter()
+                .any(in_same_group)
+            {
+                return Err(DeviceCreationError::PhysicalDevicesNotInSameGroup);
+            }
+
+            physical_devices_vk = physical_devices
+                .iter()
+                .map(|device| device.internal_object())
+                .collect();
+        }
+
+        // VUID-VkDeviceQueueCreateInfo-flags-02861
+        if queue_create_infos.iter().any(|info| info.protected)
+            && !enabled_features.protected_memory
+        {
+            return Err(DeviceCreationError::FeatureNotEnabled {
+                feature: "protected_memory",
+                reason: "one of queue_create_infos had protected set",
+            });
+        }
+
         // VUID-VkDeviceCreateInfo-pNext-02829
         // VUID-VkDeviceCreateInfo-pNext-02830
         // VUID-VkDeviceCreateInfo-pNext-06532
@@ -330,6 +677,34 @@ impl Device {
         );
         features_ffi.write(&enabled_features);
 
+        if !extension_features.is_empty() {
+            if !(instance.api_version() >= Version::V1_1
+                || instance
+                    .enabled_extensions()
+                    .khr_get_physical_device_properties2)
+            {
+                return Err(DeviceCreationError::ExtensionNotEnabled {
+                    extension: "khr_get_physical_device_properties2",
+                    reason: "extension_features was not empty",
+                });
+            }
+
+            // VUID-VkDeviceCreateInfo-sType-unique
+            let mut next = features_ffi.head_as_ref().p_next as *const ash::vk::BaseInStructure;
+
+            while let Some(base) = next.as_ref() {
+                if extension_features
+                    .chain()
+                    .s_types()
+                    .any(|ty| ty == base.s_type)
+                {
+                    return Err(DeviceCreationError::ExtensionFeaturesInFeatures);
+                }
+
+                next = base.p_next;
+            }
+        }
+
         // Device layers were deprecated in Vulkan 1.0.13, and device layer requests should be
         // ignored by the driver. For backwards compatibility, the spec recommends passing the
         // exact instance layers to the device as well. There's no need to support separate
@@ -349,6 +724,58 @@ impl Device {
             .map(|layer| layer.as_ptr())
             .collect::<SmallVec<[_; 2]>>();
 
+        /*
+            Memory report callbacks
+        */
+
+        let memory_report_callbacks: Vec<_> =
+            memory_report_callbacks.into_iter().map(Box::new).collect();
+
+        if !memory_report_callbacks.is_empty() {
+            if !enabled_extensions.ext_device_memory_report {
+                return Err(DeviceCreationError::ExtensionNotEnabled {
+                    extension: "ext_device_memory_report",
+                    reason: "memory_report_callbacks was not empty",
+                });
+            }
+
+            if !enabled_features.device_memory_report {
+                return Err(DeviceCreationError::FeatureNotEnabled {
+                    feature: "device_memory_report",
+                    reason: "memory_report_callbacks was not empty",
+                });
+            }
+        }
+
+        let mut memory_report_create_infos: SmallVec<[_; 2]> = memory_report_callbacks
+            .iter()
+            .map(
+                |user_callback| ash::vk::DeviceDeviceMemoryReportCreateInfoEXT {
+                    flags: ash::vk::DeviceMemoryReportFlagsEXT::empty(),
+                    pfn_user_callback: Some(memory_report::trampoline),
+                    p_user_data: &**user_callback as &Arc<_> as *const Arc<_> as *const c_void
+                        as *mut _,
+                    ..Default::default()
+                },
+            )
+            .collect();
+
+        for i in 1..memory_report_create_infos.len() {
+            memory_report_create_infos[i - 1].p_next =
+                &memory_report_create_infos[i] as *const _ as *const _;
+        }
+
+        /*
+            Debug names
+        */
+
+        if automatic_debug_names && !instance.enabled_extensions().ext_debug_utils {
+            return Err(DeviceCreationError::ExtensionNotEnabled {
+                extension: "ext_debug_utils",
+                reason: "automatic_debug_names was true",
+            });
+        }
+
         /*
             Create the device
         */
@@ -376,12 +803,38 @@ impl Device {
             create_info.p_enabled_features = &features_ffi.head_as_ref().features;
         }
 
+        if let Some(last) = memory_report_create_infos.last_mut() {
+            last.p_next = create_info.p_next;
+            create_info.p_next = &memory_report_create_infos[0] as *const _ as *const _;
+        }
+
+        let mut device_group_create_info = ash::vk::DeviceGroupDeviceCreateInfo {
+            physical_device_count: physical_devices_vk.len() as u32,
+            p_physical_devices: physical_devices_vk.as_ptr(),
+            ..Default::default()
+        };
+
+        if !physical_devices_vk.is_empty() {
+            device_group_create_info.p_next = create_info.p_next;
+            create_info.p_next = &device_group_create_info as *const _ as *const _;
+        }
+
+        // Feature structures contain no pointers, so they can be used for several devices.
+        let _extension_features_vk = extension_features
+            .chain()
+            .link_reusable(&mut create_info.p_next);
+        let _extensions_vk = extensions.link(&mut create_info.p_next);
+
+        let host_allocator = host_allocator.map(HostAllocationCallbacks::new);
+        let allocation_callbacks = host_allocator
+            .as_ref()
+            .map(HostAllocationCallbacks::to_vulkan);
+
         let handle = unsafe {
             let mut output = MaybeUninit::uninit();
-            check_errors((fns_i.v1_0.create_device)(
-                physical_device.internal_object(),
+            check_errors(create_fn(
                 &create_info,
-                ptr::null(),
+                callbacks_ptr(&allocation_callbacks),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -396,6 +849,14 @@ impl Device {
             handle,
             instance: physical_device.instance().clone(),
             physical_device: physical_device.index(),
+            physical_devices: if physical_devices.is_empty() {
+                smallvec![physical_device.index()]
+            } else {
+                physical_devices
+                    .iter()
+                    .map(|device| device.index())
+                    .collect()
+            },
             api_version,
             fns,
             standard_pool: Mutex::new(Weak::new()),
@@ -403,30 +864,67 @@ impl Device {
             standard_command_pools: Mutex::new(Default::default()),
             enabled_extensions,
             enabled_features,
+            enabled_extension_features: extension_features,
             active_queue_families,
             allocation_count: Mutex::new(0),
             fence_pool: Mutex::new(Vec::new()),
             semaphore_pool: Mutex::new(Vec::new()),
             event_pool: Mutex::new(Vec::new()),
+            host_allocator,
+            memory_report_callbacks: MemoryReportCallbacks(memory_report_callbacks),
+            automatic_debug_names,
+            object_tracker: object_tracking.then(Default::default),
+            created: Instant::now(),
+            submission_history: Mutex::new(VecDeque::with_capacity(submission_history_len)),
+            submission_history_len,
+            lost: AtomicBool::new(false),
+            profiling_lock: AtomicBool::new(false),
+            queue_family_params,
         });
 
+        device.set_automatic_debug_name(handle);
+
         // Iterator to return the queues
         let queues_iter = {
             let device = device.clone();
-            queues_to_get
-                .into_iter()
-                .map(move |QueueToGet { family, id }| unsafe {
-                    let fns = device.fns();
-                    let mut output = MaybeUninit::uninit();
+            queues_to_get.into_iter().map(move |queue_to_get| unsafe {
+                let QueueToGet {
+                    family,
+                    id,
+                    priority,
+                    global_priority,
+                    protected,
+                } = queue_to_get;
+                let fns = device.fns();
+                let mut output = MaybeUninit::uninit();
+
+                if protected {
+                    // VUID-vkGetDeviceQueue-flags-01841
+                    let queue_info = ash::vk::DeviceQueueInfo2 {
+                        flags: ash::vk::DeviceQueueCreateFlags::PROTECTED,
+                        queue_family_index: family,
+                        queue_index: id,
+                        ..Default::default()
+                    };
+                    (fns.v1_1.get_device_queue2)(handle, &queue_info, output.as_mut_ptr());
+                } else {
                     (fns.v1_0.get_device_queue)(handle, family, id, output.as_mut_ptr());
+                }
 
-                    Arc::new(Queue {
-                        handle: Mutex::new(output.assume_init()),
-                        device: device.clone(),
-                        family,
-                        id,
-                    })
+                let handle = output.assume_init();
+                device.set_automatic_debug_name(handle);
+
+                Arc::new(Queue {
+                    handle: Mutex::new(handle),
+                    device: device.clone(),
+                    family,
+                    id,
+                    priority,
+                    global_priority,
+                    protected,
+                    labels: Mutex::new(Vec::new()),
                 })
+            })
         };
 
         Ok((device, queues_iter))
@@ -459,111 +957,609 @@ impl Device {
     /// of the device (either explicitly or implicitly, for example with a future's destructor)
     /// while this function is waiting.
     ///
-    pub unsafe fn wait(&self) -> Result<(), OomError> {
+    pub unsafe fn wait(&self) -> Result<(), WaitIdleError> {
         let fns = self.fns();
-        check_errors((fns.v1_0.device_wait_idle)(self.handle))?;
+        self.check_errors((fns.v1_0.device_wait_idle)(self.handle))?;
         Ok(())
     }
 
-    /// Returns the instance used to create this device.
+    /// Returns whether the device has been lost.
+    ///
+    /// This becomes `true` once an operation on the device or one of its queues has returned a
+    /// `DeviceLost` error. See [the documentation of `Device`](Device#device-loss) for more
+    /// information.
     #[inline]
-    pub fn instance(&self) -> &Arc<Instance> {
-        &self.instance
+    pub fn is_lost(&self) -> bool {
+        self.lost.load(Ordering::Relaxed)
     }
 
-    /// Returns the physical device that was used to create this device.
-    #[inline]
-    pub fn physical_device(&self) -> PhysicalDevice {
-        PhysicalDevice::from_index(&self.instance, self.physical_device).unwrap()
-    }
+    // Like `check_errors`, but also records that the device was lost.
+    pub(crate) fn check_errors(&self, result: ash::vk::Result) -> Result<Success, Error> {
+        let result = check_errors(result);
 
-    /// Returns an iterator to the list of queues families that this device uses.
-    ///
-    /// > **Note**: Will return `-> impl ExactSizeIterator<Item = QueueFamily>` in the future.
-    // TODO: ^
-    #[inline]
-    pub fn active_queue_families<'a>(&'a self) -> impl ExactSizeIterator<Item = QueueFamily<'a>> {
-        let physical_device = self.physical_device();
-        self.active_queue_families
-            .iter()
-            .map(move |&id| physical_device.queue_family_by_id(id).unwrap())
-    }
+        if let Err(Error::DeviceLost) = result {
+            self.lost.store(true, Ordering::Relaxed);
+        }
 
-    /// Returns the extensions that have been enabled on the device.
-    #[inline]
-    pub fn enabled_extensions(&self) -> &DeviceExtensions {
-        &self.enabled_extensions
+        result
     }
 
-    /// Returns the features that have been enabled on the device.
-    #[inline]
-    pub fn enabled_features(&self) -> &Features {
-        &self.enabled_features
-    }
+    /// Returns information about the fault that caused the device to be lost, as reported by the
+    /// implementation.
+    ///
+    /// The [`ext_device_fault`](DeviceExtensions::ext_device_fault) extension and the
+    /// [`device_fault`](Features::device_fault) feature must be enabled on the device, and the
+    /// device must be lost. The binary data of the vendor is only returned if the
+    /// [`device_fault_vendor_binary`](Features::device_fault_vendor_binary) feature is enabled
+    /// too.
+    pub fn fault_info(&self) -> Result<DeviceFaultInfo, DeviceFaultError> {
+        if !self.enabled_extensions.ext_device_fault {
+            return Err(DeviceFaultError::ExtensionNotEnabled {
+                extension: "ext_device_fault",
+                reason: "tried to get the fault info of the device",
+            });
+        }
 
-    /// Returns the standard memory pool used by default if you don't provide any other pool.
-    pub fn standard_pool(me: &Arc<Self>) -> Arc<StdMemoryPool> {
-        let mut pool = me.standard_pool.lock().unwrap();
+        if !self.enabled_features.device_fault {
+            return Err(DeviceFaultError::FeatureNotEnabled {
+                feature: "device_fault",
+                reason: "tried to get the fault info of the device",
+            });
+        }
 
-        if let Some(p) = pool.upgrade() {
-            return p;
+        // The fault info is only reported for a lost device.
+        if !self.is_lost() {
+            return Err(DeviceFaultError::DeviceNotLost);
         }
 
-        // The weak pointer is empty, so we create the pool.
-        let new_pool = StdMemoryPool::new(me.clone());
-        *pool = Arc::downgrade(&new_pool);
-        new_pool
-    }
+        let fns = self.fns();
 
-    /// Returns the standard descriptor pool used by default if you don't provide any other pool.
-    pub fn standard_descriptor_pool(me: &Arc<Self>) -> Arc<StdDescriptorPool> {
-        let mut pool = me.standard_descriptor_pool.lock().unwrap();
+        let (description, address_infos, vendor_infos, vendor_binary_data) = unsafe {
+            loop {
+                let mut counts = ash::vk::DeviceFaultCountsEXT::default();
+                check_errors((fns.ext_device_fault.get_device_fault_info_ext)(
+                    self.handle,
+                    &mut counts,
+                    ptr::null_mut(),
+                ))?;
+
+                if !self.enabled_features.device_fault_vendor_binary {
+                    counts.vendor_binary_size = 0;
+                }
 
-        if let Some(p) = pool.upgrade() {
-            return p;
-        }
+                let mut address_infos = vec![
+                    ash::vk::DeviceFaultAddressInfoEXT::default();
+                    counts.address_info_count as usize
+                ];
+                let mut vendor_infos = vec![
+                    ash::vk::DeviceFaultVendorInfoEXT::default();
+                    counts.vendor_info_count as usize
+                ];
+                let mut vendor_binary_data = vec![0u8; counts.vendor_binary_size as usize];
+
+                let mut info = ash::vk::DeviceFaultInfoEXT {
+                    p_address_infos: address_infos.as_mut_ptr(),
+                    p_vendor_infos: vendor_infos.as_mut_ptr(),
+                    p_vendor_binary_data: if vendor_binary_data.is_empty() {
+                        ptr::null_mut()
+                    } else {
+                        vendor_binary_data.as_mut_ptr() as *mut c_void
+                    },
+                    ..Default::default()
+                };
+
+                let result = check_errors((fns.ext_device_fault.get_device_fault_info_ext)(
+                    self.handle,
+                    &mut counts,
+                    &mut info,
+                ))?;
+
+                if !matches!(result, Success::Incomplete) {
+                    address_infos.truncate(counts.address_info_count as usize);
+                    vendor_infos.truncate(counts.vendor_info_count as usize);
+                    vendor_binary_data.truncate(counts.vendor_binary_size as usize);
+
+                    break (
+                        CStr::from_ptr(info.description.as_ptr())
+                            .to_string_lossy()
+                            .into_owned(),
+                        address_infos,
+                        vendor_infos,
+                        vendor_binary_data,
+                    );
+                }
+            }
+        };
 
-        // The weak pointer is empty, so we create the pool.
-        let new_pool = Arc::new(StdDescriptorPool::new(me.clone()));
-        *pool = Arc::downgrade(&new_pool);
-        new_pool
+        Ok(DeviceFaultInfo {
+            description,
+            address_infos: address_infos
+                .into_iter()
+                .filter_map(|info| {
+                    Some(DeviceFaultAddressInfo {
+                        // Address types added by later versions of the extension are skipped.
+                        address_type: info.address_type.try_into().ok()?,
+                        reported_address: info.reported_address,
+                        address_precision: info.address_precision,
+                    })
+                })
+                .collect(),
+            vendor_infos: vendor_infos
+                .into_iter()
+                .map(|info| DeviceFaultVendorInfo {
+                    description: unsafe {
+                        CStr::from_ptr(info.description.as_ptr())
+                            .to_string_lossy()
+                            .into_owned()
+                    },
+                    vendor_fault_code: info.vendor_fault_code,
+                    vendor_fault_data: info.vendor_fault_data,
+                })
+                .collect(),
+            vendor_binary_data,
+        })
     }
 
-    /// Returns the standard command buffer pool used by default if you don't provide any other
-    /// pool.
-    ///
-    /// # Panic
+    /// Acquires the profiling lock of the device. The lock must be held while command buffers
+    /// that contain performance queries are recorded and executed.
     ///
-    /// - Panics if the device and the queue family don't belong to the same physical device.
+    /// Only one process can hold the lock at a time. If another process holds it, this waits
+    /// until it is released, for at most `timeout` if it is `Some`.
     ///
-    pub fn standard_command_pool(me: &Arc<Self>, queue: QueueFamily) -> Arc<StandardCommandPool> {
-        let mut standard_command_pools = me.standard_command_pools.lock().unwrap();
+    /// The [`khr_performance_query`](DeviceExtensions::khr_performance_query) extension must be
+    /// enabled on the device.
+    pub fn acquire_profiling_lock(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<(), ProfilingLockError> {
+        if !self.enabled_extensions.khr_performance_query {
+            return Err(ProfilingLockError::ExtensionNotEnabled {
+                extension: "khr_performance_query",
+                reason: "tried to acquire the profiling lock",
+            });
+        }
 
-        match standard_command_pools.entry(queue.id()) {
-            Entry::Occupied(mut entry) => {
-                if let Some(pool) = entry.get().upgrade() {
-                    return pool;
-                }
+        let timeout_ns = if let Some(timeout) = timeout {
+            timeout
+                .as_secs()
+                .saturating_mul(1_000_000_000)
+                .saturating_add(timeout.subsec_nanos() as u64)
+        } else {
+            u64::MAX
+        };
 
-                let new_pool = Arc::new(StandardCommandPool::new(me.clone(), queue));
-                *entry.get_mut() = Arc::downgrade(&new_pool);
-                new_pool
-            }
-            Entry::Vacant(entry) => {
-                let new_pool = Arc::new(StandardCommandPool::new(me.clone(), queue));
-                entry.insert(Arc::downgrade(&new_pool));
-                new_pool
-            }
+        let info = ash::vk::AcquireProfilingLockInfoKHR {
+            flags: ash::vk::AcquireProfilingLockFlagsKHR::empty(),
+            timeout: timeout_ns,
+            ..Default::default()
+        };
+
+        let result = unsafe {
+            let fns = self.fns();
+            check_errors((fns.khr_performance_query.acquire_profiling_lock_khr)(
+                self.handle,
+                &info,
+            ))?
+        };
+
+        if let Success::Timeout = result {
+            return Err(ProfilingLockError::Timeout);
         }
+
+        self.profiling_lock.store(true, Ordering::Relaxed);
+
+        Ok(())
     }
 
-    /// Used to track the number of allocations on this device.
+    /// Releases the profiling lock that was acquired with
+    /// [`acquire_profiling_lock`](Self::acquire_profiling_lock).
     ///
-    /// To ensure valid usage of the Vulkan API, we cannot call `vkAllocateMemory` when
-    /// `maxMemoryAllocationCount` has been exceeded. See the Vulkan specs:
-    /// https://www.khronos.org/registry/vulkan/specs/1.0/html/vkspec.html#vkAllocateMemory
+    /// # Safety
     ///
-    /// Warning: You should never modify this value, except in `device_memory` module
+    /// - No command buffer that contains performance queries must be in the recording state, or
+    ///   be waiting to be executed or executing on a queue.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the profiling lock is not held.
+    pub unsafe fn release_profiling_lock(&self) {
+        // VUID-vkReleaseProfilingLockKHR-device-03235
+        assert!(self.profiling_lock.swap(false, Ordering::Relaxed));
+
+        let fns = self.fns();
+        (fns.khr_performance_query.release_profiling_lock_khr)(self.handle);
+    }
+
+    /// Returns whether the profiling lock is held, after a call to
+    /// [`acquire_profiling_lock`](Self::acquire_profiling_lock).
+    #[inline]
+    pub fn is_profiling_lock_held(&self) -> bool {
+        self.profiling_lock.load(Ordering::Relaxed)
+    }
+
+    /// Creates a new device from the same physical devices, and with the same parameters, as this
+    /// one. Along with the device, returns its queues in the same order as [`Device::new`].
+    ///
+    /// This is used to replace a device that has been [lost](Device::is_lost). The new device has
+    /// the same enabled extensions and features, including the ones that vulkano enabled
+    /// automatically, and the same memory report callbacks. It is always created with
+    /// `vkCreateDevice`, even if this device was created with [`Device::with_create_fn`].
+    ///
+    /// The structures of [`DeviceCreateInfo::extensions`] are not kept by the device, because the
+    /// pointers that they contain may no longer be valid. `extensions` replaces them, and must be
+    /// built again by the caller if it is needed.
+    ///
+    /// No objects are shared between the two devices, so the objects that were created from this
+    /// device must be created again from the new one.
+    pub fn recreate(
+        &self,
+        extensions: ExtensionChain<Device>,
+    ) -> Result<(Arc<Device>, impl ExactSizeIterator<Item = Arc<Queue>>), DeviceCreationError> {
+        let physical_device = self.physical_device();
+
+        let create_info = DeviceCreateInfo {
+            enabled_extensions: self.enabled_extensions,
+            enabled_features: self.enabled_features.clone(),
+            extension_features: self.enabled_extension_features.clone(),
+            // A single physical device is the same as none at all, and doesn't require the
+            // extensions for device groups.
+            physical_devices: if self.physical_devices.len() > 1 {
+                self.physical_devices().collect()
+            } else {
+                Vec::new()
+            },
+            extensions,
+            host_allocator: self
+                .host_allocator
+                .as_ref()
+                .map(|callbacks| callbacks.allocator().clone()),
+            queue_create_infos: self
+                .queue_family_params
+                .iter()
+                .map(|params| QueueCreateInfo {
+                    family: physical_device.queue_family_by_id(params.family).unwrap(),
+                    queues: params.queues.clone(),
+                    protected: params.protected,
+                    global_priority: params.global_priority,
+                    _ne: crate::NonExhaustive(()),
+                })
+                .collect(),
+            automatic_debug_names: self.automatic_debug_names,
+            object_tracking: self.object_tracker.is_some(),
+            submission_history_len: self.submission_history_len,
+            _ne: crate::NonExhaustive(()),
+        };
+
+        // Safety: the callbacks were already valid for this device.
+        unsafe {
+            Self::with_memory_report_callbacks(
+                physical_device,
+                create_info,
+                self.memory_report_callbacks
+                    .0
+                    .iter()
+                    .map(|callback| (**callback).clone()),
+            )
+        }
+    }
+
+    /// Returns the instance used to create this device.
+    #[inline]
+    pub fn instance(&self) -> &Arc<Instance> {
+        &self.instance
+    }
+
+    /// Returns the physical device that was used to create this device.
+    #[inline]
+    pub fn physical_device(&self) -> PhysicalDevice {
+        PhysicalDevice::from_index(&self.instance, self.physical_device).unwrap()
+    }
+
+    // Returns whether `device_mask` is not zero, and only contains the device indices of the
+    // physical devices that the device was created from.
+    pub(crate) fn is_valid_device_mask(&self, device_mask: u32) -> bool {
+        device_mask != 0
+            && device_mask
+                .checked_shr(self.physical_devices.len() as u32)
+                .unwrap_or(0)
+                == 0
+    }
+
+    /// Returns the physical devices that the device was created from, in the order of their
+    /// device index. This contains only [`physical_device`](Self::physical_device) if
+    /// [`DeviceCreateInfo::physical_devices`] was empty.
+    #[inline]
+    pub fn physical_devices(&self) -> impl ExactSizeIterator<Item = PhysicalDevice> {
+        self.physical_devices
+            .iter()
+            .map(move |&index| PhysicalDevice::from_index(&self.instance, index).unwrap())
+    }
+
+    /// Returns the capabilities of the device for presenting the images of its physical devices.
+    ///
+    /// The device API version must be at least 1.1, or the `khr_device_group` extension must be
+    /// enabled. The `khr_swapchain` extension must also be enabled.
+    pub fn group_present_capabilities(
+        &self,
+    ) -> Result<DeviceGroupPresentCapabilities, SurfacePropertiesError> {
+        self.validate_device_group_present()?;
+
+        let capabilities = unsafe {
+            let fns = self.fns();
+            let mut output = ash::vk::DeviceGroupPresentCapabilitiesKHR::default();
+            check_errors((fns
+                .khr_swapchain
+                .get_device_group_present_capabilities_khr)(
+                self.handle, &mut output
+            ))?;
+            output
+        };
+
+        Ok(DeviceGroupPresentCapabilities {
+            present_mask: capabilities.present_mask,
+            modes: capabilities.modes.into(),
+        })
+    }
+
+    /// Returns the device group present modes that the device supports for the given surface.
+    ///
+    /// The device API version must be at least 1.1, or the `khr_device_group` extension must be
+    /// enabled. The `khr_swapchain` extension must also be enabled.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the device and the surface don't belong to the same instance.
+    pub fn surface_group_present_modes<W>(
+        &self,
+        surface: &Surface<W>,
+    ) -> Result<DeviceGroupPresentModes, SurfacePropertiesError> {
+        assert_eq!(
+            self.instance.internal_object(),
+            surface.instance().internal_object(),
+        );
+
+        self.validate_device_group_present()?;
+
+        let modes = unsafe {
+            let fns = self.fns();
+            let mut output = ash::vk::DeviceGroupPresentModeFlagsKHR::empty();
+            check_errors((fns
+                .khr_swapchain
+                .get_device_group_surface_present_modes_khr)(
+                self.handle,
+                surface.internal_object(),
+                &mut output,
+            ))?;
+            output
+        };
+
+        Ok(modes.into())
+    }
+
+    fn validate_device_group_present(&self) -> Result<(), SurfacePropertiesError> {
+        if !self.enabled_extensions.khr_swapchain {
+            return Err(SurfacePropertiesError::ExtensionNotEnabled {
+                extension: "khr_swapchain",
+                reason: "tried to query the device group present capabilities",
+            });
+        }
+
+        if !(self.api_version >= Version::V1_1 || self.enabled_extensions.khr_device_group) {
+            return Err(SurfacePropertiesError::ExtensionNotEnabled {
+                extension: "khr_device_group",
+                reason: "tried to query the device group present capabilities",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns an iterator to the list of queues families that this device uses.
+    ///
+    /// > **Note**: Will return `-> impl ExactSizeIterator<Item = QueueFamily>` in the future.
+    // TODO: ^
+    #[inline]
+    pub fn active_queue_families<'a>(&'a self) -> impl ExactSizeIterator<Item = QueueFamily<'a>> {
+        let physical_device = self.physical_device();
+        self.active_queue_families
+            .iter()
+            .map(move |&id| physical_device.queue_family_by_id(id).unwrap())
+    }
+
+    /// Returns the extensions that have been enabled on the device.
+    #[inline]
+    pub fn enabled_extensions(&self) -> &DeviceExtensions {
+        &self.enabled_extensions
+    }
+
+    /// Returns the features that have been enabled on the device.
+    ///
+    /// For the features of extensions that were promoted to core, this tells whether they can be
+    /// used, regardless of whether they are provided by the core API or by the extension. Vulkano
+    /// uses the core entry points and structures whenever the API version of the device allows
+    /// it.
+    #[inline]
+    pub fn enabled_features(&self) -> &Features {
+        &self.enabled_features
+    }
+
+    /// Returns the feature structures of extensions that were given when creating the device.
+    #[inline]
+    pub fn enabled_extension_features(&self) -> &ExtensionFeatures {
+        &self.enabled_extension_features
+    }
+
+    /// Returns the host allocator that was given when creating the device, if any.
+    #[inline]
+    pub fn host_allocator(&self) -> Option<&Arc<dyn HostAllocator>> {
+        self.host_allocator
+            .as_ref()
+            .map(HostAllocationCallbacks::allocator)
+    }
+
+    /// Returns whether objects created by vulkano on this device are automatically given debug
+    /// names.
+    #[inline]
+    pub fn automatic_debug_names(&self) -> bool {
+        self.automatic_debug_names
+    }
+
+    /// Returns the tracker that counts the objects that are alive on this device, if
+    /// [`DeviceCreateInfo::object_tracking`] was `true`.
+    #[inline]
+    pub fn object_tracker(&self) -> Option<&Arc<ObjectTracker>> {
+        self.object_tracker.as_ref()
+    }
+
+    /// Returns the most recent submissions to the queues of the device, oldest first. At most
+    /// [`DeviceCreateInfo::submission_history_len`] submissions are kept.
+    #[inline]
+    pub fn submission_history(&self) -> Vec<SubmissionRecord> {
+        self.submission_history
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    // Records a submission to `queue` in the submission history, if it is enabled.
+    pub(crate) fn record_submission(
+        &self,
+        queue: &Queue,
+        command_buffers: &[ash::vk::CommandBuffer],
+        wait_semaphore_count: usize,
+        signal_semaphore_count: usize,
+    ) {
+        if self.submission_history_len == 0 {
+            return;
+        }
+
+        let record = SubmissionRecord {
+            time: self.created.elapsed(),
+            queue_family_index: queue.family,
+            queue_index: queue.id,
+            command_buffers: command_buffers.iter().map(|cb| cb.as_raw()).collect(),
+            wait_semaphore_count,
+            signal_semaphore_count,
+            queue_labels: queue.debug_utils_labels(),
+        };
+
+        let mut history = self.submission_history.lock().unwrap();
+
+        if history.len() == self.submission_history_len {
+            history.pop_front();
+        }
+
+        history.push_back(record);
+    }
+
+    // The time between the creation of the device and now.
+    #[inline]
+    pub(crate) fn time_since_creation(&self) -> Duration {
+        self.created.elapsed()
+    }
+
+    // Counts an object of the given type while the returned value is alive, if object tracking
+    // is enabled.
+    #[inline]
+    pub(crate) fn track_object(&self, object_type: TrackedObjectType) -> TrackedObject {
+        TrackedObject::new(self.object_tracker.as_ref(), object_type)
+    }
+
+    /// Returns the standard memory pool used by default if you don't provide any other pool.
+    pub fn standard_pool(me: &Arc<Self>) -> Arc<StdMemoryPool> {
+        let mut pool = me.standard_pool.lock().unwrap();
+
+        if let Some(p) = pool.upgrade() {
+            return p;
+        }
+
+        // The weak pointer is empty, so we create the pool.
+        let new_pool = StdMemoryPool::new(me.clone());
+        *pool = Arc::downgrade(&new_pool);
+        new_pool
+    }
+
+    /// Returns the standard descriptor pool used by default if you don't provide any other pool.
+    pub fn standard_descriptor_pool(me: &Arc<Self>) -> Arc<StdDescriptorPool> {
+        let mut pool = me.standard_descriptor_pool.lock().unwrap();
+
+        if let Some(p) = pool.upgrade() {
+            return p;
+        }
+
+        // The weak pointer is empty, so we create the pool.
+        let new_pool = Arc::new(StdDescriptorPool::new(me.clone()));
+        *pool = Arc::downgrade(&new_pool);
+        new_pool
+    }
+
+    /// Returns the standard command buffer pool used by default if you don't provide any other
+    /// pool.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the device and the queue family don't belong to the same physical device.
+    ///
+    pub fn standard_command_pool(me: &Arc<Self>, queue: QueueFamily) -> Arc<StandardCommandPool> {
+        Self::standard_command_pool_inner(me, queue, false)
+    }
+
+    /// Returns the standard command buffer pool used for protected command buffers.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the device and the queue family don't belong to the same physical device.
+    /// - Panics if the [`protected_memory`](crate::device::Features::protected_memory) feature is
+    ///   not enabled on the device.
+    ///
+    pub fn standard_protected_command_pool(
+        me: &Arc<Self>,
+        queue: QueueFamily,
+    ) -> Arc<StandardCommandPool> {
+        Self::standard_command_pool_inner(me, queue, true)
+    }
+
+    fn standard_command_pool_inner(
+        me: &Arc<Self>,
+        queue: QueueFamily,
+        protected: bool,
+    ) -> Arc<StandardCommandPool> {
+        let mut standard_command_pools = me.standard_command_pools.lock().unwrap();
+        let new_pool = || {
+            if protected {
+                Arc::new(StandardCommandPool::new_protected(me.clone(), queue))
+            } else {
+                Arc::new(StandardCommandPool::new(me.clone(), queue))
+            }
+        };
+
+        match standard_command_pools.entry((queue.id(), protected)) {
+            Entry::Occupied(mut entry) => {
+                if let Some(pool) = entry.get().upgrade() {
+                    return pool;
+                }
+
+                let new_pool = new_pool();
+                *entry.get_mut() = Arc::downgrade(&new_pool);
+                new_pool
+            }
+            Entry::Vacant(entry) => {
+                let new_pool = new_pool();
+                entry.insert(Arc::downgrade(&new_pool));
+                new_pool
+            }
+        }
+    }
+
+    /// Used to track the number of allocations on this device.
+    ///
+    /// To ensure valid usage of the Vulkan API, we cannot call `vkAllocateMemory` when
+    /// `maxMemoryAllocationCount` has been exceeded. See the Vulkan specs:
+    /// https://www.khronos.org/registry/vulkan/specs/1.0/html/vkspec.html#vkAllocateMemory
+    ///
+    /// Warning: You should never modify this value, except in `device_memory` module
     pub(crate) fn allocation_count(&self) -> &Mutex<u32> {
         &self.allocation_count
     }
@@ -658,6 +1654,130 @@ impl Device {
 
         Ok(())
     }
+
+    // Gives a name derived from its type to an object that vulkano has just created, if
+    // `automatic_debug_names` was enabled when creating the device.
+    pub(crate) fn set_automatic_debug_name<H: Handle>(&self, handle: H) {
+        if self.automatic_debug_names {
+            // Naming is only a debugging aid, so failing to do it is not an error.
+            let _ = unsafe { self.set_debug_name_raw(H::TYPE, handle.as_raw(), None) };
+        }
+    }
+
+    unsafe fn set_debug_name_raw(
+        &self,
+        object_type: ash::vk::ObjectType,
+        object_handle: u64,
+        label: Option<&str>,
+    ) -> Result<(), OomError> {
+        if !self.instance.enabled_extensions().ext_debug_utils {
+            return Ok(());
+        }
+
+        let object_name = match label {
+            Some(label) if self.automatic_debug_names => {
+                Some(format!("{}: {}", object_type_name(object_type), label))
+            }
+            Some(label) => Some(label.to_owned()),
+            None if self.automatic_debug_names => Some(object_type_name(object_type).to_owned()),
+            None => None,
+        };
+        let object_name_vk = object_name.map(|object_name| CString::new(object_name).unwrap());
+        let info = ash::vk::DebugUtilsObjectNameInfoEXT {
+            object_type,
+            object_handle,
+            p_object_name: object_name_vk
+                .as_ref()
+                .map_or(ptr::null(), |object_name| object_name.as_ptr()),
+            ..Default::default()
+        };
+
+        let fns = self.instance.fns();
+        check_errors((fns.ext_debug_utils.set_debug_utils_object_name_ext)(
+            self.handle,
+            &info,
+        ))?;
+
+        Ok(())
+    }
+}
+
+// Returns the name of the type of an object, which is used in automatic debug names.
+fn object_type_name(object_type: ash::vk::ObjectType) -> &'static str {
+    match object_type {
+        ash::vk::ObjectType::BUFFER => "Buffer",
+        ash::vk::ObjectType::BUFFER_VIEW => "BufferView",
+        ash::vk::ObjectType::COMMAND_BUFFER => "CommandBuffer",
+        ash::vk::ObjectType::COMMAND_POOL => "CommandPool",
+        ash::vk::ObjectType::DESCRIPTOR_POOL => "DescriptorPool",
+        ash::vk::ObjectType::DESCRIPTOR_SET => "DescriptorSet",
+        ash::vk::ObjectType::DESCRIPTOR_SET_LAYOUT => "DescriptorSetLayout",
+        ash::vk::ObjectType::DEVICE => "Device",
+        ash::vk::ObjectType::DEVICE_MEMORY => "DeviceMemory",
+        ash::vk::ObjectType::EVENT => "Event",
+        ash::vk::ObjectType::FENCE => "Fence",
+        ash::vk::ObjectType::FRAMEBUFFER => "Framebuffer",
+        ash::vk::ObjectType::IMAGE => "Image",
+        ash::vk::ObjectType::IMAGE_VIEW => "ImageView",
+        ash::vk::ObjectType::PIPELINE => "Pipeline",
+        ash::vk::ObjectType::PIPELINE_CACHE => "PipelineCache",
+        ash::vk::ObjectType::PIPELINE_LAYOUT => "PipelineLayout",
+        ash::vk::ObjectType::QUERY_POOL => "QueryPool",
+        ash::vk::ObjectType::QUEUE => "Queue",
+        ash::vk::ObjectType::RENDER_PASS => "RenderPass",
+        ash::vk::ObjectType::SAMPLER => "Sampler",
+        ash::vk::ObjectType::SAMPLER_YCBCR_CONVERSION => "SamplerYcbcrConversion",
+        ash::vk::ObjectType::SEMAPHORE => "Semaphore",
+        ash::vk::ObjectType::SHADER_MODULE => "ShaderModule",
+        ash::vk::ObjectType::SWAPCHAIN_KHR => "Swapchain",
+        _ => "Object",
+    }
+}
+
+// These features were promoted to core from an extension. Below the version that they were
+// promoted in, they can only be enabled through the feature structure of the extension, so the
+// extension is enabled along with the feature. Returns these extensions for `features`.
+pub(crate) fn promoted_feature_extensions(
+    features: &Features,
+    api_version: Version,
+    supported_extensions: &DeviceExtensions,
+) -> DeviceExtensions {
+    let mut extensions = DeviceExtensions::none();
+
+    for (feature_enabled, version, extension_supported, extension_enabled) in [
+        (
+            features.timeline_semaphore,
+            Version::V1_2,
+            supported_extensions.khr_timeline_semaphore,
+            &mut extensions.khr_timeline_semaphore,
+        ),
+        (
+            features.dynamic_rendering,
+            Version::V1_3,
+            supported_extensions.khr_dynamic_rendering,
+            &mut extensions.khr_dynamic_rendering,
+        ),
+        (
+            features.maintenance4,
+            Version::V1_3,
+            supported_extensions.khr_maintenance4,
+            &mut extensions.khr_maintenance4,
+        ),
+        (
+            features.synchronization2,
+            Version::V1_3,
+            supported_extensions.khr_synchronization2,
+            &mut extensions.khr_synchronization2,
+        ),
+    ] {
+        // If the extension isn't supported, neither is the feature, which is checked when
+        // creating the device.
+        if feature_enabled && api_version < version && extension_supported {
+            *extension_enabled = true;
+        }
+    }
+
+    extensions
 }
 
 impl Drop for Device {
@@ -675,7 +1795,11 @@ impl Drop for Device {
             for &raw_event in self.event_pool.lock().unwrap().iter() {
                 (fns.v1_0.destroy_event)(self.handle, raw_event, ptr::null());
             }
-            (fns.v1_0.destroy_device)(self.handle, ptr::null());
+            let allocation_callbacks = self
+                .host_allocator
+                .as_ref()
+                .map(HostAllocationCallbacks::to_vulkan);
+            (fns.v1_0.destroy_device)(self.handle, callbacks_ptr(&allocation_callbacks));
         }
     }
 }
@@ -716,12 +1840,37 @@ pub enum DeviceCreationError {
     TooManyObjects,
     /// Failed to connect to the device.
     DeviceLost,
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
     /// Some of the requested features are unsupported by the physical device.
     FeatureNotPresent,
+    /// `physical_devices` was not empty, but didn't contain the physical device that the device
+    /// is created from.
+    PhysicalDeviceNotInPhysicalDevices,
+    /// The physical devices in `physical_devices` don't all belong to the same device group.
+    PhysicalDevicesNotInSameGroup,
+    /// A structure in `extension_features` has the same type as a structure that vulkano fills
+    /// from `enabled_features`. The features must be enabled through `enabled_features` instead.
+    ExtensionFeaturesInFeatures,
     /// Some of the requested device extensions are not supported by the physical device.
     ExtensionNotPresent,
     /// Tried to create too many queues for a given family.
     TooManyQueuesForFamily,
+    /// Protected queues were requested for a queue family that does not support them.
+    QueueFamilyProtectedNotSupported,
+    /// A global priority was requested for a queue family that does not support it.
+    QueueFamilyGlobalPriorityNotSupported {
+        global_priority: QueueGlobalPriority,
+    },
+    /// The global priority of one of the queues was not allowed, for example because the
+    /// process doesn't have the privileges that are needed for it.
+    NotPermitted,
     /// The priority of one of the queues is out of the [0.0; 1.0] range.
     PriorityOutOfRange,
     /// There is no memory available on the host (ie. the CPU, RAM, etc.).
@@ -751,15 +1900,48 @@ impl fmt::Display for DeviceCreationError {
                 write!(fmt, "no memory available on the graphical device")
             }
             Self::DeviceLost => write!(fmt, "failed to connect to the device"),
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
             Self::TooManyQueuesForFamily => {
                 write!(fmt, "tried to create too many queues for a given family")
             }
+            Self::QueueFamilyProtectedNotSupported => write!(
+                fmt,
+                "protected queues were requested for a queue family that does not support them"
+            ),
+            Self::QueueFamilyGlobalPriorityNotSupported { global_priority } => write!(
+                fmt,
+                "the global priority {:?} was requested for a queue family that does not support it",
+                global_priority,
+            ),
+            Self::NotPermitted => write!(
+                fmt,
+                "the global priority of one of the queues was not allowed",
+            ),
             Self::FeatureNotPresent => {
                 write!(
                     fmt,
                     "some of the requested features are unsupported by the physical device"
                 )
             }
+            Self::PhysicalDeviceNotInPhysicalDevices => write!(
+                fmt,
+                "physical_devices didn't contain the physical device that the device is created from"
+            ),
+            Self::PhysicalDevicesNotInSameGroup => write!(
+                fmt,
+                "the physical devices in physical_devices don't all belong to the same device group"
+            ),
+            Self::ExtensionFeaturesInFeatures => write!(
+                fmt,
+                "a structure in extension_features has the same type as a structure that is filled from enabled_features"
+            ),
             Self::PriorityOutOfRange => {
                 write!(
                     fmt,
@@ -789,11 +1971,22 @@ impl From<Error> for DeviceCreationError {
             Error::ExtensionNotPresent => Self::ExtensionNotPresent,
             Error::FeatureNotPresent => Self::FeatureNotPresent,
             Error::TooManyObjects => Self::TooManyObjects,
+            Error::NotPermitted => Self::NotPermitted,
             _ => panic!("Unexpected error value: {}", err as i32),
         }
     }
 }
 
+impl From<OomError> for DeviceCreationError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        match err {
+            OomError::OutOfHostMemory => Self::OutOfHostMemory,
+            OomError::OutOfDeviceMemory => Self::OutOfDeviceMemory,
+        }
+    }
+}
+
 impl From<ExtensionRestrictionError> for DeviceCreationError {
     #[inline]
     fn from(err: ExtensionRestrictionError) -> Self {
@@ -813,19 +2006,100 @@ impl From<FeatureRestrictionError> for DeviceCreationError {
 pub struct DeviceCreateInfo<'qf> {
     /// The extensions to enable on the device.
     ///
+    /// The extensions in
+    /// [`PhysicalDevice::required_extensions`](physical::PhysicalDevice::required_extensions)
+    /// are always enabled in addition to these. This includes
+    /// [`khr_portability_subset`](DeviceExtensions::khr_portability_subset) on implementations
+    /// that only support a subset of Vulkan, such as MoltenVK. The features of that extension,
+    /// such as [`triangle_fans`](Features::triangle_fans), must then be enabled to use what they
+    /// allow.
+    ///
     /// The default value is [`DeviceExtensions::none()`].
     pub enabled_extensions: DeviceExtensions,
 
     /// The features to enable on the device.
     ///
+    /// The features of extensions that were promoted to core, such as
+    /// [`dynamic_rendering`](Features::dynamic_rendering),
+    /// [`synchronization2`](Features::synchronization2),
+    /// [`timeline_semaphore`](Features::timeline_semaphore) and
+    /// [`maintenance4`](Features::maintenance4), can be enabled whatever the API version of the
+    /// device is. If the API version is lower than the version that the feature was promoted in,
+    /// the extension that provides it is enabled automatically.
+    ///
     /// The default value is [`Features::none()`].
     pub enabled_features: Features,
 
+    /// Feature structures of extensions that are not included in [`Features`], to enable on the
+    /// device.
+    ///
+    /// If this is not empty, the instance must have API version 1.1 or higher, or have the
+    /// [`khr_get_physical_device_properties2`](crate::instance::InstanceExtensions::khr_get_physical_device_properties2)
+    /// extension enabled.
+    ///
+    /// The default value is empty.
+    pub extension_features: ExtensionFeatures,
+
+    /// The physical devices of a device group to create the device from. The index of a physical
+    /// device in this list is its device index, which is used in device masks.
+    ///
+    /// If this is empty, the device is created from only the physical device given to
+    /// [`Device::new`]. Otherwise, it must contain that physical device, and all the physical
+    /// devices must belong to the same group in
+    /// [`PhysicalDevice::enumerate_groups`](physical::PhysicalDevice::enumerate_groups). The
+    /// instance must then have API version 1.1 or higher, or have the
+    /// [`khr_device_group_creation`](crate::instance::InstanceExtensions::khr_device_group_creation)
+    /// extension enabled.
+    ///
+    /// The default value is empty.
+    pub physical_devices: Vec<PhysicalDevice<'qf>>,
+
+    /// Extension structures that vulkano doesn't support, to add to the create info of the
+    /// device. See the [`extension_chain`](crate::extension_chain) module for more information.
+    /// If it is not empty, it can only be used to create one object.
+    ///
+    /// The default value is empty.
+    pub extensions: ExtensionChain<Device>,
+
+    /// An allocator to use for the host memory that the Vulkan implementation allocates for the
+    /// device. See the [`host_allocator`](crate::instance::host_allocator) module for more
+    /// information.
+    ///
+    /// If this is `None`, the implementation uses its own allocator. The allocator of the instance
+    /// is not used automatically.
+    ///
+    /// The default value is `None`.
+    pub host_allocator: Option<Arc<dyn HostAllocator>>,
+
     /// The queues to create for the device.
     ///
     /// The default value is empty, which must be overridden.
     pub queue_create_infos: Vec<QueueCreateInfo<'qf>>,
 
+    /// Whether to give a debug name to every object that vulkano creates on the device, so that
+    /// they can be told apart in the messages of the validation layers and in tools such as
+    /// RenderDoc. The name is the type of the object, followed by the label given to
+    /// [`DebugName::set_debug_name`] if there is one.
+    ///
+    /// If set to `true`, the
+    /// [`ext_debug_utils`](crate::instance::InstanceExtensions::ext_debug_utils) extension must
+    /// be enabled on the instance.
+    ///
+    /// The default value is `false`.
+    pub automatic_debug_names: bool,
+
+    /// Whether to count the objects that are alive on the device, so that leaks can be found. See
+    /// the [`object_tracker`](crate::device::object_tracker) module for more information.
+    ///
+    /// The default value is `false`.
+    pub object_tracking: bool,
+
+    /// The number of recent submissions to the queues of the device to remember, for
+    /// [`Device::submission_history`] and [`DeviceLostReport`](lost_report::DeviceLostReport).
+    ///
+    /// The default value is `0`.
+    pub submission_history_len: usize,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -835,7 +2109,14 @@ impl Default for DeviceCreateInfo<'static> {
         Self {
             enabled_extensions: DeviceExtensions::none(),
             enabled_features: Features::none(),
+            extension_features: ExtensionFeatures::new(),
+            physical_devices: Vec::new(),
+            extensions: ExtensionChain::new(),
+            host_allocator: None,
             queue_create_infos: Vec::new(),
+            automatic_debug_names: false,
+            object_tracking: false,
+            submission_history_len: 0,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -854,9 +2135,39 @@ pub struct QueueCreateInfo<'qf> {
     /// As this is only a hint, different drivers may handle this value differently and there are no
     /// guarantees about its behavior.
     ///
+    /// The index of each element is the [index](Queue::id_within_family) of the queue within its
+    /// family. For example, `vec![1.0, 0.0]` creates a high-priority queue with index 0, and a
+    /// low-priority queue with index 1, for background work such as uploads. The number of
+    /// elements must not be greater than the
+    /// [number of queues](QueueFamily::queues_count) of the family.
+    ///
     /// The default value is a single queue with a priority of 0.5.
     pub queues: Vec<f32>,
 
+    /// Whether the queues are protected-capable queues, which can execute protected submissions.
+    ///
+    /// If set to `true`, the [`protected_memory`](crate::device::Features::protected_memory)
+    /// feature must be enabled, and the queue family must
+    /// [support protected queues](QueueFamily::supports_protected).
+    ///
+    /// The default value is `false`.
+    pub protected: bool,
+
+    /// The system-wide priority of the queues, relative to the queues of other processes.
+    ///
+    /// If set to `Some`, the
+    /// [`khr_global_priority`](crate::device::DeviceExtensions::khr_global_priority) or
+    /// [`ext_global_priority`](crate::device::DeviceExtensions::ext_global_priority) extension
+    /// must be enabled. If the
+    /// [`global_priority_query`](crate::device::Features::global_priority_query) feature is
+    /// enabled, the priority must be one of the
+    /// [supported priorities](QueueFamily::supported_global_priorities) of the queue family.
+    /// Otherwise, creating the device may fail with [`DeviceCreationError::NotPermitted`], for
+    /// example if the process is not allowed to use a high priority.
+    ///
+    /// The default value is `None`, which is equivalent to [`QueueGlobalPriority::Medium`].
+    pub global_priority: Option<QueueGlobalPriority>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -867,11 +2178,54 @@ impl<'qf> QueueCreateInfo<'qf> {
         QueueCreateInfo {
             family,
             queues: vec![0.5],
+            protected: false,
+            global_priority: None,
             _ne: crate::NonExhaustive(()),
         }
     }
 }
 
+/// The system-wide priority of a queue, relative to the queues of other processes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum QueueGlobalPriority {
+    /// Lower than the default priority, for background work.
+    Low = ash::vk::QueueGlobalPriorityKHR::LOW.as_raw(),
+
+    /// The default priority.
+    Medium = ash::vk::QueueGlobalPriorityKHR::MEDIUM.as_raw(),
+
+    /// Higher than the default priority.
+    High = ash::vk::QueueGlobalPriorityKHR::HIGH.as_raw(),
+
+    /// The highest priority, for work that must never be delayed by the queues of other
+    /// processes. This usually requires special privileges.
+    Realtime = ash::vk::QueueGlobalPriorityKHR::REALTIME.as_raw(),
+}
+
+impl From<QueueGlobalPriority> for ash::vk::QueueGlobalPriorityKHR {
+    #[inline]
+    fn from(val: QueueGlobalPriority) -> Self {
+        Self::from_raw(val as i32)
+    }
+}
+
+impl TryFrom<ash::vk::QueueGlobalPriorityKHR> for QueueGlobalPriority {
+    type Error = ();
+
+    #[inline]
+    fn try_from(val: ash::vk::QueueGlobalPriorityKHR) -> Result<Self, Self::Error> {
+        Ok(match val {
+            ash::vk::QueueGlobalPriorityKHR::LOW => Self::Low,
+            ash::vk::QueueGlobalPriorityKHR::MEDIUM => Self::Medium,
+            ash::vk::QueueGlobalPriorityKHR::HIGH => Self::High,
+            ash::vk::QueueGlobalPriorityKHR::REALTIME => Self::Realtime,
+            _ => return Err(()),
+        })
+    }
+}
+
 /// Implemented on objects that belong to a Vulkan device.
 ///
 /// # Safety
@@ -894,6 +2248,43 @@ where
     }
 }
 
+/// Gives a human-readable name to an object owned by a device, for debugging purposes.
+///
+/// The name is shown in the messages of the validation layers and in tools such as RenderDoc.
+/// This trait is implemented for all the types that implement both [`VulkanObject`] and
+/// [`DeviceOwned`].
+pub trait DebugName {
+    /// Sets the name of the object to `label`, or removes it if `label` is `None`.
+    ///
+    /// If [`automatic_debug_names`](DeviceCreateInfo::automatic_debug_names) was enabled when
+    /// creating the device, the type of the object is put in front of `label`, and removing the
+    /// name restores the automatic one.
+    ///
+    /// If the [`ext_debug_utils`](crate::instance::InstanceExtensions::ext_debug_utils)
+    /// extension is not enabled on the instance, this does nothing.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `label` contains a nul character.
+    fn set_debug_name(&self, label: Option<&str>) -> Result<(), OomError>;
+}
+
+impl<T> DebugName for T
+where
+    T: VulkanObject + DeviceOwned,
+{
+    #[inline]
+    fn set_debug_name(&self, label: Option<&str>) -> Result<(), OomError> {
+        unsafe {
+            self.device().set_debug_name_raw(
+                T::Object::TYPE,
+                self.internal_object().as_raw(),
+                label,
+            )
+        }
+    }
+}
+
 /// The properties of a Unix file descriptor when it is imported.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -950,7 +2341,161 @@ impl From<Error> for MemoryFdPropertiesError {
     }
 }
 
+/// Error that can happen when acquiring the profiling lock of a device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProfilingLockError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The lock was not acquired before the timeout.
+    Timeout,
+
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl error::Error for ProfilingLockError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ProfilingLockError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::OomError(_) => write!(fmt, "no memory available"),
+            Self::Timeout => write!(fmt, "the lock was not acquired before the timeout"),
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+        }
+    }
+}
+
+impl From<Error> for ProfilingLockError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            e @ Error::OutOfHostMemory => Self::OomError(e.into()),
+            _ => panic!("Unexpected error value: {}", err as i32),
+        }
+    }
+}
+
+/// Error that can happen when getting the fault info of a device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeviceFaultError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+
+    /// The fault info was requested, but the device is not lost.
+    DeviceNotLost,
+}
+
+impl error::Error for DeviceFaultError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DeviceFaultError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::OomError(_) => write!(fmt, "no memory available"),
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
+            Self::DeviceNotLost => write!(fmt, "the device is not lost"),
+        }
+    }
+}
+
+impl From<Error> for DeviceFaultError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            e @ Error::OutOfHostMemory => Self::OomError(e.into()),
+            _ => panic!("Unexpected error value: {}", err as i32),
+        }
+    }
+}
+
+/// Error that can happen when waiting for a device or a queue to become idle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WaitIdleError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The connection to the device has been lost.
+    DeviceLost,
+}
+
+impl error::Error for WaitIdleError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for WaitIdleError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::OomError(_) => write!(fmt, "no memory available"),
+            Self::DeviceLost => write!(fmt, "the connection to the device has been lost"),
+        }
+    }
+}
+
+impl From<Error> for WaitIdleError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            e @ Error::OutOfHostMemory | e @ Error::OutOfDeviceMemory => Self::OomError(e.into()),
+            Error::DeviceLost => Self::DeviceLost,
+            _ => panic!("Unexpected error value: {}", err as i32),
+        }
+    }
+}
+
 /// Represents a queue where commands can be submitted.
+///
+/// Each queue of a family is a distinct queue, with its own [index](Queue::id_within_family)
+/// and [priority](Queue::priority). The operations that are submitted to different queues,
+/// even of the same family, are only ordered with each other through semaphores, such as with
+/// [`GpuFuture::then_signal_semaphore`](crate::sync::GpuFuture::then_signal_semaphore).
 // TODO: should use internal synchronization?
 #[derive(Debug)]
 pub struct Queue {
@@ -958,6 +2503,11 @@ pub struct Queue {
     device: Arc<Device>,
     family: u32,
     id: u32, // id within family
+    priority: f32,
+    global_priority: Option<QueueGlobalPriority>,
+    protected: bool,
+    // The queue debug labels that are open, outermost first.
+    labels: Mutex<Vec<String>>,
 }
 
 impl Queue {
@@ -977,20 +2527,41 @@ impl Queue {
     }
 
     /// Returns the index of this queue within its family.
+    ///
+    /// This is the index of its priority in [`QueueCreateInfo::queues`].
     #[inline]
     pub fn id_within_family(&self) -> u32 {
         self.id
     }
 
+    /// Returns the relative priority that the queue was created with.
+    #[inline]
+    pub fn priority(&self) -> f32 {
+        self.priority
+    }
+
+    /// Returns the global priority that the queue was created with, if one was given.
+    #[inline]
+    pub fn global_priority(&self) -> Option<QueueGlobalPriority> {
+        self.global_priority
+    }
+
+    /// Returns whether this is a protected-capable queue.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
+
     /// Waits until all work on this queue has finished.
     ///
     /// Just like `Device::wait()`, you shouldn't have to call this function in a typical program.
     #[inline]
-    pub fn wait(&self) -> Result<(), OomError> {
+    pub fn wait(&self) -> Result<(), WaitIdleError> {
         unsafe {
             let fns = self.device.fns();
             let handle = self.handle.lock().unwrap();
-            check_errors((fns.v1_0.queue_wait_idle)(*handle))?;
+            self.device
+                .check_errors((fns.v1_0.queue_wait_idle)(*handle))?;
             Ok(())
         }
     }
@@ -1025,6 +2596,8 @@ impl Queue {
             (fns.ext_debug_utils.queue_begin_debug_utils_label_ext)(*handle, &label_info);
         }
 
+        self.labels.lock().unwrap().push(label_name);
+
         Ok(())
     }
 
@@ -1066,6 +2639,8 @@ impl Queue {
             (fns.ext_debug_utils.queue_end_debug_utils_label_ext)(*handle);
         }
 
+        self.labels.lock().unwrap().pop();
+
         Ok(())
     }
 
@@ -1141,6 +2716,95 @@ impl Queue {
     }
 }
 
+impl Queue {
+    /// Returns the queue debug labels that are open, outermost first.
+    #[inline]
+    pub fn debug_utils_labels(&self) -> Vec<String> {
+        self.labels.lock().unwrap().clone()
+    }
+
+    /// Returns the last checkpoints that the device reached on this queue, after the device has
+    /// been lost. Checkpoints are inserted into command buffers with
+    /// [`set_checkpoint`](crate::command_buffer::AutoCommandBufferBuilder::set_checkpoint).
+    ///
+    /// The
+    /// [`nv_device_diagnostic_checkpoints`](DeviceExtensions::nv_device_diagnostic_checkpoints)
+    /// extension must be enabled on the device. If the
+    /// [`khr_synchronization2`](DeviceExtensions::khr_synchronization2) extension is enabled too,
+    /// the stages of the checkpoints are reported with the names of the `synchronization2` stages.
+    pub fn checkpoint_data(&self) -> Result<Vec<Checkpoint>, CheckpointError> {
+        if !self
+            .device
+            .enabled_extensions()
+            .nv_device_diagnostic_checkpoints
+        {
+            return Err(CheckpointError::ExtensionNotEnabled {
+                extension: "nv_device_diagnostic_checkpoints",
+                reason: "tried to get the checkpoint data of a queue",
+            });
+        }
+
+        // VUID-vkGetQueueCheckpointDataNV-queue-02025
+        if !self.device.is_lost() {
+            return Err(CheckpointError::DeviceNotLost);
+        }
+
+        let fns = self.device.fns();
+        let handle = self.handle.lock().unwrap();
+
+        // The marker is the value that was given to `set_checkpoint`.
+        let checkpoints = unsafe {
+            // With `synchronization2`, the more precise stages of `PipelineStageFlags2` are
+            // reported.
+            if self.device.enabled_extensions().khr_synchronization2 {
+                let mut count = 0;
+                (fns.khr_synchronization2.get_queue_checkpoint_data2_nv)(
+                    *handle,
+                    &mut count,
+                    ptr::null_mut(),
+                );
+
+                let mut data = vec![ash::vk::CheckpointData2NV::default(); count as usize];
+                (fns.khr_synchronization2.get_queue_checkpoint_data2_nv)(
+                    *handle,
+                    &mut count,
+                    data.as_mut_ptr(),
+                );
+                data.truncate(count as usize);
+
+                data.into_iter()
+                    .map(|data| Checkpoint {
+                        marker: data.p_checkpoint_marker as usize as u64,
+                        stage: format!("{:?}", data.stage),
+                    })
+                    .collect()
+            } else {
+                let mut count = 0;
+                (fns.nv_device_diagnostic_checkpoints
+                    .get_queue_checkpoint_data_nv)(
+                    *handle, &mut count, ptr::null_mut()
+                );
+
+                let mut data = vec![ash::vk::CheckpointDataNV::default(); count as usize];
+                (fns.nv_device_diagnostic_checkpoints
+                    .get_queue_checkpoint_data_nv)(
+                    *handle, &mut count, data.as_mut_ptr()
+                );
+                data.truncate(count as usize);
+
+                data.into_iter()
+                    .map(|data| Checkpoint {
+                        marker: data.p_checkpoint_marker as usize as u64,
+                        stage: format!("{:?}", data.stage),
+                    })
+                    .collect()
+            }
+        };
+
+        Ok(checkpoints)
+    }
+}
+
 unsafe impl SynchronizedVulkanObject for Queue {
     type Object = ash::vk::Queue;
 
@@ -1197,10 +2861,20 @@ impl fmt::Display for DebugUtilsError {
 
 #[cfg(test)]
 mod tests {
+    use crate::device::memory_report::{
+        DeviceMemoryReportCallback, DeviceMemoryReportEvent, DeviceMemoryReportEventType,
+    };
     use crate::device::physical::PhysicalDevice;
-    use crate::device::{Device, DeviceCreateInfo, DeviceCreationError, QueueCreateInfo};
+    use crate::device::{
+        DebugName, Device, DeviceCreateInfo, DeviceCreationError, DeviceExtensions,
+        DeviceFaultError, QueueCreateInfo, QueueGlobalPriority,
+    };
     use crate::device::{FeatureRestriction, FeatureRestrictionError, Features};
-    use std::sync::Arc;
+    use crate::extension_chain::ExtensionChain;
+    use crate::memory::{DeviceMemory, MemoryAllocateInfo};
+    use crate::sync::Fence;
+    use crate::Version;
+    use std::sync::{mpsc, Arc, Mutex};
 
     #[test]
     fn one_ref() {
@@ -1266,6 +2940,37 @@ mod tests {
         };
     }
 
+    #[test]
+    fn protected_queue_requires_feature() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let family = match physical.queue_families().find(|f| f.supports_protected()) {
+            Some(f) => f,
+            None => return,
+        };
+
+        match Device::new(
+            physical,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo {
+                    protected: true,
+                    ..QueueCreateInfo::family(family)
+                }],
+                ..Default::default()
+            },
+        ) {
+            Err(DeviceCreationError::FeatureNotEnabled {
+                feature: "protected_memory",
+                ..
+            }) => (),
+            _ => panic!(),
+        };
+    }
+
     #[test]
     fn priority_out_of_range() {
         let instance = instance!();
@@ -1302,4 +3007,425 @@ mod tests {
             )
         });
     }
+
+    #[test]
+    fn automatic_debug_names_extension_not_enabled() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        if instance.enabled_extensions().ext_debug_utils {
+            return;
+        }
+
+        let family = physical.queue_families().next().unwrap();
+
+        match Device::new(
+            physical,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo::family(family)],
+                automatic_debug_names: true,
+                ..Default::default()
+            },
+        ) {
+            Err(DeviceCreationError::ExtensionNotEnabled {
+                extension: "ext_debug_utils",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn set_debug_name_without_debug_utils() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device.instance().enabled_extensions().ext_debug_utils {
+            return;
+        }
+
+        // Does nothing, instead of calling a function that isn't loaded.
+        let fence = Fence::new(device, Default::default()).unwrap();
+        assert!(fence.set_debug_name(Some("fence")).is_ok());
+    }
+
+    #[test]
+    fn queue_priorities() {
+        let instance = instance!();
+        let (physical, family) = match PhysicalDevice::enumerate(&instance)
+            .flat_map(|p| p.queue_families())
+            .find(|family| family.queues_count() >= 2)
+        {
+            Some(family) => (family.physical_device(), family),
+            None => return,
+        };
+
+        let (_, queues) = Device::new(
+            physical,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo {
+                    queues: vec![1.0, 0.25],
+                    ..QueueCreateInfo::family(family)
+                }],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let queues: Vec<_> = queues.collect();
+
+        assert_eq!(queues.len(), 2);
+        assert_eq!(queues[0].id_within_family(), 0);
+        assert_eq!(queues[0].priority(), 1.0);
+        assert_eq!(queues[1].id_within_family(), 1);
+        assert_eq!(queues[1].priority(), 0.25);
+        assert!(queues[0] != queues[1]);
+    }
+
+    #[test]
+    fn global_priority_extension_not_enabled() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let family = physical.queue_families().next().unwrap();
+
+        match Device::new(
+            physical,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo {
+                    global_priority: Some(QueueGlobalPriority::High),
+                    ..QueueCreateInfo::family(family)
+                }],
+                ..Default::default()
+            },
+        ) {
+            Err(DeviceCreationError::ExtensionNotEnabled {
+                extension: "khr_global_priority",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn memory_report_extension_not_enabled() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let family = physical.queue_families().next().unwrap();
+        let callback: DeviceMemoryReportCallback = Arc::new(|_| {});
+
+        match unsafe {
+            Device::with_memory_report_callbacks(
+                physical,
+                DeviceCreateInfo {
+                    queue_create_infos: vec![QueueCreateInfo::family(family)],
+                    ..Default::default()
+                },
+                [callback],
+            )
+        } {
+            Err(DeviceCreationError::ExtensionNotEnabled {
+                extension: "ext_device_memory_report",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn memory_report_allocate_free() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).find(|p| {
+            p.supported_extensions().ext_device_memory_report
+                && p.supported_features().device_memory_report
+        }) {
+            Some(p) => p,
+            None => return,
+        };
+
+        let family = physical.queue_families().next().unwrap();
+        let (sender, receiver) = mpsc::channel::<DeviceMemoryReportEvent>();
+        let sender = Mutex::new(sender);
+        let callback: DeviceMemoryReportCallback = Arc::new(move |event| {
+            let _ = sender.lock().unwrap().send(event.clone());
+        });
+
+        let (device, _) = unsafe {
+            Device::with_memory_report_callbacks(
+                physical,
+                DeviceCreateInfo {
+                    enabled_extensions: DeviceExtensions {
+                        ext_device_memory_report: true,
+                        ..DeviceExtensions::none()
+                    },
+                    enabled_features: Features {
+                        device_memory_report: true,
+                        ..Features::none()
+                    },
+                    queue_create_infos: vec![QueueCreateInfo::family(family)],
+                    ..Default::default()
+                },
+                [callback],
+            )
+            .unwrap()
+        };
+
+        // Ignore the events of the memory that the implementation allocated on its own.
+        receiver.try_iter().for_each(drop);
+
+        let memory_type = physical.memory_types().next().unwrap();
+        let memory = DeviceMemory::allocate(
+            device,
+            MemoryAllocateInfo {
+                allocation_size: 1024,
+                memory_type_index: memory_type.id(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let allocate = receiver
+            .try_iter()
+            .find(|event| event.ty == DeviceMemoryReportEventType::Allocate)
+            .unwrap();
+        assert!(allocate.size >= 1024);
+        assert_eq!(allocate.heap_index, memory_type.heap().id());
+
+        drop(memory);
+        assert!(receiver.try_iter().any(|event| {
+            event.ty == DeviceMemoryReportEventType::Free
+                && event.memory_object_id == allocate.memory_object_id
+        }));
+    }
+
+    #[test]
+    fn promoted_feature_enables_extension() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance)
+            .find(|p| p.supported_features().synchronization2)
+        {
+            Some(p) => p,
+            None => return,
+        };
+
+        let family = physical.queue_families().next().unwrap();
+
+        let device = Device::new(
+            physical,
+            DeviceCreateInfo {
+                enabled_features: Features {
+                    synchronization2: true,
+                    ..Features::none()
+                },
+                queue_create_infos: vec![QueueCreateInfo::family(family)],
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .0;
+
+        assert!(device.enabled_features().synchronization2);
+        assert!(
+            device.api_version() >= Version::V1_3
+                || device.enabled_extensions().khr_synchronization2
+        );
+    }
+
+    #[test]
+    fn recreate() {
+        let (device, queue) = gfx_dev_and_queue!();
+        assert!(!device.is_lost());
+
+        let (new_device, mut queues) = device.recreate(ExtensionChain::new()).unwrap();
+        assert!(!Arc::ptr_eq(&device, &new_device));
+        assert_eq!(new_device.enabled_extensions(), device.enabled_extensions());
+        assert_eq!(new_device.enabled_features(), device.enabled_features());
+
+        let new_queue = queues.next().unwrap();
+        assert_eq!(new_queue.family().id(), queue.family().id());
+        assert_eq!(new_queue.priority(), queue.priority());
+        assert!(Arc::ptr_eq(new_queue.device(), &new_device));
+    }
+
+    #[test]
+    fn object_tracker_enumerates_objects() {
+        use crate::device::object_tracker::{tag_scope, TrackedObjectType};
+
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+        let family = physical.queue_families().next().unwrap();
+
+        let (device, _) = Device::new(
+            physical,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo::family(family)],
+                object_tracking: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let tracker = device.object_tracker().unwrap().clone();
+        let memory_type = physical.memory_types().next().unwrap();
+
+        let objects = {
+            let _scope = tag_scope("frame");
+            let memory = DeviceMemory::allocate(
+                device.clone(),
+                MemoryAllocateInfo {
+                    allocation_size: 1024,
+                    memory_type_index: memory_type.id(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let fence = Fence::new(device.clone(), Default::default()).unwrap();
+            (memory, fence)
+        };
+
+        let snapshot = tracker.snapshot();
+        assert_eq!(
+            snapshot.count(TrackedObjectType::DeviceMemory, Some("frame")),
+            1
+        );
+        assert_eq!(snapshot.count(TrackedObjectType::Fence, Some("frame")), 1);
+
+        drop(objects);
+        assert_eq!(tracker.snapshot().iter().count(), 0);
+
+        // The new device tracks its objects too.
+        let (new_device, _) = device.recreate(ExtensionChain::new()).unwrap();
+        assert!(new_device.object_tracker().is_some());
+    }
+
+    #[test]
+    fn required_extensions_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        // Includes `khr_portability_subset` on portability subset devices.
+        assert!(device
+            .enabled_extensions()
+            .is_superset_of(device.physical_device().required_extensions()));
+    }
+
+    #[test]
+    fn submission_history() {
+        use ash::vk::Handle;
+
+        let (device, queue) = gfx_dev_and_queue!();
+
+        // Disabled by default.
+        device.record_submission(&queue, &[ash::vk::CommandBuffer::from_raw(1)], 0, 0);
+        assert!(device.submission_history().is_empty());
+
+        let (device, mut queues) = Device::new(
+            device.physical_device(),
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo::family(queue.family())],
+                submission_history_len: 2,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let queue = queues.next().unwrap();
+
+        for raw in 1..=3 {
+            device.record_submission(&queue, &[ash::vk::CommandBuffer::from_raw(raw)], 0, 1);
+        }
+
+        // The oldest submission was evicted.
+        let history = device.submission_history();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].command_buffers, [2]);
+        assert_eq!(history[1].command_buffers, [3]);
+        assert!(history[0].time <= history[1].time);
+        assert_eq!(history[1].queue_family_index, queue.family().id());
+        assert_eq!(history[1].queue_index, queue.id_within_family());
+        assert_eq!(history[1].wait_semaphore_count, 0);
+        assert_eq!(history[1].signal_semaphore_count, 1);
+    }
+
+    #[test]
+    fn fault_info_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match device.fault_info() {
+            Err(DeviceFaultError::ExtensionNotEnabled {
+                extension: "ext_device_fault",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn fault_info_device_not_lost() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).find(|p| {
+            p.supported_extensions().ext_device_fault && p.supported_features().device_fault
+        }) {
+            Some(p) => p,
+            None => return,
+        };
+
+        let family = physical.queue_families().next().unwrap();
+        let create_info = |enabled_features| DeviceCreateInfo {
+            enabled_extensions: DeviceExtensions {
+                ext_device_fault: true,
+                ..DeviceExtensions::none()
+            },
+            enabled_features,
+            queue_create_infos: vec![QueueCreateInfo::family(family)],
+            ..Default::default()
+        };
+
+        let (device, _) = Device::new(physical, create_info(Features::none())).unwrap();
+        match device.fault_info() {
+            Err(DeviceFaultError::FeatureNotEnabled {
+                feature: "device_fault",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+
+        let (device, _) = Device::new(
+            physical,
+            create_info(Features {
+                device_fault: true,
+                ..Features::none()
+            }),
+        )
+        .unwrap();
+        assert!(!device.is_lost());
+        assert_eq!(device.fault_info(), Err(DeviceFaultError::DeviceNotLost));
+    }
+
+    #[test]
+    fn capabilities() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let capabilities = physical.capabilities(Version::V1_0, &DeviceExtensions::none());
+        assert_eq!(capabilities.api_version, Version::V1_0);
+        assert!(physical
+            .supported_features()
+            .is_superset_of(&capabilities.features));
+        assert!(
+            !capabilities.features.vulkan_memory_model
+                || capabilities.extensions.khr_vulkan_memory_model
+        );
+    }
 }
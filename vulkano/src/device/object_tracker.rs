@@ -0,0 +1,362 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Counting the objects that are alive on a device, to find leaks.
+//!
+//! When [`DeviceCreateInfo::object_tracking`](crate::device::DeviceCreateInfo::object_tracking)
+//! is `true`, the device has an [`ObjectTracker`] that counts the buffers, images, memory
+//! allocations, pipelines, descriptor sets, semaphores and fences that are alive on it, by
+//! [type](TrackedObjectType) and by tag.
+//!
+//! The tag of an object is the innermost [`tag_scope`] that was active on the thread that created
+//! it. A [snapshot](ObjectTracker::snapshot) of the counts can be compared with a later one, to
+//! find which tags have more objects than before:
+//!
+//! ```
+//! use vulkano::device::object_tracker::tag_scope;
+//!
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! let tracker = device.object_tracker().unwrap();
+//! let before = tracker.snapshot();
+//!
+//! {
+//!     let _scope = tag_scope("level 2");
+//!     // Load the level. Objects created on this thread are tagged with "level 2".
+//! }
+//!
+//! // Unload the level.
+//!
+//! // Prints the counts that changed, for example `+12 descriptor sets [level 2]`.
+//! print!("{}", tracker.snapshot().diff(&before));
+//! ```
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    fmt,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
+
+thread_local! {
+    // The tags of the active `TagScope`s of the thread, innermost last.
+    static TAGS: RefCell<Vec<Arc<str>>> = RefCell::new(Vec::new());
+}
+
+/// Counts the objects that are alive on a device.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug, Default)]
+pub struct ObjectTracker {
+    counts: Mutex<HashMap<ObjectKey, u64>>,
+}
+
+type ObjectKey = (TrackedObjectType, Option<Arc<str>>);
+
+impl ObjectTracker {
+    /// Returns the number of objects of type `object_type` that are alive, with any tag.
+    pub fn live_count(&self, object_type: TrackedObjectType) -> u64 {
+        self.counts
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|((ty, _), _)| *ty == object_type)
+            .map(|(_, &count)| count)
+            .sum()
+    }
+
+    /// Returns the current counts of the objects that are alive.
+    pub fn snapshot(&self) -> ObjectSnapshot {
+        ObjectSnapshot {
+            counts: self
+                .counts
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, &count)| count != 0)
+                .map(|(key, &count)| (key.clone(), count))
+                .collect(),
+        }
+    }
+}
+
+/// Tags the objects that are created on the current thread, until the returned value is dropped.
+///
+/// If several scopes are active, the innermost one is used.
+#[inline]
+pub fn tag_scope(tag: impl Into<Arc<str>>) -> TagScope {
+    TAGS.with(|tags| tags.borrow_mut().push(tag.into()));
+
+    TagScope {
+        _not_send: PhantomData,
+    }
+}
+
+/// A scope that tags the objects created on the current thread. Returned by [`tag_scope`].
+#[derive(Debug)]
+#[must_use = "the tag is removed when the scope is dropped"]
+pub struct TagScope {
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for TagScope {
+    #[inline]
+    fn drop(&mut self) {
+        TAGS.with(|tags| tags.borrow_mut().pop());
+    }
+}
+
+/// A type of object that is counted by an [`ObjectTracker`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum TrackedObjectType {
+    /// An [`UnsafeBuffer`](crate::buffer::sys::UnsafeBuffer).
+    Buffer,
+
+    /// An [`UnsafeImage`](crate::image::sys::UnsafeImage), including the images of swapchains.
+    Image,
+
+    /// A [`DeviceMemory`](crate::memory::DeviceMemory) allocation.
+    DeviceMemory,
+
+    /// A [`GraphicsPipeline`](crate::pipeline::GraphicsPipeline) or a
+    /// [`ComputePipeline`](crate::pipeline::ComputePipeline).
+    Pipeline,
+
+    /// A descriptor set allocated by a
+    /// [`PersistentDescriptorSet`](crate::descriptor_set::PersistentDescriptorSet) or a
+    /// [`SingleLayoutDescSetPool`](crate::descriptor_set::SingleLayoutDescSetPool).
+    DescriptorSet,
+
+    /// A [`Semaphore`](crate::sync::Semaphore).
+    Semaphore,
+
+    /// A [`Fence`](crate::sync::Fence).
+    Fence,
+}
+
+impl fmt::Display for TrackedObjectType {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::Buffer => write!(f, "buffers"),
+            Self::Image => write!(f, "images"),
+            Self::DeviceMemory => write!(f, "memory allocations"),
+            Self::Pipeline => write!(f, "pipelines"),
+            Self::DescriptorSet => write!(f, "descriptor sets"),
+            Self::Semaphore => write!(f, "semaphores"),
+            Self::Fence => write!(f, "fences"),
+        }
+    }
+}
+
+/// The counts of the objects that were alive at some point, returned by
+/// [`ObjectTracker::snapshot`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObjectSnapshot {
+    counts: BTreeMap<ObjectKey, u64>,
+}
+
+impl ObjectSnapshot {
+    /// Returns the number of objects of type `object_type` with the tag `tag`.
+    #[inline]
+    pub fn count(&self, object_type: TrackedObjectType, tag: Option<&str>) -> u64 {
+        self.counts
+            .get(&(object_type, tag.map(Arc::from)))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of objects of type `object_type`, with any tag.
+    #[inline]
+    pub fn total(&self, object_type: TrackedObjectType) -> u64 {
+        self.counts
+            .iter()
+            .filter(|((ty, _), _)| *ty == object_type)
+            .map(|(_, &count)| count)
+            .sum()
+    }
+
+    /// Returns the non-zero counts, ordered by type and then by tag.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (TrackedObjectType, Option<&str>, u64)> {
+        self.counts
+            .iter()
+            .map(|((ty, tag), &count)| (*ty, tag.as_deref(), count))
+    }
+
+    /// Returns the counts that are different in `self` than in `earlier`.
+    pub fn diff(&self, earlier: &ObjectSnapshot) -> ObjectSnapshotDiff {
+        let mut keys: Vec<_> = self.counts.keys().chain(earlier.counts.keys()).collect();
+        keys.sort();
+        keys.dedup();
+
+        ObjectSnapshotDiff {
+            changes: keys
+                .into_iter()
+                .filter_map(|key| {
+                    let before = earlier.counts.get(key).copied().unwrap_or(0);
+                    let after = self.counts.get(key).copied().unwrap_or(0);
+
+                    (before != after).then(|| ObjectCountChange {
+                        object_type: key.0,
+                        tag: key.1.clone(),
+                        before,
+                        after,
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for ObjectSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        for (object_type, tag, count) in self.iter() {
+            write!(f, "{} {}", count, object_type)?;
+            write_tag(f, tag)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The differences between two [`ObjectSnapshot`]s, returned by [`ObjectSnapshot::diff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObjectSnapshotDiff {
+    changes: Vec<ObjectCountChange>,
+}
+
+impl ObjectSnapshotDiff {
+    /// Returns the counts that changed, ordered by type and then by tag.
+    #[inline]
+    pub fn changes(&self) -> &[ObjectCountChange] {
+        &self.changes
+    }
+
+    /// Returns whether no count changed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for ObjectSnapshotDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        for change in &self.changes {
+            write!(f, "{:+} {}", change.delta(), change.object_type)?;
+            write_tag(f, change.tag.as_deref())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A count that is different in two [`ObjectSnapshot`]s.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObjectCountChange {
+    /// The type of the objects.
+    pub object_type: TrackedObjectType,
+
+    /// The tag of the objects.
+    pub tag: Option<Arc<str>>,
+
+    /// The count in the earlier snapshot.
+    pub before: u64,
+
+    /// The count in the later snapshot.
+    pub after: u64,
+}
+
+impl ObjectCountChange {
+    /// Returns the number of objects that were added, which is negative if objects were removed.
+    #[inline]
+    pub fn delta(&self) -> i64 {
+        self.after as i64 - self.before as i64
+    }
+}
+
+fn write_tag(f: &mut fmt::Formatter, tag: Option<&str>) -> Result<(), fmt::Error> {
+    match tag {
+        Some(tag) => writeln!(f, " [{}]", tag),
+        None => writeln!(f),
+    }
+}
+
+// Counts an object in the tracker of its device while it is alive.
+#[derive(Debug)]
+pub(crate) struct TrackedObject(Option<(Arc<ObjectTracker>, ObjectKey)>);
+
+impl TrackedObject {
+    pub(crate) fn new(
+        tracker: Option<&Arc<ObjectTracker>>,
+        object_type: TrackedObjectType,
+    ) -> Self {
+        TrackedObject(tracker.map(|tracker| {
+            let tag = TAGS.with(|tags| tags.borrow().last().cloned());
+            let key = (object_type, tag);
+            *tracker
+                .counts
+                .lock()
+                .unwrap()
+                .entry(key.clone())
+                .or_insert(0) += 1;
+
+            (tracker.clone(), key)
+        }))
+    }
+}
+
+impl Drop for TrackedObject {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some((tracker, key)) = &self.0 {
+            *tracker.counts.lock().unwrap().get_mut(key).unwrap() -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tag_scope, ObjectTracker, TrackedObject, TrackedObjectType};
+    use std::sync::Arc;
+
+    #[test]
+    fn snapshot_diff() {
+        let tracker = Arc::new(ObjectTracker::default());
+        let _buffer = TrackedObject::new(Some(&tracker), TrackedObjectType::Buffer);
+        let before = tracker.snapshot();
+
+        let sets: Vec<_> = {
+            let _scope = tag_scope("level");
+            (0..3)
+                .map(|_| TrackedObject::new(Some(&tracker), TrackedObjectType::DescriptorSet))
+                .collect()
+        };
+        let _untagged = TrackedObject::new(Some(&tracker), TrackedObjectType::DescriptorSet);
+
+        let after = tracker.snapshot();
+        assert_eq!(
+            after.count(TrackedObjectType::DescriptorSet, Some("level")),
+            3
+        );
+        assert_eq!(after.total(TrackedObjectType::DescriptorSet), 4);
+
+        let diff = after.diff(&before);
+        assert_eq!(diff.changes().len(), 2);
+        assert_eq!(
+            diff.to_string(),
+            "+1 descriptor sets\n+3 descriptor sets [level]\n"
+        );
+
+        drop(sets);
+        assert_eq!(tracker.live_count(TrackedObjectType::DescriptorSet), 1);
+        assert_eq!(tracker.live_count(TrackedObjectType::Buffer), 1);
+    }
+}
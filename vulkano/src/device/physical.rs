@@ -10,18 +10,34 @@
 use crate::{
     buffer::{BufferUsage, ExternalBufferInfo, ExternalBufferProperties},
     check_errors,
-    device::{DeviceExtensions, Features, FeaturesFfi, Properties, PropertiesFfi},
-    format::{Format, FormatProperties},
+    device::{
+        promoted_feature_extensions, DeviceExtensions, ExtensionFeatureStruct,
+        ExtensionRestrictionError, FeatureRestrictionError, Features, FeaturesFfi, Properties,
+        PropertiesFfi, QueueGlobalPriority,
+    },
+    format::{DrmFormatModifierProperties, Format, FormatProperties},
     image::{ImageCreateFlags, ImageFormatInfo, ImageFormatProperties, ImageUsage},
     instance::{Instance, InstanceCreationError},
+    memory::pool::MemoryPropertyFlags,
+    query::{PerformanceCounter, PerformanceCountersError},
     swapchain::{
-        ColorSpace, FullScreenExclusive, PresentMode, SupportedSurfaceTransforms, Surface,
-        SurfaceApi, SurfaceCapabilities, SurfaceInfo,
+        ColorSpace, FullScreenExclusive, PresentMode, PresentModePreference,
+        SupportedPresentGravity, SupportedPresentScaling, SupportedSurfaceTransforms, Surface,
+        SurfaceApi, SurfaceCapabilities, SurfaceFormatPreference, SurfaceInfo,
     },
     sync::{ExternalSemaphoreInfo, ExternalSemaphoreProperties, PipelineStage},
     DeviceSize, Error, OomError, Success, Version, VulkanObject,
 };
-use std::{error, ffi::CStr, fmt, hash::Hash, mem::MaybeUninit, ptr, sync::Arc};
+use std::{
+    error,
+    ffi::{c_void, CStr},
+    fmt,
+    hash::Hash,
+    mem::MaybeUninit,
+    os::raw::c_ulong,
+    ptr,
+    sync::Arc,
+};
 
 #[derive(Clone, Debug)]
 pub(crate) struct PhysicalDeviceInfo {
@@ -33,6 +49,7 @@ pub(crate) struct PhysicalDeviceInfo {
     properties: Properties,
     memory_properties: ash::vk::PhysicalDeviceMemoryProperties,
     queue_families: Vec<ash::vk::QueueFamilyProperties>,
+    queue_family_global_priorities: Vec<Vec<QueueGlobalPriority>>,
 }
 
 pub(crate) fn init_physical_devices(
@@ -118,6 +135,7 @@ pub(crate) fn init_physical_devices(
                 properties: Default::default(),
                 memory_properties: Default::default(),
                 queue_families: Default::default(),
+                queue_family_global_priorities: Default::default(),
             };
 
             // Get the remaining infos.
@@ -255,6 +273,21 @@ fn init_info2(instance: &Instance, info: &mut PhysicalDeviceInfo) {
         }
 
         let mut families = vec![ash::vk::QueueFamilyProperties2::default(); num as usize];
+        let mut global_priority_properties = Vec::new();
+
+        if info.supported_extensions.khr_global_priority
+            || info.supported_extensions.ext_global_priority_query
+        {
+            global_priority_properties =
+                vec![ash::vk::QueueFamilyGlobalPriorityPropertiesKHR::default(); num as usize];
+
+            for (family, global_priority_properties) in families
+                .iter_mut()
+                .zip(global_priority_properties.iter_mut())
+            {
+                family.p_next = global_priority_properties as *mut _ as *mut c_void;
+            }
+        }
 
         if instance.api_version() >= Version::V1_1 {
             (fns.v1_1.get_physical_device_queue_family_properties2)(
@@ -271,6 +304,16 @@ fn init_info2(instance: &Instance, info: &mut PhysicalDeviceInfo) {
             );
         }
 
+        info.queue_family_global_priorities = global_priority_properties
+            .iter()
+            .map(|properties| {
+                properties.priorities[..properties.priority_count as usize]
+                    .iter()
+                    .filter_map(|&priority| priority.try_into().ok())
+                    .collect()
+            })
+            .collect();
+
         families
             .into_iter()
             .map(|family| family.queue_family_properties)
@@ -365,6 +408,87 @@ impl<'a> PhysicalDevice<'a> {
             })
     }
 
+    /// Returns the device groups that the physical devices of `instance` belong to. Each physical
+    /// device belongs to exactly one group. A device can be created from several physical devices
+    /// of the same group, with
+    /// [`DeviceCreateInfo::physical_devices`](crate::device::DeviceCreateInfo::physical_devices).
+    ///
+    /// If the instance doesn't have API version 1.1 or higher, and doesn't have the
+    /// [`khr_device_group_creation`](crate::instance::InstanceExtensions::khr_device_group_creation)
+    /// extension enabled, each physical device is returned in a group of its own.
+    pub fn enumerate_groups(
+        instance: &'a Arc<Instance>,
+    ) -> Result<Vec<PhysicalDeviceGroup<'a>>, OomError> {
+        let fns = instance.fns();
+
+        let enumerate_physical_device_groups = if instance.api_version() >= Version::V1_1 {
+            fns.v1_1.enumerate_physical_device_groups
+        } else if instance.enabled_extensions().khr_device_group_creation {
+            fns.khr_device_group_creation
+                .enumerate_physical_device_groups_khr
+        } else {
+            return Ok(Self::enumerate(instance)
+                .map(|physical_device| PhysicalDeviceGroup {
+                    physical_devices: vec![physical_device],
+                    subset_allocation: false,
+                })
+                .collect());
+        };
+
+        let groups_vk = unsafe {
+            loop {
+                let mut count = 0;
+                check_errors(enumerate_physical_device_groups(
+                    instance.internal_object(),
+                    &mut count,
+                    ptr::null_mut(),
+                ))?;
+
+                let mut groups_vk =
+                    vec![ash::vk::PhysicalDeviceGroupProperties::default(); count as usize];
+                let result = check_errors(enumerate_physical_device_groups(
+                    instance.internal_object(),
+                    &mut count,
+                    groups_vk.as_mut_ptr(),
+                ))?;
+
+                if !matches!(result, Success::Incomplete) {
+                    groups_vk.truncate(count as usize);
+                    break groups_vk;
+                }
+            }
+        };
+
+        Ok(groups_vk
+            .into_iter()
+            .map(|group_vk| PhysicalDeviceGroup {
+                physical_devices: group_vk.physical_devices
+                    [..group_vk.physical_device_count as usize]
+                    .iter()
+                    .filter_map(|&handle| {
+                        Self::enumerate(instance)
+                            .find(|physical_device| physical_device.info.handle == handle)
+                    })
+                    .collect(),
+                subset_allocation: group_vk.subset_allocation != ash::vk::FALSE,
+            })
+            .collect())
+    }
+
+    /// Returns the physical device that has the Vulkan handle `handle`. Returns `None` if the
+    /// instance has no such physical device.
+    ///
+    /// This is useful when the physical device to use is chosen by another library, such as an
+    /// OpenXR runtime with `xrGetVulkanGraphicsDevice2KHR`.
+    #[inline]
+    pub fn from_handle(
+        instance: &'a Arc<Instance>,
+        handle: ash::vk::PhysicalDevice,
+    ) -> Option<PhysicalDevice<'a>> {
+        PhysicalDevice::enumerate(instance)
+            .find(|physical_device| physical_device.internal_object() == handle)
+    }
+
     /// Returns the instance corresponding to this physical device.
     ///
     /// # Example
@@ -419,12 +543,166 @@ impl<'a> PhysicalDevice<'a> {
         &self.info.properties
     }
 
+    /// Returns the properties that identify the physical device and its driver, gathered in one
+    /// place.
+    pub fn identity(&self) -> PhysicalDeviceIdentity {
+        let properties = self.properties();
+
+        PhysicalDeviceIdentity {
+            device_name: properties.device_name.clone(),
+            vendor_id: properties.vendor_id,
+            device_id: properties.device_id,
+            device_uuid: properties.device_uuid,
+            driver_uuid: properties.driver_uuid,
+            device_luid: properties
+                .device_luid
+                .filter(|_| properties.device_luid_valid == Some(true)),
+            device_node_mask: properties
+                .device_node_mask
+                .filter(|_| properties.device_luid_valid == Some(true)),
+            pci_bus_info: match (
+                properties.pci_domain,
+                properties.pci_bus,
+                properties.pci_device,
+                properties.pci_function,
+            ) {
+                (Some(domain), Some(bus), Some(device), Some(function)) => Some(PciBusInfo {
+                    domain,
+                    bus,
+                    device,
+                    function,
+                }),
+                _ => None,
+            },
+            driver_id: properties.driver_id,
+            driver_name: properties.driver_name.clone(),
+            driver_info: properties.driver_info.clone(),
+            driver_version: properties.driver_version,
+        }
+    }
+
     /// Returns the features that are supported by this physical device.
     #[inline]
     pub fn supported_features(&self) -> &'a Features {
         &self.info.supported_features
     }
 
+    /// Queries the support for the features in the feature structure `S`, which can belong to an
+    /// extension that is not included in [`Features`]. This calls
+    /// `vkGetPhysicalDeviceFeatures2` with `S` in the chain.
+    ///
+    /// Returns `None` if the instance doesn't have API version 1.1 or higher, and doesn't have the
+    /// [`khr_get_physical_device_properties2`](crate::instance::InstanceExtensions::khr_get_physical_device_properties2)
+    /// extension enabled.
+    ///
+    /// # Safety
+    ///
+    /// - The device extension or Vulkan version that provides `S` must be supported by the
+    ///   physical device.
+    pub unsafe fn extension_features<S>(&self) -> Option<S>
+    where
+        S: ExtensionFeatureStruct,
+    {
+        let fns = self.instance.fns();
+        let mut features = S::default();
+        let mut features2 = ash::vk::PhysicalDeviceFeatures2 {
+            p_next: &mut features as *mut S as *mut c_void,
+            ..Default::default()
+        };
+
+        if self.instance.api_version() >= Version::V1_1 {
+            (fns.v1_1.get_physical_device_features2)(self.info.handle, &mut features2);
+        } else if self
+            .instance
+            .enabled_extensions()
+            .khr_get_physical_device_properties2
+        {
+            (fns.khr_get_physical_device_properties2
+                .get_physical_device_features2_khr)(self.info.handle, &mut features2);
+        } else {
+            return None;
+        }
+
+        Some(features)
+    }
+
+    /// Returns which extensions and features a device created from this physical device could
+    /// use, with the API version `api_version` and the device extensions `extensions`, and why
+    /// the others can't be used.
+    ///
+    /// The API version of a device is the [`api_version`](PhysicalDevice::api_version) of the
+    /// physical device, which is limited by the
+    /// [`max_api_version`](crate::instance::InstanceCreateInfo::max_api_version) of the instance.
+    /// A lower `api_version` shows what would be usable if the instance were created with that
+    /// maximum version instead. A higher one is lowered to that of the physical device.
+    ///
+    /// The report implements `Display`, which lists the restrictions that are not met, to be
+    /// written to a log.
+    pub fn capabilities(
+        &self,
+        api_version: Version,
+        extensions: &DeviceExtensions,
+    ) -> Capabilities {
+        let api_version = api_version.min(self.api_version());
+        let instance_extensions = self.instance.enabled_extensions();
+        let supported_extensions = self.supported_extensions();
+        let supported_features = self.supported_features();
+
+        let mut usable_extensions = extensions.union(self.required_extensions());
+        let mut unusable_extensions = Vec::new();
+
+        // Other extensions may require an extension that is disabled, so the requirements are
+        // checked again each time.
+        while let Err(err) = usable_extensions.check_requirements(
+            supported_extensions,
+            api_version,
+            instance_extensions,
+        ) {
+            let disabled = usable_extensions.disable(err.extension);
+            unusable_extensions.push(err);
+
+            // The restriction can't be met by disabling the extension.
+            if !disabled {
+                break;
+            }
+        }
+
+        // The extensions of promoted features are enabled automatically along with them, if
+        // their requirements are met.
+        let mut provided_extensions = usable_extensions.union(&promoted_feature_extensions(
+            &Features::all(),
+            api_version,
+            supported_extensions,
+        ));
+
+        while let Err(err) = provided_extensions.check_requirements(
+            supported_extensions,
+            api_version,
+            instance_extensions,
+        ) {
+            if !provided_extensions.disable(err.extension) {
+                break;
+            }
+        }
+
+        Capabilities {
+            api_version,
+            extensions: usable_extensions,
+            unusable_extensions,
+            features: supported_features.intersection(&Features::provided(
+                api_version,
+                &provided_extensions,
+                instance_extensions,
+            )),
+            unusable_features: Features::all().unavailable(
+                supported_features,
+                api_version,
+                &provided_extensions,
+                instance_extensions,
+            ),
+        }
+    }
+
     /// Retrieves the external memory properties supported for buffers with a given configuration.
     ///
     /// Returns `None` if the instance API version is less than 1.1 and the
@@ -560,6 +838,83 @@ impl<'a> PhysicalDevice<'a> {
         }
     }
 
+    /// Retrieves the Linux DRM format modifiers that are supported for images with a given
+    /// format, and their properties.
+    ///
+    /// Returns an empty list if the physical device does not support the
+    /// [`ext_image_drm_format_modifier`](crate::device::DeviceExtensions::ext_image_drm_format_modifier)
+    /// extension, or if the API version is less than 1.1 and the
+    /// [`khr_get_physical_device_properties2`](crate::instance::InstanceExtensions::khr_get_physical_device_properties2)
+    /// extension is not enabled on the instance.
+    pub fn drm_format_modifier_properties(
+        &self,
+        format: Format,
+    ) -> Vec<DrmFormatModifierProperties> {
+        if !(self.supported_extensions().ext_image_drm_format_modifier
+            && (self.api_version() >= Version::V1_1
+                || self
+                    .instance
+                    .enabled_extensions()
+                    .khr_get_physical_device_properties2))
+        {
+            return Vec::new();
+        }
+
+        let get_format_properties2 = |format_properties2: &mut ash::vk::FormatProperties2| unsafe {
+            let fns = self.instance.fns();
+
+            if self.api_version() >= Version::V1_1 {
+                (fns.v1_1.get_physical_device_format_properties2)(
+                    self.info.handle,
+                    format.into(),
+                    format_properties2,
+                );
+            } else {
+                (fns.khr_get_physical_device_properties2
+                    .get_physical_device_format_properties2_khr)(
+                    self.info.handle,
+                    format.into(),
+                    format_properties2,
+                );
+            }
+        };
+
+        let mut modifier_properties_list = ash::vk::DrmFormatModifierPropertiesListEXT::default();
+        let mut format_properties2 = ash::vk::FormatProperties2 {
+            p_next: &mut modifier_properties_list as *mut _ as *mut _,
+            ..Default::default()
+        };
+        get_format_properties2(&mut format_properties2);
+
+        let mut modifier_properties =
+            Vec::with_capacity(modifier_properties_list.drm_format_modifier_count as usize);
+        modifier_properties_list.p_drm_format_modifier_properties =
+            modifier_properties.as_mut_ptr();
+        let mut format_properties2 = ash::vk::FormatProperties2 {
+            p_next: &mut modifier_properties_list as *mut _ as *mut _,
+            ..Default::default()
+        };
+        get_format_properties2(&mut format_properties2);
+
+        unsafe {
+            modifier_properties
+                .set_len(modifier_properties_list.drm_format_modifier_count as usize);
+        }
+
+        modifier_properties
+            .into_iter()
+            .map(|properties: ash::vk::DrmFormatModifierPropertiesEXT| {
+                DrmFormatModifierProperties {
+                    drm_format_modifier: properties.drm_format_modifier,
+                    drm_format_modifier_plane_count: properties.drm_format_modifier_plane_count,
+                    drm_format_modifier_tiling_features: properties
+                        .drm_format_modifier_tiling_features
+                        .into(),
+                }
+            })
+            .collect()
+    }
+
     /// Retrieves the external handle properties supported for semaphores with a given
     /// configuration.
     ///
@@ -630,6 +985,34 @@ impl<'a> PhysicalDevice<'a> {
         })
     }
 
+    /// Returns whether opaque external handles that are exported from a device created from this
+    /// physical device can be imported into a device created from `other`, and the other way
+    /// around.
+    ///
+    /// Opaque handles, such as [`ExternalMemoryHandleType::OpaqueFd`] and
+    /// [`ExternalSemaphoreHandleType::OpaqueFd`], can only be shared between physical devices
+    /// that have the same device and driver UUIDs. This includes the same physical device that is
+    /// enumerated from different instances. Returns `false` if the UUIDs are not available.
+    ///
+    /// Other handle types, such as [`ExternalMemoryHandleType::DmaBuf`] and
+    /// [`ExternalSemaphoreHandleType::SyncFd`], can be shared between different physical devices.
+    ///
+    /// [`ExternalMemoryHandleType::OpaqueFd`]: crate::memory::ExternalMemoryHandleType::OpaqueFd
+    /// [`ExternalMemoryHandleType::DmaBuf`]: crate::memory::ExternalMemoryHandleType::DmaBuf
+    /// [`ExternalSemaphoreHandleType::OpaqueFd`]: crate::sync::ExternalSemaphoreHandleType::OpaqueFd
+    /// [`ExternalSemaphoreHandleType::SyncFd`]: crate::sync::ExternalSemaphoreHandleType::SyncFd
+    pub fn is_opaque_handle_compatible(&self, other: &PhysicalDevice) -> bool {
+        let (properties, other_properties) = (self.properties(), other.properties());
+
+        matches!(
+            (properties.device_uuid, other_properties.device_uuid),
+            (Some(a), Some(b)) if a == b
+        ) && matches!(
+            (properties.driver_uuid, other_properties.driver_uuid),
+            (Some(a), Some(b)) if a == b
+        )
+    }
+
     /// Returns the properties supported for images with a given image configuration.
     ///
     /// `Some` is returned if the configuration is supported, `None` if it is not.
@@ -890,6 +1273,12 @@ impl<'a> PhysicalDevice<'a> {
 
     /// Returns the capabilities that are supported by the physical device for the given surface.
     ///
+    /// `surface_info` can specify a full-screen exclusive mode or a present mode to query the
+    /// capabilities for, which requires the
+    /// [`khr_get_surface_capabilities2`](crate::instance::InstanceExtensions::khr_get_surface_capabilities2)
+    /// extension to be enabled on the instance. Some capabilities, such as the minimum image
+    /// count, can differ between present modes.
+    ///
     /// # Panic
     ///
     /// - Panics if the physical device and the surface don't belong to the same instance.
@@ -908,9 +1297,30 @@ impl<'a> PhysicalDevice<'a> {
         let SurfaceInfo {
             full_screen_exclusive,
             win32_monitor,
+            present_mode,
             _ne: _,
         } = surface_info;
 
+        if !self
+            .instance
+            .enabled_extensions()
+            .khr_get_surface_capabilities2
+        {
+            if full_screen_exclusive != FullScreenExclusive::Default {
+                return Err(SurfacePropertiesError::ExtensionNotEnabled {
+                    extension: "khr_get_surface_capabilities2",
+                    reason: "`surface_info.full_screen_exclusive` was not `FullScreenExclusive::Default`",
+                });
+            }
+
+            if win32_monitor.is_some() {
+                return Err(SurfacePropertiesError::ExtensionNotEnabled {
+                    extension: "khr_get_surface_capabilities2",
+                    reason: "`surface_info.win32_monitor` was `Some`",
+                });
+            }
+        }
+
         let mut surface_full_screen_exclusive_info =
             if self.supported_extensions().ext_full_screen_exclusive {
                 Some(ash::vk::SurfaceFullScreenExclusiveInfoEXT {
@@ -963,6 +1373,27 @@ impl<'a> PhysicalDevice<'a> {
             surface_info2.p_next = surface_full_screen_exclusive_win32_info as *const _ as *const _;
         }
 
+        let mut surface_present_mode_info = if let Some(present_mode) = present_mode {
+            if !self.instance.enabled_extensions().ext_surface_maintenance1 {
+                return Err(SurfacePropertiesError::ExtensionNotEnabled {
+                    extension: "ext_surface_maintenance1",
+                    reason: "`surface_info.present_mode` was `Some`",
+                });
+            }
+
+            Some(ash::vk::SurfacePresentModeEXT {
+                present_mode: present_mode.into(),
+                ..Default::default()
+            })
+        } else {
+            None
+        };
+
+        if let Some(surface_present_mode_info) = surface_present_mode_info.as_mut() {
+            surface_present_mode_info.p_next = surface_info2.p_next as *mut _;
+            surface_info2.p_next = surface_present_mode_info as *const _ as *const _;
+        }
+
         /* Output */
 
         let mut surface_capabilities2 = ash::vk::SurfaceCapabilities2KHR::default();
@@ -983,6 +1414,49 @@ impl<'a> PhysicalDevice<'a> {
                 surface_capabilities_full_screen_exclusive as *mut _ as *mut _;
         }
 
+        let mut surface_present_scaling_capabilities = if surface_present_mode_info.is_some() {
+            Some(ash::vk::SurfacePresentScalingCapabilitiesEXT::default())
+        } else {
+            None
+        };
+
+        if let Some(surface_present_scaling_capabilities) =
+            surface_present_scaling_capabilities.as_mut()
+        {
+            surface_present_scaling_capabilities.p_next = surface_capabilities2.p_next as *mut _;
+            surface_capabilities2.p_next = surface_present_scaling_capabilities as *mut _ as *mut _;
+        }
+
+        let mut surface_present_mode_compatibility = if surface_present_mode_info.is_some() {
+            Some(ash::vk::SurfacePresentModeCompatibilityEXT::default())
+        } else {
+            None
+        };
+
+        if let Some(surface_present_mode_compatibility) =
+            surface_present_mode_compatibility.as_mut()
+        {
+            surface_present_mode_compatibility.p_next = surface_capabilities2.p_next as *mut _;
+            surface_capabilities2.p_next = surface_present_mode_compatibility as *mut _ as *mut _;
+        }
+
+        let mut surface_protected_capabilities = if self
+            .instance
+            .enabled_extensions()
+            .khr_surface_protected_capabilities
+        {
+            Some(ash::vk::SurfaceProtectedCapabilitiesKHR::default())
+        } else {
+            None
+        };
+
+        if let Some(surface_protected_capabilities) = surface_protected_capabilities.as_mut() {
+            surface_protected_capabilities.p_next = surface_capabilities2.p_next as *mut _;
+            surface_capabilities2.p_next = surface_protected_capabilities as *mut _ as *mut _;
+        }
+
+        let mut compatible_present_modes = Vec::new();
+
         unsafe {
             let fns = self.instance.fns();
 
@@ -998,6 +1472,30 @@ impl<'a> PhysicalDevice<'a> {
                     &surface_info2,
                     &mut surface_capabilities2,
                 ))?;
+
+                // The first call only returned the number of compatible present modes.
+                if let Some(surface_present_mode_compatibility) =
+                    surface_present_mode_compatibility.as_mut()
+                {
+                    compatible_present_modes = vec![
+                        ash::vk::PresentModeKHR::default();
+                        surface_present_mode_compatibility.present_mode_count
+                            as usize
+                    ];
+                    surface_present_mode_compatibility.p_present_modes =
+                        compatible_present_modes.as_mut_ptr();
+
+                    check_errors((fns
+                        .khr_get_surface_capabilities2
+                        .get_physical_device_surface_capabilities2_khr)(
+                        self.internal_object(),
+                        &surface_info2,
+                        &mut surface_capabilities2,
+                    ))?;
+
+                    compatible_present_modes
+                        .truncate(surface_present_mode_compatibility.present_mode_count as usize);
+                }
             } else {
                 check_errors((fns
                     .khr_surface
@@ -1090,6 +1588,47 @@ impl<'a> PhysicalDevice<'a> {
 
             full_screen_exclusive_supported: surface_capabilities_full_screen_exclusive
                 .map_or(false, |c| c.full_screen_exclusive_supported != 0),
+
+            compatible_present_modes: compatible_present_modes
+                .into_iter()
+                .filter_map(|mode_vk| mode_vk.try_into().ok())
+                .collect(),
+            supported_present_scaling: surface_present_scaling_capabilities
+                .map_or(SupportedPresentScaling::none(), |c| {
+                    c.supported_present_scaling.into()
+                }),
+            supported_present_gravity: surface_present_scaling_capabilities.map_or(
+                [SupportedPresentGravity::none(); 2],
+                |c| {
+                    [
+                        c.supported_present_gravity_x.into(),
+                        c.supported_present_gravity_y.into(),
+                    ]
+                },
+            ),
+            min_scaled_image_extent: surface_present_scaling_capabilities.and_then(|c| {
+                (c.min_scaled_image_extent.width != 0xffffffff
+                    || c.min_scaled_image_extent.height != 0xffffffff)
+                    .then(|| {
+                        [
+                            c.min_scaled_image_extent.width,
+                            c.min_scaled_image_extent.height,
+                        ]
+                    })
+            }),
+            max_scaled_image_extent: surface_present_scaling_capabilities.and_then(|c| {
+                (c.max_scaled_image_extent.width != 0xffffffff
+                    || c.max_scaled_image_extent.height != 0xffffffff)
+                    .then(|| {
+                        [
+                            c.max_scaled_image_extent.width,
+                            c.max_scaled_image_extent.height,
+                        ]
+                    })
+            }),
+
+            supports_protected: surface_protected_capabilities
+                .map_or(false, |c| c.supports_protected != 0),
         })
     }
 
@@ -1117,6 +1656,7 @@ impl<'a> PhysicalDevice<'a> {
             let SurfaceInfo {
                 full_screen_exclusive,
                 win32_monitor,
+                present_mode: _,
                 _ne: _,
             } = surface_info;
 
@@ -1213,7 +1753,10 @@ impl<'a> PhysicalDevice<'a> {
                 })
                 .collect())
         } else {
-            if surface_info != SurfaceInfo::default() {
+            // The present mode doesn't affect the formats.
+            if surface_info.full_screen_exclusive != FullScreenExclusive::Default
+                || surface_info.win32_monitor.is_some()
+            {
                 return Ok(Vec::new());
             }
 
@@ -1255,6 +1798,64 @@ impl<'a> PhysicalDevice<'a> {
         }
     }
 
+    /// Returns the supported combination of format and color space for the given surface that
+    /// best matches `color_spaces`, or `None` if none of the color spaces are supported.
+    ///
+    /// The color spaces are tried in order. For each color space, the format that comes first in
+    /// [`ColorSpace::preferred_formats`] is chosen, or else the first supported format with that
+    /// color space. Pass for example `&[ColorSpace::Hdr10St2084, ColorSpace::SrgbNonLinear]` to
+    /// output HDR if the surface supports it, and fall back to sRGB otherwise.
+    ///
+    /// The color spaces other than [`ColorSpace::SrgbNonLinear`] are only supported if the
+    /// [`ext_swapchain_colorspace`](crate::instance::InstanceExtensions::ext_swapchain_colorspace)
+    /// extension is enabled on the instance.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the physical device and the surface don't belong to the same instance.
+    pub fn select_surface_format<W>(
+        &self,
+        surface: &Surface<W>,
+        surface_info: SurfaceInfo,
+        color_spaces: &[ColorSpace],
+    ) -> Result<Option<(Format, ColorSpace)>, SurfacePropertiesError> {
+        let surface_formats = self.surface_formats(surface, surface_info)?;
+
+        Ok(color_spaces.iter().find_map(|&color_space| {
+            color_space
+                .preferred_formats()
+                .iter()
+                .map(|&format| (format, color_space))
+                .find(|surface_format| surface_formats.contains(surface_format))
+                .or_else(|| {
+                    surface_formats
+                        .iter()
+                        .find(|&&(_, cs)| cs == color_space)
+                        .copied()
+                })
+        }))
+    }
+
+    /// Returns the supported combination of format and color space for the given surface that
+    /// best matches `preference`, or `None` if none of the color spaces of `preference` are
+    /// supported.
+    ///
+    /// See [`SurfaceFormatPreference::choose`] for how the formats are ranked.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the physical device and the surface don't belong to the same instance.
+    pub fn choose_surface_format<W>(
+        &self,
+        surface: &Surface<W>,
+        surface_info: SurfaceInfo,
+        preference: &SurfaceFormatPreference,
+    ) -> Result<Option<(Format, ColorSpace)>, SurfacePropertiesError> {
+        let surface_formats = self.surface_formats(surface, surface_info)?;
+
+        Ok(preference.choose(&surface_formats))
+    }
+
     /// Returns the present modes that are supported by the physical device for the given surface.
     ///
     /// # Panic
@@ -1310,6 +1911,44 @@ impl<'a> PhysicalDevice<'a> {
             .into_iter()
             .filter_map(|mode_vk| mode_vk.try_into().ok()))
     }
+
+    /// Returns the supported present mode for the given surface that is the most preferred by
+    /// `preference`, or `Fifo` if none of the preferred present modes are supported.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the physical device and the surface don't belong to the same instance.
+    pub fn choose_present_mode<W>(
+        &self,
+        surface: &Surface<W>,
+        preference: &PresentModePreference,
+    ) -> Result<PresentMode, SurfacePropertiesError> {
+        Ok(preference.choose(self.surface_present_modes(surface)?))
+    }
+}
+
+/// A group of physical devices that a single device can be created from.
+#[derive(Clone, Debug)]
+pub struct PhysicalDeviceGroup<'a> {
+    physical_devices: Vec<PhysicalDevice<'a>>,
+    subset_allocation: bool,
+}
+
+impl<'a> PhysicalDeviceGroup<'a> {
+    /// Returns the physical devices in the group.
+    #[inline]
+    pub fn physical_devices(&self) -> &[PhysicalDevice<'a>] {
+        &self.physical_devices
+    }
+
+    /// Returns whether memory can be allocated on a subset of the physical devices of a device
+    /// that is created from the group, with
+    /// [`MemoryAllocateInfo::device_mask`](crate::memory::MemoryAllocateInfo::device_mask). If
+    /// not, memory is always allocated on all of them.
+    #[inline]
+    pub fn subset_allocation(&self) -> bool {
+        self.subset_allocation
+    }
 }
 
 unsafe impl<'a> VulkanObject for PhysicalDevice<'a> {
@@ -1389,6 +2028,12 @@ impl<'a> MemoryType<'a> {
             .unwrap()
     }
 
+    /// Returns the properties of this memory type.
+    #[inline]
+    pub fn property_flags(&self) -> MemoryPropertyFlags {
+        self.info.property_flags.into()
+    }
+
     /// Returns true if the memory type is located on the device, which means that it's the most
     /// efficient for GPU accesses.
     #[inline]
@@ -1580,6 +2225,160 @@ impl<'a> QueueFamily<'a> {
             .contains(ash::vk::QueueFlags::SPARSE_BINDING)
     }
 
+    /// Returns `true` if queues of this family can be created as protected-capable queues.
+    #[inline]
+    pub fn supports_protected(&self) -> bool {
+        self.properties
+            .queue_flags
+            .contains(ash::vk::QueueFlags::PROTECTED)
+    }
+
+    /// Returns the global priorities that queues of this family can be created with, from lowest
+    /// to highest.
+    ///
+    /// This is only known if the
+    /// [`khr_global_priority`](crate::device::DeviceExtensions::khr_global_priority) or
+    /// [`ext_global_priority_query`](crate::device::DeviceExtensions::ext_global_priority_query)
+    /// extension is supported by the physical device. Otherwise, an empty slice is returned.
+    #[inline]
+    pub fn supported_global_priorities(&self) -> &'a [QueueGlobalPriority] {
+        self.physical_device
+            .info
+            .queue_family_global_priorities
+            .get(self.id as usize)
+            .map_or(&[], |priorities| priorities.as_slice())
+    }
+
+    /// Returns the performance counters that performance queries can measure on this queue
+    /// family.
+    ///
+    /// The index of a counter in the returned list is the index that is given in
+    /// [`QueryPoolCreateInfo::performance_counter_indices`](crate::query::QueryPoolCreateInfo::performance_counter_indices).
+    ///
+    /// The [`khr_performance_query`](crate::device::DeviceExtensions::khr_performance_query)
+    /// extension must be supported by the physical device.
+    pub fn performance_counters(
+        &self,
+    ) -> Result<Vec<PerformanceCounter>, PerformanceCountersError> {
+        if !self
+            .physical_device
+            .supported_extensions()
+            .khr_performance_query
+        {
+            return Err(PerformanceCountersError::NotSupported);
+        }
+
+        let fns = self.physical_device.instance.fns();
+
+        let (counters, descriptions) = unsafe {
+            loop {
+                let mut count = 0;
+                check_errors(
+                    (fns.khr_performance_query
+                        .enumerate_physical_device_queue_family_performance_query_counters_khr)(
+                        self.physical_device.internal_object(),
+                        self.id,
+                        &mut count,
+                        ptr::null_mut(),
+                        ptr::null_mut(),
+                    ),
+                )?;
+
+                let mut counters = vec![ash::vk::PerformanceCounterKHR::default(); count as usize];
+                let mut descriptions =
+                    vec![ash::vk::PerformanceCounterDescriptionKHR::default(); count as usize];
+                let result = check_errors((fns
+                    .khr_performance_query
+                    .enumerate_physical_device_queue_family_performance_query_counters_khr)(
+                    self.physical_device.internal_object(),
+                    self.id,
+                    &mut count,
+                    counters.as_mut_ptr(),
+                    descriptions.as_mut_ptr(),
+                ))?;
+
+                if !matches!(result, Success::Incomplete) {
+                    counters.truncate(count as usize);
+                    descriptions.truncate(count as usize);
+                    break (counters, descriptions);
+                }
+            }
+        };
+
+        Ok(counters
+            .iter()
+            .zip(&descriptions)
+            .map(|(counter, description)| unsafe {
+                PerformanceCounter {
+                    unit: counter.unit.try_into().unwrap(),
+                    scope: counter.scope.try_into().unwrap(),
+                    storage: counter.storage.try_into().unwrap(),
+                    uuid: counter.uuid,
+                    name: CStr::from_ptr(description.name.as_ptr())
+                        .to_string_lossy()
+                        .into_owned(),
+                    category: CStr::from_ptr(description.category.as_ptr())
+                        .to_string_lossy()
+                        .into_owned(),
+                    description: CStr::from_ptr(description.description.as_ptr())
+                        .to_string_lossy()
+                        .into_owned(),
+                    performance_impacting: description.flags.intersects(
+                        ash::vk::PerformanceCounterDescriptionFlagsKHR::PERFORMANCE_IMPACTING,
+                    ),
+                    concurrently_impacted: description.flags.intersects(
+                        ash::vk::PerformanceCounterDescriptionFlagsKHR::CONCURRENTLY_IMPACTED,
+                    ),
+                }
+            })
+            .collect())
+    }
+
+    /// Returns the number of passes that are needed to measure the given performance counters
+    /// with the queries of one pool.
+    ///
+    /// `counter_indices` are indices into the list returned by
+    /// [`performance_counters`](Self::performance_counters).
+    pub fn performance_query_passes(
+        &self,
+        counter_indices: &[u32],
+    ) -> Result<u32, PerformanceCountersError> {
+        let counter_count = self.performance_counters()?.len() as u32;
+
+        if let Some(&index) = counter_indices
+            .iter()
+            .find(|&&index| index >= counter_count)
+        {
+            return Err(PerformanceCountersError::CounterIndexOutOfRange {
+                index,
+                counter_count,
+            });
+        }
+
+        Ok(unsafe { self.performance_query_passes_unchecked(counter_indices) })
+    }
+
+    // Safety: the khr_performance_query extension must be supported, and the indices must be
+    // valid counter indices of the queue family.
+    pub(crate) unsafe fn performance_query_passes_unchecked(&self, counter_indices: &[u32]) -> u32 {
+        let fns = self.physical_device.instance.fns();
+        let create_info = ash::vk::QueryPoolPerformanceCreateInfoKHR {
+            queue_family_index: self.id,
+            counter_index_count: counter_indices.len() as u32,
+            p_counter_indices: counter_indices.as_ptr(),
+            ..Default::default()
+        };
+
+        let mut passes = 0;
+        (fns.khr_performance_query
+            .get_physical_device_queue_family_performance_query_passes_khr)(
+            self.physical_device.internal_object(),
+            &create_info,
+            &mut passes,
+        );
+        passes
+    }
+
     /// Returns `true` if the queues of this family support a particular pipeline stage.
     #[inline]
     pub fn supports_stage(&self, stage: PipelineStage) -> bool {
@@ -1606,6 +2405,126 @@ impl<'a> QueueFamily<'a> {
             Ok(output.assume_init() != 0)
         }
     }
+
+    /// Returns whether queues of this family can present to a Wayland display.
+    ///
+    /// Returns `None` if the
+    /// [`khr_wayland_surface`](crate::instance::InstanceExtensions::khr_wayland_surface)
+    /// extension is not enabled on the instance.
+    ///
+    /// # Safety
+    ///
+    /// - `display` must be a valid Wayland `wl_display` handle.
+    pub unsafe fn supports_wayland_presentation<D>(&self, display: *const D) -> Option<bool> {
+        let instance = &self.physical_device.instance;
+
+        if !instance.enabled_extensions().khr_wayland_surface {
+            return None;
+        }
+
+        let fns = instance.fns();
+        let supported = (fns
+            .khr_wayland_surface
+            .get_physical_device_wayland_presentation_support_khr)(
+            self.physical_device.internal_object(),
+            self.id,
+            display as *mut _,
+        );
+
+        Some(supported != ash::vk::FALSE)
+    }
+
+    /// Returns whether queues of this family can present to the Microsoft Windows desktop.
+    ///
+    /// Returns `None` if the
+    /// [`khr_win32_surface`](crate::instance::InstanceExtensions::khr_win32_surface)
+    /// extension is not enabled on the instance.
+    pub fn supports_win32_presentation(&self) -> Option<bool> {
+        let instance = &self.physical_device.instance;
+
+        if !instance.enabled_extensions().khr_win32_surface {
+            return None;
+        }
+
+        let supported = unsafe {
+            let fns = instance.fns();
+            (fns.khr_win32_surface
+                .get_physical_device_win32_presentation_support_khr)(
+                self.physical_device.internal_object(),
+                self.id,
+            )
+        };
+
+        Some(supported != ash::vk::FALSE)
+    }
+
+    /// Returns whether queues of this family can present to an X11 server with an XCB
+    /// connection, for windows of the given visual.
+    ///
+    /// Returns `None` if the
+    /// [`khr_xcb_surface`](crate::instance::InstanceExtensions::khr_xcb_surface)
+    /// extension is not enabled on the instance.
+    ///
+    /// # Safety
+    ///
+    /// - `connection` must be a valid XCB `xcb_connection_t` handle.
+    pub unsafe fn supports_xcb_presentation<C>(
+        &self,
+        connection: *const C,
+        visual_id: u32,
+    ) -> Option<bool> {
+        let instance = &self.physical_device.instance;
+
+        if !instance.enabled_extensions().khr_xcb_surface {
+            return None;
+        }
+
+        let fns = instance.fns();
+        let supported = (fns
+            .khr_xcb_surface
+            .get_physical_device_xcb_presentation_support_khr)(
+            self.physical_device.internal_object(),
+            self.id,
+            connection as *mut _,
+            visual_id,
+        );
+
+        Some(supported != ash::vk::FALSE)
+    }
+
+    /// Returns whether queues of this family can present to an X11 server with an Xlib
+    /// display, for windows of the given visual.
+    ///
+    /// Returns `None` if the
+    /// [`khr_xlib_surface`](crate::instance::InstanceExtensions::khr_xlib_surface)
+    /// extension is not enabled on the instance.
+    ///
+    /// # Safety
+    ///
+    /// - `display` must be a valid Xlib `Display` handle.
+    pub unsafe fn supports_xlib_presentation<D>(
+        &self,
+        display: *const D,
+        visual_id: c_ulong,
+    ) -> Option<bool> {
+        let instance = &self.physical_device.instance;
+
+        if !instance.enabled_extensions().khr_xlib_surface {
+            return None;
+        }
+
+        let fns = instance.fns();
+        let supported = (fns
+            .khr_xlib_surface
+            .get_physical_device_xlib_presentation_support_khr)(
+            self.physical_device.internal_object(),
+            self.id,
+            display as *mut _,
+            visual_id,
+        );
+
+        Some(supported != ash::vk::FALSE)
+    }
 }
 
 impl<'a> PartialEq for QueueFamily<'a> {
@@ -1650,6 +2569,136 @@ impl fmt::Display for ConformanceVersion {
     }
 }
 
+/// The properties that identify a physical device and its driver.
+///
+/// The members that are `None` are not reported by the physical device, usually because it
+/// doesn't support the extension that provides them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PhysicalDeviceIdentity {
+    /// The name of the physical device.
+    pub device_name: String,
+
+    /// The vendor ID of the physical device, which is usually its PCI vendor ID.
+    pub vendor_id: u32,
+
+    /// The vendor-specific ID of the physical device.
+    pub device_id: u32,
+
+    /// A universally unique identifier of the physical device.
+    ///
+    /// It stays the same across instances, processes, driver versions and reboots of the system,
+    /// so it can be stored to choose the same physical device the next time the application
+    /// runs. See [`DeviceSelector::preferred_device_uuid`](crate::device::DeviceSelector::preferred_device_uuid).
+    pub device_uuid: Option<[u8; 16]>,
+
+    /// A universally unique identifier of the driver build that is in use by the physical device.
+    pub driver_uuid: Option<[u8; 16]>,
+
+    /// A locally unique identifier of the physical device, which is used on Windows to match it
+    /// with DirectX adapters.
+    pub device_luid: Option<[u8; 8]>,
+
+    /// The node of a linked device adapter that the physical device corresponds to. This is
+    /// `Some` when `device_luid` is.
+    pub device_node_mask: Option<u32>,
+
+    /// The location of the physical device on the PCI bus.
+    pub pci_bus_info: Option<PciBusInfo>,
+
+    /// The ID of the driver.
+    pub driver_id: Option<DriverId>,
+
+    /// The name of the driver.
+    pub driver_name: Option<String>,
+
+    /// Additional information about the driver, such as its version.
+    pub driver_info: Option<String>,
+
+    /// The vendor-specific version of the driver.
+    pub driver_version: u32,
+}
+
+/// The location of a physical device on the PCI bus.
+///
+/// It is displayed in the usual `domain:bus:device.function` notation, for example
+/// `0000:01:00.0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PciBusInfo {
+    pub domain: u32,
+    pub bus: u32,
+    pub device: u32,
+    pub function: u32,
+}
+
+impl fmt::Display for PciBusInfo {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{:04x}:{:02x}:{:02x}.{:x}",
+            self.domain, self.bus, self.device, self.function
+        )
+    }
+}
+
+/// Which extensions and features a device could use, as returned by
+/// [`PhysicalDevice::capabilities`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// The API version of the device. This is the lower of the requested version and the version
+    /// of the physical device.
+    pub api_version: Version,
+
+    /// The extensions that can be enabled. These are the requested extensions whose restrictions
+    /// are met, and the [required extensions](PhysicalDevice::required_extensions) of the
+    /// physical device.
+    pub extensions: DeviceExtensions,
+
+    /// For each requested extension that can't be enabled, the restriction that is not met.
+    ///
+    /// The restriction is [`NotSupported`](crate::device::ExtensionRestriction::NotSupported) if
+    /// the physical device doesn't support the extension, or
+    /// [`Requires`](crate::device::ExtensionRestriction::Requires) if it needs a higher API
+    /// version, or another extension that can't be enabled.
+    pub unusable_extensions: Vec<ExtensionRestrictionError>,
+
+    /// The features that can be enabled.
+    ///
+    /// These are the features that are supported by the physical device, and whose feature
+    /// structure is provided by the API version or the usable extensions. This includes the
+    /// features of extensions that were promoted to core, which enable their extension
+    /// automatically.
+    pub features: Features,
+
+    /// For each feature that can't be enabled, the reason.
+    ///
+    /// The restriction is [`NotSupported`](crate::device::FeatureRestriction::NotSupported) if
+    /// the physical device doesn't support the feature, or
+    /// [`Requires`](crate::device::FeatureRestriction::Requires) with the API version and
+    /// extensions that would provide it.
+    pub unusable_features: Vec<FeatureRestrictionError>,
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(formatter, "API version: {}", self.api_version)?;
+        writeln!(formatter, "Extensions: {:?}", self.extensions)?;
+
+        writeln!(formatter, "Unusable extensions:")?;
+        for err in &self.unusable_extensions {
+            writeln!(formatter, "- {}: {}", err.extension, err.restriction)?;
+        }
+
+        writeln!(formatter, "Unusable features:")?;
+        for err in &self.unusable_features {
+            writeln!(formatter, "- {}: {}", err.feature, err.restriction)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// An identifier for the driver of a physical device.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(i32)]
@@ -1800,6 +2849,11 @@ pub enum SurfacePropertiesError {
 
     // The given `SurfaceInfo` values are not supported for the surface by the physical device.
     NotSupported,
+
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
 }
 
 impl error::Error for SurfacePropertiesError {
@@ -1815,15 +2869,16 @@ impl error::Error for SurfacePropertiesError {
 impl fmt::Display for SurfacePropertiesError {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(
-            fmt,
-            "{}",
-            match *self {
-                Self::OomError(_) => "not enough memory",
-                Self::SurfaceLost => "the surface is no longer valid",
-                Self::NotSupported => "the given `SurfaceInfo` values are not supported for the surface by the physical device",
-            }
-        )
+        match *self {
+            Self::OomError(_) => write!(fmt, "not enough memory"),
+            Self::SurfaceLost => write!(fmt, "the surface is no longer valid"),
+            Self::NotSupported => write!(fmt, "the given `SurfaceInfo` values are not supported for the surface by the physical device"),
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+        }
     }
 }
 
@@ -1845,3 +2900,122 @@ impl From<Error> for SurfacePropertiesError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PhysicalDevice;
+    use std::ptr;
+
+    #[test]
+    fn presentation_support_extension_not_enabled() {
+        let instance = instance!();
+
+        for physical_device in PhysicalDevice::enumerate(&instance) {
+            for queue_family in physical_device.queue_families() {
+                // The handles are never used if the extension is not enabled.
+                unsafe {
+                    assert_eq!(
+                        queue_family.supports_wayland_presentation(ptr::null::<()>()),
+                        None
+                    );
+                    assert_eq!(
+                        queue_family.supports_xcb_presentation(ptr::null::<()>(), 0),
+                        None
+                    );
+                    assert_eq!(
+                        queue_family.supports_xlib_presentation(ptr::null::<()>(), 0),
+                        None
+                    );
+                }
+                assert_eq!(queue_family.supports_win32_presentation(), None);
+            }
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn win32_presentation_support() {
+        use crate::instance::{Instance, InstanceCreateInfo, InstanceExtensions};
+
+        let instance = match Instance::new(InstanceCreateInfo {
+            enabled_extensions: InstanceExtensions {
+                khr_surface: true,
+                khr_win32_surface: true,
+                ..InstanceExtensions::none()
+            },
+            ..Default::default()
+        }) {
+            Ok(i) => i,
+            Err(_) => return,
+        };
+
+        for physical_device in PhysicalDevice::enumerate(&instance) {
+            for queue_family in physical_device.queue_families() {
+                assert!(queue_family.supports_win32_presentation().is_some());
+            }
+        }
+    }
+
+    #[cfg(all(unix, not(target_os = "android"), not(target_os = "macos")))]
+    #[test]
+    fn xlib_presentation_support() {
+        use crate::instance::{Instance, InstanceCreateInfo, InstanceExtensions};
+        use shared_library::dynamic_library::DynamicLibrary;
+        use std::{
+            ffi::c_void,
+            mem,
+            os::raw::{c_char, c_int, c_ulong},
+            path::Path,
+        };
+
+        let instance = match Instance::new(InstanceCreateInfo {
+            enabled_extensions: InstanceExtensions {
+                khr_surface: true,
+                khr_xlib_surface: true,
+                ..InstanceExtensions::none()
+            },
+            ..Default::default()
+        }) {
+            Ok(i) => i,
+            Err(_) => return,
+        };
+
+        // Xlib is loaded at runtime, so that the test is skipped without an X server.
+        let xlib = match DynamicLibrary::open(Some(Path::new("libX11.so.6"))) {
+            Ok(l) => l,
+            Err(_) => return,
+        };
+
+        unsafe {
+            let symbol = |name: &str| -> *mut c_void { xlib.symbol(name).unwrap() };
+            let open_display: unsafe extern "C" fn(*const c_char) -> *mut c_void =
+                mem::transmute(symbol("XOpenDisplay"));
+            let close_display: unsafe extern "C" fn(*mut c_void) -> c_int =
+                mem::transmute(symbol("XCloseDisplay"));
+            let default_screen: unsafe extern "C" fn(*mut c_void) -> c_int =
+                mem::transmute(symbol("XDefaultScreen"));
+            let default_visual: unsafe extern "C" fn(*mut c_void, c_int) -> *mut c_void =
+                mem::transmute(symbol("XDefaultVisual"));
+            let visual_id_from_visual: unsafe extern "C" fn(*mut c_void) -> c_ulong =
+                mem::transmute(symbol("XVisualIDFromVisual"));
+
+            let display = open_display(ptr::null());
+
+            if display.is_null() {
+                return;
+            }
+
+            let visual_id = visual_id_from_visual(default_visual(display, default_screen(display)));
+
+            for physical_device in PhysicalDevice::enumerate(&instance) {
+                for queue_family in physical_device.queue_families() {
+                    assert!(queue_family
+                        .supports_xlib_presentation(display, visual_id)
+                        .is_some());
+                }
+            }
+
+            close_display(display);
+        }
+    }
+}
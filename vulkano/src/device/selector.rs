@@ -0,0 +1,491 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{
+    physical::{PhysicalDevice, PhysicalDeviceType, QueueFamily},
+    DeviceCreateInfo, DeviceExtensions, Features, QueueCreateInfo,
+};
+use crate::{
+    instance::Instance,
+    swapchain::{Surface, SurfacePropertiesError},
+};
+use std::{error, fmt, sync::Arc};
+
+/// Chooses the physical device to create a device from, among the physical devices that meet the
+/// requirements of the application.
+///
+/// ```no_run
+/// use vulkano::device::{Device, DeviceExtensions, DeviceSelector, QueueRequirement};
+/// # let instance: std::sync::Arc<vulkano::instance::Instance> = return;
+/// # let surface: std::sync::Arc<vulkano::swapchain::Surface<()>> = return;
+///
+/// let selected = DeviceSelector::new()
+///     .required_extensions(DeviceExtensions {
+///         khr_swapchain: true,
+///         ..DeviceExtensions::none()
+///     })
+///     .surface(&surface)
+///     .queue(QueueRequirement {
+///         graphics: true,
+///         present: true,
+///         ..Default::default()
+///     })
+///     .select(&instance)
+///     .unwrap();
+///
+/// let (device, queues) =
+///     Device::new(selected.physical_device, selected.device_create_info()).unwrap();
+/// ```
+pub struct DeviceSelector<'s> {
+    required_extensions: DeviceExtensions,
+    required_features: Features,
+    queue_requirements: Vec<QueueRequirement>,
+    supports_surface: Option<Box<dyn Fn(QueueFamily) -> Result<bool, SurfacePropertiesError> + 's>>,
+    score: Box<dyn Fn(PhysicalDevice) -> u32 + 's>,
+    preferred_device_uuid: Option<[u8; 16]>,
+}
+
+impl<'s> DeviceSelector<'s> {
+    /// Returns a `DeviceSelector` without requirements, that prefers the physical devices by
+    /// their type: discrete GPUs first, then integrated GPUs, virtual GPUs and CPUs.
+    #[inline]
+    pub fn new() -> Self {
+        DeviceSelector {
+            required_extensions: DeviceExtensions::none(),
+            required_features: Features::none(),
+            queue_requirements: Vec::new(),
+            supports_surface: None,
+            score: Box::new(device_type_score),
+            preferred_device_uuid: None,
+        }
+    }
+
+    /// Sets the extensions that the physical device must support. They are enabled by
+    /// [`SelectedDevice::device_create_info`].
+    #[inline]
+    pub fn required_extensions(mut self, extensions: DeviceExtensions) -> Self {
+        self.required_extensions = extensions;
+        self
+    }
+
+    /// Sets the features that the physical device must support. They are enabled by
+    /// [`SelectedDevice::device_create_info`].
+    #[inline]
+    pub fn required_features(mut self, features: Features) -> Self {
+        self.required_features = features;
+        self
+    }
+
+    /// Adds a queue that the physical device must provide. The queue family that is chosen for
+    /// it is at the same index in [`SelectedDevice::queue_families`].
+    #[inline]
+    pub fn queue(mut self, requirement: QueueRequirement) -> Self {
+        self.queue_requirements.push(requirement);
+        self
+    }
+
+    /// Sets the surface that the queues with [`present`](QueueRequirement::present) set must be
+    /// able to present to.
+    #[inline]
+    pub fn surface<W>(mut self, surface: &'s Arc<Surface<W>>) -> Self {
+        self.supports_surface = Some(Box::new(move |family: QueueFamily| {
+            family.supports_surface(&**surface)
+        }));
+        self
+    }
+
+    /// Sets the closure that scores the physical devices that meet the requirements. The device
+    /// with the highest score is chosen. If several devices have the highest score, the first
+    /// one that was enumerated is chosen.
+    #[inline]
+    pub fn score<F>(mut self, score: F) -> Self
+    where
+        F: Fn(PhysicalDevice) -> u32 + 's,
+    {
+        self.score = Box::new(score);
+        self
+    }
+
+    /// Sets the [`device_uuid`](crate::device::physical::PhysicalDeviceIdentity::device_uuid) of
+    /// the physical device to choose if it meets the requirements, regardless of the scores.
+    ///
+    /// The UUID doesn't change between runs of the application, so this can be used to choose
+    /// the same physical device as the last time. If the physical device is not present anymore,
+    /// or doesn't meet the requirements, the device with the highest score is chosen instead.
+    #[inline]
+    pub fn preferred_device_uuid(mut self, device_uuid: [u8; 16]) -> Self {
+        self.preferred_device_uuid = Some(device_uuid);
+        self
+    }
+
+    /// Chooses a physical device of `instance`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if a queue requirement has `present` set, but no surface was given.
+    pub fn select<'a>(
+        &self,
+        instance: &'a Arc<Instance>,
+    ) -> Result<SelectedDevice<'a>, DeviceSelectionError> {
+        assert!(
+            self.supports_surface.is_some()
+                || !self.queue_requirements.iter().any(|req| req.present)
+        );
+
+        let mut selected: Option<(SelectedDevice<'a>, (bool, u32))> = None;
+
+        for physical_device in PhysicalDevice::enumerate(instance) {
+            if !(physical_device
+                .supported_extensions()
+                .is_superset_of(&self.required_extensions)
+                && physical_device
+                    .supported_features()
+                    .is_superset_of(&self.required_features))
+            {
+                continue;
+            }
+
+            let queue_families = match self.queue_families(physical_device)? {
+                Some(queue_families) => queue_families,
+                None => continue,
+            };
+
+            // The preferred physical device beats all the others.
+            let is_preferred = self.preferred_device_uuid.is_some()
+                && physical_device.properties().device_uuid == self.preferred_device_uuid;
+            let score = (is_preferred, (self.score)(physical_device));
+
+            if selected.as_ref().map_or(true, |&(_, best)| score > best) {
+                selected = Some((
+                    SelectedDevice {
+                        physical_device,
+                        queue_families,
+                        enabled_extensions: physical_device
+                            .required_extensions()
+                            .union(&self.required_extensions),
+                        enabled_features: self.required_features.clone(),
+                    },
+                    score,
+                ));
+            }
+        }
+
+        selected
+            .map(|(selected, _)| selected)
+            .ok_or(DeviceSelectionError::NoSuitableDevice)
+    }
+
+    // Returns a queue family for each queue requirement, or `None` if one of them can't be met.
+    fn queue_families<'a>(
+        &self,
+        physical_device: PhysicalDevice<'a>,
+    ) -> Result<Option<Vec<QueueFamily<'a>>>, DeviceSelectionError> {
+        let mut queue_families = Vec::with_capacity(self.queue_requirements.len());
+
+        for requirement in &self.queue_requirements {
+            let mut best: Option<QueueFamily<'a>> = None;
+
+            for family in physical_device.queue_families() {
+                if !requirement.is_met_by(family, self.supports_surface.as_deref())? {
+                    continue;
+                }
+
+                // Prefer the families with fewer capabilities, so that dedicated families are
+                // chosen when they exist.
+                if best.map_or(true, |best| {
+                    capability_count(family) < capability_count(best)
+                }) {
+                    best = Some(family);
+                }
+            }
+
+            match best {
+                Some(family) => queue_families.push(family),
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(queue_families))
+    }
+}
+
+impl Default for DeviceSelector<'static> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for DeviceSelector<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("DeviceSelector")
+            .field("required_extensions", &self.required_extensions)
+            .field("required_features", &self.required_features)
+            .field("queue_requirements", &self.queue_requirements)
+            .field("surface", &self.supports_surface.is_some())
+            .field("preferred_device_uuid", &self.preferred_device_uuid)
+            .finish_non_exhaustive()
+    }
+}
+
+fn device_type_score(physical_device: PhysicalDevice) -> u32 {
+    match physical_device.properties().device_type {
+        PhysicalDeviceType::DiscreteGpu => 4,
+        PhysicalDeviceType::IntegratedGpu => 3,
+        PhysicalDeviceType::VirtualGpu => 2,
+        PhysicalDeviceType::Cpu => 1,
+        PhysicalDeviceType::Other => 0,
+    }
+}
+
+fn capability_count(family: QueueFamily) -> u32 {
+    [
+        family.supports_graphics(),
+        family.supports_compute(),
+        family.explicitly_supports_transfers(),
+        family.supports_sparse_binding(),
+    ]
+    .into_iter()
+    .filter(|&supported| supported)
+    .count() as u32
+}
+
+/// The capabilities that a queue family must have for a [`DeviceSelector`].
+#[derive(Clone, Debug)]
+pub struct QueueRequirement {
+    /// Whether the queue family must support graphics operations.
+    ///
+    /// The default value is `false`.
+    pub graphics: bool,
+
+    /// Whether the queue family must support compute operations.
+    ///
+    /// The default value is `false`.
+    pub compute: bool,
+
+    /// Whether the queue family must support transfer operations. This is implied by `graphics`
+    /// and `compute`, but can be set on its own to get a dedicated transfer queue family if there
+    /// is one.
+    ///
+    /// The default value is `false`.
+    pub transfer: bool,
+
+    /// Whether the queue family must support sparse binding operations.
+    ///
+    /// The default value is `false`.
+    pub sparse_binding: bool,
+
+    /// Whether the queue family must be able to present to the surface that was given with
+    /// [`DeviceSelector::surface`].
+    ///
+    /// The default value is `false`.
+    pub present: bool,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for QueueRequirement {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            graphics: false,
+            compute: false,
+            transfer: false,
+            sparse_binding: false,
+            present: false,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl QueueRequirement {
+    fn is_met_by(
+        &self,
+        family: QueueFamily,
+        supports_surface: Option<
+            &(dyn Fn(QueueFamily) -> Result<bool, SurfacePropertiesError> + '_),
+        >,
+    ) -> Result<bool, SurfacePropertiesError> {
+        let &Self {
+            graphics,
+            compute,
+            transfer,
+            sparse_binding,
+            present,
+            _ne: _,
+        } = self;
+
+        if graphics && !family.supports_graphics()
+            || compute && !family.supports_compute()
+            || transfer
+                && !(family.explicitly_supports_transfers()
+                    || family.supports_graphics()
+                    || family.supports_compute())
+            || sparse_binding && !family.supports_sparse_binding()
+        {
+            return Ok(false);
+        }
+
+        if present {
+            return supports_surface.unwrap()(family);
+        }
+
+        Ok(true)
+    }
+}
+
+/// A physical device that was chosen by a [`DeviceSelector`].
+#[derive(Clone, Debug)]
+pub struct SelectedDevice<'a> {
+    /// The chosen physical device.
+    pub physical_device: PhysicalDevice<'a>,
+
+    /// The queue family that was chosen for each queue requirement, in the order that they were
+    /// added. Several requirements can get the same queue family.
+    pub queue_families: Vec<QueueFamily<'a>>,
+
+    /// The required extensions, and the extensions that the physical device requires to be
+    /// enabled.
+    pub enabled_extensions: DeviceExtensions,
+
+    /// The required features.
+    pub enabled_features: Features,
+}
+
+impl<'a> SelectedDevice<'a> {
+    /// Returns the parameters to create a device with the enabled extensions and features, and a
+    /// queue from each of the queue families. If several requirements got the same queue family,
+    /// as many queues are created from it as the family allows.
+    pub fn device_create_info(&self) -> DeviceCreateInfo<'a> {
+        let mut queue_create_infos: Vec<QueueCreateInfo<'a>> = Vec::new();
+
+        for &family in &self.queue_families {
+            match queue_create_infos
+                .iter_mut()
+                .find(|info| info.family.id() == family.id())
+            {
+                Some(info) if info.queues.len() < family.queues_count() => info.queues.push(0.5),
+                Some(_) => (),
+                None => queue_create_infos.push(QueueCreateInfo::family(family)),
+            }
+        }
+
+        DeviceCreateInfo {
+            enabled_extensions: self.enabled_extensions,
+            enabled_features: self.enabled_features.clone(),
+            queue_create_infos,
+            ..Default::default()
+        }
+    }
+}
+
+/// Error that can happen when choosing a physical device with a `DeviceSelector`.
+#[derive(Clone, Debug)]
+pub enum DeviceSelectionError {
+    /// No physical device meets the requirements.
+    NoSuitableDevice,
+
+    /// Querying whether a queue family can present to the surface failed.
+    SurfacePropertiesError(SurfacePropertiesError),
+}
+
+impl error::Error for DeviceSelectionError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::SurfacePropertiesError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DeviceSelectionError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::NoSuitableDevice => {
+                write!(fmt, "no physical device meets the requirements")
+            }
+            Self::SurfacePropertiesError(_) => write!(
+                fmt,
+                "querying whether a queue family can present to the surface failed"
+            ),
+        }
+    }
+}
+
+impl From<SurfacePropertiesError> for DeviceSelectionError {
+    #[inline]
+    fn from(err: SurfacePropertiesError) -> Self {
+        Self::SurfacePropertiesError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeviceSelectionError, DeviceSelector, QueueRequirement};
+    use crate::device::{physical::PhysicalDevice, Device};
+
+    #[test]
+    fn select_compute() {
+        let instance = instance!();
+
+        let selected = match DeviceSelector::new()
+            .queue(QueueRequirement {
+                compute: true,
+                ..Default::default()
+            })
+            .queue(QueueRequirement {
+                transfer: true,
+                ..Default::default()
+            })
+            .select(&instance)
+        {
+            Ok(selected) => selected,
+            Err(DeviceSelectionError::NoSuitableDevice) => return,
+            Err(err) => panic!("{}", err),
+        };
+
+        assert_eq!(selected.queue_families.len(), 2);
+        assert!(selected.queue_families[0].supports_compute());
+
+        let create_info = selected.device_create_info();
+        let queue_count: usize = create_info
+            .queue_create_infos
+            .iter()
+            .map(|info| info.queues.len())
+            .sum();
+        let (_device, queues) = Device::new(selected.physical_device, create_info).unwrap();
+        assert_eq!(queues.count(), queue_count);
+    }
+
+    #[test]
+    fn preferred_device_uuid() {
+        let instance = instance!();
+
+        for physical_device in PhysicalDevice::enumerate(&instance) {
+            let device_uuid = match physical_device.identity().device_uuid {
+                Some(device_uuid) => device_uuid,
+                None => continue,
+            };
+
+            // The preferred device is chosen even if all the others have a higher score.
+            let selected = DeviceSelector::new()
+                .score(|other| (other.index() != physical_device.index()) as u32)
+                .preferred_device_uuid(device_uuid)
+                .select(&instance)
+                .unwrap();
+
+            assert_eq!(
+                selected.physical_device.properties().device_uuid,
+                Some(device_uuid)
+            );
+        }
+    }
+}
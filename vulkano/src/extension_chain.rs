@@ -0,0 +1,418 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Extending the creation of objects with structures that vulkano doesn't support.
+//!
+//! In Vulkan, the create info of an object can be extended with other structures, which are
+//! linked together in its `p_next` chain. Vulkano builds this chain itself for the extensions that
+//! it supports. For the structures that it doesn't support yet, an [`ExtensionChain`] can be given
+//! when creating these objects:
+//!
+//! - [`Instance`], with [`InstanceCreateInfo::extensions`](crate::instance::InstanceCreateInfo::extensions).
+//! - [`Device`], with [`DeviceCreateInfo::extensions`](crate::device::DeviceCreateInfo::extensions).
+//! - [`UnsafeImage`], with [`UnsafeImageCreateInfo::extensions`](crate::image::sys::UnsafeImageCreateInfo::extensions).
+//! - [`UnsafeBuffer`], with [`UnsafeBufferCreateInfo::extensions`](crate::buffer::sys::UnsafeBufferCreateInfo::extensions).
+//! - [`GraphicsPipeline`], with
+//!   [`GraphicsPipelineBuilder::extensions`](crate::pipeline::graphics::GraphicsPipelineBuilder::extensions).
+//! - [`ComputePipeline`], with [`ComputePipeline::with_extensions`].
+//!
+//! The structures are the raw structures of the [`ash`] crate, which is re-exported by vulkano.
+//! Vulkano doesn't know what they mean, so it can't check that they are valid, or take them into
+//! account when validating the other parameters. This is why adding them is unsafe.
+//!
+//! A chain can be cloned along with the create info that contains it, but the pointers in its
+//! structures are only required to stay valid until an object has been created. A chain that is
+//! not empty can therefore only be used to create a single object: once it or one of its clones
+//! has been used, using any of them again panics. [`clear`](ExtensionChain::clear) makes a chain
+//! usable again.
+//!
+//! ```
+//! use vulkano::{
+//!     ash,
+//!     extension_chain::ExtensionChain,
+//!     image::sys::UnsafeImageCreateInfo,
+//! };
+//!
+//! let mut extensions = ExtensionChain::new();
+//!
+//! unsafe {
+//!     extensions.push(ash::vk::ImageStencilUsageCreateInfo {
+//!         stencil_usage: ash::vk::ImageUsageFlags::SAMPLED,
+//!         ..Default::default()
+//!     });
+//! }
+//!
+//! let create_info = UnsafeImageCreateInfo {
+//!     extensions,
+//!     ..Default::default()
+//! };
+//! ```
+
+use crate::{
+    buffer::sys::UnsafeBuffer,
+    device::Device,
+    image::sys::UnsafeImage,
+    instance::Instance,
+    pipeline::{ComputePipeline, GraphicsPipeline},
+};
+use std::{
+    ffi::c_void,
+    fmt,
+    marker::PhantomData,
+    mem::{align_of, size_of},
+    ptr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Extension structures that are added to the `p_next` chain of the create info of an object of
+/// type `T`.
+///
+/// See the [module-level documentation](self) for more information.
+pub struct ExtensionChain<T> {
+    // The structures, copied into `u64`s so that they are aligned, and can be cloned and sent to
+    // other threads.
+    structs: Vec<Box<[u64]>>,
+    // Shared with the clones of the chain. Set once the chain or one of its clones has been used
+    // to create an object, after which the pointers in the structures may be dangling.
+    used: Arc<AtomicBool>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ExtensionChain<T> {
+    /// Returns an empty `ExtensionChain`.
+    #[inline]
+    pub fn new() -> Self {
+        ExtensionChain {
+            structs: Vec::new(),
+            used: Arc::new(AtomicBool::new(false)),
+            marker: PhantomData,
+        }
+    }
+
+    /// Adds a structure at the end of the chain. Its `p_next` member is overwritten when the
+    /// chain is built.
+    ///
+    /// # Safety
+    ///
+    /// - `extension` must be valid to add to the create info of the object, together with the
+    ///   structures that vulkano adds for the other parameters, and with the other parameters.
+    /// - `extension` must not have the same type as a structure that vulkano adds itself.
+    /// - The pointers in `extension`, other than `p_next`, must remain valid until an object
+    ///   has been created with the chain or one of its clones.
+    /// - If the structure requires an extension or a feature, it must be enabled.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the alignment of `S` is greater than 8.
+    pub unsafe fn push<S>(&mut self, extension: S)
+    where
+        S: ExtendsCreateInfo<T> + Copy,
+    {
+        assert!(
+            align_of::<S>() <= align_of::<u64>(),
+            "the alignment of the structure must not be greater than 8",
+        );
+
+        let mut storage = vec![0u64; (size_of::<S>() + 7) / 8].into_boxed_slice();
+        ptr::write(storage.as_mut_ptr() as *mut S, extension);
+        self.structs.push(storage);
+    }
+
+    /// Returns the number of structures in the chain.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.structs.len()
+    }
+
+    /// Returns whether the chain is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.structs.is_empty()
+    }
+
+    /// Removes all the structures from the chain. The chain can then be used to create an object
+    /// again, even if it or one of its clones was already used.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.structs.clear();
+        self.used = Arc::new(AtomicBool::new(false));
+    }
+
+    // Returns the `s_type` of each structure in the chain.
+    pub(crate) fn s_types(&self) -> impl Iterator<Item = ash::vk::StructureType> + '_ {
+        self.structs
+            .iter()
+            .map(|storage| unsafe { *(storage.as_ptr() as *const ash::vk::StructureType) })
+    }
+
+    // Returns a copy of the structure of type `s_type`, if there is one.
+    //
+    // Safety: `S` must be the structure that corresponds to `s_type`.
+    pub(crate) unsafe fn get<S>(&self, s_type: ash::vk::StructureType) -> Option<S>
+    where
+        S: Copy,
+    {
+        self.s_types()
+            .position(|ty| ty == s_type)
+            .map(|index| ptr::read(self.structs[index].as_ptr() as *const S))
+    }
+
+    // Removes the structure of type `s_type`, if there is one.
+    pub(crate) fn remove(&mut self, s_type: ash::vk::StructureType) {
+        if let Some(index) = self.s_types().position(|ty| ty == s_type) {
+            self.structs.remove(index);
+        }
+    }
+
+    // Copies the structures, links them in front of `*p_next`, and makes `*p_next` point to the
+    // first one. The returned value must be kept alive until the object has been created.
+    //
+    // Panics if the chain is not empty, and it or one of its clones was already linked.
+    pub(crate) unsafe fn link(&self, p_next: &mut *const c_void) -> ExtensionChainVk {
+        assert!(
+            self.structs.is_empty() || !self.used.swap(true, Ordering::AcqRel),
+            "an `ExtensionChain` that is not empty can't be used to create more than one object; \
+            the chain or one of its clones was already used",
+        );
+
+        self.link_reusable(p_next)
+    }
+
+    // Like `link`, but the chain can be linked again afterwards.
+    //
+    // Safety: the structures must not contain pointers other than `p_next`.
+    pub(crate) unsafe fn link_reusable(&self, p_next: &mut *const c_void) -> ExtensionChainVk {
+        let mut structs = self.structs.clone();
+
+        for storage in structs.iter_mut().rev() {
+            let base = storage.as_mut_ptr() as *mut ash::vk::BaseOutStructure;
+            (*base).p_next = *p_next as *mut _;
+            *p_next = base as *const c_void;
+        }
+
+        ExtensionChainVk { _structs: structs }
+    }
+}
+
+impl<T> Clone for ExtensionChain<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ExtensionChain {
+            structs: self.structs.clone(),
+            used: self.used.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for ExtensionChain<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for ExtensionChain<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("ExtensionChain")
+            .field("len", &self.structs.len())
+            .finish_non_exhaustive()
+    }
+}
+
+// The structures of an `ExtensionChain`, linked together. The boxes don't move when this is
+// moved, so the pointers stay valid.
+pub(crate) struct ExtensionChainVk {
+    _structs: Vec<Box<[u64]>>,
+}
+
+/// A raw Vulkan structure that can be added to the `p_next` chain of the create info of an object
+/// of type `T`.
+///
+/// # Safety
+///
+/// The structure must begin with the `s_type` and `p_next` members, like all extension
+/// structures of Vulkan. This trait is implemented for the structures of [`ash`] that extend the
+/// corresponding create info.
+pub unsafe trait ExtendsCreateInfo<T> {}
+
+unsafe impl<S> ExtendsCreateInfo<Instance> for S where S: ash::vk::ExtendsInstanceCreateInfo {}
+unsafe impl<S> ExtendsCreateInfo<Device> for S where S: ash::vk::ExtendsDeviceCreateInfo {}
+unsafe impl<S> ExtendsCreateInfo<UnsafeImage> for S where S: ash::vk::ExtendsImageCreateInfo {}
+unsafe impl<S> ExtendsCreateInfo<UnsafeBuffer> for S where S: ash::vk::ExtendsBufferCreateInfo {}
+unsafe impl<S> ExtendsCreateInfo<GraphicsPipeline> for S where
+    S: ash::vk::ExtendsGraphicsPipelineCreateInfo
+{
+}
+unsafe impl<S> ExtendsCreateInfo<ComputePipeline> for S where
+    S: ash::vk::ExtendsComputePipelineCreateInfo
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExtendsCreateInfo, ExtensionChain};
+    use crate::image::sys::UnsafeImage;
+    use std::{ffi::c_void, ptr};
+
+    #[test]
+    fn link() {
+        let mut chain = ExtensionChain::<UnsafeImage>::new();
+
+        unsafe {
+            chain.push(ash::vk::ImageStencilUsageCreateInfo {
+                stencil_usage: ash::vk::ImageUsageFlags::SAMPLED,
+                ..Default::default()
+            });
+            chain.push(ash::vk::ExternalMemoryImageCreateInfo {
+                handle_types: ash::vk::ExternalMemoryHandleTypeFlags::OPAQUE_FD,
+                ..Default::default()
+            });
+        }
+
+        assert_eq!(chain.len(), 2);
+
+        let mut create_info = ash::vk::ImageCreateInfo::default();
+
+        unsafe {
+            let _chain_vk = chain.link(&mut create_info.p_next);
+
+            let first = &*(create_info.p_next as *const ash::vk::ImageStencilUsageCreateInfo);
+            assert_eq!(
+                first.s_type,
+                ash::vk::StructureType::IMAGE_STENCIL_USAGE_CREATE_INFO
+            );
+            assert_eq!(first.stencil_usage, ash::vk::ImageUsageFlags::SAMPLED);
+
+            let second = &*(first.p_next as *const ash::vk::ExternalMemoryImageCreateInfo);
+            assert_eq!(
+                second.handle_types,
+                ash::vk::ExternalMemoryHandleTypeFlags::OPAQUE_FD
+            );
+            assert_eq!(second.p_next, ptr::null());
+        }
+    }
+
+    #[test]
+    fn link_in_front_of_existing() {
+        let mut chain = ExtensionChain::<UnsafeImage>::new();
+
+        unsafe {
+            for stencil_usage in [
+                ash::vk::ImageUsageFlags::SAMPLED,
+                ash::vk::ImageUsageFlags::STORAGE,
+                ash::vk::ImageUsageFlags::TRANSFER_SRC,
+            ] {
+                chain.push(ash::vk::ImageStencilUsageCreateInfo {
+                    stencil_usage,
+                    ..Default::default()
+                });
+            }
+        }
+
+        // A structure that vulkano added itself stays at the end of the chain.
+        let format_list = ash::vk::ImageFormatListCreateInfo::default();
+        let mut create_info = ash::vk::ImageCreateInfo {
+            p_next: &format_list as *const _ as *const c_void,
+            ..Default::default()
+        };
+
+        unsafe {
+            let _chain_vk = chain.link(&mut create_info.p_next);
+
+            let mut p_next = create_info.p_next;
+            let mut stencil_usages = Vec::new();
+
+            while p_next != &format_list as *const _ as *const c_void {
+                let info = &*(p_next as *const ash::vk::ImageStencilUsageCreateInfo);
+                stencil_usages.push(info.stencil_usage);
+                p_next = info.p_next;
+            }
+
+            assert_eq!(
+                stencil_usages,
+                [
+                    ash::vk::ImageUsageFlags::SAMPLED,
+                    ash::vk::ImageUsageFlags::STORAGE,
+                    ash::vk::ImageUsageFlags::TRANSFER_SRC,
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn push_over_aligned() {
+        struct Object;
+
+        #[derive(Clone, Copy)]
+        #[repr(C, align(16))]
+        struct OverAligned {
+            s_type: ash::vk::StructureType,
+            p_next: *const c_void,
+        }
+
+        unsafe impl ExtendsCreateInfo<Object> for OverAligned {}
+
+        let mut chain = ExtensionChain::<Object>::new();
+
+        assert_should_panic!(
+            "the alignment of the structure must not be greater than 8",
+            {
+                unsafe {
+                    chain.push(OverAligned {
+                        s_type: ash::vk::StructureType::IMAGE_STENCIL_USAGE_CREATE_INFO,
+                        p_next: ptr::null(),
+                    });
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn link_clone_once() {
+        let mut chain = ExtensionChain::<UnsafeImage>::new();
+
+        // An empty chain can be used any number of times.
+        unsafe {
+            let mut create_info = ash::vk::ImageCreateInfo::default();
+            chain.link(&mut create_info.p_next);
+            chain.clone().link(&mut create_info.p_next);
+        }
+
+        unsafe {
+            chain.push(ash::vk::ImageStencilUsageCreateInfo::default());
+        }
+
+        let clone = chain.clone();
+
+        unsafe {
+            let mut create_info = ash::vk::ImageCreateInfo::default();
+            chain.link(&mut create_info.p_next);
+        }
+
+        assert_should_panic!({
+            unsafe {
+                let mut create_info = ash::vk::ImageCreateInfo::default();
+                clone.link(&mut create_info.p_next);
+            }
+        });
+
+        // Once cleared, the chain can be used again.
+        chain.clear();
+
+        unsafe {
+            chain.push(ash::vk::ImageStencilUsageCreateInfo::default());
+            let mut create_info = ash::vk::ImageCreateInfo::default();
+            chain.link(&mut create_info.p_next);
+        }
+    }
+}
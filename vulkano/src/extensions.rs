@@ -21,6 +21,8 @@ pub enum SupportedExtensionsError {
     LoadingError(LoadingError),
     /// Not enough memory.
     OomError(OomError),
+    /// The layer whose extensions were requested is not present.
+    LayerNotPresent,
 }
 
 impl error::Error for SupportedExtensionsError {
@@ -29,6 +31,7 @@ impl error::Error for SupportedExtensionsError {
         match *self {
             SupportedExtensionsError::LoadingError(ref err) => Some(err),
             SupportedExtensionsError::OomError(ref err) => Some(err),
+            SupportedExtensionsError::LayerNotPresent => None,
         }
     }
 }
@@ -43,6 +46,7 @@ impl Display for SupportedExtensionsError {
                 SupportedExtensionsError::LoadingError(_) =>
                     "failed to load the Vulkan shared library",
                 SupportedExtensionsError::OomError(_) => "not enough memory available",
+                SupportedExtensionsError::LayerNotPresent => "the layer is not present",
             }
         )
     }
@@ -70,6 +74,7 @@ impl From<Error> for SupportedExtensionsError {
             err @ Error::OutOfDeviceMemory => {
                 SupportedExtensionsError::OomError(OomError::from(err))
             }
+            Error::LayerNotPresent => SupportedExtensionsError::LayerNotPresent,
             _ => panic!("unexpected error: {:?}", err),
         }
     }
@@ -95,7 +95,7 @@
 use crate::{
     device::physical::PhysicalDevice, image::ImageAspects, shader::spirv::ImageFormat, DeviceSize,
 };
-use std::ops::BitOr;
+use std::ops::{BitAnd, BitOr};
 
 // Generated by build.rs
 include!(concat!(env!("OUT_DIR"), "/formats.rs"));
@@ -737,6 +737,19 @@ impl FormatProperties {
     }
 }
 
+/// The properties of a format when it is used with a particular Linux DRM format modifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DrmFormatModifierProperties {
+    /// The DRM format modifier.
+    pub drm_format_modifier: u64,
+
+    /// The number of memory planes of an image created with this modifier.
+    pub drm_format_modifier_plane_count: u32,
+
+    /// Features available for images created with this modifier.
+    pub drm_format_modifier_tiling_features: FormatFeatures,
+}
+
 /// The features supported by a device for an image or buffer with a particular format.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[allow(missing_docs)]
@@ -946,6 +959,72 @@ impl BitOr for &FormatFeatures {
     }
 }
 
+impl BitAnd for &FormatFeatures {
+    type Output = FormatFeatures;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self::Output {
+            sampled_image: self.sampled_image && rhs.sampled_image,
+            storage_image: self.storage_image && rhs.storage_image,
+            storage_image_atomic: self.storage_image_atomic && rhs.storage_image_atomic,
+            storage_read_without_format: self.storage_read_without_format
+                && rhs.storage_read_without_format,
+            storage_write_without_format: self.storage_write_without_format
+                && rhs.storage_write_without_format,
+            color_attachment: self.color_attachment && rhs.color_attachment,
+            color_attachment_blend: self.color_attachment_blend && rhs.color_attachment_blend,
+            depth_stencil_attachment: self.depth_stencil_attachment && rhs.depth_stencil_attachment,
+            fragment_density_map: self.fragment_density_map && rhs.fragment_density_map,
+            fragment_shading_rate_attachment: self.fragment_shading_rate_attachment
+                && rhs.fragment_shading_rate_attachment,
+            transfer_src: self.transfer_src && rhs.transfer_src,
+            transfer_dst: self.transfer_dst && rhs.transfer_dst,
+            blit_src: self.blit_src && rhs.blit_src,
+            blit_dst: self.blit_dst && rhs.blit_dst,
+
+            sampled_image_filter_linear: self.sampled_image_filter_linear
+                && rhs.sampled_image_filter_linear,
+            sampled_image_filter_cubic: self.sampled_image_filter_cubic
+                && rhs.sampled_image_filter_cubic,
+            sampled_image_filter_minmax: self.sampled_image_filter_minmax
+                && rhs.sampled_image_filter_minmax,
+            midpoint_chroma_samples: self.midpoint_chroma_samples && rhs.midpoint_chroma_samples,
+            cosited_chroma_samples: self.cosited_chroma_samples && rhs.cosited_chroma_samples,
+            sampled_image_ycbcr_conversion_linear_filter: self
+                .sampled_image_ycbcr_conversion_linear_filter
+                && rhs.sampled_image_ycbcr_conversion_linear_filter,
+            sampled_image_ycbcr_conversion_separate_reconstruction_filter: self
+                .sampled_image_ycbcr_conversion_separate_reconstruction_filter
+                && rhs.sampled_image_ycbcr_conversion_separate_reconstruction_filter,
+            sampled_image_ycbcr_conversion_chroma_reconstruction_explicit: self
+                .sampled_image_ycbcr_conversion_chroma_reconstruction_explicit
+                && rhs.sampled_image_ycbcr_conversion_chroma_reconstruction_explicit,
+            sampled_image_ycbcr_conversion_chroma_reconstruction_explicit_forceable: self
+                .sampled_image_ycbcr_conversion_chroma_reconstruction_explicit_forceable
+                && rhs.sampled_image_ycbcr_conversion_chroma_reconstruction_explicit_forceable,
+            sampled_image_depth_comparison: self.sampled_image_depth_comparison
+                && rhs.sampled_image_depth_comparison,
+
+            video_decode_output: self.video_decode_output && rhs.video_decode_output,
+            video_decode_dpb: self.video_decode_dpb && rhs.video_decode_dpb,
+            video_encode_input: self.video_encode_input && rhs.video_encode_input,
+            video_encode_dpb: self.video_encode_dpb && rhs.video_encode_dpb,
+
+            disjoint: self.disjoint && rhs.disjoint,
+
+            uniform_texel_buffer: self.uniform_texel_buffer && rhs.uniform_texel_buffer,
+            storage_texel_buffer: self.storage_texel_buffer && rhs.storage_texel_buffer,
+            storage_texel_buffer_atomic: self.storage_texel_buffer_atomic
+                && rhs.storage_texel_buffer_atomic,
+            vertex_buffer: self.vertex_buffer && rhs.vertex_buffer,
+            acceleration_structure_vertex_buffer: self.acceleration_structure_vertex_buffer
+                && rhs.acceleration_structure_vertex_buffer,
+
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
 impl From<ash::vk::FormatFeatureFlags> for FormatFeatures {
     #[inline]
     #[rustfmt::skip]
@@ -8,8 +8,9 @@
 // according to those terms.
 
 use super::{
-    sys::UnsafeImage, traits::ImageContent, ImageAccess, ImageCreationError,
-    ImageDescriptorLayouts, ImageInner, ImageLayout, ImageUsage, SampleCount,
+    sys::UnsafeImage, traits::ImageContent, ImageAccess, ImageCompression,
+    ImageCompressionFixedRates, ImageCreateFlags, ImageCreationError, ImageDescriptorLayouts,
+    ImageInner, ImageLayout, ImageUsage, SampleCount,
 };
 use crate::{
     device::{Device, DeviceOwned},
@@ -21,10 +22,12 @@ use crate::{
             MappingRequirement, MemoryPoolAlloc, PotentialDedicatedAllocation, StdMemoryPoolAlloc,
         },
         DedicatedAllocation, DeviceMemoryExportError, ExternalMemoryHandleType,
-        ExternalMemoryHandleTypes, MemoryPool,
+        ExternalMemoryHandleTypes, MemoryPool, MemoryRequirements,
     },
+    sync::Sharing,
     DeviceSize,
 };
+use smallvec::SmallVec;
 use std::{
     fs::File,
     hash::{Hash, Hasher},
@@ -36,12 +39,14 @@ use std::{
 
 /// ImageAccess whose purpose is to be used as a framebuffer attachment.
 ///
-/// The image is always two-dimensional and has only one mipmap, but it can have any kind of
-/// format. Trying to use a format that the backend doesn't support for rendering will result in
-/// an error being returned when creating the image. Once you have an `AttachmentImage`, you are
-/// guaranteed that you will be able to draw on it.
+/// The image is always two-dimensional, but it can have any non-compressed format. Trying to use
+/// a format that the backend doesn't support for rendering will result in an error being returned
+/// when creating the image. Once you have an `AttachmentImage`, you are guaranteed that you will
+/// be able to draw on it.
 ///
-/// The template parameter of `AttachmentImage` is a type that describes the format of the image.
+/// The shortcut constructors cover the most common cases. For full control over the usage, sample
+/// count, mip levels, flags, sharing mode and initial layout, use
+/// [`with_create_info`](AttachmentImage::with_create_info).
 ///
 /// # Regular vs transient
 ///
@@ -56,6 +61,9 @@ use std::{
 ///
 /// This gives a hint to the Vulkan implementation that it is possible for the image's content to
 /// live exclusively in some cache memory, and that no real memory has to be allocated for it.
+/// If the device has a lazily allocated memory type, transient images are allocated from it, so
+/// that memory is only committed if the implementation actually needs it. You can check how much
+/// memory was committed with `memory_commitment`.
 ///
 /// In other words, if you are going to read from the image after drawing to it, use a regular
 /// image. If you don't need to read from it (for example if it's some kind of intermediary color,
@@ -91,50 +99,23 @@ impl AttachmentImage {
     ///
     /// Returns an error if the dimensions are too large or if the backend doesn't support this
     /// format as a framebuffer attachment.
-    #[inline]
-    pub fn new(
-        device: Arc<Device>,
-        dimensions: [u32; 2],
-        format: Format,
-    ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        AttachmentImage::new_impl(
-            device,
-            dimensions,
-            1,
-            format,
-            ImageUsage::none(),
-            SampleCount::Sample1,
-        )
-    }
-
-    /// Same as `new`, but creates an image that can be used as an input attachment.
     ///
-    /// > **Note**: This function is just a convenient shortcut for `with_usage`.
+    /// > **Note**: This function is just a convenient shortcut for `with_create_info`.
     #[inline]
-    pub fn input_attachment(
+    pub fn new(
         device: Arc<Device>,
         dimensions: [u32; 2],
         format: Format,
     ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        let base_usage = ImageUsage {
-            input_attachment: true,
-            ..ImageUsage::none()
-        };
-
-        AttachmentImage::new_impl(
+        AttachmentImage::with_create_info(
             device,
-            dimensions,
-            1,
-            format,
-            base_usage,
-            SampleCount::Sample1,
+            AttachmentImageCreateInfo::dimensions_format(dimensions, format),
         )
     }
 
     /// Same as `new`, but creates a multisampled image.
     ///
-    /// > **Note**: You can also use this function and pass `1` for the number of samples if you
-    /// > want a regular image.
+    /// > **Note**: This function is just a convenient shortcut for `with_create_info`.
     #[inline]
     pub fn multisampled(
         device: Arc<Device>,
@@ -142,25 +123,13 @@ impl AttachmentImage {
         samples: SampleCount,
         format: Format,
     ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        AttachmentImage::new_impl(device, dimensions, 1, format, ImageUsage::none(), samples)
-    }
-
-    /// Same as `multisampled`, but creates an image that can be used as an input attachment.
-    ///
-    /// > **Note**: This function is just a convenient shortcut for `multisampled_with_usage`.
-    #[inline]
-    pub fn multisampled_input_attachment(
-        device: Arc<Device>,
-        dimensions: [u32; 2],
-        samples: SampleCount,
-        format: Format,
-    ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        let base_usage = ImageUsage {
-            input_attachment: true,
-            ..ImageUsage::none()
-        };
-
-        AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples)
+        AttachmentImage::with_create_info(
+            device,
+            AttachmentImageCreateInfo {
+                samples,
+                ..AttachmentImageCreateInfo::dimensions_format(dimensions, format)
+            },
+        )
     }
 
     /// Same as `new`, but lets you specify additional usages.
@@ -168,6 +137,8 @@ impl AttachmentImage {
     /// The `color_attachment` or `depth_stencil_attachment` usages are automatically added based
     /// on the format of the usage. Therefore the `usage` parameter allows you specify usages in
     /// addition to these two.
+    ///
+    /// > **Note**: This function is just a convenient shortcut for `with_create_info`.
     #[inline]
     pub fn with_usage(
         device: Arc<Device>,
@@ -175,13 +146,18 @@ impl AttachmentImage {
         format: Format,
         usage: ImageUsage,
     ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        AttachmentImage::new_impl(device, dimensions, 1, format, usage, SampleCount::Sample1)
+        AttachmentImage::with_create_info(
+            device,
+            AttachmentImageCreateInfo {
+                usage,
+                ..AttachmentImageCreateInfo::dimensions_format(dimensions, format)
+            },
+        )
     }
 
     /// Same as `with_usage`, but creates a multisampled image.
     ///
-    /// > **Note**: You can also use this function and pass `1` for the number of samples if you
-    /// > want a regular image.
+    /// > **Note**: This function is just a convenient shortcut for `with_create_info`.
     #[inline]
     pub fn multisampled_with_usage(
         device: Arc<Device>,
@@ -190,225 +166,127 @@ impl AttachmentImage {
         format: Format,
         usage: ImageUsage,
     ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        AttachmentImage::new_impl(device, dimensions, 1, format, usage, samples)
-    }
-
-    /// Same as `multisampled_with_usage`, but creates an image with multiple layers.
-    ///
-    /// > **Note**: You can also use this function and pass `1` for the number of layers if you
-    /// > want a regular image.
-    #[inline]
-    pub fn multisampled_with_usage_with_layers(
-        device: Arc<Device>,
-        dimensions: [u32; 2],
-        array_layers: u32,
-        samples: SampleCount,
-        format: Format,
-        usage: ImageUsage,
-    ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        AttachmentImage::new_impl(device, dimensions, array_layers, format, usage, samples)
-    }
-
-    /// Same as `new`, except that the image can later be sampled.
-    ///
-    /// > **Note**: This function is just a convenient shortcut for `with_usage`.
-    #[inline]
-    pub fn sampled(
-        device: Arc<Device>,
-        dimensions: [u32; 2],
-        format: Format,
-    ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        let base_usage = ImageUsage {
-            sampled: true,
-            ..ImageUsage::none()
-        };
-
-        AttachmentImage::new_impl(
+        AttachmentImage::with_create_info(
             device,
-            dimensions,
-            1,
-            format,
-            base_usage,
-            SampleCount::Sample1,
+            AttachmentImageCreateInfo {
+                samples,
+                usage,
+                ..AttachmentImageCreateInfo::dimensions_format(dimensions, format)
+            },
         )
     }
 
-    /// Same as `sampled`, except that the image can be used as an input attachment.
+    /// Same as `new`, except that the image can later be sampled.
     ///
-    /// > **Note**: This function is just a convenient shortcut for `with_usage`.
+    /// > **Note**: This function is just a convenient shortcut for `with_create_info`.
     #[inline]
-    pub fn sampled_input_attachment(
+    pub fn sampled(
         device: Arc<Device>,
         dimensions: [u32; 2],
         format: Format,
     ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        let base_usage = ImageUsage {
-            sampled: true,
-            input_attachment: true,
-            ..ImageUsage::none()
-        };
-
-        AttachmentImage::new_impl(
+        AttachmentImage::with_create_info(
             device,
-            dimensions,
-            1,
-            format,
-            base_usage,
-            SampleCount::Sample1,
+            AttachmentImageCreateInfo {
+                usage: ImageUsage {
+                    sampled: true,
+                    ..ImageUsage::none()
+                },
+                ..AttachmentImageCreateInfo::dimensions_format(dimensions, format)
+            },
         )
     }
 
-    /// Same as `sampled`, but creates a multisampled image.
-    ///
-    /// > **Note**: You can also use this function and pass `1` for the number of samples if you
-    /// > want a regular image.
-    ///
-    /// > **Note**: This function is just a convenient shortcut for `multisampled_with_usage`.
-    #[inline]
-    pub fn sampled_multisampled(
-        device: Arc<Device>,
-        dimensions: [u32; 2],
-        samples: SampleCount,
-        format: Format,
-    ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        let base_usage = ImageUsage {
-            sampled: true,
-            ..ImageUsage::none()
-        };
-
-        AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples)
-    }
-
-    /// Same as `sampled_multisampled`, but creates an image that can be used as an input
-    /// attachment.
-    ///
-    /// > **Note**: This function is just a convenient shortcut for `multisampled_with_usage`.
-    #[inline]
-    pub fn sampled_multisampled_input_attachment(
-        device: Arc<Device>,
-        dimensions: [u32; 2],
-        samples: SampleCount,
-        format: Format,
-    ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        let base_usage = ImageUsage {
-            sampled: true,
-            input_attachment: true,
-            ..ImageUsage::none()
-        };
-
-        AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples)
-    }
-
     /// Same as `new`, except that the image will be transient.
     ///
     /// A transient image is special because its content is undefined outside of a render pass.
     /// This means that the implementation has the possibility to not allocate any memory for it.
     ///
-    /// > **Note**: This function is just a convenient shortcut for `with_usage`.
+    /// > **Note**: This function is just a convenient shortcut for `with_create_info`.
     #[inline]
     pub fn transient(
         device: Arc<Device>,
         dimensions: [u32; 2],
         format: Format,
     ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        let base_usage = ImageUsage {
-            transient_attachment: true,
-            ..ImageUsage::none()
-        };
-
-        AttachmentImage::new_impl(
+        AttachmentImage::with_create_info(
             device,
-            dimensions,
-            1,
-            format,
-            base_usage,
-            SampleCount::Sample1,
+            AttachmentImageCreateInfo {
+                usage: ImageUsage {
+                    transient_attachment: true,
+                    ..ImageUsage::none()
+                },
+                ..AttachmentImageCreateInfo::dimensions_format(dimensions, format)
+            },
         )
     }
 
-    /// Same as `transient`, except that the image can be used as an input attachment.
+    /// Same as `transient`, but creates a multisampled image.
     ///
-    /// > **Note**: This function is just a convenient shortcut for `with_usage`.
+    /// > **Note**: This function is just a convenient shortcut for `with_create_info`.
     #[inline]
-    pub fn transient_input_attachment(
+    pub fn transient_multisampled(
         device: Arc<Device>,
         dimensions: [u32; 2],
+        samples: SampleCount,
         format: Format,
     ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        let base_usage = ImageUsage {
-            transient_attachment: true,
-            input_attachment: true,
-            ..ImageUsage::none()
-        };
-
-        AttachmentImage::new_impl(
+        AttachmentImage::with_create_info(
             device,
-            dimensions,
-            1,
-            format,
-            base_usage,
-            SampleCount::Sample1,
+            AttachmentImageCreateInfo {
+                samples,
+                usage: ImageUsage {
+                    transient_attachment: true,
+                    ..ImageUsage::none()
+                },
+                ..AttachmentImageCreateInfo::dimensions_format(dimensions, format)
+            },
         )
     }
 
-    /// Same as `transient`, but creates a multisampled image.
+    /// Creates a new image from the parameters in `create_info`.
     ///
-    /// > **Note**: You can also use this function and pass `1` for the number of samples if you
-    /// > want a regular image.
+    /// The `color_attachment` or `depth_stencil_attachment` usage is automatically added based on
+    /// the format.
     ///
-    /// > **Note**: This function is just a convenient shortcut for `multisampled_with_usage`.
-    #[inline]
-    pub fn transient_multisampled(
-        device: Arc<Device>,
-        dimensions: [u32; 2],
-        samples: SampleCount,
-        format: Format,
-    ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        let base_usage = ImageUsage {
-            transient_attachment: true,
-            ..ImageUsage::none()
-        };
-
-        AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples)
-    }
-
-    /// Same as `transient_multisampled`, but creates an image that can be used as an input
-    /// attachment.
+    /// # Panics
     ///
-    /// > **Note**: This function is just a convenient shortcut for `multisampled_with_usage`.
-    #[inline]
-    pub fn transient_multisampled_input_attachment(
+    /// - Panics if `create_info.format` is `None`.
+    /// - Panics if `create_info.format` is a compressed format.
+    /// - Panics if any of the sparse flags are set in `create_info.flags`.
+    pub fn with_create_info(
         device: Arc<Device>,
-        dimensions: [u32; 2],
-        samples: SampleCount,
-        format: Format,
+        create_info: AttachmentImageCreateInfo,
     ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        let base_usage = ImageUsage {
-            transient_attachment: true,
-            input_attachment: true,
-            ..ImageUsage::none()
-        };
-
-        AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples)
-    }
+        let AttachmentImageCreateInfo {
+            dimensions,
+            array_layers,
+            format,
+            mip_levels,
+            samples,
+            usage,
+            flags,
+            sharing,
+            view_formats,
+            compression,
+            compression_fixed_rates,
+            _ne: _,
+        } = create_info;
 
-    // All constructors dispatch to this one.
-    fn new_impl(
-        device: Arc<Device>,
-        dimensions: [u32; 2],
-        array_layers: u32,
-        format: Format,
-        base_usage: ImageUsage,
-        samples: SampleCount,
-    ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
         // TODO: check dimensions against the max_framebuffer_width/height/layers limits
 
+        let format = format.expect("no format was provided");
         let aspects = format.aspects();
         let is_depth = aspects.depth || aspects.stencil;
 
-        if format.compression().is_some() {
-            panic!() // TODO: message?
-        }
+        assert!(
+            format.compression().is_none(),
+            "attachment images can not have a compressed format"
+        );
+        assert!(
+            !(flags.sparse_binding || flags.sparse_residency || flags.sparse_aliased),
+            "attachment images can not be sparse"
+        );
 
         let image = UnsafeImage::new(
             device.clone(),
@@ -419,25 +297,58 @@ impl AttachmentImage {
                     array_layers,
                 },
                 format: Some(format),
+                mip_levels,
                 samples,
                 usage: ImageUsage {
                     color_attachment: !is_depth,
                     depth_stencil_attachment: is_depth,
-                    ..base_usage
+                    ..usage
                 },
+                sharing,
+                mutable_format: flags.mutable_format,
+                cube_compatible: flags.cube_compatible,
+                array_2d_compatible: flags.array_2d_compatible,
+                block_texel_view_compatible: flags.block_texel_view_compatible,
+                alias: flags.alias,
+                view_2d_compatible: flags.view_2d_compatible,
+                protected: flags.protected,
+                view_formats,
+                compression,
+                compression_fixed_rates,
                 ..Default::default()
             },
         )?;
 
         let mem_reqs = image.memory_requirements();
+
+        // Transient images can be backed by lazily allocated memory, which the implementation
+        // only commits when it is actually needed. This is only useful if the memory is not
+        // shared with any other resource, so a dedicated allocation is always made in that case.
+        let lazily_allocated = usage.transient_attachment
+            && device
+                .physical_device()
+                .memory_types()
+                .any(|t| t.is_lazily_allocated() && mem_reqs.memory_type_bits & (1 << t.id()) != 0);
+
         let memory = MemoryPool::alloc_from_requirements(
             &Device::standard_pool(&device),
-            &mem_reqs,
+            &MemoryRequirements {
+                prefer_dedicated: mem_reqs.prefer_dedicated || lazily_allocated,
+                ..mem_reqs
+            },
             AllocLayout::Optimal,
             MappingRequirement::DoNotMap,
             Some(DedicatedAllocation::Image(&image)),
             |t| {
-                if t.is_device_local() {
+                if t.is_protected() != flags.protected {
+                    AllocFromRequirementsFilter::Forbidden
+                } else if t.is_lazily_allocated() {
+                    if lazily_allocated {
+                        AllocFromRequirementsFilter::Preferred
+                    } else {
+                        AllocFromRequirementsFilter::Forbidden
+                    }
+                } else if t.is_device_local() && !lazily_allocated {
                     AllocFromRequirementsFilter::Preferred
                 } else {
                     AllocFromRequirementsFilter::Allowed
@@ -547,6 +458,147 @@ impl AttachmentImage {
     pub fn mem_size(&self) -> DeviceSize {
         self.memory.memory().allocation_size()
     }
+
+    /// Returns whether the image is backed by lazily allocated memory.
+    ///
+    /// This can only be the case for transient images.
+    #[inline]
+    pub fn is_lazily_allocated(&self) -> bool {
+        self.memory.memory().memory_type().is_lazily_allocated()
+    }
+
+    /// If the image is backed by lazily allocated memory, returns the number of bytes of memory
+    /// that the implementation has currently committed for it. Returns `None` otherwise.
+    #[inline]
+    pub fn memory_commitment(&self) -> Option<DeviceSize> {
+        self.memory.memory().commitment().ok()
+    }
+}
+
+/// Parameters to create a new `AttachmentImage`.
+#[derive(Clone, Debug)]
+pub struct AttachmentImageCreateInfo {
+    /// The width and height of the image.
+    ///
+    /// The default value is `[0, 0]`, which must be overridden.
+    pub dimensions: [u32; 2],
+
+    /// The number of array layers to create the image with.
+    ///
+    /// The default value is `1`.
+    pub array_layers: u32,
+
+    /// The format used to store the image data.
+    ///
+    /// The format must not be compressed.
+    ///
+    /// The default value is `None`, which must be overridden.
+    pub format: Option<Format>,
+
+    /// The number of mip levels to create the image with.
+    ///
+    /// Only the first mip level can be used as a framebuffer attachment.
+    ///
+    /// The default value is `1`.
+    pub mip_levels: u32,
+
+    /// The number of samples per texel that the image should use.
+    ///
+    /// The default value is [`SampleCount::Sample1`].
+    pub samples: SampleCount,
+
+    /// How the image is going to be used, in addition to being used as an attachment.
+    ///
+    /// The `color_attachment` or `depth_stencil_attachment` usage is added automatically,
+    /// depending on the format.
+    ///
+    /// The default value is [`ImageUsage::none()`].
+    pub usage: ImageUsage,
+
+    /// Additional properties of the image.
+    ///
+    /// The sparse flags are not supported and must not be set.
+    ///
+    /// The default value is [`ImageCreateFlags::none()`].
+    pub flags: ImageCreateFlags,
+
+    /// Whether the image can be shared across multiple queues, or is limited to a single queue.
+    ///
+    /// The default value is [`Sharing::Exclusive`].
+    pub sharing: Sharing<SmallVec<[u32; 4]>>,
+
+    /// The formats that image views created from the image will have. See
+    /// [`UnsafeImageCreateInfo::view_formats`] for details.
+    ///
+    /// The default value is empty.
+    pub view_formats: Vec<Format>,
+
+    /// The compression that the implementation should apply to the image. See
+    /// [`UnsafeImageCreateInfo::compression`] for details.
+    ///
+    /// The default value is `None`.
+    pub compression: Option<ImageCompression>,
+
+    /// The fixed-rate compression bit rates to choose from for each plane of the image. See
+    /// [`UnsafeImageCreateInfo::compression_fixed_rates`] for details.
+    ///
+    /// The default value is empty.
+    pub compression_fixed_rates: Vec<ImageCompressionFixedRates>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl AttachmentImageCreateInfo {
+    /// Returns an `AttachmentImageCreateInfo` with the specified `dimensions` and `format`, and
+    /// all other fields set to their default values.
+    #[inline]
+    pub fn dimensions_format(dimensions: [u32; 2], format: Format) -> Self {
+        Self {
+            dimensions,
+            format: Some(format),
+            ..Default::default()
+        }
+    }
+
+    /// Returns an `AttachmentImageCreateInfo` for a cube-compatible image with `cube_count` cubes,
+    /// each face being `size` by `size` texels, and all other fields set to their default values.
+    ///
+    /// The image will have `6 * cube_count` array layers. Viewing the image as a cube array
+    /// requires the [`image_cube_array`](crate::device::Features::image_cube_array) feature to be
+    /// enabled.
+    #[inline]
+    pub fn cube(size: u32, cube_count: u32, format: Format) -> Self {
+        Self {
+            dimensions: [size, size],
+            array_layers: cube_count * 6,
+            format: Some(format),
+            flags: ImageCreateFlags {
+                cube_compatible: true,
+                ..ImageCreateFlags::none()
+            },
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for AttachmentImageCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            dimensions: [0, 0],
+            array_layers: 1,
+            format: None,
+            mip_levels: 1,
+            samples: SampleCount::Sample1,
+            usage: ImageUsage::none(),
+            flags: ImageCreateFlags::none(),
+            sharing: Sharing::Exclusive,
+            view_formats: Vec::new(),
+            compression: None,
+            compression_fixed_rates: Vec::new(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
 }
 
 unsafe impl<A> ImageAccess for AttachmentImage<A>
@@ -560,7 +612,7 @@ where
             first_layer: 0,
             num_layers: self.image.dimensions().array_layers(),
             first_mipmap_level: 0,
-            num_mipmap_levels: 1,
+            num_mipmap_levels: self.image.mip_levels(),
         }
     }
 
@@ -635,8 +687,11 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::AttachmentImage;
-    use crate::format::Format;
+    use super::{AttachmentImage, AttachmentImageCreateInfo};
+    use crate::{
+        format::Format,
+        image::{ImageAccess, ImageUsage},
+    };
 
     #[test]
     fn create_regular() {
@@ -650,6 +705,41 @@ mod tests {
         let _img = AttachmentImage::transient(device, [32, 32], Format::R8G8B8A8_UNORM).unwrap();
     }
 
+    #[test]
+    fn transient_memory_commitment() {
+        let (device, _) = gfx_dev_and_queue!();
+        let img = AttachmentImage::transient(device, [32, 32], Format::R8G8B8A8_UNORM).unwrap();
+
+        match img.memory_commitment() {
+            Some(commitment) => assert!(commitment <= img.mem_size()),
+            None => assert!(!img.is_lazily_allocated()),
+        }
+    }
+
+    #[test]
+    fn create_with_create_info() {
+        let (device, _) = gfx_dev_and_queue!();
+        let img = AttachmentImage::with_create_info(
+            device,
+            AttachmentImageCreateInfo {
+                array_layers: 2,
+                mip_levels: 3,
+                usage: ImageUsage {
+                    sampled: true,
+                    ..ImageUsage::none()
+                },
+                ..AttachmentImageCreateInfo::dimensions_format([32, 32], Format::R8G8B8A8_UNORM)
+            },
+        )
+        .unwrap();
+
+        let inner = img.inner();
+        assert_eq!(inner.num_layers, 2);
+        assert_eq!(inner.num_mipmap_levels, 3);
+        assert!(inner.image.usage().color_attachment);
+        assert!(inner.image.usage().sampled);
+    }
+
     #[test]
     fn d16_unorm_always_supported() {
         let (device, _) = gfx_dev_and_queue!();
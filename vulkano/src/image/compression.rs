@@ -0,0 +1,138 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::ops::BitOr;
+
+/// The kind of compression that the implementation applies to an image.
+///
+/// Requires the
+/// [`ext_image_compression_control`](crate::device::DeviceExtensions::ext_image_compression_control)
+/// extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ImageCompression {
+    /// The implementation's default compression, which is lossless.
+    Default = ash::vk::ImageCompressionFlagsEXT::DEFAULT.as_raw(),
+
+    /// Lossy fixed-rate compression, with the rate chosen by the implementation.
+    FixedRateDefault = ash::vk::ImageCompressionFlagsEXT::FIXED_RATE_DEFAULT.as_raw(),
+
+    /// Lossy fixed-rate compression, with the allowed rates for each plane given explicitly.
+    FixedRateExplicit = ash::vk::ImageCompressionFlagsEXT::FIXED_RATE_EXPLICIT.as_raw(),
+
+    /// No compression at all.
+    Disabled = ash::vk::ImageCompressionFlagsEXT::DISABLED.as_raw(),
+}
+
+impl From<ImageCompression> for ash::vk::ImageCompressionFlagsEXT {
+    #[inline]
+    fn from(val: ImageCompression) -> Self {
+        Self::from_raw(val as u32)
+    }
+}
+
+macro_rules! image_compression_fixed_rates {
+    ($($elem:ident => $val:ident,)+) => (
+        /// A set of fixed-rate compression bit rates, measured in bits per component.
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        pub struct ImageCompressionFixedRates {
+            $(
+                pub $elem: bool,
+            )+
+        }
+
+        impl ImageCompressionFixedRates {
+            /// Builds an `ImageCompressionFixedRates` with all values set to false.
+            #[inline]
+            pub const fn none() -> ImageCompressionFixedRates {
+                ImageCompressionFixedRates {
+                    $(
+                        $elem: false,
+                    )+
+                }
+            }
+        }
+
+        impl From<ImageCompressionFixedRates> for ash::vk::ImageCompressionFixedRateFlagsEXT {
+            #[inline]
+            fn from(val: ImageCompressionFixedRates) -> Self {
+                let mut result = ash::vk::ImageCompressionFixedRateFlagsEXT::empty();
+                $(
+                    if val.$elem { result |= ash::vk::ImageCompressionFixedRateFlagsEXT::$val }
+                )+
+                result
+            }
+        }
+
+        impl From<ash::vk::ImageCompressionFixedRateFlagsEXT> for ImageCompressionFixedRates {
+            #[inline]
+            fn from(val: ash::vk::ImageCompressionFixedRateFlagsEXT) -> Self {
+                ImageCompressionFixedRates {
+                    $(
+                        $elem: val.intersects(ash::vk::ImageCompressionFixedRateFlagsEXT::$val),
+                    )+
+                }
+            }
+        }
+
+        impl BitOr for ImageCompressionFixedRates {
+            type Output = Self;
+
+            #[inline]
+            fn bitor(self, rhs: Self) -> Self {
+                ImageCompressionFixedRates {
+                    $(
+                        $elem: self.$elem || rhs.$elem,
+                    )+
+                }
+            }
+        }
+    );
+}
+
+image_compression_fixed_rates! {
+    bpc1 => TYPE_1BPC,
+    bpc2 => TYPE_2BPC,
+    bpc3 => TYPE_3BPC,
+    bpc4 => TYPE_4BPC,
+    bpc5 => TYPE_5BPC,
+    bpc6 => TYPE_6BPC,
+    bpc7 => TYPE_7BPC,
+    bpc8 => TYPE_8BPC,
+    bpc9 => TYPE_9BPC,
+    bpc10 => TYPE_10BPC,
+    bpc11 => TYPE_11BPC,
+    bpc12 => TYPE_12BPC,
+    bpc13 => TYPE_13BPC,
+    bpc14 => TYPE_14BPC,
+    bpc15 => TYPE_15BPC,
+    bpc16 => TYPE_16BPC,
+    bpc17 => TYPE_17BPC,
+    bpc18 => TYPE_18BPC,
+    bpc19 => TYPE_19BPC,
+    bpc20 => TYPE_20BPC,
+    bpc21 => TYPE_21BPC,
+    bpc22 => TYPE_22BPC,
+    bpc23 => TYPE_23BPC,
+    bpc24 => TYPE_24BPC,
+}
+
+/// The compression that the implementation has applied to a subresource of an image.
+///
+/// Obtained by calling [`UnsafeImage::compression_properties`](super::sys::UnsafeImage::compression_properties).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ImageCompressionProperties {
+    /// The kind of compression that was applied. This is one of [`ImageCompression::Default`],
+    /// [`ImageCompression::FixedRateExplicit`] or [`ImageCompression::Disabled`].
+    pub compression: ImageCompression,
+
+    /// If `compression` is [`ImageCompression::FixedRateExplicit`], the bit rate that was
+    /// applied. Otherwise, this is empty.
+    pub fixed_rates: ImageCompressionFixedRates,
+}
@@ -13,11 +13,13 @@ use super::{
     ImageUsage, MipmapsCount,
 };
 use crate::{
-    buffer::{BufferAccess, BufferContents, BufferUsage, CpuAccessibleBuffer},
+    buffer::{
+        Buffer, BufferAccess, BufferContents, BufferCreationError, BufferLocation, BufferUsage,
+    },
     command_buffer::{
-        AutoCommandBufferBuilder, BlitImageInfo, CommandBufferBeginError, CommandBufferExecFuture,
-        CommandBufferUsage, CopyBufferToImageInfo, ImageBlit, PrimaryAutoCommandBuffer,
-        PrimaryCommandBuffer,
+        AutoCommandBufferBuilder, BlitImageInfo, BufferImageCopy, CommandBufferBeginError,
+        CommandBufferExecFuture, CommandBufferUsage, CopyBufferToImageInfo, ImageBlit,
+        PrimaryAutoCommandBuffer, PrimaryCommandBuffer,
     },
     device::{physical::QueueFamily, Device, DeviceOwned, Queue},
     format::Format,
@@ -31,7 +33,7 @@ use crate::{
     },
     sampler::Filter,
     sync::{NowFuture, Sharing},
-    OomError,
+    DeviceSize, OomError,
 };
 use smallvec::SmallVec;
 use std::{
@@ -259,10 +261,10 @@ impl ImmutableImage {
         I: IntoIterator<Item = Px>,
         I::IntoIter: ExactSizeIterator,
     {
-        let source = CpuAccessibleBuffer::from_iter(
+        let source = Buffer::from_iter(
             queue.device().clone(),
             BufferUsage::transfer_src(),
-            false,
+            BufferLocation::Upload,
             iter,
         )?;
         ImmutableImage::from_buffer(source, dimensions, mip_levels, format, queue)
@@ -329,6 +331,96 @@ impl ImmutableImage {
 
         Ok((image, future))
     }
+
+    /// Construct an ImmutableImage containing a copy of the data in `source`, which must hold a
+    /// complete mip chain that was generated in advance.
+    ///
+    /// The levels must be tightly packed in `source`, starting with the base level and
+    /// followed by each smaller level, with all array layers of a level stored one after another.
+    /// Each level is laid out in whole texel blocks, so for block-compressed formats the size of
+    /// a level whose dimensions are not a multiple of the block extent is rounded up.
+    ///
+    /// Unlike `from_buffer`, the mipmaps are not generated with blits, so this can be used
+    /// for block-compressed formats.
+    pub fn from_buffer_mip_chain(
+        source: Arc<dyn BufferAccess>,
+        dimensions: ImageDimensions,
+        mip_levels: u32,
+        format: Format,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (
+            Arc<Self>,
+            CommandBufferExecFuture<NowFuture, PrimaryAutoCommandBuffer>,
+        ),
+        ImmutableImageCreationError,
+    > {
+        let usage = ImageUsage {
+            transfer_dst: true,
+            sampled: true,
+            ..ImageUsage::none()
+        };
+        let flags = ImageCreateFlags::none();
+        let layout = ImageLayout::ShaderReadOnlyOptimal;
+
+        let (image, initializer) = ImmutableImage::uninitialized(
+            source.device().clone(),
+            dimensions,
+            format,
+            MipmapsCount::Specific(mip_levels),
+            usage,
+            flags,
+            layout,
+            source.device().active_queue_families(),
+        )?;
+
+        let mut buffer_offset = 0;
+        let regions = (0..image.mip_levels())
+            .map(|mip_level| {
+                let region = BufferImageCopy {
+                    buffer_offset,
+                    image_subresource: ImageSubresourceLayers {
+                        mip_level,
+                        ..image.subresource_layers()
+                    },
+                    image_extent: dimensions
+                        .mip_level_dimensions(mip_level)
+                        .unwrap()
+                        .width_height_depth(),
+                    ..Default::default()
+                };
+                buffer_offset += region.buffer_copy_size(format);
+                region
+            })
+            .collect();
+
+        if source.size() < buffer_offset {
+            return Err(ImmutableImageCreationError::SourceTooSmall {
+                source_size: source.size(),
+                required_size: buffer_offset,
+            });
+        }
+
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            source.device().clone(),
+            queue.family(),
+            CommandBufferUsage::MultipleSubmit,
+        )?;
+        cbb.copy_buffer_to_image(CopyBufferToImageInfo {
+            regions,
+            ..CopyBufferToImageInfo::buffer_image(source, initializer)
+        })
+        .unwrap();
+
+        let cb = cbb.build().unwrap();
+
+        let future = match cb.execute(queue) {
+            Ok(f) => f,
+            Err(e) => unreachable!("{:?}", e),
+        };
+
+        Ok((image, future))
+    }
 }
 
 unsafe impl<A> DeviceOwned for ImmutableImage<A> {
@@ -473,8 +565,15 @@ where
 #[derive(Clone, Debug)]
 pub enum ImmutableImageCreationError {
     ImageCreationError(ImageCreationError),
+    BufferCreationError(BufferCreationError),
     DeviceMemoryAllocationError(DeviceMemoryAllocationError),
     CommandBufferBeginError(CommandBufferBeginError),
+
+    /// The source buffer is too small to hold the data for every mip level of the image.
+    SourceTooSmall {
+        source_size: DeviceSize,
+        required_size: DeviceSize,
+    },
 }
 
 impl error::Error for ImmutableImageCreationError {
@@ -482,8 +581,10 @@ impl error::Error for ImmutableImageCreationError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
             Self::ImageCreationError(err) => Some(err),
+            Self::BufferCreationError(err) => Some(err),
             Self::DeviceMemoryAllocationError(err) => Some(err),
             Self::CommandBufferBeginError(err) => Some(err),
+            _ => None,
         }
     }
 }
@@ -493,8 +594,17 @@ impl fmt::Display for ImmutableImageCreationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::ImageCreationError(err) => err.fmt(f),
+            Self::BufferCreationError(err) => err.fmt(f),
             Self::DeviceMemoryAllocationError(err) => err.fmt(f),
             Self::CommandBufferBeginError(err) => err.fmt(f),
+            Self::SourceTooSmall {
+                source_size,
+                required_size,
+            } => write!(
+                f,
+                "the size of the source buffer ({}) is less than the size required for the image data ({})",
+                source_size, required_size,
+            ),
         }
     }
 }
@@ -506,6 +616,13 @@ impl From<ImageCreationError> for ImmutableImageCreationError {
     }
 }
 
+impl From<BufferCreationError> for ImmutableImageCreationError {
+    #[inline]
+    fn from(err: BufferCreationError) -> Self {
+        Self::BufferCreationError(err)
+    }
+}
+
 impl From<DeviceMemoryAllocationError> for ImmutableImageCreationError {
     #[inline]
     fn from(err: DeviceMemoryAllocationError) -> Self {
@@ -76,6 +76,16 @@ pub enum ImageLayout {
     /// acquired from the swapchain, and must be transitioned back into this layout before
     /// presenting them.
     PresentSrc = ash::vk::ImageLayout::PRESENT_SRC_KHR.as_raw(),
+
+    /// For an image that is used as a color or depth/stencil attachment, and at the same time
+    /// read in a shader as an input attachment or sampled image, in the same subpass. Images that
+    /// are transitioned into this layout must have the `attachment_feedback_loop` usage enabled.
+    ///
+    /// The
+    /// [`attachment_feedback_loop_layout`](crate::device::Features::attachment_feedback_loop_layout)
+    /// feature must be enabled on the device.
+    AttachmentFeedbackLoopOptimal =
+        ash::vk::ImageLayout::ATTACHMENT_FEEDBACK_LOOP_OPTIMAL_EXT.as_raw(),
 }
 
 impl From<ImageLayout> for ash::vk::ImageLayout {
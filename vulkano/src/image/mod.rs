@@ -48,11 +48,14 @@
 
 pub use self::aspect::ImageAspect;
 pub use self::aspect::ImageAspects;
-pub use self::attachment::AttachmentImage;
+pub use self::attachment::{AttachmentImage, AttachmentImageCreateInfo};
+pub use self::compression::{
+    ImageCompression, ImageCompressionFixedRates, ImageCompressionProperties,
+};
 pub use self::immutable::ImmutableImage;
 pub use self::layout::ImageDescriptorLayouts;
 pub use self::layout::ImageLayout;
-pub use self::storage::StorageImage;
+pub use self::storage::{StorageImage, StorageImageCreateInfo};
 pub use self::swapchain::SwapchainImage;
 pub use self::sys::ImageCreationError;
 pub use self::traits::ImageAccess;
@@ -69,6 +72,7 @@ use std::ops::Range;
 
 mod aspect;
 pub mod attachment; // TODO: make private
+mod compression;
 pub mod immutable; // TODO: make private
 mod layout;
 mod storage;
@@ -277,6 +281,32 @@ pub struct ImageCreateFlags {
     ///
     /// Requires `mutable_format`.
     pub block_texel_view_compatible: bool,
+    /// The image can be bound to memory that is also bound to other images with identical
+    /// creation parameters, and those images will interpret the contents of the memory
+    /// consistently.
+    ///
+    /// Requires API version 1.1 or the
+    /// [`khr_bind_memory2`](crate::device::DeviceExtensions::khr_bind_memory2) extension.
+    pub alias: bool,
+    /// For 3D images, allows an image view of type `Dim2d` that is created from the image to be
+    /// used in a storage image, sampled image or combined image sampler descriptor.
+    ///
+    /// Requires the
+    /// [`ext_image_2d_view_of_3d`](crate::device::DeviceExtensions::ext_image_2d_view_of_3d)
+    /// extension.
+    pub view_2d_compatible: bool,
+    /// The image is a protected image, whose contents can only be accessed by protected queue
+    /// operations.
+    ///
+    /// Requires the [`protected_memory`](crate::device::Features::protected_memory) feature to be
+    /// enabled, and can not be combined with the sparse flags.
+    pub protected: bool,
+    /// The planes of the image can be bound to memory separately.
+    ///
+    /// Requires API version 1.1 or the
+    /// [`khr_sampler_ycbcr_conversion`](crate::device::DeviceExtensions::khr_sampler_ycbcr_conversion)
+    /// extension.
+    pub disjoint: bool,
 }
 
 impl ImageCreateFlags {
@@ -295,6 +325,10 @@ impl From<ImageCreateFlags> for ash::vk::ImageCreateFlags {
             cube_compatible,
             array_2d_compatible,
             block_texel_view_compatible,
+            alias,
+            view_2d_compatible,
+            protected,
+            disjoint,
         } = flags;
 
         let mut vk_flags = Self::default();
@@ -319,6 +353,18 @@ impl From<ImageCreateFlags> for ash::vk::ImageCreateFlags {
         if block_texel_view_compatible {
             vk_flags |= ash::vk::ImageCreateFlags::BLOCK_TEXEL_VIEW_COMPATIBLE
         };
+        if alias {
+            vk_flags |= ash::vk::ImageCreateFlags::ALIAS
+        };
+        if view_2d_compatible {
+            vk_flags |= ash::vk::ImageCreateFlags::TYPE_2D_VIEW_COMPATIBLE_EXT
+        };
+        if protected {
+            vk_flags |= ash::vk::ImageCreateFlags::PROTECTED
+        };
+        if disjoint {
+            vk_flags |= ash::vk::ImageCreateFlags::DISJOINT
+        };
         vk_flags
     }
 }
@@ -341,6 +387,12 @@ impl From<ImageType> for ash::vk::ImageType {
 pub enum ImageTiling {
     Optimal = ash::vk::ImageTiling::OPTIMAL.as_raw(),
     Linear = ash::vk::ImageTiling::LINEAR.as_raw(),
+    /// The layout of the image is determined by a Linux DRM format modifier.
+    ///
+    /// Requires the
+    /// [`ext_image_drm_format_modifier`](crate::device::DeviceExtensions::ext_image_drm_format_modifier)
+    /// extension.
+    DrmFormatModifier = ash::vk::ImageTiling::DRM_FORMAT_MODIFIER_EXT.as_raw(),
 }
 
 impl From<ImageTiling> for ash::vk::ImageTiling {
@@ -8,8 +8,9 @@
 // according to those terms.
 
 use super::{
-    sys::UnsafeImage, traits::ImageContent, ImageAccess, ImageCreateFlags, ImageCreationError,
-    ImageDescriptorLayouts, ImageDimensions, ImageInner, ImageLayout, ImageUsage,
+    sys::UnsafeImage, traits::ImageContent, ImageAccess, ImageCompression,
+    ImageCompressionFixedRates, ImageCreateFlags, ImageCreationError, ImageDescriptorLayouts,
+    ImageDimensions, ImageInner, ImageLayout, ImageUsage, SampleCount,
 };
 use crate::{
     device::{physical::QueueFamily, Device, DeviceOwned},
@@ -18,7 +19,8 @@ use crate::{
     memory::{
         pool::{
             alloc_dedicated_with_exportable_fd, AllocFromRequirementsFilter, AllocLayout,
-            MappingRequirement, MemoryPoolAlloc, PotentialDedicatedAllocation, StdMemoryPool,
+            MappingRequirement, MemoryPoolAlloc, MemoryTypePolicy, PotentialDedicatedAllocation,
+            StdMemoryPool,
         },
         DedicatedAllocation, DeviceMemoryExportError, ExternalMemoryHandleType,
         ExternalMemoryHandleTypes, MemoryPool,
@@ -84,6 +86,7 @@ impl StorageImage {
             depth_stencil_attachment: is_depth,
             input_attachment: true,
             transient_attachment: false,
+            attachment_feedback_loop: false,
         };
         let flags = ImageCreateFlags::none();
 
@@ -91,6 +94,8 @@ impl StorageImage {
     }
 
     /// Same as `new`, but allows specifying the usage.
+    ///
+    /// > **Note**: This function is just a convenient shortcut for `with_create_info`.
     pub fn with_usage<'a, I>(
         device: Arc<Device>,
         dimensions: ImageDimensions,
@@ -107,40 +112,108 @@ impl StorageImage {
             .map(|f| f.id())
             .collect::<SmallVec<[u32; 4]>>();
 
+        StorageImage::with_create_info(
+            device,
+            StorageImageCreateInfo {
+                usage,
+                flags,
+                sharing: if queue_families.len() >= 2 {
+                    Sharing::Concurrent(queue_families)
+                } else {
+                    Sharing::Exclusive
+                },
+                ..StorageImageCreateInfo::dimensions_format(dimensions, format)
+            },
+        )
+    }
+
+    /// Creates a new image from the parameters in `create_info`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.format` is `None`.
+    /// - Panics if any of the sparse flags are set in `create_info.flags`.
+    pub fn with_create_info(
+        device: Arc<Device>,
+        create_info: StorageImageCreateInfo,
+    ) -> Result<Arc<StorageImage>, ImageCreationError> {
+        let StorageImageCreateInfo {
+            dimensions,
+            format,
+            mip_levels,
+            samples,
+            usage,
+            flags,
+            sharing,
+            view_formats,
+            compression,
+            compression_fixed_rates,
+            memory_type_policy,
+            _ne: _,
+        } = create_info;
+
+        let format = format.expect("no format was provided");
+
+        assert!(
+            !(flags.sparse_binding || flags.sparse_residency || flags.sparse_aliased),
+            "storage images can not be sparse"
+        );
+
+        let queue_families = match &sharing {
+            Sharing::Exclusive => SmallVec::new(),
+            Sharing::Concurrent(ids) => ids.clone(),
+        };
+
         let image = UnsafeImage::new(
             device.clone(),
             UnsafeImageCreateInfo {
                 dimensions,
                 format: Some(format),
+                mip_levels,
+                samples,
                 usage,
-                sharing: if queue_families.len() >= 2 {
-                    Sharing::Concurrent(queue_families.iter().cloned().collect())
-                } else {
-                    Sharing::Exclusive
-                },
+                sharing,
                 mutable_format: flags.mutable_format,
                 cube_compatible: flags.cube_compatible,
                 array_2d_compatible: flags.array_2d_compatible,
                 block_texel_view_compatible: flags.block_texel_view_compatible,
+                alias: flags.alias,
+                view_2d_compatible: flags.view_2d_compatible,
+                protected: flags.protected,
+                view_formats,
+                compression,
+                compression_fixed_rates,
                 ..Default::default()
             },
         )?;
 
         let mem_reqs = image.memory_requirements();
-        let memory = MemoryPool::alloc_from_requirements(
-            &Device::standard_pool(&device),
-            &mem_reqs,
-            AllocLayout::Optimal,
-            MappingRequirement::DoNotMap,
-            Some(DedicatedAllocation::Image(&image)),
-            |t| {
-                if t.is_device_local() {
-                    AllocFromRequirementsFilter::Preferred
-                } else {
-                    AllocFromRequirementsFilter::Allowed
-                }
-            },
-        )?;
+        let memory = match memory_type_policy {
+            Some(policy) => MemoryPool::alloc_with_policy(
+                &Device::standard_pool(&device),
+                &mem_reqs,
+                AllocLayout::Optimal,
+                MappingRequirement::DoNotMap,
+                Some(DedicatedAllocation::Image(&image)),
+                &policy,
+            )?,
+            None => MemoryPool::alloc_from_requirements(
+                &Device::standard_pool(&device),
+                &mem_reqs,
+                AllocLayout::Optimal,
+                MappingRequirement::DoNotMap,
+                Some(DedicatedAllocation::Image(&image)),
+                |t| {
+                    if t.is_protected() != flags.protected {
+                        AllocFromRequirementsFilter::Forbidden
+                    } else if t.is_device_local() {
+                        AllocFromRequirementsFilter::Preferred
+                    } else {
+                        AllocFromRequirementsFilter::Allowed
+                    }
+                },
+            )?,
+        };
         debug_assert!((memory.offset() % mem_reqs.alignment) == 0);
         unsafe {
             image.bind_memory(memory.memory(), memory.offset())?;
@@ -237,6 +310,144 @@ impl StorageImage {
     }
 }
 
+/// Parameters to create a new `StorageImage`.
+#[derive(Clone, Debug)]
+pub struct StorageImageCreateInfo {
+    /// The type, extent and number of array layers to create the image with.
+    ///
+    /// The default value is `ImageDimensions::Dim2d { width: 0, height: 0, array_layers: 1 }`,
+    /// which must be overridden.
+    pub dimensions: ImageDimensions,
+
+    /// The format used to store the image data.
+    ///
+    /// The default value is `None`, which must be overridden.
+    pub format: Option<Format>,
+
+    /// The number of mip levels to create the image with.
+    ///
+    /// The default value is `1`.
+    pub mip_levels: u32,
+
+    /// The number of samples per texel that the image should use.
+    ///
+    /// The default value is [`SampleCount::Sample1`].
+    pub samples: SampleCount,
+
+    /// How the image is going to be used.
+    ///
+    /// The default value has the `transfer_src`, `transfer_dst`, `sampled` and `storage` usages
+    /// set.
+    pub usage: ImageUsage,
+
+    /// Additional properties of the image.
+    ///
+    /// The sparse flags are not supported and must not be set.
+    ///
+    /// The default value is [`ImageCreateFlags::none()`].
+    pub flags: ImageCreateFlags,
+
+    /// Whether the image can be shared across multiple queues, or is limited to a single queue.
+    ///
+    /// The default value is [`Sharing::Exclusive`].
+    pub sharing: Sharing<SmallVec<[u32; 4]>>,
+
+    /// The formats that image views created from the image will have. See
+    /// [`UnsafeImageCreateInfo::view_formats`] for details.
+    ///
+    /// The default value is empty.
+    pub view_formats: Vec<Format>,
+
+    /// The compression that the implementation should apply to the image. See
+    /// [`UnsafeImageCreateInfo::compression`] for details.
+    ///
+    /// The default value is `None`.
+    pub compression: Option<ImageCompression>,
+
+    /// The fixed-rate compression bit rates to choose from for each plane of the image. See
+    /// [`UnsafeImageCreateInfo::compression_fixed_rates`] for details.
+    ///
+    /// The default value is empty.
+    pub compression_fixed_rates: Vec<ImageCompressionFixedRates>,
+
+    /// How the memory type of the image's memory is chosen.
+    ///
+    /// If `None`, a device-local memory type is preferred, and a protected memory type is used
+    /// if and only if `flags.protected` is set. If `Some`, the policy must allow protected memory
+    /// types when `flags.protected` is set.
+    ///
+    /// The default value is `None`.
+    pub memory_type_policy: Option<MemoryTypePolicy>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl StorageImageCreateInfo {
+    /// Returns a `StorageImageCreateInfo` with the specified `dimensions` and `format`, and all
+    /// other fields set to their default values.
+    #[inline]
+    pub fn dimensions_format(dimensions: ImageDimensions, format: Format) -> Self {
+        Self {
+            dimensions,
+            format: Some(format),
+            ..Default::default()
+        }
+    }
+
+    /// Returns a `StorageImageCreateInfo` for a cube-compatible image with `cube_count` cubes,
+    /// each face being `size` by `size` texels, and all other fields set to their default values.
+    ///
+    /// The image will have `6 * cube_count` array layers. Viewing the image as a cube array
+    /// requires the [`image_cube_array`](crate::device::Features::image_cube_array) feature to be
+    /// enabled.
+    #[inline]
+    pub fn cube(size: u32, cube_count: u32, format: Format) -> Self {
+        Self {
+            dimensions: ImageDimensions::Dim2d {
+                width: size,
+                height: size,
+                array_layers: cube_count * 6,
+            },
+            format: Some(format),
+            flags: ImageCreateFlags {
+                cube_compatible: true,
+                ..ImageCreateFlags::none()
+            },
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for StorageImageCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            dimensions: ImageDimensions::Dim2d {
+                width: 0,
+                height: 0,
+                array_layers: 1,
+            },
+            format: None,
+            mip_levels: 1,
+            samples: SampleCount::Sample1,
+            usage: ImageUsage {
+                transfer_src: true,
+                transfer_dst: true,
+                sampled: true,
+                storage: true,
+                ..ImageUsage::none()
+            },
+            flags: ImageCreateFlags::none(),
+            sharing: Sharing::Exclusive,
+            view_formats: Vec::new(),
+            compression: None,
+            compression_fixed_rates: Vec::new(),
+            memory_type_policy: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
 unsafe impl<A> DeviceOwned for StorageImage<A>
 where
     A: MemoryPool,
@@ -257,7 +468,7 @@ where
             first_layer: 0,
             num_layers: self.dimensions.array_layers(),
             first_mipmap_level: 0,
-            num_mipmap_levels: 1,
+            num_mipmap_levels: self.image.mip_levels(),
         }
     }
 
@@ -316,9 +527,15 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::StorageImage;
+    use super::{StorageImage, StorageImageCreateInfo};
     use crate::format::Format;
-    use crate::image::ImageDimensions;
+    use crate::image::{
+        view::{
+            ImageView, ImageViewAbstract, ImageViewCreateInfo, ImageViewCreationError,
+            ImageViewType,
+        },
+        ImageAccess, ImageDimensions,
+    };
 
     #[test]
     fn create() {
@@ -335,4 +552,58 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn create_with_mip_levels() {
+        let (device, _) = gfx_dev_and_queue!();
+        let img = StorageImage::with_create_info(
+            device,
+            StorageImageCreateInfo {
+                mip_levels: 4,
+                ..StorageImageCreateInfo::dimensions_format(
+                    ImageDimensions::Dim2d {
+                        width: 32,
+                        height: 32,
+                        array_layers: 1,
+                    },
+                    Format::R8G8B8A8_UNORM,
+                )
+            },
+        )
+        .unwrap();
+
+        assert_eq!(img.inner().num_mipmap_levels, 4);
+    }
+
+    #[test]
+    fn create_cube_array() {
+        let (device, _) = gfx_dev_and_queue!();
+        let img = StorageImage::with_create_info(
+            device.clone(),
+            StorageImageCreateInfo::cube(32, 2, Format::R8G8B8A8_UNORM),
+        )
+        .unwrap();
+        assert_eq!(img.dimensions().array_layers(), 12);
+
+        let cube = ImageView::new(img.clone(), ImageViewCreateInfo::cube(&img, 1)).unwrap();
+        assert_eq!(cube.view_type(), ImageViewType::Cube);
+        assert_eq!(cube.subresource_range().array_layers, 6..12);
+
+        let faces = ImageView::new_cube_faces(img.clone(), 0).unwrap();
+        assert_eq!(faces.len(), 12);
+        assert_eq!(faces[7].subresource_range().array_layers, 7..8);
+        assert_eq!(faces[7].subresource_range().mip_levels, 0..1);
+
+        match ImageView::new_cube_array(img) {
+            Ok(view) => {
+                assert!(device.enabled_features().image_cube_array);
+                assert_eq!(view.view_type(), ImageViewType::CubeArray);
+            }
+            Err(ImageViewCreationError::FeatureNotEnabled {
+                feature: "image_cube_array",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
 }
@@ -14,14 +14,18 @@
 //! that you create must wrap around the types in this module.
 
 use super::{
-    ImageAspect, ImageAspects, ImageCreateFlags, ImageDimensions, ImageLayout,
+    ImageAspect, ImageAspects, ImageCompression, ImageCompressionFixedRates,
+    ImageCompressionProperties, ImageCreateFlags, ImageDimensions, ImageLayout,
     ImageSubresourceLayers, ImageSubresourceRange, ImageTiling, ImageUsage, SampleCount,
-    SampleCounts,
 };
 use crate::{
     buffer::cpu_access::{ReadLockError, WriteLockError},
     check_errors,
-    device::{Device, DeviceOwned},
+    device::{
+        object_tracker::{TrackedObject, TrackedObjectType},
+        Device, DeviceOwned,
+    },
+    extension_chain::ExtensionChain,
     format::{ChromaSampling, Format, FormatFeatures, NumericType},
     image::{ImageFormatInfo, ImageFormatProperties, ImageType},
     memory::{
@@ -29,7 +33,7 @@ use crate::{
         ExternalMemoryHandleTypes, MemoryRequirements,
     },
     sync::{AccessError, CurrentAccess, Sharing},
-    DeviceSize, Error, OomError, Version, VulkanObject,
+    DeviceSize, Error, OomError, ValidationError, Version, VulkanObject,
 };
 use ash::vk::Handle;
 use parking_lot::{Mutex, MutexGuard};
@@ -54,6 +58,9 @@ use std::{
 /// - You must manually bind memory to the image with `bind_memory`. The memory must respect the
 ///   requirements returned by `memory_requirements`.
 /// - The memory that you bind to the image must be manually kept alive.
+/// - If the memory is also bound to other buffers or images, accesses to the aliased resources
+///   must be manually synchronized, as vulkano tracks accesses per resource and not per memory
+///   range.
 /// - The queue family ownership must be manually enforced.
 /// - The usage must be manually enforced.
 /// - The image layout must be manually enforced and transitioned.
@@ -74,6 +81,12 @@ pub struct UnsafeImage {
     cube_compatible: bool,
     array_2d_compatible: bool,
     block_texel_view_compatible: bool,
+    alias: bool,
+    view_2d_compatible: bool,
+    protected: bool,
+    disjoint: bool,
+    view_formats: Vec<Format>,
+    drm_format_modifier: Option<u64>,
 
     aspect_list: SmallVec<[ImageAspect; 4]>,
     aspect_size: DeviceSize,
@@ -81,6 +94,7 @@ pub struct UnsafeImage {
     needs_destruction: bool, // `vkDestroyImage` is called only if true.
     range_size: DeviceSize,
     state: Mutex<ImageState>,
+    tracked: TrackedObject,
 }
 
 impl UnsafeImage {
@@ -104,7 +118,7 @@ impl UnsafeImage {
         device: Arc<Device>,
         mut create_info: UnsafeImageCreateInfo,
     ) -> Result<Arc<UnsafeImage>, ImageCreationError> {
-        let format_features = Self::validate(&device, &mut create_info)?;
+        let mut format_features = Self::validate(&device, &mut create_info)?;
         let handle = unsafe { Self::create(&device, &create_info)? };
 
         let UnsafeImageCreateInfo {
@@ -113,6 +127,8 @@ impl UnsafeImage {
             mip_levels,
             samples,
             tiling,
+            drm_format_modifiers: _,
+            drm_format_modifier_plane_layouts: _,
             usage,
             sharing,
             initial_layout,
@@ -121,15 +137,60 @@ impl UnsafeImage {
             cube_compatible,
             array_2d_compatible,
             block_texel_view_compatible,
+            alias,
+            view_2d_compatible,
+            protected,
+            disjoint,
+            view_formats,
+            compression: _,
+            compression_fixed_rates: _,
+            extensions: _,
             _ne: _,
         } = create_info;
 
+        // The implementation picks one of the provided modifiers, and the format features of the
+        // image are those of that modifier.
+        let drm_format_modifier = if tiling == ImageTiling::DrmFormatModifier {
+            let drm_format_modifier = unsafe {
+                let fns = device.fns();
+                let mut properties = ash::vk::ImageDrmFormatModifierPropertiesEXT::default();
+                let result = check_errors((fns
+                    .ext_image_drm_format_modifier
+                    .get_image_drm_format_modifier_properties_ext)(
+                    device.internal_object(),
+                    handle,
+                    &mut properties,
+                ));
+
+                if let Err(err) = result {
+                    (fns.v1_0.destroy_image)(device.internal_object(), handle, ptr::null());
+                    return Err(err.into());
+                }
+
+                properties.drm_format_modifier
+            };
+
+            if let Some(properties) = device
+                .physical_device()
+                .drm_format_modifier_properties(format.unwrap())
+                .into_iter()
+                .find(|properties| properties.drm_format_modifier == drm_format_modifier)
+            {
+                format_features = properties.drm_format_modifier_tiling_features;
+            }
+
+            Some(drm_format_modifier)
+        } else {
+            None
+        };
+
         let aspects = format.unwrap().aspects();
         let aspect_list: SmallVec<[ImageAspect; 4]> = aspects.iter().collect();
         let mip_level_size = dimensions.array_layers() as DeviceSize;
         let aspect_size = mip_level_size * mip_levels as DeviceSize;
         let range_size = aspect_list.len() as DeviceSize * aspect_size;
 
+        let tracked = device.track_object(TrackedObjectType::Image);
         let image = UnsafeImage {
             device,
             handle,
@@ -146,6 +207,12 @@ impl UnsafeImage {
             cube_compatible,
             array_2d_compatible,
             block_texel_view_compatible,
+            alias,
+            view_2d_compatible,
+            protected,
+            disjoint,
+            view_formats,
+            drm_format_modifier,
 
             aspect_list,
             aspect_size,
@@ -153,6 +220,7 @@ impl UnsafeImage {
             needs_destruction: true,
             range_size,
             state: Mutex::new(ImageState::new(range_size, initial_layout)),
+            tracked,
         };
 
         Ok(Arc::new(image))
@@ -168,6 +236,8 @@ impl UnsafeImage {
             mip_levels,
             samples,
             tiling,
+            ref drm_format_modifiers,
+            ref drm_format_modifier_plane_layouts,
             usage,
             ref mut sharing,
             initial_layout,
@@ -176,6 +246,14 @@ impl UnsafeImage {
             cube_compatible,
             array_2d_compatible,
             block_texel_view_compatible,
+            alias,
+            view_2d_compatible,
+            protected,
+            disjoint,
+            ref view_formats,
+            compression,
+            ref compression_fixed_rates,
+            extensions: _,
             _ne: _,
         } = create_info;
 
@@ -214,18 +292,140 @@ impl UnsafeImage {
         // VUID-VkImageCreateInfo-flags-01573
         assert!(!(block_texel_view_compatible && !mutable_format));
 
+        if usage.attachment_feedback_loop
+            && !device
+                .enabled_extensions()
+                .ext_attachment_feedback_loop_layout
+        {
+            return Err(ImageCreationError::ExtensionNotEnabled {
+                extension: "ext_attachment_feedback_loop_layout",
+                reason: "usage contained `attachment_feedback_loop`",
+            });
+        }
+
+        if tiling == ImageTiling::DrmFormatModifier {
+            if !device.enabled_extensions().ext_image_drm_format_modifier {
+                return Err(ImageCreationError::ExtensionNotEnabled {
+                    extension: "ext_image_drm_format_modifier",
+                    reason: "tiling was `ImageTiling::DrmFormatModifier`",
+                });
+            }
+
+            // VUID-VkImageCreateInfo-tiling-02261
+            assert!(!drm_format_modifiers.is_empty());
+
+            if mutable_format && view_formats.is_empty() {
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "drm_format_modifier_mutable_format_without_view_formats",
+                    context: "UnsafeImageCreateInfo.view_formats",
+                    problem: "the tiling was `DrmFormatModifier` and the mutable_format flag was \
+                        enabled, but view_formats was empty"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-tiling-02353"],
+                    ..Default::default()
+                }));
+            }
+        } else {
+            // VUID-VkImageCreateInfo-pNext-02262
+            assert!(drm_format_modifiers.is_empty());
+        }
+
         // Get format features
         let format_features = {
             let format_properties = physical_device.format_properties(format);
             match tiling {
                 ImageTiling::Linear => format_properties.linear_tiling_features,
                 ImageTiling::Optimal => format_properties.optimal_tiling_features,
+                ImageTiling::DrmFormatModifier => {
+                    let modifier_properties =
+                        physical_device.drm_format_modifier_properties(format);
+                    let mut format_features: Option<FormatFeatures> = None;
+
+                    for &drm_format_modifier in drm_format_modifiers {
+                        let properties = modifier_properties
+                            .iter()
+                            .find(|properties| {
+                                properties.drm_format_modifier == drm_format_modifier
+                            })
+                            .ok_or_else(|| {
+                                ImageCreationError::ValidationError(ValidationError {
+                                    code: "drm_format_modifier_not_supported",
+                                    context: "UnsafeImageCreateInfo.drm_format_modifiers",
+                                    problem: format!(
+                                        "the DRM format modifier {:#x} was not supported for the \
+                                        format",
+                                        drm_format_modifier,
+                                    )
+                                    .into(),
+                                    vuids: &[],
+                                    ..Default::default()
+                                })
+                            })?;
+
+                        // The image can end up with any of the modifiers, so only the features
+                        // that all of them have can be relied on.
+                        format_features = Some(match format_features {
+                            Some(format_features) => {
+                                &format_features & &properties.drm_format_modifier_tiling_features
+                            }
+                            None => properties.drm_format_modifier_tiling_features,
+                        });
+                    }
+
+                    if !drm_format_modifier_plane_layouts.is_empty() {
+                        // VUID-VkImageDrmFormatModifierExplicitCreateInfoEXT-drmFormatModifierPlaneCount-02265
+                        assert!(drm_format_modifiers.len() == 1);
+                        let required = modifier_properties
+                            .iter()
+                            .find(|properties| {
+                                properties.drm_format_modifier == drm_format_modifiers[0]
+                            })
+                            .unwrap()
+                            .drm_format_modifier_plane_count;
+
+                        if drm_format_modifier_plane_layouts.len() as u32 != required {
+                            return Err(ImageCreationError::ValidationError(ValidationError {
+                                code: "drm_format_modifier_plane_layouts_count_mismatch",
+                                context: "UnsafeImageCreateInfo.drm_format_modifier_plane_layouts",
+                                problem: format!(
+                                    "the number of elements in drm_format_modifier_plane_layouts \
+                                    ({}) did not match the number of memory planes of the DRM \
+                                    format modifier ({})",
+                                    drm_format_modifier_plane_layouts.len() as u32,
+                                    required,
+                                )
+                                .into(),
+                                vuids: &[],
+                                ..Default::default()
+                            }));
+                        }
+                    }
+
+                    format_features.unwrap()
+                }
             }
         };
 
+        for layout in drm_format_modifier_plane_layouts {
+            // VUID-VkImageDrmFormatModifierExplicitCreateInfoEXT-size-02267
+            assert!(layout.size == 0);
+
+            // VUID-VkImageDrmFormatModifierExplicitCreateInfoEXT-arrayPitch-02268
+            assert!(dimensions.array_layers() != 1 || layout.array_pitch == 0);
+
+            // VUID-VkImageDrmFormatModifierExplicitCreateInfoEXT-depthPitch-02269
+            assert!(matches!(dimensions, ImageDimensions::Dim3d { .. }) || layout.depth_pitch == 0);
+        }
+
         // Format isn't supported at all?
         if format_features == FormatFeatures::default() {
-            return Err(ImageCreationError::FormatNotSupported);
+            return Err(ImageCreationError::ValidationError(ValidationError {
+                code: "format_not_supported",
+                context: "UnsafeImageCreateInfo.format",
+                problem: "the given format was not supported by the device".into(),
+                vuids: &[],
+                ..Default::default()
+            }));
         }
 
         // Decode the dimensions
@@ -266,48 +466,96 @@ impl UnsafeImage {
         let max_mip_levels = dimensions.max_mip_levels();
         debug_assert!(max_mip_levels >= 1);
 
-        // VUID-VkImageCreateInfo-mipLevels-00958
         if mip_levels > max_mip_levels {
-            return Err(ImageCreationError::MaxMipLevelsExceeded {
-                mip_levels,
-                max: max_mip_levels,
-            });
+            return Err(ImageCreationError::ValidationError(ValidationError {
+                code: "max_mip_levels_exceeded",
+                context: "UnsafeImageCreateInfo.mip_levels",
+                problem: format!(
+                    "the number of mip levels ({}) exceeds the maximum for this image \
+                    configuration ({})",
+                    mip_levels, max_mip_levels,
+                )
+                .into(),
+                vuids: &["VUID-VkImageCreateInfo-mipLevels-00958"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkImageCreateInfo-samples-02257
         if samples != SampleCount::Sample1 {
             if image_type != ImageType::Dim2d {
-                return Err(ImageCreationError::MultisampleNot2d);
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "multisample_not2d",
+                    context: "UnsafeImageCreateInfo.samples",
+                    problem: "multisampling was enabled, but the image type was not 2D".into(),
+                    vuids: &["VUID-VkImageCreateInfo-samples-02257"],
+                    ..Default::default()
+                }));
             }
 
             if cube_compatible {
-                return Err(ImageCreationError::MultisampleCubeCompatible);
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "multisample_cube_compatible",
+                    context: "UnsafeImageCreateInfo.samples",
+                    problem: "multisampling was enabled, and the `cube_compatible` flag was set"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-samples-02257"],
+                    ..Default::default()
+                }));
             }
 
             if mip_levels != 1 {
-                return Err(ImageCreationError::MultisampleMultipleMipLevels);
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "multisample_multiple_mip_levels",
+                    context: "UnsafeImageCreateInfo.samples",
+                    problem: "multisampling was enabled, and multiple mip levels were specified"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-samples-02257"],
+                    ..Default::default()
+                }));
             }
 
             if tiling == ImageTiling::Linear {
-                return Err(ImageCreationError::MultisampleLinearTiling);
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "multisample_linear_tiling",
+                    context: "UnsafeImageCreateInfo.samples",
+                    problem: "multisampling was enabled, and tiling was `Linear`".into(),
+                    vuids: &["VUID-VkImageCreateInfo-samples-02257"],
+                    ..Default::default()
+                }));
             }
         }
 
         // Check limits for YCbCr formats
         if let Some(chroma_sampling) = format.ycbcr_chroma_sampling() {
-            // VUID-VkImageCreateInfo-format-06410
             if mip_levels != 1 {
-                return Err(ImageCreationError::YcbcrFormatMultipleMipLevels);
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "ycbcr_format_multiple_mip_levels",
+                    context: "UnsafeImageCreateInfo.format",
+                    problem: "a YCbCr format was given, and multiple mip levels were specified"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-format-06410"],
+                    ..Default::default()
+                }));
             }
 
-            // VUID-VkImageCreateInfo-format-06411
             if samples != SampleCount::Sample1 {
-                return Err(ImageCreationError::YcbcrFormatMultisampling);
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "ycbcr_format_multisampling",
+                    context: "UnsafeImageCreateInfo.format",
+                    problem: "a YCbCr format was given, and multisampling was enabled".into(),
+                    vuids: &["VUID-VkImageCreateInfo-format-06411"],
+                    ..Default::default()
+                }));
             }
 
-            // VUID-VkImageCreateInfo-format-06412
             if image_type != ImageType::Dim2d {
-                return Err(ImageCreationError::YcbcrFormatNot2d);
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "ycbcr_format_not2d",
+                    context: "UnsafeImageCreateInfo.format",
+                    problem: "a YCbCr format was given, but the image type was not 2D".into(),
+                    vuids: &["VUID-VkImageCreateInfo-format-06412"],
+                    ..Default::default()
+                }));
             }
 
             // VUID-VkImageCreateInfo-format-06413
@@ -321,16 +569,34 @@ impl UnsafeImage {
             match chroma_sampling {
                 ChromaSampling::Mode444 => (),
                 ChromaSampling::Mode422 => {
-                    // VUID-VkImageCreateInfo-format-04712
                     if !(extent[0] % 2 == 0) {
-                        return Err(ImageCreationError::YcbcrFormatInvalidDimensions);
+                        return Err(ImageCreationError::ValidationError(ValidationError {
+                            code: "ycbcr_format_invalid_dimensions",
+                            context: "UnsafeImageCreateInfo.format",
+                            problem: "a YCbCr format was given, but the specified width and/or \
+                                height was not a multiple of 2 as required by the format's chroma \
+                                subsampling"
+                                .into(),
+                            vuids: &["VUID-VkImageCreateInfo-format-04712"],
+                            ..Default::default()
+                        }));
                     }
                 }
                 ChromaSampling::Mode420 => {
-                    // VUID-VkImageCreateInfo-format-04712
-                    // VUID-VkImageCreateInfo-format-04713
                     if !(extent[0] % 2 == 0 && extent[1] % 2 == 0) {
-                        return Err(ImageCreationError::YcbcrFormatInvalidDimensions);
+                        return Err(ImageCreationError::ValidationError(ValidationError {
+                            code: "ycbcr_format_invalid_dimensions",
+                            context: "UnsafeImageCreateInfo.format",
+                            problem: "a YCbCr format was given, but the specified width and/or \
+                                height was not a multiple of 2 as required by the format's chroma \
+                                subsampling"
+                                .into(),
+                            vuids: &[
+                                "VUID-VkImageCreateInfo-format-04712",
+                                "VUID-VkImageCreateInfo-format-04713",
+                            ],
+                            ..Default::default()
+                        }));
                     }
                 }
             }
@@ -339,31 +605,54 @@ impl UnsafeImage {
         /* Check usage requirements */
 
         if usage.sampled && !format_features.sampled_image {
-            return Err(ImageCreationError::FormatUsageNotSupported { usage: "sampled" });
+            return Err(ImageCreationError::ValidationError(ValidationError {
+                code: "format_usage_not_supported",
+                context: "UnsafeImageCreateInfo.usage",
+                problem: "the format features of the image do not support the `sampled` usage"
+                    .into(),
+                vuids: &[],
+                ..Default::default()
+            }));
         }
 
         if usage.color_attachment && !format_features.color_attachment {
-            return Err(ImageCreationError::FormatUsageNotSupported {
-                usage: "color_attachment",
-            });
+            return Err(ImageCreationError::ValidationError(ValidationError {
+                code: "format_usage_not_supported",
+                context: "UnsafeImageCreateInfo.usage",
+                problem: "the format features of the image do not support the `color_attachment` \
+                    usage"
+                    .into(),
+                vuids: &[],
+                ..Default::default()
+            }));
         }
 
         if usage.depth_stencil_attachment && !format_features.depth_stencil_attachment {
-            return Err(ImageCreationError::FormatUsageNotSupported {
-                usage: "depth_stencil_attachment",
-            });
+            return Err(ImageCreationError::ValidationError(ValidationError {
+                code: "format_usage_not_supported",
+                context: "UnsafeImageCreateInfo.usage",
+                problem: "the format features of the image do not support the \
+                    `depth_stencil_attachment` usage"
+                    .into(),
+                vuids: &[],
+                ..Default::default()
+            }));
         }
 
         if usage.input_attachment
             && !(format_features.color_attachment || format_features.depth_stencil_attachment)
         {
-            return Err(ImageCreationError::FormatUsageNotSupported {
-                usage: "input_attachment",
-            });
+            return Err(ImageCreationError::ValidationError(ValidationError {
+                code: "format_usage_not_supported",
+                context: "UnsafeImageCreateInfo.usage",
+                problem: "the format features of the image do not support the `input_attachment` \
+                    usage"
+                    .into(),
+                vuids: &[],
+                ..Default::default()
+            }));
         }
 
-        // VUID-VkImageCreateInfo-usage-00964
-        // VUID-VkImageCreateInfo-usage-00965
         if (usage.color_attachment
             || usage.depth_stencil_attachment
             || usage.input_attachment
@@ -371,18 +660,38 @@ impl UnsafeImage {
             && (extent[0] > device_properties.max_framebuffer_width
                 || extent[1] > device_properties.max_framebuffer_height)
         {
-            return Err(ImageCreationError::MaxFramebufferDimensionsExceeded {
-                extent: [extent[0], extent[1]],
-                max: [
-                    device_properties.max_framebuffer_width,
-                    device_properties.max_framebuffer_height,
+            return Err(ImageCreationError::ValidationError(ValidationError {
+                code: "max_framebuffer_dimensions_exceeded",
+                context: "UnsafeImageCreateInfo.dimensions",
+                problem: format!(
+                    "the usage included one of the attachment types, and the width and height \
+                    ({:?}) exceeded the `max_framebuffer_width` or `max_framebuffer_height` \
+                    limits ({:?})",
+                    [extent[0], extent[1]],
+                    [
+                        device_properties.max_framebuffer_width,
+                        device_properties.max_framebuffer_height,
+                    ],
+                )
+                .into(),
+                vuids: &[
+                    "VUID-VkImageCreateInfo-usage-00964",
+                    "VUID-VkImageCreateInfo-usage-00965",
                 ],
-            });
+                ..Default::default()
+            }));
         }
 
         if usage.storage {
             if !format_features.storage_image {
-                return Err(ImageCreationError::FormatUsageNotSupported { usage: "storage" });
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "format_usage_not_supported",
+                    context: "UnsafeImageCreateInfo.usage",
+                    problem: "the format features of the image do not support the `storage` usage"
+                        .into(),
+                    vuids: &[],
+                    ..Default::default()
+                }));
             }
 
             // VUID-VkImageCreateInfo-usage-00968
@@ -399,48 +708,254 @@ impl UnsafeImage {
         // These flags only exist in later versions, ignore them otherwise
         if device.api_version() >= Version::V1_1 || device.enabled_extensions().khr_maintenance1 {
             if usage.transfer_src && !format_features.transfer_src {
-                return Err(ImageCreationError::FormatUsageNotSupported {
-                    usage: "transfer_src",
-                });
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "format_usage_not_supported",
+                    context: "UnsafeImageCreateInfo.usage",
+                    problem: "the format features of the image do not support the `transfer_src` \
+                        usage"
+                        .into(),
+                    vuids: &[],
+                    ..Default::default()
+                }));
             }
             if usage.transfer_dst && !format_features.transfer_dst {
-                return Err(ImageCreationError::FormatUsageNotSupported {
-                    usage: "transfer_dst",
-                });
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "format_usage_not_supported",
+                    context: "UnsafeImageCreateInfo.usage",
+                    problem: "the format features of the image do not support the `transfer_dst` \
+                        usage"
+                        .into(),
+                    vuids: &[],
+                    ..Default::default()
+                }));
             }
         }
 
         /* Check flags requirements */
 
         if cube_compatible {
-            // VUID-VkImageCreateInfo-flags-00949
             if image_type != ImageType::Dim2d {
-                return Err(ImageCreationError::CubeCompatibleNot2d);
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "cube_compatible_not2d",
+                    context: "UnsafeImageCreateInfo.cube_compatible",
+                    problem: "the cube_compatible flag was enabled, but the image type was not 2D"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-flags-00949"],
+                    ..Default::default()
+                }));
             }
 
-            // VUID-VkImageCreateInfo-imageType-00954
             if extent[0] != extent[1] {
-                return Err(ImageCreationError::CubeCompatibleNotSquare);
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "cube_compatible_not_square",
+                    context: "UnsafeImageCreateInfo.cube_compatible",
+                    problem: "the cube_compatible flag was enabled, but the image dimensions were \
+                        not square"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-imageType-00954"],
+                    ..Default::default()
+                }));
             }
 
-            // VUID-VkImageCreateInfo-imageType-00954
             if array_layers < 6 {
-                return Err(ImageCreationError::CubeCompatibleNotEnoughArrayLayers);
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "cube_compatible_not_enough_array_layers",
+                    context: "UnsafeImageCreateInfo.cube_compatible",
+                    problem:
+                        "the cube_compatible flag was enabled, but the number of array layers was \
+                        less than 6"
+                            .into(),
+                    vuids: &["VUID-VkImageCreateInfo-imageType-00954"],
+                    ..Default::default()
+                }));
             }
         }
 
         if array_2d_compatible {
-            // VUID-VkImageCreateInfo-flags-00950
             if image_type != ImageType::Dim3d {
-                return Err(ImageCreationError::Array2dCompatibleNot3d);
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "array2d_compatible_not3d",
+                    context: "UnsafeImageCreateInfo.array_2d_compatible",
+                    problem:
+                        "the array_2d_compatible flag was enabled, but the image type was not 3D"
+                            .into(),
+                    vuids: &["VUID-VkImageCreateInfo-flags-00950"],
+                    ..Default::default()
+                }));
             }
         }
 
         if block_texel_view_compatible {
-            // VUID-VkImageCreateInfo-flags-01572
             if format.compression().is_none() {
-                return Err(ImageCreationError::BlockTexelViewCompatibleNotCompressed);
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "block_texel_view_compatible_not_compressed",
+                    context: "UnsafeImageCreateInfo.block_texel_view_compatible",
+                    problem: "the block_texel_view_compatible flag was enabled, but the given \
+                        format was not compressed"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-flags-01572"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if alias {
+            if !(device.api_version() >= Version::V1_1
+                || device.enabled_extensions().khr_bind_memory2)
+            {
+                return Err(ImageCreationError::ExtensionNotEnabled {
+                    extension: "khr_bind_memory2",
+                    reason: "alias was set",
+                });
+            }
+        }
+
+        if view_2d_compatible {
+            if !device.enabled_extensions().ext_image_2d_view_of_3d {
+                return Err(ImageCreationError::ExtensionNotEnabled {
+                    extension: "ext_image_2d_view_of_3d",
+                    reason: "view_2d_compatible was set",
+                });
+            }
+
+            if image_type != ImageType::Dim3d {
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "view2d_compatible_not3d",
+                    context: "UnsafeImageCreateInfo.view_2d_compatible",
+                    problem: "the view_2d_compatible flag was enabled, but the image type was not \
+                        3D"
+                    .into(),
+                    vuids: &["VUID-VkImageCreateInfo-flags-06722"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if protected {
+            // VUID-VkImageCreateInfo-flags-01890
+            if !device.enabled_features().protected_memory {
+                return Err(ImageCreationError::FeatureNotEnabled {
+                    feature: "protected_memory",
+                    reason: "protected was set",
+                });
+            }
+        }
+
+        if disjoint {
+            if !(device.api_version() >= Version::V1_1
+                || device.enabled_extensions().khr_sampler_ycbcr_conversion)
+            {
+                return Err(ImageCreationError::ExtensionNotEnabled {
+                    extension: "khr_sampler_ycbcr_conversion",
+                    reason: "disjoint was set",
+                });
+            }
+
+            if format.planes().is_empty() && tiling != ImageTiling::DrmFormatModifier {
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "disjoint_not_multi_planar",
+                    context: "UnsafeImageCreateInfo.disjoint",
+                    problem: "the disjoint flag was enabled, but the format was not multi-planar"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-format-01577"],
+                    ..Default::default()
+                }));
+            }
+
+            if !format_features.disjoint {
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "disjoint_not_supported",
+                    context: "UnsafeImageCreateInfo.disjoint",
+                    problem: "the disjoint flag was enabled, but the format features of the image \
+                        did not include `disjoint`"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-imageCreateFormatFeatures-02260"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if !view_formats.is_empty() {
+            if !(device.api_version() >= Version::V1_2
+                || device.enabled_extensions().khr_image_format_list)
+            {
+                return Err(ImageCreationError::ExtensionNotEnabled {
+                    extension: "khr_image_format_list",
+                    reason: "view_formats was not empty",
+                });
+            }
+
+            if !mutable_format && (view_formats.len() != 1 || view_formats[0] != format) {
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "view_formats_without_mutable_format",
+                    context: "UnsafeImageCreateInfo.view_formats",
+                    problem: "the view_formats list contained a format other than the format of \
+                        the image, but the mutable_format flag was not enabled"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-flags-04738"],
+                    ..Default::default()
+                }));
             }
+
+            for &view_format in view_formats {
+                if !(view_format.compatibility() == format.compatibility()
+                    || block_texel_view_compatible
+                        && view_format.compression().is_none()
+                        && view_format.block_size() == format.block_size())
+                {
+                    return Err(ImageCreationError::ValidationError(ValidationError {
+                        code: "view_format_not_compatible",
+                        context: "UnsafeImageCreateInfo.view_formats",
+                        problem: format!(
+                            "the format {:?} in view_formats was not compatible with the format \
+                            of the image",
+                            view_format,
+                        )
+                        .into(),
+                        vuids: &["VUID-VkImageCreateInfo-pNext-06722"],
+                        ..Default::default()
+                    }));
+                }
+            }
+        }
+
+        if let Some(compression) = compression {
+            if !device.enabled_extensions().ext_image_compression_control {
+                return Err(ImageCreationError::ExtensionNotEnabled {
+                    extension: "ext_image_compression_control",
+                    reason: "compression was `Some`",
+                });
+            }
+
+            if !device.enabled_features().image_compression_control {
+                return Err(ImageCreationError::FeatureNotEnabled {
+                    feature: "image_compression_control",
+                    reason: "compression was `Some`",
+                });
+            }
+        }
+
+        let required_fixed_rates_count = if compression == Some(ImageCompression::FixedRateExplicit)
+        {
+            format.planes().len().max(1) as u32
+        } else {
+            0
+        };
+
+        if compression_fixed_rates.len() as u32 != required_fixed_rates_count {
+            return Err(ImageCreationError::ValidationError(ValidationError {
+                code: "compression_fixed_rates_count_mismatch",
+                context: "UnsafeImageCreateInfo.compression_fixed_rates",
+                problem: format!(
+                    "the number of elements in compression_fixed_rates ({}) did not match the \
+                    number required for the given compression and format ({})",
+                    compression_fixed_rates.len() as u32,
+                    required_fixed_rates_count,
+                )
+                .into(),
+                vuids: &["VUID-VkImageCompressionControlEXT-flags-06748"],
+                ..Default::default()
+            }));
         }
 
         /* Check sharing mode and queue families */
@@ -454,9 +969,19 @@ impl UnsafeImage {
                 assert!(ids.len() >= 2);
 
                 for &id in ids.iter() {
-                    // VUID-VkImageCreateInfo-sharingMode-01420
                     if device.physical_device().queue_family_by_id(id).is_none() {
-                        return Err(ImageCreationError::SharingInvalidQueueFamilyId { id });
+                        return Err(ImageCreationError::ValidationError(ValidationError {
+                            code: "sharing_invalid_queue_family_id",
+                            context: "UnsafeImageCreateInfo.sharing",
+                            problem: format!(
+                                "the sharing mode was set to `Concurrent`, but the queue family \
+                                id {} was not valid",
+                                id,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkImageCreateInfo-sharingMode-01420"],
+                            ..Default::default()
+                        }));
                     }
                 }
             }
@@ -474,9 +999,16 @@ impl UnsafeImage {
                 });
             }
 
-            // VUID-VkImageCreateInfo-pNext-01443
             if initial_layout != ImageLayout::Undefined {
-                return Err(ImageCreationError::ExternalMemoryInvalidInitialLayout);
+                return Err(ImageCreationError::ValidationError(ValidationError {
+                    code: "external_memory_invalid_initial_layout",
+                    context: "UnsafeImageCreateInfo.initial_layout",
+                    problem: "one or more external memory handle types were provided, but the \
+                        initial layout was not `Undefined`"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-pNext-01443"],
+                    ..Default::default()
+                }));
             }
         }
 
@@ -642,11 +1174,15 @@ impl UnsafeImage {
             }
         };
 
-        let must_query_device = extent_must_query()
-            || mip_levels_must_query()
-            || array_layers_must_query()
-            || samples_must_query()
-            || linear_must_query();
+        // TODO: Querying the image format properties of an image with DRM format modifier
+        // tiling requires providing the modifier, which `image_format_properties` doesn't
+        // support yet.
+        let must_query_device = tiling != ImageTiling::DrmFormatModifier
+            && (extent_must_query()
+                || mip_levels_must_query()
+                || array_layers_must_query()
+                || samples_must_query()
+                || linear_must_query());
 
         // We determined that we must query the device in order to be sure that the image
         // configuration is supported.
@@ -689,44 +1225,85 @@ impl UnsafeImage {
                     ..
                 } = match image_format_properties {
                     Some(x) => x,
-                    None => return Err(ImageCreationError::ImageFormatPropertiesNotSupported),
+                    None => {
+                        return Err(ImageCreationError::ValidationError(ValidationError {
+                            code: "image_format_properties_not_supported",
+                            context: "UnsafeImageCreateInfo",
+                            problem: "the image configuration as queried through the \
+                                `image_format_properties` function was not supported by the \
+                                device"
+                                .into(),
+                            vuids: &["VUID-VkImageCreateInfo-imageCreateMaxMipLevels-02251"],
+                            ..Default::default()
+                        }))
+                    }
                 };
 
-                // VUID-VkImageCreateInfo-extent-02252
-                // VUID-VkImageCreateInfo-extent-02253
-                // VUID-VkImageCreateInfo-extent-02254
                 if extent[0] > max_extent[0]
                     || extent[1] > max_extent[1]
                     || extent[2] > max_extent[2]
                 {
-                    return Err(ImageCreationError::MaxDimensionsExceeded {
-                        extent,
-                        max: max_extent,
-                    });
+                    return Err(ImageCreationError::ValidationError(ValidationError {
+                        code: "max_dimensions_exceeded",
+                        context: "UnsafeImageCreateInfo.dimensions",
+                        problem: format!(
+                            "the extent ({:?}) exceeds the maximum supported by the device for \
+                            this image configuration ({:?})",
+                            extent, max_extent,
+                        )
+                        .into(),
+                        vuids: &[
+                            "VUID-VkImageCreateInfo-extent-02252",
+                            "VUID-VkImageCreateInfo-extent-02253",
+                            "VUID-VkImageCreateInfo-extent-02254",
+                        ],
+                        ..Default::default()
+                    }));
                 }
 
-                // VUID-VkImageCreateInfo-mipLevels-02255
                 if mip_levels > max_mip_levels {
-                    return Err(ImageCreationError::MaxMipLevelsExceeded {
-                        mip_levels,
-                        max: max_mip_levels,
-                    });
+                    return Err(ImageCreationError::ValidationError(ValidationError {
+                        code: "max_mip_levels_exceeded",
+                        context: "UnsafeImageCreateInfo.mip_levels",
+                        problem: format!(
+                            "the number of mip levels ({}) exceeds the maximum for this image \
+                            configuration ({})",
+                            mip_levels, max_mip_levels,
+                        )
+                        .into(),
+                        vuids: &["VUID-VkImageCreateInfo-mipLevels-02255"],
+                        ..Default::default()
+                    }));
                 }
 
-                // VUID-VkImageCreateInfo-arrayLayers-02256
                 if array_layers > max_array_layers {
-                    return Err(ImageCreationError::MaxArrayLayersExceeded {
-                        array_layers,
-                        max: max_array_layers,
-                    });
+                    return Err(ImageCreationError::ValidationError(ValidationError {
+                        code: "max_array_layers_exceeded",
+                        context: "UnsafeImageCreateInfo.dimensions",
+                        problem: format!(
+                            "the number of array layers ({}) exceeds the maximum supported by the \
+                            device for this image configuration ({})",
+                            array_layers, max_array_layers,
+                        )
+                        .into(),
+                        vuids: &["VUID-VkImageCreateInfo-arrayLayers-02256"],
+                        ..Default::default()
+                    }));
                 }
 
-                // VUID-VkImageCreateInfo-samples-02258
                 if !sample_counts.contains(samples) {
-                    return Err(ImageCreationError::SampleCountNotSupported {
-                        samples,
-                        supported: sample_counts,
-                    });
+                    return Err(ImageCreationError::ValidationError(ValidationError {
+                        code: "sample_count_not_supported",
+                        context: "UnsafeImageCreateInfo.samples",
+                        problem: format!(
+                            "the sample count ({:?}) is not supported by the device for this \
+                            image configuration (supported: {:?})",
+                            samples, sample_counts,
+                        )
+                        .into(),
+                        vuids: &["VUID-VkImageCreateInfo-samples-02258"],
+                        ..Default::default()
+                    }));
                 }
 
                 // TODO: check resource size?
@@ -746,6 +1323,8 @@ impl UnsafeImage {
             mip_levels,
             samples,
             tiling,
+            ref drm_format_modifiers,
+            ref drm_format_modifier_plane_layouts,
             usage,
             ref sharing,
             initial_layout,
@@ -754,6 +1333,14 @@ impl UnsafeImage {
             cube_compatible,
             array_2d_compatible,
             block_texel_view_compatible,
+            alias,
+            view_2d_compatible,
+            protected,
+            disjoint,
+            ref view_formats,
+            compression,
+            ref compression_fixed_rates,
+            ref extensions,
             _ne: _,
         } = create_info;
 
@@ -762,6 +1349,10 @@ impl UnsafeImage {
             cube_compatible,
             array_2d_compatible,
             block_texel_view_compatible,
+            alias,
+            view_2d_compatible,
+            protected,
+            disjoint,
             ..ImageCreateFlags::none()
         };
 
@@ -814,22 +1405,85 @@ impl UnsafeImage {
             .queue_family_indices(queue_family_indices)
             .initial_layout(initial_layout.into());
 
+        let view_formats_vk: SmallVec<[_; 4]> = view_formats
+            .iter()
+            .copied()
+            .map(ash::vk::Format::from)
+            .collect();
+        let mut image_format_list_info = if !view_formats_vk.is_empty() {
+            Some(ash::vk::ImageFormatListCreateInfo::builder().view_formats(&view_formats_vk))
+        } else {
+            None
+        };
+
         if let Some(next) = external_memory_image_create_info.as_mut() {
             create_info = create_info.push_next(next);
         }
 
+        if let Some(next) = image_format_list_info.as_mut() {
+            create_info = create_info.push_next(next);
+        }
+
+        let plane_layouts_vk: SmallVec<[_; 4]> = drm_format_modifier_plane_layouts
+            .iter()
+            .map(|layout| ash::vk::SubresourceLayout {
+                offset: layout.offset,
+                size: layout.size,
+                row_pitch: layout.row_pitch,
+                array_pitch: layout.array_pitch,
+                depth_pitch: layout.depth_pitch,
+            })
+            .collect();
+        let mut drm_format_modifier_explicit_info = None;
+        let mut drm_format_modifier_list_info = None;
+
+        if !plane_layouts_vk.is_empty() {
+            let next = drm_format_modifier_explicit_info.insert(
+                ash::vk::ImageDrmFormatModifierExplicitCreateInfoEXT::builder()
+                    .drm_format_modifier(drm_format_modifiers[0])
+                    .plane_layouts(&plane_layouts_vk),
+            );
+            create_info = create_info.push_next(next);
+        } else if !drm_format_modifiers.is_empty() {
+            let next = drm_format_modifier_list_info.insert(
+                ash::vk::ImageDrmFormatModifierListCreateInfoEXT::builder()
+                    .drm_format_modifiers(drm_format_modifiers),
+            );
+            create_info = create_info.push_next(next);
+        }
+
+        let compression_fixed_rates_vk: SmallVec<[_; 3]> = compression_fixed_rates
+            .iter()
+            .copied()
+            .map(ash::vk::ImageCompressionFixedRateFlagsEXT::from)
+            .collect();
+        let mut image_compression_control = compression.map(|compression| {
+            ash::vk::ImageCompressionControlEXT::builder()
+                .flags(compression.into())
+                .fixed_rate_flags(&compression_fixed_rates_vk)
+        });
+
+        if let Some(next) = image_compression_control.as_mut() {
+            create_info = create_info.push_next(next);
+        }
+
+        let mut create_info = create_info.build();
+        let _extensions_vk = extensions.link(&mut create_info.p_next);
+
         let handle = {
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
             check_errors((fns.v1_0.create_image)(
                 device.internal_object(),
-                &create_info.build(),
+                &create_info,
                 ptr::null(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         Ok(handle)
     }
 
@@ -845,6 +1499,7 @@ impl UnsafeImage {
         dimensions: ImageDimensions,
         samples: SampleCount,
         mip_levels: u32,
+        view_formats: Vec<Format>,
     ) -> Arc<UnsafeImage> {
         let tiling = ImageTiling::Optimal;
         let format_features = device
@@ -872,6 +1527,7 @@ impl UnsafeImage {
         let aspect_size = mip_level_size * mip_levels as DeviceSize;
         let range_size = aspect_list.len() as DeviceSize * aspect_size;
 
+        let tracked = device.track_object(TrackedObjectType::Image);
         let image = UnsafeImage {
             handle,
             device: device.clone(),
@@ -888,6 +1544,12 @@ impl UnsafeImage {
             cube_compatible: flags.cube_compatible,
             array_2d_compatible: flags.array_2d_compatible,
             block_texel_view_compatible: flags.block_texel_view_compatible,
+            alias: flags.alias,
+            view_2d_compatible: flags.view_2d_compatible,
+            protected: flags.protected,
+            disjoint: flags.disjoint,
+            view_formats,
+            drm_format_modifier: None,
 
             aspect_list,
             aspect_size,
@@ -895,6 +1557,7 @@ impl UnsafeImage {
             needs_destruction: false, // TODO: pass as parameter
             range_size,
             state: Mutex::new(ImageState::new(range_size, initial_layout)),
+            tracked,
         };
 
         Arc::new(image)
@@ -902,12 +1565,73 @@ impl UnsafeImage {
 
     /// Returns the memory requirements for this image.
     pub fn memory_requirements(&self) -> MemoryRequirements {
-        let image_memory_requirements_info2 = ash::vk::ImageMemoryRequirementsInfo2 {
+        self.memory_requirements_impl(None)
+    }
+
+    /// Returns the memory requirements for a single plane of a disjoint image.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the image was not created with the `disjoint` flag.
+    /// - Panics if `plane` is not one of the planes of the image.
+    pub fn plane_memory_requirements(&self, plane: ImageAspect) -> MemoryRequirements {
+        assert!(self.disjoint);
+
+        // VUID-VkImagePlaneMemoryRequirementsInfo-planeAspect-02281
+        // VUID-VkImagePlaneMemoryRequirementsInfo-planeAspect-02282
+        assert!(self.planes().contains(&plane));
+
+        self.memory_requirements_impl(Some(plane))
+    }
+
+    // Returns the planes that can be bound to memory separately if the image is disjoint.
+    fn planes(&self) -> &'static [ImageAspect] {
+        match self.drm_format_modifier {
+            Some(drm_format_modifier) => {
+                let plane_count = self
+                    .device
+                    .physical_device()
+                    .drm_format_modifier_properties(self.format.unwrap())
+                    .into_iter()
+                    .find(|properties| properties.drm_format_modifier == drm_format_modifier)
+                    .map_or(1, |properties| properties.drm_format_modifier_plane_count);
+
+                let memory_planes: &'static [ImageAspect] = &[
+                    ImageAspect::MemoryPlane0,
+                    ImageAspect::MemoryPlane1,
+                    ImageAspect::MemoryPlane2,
+                ];
+                &memory_planes[..(plane_count as usize).min(memory_planes.len())]
+            }
+            None => {
+                let format_planes: &'static [ImageAspect] = &[
+                    ImageAspect::Plane0,
+                    ImageAspect::Plane1,
+                    ImageAspect::Plane2,
+                ];
+                &format_planes[..self.format.unwrap().planes().len()]
+            }
+        }
+    }
+
+    fn memory_requirements_impl(&self, plane: Option<ImageAspect>) -> MemoryRequirements {
+        let mut image_memory_requirements_info2 = ash::vk::ImageMemoryRequirementsInfo2 {
             image: self.handle,
             ..Default::default()
         };
         let mut memory_requirements2 = ash::vk::MemoryRequirements2::default();
 
+        let mut image_plane_memory_requirements_info =
+            plane.map(|plane| ash::vk::ImagePlaneMemoryRequirementsInfo {
+                plane_aspect: plane.into(),
+                ..Default::default()
+            });
+
+        if let Some(next) = image_plane_memory_requirements_info.as_mut() {
+            next.p_next = image_memory_requirements_info2.p_next;
+            image_memory_requirements_info2.p_next = next as *const _ as *const _;
+        }
+
         let mut memory_dedicated_requirements = if self.device.api_version() >= Version::V1_1
             || self.device.enabled_extensions().khr_dedicated_allocation
         {
@@ -967,6 +1691,9 @@ impl UnsafeImage {
     ) -> Result<(), OomError> {
         let fns = self.device.fns();
 
+        // VUID-vkBindImageMemory-image-01608
+        debug_assert!(!self.disjoint);
+
         // We check for correctness in debug mode.
         debug_assert!({
             let mut mem_reqs = MaybeUninit::uninit();
@@ -982,6 +1709,10 @@ impl UnsafeImage {
                 && mem_reqs.memory_type_bits & (1 << memory.memory_type().id()) != 0
         });
 
+        // VUID-vkBindImageMemory-None-01901
+        // VUID-vkBindImageMemory-None-01902
+        debug_assert!(memory.memory_type().is_protected() == self.protected);
+
         check_errors((fns.v1_0.bind_image_memory)(
             self.device.internal_object(),
             self.handle,
@@ -991,6 +1722,84 @@ impl UnsafeImage {
         Ok(())
     }
 
+    /// Binds device memory to the planes of a disjoint image. Each plane can be bound to a
+    /// different memory object, for example when the planes of an imported DRM image come from
+    /// separate file descriptors.
+    ///
+    /// Each element of `planes` contains the plane, the memory to bind it to, and the offset
+    /// into that memory.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the image was not created with the `disjoint` flag.
+    /// - Panics if any of the planes is not one of the planes of the image, or if not every plane
+    ///   of the image is provided exactly once.
+    ///
+    /// # Safety
+    ///
+    /// - The memory and offset for each plane must satisfy the requirements returned by
+    ///   [`plane_memory_requirements`](Self::plane_memory_requirements) for that plane.
+    pub unsafe fn bind_memory_planes<'a>(
+        &self,
+        planes: impl IntoIterator<Item = (ImageAspect, &'a DeviceMemory, DeviceSize)>,
+    ) -> Result<(), OomError> {
+        assert!(self.disjoint);
+
+        let planes: SmallVec<[_; 3]> = planes.into_iter().collect();
+        let image_planes = self.planes();
+
+        // VUID-VkBindImageMemoryInfo-pNext-01618
+        assert!(planes.len() == image_planes.len());
+        assert!(image_planes.iter().all(|&aspect| planes
+            .iter()
+            .filter(|&&(p, _, _)| p == aspect)
+            .count()
+            == 1));
+
+        for &(_, memory, _) in &planes {
+            debug_assert!(memory.memory_type().is_protected() == self.protected);
+        }
+
+        let plane_infos_vk: SmallVec<[_; 3]> = planes
+            .iter()
+            .map(|&(plane, _, _)| ash::vk::BindImagePlaneMemoryInfo {
+                plane_aspect: plane.into(),
+                ..Default::default()
+            })
+            .collect();
+        let bind_infos_vk: SmallVec<[_; 3]> = planes
+            .iter()
+            .zip(&plane_infos_vk)
+            .map(
+                |(&(_, memory, offset), plane_info_vk)| ash::vk::BindImageMemoryInfo {
+                    p_next: plane_info_vk as *const _ as *const _,
+                    image: self.handle,
+                    memory: memory.internal_object(),
+                    memory_offset: offset,
+                    ..Default::default()
+                },
+            )
+            .collect();
+
+        let fns = self.device.fns();
+
+        if self.device.api_version() >= Version::V1_1 {
+            check_errors((fns.v1_1.bind_image_memory2)(
+                self.device.internal_object(),
+                bind_infos_vk.len() as u32,
+                bind_infos_vk.as_ptr(),
+            ))?;
+        } else {
+            check_errors((fns.khr_bind_memory2.bind_image_memory2_khr)(
+                self.device.internal_object(),
+                bind_infos_vk.len() as u32,
+                bind_infos_vk.as_ptr(),
+            ))?;
+        }
+
+        Ok(())
+    }
+
     #[inline]
     pub(crate) fn range_size(&self) -> DeviceSize {
         self.range_size
@@ -1168,6 +1977,44 @@ impl UnsafeImage {
         self.block_texel_view_compatible
     }
 
+    /// Returns whether `alias` is enabled on the image.
+    #[inline]
+    pub fn alias(&self) -> bool {
+        self.alias
+    }
+
+    /// Returns whether `view_2d_compatible` is enabled on the image.
+    #[inline]
+    pub fn view_2d_compatible(&self) -> bool {
+        self.view_2d_compatible
+    }
+
+    /// Returns whether the image is a protected image.
+    #[inline]
+    pub fn protected(&self) -> bool {
+        self.protected
+    }
+
+    /// Returns whether `disjoint` is enabled on the image.
+    #[inline]
+    pub fn disjoint(&self) -> bool {
+        self.disjoint
+    }
+
+    /// Returns the Linux DRM format modifier that the image was created with, if its tiling is
+    /// [`ImageTiling::DrmFormatModifier`].
+    #[inline]
+    pub fn drm_format_modifier(&self) -> Option<u64> {
+        self.drm_format_modifier
+    }
+
+    /// Returns the formats that image views created from the image are restricted to. If empty,
+    /// there is no restriction beyond the usual format compatibility rules.
+    #[inline]
+    pub fn view_formats(&self) -> &[Format] {
+        &self.view_formats
+    }
+
     /// Returns an `ImageSubresourceLayers` covering the first mip level of the image. All aspects
     /// of the image are selected, or `plane0` if the image is multi-planar.
     #[inline]
@@ -1272,22 +2119,39 @@ impl UnsafeImage {
     }
 
     /// Same as `color_linear_layout`, except that it retrieves layout for the requested YCbCr
-    /// component too if the format is a YCbCr format.
+    /// component too if the format is a YCbCr format, or for the requested memory plane if the
+    /// image has DRM format modifier tiling.
     ///
     /// # Panic
     ///
     /// - Panics if plane aspect is out of range.
-    /// - Panics if the aspect is not a color or planar aspect.
+    /// - Panics if the aspect is not a color, planar or memory plane aspect.
+    /// - Panics if the aspect is a memory plane aspect, but the image does not have DRM format
+    ///   modifier tiling.
     /// - Panics if the number of mipmaps is not 1.
     #[inline]
     pub unsafe fn multiplane_color_layout(&self, aspect: ImageAspect) -> LinearLayout {
-        // This function only supports color and planar aspects currently.
+        // This function only supports color, planar and memory plane aspects currently.
         assert!(matches!(
             aspect,
-            ImageAspect::Color | ImageAspect::Plane0 | ImageAspect::Plane1 | ImageAspect::Plane2
+            ImageAspect::Color
+                | ImageAspect::Plane0
+                | ImageAspect::Plane1
+                | ImageAspect::Plane2
+                | ImageAspect::MemoryPlane0
+                | ImageAspect::MemoryPlane1
+                | ImageAspect::MemoryPlane2
         ));
         assert!(self.mip_levels == 1);
 
+        // VUID-vkGetImageSubresourceLayout-tiling-02271
+        if matches!(
+            aspect,
+            ImageAspect::MemoryPlane0 | ImageAspect::MemoryPlane1 | ImageAspect::MemoryPlane2
+        ) {
+            assert!(self.tiling == ImageTiling::DrmFormatModifier);
+        }
+
         if matches!(
             aspect,
             ImageAspect::Plane0 | ImageAspect::Plane1 | ImageAspect::Plane2
@@ -1327,17 +2191,90 @@ impl UnsafeImage {
             depth_pitch: out.depth_pitch,
         }
     }
-}
 
-impl Drop for UnsafeImage {
-    #[inline]
-    fn drop(&mut self) {
-        if !self.needs_destruction {
-            return;
-        }
+    /// Queries the compression that the implementation has applied to a subresource of the
+    /// image.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the
+    ///   [`ext_image_compression_control`](crate::device::DeviceExtensions::ext_image_compression_control)
+    ///   extension is not enabled on the device.
+    /// - Panics if `aspect` is not one of the aspects of the image's format.
+    /// - Panics if `mip_level` or `array_layer` is out of range.
+    pub fn compression_properties(
+        &self,
+        aspect: ImageAspect,
+        mip_level: u32,
+        array_layer: u32,
+    ) -> ImageCompressionProperties {
+        assert!(
+            self.device
+                .enabled_extensions()
+                .ext_image_compression_control
+        );
 
-        unsafe {
-            let fns = self.device.fns();
+        // VUID-vkGetImageSubresourceLayout2EXT-aspectMask-00997
+        assert!(self.format.unwrap().aspects().iter().any(|a| a == aspect));
+
+        // VUID-vkGetImageSubresourceLayout2EXT-mipLevel-01716
+        assert!(mip_level < self.mip_levels);
+
+        // VUID-vkGetImageSubresourceLayout2EXT-arrayLayer-01717
+        assert!(array_layer < self.dimensions.array_layers());
+
+        let subresource = ash::vk::ImageSubresource2EXT {
+            image_subresource: ash::vk::ImageSubresource {
+                aspect_mask: aspect.into(),
+                mip_level,
+                array_layer,
+            },
+            ..Default::default()
+        };
+
+        let mut image_compression_properties = ash::vk::ImageCompressionPropertiesEXT::default();
+        let mut subresource_layout = ash::vk::SubresourceLayout2EXT {
+            p_next: &mut image_compression_properties as *mut _ as *mut _,
+            ..Default::default()
+        };
+
+        unsafe {
+            let fns = self.device.fns();
+            (fns.ext_image_compression_control
+                .get_image_subresource_layout2_ext)(
+                self.device.internal_object(),
+                self.handle,
+                &subresource,
+                &mut subresource_layout,
+            );
+        }
+
+        let compression = match image_compression_properties.image_compression_flags {
+            ash::vk::ImageCompressionFlagsEXT::FIXED_RATE_EXPLICIT => {
+                ImageCompression::FixedRateExplicit
+            }
+            ash::vk::ImageCompressionFlagsEXT::DISABLED => ImageCompression::Disabled,
+            _ => ImageCompression::Default,
+        };
+
+        ImageCompressionProperties {
+            compression,
+            fixed_rates: image_compression_properties
+                .image_compression_fixed_rate_flags
+                .into(),
+        }
+    }
+}
+
+impl Drop for UnsafeImage {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
+        unsafe {
+            let fns = self.device.fns();
             (fns.v1_0.destroy_image)(self.device.internal_object(), self.handle, ptr::null());
         }
     }
@@ -1404,6 +2341,25 @@ pub struct UnsafeImageCreateInfo {
     /// The default value is [`ImageTiling::Optimal`].
     pub tiling: ImageTiling,
 
+    /// If `tiling` is [`ImageTiling::DrmFormatModifier`], the Linux DRM format modifiers that the
+    /// image can be created with. The implementation picks one of them, which can be queried
+    /// afterwards with [`UnsafeImage::drm_format_modifier`].
+    ///
+    /// Must be non-empty if `tiling` is [`ImageTiling::DrmFormatModifier`], and empty otherwise.
+    ///
+    /// The default value is empty.
+    pub drm_format_modifiers: Vec<u64>,
+
+    /// If not empty, the layouts of the memory planes of the image, as imported for example from
+    /// a DMA-BUF. `drm_format_modifiers` must then contain exactly one modifier, and this must
+    /// contain one element for each memory plane of that modifier.
+    ///
+    /// The `size` of each layout must be `0`. The `array_pitch` must be `0` if the image has only
+    /// one array layer, and the `depth_pitch` must be `0` if the image is not three-dimensional.
+    ///
+    /// The default value is empty.
+    pub drm_format_modifier_plane_layouts: Vec<LinearLayout>,
+
     /// How the image is going to be used.
     ///
     /// The default value is [`ImageUsage::none()`], which must be overridden.
@@ -1463,6 +2419,100 @@ pub struct UnsafeImageCreateInfo {
     /// The default value is `false`.
     pub block_texel_view_compatible: bool,
 
+    /// Whether the image can be bound to memory that is also bound to other images that were
+    /// created with identical parameters, where all of those images interpret the contents of
+    /// the memory in the same way.
+    ///
+    /// Resources can always be bound to overlapping memory ranges, but without this flag the
+    /// contents seen through one image after writing through another are undefined.
+    ///
+    /// If set to `true`, the device API version must be at least 1.1, or the
+    /// [`khr_bind_memory2`](crate::device::DeviceExtensions::khr_bind_memory2) extension must be
+    /// enabled on the device.
+    ///
+    /// The default value is `false`.
+    pub alias: bool,
+
+    /// For 3D images, whether an image view of type
+    /// [`ImageViewType::Dim2d`](crate::image::view::ImageViewType::Dim2d) created from the image
+    /// can be used in a storage image, sampled image or combined image sampler descriptor.
+    ///
+    /// If set to `true`, the
+    /// [`ext_image_2d_view_of_3d`](crate::device::DeviceExtensions::ext_image_2d_view_of_3d)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is `false`.
+    pub view_2d_compatible: bool,
+
+    /// Whether the image is a protected image, whose contents can only be accessed by protected
+    /// queue operations. It must be bound to memory of a protected memory type.
+    ///
+    /// If set to `true`, the [`protected_memory`](crate::device::Features::protected_memory)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `false`.
+    pub protected: bool,
+
+    /// Whether the planes of the image can be bound to memory separately, with
+    /// [`UnsafeImage::bind_memory_planes`]. The planes are the format planes for images with a
+    /// multi-planar format, or the memory planes for images with DRM format modifier tiling.
+    ///
+    /// If set to `true`, the device API version must be at least 1.1, or the
+    /// [`khr_sampler_ycbcr_conversion`](crate::device::DeviceExtensions::khr_sampler_ycbcr_conversion)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is `false`.
+    pub disjoint: bool,
+
+    /// The formats that image views created from the image will have.
+    ///
+    /// If not empty, image views created from the image can only have a format that is in this
+    /// list. If `mutable_format` is not set, the list can only contain `format` itself. Otherwise,
+    /// each format must be compatible with `format`, in the same way as for image views. Providing
+    /// the list allows the implementation to keep optimizations, such as compression, that it
+    /// would otherwise have to disable for images with the `mutable_format` flag.
+    ///
+    /// If not empty, the device API version must be at least 1.2, or the
+    /// [`khr_image_format_list`](crate::device::DeviceExtensions::khr_image_format_list) extension
+    /// must be enabled on the device.
+    ///
+    /// The default value is empty.
+    pub view_formats: Vec<Format>,
+
+    /// The compression that the implementation should apply to the image. If `None`, the
+    /// implementation's default compression is used.
+    ///
+    /// Fixed-rate compression is lossy, but it can save a lot of memory bandwidth. The
+    /// compression that was actually applied can be queried afterwards with
+    /// [`UnsafeImage::compression_properties`].
+    ///
+    /// If not `None`, the
+    /// [`ext_image_compression_control`](crate::device::DeviceExtensions::ext_image_compression_control)
+    /// extension and the
+    /// [`image_compression_control`](crate::device::Features::image_compression_control)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub compression: Option<ImageCompression>,
+
+    /// If `compression` is [`ImageCompression::FixedRateExplicit`], the fixed-rate compression
+    /// bit rates that the implementation is allowed to choose from, for each plane of the image.
+    /// The implementation will pick the lowest rate among the ones given that it supports.
+    ///
+    /// If `compression` is `FixedRateExplicit`, the list must contain one element for each plane
+    /// of `format`, or a single element if `format` is not multi-planar. Otherwise, the list must
+    /// be empty.
+    ///
+    /// The default value is empty.
+    pub compression_fixed_rates: Vec<ImageCompressionFixedRates>,
+
+    /// Extension structures that vulkano doesn't support, to add to the create info of the
+    /// image. See the [`extension_chain`](crate::extension_chain) module for more information.
+    /// If it is not empty, it can only be used to create one object.
+    ///
+    /// The default value is empty.
+    pub extensions: ExtensionChain<UnsafeImage>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -1478,6 +2528,8 @@ impl Default for UnsafeImageCreateInfo {
             mip_levels: 1,
             samples: SampleCount::Sample1,
             tiling: ImageTiling::Optimal,
+            drm_format_modifiers: Vec::new(),
+            drm_format_modifier_plane_layouts: Vec::new(),
             usage: ImageUsage::none(),
             sharing: Sharing::Exclusive,
             initial_layout: ImageLayout::Undefined,
@@ -1486,12 +2538,20 @@ impl Default for UnsafeImageCreateInfo {
             cube_compatible: false,
             array_2d_compatible: false,
             block_texel_view_compatible: false,
+            alias: false,
+            view_2d_compatible: false,
+            protected: false,
+            disjoint: false,
+            view_formats: Vec::new(),
+            compression: None,
+            compression_fixed_rates: Vec::new(),
+            extensions: ExtensionChain::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
 }
 
-/// Error that can happen when creating an instance.
+/// Error that can happen when creating an image.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ImageCreationError {
     /// Allocating memory failed.
@@ -1506,87 +2566,12 @@ pub enum ImageCreationError {
         reason: &'static str,
     },
 
-    /// The array_2d_compatible flag was enabled, but the image type was not 3D.
-    Array2dCompatibleNot3d,
-
-    /// The block_texel_view_compatible flag was enabled, but the given format was not compressed.
-    BlockTexelViewCompatibleNotCompressed,
-
-    /// The cube_compatible flag was enabled, but the image type was not 2D.
-    CubeCompatibleNot2d,
-
-    /// The cube_compatible flag was enabled, but the number of array layers was less than 6.
-    CubeCompatibleNotEnoughArrayLayers,
-
-    /// The cube_compatible flag was enabled, but the image dimensions were not square.
-    CubeCompatibleNotSquare,
-
-    /// The cube_compatible flag was enabled together with multisampling.
-    CubeCompatibleMultisampling,
-
-    /// One or more external memory handle types were provided, but the initial layout was not
-    /// `Undefined`.
-    ExternalMemoryInvalidInitialLayout,
-
-    /// The given format was not supported by the device.
-    FormatNotSupported,
-
-    /// A requested usage flag was not supported by the given format.
-    FormatUsageNotSupported { usage: &'static str },
-
-    /// The image configuration as queried through the `image_format_properties` function was not
-    /// supported by the device.
-    ImageFormatPropertiesNotSupported,
-
-    /// The number of array layers exceeds the maximum supported by the device for this image
-    /// configuration.
-    MaxArrayLayersExceeded { array_layers: u32, max: u32 },
-
-    /// The specified dimensions exceed the maximum supported by the device for this image
-    /// configuration.
-    MaxDimensionsExceeded { extent: [u32; 3], max: [u32; 3] },
-
-    /// The usage included one of the attachment types, and the specified width and height exceeded
-    /// the `max_framebuffer_width` or `max_framebuffer_height` limits.
-    MaxFramebufferDimensionsExceeded { extent: [u32; 2], max: [u32; 2] },
-
-    /// The maximum number of mip levels for the given dimensions has been exceeded.
-    MaxMipLevelsExceeded { mip_levels: u32, max: u32 },
-
-    /// Multisampling was enabled, and the `cube_compatible` flag was set.
-    MultisampleCubeCompatible,
-
-    /// Multisampling was enabled, and tiling was `Linear`.
-    MultisampleLinearTiling,
-
-    /// Multisampling was enabled, and multiple mip levels were specified.
-    MultisampleMultipleMipLevels,
+    /// The implementation ran out of resources for fixed-rate compression.
+    CompressionExhausted,
 
-    /// Multisampling was enabled, but the image type was not 2D.
-    MultisampleNot2d,
-
-    /// The sample count is not supported by the device for this image configuration.
-    SampleCountNotSupported {
-        samples: SampleCount,
-        supported: SampleCounts,
-    },
-
-    /// The sharing mode was set to `Concurrent`, but one of the specified queue family ids was not
-    /// valid.
-    SharingInvalidQueueFamilyId { id: u32 },
-
-    /// A YCbCr format was given, but the specified width and/or height was not a multiple of 2
-    /// as required by the format's chroma subsampling.
-    YcbcrFormatInvalidDimensions,
-
-    /// A YCbCr format was given, and multiple mip levels were specified.
-    YcbcrFormatMultipleMipLevels,
-
-    /// A YCbCr format was given, and multisampling was enabled.
-    YcbcrFormatMultisampling,
-
-    /// A YCbCr format was given, but the image type was not 2D.
-    YcbcrFormatNot2d,
+    /// The create info broke a rule of the Vulkan specification, such as flags that are not
+    /// allowed for the image type, or a configuration that the device does not support.
+    ValidationError(ValidationError),
 }
 
 impl error::Error for ImageCreationError {
@@ -1612,114 +2597,13 @@ impl fmt::Display for ImageCreationError {
             Self::FeatureNotEnabled { feature, reason } => {
                 write!(fmt, "the feature {} must be enabled: {}", feature, reason)
             }
-            Self::Array2dCompatibleNot3d => {
-                write!(
-                    fmt,
-                    "the array_2d_compatible flag was enabled, but the image type was not 3D"
-                )
-            }
-            Self::BlockTexelViewCompatibleNotCompressed => {
-                write!(fmt, "the block_texel_view_compatible flag was enabled, but the given format was not compressed")
-            }
-            Self::CubeCompatibleNot2d => {
-                write!(
-                    fmt,
-                    "the cube_compatible flag was enabled, but the image type was not 2D"
-                )
-            }
-            Self::CubeCompatibleNotEnoughArrayLayers => {
-                write!(fmt, "the cube_compatible flag was enabled, but the number of array layers was less than 6")
-            }
-            Self::CubeCompatibleNotSquare => {
-                write!(fmt, "the cube_compatible flag was enabled, but the image dimensions were not square")
-            }
-            Self::CubeCompatibleMultisampling => {
-                write!(
-                    fmt,
-                    "the cube_compatible flag was enabled together with multisampling"
-                )
-            }
-            Self::ExternalMemoryInvalidInitialLayout => {
-                write!(fmt, "one or more external memory handle types were provided, but the initial layout was not `Undefined`")
-            }
-            Self::FormatNotSupported => {
-                write!(fmt, "the given format was not supported by the device")
-            }
-            Self::FormatUsageNotSupported { usage } => {
-                write!(
-                    fmt,
-                    "a requested usage flag was not supported by the given format"
-                )
-            }
-            Self::ImageFormatPropertiesNotSupported => {
-                write!(fmt, "the image configuration as queried through the `image_format_properties` function was not supported by the device")
-            }
-            Self::MaxArrayLayersExceeded { array_layers, max } => {
-                write!(fmt, "the number of array layers exceeds the maximum supported by the device for this image configuration")
-            }
-            Self::MaxDimensionsExceeded { extent, max } => {
-                write!(fmt, "the specified dimensions exceed the maximum supported by the device for this image configuration")
-            }
-            Self::MaxFramebufferDimensionsExceeded { extent, max } => {
-                write!(fmt, "the usage included one of the attachment types, and the specified width and height exceeded the `max_framebuffer_width` or `max_framebuffer_height` limits")
-            }
-            Self::MaxMipLevelsExceeded { mip_levels, max } => {
-                write!(
-                    fmt,
-                    "the maximum number of mip levels for the given dimensions has been exceeded"
-                )
-            }
-            Self::MultisampleCubeCompatible => {
-                write!(
-                    fmt,
-                    "multisampling was enabled, and the `cube_compatible` flag was set"
-                )
-            }
-            Self::MultisampleLinearTiling => {
-                write!(fmt, "multisampling was enabled, and tiling was `Linear`")
-            }
-            Self::MultisampleMultipleMipLevels => {
-                write!(
-                    fmt,
-                    "multisampling was enabled, and multiple mip levels were specified"
-                )
-            }
-            Self::MultisampleNot2d => {
-                write!(
-                    fmt,
-                    "multisampling was enabled, but the image type was not 2D"
-                )
-            }
-            Self::SampleCountNotSupported { samples, supported } => {
-                write!(
-                    fmt,
-                    "the sample count is not supported by the device for this image configuration"
-                )
-            }
-            Self::SharingInvalidQueueFamilyId { id } => {
-                write!(fmt, "the sharing mode was set to `Concurrent`, but one of the specified queue family ids was not valid")
-            }
-            Self::YcbcrFormatInvalidDimensions => {
-                write!(fmt, "a YCbCr format was given, but the specified width and/or height was not a multiple of 2 as required by the format's chroma subsampling")
-            }
-            Self::YcbcrFormatMultipleMipLevels => {
-                write!(
-                    fmt,
-                    "a YCbCr format was given, and multiple mip levels were specified"
-                )
-            }
-            Self::YcbcrFormatMultisampling => {
+            Self::CompressionExhausted => {
                 write!(
                     fmt,
-                    "a YCbCr format was given, and multisampling was enabled"
-                )
-            }
-            Self::YcbcrFormatNot2d => {
-                write!(
-                    fmt,
-                    "a YCbCr format was given, but the image type was not 2D"
+                    "the implementation ran out of resources for fixed-rate compression"
                 )
             }
+            Self::ValidationError(ref err) => write!(fmt, "{}", err),
         }
     }
 }
@@ -1731,6 +2615,13 @@ impl From<OomError> for ImageCreationError {
     }
 }
 
+impl From<ValidationError> for ImageCreationError {
+    #[inline]
+    fn from(err: ValidationError) -> Self {
+        Self::ValidationError(err)
+    }
+}
+
 impl From<DeviceMemoryAllocationError> for ImageCreationError {
     #[inline]
     fn from(err: DeviceMemoryAllocationError) -> Self {
@@ -1744,6 +2635,7 @@ impl From<Error> for ImageCreationError {
         match err {
             err @ Error::OutOfHostMemory => Self::AllocError(err.into()),
             err @ Error::OutOfDeviceMemory => Self::AllocError(err.into()),
+            Error::CompressionExhausted => Self::CompressionExhausted,
             _ => panic!("unexpected error: {:?}", err),
         }
     }
@@ -2154,10 +3046,13 @@ mod tests {
     use crate::image::sys::SubresourceRangeIterator;
     use crate::image::ImageAspect;
     use crate::image::ImageAspects;
+    use crate::image::ImageCompressionFixedRates;
     use crate::image::ImageDimensions;
     use crate::image::ImageSubresourceRange;
+    use crate::image::ImageTiling;
     use crate::image::SampleCount;
     use crate::DeviceSize;
+    use crate::Version;
     use smallvec::SmallVec;
 
     #[test]
@@ -2255,7 +3150,10 @@ mod tests {
         );
 
         match res {
-            Err(ImageCreationError::MaxMipLevelsExceeded { .. }) => (),
+            Err(ImageCreationError::ValidationError(err)) => {
+                assert_eq!(err.code, "max_mip_levels_exceeded");
+                assert!(err.has_vuid("VUID-VkImageCreateInfo-mipLevels-00958"));
+            }
             _ => panic!(),
         };
     }
@@ -2287,7 +3185,9 @@ mod tests {
                 feature: "shader_storage_image_multisample",
                 ..
             }) => (),
-            Err(ImageCreationError::SampleCountNotSupported { .. }) => (), // unlikely but possible
+            // unlikely but possible
+            Err(ImageCreationError::ValidationError(err))
+                if err.code == "sample_count_not_supported" => {}
             _ => panic!(),
         };
     }
@@ -2314,10 +3214,10 @@ mod tests {
         );
 
         match res {
-            Err(ImageCreationError::FormatNotSupported) => (),
-            Err(ImageCreationError::FormatUsageNotSupported {
-                usage: "color_attachment",
-            }) => (),
+            Err(ImageCreationError::ValidationError(err))
+                if err.code == "format_not_supported"
+                    || err.code == "format_usage_not_supported"
+                        && err.context == "UnsafeImageCreateInfo.usage" => {}
             _ => panic!(),
         };
     }
@@ -2370,12 +3270,254 @@ mod tests {
         );
 
         match res {
-            Err(ImageCreationError::CubeCompatibleNotEnoughArrayLayers) => (),
-            Err(ImageCreationError::CubeCompatibleNotSquare) => (),
+            Err(ImageCreationError::ValidationError(err)) => {
+                assert!(
+                    err.code == "cube_compatible_not_enough_array_layers"
+                        || err.code == "cube_compatible_not_square"
+                );
+                assert!(err.has_vuid("VUID-VkImageCreateInfo-imageType-00954"));
+            }
             _ => panic!(),
         };
     }
 
+    #[test]
+    fn alias_requires_bind_memory2() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = UnsafeImage::new(
+            device.clone(),
+            UnsafeImageCreateInfo {
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage {
+                    color_attachment: true,
+                    ..ImageUsage::none()
+                },
+                alias: true,
+                ..Default::default()
+            },
+        );
+
+        if device.api_version() >= Version::V1_1 || device.enabled_extensions().khr_bind_memory2 {
+            assert!(res.unwrap().alias());
+        } else {
+            match res {
+                Err(ImageCreationError::ExtensionNotEnabled {
+                    extension: "khr_bind_memory2",
+                    ..
+                }) => (),
+                _ => panic!(),
+            }
+        }
+    }
+
+    #[test]
+    fn view_2d_compatible_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = UnsafeImage::new(
+            device,
+            UnsafeImageCreateInfo {
+                dimensions: ImageDimensions::Dim3d {
+                    width: 32,
+                    height: 32,
+                    depth: 32,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage {
+                    storage: true,
+                    ..ImageUsage::none()
+                },
+                view_2d_compatible: true,
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageCreationError::ExtensionNotEnabled {
+                extension: "ext_image_2d_view_of_3d",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn protected_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = UnsafeImage::new(
+            device,
+            UnsafeImageCreateInfo {
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage {
+                    sampled: true,
+                    ..ImageUsage::none()
+                },
+                protected: true,
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageCreationError::FeatureNotEnabled {
+                feature: "protected_memory",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn attachment_feedback_loop_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = UnsafeImage::new(
+            device,
+            UnsafeImageCreateInfo {
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage {
+                    color_attachment: true,
+                    attachment_feedback_loop: true,
+                    ..ImageUsage::none()
+                },
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageCreationError::ExtensionNotEnabled {
+                extension: "ext_attachment_feedback_loop_layout",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn drm_format_modifier_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = UnsafeImage::new(
+            device,
+            UnsafeImageCreateInfo {
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                tiling: ImageTiling::DrmFormatModifier,
+                drm_format_modifiers: vec![0],
+                usage: ImageUsage {
+                    sampled: true,
+                    ..ImageUsage::none()
+                },
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageCreationError::ExtensionNotEnabled {
+                extension: "ext_image_drm_format_modifier",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn compression_fixed_rates_without_fixed_rate_explicit() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = UnsafeImage::new(
+            device,
+            UnsafeImageCreateInfo {
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage {
+                    sampled: true,
+                    ..ImageUsage::none()
+                },
+                compression_fixed_rates: vec![ImageCompressionFixedRates {
+                    bpc2: true,
+                    ..ImageCompressionFixedRates::none()
+                }],
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageCreationError::ValidationError(err)) => {
+                assert_eq!(err.code, "compression_fixed_rates_count_mismatch");
+                assert!(err.has_vuid("VUID-VkImageCompressionControlEXT-flags-06748"));
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn view_formats_without_mutable_format() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = UnsafeImage::new(
+            device.clone(),
+            UnsafeImageCreateInfo {
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage {
+                    sampled: true,
+                    ..ImageUsage::none()
+                },
+                view_formats: vec![Format::R8G8B8A8_UNORM, Format::R8G8B8A8_SRGB],
+                ..Default::default()
+            },
+        );
+
+        if device.api_version() >= Version::V1_2
+            || device.enabled_extensions().khr_image_format_list
+        {
+            match res {
+                Err(ImageCreationError::ValidationError(err)) => {
+                    assert_eq!(err.code, "view_formats_without_mutable_format");
+                    assert!(err.has_vuid("VUID-VkImageCreateInfo-flags-04738"));
+                }
+                _ => panic!(),
+            }
+        } else {
+            match res {
+                Err(ImageCreationError::ExtensionNotEnabled {
+                    extension: "khr_image_format_list",
+                    ..
+                }) => (),
+                _ => panic!(),
+            }
+        }
+    }
+
     #[test]
     fn subresource_range_iterator() {
         // A fictitious set of aspects that no real image would actually ever have.
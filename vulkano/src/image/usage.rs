@@ -46,6 +46,14 @@ pub struct ImageUsage {
     /// Can be used as an input attachment. In other words, you can draw to it in a subpass then
     /// read from it in a following pass.
     pub input_attachment: bool,
+
+    /// Can be used as an attachment and read in a shader in the same subpass, in the
+    /// `AttachmentFeedbackLoopOptimal` layout.
+    ///
+    /// The
+    /// [`ext_attachment_feedback_loop_layout`](crate::device::DeviceExtensions::ext_attachment_feedback_loop_layout)
+    /// extension must be enabled on the device.
+    pub attachment_feedback_loop: bool,
 }
 
 impl ImageUsage {
@@ -62,6 +70,7 @@ impl ImageUsage {
             depth_stencil_attachment: true,
             transient_attachment: true,
             input_attachment: true,
+            attachment_feedback_loop: true,
         }
     }
 
@@ -89,6 +98,7 @@ impl ImageUsage {
             depth_stencil_attachment: false,
             transient_attachment: false,
             input_attachment: false,
+            attachment_feedback_loop: false,
         }
     }
 
@@ -104,6 +114,7 @@ impl ImageUsage {
             depth_stencil_attachment: false,
             transient_attachment: false,
             input_attachment: false,
+            attachment_feedback_loop: false,
         }
     }
 
@@ -119,6 +130,7 @@ impl ImageUsage {
             depth_stencil_attachment: true,
             transient_attachment: false,
             input_attachment: false,
+            attachment_feedback_loop: false,
         }
     }
 
@@ -134,6 +146,7 @@ impl ImageUsage {
             depth_stencil_attachment: false,
             transient_attachment: true,
             input_attachment: false,
+            attachment_feedback_loop: false,
         }
     }
 
@@ -149,6 +162,7 @@ impl ImageUsage {
             depth_stencil_attachment: true,
             transient_attachment: true,
             input_attachment: false,
+            attachment_feedback_loop: false,
         }
     }
 }
@@ -181,6 +195,9 @@ impl From<ImageUsage> for ash::vk::ImageUsageFlags {
         if val.input_attachment {
             result |= ash::vk::ImageUsageFlags::INPUT_ATTACHMENT;
         }
+        if val.attachment_feedback_loop {
+            result |= ash::vk::ImageUsageFlags::ATTACHMENT_FEEDBACK_LOOP_EXT;
+        }
         result
     }
 }
@@ -199,6 +216,9 @@ impl From<ash::vk::ImageUsageFlags> for ImageUsage {
             transient_attachment: !(val & ash::vk::ImageUsageFlags::TRANSIENT_ATTACHMENT)
                 .is_empty(),
             input_attachment: !(val & ash::vk::ImageUsageFlags::INPUT_ATTACHMENT).is_empty(),
+            attachment_feedback_loop: !(val
+                & ash::vk::ImageUsageFlags::ATTACHMENT_FEEDBACK_LOOP_EXT)
+                .is_empty(),
         }
     }
 }
@@ -217,6 +237,7 @@ impl BitOr for ImageUsage {
             depth_stencil_attachment: self.depth_stencil_attachment || rhs.depth_stencil_attachment,
             transient_attachment: self.transient_attachment || rhs.transient_attachment,
             input_attachment: self.input_attachment || rhs.input_attachment,
+            attachment_feedback_loop: self.attachment_feedback_loop || rhs.attachment_feedback_loop,
         }
     }
 }
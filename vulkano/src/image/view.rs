@@ -17,10 +17,10 @@ use super::{ImageAccess, ImageDimensions, ImageFormatInfo, ImageSubresourceRange
 use crate::{
     check_errors,
     device::{Device, DeviceOwned},
-    format::{ChromaSampling, Format, FormatFeatures},
+    format::{ChromaSampling, CompressionType, Format, FormatFeatures},
     image::{ImageAspects, ImageTiling, ImageType, SampleCount},
     sampler::{ycbcr::SamplerYcbcrConversion, ComponentMapping},
-    Error, OomError, VulkanObject,
+    Error, OomError, Version, VulkanObject,
 };
 use std::{
     error, fmt,
@@ -46,6 +46,7 @@ where
     subresource_range: ImageSubresourceRange,
     usage: ImageUsage,
     view_type: ImageViewType,
+    astc_decode_mode: Option<Format>,
 
     filter_cubic: bool,
     filter_cubic_minmax: bool,
@@ -77,7 +78,9 @@ where
             format,
             component_mapping,
             subresource_range,
+            usage: _,
             sampler_ycbcr_conversion,
+            astc_decode_mode,
             _ne: _,
         } = create_info;
 
@@ -115,6 +118,7 @@ where
             subresource_range,
             usage,
             sampler_ycbcr_conversion,
+            astc_decode_mode,
 
             filter_cubic,
             filter_cubic_minmax,
@@ -130,7 +134,9 @@ where
             format,
             component_mapping,
             ref subresource_range,
+            usage,
             ref sampler_ycbcr_conversion,
+            astc_decode_mode,
             _ne: _,
         } = create_info;
 
@@ -179,6 +185,18 @@ where
                 match image_inner.tiling() {
                     ImageTiling::Optimal => format_properties.optimal_tiling_features,
                     ImageTiling::Linear => format_properties.linear_tiling_features,
+                    ImageTiling::DrmFormatModifier => image_inner
+                        .device()
+                        .physical_device()
+                        .drm_format_modifier_properties(format)
+                        .into_iter()
+                        .find(|properties| {
+                            Some(properties.drm_format_modifier)
+                                == image_inner.drm_format_modifier()
+                        })
+                        .map_or_else(Default::default, |properties| {
+                            properties.drm_format_modifier_tiling_features
+                        }),
                 }
             } else {
                 *image_inner.format_features()
@@ -231,9 +249,32 @@ where
         }
 
         // Get usage
-        // Can be different from image usage, see
+        // Can be a subset of the image usage, see
         // https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/VkImageViewCreateInfo.html#_description
-        let usage = *image_inner.usage();
+        let usage = if usage == ImageUsage::none() {
+            *image_inner.usage()
+        } else {
+            let device = image_inner.device();
+
+            if !(device.api_version() >= Version::V1_1
+                || device.enabled_extensions().khr_maintenance2)
+            {
+                return Err(ImageViewCreationError::ExtensionNotEnabled {
+                    extension: "khr_maintenance2",
+                    reason: "usage was not empty",
+                });
+            }
+
+            // VUID-VkImageViewCreateInfo-pNext-02662
+            if *image_inner.usage() | usage != *image_inner.usage() {
+                return Err(ImageViewCreationError::UsageNotSupportedByImage {
+                    usage,
+                    supported_usage: *image_inner.usage(),
+                });
+            }
+
+            usage
+        };
 
         // Check for compatibility with the image
         let image_type = image.dimensions().image_type();
@@ -271,8 +312,11 @@ where
         if image_type == ImageType::Dim3d
             && (view_type == ImageViewType::Dim2d || view_type == ImageViewType::Dim2dArray)
         {
-            // VUID-VkImageViewCreateInfo-image-01005
-            if !image_inner.array_2d_compatible() {
+            // VUID-VkImageViewCreateInfo-image-06723
+            // VUID-VkImageViewCreateInfo-image-06728
+            if !(image_inner.array_2d_compatible()
+                || view_type == ImageViewType::Dim2d && image_inner.view_2d_compatible())
+            {
                 return Err(ImageViewCreationError::ImageNotArray2dCompatible);
             }
 
@@ -415,10 +459,18 @@ where
             }
         }
         // VUID-VkImageViewCreateInfo-image-01762
-        else if Some(format) != image_inner.format() {
+        else if !(image_inner.mutable_format()
+            && image_inner.format().unwrap().planes().is_empty())
+            && Some(format) != image_inner.format()
+        {
             return Err(ImageViewCreationError::FormatNotCompatible);
         }
 
+        // VUID-VkImageViewCreateInfo-pNext-01585
+        if !image_inner.view_formats().is_empty() && !image_inner.view_formats().contains(&format) {
+            return Err(ImageViewCreationError::FormatNotInViewFormats);
+        }
+
         // VUID-VkImageViewCreateInfo-imageViewType-04973
         if (view_type == ImageViewType::Dim1d
             || view_type == ImageViewType::Dim2d
@@ -480,6 +532,45 @@ where
             }
         }
 
+        if let Some(decode_mode) = astc_decode_mode {
+            let device = image_inner.device();
+
+            if !device.enabled_extensions().ext_astc_decode_mode {
+                return Err(ImageViewCreationError::ExtensionNotEnabled {
+                    extension: "ext_astc_decode_mode",
+                    reason: "astc_decode_mode was `Some`",
+                });
+            }
+
+            // VUID-VkImageViewASTCDecodeModeEXT-decodeMode-02230
+            if !matches!(
+                decode_mode,
+                Format::R16G16B16A16_SFLOAT
+                    | Format::R8G8B8A8_UNORM
+                    | Format::E5B9G9R9_UFLOAT_PACK32
+            ) {
+                return Err(ImageViewCreationError::AstcDecodeModeNotSupported { decode_mode });
+            }
+
+            // VUID-VkImageViewASTCDecodeModeEXT-decodeMode-02231
+            if decode_mode == Format::E5B9G9R9_UFLOAT_PACK32
+                && !device.enabled_features().decode_mode_shared_exponent
+            {
+                return Err(ImageViewCreationError::FeatureNotEnabled {
+                    feature: "decode_mode_shared_exponent",
+                    reason: "astc_decode_mode was `Some(Format::E5B9G9R9_UFLOAT_PACK32)`",
+                });
+            }
+
+            // VUID-VkImageViewASTCDecodeModeEXT-format-04084
+            if !matches!(
+                format.compression(),
+                Some(CompressionType::ASTC_LDR | CompressionType::ASTC_HDR)
+            ) {
+                return Err(ImageViewCreationError::AstcDecodeModeFormatNotAstc);
+            }
+        }
+
         Ok((format_features, usage))
     }
 
@@ -492,7 +583,9 @@ where
             format,
             component_mapping,
             ref subresource_range,
+            usage,
             ref sampler_ycbcr_conversion,
+            astc_decode_mode,
             _ne: _,
         } = create_info;
 
@@ -522,6 +615,31 @@ where
             create_info.p_next = sampler_ycbcr_conversion_info as *const _ as *const _;
         }
 
+        let mut astc_decode_mode_info =
+            astc_decode_mode.map(|decode_mode| ash::vk::ImageViewASTCDecodeModeEXT {
+                decode_mode: decode_mode.into(),
+                ..Default::default()
+            });
+
+        if let Some(astc_decode_mode_info) = astc_decode_mode_info.as_mut() {
+            astc_decode_mode_info.p_next = create_info.p_next;
+            create_info.p_next = astc_decode_mode_info as *const _ as *const _;
+        }
+
+        let mut usage_info = if usage != ImageUsage::none() {
+            Some(ash::vk::ImageViewUsageCreateInfo {
+                usage: usage.into(),
+                ..Default::default()
+            })
+        } else {
+            None
+        };
+
+        if let Some(usage_info) = usage_info.as_mut() {
+            usage_info.p_next = create_info.p_next;
+            create_info.p_next = usage_info as *const _ as *const _;
+        }
+
         let handle = {
             let fns = image_inner.device().fns();
             let mut output = MaybeUninit::uninit();
@@ -534,6 +652,8 @@ where
             output.assume_init()
         };
 
+        image_inner.device().set_automatic_debug_name(handle);
+
         Ok(handle)
     }
 
@@ -545,11 +665,42 @@ where
         Self::new(image, create_info)
     }
 
+    /// Creates a `Dim2d` view of the mip level `mip_level` for each array layer of `image`, so
+    /// that each face of a cube or cube array image can be used individually, for example as a
+    /// framebuffer attachment. Equivalent to calling `ImageView::new` with
+    /// `ImageViewCreateInfo::cube_face` for every array layer.
+    pub fn new_cube_faces(
+        image: Arc<I>,
+        mip_level: u32,
+    ) -> Result<Vec<Arc<ImageView<I>>>, ImageViewCreationError> {
+        (0..image.dimensions().array_layers())
+            .map(|array_layer| {
+                let create_info = ImageViewCreateInfo::cube_face(&image, array_layer, mip_level);
+                Self::new(image.clone(), create_info)
+            })
+            .collect()
+    }
+
+    /// Creates a `CubeArray` view covering the whole of `image`. Equivalent to
+    /// `ImageView::new(image, ImageViewCreateInfo::cube_array(image))`.
+    #[inline]
+    pub fn new_cube_array(image: Arc<I>) -> Result<Arc<ImageView<I>>, ImageViewCreationError> {
+        let create_info = ImageViewCreateInfo::cube_array(&image);
+        Self::new(image, create_info)
+    }
+
     /// Returns the wrapped image that this image view was created from.
     #[inline]
     pub fn image(&self) -> &Arc<I> {
         &self.image
     }
+
+    /// Returns the intermediate format that ASTC texels are decoded to, if it was specified when
+    /// creating the image view.
+    #[inline]
+    pub fn astc_decode_mode(&self) -> Option<Format> {
+        self.astc_decode_mode
+    }
 }
 
 impl<I> Drop for ImageView<I>
@@ -625,7 +776,13 @@ pub struct ImageViewCreateInfo {
     /// The format of the image view.
     ///
     /// If this is set to a format that is different from the image, the image must be created with
-    /// the `mutable_format` flag.
+    /// the `mutable_format` flag. The format must then be in the same
+    /// [compatibility class](Format::compatibility) as the format of the image, which allows the
+    /// data of the image to be reinterpreted, for example viewing an `R32_UINT` image as
+    /// `R8G8B8A8_UNORM`. For multi-planar images, the format must instead be compatible with the
+    /// plane selected in `subresource_range`, and for images created with the
+    /// `block_texel_view_compatible` flag, it may also be an uncompressed format with the same
+    /// block size.
     ///
     /// The default value is `None`, which must be overridden.
     pub format: Option<Format>,
@@ -652,6 +809,33 @@ pub struct ImageViewCreateInfo {
     /// The default value is `None`.
     pub sampler_ycbcr_conversion: Option<Arc<SamplerYcbcrConversion>>,
 
+    /// How the image view is going to be used. If this is empty, the usage of the image is used.
+    ///
+    /// If not empty, this must be a subset of the usage of the image, and the device API version
+    /// must be at least 1.1, or the
+    /// [`khr_maintenance2`](crate::device::DeviceExtensions::khr_maintenance2) extension must be
+    /// enabled on the device. This allows creating a view whose format doesn't support all the
+    /// usages of a mutable format image, for example an sRGB color attachment view of a swapchain
+    /// image that also has the `storage` usage.
+    ///
+    /// The default value is [`ImageUsage::none()`].
+    pub usage: ImageUsage,
+
+    /// For image views with an ASTC format, the intermediate format that texels are decoded to
+    /// before they are filtered. If `None`, texels are decoded to 16-bit floating point values.
+    ///
+    /// Decoding to a lower precision can reduce power consumption and memory bandwidth. The
+    /// allowed values are `R16G16B16A16_SFLOAT`, `R8G8B8A8_UNORM` and, if the
+    /// [`decode_mode_shared_exponent`](crate::device::Features::decode_mode_shared_exponent)
+    /// feature is enabled, `E5B9G9R9_UFLOAT_PACK32`.
+    ///
+    /// If not `None`, the
+    /// [`ext_astc_decode_mode`](crate::device::DeviceExtensions::ext_astc_decode_mode) extension
+    /// must be enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub astc_decode_mode: Option<Format>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -667,7 +851,9 @@ impl Default for ImageViewCreateInfo {
                 array_layers: 0..0,
                 mip_levels: 0..0,
             },
+            usage: ImageUsage::none(),
             sampler_ycbcr_conversion: None,
+            astc_decode_mode: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -698,6 +884,70 @@ impl ImageViewCreateInfo {
             ..Default::default()
         }
     }
+
+    /// Returns an `ImageViewCreateInfo` with the `Cube` view type, covering the six array layers
+    /// of the cube with index `cube_index`, and all mip levels of the image.
+    ///
+    /// The image must have been created with the `cube_compatible` flag.
+    pub fn cube<I>(image: &I, cube_index: u32) -> Self
+    where
+        I: ImageAccess + ?Sized,
+    {
+        let first_layer = cube_index * 6;
+
+        Self {
+            view_type: ImageViewType::Cube,
+            format: Some(image.format()),
+            subresource_range: ImageSubresourceRange {
+                array_layers: first_layer..first_layer + 6,
+                ..image.subresource_range()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Returns an `ImageViewCreateInfo` with the `CubeArray` view type, covering all array layers
+    /// and mip levels of the image.
+    ///
+    /// The image must have been created with the `cube_compatible` flag, its number of array
+    /// layers must be a multiple of 6, and the
+    /// [`image_cube_array`](crate::device::Features::image_cube_array) feature must be enabled on
+    /// the device.
+    pub fn cube_array<I>(image: &I) -> Self
+    where
+        I: ImageAccess + ?Sized,
+    {
+        Self {
+            view_type: ImageViewType::CubeArray,
+            format: Some(image.format()),
+            subresource_range: image.subresource_range(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns an `ImageViewCreateInfo` with the `Dim2d` view type, covering a single face of a
+    /// cube image and a single mip level of the image.
+    ///
+    /// `array_layer` is the array layer of the face in the image. The faces of the cube with index
+    /// `n` are stored in layers `6 * n` to `6 * n + 5`, in the order +X, -X, +Y, -Y, +Z, -Z.
+    ///
+    /// A view that is used as a framebuffer attachment must contain exactly one mip level, which
+    /// is `mip_level`.
+    pub fn cube_face<I>(image: &I, array_layer: u32, mip_level: u32) -> Self
+    where
+        I: ImageAccess + ?Sized,
+    {
+        Self {
+            view_type: ImageViewType::Dim2d,
+            format: Some(image.format()),
+            subresource_range: ImageSubresourceRange {
+                array_layers: array_layer..array_layer + 1,
+                mip_levels: mip_level..mip_level + 1,
+                ..image.subresource_range()
+            },
+            ..Default::default()
+        }
+    }
 }
 
 /// Error that can happen when creating an image view.
@@ -706,6 +956,10 @@ pub enum ImageViewCreationError {
     /// Allocating memory failed.
     OomError(OomError),
 
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
     FeatureNotEnabled {
         feature: &'static str,
         reason: &'static str,
@@ -718,6 +972,13 @@ pub enum ImageViewCreationError {
     /// The specified range of array layers was not a subset of those in the image.
     ArrayLayersOutOfRange { range_end: u32, max: u32 },
 
+    /// An ASTC decode mode was specified, but the format of the image view was not an ASTC format.
+    AstcDecodeModeFormatNotAstc,
+
+    /// The specified ASTC decode mode is not one of the formats that ASTC texels can be decoded
+    /// to.
+    AstcDecodeModeNotSupported { decode_mode: Format },
+
     /// The image has the `block_texel_view_compatible` flag, but a range of multiple array layers
     /// was specified.
     BlockTexelViewCompatibleMultipleArrayLayers,
@@ -737,6 +998,10 @@ pub enum ImageViewCreationError {
     /// The requested format was not compatible with the image.
     FormatNotCompatible,
 
+    /// The image was created with a non-empty `view_formats` list, but the requested format was
+    /// not in it.
+    FormatNotInViewFormats,
+
     /// The given format was not supported by the device.
     FormatNotSupported,
 
@@ -758,7 +1023,7 @@ pub enum ImageViewCreationError {
     ImageMissingUsage,
 
     /// A 2D image view was requested from a 3D image, but the image was not created with the
-    /// `array_2d_compatible` flag.
+    /// `array_2d_compatible` flag, or for a `Dim2d` view, the `view_2d_compatible` flag.
     ImageNotArray2dCompatible,
 
     /// A cube image view type was requested, but the image was not created with the
@@ -793,6 +1058,12 @@ pub enum ImageViewCreationError {
     /// A non-arrayed image view type was specified, but a range of multiple array layers was
     /// specified.
     TypeNonArrayedMultipleArrayLayers,
+
+    /// The requested usage was not a subset of the usage of the image.
+    UsageNotSupportedByImage {
+        usage: ImageUsage,
+        supported_usage: ImageUsage,
+    },
 }
 
 impl error::Error for ImageViewCreationError {
@@ -813,6 +1084,11 @@ impl fmt::Display for ImageViewCreationError {
                 fmt,
                 "allocating memory failed",
             ),
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
             Self::FeatureNotEnabled { feature, reason } => {
                 write!(fmt, "the feature {} must be enabled: {}", feature, reason)
             }
@@ -824,6 +1100,15 @@ impl fmt::Display for ImageViewCreationError {
                 fmt,
                 "the specified range of array layers was not a subset of those in the image",
             ),
+            Self::AstcDecodeModeFormatNotAstc => write!(
+                fmt,
+                "an ASTC decode mode was specified, but the format of the image view was not an ASTC format",
+            ),
+            Self::AstcDecodeModeNotSupported { decode_mode } => write!(
+                fmt,
+                "the specified ASTC decode mode {:?} is not one of the formats that ASTC texels can be decoded to",
+                decode_mode,
+            ),
             Self::BlockTexelViewCompatibleMultipleArrayLayers => write!(
                 fmt,
                 "the image has the `block_texel_view_compatible` flag, but a range of multiple array layers was specified",
@@ -844,6 +1129,10 @@ impl fmt::Display for ImageViewCreationError {
                 fmt,
                 "the requested format was not compatible with the image",
             ),
+            Self::FormatNotInViewFormats => write!(
+                fmt,
+                "the image was created with a non-empty `view_formats` list, but the requested format was not in it",
+            ),
             Self::FormatNotSupported => write!(
                 fmt,
                 "the given format was not supported by the device"
@@ -866,7 +1155,7 @@ impl fmt::Display for ImageViewCreationError {
             ),
             Self::ImageNotArray2dCompatible => write!(
                 fmt,
-                "a 2D image view was requested from a 3D image, but the image was not created with the `array_2d_compatible` flag",
+                "a 2D image view was requested from a 3D image, but the image was not created with the `array_2d_compatible` or `view_2d_compatible` flag",
             ),
             Self::ImageNotCubeCompatible => write!(
                 fmt,
@@ -903,7 +1192,11 @@ impl fmt::Display for ImageViewCreationError {
             Self::TypeNonArrayedMultipleArrayLayers => write!(
                 fmt,
                 "a non-arrayed image view type was specified, but a range of multiple array layers was specified"
-            )
+            ),
+            Self::UsageNotSupportedByImage { .. } => write!(
+                fmt,
+                "the requested usage was not a subset of the usage of the image",
+            ),
         }
     }
 }
@@ -1164,3 +1457,146 @@ impl Hash for dyn ImageViewAbstract {
         self.device().hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ImageView, ImageViewAbstract, ImageViewCreateInfo, ImageViewCreationError};
+    use crate::{
+        format::Format,
+        image::{
+            ImageCreateFlags, ImageDimensions, ImageUsage, StorageImage, StorageImageCreateInfo,
+        },
+        Version,
+    };
+
+    #[test]
+    fn mutable_format_compatible_view() {
+        let (device, _) = gfx_dev_and_queue!();
+        let image = StorageImage::with_create_info(
+            device,
+            StorageImageCreateInfo {
+                flags: ImageCreateFlags {
+                    mutable_format: true,
+                    ..ImageCreateFlags::none()
+                },
+                ..StorageImageCreateInfo::dimensions_format(
+                    ImageDimensions::Dim2d {
+                        width: 32,
+                        height: 32,
+                        array_layers: 1,
+                    },
+                    Format::R32_UINT,
+                )
+            },
+        )
+        .unwrap();
+
+        let view = ImageView::new(
+            image.clone(),
+            ImageViewCreateInfo {
+                format: Some(Format::R8G8B8A8_UNORM),
+                ..ImageViewCreateInfo::from_image(&*image)
+            },
+        )
+        .unwrap();
+        assert_eq!(view.format(), Some(Format::R8G8B8A8_UNORM));
+
+        match ImageView::new(
+            image.clone(),
+            ImageViewCreateInfo {
+                format: Some(Format::R16_UINT),
+                ..ImageViewCreateInfo::from_image(&*image)
+            },
+        ) {
+            Err(ImageViewCreationError::FormatNotCompatible) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn different_format_requires_mutable_format() {
+        let (device, _) = gfx_dev_and_queue!();
+        let image = StorageImage::with_create_info(
+            device,
+            StorageImageCreateInfo::dimensions_format(
+                ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                Format::R32_UINT,
+            ),
+        )
+        .unwrap();
+
+        match ImageView::new(
+            image.clone(),
+            ImageViewCreateInfo {
+                format: Some(Format::R8G8B8A8_UNORM),
+                ..ImageViewCreateInfo::from_image(&*image)
+            },
+        ) {
+            Err(ImageViewCreationError::FormatNotCompatible) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn view_usage() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if !(device.api_version() >= Version::V1_1 || device.enabled_extensions().khr_maintenance2)
+        {
+            return;
+        }
+
+        let image = StorageImage::with_create_info(
+            device,
+            StorageImageCreateInfo {
+                flags: ImageCreateFlags {
+                    mutable_format: true,
+                    ..ImageCreateFlags::none()
+                },
+                ..StorageImageCreateInfo::dimensions_format(
+                    ImageDimensions::Dim2d {
+                        width: 32,
+                        height: 32,
+                        array_layers: 1,
+                    },
+                    Format::R8G8B8A8_UNORM,
+                )
+            },
+        )
+        .unwrap();
+
+        // sRGB formats usually don't support storage, so the view must leave it out.
+        let view = ImageView::new(
+            image.clone(),
+            ImageViewCreateInfo {
+                format: Some(Format::R8G8B8A8_SRGB),
+                usage: ImageUsage {
+                    sampled: true,
+                    ..ImageUsage::none()
+                },
+                ..ImageViewCreateInfo::from_image(&*image)
+            },
+        )
+        .unwrap();
+        assert!(view.usage().sampled);
+        assert!(!view.usage().storage);
+
+        match ImageView::new(
+            image.clone(),
+            ImageViewCreateInfo {
+                usage: ImageUsage {
+                    color_attachment: true,
+                    ..ImageUsage::none()
+                },
+                ..ImageViewCreateInfo::from_image(&*image)
+            },
+        ) {
+            Err(ImageViewCreationError::UsageNotSupportedByImage { .. }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,454 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Buffers that hold indirect draw commands, along with their count and per-instance data.
+//!
+//! Drawing with [`draw_indirect_count`] or [`draw_indexed_indirect_count`] needs a buffer of draw
+//! commands and a buffer that holds their number. They are often written by a compute shader,
+//! which also reads per-instance data that the draw commands refer to with their
+//! `first_instance`. An [`IndirectDrawBuffer`] lays these three parts out in a single buffer:
+//!
+//! - The count, a `u32`.
+//! - An array of draw commands of type `C`, either [`DrawIndirectCommand`] or
+//!   [`DrawIndexedIndirectCommand`].
+//! - An optional array of per-instance data of type `I`.
+//!
+//! Each part starts at an offset that is suitably aligned for it to be bound as a separate storage
+//! buffer, and each of them is available as a typed [`Subbuffer`]. Since the element types are
+//! used on both sides, the host and the shaders can't disagree on the strides.
+//!
+//! The draw command types have the same layout as the following structs in GLSL, with the `std430`
+//! layout:
+//!
+//! ```glsl
+//! struct DrawIndirectCommand {
+//!     uint vertex_count;
+//!     uint instance_count;
+//!     uint first_vertex;
+//!     uint first_instance;
+//! };
+//!
+//! struct DrawIndexedIndirectCommand {
+//!     uint index_count;
+//!     uint instance_count;
+//!     uint first_index;
+//!     int vertex_offset;
+//!     uint first_instance;
+//! };
+//! ```
+//!
+//! The instance type can be a struct generated by `vulkano_shaders::shader!` from the declaration
+//! in the shader, provided that it derives `Pod` and `Zeroable` through the `types_meta` option of
+//! the macro.
+//!
+//! [`draw_indirect_count`]: crate::command_buffer::AutoCommandBufferBuilder::draw_indirect_count
+//! [`draw_indexed_indirect_count`]: crate::command_buffer::AutoCommandBufferBuilder::draw_indexed_indirect_count
+//!
+//! # Example
+//!
+//! ```
+//! use vulkano::{
+//!     buffer::BufferLocation,
+//!     command_buffer::DrawIndexedIndirectCommand,
+//!     indirect::{IndirectDrawBuffer, IndirectDrawBufferCreateInfo},
+//! };
+//!
+//! #[repr(C)]
+//! #[derive(Clone, Copy, Default, bytemuck::Zeroable, bytemuck::Pod)]
+//! struct Instance {
+//!     model: [[f32; 4]; 4],
+//! }
+//!
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! # let mut builder: vulkano::command_buffer::AutoCommandBufferBuilder<vulkano::command_buffer::PrimaryAutoCommandBuffer> = return;
+//! let draws = IndirectDrawBuffer::<DrawIndexedIndirectCommand, Instance>::new(
+//!     device.clone(),
+//!     IndirectDrawBufferCreateInfo {
+//!         command_capacity: 1024,
+//!         instance_capacity: 1024,
+//!         location: BufferLocation::Upload,
+//!         ..Default::default()
+//!     },
+//! )
+//! .unwrap();
+//!
+//! draws.instances().unwrap().write().unwrap()[0] = Instance::default();
+//!
+//! // Inside a render pass, after a compute shader filled `draws.commands()` and `draws.count()`:
+//! draws.draw(&mut builder).unwrap();
+//! ```
+
+use crate::{
+    buffer::{
+        Buffer, BufferCreateInfo, BufferCreationError, BufferLocation, BufferUsage, Subbuffer,
+    },
+    command_buffer::{
+        AutoCommandBufferBuilder, CopyError, DrawIndexedIndirectCommand, DrawIndexedIndirectError,
+        DrawIndirectCommand, DrawIndirectError, FillBufferInfo,
+    },
+    device::{Device, DeviceOwned},
+    DeviceSize,
+};
+use bytemuck::Pod;
+use std::{
+    mem::{align_of, size_of},
+    sync::Arc,
+};
+
+/// A buffer that holds a count, an array of indirect draw commands of type `C`, and an array of
+/// per-instance data of type `I`.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug)]
+pub struct IndirectDrawBuffer<C, I = ()> {
+    buffer: Arc<Buffer>,
+    layout: IndirectDrawBufferLayout,
+    count: Arc<Subbuffer<u32>>,
+    commands: Arc<Subbuffer<[C]>>,
+    instances: Option<Arc<Subbuffer<[I]>>>,
+}
+
+impl<C, I> IndirectDrawBuffer<C, I>
+where
+    C: Pod + Send + Sync,
+    I: Pod + Send + Sync,
+{
+    /// Creates a new `IndirectDrawBuffer`.
+    ///
+    /// The buffer is created with the `indirect_buffer`, `storage_buffer` and `transfer_dst`
+    /// usages, in addition to `create_info.usage`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.command_capacity` is 0.
+    /// - Panics if `C` has zero size.
+    /// - Panics if `create_info.instance_capacity` is not 0 and `I` has zero size.
+    pub fn new(
+        device: Arc<Device>,
+        create_info: IndirectDrawBufferCreateInfo,
+    ) -> Result<IndirectDrawBuffer<C, I>, BufferCreationError> {
+        let IndirectDrawBufferCreateInfo {
+            command_capacity,
+            instance_capacity,
+            location,
+            usage,
+            _ne: _,
+        } = create_info;
+
+        assert!(command_capacity != 0, "the command capacity must not be 0");
+        assert!(
+            size_of::<C>() != 0,
+            "the command type must not have zero size"
+        );
+        assert!(
+            instance_capacity == 0 || size_of::<I>() != 0,
+            "the instance type must not have zero size"
+        );
+
+        let layout =
+            IndirectDrawBufferLayout::new::<C, I>(&device, command_capacity, instance_capacity);
+
+        let buffer = Buffer::new(
+            device,
+            BufferCreateInfo {
+                location,
+                size: layout.size,
+                usage: BufferUsage {
+                    indirect_buffer: true,
+                    storage_buffer: true,
+                    transfer_dst: true,
+                    ..usage
+                },
+                ..Default::default()
+            },
+        )?;
+        let bytes = buffer.subbuffer();
+
+        let count = bytes
+            .slice(layout.count_offset..layout.count_offset + size_of::<u32>() as DeviceSize)
+            .and_then(|subbuffer| subbuffer.cast_sized())
+            .unwrap();
+        let commands = bytes
+            .slice(layout.commands_offset..layout.commands_offset + layout.commands_size)
+            .and_then(|subbuffer| subbuffer.cast_slice())
+            .unwrap();
+        let instances = (instance_capacity != 0).then(|| {
+            bytes
+                .slice(layout.instances_offset..layout.instances_offset + layout.instances_size)
+                .and_then(|subbuffer| subbuffer.cast_slice())
+                .unwrap()
+        });
+
+        Ok(IndirectDrawBuffer {
+            buffer,
+            layout,
+            count,
+            commands,
+            instances,
+        })
+    }
+
+    /// Returns the buffer that holds all the parts.
+    #[inline]
+    pub fn buffer(&self) -> &Arc<Buffer> {
+        &self.buffer
+    }
+
+    /// Returns the offsets and sizes of the parts within the buffer.
+    #[inline]
+    pub fn layout(&self) -> &IndirectDrawBufferLayout {
+        &self.layout
+    }
+
+    /// Returns the number of draw commands that the buffer can hold.
+    #[inline]
+    pub fn command_capacity(&self) -> u32 {
+        self.commands.len() as u32
+    }
+
+    /// Returns the number of instances that the buffer can hold.
+    #[inline]
+    pub fn instance_capacity(&self) -> u32 {
+        self.instances
+            .as_ref()
+            .map_or(0, |instances| instances.len() as u32)
+    }
+
+    /// Returns the subbuffer that holds the number of draw commands to execute.
+    #[inline]
+    pub fn count(&self) -> &Arc<Subbuffer<u32>> {
+        &self.count
+    }
+
+    /// Returns the subbuffer that holds the draw commands.
+    #[inline]
+    pub fn commands(&self) -> &Arc<Subbuffer<[C]>> {
+        &self.commands
+    }
+
+    /// Returns the subbuffer that holds the per-instance data, or `None` if the buffer was
+    /// created with an instance capacity of 0.
+    #[inline]
+    pub fn instances(&self) -> Option<&Arc<Subbuffer<[I]>>> {
+        self.instances.as_ref()
+    }
+
+    /// Records the reset of the count to 0 into `builder`, which must be outside of a render
+    /// pass. This is typically done before a compute shader appends draw commands to the buffer.
+    #[inline]
+    pub fn reset_count<L, P>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+    ) -> Result<(), CopyError> {
+        builder.fill_buffer(FillBufferInfo::dst_buffer(self.count.clone()))?;
+
+        Ok(())
+    }
+}
+
+impl<I> IndirectDrawBuffer<DrawIndirectCommand, I>
+where
+    I: Pod + Send + Sync,
+{
+    /// Records the drawing of the first [`count`](Self::count) draw commands, with the graphics
+    /// pipeline that is bound.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if neither the
+    ///   [`draw_indirect_count`](crate::device::Features::draw_indirect_count) feature nor the
+    ///   [`khr_draw_indirect_count`](crate::device::DeviceExtensions::khr_draw_indirect_count)
+    ///   extension is enabled on the device.
+    #[inline]
+    pub fn draw<L, P>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+    ) -> Result<(), DrawIndirectError> {
+        builder.draw_indirect_count(self.commands.clone(), self.count.clone())?;
+
+        Ok(())
+    }
+}
+
+impl<I> IndirectDrawBuffer<DrawIndexedIndirectCommand, I>
+where
+    I: Pod + Send + Sync,
+{
+    /// Records the drawing of the first [`count`](Self::count) draw commands, with the graphics
+    /// pipeline and the index buffer that are bound.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if neither the
+    ///   [`draw_indirect_count`](crate::device::Features::draw_indirect_count) feature nor the
+    ///   [`khr_draw_indirect_count`](crate::device::DeviceExtensions::khr_draw_indirect_count)
+    ///   extension is enabled on the device.
+    #[inline]
+    pub fn draw<L, P>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+    ) -> Result<(), DrawIndexedIndirectError> {
+        builder.draw_indexed_indirect_count(self.commands.clone(), self.count.clone())?;
+
+        Ok(())
+    }
+}
+
+unsafe impl<C, I> DeviceOwned for IndirectDrawBuffer<C, I> {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.buffer.device()
+    }
+}
+
+/// Parameters to create a new `IndirectDrawBuffer`.
+#[derive(Clone, Debug)]
+pub struct IndirectDrawBufferCreateInfo {
+    /// The number of draw commands that the buffer can hold.
+    ///
+    /// The default value is `0`, which must be overridden.
+    pub command_capacity: u32,
+
+    /// The number of instances that the buffer can hold. If 0, the buffer has no per-instance
+    /// data.
+    ///
+    /// The default value is `0`.
+    pub instance_capacity: u32,
+
+    /// Where the memory of the buffer is allocated. Use a host-accessible location to write the
+    /// parts of the buffer from the host.
+    ///
+    /// The default value is [`BufferLocation::Device`].
+    pub location: BufferLocation,
+
+    /// Additional usages of the buffer.
+    ///
+    /// The default value is [`BufferUsage::none()`].
+    pub usage: BufferUsage,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for IndirectDrawBufferCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            command_capacity: 0,
+            instance_capacity: 0,
+            location: BufferLocation::Device,
+            usage: BufferUsage::none(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// The offsets and sizes in bytes of the parts of an [`IndirectDrawBuffer`].
+///
+/// Every offset is a multiple of the
+/// [`min_storage_buffer_offset_alignment`](crate::device::Properties::min_storage_buffer_offset_alignment)
+/// of the device, of 4, and of the alignment of the type of the part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndirectDrawBufferLayout {
+    /// The offset of the count.
+    pub count_offset: DeviceSize,
+
+    /// The offset of the array of draw commands.
+    pub commands_offset: DeviceSize,
+
+    /// The size of the array of draw commands.
+    pub commands_size: DeviceSize,
+
+    /// The offset of the array of per-instance data. This is equal to the size of the buffer if
+    /// there is no per-instance data.
+    pub instances_offset: DeviceSize,
+
+    /// The size of the array of per-instance data.
+    pub instances_size: DeviceSize,
+
+    /// The size of the whole buffer.
+    pub size: DeviceSize,
+}
+
+impl IndirectDrawBufferLayout {
+    fn new<C, I>(device: &Device, command_capacity: u32, instance_capacity: u32) -> Self {
+        let storage_alignment = device
+            .physical_device()
+            .properties()
+            .min_storage_buffer_offset_alignment;
+        let alignment = |type_alignment: usize| {
+            // All of these are powers of two.
+            storage_alignment.max(4).max(type_alignment as DeviceSize)
+        };
+        let align_up = |offset: DeviceSize, alignment: DeviceSize| {
+            (offset + alignment - 1) / alignment * alignment
+        };
+
+        let count_offset = 0;
+        let commands_offset = align_up(
+            count_offset + size_of::<u32>() as DeviceSize,
+            alignment(align_of::<C>()),
+        );
+        let commands_size = command_capacity as DeviceSize * size_of::<C>() as DeviceSize;
+        let instances_size = instance_capacity as DeviceSize * size_of::<I>() as DeviceSize;
+        let instances_offset = if instances_size != 0 {
+            align_up(commands_offset + commands_size, alignment(align_of::<I>()))
+        } else {
+            commands_offset + commands_size
+        };
+
+        IndirectDrawBufferLayout {
+            count_offset,
+            commands_offset,
+            commands_size,
+            instances_offset,
+            instances_size,
+            size: instances_offset + instances_size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IndirectDrawBuffer, IndirectDrawBufferCreateInfo};
+    use crate::{buffer::BufferLocation, command_buffer::DrawIndexedIndirectCommand};
+
+    #[test]
+    fn layout() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let draws = IndirectDrawBuffer::<DrawIndexedIndirectCommand, [f32; 4]>::new(
+            device.clone(),
+            IndirectDrawBufferCreateInfo {
+                command_capacity: 3,
+                instance_capacity: 5,
+                location: BufferLocation::Upload,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let layout = *draws.layout();
+        let alignment = device
+            .physical_device()
+            .properties()
+            .min_storage_buffer_offset_alignment
+            .max(4);
+
+        assert_eq!(draws.command_capacity(), 3);
+        assert_eq!(draws.instance_capacity(), 5);
+        assert_eq!(draws.commands().offset(), layout.commands_offset);
+        assert_eq!(draws.commands().size(), 3 * 20);
+        assert_eq!(draws.instances().unwrap().size(), 5 * 16);
+        assert_eq!(layout.commands_offset % alignment, 0);
+        assert_eq!(layout.instances_offset % alignment, 0);
+        assert!(layout.instances_offset >= layout.commands_offset + layout.commands_size);
+
+        draws.instances().unwrap().write().unwrap()[4] = [1.0; 4];
+        *draws.count().write().unwrap() = 2;
+    }
+}
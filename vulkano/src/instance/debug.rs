@@ -41,16 +41,50 @@
 //! be callable. If you don't store the return value of `DebugUtilsMessenger`'s constructor in a
 //! variable, it will be immediately destroyed and your callback will not work.
 //!
+//! # Filtering messages
+//!
+//! A [`DebugUtilsMessageFilter`] wraps a callback so that it is only called for the messages
+//! that pass the filter. This can be used to silence known messages by their ID:
+//!
+//! ```
+//! # use vulkano::instance::Instance;
+//! # use std::sync::Arc;
+//! # let instance: Arc<Instance> = return;
+//! use vulkano::instance::debug::{
+//!     DebugUtilsMessageFilter, DebugUtilsMessageSeverityLevel, DebugUtilsMessenger,
+//!     DebugUtilsMessengerCreateInfo,
+//! };
+//!
+//! let filter = DebugUtilsMessageFilter::new()
+//!     .min_severity(DebugUtilsMessageSeverityLevel::Warning)
+//!     .ignore_id("UNASSIGNED-BestPractices-vkCreateInstance-specialuse-extension");
+//!
+//! let _callback = unsafe {
+//!     DebugUtilsMessenger::new(
+//!         instance,
+//!         DebugUtilsMessengerCreateInfo::user_callback(filter.callback(Arc::new(|msg| {
+//!             println!("{:?}: {}", msg.vuid, msg.description);
+//!         }))),
+//!     ).ok()
+//! };
+//! ```
+//!
+//! With the `log` or `tracing` features of vulkano, [`log_callback`] and [`tracing_callback`]
+//! return callbacks that forward the messages to these crates.
+//!
 
 use super::Instance;
 use crate::{check_errors, Error, VulkanObject};
+use smallvec::SmallVec;
 use std::{
+    collections::HashSet,
     error,
     ffi::{c_void, CStr},
     fmt,
     mem::MaybeUninit,
+    os::raw::c_char,
     panic::{catch_unwind, AssertUnwindSafe, RefUnwindSafe},
-    ptr,
+    ptr, slice,
     sync::Arc,
 };
 
@@ -204,25 +238,42 @@ pub(super) unsafe extern "system" fn trampoline(
     let _ = catch_unwind(AssertUnwindSafe(move || {
         let user_callback = user_data as *mut UserCallback as *const _;
         let user_callback: &UserCallback = &*user_callback;
+        let callback_data = &*callback_data;
 
-        let layer_prefix = (*callback_data)
+        let to_str = |ptr: *const c_char| {
+            CStr::from_ptr(ptr)
+                .to_str()
+                .expect("debug callback message not utf-8")
+        };
+
+        let layer_prefix = callback_data
             .p_message_id_name
             .as_ref()
-            .map(|msg_id_name| {
-                CStr::from_ptr(msg_id_name)
-                    .to_str()
-                    .expect("debug callback message not utf-8")
-            });
-
-        let description = CStr::from_ptr((*callback_data).p_message)
-            .to_str()
-            .expect("debug callback message not utf-8");
+            .map(|msg_id_name| to_str(msg_id_name));
+
+        let description = to_str(callback_data.p_message);
+
+        let objects: SmallVec<[_; 4]> = if callback_data.object_count == 0 {
+            SmallVec::new()
+        } else {
+            slice::from_raw_parts(callback_data.p_objects, callback_data.object_count as usize)
+                .iter()
+                .map(|object| DebugUtilsMessageObject {
+                    object_type: object.object_type,
+                    object_handle: object.object_handle,
+                    object_name: object.p_object_name.as_ref().map(|name| to_str(name)),
+                })
+                .collect()
+        };
 
         let message = Message {
             severity: message_severity.into(),
             ty: message_types.into(),
             layer_prefix,
             description,
+            message_id_number: callback_data.message_id_number,
+            vuid: layer_prefix.filter(|name| name.starts_with("VUID-")),
+            objects: &objects,
         };
 
         user_callback(&message);
@@ -319,15 +370,184 @@ impl fmt::Debug for DebugUtilsMessengerCreateInfo {
 }
 
 /// A message received by the callback.
+#[derive(Clone, Debug)]
 pub struct Message<'a> {
     /// Severity of message.
     pub severity: DebugUtilsMessageSeverity,
     /// Type of message,
     pub ty: DebugUtilsMessageType,
-    /// Prefix of the layer that reported this message or `None` if unknown.
+    /// The name of the ID of the message, or `None` if unknown. For the validation layers, this
+    /// is the VUID of the message, or a name starting with `UNASSIGNED-` for the checks that
+    /// don't have one.
     pub layer_prefix: Option<&'a str>,
     /// Description of the message.
     pub description: &'a str,
+    /// The number of the ID of the message. The validation layers derive it from the ID name.
+    pub message_id_number: i32,
+    /// The Valid Usage ID of the specification that the message is about, if there is one.
+    pub vuid: Option<&'a str>,
+    /// The objects that the message is about.
+    pub objects: &'a [DebugUtilsMessageObject<'a>],
+}
+
+impl<'a> Message<'a> {
+    /// Returns the highest severity level of the message.
+    ///
+    /// The implementation should only set one level, but if `severity` is empty, this returns
+    /// `Verbose`.
+    #[inline]
+    pub fn severity_level(&self) -> DebugUtilsMessageSeverityLevel {
+        if self.severity.error {
+            DebugUtilsMessageSeverityLevel::Error
+        } else if self.severity.warning {
+            DebugUtilsMessageSeverityLevel::Warning
+        } else if self.severity.information {
+            DebugUtilsMessageSeverityLevel::Information
+        } else {
+            DebugUtilsMessageSeverityLevel::Verbose
+        }
+    }
+}
+
+/// An object that a message received by the callback is about.
+#[derive(Clone, Copy, Debug)]
+pub struct DebugUtilsMessageObject<'a> {
+    /// The type of the object.
+    pub object_type: ash::vk::ObjectType,
+    /// The raw handle of the object.
+    pub object_handle: u64,
+    /// The debug name that was given to the object, if any.
+    pub object_name: Option<&'a str>,
+}
+
+/// A single level of severity of a message, ordered from the least to the most severe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DebugUtilsMessageSeverityLevel {
+    /// Diagnostic information from the loader and layers.
+    Verbose,
+    /// An informational message that may be handy when debugging an application.
+    Information,
+    /// An unexpected use.
+    Warning,
+    /// An error that may cause undefined results, including an application crash.
+    Error,
+}
+
+/// Filters the messages that are passed to a callback.
+///
+/// All messages pass an empty filter.
+#[derive(Clone, Debug, Default)]
+pub struct DebugUtilsMessageFilter {
+    min_severity: Option<DebugUtilsMessageSeverityLevel>,
+    ignored_ids: HashSet<String>,
+    ignored_id_numbers: HashSet<i32>,
+}
+
+impl DebugUtilsMessageFilter {
+    /// Returns a filter that all messages pass.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects the messages that are less severe than `level`.
+    #[inline]
+    pub fn min_severity(mut self, level: DebugUtilsMessageSeverityLevel) -> Self {
+        self.min_severity = Some(level);
+        self
+    }
+
+    /// Rejects the messages whose ID name is `id`, such as a VUID.
+    #[inline]
+    pub fn ignore_id(mut self, id: impl Into<String>) -> Self {
+        self.ignored_ids.insert(id.into());
+        self
+    }
+
+    /// Rejects the messages whose ID number is `id_number`.
+    #[inline]
+    pub fn ignore_id_number(mut self, id_number: i32) -> Self {
+        self.ignored_id_numbers.insert(id_number);
+        self
+    }
+
+    /// Returns whether `message` passes the filter.
+    pub fn matches(&self, message: &Message) -> bool {
+        if let Some(min_severity) = self.min_severity {
+            if message.severity_level() < min_severity {
+                return false;
+            }
+        }
+
+        if let Some(id) = message.layer_prefix {
+            if self.ignored_ids.contains(id) {
+                return false;
+            }
+        }
+
+        !self.ignored_id_numbers.contains(&message.message_id_number)
+    }
+
+    /// Returns a callback that calls `user_callback` for the messages that pass the filter.
+    pub fn callback(self, user_callback: UserCallback) -> UserCallback {
+        Arc::new(move |message: &Message| {
+            if self.matches(message) {
+                user_callback(message)
+            }
+        })
+    }
+}
+
+/// Returns a callback that forwards the messages to the [`log`](https://docs.rs/log) crate,
+/// with the `vulkano` target.
+///
+/// Verbose messages are logged at the trace level.
+#[cfg(feature = "log")]
+pub fn log_callback() -> UserCallback {
+    Arc::new(|message: &Message| {
+        let level = match message.severity_level() {
+            DebugUtilsMessageSeverityLevel::Error => log::Level::Error,
+            DebugUtilsMessageSeverityLevel::Warning => log::Level::Warn,
+            DebugUtilsMessageSeverityLevel::Information => log::Level::Info,
+            DebugUtilsMessageSeverityLevel::Verbose => log::Level::Trace,
+        };
+
+        log::log!(
+            target: "vulkano",
+            level,
+            "[{}] {}",
+            message.layer_prefix.unwrap_or("unknown"),
+            message.description
+        );
+    })
+}
+
+/// Returns a callback that forwards the messages to the [`tracing`](https://docs.rs/tracing)
+/// crate as events, with the `vulkano` target.
+///
+/// Verbose messages are recorded at the trace level.
+#[cfg(feature = "tracing")]
+pub fn tracing_callback() -> UserCallback {
+    Arc::new(|message: &Message| {
+        let id = message.layer_prefix.unwrap_or("unknown");
+        let id_number = message.message_id_number;
+        let description = message.description;
+
+        match message.severity_level() {
+            DebugUtilsMessageSeverityLevel::Error => {
+                tracing::error!(target: "vulkano", id, id_number, "{}", description)
+            }
+            DebugUtilsMessageSeverityLevel::Warning => {
+                tracing::warn!(target: "vulkano", id, id_number, "{}", description)
+            }
+            DebugUtilsMessageSeverityLevel::Information => {
+                tracing::info!(target: "vulkano", id, id_number, "{}", description)
+            }
+            DebugUtilsMessageSeverityLevel::Verbose => {
+                tracing::trace!(target: "vulkano", id, id_number, "{}", description)
+            }
+        }
+    })
 }
 
 /// Severity of message.
@@ -560,6 +780,80 @@ impl From<ash::vk::DebugUtilsMessageTypeFlagsEXT> for DebugUtilsMessageType {
     }
 }
 
+/// A feature of the validation layers to enable, that is disabled by default.
+///
+/// See the documentation of the validation layers for more information about each feature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[repr(i32)]
+pub enum ValidationFeatureEnable {
+    /// Instruments the shaders to validate their accesses to resources on the GPU.
+    GpuAssisted = ash::vk::ValidationFeatureEnableEXT::GPU_ASSISTED.as_raw(),
+
+    /// Reserves a descriptor set binding slot for the `GpuAssisted` validation. Requires
+    /// `GpuAssisted` to be enabled too.
+    GpuAssistedReserveBindingSlot =
+        ash::vk::ValidationFeatureEnableEXT::GPU_ASSISTED_RESERVE_BINDING_SLOT.as_raw(),
+
+    /// Warns about API usages that are valid, but potentially slow or not recommended.
+    BestPractices = ash::vk::ValidationFeatureEnableEXT::BEST_PRACTICES.as_raw(),
+
+    /// Reports the messages of the `debugPrintfEXT` calls of the shaders. Can't be enabled
+    /// together with `GpuAssisted`.
+    DebugPrintf = ash::vk::ValidationFeatureEnableEXT::DEBUG_PRINTF.as_raw(),
+
+    /// Validates the synchronization between the accesses to resources.
+    SynchronizationValidation =
+        ash::vk::ValidationFeatureEnableEXT::SYNCHRONIZATION_VALIDATION.as_raw(),
+}
+
+impl From<ValidationFeatureEnable> for ash::vk::ValidationFeatureEnableEXT {
+    #[inline]
+    fn from(val: ValidationFeatureEnable) -> Self {
+        Self::from_raw(val as i32)
+    }
+}
+
+/// A feature of the validation layers to disable, that is enabled by default.
+///
+/// See the documentation of the validation layers for more information about each feature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[repr(i32)]
+pub enum ValidationFeatureDisable {
+    /// Disables all the validation.
+    All = ash::vk::ValidationFeatureDisableEXT::ALL.as_raw(),
+
+    /// Disables the validation of the shaders.
+    Shaders = ash::vk::ValidationFeatureDisableEXT::SHADERS.as_raw(),
+
+    /// Disables the checks that objects aren't used concurrently from several threads when they
+    /// must be externally synchronized.
+    ThreadSafety = ash::vk::ValidationFeatureDisableEXT::THREAD_SAFETY.as_raw(),
+
+    /// Disables the validation of the parameters of the API calls.
+    ApiParameters = ash::vk::ValidationFeatureDisableEXT::API_PARAMETERS.as_raw(),
+
+    /// Disables the tracking of the lifetimes of the objects.
+    ObjectLifetimes = ash::vk::ValidationFeatureDisableEXT::OBJECT_LIFETIMES.as_raw(),
+
+    /// Disables the core validation checks.
+    CoreChecks = ash::vk::ValidationFeatureDisableEXT::CORE_CHECKS.as_raw(),
+
+    /// Disables the wrapping of the handles of non-dispatchable objects into unique handles.
+    UniqueHandles = ash::vk::ValidationFeatureDisableEXT::UNIQUE_HANDLES.as_raw(),
+
+    /// Disables the cache of the shader validation results.
+    ShaderValidationCache = ash::vk::ValidationFeatureDisableEXT::SHADER_VALIDATION_CACHE.as_raw(),
+}
+
+impl From<ValidationFeatureDisable> for ash::vk::ValidationFeatureDisableEXT {
+    #[inline]
+    fn from(val: ValidationFeatureDisable) -> Self {
+        Self::from_raw(val as i32)
+    }
+}
+
 /// A label to associate with a span of work in a queue.
 ///
 /// When debugging, labels can be useful to identify which queue, or where in a specific queue,
@@ -616,4 +910,31 @@ mod tests {
             let _ = callback;
         });
     }
+
+    #[test]
+    fn message_filter() {
+        let message = Message {
+            severity: DebugUtilsMessageSeverity::warnings(),
+            ty: DebugUtilsMessageType::validation(),
+            layer_prefix: Some("VUID-vkCmdDraw-None-02699"),
+            description: "",
+            message_id_number: 42,
+            vuid: Some("VUID-vkCmdDraw-None-02699"),
+            objects: &[],
+        };
+
+        assert!(DebugUtilsMessageFilter::new().matches(&message));
+        assert!(DebugUtilsMessageFilter::new()
+            .min_severity(DebugUtilsMessageSeverityLevel::Warning)
+            .matches(&message));
+        assert!(!DebugUtilsMessageFilter::new()
+            .min_severity(DebugUtilsMessageSeverityLevel::Error)
+            .matches(&message));
+        assert!(!DebugUtilsMessageFilter::new()
+            .ignore_id("VUID-vkCmdDraw-None-02699")
+            .matches(&message));
+        assert!(!DebugUtilsMessageFilter::new()
+            .ignore_id_number(42)
+            .matches(&message));
+    }
 }
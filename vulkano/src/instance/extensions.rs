@@ -19,6 +19,7 @@ pub use crate::{
 use std::{
     ffi::{CStr, CString},
     fmt::Formatter,
+    os::raw::c_char,
     ptr,
 };
 
@@ -37,6 +38,7 @@ impl InstanceExtensions {
             Ok(l) => Ok(l),
             Err(SupportedExtensionsError::LoadingError(e)) => Err(e),
             Err(SupportedExtensionsError::OomError(e)) => panic!("{:?}", e),
+            Err(SupportedExtensionsError::LayerNotPresent) => unreachable!(),
         }
     }
 
@@ -51,6 +53,7 @@ impl InstanceExtensions {
             Ok(l) => Ok(l),
             Err(SupportedExtensionsError::LoadingError(e)) => Err(e),
             Err(SupportedExtensionsError::OomError(e)) => panic!("{:?}", e),
+            Err(SupportedExtensionsError::LayerNotPresent) => unreachable!(),
         }
     }
 
@@ -58,6 +61,41 @@ impl InstanceExtensions {
     pub fn supported_by_core_raw_with_loader<L>(
         ptrs: &loader::FunctionPointers<L>,
     ) -> Result<Self, SupportedExtensionsError>
+    where
+        L: loader::Loader,
+    {
+        Self::supported_by_layer_raw(ptrs, ptr::null())
+    }
+
+    /// Returns an `InstanceExtensions` object with extensions supported by the layer named
+    /// `layer_name`.
+    ///
+    /// Some extensions, such as `ext_validation_features`, are only provided by a layer. They can
+    /// be enabled on an instance when the layer is enabled too.
+    pub fn supported_by_layer(layer_name: &str) -> Result<Self, SupportedExtensionsError> {
+        InstanceExtensions::supported_by_layer_with_loader(loader::auto_loader()?, layer_name)
+    }
+
+    /// Same as `supported_by_layer`, but allows specifying a loader.
+    pub fn supported_by_layer_with_loader<L>(
+        ptrs: &loader::FunctionPointers<L>,
+        layer_name: &str,
+    ) -> Result<Self, SupportedExtensionsError>
+    where
+        L: loader::Loader,
+    {
+        // A name with a nul character can't be the name of a layer.
+        let layer_name =
+            CString::new(layer_name).map_err(|_| SupportedExtensionsError::LayerNotPresent)?;
+        Self::supported_by_layer_raw(ptrs, layer_name.as_ptr())
+    }
+
+    // Enumerates the extensions of the layer named `p_layer_name`, or of the implementation and
+    // the implicit layers if it's null.
+    fn supported_by_layer_raw<L>(
+        ptrs: &loader::FunctionPointers<L>,
+        p_layer_name: *const c_char,
+    ) -> Result<Self, SupportedExtensionsError>
     where
         L: loader::Loader,
     {
@@ -67,14 +105,14 @@ impl InstanceExtensions {
             loop {
                 let mut count = 0;
                 check_errors((fns.v1_0.enumerate_instance_extension_properties)(
-                    ptr::null(),
+                    p_layer_name,
                     &mut count,
                     ptr::null_mut(),
                 ))?;
 
                 let mut properties = Vec::with_capacity(count as usize);
                 let result = check_errors((fns.v1_0.enumerate_instance_extension_properties)(
-                    ptr::null(),
+                    p_layer_name,
                     &mut count,
                     properties.as_mut_ptr(),
                 ))?;
@@ -94,7 +132,8 @@ impl InstanceExtensions {
 
 #[cfg(test)]
 mod tests {
-    use crate::instance::InstanceExtensions;
+    use crate::extensions::SupportedExtensionsError;
+    use crate::instance::{layers_list, InstanceExtensions};
     use std::ffi::CString;
 
     #[test]
@@ -102,4 +141,25 @@ mod tests {
         let i: Vec<CString> = (&InstanceExtensions::none()).into();
         assert!(i.iter().next().is_none());
     }
+
+    #[test]
+    fn supported_by_layer() {
+        let layers = match layers_list() {
+            Ok(l) => l,
+            Err(_) => return,
+        };
+
+        for layer in layers {
+            InstanceExtensions::supported_by_layer(layer.name()).unwrap();
+        }
+
+        assert!(matches!(
+            InstanceExtensions::supported_by_layer("VK_LAYER_VULKANO_nonexistent"),
+            Err(SupportedExtensionsError::LayerNotPresent)
+        ));
+        assert!(matches!(
+            InstanceExtensions::supported_by_layer("VK_LAYER_VULKANO\0nonexistent"),
+            Err(SupportedExtensionsError::LayerNotPresent)
+        ));
+    }
 }
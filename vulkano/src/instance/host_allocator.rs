@@ -0,0 +1,356 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Routing the host memory allocations of the implementation through your own allocator.
+//!
+//! The Vulkan implementation allocates host (CPU) memory for its own bookkeeping. By default it
+//! uses its own allocator for this, but you can provide a [`HostAllocator`] when creating an
+//! instance or a device, through the `host_allocator` member of
+//! [`InstanceCreateInfo`](crate::instance::InstanceCreateInfo) and
+//! [`DeviceCreateInfo`](crate::device::DeviceCreateInfo). The implementation will then use it for
+//! the allocations that belong to the instance or device, for as long as it exists.
+//!
+//! The allocator that is given to an instance is not automatically used for the devices created
+//! from it; if you want all host allocations to be routed through your allocator, you must
+//! provide it to both.
+//!
+//! ```
+//! use std::{
+//!     alloc::{GlobalAlloc, Layout, System},
+//!     collections::HashMap,
+//!     ffi::c_void,
+//!     sync::{
+//!         atomic::{AtomicUsize, Ordering},
+//!         Mutex,
+//!     },
+//! };
+//! use vulkano::instance::host_allocator::{HostAllocationScope, HostAllocator};
+//!
+//! /// Forwards to the system allocator, and keeps track of the number of allocated bytes.
+//! #[derive(Default)]
+//! struct TrackingAllocator {
+//!     layouts: Mutex<HashMap<usize, Layout>>,
+//!     allocated: AtomicUsize,
+//! }
+//!
+//! unsafe impl HostAllocator for TrackingAllocator {
+//!     fn allocate(&self, size: usize, alignment: usize, _: HostAllocationScope) -> *mut c_void {
+//!         let layout = match Layout::from_size_align(size, alignment) {
+//!             Ok(layout) => layout,
+//!             Err(_) => return std::ptr::null_mut(),
+//!         };
+//!         let ptr = unsafe { System.alloc(layout) };
+//!
+//!         if !ptr.is_null() {
+//!             self.layouts.lock().unwrap().insert(ptr as usize, layout);
+//!             self.allocated.fetch_add(size, Ordering::Relaxed);
+//!         }
+//!
+//!         ptr as *mut c_void
+//!     }
+//!
+//!     unsafe fn reallocate(
+//!         &self,
+//!         original: *mut c_void,
+//!         size: usize,
+//!         alignment: usize,
+//!         scope: HostAllocationScope,
+//!     ) -> *mut c_void {
+//!         let old_size = self.layouts.lock().unwrap()[&(original as usize)].size();
+//!         let ptr = self.allocate(size, alignment, scope);
+//!
+//!         if !ptr.is_null() {
+//!             std::ptr::copy_nonoverlapping(original, ptr, old_size.min(size));
+//!             self.free(original);
+//!         }
+//!
+//!         ptr
+//!     }
+//!
+//!     unsafe fn free(&self, memory: *mut c_void) {
+//!         let layout = self.layouts.lock().unwrap().remove(&(memory as usize)).unwrap();
+//!         self.allocated.fetch_sub(layout.size(), Ordering::Relaxed);
+//!         System.dealloc(memory as *mut u8, layout);
+//!     }
+//! }
+//! ```
+
+use std::{
+    ffi::c_void,
+    fmt,
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr,
+    sync::Arc,
+};
+
+/// An allocator for the host memory that the Vulkan implementation allocates internally.
+///
+/// # Safety
+///
+/// - `allocate` and `reallocate` must return either a null pointer, or a pointer to a block of at
+///   least `size` bytes that is aligned to `alignment`, and that is not in use by anything else
+///   until it is passed to `reallocate` or `free`.
+/// - `reallocate` must preserve the contents of the original block, up to the smaller of the old
+///   and new sizes. If it returns a null pointer, the original block must be left unchanged.
+/// - The methods may be called from any thread, and must not make any calls to the Vulkan API.
+pub unsafe trait HostAllocator: Send + Sync {
+    /// Allocates a block of `size` bytes with the given alignment.
+    ///
+    /// `alignment` is always a power of two. Returning a null pointer signals that the allocation
+    /// failed.
+    fn allocate(&self, size: usize, alignment: usize, scope: HostAllocationScope) -> *mut c_void;
+
+    /// Changes the size of a block that was previously returned by `allocate` or `reallocate`,
+    /// possibly moving it.
+    ///
+    /// `size` is never zero, and `alignment` is always a power of two and the same as when the
+    /// block was first allocated. Returning a null pointer signals that the allocation failed.
+    ///
+    /// # Safety
+    ///
+    /// - `original` is a non-null pointer that was returned by this allocator and not yet freed.
+    unsafe fn reallocate(
+        &self,
+        original: *mut c_void,
+        size: usize,
+        alignment: usize,
+        scope: HostAllocationScope,
+    ) -> *mut c_void;
+
+    /// Frees a block that was previously returned by `allocate` or `reallocate`.
+    ///
+    /// # Safety
+    ///
+    /// - `memory` is a non-null pointer that was returned by this allocator and not yet freed.
+    unsafe fn free(&self, memory: *mut c_void);
+
+    /// Called when the implementation has allocated memory with its own allocator, because it
+    /// could not use this allocator. This happens, for example, for executable memory.
+    ///
+    /// This is purely informational. The default implementation does nothing.
+    #[inline]
+    fn internal_allocation_notification(
+        &self,
+        size: usize,
+        allocation_type: InternalAllocationType,
+        scope: HostAllocationScope,
+    ) {
+        let _ = (size, allocation_type, scope);
+    }
+
+    /// Called when the implementation has freed memory that it allocated with its own allocator.
+    ///
+    /// This is purely informational. The default implementation does nothing.
+    #[inline]
+    fn internal_free_notification(
+        &self,
+        size: usize,
+        allocation_type: InternalAllocationType,
+        scope: HostAllocationScope,
+    ) {
+        let _ = (size, allocation_type, scope);
+    }
+}
+
+impl fmt::Debug for dyn HostAllocator {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("HostAllocator").finish_non_exhaustive()
+    }
+}
+
+/// How long a host allocation is expected to live.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum HostAllocationScope {
+    /// The allocation lives only for the duration of a Vulkan command.
+    Command = ash::vk::SystemAllocationScope::COMMAND.as_raw(),
+
+    /// The allocation lives as long as a Vulkan object that is being created or used.
+    Object = ash::vk::SystemAllocationScope::OBJECT.as_raw(),
+
+    /// The allocation belongs to a pipeline cache or validation cache.
+    Cache = ash::vk::SystemAllocationScope::CACHE.as_raw(),
+
+    /// The allocation lives as long as the device.
+    Device = ash::vk::SystemAllocationScope::DEVICE.as_raw(),
+
+    /// The allocation lives as long as the instance.
+    Instance = ash::vk::SystemAllocationScope::INSTANCE.as_raw(),
+}
+
+impl From<ash::vk::SystemAllocationScope> for HostAllocationScope {
+    #[inline]
+    fn from(val: ash::vk::SystemAllocationScope) -> Self {
+        match val {
+            ash::vk::SystemAllocationScope::COMMAND => Self::Command,
+            ash::vk::SystemAllocationScope::CACHE => Self::Cache,
+            ash::vk::SystemAllocationScope::DEVICE => Self::Device,
+            ash::vk::SystemAllocationScope::INSTANCE => Self::Instance,
+            // Scopes added by later versions of Vulkan are reported as object scope.
+            _ => Self::Object,
+        }
+    }
+}
+
+/// The kind of memory that the implementation allocated with its own allocator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum InternalAllocationType {
+    /// Memory that the host can execute code from.
+    Executable = ash::vk::InternalAllocationType::EXECUTABLE.as_raw(),
+}
+
+impl TryFrom<ash::vk::InternalAllocationType> for InternalAllocationType {
+    type Error = ();
+
+    #[inline]
+    fn try_from(val: ash::vk::InternalAllocationType) -> Result<Self, Self::Error> {
+        Ok(match val {
+            ash::vk::InternalAllocationType::EXECUTABLE => Self::Executable,
+            _ => return Err(()),
+        })
+    }
+}
+
+// The allocator of an instance or device. It is boxed so that its address, which is given to the
+// implementation as user data, stays the same for the lifetime of the object. The same callbacks
+// must be provided when the object is destroyed as when it was created.
+pub(crate) struct HostAllocationCallbacks(Box<Arc<dyn HostAllocator>>);
+
+impl HostAllocationCallbacks {
+    #[inline]
+    pub(crate) fn new(allocator: Arc<dyn HostAllocator>) -> Self {
+        Self(Box::new(allocator))
+    }
+
+    #[inline]
+    pub(crate) fn allocator(&self) -> &Arc<dyn HostAllocator> {
+        &self.0
+    }
+
+    pub(crate) fn to_vulkan(&self) -> ash::vk::AllocationCallbacks {
+        ash::vk::AllocationCallbacks {
+            p_user_data: &*self.0 as *const Arc<_> as *const c_void as *mut _,
+            pfn_allocation: Some(allocation_trampoline),
+            pfn_reallocation: Some(reallocation_trampoline),
+            pfn_free: Some(free_trampoline),
+            pfn_internal_allocation: Some(internal_allocation_trampoline),
+            pfn_internal_free: Some(internal_free_trampoline),
+        }
+    }
+}
+
+impl fmt::Debug for HostAllocationCallbacks {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt::Debug::fmt(&**self.0, f)
+    }
+}
+
+// Returns a pointer to the given callbacks, or a null pointer if there are none.
+#[inline]
+pub(crate) fn callbacks_ptr(
+    callbacks: &Option<ash::vk::AllocationCallbacks>,
+) -> *const ash::vk::AllocationCallbacks {
+    callbacks
+        .as_ref()
+        .map_or(ptr::null(), |callbacks| callbacks as *const _)
+}
+
+// Panics must not unwind into the implementation. A panicking allocator is treated as if it
+// failed to allocate.
+
+unsafe fn user_allocator<'a>(user_data: *mut c_void) -> &'a Arc<dyn HostAllocator> {
+    &*(user_data as *const Arc<dyn HostAllocator>)
+}
+
+unsafe extern "system" fn allocation_trampoline(
+    user_data: *mut c_void,
+    size: usize,
+    alignment: usize,
+    scope: ash::vk::SystemAllocationScope,
+) -> *mut c_void {
+    catch_unwind(AssertUnwindSafe(move || {
+        user_allocator(user_data).allocate(size, alignment, scope.into())
+    }))
+    .unwrap_or(ptr::null_mut())
+}
+
+unsafe extern "system" fn reallocation_trampoline(
+    user_data: *mut c_void,
+    original: *mut c_void,
+    size: usize,
+    alignment: usize,
+    scope: ash::vk::SystemAllocationScope,
+) -> *mut c_void {
+    catch_unwind(AssertUnwindSafe(move || {
+        let allocator = user_allocator(user_data);
+
+        // A null original pointer behaves like an allocation, and a zero size like a free.
+        if original.is_null() {
+            allocator.allocate(size, alignment, scope.into())
+        } else if size == 0 {
+            allocator.free(original);
+            ptr::null_mut()
+        } else {
+            allocator.reallocate(original, size, alignment, scope.into())
+        }
+    }))
+    .unwrap_or(ptr::null_mut())
+}
+
+unsafe extern "system" fn free_trampoline(user_data: *mut c_void, memory: *mut c_void) {
+    if memory.is_null() {
+        return;
+    }
+
+    let _ = catch_unwind(AssertUnwindSafe(move || {
+        user_allocator(user_data).free(memory);
+    }));
+}
+
+unsafe extern "system" fn internal_allocation_trampoline(
+    user_data: *mut c_void,
+    size: usize,
+    allocation_type: ash::vk::InternalAllocationType,
+    scope: ash::vk::SystemAllocationScope,
+) {
+    // Allocation types added by later versions of Vulkan are skipped.
+    let allocation_type = match InternalAllocationType::try_from(allocation_type) {
+        Ok(allocation_type) => allocation_type,
+        Err(()) => return,
+    };
+
+    let _ = catch_unwind(AssertUnwindSafe(move || {
+        user_allocator(user_data).internal_allocation_notification(
+            size,
+            allocation_type,
+            scope.into(),
+        );
+    }));
+}
+
+unsafe extern "system" fn internal_free_trampoline(
+    user_data: *mut c_void,
+    size: usize,
+    allocation_type: ash::vk::InternalAllocationType,
+    scope: ash::vk::SystemAllocationScope,
+) {
+    let allocation_type = match InternalAllocationType::try_from(allocation_type) {
+        Ok(allocation_type) => allocation_type,
+        Err(()) => return,
+    };
+
+    let _ = catch_unwind(AssertUnwindSafe(move || {
+        user_allocator(user_data).internal_free_notification(size, allocation_type, scope.into());
+    }));
+}
@@ -15,8 +15,9 @@ use crate::OomError;
 use crate::Success;
 use crate::Version;
 use std::error;
-use std::ffi::CStr;
+use std::ffi::{c_void, CStr, CString};
 use std::fmt;
+use std::os::raw::c_char;
 use std::ptr;
 
 /// Queries the list of layers that are available when creating an instance.
@@ -80,6 +81,36 @@ where
         .map(|p| LayerProperties { props: p }))
 }
 
+/// Returns the properties of the layer named `name`, or `None` if the layer is not available
+/// when creating an instance.
+///
+/// This function returns an error if it failed to load the Vulkan library.
+///
+/// # Example
+///
+/// ```no_run
+/// use vulkano::instance;
+///
+/// match instance::layer_properties("VK_LAYER_KHRONOS_validation").unwrap() {
+///     Some(layer) => println!("Validation layer version: {}", layer.implementation_version()),
+///     None => println!("The validation layer is not installed"),
+/// }
+/// ```
+pub fn layer_properties(name: &str) -> Result<Option<LayerProperties>, LayersListError> {
+    layer_properties_from_loader(loader::auto_loader()?, name)
+}
+
+/// Same as `layer_properties()`, but allows specifying a loader.
+pub fn layer_properties_from_loader<L>(
+    ptrs: &loader::FunctionPointers<L>,
+    name: &str,
+) -> Result<Option<LayerProperties>, LayersListError>
+where
+    L: loader::Loader,
+{
+    Ok(layers_list_from_loader(ptrs)?.find(|layer| layer.name() == name))
+}
+
 /// Properties of a layer.
 #[derive(Clone)]
 pub struct LayerProperties {
@@ -170,6 +201,202 @@ impl LayerProperties {
     }
 }
 
+/// A setting to pass to a layer when creating an instance.
+///
+/// The settings that a layer accepts, and the type of their values, are described in the
+/// documentation of the layer. For example, the validation layer describes its settings in its
+/// `VkLayer_khronos_validation.json` manifest file, and accepts `message_limit` and
+/// `thread_safety` settings among others.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerSetting {
+    /// The name of the layer that the setting is for.
+    pub layer_name: String,
+
+    /// The name of the setting.
+    pub setting_name: String,
+
+    /// The values of the setting.
+    pub values: LayerSettingValues,
+}
+
+impl LayerSetting {
+    /// Returns a `LayerSetting` with the given names and values.
+    #[inline]
+    pub fn new(
+        layer_name: impl Into<String>,
+        setting_name: impl Into<String>,
+        values: LayerSettingValues,
+    ) -> Self {
+        Self {
+            layer_name: layer_name.into(),
+            setting_name: setting_name.into(),
+            values,
+        }
+    }
+}
+
+/// The values of a layer setting, and their type.
+///
+/// Most settings have a single value, but some settings accept a list of values.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum LayerSettingValues {
+    Bool32(Vec<bool>),
+    Int32(Vec<i32>),
+    Int64(Vec<i64>),
+    Uint32(Vec<u32>),
+    Uint64(Vec<u64>),
+    Float32(Vec<f32>),
+    Float64(Vec<f64>),
+    String(Vec<String>),
+}
+
+// VK_EXT_layer_settings is newer than the version of ash that vulkano uses (ash 0.37.3 is built
+// from the 1.3.251 headers, which do not include it), so its structures, structure type and
+// setting types are defined here, following the Vulkan headers. They can be replaced by the ones
+// from ash once vulkano moves to a version that includes the extension.
+#[repr(C)]
+pub(crate) struct LayerSettingsCreateInfoEXT {
+    s_type: ash::vk::StructureType,
+    p_next: *const c_void,
+    setting_count: u32,
+    p_settings: *const LayerSettingEXT,
+}
+
+#[repr(C)]
+struct LayerSettingEXT {
+    p_layer_name: *const c_char,
+    p_setting_name: *const c_char,
+    ty: i32,
+    value_count: u32,
+    p_values: *const c_void,
+}
+
+const STRUCTURE_TYPE_LAYER_SETTINGS_CREATE_INFO_EXT: ash::vk::StructureType =
+    ash::vk::StructureType::from_raw(1000496000);
+
+// Values of `VkLayerSettingTypeEXT`.
+const LAYER_SETTING_TYPE_BOOL32_EXT: i32 = 0;
+const LAYER_SETTING_TYPE_INT32_EXT: i32 = 1;
+const LAYER_SETTING_TYPE_INT64_EXT: i32 = 2;
+const LAYER_SETTING_TYPE_UINT32_EXT: i32 = 3;
+const LAYER_SETTING_TYPE_UINT64_EXT: i32 = 4;
+const LAYER_SETTING_TYPE_FLOAT32_EXT: i32 = 5;
+const LAYER_SETTING_TYPE_FLOAT64_EXT: i32 = 6;
+const LAYER_SETTING_TYPE_STRING_EXT: i32 = 7;
+
+/// A `LayerSettingsCreateInfoEXT`, together with the data that it points to.
+///
+/// The values of the settings that need no conversion are pointed to directly, so the
+/// `LayerSetting`s must outlive this.
+pub(crate) struct LayerSettingsVk {
+    create_info: LayerSettingsCreateInfoEXT,
+    _settings: Vec<LayerSettingEXT>,
+    _names: Vec<CString>,
+    _bool32_values: Vec<Vec<ash::vk::Bool32>>,
+    _string_values: Vec<(Vec<CString>, Vec<*const c_char>)>,
+}
+
+impl LayerSettingsVk {
+    pub(crate) fn new(layer_settings: &[LayerSetting]) -> Self {
+        let mut settings = Vec::with_capacity(layer_settings.len());
+        let mut names = Vec::with_capacity(layer_settings.len() * 2);
+        let mut bool32_values = Vec::new();
+        let mut string_values = Vec::new();
+
+        for setting in layer_settings {
+            let LayerSetting {
+                layer_name,
+                setting_name,
+                values,
+            } = setting;
+
+            // `Instance::new` has already checked that there are no NUL bytes.
+            let layer_name = CString::new(layer_name.as_str()).unwrap();
+            let setting_name = CString::new(setting_name.as_str()).unwrap();
+
+            let (ty, value_count, p_values) = match values {
+                LayerSettingValues::Bool32(values) => {
+                    let values_vk: Vec<_> =
+                        values.iter().map(|&v| ash::vk::Bool32::from(v)).collect();
+                    let p_values = values_vk.as_ptr() as *const c_void;
+                    bool32_values.push(values_vk);
+                    (LAYER_SETTING_TYPE_BOOL32_EXT, values.len(), p_values)
+                }
+                LayerSettingValues::Int32(values) => (
+                    LAYER_SETTING_TYPE_INT32_EXT,
+                    values.len(),
+                    values.as_ptr() as *const c_void,
+                ),
+                LayerSettingValues::Int64(values) => (
+                    LAYER_SETTING_TYPE_INT64_EXT,
+                    values.len(),
+                    values.as_ptr() as *const c_void,
+                ),
+                LayerSettingValues::Uint32(values) => (
+                    LAYER_SETTING_TYPE_UINT32_EXT,
+                    values.len(),
+                    values.as_ptr() as *const c_void,
+                ),
+                LayerSettingValues::Uint64(values) => (
+                    LAYER_SETTING_TYPE_UINT64_EXT,
+                    values.len(),
+                    values.as_ptr() as *const c_void,
+                ),
+                LayerSettingValues::Float32(values) => (
+                    LAYER_SETTING_TYPE_FLOAT32_EXT,
+                    values.len(),
+                    values.as_ptr() as *const c_void,
+                ),
+                LayerSettingValues::Float64(values) => (
+                    LAYER_SETTING_TYPE_FLOAT64_EXT,
+                    values.len(),
+                    values.as_ptr() as *const c_void,
+                ),
+                LayerSettingValues::String(values) => {
+                    let values_cstr: Vec<_> = values
+                        .iter()
+                        .map(|v| CString::new(v.as_str()).unwrap())
+                        .collect();
+                    let values_ptrs: Vec<_> = values_cstr.iter().map(|v| v.as_ptr()).collect();
+                    let p_values = values_ptrs.as_ptr() as *const c_void;
+                    string_values.push((values_cstr, values_ptrs));
+                    (LAYER_SETTING_TYPE_STRING_EXT, values.len(), p_values)
+                }
+            };
+
+            settings.push(LayerSettingEXT {
+                p_layer_name: layer_name.as_ptr(),
+                p_setting_name: setting_name.as_ptr(),
+                ty,
+                value_count: value_count as u32,
+                p_values,
+            });
+            names.push(layer_name);
+            names.push(setting_name);
+        }
+
+        LayerSettingsVk {
+            create_info: LayerSettingsCreateInfoEXT {
+                s_type: STRUCTURE_TYPE_LAYER_SETTINGS_CREATE_INFO_EXT,
+                p_next: ptr::null(),
+                setting_count: settings.len() as u32,
+                p_settings: settings.as_ptr(),
+            },
+            _settings: settings,
+            _names: names,
+            _bool32_values: bool32_values,
+            _string_values: string_values,
+        }
+    }
+
+    /// Adds the create info in front of the chain that starts at `p_next`.
+    pub(crate) fn link(&mut self, p_next: &mut *const c_void) {
+        self.create_info.p_next = *p_next;
+        *p_next = &self.create_info as *const _ as *const _;
+    }
+}
+
 /// Error that can happen when loading the list of layers.
 #[derive(Clone, Debug)]
 pub enum LayersListError {
@@ -230,7 +457,17 @@ impl From<Error> for LayersListError {
 
 #[cfg(test)]
 mod tests {
+    use super::{
+        LayerSetting, LayerSettingValues, LayerSettingsCreateInfoEXT, LayerSettingsVk,
+        LAYER_SETTING_TYPE_BOOL32_EXT, LAYER_SETTING_TYPE_INT64_EXT, LAYER_SETTING_TYPE_STRING_EXT,
+        STRUCTURE_TYPE_LAYER_SETTINGS_CREATE_INFO_EXT,
+    };
     use crate::instance;
+    use std::{
+        ffi::{c_void, CStr},
+        os::raw::c_char,
+        ptr, slice,
+    };
 
     #[test]
     fn layers_list() {
@@ -241,4 +478,87 @@ mod tests {
 
         while let Some(_) = list.next() {}
     }
+
+    #[test]
+    fn layer_properties() {
+        let first = match instance::layers_list() {
+            Ok(mut l) => l.next(),
+            Err(_) => return,
+        };
+
+        assert!(instance::layer_properties("VK_LAYER_VULKANO_nonexistent")
+            .unwrap()
+            .is_none());
+
+        if let Some(first) = first {
+            let properties = instance::layer_properties(first.name()).unwrap().unwrap();
+            assert_eq!(properties.name(), first.name());
+            assert_eq!(properties.description(), first.description());
+            assert_eq!(
+                properties.implementation_version(),
+                first.implementation_version()
+            );
+        }
+    }
+
+    #[test]
+    fn layer_settings_vk() {
+        let settings = [
+            LayerSetting::new(
+                "layer",
+                "bool",
+                LayerSettingValues::Bool32(vec![true, false]),
+            ),
+            LayerSetting::new("layer", "int", LayerSettingValues::Int64(vec![-5])),
+            LayerSetting::new(
+                "layer",
+                "string",
+                LayerSettingValues::String(vec!["a".to_owned(), "bc".to_owned()]),
+            ),
+        ];
+        let mut settings_vk = LayerSettingsVk::new(&settings);
+
+        let mut p_next = ptr::null();
+        settings_vk.link(&mut p_next);
+        assert_eq!(
+            p_next,
+            &settings_vk.create_info as *const _ as *const c_void
+        );
+
+        unsafe {
+            let create_info = &*(p_next as *const LayerSettingsCreateInfoEXT);
+            assert_eq!(
+                create_info.s_type,
+                STRUCTURE_TYPE_LAYER_SETTINGS_CREATE_INFO_EXT
+            );
+            assert!(create_info.p_next.is_null());
+            assert_eq!(create_info.setting_count, 3);
+
+            let settings_vk =
+                slice::from_raw_parts(create_info.p_settings, create_info.setting_count as usize);
+
+            assert_eq!(
+                CStr::from_ptr(settings_vk[0].p_layer_name).to_str(),
+                Ok("layer")
+            );
+            assert_eq!(
+                CStr::from_ptr(settings_vk[0].p_setting_name).to_str(),
+                Ok("bool")
+            );
+            assert_eq!(settings_vk[0].ty, LAYER_SETTING_TYPE_BOOL32_EXT);
+            assert_eq!(
+                slice::from_raw_parts(settings_vk[0].p_values as *const ash::vk::Bool32, 2),
+                &[ash::vk::TRUE, ash::vk::FALSE]
+            );
+
+            assert_eq!(settings_vk[1].ty, LAYER_SETTING_TYPE_INT64_EXT);
+            assert_eq!(settings_vk[1].value_count, 1);
+            assert_eq!(*(settings_vk[1].p_values as *const i64), -5);
+
+            assert_eq!(settings_vk[2].ty, LAYER_SETTING_TYPE_STRING_EXT);
+            let values = slice::from_raw_parts(settings_vk[2].p_values as *const *const c_char, 2);
+            assert_eq!(CStr::from_ptr(values[0]).to_str(), Ok("a"));
+            assert_eq!(CStr::from_ptr(values[1]).to_str(), Ok("bc"));
+        }
+    }
 }
@@ -52,17 +52,26 @@
 //! `device` module for more info.
 
 use self::{
-    debug::{DebugUtilsMessengerCreateInfo, UserCallback},
+    debug::{
+        DebugUtilsMessengerCreateInfo, UserCallback, ValidationFeatureDisable,
+        ValidationFeatureEnable,
+    },
+    host_allocator::{callbacks_ptr, HostAllocationCallbacks, HostAllocator},
+    layers::LayerSettingsVk,
     loader::{FunctionPointers, Loader},
 };
 pub use self::{
     extensions::InstanceExtensions,
-    layers::{layers_list, LayerProperties, LayersListError},
+    layers::{
+        layer_properties, layers_list, LayerProperties, LayerSetting, LayerSettingValues,
+        LayersListError,
+    },
     loader::LoadingError,
 };
 use crate::{
     check_errors,
     device::physical::{init_physical_devices, PhysicalDeviceInfo},
+    extension_chain::ExtensionChain,
     instance::debug::{trampoline, DebugUtilsMessageSeverity, DebugUtilsMessageType},
     Error, OomError, VulkanObject,
 };
@@ -86,6 +95,7 @@ use std::{
 
 pub mod debug;
 pub(crate) mod extensions;
+pub mod host_allocator;
 mod layers;
 pub mod loader;
 
@@ -179,7 +189,11 @@ pub mod loader;
 ///
 /// When creating an `Instance`, you have the possibility to pass a list of **layers** that will
 /// be activated on the newly-created instance. The list of available layers can be retrieved by
-/// calling [the `layers_list` function](crate::instance::layers_list).
+/// calling [the `layers_list` function](crate::instance::layers_list), and the properties of a
+/// specific layer with [the `layer_properties` function](crate::instance::layer_properties).
+///
+/// Some layers can be configured with [`layer_settings`](InstanceCreateInfo::layer_settings),
+/// if they provide the `ext_layer_settings` extension.
 ///
 /// A layer is a component that will hook and potentially modify the Vulkan function calls.
 /// For example, activating a layer could add a frames-per-second counter on the screen, or it
@@ -231,7 +245,11 @@ pub struct Instance {
     enabled_extensions: InstanceExtensions,
     enabled_layers: Vec<String>,
     function_pointers: OwnedOrRef<FunctionPointers<Box<dyn Loader>>>,
+    host_allocator: Option<HostAllocationCallbacks>,
     max_api_version: Version,
+    enabled_validation_features: Vec<ValidationFeatureEnable>,
+    disabled_validation_features: Vec<ValidationFeatureDisable>,
+    layer_settings: Vec<LayerSetting>,
     user_callbacks: Vec<Box<UserCallback>>,
 }
 
@@ -273,16 +291,75 @@ impl Instance {
     pub unsafe fn with_debug_utils_messengers(
         create_info: InstanceCreateInfo,
         debug_utils_messengers: impl IntoIterator<Item = DebugUtilsMessengerCreateInfo>,
+    ) -> Result<Arc<Instance>, InstanceCreationError> {
+        Self::new_inner(
+            create_info,
+            debug_utils_messengers,
+            |function_pointers, create_info, allocator, output| {
+                (function_pointers.fns().v1_0.create_instance)(create_info, allocator, output)
+            },
+        )
+    }
+
+    /// Creates a new `Instance`, calling `create_fn` instead of `vkCreateInstance` to create the
+    /// Vulkan instance.
+    ///
+    /// This is needed when the instance must be created by another library, such as an OpenXR
+    /// runtime with `xrCreateVulkanInstanceKHR`. `create_fn` receives the parameters that would
+    /// have been passed to `vkCreateInstance`, and must return its result.
+    ///
+    /// # Panics
+    ///
+    /// - Panics for the same reasons as [`Instance::new`].
+    ///
+    /// # Safety
+    ///
+    /// - `create_fn` must create the instance from the `ash::vk::InstanceCreateInfo` it is given,
+    ///   with the same loader as the one in `create_info.function_pointers`. It may enable
+    ///   additional extensions and layers, which vulkano will not know about.
+    /// - If `create_fn` returns `SUCCESS`, it must have written a valid instance handle to the
+    ///   output pointer.
+    pub unsafe fn with_create_fn<F>(
+        create_info: InstanceCreateInfo,
+        create_fn: F,
+    ) -> Result<Arc<Instance>, InstanceCreationError>
+    where
+        F: FnOnce(
+            &ash::vk::InstanceCreateInfo,
+            *const ash::vk::AllocationCallbacks,
+            *mut ash::vk::Instance,
+        ) -> ash::vk::Result,
+    {
+        Self::new_inner(create_info, [], |_, create_info, allocator, output| {
+            create_fn(create_info, allocator, output)
+        })
+    }
+
+    unsafe fn new_inner(
+        create_info: InstanceCreateInfo,
+        debug_utils_messengers: impl IntoIterator<Item = DebugUtilsMessengerCreateInfo>,
+        create_fn: impl FnOnce(
+            &FunctionPointers<Box<dyn Loader>>,
+            &ash::vk::InstanceCreateInfo,
+            *const ash::vk::AllocationCallbacks,
+            *mut ash::vk::Instance,
+        ) -> ash::vk::Result,
     ) -> Result<Arc<Instance>, InstanceCreationError> {
         let InstanceCreateInfo {
             application_name,
             application_version,
-            enabled_extensions,
+            mut enabled_extensions,
             enabled_layers,
             engine_name,
             engine_version,
+            extensions,
             function_pointers,
+            host_allocator,
             max_api_version,
+            enumerate_portability,
+            enabled_validation_features,
+            disabled_validation_features,
+            layer_settings,
             _ne: _,
         } = create_info;
 
@@ -308,13 +385,37 @@ impl Instance {
         // VUID-VkApplicationInfo-apiVersion-04010
         assert!(max_api_version >= Version::V1_0);
 
-        // Check if the extensions are correct
-        enabled_extensions.check_requirements(
-            &InstanceExtensions::supported_by_core_with_loader(&function_pointers)?,
-            api_version,
-        )?;
+        // Check if the extensions are correct. Extensions provided by one of the enabled layers
+        // can be enabled as well.
+        let mut supported_extensions =
+            InstanceExtensions::supported_by_core_with_loader(&function_pointers)?;
+
+        for layer_name in &enabled_layers {
+            supported_extensions =
+                supported_extensions.union(&InstanceExtensions::supported_by_layer_with_loader(
+                    &function_pointers,
+                    layer_name,
+                )?);
+        }
+
+        let mut flags = ash::vk::InstanceCreateFlags::empty();
+
+        // Loaders that don't support `khr_portability_enumeration` already enumerate the
+        // portability subset implementations.
+        if enumerate_portability && supported_extensions.khr_portability_enumeration {
+            enabled_extensions.khr_portability_enumeration = true;
+            flags |= ash::vk::InstanceCreateFlags::ENUMERATE_PORTABILITY_KHR;
+
+            // Required to enable `khr_portability_subset` on the devices.
+            if api_version < Version::V1_1
+                && supported_extensions.khr_get_physical_device_properties2
+            {
+                enabled_extensions.khr_get_physical_device_properties2 = true;
+            }
+        }
+
+        enabled_extensions.check_requirements(&supported_extensions, api_version)?;
 
-        // FIXME: check whether each layer is supported
         let enabled_layers_cstr: Vec<CString> = enabled_layers
             .iter()
             .map(|name| CString::new(name.clone()).unwrap())
@@ -350,7 +451,7 @@ impl Instance {
         };
 
         let mut create_info = ash::vk::InstanceCreateInfo {
-            flags: ash::vk::InstanceCreateFlags::empty(),
+            flags,
             p_application_info: &application_info,
             enabled_layer_count: enabled_layers_ptrs.len() as u32,
             pp_enabled_layer_names: enabled_layers_ptrs.as_ptr(),
@@ -413,13 +514,109 @@ impl Instance {
             create_info.p_next = info as *const _ as *const _;
         }
 
+        // Handle validation features
+        let enabled_validation_features_vk: SmallVec<[_; 4]> = enabled_validation_features
+            .iter()
+            .copied()
+            .map(ash::vk::ValidationFeatureEnableEXT::from)
+            .collect();
+        let disabled_validation_features_vk: SmallVec<[_; 4]> = disabled_validation_features
+            .iter()
+            .copied()
+            .map(ash::vk::ValidationFeatureDisableEXT::from)
+            .collect();
+
+        let mut validation_features = if !enabled_validation_features.is_empty()
+            || !disabled_validation_features.is_empty()
+        {
+            if !enabled_extensions.ext_validation_features {
+                return Err(InstanceCreationError::ExtensionNotEnabled {
+                    extension: "ext_validation_features",
+                    reason:
+                        "enabled_validation_features or disabled_validation_features were not empty",
+                });
+            }
+
+            // VUID-VkValidationFeaturesEXT-pEnabledValidationFeatures-02967
+            if enabled_validation_features
+                .contains(&ValidationFeatureEnable::GpuAssistedReserveBindingSlot)
+                && !enabled_validation_features.contains(&ValidationFeatureEnable::GpuAssisted)
+            {
+                return Err(InstanceCreationError::ValidationFeatureRequiresGpuAssisted);
+            }
+
+            // VUID-VkValidationFeaturesEXT-pEnabledValidationFeatures-02968
+            if enabled_validation_features.contains(&ValidationFeatureEnable::DebugPrintf)
+                && enabled_validation_features.contains(&ValidationFeatureEnable::GpuAssisted)
+            {
+                return Err(InstanceCreationError::ValidationFeaturesDebugPrintfAndGpuAssisted);
+            }
+
+            Some(ash::vk::ValidationFeaturesEXT {
+                enabled_validation_feature_count: enabled_validation_features_vk.len() as u32,
+                p_enabled_validation_features: enabled_validation_features_vk.as_ptr(),
+                disabled_validation_feature_count: disabled_validation_features_vk.len() as u32,
+                p_disabled_validation_features: disabled_validation_features_vk.as_ptr(),
+                ..Default::default()
+            })
+        } else {
+            None
+        };
+
+        if let Some(validation_features) = validation_features.as_mut() {
+            validation_features.p_next = create_info.p_next;
+            create_info.p_next = validation_features as *const _ as *const _;
+        }
+
+        // Handle layer settings
+        for (index, setting) in layer_settings.iter().enumerate() {
+            let LayerSetting {
+                layer_name,
+                setting_name,
+                values,
+            } = setting;
+
+            let values_contain_nul = match values {
+                LayerSettingValues::String(values) => values.iter().any(|v| v.contains('\0')),
+                _ => false,
+            };
+
+            if layer_name.contains('\0') || setting_name.contains('\0') || values_contain_nul {
+                return Err(InstanceCreationError::LayerSettingContainsNul { index });
+            }
+        }
+
+        let mut layer_settings_vk = if !layer_settings.is_empty() {
+            if !enabled_extensions.ext_layer_settings {
+                return Err(InstanceCreationError::ExtensionNotEnabled {
+                    extension: "ext_layer_settings",
+                    reason: "layer_settings was not empty",
+                });
+            }
+
+            Some(LayerSettingsVk::new(&layer_settings))
+        } else {
+            None
+        };
+
+        if let Some(layer_settings_vk) = layer_settings_vk.as_mut() {
+            layer_settings_vk.link(&mut create_info.p_next);
+        }
+
+        let _extensions_vk = extensions.link(&mut create_info.p_next);
+
+        let host_allocator = host_allocator.map(HostAllocationCallbacks::new);
+        let allocation_callbacks = host_allocator
+            .as_ref()
+            .map(HostAllocationCallbacks::to_vulkan);
+
         // Creating the Vulkan instance.
         let handle = {
             let mut output = MaybeUninit::uninit();
-            let fns = function_pointers.fns();
-            check_errors((fns.v1_0.create_instance)(
+            check_errors(create_fn(
+                &*function_pointers,
                 &create_info,
-                ptr::null(),
+                callbacks_ptr(&allocation_callbacks),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -441,7 +638,11 @@ impl Instance {
             enabled_extensions,
             enabled_layers,
             function_pointers,
+            host_allocator,
             max_api_version,
+            enabled_validation_features,
+            disabled_validation_features,
+            layer_settings,
             user_callbacks,
         };
 
@@ -484,15 +685,45 @@ impl Instance {
     pub fn enabled_layers(&self) -> &[String] {
         &self.enabled_layers
     }
+
+    /// Returns the validation features that were enabled when creating the instance.
+    #[inline]
+    pub fn enabled_validation_features(&self) -> &[ValidationFeatureEnable] {
+        &self.enabled_validation_features
+    }
+
+    /// Returns the validation features that were disabled when creating the instance.
+    #[inline]
+    pub fn disabled_validation_features(&self) -> &[ValidationFeatureDisable] {
+        &self.disabled_validation_features
+    }
+
+    /// Returns the layer settings that were given when creating the instance.
+    #[inline]
+    pub fn layer_settings(&self) -> &[LayerSetting] {
+        &self.layer_settings
+    }
+
+    /// Returns the host allocator that was given when creating the instance, if any.
+    #[inline]
+    pub fn host_allocator(&self) -> Option<&Arc<dyn HostAllocator>> {
+        self.host_allocator
+            .as_ref()
+            .map(HostAllocationCallbacks::allocator)
+    }
 }
 
 impl Drop for Instance {
     #[inline]
     fn drop(&mut self) {
         let fns = self.fns();
+        let allocation_callbacks = self
+            .host_allocator
+            .as_ref()
+            .map(HostAllocationCallbacks::to_vulkan);
 
         unsafe {
-            (fns.v1_0.destroy_instance)(self.handle, ptr::null());
+            (fns.v1_0.destroy_instance)(self.handle, callbacks_ptr(&allocation_callbacks));
         }
     }
 }
@@ -532,7 +763,11 @@ impl fmt::Debug for Instance {
             enabled_extensions,
             enabled_layers,
             function_pointers,
+            host_allocator,
             max_api_version,
+            enabled_validation_features,
+            disabled_validation_features,
+            layer_settings,
             user_callbacks: _,
         } = self;
 
@@ -544,7 +779,11 @@ impl fmt::Debug for Instance {
             .field("enabled_extensions", enabled_extensions)
             .field("enabled_layers", enabled_layers)
             .field("function_pointers", function_pointers)
+            .field("host_allocator", host_allocator)
             .field("max_api_version", max_api_version)
+            .field("enabled_validation_features", enabled_validation_features)
+            .field("disabled_validation_features", disabled_validation_features)
+            .field("layer_settings", layer_settings)
             .finish_non_exhaustive()
     }
 }
@@ -564,6 +803,9 @@ pub struct InstanceCreateInfo {
 
     /// The extensions to enable on the instance.
     ///
+    /// Besides the extensions supported by the implementation, this can contain extensions
+    /// provided by one of the layers in `enabled_layers`.
+    ///
     /// The default value is [`InstanceExtensions::none()`].
     pub enabled_extensions: InstanceExtensions,
 
@@ -581,12 +823,27 @@ pub struct InstanceCreateInfo {
     /// The default value is zero.
     pub engine_version: Version,
 
+    /// Extension structures that vulkano doesn't support, to add to the create info of the
+    /// instance. See the [`extension_chain`](crate::extension_chain) module for more information.
+    /// If it is not empty, it can only be used to create one object.
+    ///
+    /// The default value is empty.
+    pub extensions: ExtensionChain<Instance>,
+
     /// Function pointers loaded from a custom loader.
     ///
     /// You can use this if you want to load the Vulkan API explicitly, rather than using Vulkano's
     /// default.
     pub function_pointers: Option<FunctionPointers<Box<dyn Loader>>>,
 
+    /// An allocator to use for the host memory that the Vulkan implementation allocates for the
+    /// instance. See the [`host_allocator`] module for more information.
+    ///
+    /// If this is `None`, the implementation uses its own allocator.
+    ///
+    /// The default value is `None`.
+    pub host_allocator: Option<Arc<dyn HostAllocator>>,
+
     /// The highest Vulkan API version that the application will use with the instance.
     ///
     /// Usually, you will want to leave this at the default.
@@ -595,6 +852,50 @@ pub struct InstanceCreateInfo {
     /// supported instance version is 1.0, then it will be 1.0.
     pub max_api_version: Option<Version>,
 
+    /// Whether to include the physical devices of implementations that only support a subset of
+    /// Vulkan, such as MoltenVK on macOS and iOS, when enumerating the physical devices.
+    ///
+    /// If set to `true`, vulkano enables the
+    /// [`khr_portability_enumeration`](crate::instance::InstanceExtensions::khr_portability_enumeration)
+    /// extension if the loader supports it, together with
+    /// [`khr_get_physical_device_properties2`](crate::instance::InstanceExtensions::khr_get_physical_device_properties2)
+    /// if needed to create devices from these physical devices. Older loaders always include
+    /// them. The restrictions of these physical devices are described by the
+    /// [`khr_portability_subset`](crate::device::DeviceExtensions::khr_portability_subset)
+    /// extension.
+    ///
+    /// The default value is `false`.
+    pub enumerate_portability: bool,
+
+    /// Features of the validation layers to enable, that are disabled by default.
+    ///
+    /// If not empty, the
+    /// [`ext_validation_features`](crate::instance::InstanceExtensions::ext_validation_features)
+    /// extension must be enabled on the instance.
+    ///
+    /// The default value is empty.
+    pub enabled_validation_features: Vec<ValidationFeatureEnable>,
+
+    /// Features of the validation layers to disable, that are enabled by default.
+    ///
+    /// If not empty, the
+    /// [`ext_validation_features`](crate::instance::InstanceExtensions::ext_validation_features)
+    /// extension must be enabled on the instance.
+    ///
+    /// The default value is empty.
+    pub disabled_validation_features: Vec<ValidationFeatureDisable>,
+
+    /// Settings to pass to the layers, to configure them when they are enabled on the instance.
+    ///
+    /// If not empty, the
+    /// [`ext_layer_settings`](crate::instance::InstanceExtensions::ext_layer_settings)
+    /// extension must be enabled on the instance. It is usually provided by the layers that
+    /// accept settings, rather than by the implementation. The names and string values of the
+    /// settings must not contain NUL bytes.
+    ///
+    /// The default value is empty.
+    pub layer_settings: Vec<LayerSetting>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -608,8 +909,14 @@ impl Default for InstanceCreateInfo {
             enabled_layers: Vec::new(),
             engine_name: None,
             engine_version: Version::major_minor(0, 0),
+            extensions: ExtensionChain::new(),
             function_pointers: None,
+            host_allocator: None,
             max_api_version: None,
+            enumerate_portability: false,
+            enabled_validation_features: Vec::new(),
+            disabled_validation_features: Vec::new(),
+            layer_settings: Vec::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -658,6 +965,14 @@ pub enum InstanceCreationError {
         extension: &'static str,
         reason: &'static str,
     },
+    /// `enabled_validation_features` contains `GpuAssistedReserveBindingSlot`, but not
+    /// `GpuAssisted`.
+    ValidationFeatureRequiresGpuAssisted,
+    /// `enabled_validation_features` contains both `DebugPrintf` and `GpuAssisted`.
+    ValidationFeaturesDebugPrintfAndGpuAssisted,
+    /// The layer name, the setting name or one of the string values of
+    /// `layer_settings[index]` contains a NUL byte.
+    LayerSettingContainsNul { index: usize },
 }
 
 impl error::Error for InstanceCreationError {
@@ -689,6 +1004,19 @@ impl fmt::Display for InstanceCreationError {
                 "the extension {} must be enabled: {}",
                 extension, reason
             ),
+            Self::ValidationFeatureRequiresGpuAssisted => write!(
+                fmt,
+                "the `GpuAssistedReserveBindingSlot` validation feature was enabled without the `GpuAssisted` validation feature",
+            ),
+            Self::ValidationFeaturesDebugPrintfAndGpuAssisted => write!(
+                fmt,
+                "the `DebugPrintf` and `GpuAssisted` validation features were both enabled",
+            ),
+            Self::LayerSettingContainsNul { index } => write!(
+                fmt,
+                "layer setting {} contains a NUL byte in its names or string values",
+                index,
+            ),
         }
     }
 }
@@ -707,6 +1035,17 @@ impl From<LoadingError> for InstanceCreationError {
     }
 }
 
+impl From<SupportedExtensionsError> for InstanceCreationError {
+    #[inline]
+    fn from(err: SupportedExtensionsError) -> Self {
+        match err {
+            SupportedExtensionsError::LoadingError(err) => Self::LoadingError(err),
+            SupportedExtensionsError::OomError(err) => Self::OomError(err),
+            SupportedExtensionsError::LayerNotPresent => Self::LayerNotPresent,
+        }
+    }
+}
+
 impl From<ExtensionRestrictionError> for InstanceCreationError {
     #[inline]
     fn from(err: ExtensionRestrictionError) -> Self {
@@ -773,4 +1112,320 @@ mod tests {
         let by_id = phys.queue_family_by_id(queue_family.id()).unwrap();
         assert_eq!(by_id.id(), queue_family.id());
     }
+
+    #[test]
+    fn host_allocator() {
+        use super::{
+            host_allocator::{HostAllocationScope, HostAllocator},
+            Instance, InstanceCreateInfo,
+        };
+        use std::{
+            alloc::{GlobalAlloc, Layout, System},
+            collections::HashMap,
+            ffi::c_void,
+            ptr,
+            sync::{Arc, Mutex},
+        };
+
+        #[derive(Default)]
+        struct CountingAllocator {
+            live: Mutex<HashMap<usize, Layout>>,
+        }
+
+        unsafe impl HostAllocator for CountingAllocator {
+            fn allocate(
+                &self,
+                size: usize,
+                alignment: usize,
+                _scope: HostAllocationScope,
+            ) -> *mut c_void {
+                let layout = Layout::from_size_align(size.max(1), alignment).unwrap();
+                let memory = unsafe { System.alloc(layout) };
+                self.live.lock().unwrap().insert(memory as usize, layout);
+                memory as *mut c_void
+            }
+
+            unsafe fn reallocate(
+                &self,
+                original: *mut c_void,
+                size: usize,
+                alignment: usize,
+                scope: HostAllocationScope,
+            ) -> *mut c_void {
+                let old_size = self.live.lock().unwrap()[&(original as usize)].size();
+                let memory = self.allocate(size, alignment, scope);
+                ptr::copy_nonoverlapping(
+                    original as *const u8,
+                    memory as *mut u8,
+                    old_size.min(size),
+                );
+                self.free(original);
+                memory
+            }
+
+            unsafe fn free(&self, memory: *mut c_void) {
+                let layout = self
+                    .live
+                    .lock()
+                    .unwrap()
+                    .remove(&(memory as usize))
+                    .unwrap();
+                System.dealloc(memory as *mut u8, layout);
+            }
+        }
+
+        let allocator = Arc::new(CountingAllocator::default());
+        let instance = match Instance::new(InstanceCreateInfo {
+            host_allocator: Some(allocator.clone()),
+            ..Default::default()
+        }) {
+            Ok(i) => i,
+            Err(_) => return,
+        };
+        assert!(instance.host_allocator().is_some());
+
+        drop(instance);
+        assert!(allocator.live.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn enumerate_portability() {
+        use super::{Instance, InstanceCreateInfo, InstanceExtensions};
+
+        let instance = match Instance::new(InstanceCreateInfo {
+            enumerate_portability: true,
+            ..Default::default()
+        }) {
+            Ok(i) => i,
+            Err(_) => return,
+        };
+
+        // The extension is enabled automatically if the loader supports it.
+        let supported = InstanceExtensions::supported_by_core().unwrap();
+        assert_eq!(
+            instance.enabled_extensions().khr_portability_enumeration,
+            supported.khr_portability_enumeration
+        );
+    }
+
+    #[test]
+    fn layer_not_present() {
+        use super::{Instance, InstanceCreateInfo, InstanceCreationError};
+
+        let _ = instance!();
+
+        assert!(matches!(
+            Instance::new(InstanceCreateInfo {
+                enabled_layers: vec!["VK_LAYER_VULKANO_nonexistent".to_owned()],
+                ..Default::default()
+            }),
+            Err(InstanceCreationError::LayerNotPresent)
+        ));
+    }
+
+    #[test]
+    fn validation_features_extension_not_enabled() {
+        use super::{
+            debug::ValidationFeatureEnable, Instance, InstanceCreateInfo, InstanceCreationError,
+        };
+
+        let _ = instance!();
+
+        match Instance::new(InstanceCreateInfo {
+            enabled_validation_features: vec![ValidationFeatureEnable::BestPractices],
+            ..Default::default()
+        }) {
+            Err(InstanceCreationError::ExtensionNotEnabled {
+                extension: "ext_validation_features",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn validation_features() {
+        use super::{
+            debug::{ValidationFeatureDisable, ValidationFeatureEnable},
+            Instance, InstanceCreateInfo, InstanceExtensions,
+        };
+
+        let _ = instance!();
+        let layer_name = "VK_LAYER_KHRONOS_validation";
+
+        // The extension is provided by the validation layer.
+        match InstanceExtensions::supported_by_layer(layer_name) {
+            Ok(extensions) if extensions.ext_validation_features => (),
+            _ => return,
+        }
+
+        let instance = Instance::new(InstanceCreateInfo {
+            enabled_extensions: InstanceExtensions {
+                ext_validation_features: true,
+                ..InstanceExtensions::none()
+            },
+            enabled_layers: vec![layer_name.to_owned()],
+            enabled_validation_features: vec![ValidationFeatureEnable::SynchronizationValidation],
+            disabled_validation_features: vec![ValidationFeatureDisable::ThreadSafety],
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(instance.enabled_extensions().ext_validation_features);
+        assert_eq!(
+            instance.enabled_validation_features(),
+            &[ValidationFeatureEnable::SynchronizationValidation]
+        );
+        assert_eq!(
+            instance.disabled_validation_features(),
+            &[ValidationFeatureDisable::ThreadSafety]
+        );
+    }
+
+    #[test]
+    fn validation_features_invalid() {
+        use super::{
+            debug::ValidationFeatureEnable, Instance, InstanceCreateInfo, InstanceCreationError,
+            InstanceExtensions,
+        };
+
+        let _ = instance!();
+        let layer_name = "VK_LAYER_KHRONOS_validation";
+
+        match InstanceExtensions::supported_by_layer(layer_name) {
+            Ok(extensions) if extensions.ext_validation_features => (),
+            _ => return,
+        }
+
+        let create_info = |enabled_validation_features| InstanceCreateInfo {
+            enabled_extensions: InstanceExtensions {
+                ext_validation_features: true,
+                ..InstanceExtensions::none()
+            },
+            enabled_layers: vec![layer_name.to_owned()],
+            enabled_validation_features,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            Instance::new(create_info(vec![
+                ValidationFeatureEnable::GpuAssistedReserveBindingSlot
+            ])),
+            Err(InstanceCreationError::ValidationFeatureRequiresGpuAssisted)
+        ));
+
+        assert!(matches!(
+            Instance::new(create_info(vec![
+                ValidationFeatureEnable::DebugPrintf,
+                ValidationFeatureEnable::GpuAssisted,
+            ])),
+            Err(InstanceCreationError::ValidationFeaturesDebugPrintfAndGpuAssisted)
+        ));
+    }
+
+    #[test]
+    fn layer_settings_extension_not_enabled() {
+        use super::{
+            Instance, InstanceCreateInfo, InstanceCreationError, LayerSetting, LayerSettingValues,
+        };
+
+        let _ = instance!();
+
+        match Instance::new(InstanceCreateInfo {
+            layer_settings: vec![LayerSetting::new(
+                "VK_LAYER_KHRONOS_validation",
+                "thread_safety",
+                LayerSettingValues::Bool32(vec![false]),
+            )],
+            ..Default::default()
+        }) {
+            Err(InstanceCreationError::ExtensionNotEnabled {
+                extension: "ext_layer_settings",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn layer_settings_contains_nul() {
+        use super::{
+            Instance, InstanceCreateInfo, InstanceCreationError, LayerSetting, LayerSettingValues,
+        };
+
+        let _ = instance!();
+
+        let settings = [
+            LayerSetting::new(
+                "VK_LAYER_KHRONOS_validation\0",
+                "thread_safety",
+                LayerSettingValues::Bool32(vec![false]),
+            ),
+            LayerSetting::new(
+                "VK_LAYER_KHRONOS_validation",
+                "thread\0safety",
+                LayerSettingValues::Bool32(vec![false]),
+            ),
+            LayerSetting::new(
+                "VK_LAYER_KHRONOS_validation",
+                "debug_action",
+                LayerSettingValues::String(vec!["VK_DBG_LAYER_ACTION_LOG_MSG\0".into()]),
+            ),
+        ];
+
+        for setting in settings {
+            match Instance::new(InstanceCreateInfo {
+                layer_settings: vec![setting],
+                ..Default::default()
+            }) {
+                Err(InstanceCreationError::LayerSettingContainsNul { index: 0 }) => (),
+                _ => panic!(),
+            }
+        }
+    }
+
+    #[test]
+    fn layer_settings() {
+        use super::{
+            Instance, InstanceCreateInfo, InstanceExtensions, LayerSetting, LayerSettingValues,
+        };
+
+        let _ = instance!();
+        let layer_name = "VK_LAYER_KHRONOS_validation";
+
+        match InstanceExtensions::supported_by_layer(layer_name) {
+            Ok(extensions) if extensions.ext_layer_settings => (),
+            _ => return,
+        }
+
+        let layer_settings = vec![
+            LayerSetting::new(
+                layer_name,
+                "thread_safety",
+                LayerSettingValues::Bool32(vec![false]),
+            ),
+            LayerSetting::new(
+                layer_name,
+                "duplicate_message_limit",
+                LayerSettingValues::Uint32(vec![3]),
+            ),
+            LayerSetting::new(
+                layer_name,
+                "report_flags",
+                LayerSettingValues::String(vec!["error".to_owned(), "warn".to_owned()]),
+            ),
+        ];
+
+        let instance = Instance::new(InstanceCreateInfo {
+            enabled_extensions: InstanceExtensions {
+                ext_layer_settings: true,
+                ..InstanceExtensions::none()
+            },
+            enabled_layers: vec![layer_name.to_owned()],
+            layer_settings: layer_settings.clone(),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(instance.layer_settings(), layer_settings.as_slice());
+    }
 }
@@ -62,9 +62,11 @@
 #![allow(dead_code)] // TODO: remove
 #![allow(unused_variables)] // TODO: remove
 
+pub use ash;
 pub use ash::vk::Handle;
 pub use half;
 use std::{
+    borrow::Cow,
     error, fmt,
     ops::Deref,
     sync::{Arc, MutexGuard},
@@ -75,21 +77,30 @@ pub use version::Version;
 mod tests;
 #[macro_use]
 mod extensions;
+#[macro_use]
+mod trace;
 pub mod buffer;
 pub mod command_buffer;
 pub mod descriptor_set;
 pub mod device;
+pub mod extension_chain;
 pub mod format;
+pub mod indirect;
 mod version;
 #[macro_use]
 pub mod render_pass;
+#[cfg(feature = "renderdoc")]
+pub mod renderdoc;
 mod fns;
 pub mod image;
 pub mod instance;
 pub mod memory;
+pub mod openxr;
 pub mod pipeline;
+pub mod profiler;
 pub mod query;
 pub mod range_set;
+pub mod render_graph;
 pub mod sampler;
 pub mod shader;
 pub mod swapchain;
@@ -160,6 +171,71 @@ impl From<Error> for OomError {
     }
 }
 
+/// Error that can happen when the parameters of a function break a rule of the Vulkan
+/// specification.
+///
+/// The rules are identified by their VUIDs (valid usage IDs), which are stable, so they can be
+/// matched on in tests and searched for in the specification. The offending values are given in
+/// `problem`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    /// A short identifier of the kind of error, such as `"device_mask_zero"`. Unlike `problem`,
+    /// it doesn't change between versions of vulkano, so it can be matched on even if the broken
+    /// rule has no VUID.
+    pub code: &'static str,
+
+    /// The function, parameter or field that the error is about, such as `"set_device_mask"` or
+    /// `"QueryPoolCreateInfo.query_count"`.
+    pub context: &'static str,
+
+    /// A description of what is wrong, including the offending values.
+    pub problem: Cow<'static, str>,
+
+    /// The VUIDs of the rules that were broken. This is empty if the rules don't have VUIDs.
+    pub vuids: &'static [&'static str],
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for ValidationError {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            code: "",
+            context: "",
+            problem: Cow::Borrowed(""),
+            vuids: &[],
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl ValidationError {
+    /// Returns whether the rule with the VUID `vuid` was broken.
+    #[inline]
+    pub fn has_vuid(&self, vuid: &str) -> bool {
+        self.vuids.contains(&vuid)
+    }
+}
+
+impl error::Error for ValidationError {}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "{}: {} (error code: {}",
+            self.context, self.problem, self.code
+        )?;
+
+        if !self.vuids.is_empty() {
+            write!(f, ", Vulkan VUIDs: {}", self.vuids.join(", "))?;
+        }
+
+        write!(f, ")")
+    }
+}
+
 /// All possible success codes returned by any Vulkan function.
 #[derive(Debug, Copy, Clone)]
 #[repr(i32)]
@@ -200,6 +276,8 @@ pub(crate) enum Error {
     OutOfPoolMemory = ash::vk::Result::ERROR_OUT_OF_POOL_MEMORY_KHR.as_raw(),
     InvalidExternalHandle = ash::vk::Result::ERROR_INVALID_EXTERNAL_HANDLE.as_raw(),
     FullScreenExclusiveLost = ash::vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT.as_raw(),
+    CompressionExhausted = ash::vk::Result::ERROR_COMPRESSION_EXHAUSTED_EXT.as_raw(),
+    NotPermitted = ash::vk::Result::ERROR_NOT_PERMITTED_KHR.as_raw(),
 }
 
 /// Checks whether the result returned correctly.
@@ -233,6 +311,8 @@ fn check_errors(result: ash::vk::Result) -> Result<Success, Error> {
         ash::vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT => {
             Err(Error::FullScreenExclusiveLost)
         }
+        ash::vk::Result::ERROR_COMPRESSION_EXHAUSTED_EXT => Err(Error::CompressionExhausted),
+        ash::vk::Result::ERROR_NOT_PERMITTED_KHR => Err(Error::NotPermitted),
         ash::vk::Result::ERROR_INVALID_SHADER_NV => panic!(
             "Vulkan function returned \
                                                VK_ERROR_INVALID_SHADER_NV"
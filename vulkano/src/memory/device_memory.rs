@@ -10,7 +10,11 @@
 use super::DedicatedAllocation;
 use crate::{
     check_errors,
-    device::{physical::MemoryType, Device, DeviceOwned},
+    device::{
+        object_tracker::{TrackedObject, TrackedObjectType},
+        physical::MemoryType,
+        Device, DeviceOwned,
+    },
     DeviceSize, Error, OomError, Version, VulkanObject,
 };
 use std::{
@@ -55,8 +59,10 @@ pub struct DeviceMemory {
     allocation_size: DeviceSize,
     memory_type_index: u32,
     export_handle_types: ExternalMemoryHandleTypes,
+    priority: Mutex<f32>,
 
     mapped: Mutex<bool>,
+    tracked: TrackedObject,
 }
 
 impl DeviceMemory {
@@ -82,8 +88,11 @@ impl DeviceMemory {
             memory_type_index,
             dedicated_allocation,
             export_handle_types,
+            priority,
+            device_mask: _,
             _ne: _,
         } = allocate_info;
+        let tracked = device.track_object(TrackedObjectType::DeviceMemory);
 
         Ok(DeviceMemory {
             handle,
@@ -92,8 +101,10 @@ impl DeviceMemory {
             allocation_size,
             memory_type_index,
             export_handle_types,
+            priority: Mutex::new(priority),
 
             mapped: Mutex::new(false),
+            tracked,
         })
     }
 
@@ -121,8 +132,11 @@ impl DeviceMemory {
             memory_type_index,
             dedicated_allocation,
             export_handle_types,
+            priority,
+            device_mask: _,
             _ne: _,
         } = allocate_info;
+        let tracked = device.track_object(TrackedObjectType::DeviceMemory);
 
         Ok(DeviceMemory {
             handle,
@@ -131,8 +145,10 @@ impl DeviceMemory {
             allocation_size,
             memory_type_index,
             export_handle_types,
+            priority: Mutex::new(priority),
 
             mapped: Mutex::new(false),
+            tracked,
         })
     }
 
@@ -146,6 +162,8 @@ impl DeviceMemory {
             memory_type_index,
             ref mut dedicated_allocation,
             export_handle_types,
+            priority,
+            device_mask,
             _ne: _,
         } = allocate_info;
 
@@ -222,6 +240,36 @@ impl DeviceMemory {
             }
         }
 
+        // VUID-VkMemoryPriorityAllocateInfoEXT-priority-02602
+        assert!((0.0..=1.0).contains(&priority));
+
+        if priority != 0.5 && !device.enabled_extensions().ext_memory_priority {
+            return Err(DeviceMemoryAllocationError::ExtensionNotEnabled {
+                extension: "ext_memory_priority",
+                reason: "`priority` was not 0.5",
+            });
+        }
+
+        if let Some(device_mask) = device_mask {
+            if !(device.api_version() >= Version::V1_1
+                || device.enabled_extensions().khr_device_group)
+            {
+                return Err(DeviceMemoryAllocationError::ExtensionNotEnabled {
+                    extension: "khr_device_group",
+                    reason: "`device_mask` was `Some`",
+                });
+            }
+
+            // VUID-VkMemoryAllocateFlagsInfo-deviceMask-00675
+            // VUID-VkMemoryAllocateFlagsInfo-deviceMask-00676
+            if !device.is_valid_device_mask(device_mask) {
+                return Err(DeviceMemoryAllocationError::DeviceMaskInvalid {
+                    device_mask,
+                    physical_device_count: device.physical_devices().len() as u32,
+                });
+            }
+        }
+
         // VUID-VkMemoryAllocateInfo-pNext-00639
         // VUID-VkExportMemoryAllocateInfo-handleTypes-00656
         // TODO: how do you fullfill this when you don't know the image or buffer parameters?
@@ -311,6 +359,8 @@ impl DeviceMemory {
             memory_type_index,
             dedicated_allocation,
             export_handle_types,
+            priority,
+            device_mask,
             _ne: _,
         } = allocate_info;
 
@@ -351,6 +401,29 @@ impl DeviceMemory {
             allocate_info = allocate_info.push_next(info);
         }
 
+        let mut priority_allocate_info = if device.enabled_extensions().ext_memory_priority {
+            Some(ash::vk::MemoryPriorityAllocateInfoEXT {
+                priority,
+                ..Default::default()
+            })
+        } else {
+            None
+        };
+
+        if let Some(info) = priority_allocate_info.as_mut() {
+            allocate_info = allocate_info.push_next(info);
+        }
+
+        let mut flags_info = device_mask.map(|device_mask| ash::vk::MemoryAllocateFlagsInfo {
+            flags: ash::vk::MemoryAllocateFlags::DEVICE_MASK,
+            device_mask,
+            ..Default::default()
+        });
+
+        if let Some(info) = flags_info.as_mut() {
+            allocate_info = allocate_info.push_next(info);
+        }
+
         #[cfg(unix)]
         let mut import_fd_info = match import_info {
             Some(MemoryImportInfo::Fd { handle_type, file }) => {
@@ -395,6 +468,8 @@ impl DeviceMemory {
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         *allocation_count += 1;
 
         Ok(handle)
@@ -415,6 +490,95 @@ impl DeviceMemory {
         self.allocation_size
     }
 
+    /// Returns the priority of the memory allocation, relative to other allocations.
+    ///
+    /// This is the value that was given when allocating the memory, or the value of the last
+    /// call to [`set_priority`](DeviceMemory::set_priority).
+    #[inline]
+    pub fn priority(&self) -> f32 {
+        *self.priority.lock().unwrap()
+    }
+
+    /// Changes the priority of the memory allocation, relative to other allocations.
+    ///
+    /// When the device runs out of device-local memory, the implementation may move allocations
+    /// to system memory to make room for new ones. Allocations with a lower priority are moved
+    /// first.
+    ///
+    /// The [`pageable_device_local_memory`](crate::device::Features::pageable_device_local_memory)
+    /// feature must be enabled on the device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `priority` is not between 0.0 and 1.0 inclusive.
+    pub fn set_priority(&self, priority: f32) -> Result<(), DeviceMemoryPriorityError> {
+        if !self
+            .device
+            .enabled_extensions()
+            .ext_pageable_device_local_memory
+        {
+            return Err(DeviceMemoryPriorityError::ExtensionNotEnabled {
+                extension: "ext_pageable_device_local_memory",
+                reason: "called `set_priority`",
+            });
+        }
+
+        if !self.device.enabled_features().pageable_device_local_memory {
+            return Err(DeviceMemoryPriorityError::FeatureNotEnabled {
+                feature: "pageable_device_local_memory",
+                reason: "called `set_priority`",
+            });
+        }
+
+        // VUID-vkSetDeviceMemoryPriorityEXT-priority-06258
+        assert!((0.0..=1.0).contains(&priority));
+
+        let mut current = self.priority.lock().unwrap();
+
+        unsafe {
+            let fns = self.device.fns();
+            (fns.ext_pageable_device_local_memory
+                .set_device_memory_priority_ext)(
+                self.device.internal_object(),
+                self.handle,
+                priority,
+            );
+        }
+
+        *current = priority;
+
+        Ok(())
+    }
+
+    /// Retrieves the amount of lazily-allocated memory that is currently committed for this
+    /// memory object, in bytes.
+    ///
+    /// The result may be outdated by the time it is returned, as the implementation is free to
+    /// commit more memory at any time while the memory is in use.
+    ///
+    /// Returns an error if the memory was not allocated from a
+    /// [lazily allocated](MemoryType::is_lazily_allocated) memory type.
+    #[inline]
+    pub fn commitment(&self) -> Result<DeviceSize, DeviceMemoryCommitmentError> {
+        // VUID-vkGetDeviceMemoryCommitment-memory-00690
+        if !self.memory_type().is_lazily_allocated() {
+            return Err(DeviceMemoryCommitmentError::MemoryTypeNotLazilyAllocated {
+                memory_type_index: self.memory_type_index,
+            });
+        }
+
+        unsafe {
+            let fns = self.device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.v1_0.get_device_memory_commitment)(
+                self.device.internal_object(),
+                self.handle,
+                output.as_mut_ptr(),
+            );
+            Ok(output.assume_init())
+        }
+    }
+
     /// Exports the device memory into a Unix file descriptor. The caller owns the returned `File`.
     ///
     /// # Panic
@@ -567,6 +731,13 @@ pub enum DeviceMemoryAllocationError {
         memory_type_count: u32,
     },
 
+    /// The provided `device_mask` was zero, or contained bits for physical devices that the
+    /// device was not created from.
+    DeviceMaskInvalid {
+        device_mask: u32,
+        physical_device_count: u32,
+    },
+
     /// Spec violation, containing the Valid Usage ID (VUID) from the Vulkan spec.
     // TODO: Remove
     SpecViolation(u32),
@@ -624,6 +795,11 @@ impl fmt::Display for DeviceMemoryAllocationError {
                 "the provided `memory_type_index` ({}) was not less than the number of memory types in the physical device ({})",
                 memory_type_index, memory_type_count,
             ),
+            Self::DeviceMaskInvalid { device_mask, physical_device_count } => write!(
+                fmt,
+                "the provided `device_mask` ({:#b}) was zero, or contained bits for physical devices that the device was not created from (the device has {} physical devices)",
+                device_mask, physical_device_count,
+            ),
 
             Self::SpecViolation(u) => {
                 write!(fmt, "valid usage ID check {} failed", u)
@@ -684,6 +860,29 @@ pub struct MemoryAllocateInfo<'d> {
     /// The handle types that can be exported from the allocated memory.
     pub export_handle_types: ExternalMemoryHandleTypes,
 
+    /// The priority of the allocation, relative to other allocations, between 0.0 and 1.0.
+    ///
+    /// When the device runs out of device-local memory, allocations with a lower priority are
+    /// more likely to be moved to system memory. A value other than 0.5 requires the
+    /// [`ext_memory_priority`](crate::device::DeviceExtensions::ext_memory_priority) extension to
+    /// be enabled on the device.
+    ///
+    /// The default value is `0.5`.
+    pub priority: f32,
+
+    /// For a device that was created from several physical devices, the device indices of the
+    /// physical devices to allocate the memory on, as a bit mask.
+    ///
+    /// If this is `None`, the memory is allocated on all the physical devices of the device.
+    /// Otherwise, the device API version must be at least 1.1, or the
+    /// [`khr_device_group`](crate::device::DeviceExtensions::khr_device_group) extension must be
+    /// enabled on the device. If the device group doesn't support
+    /// [subset allocation](crate::device::physical::PhysicalDeviceGroup::subset_allocation), the
+    /// memory is still allocated on all the physical devices.
+    ///
+    /// The default value is `None`.
+    pub device_mask: Option<u32>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -695,6 +894,8 @@ impl Default for MemoryAllocateInfo<'static> {
             memory_type_index: u32::MAX,
             dedicated_allocation: None,
             export_handle_types: ExternalMemoryHandleTypes::none(),
+            priority: 0.5,
+            device_mask: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -708,6 +909,8 @@ impl<'d> MemoryAllocateInfo<'d> {
             memory_type_index: u32::MAX,
             dedicated_allocation: Some(dedicated_allocation),
             export_handle_types: ExternalMemoryHandleTypes::none(),
+            priority: 0.5,
+            device_mask: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -737,6 +940,12 @@ pub enum MemoryImportInfo {
     /// - If `file` was not created by the Vulkan API, then
     ///   [`MemoryAllocateInfo::memory_type_index`] must be one of the memory types returned by
     ///   [`Device::memory_fd_properties`].
+    /// - If `handle_type` is [`ExternalMemoryHandleType::OpaqueFd`], `file` must have been
+    ///   exported from a device whose physical device is compatible with the physical device of
+    ///   the importing device, see
+    ///   [`PhysicalDevice::is_opaque_handle_compatible`](crate::device::physical::PhysicalDevice::is_opaque_handle_compatible).
+    ///   To share memory between physical devices that aren't compatible, use
+    ///   [`ExternalMemoryHandleType::DmaBuf`] instead.
     Fd {
         handle_type: ExternalMemoryHandleType,
         file: File,
@@ -1054,6 +1263,59 @@ impl From<OomError> for DeviceMemoryExportError {
     }
 }
 
+/// Error type returned by [`DeviceMemory::set_priority`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeviceMemoryPriorityError {
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl error::Error for DeviceMemoryPriorityError {}
+
+impl fmt::Display for DeviceMemoryPriorityError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
+        }
+    }
+}
+
+/// Error type returned by [`DeviceMemory::commitment`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeviceMemoryCommitmentError {
+    /// The memory was not allocated from a lazily allocated memory type.
+    MemoryTypeNotLazilyAllocated { memory_type_index: u32 },
+}
+
+impl error::Error for DeviceMemoryCommitmentError {}
+
+impl fmt::Display for DeviceMemoryCommitmentError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::MemoryTypeNotLazilyAllocated { memory_type_index } => write!(
+                fmt,
+                "the memory type of the allocation ({}) is not lazily allocated",
+                memory_type_index,
+            ),
+        }
+    }
+}
+
 /// Represents device memory that has been mapped in a CPU-accessible space.
 ///
 /// In order to access the contents of the allocated memory, you can use the `read` and `write`
@@ -1478,6 +1740,8 @@ mod tests {
     use super::MemoryAllocateInfo;
     use crate::memory::DeviceMemory;
     use crate::memory::DeviceMemoryAllocationError;
+    use crate::memory::DeviceMemoryCommitmentError;
+    use crate::memory::DeviceMemoryPriorityError;
     use crate::OomError;
 
     #[test]
@@ -1597,4 +1861,95 @@ mod tests {
         }
         assert_eq!(*device.allocation_count().lock().unwrap(), 1);
     }
+
+    #[test]
+    fn priority_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_type = device.physical_device().memory_types().next().unwrap();
+
+        match DeviceMemory::allocate(
+            device.clone(),
+            MemoryAllocateInfo {
+                allocation_size: 256,
+                memory_type_index: memory_type.id(),
+                priority: 0.25,
+                ..Default::default()
+            },
+        ) {
+            Err(DeviceMemoryAllocationError::ExtensionNotEnabled { .. }) => (),
+            _ => panic!(),
+        }
+
+        let memory = DeviceMemory::allocate(
+            device.clone(),
+            MemoryAllocateInfo {
+                allocation_size: 256,
+                memory_type_index: memory_type.id(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(memory.priority(), 0.5);
+
+        match memory.set_priority(0.25) {
+            Err(DeviceMemoryPriorityError::ExtensionNotEnabled { .. }) => (),
+            _ => panic!(),
+        }
+        assert_eq!(memory.priority(), 0.5);
+    }
+
+    #[test]
+    fn commitment_not_lazily_allocated() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_type = match device
+            .physical_device()
+            .memory_types()
+            .find(|t| !t.is_lazily_allocated())
+        {
+            Some(t) => t,
+            None => return,
+        };
+
+        let memory = DeviceMemory::allocate(
+            device.clone(),
+            MemoryAllocateInfo {
+                allocation_size: 256,
+                memory_type_index: memory_type.id(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        match memory.commitment() {
+            Err(DeviceMemoryCommitmentError::MemoryTypeNotLazilyAllocated {
+                memory_type_index,
+            }) => assert_eq!(memory_type_index, memory_type.id()),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn commitment_lazily_allocated() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_type = match device
+            .physical_device()
+            .memory_types()
+            .find(|t| t.is_lazily_allocated())
+        {
+            Some(t) => t,
+            None => return,
+        };
+
+        let memory = DeviceMemory::allocate(
+            device.clone(),
+            MemoryAllocateInfo {
+                allocation_size: 4096,
+                memory_type_index: memory_type.id(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(memory.commitment().unwrap() <= memory.allocation_size());
+    }
 }
@@ -94,9 +94,10 @@
 
 pub use self::{
     device_memory::{
-        DeviceMemory, DeviceMemoryAllocationError, DeviceMemoryExportError,
-        ExternalMemoryHandleType, ExternalMemoryHandleTypes, MappedDeviceMemory,
-        MemoryAllocateInfo, MemoryImportInfo, MemoryMapError,
+        DeviceMemory, DeviceMemoryAllocationError, DeviceMemoryCommitmentError,
+        DeviceMemoryExportError, DeviceMemoryPriorityError, ExternalMemoryHandleType,
+        ExternalMemoryHandleTypes, MappedDeviceMemory, MemoryAllocateInfo, MemoryImportInfo,
+        MemoryMapError,
     },
     pool::MemoryPool,
 };
@@ -15,7 +15,9 @@ use crate::memory::DeviceMemory;
 use crate::memory::DeviceMemoryAllocationError;
 use crate::memory::MappedDeviceMemory;
 use crate::DeviceSize;
+use crate::VulkanObject;
 use std::cmp;
+use std::collections::HashMap;
 use std::ops::Range;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -27,6 +29,8 @@ pub struct StdHostVisibleMemoryTypePool {
     memory_type: u32,
     // TODO: obviously very inefficient
     occupied: Mutex<Vec<(Arc<MappedDeviceMemory>, Vec<Range<DeviceSize>>)>>,
+    // Tags of the allocations, keyed by their memory block and offset.
+    tags: Mutex<HashMap<(ash::vk::DeviceMemory, DeviceSize), String>>,
 }
 
 impl StdHostVisibleMemoryTypePool {
@@ -53,6 +57,7 @@ impl StdHostVisibleMemoryTypePool {
             device: device.clone(),
             memory_type: memory_type.id(),
             occupied: Mutex::new(Vec::new()),
+            tags: Mutex::new(HashMap::new()),
         })
     }
 
@@ -149,6 +154,26 @@ impl StdHostVisibleMemoryTypePool {
             .memory_type_by_id(self.memory_type)
             .unwrap()
     }
+
+    // Returns the size of each memory block of the pool, together with the ranges that are
+    // allocated in it and their tags.
+    pub(crate) fn blocks(&self) -> Vec<(DeviceSize, Vec<(Range<DeviceSize>, Option<String>)>)> {
+        let occupied = self.occupied.lock().unwrap();
+        let tags = self.tags.lock().unwrap();
+
+        occupied
+            .iter()
+            .map(|(dev_mem, entries)| {
+                let handle = (**dev_mem).as_ref().internal_object();
+                let entries = entries
+                    .iter()
+                    .map(|range| (range.clone(), tags.get(&(handle, range.start)).cloned()))
+                    .collect();
+
+                ((**dev_mem).as_ref().allocation_size(), entries)
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug)]
@@ -174,6 +199,29 @@ impl StdHostVisibleMemoryTypePoolAlloc {
     pub fn size(&self) -> DeviceSize {
         self.size
     }
+
+    /// Returns the tag of the allocation, if it has one.
+    #[inline]
+    pub fn tag(&self) -> Option<String> {
+        let key = ((*self.memory).as_ref().internal_object(), self.offset);
+        self.pool.tags.lock().unwrap().get(&key).cloned()
+    }
+
+    /// Sets or removes the tag of the allocation.
+    #[inline]
+    pub fn set_tag(&self, tag: Option<&str>) {
+        let key = ((*self.memory).as_ref().internal_object(), self.offset);
+        let mut tags = self.pool.tags.lock().unwrap();
+
+        match tag {
+            Some(tag) => {
+                tags.insert(key, tag.to_owned());
+            }
+            None => {
+                tags.remove(&key);
+            }
+        }
+    }
 }
 
 impl Drop for StdHostVisibleMemoryTypePoolAlloc {
@@ -186,5 +234,8 @@ impl Drop for StdHostVisibleMemoryTypePoolAlloc {
             .unwrap();
 
         entries.1.retain(|e| e.start != self.offset);
+
+        let key = ((*self.memory).as_ref().internal_object(), self.offset);
+        self.pool.tags.lock().unwrap().remove(&key);
     }
 }
@@ -11,8 +11,14 @@ pub use self::host_visible::StdHostVisibleMemoryTypePool;
 pub use self::host_visible::StdHostVisibleMemoryTypePoolAlloc;
 pub use self::non_host_visible::StdNonHostVisibleMemoryTypePool;
 pub use self::non_host_visible::StdNonHostVisibleMemoryTypePoolAlloc;
+pub use self::policy::MemoryPropertyFlags;
+pub use self::policy::MemoryTypePolicy;
 pub use self::pool::StdMemoryPool;
 pub use self::pool::StdMemoryPoolAlloc;
+pub use self::report::MemoryAllocationReport;
+pub use self::report::MemoryHeapReport;
+pub use self::report::MemoryPoolReport;
+pub use self::report::MemoryTagReport;
 use crate::device::physical::MemoryType;
 use crate::device::{Device, DeviceOwned};
 use crate::memory::device_memory::MemoryAllocateInfo;
@@ -27,7 +33,9 @@ use std::sync::Arc;
 
 mod host_visible;
 mod non_host_visible;
+mod policy;
 mod pool;
+mod report;
 
 // If the allocation size goes beyond this, then we perform a dedicated allocation which bypasses
 // the pool. This prevents the pool from overallocating a significant amount of memory.
@@ -42,30 +50,87 @@ fn choose_allocation_memory_type<'s, F>(
 where
     F: FnMut(MemoryType) -> AllocFromRequirementsFilter,
 {
-    let mem_ty = {
-        let mut filter = |ty: MemoryType| {
-            if map == MappingRequirement::Map && !ty.is_host_visible() {
-                return AllocFromRequirementsFilter::Forbidden;
-            }
-            filter(ty)
-        };
-        let first_loop = device
-            .physical_device()
-            .memory_types()
-            .map(|t| (t, AllocFromRequirementsFilter::Preferred));
-        let second_loop = device
-            .physical_device()
-            .memory_types()
-            .map(|t| (t, AllocFromRequirementsFilter::Allowed));
-        first_loop
-            .chain(second_loop)
-            .filter(|&(t, _)| (requirements.memory_type_bits & (1 << t.id())) != 0)
-            .filter(|&(t, rq)| filter(t) == rq)
-            .next()
-            .expect("Couldn't find a memory type to allocate from")
-            .0
-    };
-    mem_ty
+    choose_ranked_memory_type(device, requirements, map, |ty| match filter(ty) {
+        AllocFromRequirementsFilter::Preferred => Some(0),
+        AllocFromRequirementsFilter::Allowed => Some(1),
+        AllocFromRequirementsFilter::Forbidden => None,
+    })
+}
+
+// Returns the memory type with the lowest rank, or the one with the lowest index among those with
+// the same rank. A rank of `None` means that the memory type must not be used.
+fn choose_ranked_memory_type<'s, F>(
+    device: &'s Arc<Device>,
+    requirements: &MemoryRequirements,
+    map: MappingRequirement,
+    mut rank: F,
+) -> MemoryType<'s>
+where
+    F: FnMut(MemoryType) -> Option<usize>,
+{
+    device
+        .physical_device()
+        .memory_types()
+        .filter(|t| (requirements.memory_type_bits & (1 << t.id())) != 0)
+        .filter(|t| map == MappingRequirement::DoNotMap || t.is_host_visible())
+        .filter_map(|t| rank(t).map(|rank| (t, rank)))
+        .min_by_key(|&(t, rank)| (rank, t.id()))
+        .expect("Couldn't find a memory type to allocate from")
+        .0
+}
+
+// Allocates memory from `memory_type`, performing a dedicated allocation if it is preferred.
+fn alloc_from_memory_type<P>(
+    pool: &P,
+    memory_type: MemoryType,
+    requirements: &MemoryRequirements,
+    layout: AllocLayout,
+    map: MappingRequirement,
+    dedicated_allocation: Option<DedicatedAllocation>,
+) -> Result<PotentialDedicatedAllocation<P::Alloc>, DeviceMemoryAllocationError>
+where
+    P: MemoryPool + ?Sized,
+{
+    // Redirect to `pool.alloc_generic` if we don't perform a dedicated allocation.
+    if !requirements.prefer_dedicated && requirements.size <= MAX_POOL_ALLOC {
+        let alloc = pool.alloc_generic(
+            memory_type,
+            requirements.size,
+            requirements.alignment,
+            layout,
+            map,
+        )?;
+        return Ok(alloc.into());
+    }
+    if dedicated_allocation.is_none() {
+        let alloc = pool.alloc_generic(
+            memory_type,
+            requirements.size,
+            requirements.alignment,
+            layout,
+            map,
+        )?;
+        return Ok(alloc.into());
+    }
+
+    // If we reach here, then we perform a dedicated alloc.
+    let memory = DeviceMemory::allocate(
+        pool.device().clone(),
+        MemoryAllocateInfo {
+            allocation_size: requirements.size,
+            memory_type_index: memory_type.id(),
+            dedicated_allocation,
+            ..Default::default()
+        },
+    )?;
+
+    match map {
+        MappingRequirement::Map => {
+            let mapped_memory = MappedDeviceMemory::new(memory, 0..requirements.size)?;
+            Ok(PotentialDedicatedAllocation::DedicatedMapped(mapped_memory))
+        }
+        MappingRequirement::DoNotMap => Ok(PotentialDedicatedAllocation::Dedicated(memory)),
+    }
 }
 
 /// Allocate dedicated memory with exportable fd.
@@ -185,46 +250,60 @@ pub unsafe trait MemoryPool: DeviceOwned {
         // Choose a suitable memory type.
         let memory_type = choose_allocation_memory_type(self.device(), requirements, filter, map);
 
-        // Redirect to `self.alloc_generic` if we don't perform a dedicated allocation.
-        if !requirements.prefer_dedicated && requirements.size <= MAX_POOL_ALLOC {
-            let alloc = self.alloc_generic(
-                memory_type,
-                requirements.size,
-                requirements.alignment,
-                layout,
-                map,
-            )?;
-            return Ok(alloc.into());
-        }
-        if dedicated_allocation.is_none() {
-            let alloc = self.alloc_generic(
-                memory_type,
-                requirements.size,
-                requirements.alignment,
-                layout,
-                map,
-            )?;
-            return Ok(alloc.into());
-        }
+        alloc_from_memory_type(
+            self,
+            memory_type,
+            requirements,
+            layout,
+            map,
+            dedicated_allocation,
+        )
+    }
 
-        // If we reach here, then we perform a dedicated alloc.
-        let memory = DeviceMemory::allocate(
-            self.device().clone(),
-            MemoryAllocateInfo {
-                allocation_size: requirements.size,
-                memory_type_index: memory_type.id(),
-                dedicated_allocation,
-                ..Default::default()
-            },
-        )?;
+    /// Chooses a memory type according to `policy` and allocates memory from it.
+    ///
+    /// This behaves like `alloc_from_requirements`, except that the memory type is chosen by a
+    /// [`MemoryTypePolicy`] instead of a filter function. If `map` is `MappingRequirement::Map`,
+    /// then non-host-visible memory types are automatically filtered out.
+    ///
+    /// # Safety
+    ///
+    /// Implementation safety:
+    ///
+    /// - The returned object must match the requirements.
+    /// - When a linear object is allocated next to an optimal object, it is mandatory that
+    ///   the boundary is aligned to the value of the `buffer_image_granularity` limit.
+    /// - If `dedicated` is not `None`, the returned memory must either not be dedicated or be
+    ///   dedicated to the resource that was passed.
+    ///
+    /// Note that it is not unsafe to *call* this function, but it is unsafe to bind the memory
+    /// returned by this function to a resource.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if no memory type satisfies `policy`.
+    /// - Panics if `size` is 0.
+    /// - Panics if `alignment` is 0.
+    ///
+    fn alloc_with_policy(
+        &self,
+        requirements: &MemoryRequirements,
+        layout: AllocLayout,
+        map: MappingRequirement,
+        dedicated_allocation: Option<DedicatedAllocation>,
+        policy: &MemoryTypePolicy,
+    ) -> Result<PotentialDedicatedAllocation<Self::Alloc>, DeviceMemoryAllocationError> {
+        let memory_type =
+            choose_ranked_memory_type(self.device(), requirements, map, |t| policy.rank(t));
 
-        match map {
-            MappingRequirement::Map => {
-                let mapped_memory = MappedDeviceMemory::new(memory, 0..requirements.size)?;
-                Ok(PotentialDedicatedAllocation::DedicatedMapped(mapped_memory))
-            }
-            MappingRequirement::DoNotMap => Ok(PotentialDedicatedAllocation::Dedicated(memory)),
-        }
+        alloc_from_memory_type(
+            self,
+            memory_type,
+            requirements,
+            layout,
+            map,
+            dedicated_allocation,
+        )
     }
 }
 
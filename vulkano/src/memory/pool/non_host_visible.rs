@@ -14,7 +14,9 @@ use crate::memory::device_memory::MemoryAllocateInfo;
 use crate::memory::DeviceMemory;
 use crate::memory::DeviceMemoryAllocationError;
 use crate::DeviceSize;
+use crate::VulkanObject;
 use std::cmp;
+use std::collections::HashMap;
 use std::ops::Range;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -26,6 +28,8 @@ pub struct StdNonHostVisibleMemoryTypePool {
     memory_type: u32,
     // TODO: obviously very inefficient
     occupied: Mutex<Vec<(Arc<DeviceMemory>, Vec<Range<DeviceSize>>)>>,
+    // Tags of the allocations, keyed by their memory block and offset.
+    tags: Mutex<HashMap<(ash::vk::DeviceMemory, DeviceSize), String>>,
 }
 
 impl StdNonHostVisibleMemoryTypePool {
@@ -53,6 +57,7 @@ impl StdNonHostVisibleMemoryTypePool {
             device: device.clone(),
             memory_type: memory_type.id(),
             occupied: Mutex::new(Vec::new()),
+            tags: Mutex::new(HashMap::new()),
         })
     }
 
@@ -148,6 +153,45 @@ impl StdNonHostVisibleMemoryTypePool {
             .memory_type_by_id(self.memory_type)
             .unwrap()
     }
+
+    // Returns the size of each memory block of the pool, together with the ranges that are
+    // allocated in it and their tags.
+    pub(crate) fn blocks(&self) -> Vec<(DeviceSize, Vec<(Range<DeviceSize>, Option<String>)>)> {
+        let occupied = self.occupied.lock().unwrap();
+        let tags = self.tags.lock().unwrap();
+
+        occupied
+            .iter()
+            .map(|(dev_mem, entries)| {
+                let handle = dev_mem.internal_object();
+                let entries = entries
+                    .iter()
+                    .map(|range| (range.clone(), tags.get(&(handle, range.start)).cloned()))
+                    .collect();
+
+                (dev_mem.allocation_size(), entries)
+            })
+            .collect()
+    }
+
+    /// Returns the total amount of lazily-allocated memory that is currently committed for all
+    /// the memory blocks of this pool, in bytes.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the memory type of the pool is not
+    ///   [lazily allocated](MemoryType::is_lazily_allocated).
+    #[inline]
+    pub fn commitment(&self) -> DeviceSize {
+        assert!(self.memory_type().is_lazily_allocated());
+
+        self.occupied
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(dev_mem, _)| dev_mem.commitment().unwrap())
+            .sum()
+    }
 }
 
 #[derive(Debug)]
@@ -173,6 +217,29 @@ impl StdNonHostVisibleMemoryTypePoolAlloc {
     pub fn size(&self) -> DeviceSize {
         self.size
     }
+
+    /// Returns the tag of the allocation, if it has one.
+    #[inline]
+    pub fn tag(&self) -> Option<String> {
+        let key = (self.memory.internal_object(), self.offset);
+        self.pool.tags.lock().unwrap().get(&key).cloned()
+    }
+
+    /// Sets or removes the tag of the allocation.
+    #[inline]
+    pub fn set_tag(&self, tag: Option<&str>) {
+        let key = (self.memory.internal_object(), self.offset);
+        let mut tags = self.pool.tags.lock().unwrap();
+
+        match tag {
+            Some(tag) => {
+                tags.insert(key, tag.to_owned());
+            }
+            None => {
+                tags.remove(&key);
+            }
+        }
+    }
 }
 
 impl Drop for StdNonHostVisibleMemoryTypePoolAlloc {
@@ -185,5 +252,8 @@ impl Drop for StdNonHostVisibleMemoryTypePoolAlloc {
             .unwrap();
 
         entries.1.retain(|e| e.start != self.offset);
+
+        let key = (self.memory.internal_object(), self.offset);
+        self.pool.tags.lock().unwrap().remove(&key);
     }
 }
@@ -0,0 +1,238 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::device::physical::MemoryType;
+use smallvec::SmallVec;
+use std::ops::BitOr;
+
+/// Properties of a memory type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MemoryPropertyFlags {
+    /// The memory is located on the device, and is the most efficient for device accesses.
+    pub device_local: bool,
+
+    /// The memory can be mapped and accessed by the host.
+    pub host_visible: bool,
+
+    /// Host writes and device writes are visible to the other party without flushing or
+    /// invalidating.
+    pub host_coherent: bool,
+
+    /// The memory is cached by the host.
+    pub host_cached: bool,
+
+    /// The memory is only allocated when it is first used by the device.
+    pub lazily_allocated: bool,
+
+    /// The memory can only be accessed by the device, and by protected queue operations.
+    pub protected: bool,
+}
+
+impl MemoryPropertyFlags {
+    /// Builds a `MemoryPropertyFlags` with all values set to false.
+    #[inline]
+    pub const fn none() -> Self {
+        MemoryPropertyFlags {
+            device_local: false,
+            host_visible: false,
+            host_coherent: false,
+            host_cached: false,
+            lazily_allocated: false,
+            protected: false,
+        }
+    }
+
+    /// Returns whether any of the fields are set.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        !(self.device_local
+            || self.host_visible
+            || self.host_coherent
+            || self.host_cached
+            || self.lazily_allocated
+            || self.protected)
+    }
+
+    /// Returns whether all of the fields that are set in `other` are also set in `self`.
+    #[inline]
+    pub const fn contains(&self, other: &Self) -> bool {
+        (self.device_local || !other.device_local)
+            && (self.host_visible || !other.host_visible)
+            && (self.host_coherent || !other.host_coherent)
+            && (self.host_cached || !other.host_cached)
+            && (self.lazily_allocated || !other.lazily_allocated)
+            && (self.protected || !other.protected)
+    }
+
+    /// Returns whether any of the fields that are set in `other` are also set in `self`.
+    #[inline]
+    pub const fn intersects(&self, other: &Self) -> bool {
+        (self.device_local && other.device_local)
+            || (self.host_visible && other.host_visible)
+            || (self.host_coherent && other.host_coherent)
+            || (self.host_cached && other.host_cached)
+            || (self.lazily_allocated && other.lazily_allocated)
+            || (self.protected && other.protected)
+    }
+}
+
+impl From<ash::vk::MemoryPropertyFlags> for MemoryPropertyFlags {
+    #[inline]
+    fn from(val: ash::vk::MemoryPropertyFlags) -> Self {
+        MemoryPropertyFlags {
+            device_local: val.intersects(ash::vk::MemoryPropertyFlags::DEVICE_LOCAL),
+            host_visible: val.intersects(ash::vk::MemoryPropertyFlags::HOST_VISIBLE),
+            host_coherent: val.intersects(ash::vk::MemoryPropertyFlags::HOST_COHERENT),
+            host_cached: val.intersects(ash::vk::MemoryPropertyFlags::HOST_CACHED),
+            lazily_allocated: val.intersects(ash::vk::MemoryPropertyFlags::LAZILY_ALLOCATED),
+            protected: val.intersects(ash::vk::MemoryPropertyFlags::PROTECTED),
+        }
+    }
+}
+
+impl BitOr for MemoryPropertyFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        MemoryPropertyFlags {
+            device_local: self.device_local || rhs.device_local,
+            host_visible: self.host_visible || rhs.host_visible,
+            host_coherent: self.host_coherent || rhs.host_coherent,
+            host_cached: self.host_cached || rhs.host_cached,
+            lazily_allocated: self.lazily_allocated || rhs.lazily_allocated,
+            protected: self.protected || rhs.protected,
+        }
+    }
+}
+
+/// Describes how the memory type of an allocation is chosen.
+///
+/// A memory type is a candidate if the resource can be bound to it, if it has all of the
+/// `required_flags` and none of the `excluded_flags`, and if its heap is not in
+/// `excluded_heaps`. Among the candidates, the entries of `preferred_flags` are tried in order,
+/// and the first memory type that has all the flags of an entry is chosen. If no candidate
+/// matches any entry, the first candidate is chosen.
+///
+/// # Example
+///
+/// Forcing an allocation into host-visible memory, preferably device-local as is common on
+/// devices with unified memory:
+///
+/// ```
+/// use vulkano::memory::pool::{MemoryPropertyFlags, MemoryTypePolicy};
+///
+/// let policy = MemoryTypePolicy {
+///     required_flags: MemoryPropertyFlags {
+///         host_visible: true,
+///         ..MemoryPropertyFlags::none()
+///     },
+///     preferred_flags: vec![MemoryPropertyFlags {
+///         device_local: true,
+///         ..MemoryPropertyFlags::none()
+///     }],
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryTypePolicy {
+    /// The properties that the memory type must have.
+    ///
+    /// The default value is [`MemoryPropertyFlags::none()`].
+    pub required_flags: MemoryPropertyFlags,
+
+    /// The sets of properties that the memory type should preferably have, from the most preferred
+    /// to the least preferred.
+    ///
+    /// The default value contains a single entry with `device_local` set.
+    pub preferred_flags: Vec<MemoryPropertyFlags>,
+
+    /// The properties that the memory type must not have.
+    ///
+    /// The default value has `lazily_allocated` and `protected` set.
+    pub excluded_flags: MemoryPropertyFlags,
+
+    /// The indices of the memory heaps that must not be allocated from.
+    ///
+    /// The default value is empty.
+    pub excluded_heaps: SmallVec<[u32; 2]>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for MemoryTypePolicy {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            required_flags: MemoryPropertyFlags::none(),
+            preferred_flags: vec![MemoryPropertyFlags {
+                device_local: true,
+                ..MemoryPropertyFlags::none()
+            }],
+            excluded_flags: MemoryPropertyFlags {
+                lazily_allocated: true,
+                protected: true,
+                ..MemoryPropertyFlags::none()
+            },
+            excluded_heaps: SmallVec::new(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl MemoryTypePolicy {
+    /// Returns the rank of `memory_type` according to the policy, where lower is better, or `None`
+    /// if the memory type must not be used.
+    pub(crate) fn rank(&self, memory_type: MemoryType) -> Option<usize> {
+        let flags = memory_type.property_flags();
+
+        if !flags.contains(&self.required_flags)
+            || flags.intersects(&self.excluded_flags)
+            || self.excluded_heaps.contains(&memory_type.heap().id())
+        {
+            return None;
+        }
+
+        Some(
+            self.preferred_flags
+                .iter()
+                .position(|preferred| flags.contains(preferred))
+                .unwrap_or(self.preferred_flags.len()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MemoryPropertyFlags;
+
+    #[test]
+    fn contains_intersects() {
+        let host = MemoryPropertyFlags {
+            host_visible: true,
+            host_coherent: true,
+            ..MemoryPropertyFlags::none()
+        };
+        let visible = MemoryPropertyFlags {
+            host_visible: true,
+            ..MemoryPropertyFlags::none()
+        };
+        let device = MemoryPropertyFlags {
+            device_local: true,
+            ..MemoryPropertyFlags::none()
+        };
+
+        assert!(host.contains(&visible));
+        assert!(!visible.contains(&host));
+        assert!(host.contains(&MemoryPropertyFlags::none()));
+        assert!(host.intersects(&visible));
+        assert!(!host.intersects(&device));
+        assert!((host | device).contains(&device));
+    }
+}
@@ -12,8 +12,12 @@ use crate::device::Device;
 use crate::device::DeviceOwned;
 use crate::memory::pool::AllocLayout;
 use crate::memory::pool::MappingRequirement;
+use crate::memory::pool::MemoryAllocationReport;
+use crate::memory::pool::MemoryHeapReport;
 use crate::memory::pool::MemoryPool;
 use crate::memory::pool::MemoryPoolAlloc;
+use crate::memory::pool::MemoryPoolReport;
+use crate::memory::pool::MemoryTagReport;
 use crate::memory::pool::StdHostVisibleMemoryTypePool;
 use crate::memory::pool::StdHostVisibleMemoryTypePoolAlloc;
 use crate::memory::pool::StdNonHostVisibleMemoryTypePool;
@@ -22,6 +26,7 @@ use crate::memory::DeviceMemory;
 use crate::memory::DeviceMemoryAllocationError;
 use crate::memory::MappedDeviceMemory;
 use crate::DeviceSize;
+use std::cmp::max;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -46,6 +51,122 @@ impl StdMemoryPool {
             pools: Mutex::new(HashMap::with_capacity(cap)),
         })
     }
+
+    /// Produces a report of the memory that is currently allocated from the pool, with totals per
+    /// memory heap and per allocation tag. The `largest_count` largest allocations are also
+    /// listed individually.
+    ///
+    /// Dedicated allocations are not owned by the pool, and are not included in the report.
+    pub fn report(&self, largest_count: usize) -> MemoryPoolReport {
+        let physical_device = self.device.physical_device();
+        let mut heaps: Vec<MemoryHeapReport> = physical_device
+            .memory_heaps()
+            .map(|heap| MemoryHeapReport {
+                heap_index: heap.id(),
+                heap_size: heap.size(),
+                block_count: 0,
+                block_bytes: 0,
+                allocation_count: 0,
+                allocated_bytes: 0,
+                largest_free_range: 0,
+                fragmentation: 0.0,
+            })
+            .collect();
+        let mut free_bytes = vec![0; heaps.len()];
+        let mut tags: Vec<MemoryTagReport> = Vec::new();
+        let mut allocations = Vec::new();
+
+        for (&(memory_type_index, _, _), pool) in self.pools.lock().unwrap().iter() {
+            let heap_index = physical_device
+                .memory_type_by_id(memory_type_index)
+                .unwrap()
+                .heap()
+                .id();
+            let heap = &mut heaps[heap_index as usize];
+            let blocks = match pool {
+                Pool::HostVisible(pool) => pool.blocks(),
+                Pool::NonHostVisible(pool) => pool.blocks(),
+            };
+
+            for (block_size, entries) in blocks {
+                heap.block_count += 1;
+                heap.block_bytes += block_size;
+
+                // The entries are sorted by offset, so the free ranges are the gaps between them.
+                let mut free_start = 0;
+
+                for (range, tag) in entries {
+                    let size = range.end - range.start;
+                    heap.allocation_count += 1;
+                    heap.allocated_bytes += size;
+
+                    let free_size = range.start.saturating_sub(free_start);
+                    free_bytes[heap_index as usize] += free_size;
+                    heap.largest_free_range = max(heap.largest_free_range, free_size);
+                    free_start = range.end;
+
+                    match tags.iter_mut().find(|t| t.tag == tag) {
+                        Some(tag_report) => {
+                            tag_report.allocation_count += 1;
+                            tag_report.allocated_bytes += size;
+                        }
+                        None => tags.push(MemoryTagReport {
+                            tag: tag.clone(),
+                            allocation_count: 1,
+                            allocated_bytes: size,
+                        }),
+                    }
+
+                    allocations.push(MemoryAllocationReport {
+                        tag,
+                        memory_type_index,
+                        heap_index,
+                        size,
+                    });
+                }
+
+                let free_size = block_size.saturating_sub(free_start);
+                free_bytes[heap_index as usize] += free_size;
+                heap.largest_free_range = max(heap.largest_free_range, free_size);
+            }
+        }
+
+        for (heap, free_bytes) in heaps.iter_mut().zip(free_bytes) {
+            if free_bytes != 0 {
+                heap.fragmentation = 1.0 - heap.largest_free_range as f32 / free_bytes as f32;
+            }
+        }
+
+        tags.sort_by(|a, b| b.allocated_bytes.cmp(&a.allocated_bytes));
+        allocations.sort_by(|a, b| b.size.cmp(&a.size));
+        allocations.truncate(largest_count);
+
+        MemoryPoolReport {
+            heaps,
+            tags,
+            largest_allocations: allocations,
+        }
+    }
+
+    /// Returns the total amount of lazily-allocated memory that is currently committed for the
+    /// memory blocks of this pool, in bytes.
+    ///
+    /// Only memory blocks of [lazily allocated](MemoryType::is_lazily_allocated) memory types are
+    /// counted. Dedicated allocations are not owned by the pool, and are not counted either; use
+    /// [`DeviceMemory::commitment`] for those.
+    pub fn commitment(&self) -> DeviceSize {
+        self.pools
+            .lock()
+            .unwrap()
+            .values()
+            .map(|pool| match pool {
+                Pool::NonHostVisible(pool) if pool.memory_type().is_lazily_allocated() => {
+                    pool.commitment()
+                }
+                _ => 0,
+            })
+            .sum()
+    }
 }
 
 fn generic_allocation(
@@ -148,6 +269,25 @@ impl StdMemoryPoolAlloc {
             StdMemoryPoolAllocInner::HostVisible(ref mem) => mem.size(),
         }
     }
+
+    /// Returns the tag of the allocation, if it has one.
+    #[inline]
+    pub fn tag(&self) -> Option<String> {
+        match self.inner {
+            StdMemoryPoolAllocInner::NonHostVisible(ref mem) => mem.tag(),
+            StdMemoryPoolAllocInner::HostVisible(ref mem) => mem.tag(),
+        }
+    }
+
+    /// Sets or removes the tag of the allocation. Allocations are grouped by tag in the
+    /// [`report`](StdMemoryPool::report) of the pool.
+    #[inline]
+    pub fn set_tag(&self, tag: Option<&str>) {
+        match self.inner {
+            StdMemoryPoolAllocInner::NonHostVisible(ref mem) => mem.set_tag(tag),
+            StdMemoryPoolAllocInner::HostVisible(ref mem) => mem.set_tag(tag),
+        }
+    }
 }
 
 unsafe impl MemoryPoolAlloc for StdMemoryPoolAlloc {
@@ -181,3 +321,44 @@ enum StdMemoryPoolAllocInner {
     NonHostVisible(StdNonHostVisibleMemoryTypePoolAlloc),
     HostVisible(StdHostVisibleMemoryTypePoolAlloc),
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::memory::pool::AllocLayout;
+    use crate::memory::pool::MappingRequirement;
+    use crate::memory::pool::MemoryPool;
+    use crate::memory::pool::MemoryPoolAlloc;
+    use crate::memory::pool::StdMemoryPool;
+
+    #[test]
+    fn commitment_empty() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = StdMemoryPool::new(device);
+        assert_eq!(pool.commitment(), 0);
+    }
+
+    #[test]
+    fn commitment_lazily_allocated() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_type = match device
+            .physical_device()
+            .memory_types()
+            .find(|t| t.is_lazily_allocated())
+        {
+            Some(t) => t,
+            None => return,
+        };
+
+        let pool = StdMemoryPool::new(device);
+        let alloc = pool
+            .alloc_generic(
+                memory_type,
+                4096,
+                1,
+                AllocLayout::Optimal,
+                MappingRequirement::DoNotMap,
+            )
+            .unwrap();
+        assert!(pool.commitment() <= alloc.memory().allocation_size());
+    }
+}
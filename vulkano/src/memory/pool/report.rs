@@ -0,0 +1,94 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::DeviceSize;
+
+/// A report of the memory that is allocated from a memory pool.
+///
+/// Obtained by calling [`StdMemoryPool::report`](super::StdMemoryPool::report). With the `serde`
+/// feature, the report and the types it contains implement `serde::Serialize`, so that it can be
+/// written out in any format that serde supports.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MemoryPoolReport {
+    /// Totals for each memory heap of the physical device, indexed by heap index.
+    pub heaps: Vec<MemoryHeapReport>,
+
+    /// Totals for each allocation tag, sorted from the largest to the smallest number of
+    /// allocated bytes. Allocations without a tag are grouped under `None`.
+    pub tags: Vec<MemoryTagReport>,
+
+    /// The largest allocations, sorted from largest to smallest.
+    pub largest_allocations: Vec<MemoryAllocationReport>,
+}
+
+/// Totals of the memory allocated from a single memory heap.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MemoryHeapReport {
+    /// The index of the heap.
+    pub heap_index: u32,
+
+    /// The size of the heap in bytes.
+    pub heap_size: DeviceSize,
+
+    /// The number of memory blocks that the pool has allocated from this heap.
+    pub block_count: u32,
+
+    /// The total size in bytes of the memory blocks allocated from this heap.
+    pub block_bytes: DeviceSize,
+
+    /// The number of allocations in the memory blocks of this heap.
+    pub allocation_count: u32,
+
+    /// The total size in bytes of the allocations in the memory blocks of this heap.
+    pub allocated_bytes: DeviceSize,
+
+    /// The size in bytes of the largest contiguous free range in a memory block of this heap.
+    pub largest_free_range: DeviceSize,
+
+    /// How fragmented the free space of the memory blocks of this heap is, between `0.0` and
+    /// `1.0`.
+    ///
+    /// This is `1 - largest_free_range / free_bytes`. It is `0.0` if all the free space is
+    /// contiguous, or if there is no free space, and approaches `1.0` as the free space gets split
+    /// into many small ranges.
+    pub fragmentation: f32,
+}
+
+/// Totals of the allocations that have the same tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MemoryTagReport {
+    /// The tag, or `None` for allocations that have no tag.
+    pub tag: Option<String>,
+
+    /// The number of allocations with this tag.
+    pub allocation_count: u32,
+
+    /// The total size in bytes of the allocations with this tag.
+    pub allocated_bytes: DeviceSize,
+}
+
+/// A single allocation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MemoryAllocationReport {
+    /// The tag of the allocation, if it has one.
+    pub tag: Option<String>,
+
+    /// The index of the memory type the allocation was made from.
+    pub memory_type_index: u32,
+
+    /// The index of the memory heap the allocation was made from.
+    pub heap_index: u32,
+
+    /// The size of the allocation in bytes.
+    pub size: DeviceSize,
+}
@@ -0,0 +1,386 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Interoperability with OpenXR.
+//!
+//! With the `XR_KHR_vulkan_enable2` extension, an OpenXR runtime creates the Vulkan instance and
+//! device itself, chooses the physical device, and owns the images of its swapchains. Vulkano
+//! does not depend on an OpenXR binding, but provides the entry points that such a binding needs:
+//!
+//! - [`Instance::with_create_fn`](crate::instance::Instance::with_create_fn) creates an instance
+//!   with `xrCreateVulkanInstanceKHR` instead of `vkCreateInstance`.
+//! - [`PhysicalDevice::from_handle`](crate::device::physical::PhysicalDevice::from_handle) finds
+//!   the physical device returned by `xrGetVulkanGraphicsDevice2KHR`.
+//! - [`Device::with_create_fn`](crate::device::Device::with_create_fn) creates a device with
+//!   `xrCreateVulkanDeviceKHR` instead of `vkCreateDevice`.
+//! - [`OpenXrSwapchainImage`] wraps the images returned by `xrEnumerateSwapchainImages`, so that
+//!   they can be used in framebuffers and commands like any other image.
+//!
+//! With the older `XR_KHR_vulkan_enable` extension, the runtime instead returns the names of the
+//! instance and device extensions that must be enabled. They can be converted with
+//! `InstanceExtensions::from` and `DeviceExtensions::from`, and the instance and device created
+//! normally.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use vulkano::{
+//!     device::{physical::PhysicalDevice, Device, DeviceCreateInfo, QueueCreateInfo},
+//!     format::Format,
+//!     instance::{Instance, InstanceCreateInfo},
+//!     openxr::{OpenXrSwapchainImage, OpenXrSwapchainImageCreateInfo},
+//!     Handle, VulkanObject,
+//! };
+//!
+//! # fn xr_create_vulkan_instance(_: &ash::vk::InstanceCreateInfo, _: *const ash::vk::AllocationCallbacks, _: *mut ash::vk::Instance) -> ash::vk::Result { unimplemented!() }
+//! # fn xr_get_vulkan_graphics_device(_: ash::vk::Instance) -> ash::vk::PhysicalDevice { unimplemented!() }
+//! # fn xr_create_vulkan_device(_: ash::vk::PhysicalDevice, _: &ash::vk::DeviceCreateInfo, _: *const ash::vk::AllocationCallbacks, _: *mut ash::vk::Device) -> ash::vk::Result { unimplemented!() }
+//! # fn xr_enumerate_swapchain_images() -> Vec<u64> { unimplemented!() }
+//! // Each of the `xr_*` functions stands for a call to the OpenXR runtime, for example through
+//! // the `openxr` crate.
+//! let instance = unsafe {
+//!     Instance::with_create_fn(InstanceCreateInfo::default(), |create_info, allocator, output| {
+//!         xr_create_vulkan_instance(create_info, allocator, output)
+//!     })
+//! }
+//! .unwrap();
+//!
+//! let physical_device = PhysicalDevice::from_handle(
+//!     &instance,
+//!     xr_get_vulkan_graphics_device(instance.internal_object()),
+//! )
+//! .unwrap();
+//! let queue_family = physical_device
+//!     .queue_families()
+//!     .find(|q| q.supports_graphics())
+//!     .unwrap();
+//!
+//! let (device, mut queues) = unsafe {
+//!     Device::with_create_fn(
+//!         physical_device,
+//!         DeviceCreateInfo {
+//!             queue_create_infos: vec![QueueCreateInfo::family(queue_family)],
+//!             ..Default::default()
+//!         },
+//!         |create_info, allocator, output| {
+//!             xr_create_vulkan_device(
+//!                 physical_device.internal_object(),
+//!                 create_info,
+//!                 allocator,
+//!                 output,
+//!             )
+//!         },
+//!     )
+//! }
+//! .unwrap();
+//!
+//! // The parameters must be the same as in the `XrSwapchainCreateInfo` of the swapchain.
+//! let images: Vec<_> = xr_enumerate_swapchain_images()
+//!     .into_iter()
+//!     .map(|handle| unsafe {
+//!         OpenXrSwapchainImage::from_handle(
+//!             device.clone(),
+//!             ash::vk::Image::from_raw(handle),
+//!             OpenXrSwapchainImageCreateInfo {
+//!                 dimensions: [1440, 1600],
+//!                 array_layers: 2,
+//!                 format: Some(Format::R8G8B8A8_SRGB),
+//!                 ..Default::default()
+//!             },
+//!         )
+//!     })
+//!     .collect();
+//! ```
+
+use crate::{
+    device::{Device, DeviceOwned},
+    format::Format,
+    image::{
+        sys::UnsafeImage, ImageAccess, ImageCreateFlags, ImageDescriptorLayouts, ImageDimensions,
+        ImageInner, ImageLayout, ImageUsage, SampleCount,
+    },
+};
+use std::{
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+/// An image that belongs to an OpenXR swapchain.
+///
+/// Like a [`SwapchainImage`](crate::image::SwapchainImage), an OpenXR swapchain image can only be
+/// used between the calls to `xrAcquireSwapchainImage` and `xrWaitSwapchainImage` and the call
+/// to `xrReleaseSwapchainImage`, and the command buffers that use it must have finished
+/// executing before it is released.
+///
+/// OpenXR guarantees that an acquired image is in the `ColorAttachmentOptimal` layout if it has
+/// the `color_attachment` usage, and in the `DepthStencilAttachmentOptimal` layout if it has the
+/// `depth_stencil_attachment` usage. Vulkano expects the image to be in that layout at the start
+/// of each command buffer, and transitions it back to that layout at the end, as required by
+/// OpenXR for releasing the image.
+#[derive(Debug)]
+pub struct OpenXrSwapchainImage {
+    image: Arc<UnsafeImage>,
+    layout: ImageLayout,
+}
+
+impl OpenXrSwapchainImage {
+    /// Wraps an image returned by `xrEnumerateSwapchainImages`.
+    ///
+    /// The image is not destroyed when the `OpenXrSwapchainImage` is dropped, as it is owned by
+    /// the OpenXR swapchain.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.format` is `None`.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be an image of an OpenXR swapchain that was created for `device`.
+    /// - `create_info` must match the `XrSwapchainCreateInfo` that the swapchain was created with.
+    /// - The `OpenXrSwapchainImage` must not be used after the swapchain has been destroyed.
+    pub unsafe fn from_handle(
+        device: Arc<Device>,
+        handle: ash::vk::Image,
+        create_info: OpenXrSwapchainImageCreateInfo,
+    ) -> Arc<OpenXrSwapchainImage> {
+        let OpenXrSwapchainImageCreateInfo {
+            dimensions,
+            array_layers,
+            format,
+            mip_levels,
+            samples,
+            usage,
+            _ne: _,
+        } = create_info;
+
+        let format = format.expect("format must be specified for an OpenXR swapchain image");
+        let layout = if usage.color_attachment {
+            ImageLayout::ColorAttachmentOptimal
+        } else if usage.depth_stencil_attachment {
+            ImageLayout::DepthStencilAttachmentOptimal
+        } else {
+            ImageLayout::General
+        };
+
+        let image = UnsafeImage::from_raw(
+            device,
+            handle,
+            usage,
+            format,
+            ImageCreateFlags::none(),
+            ImageDimensions::Dim2d {
+                width: dimensions[0],
+                height: dimensions[1],
+                array_layers,
+            },
+            samples,
+            mip_levels,
+            Vec::new(),
+        );
+
+        Arc::new(OpenXrSwapchainImage { image, layout })
+    }
+
+    /// Returns the layout that the image is in when it is acquired, and must be in when it is
+    /// released.
+    #[inline]
+    pub fn layout(&self) -> ImageLayout {
+        self.layout
+    }
+}
+
+unsafe impl DeviceOwned for OpenXrSwapchainImage {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.image.device()
+    }
+}
+
+unsafe impl ImageAccess for OpenXrSwapchainImage {
+    #[inline]
+    fn inner(&self) -> ImageInner {
+        ImageInner {
+            image: &self.image,
+            first_layer: 0,
+            num_layers: self.image.dimensions().array_layers(),
+            first_mipmap_level: 0,
+            num_mipmap_levels: self.image.mip_levels(),
+        }
+    }
+
+    #[inline]
+    fn initial_layout_requirement(&self) -> ImageLayout {
+        self.layout
+    }
+
+    #[inline]
+    fn final_layout_requirement(&self) -> ImageLayout {
+        self.layout
+    }
+
+    #[inline]
+    fn descriptor_layouts(&self) -> Option<ImageDescriptorLayouts> {
+        Some(ImageDescriptorLayouts {
+            storage_image: ImageLayout::General,
+            combined_image_sampler: ImageLayout::ShaderReadOnlyOptimal,
+            sampled_image: ImageLayout::ShaderReadOnlyOptimal,
+            input_attachment: ImageLayout::ShaderReadOnlyOptimal,
+        })
+    }
+
+    // The runtime hands out images that are already in `layout`.
+    #[inline]
+    fn is_layout_initialized(&self) -> bool {
+        true
+    }
+}
+
+impl PartialEq for OpenXrSwapchainImage {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner() == other.inner()
+    }
+}
+
+impl Eq for OpenXrSwapchainImage {}
+
+impl Hash for OpenXrSwapchainImage {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner().hash(state);
+    }
+}
+
+/// Parameters to wrap an OpenXR swapchain image.
+///
+/// The usage flags of `XrSwapchainCreateInfo` correspond to the following fields of `usage`:
+///
+/// | OpenXR usage                                      | `ImageUsage` field         |
+/// |---------------------------------------------------|----------------------------|
+/// | `XR_SWAPCHAIN_USAGE_COLOR_ATTACHMENT_BIT`         | `color_attachment`         |
+/// | `XR_SWAPCHAIN_USAGE_DEPTH_STENCIL_ATTACHMENT_BIT` | `depth_stencil_attachment` |
+/// | `XR_SWAPCHAIN_USAGE_UNORDERED_ACCESS_BIT`         | `storage`                  |
+/// | `XR_SWAPCHAIN_USAGE_TRANSFER_SRC_BIT`             | `transfer_src`             |
+/// | `XR_SWAPCHAIN_USAGE_TRANSFER_DST_BIT`             | `transfer_dst`             |
+/// | `XR_SWAPCHAIN_USAGE_SAMPLED_BIT`                  | `sampled`                  |
+/// | `XR_SWAPCHAIN_USAGE_INPUT_ATTACHMENT_BIT_KHR`     | `input_attachment`         |
+#[derive(Clone, Debug)]
+pub struct OpenXrSwapchainImageCreateInfo {
+    /// The width and height of the image.
+    ///
+    /// The default value is `[0, 0]`, which must be overridden.
+    pub dimensions: [u32; 2],
+
+    /// The number of array layers of the image.
+    ///
+    /// The default value is `1`.
+    pub array_layers: u32,
+
+    /// The format of the image.
+    ///
+    /// The default value is `None`, which must be overridden.
+    pub format: Option<Format>,
+
+    /// The number of mip levels of the image.
+    ///
+    /// The default value is `1`.
+    pub mip_levels: u32,
+
+    /// The number of samples per pixel of the image.
+    ///
+    /// The default value is [`SampleCount::Sample1`].
+    pub samples: SampleCount,
+
+    /// The usage of the image.
+    ///
+    /// The default value is `color_attachment` only.
+    pub usage: ImageUsage,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for OpenXrSwapchainImageCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            dimensions: [0, 0],
+            array_layers: 1,
+            format: None,
+            mip_levels: 1,
+            samples: SampleCount::Sample1,
+            usage: ImageUsage {
+                color_attachment: true,
+                ..ImageUsage::none()
+            },
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OpenXrSwapchainImage, OpenXrSwapchainImageCreateInfo};
+    use crate::{
+        format::Format,
+        image::{
+            sys::{UnsafeImage, UnsafeImageCreateInfo},
+            ImageAccess, ImageDimensions, ImageLayout, ImageUsage,
+        },
+        VulkanObject,
+    };
+
+    #[test]
+    fn wrap_depth_image() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            depth_stencil_attachment: true,
+            ..ImageUsage::none()
+        };
+        // Stands in for an image created by the OpenXR runtime.
+        let runtime_image = UnsafeImage::new(
+            device.clone(),
+            UnsafeImageCreateInfo {
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 2,
+                },
+                format: Some(Format::D16_UNORM),
+                usage,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let image = unsafe {
+            OpenXrSwapchainImage::from_handle(
+                device,
+                runtime_image.internal_object(),
+                OpenXrSwapchainImageCreateInfo {
+                    dimensions: [32, 32],
+                    array_layers: 2,
+                    format: Some(Format::D16_UNORM),
+                    usage,
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_eq!(image.dimensions().array_layers(), 2);
+        assert_eq!(
+            image.initial_layout_requirement(),
+            ImageLayout::DepthStencilAttachmentOptimal
+        );
+        assert_eq!(
+            image.final_layout_requirement(),
+            ImageLayout::DepthStencilAttachmentOptimal
+        );
+    }
+}
@@ -129,6 +129,8 @@ impl PipelineCache {
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(cache);
+
         Ok(Arc::new(PipelineCache {
             device: device.clone(),
             cache: cache,
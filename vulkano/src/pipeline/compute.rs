@@ -27,7 +27,9 @@ use crate::check_errors;
 use crate::descriptor_set::layout::{
     DescriptorSetLayout, DescriptorSetLayoutCreateInfo, DescriptorSetLayoutCreationError,
 };
+use crate::device::object_tracker::{TrackedObject, TrackedObjectType};
 use crate::device::{Device, DeviceOwned};
+use crate::extension_chain::ExtensionChain;
 use crate::pipeline::cache::PipelineCache;
 use crate::pipeline::layout::{
     PipelineLayout, PipelineLayoutCreationError, PipelineLayoutSupersetError,
@@ -60,6 +62,7 @@ pub struct ComputePipeline {
     layout: Arc<PipelineLayout>,
     descriptor_requirements: HashMap<(u32, u32), DescriptorRequirements>,
     num_used_descriptor_sets: u32,
+    tracked: TrackedObject,
 }
 
 impl ComputePipeline {
@@ -166,6 +169,37 @@ impl ComputePipeline {
     where
         Css: SpecializationConstants,
     {
+        Self::with_extensions(
+            device,
+            shader,
+            specialization_constants,
+            layout,
+            cache,
+            &ExtensionChain::new(),
+        )
+    }
+
+    /// Same as `with_unchecked_pipeline_layout`, but adds extension structures that vulkano
+    /// doesn't support to the create info of the pipeline. See the
+    /// [`extension_chain`](crate::extension_chain) module for more information.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `extensions` is not empty, and it or one of its clones was already used to
+    ///   create an object.
+    pub unsafe fn with_extensions<Css>(
+        device: Arc<Device>,
+        shader: EntryPoint,
+        specialization_constants: &Css,
+        layout: Arc<PipelineLayout>,
+        cache: Option<Arc<PipelineCache>>,
+        extensions: &ExtensionChain<ComputePipeline>,
+    ) -> Result<Arc<ComputePipeline>, ComputePipelineCreationError>
+    where
+        Css: SpecializationConstants,
+    {
+        trace_span!("create_compute_pipeline");
+
         let fns = device.fns();
 
         let handle = {
@@ -190,7 +224,7 @@ impl ComputePipeline {
                 ..Default::default()
             };
 
-            let infos = ash::vk::ComputePipelineCreateInfo {
+            let mut infos = ash::vk::ComputePipelineCreateInfo {
                 flags: ash::vk::PipelineCreateFlags::empty(),
                 stage,
                 layout: layout.internal_object(),
@@ -199,6 +233,8 @@ impl ComputePipeline {
                 ..Default::default()
             };
 
+            let _extensions_vk = extensions.link(&mut infos.p_next);
+
             let cache_handle = match cache {
                 Some(ref cache) => cache.internal_object(),
                 None => ash::vk::PipelineCache::null(),
@@ -216,6 +252,8 @@ impl ComputePipeline {
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         let descriptor_requirements: HashMap<_, _> = shader
             .descriptor_requirements()
             .map(|(loc, reqs)| (loc, reqs.clone()))
@@ -233,6 +271,7 @@ impl ComputePipeline {
             layout,
             descriptor_requirements,
             num_used_descriptor_sets,
+            tracked: device.track_object(TrackedObjectType::Pipeline),
         }))
     }
 
@@ -406,8 +445,9 @@ impl From<Error> for ComputePipelineCreationError {
 
 #[cfg(test)]
 mod tests {
+    use crate::buffer::Buffer;
+    use crate::buffer::BufferLocation;
     use crate::buffer::BufferUsage;
-    use crate::buffer::CpuAccessibleBuffer;
     use crate::command_buffer::AutoCommandBufferBuilder;
     use crate::command_buffer::CommandBufferUsage;
     use crate::descriptor_set::PersistentDescriptorSet;
@@ -498,8 +538,13 @@ mod tests {
         )
         .unwrap();
 
-        let data_buffer =
-            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, 0).unwrap();
+        let data_buffer = Buffer::from_data(
+            device.clone(),
+            BufferUsage::all(),
+            BufferLocation::Download,
+            0,
+        )
+        .unwrap();
 
         let set = PersistentDescriptorSet::new(
             pipeline.layout().set_layouts().get(0).unwrap().clone(),
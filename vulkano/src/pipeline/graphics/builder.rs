@@ -31,8 +31,10 @@ use super::{
 use crate::{
     check_errors,
     descriptor_set::layout::{DescriptorSetLayout, DescriptorSetLayoutCreateInfo},
-    device::{Device, DeviceOwned},
+    device::{object_tracker::TrackedObjectType, Device, DeviceOwned},
+    extension_chain::ExtensionChain,
     format::NumericType,
+    image::ImageAspects,
     pipeline::{
         cache::PipelineCache,
         graphics::{
@@ -77,6 +79,8 @@ pub struct GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, T
     multisample_state: MultisampleState,
     depth_stencil_state: DepthStencilState,
     color_blend_state: ColorBlendState,
+    attachment_feedback_loop: StateMode<ImageAspects>,
+    extensions: ExtensionChain<GraphicsPipeline>,
 }
 
 // Additional parameters if tessellation is used.
@@ -121,6 +125,8 @@ impl
             multisample_state: Default::default(),
             depth_stencil_state: Default::default(),
             color_blend_state: Default::default(),
+            attachment_feedback_loop: StateMode::Fixed(ImageAspects::none()),
+            extensions: ExtensionChain::new(),
         }
     }
 }
@@ -267,6 +273,8 @@ where
         device: Arc<Device>,
         pipeline_layout: Arc<PipelineLayout>,
     ) -> Result<Arc<GraphicsPipeline>, GraphicsPipelineCreationError> {
+        trace_span!("create_graphics_pipeline");
+
         let vertex_input_state = self
             .vertex_input_state
             .definition(self.vertex_shader.as_ref().unwrap().0.input_interface())?;
@@ -309,6 +317,8 @@ where
                 ref multisample_state,
                 ref depth_stencil_state,
                 ref color_blend_state,
+                attachment_feedback_loop: _,
+                extensions: _,
             } = &self;
 
             let render_pass = render_pass.as_ref().expect("Missing render pass");
@@ -378,6 +388,8 @@ where
             multisample_state,
             depth_stencil_state,
             color_blend_state,
+            attachment_feedback_loop,
+            extensions: _,
         } = self;
 
         let num_used_descriptor_sets = descriptor_requirements
@@ -387,6 +399,8 @@ where
             .map(|x| x + 1)
             .unwrap_or(0);
 
+        let tracked = device.track_object(TrackedObjectType::Pipeline);
+
         Ok(Arc::new(GraphicsPipeline {
             handle,
             device,
@@ -407,7 +421,9 @@ where
             multisample_state: has.fragment_output_state.then(|| multisample_state),
             depth_stencil_state: has.depth_stencil_state.then(|| depth_stencil_state),
             color_blend_state: has.color_blend_state.then(|| color_blend_state),
+            attachment_feedback_loop: has.fragment_output_state.then(|| attachment_feedback_loop),
             dynamic_state,
+            tracked,
         }))
     }
 
@@ -439,6 +455,8 @@ where
             ref multisample_state,
             ref depth_stencil_state,
             ref color_blend_state,
+            attachment_feedback_loop,
+            extensions: _,
         } = self;
 
         let render_pass = render_pass.as_ref().expect("Missing render pass");
@@ -618,6 +636,23 @@ where
                         );
                     }
 
+                    // VUID-VkVertexInputBindingDescription-stride-04456
+                    if device.enabled_extensions().khr_portability_subset {
+                        let alignment = properties
+                            .min_vertex_input_binding_stride_alignment
+                            .unwrap_or(1);
+
+                        if binding_desc.stride < alignment || binding_desc.stride % alignment != 0 {
+                            return Err(
+                                GraphicsPipelineCreationError::VertexInputBindingStrideNotAligned {
+                                    binding,
+                                    alignment,
+                                    obtained: binding_desc.stride,
+                                },
+                            );
+                        }
+                    }
+
                     match binding_desc.input_rate {
                         VertexInputRate::Instance { divisor } if divisor != 1 => {
                             // VUID-VkVertexInputBindingDivisorDescriptionEXT-vertexAttributeInstanceRateDivisor-02229
@@ -746,6 +781,17 @@ where
                             // TODO:
                             // VUID-VkGraphicsPipelineCreateInfo-topology-00737
                         }
+                        PrimitiveTopology::TriangleFan => {
+                            // VUID-VkPipelineInputAssemblyStateCreateInfo-triangleFans-04452
+                            if device.enabled_extensions().khr_portability_subset
+                                && !device.enabled_features().triangle_fans
+                            {
+                                return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                                    feature: "triangle_fans",
+                                    reason: "this device is a portability subset device, and InputAssemblyState::topology was set to PrimitiveTopology::TriangleFan",
+                                });
+                            }
+                        }
                         _ => (),
                     },
                     PartialStateMode::Dynamic(topology_class) => {
@@ -1086,6 +1132,18 @@ where
                     });
                 }
 
+                // VUID-VkPipelineRasterizationStateCreateInfo-pointPolygons-04458
+                if polygon_mode == PolygonMode::Point
+                    && rasterizer_discard_enable != StateMode::Fixed(true)
+                    && device.enabled_extensions().khr_portability_subset
+                    && !device.enabled_features().point_polygons
+                {
+                    return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                        feature: "point_polygons",
+                        reason: "this device is a portability subset device, and RasterizationState::polygon_mode was Point",
+                    });
+                }
+
                 // VUID?
                 if matches!(cull_mode, StateMode::Dynamic)
                     && !(device.api_version() >= Version::V1_3
@@ -1542,7 +1600,7 @@ where
                 });
 
                 match entry_point.execution() {
-                    ShaderExecution::Fragment => (),
+                    ShaderExecution::Fragment(_) => (),
                     _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
                 }
 
@@ -1585,6 +1643,8 @@ where
                 ref depth,
                 ref depth_bounds,
                 ref stencil,
+                rasterization_order_depth_attachment_access,
+                rasterization_order_stencil_attachment_access,
             } = depth_stencil_state;
 
             if let Some(depth_state) = depth {
@@ -1767,6 +1827,60 @@ where
                 // TODO:
                 // VUID-VkGraphicsPipelineCreateInfo-renderPass-06040
             }
+
+            if rasterization_order_depth_attachment_access {
+                // VUID-VkPipelineDepthStencilStateCreateInfo-rasterizationOrderDepthAttachmentAccess-06463
+                if !device
+                    .enabled_features()
+                    .rasterization_order_depth_attachment_access
+                {
+                    return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                        feature: "rasterization_order_depth_attachment_access",
+                        reason: "DepthStencilState::rasterization_order_depth_attachment_access was true",
+                    });
+                }
+
+                // VUID-VkGraphicsPipelineCreateInfo-renderPass-06485
+                let subpass_enabled = match render_pass {
+                    PipelineRenderPassType::BeginRenderPass(subpass) => {
+                        subpass
+                            .subpass_desc()
+                            .rasterization_order_depth_attachment_access
+                    }
+                    PipelineRenderPassType::BeginRendering(_) => false,
+                };
+
+                if !subpass_enabled {
+                    return Err(GraphicsPipelineCreationError::RasterizationOrderAttachmentAccessNotEnabledInSubpass);
+                }
+            }
+
+            if rasterization_order_stencil_attachment_access {
+                // VUID-VkPipelineDepthStencilStateCreateInfo-rasterizationOrderStencilAttachmentAccess-06464
+                if !device
+                    .enabled_features()
+                    .rasterization_order_stencil_attachment_access
+                {
+                    return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                        feature: "rasterization_order_stencil_attachment_access",
+                        reason: "DepthStencilState::rasterization_order_stencil_attachment_access was true",
+                    });
+                }
+
+                // VUID-VkGraphicsPipelineCreateInfo-renderPass-06486
+                let subpass_enabled = match render_pass {
+                    PipelineRenderPassType::BeginRenderPass(subpass) => {
+                        subpass
+                            .subpass_desc()
+                            .rasterization_order_stencil_attachment_access
+                    }
+                    PipelineRenderPassType::BeginRendering(_) => false,
+                };
+
+                if !subpass_enabled {
+                    return Err(GraphicsPipelineCreationError::RasterizationOrderAttachmentAccessNotEnabledInSubpass);
+                }
+            }
         }
 
         /*
@@ -1831,6 +1945,53 @@ where
                 // TODO:
                 // VUID-VkGraphicsPipelineCreateInfo-lineRasterizationMode-02766
             }
+
+            // Attachment feedback loop
+            match attachment_feedback_loop {
+                StateMode::Fixed(aspects) => {
+                    assert!(
+                        ImageAspects {
+                            color: false,
+                            depth: false,
+                            stencil: false,
+                            ..aspects
+                        } == ImageAspects::none(),
+                        "only the color, depth and stencil aspects can be used for attachment feedback loops",
+                    );
+
+                    if aspects != ImageAspects::none() {
+                        if !device
+                            .enabled_extensions()
+                            .ext_attachment_feedback_loop_layout
+                        {
+                            return Err(GraphicsPipelineCreationError::ExtensionNotEnabled {
+                                extension: "ext_attachment_feedback_loop_layout",
+                                reason: "attachment_feedback_loop was set to a non-empty value",
+                            });
+                        }
+
+                        // VUID-VkGraphicsPipelineCreateInfo-flags-06482
+                        // VUID-VkGraphicsPipelineCreateInfo-flags-06483
+                        if matches!(render_pass, PipelineRenderPassType::BeginRendering(_)) {
+                            return Err(
+                                GraphicsPipelineCreationError::AttachmentFeedbackLoopWithoutRenderPass,
+                            );
+                        }
+                    }
+                }
+                StateMode::Dynamic => {
+                    // VUID-VkGraphicsPipelineCreateInfo-attachmentFeedbackLoopDynamicState-08910
+                    if !device
+                        .enabled_features()
+                        .attachment_feedback_loop_dynamic_state
+                    {
+                        return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                            feature: "attachment_feedback_loop_dynamic_state",
+                            reason: "attachment_feedback_loop was set to Dynamic",
+                        });
+                    }
+                }
+            }
         }
 
         // Color blend state
@@ -1841,6 +2002,7 @@ where
                 logic_op,
                 ref attachments,
                 blend_constants,
+                rasterization_order_attachment_access,
             } = color_blend_state;
 
             if let Some(logic_op) = logic_op {
@@ -1863,6 +2025,33 @@ where
                 }
             }
 
+            if rasterization_order_attachment_access {
+                // VUID-VkPipelineColorBlendStateCreateInfo-rasterizationOrderColorAttachmentAccess-06465
+                if !device
+                    .enabled_features()
+                    .rasterization_order_color_attachment_access
+                {
+                    return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                        feature: "rasterization_order_color_attachment_access",
+                        reason: "ColorBlendState::rasterization_order_attachment_access was true",
+                    });
+                }
+
+                // VUID-VkGraphicsPipelineCreateInfo-renderPass-06484
+                let subpass_enabled = match render_pass {
+                    PipelineRenderPassType::BeginRenderPass(subpass) => {
+                        subpass
+                            .subpass_desc()
+                            .rasterization_order_color_attachment_access
+                    }
+                    PipelineRenderPassType::BeginRendering(_) => false,
+                };
+
+                if !subpass_enabled {
+                    return Err(GraphicsPipelineCreationError::RasterizationOrderAttachmentAccessNotEnabledInSubpass);
+                }
+            }
+
             let color_attachment_count = match render_pass {
                 PipelineRenderPassType::BeginRenderPass(subpass) => {
                     subpass.subpass_desc().color_attachments.len()
@@ -1931,6 +2120,25 @@ where
                         });
                     }
 
+                    // VUID-VkPipelineColorBlendAttachmentState-constantAlphaColorBlendFactors-04454
+                    // VUID-VkPipelineColorBlendAttachmentState-constantAlphaColorBlendFactors-04455
+                    if device.enabled_extensions().khr_portability_subset
+                        && !device.enabled_features().constant_alpha_color_blend_factors
+                        && [blend.color_source, blend.color_destination]
+                            .into_iter()
+                            .any(|blend_factor| {
+                                matches!(
+                                    blend_factor,
+                                    BlendFactor::ConstantAlpha | BlendFactor::OneMinusConstantAlpha
+                                )
+                            })
+                    {
+                        return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                            feature: "constant_alpha_color_blend_factors",
+                            reason: "this device is a portability subset device, and the color_source or color_destination member of AttachmentBlend was set to ConstantAlpha or OneMinusConstantAlpha",
+                        });
+                    }
+
                     let attachment_format = match render_pass {
                         PipelineRenderPassType::BeginRenderPass(subpass) => subpass
                             .subpass_desc()
@@ -2075,6 +2283,8 @@ where
             multisample_state,
             depth_stencil_state,
             color_blend_state,
+            attachment_feedback_loop,
+            extensions,
         } = self;
 
         let render_pass = render_pass.as_ref().unwrap();
@@ -2772,6 +2982,8 @@ where
                 ref depth,
                 ref depth_bounds,
                 ref stencil,
+                rasterization_order_depth_attachment_access,
+                rasterization_order_stencil_attachment_access,
             } = depth_stencil_state;
 
             let (depth_test_enable, depth_write_enable, depth_compare_op) =
@@ -2938,8 +3150,18 @@ where
                 (ash::vk::FALSE, Default::default(), Default::default())
             };
 
+            let mut flags = ash::vk::PipelineDepthStencilStateCreateFlags::empty();
+
+            if rasterization_order_depth_attachment_access {
+                flags |= ash::vk::PipelineDepthStencilStateCreateFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS_ARM;
+            }
+
+            if rasterization_order_stencil_attachment_access {
+                flags |= ash::vk::PipelineDepthStencilStateCreateFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS_ARM;
+            }
+
             let _ = depth_stencil_state_vk.insert(ash::vk::PipelineDepthStencilStateCreateInfo {
-                flags: ash::vk::PipelineDepthStencilStateCreateFlags::empty(),
+                flags,
                 depth_test_enable,
                 depth_write_enable,
                 depth_compare_op,
@@ -3000,6 +3222,7 @@ where
                 logic_op,
                 ref attachments,
                 blend_constants,
+                rasterization_order_attachment_access,
             } = color_blend_state;
 
             color_blend_attachments_vk.extend(attachments.iter().map(
@@ -3058,9 +3281,15 @@ where
                 }
             };
 
+            let mut flags = ash::vk::PipelineColorBlendStateCreateFlags::empty();
+
+            if rasterization_order_attachment_access {
+                flags |= ash::vk::PipelineColorBlendStateCreateFlags::RASTERIZATION_ORDER_ATTACHMENT_ACCESS_ARM;
+            }
+
             let mut color_blend_state_vk =
                 color_blend_state_vk.insert(ash::vk::PipelineColorBlendStateCreateInfo {
-                    flags: ash::vk::PipelineColorBlendStateCreateFlags::empty(),
+                    flags,
                     logic_op_enable,
                     logic_op,
                     attachment_count: color_blend_attachments_vk.len() as u32,
@@ -3100,6 +3329,37 @@ where
             }
         }
 
+        /*
+            Attachment feedback loop
+        */
+
+        let mut flags = ash::vk::PipelineCreateFlags::empty();
+
+        if has.fragment_output_state {
+            match *attachment_feedback_loop {
+                StateMode::Fixed(aspects) => {
+                    if aspects.color {
+                        flags |= ash::vk::PipelineCreateFlags::COLOR_ATTACHMENT_FEEDBACK_LOOP_EXT;
+                    }
+
+                    if aspects.depth || aspects.stencil {
+                        flags |=
+                            ash::vk::PipelineCreateFlags::DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP_EXT;
+                    }
+
+                    if device
+                        .enabled_extensions()
+                        .ext_attachment_feedback_loop_dynamic_state
+                    {
+                        dynamic_state.insert(DynamicState::AttachmentFeedbackLoopEnable, false);
+                    }
+                }
+                StateMode::Dynamic => {
+                    dynamic_state.insert(DynamicState::AttachmentFeedbackLoopEnable, true);
+                }
+            }
+        }
+
         /*
             Dynamic state
         */
@@ -3130,7 +3390,7 @@ where
         */
 
         let mut create_info = ash::vk::GraphicsPipelineCreateInfo {
-            flags: ash::vk::PipelineCreateFlags::empty(), // TODO: some flags are available but none are critical
+            flags, // TODO: other flags are available but none are critical
             stage_count: stages_vk.len() as u32,
             p_stages: stages_vk.as_ptr(),
             p_vertex_input_state: vertex_input_state_vk
@@ -3187,6 +3447,8 @@ where
             create_info.p_next = info as *const _ as *const _;
         }
 
+        let _extensions_vk = extensions.link(&mut create_info.p_next);
+
         let cache_handle = match cache.as_ref() {
             Some(cache) => cache.internal_object(),
             None => ash::vk::PipelineCache::null(),
@@ -3213,6 +3475,8 @@ where
             panic!("vkCreateGraphicsPipelines provided a NULL handle");
         }
 
+        device.set_automatic_debug_name(handle);
+
         Ok((handle, descriptor_requirements, dynamic_state, stages))
     }
 
@@ -3259,6 +3523,8 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
+            attachment_feedback_loop: self.attachment_feedback_loop,
+            extensions: self.extensions,
         }
     }
 
@@ -3297,6 +3563,8 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
+            attachment_feedback_loop: self.attachment_feedback_loop,
+            extensions: self.extensions,
         }
     }
 
@@ -3329,6 +3597,8 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
+            attachment_feedback_loop: self.attachment_feedback_loop,
+            extensions: self.extensions,
         }
     }
 
@@ -3363,6 +3633,8 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
+            attachment_feedback_loop: self.attachment_feedback_loop,
+            extensions: self.extensions,
         }
     }
 
@@ -3395,6 +3667,8 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
+            attachment_feedback_loop: self.attachment_feedback_loop,
+            extensions: self.extensions,
         }
     }
 
@@ -3474,6 +3748,37 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         self
     }
 
+    /// Sets the aspects of the attachments that can be read in the fragment shader while they are
+    /// being written to, in the `AttachmentFeedbackLoopOptimal` layout. Only the `color`, `depth`
+    /// and `stencil` aspects can be set.
+    ///
+    /// If any aspect is set, the
+    /// [`ext_attachment_feedback_loop_layout`](crate::device::DeviceExtensions::ext_attachment_feedback_loop_layout)
+    /// extension must be enabled on the device. If set to `Dynamic`, the
+    /// [`attachment_feedback_loop_dynamic_state`](crate::device::Features::attachment_feedback_loop_dynamic_state)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `Fixed(ImageAspects::none())`.
+    #[inline]
+    pub fn attachment_feedback_loop(
+        mut self,
+        attachment_feedback_loop: StateMode<ImageAspects>,
+    ) -> Self {
+        self.attachment_feedback_loop = attachment_feedback_loop;
+        self
+    }
+
+    /// Sets the extension structures that vulkano doesn't support, to add to the create info of
+    /// the pipeline. See the [`extension_chain`](crate::extension_chain) module for more
+    /// information. If it is not empty, it can only be used to create one pipeline.
+    ///
+    /// The default value is empty.
+    #[inline]
+    pub fn extensions(mut self, extensions: ExtensionChain<GraphicsPipeline>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
     /// Sets the tessellation shaders stage as disabled. This is the default.
     #[deprecated(since = "0.27")]
     #[inline]
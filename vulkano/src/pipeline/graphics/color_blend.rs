@@ -46,6 +46,19 @@ pub struct ColorBlendState {
 
     /// The constant color to use for some of the `BlendFactor` variants.
     pub blend_constants: StateMode<[f32; 4]>,
+
+    /// Whether reads of the color attachments through input attachments in the fragment shader
+    /// are ordered with the writes of earlier fragments, in rasterization order. This allows
+    /// programmable blending without a self-dependency and pipeline barrier between draws.
+    ///
+    /// If set to `true`, the
+    /// [`rasterization_order_color_attachment_access`](crate::device::Features::rasterization_order_color_attachment_access)
+    /// feature must be enabled on the device, and the subpass must have been created with
+    /// [`SubpassDescription::rasterization_order_color_attachment_access`](crate::render_pass::SubpassDescription::rasterization_order_color_attachment_access)
+    /// set to `true`.
+    ///
+    /// The default value is `false`.
+    pub rasterization_order_attachment_access: bool,
 }
 
 impl ColorBlendState {
@@ -64,6 +77,7 @@ impl ColorBlendState {
                 })
                 .collect(),
             blend_constants: StateMode::Fixed([0.0, 0.0, 0.0, 0.0]),
+            rasterization_order_attachment_access: false,
         }
     }
 
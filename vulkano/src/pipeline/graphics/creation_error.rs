@@ -32,6 +32,10 @@ pub enum GraphicsPipelineCreationError {
         reason: &'static str,
     },
 
+    /// An attachment feedback loop was enabled on a pipeline that is created for dynamic
+    /// rendering.
+    AttachmentFeedbackLoopWithoutRenderPass,
+
     /// A color attachment has a format that does not support blending.
     ColorAttachmentFormatBlendNotSupported { attachment_index: u32 },
 
@@ -157,6 +161,10 @@ pub enum GraphicsPipelineCreationError {
     /// Error while creating the pipeline layout object.
     PipelineLayoutCreationError(PipelineLayoutCreationError),
 
+    /// Rasterization order attachment access was enabled in the color blend or depth/stencil
+    /// state, but not in the subpass the pipeline is created for.
+    RasterizationOrderAttachmentAccessNotEnabledInSubpass,
+
     /// The output interface of one shader and the input interface of the next shader do not match.
     ShaderStagesMismatch(ShaderInterfaceMismatchError),
 
@@ -188,6 +196,17 @@ pub enum GraphicsPipelineCreationError {
     /// The format specified by a vertex input attribute is not supported for vertex buffers.
     VertexInputAttributeUnsupportedFormat { location: u32, format: Format },
 
+    /// The stride of a vertex input binding is not a multiple of the
+    /// `min_vertex_input_binding_stride_alignment` property of a portability subset device.
+    VertexInputBindingStrideNotAligned {
+        /// Index of the faulty binding.
+        binding: u32,
+        /// Required alignment.
+        alignment: u32,
+        /// Value that was passed.
+        obtained: u32,
+    },
+
     /// The minimum or maximum bounds of viewports have been exceeded.
     ViewportBoundsExceeded,
 
@@ -228,6 +247,10 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 "the feature {} must be enabled: {}",
                 feature, reason
             ),
+            Self::AttachmentFeedbackLoopWithoutRenderPass => write!(
+                f,
+                "an attachment feedback loop was enabled on a pipeline that is created for dynamic rendering",
+            ),
             Self::ColorAttachmentFormatBlendNotSupported { attachment_index } => write!(
                 f,
                 "color attachment {} has a format that does not support blending",
@@ -333,6 +356,10 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 f,
                 "error while creating the pipeline layout object",
             ),
+            Self::RasterizationOrderAttachmentAccessNotEnabledInSubpass => write!(
+                f,
+                "rasterization order attachment access was enabled in the pipeline, but not in the subpass",
+            ),
             Self::ShaderStagesMismatch(_) => write!(
                 f,
                 "the output interface of one shader and the input interface of the next shader do not match",
@@ -373,6 +400,15 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 "the format {:?} specified by vertex input attribute location {} is not supported for vertex buffers",
                 format, location,
             ),
+            Self::VertexInputBindingStrideNotAligned {
+                binding,
+                alignment,
+                obtained,
+            } => write!(
+                f,
+                "the stride {} of vertex input binding {} is not a multiple of the required alignment {}",
+                obtained, binding, alignment,
+            ),
             Self::ViewportBoundsExceeded => write!(
                 f,
                 "the minimum or maximum bounds of viewports have been exceeded",
@@ -43,6 +43,32 @@ pub struct DepthStencilState {
     /// If set to `None`, the stencil test is disabled, all fragments will pass and no stencil
     /// writes are performed.
     pub stencil: Option<StencilState>,
+
+    /// Whether reads of the depth aspect of the depth/stencil attachment through an input
+    /// attachment in the fragment shader are ordered with the writes of earlier fragments, in
+    /// rasterization order.
+    ///
+    /// If set to `true`, the
+    /// [`rasterization_order_depth_attachment_access`](crate::device::Features::rasterization_order_depth_attachment_access)
+    /// feature must be enabled on the device, and the subpass must have been created with
+    /// [`SubpassDescription::rasterization_order_depth_attachment_access`](crate::render_pass::SubpassDescription::rasterization_order_depth_attachment_access)
+    /// set to `true`.
+    ///
+    /// The default value is `false`.
+    pub rasterization_order_depth_attachment_access: bool,
+
+    /// Whether reads of the stencil aspect of the depth/stencil attachment through an input
+    /// attachment in the fragment shader are ordered with the writes of earlier fragments, in
+    /// rasterization order.
+    ///
+    /// If set to `true`, the
+    /// [`rasterization_order_stencil_attachment_access`](crate::device::Features::rasterization_order_stencil_attachment_access)
+    /// feature must be enabled on the device, and the subpass must have been created with
+    /// [`SubpassDescription::rasterization_order_stencil_attachment_access`](crate::render_pass::SubpassDescription::rasterization_order_stencil_attachment_access)
+    /// set to `true`.
+    ///
+    /// The default value is `false`.
+    pub rasterization_order_stencil_attachment_access: bool,
 }
 
 impl DepthStencilState {
@@ -53,6 +79,8 @@ impl DepthStencilState {
             depth: Default::default(),
             depth_bounds: Default::default(),
             stencil: Default::default(),
+            rasterization_order_depth_attachment_access: false,
+            rasterization_order_stencil_attachment_access: false,
         }
     }
 
@@ -68,6 +96,8 @@ impl DepthStencilState {
             }),
             depth_bounds: Default::default(),
             stencil: Default::default(),
+            rasterization_order_depth_attachment_access: false,
+            rasterization_order_stencil_attachment_access: false,
         }
     }
 }
@@ -64,9 +64,11 @@ use self::{
     render_pass::PipelineRenderPassType, tessellation::TessellationState,
     vertex_input::VertexInputState, viewport::ViewportState,
 };
-use super::{DynamicState, Pipeline, PipelineBindPoint, PipelineLayout};
+use super::{DynamicState, Pipeline, PipelineBindPoint, PipelineLayout, StateMode};
 use crate::{
-    device::{Device, DeviceOwned},
+    device::{object_tracker::TrackedObject, Device, DeviceOwned},
+    image::ImageAspects,
+    render_pass::{RenderPassCompatibilityError, Subpass},
     shader::{DescriptorRequirements, ShaderStage},
     VulkanObject,
 };
@@ -117,7 +119,9 @@ pub struct GraphicsPipeline {
     multisample_state: Option<MultisampleState>,
     depth_stencil_state: Option<DepthStencilState>,
     color_blend_state: Option<ColorBlendState>,
+    attachment_feedback_loop: Option<StateMode<ImageAspects>>,
     dynamic_state: HashMap<DynamicState, bool>,
+    tracked: TrackedObject,
 }
 
 impl GraphicsPipeline {
@@ -151,6 +155,32 @@ impl GraphicsPipeline {
         &self.render_pass
     }
 
+    /// Checks whether the pipeline can be used in `subpass`, which is the case if the pipeline was
+    /// created for a subpass with the same index, of a render pass that is compatible with the
+    /// render pass of `subpass`.
+    pub fn check_compatible_with_subpass(
+        &self,
+        subpass: &Subpass,
+    ) -> Result<(), RenderPassCompatibilityError> {
+        let pipeline_subpass = match &self.render_pass {
+            PipelineRenderPassType::BeginRenderPass(subpass) => subpass,
+            PipelineRenderPassType::BeginRendering(_) => {
+                return Err(RenderPassCompatibilityError::PipelineNotForRenderPass)
+            }
+        };
+
+        if pipeline_subpass.index() != subpass.index() {
+            return Err(RenderPassCompatibilityError::SubpassIndexMismatch {
+                pipeline_subpass: pipeline_subpass.index(),
+                other_subpass: subpass.index(),
+            });
+        }
+
+        pipeline_subpass
+            .render_pass()
+            .check_compatible_with(subpass.render_pass())
+    }
+
     /// Returns information about a particular shader.
     ///
     /// `None` is returned if the pipeline does not contain this shader.
@@ -226,6 +256,13 @@ impl GraphicsPipeline {
         self.color_blend_state.as_ref()
     }
 
+    /// Returns the aspects of the attachments that can be read while they are written to, as
+    /// given when creating this pipeline.
+    #[inline]
+    pub fn attachment_feedback_loop(&self) -> Option<StateMode<ImageAspects>> {
+        self.attachment_feedback_loop
+    }
+
     /// Returns whether a particular state is must be dynamically set.
     ///
     /// `None` is returned if the pipeline does not contain this state. Previously set dynamic
@@ -306,3 +343,134 @@ impl Hash for GraphicsPipeline {
         self.device.hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        input_assembly::{InputAssemblyState, PrimitiveTopology},
+        viewport::ViewportState,
+        GraphicsPipeline, GraphicsPipelineCreationError,
+    };
+    use crate::{
+        device::Device, format::Format, image::ImageAspects, pipeline::StateMode,
+        render_pass::Subpass, shader::ShaderModule,
+    };
+    use std::sync::Arc;
+
+    // Empty vertex and fragment shaders.
+    const VERTEX_SHADER: [u32; 29] = [
+        0x07230203, 0x00010000, 0x00000000, 0x00000005, 0x00000000, 0x00020011, 0x00000001,
+        0x0003000e, 0x00000000, 0x00000001, 0x0005000f, 0x00000000, 0x00000001, 0x6e69616d,
+        0x00000000, 0x00020013, 0x00000002, 0x00030021, 0x00000003, 0x00000002, 0x00050036,
+        0x00000002, 0x00000001, 0x00000000, 0x00000003, 0x000200f8, 0x00000004, 0x000100fd,
+        0x00010038,
+    ];
+    const FRAGMENT_SHADER: [u32; 32] = [
+        0x07230203, 0x00010000, 0x00000000, 0x00000005, 0x00000000, 0x00020011, 0x00000001,
+        0x0003000e, 0x00000000, 0x00000001, 0x0005000f, 0x00000004, 0x00000001, 0x6e69616d,
+        0x00000000, 0x00030010, 0x00000001, 0x00000007, 0x00020013, 0x00000002, 0x00030021,
+        0x00000003, 0x00000002, 0x00050036, 0x00000002, 0x00000001, 0x00000000, 0x00000003,
+        0x000200f8, 0x00000004, 0x000100fd, 0x00010038,
+    ];
+
+    // Returns the empty shaders and a subpass with a single color attachment.
+    fn shaders_and_subpass(
+        device: &Arc<Device>,
+    ) -> (Arc<ShaderModule>, Arc<ShaderModule>, Subpass) {
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_SHADER).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_SHADER).unwrap() };
+
+        let render_pass = crate::single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: DontCare,
+                    store: Store,
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        )
+        .unwrap();
+
+        (vs, fs, Subpass::from(render_pass, 0).unwrap())
+    }
+
+    #[test]
+    fn triangle_fans_portability_subset() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let result = GraphicsPipeline::start()
+            .vertex_shader(vs.entry_point("main").unwrap(), ())
+            .input_assembly_state(
+                InputAssemblyState::new().topology(PrimitiveTopology::TriangleFan),
+            )
+            .viewport_state(ViewportState::viewport_dynamic_scissor_irrelevant())
+            .fragment_shader(fs.entry_point("main").unwrap(), ())
+            .render_pass(subpass)
+            .build(device.clone());
+
+        // Only portability subset devices need the `triangle_fans` feature.
+        if device.enabled_extensions().khr_portability_subset {
+            match result {
+                Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                    feature: "triangle_fans",
+                    ..
+                }) => (),
+                _ => panic!(),
+            }
+        } else {
+            result.unwrap();
+        }
+    }
+
+    #[test]
+    fn attachment_feedback_loop_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        let (vs, fs, subpass) = shaders_and_subpass(&device);
+
+        let result = GraphicsPipeline::start()
+            .vertex_shader(vs.entry_point("main").unwrap(), ())
+            .viewport_state(ViewportState::viewport_dynamic_scissor_irrelevant())
+            .fragment_shader(fs.entry_point("main").unwrap(), ())
+            .attachment_feedback_loop(StateMode::Fixed(ImageAspects {
+                color: true,
+                ..ImageAspects::none()
+            }))
+            .render_pass(subpass)
+            .build(device);
+
+        match result {
+            Err(GraphicsPipelineCreationError::ExtensionNotEnabled {
+                extension: "ext_attachment_feedback_loop_layout",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn attachment_feedback_loop_dynamic_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        let (vs, fs, subpass) = shaders_and_subpass(&device);
+
+        let result = GraphicsPipeline::start()
+            .vertex_shader(vs.entry_point("main").unwrap(), ())
+            .viewport_state(ViewportState::viewport_dynamic_scissor_irrelevant())
+            .fragment_shader(fs.entry_point("main").unwrap(), ())
+            .attachment_feedback_loop(StateMode::Dynamic)
+            .render_pass(subpass)
+            .build(device);
+
+        match result {
+            Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                feature: "attachment_feedback_loop_dynamic_state",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+}
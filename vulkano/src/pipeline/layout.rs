@@ -506,6 +506,8 @@ impl PipelineLayout {
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         Ok(handle)
     }
 
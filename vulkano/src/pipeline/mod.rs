@@ -104,6 +104,8 @@ pub enum DynamicState {
     LogicOp = ash::vk::DynamicState::LOGIC_OP_EXT.as_raw(),
     PrimitiveRestartEnable = ash::vk::DynamicState::PRIMITIVE_RESTART_ENABLE_EXT.as_raw(),
     ColorWriteEnable = ash::vk::DynamicState::COLOR_WRITE_ENABLE_EXT.as_raw(),
+    AttachmentFeedbackLoopEnable =
+        ash::vk::DynamicState::ATTACHMENT_FEEDBACK_LOOP_ENABLE_EXT.as_raw(),
 }
 
 impl From<DynamicState> for ash::vk::DynamicState {
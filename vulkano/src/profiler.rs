@@ -0,0 +1,655 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Measuring the time that the device spends executing commands.
+//!
+//! A [`GpuProfiler`] writes timestamp queries around named *zones* of commands, which can be
+//! nested in each other. The timestamps of each frame are read back once the device has finished
+//! executing the frame, and turned into a [`FrameProfile`]: a tree of the zones of the frame with
+//! their durations.
+//!
+//! The profiler keeps one set of queries for each frame in flight, and resets the queries of a
+//! frame at its beginning, after reading the results of the previous frame that used them. The
+//! results of a frame are available once all its command buffers have completed, which is when
+//! the fence that is signaled after them is signaled. They are read by
+//! [`resolve`](GpuProfiler::resolve), which never waits. If a frame has not completed by the time
+//! its queries are needed again, its results are lost, so `frames_in_flight` should be at least
+//! the number of frames that the application lets the device run behind.
+//!
+//! # Example
+//!
+//! ```
+//! use vulkano::profiler::{GpuProfiler, GpuProfilerCreateInfo};
+//!
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! # let mut builder: vulkano::command_buffer::AutoCommandBufferBuilder<vulkano::command_buffer::PrimaryAutoCommandBuffer> = return;
+//! let mut profiler = GpuProfiler::new(
+//!     device.clone(),
+//!     GpuProfilerCreateInfo {
+//!         frames_in_flight: 2,
+//!         ..Default::default()
+//!     },
+//! )
+//! .unwrap();
+//!
+//! // Each frame, outside of a render pass:
+//! profiler.begin_frame(&mut builder).unwrap();
+//!
+//! profiler.begin_zone(&mut builder, "shadows").unwrap();
+//! // Record the commands of the zone.
+//! profiler.end_zone(&mut builder).unwrap();
+//!
+//! profiler.begin_zone(&mut builder, "main pass").unwrap();
+//! profiler.begin_zone(&mut builder, "opaque").unwrap();
+//! // Zones can be nested.
+//! profiler.end_zone(&mut builder).unwrap();
+//! profiler.end_zone(&mut builder).unwrap();
+//!
+//! profiler.end_frame();
+//!
+//! // Later, for example after waiting for the fence of a previous frame:
+//! for frame in profiler.resolve().unwrap() {
+//!     for zone in frame.zones() {
+//!         println!("{}: {:?}", zone.name, zone.duration);
+//!     }
+//! }
+//! ```
+
+use crate::{
+    command_buffer::{AutoCommandBufferBuilder, ResetQueryPoolError, WriteTimestampError},
+    device::{Device, DeviceOwned},
+    query::{
+        GetResultsError, QueryPool, QueryPoolCreateInfo, QueryPoolCreationError, QueryResultFlags,
+        QueryType,
+    },
+    sync::PipelineStage,
+};
+use std::{error, fmt, ops::Range, sync::Arc, time::Duration};
+
+/// Timestamp queries that measure the durations of nested zones of commands, over several frames
+/// in flight.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug)]
+pub struct GpuProfiler {
+    query_pool: Arc<QueryPool>,
+    capacity: u32,
+    frames_in_flight: u32,
+    timestamp_period: f64,
+
+    // The number of frames that were begun.
+    next_frame: u64,
+    // The zones of the frame being recorded, in the order in which they were begun, and the valid
+    // bits of the timestamps of its queue family.
+    current: Option<(Vec<ZoneRecord>, u32)>,
+    // The zones that have been begun and not ended yet, innermost last.
+    stack: Vec<usize>,
+    // For each frame in flight, the frame that was ended and whose results have not been read
+    // yet.
+    pending: Vec<Option<PendingFrame>>,
+    last_frame: Option<FrameProfile>,
+}
+
+#[derive(Debug)]
+struct ZoneRecord {
+    name: String,
+    parent: Option<usize>,
+}
+
+#[derive(Debug)]
+struct PendingFrame {
+    frame: u64,
+    zones: Vec<ZoneRecord>,
+    valid_bits: u32,
+}
+
+impl GpuProfiler {
+    /// Creates a new `GpuProfiler`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.capacity` or `create_info.frames_in_flight` is 0.
+    pub fn new(
+        device: Arc<Device>,
+        create_info: GpuProfilerCreateInfo,
+    ) -> Result<GpuProfiler, QueryPoolCreationError> {
+        let GpuProfilerCreateInfo {
+            capacity,
+            frames_in_flight,
+            _ne: _,
+        } = create_info;
+
+        assert!(capacity != 0, "the capacity must not be 0");
+        assert!(frames_in_flight != 0, "frames_in_flight must not be 0");
+
+        let query_pool = QueryPool::new(
+            device.clone(),
+            QueryPoolCreateInfo {
+                query_count: capacity
+                    .checked_mul(2 * frames_in_flight)
+                    .expect("capacity * 2 * frames_in_flight must fit in a u32"),
+                ..QueryPoolCreateInfo::query_type(QueryType::Timestamp)
+            },
+        )?;
+
+        Ok(GpuProfiler {
+            query_pool,
+            capacity,
+            frames_in_flight,
+            timestamp_period: device.physical_device().properties().timestamp_period as f64,
+
+            next_frame: 0,
+            current: None,
+            stack: Vec::new(),
+            pending: (0..frames_in_flight).map(|_| None).collect(),
+            last_frame: None,
+        })
+    }
+
+    /// Returns the query pool that contains the queries of all the frames in flight.
+    #[inline]
+    pub fn query_pool(&self) -> &Arc<QueryPool> {
+        &self.query_pool
+    }
+
+    /// Returns the maximum number of zones that can be begun in a frame.
+    #[inline]
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Returns the number of frames that can be in flight at the same time.
+    #[inline]
+    pub fn frames_in_flight(&self) -> u32 {
+        self.frames_in_flight
+    }
+
+    /// Returns the profile of the most recent frame that was resolved, if any.
+    #[inline]
+    pub fn last_frame(&self) -> Option<&FrameProfile> {
+        self.last_frame.as_ref()
+    }
+
+    /// Records the start of a new frame into `builder`, which must be outside of a render pass.
+    ///
+    /// The results of the completed frames are [resolved](Self::resolve) first. Then the queries
+    /// of the frame in flight that the new frame uses are reset; if the frame that used them
+    /// before has not completed yet, its results are lost. `builder` must be submitted before the
+    /// command buffers that record the zones of the frame, or be one of them.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the previous frame was not ended with [`end_frame`](Self::end_frame).
+    pub fn begin_frame<L, P>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+    ) -> Result<(), GpuProfilerError> {
+        assert!(
+            self.current.is_none(),
+            "the previous frame must be ended first"
+        );
+
+        let valid_bits = builder
+            .queue_family()
+            .timestamp_valid_bits()
+            .ok_or(GpuProfilerError::TimestampsNotSupported)?;
+
+        self.resolve()?;
+
+        let slot = self.slot(self.next_frame);
+        self.pending[slot] = None;
+
+        unsafe {
+            // The queries of the slot are not read anymore, and they are written again only
+            // after this reset in submission order.
+            builder.reset_query_pool(self.query_pool.clone(), self.slot_queries(slot))?;
+        }
+
+        self.current = Some((Vec::new(), valid_bits));
+
+        Ok(())
+    }
+
+    /// Begins a zone named `name`, by writing a timestamp into `builder` once the previous
+    /// commands have completed.
+    ///
+    /// The zone contains the commands that are recorded until the matching call to
+    /// [`end_zone`](Self::end_zone). If another zone is active, the new zone is nested in it.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no frame was begun with [`begin_frame`](Self::begin_frame).
+    /// - Panics if [`capacity`](Self::capacity) zones were already begun in the frame.
+    pub fn begin_zone<L, P>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+        name: impl Into<String>,
+    ) -> Result<(), GpuProfilerError> {
+        let index = {
+            let (zones, _) = self.current.as_ref().expect("a frame must be begun first");
+            assert!(
+                (zones.len() as u32) < self.capacity,
+                "the capacity of the profiler was exceeded"
+            );
+            zones.len()
+        };
+
+        self.write_timestamp(builder, index as u32 * 2)?;
+
+        let (zones, _) = self.current.as_mut().unwrap();
+        zones.push(ZoneRecord {
+            name: name.into(),
+            parent: self.stack.last().copied(),
+        });
+        self.stack.push(index);
+
+        Ok(())
+    }
+
+    /// Ends the innermost active zone, by writing a timestamp into `builder` once the previous
+    /// commands have completed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no zone is active.
+    pub fn end_zone<L, P>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+    ) -> Result<(), GpuProfilerError> {
+        let index = *self.stack.last().expect("a zone must be begun first");
+        self.write_timestamp(builder, index as u32 * 2 + 1)?;
+        self.stack.pop();
+
+        Ok(())
+    }
+
+    /// Ends the current frame. Its results can be [resolved](Self::resolve) once all the command
+    /// buffers that recorded its zones have completed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no frame was begun with [`begin_frame`](Self::begin_frame).
+    /// - Panics if a zone was begun and not ended with [`end_zone`](Self::end_zone).
+    pub fn end_frame(&mut self) {
+        let (zones, valid_bits) = self.current.take().expect("a frame must be begun first");
+        assert!(self.stack.is_empty(), "all the zones must be ended first");
+
+        let frame = self.next_frame;
+        self.next_frame += 1;
+        let slot = self.slot(frame);
+
+        self.pending[slot] = Some(PendingFrame {
+            frame,
+            zones,
+            valid_bits,
+        });
+    }
+
+    /// Reads the results of the frames that have completed since the last call, without waiting,
+    /// and returns their profiles in the order of the frames.
+    ///
+    /// This is also called by [`begin_frame`](Self::begin_frame), so a frame that completes is
+    /// returned by whichever call comes first. The most recent profile is also kept, and returned
+    /// by [`last_frame`](Self::last_frame).
+    pub fn resolve(&mut self) -> Result<Vec<FrameProfile>, GpuProfilerError> {
+        let mut profiles = Vec::new();
+        let mut timestamps = Vec::new();
+
+        for slot in 0..self.frames_in_flight as usize {
+            let pending = match &self.pending[slot] {
+                Some(pending) => pending,
+                None => continue,
+            };
+
+            if !pending.zones.is_empty() {
+                let queries = self.slot_queries(slot);
+                let queries = queries.start..queries.start + pending.zones.len() as u32 * 2;
+                timestamps.clear();
+                timestamps.resize(queries.len(), 0u64);
+
+                let available = self
+                    .query_pool
+                    .queries_range(queries)
+                    .unwrap()
+                    .get_results(&mut timestamps, QueryResultFlags::default())?;
+
+                if !available {
+                    continue;
+                }
+            }
+
+            let PendingFrame {
+                frame,
+                zones,
+                valid_bits,
+            } = self.pending[slot].take().unwrap();
+
+            let mask = if valid_bits >= 64 {
+                u64::MAX
+            } else {
+                (1 << valid_bits) - 1
+            };
+
+            profiles.push(FrameProfile {
+                frame,
+                zones: build_zones(&zones, &timestamps, mask, self.timestamp_period),
+            });
+        }
+
+        profiles.sort_by_key(|profile| profile.frame);
+
+        if let Some(profile) = profiles.last() {
+            self.last_frame = Some(profile.clone());
+        }
+
+        Ok(profiles)
+    }
+
+    fn write_timestamp<L, P>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, P>,
+        query: u32,
+    ) -> Result<(), GpuProfilerError> {
+        let slot = self.slot(self.next_frame);
+
+        unsafe {
+            // The queries of the frame were reset in `begin_frame`. The timestamps are written
+            // once the previous commands have completed, so that the duration of a zone includes
+            // all of its work and none of the work before it.
+            builder.write_timestamp(
+                self.query_pool.clone(),
+                self.slot_queries(slot).start + query,
+                PipelineStage::BottomOfPipe,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn slot(&self, frame: u64) -> usize {
+        (frame % self.frames_in_flight as u64) as usize
+    }
+
+    // The range of queries of a frame in flight.
+    fn slot_queries(&self, slot: usize) -> Range<u32> {
+        let start = slot as u32 * self.capacity * 2;
+        start..start + self.capacity * 2
+    }
+}
+
+unsafe impl DeviceOwned for GpuProfiler {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.query_pool.device()
+    }
+}
+
+/// Parameters to create a new `GpuProfiler`.
+#[derive(Clone, Debug)]
+pub struct GpuProfilerCreateInfo {
+    /// The maximum number of zones that can be begun in a frame. Each zone uses two queries.
+    ///
+    /// The default value is `256`.
+    pub capacity: u32,
+
+    /// The number of frames that can be in flight at the same time. Each of them uses its own set
+    /// of queries, so that a frame can be recorded while the previous ones are still executing.
+    ///
+    /// The default value is `2`.
+    pub frames_in_flight: u32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for GpuProfilerCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            capacity: 256,
+            frames_in_flight: 2,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// The zones of a frame that was measured by a [`GpuProfiler`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrameProfile {
+    frame: u64,
+    zones: Vec<ZoneProfile>,
+}
+
+impl FrameProfile {
+    /// Returns the number of frames that were begun before this one by the profiler.
+    #[inline]
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Returns the zones that were not nested in another zone, in the order in which they were
+    /// begun.
+    #[inline]
+    pub fn zones(&self) -> &[ZoneProfile] {
+        &self.zones
+    }
+
+    /// Returns the first zone named `name`, searching nested zones depth-first, or `None` if
+    /// there is no such zone.
+    pub fn find(&self, name: &str) -> Option<&ZoneProfile> {
+        fn find<'a>(zones: &'a [ZoneProfile], name: &str) -> Option<&'a ZoneProfile> {
+            zones.iter().find_map(|zone| {
+                if zone.name == name {
+                    Some(zone)
+                } else {
+                    find(&zone.children, name)
+                }
+            })
+        }
+
+        find(&self.zones, name)
+    }
+
+    /// Returns the total duration of the zones that were not nested in another zone.
+    #[inline]
+    pub fn total(&self) -> Duration {
+        self.zones.iter().map(|zone| zone.duration).sum()
+    }
+}
+
+/// A zone of a [`FrameProfile`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZoneProfile {
+    /// The name that was given to the zone when it was begun.
+    pub name: String,
+
+    /// The time between the beginning of the first zone of the frame and the beginning of this
+    /// zone.
+    pub start: Duration,
+
+    /// The time between the beginning and the end of the zone.
+    pub duration: Duration,
+
+    /// The zones that were nested in this zone, in the order in which they were begun.
+    pub children: Vec<ZoneProfile>,
+}
+
+/// Error that can happen when using a [`GpuProfiler`].
+#[derive(Clone, Debug)]
+pub enum GpuProfilerError {
+    GetResultsError(GetResultsError),
+    ResetQueryPoolError(ResetQueryPoolError),
+    WriteTimestampError(WriteTimestampError),
+
+    /// The queue family of the command buffer doesn't support timestamps.
+    TimestampsNotSupported,
+}
+
+impl error::Error for GpuProfilerError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::GetResultsError(err) => Some(err),
+            Self::ResetQueryPoolError(err) => Some(err),
+            Self::WriteTimestampError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GpuProfilerError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::GetResultsError(_) => write!(f, "reading the query results failed"),
+            Self::ResetQueryPoolError(_) => write!(f, "resetting the queries failed"),
+            Self::WriteTimestampError(_) => write!(f, "writing a timestamp failed"),
+            Self::TimestampsNotSupported => write!(
+                f,
+                "the queue family of the command buffer doesn't support timestamps",
+            ),
+        }
+    }
+}
+
+impl From<GetResultsError> for GpuProfilerError {
+    #[inline]
+    fn from(err: GetResultsError) -> Self {
+        Self::GetResultsError(err)
+    }
+}
+
+impl From<ResetQueryPoolError> for GpuProfilerError {
+    #[inline]
+    fn from(err: ResetQueryPoolError) -> Self {
+        Self::ResetQueryPoolError(err)
+    }
+}
+
+impl From<WriteTimestampError> for GpuProfilerError {
+    #[inline]
+    fn from(err: WriteTimestampError) -> Self {
+        Self::WriteTimestampError(err)
+    }
+}
+
+// Builds the tree of zones from the two timestamps of each zone, in the order of `zones`.
+fn build_zones(
+    zones: &[ZoneRecord],
+    timestamps: &[u64],
+    mask: u64,
+    timestamp_period: f64,
+) -> Vec<ZoneProfile> {
+    let to_duration = |begin: u64, end: u64| {
+        let ticks = end.wrapping_sub(begin) & mask;
+        Duration::from_nanos((ticks as f64 * timestamp_period) as u64)
+    };
+    let origin = timestamps.first().copied().unwrap_or(0);
+
+    let mut profiles: Vec<_> = zones
+        .iter()
+        .enumerate()
+        .map(|(index, zone)| {
+            let begin = timestamps[index * 2];
+            let end = timestamps[index * 2 + 1];
+
+            Some(ZoneProfile {
+                name: zone.name.clone(),
+                start: to_duration(origin, begin),
+                duration: to_duration(begin, end),
+                children: Vec::new(),
+            })
+        })
+        .collect();
+    let mut roots = Vec::new();
+
+    // A zone is begun after its parent, so going backwards, all the children of a zone have been
+    // moved into it by the time it is moved into its own parent. They are moved in reverse order.
+    for index in (0..zones.len()).rev() {
+        let mut profile = profiles[index].take().unwrap();
+        profile.children.reverse();
+
+        match zones[index].parent {
+            Some(parent) => profiles[parent].as_mut().unwrap().children.push(profile),
+            None => roots.push(profile),
+        }
+    }
+
+    roots.reverse();
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_zones, GpuProfiler, GpuProfilerCreateInfo, ZoneRecord};
+    use crate::command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage};
+    use std::time::Duration;
+
+    #[test]
+    fn zone_tree() {
+        let zone = |name: &str, parent| ZoneRecord {
+            name: name.to_owned(),
+            parent,
+        };
+        let zones = [
+            zone("a", None),
+            zone("b", Some(0)),
+            zone("c", Some(1)),
+            zone("d", Some(0)),
+            zone("e", None),
+        ];
+        let timestamps = [100, 200, 110, 150, 120, 130, 150, 190, 200, 210];
+
+        let roots = build_zones(&zones, &timestamps, u64::MAX, 2.0);
+
+        assert_eq!(roots.len(), 2);
+        assert_eq!(roots[0].name, "a");
+        assert_eq!(roots[0].duration, Duration::from_nanos(200));
+        assert_eq!(roots[0].children.len(), 2);
+        assert_eq!(roots[0].children[0].name, "b");
+        assert_eq!(roots[0].children[0].start, Duration::from_nanos(20));
+        assert_eq!(roots[0].children[0].children[0].name, "c");
+        assert_eq!(roots[0].children[1].name, "d");
+        assert_eq!(roots[1].name, "e");
+        assert_eq!(roots[1].start, Duration::from_nanos(200));
+    }
+
+    #[test]
+    fn record_zones() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        if queue.family().timestamp_valid_bits().is_none() {
+            return;
+        }
+
+        let mut profiler = GpuProfiler::new(
+            device.clone(),
+            GpuProfilerCreateInfo {
+                capacity: 4,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        profiler.begin_frame(&mut builder).unwrap();
+        profiler.begin_zone(&mut builder, "outer").unwrap();
+        profiler.begin_zone(&mut builder, "inner").unwrap();
+        profiler.end_zone(&mut builder).unwrap();
+        profiler.end_zone(&mut builder).unwrap();
+        profiler.end_frame();
+
+        builder.build().unwrap();
+    }
+}
@@ -22,11 +22,14 @@ use std::{
     error,
     ffi::c_void,
     fmt,
+    future::Future,
     hash::{Hash, Hasher},
-    mem::{size_of_val, MaybeUninit},
+    mem::{size_of, size_of_val, MaybeUninit},
     ops::Range,
+    pin::Pin,
     ptr,
     sync::Arc,
+    task::{Context, Poll},
 };
 
 /// A collection of one or more queries of a particular type.
@@ -37,6 +40,9 @@ pub struct QueryPool {
 
     query_type: QueryType,
     query_count: u32,
+    performance_queue_family_index: u32,
+    performance_counters: Vec<PerformanceCounter>,
+    performance_query_passes: u32,
 }
 
 impl QueryPool {
@@ -45,6 +51,8 @@ impl QueryPool {
     /// # Panics
     ///
     /// - Panics if `create_info.query_count` is `0`.
+    /// - Panics if `create_info.query_type` is `PerformanceQuery` and
+    ///   `create_info.performance_counter_indices` is empty.
     pub fn new(
         device: Arc<Device>,
         create_info: QueryPoolCreateInfo,
@@ -52,12 +60,17 @@ impl QueryPool {
         let QueryPoolCreateInfo {
             query_type,
             query_count,
+            performance_queue_family_index,
+            performance_counter_indices,
             _ne: _,
         } = create_info;
 
         // VUID-VkQueryPoolCreateInfo-queryCount-02763
         assert!(query_count != 0);
 
+        let mut performance_counters = Vec::new();
+        let mut performance_query_passes = 1;
+
         let pipeline_statistics = match query_type {
             QueryType::PipelineStatistics(flags) => {
                 // VUID-VkQueryPoolCreateInfo-queryType-00791
@@ -65,15 +78,93 @@ impl QueryPool {
                     return Err(QueryPoolCreationError::PipelineStatisticsQueryFeatureNotEnabled);
                 }
 
+                if flags.task_shader_invocations || flags.mesh_shader_invocations {
+                    // VUID-VkQueryPoolCreateInfo-meshShaderQueries-07069
+                    if !device.enabled_features().mesh_shader_queries {
+                        return Err(QueryPoolCreationError::FeatureNotEnabled {
+                            feature: "mesh_shader_queries",
+                            reason: "query_type was QueryType::PipelineStatistics with the \
+                                task_shader_invocations or mesh_shader_invocations flags",
+                        });
+                    }
+                }
+
                 // VUID-VkQueryPoolCreateInfo-queryType-00792
                 flags.into()
             }
+            QueryType::MeshPrimitivesGenerated => {
+                if !device.enabled_extensions().ext_mesh_shader {
+                    return Err(QueryPoolCreationError::ExtensionNotEnabled {
+                        extension: "ext_mesh_shader",
+                        reason: "query_type was QueryType::MeshPrimitivesGenerated",
+                    });
+                }
+
+                // VUID-VkQueryPoolCreateInfo-meshShaderQueries-07068
+                if !device.enabled_features().mesh_shader_queries {
+                    return Err(QueryPoolCreationError::FeatureNotEnabled {
+                        feature: "mesh_shader_queries",
+                        reason: "query_type was QueryType::MeshPrimitivesGenerated",
+                    });
+                }
+
+                ash::vk::QueryPipelineStatisticFlags::empty()
+            }
+            QueryType::PerformanceQuery => {
+                if !device.enabled_extensions().khr_performance_query {
+                    return Err(QueryPoolCreationError::ExtensionNotEnabled {
+                        extension: "khr_performance_query",
+                        reason: "query_type was QueryType::PerformanceQuery",
+                    });
+                }
+
+                // VUID-VkQueryPoolPerformanceCreateInfoKHR-performanceCounterQueryPools-03237
+                if !device.enabled_features().performance_counter_query_pools {
+                    return Err(QueryPoolCreationError::FeatureNotEnabled {
+                        feature: "performance_counter_query_pools",
+                        reason: "query_type was QueryType::PerformanceQuery",
+                    });
+                }
+
+                // VUID-VkQueryPoolPerformanceCreateInfoKHR-counterIndexCount-arraylength
+                assert!(!performance_counter_indices.is_empty());
+
+                // VUID-VkQueryPoolPerformanceCreateInfoKHR-queueFamilyIndex-03236
+                let queue_family = device
+                    .physical_device()
+                    .queue_family_by_id(performance_queue_family_index)
+                    .ok_or(QueryPoolCreationError::QueueFamilyIndexOutOfRange {
+                        queue_family_index: performance_queue_family_index,
+                        queue_family_count: device.physical_device().queue_families().len() as u32,
+                    })?;
+
+                let counters = queue_family.performance_counters()?;
+
+                // VUID-VkQueryPoolPerformanceCreateInfoKHR-pCounterIndices-03321
+                for &index in &performance_counter_indices {
+                    match counters.get(index as usize) {
+                        Some(counter) => performance_counters.push(counter.clone()),
+                        None => {
+                            return Err(QueryPoolCreationError::CounterIndexOutOfRange {
+                                index,
+                                counter_count: counters.len() as u32,
+                            })
+                        }
+                    }
+                }
+
+                performance_query_passes = unsafe {
+                    queue_family.performance_query_passes_unchecked(&performance_counter_indices)
+                };
+
+                ash::vk::QueryPipelineStatisticFlags::empty()
+            }
             QueryType::Occlusion | QueryType::Timestamp => {
                 ash::vk::QueryPipelineStatisticFlags::empty()
             }
         };
 
-        let create_info = ash::vk::QueryPoolCreateInfo {
+        let mut create_info = ash::vk::QueryPoolCreateInfo {
             flags: ash::vk::QueryPoolCreateFlags::empty(),
             query_type: query_type.into(),
             query_count,
@@ -81,6 +172,21 @@ impl QueryPool {
             ..Default::default()
         };
 
+        // VUID-VkQueryPoolCreateInfo-queryType-03222
+        let performance_create_info =
+            matches!(query_type, QueryType::PerformanceQuery).then(|| {
+                ash::vk::QueryPoolPerformanceCreateInfoKHR {
+                    queue_family_index: performance_queue_family_index,
+                    counter_index_count: performance_counter_indices.len() as u32,
+                    p_counter_indices: performance_counter_indices.as_ptr(),
+                    ..Default::default()
+                }
+            });
+
+        if let Some(next) = performance_create_info.as_ref() {
+            create_info.p_next = next as *const _ as *const _;
+        }
+
         let handle = unsafe {
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
@@ -93,12 +199,17 @@ impl QueryPool {
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         Ok(Arc::new(QueryPool {
             handle,
             device,
 
             query_type,
             query_count,
+            performance_queue_family_index,
+            performance_counters,
+            performance_query_passes,
         }))
     }
 
@@ -114,6 +225,32 @@ impl QueryPool {
         self.query_count
     }
 
+    /// For performance query pools, returns the queue family whose performance counters are
+    /// measured. Returns `0` for other query types.
+    #[inline]
+    pub fn performance_queue_family_index(&self) -> u32 {
+        self.performance_queue_family_index
+    }
+
+    /// For performance query pools, returns the counters that each query measures, in the order
+    /// that their values are written in the results. Returns an empty slice for other query
+    /// types.
+    #[inline]
+    pub fn performance_counters(&self) -> &[PerformanceCounter] {
+        &self.performance_counters
+    }
+
+    /// For performance query pools, returns the number of passes that are needed to measure all
+    /// the counters. Returns `1` for other query types.
+    ///
+    /// The command buffers that use the queries of the pool must be submitted once for each pass,
+    /// with a different counter pass index each time, before the results are complete. See
+    /// [`CommandBufferExecFuture::counter_pass_index`](crate::command_buffer::CommandBufferExecFuture::counter_pass_index).
+    #[inline]
+    pub fn performance_query_passes(&self) -> u32 {
+        self.performance_query_passes
+    }
+
     /// Returns a reference to a single query slot, or `None` if the index is out of range.
     #[inline]
     pub fn query(&self, index: u32) -> Option<Query> {
@@ -139,6 +276,37 @@ impl QueryPool {
             None
         }
     }
+
+    /// Returns a future that resolves to the results of the queries in `range`, once they are all
+    /// available.
+    ///
+    /// The future never blocks: each time it is polled, it reads the results that have become
+    /// available. See [`QueryResultsFuture`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `range` is empty or out of range.
+    #[inline]
+    pub fn results_future<T>(self: &Arc<Self>, range: Range<u32>) -> QueryResultsFuture<T>
+    where
+        T: QueryResultElement + Copy + Default + Eq,
+    {
+        assert!(!range.is_empty(), "the range must not be empty");
+        assert!(
+            range.end <= self.query_count,
+            "the range must be within the queries of the pool"
+        );
+
+        let per_query_len = self.query_type.result_len() as usize;
+
+        QueryResultsFuture {
+            pool: self.clone(),
+            next: range.start,
+            results: vec![T::default(); per_query_len * range.len()],
+            range,
+            buffer: Vec::new(),
+        }
+    }
 }
 
 impl Drop for QueryPool {
@@ -197,6 +365,23 @@ pub struct QueryPoolCreateInfo {
     /// The default value is `0`, which must be overridden.
     pub query_count: u32,
 
+    /// For performance queries, the queue family whose performance counters are measured. The
+    /// queries can only be used in command buffers of this queue family.
+    ///
+    /// This is ignored for other query types.
+    ///
+    /// The default value is `0`.
+    pub performance_queue_family_index: u32,
+
+    /// For performance queries, the counters that each query measures, as indices into the list
+    /// returned by
+    /// [`QueueFamily::performance_counters`](crate::device::physical::QueueFamily::performance_counters).
+    ///
+    /// This must not be empty for performance queries, and is ignored for other query types.
+    ///
+    /// The default value is empty.
+    pub performance_counter_indices: Vec<u32>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -207,6 +392,8 @@ impl QueryPoolCreateInfo {
         Self {
             query_type,
             query_count: 0,
+            performance_queue_family_index: 0,
+            performance_counter_indices: Vec::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -217,8 +404,33 @@ impl QueryPoolCreateInfo {
 pub enum QueryPoolCreationError {
     /// Not enough memory.
     OomError(OomError),
+
+    /// The performance counters of the queue family could not be enumerated.
+    InitializationFailed,
+
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+
     /// A pipeline statistics pool was requested but the corresponding feature wasn't enabled.
     PipelineStatisticsQueryFeatureNotEnabled,
+
+    /// One of the performance counter indices is not less than the number of counters of the
+    /// queue family.
+    CounterIndexOutOfRange { index: u32, counter_count: u32 },
+
+    /// The performance queue family index is not less than the number of queue families of the
+    /// physical device.
+    QueueFamilyIndexOutOfRange {
+        queue_family_index: u32,
+        queue_family_count: u32,
+    },
 }
 
 impl error::Error for QueryPoolCreationError {
@@ -234,17 +446,44 @@ impl error::Error for QueryPoolCreationError {
 impl fmt::Display for QueryPoolCreationError {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(
-            fmt,
-            "{}",
-            match *self {
-                QueryPoolCreationError::OomError(_) => "not enough memory available",
-                QueryPoolCreationError::PipelineStatisticsQueryFeatureNotEnabled => {
-                    "a pipeline statistics pool was requested but the corresponding feature \
-                 wasn't enabled"
-                }
+        match *self {
+            Self::OomError(_) => write!(fmt, "not enough memory available"),
+            Self::InitializationFailed => write!(
+                fmt,
+                "the performance counters of the queue family could not be enumerated",
+            ),
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
             }
-        )
+            Self::PipelineStatisticsQueryFeatureNotEnabled => write!(
+                fmt,
+                "a pipeline statistics pool was requested but the corresponding feature wasn't \
+                enabled",
+            ),
+            Self::CounterIndexOutOfRange {
+                index,
+                counter_count,
+            } => write!(
+                fmt,
+                "the performance counter index {} is not less than the number of counters of the \
+                queue family ({})",
+                index, counter_count,
+            ),
+            Self::QueueFamilyIndexOutOfRange {
+                queue_family_index,
+                queue_family_count,
+            } => write!(
+                fmt,
+                "the queue family index {} is not less than the number of queue families of the \
+                physical device ({})",
+                queue_family_index, queue_family_count,
+            ),
+        }
     }
 }
 
@@ -255,6 +494,25 @@ impl From<OomError> for QueryPoolCreationError {
     }
 }
 
+impl From<PerformanceCountersError> for QueryPoolCreationError {
+    #[inline]
+    fn from(err: PerformanceCountersError) -> QueryPoolCreationError {
+        match err {
+            PerformanceCountersError::OomError(err) => Self::OomError(err),
+            PerformanceCountersError::InitializationFailed => Self::InitializationFailed,
+            PerformanceCountersError::CounterIndexOutOfRange {
+                index,
+                counter_count,
+            } => Self::CounterIndexOutOfRange {
+                index,
+                counter_count,
+            },
+            // The extension is enabled on the device, so it is supported.
+            PerformanceCountersError::NotSupported => unreachable!(),
+        }
+    }
+}
+
 impl From<Error> for QueryPoolCreationError {
     #[inline]
     fn from(err: Error) -> QueryPoolCreationError {
@@ -338,16 +596,18 @@ impl<'a> QueriesRange<'a> {
 
         let result = unsafe {
             let fns = self.pool.device.fns();
-            check_errors((fns.v1_0.get_query_pool_results)(
-                self.pool.device.internal_object(),
-                self.pool.internal_object(),
-                self.range.start,
-                self.range.end - self.range.start,
-                size_of_val(destination),
-                destination.as_mut_ptr() as *mut c_void,
-                stride,
-                ash::vk::QueryResultFlags::from(flags) | T::FLAG,
-            ))?
+            self.pool
+                .device
+                .check_errors((fns.v1_0.get_query_pool_results)(
+                    self.pool.device.internal_object(),
+                    self.pool.internal_object(),
+                    self.range.start,
+                    self.range.end - self.range.start,
+                    size_of_val(destination),
+                    destination.as_mut_ptr() as *mut c_void,
+                    stride,
+                    ash::vk::QueryResultFlags::from(flags) | T::FLAG,
+                ))?
         };
 
         Ok(match result {
@@ -357,6 +617,127 @@ impl<'a> QueriesRange<'a> {
         })
     }
 
+    /// Copies the results of this range of performance queries to a buffer on the CPU.
+    ///
+    /// [`self.pool().performance_counters().len()`](QueryPool::performance_counters) values
+    /// will be written for each query in the range, in the order of the counters.
+    /// The provided buffer must be large enough to hold the data.
+    ///
+    /// `true` is returned if every result was available and written to the buffer. `false`
+    /// is returned if some results were not yet available; nothing is written to the buffer then.
+    /// The results only become available once the command buffers that use the queries have been
+    /// submitted for each of the [`performance_query_passes`](QueryPool::performance_query_passes)
+    /// of the pool.
+    ///
+    /// Only [`QueryResultFlags::wait`] is allowed in `flags`.
+    pub fn get_performance_results(
+        &self,
+        destination: &mut [PerformanceCounterValue],
+        flags: QueryResultFlags,
+    ) -> Result<bool, GetResultsError> {
+        if !matches!(self.pool.query_type, QueryType::PerformanceQuery) {
+            return Err(GetResultsError::NotPermitted);
+        }
+
+        // VUID-vkGetQueryPoolResults-queryType-03230
+        if flags.with_availability || flags.partial {
+            return Err(GetResultsError::InvalidFlags);
+        }
+
+        let counters = &self.pool.performance_counters;
+        let count = self.range.end - self.range.start;
+        let required_len = counters.len() * count as usize;
+
+        if destination.len() < required_len {
+            return Err(GetResultsError::BufferTooSmall {
+                required_len: required_len as DeviceSize,
+                actual_len: destination.len() as DeviceSize,
+            });
+        }
+
+        let mut results = vec![ash::vk::PerformanceCounterResultKHR::default(); required_len];
+        let stride = counters.len() * size_of::<ash::vk::PerformanceCounterResultKHR>();
+
+        // VUID-vkGetQueryPoolResults-queryType-03231
+        // Not checked: the results are reported as not ready until every pass has been submitted.
+        let result = unsafe {
+            let fns = self.pool.device.fns();
+            self.pool
+                .device
+                .check_errors((fns.v1_0.get_query_pool_results)(
+                    self.pool.device.internal_object(),
+                    self.pool.internal_object(),
+                    self.range.start,
+                    count,
+                    size_of_val(results.as_slice()),
+                    results.as_mut_ptr() as *mut c_void,
+                    stride as DeviceSize,
+                    flags.into(),
+                ))?
+        };
+
+        match result {
+            Success::Success => (),
+            Success::NotReady => return Ok(false),
+            s => panic!("unexpected success value: {:?}", s),
+        }
+
+        for ((result, counter), value) in
+            results.iter().zip(counters.iter().cycle()).zip(destination)
+        {
+            *value = unsafe { PerformanceCounterValue::from_vulkan(counter.storage, result) };
+        }
+
+        Ok(true)
+    }
+
+    /// Copies the results of this range of pipeline statistics queries to a buffer on the CPU,
+    /// as one [`PipelineStatistics`] for each query in the range.
+    /// The provided buffer must be large enough to hold the data.
+    ///
+    /// `true` is returned if every result was available and written to the buffer. `false`
+    /// is returned if some results were not yet available; nothing is written to the buffer then.
+    ///
+    /// [`QueryResultFlags::with_availability`] is not allowed in `flags`, as the availability is
+    /// given by the return value.
+    pub fn get_pipeline_statistics(
+        &self,
+        destination: &mut [PipelineStatistics],
+        flags: QueryResultFlags,
+    ) -> Result<bool, GetResultsError> {
+        let statistics = match self.pool.query_type {
+            QueryType::PipelineStatistics(flags) => flags,
+            _ => return Err(GetResultsError::NotPermitted),
+        };
+
+        if flags.with_availability {
+            return Err(GetResultsError::InvalidFlags);
+        }
+
+        let count = (self.range.end - self.range.start) as usize;
+
+        if destination.len() < count {
+            return Err(GetResultsError::BufferTooSmall {
+                required_len: count as DeviceSize,
+                actual_len: destination.len() as DeviceSize,
+            });
+        }
+
+        let per_query_len = statistics.count() as usize;
+        let mut results = vec![0u64; per_query_len * count];
+
+        if per_query_len != 0 && !self.get_results(&mut results, flags)? {
+            return Ok(false);
+        }
+
+        for (index, value) in destination[..count].iter_mut().enumerate() {
+            let results = &results[index * per_query_len..(index + 1) * per_query_len];
+            *value = PipelineStatistics::from_results(statistics, results);
+        }
+
+        Ok(true)
+    }
+
     pub(crate) fn check_query_pool_results<T>(
         &self,
         buffer_start: DeviceSize,
@@ -369,6 +750,11 @@ impl<'a> QueriesRange<'a> {
         assert!(buffer_len > 0);
         debug_assert!(buffer_start % std::mem::size_of::<T>() as DeviceSize == 0);
 
+        // Performance queries have their own result type, see `get_performance_results`.
+        if matches!(self.pool.query_type, QueryType::PerformanceQuery) {
+            return Err(GetResultsError::NotPermitted);
+        }
+
         let count = self.range.end - self.range.start;
         let per_query_len =
             self.pool.query_type.result_len() + flags.with_availability as DeviceSize;
@@ -384,17 +770,120 @@ impl<'a> QueriesRange<'a> {
         match self.pool.query_type {
             QueryType::Occlusion => (),
             QueryType::PipelineStatistics(_) => (),
+            QueryType::MeshPrimitivesGenerated => (),
             QueryType::Timestamp => {
                 if flags.partial {
                     return Err(GetResultsError::InvalidFlags);
                 }
             }
+            QueryType::PerformanceQuery => unreachable!(),
         }
 
         Ok(per_query_len * std::mem::size_of::<T>() as DeviceSize)
     }
 }
 
+/// A future that resolves to the results of a range of queries, once they are all available.
+///
+/// Returned by [`QueryPool::results_future`]. Each time it is polled, the results are read with
+/// [`QueryResultFlags::with_availability`] and without waiting, and the results of the queries
+/// that have become available are kept. It resolves to
+/// [`result_len`](QueryType::result_len) elements for each query in the range.
+///
+/// The device can't notify the host when queries become available, so while results are missing,
+/// the future asks to be polled again right away. It is best suited to executors that poll once
+/// per frame. It can also be polled manually with [`try_results`](Self::try_results).
+#[derive(Debug)]
+pub struct QueryResultsFuture<T> {
+    pool: Arc<QueryPool>,
+    range: Range<u32>,
+    // The first query whose results are not available yet.
+    next: u32,
+    results: Vec<T>,
+    buffer: Vec<T>,
+}
+
+impl<T> QueryResultsFuture<T>
+where
+    T: QueryResultElement + Copy + Default + Eq,
+{
+    /// Returns the query pool of the queries.
+    #[inline]
+    pub fn pool(&self) -> &Arc<QueryPool> {
+        &self.pool
+    }
+
+    /// Returns the range of queries whose results are read.
+    #[inline]
+    pub fn range(&self) -> Range<u32> {
+        self.range.clone()
+    }
+
+    /// Reads the results that have become available, without waiting. Returns the results of all
+    /// the queries if they are all available, or `None` otherwise.
+    pub fn try_results(&mut self) -> Result<Option<&[T]>, GetResultsError> {
+        if self.next < self.range.end {
+            let per_query_len = self.pool.query_type.result_len() as usize;
+            let count = (self.range.end - self.next) as usize;
+
+            // The last element of each query is its availability.
+            self.buffer.clear();
+            self.buffer
+                .resize((per_query_len + 1) * count, T::default());
+
+            self.pool
+                .queries_range(self.next..self.range.end)
+                .unwrap()
+                .get_results(
+                    &mut self.buffer,
+                    QueryResultFlags {
+                        with_availability: true,
+                        ..QueryResultFlags::default()
+                    },
+                )?;
+
+            let first = (self.next - self.range.start) as usize;
+            let mut contiguous = true;
+
+            for (index, query) in self.buffer.chunks_exact(per_query_len + 1).enumerate() {
+                let (results, availability) = query.split_at(per_query_len);
+
+                if availability[0] == T::default() {
+                    contiguous = false;
+                    continue;
+                }
+
+                let start = (first + index) * per_query_len;
+                self.results[start..start + per_query_len].copy_from_slice(results);
+
+                if contiguous {
+                    self.next += 1;
+                }
+            }
+        }
+
+        Ok((self.next == self.range.end).then(|| self.results.as_slice()))
+    }
+}
+
+impl<T> Future for QueryResultsFuture<T>
+where
+    T: QueryResultElement + Copy + Default + Eq + Unpin,
+{
+    type Output = Result<Vec<T>, GetResultsError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        match self.get_mut().try_results() {
+            Ok(Some(results)) => Poll::Ready(Ok(results.to_vec())),
+            Ok(None) => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            Err(err) => Poll::Ready(Err(err)),
+        }
+    }
+}
+
 /// Error that can happen when calling [`QueriesRange::get_results`].
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum GetResultsError {
@@ -409,6 +898,8 @@ pub enum GetResultsError {
     DeviceLost,
     /// The provided flags are not allowed for this type of query.
     InvalidFlags,
+    /// This operation is not permitted on this query type.
+    NotPermitted,
     /// Not enough memory.
     OomError(OomError),
 }
@@ -447,6 +938,7 @@ impl fmt::Display for GetResultsError {
                 Self::InvalidFlags => {
                     "the provided flags are not allowed for this type of query"
                 }
+                Self::NotPermitted => "this operation is not permitted on this query type",
                 Self::OomError(_) => "not enough memory available",
             }
         )
@@ -489,22 +981,36 @@ pub enum QueryType {
     PipelineStatistics(QueryPipelineStatisticFlags),
     /// Writes timestamps at chosen points in a command buffer.
     Timestamp,
+    /// Measures hardware performance counters, chosen when creating the pool.
+    ///
+    /// The results are retrieved with
+    /// [`QueriesRange::get_performance_results`](QueriesRange::get_performance_results).
+    PerformanceQuery,
+    /// Tracks the number of primitives that are generated by mesh shaders.
+    ///
+    /// The [`ext_mesh_shader`](crate::device::DeviceExtensions::ext_mesh_shader) extension and
+    /// the [`mesh_shader_queries`](crate::device::Features::mesh_shader_queries) feature must be
+    /// enabled on the device.
+    MeshPrimitivesGenerated,
 }
 
 impl QueryType {
     /// Returns the number of [`QueryResultElement`]s that are needed to hold the result of a
     /// single query of this type.
     ///
-    /// - For `Occlusion` and `Timestamp` queries, this returns 1.
+    /// - For `Occlusion`, `Timestamp` and `MeshPrimitivesGenerated` queries, this returns 1.
     /// - For `PipelineStatistics` queries, this returns the number of statistics flags enabled.
+    /// - For `PerformanceQuery` queries, this returns 0, as their results are not
+    ///   `QueryResultElement`s.
     ///
     /// If the results are retrieved with [`QueryResultFlags::with_availability`] enabled, then
     /// an additional element is required per query.
     #[inline]
     pub const fn result_len(&self) -> DeviceSize {
         match self {
-            Self::Occlusion | Self::Timestamp => 1,
+            Self::Occlusion | Self::Timestamp | Self::MeshPrimitivesGenerated => 1,
             Self::PipelineStatistics(flags) => flags.count(),
+            Self::PerformanceQuery => 0,
         }
     }
 }
@@ -516,6 +1022,8 @@ impl From<QueryType> for ash::vk::QueryType {
             QueryType::Occlusion => ash::vk::QueryType::OCCLUSION,
             QueryType::PipelineStatistics(_) => ash::vk::QueryType::PIPELINE_STATISTICS,
             QueryType::Timestamp => ash::vk::QueryType::TIMESTAMP,
+            QueryType::PerformanceQuery => ash::vk::QueryType::PERFORMANCE_QUERY_KHR,
+            QueryType::MeshPrimitivesGenerated => ash::vk::QueryType::MESH_PRIMITIVES_GENERATED_EXT,
         }
     }
 }
@@ -565,6 +1073,16 @@ pub struct QueryPipelineStatisticFlags {
     pub tessellation_evaluation_shader_invocations: bool,
     /// Count the number of times a compute shader is invoked.
     pub compute_shader_invocations: bool,
+    /// Count the number of times a task shader is invoked.
+    ///
+    /// The [`mesh_shader_queries`](crate::device::Features::mesh_shader_queries) feature must be
+    /// enabled on the device.
+    pub task_shader_invocations: bool,
+    /// Count the number of times a mesh shader is invoked.
+    ///
+    /// The [`mesh_shader_queries`](crate::device::Features::mesh_shader_queries) feature must be
+    /// enabled on the device.
+    pub mesh_shader_invocations: bool,
 }
 
 impl QueryPipelineStatisticFlags {
@@ -582,6 +1100,8 @@ impl QueryPipelineStatisticFlags {
             tessellation_control_shader_patches: false,
             tessellation_evaluation_shader_invocations: false,
             compute_shader_invocations: false,
+            task_shader_invocations: false,
+            mesh_shader_invocations: false,
         }
     }
 
@@ -600,6 +1120,8 @@ impl QueryPipelineStatisticFlags {
             tessellation_control_shader_patches,
             tessellation_evaluation_shader_invocations,
             compute_shader_invocations,
+            task_shader_invocations,
+            mesh_shader_invocations,
         } = self;
         input_assembly_vertices as DeviceSize
             + input_assembly_primitives as DeviceSize
@@ -612,6 +1134,8 @@ impl QueryPipelineStatisticFlags {
             + tessellation_control_shader_patches as DeviceSize
             + tessellation_evaluation_shader_invocations as DeviceSize
             + compute_shader_invocations as DeviceSize
+            + task_shader_invocations as DeviceSize
+            + mesh_shader_invocations as DeviceSize
     }
 
     /// Returns `true` if any flags referring to compute operations are set to `true`.
@@ -638,6 +1162,8 @@ impl QueryPipelineStatisticFlags {
             fragment_shader_invocations,
             tessellation_control_shader_patches,
             tessellation_evaluation_shader_invocations,
+            task_shader_invocations,
+            mesh_shader_invocations,
             ..
         } = self;
         input_assembly_vertices
@@ -650,6 +1176,8 @@ impl QueryPipelineStatisticFlags {
             || fragment_shader_invocations
             || tessellation_control_shader_patches
             || tessellation_evaluation_shader_invocations
+            || task_shader_invocations
+            || mesh_shader_invocations
     }
 }
 
@@ -690,10 +1218,78 @@ impl From<QueryPipelineStatisticFlags> for ash::vk::QueryPipelineStatisticFlags
         if value.compute_shader_invocations {
             result |= ash::vk::QueryPipelineStatisticFlags::COMPUTE_SHADER_INVOCATIONS;
         }
+        if value.task_shader_invocations {
+            result |= ash::vk::QueryPipelineStatisticFlags::TASK_SHADER_INVOCATIONS_EXT;
+        }
+        if value.mesh_shader_invocations {
+            result |= ash::vk::QueryPipelineStatisticFlags::MESH_SHADER_INVOCATIONS_EXT;
+        }
         result
     }
 }
 
+/// The results of a pipeline statistics query.
+///
+/// Each field is `None` if the corresponding statistic was not enabled in the
+/// [`QueryPipelineStatisticFlags`] of the query pool.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PipelineStatistics {
+    /// The number of vertices processed by the input assembly.
+    pub input_assembly_vertices: Option<u64>,
+    /// The number of primitives processed by the input assembly.
+    pub input_assembly_primitives: Option<u64>,
+    /// The number of times a vertex shader was invoked.
+    pub vertex_shader_invocations: Option<u64>,
+    /// The number of times a geometry shader was invoked.
+    pub geometry_shader_invocations: Option<u64>,
+    /// The number of primitives generated by geometry shaders.
+    pub geometry_shader_primitives: Option<u64>,
+    /// The number of times the clipping stage was invoked on a primitive.
+    pub clipping_invocations: Option<u64>,
+    /// The number of primitives that were output by the clipping stage.
+    pub clipping_primitives: Option<u64>,
+    /// The number of times a fragment shader was invoked.
+    pub fragment_shader_invocations: Option<u64>,
+    /// The number of patches processed by a tessellation control shader.
+    pub tessellation_control_shader_patches: Option<u64>,
+    /// The number of times a tessellation evaluation shader was invoked.
+    pub tessellation_evaluation_shader_invocations: Option<u64>,
+    /// The number of times a compute shader was invoked.
+    pub compute_shader_invocations: Option<u64>,
+    /// The number of times a task shader was invoked.
+    pub task_shader_invocations: Option<u64>,
+    /// The number of times a mesh shader was invoked.
+    pub mesh_shader_invocations: Option<u64>,
+}
+
+impl PipelineStatistics {
+    // Unpacks the results of one query. Vulkan writes the enabled statistics in the order of
+    // their bits, which is the order of the fields.
+    fn from_results(flags: QueryPipelineStatisticFlags, results: &[u64]) -> Self {
+        let mut results = results.iter().copied();
+        let mut next = |enabled: bool| if enabled { results.next() } else { None };
+
+        PipelineStatistics {
+            input_assembly_vertices: next(flags.input_assembly_vertices),
+            input_assembly_primitives: next(flags.input_assembly_primitives),
+            vertex_shader_invocations: next(flags.vertex_shader_invocations),
+            geometry_shader_invocations: next(flags.geometry_shader_invocations),
+            geometry_shader_primitives: next(flags.geometry_shader_primitives),
+            clipping_invocations: next(flags.clipping_invocations),
+            clipping_primitives: next(flags.clipping_primitives),
+            fragment_shader_invocations: next(flags.fragment_shader_invocations),
+            tessellation_control_shader_patches: next(flags.tessellation_control_shader_patches),
+            tessellation_evaluation_shader_invocations: next(
+                flags.tessellation_evaluation_shader_invocations,
+            ),
+            compute_shader_invocations: next(flags.compute_shader_invocations),
+            task_shader_invocations: next(flags.task_shader_invocations),
+            mesh_shader_invocations: next(flags.mesh_shader_invocations),
+        }
+    }
+}
+
 /// Flags to control how the results of a query should be retrieved.
 ///
 /// `VK_QUERY_RESULT_64_BIT` is not included, as it is determined automatically via the
@@ -729,14 +1325,478 @@ impl From<QueryResultFlags> for ash::vk::QueryResultFlags {
     }
 }
 
+/// A hardware performance counter that performance queries can measure.
+///
+/// The counters of a queue family are returned by
+/// [`QueueFamily::performance_counters`](crate::device::physical::QueueFamily::performance_counters).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PerformanceCounter {
+    /// The unit of the values of the counter.
+    pub unit: PerformanceCounterUnit,
+
+    /// The commands that the counter measures.
+    pub scope: PerformanceCounterScope,
+
+    /// The type of the values of the counter.
+    pub storage: PerformanceCounterStorage,
+
+    /// An identifier of the counter, which is the same for every queue family and physical device
+    /// that can measure it.
+    pub uuid: [u8; 16],
+
+    /// The name of the counter.
+    pub name: String,
+
+    /// The category of the counter, which groups related counters together.
+    pub category: String,
+
+    /// A description of what the counter measures.
+    pub description: String,
+
+    /// Measuring the counter may impact the performance of the commands being measured.
+    pub performance_impacting: bool,
+
+    /// Concurrent work on other queues may impact the values of the counter.
+    pub concurrently_impacted: bool,
+}
+
+/// The unit of the values of a performance counter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum PerformanceCounterUnit {
+    /// A number without a unit.
+    Generic = ash::vk::PerformanceCounterUnitKHR::GENERIC.as_raw(),
+    /// A percentage, from 0 to 100.
+    Percentage = ash::vk::PerformanceCounterUnitKHR::PERCENTAGE.as_raw(),
+    Nanoseconds = ash::vk::PerformanceCounterUnitKHR::NANOSECONDS.as_raw(),
+    Bytes = ash::vk::PerformanceCounterUnitKHR::BYTES.as_raw(),
+    BytesPerSecond = ash::vk::PerformanceCounterUnitKHR::BYTES_PER_SECOND.as_raw(),
+    Kelvin = ash::vk::PerformanceCounterUnitKHR::KELVIN.as_raw(),
+    Watts = ash::vk::PerformanceCounterUnitKHR::WATTS.as_raw(),
+    Volts = ash::vk::PerformanceCounterUnitKHR::VOLTS.as_raw(),
+    Amps = ash::vk::PerformanceCounterUnitKHR::AMPS.as_raw(),
+    Hertz = ash::vk::PerformanceCounterUnitKHR::HERTZ.as_raw(),
+    Cycles = ash::vk::PerformanceCounterUnitKHR::CYCLES.as_raw(),
+}
+
+impl From<PerformanceCounterUnit> for ash::vk::PerformanceCounterUnitKHR {
+    #[inline]
+    fn from(val: PerformanceCounterUnit) -> Self {
+        Self::from_raw(val as i32)
+    }
+}
+
+impl TryFrom<ash::vk::PerformanceCounterUnitKHR> for PerformanceCounterUnit {
+    type Error = ();
+
+    #[inline]
+    fn try_from(val: ash::vk::PerformanceCounterUnitKHR) -> Result<Self, Self::Error> {
+        Ok(match val {
+            ash::vk::PerformanceCounterUnitKHR::GENERIC => Self::Generic,
+            ash::vk::PerformanceCounterUnitKHR::PERCENTAGE => Self::Percentage,
+            ash::vk::PerformanceCounterUnitKHR::NANOSECONDS => Self::Nanoseconds,
+            ash::vk::PerformanceCounterUnitKHR::BYTES => Self::Bytes,
+            ash::vk::PerformanceCounterUnitKHR::BYTES_PER_SECOND => Self::BytesPerSecond,
+            ash::vk::PerformanceCounterUnitKHR::KELVIN => Self::Kelvin,
+            ash::vk::PerformanceCounterUnitKHR::WATTS => Self::Watts,
+            ash::vk::PerformanceCounterUnitKHR::VOLTS => Self::Volts,
+            ash::vk::PerformanceCounterUnitKHR::AMPS => Self::Amps,
+            ash::vk::PerformanceCounterUnitKHR::HERTZ => Self::Hertz,
+            ash::vk::PerformanceCounterUnitKHR::CYCLES => Self::Cycles,
+            _ => return Err(()),
+        })
+    }
+}
+
+/// The commands that a performance counter measures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum PerformanceCounterScope {
+    /// The whole command buffer. A query that measures the counter must begin with the first
+    /// command of the command buffer, and end with the last one.
+    CommandBuffer = ash::vk::PerformanceCounterScopeKHR::COMMAND_BUFFER.as_raw(),
+
+    /// A whole render pass instance. A query that measures the counter must begin and end
+    /// outside of a render pass instance.
+    RenderPass = ash::vk::PerformanceCounterScopeKHR::RENDER_PASS.as_raw(),
+
+    /// Any commands.
+    Command = ash::vk::PerformanceCounterScopeKHR::COMMAND.as_raw(),
+}
+
+impl From<PerformanceCounterScope> for ash::vk::PerformanceCounterScopeKHR {
+    #[inline]
+    fn from(val: PerformanceCounterScope) -> Self {
+        Self::from_raw(val as i32)
+    }
+}
+
+impl TryFrom<ash::vk::PerformanceCounterScopeKHR> for PerformanceCounterScope {
+    type Error = ();
+
+    #[inline]
+    fn try_from(val: ash::vk::PerformanceCounterScopeKHR) -> Result<Self, Self::Error> {
+        Ok(match val {
+            ash::vk::PerformanceCounterScopeKHR::COMMAND_BUFFER => Self::CommandBuffer,
+            ash::vk::PerformanceCounterScopeKHR::RENDER_PASS => Self::RenderPass,
+            ash::vk::PerformanceCounterScopeKHR::COMMAND => Self::Command,
+            _ => return Err(()),
+        })
+    }
+}
+
+/// The type of the values of a performance counter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum PerformanceCounterStorage {
+    Int32 = ash::vk::PerformanceCounterStorageKHR::INT32.as_raw(),
+    Int64 = ash::vk::PerformanceCounterStorageKHR::INT64.as_raw(),
+    Uint32 = ash::vk::PerformanceCounterStorageKHR::UINT32.as_raw(),
+    Uint64 = ash::vk::PerformanceCounterStorageKHR::UINT64.as_raw(),
+    Float32 = ash::vk::PerformanceCounterStorageKHR::FLOAT32.as_raw(),
+    Float64 = ash::vk::PerformanceCounterStorageKHR::FLOAT64.as_raw(),
+}
+
+impl From<PerformanceCounterStorage> for ash::vk::PerformanceCounterStorageKHR {
+    #[inline]
+    fn from(val: PerformanceCounterStorage) -> Self {
+        Self::from_raw(val as i32)
+    }
+}
+
+impl TryFrom<ash::vk::PerformanceCounterStorageKHR> for PerformanceCounterStorage {
+    type Error = ();
+
+    #[inline]
+    fn try_from(val: ash::vk::PerformanceCounterStorageKHR) -> Result<Self, Self::Error> {
+        Ok(match val {
+            ash::vk::PerformanceCounterStorageKHR::INT32 => Self::Int32,
+            ash::vk::PerformanceCounterStorageKHR::INT64 => Self::Int64,
+            ash::vk::PerformanceCounterStorageKHR::UINT32 => Self::Uint32,
+            ash::vk::PerformanceCounterStorageKHR::UINT64 => Self::Uint64,
+            ash::vk::PerformanceCounterStorageKHR::FLOAT32 => Self::Float32,
+            ash::vk::PerformanceCounterStorageKHR::FLOAT64 => Self::Float64,
+            _ => return Err(()),
+        })
+    }
+}
+
+/// The value of a performance counter in the results of a performance query. Its type is given
+/// by the [`storage`](PerformanceCounter::storage) of the counter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PerformanceCounterValue {
+    Int32(i32),
+    Int64(i64),
+    Uint32(u32),
+    Uint64(u64),
+    Float32(f32),
+    Float64(f64),
+}
+
+impl PerformanceCounterValue {
+    /// Returns the value converted to a `f64`.
+    #[inline]
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Self::Int32(val) => val as f64,
+            Self::Int64(val) => val as f64,
+            Self::Uint32(val) => val as f64,
+            Self::Uint64(val) => val as f64,
+            Self::Float32(val) => val as f64,
+            Self::Float64(val) => val,
+        }
+    }
+
+    // Safety: `storage` must be the storage of the counter that `result` was written for.
+    unsafe fn from_vulkan(
+        storage: PerformanceCounterStorage,
+        result: &ash::vk::PerformanceCounterResultKHR,
+    ) -> Self {
+        match storage {
+            PerformanceCounterStorage::Int32 => Self::Int32(result.int32),
+            PerformanceCounterStorage::Int64 => Self::Int64(result.int64),
+            PerformanceCounterStorage::Uint32 => Self::Uint32(result.uint32),
+            PerformanceCounterStorage::Uint64 => Self::Uint64(result.uint64),
+            PerformanceCounterStorage::Float32 => Self::Float32(result.float32),
+            PerformanceCounterStorage::Float64 => Self::Float64(result.float64),
+        }
+    }
+}
+
+impl Default for PerformanceCounterValue {
+    #[inline]
+    fn default() -> Self {
+        Self::Uint64(0)
+    }
+}
+
+/// Error that can happen when enumerating the performance counters of a queue family.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PerformanceCountersError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The performance counters could not be enumerated, for implementation-specific reasons.
+    InitializationFailed,
+
+    /// The [`khr_performance_query`](crate::device::DeviceExtensions::khr_performance_query)
+    /// extension is not supported by the physical device.
+    NotSupported,
+
+    /// One of the counter indices is not less than the number of counters of the queue family.
+    CounterIndexOutOfRange { index: u32, counter_count: u32 },
+}
+
+impl error::Error for PerformanceCountersError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PerformanceCountersError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::OomError(_) => write!(fmt, "not enough memory available"),
+            Self::InitializationFailed => write!(
+                fmt,
+                "the performance counters could not be enumerated, for implementation-specific \
+                reasons",
+            ),
+            Self::NotSupported => write!(
+                fmt,
+                "the khr_performance_query extension is not supported by the physical device",
+            ),
+            Self::CounterIndexOutOfRange {
+                index,
+                counter_count,
+            } => write!(
+                fmt,
+                "the counter index {} is not less than the number of counters of the queue family \
+                ({})",
+                index, counter_count,
+            ),
+        }
+    }
+}
+
+impl From<OomError> for PerformanceCountersError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<Error> for PerformanceCountersError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            err @ Error::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
+            Error::InitializationFailed => Self::InitializationFailed,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::QueryPoolCreateInfo;
+    use crate::query::PipelineStatistics;
     use crate::query::QueryPipelineStatisticFlags;
     use crate::query::QueryPool;
     use crate::query::QueryPoolCreationError;
     use crate::query::QueryType;
 
+    #[test]
+    fn pipeline_statistics_results() {
+        let flags = QueryPipelineStatisticFlags {
+            vertex_shader_invocations: true,
+            fragment_shader_invocations: true,
+            compute_shader_invocations: true,
+            ..QueryPipelineStatisticFlags::none()
+        };
+        let statistics = PipelineStatistics::from_results(flags, &[3, 5, 7]);
+
+        assert_eq!(statistics.input_assembly_vertices, None);
+        assert_eq!(statistics.vertex_shader_invocations, Some(3));
+        assert_eq!(statistics.clipping_primitives, None);
+        assert_eq!(statistics.fragment_shader_invocations, Some(5));
+        assert_eq!(statistics.compute_shader_invocations, Some(7));
+        assert_eq!(statistics.task_shader_invocations, None);
+    }
+
+    #[test]
+    fn pipeline_statistics_results_mesh_shader() {
+        let flags = QueryPipelineStatisticFlags {
+            clipping_primitives: true,
+            fragment_shader_invocations: true,
+            task_shader_invocations: true,
+            mesh_shader_invocations: true,
+            ..QueryPipelineStatisticFlags::none()
+        };
+        assert_eq!(flags.count(), 4);
+        assert!(flags.is_graphics());
+        assert!(!flags.is_compute());
+
+        // The mesh shader statistics come after the others.
+        let statistics = PipelineStatistics::from_results(flags, &[2, 3, 5, 7]);
+
+        assert_eq!(statistics.vertex_shader_invocations, None);
+        assert_eq!(statistics.clipping_primitives, Some(2));
+        assert_eq!(statistics.fragment_shader_invocations, Some(3));
+        assert_eq!(statistics.compute_shader_invocations, None);
+        assert_eq!(statistics.task_shader_invocations, Some(5));
+        assert_eq!(statistics.mesh_shader_invocations, Some(7));
+    }
+
+    #[test]
+    fn mesh_shader_queries_feature() {
+        let (device, _) = gfx_dev_and_queue!(pipeline_statistics_query);
+        let query_type = QueryType::PipelineStatistics(QueryPipelineStatisticFlags {
+            mesh_shader_invocations: true,
+            ..QueryPipelineStatisticFlags::none()
+        });
+        match QueryPool::new(
+            device,
+            QueryPoolCreateInfo {
+                query_count: 1,
+                ..QueryPoolCreateInfo::query_type(query_type)
+            },
+        ) {
+            Err(QueryPoolCreationError::FeatureNotEnabled {
+                feature: "mesh_shader_queries",
+                ..
+            }) => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn mesh_primitives_generated_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+        match QueryPool::new(
+            device,
+            QueryPoolCreateInfo {
+                query_count: 1,
+                ..QueryPoolCreateInfo::query_type(QueryType::MeshPrimitivesGenerated)
+            },
+        ) {
+            Err(QueryPoolCreationError::ExtensionNotEnabled {
+                extension: "ext_mesh_shader",
+                ..
+            }) => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn results_future_range() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = QueryPool::new(
+            device,
+            QueryPoolCreateInfo {
+                query_count: 2,
+                ..QueryPoolCreateInfo::query_type(QueryType::Timestamp)
+            },
+        )
+        .unwrap();
+
+        assert_should_panic!("the range must not be empty", {
+            pool.results_future::<u64>(1..1);
+        });
+        assert_should_panic!("the range must be within the queries of the pool", {
+            pool.results_future::<u64>(1..3);
+        });
+
+        let future = pool.results_future::<u64>(1..2);
+        assert_eq!(future.range(), 1..2);
+    }
+
+    #[test]
+    fn results_future() {
+        use crate::{
+            command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
+            sync::{now, GpuFuture, PipelineStage},
+        };
+        use std::{
+            future::Future,
+            pin::Pin,
+            sync::Arc,
+            task::{Context, Poll, Wake, Waker},
+        };
+
+        let (device, queue) = gfx_dev_and_queue!();
+
+        if queue.family().timestamp_valid_bits().is_none() {
+            return;
+        }
+
+        let pool = QueryPool::new(
+            device.clone(),
+            QueryPoolCreateInfo {
+                query_count: 2,
+                ..QueryPoolCreateInfo::query_type(QueryType::Timestamp)
+            },
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        unsafe {
+            builder
+                .reset_query_pool(pool.clone(), 0..2)
+                .unwrap()
+                .write_timestamp(pool.clone(), 0, PipelineStage::TopOfPipe)
+                .unwrap()
+                .write_timestamp(pool.clone(), 1, PipelineStage::BottomOfPipe)
+                .unwrap();
+        }
+
+        let command_buffer = builder.build().unwrap();
+        let mut future = pool.results_future::<u64>(0..2);
+
+        now(device)
+            .then_execute(queue, command_buffer)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        // Both queries are available once the command buffer has completed.
+        struct NoopWaker;
+
+        impl Wake for NoopWaker {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+
+        let results = match Pin::new(&mut future).poll(&mut cx) {
+            Poll::Ready(Ok(results)) => results,
+            _ => panic!(),
+        };
+        assert_eq!(results.len(), 2);
+
+        // The results are kept after the future has resolved.
+        assert_eq!(future.try_results().unwrap(), Some(results.as_slice()));
+    }
+
     #[test]
     fn pipeline_statistics_feature() {
         let (device, _) = gfx_dev_and_queue!();
@@ -752,4 +1812,24 @@ mod tests {
             _ => panic!(),
         };
     }
+
+    #[test]
+    fn performance_query_extension() {
+        let (device, queue) = gfx_dev_and_queue!();
+        match QueryPool::new(
+            device,
+            QueryPoolCreateInfo {
+                query_count: 1,
+                performance_queue_family_index: queue.family().id(),
+                performance_counter_indices: vec![0],
+                ..QueryPoolCreateInfo::query_type(QueryType::PerformanceQuery)
+            },
+        ) {
+            Err(QueryPoolCreationError::ExtensionNotEnabled {
+                extension: "khr_performance_query",
+                ..
+            }) => (),
+            _ => panic!(),
+        };
+    }
 }
@@ -0,0 +1,763 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{
+    AttachmentLoad, GraphicsPassInfo, ImageResourceInfo, ImageUse, PassCallback, PassContext,
+    PassId, PassInfo, PassNode, RenderGraph, ResourceId, ResourceNode, Step,
+};
+use crate::{
+    device::Device,
+    format::{ClearValue, Format},
+    image::{ImageUsage, SampleCount},
+    render_pass::{
+        AttachmentDescription, LoadOp, RenderPassBuilder, RenderPassCreationError, StoreOp,
+        Subpass, SubpassBuilder, SubpassDependency,
+    },
+    sync::PipelineStages,
+};
+use std::{collections::BTreeSet, sync::Arc};
+
+/// Declares the resources and passes of a [`RenderGraph`].
+///
+/// See the [module-level documentation](super) for more information.
+pub struct RenderGraphBuilder {
+    device: Arc<Device>,
+    resources: Vec<ResourceDecl>,
+    passes: Vec<PassDecl>,
+}
+
+struct ResourceDecl {
+    format: Format,
+    samples: SampleCount,
+    external: bool,
+}
+
+struct PassDecl {
+    kind: PassKind,
+    callback: PassCallback,
+}
+
+enum PassKind {
+    Graphics(GraphicsPassInfo),
+    Other(PassInfo),
+}
+
+// How a pass accesses a resource.
+#[derive(Clone, Copy)]
+struct Access {
+    resource: ResourceId,
+    read: bool,
+    write: bool,
+    // Whether the access is as an attachment or input attachment.
+    attachment: bool,
+}
+
+impl PassDecl {
+    fn images(&self) -> &[(ResourceId, ImageUse)] {
+        match &self.kind {
+            PassKind::Graphics(info) => &info.images,
+            PassKind::Other(info) => &info.images,
+        }
+    }
+
+    fn accesses(&self) -> Vec<Access> {
+        let mut accesses = Vec::new();
+
+        if let PassKind::Graphics(info) = &self.kind {
+            for &(resource, load) in info
+                .color_attachments
+                .iter()
+                .chain(info.depth_stencil_attachment.iter())
+            {
+                accesses.push(Access {
+                    resource,
+                    read: matches!(load, AttachmentLoad::Load),
+                    write: true,
+                    attachment: true,
+                });
+            }
+
+            for &resource in &info.input_attachments {
+                accesses.push(Access {
+                    resource,
+                    read: true,
+                    write: false,
+                    attachment: true,
+                });
+            }
+        }
+
+        for &(resource, image_use) in self.images() {
+            accesses.push(Access {
+                resource,
+                read: image_use != ImageUse::TransferDst,
+                write: image_use.is_write(),
+                attachment: false,
+            });
+        }
+
+        accesses
+    }
+
+    // Returns the resources that the pass uses as attachments or input attachments.
+    fn attachment_resources(&self) -> Vec<ResourceId> {
+        match &self.kind {
+            PassKind::Graphics(info) => (info.color_attachments.iter())
+                .chain(info.depth_stencil_attachment.iter())
+                .map(|&(resource, _)| resource)
+                .chain(info.input_attachments.iter().copied())
+                .collect(),
+            PassKind::Other(_) => Vec::new(),
+        }
+    }
+}
+
+enum Group {
+    Graphics(Vec<usize>),
+    Other(usize),
+}
+
+impl RenderGraphBuilder {
+    /// Starts building a render graph with no resources or passes.
+    #[inline]
+    pub fn new(device: Arc<Device>) -> Self {
+        Self {
+            device,
+            resources: Vec::new(),
+            passes: Vec::new(),
+        }
+    }
+
+    /// Adds an image that is allocated by the graph. Its content only lives for the duration of
+    /// an execution of the graph.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `info.format` is `None`.
+    #[inline]
+    pub fn add_transient_image(&mut self, info: ImageResourceInfo) -> ResourceId {
+        self.add_image(info, false)
+    }
+
+    /// Adds an image that is provided each time the graph is executed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `info.format` is `None`.
+    #[inline]
+    pub fn add_external_image(&mut self, info: ImageResourceInfo) -> ResourceId {
+        self.add_image(info, true)
+    }
+
+    fn add_image(&mut self, info: ImageResourceInfo, external: bool) -> ResourceId {
+        let ImageResourceInfo {
+            format,
+            samples,
+            _ne: _,
+        } = info;
+
+        self.resources.push(ResourceDecl {
+            format: format.expect("no format was provided"),
+            samples,
+            external,
+        });
+
+        ResourceId(self.resources.len() as u32 - 1)
+    }
+
+    /// Adds a pass that draws in a subpass of a render pass.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `info` refers to a resource that was not added to this builder.
+    pub fn add_graphics_pass<F>(&mut self, info: GraphicsPassInfo, callback: F) -> PassId
+    where
+        F: FnMut(&mut PassContext<'_>) + Send + 'static,
+    {
+        self.add_pass_decl(PassKind::Graphics(info), Box::new(callback))
+    }
+
+    /// Adds a pass that is recorded outside of a render pass, such as a compute or transfer pass.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `info` refers to a resource that was not added to this builder.
+    pub fn add_pass<F>(&mut self, info: PassInfo, callback: F) -> PassId
+    where
+        F: FnMut(&mut PassContext<'_>) + Send + 'static,
+    {
+        self.add_pass_decl(PassKind::Other(info), Box::new(callback))
+    }
+
+    fn add_pass_decl(&mut self, kind: PassKind, callback: PassCallback) -> PassId {
+        let pass = PassDecl { kind, callback };
+
+        for access in pass.accesses() {
+            assert!(
+                (access.resource.0 as usize) < self.resources.len(),
+                "the pass uses a resource that was not added to the builder"
+            );
+        }
+
+        self.passes.push(pass);
+        PassId(self.passes.len() as u32 - 1)
+    }
+
+    /// Orders the passes, merges them into render passes, and creates the render passes.
+    pub fn build(self) -> Result<RenderGraph, RenderPassCreationError> {
+        let RenderGraphBuilder {
+            device,
+            resources,
+            passes,
+        } = self;
+
+        let accesses: Vec<Vec<Access>> = passes.iter().map(PassDecl::accesses).collect();
+        let groups = order_passes(&passes, &accesses, resources.len());
+
+        // The position of each pass in the execution order.
+        let mut positions = vec![0; passes.len()];
+        let mut position = 0;
+
+        for group in &groups {
+            match group {
+                Group::Graphics(group_passes) => {
+                    for &pass in group_passes {
+                        positions[pass] = position;
+                        position += 1;
+                    }
+                }
+                &Group::Other(pass) => {
+                    positions[pass] = position;
+                    position += 1;
+                }
+            }
+        }
+
+        // Returns whether the content of `resource` must be stored after the passes at positions
+        // up to `last_position`: the next access to the resource reads it, or there is no next
+        // access and the resource is external.
+        let must_store = |resource: ResourceId, last_position: usize| {
+            let next_access = (0..passes.len())
+                .filter(|&pass| positions[pass] > last_position)
+                .filter_map(|pass| {
+                    accesses[pass]
+                        .iter()
+                        .filter(|access| access.resource == resource)
+                        .map(|access| (positions[pass], access.read))
+                        .reduce(|a, b| (a.0, a.1 || b.1))
+                })
+                .min_by_key(|&(position, _)| position);
+
+            match next_access {
+                Some((_, read)) => read,
+                None => resources[resource.0 as usize].external,
+            }
+        };
+
+        let mut subpasses: Vec<Option<Subpass>> = vec![None; passes.len()];
+        let mut pass_groups: Vec<Option<usize>> = vec![None; passes.len()];
+        let mut steps = Vec::with_capacity(groups.len());
+
+        for (group_index, group) in groups.iter().enumerate() {
+            let group_passes = match group {
+                Group::Graphics(group_passes) => group_passes,
+                &Group::Other(pass) => {
+                    pass_groups[pass] = Some(group_index);
+                    steps.push(Step::Pass(PassId(pass as u32)));
+                    continue;
+                }
+            };
+
+            // The resources that are attachments of the render pass, and how they are loaded.
+            let mut attachments: Vec<(ResourceId, LoadOp, Option<ClearValue>)> = Vec::new();
+
+            for &pass in group_passes {
+                let info = match &passes[pass].kind {
+                    PassKind::Graphics(info) => info,
+                    PassKind::Other(_) => unreachable!(),
+                };
+
+                for &(resource, load) in info
+                    .color_attachments
+                    .iter()
+                    .chain(info.depth_stencil_attachment.iter())
+                {
+                    if !attachments.iter().any(|&(r, _, _)| r == resource) {
+                        let (load_op, clear_value) = match load {
+                            AttachmentLoad::Load => (LoadOp::Load, None),
+                            AttachmentLoad::Clear(clear_value) => {
+                                (LoadOp::Clear, Some(clear_value))
+                            }
+                            AttachmentLoad::DontCare => (LoadOp::DontCare, None),
+                        };
+                        attachments.push((resource, load_op, clear_value));
+                    }
+                }
+
+                for &resource in &info.input_attachments {
+                    if !attachments.iter().any(|&(r, _, _)| r == resource) {
+                        attachments.push((resource, LoadOp::Load, None));
+                    }
+                }
+            }
+
+            let last_position = positions[*group_passes.last().unwrap()];
+            let attachment_index = |resource: ResourceId| {
+                attachments
+                    .iter()
+                    .position(|&(r, _, _)| r == resource)
+                    .unwrap() as u32
+            };
+
+            let mut render_pass_builder = RenderPassBuilder::new();
+
+            for &(resource, load_op, _) in &attachments {
+                let resource_decl = &resources[resource.0 as usize];
+                let store_op = if must_store(resource, last_position) {
+                    StoreOp::Store
+                } else {
+                    StoreOp::DontCare
+                };
+
+                render_pass_builder = render_pass_builder.attachment(AttachmentDescription {
+                    format: Some(resource_decl.format),
+                    samples: resource_decl.samples,
+                    load_op,
+                    store_op,
+                    stencil_load_op: load_op,
+                    stencil_store_op: store_op,
+                    ..Default::default()
+                });
+            }
+
+            for (subpass_index, &pass) in group_passes.iter().enumerate() {
+                let info = match &passes[pass].kind {
+                    PassKind::Graphics(info) => info,
+                    PassKind::Other(_) => unreachable!(),
+                };
+
+                let mut subpass_builder = SubpassBuilder::new();
+
+                for &resource in &info.input_attachments {
+                    subpass_builder = subpass_builder.input_attachment(attachment_index(resource));
+                }
+
+                for &(resource, _) in &info.color_attachments {
+                    subpass_builder = subpass_builder.color_attachment(attachment_index(resource));
+                }
+
+                if let Some((resource, _)) = info.depth_stencil_attachment {
+                    subpass_builder =
+                        subpass_builder.depth_stencil_attachment(attachment_index(resource));
+                }
+
+                // Attachments that are used before and after this subpass, but not by it, must be
+                // preserved.
+                let used = passes[pass].attachment_resources();
+
+                for &(resource, _, _) in &attachments {
+                    let is_used_by = |other_pass: &usize| {
+                        passes[*other_pass]
+                            .attachment_resources()
+                            .contains(&resource)
+                    };
+
+                    if !used.contains(&resource)
+                        && group_passes[..subpass_index].iter().any(is_used_by)
+                        && group_passes[subpass_index + 1..].iter().any(is_used_by)
+                    {
+                        subpass_builder =
+                            subpass_builder.preserve_attachment(attachment_index(resource));
+                    }
+                }
+
+                render_pass_builder = render_pass_builder.subpass(subpass_builder);
+            }
+
+            for subpass_index in 1..group_passes.len() as u32 {
+                let stages = PipelineStages {
+                    all_graphics: true,
+                    ..PipelineStages::none()
+                };
+
+                render_pass_builder = render_pass_builder.dependency(SubpassDependency {
+                    source_subpass: Some(subpass_index - 1),
+                    destination_subpass: Some(subpass_index),
+                    source_stages: stages,
+                    destination_stages: stages,
+                    source_access: stages.supported_access(),
+                    destination_access: stages.supported_access(),
+                    by_region: true,
+                    ..Default::default()
+                });
+            }
+
+            let render_pass = render_pass_builder.build(device.clone())?;
+
+            for (subpass_index, &pass) in group_passes.iter().enumerate() {
+                subpasses[pass] = Subpass::from(render_pass.clone(), subpass_index as u32);
+                pass_groups[pass] = Some(group_index);
+            }
+
+            steps.push(Step::RenderPass {
+                render_pass,
+                attachments: attachments.iter().map(|&(r, _, _)| r).collect(),
+                clear_values: attachments.iter().map(|&(_, _, c)| c).collect(),
+                passes: group_passes
+                    .iter()
+                    .map(|&pass| {
+                        let contents = match &passes[pass].kind {
+                            PassKind::Graphics(info) => info.contents,
+                            PassKind::Other(_) => unreachable!(),
+                        };
+                        (PassId(pass as u32), contents)
+                    })
+                    .collect(),
+            });
+        }
+
+        let resource_nodes = resources
+            .iter()
+            .enumerate()
+            .map(|(index, resource_decl)| {
+                let resource = ResourceId(index as u32);
+                let mut usage = ImageUsage::none();
+                let mut only_attachment = true;
+                let mut used_groups = BTreeSet::new();
+                let mut attachment_groups = BTreeSet::new();
+
+                for (pass, pass_decl) in passes.iter().enumerate() {
+                    if accesses[pass]
+                        .iter()
+                        .any(|access| access.resource == resource)
+                    {
+                        used_groups.insert(pass_groups[pass].unwrap());
+                    }
+
+                    if let PassKind::Graphics(info) = &pass_decl.kind {
+                        if info.color_attachments.iter().any(|&(r, _)| r == resource) {
+                            usage.color_attachment = true;
+                        }
+
+                        if matches!(info.depth_stencil_attachment, Some((r, _)) if r == resource) {
+                            usage.depth_stencil_attachment = true;
+                        }
+
+                        if info.input_attachments.contains(&resource) {
+                            usage.input_attachment = true;
+                        }
+
+                        if pass_decl.attachment_resources().contains(&resource) {
+                            attachment_groups.insert(pass_groups[pass].unwrap());
+                        }
+                    }
+
+                    for &(_, image_use) in
+                        pass_decl.images().iter().filter(|&&(r, _)| r == resource)
+                    {
+                        only_attachment = false;
+
+                        match image_use {
+                            ImageUse::Sampled => usage.sampled = true,
+                            ImageUse::StorageRead | ImageUse::StorageWrite => usage.storage = true,
+                            ImageUse::TransferSrc => usage.transfer_src = true,
+                            ImageUse::TransferDst => usage.transfer_dst = true,
+                        }
+                    }
+                }
+
+                // Images whose content never leaves a single render pass don't need memory.
+                usage.transient_attachment =
+                    !resource_decl.external && only_attachment && attachment_groups.len() == 1;
+
+                ResourceNode {
+                    format: resource_decl.format,
+                    samples: resource_decl.samples,
+                    external: resource_decl.external,
+                    usage,
+                    lifetime: used_groups
+                        .iter()
+                        .next()
+                        .map(|&first| (first, *used_groups.iter().next_back().unwrap())),
+                }
+            })
+            .collect();
+
+        let pass_nodes = passes
+            .into_iter()
+            .zip(subpasses)
+            .map(|(pass_decl, subpass)| PassNode {
+                name: match pass_decl.kind {
+                    PassKind::Graphics(info) => info.name,
+                    PassKind::Other(info) => info.name,
+                },
+                callback: pass_decl.callback,
+                subpass,
+            })
+            .collect();
+
+        Ok(RenderGraph {
+            device,
+            transient_images: None,
+            timing: None,
+            resources: resource_nodes,
+            passes: pass_nodes,
+            steps,
+        })
+    }
+}
+
+// Orders the passes so that every pass comes after the passes it depends on, and groups
+// consecutive graphics passes that can share a render pass.
+fn order_passes(passes: &[PassDecl], accesses: &[Vec<Access>], num_resources: usize) -> Vec<Group> {
+    let mut successors: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); passes.len()];
+    let mut num_predecessors = vec![0; passes.len()];
+
+    {
+        let mut last_writer: Vec<Option<usize>> = vec![None; num_resources];
+        let mut readers_since_write: Vec<Vec<usize>> = vec![Vec::new(); num_resources];
+        let mut add_edge = |from: usize, to: usize| {
+            if from != to && successors[from].insert(to) {
+                num_predecessors[to] += 1;
+            }
+        };
+
+        for (pass, pass_accesses) in accesses.iter().enumerate() {
+            for access in pass_accesses {
+                let resource = access.resource.0 as usize;
+
+                if let Some(writer) = last_writer[resource] {
+                    add_edge(writer, pass);
+                }
+
+                if access.write {
+                    for &reader in &readers_since_write[resource] {
+                        add_edge(reader, pass);
+                    }
+                }
+            }
+
+            for access in pass_accesses {
+                let resource = access.resource.0 as usize;
+
+                if access.write {
+                    last_writer[resource] = Some(pass);
+                    readers_since_write[resource].clear();
+                } else if access.read {
+                    readers_since_write[resource].push(pass);
+                }
+            }
+        }
+    }
+
+    let mut ready: BTreeSet<usize> = (0..passes.len())
+        .filter(|&pass| num_predecessors[pass] == 0)
+        .collect();
+    let mut groups = Vec::new();
+    let mut current_group: Option<Vec<usize>> = None;
+
+    while !ready.is_empty() {
+        let merged = current_group.as_ref().and_then(|group_passes| {
+            ready
+                .iter()
+                .copied()
+                .find(|&pass| can_merge(passes, accesses, group_passes, pass))
+        });
+
+        let pass = match merged {
+            Some(pass) => {
+                current_group.as_mut().unwrap().push(pass);
+                pass
+            }
+            None => {
+                if let Some(group_passes) = current_group.take() {
+                    groups.push(Group::Graphics(group_passes));
+                }
+
+                let pass = *ready.iter().next().unwrap();
+
+                match passes[pass].kind {
+                    PassKind::Graphics(_) => current_group = Some(vec![pass]),
+                    PassKind::Other(_) => groups.push(Group::Other(pass)),
+                }
+
+                pass
+            }
+        };
+
+        ready.remove(&pass);
+
+        for &successor in &successors[pass] {
+            num_predecessors[successor] -= 1;
+
+            if num_predecessors[successor] == 0 {
+                ready.insert(successor);
+            }
+        }
+    }
+
+    if let Some(group_passes) = current_group.take() {
+        groups.push(Group::Graphics(group_passes));
+    }
+
+    groups
+}
+
+// Returns whether `pass` can be added as the next subpass of the render pass of `group_passes`.
+fn can_merge(
+    passes: &[PassDecl],
+    accesses: &[Vec<Access>],
+    group_passes: &[usize],
+    pass: usize,
+) -> bool {
+    if !matches!(passes[pass].kind, PassKind::Graphics(_)) {
+        return false;
+    }
+
+    // An image can't be used both as an attachment and in another way within a render pass, and
+    // non-attachment writes can't be synchronized between subpasses.
+    group_passes.iter().all(|&group_pass| {
+        accesses[pass].iter().all(|access| {
+            accesses[group_pass]
+                .iter()
+                .filter(|other| other.resource == access.resource)
+                .all(|other| {
+                    (access.attachment && other.attachment)
+                        || (!access.attachment
+                            && !other.attachment
+                            && !access.write
+                            && !other.write)
+                })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenderGraphBuilder;
+    use crate::{
+        format::Format,
+        render_graph::{AttachmentLoad, GraphicsPassInfo, ImageResourceInfo, ImageUse, PassInfo},
+    };
+
+    #[test]
+    fn merge_subpasses() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let mut builder = RenderGraphBuilder::new(device);
+        let output = builder.add_external_image(ImageResourceInfo {
+            format: Some(Format::R8G8B8A8_UNORM),
+            ..Default::default()
+        });
+        let gbuffer = builder.add_transient_image(ImageResourceInfo {
+            format: Some(Format::R8G8B8A8_UNORM),
+            ..Default::default()
+        });
+        let geometry = builder.add_graphics_pass(
+            GraphicsPassInfo {
+                color_attachments: vec![(gbuffer, AttachmentLoad::Clear([0.0; 4].into()))],
+                ..Default::default()
+            },
+            |_| (),
+        );
+        let lighting = builder.add_graphics_pass(
+            GraphicsPassInfo {
+                color_attachments: vec![(output, AttachmentLoad::DontCare)],
+                input_attachments: vec![gbuffer],
+                ..Default::default()
+            },
+            |_| (),
+        );
+        let graph = builder.build().unwrap();
+
+        assert_eq!(graph.execution_order(), [geometry, lighting]);
+
+        let geometry_subpass = graph.subpass(geometry).unwrap();
+        let lighting_subpass = graph.subpass(lighting).unwrap();
+        assert_eq!(
+            geometry_subpass.render_pass(),
+            lighting_subpass.render_pass()
+        );
+        assert_eq!(lighting_subpass.index(), 1);
+        assert!(
+            graph.resources[gbuffer.index() as usize]
+                .usage
+                .transient_attachment
+        );
+    }
+
+    #[test]
+    fn sampled_splits_render_passes() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let mut builder = RenderGraphBuilder::new(device);
+        let output = builder.add_external_image(ImageResourceInfo {
+            format: Some(Format::R8G8B8A8_UNORM),
+            ..Default::default()
+        });
+        let shadow = builder.add_transient_image(ImageResourceInfo {
+            format: Some(Format::D16_UNORM),
+            ..Default::default()
+        });
+        let storage = builder.add_transient_image(ImageResourceInfo {
+            format: Some(Format::R8G8B8A8_UNORM),
+            ..Default::default()
+        });
+
+        // Added first, but depends on the passes below.
+        let main = builder.add_graphics_pass(
+            GraphicsPassInfo {
+                color_attachments: vec![(output, AttachmentLoad::DontCare)],
+                ..Default::default()
+            },
+            |_| (),
+        );
+        let shadows = builder.add_graphics_pass(
+            GraphicsPassInfo {
+                depth_stencil_attachment: Some((shadow, AttachmentLoad::Clear(1.0f32.into()))),
+                ..Default::default()
+            },
+            |_| (),
+        );
+        let compute = builder.add_pass(
+            PassInfo {
+                images: vec![(storage, ImageUse::StorageWrite)],
+                ..Default::default()
+            },
+            |_| (),
+        );
+        let composite = builder.add_graphics_pass(
+            GraphicsPassInfo {
+                color_attachments: vec![(output, AttachmentLoad::Load)],
+                images: vec![(shadow, ImageUse::Sampled), (storage, ImageUse::Sampled)],
+                ..Default::default()
+            },
+            |_| (),
+        );
+        let graph = builder.build().unwrap();
+
+        let order = graph.execution_order();
+        let position = |pass| order.iter().position(|&p| p == pass).unwrap();
+        assert!(position(main) < position(composite));
+        assert!(position(shadows) < position(composite));
+        assert!(position(compute) < position(composite));
+
+        assert!(graph.subpass(compute).is_none());
+        assert_ne!(
+            graph.subpass(shadows).unwrap().render_pass(),
+            graph.subpass(composite).unwrap().render_pass()
+        );
+        assert!(graph.resources[shadow.index() as usize].usage.sampled);
+        assert!(
+            !graph.resources[shadow.index() as usize]
+                .usage
+                .transient_attachment
+        );
+    }
+}
@@ -0,0 +1,1004 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Describing a frame as a graph of passes that read and write images.
+//!
+//! A render graph is made of *passes* and *resources*. A resource is a virtual image, that is
+//! either *transient*, in which case the graph allocates it, or *external*, in which case an image
+//! view is provided each time the graph is executed (for example a swapchain image). Each pass
+//! declares how it uses resources, and provides a callback that records its commands.
+//!
+//! When the graph is built with [`RenderGraphBuilder::build`], it:
+//!
+//! - Orders the passes. A pass that uses a resource is always executed after the passes that were
+//!   added before it and write that resource, and before the passes that were added after it and
+//!   write that resource.
+//! - Merges consecutive graphics passes into the subpasses of a single render pass whenever
+//!   possible, so that for example a lighting pass can read a G-buffer through input attachments.
+//! - Creates the render passes, whose attachments are loaded and stored only when needed.
+//!
+//! When the graph is executed with [`RenderGraph::execute`], it allocates the transient images,
+//! begins and ends the render passes, and calls the callbacks of the passes in order. Pipeline
+//! barriers and image layout transitions between the passes are inserted by the
+//! [`AutoCommandBufferBuilder`] that the graph records into, based on the commands that the
+//! callbacks record.
+//!
+//! # Transient images
+//!
+//! A transient image whose content never leaves a single render pass is created with the
+//! `transient_attachment` usage, and is backed by lazily allocated memory when the device has
+//! some. The other transient images are only alive between the first and the last pass that uses
+//! them, so images whose lifetimes don't overlap are bound to the same memory. For example, the
+//! targets of a chain of post-processing passes, where each pass samples the output of the
+//! previous one, only need the memory of two targets. Aliasing barriers are recorded where the
+//! memory changes hands. The memory that this saves is reported by
+//! [`RenderGraph::transient_memory_usage`].
+//!
+//! # Timing
+//!
+//! After [`RenderGraph::enable_timing`] is called, the graph writes a timestamp query before and
+//! after each pass when it is executed. Once the device has finished executing the commands of an
+//! execution, the time spent in each pass can be retrieved by the name of the pass with
+//! [`RenderGraph::timings`]. The results are read without waiting, when the graph is executed
+//! again, so they lag behind the executions that are being recorded by up to the number of frames
+//! in flight.
+//!
+//! # Example
+//!
+//! ```
+//! use vulkano::{
+//!     format::Format,
+//!     render_graph::{AttachmentLoad, GraphicsPassInfo, ImageResourceInfo, RenderGraphBuilder},
+//! };
+//!
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! # let swapchain_format: Format = return;
+//! let mut builder = RenderGraphBuilder::new(device.clone());
+//!
+//! let final_color = builder.add_external_image(ImageResourceInfo {
+//!     format: Some(swapchain_format),
+//!     ..Default::default()
+//! });
+//! let diffuse = builder.add_transient_image(ImageResourceInfo {
+//!     format: Some(Format::A2B10G10R10_UNORM_PACK32),
+//!     ..Default::default()
+//! });
+//! let depth = builder.add_transient_image(ImageResourceInfo {
+//!     format: Some(Format::D16_UNORM),
+//!     ..Default::default()
+//! });
+//!
+//! let geometry_pass = builder.add_graphics_pass(
+//!     GraphicsPassInfo {
+//!         color_attachments: vec![(diffuse, AttachmentLoad::Clear([0.0; 4].into()))],
+//!         depth_stencil_attachment: Some((depth, AttachmentLoad::Clear(1.0f32.into()))),
+//!         ..Default::default()
+//!     },
+//!     |context| {
+//!         // Draw the objects of the scene with `context.builder()`.
+//!     },
+//! );
+//! let lighting_pass = builder.add_graphics_pass(
+//!     GraphicsPassInfo {
+//!         color_attachments: vec![(final_color, AttachmentLoad::Clear([0.0; 4].into()))],
+//!         input_attachments: vec![diffuse, depth],
+//!         ..Default::default()
+//!     },
+//!     |context| {
+//!         // Apply lighting, reading `context.image(diffuse)` as an input attachment.
+//!     },
+//! );
+//!
+//! let graph = builder.build().unwrap();
+//!
+//! // Both passes are subpasses of the same render pass.
+//! let geometry_subpass = graph.subpass(geometry_pass).unwrap();
+//! let lighting_subpass = graph.subpass(lighting_pass).unwrap();
+//! assert_eq!(geometry_subpass.render_pass(), lighting_subpass.render_pass());
+//! ```
+
+pub use self::{
+    builder::RenderGraphBuilder,
+    timing::{FrameTimings, PassTiming},
+};
+use self::{timing::Timing, transient::TransientImages};
+use crate::{
+    command_buffer::{
+        AutoCommandBufferBuilder, AutoCommandBufferBuilderContextError, PrimaryAutoCommandBuffer,
+        RenderPassBeginInfo, RenderPassError, ResetQueryPoolError, SubpassContents,
+        WriteTimestampError,
+    },
+    device::{Device, DeviceOwned},
+    format::{ClearValue, Format},
+    image::{
+        view::ImageViewCreationError, ImageAccess, ImageCreationError, ImageUsage,
+        ImageViewAbstract, SampleCount,
+    },
+    memory::DeviceMemoryAllocationError,
+    query::{GetResultsError, QueryPoolCreationError},
+    render_pass::{
+        Framebuffer, FramebufferCreateInfo, FramebufferCreationError, RenderPass, Subpass,
+    },
+    sync::{MemoryBarrier, PipelineStages},
+    DeviceSize, OomError,
+};
+use std::{error, fmt, sync::Arc};
+
+mod builder;
+mod timing;
+mod transient;
+
+/// Identifies an image resource of a render graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ResourceId(u32);
+
+impl ResourceId {
+    /// Returns the index of the resource, in the order in which the resources were added.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Identifies a pass of a render graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PassId(u32);
+
+impl PassId {
+    /// Returns the index of the pass, in the order in which the passes were added.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Parameters of an image resource of a render graph.
+#[derive(Clone, Debug)]
+pub struct ImageResourceInfo {
+    /// The format of the image.
+    ///
+    /// The default value is `None`, which must be overridden.
+    pub format: Option<Format>,
+
+    /// The number of samples per pixel of the image.
+    ///
+    /// The default value is [`SampleCount::Sample1`].
+    pub samples: SampleCount,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for ImageResourceInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            format: None,
+            samples: SampleCount::Sample1,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// What happens to the content of an attachment at the start of a graphics pass.
+#[derive(Clone, Copy, Debug)]
+pub enum AttachmentLoad {
+    /// The previous content of the image is kept. The pass reads the image.
+    Load,
+
+    /// The image is cleared with the given value.
+    Clear(ClearValue),
+
+    /// The previous content of the image is discarded.
+    DontCare,
+}
+
+/// How a pass uses an image, other than as an attachment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ImageUse {
+    /// The image is read through a sampler or as a sampled image in a shader.
+    Sampled,
+
+    /// The image is read as a storage image in a shader.
+    StorageRead,
+
+    /// The image is written, and possibly read, as a storage image in a shader.
+    StorageWrite,
+
+    /// The image is the source of a transfer command.
+    TransferSrc,
+
+    /// The image is the destination of a transfer command.
+    TransferDst,
+}
+
+impl ImageUse {
+    /// Returns whether the content of the image is modified.
+    #[inline]
+    pub fn is_write(&self) -> bool {
+        matches!(self, Self::StorageWrite | Self::TransferDst)
+    }
+}
+
+/// Parameters of a graphics pass of a render graph.
+#[derive(Clone, Debug)]
+pub struct GraphicsPassInfo {
+    /// The name of the pass, that identifies it in the [timings](RenderGraph::timings) of the
+    /// graph.
+    ///
+    /// The default value is empty.
+    pub name: String,
+
+    /// The images that the pass draws to, and what happens to their content at the start of the
+    /// pass. The index in the list is the location of the output in the fragment shader.
+    ///
+    /// The default value is empty.
+    pub color_attachments: Vec<(ResourceId, AttachmentLoad)>,
+
+    /// The depth/stencil image of the pass, and what happens to its content at the start of the
+    /// pass.
+    ///
+    /// The default value is `None`.
+    pub depth_stencil_attachment: Option<(ResourceId, AttachmentLoad)>,
+
+    /// The images that the pass reads as input attachments. The index in the list is the input
+    /// attachment index in the fragment shader.
+    ///
+    /// The default value is empty.
+    pub input_attachments: Vec<ResourceId>,
+
+    /// The other images that the pass uses, for example images sampled by its shaders.
+    ///
+    /// An image that is used this way can't be an attachment of a pass that is merged in the same
+    /// render pass.
+    ///
+    /// The default value is empty.
+    pub images: Vec<(ResourceId, ImageUse)>,
+
+    /// How the callback of the pass records its commands.
+    ///
+    /// The default value is [`SubpassContents::Inline`].
+    pub contents: SubpassContents,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for GraphicsPassInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            color_attachments: Vec::new(),
+            depth_stencil_attachment: None,
+            input_attachments: Vec::new(),
+            images: Vec::new(),
+            contents: SubpassContents::Inline,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Parameters of a pass of a render graph that is recorded outside of a render pass, such as a
+/// compute or transfer pass.
+#[derive(Clone, Debug)]
+pub struct PassInfo {
+    /// The name of the pass, that identifies it in the [timings](RenderGraph::timings) of the
+    /// graph.
+    ///
+    /// The default value is empty.
+    pub name: String,
+
+    /// The images that the pass uses.
+    ///
+    /// The default value is empty.
+    pub images: Vec<(ResourceId, ImageUse)>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for PassInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            images: Vec::new(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// The callback that records the commands of a pass.
+pub type PassCallback = Box<dyn FnMut(&mut PassContext<'_>) + Send>;
+
+/// Provided to the callback of a pass when it is executed.
+pub struct PassContext<'a> {
+    builder: &'a mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+    subpass: Option<Subpass>,
+    images: &'a [Option<Arc<dyn ImageViewAbstract>>],
+    extent: [u32; 2],
+}
+
+impl<'a> PassContext<'a> {
+    /// Returns the command buffer builder to record the commands of the pass with.
+    ///
+    /// For graphics passes, the subpass of the pass has already begun.
+    #[inline]
+    pub fn builder(&mut self) -> &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer> {
+        self.builder
+    }
+
+    /// Returns the subpass that the pass is executed in, or `None` if the pass is not a graphics
+    /// pass.
+    #[inline]
+    pub fn subpass(&self) -> Option<&Subpass> {
+        self.subpass.as_ref()
+    }
+
+    /// Returns the image view that is bound to `resource` for this execution of the graph.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `resource` is a transient resource that is not used by any pass.
+    #[inline]
+    pub fn image(&self, resource: ResourceId) -> &Arc<dyn ImageViewAbstract> {
+        self.images[resource.0 as usize]
+            .as_ref()
+            .expect("the resource is not used by any pass")
+    }
+
+    /// Returns the width and height of the images of the graph.
+    #[inline]
+    pub fn extent(&self) -> [u32; 2] {
+        self.extent
+    }
+}
+
+/// The memory that a render graph has allocated for its transient images.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransientMemoryUsage {
+    /// The total size in bytes that the transient images would need if each of them had its own
+    /// memory.
+    pub required_size: DeviceSize,
+
+    /// The total size in bytes of the memory that is actually allocated, with images that are not
+    /// alive at the same time sharing memory.
+    pub allocated_size: DeviceSize,
+
+    /// The number of memory allocations that the transient images are bound to.
+    pub num_allocations: u32,
+}
+
+impl TransientMemoryUsage {
+    /// Returns the size in bytes of the memory that is saved by aliasing the transient images.
+    #[inline]
+    pub fn saved_size(&self) -> DeviceSize {
+        self.required_size - self.allocated_size
+    }
+}
+
+/// A render graph that has been built, and can be executed.
+///
+/// See the [module-level documentation](self) for more information.
+pub struct RenderGraph {
+    device: Arc<Device>,
+    resources: Vec<ResourceNode>,
+    passes: Vec<PassNode>,
+    steps: Vec<Step>,
+    transient_images: Option<TransientImages>,
+    timing: Option<Timing>,
+}
+
+struct ResourceNode {
+    format: Format,
+    samples: SampleCount,
+    external: bool,
+    usage: ImageUsage,
+    // The first and last steps that use the resource, or `None` if no pass uses it.
+    lifetime: Option<(usize, usize)>,
+}
+
+struct PassNode {
+    name: String,
+    callback: PassCallback,
+    subpass: Option<Subpass>,
+}
+
+// A unit of execution of a graph.
+enum Step {
+    RenderPass {
+        render_pass: Arc<RenderPass>,
+        // The resource bound to each attachment of the render pass.
+        attachments: Vec<ResourceId>,
+        clear_values: Vec<Option<ClearValue>>,
+        // The passes that are executed in each subpass, and their contents.
+        passes: Vec<(PassId, SubpassContents)>,
+    },
+    Pass(PassId),
+}
+
+impl RenderGraph {
+    /// Returns the subpass that `pass` is executed in, or `None` if it is not a graphics pass.
+    ///
+    /// Graphics pipelines that are used by the pass must be created for this subpass.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `pass` is not a pass of this graph.
+    #[inline]
+    pub fn subpass(&self, pass: PassId) -> Option<Subpass> {
+        self.passes[pass.0 as usize].subpass.clone()
+    }
+
+    /// Returns the passes of the graph in the order in which they are executed.
+    pub fn execution_order(&self) -> Vec<PassId> {
+        let mut order = Vec::with_capacity(self.passes.len());
+
+        for step in &self.steps {
+            match step {
+                Step::RenderPass { passes, .. } => {
+                    order.extend(passes.iter().map(|&(pass, _)| pass))
+                }
+                &Step::Pass(pass) => order.push(pass),
+            }
+        }
+
+        order
+    }
+
+    /// Replaces the callback of `pass`.
+    ///
+    /// This is useful to provide callbacks that use graphics pipelines, which can only be created
+    /// once the graph has been built.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `pass` is not a pass of this graph.
+    #[inline]
+    pub fn set_callback<F>(&mut self, pass: PassId, callback: F)
+    where
+        F: FnMut(&mut PassContext<'_>) + Send + 'static,
+    {
+        self.passes[pass.0 as usize].callback = Box::new(callback);
+    }
+
+    /// Returns the memory that is allocated for the transient images, or `None` if the graph has
+    /// not been executed yet.
+    ///
+    /// Transient images that are backed by lazily allocated memory are not included.
+    #[inline]
+    pub fn transient_memory_usage(&self) -> Option<TransientMemoryUsage> {
+        self.transient_images
+            .as_ref()
+            .map(|transient_images| transient_images.memory_usage)
+    }
+
+    /// Enables the timing of the passes of the graph, replacing the timings that were measured
+    /// so far.
+    ///
+    /// `frames_in_flight` is the number of executions of the graph that can be pending on the
+    /// device at the same time. Each of them gets its own timestamp queries. If the graph is
+    /// executed again before the device has finished executing the commands of the execution
+    /// that used the same queries, the timings of that execution are lost.
+    ///
+    /// Graphics passes whose contents are [`SubpassContents::SecondaryCommandBuffers`] are not
+    /// timed, as commands can't be recorded in their subpass.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `frames_in_flight` is `0`.
+    pub fn enable_timing(&mut self, frames_in_flight: u32) -> Result<(), QueryPoolCreationError> {
+        assert!(frames_in_flight != 0);
+
+        let timed_passes = self
+            .steps
+            .iter()
+            .flat_map(|step| match step {
+                Step::RenderPass { passes, .. } => passes
+                    .iter()
+                    .filter(|&&(_, contents)| contents == SubpassContents::Inline)
+                    .map(|&(pass, _)| pass)
+                    .collect(),
+                &Step::Pass(pass) => vec![pass],
+            })
+            .collect();
+        self.timing = Some(Timing::new(&self.device, timed_passes, frames_in_flight)?);
+
+        Ok(())
+    }
+
+    /// Disables the timing of the passes of the graph.
+    #[inline]
+    pub fn disable_timing(&mut self) {
+        self.timing = None;
+    }
+
+    /// Returns the timings of the most recent execution of the graph whose commands the device
+    /// has finished executing, or `None` if timing is disabled or no such execution has been
+    /// observed yet.
+    ///
+    /// The timings are updated when the graph is executed.
+    #[inline]
+    pub fn timings(&self) -> Option<&FrameTimings> {
+        self.timing
+            .as_ref()
+            .and_then(|timing| timing.last_timings.as_ref())
+    }
+
+    /// Records the passes of the graph into `builder`.
+    ///
+    /// `external_images` provides an image view for each external resource of the graph. All the
+    /// images of the graph, including the transient images that the graph allocates, have a width
+    /// and height of `extent`. The transient images are reallocated when `extent` changes.
+    ///
+    /// Transient images share memory, so the content of a transient image is undefined at the
+    /// start of each execution. The command buffers that the graph is recorded into must be
+    /// executed on the same queue, so that the aliasing barriers that the graph records order
+    /// the accesses of one execution before the accesses of the next.
+    pub fn execute(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+        extent: [u32; 2],
+        external_images: &[(ResourceId, Arc<dyn ImageViewAbstract>)],
+    ) -> Result<(), RenderGraphExecuteError> {
+        let images = self.resolve_images(extent, external_images)?;
+        let aliasing_barriers = &self.transient_images.as_ref().unwrap().aliasing_barriers;
+
+        if let Some(timing) = &mut self.timing {
+            timing.begin_frame(builder, &self.passes)?;
+        }
+
+        for (step_index, step) in self.steps.iter().enumerate() {
+            if aliasing_barriers[step_index] {
+                let stages = PipelineStages {
+                    all_commands: true,
+                    ..PipelineStages::none()
+                };
+
+                unsafe {
+                    builder.aliasing_barrier(MemoryBarrier {
+                        source_stages: stages,
+                        source_access: stages.supported_access(),
+                        destination_stages: stages,
+                        destination_access: stages.supported_access(),
+                        ..Default::default()
+                    })?;
+                }
+            }
+
+            match step {
+                Step::RenderPass {
+                    render_pass,
+                    attachments,
+                    clear_values,
+                    passes,
+                } => {
+                    let framebuffer = Framebuffer::new(
+                        render_pass.clone(),
+                        FramebufferCreateInfo {
+                            attachments: attachments
+                                .iter()
+                                .map(|resource| images[resource.0 as usize].clone().unwrap())
+                                .collect(),
+                            ..Default::default()
+                        },
+                    )?;
+
+                    for (subpass_index, &(pass_id, contents)) in passes.iter().enumerate() {
+                        if subpass_index == 0 {
+                            builder.begin_render_pass(
+                                RenderPassBeginInfo {
+                                    clear_values: clear_values.clone(),
+                                    ..RenderPassBeginInfo::framebuffer(framebuffer.clone())
+                                },
+                                contents,
+                            )?;
+                        } else {
+                            builder.next_subpass(contents)?;
+                        }
+
+                        if let Some(timing) = &self.timing {
+                            timing.write_timestamp(builder, pass_id, false)?;
+                        }
+
+                        let pass = &mut self.passes[pass_id.0 as usize];
+                        (pass.callback)(&mut PassContext {
+                            builder: &mut *builder,
+                            subpass: pass.subpass.clone(),
+                            images: &images,
+                            extent,
+                        });
+
+                        if let Some(timing) = &self.timing {
+                            timing.write_timestamp(builder, pass_id, true)?;
+                        }
+                    }
+
+                    builder.end_render_pass()?;
+                }
+                &Step::Pass(pass_id) => {
+                    if let Some(timing) = &self.timing {
+                        timing.write_timestamp(builder, pass_id, false)?;
+                    }
+
+                    let pass = &mut self.passes[pass_id.0 as usize];
+                    (pass.callback)(&mut PassContext {
+                        builder: &mut *builder,
+                        subpass: None,
+                        images: &images,
+                        extent,
+                    });
+
+                    if let Some(timing) = &self.timing {
+                        timing.write_timestamp(builder, pass_id, true)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Returns the image view of each resource, allocating the transient images if needed.
+    fn resolve_images(
+        &mut self,
+        extent: [u32; 2],
+        external_images: &[(ResourceId, Arc<dyn ImageViewAbstract>)],
+    ) -> Result<Vec<Option<Arc<dyn ImageViewAbstract>>>, RenderGraphExecuteError> {
+        let is_outdated = self
+            .transient_images
+            .as_ref()
+            .map_or(true, |transient_images| transient_images.extent != extent);
+
+        if is_outdated {
+            // Free the previous images first, so that their memory can be reused.
+            self.transient_images = None;
+            self.transient_images = Some(TransientImages::new(
+                &self.device,
+                &self.resources,
+                self.steps.len(),
+                extent,
+            )?);
+        }
+
+        let mut images = self.transient_images.as_ref().unwrap().images.clone();
+
+        for (index, resource_node) in self.resources.iter().enumerate() {
+            let resource = ResourceId(index as u32);
+
+            if resource_node.external {
+                let image_view = external_images
+                    .iter()
+                    .find(|(id, _)| *id == resource)
+                    .map(|(_, image_view)| image_view.clone())
+                    .ok_or(RenderGraphExecuteError::ExternalImageMissing { resource })?;
+
+                if image_view.format() != Some(resource_node.format) {
+                    return Err(RenderGraphExecuteError::ExternalImageFormatMismatch { resource });
+                }
+
+                if image_view.image().dimensions().width_height() != extent {
+                    return Err(RenderGraphExecuteError::ExternalImageExtentMismatch { resource });
+                }
+
+                images[index] = Some(image_view);
+            }
+        }
+
+        Ok(images)
+    }
+}
+
+unsafe impl DeviceOwned for RenderGraph {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl fmt::Debug for RenderGraph {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("RenderGraph")
+            .field("device", &self.device)
+            .field("num_resources", &self.resources.len())
+            .field("num_passes", &self.passes.len())
+            .finish()
+    }
+}
+
+/// Error that can happen when executing a render graph.
+#[derive(Clone, Debug)]
+pub enum RenderGraphExecuteError {
+    AutoCommandBufferBuilderContextError(AutoCommandBufferBuilderContextError),
+    DeviceMemoryAllocationError(DeviceMemoryAllocationError),
+    ImageCreationError(ImageCreationError),
+    ImageViewCreationError(ImageViewCreationError),
+    FramebufferCreationError(FramebufferCreationError),
+    RenderPassError(RenderPassError),
+    ResetQueryPoolError(ResetQueryPoolError),
+    WriteTimestampError(WriteTimestampError),
+    GetResultsError(GetResultsError),
+
+    /// None of the memory types that a transient image can be bound to are suitable.
+    NoSuitableMemoryType,
+
+    /// No image view was provided for an external resource.
+    ExternalImageMissing {
+        resource: ResourceId,
+    },
+
+    /// The image view provided for an external resource does not have the format of the
+    /// resource.
+    ExternalImageFormatMismatch {
+        resource: ResourceId,
+    },
+
+    /// The image view provided for an external resource does not have the extent that the graph
+    /// is executed with.
+    ExternalImageExtentMismatch {
+        resource: ResourceId,
+    },
+
+    /// Timing is enabled, but the queue family of the command buffer doesn't support timestamps.
+    TimestampsNotSupported,
+}
+
+impl error::Error for RenderGraphExecuteError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::AutoCommandBufferBuilderContextError(err) => Some(err),
+            Self::DeviceMemoryAllocationError(err) => Some(err),
+            Self::ImageCreationError(err) => Some(err),
+            Self::ImageViewCreationError(err) => Some(err),
+            Self::FramebufferCreationError(err) => Some(err),
+            Self::RenderPassError(err) => Some(err),
+            Self::ResetQueryPoolError(err) => Some(err),
+            Self::WriteTimestampError(err) => Some(err),
+            Self::GetResultsError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RenderGraphExecuteError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::AutoCommandBufferBuilderContextError(_) => {
+                write!(f, "recording an aliasing barrier failed")
+            }
+            Self::DeviceMemoryAllocationError(_) => {
+                write!(f, "allocating memory for the transient images failed")
+            }
+            Self::ImageCreationError(_) => write!(f, "creating a transient image failed"),
+            Self::ImageViewCreationError(_) => {
+                write!(f, "creating a view of a transient image failed")
+            }
+            Self::FramebufferCreationError(_) => write!(f, "creating a framebuffer failed"),
+            Self::RenderPassError(_) => write!(f, "recording a render pass command failed"),
+            Self::ResetQueryPoolError(_) => {
+                write!(f, "resetting the timestamp queries of the passes failed")
+            }
+            Self::WriteTimestampError(_) => write!(f, "writing a timestamp of a pass failed"),
+            Self::GetResultsError(_) => {
+                write!(f, "reading the timestamp queries of the passes failed")
+            }
+            Self::NoSuitableMemoryType => write!(
+                f,
+                "none of the memory types that a transient image can be bound to are suitable",
+            ),
+            Self::ExternalImageMissing { resource } => write!(
+                f,
+                "no image view was provided for external resource {}",
+                resource.0,
+            ),
+            Self::ExternalImageFormatMismatch { resource } => write!(
+                f,
+                "the image view provided for external resource {} does not have the format of the resource",
+                resource.0,
+            ),
+            Self::ExternalImageExtentMismatch { resource } => write!(
+                f,
+                "the image view provided for external resource {} does not have the extent that the graph is executed with",
+                resource.0,
+            ),
+            Self::TimestampsNotSupported => write!(
+                f,
+                "timing is enabled, but the queue family of the command buffer doesn't support timestamps",
+            ),
+        }
+    }
+}
+
+impl From<AutoCommandBufferBuilderContextError> for RenderGraphExecuteError {
+    #[inline]
+    fn from(err: AutoCommandBufferBuilderContextError) -> Self {
+        Self::AutoCommandBufferBuilderContextError(err)
+    }
+}
+
+impl From<DeviceMemoryAllocationError> for RenderGraphExecuteError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocationError) -> Self {
+        Self::DeviceMemoryAllocationError(err)
+    }
+}
+
+impl From<OomError> for RenderGraphExecuteError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::DeviceMemoryAllocationError(err.into())
+    }
+}
+
+impl From<ImageCreationError> for RenderGraphExecuteError {
+    #[inline]
+    fn from(err: ImageCreationError) -> Self {
+        Self::ImageCreationError(err)
+    }
+}
+
+impl From<ImageViewCreationError> for RenderGraphExecuteError {
+    #[inline]
+    fn from(err: ImageViewCreationError) -> Self {
+        Self::ImageViewCreationError(err)
+    }
+}
+
+impl From<FramebufferCreationError> for RenderGraphExecuteError {
+    #[inline]
+    fn from(err: FramebufferCreationError) -> Self {
+        Self::FramebufferCreationError(err)
+    }
+}
+
+impl From<RenderPassError> for RenderGraphExecuteError {
+    #[inline]
+    fn from(err: RenderPassError) -> Self {
+        Self::RenderPassError(err)
+    }
+}
+
+impl From<ResetQueryPoolError> for RenderGraphExecuteError {
+    #[inline]
+    fn from(err: ResetQueryPoolError) -> Self {
+        Self::ResetQueryPoolError(err)
+    }
+}
+
+impl From<WriteTimestampError> for RenderGraphExecuteError {
+    #[inline]
+    fn from(err: WriteTimestampError) -> Self {
+        Self::WriteTimestampError(err)
+    }
+}
+
+impl From<GetResultsError> for RenderGraphExecuteError {
+    #[inline]
+    fn from(err: GetResultsError) -> Self {
+        Self::GetResultsError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AttachmentLoad, GraphicsPassInfo, ImageResourceInfo, ImageUse, PassInfo, RenderGraphBuilder,
+    };
+    use crate::{
+        command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
+        format::Format,
+        sync::{now, GpuFuture},
+    };
+
+    #[test]
+    fn alias_transient_images() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        // A chain of passes that each sample the output of the previous one.
+        let mut builder = RenderGraphBuilder::new(device.clone());
+        let mut previous = None;
+
+        for _ in 0..4 {
+            let target = builder.add_transient_image(ImageResourceInfo {
+                format: Some(Format::R16G16B16A16_SFLOAT),
+                ..Default::default()
+            });
+            builder.add_graphics_pass(
+                GraphicsPassInfo {
+                    color_attachments: vec![(target, AttachmentLoad::DontCare)],
+                    images: previous
+                        .map(|previous| vec![(previous, ImageUse::Sampled)])
+                        .unwrap_or_default(),
+                    ..Default::default()
+                },
+                |_| (),
+            );
+            previous = Some(target);
+        }
+
+        let mut graph = builder.build().unwrap();
+        assert!(graph.transient_memory_usage().is_none());
+
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        graph.execute(&mut cbb, [64, 64], &[]).unwrap();
+
+        // The last target is only used as an attachment, so it is not aliased. The first and
+        // third targets are never alive at the same time.
+        let usage = graph.transient_memory_usage().unwrap();
+        assert_eq!(usage.num_allocations, 2);
+        assert!(usage.saved_size() > 0);
+    }
+
+    #[test]
+    fn pass_timings() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        if queue.family().timestamp_valid_bits().is_none() {
+            return;
+        }
+
+        let mut builder = RenderGraphBuilder::new(device.clone());
+        let target = builder.add_transient_image(ImageResourceInfo {
+            format: Some(Format::R8G8B8A8_UNORM),
+            ..Default::default()
+        });
+        builder.add_graphics_pass(
+            GraphicsPassInfo {
+                name: "clear".into(),
+                color_attachments: vec![(target, AttachmentLoad::Clear([0.0; 4].into()))],
+                ..Default::default()
+            },
+            |_| (),
+        );
+        builder.add_pass(
+            PassInfo {
+                name: "copy".into(),
+                images: vec![(target, ImageUse::TransferSrc)],
+                ..Default::default()
+            },
+            |_| (),
+        );
+
+        let mut graph = builder.build().unwrap();
+        graph.enable_timing(1).unwrap();
+
+        for _ in 0..2 {
+            let mut cbb = AutoCommandBufferBuilder::primary(
+                device.clone(),
+                queue.family(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+            graph.execute(&mut cbb, [64, 64], &[]).unwrap();
+            let cb = cbb.build().unwrap();
+
+            now(device.clone())
+                .then_execute(queue.clone(), cb)
+                .unwrap()
+                .then_signal_fence_and_flush()
+                .unwrap()
+                .wait(None)
+                .unwrap();
+        }
+
+        // The results of the first execution are read when the graph is executed again.
+        let timings = graph.timings().unwrap();
+        assert_eq!(timings.frame(), 0);
+        assert_eq!(timings.passes().len(), 2);
+        assert!(timings.get("clear").is_some());
+        assert!(timings.get("copy").is_some());
+        assert!(timings.get("present").is_none());
+    }
+}
@@ -0,0 +1,258 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{PassId, PassNode, RenderGraphExecuteError};
+use crate::{
+    command_buffer::{AutoCommandBufferBuilder, PrimaryAutoCommandBuffer},
+    device::Device,
+    query::{QueryPool, QueryPoolCreateInfo, QueryPoolCreationError, QueryResultFlags, QueryType},
+    sync::PipelineStage,
+};
+use std::{sync::Arc, time::Duration};
+
+/// The time that the device spent executing the passes of a render graph, for one execution of
+/// the graph.
+#[derive(Clone, Debug)]
+pub struct FrameTimings {
+    frame: u64,
+    passes: Vec<PassTiming>,
+}
+
+impl FrameTimings {
+    /// Returns the number of executions of the graph that happened before the one that these
+    /// timings were measured for, since timing was enabled.
+    #[inline]
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Returns the timings of the passes, in the order in which they were executed.
+    #[inline]
+    pub fn passes(&self) -> &[PassTiming] {
+        &self.passes
+    }
+
+    /// Returns the total duration of the passes named `name`, or `None` if no pass with this name
+    /// was timed.
+    pub fn get(&self, name: &str) -> Option<Duration> {
+        self.passes
+            .iter()
+            .filter(|timing| timing.name == name)
+            .map(|timing| timing.duration)
+            .reduce(|a, b| a + b)
+    }
+
+    /// Returns the total duration of the passes that were timed.
+    #[inline]
+    pub fn total(&self) -> Duration {
+        self.passes.iter().map(|timing| timing.duration).sum()
+    }
+}
+
+/// The time that the device spent executing a pass of a render graph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PassTiming {
+    /// The pass that was timed.
+    pub pass: PassId,
+
+    /// The name that was given to the pass when it was added to the graph.
+    pub name: String,
+
+    /// The time between the end of the work of the previous commands and the end of the work
+    /// of the pass.
+    pub duration: Duration,
+}
+
+// The timestamp queries that a render graph writes around its passes.
+pub(super) struct Timing {
+    // `None` if no pass can be timed.
+    query_pool: Option<Arc<QueryPool>>,
+    // The index of the first of the two queries of each pass in the queries of a frame, in
+    // execution order. Passes that can't be timed are not included.
+    timed_passes: Vec<(PassId, u32)>,
+    frames_in_flight: u32,
+    timestamp_period: f64,
+    // The number of executions of the graph since timing was enabled.
+    next_frame: u64,
+    // For each slot of queries, the frame whose results have not been read yet and the valid
+    // bits of the timestamps.
+    pending: Vec<Option<(u64, u32)>>,
+    pub(super) last_timings: Option<FrameTimings>,
+}
+
+impl Timing {
+    pub(super) fn new(
+        device: &Arc<Device>,
+        timed_passes: Vec<PassId>,
+        frames_in_flight: u32,
+    ) -> Result<Self, QueryPoolCreationError> {
+        let queries_per_frame = timed_passes.len() as u32 * 2;
+        let query_pool = if queries_per_frame != 0 {
+            Some(QueryPool::new(
+                device.clone(),
+                QueryPoolCreateInfo {
+                    query_count: queries_per_frame * frames_in_flight,
+                    ..QueryPoolCreateInfo::query_type(QueryType::Timestamp)
+                },
+            )?)
+        } else {
+            None
+        };
+
+        Ok(Timing {
+            query_pool,
+            timed_passes: timed_passes
+                .into_iter()
+                .enumerate()
+                .map(|(index, pass)| (pass, index as u32 * 2))
+                .collect(),
+            frames_in_flight,
+            timestamp_period: device.physical_device().properties().timestamp_period as f64,
+            next_frame: 0,
+            pending: vec![None; frames_in_flight as usize],
+            last_timings: None,
+        })
+    }
+
+    // Reads the results of the previous frames that have completed, and resets the queries of
+    // the frame that is being recorded.
+    pub(super) fn begin_frame(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+        passes: &[PassNode],
+    ) -> Result<(), RenderGraphExecuteError> {
+        self.read_results(passes)?;
+
+        let frame = self.next_frame;
+        self.next_frame += 1;
+        let slot = (frame % self.frames_in_flight as u64) as usize;
+
+        // The results of a frame that has not completed yet are lost.
+        self.pending[slot] = None;
+
+        if let Some(query_pool) = &self.query_pool {
+            let valid_bits = builder
+                .queue_family()
+                .timestamp_valid_bits()
+                .ok_or(RenderGraphExecuteError::TimestampsNotSupported)?;
+
+            unsafe {
+                builder.reset_query_pool(query_pool.clone(), self.slot_queries(slot))?;
+            }
+
+            self.pending[slot] = Some((frame, valid_bits));
+        } else {
+            self.last_timings = Some(FrameTimings {
+                frame,
+                passes: Vec::new(),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Writes the timestamp before (if `end` is false) or after (if `end` is true) the commands
+    // of `pass`, if the pass is timed.
+    pub(super) fn write_timestamp(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+        pass: PassId,
+        end: bool,
+    ) -> Result<(), RenderGraphExecuteError> {
+        let query_pool = match &self.query_pool {
+            Some(query_pool) => query_pool,
+            None => return Ok(()),
+        };
+        let first_query = match self.timed_passes.iter().find(|&&(p, _)| p == pass) {
+            Some(&(_, first_query)) => first_query,
+            None => return Ok(()),
+        };
+        let slot = ((self.next_frame - 1) % self.frames_in_flight as u64) as usize;
+        let query = self.slot_queries(slot).start + first_query + end as u32;
+
+        unsafe {
+            // Both timestamps are written once the previous commands have completed, so that
+            // the duration of a pass doesn't include the work of the passes before it.
+            builder.write_timestamp(query_pool.clone(), query, PipelineStage::BottomOfPipe)?;
+        }
+
+        Ok(())
+    }
+
+    // Stores the timings of the most recent frame whose queries are all available.
+    fn read_results(&mut self, passes: &[PassNode]) -> Result<(), RenderGraphExecuteError> {
+        let query_pool = match &self.query_pool {
+            Some(query_pool) => query_pool,
+            None => return Ok(()),
+        };
+        let mut results = vec![0u64; self.timed_passes.len() * 2];
+
+        for slot in 0..self.frames_in_flight as usize {
+            let (frame, valid_bits) = match self.pending[slot] {
+                Some(pending) => pending,
+                None => continue,
+            };
+
+            let available = query_pool
+                .queries_range(self.slot_queries(slot))
+                .unwrap()
+                .get_results(&mut results, QueryResultFlags::default())?;
+
+            if !available {
+                continue;
+            }
+
+            self.pending[slot] = None;
+
+            if self
+                .last_timings
+                .as_ref()
+                .map_or(false, |timings| timings.frame > frame)
+            {
+                continue;
+            }
+
+            let mask = if valid_bits >= 64 {
+                u64::MAX
+            } else {
+                (1 << valid_bits) - 1
+            };
+
+            self.last_timings = Some(FrameTimings {
+                frame,
+                passes: self
+                    .timed_passes
+                    .iter()
+                    .map(|&(pass, first_query)| {
+                        let begin = results[first_query as usize];
+                        let end = results[first_query as usize + 1];
+                        let ticks = end.wrapping_sub(begin) & mask;
+
+                        PassTiming {
+                            pass,
+                            name: passes[pass.0 as usize].name.clone(),
+                            duration: Duration::from_nanos(
+                                (ticks as f64 * self.timestamp_period) as u64,
+                            ),
+                        }
+                    })
+                    .collect(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn slot_queries(&self, slot: usize) -> std::ops::Range<u32> {
+        let queries_per_frame = self.timed_passes.len() as u32 * 2;
+        let start = slot as u32 * queries_per_frame;
+
+        start..start + queries_per_frame
+    }
+}
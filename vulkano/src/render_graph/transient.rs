@@ -0,0 +1,274 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{RenderGraphExecuteError, ResourceNode, TransientMemoryUsage};
+use crate::{
+    device::{Device, DeviceOwned},
+    image::{
+        sys::{UnsafeImage, UnsafeImageCreateInfo},
+        view::ImageView,
+        AttachmentImage, AttachmentImageCreateInfo, ImageAccess, ImageDescriptorLayouts,
+        ImageDimensions, ImageInner, ImageLayout, ImageViewAbstract,
+    },
+    memory::{pool::MemoryTypePolicy, DeviceMemory, MemoryAllocateInfo},
+    DeviceSize,
+};
+use std::{
+    cmp::Reverse,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+// The images that a render graph allocates for its transient resources.
+pub(super) struct TransientImages {
+    pub(super) extent: [u32; 2],
+    // The image view of each resource, or `None` if the resource is external or unused.
+    pub(super) images: Vec<Option<Arc<dyn ImageViewAbstract>>>,
+    // Whether an aliasing barrier must be recorded before each step of the graph.
+    pub(super) aliasing_barriers: Vec<bool>,
+    pub(super) memory_usage: TransientMemoryUsage,
+}
+
+impl TransientImages {
+    // Allocates the transient images of `resources`.
+    //
+    // Images whose content never leaves a render pass get their own lazily allocated memory if
+    // possible. The other images are bound to memory that is shared with images whose lifetimes,
+    // expressed in steps of the graph, don't overlap.
+    pub(super) fn new(
+        device: &Arc<Device>,
+        resources: &[ResourceNode],
+        num_steps: usize,
+        extent: [u32; 2],
+    ) -> Result<Self, RenderGraphExecuteError> {
+        let mut images: Vec<Option<Arc<dyn ImageViewAbstract>>> = vec![None; resources.len()];
+        let mut aliased = Vec::new();
+
+        for (index, resource_node) in resources.iter().enumerate() {
+            let lifetime = match resource_node.lifetime {
+                Some(lifetime) if !resource_node.external => lifetime,
+                _ => continue,
+            };
+
+            if resource_node.usage.transient_attachment {
+                let image = AttachmentImage::with_create_info(
+                    device.clone(),
+                    AttachmentImageCreateInfo {
+                        samples: resource_node.samples,
+                        usage: resource_node.usage,
+                        ..AttachmentImageCreateInfo::dimensions_format(extent, resource_node.format)
+                    },
+                )?;
+                images[index] = Some(ImageView::new_default(image)? as Arc<_>);
+            } else {
+                let image = UnsafeImage::new(
+                    device.clone(),
+                    UnsafeImageCreateInfo {
+                        dimensions: ImageDimensions::Dim2d {
+                            width: extent[0],
+                            height: extent[1],
+                            array_layers: 1,
+                        },
+                        format: Some(resource_node.format),
+                        samples: resource_node.samples,
+                        usage: resource_node.usage,
+                        ..Default::default()
+                    },
+                )?;
+                let mem_reqs = image.memory_requirements();
+                aliased.push((index, lifetime, image, mem_reqs));
+            }
+        }
+
+        // Place the largest images first, so that smaller images fill the memory of larger ones
+        // rather than the other way around.
+        aliased.sort_by_key(|(index, _, _, mem_reqs)| (Reverse(mem_reqs.size), *index));
+
+        let policy = MemoryTypePolicy::default();
+        let best_memory_type = |memory_type_bits: u32| {
+            device
+                .physical_device()
+                .memory_types()
+                .filter(|t| memory_type_bits & (1 << t.id()) != 0)
+                .filter_map(|t| policy.rank(t).map(|rank| (rank, t.id())))
+                .min()
+                .map(|(_, id)| id)
+        };
+
+        struct Slot {
+            memory_type_bits: u32,
+            size: DeviceSize,
+            // Indices into `aliased`.
+            residents: Vec<usize>,
+        }
+
+        let mut slots: Vec<Slot> = Vec::new();
+
+        for (aliased_index, (_, lifetime, _, mem_reqs)) in aliased.iter().enumerate() {
+            let overlaps = |other: &usize| {
+                let other_lifetime = aliased[*other].1;
+                lifetime.0 <= other_lifetime.1 && other_lifetime.0 <= lifetime.1
+            };
+
+            let slot = slots.iter_mut().find(|slot| {
+                best_memory_type(slot.memory_type_bits & mem_reqs.memory_type_bits).is_some()
+                    && !slot.residents.iter().any(overlaps)
+            });
+
+            match slot {
+                Some(slot) => {
+                    slot.memory_type_bits &= mem_reqs.memory_type_bits;
+                    slot.size = slot.size.max(mem_reqs.size);
+                    slot.residents.push(aliased_index);
+                }
+                None => slots.push(Slot {
+                    memory_type_bits: mem_reqs.memory_type_bits,
+                    size: mem_reqs.size,
+                    residents: vec![aliased_index],
+                }),
+            }
+        }
+
+        let mut aliasing_barriers = vec![false; num_steps];
+
+        for slot in &slots {
+            let memory_type_index = best_memory_type(slot.memory_type_bits)
+                .ok_or(RenderGraphExecuteError::NoSuitableMemoryType)?;
+            let memory = Arc::new(DeviceMemory::allocate(
+                device.clone(),
+                MemoryAllocateInfo {
+                    allocation_size: slot.size,
+                    memory_type_index,
+                    ..Default::default()
+                },
+            )?);
+
+            for &aliased_index in &slot.residents {
+                let (index, lifetime, image, _) = &aliased[aliased_index];
+
+                // Offset 0 satisfies any alignment requirement.
+                unsafe {
+                    image.bind_memory(&memory, 0)?;
+                }
+
+                // The memory was used by another image before, in this execution or in the
+                // previous one.
+                if slot.residents.len() > 1 {
+                    aliasing_barriers[lifetime.0] = true;
+                }
+
+                let aspects = image.format().unwrap().aspects();
+                let usage = image.usage();
+                let layout = if aspects.depth || aspects.stencil {
+                    if usage.depth_stencil_attachment {
+                        ImageLayout::DepthStencilAttachmentOptimal
+                    } else {
+                        ImageLayout::General
+                    }
+                } else if usage.color_attachment {
+                    ImageLayout::ColorAttachmentOptimal
+                } else {
+                    ImageLayout::General
+                };
+
+                let image = Arc::new(AliasedImage {
+                    image: image.clone(),
+                    _memory: memory.clone(),
+                    layout,
+                });
+                images[*index] = Some(ImageView::new_default(image)? as Arc<_>);
+            }
+        }
+
+        let memory_usage = TransientMemoryUsage {
+            required_size: aliased
+                .iter()
+                .map(|(_, _, _, mem_reqs)| mem_reqs.size)
+                .sum(),
+            allocated_size: slots.iter().map(|slot| slot.size).sum(),
+            num_allocations: slots.len() as u32,
+        };
+
+        Ok(TransientImages {
+            extent,
+            images,
+            aliasing_barriers,
+            memory_usage,
+        })
+    }
+}
+
+// An image that is bound to memory which is possibly shared with other images.
+//
+// The content of the memory is overwritten by the other images between executions of the graph,
+// so the image is always considered to be in the `Undefined` layout when it is first used in a
+// command buffer.
+#[derive(Debug)]
+struct AliasedImage {
+    image: Arc<UnsafeImage>,
+    // Keeps the memory alive for as long as the image exists.
+    _memory: Arc<DeviceMemory>,
+    layout: ImageLayout,
+}
+
+unsafe impl ImageAccess for AliasedImage {
+    #[inline]
+    fn inner(&self) -> ImageInner {
+        ImageInner {
+            image: &self.image,
+            first_layer: 0,
+            num_layers: self.image.dimensions().array_layers(),
+            first_mipmap_level: 0,
+            num_mipmap_levels: self.image.mip_levels(),
+        }
+    }
+
+    #[inline]
+    fn initial_layout_requirement(&self) -> ImageLayout {
+        self.layout
+    }
+
+    #[inline]
+    fn final_layout_requirement(&self) -> ImageLayout {
+        self.layout
+    }
+
+    #[inline]
+    fn descriptor_layouts(&self) -> Option<ImageDescriptorLayouts> {
+        Some(ImageDescriptorLayouts {
+            storage_image: ImageLayout::General,
+            combined_image_sampler: ImageLayout::ShaderReadOnlyOptimal,
+            sampled_image: ImageLayout::ShaderReadOnlyOptimal,
+            input_attachment: ImageLayout::ShaderReadOnlyOptimal,
+        })
+    }
+}
+
+unsafe impl DeviceOwned for AliasedImage {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.image.device()
+    }
+}
+
+impl PartialEq for AliasedImage {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner() == other.inner()
+    }
+}
+
+impl Eq for AliasedImage {}
+
+impl Hash for AliasedImage {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner().hash(state);
+    }
+}
@@ -0,0 +1,546 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{
+    AttachmentDescription, AttachmentReference, LoadOp, RenderPass, RenderPassCreateInfo,
+    RenderPassCreationError, ResolveMode, SubpassDependency, SubpassDescription,
+};
+use crate::{device::Device, image::ImageLayout};
+use smallvec::SmallVec;
+use std::sync::Arc;
+
+/// Builds a `RenderPass` at runtime, one attachment and one subpass at a time.
+///
+/// This is the runtime equivalent of the `ordered_passes_renderpass!` macro, for render passes
+/// whose structure is only known when the program runs, for example because it is read from a
+/// file.
+///
+/// Attachment references whose layout is [`ImageLayout::Undefined`] are given a layout that
+/// matches the way the attachment is used in the subpass. If an attachment is both read as an
+/// input attachment and written in the same subpass, [`ImageLayout::General`] is used.
+///
+/// Attachments whose `final_layout` is [`ImageLayout::Undefined`] are given the layout of the last
+/// subpass that uses them. Attachments whose `initial_layout` is [`ImageLayout::Undefined`] and
+/// whose `load_op` or `stencil_load_op` is [`LoadOp::Load`] are given the layout of the first
+/// subpass that uses them.
+///
+/// Everything else is taken as provided, and is validated by [`RenderPass::new`] in the same way
+/// as render passes created with the macros.
+///
+/// ```
+/// use vulkano::{
+///     format::Format,
+///     render_pass::{
+///         AttachmentDescription, LoadOp, RenderPassBuilder, StoreOp, SubpassBuilder,
+///     },
+/// };
+///
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// let render_pass = RenderPassBuilder::new()
+///     .attachment(AttachmentDescription {
+///         format: Some(Format::R8G8B8A8_UNORM),
+///         load_op: LoadOp::Clear,
+///         store_op: StoreOp::Store,
+///         ..Default::default()
+///     })
+///     .subpass(SubpassBuilder::new().color_attachment(0))
+///     .build(device.clone())
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RenderPassBuilder {
+    attachments: Vec<AttachmentDescription>,
+    subpasses: Vec<SubpassDescription>,
+    dependencies: Vec<SubpassDependency>,
+    correlated_view_masks: Vec<u32>,
+}
+
+impl RenderPassBuilder {
+    /// Starts building a render pass with no attachments, subpasses or dependencies.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an attachment to the render pass. Its index is the number of attachments that were
+    /// added before it.
+    #[inline]
+    pub fn attachment(mut self, attachment: AttachmentDescription) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    /// Adds a subpass to the render pass. Its index is the number of subpasses that were added
+    /// before it.
+    #[inline]
+    pub fn subpass(mut self, subpass: impl Into<SubpassDescription>) -> Self {
+        self.subpasses.push(subpass.into());
+        self
+    }
+
+    /// Adds a dependency between two subpasses, or between a subpass and the commands outside the
+    /// render pass.
+    #[inline]
+    pub fn dependency(mut self, dependency: SubpassDependency) -> Self {
+        self.dependencies.push(dependency);
+        self
+    }
+
+    /// Adds a set of views that are highly correlated, when using multiview rendering.
+    ///
+    /// See [`RenderPassCreateInfo::correlated_view_masks`].
+    #[inline]
+    pub fn correlated_view_mask(mut self, view_mask: u32) -> Self {
+        self.correlated_view_masks.push(view_mask);
+        self
+    }
+
+    /// Returns the number of attachments that have been added so far.
+    #[inline]
+    pub fn num_attachments(&self) -> u32 {
+        self.attachments.len() as u32
+    }
+
+    /// Returns the number of subpasses that have been added so far.
+    #[inline]
+    pub fn num_subpasses(&self) -> u32 {
+        self.subpasses.len() as u32
+    }
+
+    /// Fills in the layouts that were left undefined, and returns the parameters to create the
+    /// render pass with.
+    pub fn into_create_info(self) -> RenderPassCreateInfo {
+        let RenderPassBuilder {
+            mut attachments,
+            mut subpasses,
+            dependencies,
+            correlated_view_masks,
+        } = self;
+
+        // The layouts of the first and the last use of each attachment.
+        let mut used_layouts: Vec<Option<(ImageLayout, ImageLayout)>> =
+            vec![None; attachments.len()];
+
+        for subpass in &mut subpasses {
+            let SubpassDescription {
+                input_attachments,
+                color_attachments,
+                resolve_attachments,
+                depth_stencil_attachment,
+                depth_stencil_resolve_attachment,
+                ..
+            } = subpass;
+
+            let read: SmallVec<[u32; 4]> = input_attachments
+                .iter()
+                .flatten()
+                .map(|atch_ref| atch_ref.attachment)
+                .collect();
+            let written: SmallVec<[u32; 4]> = color_attachments
+                .iter()
+                .flatten()
+                .chain(depth_stencil_attachment.iter())
+                .map(|atch_ref| atch_ref.attachment)
+                .collect();
+
+            for atch_ref in input_attachments.iter_mut().flatten() {
+                let is_depth_stencil = attachments
+                    .get(atch_ref.attachment as usize)
+                    .and_then(|attachment| attachment.format)
+                    .map_or(false, |format| {
+                        let aspects = format.aspects();
+                        aspects.depth || aspects.stencil
+                    });
+                let optimal_layout = if is_depth_stencil {
+                    ImageLayout::DepthStencilReadOnlyOptimal
+                } else {
+                    ImageLayout::ShaderReadOnlyOptimal
+                };
+
+                fill_layout(
+                    atch_ref,
+                    optimal_layout,
+                    written.contains(&atch_ref.attachment),
+                );
+            }
+
+            for atch_ref in color_attachments.iter_mut().flatten() {
+                fill_layout(
+                    atch_ref,
+                    ImageLayout::ColorAttachmentOptimal,
+                    read.contains(&atch_ref.attachment),
+                );
+            }
+
+            for atch_ref in resolve_attachments.iter_mut().flatten() {
+                fill_layout(atch_ref, ImageLayout::ColorAttachmentOptimal, false);
+            }
+
+            if let Some(atch_ref) = depth_stencil_attachment {
+                fill_layout(
+                    atch_ref,
+                    ImageLayout::DepthStencilAttachmentOptimal,
+                    read.contains(&atch_ref.attachment),
+                );
+            }
+
+            if let Some(atch_ref) = depth_stencil_resolve_attachment {
+                fill_layout(atch_ref, ImageLayout::DepthStencilAttachmentOptimal, false);
+            }
+
+            for atch_ref in (input_attachments.iter().flatten())
+                .chain(color_attachments.iter().flatten())
+                .chain(resolve_attachments.iter().flatten())
+                .chain(depth_stencil_attachment.iter())
+                .chain(depth_stencil_resolve_attachment.iter())
+            {
+                // Out-of-range references are reported by `RenderPass::new`.
+                if let Some(layouts) = used_layouts.get_mut(atch_ref.attachment as usize) {
+                    let first_layout = layouts.map_or(atch_ref.layout, |(first, _)| first);
+                    *layouts = Some((first_layout, atch_ref.layout));
+                }
+            }
+        }
+
+        for (attachment, layouts) in attachments.iter_mut().zip(used_layouts) {
+            let (first_layout, last_layout) = match layouts {
+                Some(x) => x,
+                None => continue,
+            };
+
+            if attachment.initial_layout == ImageLayout::Undefined
+                && (attachment.load_op == LoadOp::Load
+                    || attachment.stencil_load_op == LoadOp::Load)
+            {
+                attachment.initial_layout = first_layout;
+            }
+
+            if attachment.final_layout == ImageLayout::Undefined {
+                attachment.final_layout = last_layout;
+            }
+        }
+
+        RenderPassCreateInfo {
+            attachments,
+            subpasses,
+            dependencies,
+            correlated_view_masks,
+            ..Default::default()
+        }
+    }
+
+    /// Builds the render pass.
+    #[inline]
+    pub fn build(self, device: Arc<Device>) -> Result<Arc<RenderPass>, RenderPassCreationError> {
+        RenderPass::new(device, self.into_create_info())
+    }
+}
+
+fn fill_layout(atch_ref: &mut AttachmentReference, optimal_layout: ImageLayout, feedback: bool) {
+    if atch_ref.layout == ImageLayout::Undefined {
+        atch_ref.layout = if feedback {
+            ImageLayout::General
+        } else {
+            optimal_layout
+        };
+    }
+}
+
+/// Builds a subpass for a [`RenderPassBuilder`].
+///
+/// Attachments are referred to by their index in the render pass. The layouts of the references
+/// are determined by the `RenderPassBuilder`.
+#[derive(Clone, Debug, Default)]
+pub struct SubpassBuilder {
+    description: SubpassDescription,
+}
+
+impl SubpassBuilder {
+    /// Starts building a subpass that uses no attachments.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the views that the subpass renders to, when using multiview rendering.
+    ///
+    /// See [`SubpassDescription::view_mask`].
+    #[inline]
+    pub fn view_mask(mut self, view_mask: u32) -> Self {
+        self.description.view_mask = view_mask;
+        self
+    }
+
+    /// Adds an input attachment. Its index in the shader is the number of input attachments that
+    /// were added before it.
+    #[inline]
+    pub fn input_attachment(mut self, attachment: u32) -> Self {
+        self.description
+            .input_attachments
+            .push(Some(undefined_reference(attachment)));
+        self
+    }
+
+    /// Skips an input attachment index in the shader.
+    #[inline]
+    pub fn unused_input_attachment(mut self) -> Self {
+        self.description.input_attachments.push(None);
+        self
+    }
+
+    /// Adds a color attachment. Its index in the shader is the number of color attachments that
+    /// were added before it.
+    #[inline]
+    pub fn color_attachment(mut self, attachment: u32) -> Self {
+        self.description
+            .color_attachments
+            .push(Some(undefined_reference(attachment)));
+        self
+    }
+
+    /// Skips a color attachment index in the shader.
+    #[inline]
+    pub fn unused_color_attachment(mut self) -> Self {
+        self.description.color_attachments.push(None);
+        self
+    }
+
+    /// Adds a resolve attachment for the color attachment with the same index.
+    ///
+    /// If resolve attachments are used, there must be as many of them as there are color
+    /// attachments.
+    #[inline]
+    pub fn resolve_attachment(mut self, attachment: u32) -> Self {
+        self.description
+            .resolve_attachments
+            .push(Some(undefined_reference(attachment)));
+        self
+    }
+
+    /// Adds a placeholder for a color attachment that is not resolved.
+    #[inline]
+    pub fn unused_resolve_attachment(mut self) -> Self {
+        self.description.resolve_attachments.push(None);
+        self
+    }
+
+    /// Sets the depth/stencil attachment.
+    #[inline]
+    pub fn depth_stencil_attachment(mut self, attachment: u32) -> Self {
+        self.description.depth_stencil_attachment = Some(undefined_reference(attachment));
+        self
+    }
+
+    /// Sets the attachment that the depth/stencil attachment is resolved into, and how the depth
+    /// and stencil aspects are resolved.
+    ///
+    /// See [`SubpassDescription::depth_stencil_resolve_attachment`].
+    #[inline]
+    pub fn depth_stencil_resolve_attachment(
+        mut self,
+        attachment: u32,
+        depth_resolve_mode: Option<ResolveMode>,
+        stencil_resolve_mode: Option<ResolveMode>,
+    ) -> Self {
+        self.description.depth_stencil_resolve_attachment = Some(undefined_reference(attachment));
+        self.description.depth_resolve_mode = depth_resolve_mode;
+        self.description.stencil_resolve_mode = stencil_resolve_mode;
+        self
+    }
+
+    /// Adds an attachment that is not used by the subpass, but whose content must be preserved
+    /// throughout it.
+    #[inline]
+    pub fn preserve_attachment(mut self, attachment: u32) -> Self {
+        self.description.preserve_attachments.push(attachment);
+        self
+    }
+
+    /// Makes reads of the color, depth and stencil attachments through input attachments
+    /// ordered with the writes of earlier fragments, in rasterization order.
+    ///
+    /// See [`SubpassDescription::rasterization_order_color_attachment_access`],
+    /// [`SubpassDescription::rasterization_order_depth_attachment_access`] and
+    /// [`SubpassDescription::rasterization_order_stencil_attachment_access`].
+    #[inline]
+    pub fn rasterization_order_attachment_access(
+        mut self,
+        color: bool,
+        depth: bool,
+        stencil: bool,
+    ) -> Self {
+        self.description.rasterization_order_color_attachment_access = color;
+        self.description.rasterization_order_depth_attachment_access = depth;
+        self.description
+            .rasterization_order_stencil_attachment_access = stencil;
+        self
+    }
+}
+
+impl From<SubpassBuilder> for SubpassDescription {
+    #[inline]
+    fn from(val: SubpassBuilder) -> Self {
+        val.description
+    }
+}
+
+fn undefined_reference(attachment: u32) -> AttachmentReference {
+    AttachmentReference {
+        attachment,
+        layout: ImageLayout::Undefined,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RenderPassBuilder, SubpassBuilder};
+    use crate::{
+        format::Format,
+        image::ImageLayout,
+        render_pass::{AttachmentDescription, LoadOp, StoreOp, SubpassDependency},
+        sync::{AccessFlags, PipelineStages},
+    };
+
+    #[test]
+    fn layouts() {
+        let create_info = RenderPassBuilder::new()
+            .attachment(AttachmentDescription {
+                format: Some(Format::R8G8B8A8_UNORM),
+                load_op: LoadOp::Load,
+                store_op: StoreOp::Store,
+                ..Default::default()
+            })
+            .attachment(AttachmentDescription {
+                format: Some(Format::D16_UNORM),
+                load_op: LoadOp::Clear,
+                ..Default::default()
+            })
+            .attachment(AttachmentDescription {
+                format: Some(Format::R8G8B8A8_UNORM),
+                final_layout: ImageLayout::PresentSrc,
+                ..Default::default()
+            })
+            .subpass(
+                SubpassBuilder::new()
+                    .color_attachment(0)
+                    .depth_stencil_attachment(1)
+                    .preserve_attachment(2),
+            )
+            .subpass(
+                SubpassBuilder::new()
+                    .input_attachment(0)
+                    .input_attachment(1)
+                    .color_attachment(2),
+            )
+            .into_create_info();
+
+        let subpass = &create_info.subpasses[1];
+        assert_eq!(
+            subpass.input_attachments[0].as_ref().unwrap().layout,
+            ImageLayout::ShaderReadOnlyOptimal,
+        );
+        assert_eq!(
+            subpass.input_attachments[1].as_ref().unwrap().layout,
+            ImageLayout::DepthStencilReadOnlyOptimal,
+        );
+
+        let attachments = &create_info.attachments;
+        assert_eq!(
+            attachments[0].initial_layout,
+            ImageLayout::ColorAttachmentOptimal,
+        );
+        assert_eq!(
+            attachments[0].final_layout,
+            ImageLayout::ShaderReadOnlyOptimal,
+        );
+        assert_eq!(attachments[1].initial_layout, ImageLayout::Undefined);
+        assert_eq!(
+            attachments[1].final_layout,
+            ImageLayout::DepthStencilReadOnlyOptimal,
+        );
+        assert_eq!(attachments[2].final_layout, ImageLayout::PresentSrc);
+    }
+
+    #[test]
+    fn feedback_loop_general() {
+        let create_info = RenderPassBuilder::new()
+            .attachment(AttachmentDescription {
+                format: Some(Format::R8G8B8A8_UNORM),
+                ..Default::default()
+            })
+            .subpass(
+                SubpassBuilder::new()
+                    .input_attachment(0)
+                    .color_attachment(0),
+            )
+            .into_create_info();
+
+        let subpass = &create_info.subpasses[0];
+        assert_eq!(
+            subpass.input_attachments[0].as_ref().unwrap().layout,
+            ImageLayout::General,
+        );
+        assert_eq!(
+            subpass.color_attachments[0].as_ref().unwrap().layout,
+            ImageLayout::General,
+        );
+    }
+
+    #[test]
+    fn build() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let stages = PipelineStages {
+            color_attachment_output: true,
+            fragment_shader: true,
+            ..PipelineStages::none()
+        };
+
+        let render_pass = RenderPassBuilder::new()
+            .attachment(AttachmentDescription {
+                format: Some(Format::R8G8B8A8_UNORM),
+                load_op: LoadOp::Clear,
+                ..Default::default()
+            })
+            .attachment(AttachmentDescription {
+                format: Some(Format::R8G8B8A8_UNORM),
+                load_op: LoadOp::Clear,
+                store_op: StoreOp::Store,
+                ..Default::default()
+            })
+            .subpass(SubpassBuilder::new().color_attachment(0))
+            .subpass(
+                SubpassBuilder::new()
+                    .input_attachment(0)
+                    .color_attachment(1),
+            )
+            .dependency(SubpassDependency {
+                source_subpass: Some(0),
+                destination_subpass: Some(1),
+                source_stages: stages,
+                destination_stages: stages,
+                source_access: AccessFlags {
+                    color_attachment_write: true,
+                    ..AccessFlags::none()
+                },
+                destination_access: AccessFlags {
+                    input_attachment_read: true,
+                    ..AccessFlags::none()
+                },
+                by_region: true,
+                ..Default::default()
+            })
+            .build(device)
+            .unwrap();
+
+        assert_eq!(render_pass.subpasses().len(), 2);
+        assert_eq!(render_pass.dependencies().len(), 1);
+    }
+}
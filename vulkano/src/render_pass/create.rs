@@ -8,7 +8,7 @@
 // according to those terms.
 
 use super::{
-    AttachmentDescription, AttachmentReference, LoadOp, RenderPass, RenderPassCreateInfo,
+    AttachmentDescription, AttachmentReference, LoadOp, RenderPass, RenderPassCreateInfo, StoreOp,
     SubpassDependency, SubpassDescription,
 };
 use crate::{
@@ -68,6 +68,26 @@ impl RenderPass {
                 });
             }
 
+            if (load_op == LoadOp::None || stencil_load_op == LoadOp::None)
+                && !device.enabled_extensions().ext_load_store_op_none
+            {
+                return Err(RenderPassCreationError::ExtensionNotEnabled {
+                    extension: "ext_load_store_op_none",
+                    reason: "an attachment used `LoadOp::None`",
+                });
+            }
+
+            if (store_op == StoreOp::None || stencil_store_op == StoreOp::None)
+                && !(device.api_version() >= Version::V1_3
+                    || device.enabled_extensions().ext_load_store_op_none
+                    || device.enabled_extensions().khr_dynamic_rendering)
+            {
+                return Err(RenderPassCreationError::ExtensionNotEnabled {
+                    extension: "ext_load_store_op_none",
+                    reason: "an attachment used `StoreOp::None`",
+                });
+            }
+
             let format = format.unwrap();
             let aspects = format.aspects();
 
@@ -99,6 +119,16 @@ impl RenderPass {
                             });
                         }
                     }
+                    ImageLayout::AttachmentFeedbackLoopOptimal => {
+                        // VUID-VkAttachmentDescription2-attachmentFeedbackLoopLayout-07309
+                        // VUID-VkAttachmentDescription2-attachmentFeedbackLoopLayout-07310
+                        if !device.enabled_features().attachment_feedback_loop_layout {
+                            return Err(RenderPassCreationError::FeatureNotEnabled {
+                                feature: "attachment_feedback_loop_layout",
+                                reason: "an attachment used `ImageLayout::AttachmentFeedbackLoopOptimal` as its initial or final layout",
+                            });
+                        }
+                    }
                     _ => (),
                 }
             }
@@ -130,11 +160,53 @@ impl RenderPass {
                 ref color_attachments,
                 ref resolve_attachments,
                 ref depth_stencil_attachment,
+                ref depth_stencil_resolve_attachment,
+                depth_resolve_mode,
+                stencil_resolve_mode,
                 ref preserve_attachments,
+                rasterization_order_color_attachment_access,
+                rasterization_order_depth_attachment_access,
+                rasterization_order_stencil_attachment_access,
                 _ne: _,
             } = subpass;
             let subpass_num = subpass_num as u32;
 
+            // VUID-VkSubpassDescription2-rasterizationOrderColorAttachmentAccess-06499
+            if rasterization_order_color_attachment_access
+                && !device
+                    .enabled_features()
+                    .rasterization_order_color_attachment_access
+            {
+                return Err(RenderPassCreationError::FeatureNotEnabled {
+                    feature: "rasterization_order_color_attachment_access",
+                    reason: "a subpass specified `rasterization_order_color_attachment_access`",
+                });
+            }
+
+            // VUID-VkSubpassDescription2-rasterizationOrderDepthAttachmentAccess-06500
+            if rasterization_order_depth_attachment_access
+                && !device
+                    .enabled_features()
+                    .rasterization_order_depth_attachment_access
+            {
+                return Err(RenderPassCreationError::FeatureNotEnabled {
+                    feature: "rasterization_order_depth_attachment_access",
+                    reason: "a subpass specified `rasterization_order_depth_attachment_access`",
+                });
+            }
+
+            // VUID-VkSubpassDescription2-rasterizationOrderStencilAttachmentAccess-06501
+            if rasterization_order_stencil_attachment_access
+                && !device
+                    .enabled_features()
+                    .rasterization_order_stencil_attachment_access
+            {
+                return Err(RenderPassCreationError::FeatureNotEnabled {
+                    feature: "rasterization_order_stencil_attachment_access",
+                    reason: "a subpass specified `rasterization_order_stencil_attachment_access`",
+                });
+            }
+
             // VUID-VkRenderPassCreateInfo2-viewMask-03058
             if (view_mask != 0) != is_multiview {
                 return Err(RenderPassCreationError::SubpassMultiviewMismatch {
@@ -195,6 +267,16 @@ impl RenderPass {
                     layout @ None => *layout = Some(atch_ref.layout),
                 }
 
+                // VUID-VkAttachmentReference2-attachmentFeedbackLoopLayout-07311
+                if atch_ref.layout == ImageLayout::AttachmentFeedbackLoopOptimal
+                    && !device.enabled_features().attachment_feedback_loop_layout
+                {
+                    return Err(RenderPassCreationError::FeatureNotEnabled {
+                        feature: "attachment_feedback_loop_layout",
+                        reason: "a subpass used `ImageLayout::AttachmentFeedbackLoopOptimal` for an attachment reference",
+                    });
+                }
+
                 let first_use =
                     !std::mem::replace(&mut attachment_used[atch_ref.attachment as usize], true);
 
@@ -244,7 +326,9 @@ impl RenderPass {
                 // VUID-VkSubpassDescription2-None-04439
                 if !matches!(
                     atch_ref.layout,
-                    ImageLayout::ColorAttachmentOptimal | ImageLayout::General
+                    ImageLayout::ColorAttachmentOptimal
+                        | ImageLayout::General
+                        | ImageLayout::AttachmentFeedbackLoopOptimal
                 ) {
                     return Err(RenderPassCreationError::SubpassAttachmentLayoutInvalid {
                         subpass: subpass_num,
@@ -303,6 +387,7 @@ impl RenderPass {
                     ImageLayout::DepthStencilAttachmentOptimal
                         | ImageLayout::DepthStencilReadOnlyOptimal
                         | ImageLayout::General
+                        | ImageLayout::AttachmentFeedbackLoopOptimal
                 ) {
                     return Err(RenderPassCreationError::SubpassAttachmentLayoutInvalid {
                         subpass: subpass_num,
@@ -373,6 +458,7 @@ impl RenderPass {
                     ImageLayout::DepthStencilReadOnlyOptimal
                         | ImageLayout::ShaderReadOnlyOptimal
                         | ImageLayout::General
+                        | ImageLayout::AttachmentFeedbackLoopOptimal
                 ) {
                     return Err(RenderPassCreationError::SubpassAttachmentLayoutInvalid {
                         subpass: subpass_num,
@@ -526,6 +612,162 @@ impl RenderPass {
                 }
             }
 
+            /*
+                Check depth/stencil resolve attachment
+            */
+
+            if let Some(atch_ref) = depth_stencil_resolve_attachment.as_ref() {
+                if !(device.api_version() >= Version::V1_2
+                    || device.enabled_extensions().khr_depth_stencil_resolve)
+                {
+                    return Err(RenderPassCreationError::ExtensionNotEnabled {
+                        extension: "khr_depth_stencil_resolve",
+                        reason: "a subpass specified a depth/stencil resolve attachment",
+                    });
+                }
+
+                let (atch, features, _first_use) = check_attachment(atch_ref)?;
+
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-02651
+                if !features.depth_stencil_attachment {
+                    return Err(
+                        RenderPassCreationError::SubpassAttachmentFormatUsageNotSupported {
+                            subpass: subpass_num,
+                            attachment: atch_ref.attachment,
+                            usage: "depth/stencil resolve",
+                        },
+                    );
+                }
+
+                // VUID-VkAttachmentReference2-layout-03077
+                // VUID-VkSubpassDescription2-None-04439
+                if !matches!(
+                    atch_ref.layout,
+                    ImageLayout::DepthStencilAttachmentOptimal | ImageLayout::General
+                ) {
+                    return Err(RenderPassCreationError::SubpassAttachmentLayoutInvalid {
+                        subpass: subpass_num,
+                        attachment: atch_ref.attachment,
+                        usage: "depth/stencil resolve",
+                    });
+                }
+
+                // Not required by spec, but enforced by Vulkano for sanity.
+                if atch_ref.aspects != ImageAspects::none() {
+                    return Err(RenderPassCreationError::SubpassAttachmentAspectsNotEmpty {
+                        subpass: subpass_num,
+                        attachment: atch_ref.attachment,
+                    });
+                }
+
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03177
+                let depth_stencil_atch_ref = depth_stencil_attachment.as_ref().ok_or_else(|| {
+                    RenderPassCreationError::SubpassDepthStencilResolveAttachmentWithoutDepthStencilAttachment {
+                        subpass: subpass_num,
+                    }
+                })?;
+                let depth_stencil_atch = &attachments[depth_stencil_atch_ref.attachment as usize];
+
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03178
+                if depth_resolve_mode.is_none() && stencil_resolve_mode.is_none() {
+                    return Err(
+                        RenderPassCreationError::SubpassDepthStencilResolveModesNone {
+                            subpass: subpass_num,
+                        },
+                    );
+                }
+
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03179
+                if depth_stencil_atch.samples == SampleCount::Sample1 {
+                    return Err(
+                        RenderPassCreationError::SubpassDepthStencilAttachmentWithResolveNotMultisampled {
+                            subpass: subpass_num,
+                            attachment: depth_stencil_atch_ref.attachment,
+                        },
+                    );
+                }
+
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03180
+                if atch.samples != SampleCount::Sample1 {
+                    return Err(
+                        RenderPassCreationError::SubpassResolveAttachmentMultisampled {
+                            subpass: subpass_num,
+                            attachment: atch_ref.attachment,
+                        },
+                    );
+                }
+
+                let format = atch.format.unwrap();
+                let depth_stencil_format = depth_stencil_atch.format.unwrap();
+                let aspects = format.aspects();
+
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03181
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03182
+                let depth_mismatch = aspects.depth
+                    && (format.components()[0] != depth_stencil_format.components()[0]
+                        || format.type_depth() != depth_stencil_format.type_depth());
+                let stencil_mismatch = aspects.stencil
+                    && (format.components()[1] != depth_stencil_format.components()[1]
+                        || format.type_stencil() != depth_stencil_format.type_stencil());
+
+                if depth_mismatch || stencil_mismatch {
+                    return Err(
+                        RenderPassCreationError::SubpassDepthStencilResolveAttachmentFormatMismatch {
+                            subpass: subpass_num,
+                            resolve_attachment: atch_ref.attachment,
+                            depth_stencil_attachment: depth_stencil_atch_ref.attachment,
+                        },
+                    );
+                }
+
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03183
+                if let Some(mode) = depth_resolve_mode.filter(|_| aspects.depth) {
+                    if !properties
+                        .supported_depth_resolve_modes
+                        .map_or(false, |modes| modes.contains(mode))
+                    {
+                        return Err(
+                            RenderPassCreationError::SubpassDepthResolveModeNotSupported {
+                                subpass: subpass_num,
+                            },
+                        );
+                    }
+                }
+
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03184
+                if let Some(mode) = stencil_resolve_mode.filter(|_| aspects.stencil) {
+                    if !properties
+                        .supported_stencil_resolve_modes
+                        .map_or(false, |modes| modes.contains(mode))
+                    {
+                        return Err(
+                            RenderPassCreationError::SubpassStencilResolveModeNotSupported {
+                                subpass: subpass_num,
+                            },
+                        );
+                    }
+                }
+
+                if aspects.depth && aspects.stencil && depth_resolve_mode != stencil_resolve_mode {
+                    let independent_resolve = properties.independent_resolve.unwrap_or(false);
+                    let independent_resolve_none =
+                        properties.independent_resolve_none.unwrap_or(false);
+
+                    // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03185
+                    // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03186
+                    if !(independent_resolve
+                        || independent_resolve_none
+                            && (depth_resolve_mode.is_none() || stencil_resolve_mode.is_none()))
+                    {
+                        return Err(
+                            RenderPassCreationError::SubpassDepthStencilResolveModesNotSupported {
+                                subpass: subpass_num,
+                            },
+                        );
+                    }
+                }
+            }
+
             /*
                 Check preserve attachments
             */
@@ -909,6 +1151,7 @@ impl RenderPass {
                     .chain(subpass.resolve_attachments.iter())
                     .map(Option::as_ref)
                     .chain(subpass.depth_stencil_attachment.iter().map(Some))
+                    .chain(subpass.depth_stencil_resolve_attachment.iter().map(Some))
                     .map(|atch_ref| {
                         if let Some(atch_ref) = atch_ref {
                             ash::vk::AttachmentReference2 {
@@ -927,13 +1170,53 @@ impl RenderPass {
             })
             .collect::<SmallVec<[_; 8]>>();
 
-        let subpasses_vk = {
+        // The depth/stencil resolve info of each subpass, or `None` if the subpass has no
+        // depth/stencil resolve attachment. Must be built before `subpasses_vk`, which points
+        // into it.
+        let depth_stencil_resolves_vk = {
             // `ref_index` is increased during the loop and points to the next element to use
             // in `attachment_references_vk`.
             let mut ref_index = 0usize;
             let out: SmallVec<[_; 4]> = subpasses
                 .iter()
                 .map(|subpass| {
+                    ref_index += subpass.input_attachments.len()
+                        + subpass.color_attachments.len()
+                        + subpass.resolve_attachments.len()
+                        + subpass.depth_stencil_attachment.is_some() as usize;
+
+                    subpass.depth_stencil_resolve_attachment.as_ref().map(|_| {
+                        let depth_stencil_resolve =
+                            attachment_references_vk.as_ptr().offset(ref_index as isize);
+                        ref_index += 1;
+
+                        ash::vk::SubpassDescriptionDepthStencilResolve {
+                            depth_resolve_mode: subpass
+                                .depth_resolve_mode
+                                .map_or(ash::vk::ResolveModeFlags::NONE, Into::into),
+                            stencil_resolve_mode: subpass
+                                .stencil_resolve_mode
+                                .map_or(ash::vk::ResolveModeFlags::NONE, Into::into),
+                            p_depth_stencil_resolve_attachment: depth_stencil_resolve,
+                            ..Default::default()
+                        }
+                    })
+                })
+                .collect();
+
+            debug_assert!(ref_index == attachment_references_vk.len());
+
+            out
+        };
+
+        let subpasses_vk = {
+            // `ref_index` is increased during the loop and points to the next element to use
+            // in `attachment_references_vk`.
+            let mut ref_index = 0usize;
+            let out: SmallVec<[_; 4]> = subpasses
+                .iter()
+                .zip(&depth_stencil_resolves_vk)
+                .map(|(subpass, depth_stencil_resolve_vk)| {
                     let input_attachments =
                         attachment_references_vk.as_ptr().offset(ref_index as isize);
                     ref_index += subpass.input_attachments.len();
@@ -950,9 +1233,13 @@ impl RenderPass {
                     } else {
                         ptr::null()
                     };
+                    ref_index += subpass.depth_stencil_resolve_attachment.is_some() as usize;
 
                     ash::vk::SubpassDescription2 {
-                        flags: ash::vk::SubpassDescriptionFlags::empty(),
+                        p_next: depth_stencil_resolve_vk
+                            .as_ref()
+                            .map_or(ptr::null(), |info| info as *const _ as *const _),
+                        flags: subpass_flags_vk(subpass),
                         pipeline_bind_point: ash::vk::PipelineBindPoint::GRAPHICS, // TODO: any need to make this user-specifiable?
                         view_mask: subpass.view_mask,
                         input_attachment_count: subpass.input_attachments.len() as u32,
@@ -1149,7 +1436,7 @@ impl RenderPass {
                     };
 
                     ash::vk::SubpassDescription {
-                        flags: ash::vk::SubpassDescriptionFlags::empty(),
+                        flags: subpass_flags_vk(subpass),
                         pipeline_bind_point: ash::vk::PipelineBindPoint::GRAPHICS,
                         input_attachment_count: subpass.input_attachments.len() as u32,
                         p_input_attachments: if subpass.input_attachments.is_empty() {
@@ -1330,6 +1617,25 @@ impl RenderPass {
 }
 
 /// Error that can happen when creating a `RenderPass`.
+fn subpass_flags_vk(subpass: &SubpassDescription) -> ash::vk::SubpassDescriptionFlags {
+    let mut flags = ash::vk::SubpassDescriptionFlags::empty();
+
+    if subpass.rasterization_order_color_attachment_access {
+        flags |= ash::vk::SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS_ARM;
+    }
+
+    if subpass.rasterization_order_depth_attachment_access {
+        flags |= ash::vk::SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS_ARM;
+    }
+
+    if subpass.rasterization_order_stencil_attachment_access {
+        flags |=
+            ash::vk::SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS_ARM;
+    }
+
+    flags
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum RenderPassCreationError {
     /// Not enough memory.
@@ -1448,6 +1754,33 @@ pub enum RenderPassCreationError {
         first_samples: SampleCount,
     },
 
+    /// The depth resolve mode of a subpass is not supported by the device.
+    SubpassDepthResolveModeNotSupported { subpass: u32 },
+
+    /// An attachment used as a depth/stencil attachment in a subpass with a depth/stencil resolve
+    /// attachment has a `samples` value of [`SampleCount::Sample1`].
+    SubpassDepthStencilAttachmentWithResolveNotMultisampled { subpass: u32, attachment: u32 },
+
+    /// An attachment used as a depth/stencil resolve attachment in a subpass has a format whose
+    /// aspects do not match those of the depth/stencil attachment.
+    SubpassDepthStencilResolveAttachmentFormatMismatch {
+        subpass: u32,
+        resolve_attachment: u32,
+        depth_stencil_attachment: u32,
+    },
+
+    /// The depth/stencil resolve attachment of a subpass is `Some`, but the depth/stencil
+    /// attachment is `None`.
+    SubpassDepthStencilResolveAttachmentWithoutDepthStencilAttachment { subpass: u32 },
+
+    /// A subpass has a depth/stencil resolve attachment, but both its depth and stencil resolve
+    /// modes are `None`.
+    SubpassDepthStencilResolveModesNone { subpass: u32 },
+
+    /// The combination of depth and stencil resolve modes of a subpass is not supported by the
+    /// device.
+    SubpassDepthStencilResolveModesNotSupported { subpass: u32 },
+
     /// A reference to an attachment used as an input attachment in a subpass selects aspects that
     /// are not present in the format of the attachment.
     SubpassInputAttachmentAspectsNotCompatible { subpass: u32, attachment: u32 },
@@ -1497,6 +1830,9 @@ pub enum RenderPassCreationError {
     /// A resolve attachment in a subpass is `Some`, but the corresponding color attachment is
     /// `None`.
     SubpassResolveAttachmentWithoutColorAttachment { subpass: u32 },
+
+    /// The stencil resolve mode of a subpass is not supported by the device.
+    SubpassStencilResolveModeNotSupported { subpass: u32 },
 }
 
 impl error::Error for RenderPassCreationError {
@@ -1647,6 +1983,40 @@ impl fmt::Display for RenderPassCreationError {
                 "attachment {} used as a color or depth/stencil attachment in subpass {} has a `samples` value {:?} that is different from the first color attachment ({:?})",
                 attachment, subpass, samples, first_samples,
             ),
+            Self::SubpassDepthResolveModeNotSupported { subpass } => write!(
+                fmt,
+                "the depth resolve mode of subpass {} is not supported by the device",
+                subpass,
+            ),
+            Self::SubpassDepthStencilAttachmentWithResolveNotMultisampled { subpass, attachment } => write!(
+                fmt,
+                "attachment {} used as a depth/stencil attachment in subpass {} with a depth/stencil resolve attachment has a `samples` value of `SampleCount::Sample1`",
+                attachment, subpass,
+            ),
+            Self::SubpassDepthStencilResolveAttachmentFormatMismatch {
+                subpass,
+                resolve_attachment,
+                depth_stencil_attachment,
+            } => write!(
+                fmt,
+                "attachment {} used as a depth/stencil resolve attachment in subpass {} has a format whose aspects do not match those of the depth/stencil attachment {}",
+                resolve_attachment, subpass, depth_stencil_attachment,
+            ),
+            Self::SubpassDepthStencilResolveAttachmentWithoutDepthStencilAttachment { subpass } => write!(
+                fmt,
+                "the depth/stencil resolve attachment of subpass {} is `Some`, but the depth/stencil attachment is `None`",
+                subpass,
+            ),
+            Self::SubpassDepthStencilResolveModesNone { subpass } => write!(
+                fmt,
+                "subpass {} has a depth/stencil resolve attachment, but both its depth and stencil resolve modes are `None`",
+                subpass,
+            ),
+            Self::SubpassDepthStencilResolveModesNotSupported { subpass } => write!(
+                fmt,
+                "the combination of depth and stencil resolve modes of subpass {} is not supported by the device",
+                subpass,
+            ),
             Self::SubpassInputAttachmentAspectsNotCompatible { subpass, attachment } => write!(
                 fmt,
                 "a reference to attachment {} used as an input attachment in subpass {} selects aspects that are not present in the format of the attachment",
@@ -1696,6 +2066,11 @@ impl fmt::Display for RenderPassCreationError {
                 "a resolve attachment in subpass {} is `Some`, but the corresponding color attachment is `None`",
                 subpass,
             ),
+            Self::SubpassStencilResolveModeNotSupported { subpass } => write!(
+                fmt,
+                "the stencil resolve mode of subpass {} is not supported by the device",
+                subpass,
+            ),
         }
     }
 }
@@ -7,7 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use super::RenderPass;
+use super::{RenderPass, RenderPassCompatibilityError};
 use crate::{
     check_errors,
     device::{Device, DeviceOwned},
@@ -150,6 +150,18 @@ impl Framebuffer {
                         }
                     }
 
+                    // VUID-VkFramebufferCreateInfo-pAttachments-02634
+                    if let Some(atch_ref) = &subpass.depth_stencil_resolve_attachment {
+                        if atch_ref.attachment == attachment_num {
+                            if !image_view.usage().depth_stencil_attachment {
+                                return Err(FramebufferCreationError::AttachmentMissingUsage {
+                                    attachment: attachment_num,
+                                    usage: "depth_stencil",
+                                });
+                            }
+                        }
+                    }
+
                     // VUID-VkFramebufferCreateInfo-pAttachments-00879
                     if subpass
                         .input_attachments
@@ -318,6 +330,8 @@ impl Framebuffer {
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         Ok(Arc::new(Framebuffer {
             handle,
             render_pass,
@@ -334,6 +348,16 @@ impl Framebuffer {
         &self.render_pass
     }
 
+    /// Checks whether the framebuffer can be used with `render_pass`, which is the case if
+    /// `render_pass` is compatible with the render pass that was used to create the framebuffer.
+    #[inline]
+    pub fn check_compatible_with(
+        &self,
+        render_pass: &RenderPass,
+    ) -> Result<(), RenderPassCompatibilityError> {
+        self.render_pass.check_compatible_with(render_pass)
+    }
+
     /// Returns the attachments of the framebuffer.
     #[inline]
     pub fn attachments(&self) -> &[Arc<dyn ImageViewAbstract>] {
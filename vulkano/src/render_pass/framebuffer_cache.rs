@@ -0,0 +1,220 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{Framebuffer, FramebufferCreateInfo, FramebufferCreationError, RenderPass};
+use crate::VulkanObject;
+use smallvec::SmallVec;
+use std::{collections::VecDeque, sync::Arc};
+
+/// Cache of framebuffers, keyed by their render pass, attachments and dimensions.
+///
+/// A framebuffer has to be created for every combination of render pass and attachment images
+/// that is rendered to. For example, rendering to a swapchain requires one framebuffer per
+/// swapchain image, and all of them must be recreated when the swapchain is recreated. This cache
+/// keeps the framebuffers that were created recently, so that they can be reused instead of being
+/// created again for every frame.
+///
+/// When the cache is full, the framebuffer that was least recently returned is dropped to make
+/// room for the new one.
+///
+/// The cache keeps the framebuffers alive, and therefore also the render passes and image views
+/// they were created from. Call [`clear`](FramebufferCache::clear) to release them, for example
+/// after recreating a swapchain.
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use vulkano::image::view::ImageViewAbstract;
+/// # use vulkano::render_pass::RenderPass;
+/// use vulkano::render_pass::{FramebufferCache, FramebufferCreateInfo};
+///
+/// # let render_pass: Arc<RenderPass> = return;
+/// # let image_view: Arc<dyn ImageViewAbstract> = return;
+/// let mut cache = FramebufferCache::new(8);
+///
+/// // Returns the same framebuffer every time it's called with the same attachments.
+/// let framebuffer = cache
+///     .get_or_create(
+///         render_pass.clone(),
+///         FramebufferCreateInfo {
+///             attachments: vec![image_view.clone()],
+///             ..Default::default()
+///         },
+///     )
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct FramebufferCache {
+    capacity: usize,
+
+    // Ordered from least recently used to most recently used.
+    entries: VecDeque<(FramebufferKey, Arc<Framebuffer>)>,
+}
+
+impl FramebufferCache {
+    /// Creates a new empty `FramebufferCache` that holds at most `capacity` framebuffers.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `capacity` is 0.
+    #[inline]
+    pub fn new(capacity: usize) -> FramebufferCache {
+        assert!(capacity != 0, "the capacity of the cache must not be 0");
+
+        FramebufferCache {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the maximum number of framebuffers that the cache holds.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of framebuffers that are currently in the cache.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the cache is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns a framebuffer for `render_pass` and `create_info`.
+    ///
+    /// If a framebuffer was previously created with the same render pass, the same attachment
+    /// image views in the same order, and the same `extent` and `layers` values, and it is still
+    /// in the cache, then it is returned. Otherwise a new framebuffer is created and stored in the
+    /// cache.
+    pub fn get_or_create(
+        &mut self,
+        render_pass: Arc<RenderPass>,
+        create_info: FramebufferCreateInfo,
+    ) -> Result<Arc<Framebuffer>, FramebufferCreationError> {
+        let key = FramebufferKey {
+            render_pass: render_pass.clone(),
+            attachments: create_info
+                .attachments
+                .iter()
+                .map(|view| view.internal_object())
+                .collect(),
+            extent: create_info.extent,
+            layers: create_info.layers,
+        };
+
+        if let Some(index) = self.entries.iter().position(|(k, _)| *k == key) {
+            let entry = self.entries.remove(index).unwrap();
+            let framebuffer = entry.1.clone();
+            self.entries.push_back(entry);
+
+            return Ok(framebuffer);
+        }
+
+        let framebuffer = Framebuffer::new(render_pass, create_info)?;
+
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back((key, framebuffer.clone()));
+
+        Ok(framebuffer)
+    }
+
+    /// Removes all the framebuffers that were created for `render_pass` from the cache.
+    #[inline]
+    pub fn remove_render_pass(&mut self, render_pass: &RenderPass) {
+        self.entries
+            .retain(|(key, _)| key.render_pass.as_ref() != render_pass);
+    }
+
+    /// Removes all the framebuffers from the cache.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+// The image views are compared by handle. This is sound because the cached framebuffer keeps the
+// views alive, so their handles can't be reused by another view while the entry exists.
+#[derive(Debug, PartialEq, Eq)]
+struct FramebufferKey {
+    render_pass: Arc<RenderPass>,
+    attachments: SmallVec<[ash::vk::ImageView; 4]>,
+    extent: [u32; 2],
+    layers: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FramebufferCache;
+    use crate::{
+        format::Format,
+        image::{attachment::AttachmentImage, view::ImageView},
+        render_pass::FramebufferCreateInfo,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn reuse_and_evict() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let render_pass = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        )
+        .unwrap();
+
+        let view1 = ImageView::new_default(
+            AttachmentImage::new(device.clone(), [64, 64], Format::R8G8B8A8_UNORM).unwrap(),
+        )
+        .unwrap();
+        let view2 = ImageView::new_default(
+            AttachmentImage::new(device.clone(), [64, 64], Format::R8G8B8A8_UNORM).unwrap(),
+        )
+        .unwrap();
+
+        let mut cache = FramebufferCache::new(1);
+        let mut get = |view: &Arc<ImageView<AttachmentImage>>| {
+            cache
+                .get_or_create(
+                    render_pass.clone(),
+                    FramebufferCreateInfo {
+                        attachments: vec![view.clone()],
+                        ..Default::default()
+                    },
+                )
+                .unwrap()
+        };
+
+        let framebuffer1 = get(&view1);
+        assert!(Arc::ptr_eq(&framebuffer1, &get(&view1)));
+
+        // Evicts the framebuffer of `view1`.
+        let framebuffer2 = get(&view2);
+        assert!(!Arc::ptr_eq(&framebuffer1, &framebuffer2));
+        assert!(!Arc::ptr_eq(&framebuffer1, &get(&view1)));
+    }
+}
@@ -17,6 +17,9 @@ macro_rules! single_pass_renderpass {
             color: [$($color_atch:ident),*],
             depth_stencil: {$($depth_atch:ident)*}$(,)*
             $(resolve: [$($resolve_atch:ident),*])*$(,)*
+            $(depth_stencil_resolve: {$($ds_resolve_atch:ident)*}$(,)*)?
+            $(depth_resolve_mode: $depth_resolve_mode:ident$(,)*)?
+            $(stencil_resolve_mode: $stencil_resolve_mode:ident$(,)*)?
         }
     ) => (
         $crate::ordered_passes_renderpass!(
@@ -27,7 +30,10 @@ macro_rules! single_pass_renderpass {
                     color: [$($color_atch),*],
                     depth_stencil: {$($depth_atch)*},
                     input: [],
-                    resolve: [$($($resolve_atch),*)*]
+                    resolve: [$($($resolve_atch),*)*],
+                    $(depth_stencil_resolve: {$($ds_resolve_atch)*},)?
+                    $(depth_resolve_mode: $depth_resolve_mode,)?
+                    $(stencil_resolve_mode: $stencil_resolve_mode,)?
                 }
             ]
         )
@@ -58,6 +64,9 @@ macro_rules! ordered_passes_renderpass {
                     depth_stencil: {$($depth_atch:ident)*},
                     input: [$($input_atch:ident),*]$(,)*
                     $(resolve: [$($resolve_atch:ident),*])*$(,)*
+                    $(depth_stencil_resolve: {$($ds_resolve_atch:ident)*}$(,)*)?
+                    $(depth_resolve_mode: $depth_resolve_mode:ident$(,)*)?
+                    $(stencil_resolve_mode: $stencil_resolve_mode:ident$(,)*)?
                 }
             ),*
         ]
@@ -135,11 +144,45 @@ macro_rules! ordered_passes_renderpass {
                                 })
                             }),*)*
                         ],
+                        depth_stencil_resolve_attachment: {
+                            let resolve: Option<$crate::render_pass::AttachmentReference> = None;
+                            $($(
+                                let layout = &mut layouts[$ds_resolve_atch as usize];
+                                layout.1 = Some($crate::image::ImageLayout::DepthStencilAttachmentOptimal);
+                                layout.0 = layout.0.or(layout.1);
+
+                                let resolve = Some($crate::render_pass::AttachmentReference {
+                                    attachment: $ds_resolve_atch,
+                                    layout: $crate::image::ImageLayout::DepthStencilAttachmentOptimal,
+                                    ..Default::default()
+                                });
+                            )*)?
+                            resolve
+                        },
+                        depth_resolve_mode: {
+                            let mode: Option<$crate::render_pass::ResolveMode> = None;
+                            $($(
+                                let _ = $ds_resolve_atch;
+                                let mode = Some($crate::render_pass::ResolveMode::SampleZero);
+                            )*)?
+                            $(let mode = mode.map(|_| $crate::render_pass::ResolveMode::$depth_resolve_mode);)?
+                            mode
+                        },
+                        stencil_resolve_mode: {
+                            let mode: Option<$crate::render_pass::ResolveMode> = None;
+                            $($(
+                                let _ = $ds_resolve_atch;
+                                let mode = Some($crate::render_pass::ResolveMode::SampleZero);
+                            )*)?
+                            $(let mode = mode.map(|_| $crate::render_pass::ResolveMode::$stencil_resolve_mode);)?
+                            mode
+                        },
                         preserve_attachments: (0 .. attachment_num).filter(|&a| {
                             $(if a == $color_atch { return false; })*
                             $(if a == $depth_atch { return false; })*
                             $(if a == $input_atch { return false; })*
                             $($(if a == $resolve_atch { return false; })*)*
+                            $($(if a == $ds_resolve_atch { return false; })*)?
                             true
                         }).collect(),
                         ..Default::default()
@@ -228,7 +271,7 @@ macro_rules! ordered_passes_renderpass {
 
 #[cfg(test)]
 mod tests {
-    use crate::format::Format;
+    use crate::{format::Format, render_pass::RenderPassCreationError};
 
     #[test]
     fn single_pass_resolve() {
@@ -256,4 +299,41 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn single_pass_depth_stencil_resolve() {
+        let (device, _) = gfx_dev_and_queue!();
+        let result = single_pass_renderpass!(device.clone(),
+            attachments: {
+                depth: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::D16_UNORM,
+                    samples: 4,
+                },
+                resolved: {
+                    load: DontCare,
+                    store: Store,
+                    format: Format::D16_UNORM,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [],
+                depth_stencil: {depth},
+                depth_stencil_resolve: {resolved},
+                depth_resolve_mode: SampleZero,
+            }
+        );
+
+        match result {
+            Ok(render_pass) => {
+                let subpass = &render_pass.subpasses()[0];
+                assert!(subpass.depth_stencil_resolve_attachment.is_some());
+                assert!(!subpass.preserve_attachments.contains(&1));
+            }
+            Err(RenderPassCreationError::ExtensionNotEnabled { .. }) => (),
+            Err(err) => panic!("{}", err),
+        }
+    }
 }
@@ -0,0 +1,352 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{AttachmentReference, RenderPass, SubpassDescription};
+use crate::{
+    image::{ImageLayout, SampleCount},
+    sync::AccessFlags,
+};
+use std::fmt;
+
+impl RenderPass {
+    /// Returns the reasons why consecutive subpasses of the render pass may not be merged by a
+    /// tile-based GPU.
+    ///
+    /// Tile-based GPUs can execute several subpasses in a single pass over the tile memory, so
+    /// that the attachments written by one subpass are read by the next without ever leaving the
+    /// chip. Whether this happens is decided by the driver and is not reported by Vulkan, but the
+    /// drivers of the main mobile vendors document the conditions that prevent it. This function
+    /// checks the render pass against those conditions.
+    ///
+    /// The returned list is empty if no obstacle was found. This does not guarantee that the
+    /// driver merges the subpasses, as it may apply additional restrictions, for example on the
+    /// amount of tile memory needed by the attachments.
+    pub fn subpass_merge_obstacles(&self) -> Vec<SubpassMergeObstacle> {
+        let mut obstacles = Vec::new();
+        let last_subpass = self.subpasses.len() as u32 - 1;
+
+        for subpass in 1..=last_subpass {
+            let previous_desc = &self.subpasses[subpass as usize - 1];
+            let current_desc = &self.subpasses[subpass as usize];
+            let mut push = |reason| obstacles.push(SubpassMergeObstacle { subpass, reason });
+
+            if let (Some(previous), Some(current)) = (
+                self.subpass_samples(previous_desc),
+                self.subpass_samples(current_desc),
+            ) {
+                if previous != current {
+                    push(SubpassMergeObstacleReason::SampleCountMismatch { previous, current });
+                }
+            }
+
+            if previous_desc.view_mask != current_desc.view_mask {
+                push(SubpassMergeObstacleReason::ViewMaskMismatch);
+            }
+
+            for (dependency_index, dependency) in self.dependencies.iter().enumerate() {
+                let dependency_index = dependency_index as u32;
+
+                match (dependency.source_subpass, dependency.destination_subpass) {
+                    // Waiting for work outside the render pass in the middle of it, or making work
+                    // outside the render pass wait for a subpass that is not the last, requires
+                    // the tile memory to be flushed at that point.
+                    (None, Some(destination)) if destination == subpass => {
+                        push(SubpassMergeObstacleReason::ExternalDependency {
+                            dependency: dependency_index,
+                        });
+                    }
+                    (Some(source), None) if source == subpass - 1 => {
+                        push(SubpassMergeObstacleReason::ExternalDependency {
+                            dependency: dependency_index,
+                        });
+                    }
+                    (Some(source), Some(destination))
+                        if source == subpass - 1 && destination == subpass =>
+                    {
+                        if !dependency.by_region {
+                            push(SubpassMergeObstacleReason::DependencyNotByRegion {
+                                dependency: dependency_index,
+                            });
+                        }
+
+                        if has_non_attachment_access(dependency.source_access)
+                            || has_non_attachment_access(dependency.destination_access)
+                        {
+                            push(SubpassMergeObstacleReason::NonAttachmentAccess {
+                                dependency: dependency_index,
+                            });
+                        }
+                    }
+                    _ => (),
+                }
+            }
+
+            for resolve_ref in (previous_desc.resolve_attachments.iter().flatten())
+                .chain(previous_desc.depth_stencil_resolve_attachment.iter())
+            {
+                if attachment_references(current_desc)
+                    .any(|atch_ref| atch_ref.attachment == resolve_ref.attachment)
+                {
+                    push(SubpassMergeObstacleReason::ResolvedAttachmentUsed {
+                        attachment: resolve_ref.attachment,
+                    });
+                }
+            }
+
+            for previous_ref in attachment_references(previous_desc) {
+                let current_ref = match attachment_references(current_desc)
+                    .find(|atch_ref| atch_ref.attachment == previous_ref.attachment)
+                {
+                    Some(x) => x,
+                    None => continue,
+                };
+
+                if is_read_only_layout(previous_ref.layout)
+                    && !is_read_only_layout(current_ref.layout)
+                {
+                    push(SubpassMergeObstacleReason::LayoutTransition {
+                        attachment: previous_ref.attachment,
+                        old_layout: previous_ref.layout,
+                        new_layout: current_ref.layout,
+                    });
+                }
+            }
+        }
+
+        obstacles
+    }
+
+    fn subpass_samples(&self, subpass_desc: &SubpassDescription) -> Option<SampleCount> {
+        subpass_desc
+            .color_attachments
+            .iter()
+            .flatten()
+            .chain(subpass_desc.depth_stencil_attachment.iter())
+            .next()
+            .map(|atch_ref| self.attachments[atch_ref.attachment as usize].samples)
+    }
+}
+
+fn attachment_references(
+    subpass_desc: &SubpassDescription,
+) -> impl Iterator<Item = &AttachmentReference> {
+    (subpass_desc.input_attachments.iter().flatten())
+        .chain(subpass_desc.color_attachments.iter().flatten())
+        .chain(subpass_desc.resolve_attachments.iter().flatten())
+        .chain(subpass_desc.depth_stencil_attachment.iter())
+        .chain(subpass_desc.depth_stencil_resolve_attachment.iter())
+}
+
+fn has_non_attachment_access(access: AccessFlags) -> bool {
+    AccessFlags {
+        input_attachment_read: false,
+        color_attachment_read: false,
+        color_attachment_write: false,
+        depth_stencil_attachment_read: false,
+        depth_stencil_attachment_write: false,
+        ..access
+    } != AccessFlags::none()
+}
+
+fn is_read_only_layout(layout: ImageLayout) -> bool {
+    matches!(
+        layout,
+        ImageLayout::ShaderReadOnlyOptimal | ImageLayout::DepthStencilReadOnlyOptimal
+    )
+}
+
+/// A reason why two consecutive subpasses may not be merged by a tile-based GPU.
+///
+/// Returned by [`RenderPass::subpass_merge_obstacles`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubpassMergeObstacle {
+    /// The index of the later of the two subpasses. The obstacle is between `subpass - 1` and
+    /// `subpass`.
+    pub subpass: u32,
+
+    /// Why the subpasses may not be merged.
+    pub reason: SubpassMergeObstacleReason,
+}
+
+impl fmt::Display for SubpassMergeObstacle {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "subpasses {} and {} may not be merged: {}",
+            self.subpass - 1,
+            self.subpass,
+            self.reason,
+        )
+    }
+}
+
+/// The reason of a [`SubpassMergeObstacle`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubpassMergeObstacleReason {
+    /// The subpasses render with a different number of samples.
+    SampleCountMismatch {
+        previous: SampleCount,
+        current: SampleCount,
+    },
+
+    /// The subpasses render to a different set of views.
+    ViewMaskMismatch,
+
+    /// A dependency with commands outside the render pass waits for the earlier subpass, or makes
+    /// the later subpass wait, so the attachments must be written to memory in between.
+    ExternalDependency { dependency: u32 },
+
+    /// A dependency between the subpasses does not have `by_region` set, so every pixel of the
+    /// earlier subpass must be finished before the later subpass starts.
+    DependencyNotByRegion { dependency: u32 },
+
+    /// A dependency between the subpasses includes accesses other than attachment accesses, for
+    /// example sampling an attachment as a texture, so the attachments must be written to memory
+    /// in between.
+    NonAttachmentAccess { dependency: u32 },
+
+    /// An attachment is written as a resolve attachment by the earlier subpass and used by the
+    /// later subpass. Resolving writes the attachment to memory.
+    ResolvedAttachmentUsed { attachment: u32 },
+
+    /// An attachment that is used in a read-only layout by the earlier subpass is used in a
+    /// writable layout by the later subpass.
+    LayoutTransition {
+        attachment: u32,
+        old_layout: ImageLayout,
+        new_layout: ImageLayout,
+    },
+}
+
+impl fmt::Display for SubpassMergeObstacleReason {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::SampleCountMismatch { previous, current } => write!(
+                f,
+                "the subpasses have a different number of samples ({:?} and {:?})",
+                previous, current,
+            ),
+            Self::ViewMaskMismatch => write!(f, "the subpasses have a different view mask"),
+            Self::ExternalDependency { dependency } => write!(
+                f,
+                "dependency {} is an external dependency in the middle of the render pass",
+                dependency,
+            ),
+            Self::DependencyNotByRegion { dependency } => write!(
+                f,
+                "dependency {} between the subpasses is not by region",
+                dependency,
+            ),
+            Self::NonAttachmentAccess { dependency } => write!(
+                f,
+                "dependency {} between the subpasses includes accesses other than attachment accesses",
+                dependency,
+            ),
+            Self::ResolvedAttachmentUsed { attachment } => write!(
+                f,
+                "attachment {} is resolved by the earlier subpass and used by the later subpass",
+                attachment,
+            ),
+            Self::LayoutTransition {
+                attachment,
+                old_layout,
+                new_layout,
+            } => write!(
+                f,
+                "attachment {} goes from the read-only layout {:?} to the writable layout {:?}",
+                attachment, old_layout, new_layout,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SubpassMergeObstacleReason;
+    use crate::{
+        format::Format,
+        render_pass::{
+            AttachmentDescription, LoadOp, RenderPassBuilder, StoreOp, SubpassBuilder,
+            SubpassDependency,
+        },
+        sync::{AccessFlags, PipelineStages},
+    };
+
+    #[test]
+    fn obstacles() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let attachment = AttachmentDescription {
+            format: Some(Format::R8G8B8A8_UNORM),
+            load_op: LoadOp::Clear,
+            store_op: StoreOp::Store,
+            ..Default::default()
+        };
+        let stages = PipelineStages {
+            color_attachment_output: true,
+            fragment_shader: true,
+            ..PipelineStages::none()
+        };
+
+        let render_pass = RenderPassBuilder::new()
+            .attachment(attachment.clone())
+            .attachment(attachment.clone())
+            .attachment(attachment)
+            .subpass(SubpassBuilder::new().color_attachment(0))
+            .subpass(
+                SubpassBuilder::new()
+                    .input_attachment(0)
+                    .color_attachment(1),
+            )
+            .subpass(SubpassBuilder::new().color_attachment(2))
+            .dependency(SubpassDependency {
+                source_subpass: Some(0),
+                destination_subpass: Some(1),
+                source_stages: stages,
+                destination_stages: stages,
+                source_access: AccessFlags {
+                    color_attachment_write: true,
+                    ..AccessFlags::none()
+                },
+                destination_access: AccessFlags {
+                    input_attachment_read: true,
+                    ..AccessFlags::none()
+                },
+                by_region: true,
+                ..Default::default()
+            })
+            .dependency(SubpassDependency {
+                source_subpass: Some(1),
+                destination_subpass: Some(2),
+                source_stages: stages,
+                destination_stages: stages,
+                source_access: AccessFlags {
+                    color_attachment_write: true,
+                    ..AccessFlags::none()
+                },
+                destination_access: AccessFlags {
+                    shader_read: true,
+                    ..AccessFlags::none()
+                },
+                by_region: false,
+                ..Default::default()
+            })
+            .build(device)
+            .unwrap();
+
+        let obstacles = render_pass.subpass_merge_obstacles();
+        assert!(obstacles.iter().all(|obstacle| obstacle.subpass == 2));
+        assert!(obstacles.iter().any(|obstacle| obstacle.reason
+            == SubpassMergeObstacleReason::DependencyNotByRegion { dependency: 1 }));
+        assert!(obstacles.iter().any(|obstacle| obstacle.reason
+            == SubpassMergeObstacleReason::NonAttachmentAccess { dependency: 1 }));
+    }
+}
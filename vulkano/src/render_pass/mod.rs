@@ -25,10 +25,16 @@
 //! Consequently you can create graphics pipelines from a render pass object alone.
 //! A `Framebuffer` object is only needed when you actually add draw commands to a command buffer.
 
+pub use self::builder::{RenderPassBuilder, SubpassBuilder};
 pub use self::create::RenderPassCreationError;
 pub use self::framebuffer::Framebuffer;
 pub use self::framebuffer::FramebufferCreateInfo;
 pub use self::framebuffer::FramebufferCreationError;
+pub use self::framebuffer_cache::FramebufferCache;
+pub use self::merge::{SubpassMergeObstacle, SubpassMergeObstacleReason};
+pub use self::requirements::{
+    InputAttachmentRequirements, SubpassRequirements, SubpassRequirementsError,
+};
 use crate::{
     device::{Device, DeviceOwned},
     format::Format,
@@ -39,6 +45,7 @@ use crate::{
 };
 use std::cmp::max;
 use std::{
+    error, fmt,
     hash::{Hash, Hasher},
     mem::MaybeUninit,
     ptr,
@@ -47,8 +54,12 @@ use std::{
 
 #[macro_use]
 mod macros;
+mod builder;
 mod create;
 mod framebuffer;
+mod framebuffer_cache;
+mod merge;
+mod requirements;
 
 /// An object representing the discrete steps in which rendering is done.
 ///
@@ -103,6 +114,9 @@ mod framebuffer;
 /// ```
 ///
 /// See the documentation of the macro for more details. TODO: put link here
+///
+/// If the structure of the render pass is only known at runtime, use a [`RenderPassBuilder`]
+/// instead.
 #[derive(Debug)]
 pub struct RenderPass {
     handle: ash::vk::RenderPass,
@@ -140,6 +154,8 @@ impl RenderPass {
             }
         };
 
+        device.set_automatic_debug_name(handle);
+
         let RenderPassCreateInfo {
             attachments,
             subpasses,
@@ -245,9 +261,26 @@ impl RenderPass {
 
     /// Returns `true` if this render pass is compatible with the other render pass,
     /// as defined in the [`Render Pass Compatibility` section of the Vulkan specs](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/html/chap8.html#renderpass-compatibility).
+    ///
+    /// Use [`check_compatible_with`](Self::check_compatible_with) to find out why two render
+    /// passes are not compatible.
+    #[inline]
     pub fn is_compatible_with(&self, other: &RenderPass) -> bool {
+        self.check_compatible_with(other).is_ok()
+    }
+
+    /// Checks whether this render pass is compatible with the other render pass, as defined in
+    /// the [`Render Pass Compatibility` section of the Vulkan specs](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/html/chap8.html#renderpass-compatibility),
+    /// and returns the first difference that was found if they are not.
+    ///
+    /// Framebuffers and graphics pipelines can be used with any render pass that is compatible
+    /// with the one they were created with.
+    pub fn check_compatible_with(
+        &self,
+        other: &RenderPass,
+    ) -> Result<(), RenderPassCompatibilityError> {
         if self == other {
-            return true;
+            return Ok(());
         }
 
         let Self {
@@ -272,167 +305,218 @@ impl RenderPass {
         } = other;
 
         if attachments1.len() != attachments2.len() {
-            return false;
+            return Err(RenderPassCompatibilityError::AttachmentCountMismatch);
         }
 
-        if !attachments1
-            .iter()
-            .zip(attachments2)
-            .all(|(attachment_desc1, attachment_desc2)| {
-                let AttachmentDescription {
-                    format: format1,
-                    samples: samples1,
-                    load_op: _,
-                    store_op: _,
-                    stencil_load_op: _,
-                    stencil_store_op: _,
-                    initial_layout: _,
-                    final_layout: _,
-                    _ne: _,
-                } = attachment_desc1;
-                let AttachmentDescription {
-                    format: format2,
-                    samples: samples2,
-                    load_op: _,
-                    store_op: _,
-                    stencil_load_op: _,
-                    stencil_store_op: _,
-                    initial_layout: _,
-                    final_layout: _,
-                    _ne: _,
-                } = attachment_desc2;
-
-                format1 == format2 && samples1 == samples2
-            })
+        for (attachment, (attachment_desc1, attachment_desc2)) in
+            attachments1.iter().zip(attachments2).enumerate()
         {
-            return false;
+            let AttachmentDescription {
+                format: format1,
+                samples: samples1,
+                load_op: _,
+                store_op: _,
+                stencil_load_op: _,
+                stencil_store_op: _,
+                initial_layout: _,
+                final_layout: _,
+                _ne: _,
+            } = attachment_desc1;
+            let AttachmentDescription {
+                format: format2,
+                samples: samples2,
+                load_op: _,
+                store_op: _,
+                stencil_load_op: _,
+                stencil_store_op: _,
+                initial_layout: _,
+                final_layout: _,
+                _ne: _,
+            } = attachment_desc2;
+
+            if format1 != format2 || samples1 != samples2 {
+                return Err(RenderPassCompatibilityError::AttachmentMismatch {
+                    attachment: attachment as u32,
+                });
+            }
         }
 
-        let are_atch_refs_compatible = |atch_ref1, atch_ref2| match (atch_ref1, atch_ref2) {
-            (None, None) => true,
-            (Some(atch_ref1), Some(atch_ref2)) => {
-                let &AttachmentReference {
-                    attachment: attachment1,
-                    layout: _,
-                    aspects: aspects1,
-                    _ne: _,
-                } = atch_ref1;
-                let AttachmentDescription {
-                    format: format1,
-                    samples: samples1,
-                    load_op: _,
-                    store_op: _,
-                    stencil_load_op: _,
-                    stencil_store_op: _,
-                    initial_layout: _,
-                    final_layout: _,
-                    _ne: _,
-                } = &attachments1[attachment1 as usize];
-
-                let &AttachmentReference {
-                    attachment: attachment2,
-                    layout: _,
-                    aspects: aspects2,
-                    _ne: _,
-                } = atch_ref2;
-                let AttachmentDescription {
-                    format: format2,
-                    samples: samples2,
-                    load_op: _,
-                    store_op: _,
-                    stencil_load_op: _,
-                    stencil_store_op: _,
-                    initial_layout: _,
-                    final_layout: _,
-                    _ne: _,
-                } = &attachments2[attachment2 as usize];
-
-                format1 == format2 && samples1 == samples2 && aspects1 == aspects2
-            }
-            _ => false,
-        };
+        let are_atch_refs_compatible =
+            |atch_ref1: Option<&AttachmentReference>, atch_ref2: Option<&AttachmentReference>| {
+                match (atch_ref1, atch_ref2) {
+                    (None, None) => true,
+                    (Some(atch_ref1), Some(atch_ref2)) => {
+                        let &AttachmentReference {
+                            attachment: attachment1,
+                            layout: _,
+                            aspects: aspects1,
+                            _ne: _,
+                        } = atch_ref1;
+                        let AttachmentDescription {
+                            format: format1,
+                            samples: samples1,
+                            load_op: _,
+                            store_op: _,
+                            stencil_load_op: _,
+                            stencil_store_op: _,
+                            initial_layout: _,
+                            final_layout: _,
+                            _ne: _,
+                        } = &attachments1[attachment1 as usize];
+
+                        let &AttachmentReference {
+                            attachment: attachment2,
+                            layout: _,
+                            aspects: aspects2,
+                            _ne: _,
+                        } = atch_ref2;
+                        let AttachmentDescription {
+                            format: format2,
+                            samples: samples2,
+                            load_op: _,
+                            store_op: _,
+                            stencil_load_op: _,
+                            stencil_store_op: _,
+                            initial_layout: _,
+                            final_layout: _,
+                            _ne: _,
+                        } = &attachments2[attachment2 as usize];
+
+                        format1 == format2 && samples1 == samples2 && aspects1 == aspects2
+                    }
+                    _ => false,
+                }
+            };
+
+        // Returns the index of the first pair of references that are not compatible.
+        let find_incompatible_atch_ref =
+            |atch_refs1: &[Option<AttachmentReference>],
+             atch_refs2: &[Option<AttachmentReference>]| {
+                (0..max(atch_refs1.len(), atch_refs2.len())).find(|&i| {
+                    !are_atch_refs_compatible(
+                        atch_refs1.get(i).and_then(|x| x.as_ref()),
+                        atch_refs2.get(i).and_then(|x| x.as_ref()),
+                    )
+                })
+            };
 
         if subpasses1.len() != subpasses2.len() {
-            return false;
+            return Err(RenderPassCompatibilityError::SubpassCountMismatch);
         }
 
-        if !(subpasses1.iter())
-            .zip(subpasses2.iter())
-            .all(|(subpass1, subpass2)| {
-                let &SubpassDescription {
-                    view_mask: view_mask1,
-                    input_attachments: ref input_attachments1,
-                    color_attachments: ref color_attachments1,
-                    resolve_attachments: ref resolve_attachments1,
-                    depth_stencil_attachment: ref depth_stencil_attachment1,
-                    preserve_attachments: _,
-                    _ne: _,
-                } = subpass1;
-                let &SubpassDescription {
-                    view_mask: view_mask2,
-                    input_attachments: ref input_attachments2,
-                    color_attachments: ref color_attachments2,
-                    resolve_attachments: ref resolve_attachments2,
-                    depth_stencil_attachment: ref depth_stencil_attachment2,
-                    preserve_attachments: _,
-                    _ne: _,
-                } = subpass2;
-
-                if !(0..max(input_attachments1.len(), input_attachments2.len())).all(|i| {
-                    are_atch_refs_compatible(
-                        input_attachments1.get(i).and_then(|x| x.as_ref()),
-                        input_attachments2.get(i).and_then(|x| x.as_ref()),
-                    )
-                }) {
-                    return false;
-                }
+        for (subpass, (subpass1, subpass2)) in subpasses1.iter().zip(subpasses2).enumerate() {
+            let subpass = subpass as u32;
+            let &SubpassDescription {
+                view_mask: view_mask1,
+                input_attachments: ref input_attachments1,
+                color_attachments: ref color_attachments1,
+                resolve_attachments: ref resolve_attachments1,
+                depth_stencil_attachment: ref depth_stencil_attachment1,
+                depth_stencil_resolve_attachment: ref depth_stencil_resolve_attachment1,
+                depth_resolve_mode: depth_resolve_mode1,
+                stencil_resolve_mode: stencil_resolve_mode1,
+                preserve_attachments: _,
+                rasterization_order_color_attachment_access: _,
+                rasterization_order_depth_attachment_access: _,
+                rasterization_order_stencil_attachment_access: _,
+                _ne: _,
+            } = subpass1;
+            let &SubpassDescription {
+                view_mask: view_mask2,
+                input_attachments: ref input_attachments2,
+                color_attachments: ref color_attachments2,
+                resolve_attachments: ref resolve_attachments2,
+                depth_stencil_attachment: ref depth_stencil_attachment2,
+                depth_stencil_resolve_attachment: ref depth_stencil_resolve_attachment2,
+                depth_resolve_mode: depth_resolve_mode2,
+                stencil_resolve_mode: stencil_resolve_mode2,
+                preserve_attachments: _,
+                rasterization_order_color_attachment_access: _,
+                rasterization_order_depth_attachment_access: _,
+                rasterization_order_stencil_attachment_access: _,
+                _ne: _,
+            } = subpass2;
+
+            if let Some(index) = find_incompatible_atch_ref(input_attachments1, input_attachments2)
+            {
+                return Err(RenderPassCompatibilityError::SubpassAttachmentMismatch {
+                    subpass,
+                    attachment_type: "input",
+                    index: index as u32,
+                });
+            }
 
-                if !(0..max(color_attachments1.len(), color_attachments2.len())).all(|i| {
-                    are_atch_refs_compatible(
-                        color_attachments1.get(i).and_then(|x| x.as_ref()),
-                        color_attachments2.get(i).and_then(|x| x.as_ref()),
-                    )
-                }) {
-                    return false;
-                }
+            if let Some(index) = find_incompatible_atch_ref(color_attachments1, color_attachments2)
+            {
+                return Err(RenderPassCompatibilityError::SubpassAttachmentMismatch {
+                    subpass,
+                    attachment_type: "color",
+                    index: index as u32,
+                });
+            }
 
-                if subpasses1.len() > 1
-                    && !(0..max(resolve_attachments1.len(), resolve_attachments2.len())).all(|i| {
-                        are_atch_refs_compatible(
-                            resolve_attachments1.get(i).and_then(|x| x.as_ref()),
-                            resolve_attachments2.get(i).and_then(|x| x.as_ref()),
-                        )
-                    })
+            // Resolve attachments are only relevant for compatibility if there is more than one
+            // subpass.
+            if subpasses1.len() > 1 {
+                if let Some(index) =
+                    find_incompatible_atch_ref(resolve_attachments1, resolve_attachments2)
                 {
-                    return false;
+                    return Err(RenderPassCompatibilityError::SubpassAttachmentMismatch {
+                        subpass,
+                        attachment_type: "resolve",
+                        index: index as u32,
+                    });
                 }
+            }
+
+            if !are_atch_refs_compatible(
+                depth_stencil_attachment1.as_ref(),
+                depth_stencil_attachment2.as_ref(),
+            ) {
+                return Err(RenderPassCompatibilityError::SubpassAttachmentMismatch {
+                    subpass,
+                    attachment_type: "depth/stencil",
+                    index: 0,
+                });
+            }
 
+            if subpasses1.len() > 1 {
                 if !are_atch_refs_compatible(
-                    depth_stencil_attachment1.as_ref(),
-                    depth_stencil_attachment2.as_ref(),
+                    depth_stencil_resolve_attachment1.as_ref(),
+                    depth_stencil_resolve_attachment2.as_ref(),
                 ) {
-                    return false;
+                    return Err(RenderPassCompatibilityError::SubpassAttachmentMismatch {
+                        subpass,
+                        attachment_type: "depth/stencil resolve",
+                        index: 0,
+                    });
                 }
 
-                if view_mask1 != view_mask2 {
-                    return false;
+                if depth_resolve_mode1 != depth_resolve_mode2
+                    || stencil_resolve_mode1 != stencil_resolve_mode2
+                {
+                    return Err(
+                        RenderPassCompatibilityError::SubpassDepthStencilResolveModeMismatch {
+                            subpass,
+                        },
+                    );
                 }
+            }
 
-                true
-            })
-        {
-            return false;
+            if view_mask1 != view_mask2 {
+                return Err(RenderPassCompatibilityError::SubpassViewMaskMismatch { subpass });
+            }
         }
 
         if dependencies1 != dependencies2 {
-            return false;
+            return Err(RenderPassCompatibilityError::DependenciesMismatch);
         }
 
         if correlated_view_masks1 != correlated_view_masks2 {
-            return false;
+            return Err(RenderPassCompatibilityError::CorrelatedViewMasksMismatch);
         }
 
-        true
+        Ok(())
     }
 
     /// Returns `true` if the subpass of this description is compatible with the shader's fragment
@@ -875,6 +959,46 @@ pub struct SubpassDescription {
     /// The default value is `None`.
     pub depth_stencil_attachment: Option<AttachmentReference>,
 
+    /// The attachment of the render pass that `depth_stencil_attachment` is resolved into at the
+    /// end of this subpass.
+    ///
+    /// If set to `Some`, `depth_stencil_attachment` must also be `Some`, and have a `samples` value
+    /// other than [`SampleCount::Sample1`], while the resolve attachment must have a `samples` value
+    /// of [`SampleCount::Sample1`]. Each aspect of the resolve attachment's format must have the
+    /// same number of bits and numeric type as the same aspect of the depth/stencil attachment.
+    ///
+    /// If set to `Some`, the device API version must be at least 1.2, or the
+    /// [`khr_depth_stencil_resolve`](crate::device::DeviceExtensions::khr_depth_stencil_resolve)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub depth_stencil_resolve_attachment: Option<AttachmentReference>,
+
+    /// How the depth aspect of `depth_stencil_attachment` is resolved into
+    /// `depth_stencil_resolve_attachment`. If `None`, the depth aspect is not resolved.
+    ///
+    /// If the resolve attachment's format has a depth aspect, the mode must be supported according
+    /// to the [`supported_depth_resolve_modes`](crate::device::Properties::supported_depth_resolve_modes)
+    /// device property.
+    ///
+    /// The default value is `None`.
+    pub depth_resolve_mode: Option<ResolveMode>,
+
+    /// How the stencil aspect of `depth_stencil_attachment` is resolved into
+    /// `depth_stencil_resolve_attachment`. If `None`, the stencil aspect is not resolved.
+    ///
+    /// If the resolve attachment's format has a stencil aspect, the mode must be supported
+    /// according to the
+    /// [`supported_stencil_resolve_modes`](crate::device::Properties::supported_stencil_resolve_modes)
+    /// device property. If the format has both a depth and a stencil aspect, then the two modes
+    /// must be equal, unless allowed by the
+    /// [`independent_resolve`](crate::device::Properties::independent_resolve) and
+    /// [`independent_resolve_none`](crate::device::Properties::independent_resolve_none) device
+    /// properties.
+    ///
+    /// The default value is `None`.
+    pub stencil_resolve_mode: Option<ResolveMode>,
+
     /// The indices of attachments of the render pass that will be preserved during this subpass.
     ///
     /// The referenced attachments must not be used as any other attachment type in the subpass.
@@ -882,6 +1006,38 @@ pub struct SubpassDescription {
     /// The default value is empty.
     pub preserve_attachments: Vec<u32>,
 
+    /// Whether reads of the color attachments through input attachments are ordered with the
+    /// writes of earlier fragments in rasterization order, without needing a self-dependency.
+    /// Pipelines must also opt in through
+    /// [`ColorBlendState::rasterization_order_attachment_access`](crate::pipeline::graphics::color_blend::ColorBlendState::rasterization_order_attachment_access).
+    ///
+    /// If set to `true`, the
+    /// [`rasterization_order_color_attachment_access`](crate::device::Features::rasterization_order_color_attachment_access)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `false`.
+    pub rasterization_order_color_attachment_access: bool,
+
+    /// Whether reads of the depth aspect of the depth/stencil attachment through an input
+    /// attachment are ordered with the writes of earlier fragments in rasterization order.
+    ///
+    /// If set to `true`, the
+    /// [`rasterization_order_depth_attachment_access`](crate::device::Features::rasterization_order_depth_attachment_access)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `false`.
+    pub rasterization_order_depth_attachment_access: bool,
+
+    /// Whether reads of the stencil aspect of the depth/stencil attachment through an input
+    /// attachment are ordered with the writes of earlier fragments in rasterization order.
+    ///
+    /// If set to `true`, the
+    /// [`rasterization_order_stencil_attachment_access`](crate::device::Features::rasterization_order_stencil_attachment_access)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `false`.
+    pub rasterization_order_stencil_attachment_access: bool,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -892,9 +1048,15 @@ impl Default for SubpassDescription {
             view_mask: 0,
             color_attachments: Vec::new(),
             depth_stencil_attachment: None,
+            depth_stencil_resolve_attachment: None,
+            depth_resolve_mode: None,
+            stencil_resolve_mode: None,
             input_attachments: Vec::new(),
             resolve_attachments: Vec::new(),
             preserve_attachments: Vec::new(),
+            rasterization_order_color_attachment_access: false,
+            rasterization_order_depth_attachment_access: false,
+            rasterization_order_stencil_attachment_access: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1082,6 +1244,16 @@ pub enum LoadOp {
     /// If you are going to fill the attachment with a uniform value, it is better to use `Clear`
     /// instead.
     DontCare = ash::vk::AttachmentLoadOp::DONT_CARE.as_raw(),
+
+    /// The attachment will not be accessed at the start of the render pass. The content of the
+    /// attachment is preserved, but is undefined inside the render pass.
+    ///
+    /// This is what you should use for attachments that are only read, or not used at all, in the
+    /// render pass, so that the implementation doesn't need to load them.
+    ///
+    /// The [`ext_load_store_op_none`](crate::device::DeviceExtensions::ext_load_store_op_none)
+    /// extension must be enabled on the device.
+    None = ash::vk::AttachmentLoadOp::NONE_EXT.as_raw(),
 }
 
 impl From<LoadOp> for ash::vk::AttachmentLoadOp {
@@ -1112,6 +1284,19 @@ pub enum StoreOp {
     /// use a cache and write the output directly in memory. In other words, the content of the
     /// image will be undefined.
     DontCare = ash::vk::AttachmentStoreOp::DONT_CARE.as_raw(),
+
+    /// The attachment will not be accessed at the end of the render pass. If the attachment was
+    /// not written to during the render pass, its content is preserved. Otherwise, its content is
+    /// undefined.
+    ///
+    /// This is what you should use for attachments that are only read in the render pass, so that
+    /// the implementation doesn't need to write them back to memory.
+    ///
+    /// The device API version must be at least 1.3, or one of the
+    /// [`ext_load_store_op_none`](crate::device::DeviceExtensions::ext_load_store_op_none) or
+    /// [`khr_dynamic_rendering`](crate::device::DeviceExtensions::khr_dynamic_rendering)
+    /// extensions must be enabled on the device.
+    None = ash::vk::AttachmentStoreOp::NONE.as_raw(),
 }
 
 impl From<StoreOp> for ash::vk::AttachmentStoreOp {
@@ -1188,11 +1373,119 @@ impl From<ash::vk::ResolveModeFlags> for ResolveModes {
     }
 }
 
+/// Error returned when two render passes, or a render pass and an object created with another
+/// render pass, are not compatible.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RenderPassCompatibilityError {
+    /// The render passes have a different number of attachments.
+    AttachmentCountMismatch,
+
+    /// The format or the number of samples of an attachment is different.
+    AttachmentMismatch { attachment: u32 },
+
+    /// The render passes have a different number of subpasses.
+    SubpassCountMismatch,
+
+    /// An attachment reference of a subpass refers to attachments with a different format or
+    /// number of samples, selects different aspects, or is present in only one of the subpasses.
+    SubpassAttachmentMismatch {
+        subpass: u32,
+        attachment_type: &'static str,
+        index: u32,
+    },
+
+    /// The depth or stencil resolve mode of a subpass is different.
+    SubpassDepthStencilResolveModeMismatch { subpass: u32 },
+
+    /// The view mask of a subpass is different.
+    SubpassViewMaskMismatch { subpass: u32 },
+
+    /// The subpass dependencies are different.
+    DependenciesMismatch,
+
+    /// The correlated view masks are different.
+    CorrelatedViewMasksMismatch,
+
+    /// The graphics pipeline was created for a different subpass index.
+    SubpassIndexMismatch {
+        pipeline_subpass: u32,
+        other_subpass: u32,
+    },
+
+    /// The graphics pipeline was created for dynamic rendering, not for a render pass.
+    PipelineNotForRenderPass,
+}
+
+impl error::Error for RenderPassCompatibilityError {}
+
+impl fmt::Display for RenderPassCompatibilityError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::AttachmentCountMismatch => {
+                write!(
+                    f,
+                    "the render passes have a different number of attachments"
+                )
+            }
+            Self::AttachmentMismatch { attachment } => write!(
+                f,
+                "the format or the number of samples of attachment {} is different",
+                attachment,
+            ),
+            Self::SubpassCountMismatch => {
+                write!(f, "the render passes have a different number of subpasses")
+            }
+            Self::SubpassAttachmentMismatch {
+                subpass,
+                attachment_type,
+                index,
+            } => write!(
+                f,
+                "{} attachment reference {} of subpass {} is not compatible",
+                attachment_type, index, subpass,
+            ),
+            Self::SubpassDepthStencilResolveModeMismatch { subpass } => write!(
+                f,
+                "the depth or stencil resolve mode of subpass {} is different",
+                subpass,
+            ),
+            Self::SubpassViewMaskMismatch { subpass } => {
+                write!(f, "the view mask of subpass {} is different", subpass)
+            }
+            Self::DependenciesMismatch => write!(f, "the subpass dependencies are different"),
+            Self::CorrelatedViewMasksMismatch => {
+                write!(f, "the correlated view masks are different")
+            }
+            Self::SubpassIndexMismatch {
+                pipeline_subpass,
+                other_subpass,
+            } => write!(
+                f,
+                "the graphics pipeline was created for subpass {}, not subpass {}",
+                pipeline_subpass, other_subpass,
+            ),
+            Self::PipelineNotForRenderPass => write!(
+                f,
+                "the graphics pipeline was created for dynamic rendering, not for a render pass",
+            ),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::format::Format;
+    use crate::image::ImageLayout;
+    use crate::render_pass::AttachmentDescription;
+    use crate::render_pass::AttachmentReference;
+    use crate::render_pass::LoadOp;
     use crate::render_pass::RenderPass;
+    use crate::render_pass::RenderPassCompatibilityError;
+    use crate::render_pass::RenderPassCreateInfo;
     use crate::render_pass::RenderPassCreationError;
+    use crate::render_pass::StoreOp;
+    use crate::render_pass::SubpassDescription;
 
     #[test]
     fn empty() {
@@ -1254,4 +1547,188 @@ mod tests {
         assert_ne!(granularity[0], 0);
         assert_ne!(granularity[1], 0);
     }
+
+    #[test]
+    fn load_op_none_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp = single_pass_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: None, store: Store, format: Format::R8G8B8A8_UNORM, samples: 1, }
+            },
+            pass: {
+                color: [a],
+                depth_stencil: {}
+            }
+        };
+
+        match rp {
+            Err(RenderPassCreationError::ExtensionNotEnabled {
+                extension: "ext_load_store_op_none",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn rasterization_order_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp = RenderPass::new(
+            device,
+            RenderPassCreateInfo {
+                attachments: vec![AttachmentDescription {
+                    format: Some(Format::R8G8B8A8_UNORM),
+                    load_op: LoadOp::Load,
+                    store_op: StoreOp::Store,
+                    initial_layout: ImageLayout::General,
+                    final_layout: ImageLayout::General,
+                    ..Default::default()
+                }],
+                subpasses: vec![SubpassDescription {
+                    color_attachments: vec![Some(AttachmentReference {
+                        attachment: 0,
+                        layout: ImageLayout::General,
+                        ..Default::default()
+                    })],
+                    input_attachments: vec![Some(AttachmentReference {
+                        attachment: 0,
+                        layout: ImageLayout::General,
+                        ..Default::default()
+                    })],
+                    rasterization_order_color_attachment_access: true,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        );
+
+        match rp {
+            Err(RenderPassCreationError::FeatureNotEnabled {
+                feature: "rasterization_order_color_attachment_access",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn attachment_feedback_loop_layout_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp = RenderPass::new(
+            device,
+            RenderPassCreateInfo {
+                attachments: vec![AttachmentDescription {
+                    format: Some(Format::R8G8B8A8_UNORM),
+                    load_op: LoadOp::Load,
+                    store_op: StoreOp::Store,
+                    initial_layout: ImageLayout::AttachmentFeedbackLoopOptimal,
+                    final_layout: ImageLayout::AttachmentFeedbackLoopOptimal,
+                    ..Default::default()
+                }],
+                subpasses: vec![SubpassDescription {
+                    color_attachments: vec![Some(AttachmentReference {
+                        attachment: 0,
+                        layout: ImageLayout::ColorAttachmentOptimal,
+                        ..Default::default()
+                    })],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        );
+
+        match rp {
+            Err(RenderPassCreationError::FeatureNotEnabled {
+                feature: "attachment_feedback_loop_layout",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn attachment_feedback_loop_reference_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp = RenderPass::new(
+            device,
+            RenderPassCreateInfo {
+                attachments: vec![AttachmentDescription {
+                    format: Some(Format::R8G8B8A8_UNORM),
+                    load_op: LoadOp::Load,
+                    store_op: StoreOp::Store,
+                    initial_layout: ImageLayout::General,
+                    final_layout: ImageLayout::General,
+                    ..Default::default()
+                }],
+                subpasses: vec![SubpassDescription {
+                    color_attachments: vec![Some(AttachmentReference {
+                        attachment: 0,
+                        layout: ImageLayout::AttachmentFeedbackLoopOptimal,
+                        ..Default::default()
+                    })],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        );
+
+        match rp {
+            Err(RenderPassCreationError::FeatureNotEnabled {
+                feature: "attachment_feedback_loop_layout",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn compatibility() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp1 = single_pass_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: Clear, store: Store, format: Format::R8G8B8A8_UNORM, samples: 1, }
+            },
+            pass: {
+                color: [a],
+                depth_stencil: {}
+            }
+        }
+        .unwrap();
+        let rp2 = single_pass_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: DontCare, store: DontCare, format: Format::R8G8B8A8_UNORM, samples: 1, }
+            },
+            pass: {
+                color: [a],
+                depth_stencil: {}
+            }
+        }
+        .unwrap();
+        let rp3 = single_pass_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: Clear, store: Store, format: Format::R8G8B8A8_SRGB, samples: 1, }
+            },
+            pass: {
+                color: [a],
+                depth_stencil: {}
+            }
+        }
+        .unwrap();
+
+        assert!(rp1.check_compatible_with(&rp2).is_ok());
+        assert_eq!(
+            rp1.check_compatible_with(&rp3),
+            Err(RenderPassCompatibilityError::AttachmentMismatch { attachment: 0 }),
+        );
+        assert!(!rp1.is_compatible_with(&rp3));
+    }
 }
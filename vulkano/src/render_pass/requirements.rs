@@ -0,0 +1,473 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{
+    AttachmentDescription, AttachmentReference, Subpass, SubpassBuilder, SubpassDescription,
+};
+use crate::{
+    descriptor_set::layout::DescriptorType,
+    format::Format,
+    image::{ImageAspects, SampleCount},
+    shader::{EntryPoint, ShaderExecution, ShaderScalarType},
+};
+use std::{error, fmt};
+
+/// The attachments that a fragment shader reads and writes, as found in its SPIR-V code.
+///
+/// This can be used to build a subpass that matches the shader with
+/// [`subpass_builder`](Self::subpass_builder), or to check that an existing subpass matches it
+/// with [`validate`](Self::validate) before the render pass or the graphics pipeline is created.
+/// Without this check, a mismatch only shows up when drawing, as garbage values or as errors of
+/// the validation layers.
+///
+/// ```
+/// use vulkano::render_pass::{RenderPassBuilder, SubpassRequirements};
+///
+/// # let fs: std::sync::Arc<vulkano::shader::ShaderModule> = return;
+/// # let attachment: vulkano::render_pass::AttachmentDescription = return;
+/// let requirements = SubpassRequirements::from_fragment_shader(&fs.entry_point("main").unwrap());
+///
+/// // Write each color output of the shader to the attachment with the same index.
+/// let builder = RenderPassBuilder::new()
+///     .attachment(attachment)
+///     .subpass(requirements.subpass_builder(&[0], &[], None));
+/// let create_info = builder.into_create_info();
+///
+/// requirements
+///     .validate(&create_info.attachments, &create_info.subpasses[0])
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubpassRequirements {
+    /// For each location, the scalar type of the color output that the shader writes to it, or
+    /// `None` if the shader doesn't write to the location.
+    pub color_attachments: Vec<Option<ShaderScalarType>>,
+
+    /// For each input attachment index, the requirements of the input attachment that the shader
+    /// reads from it, or `None` if the shader doesn't read from the index.
+    pub input_attachments: Vec<Option<InputAttachmentRequirements>>,
+
+    /// Whether the shader writes the depth of fragments, which requires a depth attachment.
+    pub depth_write: bool,
+}
+
+impl SubpassRequirements {
+    /// Returns the requirements of a fragment shader.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `entry_point` is not a fragment shader.
+    pub fn from_fragment_shader(entry_point: &EntryPoint) -> Self {
+        let depth_write = match entry_point.execution() {
+            ShaderExecution::Fragment(execution) => execution.depth_replacing,
+            _ => panic!("the entry point must be a fragment shader"),
+        };
+
+        let mut color_attachments = Vec::new();
+
+        for element in entry_point.output_interface().elements() {
+            let locations = element.location..element.location + element.ty.num_locations();
+
+            if color_attachments.len() < locations.end as usize {
+                color_attachments.resize(locations.end as usize, None);
+            }
+
+            for location in locations {
+                color_attachments[location as usize] = Some(element.ty.base_type);
+            }
+        }
+
+        let mut input_attachments = Vec::new();
+
+        for reqs in entry_point.descriptor_requirements().map(|(_, reqs)| reqs) {
+            let first_index = match reqs.input_attachment_index {
+                Some(index) if reqs.descriptor_types == [DescriptorType::InputAttachment] => index,
+                _ => continue,
+            };
+            let indices = first_index..first_index + reqs.descriptor_count;
+
+            if input_attachments.len() < indices.end as usize {
+                input_attachments.resize(indices.end as usize, None);
+            }
+
+            for index in indices {
+                input_attachments[index as usize] = Some(InputAttachmentRequirements {
+                    scalar_type: reqs.image_scalar_type,
+                    multisampled: reqs.image_multisampled,
+                });
+            }
+        }
+
+        SubpassRequirements {
+            color_attachments,
+            input_attachments,
+            depth_write,
+        }
+    }
+
+    /// Returns a `SubpassBuilder` with the attachment references that the shader needs.
+    ///
+    /// - `color_attachments[location]` is the attachment that the color output at `location` is
+    ///   written to. Locations that the shader doesn't write to get an unused reference, and
+    ///   their value in `color_attachments` is ignored.
+    /// - `input_attachments[index]` is the attachment that the shader reads from at the input
+    ///   attachment index `index`. Indices that the shader doesn't read from get an unused
+    ///   reference, and their value in `input_attachments` is ignored.
+    /// - `depth_stencil_attachment` is the depth/stencil attachment of the subpass. It is only
+    ///   required if the shader writes depth, but can be given anyway for depth testing.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `color_attachments` is shorter than `self.color_attachments`.
+    /// - Panics if `input_attachments` is shorter than `self.input_attachments`.
+    /// - Panics if `depth_stencil_attachment` is `None` and `self.depth_write` is `true`.
+    pub fn subpass_builder(
+        &self,
+        color_attachments: &[u32],
+        input_attachments: &[u32],
+        depth_stencil_attachment: Option<u32>,
+    ) -> SubpassBuilder {
+        assert!(
+            color_attachments.len() >= self.color_attachments.len(),
+            "an attachment must be given for each location that the shader writes to"
+        );
+        assert!(
+            input_attachments.len() >= self.input_attachments.len(),
+            "an attachment must be given for each input attachment index that the shader reads \
+            from"
+        );
+        assert!(
+            depth_stencil_attachment.is_some() || !self.depth_write,
+            "a depth attachment must be given if the shader writes depth"
+        );
+
+        let mut builder = SubpassBuilder::new();
+
+        for (location, color) in self.color_attachments.iter().enumerate() {
+            builder = match color {
+                Some(_) => builder.color_attachment(color_attachments[location]),
+                None => builder.unused_color_attachment(),
+            };
+        }
+
+        for (index, input) in self.input_attachments.iter().enumerate() {
+            builder = match input {
+                Some(_) => builder.input_attachment(input_attachments[index]),
+                None => builder.unused_input_attachment(),
+            };
+        }
+
+        if let Some(attachment) = depth_stencil_attachment {
+            builder = builder.depth_stencil_attachment(attachment);
+        }
+
+        builder
+    }
+
+    /// Checks that `subpass`, in a render pass with the given `attachments`, provides the
+    /// attachments that the shader reads and writes, with compatible formats and sample counts.
+    ///
+    /// Attachments that the subpass provides but the shader doesn't use are allowed.
+    pub fn validate(
+        &self,
+        attachments: &[AttachmentDescription],
+        subpass: &SubpassDescription,
+    ) -> Result<(), SubpassRequirementsError> {
+        for (location, &scalar_type) in self.color_attachments.iter().enumerate() {
+            let location = location as u32;
+            let scalar_type = match scalar_type {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let atch_ref = subpass
+                .color_attachments
+                .get(location as usize)
+                .cloned()
+                .flatten()
+                .ok_or(SubpassRequirementsError::ColorAttachmentMissing { location })?;
+            let format = attachment_format(attachments, &atch_ref);
+
+            if format
+                .and_then(|format| format.type_color())
+                .map(ShaderScalarType::from)
+                != Some(scalar_type)
+            {
+                return Err(SubpassRequirementsError::ColorAttachmentFormatMismatch {
+                    location,
+                    attachment: atch_ref.attachment,
+                    required: scalar_type,
+                });
+            }
+        }
+
+        for (index, input) in self.input_attachments.iter().enumerate() {
+            let index = index as u32;
+            let input = match input {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let atch_ref = subpass
+                .input_attachments
+                .get(index as usize)
+                .cloned()
+                .flatten()
+                .ok_or(SubpassRequirementsError::InputAttachmentMissing { index })?;
+            let format = attachment_format(attachments, &atch_ref);
+
+            if let Some(required) = input.scalar_type {
+                let aspects = if atch_ref.aspects != ImageAspects::none() {
+                    atch_ref.aspects
+                } else {
+                    format.map_or(ImageAspects::none(), |format| format.aspects())
+                };
+
+                let numeric_type = format.and_then(|format| {
+                    if aspects.color {
+                        format.type_color()
+                    } else if aspects.depth {
+                        format.type_depth()
+                    } else if aspects.stencil {
+                        format.type_stencil()
+                    } else {
+                        None
+                    }
+                });
+
+                if numeric_type.map(ShaderScalarType::from) != Some(required) {
+                    return Err(SubpassRequirementsError::InputAttachmentFormatMismatch {
+                        index,
+                        attachment: atch_ref.attachment,
+                        required,
+                    });
+                }
+            }
+
+            let samples = attachments[atch_ref.attachment as usize].samples;
+
+            if input.multisampled != (samples != SampleCount::Sample1) {
+                return Err(SubpassRequirementsError::InputAttachmentSamplesMismatch {
+                    index,
+                    attachment: atch_ref.attachment,
+                    multisampled: input.multisampled,
+                });
+            }
+        }
+
+        if self.depth_write {
+            let has_depth = subpass
+                .depth_stencil_attachment
+                .as_ref()
+                .and_then(|atch_ref| attachment_format(attachments, atch_ref))
+                .map_or(false, |format| format.aspects().depth);
+
+            if !has_depth {
+                return Err(SubpassRequirementsError::DepthAttachmentMissing);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that an existing subpass matches the requirements. See [`validate`](Self::validate).
+    #[inline]
+    pub fn validate_subpass(&self, subpass: &Subpass) -> Result<(), SubpassRequirementsError> {
+        self.validate(subpass.render_pass().attachments(), subpass.subpass_desc())
+    }
+}
+
+/// The requirements of a fragment shader on an input attachment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InputAttachmentRequirements {
+    /// The scalar type that the shader reads the attachment as, or `None` if it is unknown.
+    pub scalar_type: Option<ShaderScalarType>,
+
+    /// Whether the shader reads the attachment as multisampled, which requires an attachment with
+    /// more than one sample.
+    pub multisampled: bool,
+}
+
+// Panics if the attachment index is out of range, as `RenderPass::new` would reject it anyway.
+fn attachment_format(
+    attachments: &[AttachmentDescription],
+    atch_ref: &AttachmentReference,
+) -> Option<Format> {
+    attachments
+        .get(atch_ref.attachment as usize)
+        .expect("the subpass references an attachment that does not exist")
+        .format
+}
+
+/// Error that can happen when a subpass doesn't match the [`SubpassRequirements`] of a shader.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubpassRequirementsError {
+    /// The shader writes to a location that has no color attachment in the subpass.
+    ColorAttachmentMissing { location: u32 },
+
+    /// The color attachment at a location has a format whose scalar type is not the one that the
+    /// shader writes.
+    ColorAttachmentFormatMismatch {
+        location: u32,
+        attachment: u32,
+        required: ShaderScalarType,
+    },
+
+    /// The shader writes depth, but the subpass has no depth attachment.
+    DepthAttachmentMissing,
+
+    /// The shader reads from an input attachment index that has no input attachment in the
+    /// subpass.
+    InputAttachmentMissing { index: u32 },
+
+    /// The input attachment at an index has a format whose scalar type is not the one that the
+    /// shader reads.
+    InputAttachmentFormatMismatch {
+        index: u32,
+        attachment: u32,
+        required: ShaderScalarType,
+    },
+
+    /// The shader reads an input attachment as multisampled but the attachment has a single
+    /// sample, or the opposite.
+    InputAttachmentSamplesMismatch {
+        index: u32,
+        attachment: u32,
+        multisampled: bool,
+    },
+}
+
+impl error::Error for SubpassRequirementsError {}
+
+impl fmt::Display for SubpassRequirementsError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::ColorAttachmentMissing { location } => write!(
+                f,
+                "the shader writes to location {}, but the subpass has no color attachment there",
+                location,
+            ),
+            Self::ColorAttachmentFormatMismatch {
+                location,
+                attachment,
+                required,
+            } => write!(
+                f,
+                "the format of attachment {}, used as the color attachment at location {}, is not \
+                of the scalar type {:?} written by the shader",
+                attachment, location, required,
+            ),
+            Self::DepthAttachmentMissing => write!(
+                f,
+                "the shader writes depth, but the subpass has no depth attachment",
+            ),
+            Self::InputAttachmentMissing { index } => write!(
+                f,
+                "the shader reads from input attachment index {}, but the subpass has no input \
+                attachment there",
+                index,
+            ),
+            Self::InputAttachmentFormatMismatch {
+                index,
+                attachment,
+                required,
+            } => write!(
+                f,
+                "the format of attachment {}, used as the input attachment at index {}, is not of \
+                the scalar type {:?} read by the shader",
+                attachment, index, required,
+            ),
+            Self::InputAttachmentSamplesMismatch {
+                index,
+                attachment,
+                multisampled,
+            } => {
+                if *multisampled {
+                    write!(
+                        f,
+                        "the shader reads input attachment index {} as multisampled, but \
+                        attachment {} has a single sample",
+                        index, attachment,
+                    )
+                } else {
+                    write!(
+                        f,
+                        "the shader reads input attachment index {} as single-sampled, but \
+                        attachment {} is multisampled",
+                        index, attachment,
+                    )
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InputAttachmentRequirements, SubpassRequirements, SubpassRequirementsError};
+    use crate::{
+        format::Format,
+        render_pass::{AttachmentDescription, RenderPassBuilder},
+        shader::ShaderScalarType,
+    };
+
+    #[test]
+    fn validate() {
+        let requirements = SubpassRequirements {
+            color_attachments: vec![
+                Some(ShaderScalarType::Float),
+                None,
+                Some(ShaderScalarType::Uint),
+            ],
+            input_attachments: vec![Some(InputAttachmentRequirements {
+                scalar_type: Some(ShaderScalarType::Float),
+                multisampled: false,
+            })],
+            depth_write: true,
+        };
+        let attachment = |format| AttachmentDescription {
+            format: Some(format),
+            ..Default::default()
+        };
+
+        let create_info = RenderPassBuilder::new()
+            .attachment(attachment(Format::R8G8B8A8_UNORM))
+            .attachment(attachment(Format::R32_UINT))
+            .attachment(attachment(Format::R16G16B16A16_SFLOAT))
+            .attachment(attachment(Format::D32_SFLOAT))
+            .subpass(requirements.subpass_builder(&[0, 0, 1], &[2], Some(3)))
+            .into_create_info();
+        let subpass = &create_info.subpasses[0];
+
+        assert!(subpass.color_attachments[1].is_none());
+        assert_eq!(
+            requirements.validate(&create_info.attachments, subpass),
+            Ok(())
+        );
+
+        // Swapping the color attachments makes their scalar types mismatch.
+        let create_info = RenderPassBuilder::new()
+            .attachment(attachment(Format::R8G8B8A8_UNORM))
+            .attachment(attachment(Format::R32_UINT))
+            .attachment(attachment(Format::R16G16B16A16_SFLOAT))
+            .attachment(attachment(Format::D32_SFLOAT))
+            .subpass(requirements.subpass_builder(&[1, 0, 0], &[2], Some(3)))
+            .into_create_info();
+
+        assert_eq!(
+            requirements.validate(&create_info.attachments, &create_info.subpasses[0]),
+            Err(SubpassRequirementsError::ColorAttachmentFormatMismatch {
+                location: 0,
+                attachment: 1,
+                required: ShaderScalarType::Float,
+            })
+        );
+    }
+}
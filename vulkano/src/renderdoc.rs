@@ -0,0 +1,412 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Programmatic frame captures with [RenderDoc](https://renderdoc.org).
+//!
+//! This module wraps the in-application API of RenderDoc, which lets the application decide
+//! which frames are captured, for example the frame where a bug reproduces. It requires the
+//! `renderdoc` feature.
+//!
+//! The API is provided by the RenderDoc library, which hooks the Vulkan functions of the process.
+//! When the application is launched from RenderDoc, or RenderDoc is injected into it, the library
+//! is already loaded. Otherwise, [`RenderDoc::new`] loads it, which must then happen before the
+//! Vulkan instance is created.
+//!
+//! # Example
+//!
+//! ```
+//! use vulkano::renderdoc::RenderDoc;
+//!
+//! # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+//! let renderdoc = RenderDoc::new().ok();
+//!
+//! if let Some(renderdoc) = &renderdoc {
+//!     renderdoc.start_frame_capture(Some(queue.device()));
+//! }
+//!
+//! // Record and submit the commands of the frame to `queue`.
+//!
+//! if let Some(renderdoc) = &renderdoc {
+//!     renderdoc.end_frame_capture(Some(queue.device()));
+//! }
+//! ```
+
+use crate::{device::Device, Version, VulkanObject};
+use ash::vk::Handle;
+use shared_library::dynamic_library::DynamicLibrary;
+use std::{
+    error,
+    ffi::{CStr, CString},
+    fmt, mem,
+    os::raw::{c_char, c_int, c_void},
+    path::{Path, PathBuf},
+    ptr,
+    sync::Arc,
+};
+
+#[cfg(windows)]
+const LIBRARY_NAME: &str = "renderdoc.dll";
+#[cfg(target_os = "android")]
+const LIBRARY_NAME: &str = "libVkLayer_GLES_RenderDoc.so";
+#[cfg(not(any(windows, target_os = "android")))]
+const LIBRARY_NAME: &str = "librenderdoc.so";
+
+// `eRENDERDOC_API_Version_1_1_2`, the oldest version that provides all the functions used here.
+const API_VERSION_1_1_2: c_int = 10102;
+
+type GetApi = unsafe extern "C" fn(version: c_int, out_api_pointers: *mut *mut c_void) -> c_int;
+
+// The beginning of `RENDERDOC_API_1_1_2`. The functions that are not used are left as pointers.
+#[repr(C)]
+struct Api {
+    get_api_version: unsafe extern "C" fn(major: *mut c_int, minor: *mut c_int, patch: *mut c_int),
+    set_capture_option_u32: *const c_void,
+    set_capture_option_f32: *const c_void,
+    get_capture_option_u32: *const c_void,
+    get_capture_option_f32: *const c_void,
+    set_focus_toggle_keys: *const c_void,
+    set_capture_keys: *const c_void,
+    get_overlay_bits: *const c_void,
+    mask_overlay_bits: *const c_void,
+    remove_hooks: *const c_void,
+    unload_crash_handler: *const c_void,
+    set_capture_file_path_template: unsafe extern "C" fn(path_template: *const c_char),
+    get_capture_file_path_template: unsafe extern "C" fn() -> *const c_char,
+    get_num_captures: unsafe extern "C" fn() -> u32,
+    get_capture: unsafe extern "C" fn(
+        index: u32,
+        filename: *mut c_char,
+        path_length: *mut u32,
+        timestamp: *mut u64,
+    ) -> u32,
+    trigger_capture: unsafe extern "C" fn(),
+    is_target_control_connected: unsafe extern "C" fn() -> u32,
+    launch_replay_ui: *const c_void,
+    set_active_window: *const c_void,
+    start_frame_capture: unsafe extern "C" fn(device: *const c_void, window: *const c_void),
+    is_frame_capturing: unsafe extern "C" fn() -> u32,
+    end_frame_capture: unsafe extern "C" fn(device: *const c_void, window: *const c_void) -> u32,
+    trigger_multi_frame_capture: unsafe extern "C" fn(num_frames: u32),
+}
+
+/// The in-application API of RenderDoc.
+///
+/// See the [module-level documentation](self) for more information.
+pub struct RenderDoc {
+    // Keeps the library loaded for as long as `api` is used.
+    _library: DynamicLibrary,
+    api: *const Api,
+    api_version: Version,
+}
+
+// The functions of the API can be called from any thread.
+unsafe impl Send for RenderDoc {}
+unsafe impl Sync for RenderDoc {}
+
+impl RenderDoc {
+    /// Loads the RenderDoc library, if it is not loaded yet, and retrieves its in-application
+    /// API.
+    ///
+    /// This returns an error if RenderDoc is not installed, or is older than version 1.1.2 of the
+    /// API. If the library was not loaded yet, it must be loaded before the Vulkan instance is
+    /// created for RenderDoc to capture it.
+    pub fn new() -> Result<RenderDoc, RenderDocError> {
+        unsafe {
+            let library = DynamicLibrary::open(Some(Path::new(LIBRARY_NAME)))
+                .map_err(RenderDocError::LibraryLoadFailure)?;
+
+            let get_api: GetApi = {
+                let ptr: *mut c_void = library
+                    .symbol("RENDERDOC_GetAPI")
+                    .map_err(|_| RenderDocError::MissingEntryPoint)?;
+                mem::transmute(ptr)
+            };
+
+            let mut api: *mut c_void = ptr::null_mut();
+
+            if get_api(API_VERSION_1_1_2, &mut api) != 1 || api.is_null() {
+                return Err(RenderDocError::VersionNotSupported);
+            }
+
+            let api = api as *const Api;
+            let api_version = {
+                let (mut major, mut minor, mut patch) = (0, 0, 0);
+                ((*api).get_api_version)(&mut major, &mut minor, &mut patch);
+
+                Version {
+                    major: major as u32,
+                    minor: minor as u32,
+                    patch: patch as u32,
+                }
+            };
+
+            Ok(RenderDoc {
+                _library: library,
+                api,
+                api_version,
+            })
+        }
+    }
+
+    /// Returns the version of the API that RenderDoc provides.
+    #[inline]
+    pub fn api_version(&self) -> Version {
+        self.api_version
+    }
+
+    /// Captures the next frame that is presented to any window.
+    #[inline]
+    pub fn trigger_capture(&self) {
+        unsafe { (self.api().trigger_capture)() }
+    }
+
+    /// Captures the next `num_frames` frames that are presented to any window, each in its own
+    /// capture.
+    #[inline]
+    pub fn trigger_multi_frame_capture(&self, num_frames: u32) {
+        unsafe { (self.api().trigger_multi_frame_capture)(num_frames) }
+    }
+
+    /// Starts capturing the commands that are submitted to the queues of `device`, or of any
+    /// device if `device` is `None`, until [`end_frame_capture`](Self::end_frame_capture) is
+    /// called.
+    ///
+    /// RenderDoc identifies devices by their instance, so the devices that were created from the
+    /// same instance as `device` are captured too.
+    #[inline]
+    pub fn start_frame_capture(&self, device: Option<&Arc<Device>>) {
+        unsafe { (self.api().start_frame_capture)(device_pointer(device), ptr::null()) }
+    }
+
+    /// Returns whether a capture was started with
+    /// [`start_frame_capture`](Self::start_frame_capture) and not ended yet.
+    #[inline]
+    pub fn is_frame_capturing(&self) -> bool {
+        unsafe { (self.api().is_frame_capturing)() != 0 }
+    }
+
+    /// Ends the capture that was started with [`start_frame_capture`](Self::start_frame_capture)
+    /// with the same `device`, and writes it to a file.
+    ///
+    /// Returns `false` if there was no such capture, or if writing it failed.
+    #[inline]
+    pub fn end_frame_capture(&self, device: Option<&Arc<Device>>) -> bool {
+        unsafe { (self.api().end_frame_capture)(device_pointer(device), ptr::null()) != 0 }
+    }
+
+    /// Returns whether a RenderDoc user interface is connected to the application.
+    #[inline]
+    pub fn is_target_control_connected(&self) -> bool {
+        unsafe { (self.api().is_target_control_connected)() != 0 }
+    }
+
+    /// Returns the template of the paths of the capture files.
+    pub fn capture_file_path_template(&self) -> PathBuf {
+        unsafe {
+            let path_template = (self.api().get_capture_file_path_template)();
+
+            if path_template.is_null() {
+                PathBuf::new()
+            } else {
+                PathBuf::from(CStr::from_ptr(path_template).to_string_lossy().into_owned())
+            }
+        }
+    }
+
+    /// Sets the template of the paths of the capture files. RenderDoc appends a suffix and the
+    /// extension to it.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `path_template` is not valid UTF-8 or contains a nul character.
+    pub fn set_capture_file_path_template(&self, path_template: impl AsRef<Path>) {
+        let path_template = CString::new(
+            path_template
+                .as_ref()
+                .to_str()
+                .expect("the path template must be valid UTF-8"),
+        )
+        .expect("the path template must not contain a nul character");
+
+        unsafe { (self.api().set_capture_file_path_template)(path_template.as_ptr()) }
+    }
+
+    /// Returns the number of captures that were made since the application started.
+    #[inline]
+    pub fn num_captures(&self) -> u32 {
+        unsafe { (self.api().get_num_captures)() }
+    }
+
+    /// Returns the capture at `index`, or `None` if `index` is not less than
+    /// [`num_captures`](Self::num_captures).
+    pub fn capture(&self, index: u32) -> Option<Capture> {
+        unsafe {
+            let api = self.api();
+            let mut path_length = 0;
+
+            if (api.get_capture)(index, ptr::null_mut(), &mut path_length, ptr::null_mut()) == 0 {
+                return None;
+            }
+
+            // The length includes the nul terminator.
+            let mut path = vec![0u8; path_length as usize];
+            let mut timestamp = 0;
+
+            if (api.get_capture)(
+                index,
+                path.as_mut_ptr() as *mut c_char,
+                ptr::null_mut(),
+                &mut timestamp,
+            ) == 0
+            {
+                return None;
+            }
+
+            let len = path.iter().position(|&b| b == 0).unwrap_or(path.len());
+            let path = String::from_utf8_lossy(&path[..len]).into_owned();
+
+            Some(Capture {
+                path: PathBuf::from(path),
+                timestamp,
+            })
+        }
+    }
+
+    #[inline]
+    fn api(&self) -> &Api {
+        unsafe { &*self.api }
+    }
+}
+
+impl fmt::Debug for RenderDoc {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("RenderDoc")
+            .field("api_version", &self.api_version)
+            .finish()
+    }
+}
+
+// RenderDoc identifies a Vulkan device by the dispatch table of its instance, which is the first
+// pointer in the dispatchable instance handle.
+unsafe fn device_pointer(device: Option<&Arc<Device>>) -> *const c_void {
+    match device {
+        Some(device) => {
+            let instance = device.instance().internal_object().as_raw() as *const *const c_void;
+            *instance
+        }
+        None => ptr::null(),
+    }
+}
+
+/// A capture file that was written by RenderDoc.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Capture {
+    /// The path of the capture file.
+    pub path: PathBuf,
+
+    /// The time when the capture was made, in seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+/// Error that can happen when loading the RenderDoc API.
+#[derive(Clone, Debug)]
+pub enum RenderDocError {
+    /// Failed to load the RenderDoc library.
+    LibraryLoadFailure(String),
+
+    /// The library doesn't provide the `RENDERDOC_GetAPI` function.
+    MissingEntryPoint,
+
+    /// The version of RenderDoc doesn't provide version 1.1.2 of the API.
+    VersionNotSupported,
+}
+
+impl error::Error for RenderDocError {}
+
+impl fmt::Display for RenderDocError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::LibraryLoadFailure(err) => {
+                write!(f, "failed to load the RenderDoc library: {}", err)
+            }
+            Self::MissingEntryPoint => write!(
+                f,
+                "the library doesn't provide the `RENDERDOC_GetAPI` function",
+            ),
+            Self::VersionNotSupported => write!(
+                f,
+                "the version of RenderDoc doesn't provide version 1.1.2 of the API",
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Api, RenderDoc};
+    use crate::Version;
+    use std::{mem::size_of, os::raw::c_void};
+
+    #[test]
+    fn api_layout() {
+        // `RENDERDOC_API_1_1_2` has 23 function pointers.
+        assert_eq!(size_of::<Api>(), 23 * size_of::<*const c_void>());
+    }
+
+    #[test]
+    fn capture_file_path_template() {
+        let renderdoc = match RenderDoc::new() {
+            Ok(x) => x,
+            Err(_) => return,
+        };
+
+        assert!(
+            renderdoc.api_version()
+                >= Version {
+                    major: 1,
+                    minor: 1,
+                    patch: 2
+                }
+        );
+
+        let path_template = std::env::temp_dir().join("vulkano_renderdoc_test");
+        renderdoc.set_capture_file_path_template(&path_template);
+        assert_eq!(renderdoc.capture_file_path_template(), path_template);
+
+        assert_should_panic!("the path template must not contain a nul character", {
+            renderdoc.set_capture_file_path_template("capture\0");
+        });
+    }
+
+    #[test]
+    fn frame_capture() {
+        // The library must be loaded before the instance is created.
+        let renderdoc = match RenderDoc::new() {
+            Ok(x) => x,
+            Err(_) => return,
+        };
+        let (device, _) = gfx_dev_and_queue!();
+
+        let num_captures = renderdoc.num_captures();
+        assert!(renderdoc.capture(num_captures).is_none());
+
+        renderdoc.start_frame_capture(Some(&device));
+        assert!(renderdoc.is_frame_capturing());
+        assert!(renderdoc.end_frame_capture(Some(&device)));
+        assert!(!renderdoc.is_frame_capturing());
+
+        // There is no capture to end anymore.
+        assert!(!renderdoc.end_frame_capture(Some(&device)));
+
+        assert_eq!(renderdoc.num_captures(), num_captures + 1);
+        assert!(renderdoc.capture(num_captures).is_some());
+    }
+}
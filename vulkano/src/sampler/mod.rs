@@ -173,6 +173,18 @@ impl Sampler {
                     maximum: limit,
                 });
             }
+
+            // VUID-VkSamplerCreateInfo-samplerMipLodBias-04467
+            if mip_lod_bias != 0.0
+                && device.enabled_extensions().khr_portability_subset
+                && !device.enabled_features().sampler_mip_lod_bias
+            {
+                return Err(SamplerCreationError::FeatureNotEnabled {
+                    feature: "sampler_mip_lod_bias",
+                    reason:
+                        "this device is a portability subset device, and mip_lod_bias was not zero",
+                });
+            }
         }
 
         let (anisotropy_enable, max_anisotropy) = if let Some(max_anisotropy) = anisotropy {
@@ -402,6 +414,8 @@ impl Sampler {
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         Ok(Arc::new(Sampler {
             handle,
             device,
@@ -1460,6 +1474,32 @@ mod tests {
         assert!(!s.unnormalized_coordinates());
     }
 
+    #[test]
+    fn mip_lod_bias_portability_subset() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let result = Sampler::new(
+            device.clone(),
+            SamplerCreateInfo {
+                mip_lod_bias: 1.0,
+                ..Default::default()
+            },
+        );
+
+        // Only portability subset devices need the `sampler_mip_lod_bias` feature.
+        if device.enabled_extensions().khr_portability_subset {
+            match result {
+                Err(SamplerCreationError::FeatureNotEnabled {
+                    feature: "sampler_mip_lod_bias",
+                    ..
+                }) => (),
+                _ => panic!(),
+            }
+        } else {
+            result.unwrap();
+        }
+    }
+
     #[test]
     fn create_compare() {
         let (device, queue) = gfx_dev_and_queue!();
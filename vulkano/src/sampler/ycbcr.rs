@@ -303,6 +303,8 @@ impl SamplerYcbcrConversion {
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         Ok(Arc::new(SamplerYcbcrConversion {
             handle,
             device,
@@ -19,7 +19,7 @@
 
 use crate::check_errors;
 use crate::descriptor_set::layout::DescriptorType;
-use crate::device::Device;
+use crate::device::{Device, Features};
 use crate::format::{Format, NumericType};
 use crate::image::view::ImageViewType;
 use crate::pipeline::graphics::input_assembly::PrimitiveTopology;
@@ -73,6 +73,16 @@ impl ShaderModule {
     ) -> Result<Arc<ShaderModule>, ShaderCreationError> {
         let spirv = Spirv::new(words)?;
 
+        // The capabilities for atomic operations are satisfied by any one of several features,
+        // so the features needed by each operation are checked separately.
+        let atomic_features = reflect::atomic_features(&spirv);
+
+        if !device.enabled_features().is_superset_of(&atomic_features) {
+            return Err(ShaderCreationError::AtomicFeaturesNotEnabled {
+                missing: atomic_features.difference(device.enabled_features()),
+            });
+        }
+
         Self::from_words_with_data(
             device,
             words,
@@ -110,6 +120,8 @@ impl ShaderModule {
     ///
     /// - The SPIR-V code is not validated at all.
     /// - The provided information must match what the SPIR-V code contains.
+    /// - The features returned by [`reflect::atomic_features`] for the SPIR-V code must be
+    ///   enabled on the device.
     pub unsafe fn from_words_with_data<'a>(
         device: Arc<Device>,
         words: &[u32],
@@ -162,6 +174,8 @@ impl ShaderModule {
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         let entries = entry_points.into_iter().collect::<Vec<_>>();
         let entry_points = entries
             .iter()
@@ -280,6 +294,13 @@ impl Drop for ShaderModule {
 #[derive(Clone, Debug)]
 pub enum ShaderCreationError {
     OomError(OomError),
+
+    /// The shader performs atomic operations that require features that are not enabled on the
+    /// device.
+    AtomicFeaturesNotEnabled {
+        missing: Features,
+    },
+
     SpirvCapabilityNotSupported {
         capability: Capability,
         reason: ShaderSupportError,
@@ -300,6 +321,7 @@ impl Error for ShaderCreationError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
             Self::OomError(err) => Some(err),
+            Self::AtomicFeaturesNotEnabled { .. } => None,
             Self::SpirvCapabilityNotSupported { reason, .. } => Some(reason),
             Self::SpirvError(err) => Some(err),
             Self::SpirvExtensionNotSupported { reason, .. } => Some(reason),
@@ -312,6 +334,10 @@ impl Display for ShaderCreationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::AtomicFeaturesNotEnabled { .. } => write!(
+                f,
+                "the shader performs atomic operations that require features that are not enabled on the device",
+            ),
             Self::SpirvCapabilityNotSupported { capability, .. } => write!(
                 f,
                 "the SPIR-V capability {:?} enabled by the shader is not supported by the device",
@@ -375,6 +401,7 @@ pub struct EntryPointInfo {
     pub specialization_constant_requirements: HashMap<u32, SpecializationConstantRequirements>,
     pub input_interface: ShaderInterface,
     pub output_interface: ShaderInterface,
+    pub uses_view_index: bool,
 }
 
 /// Represents a shader entry point in a shader module.
@@ -445,6 +472,15 @@ impl<'a> EntryPoint<'a> {
     pub fn output_interface(&self) -> &ShaderInterface {
         &self.info.output_interface
     }
+
+    /// Returns whether the shader reads the `ViewIndex` built-in, which gives the index of the
+    /// view being rendered to when multiview is enabled.
+    ///
+    /// When multiview is not enabled, the view index is always `0`.
+    #[inline]
+    pub fn uses_view_index(&self) -> bool {
+        self.info.uses_view_index
+    }
 }
 
 /// The mode in which a shader executes. This includes both information about the shader type/stage,
@@ -455,7 +491,7 @@ pub enum ShaderExecution {
     TessellationControl,
     TessellationEvaluation,
     Geometry(GeometryShaderExecution),
-    Fragment,
+    Fragment(FragmentShaderExecution),
     Compute,
     RayGeneration,
     AnyHit,
@@ -488,6 +524,15 @@ pub struct GeometryShaderExecution {
     pub output: GeometryShaderOutput,*/
 }
 
+/// The mode in which a fragment shader executes.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FragmentShaderExecution {
+    /// Whether the shader may write the depth of fragments, replacing the depth that is
+    /// interpolated from the primitive. This corresponds to the `DepthReplacing` execution mode,
+    /// and to writing `gl_FragDepth` in GLSL.
+    pub depth_replacing: bool,
+}
+
 /// The input primitive type that is expected by a geometry shader.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum GeometryShaderInput {
@@ -561,6 +606,11 @@ pub struct DescriptorRequirements {
     /// This is `None` for non-image descriptors.
     pub image_view_type: Option<ImageViewType>,
 
+    /// For input attachment bindings, the input attachment index that the first descriptor reads
+    /// from. Each following descriptor in an array reads from the next index.
+    /// This is `None` for other descriptors.
+    pub input_attachment_index: Option<u32>,
+
     /// For sampler bindings, the descriptor indices that require a depth comparison sampler.
     pub sampler_compare: HashSet<u32>,
 
@@ -654,6 +704,7 @@ impl DescriptorRequirements {
             image_multisampled: self.image_multisampled,
             image_scalar_type: self.image_scalar_type.or(other.image_scalar_type),
             image_view_type: self.image_view_type.or(other.image_view_type),
+            input_attachment_index: self.input_attachment_index.or(other.input_attachment_index),
             sampler_compare: &self.sampler_compare | &other.sampler_compare,
             sampler_no_unnormalized_coordinates: &self.sampler_no_unnormalized_coordinates
                 | &other.sampler_no_unnormalized_coordinates,
@@ -1066,7 +1117,7 @@ impl From<ShaderExecution> for ShaderStage {
             ShaderExecution::TessellationControl => Self::TessellationControl,
             ShaderExecution::TessellationEvaluation => Self::TessellationEvaluation,
             ShaderExecution::Geometry(_) => Self::Geometry,
-            ShaderExecution::Fragment => Self::Fragment,
+            ShaderExecution::Fragment(_) => Self::Fragment,
             ShaderExecution::Compute => Self::Compute,
             ShaderExecution::RayGeneration => Self::Raygen,
             ShaderExecution::AnyHit => Self::AnyHit,
@@ -1482,3 +1533,170 @@ fn check_spirv_version(device: &Device, mut version: Version) -> Result<(), Shad
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{reflect, spirv::Spirv, ShaderCreationError, ShaderModule};
+    use crate::device::Features;
+
+    /*
+        OpCapability Shader
+        OpCapability AtomicFloat32AddEXT
+        OpExtension "SPV_EXT_shader_atomic_float_add"
+        OpMemoryModel Logical GLSL450
+        OpEntryPoint GLCompute %main "main"
+        OpExecutionMode %main LocalSize 1 1 1
+        OpDecorate %Block BufferBlock
+        OpMemberDecorate %Block 0 Offset 0
+        OpDecorate %buf DescriptorSet 0
+        OpDecorate %buf Binding 0
+        %void = OpTypeVoid
+        %fn = OpTypeFunction %void
+        %float = OpTypeFloat 32
+        %uint = OpTypeInt 32 0
+        %Block = OpTypeStruct %float
+        %ptr_Block = OpTypePointer Uniform %Block
+        %ptr_float = OpTypePointer Uniform %float
+        %buf = OpVariable %ptr_Block Uniform
+        %uint_0 = OpConstant %uint 0
+        %uint_1 = OpConstant %uint 1
+        %float_1 = OpConstant %float 1.0
+        %main = OpFunction %void None %fn
+        %entry = OpLabel
+        %ptr = OpAccessChain %ptr_float %buf %uint_0
+        %old = OpAtomicFAddEXT %float %ptr %uint_1 %uint_0 %float_1
+        OpReturn
+        OpFunctionEnd
+    */
+    const FLOAT_ADD_MODULE: [u32; 108] = [
+        0x07230203, 0x00010000, 0x00000000, 0x00000010, 0x00000000, 0x00020011, 0x00000001,
+        0x00020011, 0x00001791, 0x0009000a, 0x5f565053, 0x5f545845, 0x64616873, 0x615f7265,
+        0x696d6f74, 0x6c665f63, 0x5f74616f, 0x00646461, 0x0003000e, 0x00000000, 0x00000001,
+        0x0005000f, 0x00000005, 0x00000001, 0x6e69616d, 0x00000000, 0x00060010, 0x00000001,
+        0x00000011, 0x00000001, 0x00000001, 0x00000001, 0x00030047, 0x00000002, 0x00000003,
+        0x00050048, 0x00000002, 0x00000000, 0x00000023, 0x00000000, 0x00040047, 0x00000003,
+        0x00000022, 0x00000000, 0x00040047, 0x00000003, 0x00000021, 0x00000000, 0x00020013,
+        0x00000004, 0x00030021, 0x00000005, 0x00000004, 0x00030016, 0x00000006, 0x00000020,
+        0x00040015, 0x00000007, 0x00000020, 0x00000000, 0x0003001e, 0x00000002, 0x00000006,
+        0x00040020, 0x00000008, 0x00000002, 0x00000002, 0x00040020, 0x00000009, 0x00000002,
+        0x00000006, 0x0004003b, 0x00000008, 0x00000003, 0x00000002, 0x0004002b, 0x00000007,
+        0x0000000a, 0x00000000, 0x0004002b, 0x00000007, 0x0000000b, 0x00000001, 0x0004002b,
+        0x00000006, 0x0000000c, 0x3f800000, 0x00050036, 0x00000004, 0x00000001, 0x00000000,
+        0x00000005, 0x000200f8, 0x0000000d, 0x00050041, 0x00000009, 0x0000000e, 0x00000003,
+        0x0000000a, 0x00071793, 0x00000006, 0x0000000f, 0x0000000e, 0x0000000b, 0x0000000a,
+        0x0000000c, 0x000100fd, 0x00010038,
+    ];
+
+    /*
+        OpCapability Shader
+        OpCapability Int64
+        OpCapability Int64Atomics
+        OpMemoryModel Logical GLSL450
+        OpEntryPoint GLCompute %main "main"
+        OpExecutionMode %main LocalSize 1 1 1
+        %void = OpTypeVoid
+        %fn = OpTypeFunction %void
+        %ulong = OpTypeInt 64 0
+        %uint = OpTypeInt 32 0
+        %ptr_ulong = OpTypePointer Workgroup %ulong
+        %shared = OpVariable %ptr_ulong Workgroup
+        %uint_0 = OpConstant %uint 0
+        %uint_2 = OpConstant %uint 2
+        %ulong_1 = OpConstant %ulong 1 0
+        %main = OpFunction %void None %fn
+        %entry = OpLabel
+        %old = OpAtomicIAdd %ulong %shared %uint_2 %uint_0 %ulong_1
+        OpReturn
+        OpFunctionEnd
+    */
+    const INT64_SHARED_MODULE: [u32; 75] = [
+        0x07230203, 0x00010000, 0x00000000, 0x0000000d, 0x00000000, 0x00020011, 0x00000001,
+        0x00020011, 0x0000000b, 0x00020011, 0x0000000c, 0x0003000e, 0x00000000, 0x00000001,
+        0x0005000f, 0x00000005, 0x00000001, 0x6e69616d, 0x00000000, 0x00060010, 0x00000001,
+        0x00000011, 0x00000001, 0x00000001, 0x00000001, 0x00020013, 0x00000002, 0x00030021,
+        0x00000003, 0x00000002, 0x00040015, 0x00000004, 0x00000040, 0x00000000, 0x00040015,
+        0x00000005, 0x00000020, 0x00000000, 0x00040020, 0x00000006, 0x00000004, 0x00000004,
+        0x0004003b, 0x00000006, 0x00000007, 0x00000004, 0x0004002b, 0x00000005, 0x00000008,
+        0x00000000, 0x0004002b, 0x00000005, 0x00000009, 0x00000002, 0x0005002b, 0x00000004,
+        0x0000000a, 0x00000001, 0x00000000, 0x00050036, 0x00000002, 0x00000001, 0x00000000,
+        0x00000003, 0x000200f8, 0x0000000b, 0x000700ea, 0x00000004, 0x0000000c, 0x00000007,
+        0x00000009, 0x00000008, 0x0000000a, 0x000100fd, 0x00010038,
+    ];
+
+    /*
+        OpCapability Shader
+        OpCapability MultiView
+        OpExtension "SPV_KHR_multiview"
+        OpMemoryModel Logical GLSL450
+        OpEntryPoint Vertex %main "main" %view_index
+        OpDecorate %view_index BuiltIn ViewIndex
+        %void = OpTypeVoid
+        %fn = OpTypeFunction %void
+        %int = OpTypeInt 32 1
+        %ptr_int = OpTypePointer Input %int
+        %view_index = OpVariable %ptr_int Input
+        %main = OpFunction %void None %fn
+        %entry = OpLabel
+        %index = OpLoad %int %view_index
+        OpReturn
+        OpFunctionEnd
+    */
+    const VIEW_INDEX_MODULE: [u32; 58] = [
+        0x07230203, 0x00010000, 0x00000000, 0x00000009, 0x00000000, 0x00020011, 0x00000001,
+        0x00020011, 0x00001157, 0x0006000a, 0x5f565053, 0x5f52484b, 0x746c756d, 0x65697669,
+        0x00000077, 0x0003000e, 0x00000000, 0x00000001, 0x0006000f, 0x00000000, 0x00000001,
+        0x6e69616d, 0x00000000, 0x00000002, 0x00040047, 0x00000002, 0x0000000b, 0x00001158,
+        0x00020013, 0x00000003, 0x00030021, 0x00000004, 0x00000003, 0x00040015, 0x00000005,
+        0x00000020, 0x00000001, 0x00040020, 0x00000006, 0x00000001, 0x00000005, 0x0004003b,
+        0x00000006, 0x00000002, 0x00000001, 0x00050036, 0x00000003, 0x00000001, 0x00000000,
+        0x00000004, 0x000200f8, 0x00000007, 0x0004003d, 0x00000005, 0x00000008, 0x00000002,
+        0x000100fd, 0x00010038,
+    ];
+
+    #[test]
+    fn atomic_features_float_add() {
+        let spirv = Spirv::new(&FLOAT_ADD_MODULE).unwrap();
+        assert_eq!(
+            reflect::atomic_features(&spirv),
+            Features {
+                shader_buffer_float32_atomic_add: true,
+                ..Features::none()
+            },
+        );
+    }
+
+    #[test]
+    fn atomic_features_int64_shared() {
+        let spirv = Spirv::new(&INT64_SHARED_MODULE).unwrap();
+        assert_eq!(
+            reflect::atomic_features(&spirv),
+            Features {
+                shader_shared_int64_atomics: true,
+                ..Features::none()
+            },
+        );
+    }
+
+    #[test]
+    fn uses_view_index() {
+        let spirv = Spirv::new(&VIEW_INDEX_MODULE).unwrap();
+        let (_, _, info) = reflect::entry_points(&spirv).next().unwrap();
+        assert!(info.uses_view_index);
+
+        let spirv = Spirv::new(&FLOAT_ADD_MODULE).unwrap();
+        let (_, _, info) = reflect::entry_points(&spirv).next().unwrap();
+        assert!(!info.uses_view_index);
+    }
+
+    #[test]
+    fn atomic_features_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match unsafe { ShaderModule::from_words(device, &FLOAT_ADD_MODULE) } {
+            Err(ShaderCreationError::AtomicFeaturesNotEnabled { missing }) => {
+                assert!(missing.shader_buffer_float32_atomic_add)
+            }
+            _ => panic!(),
+        }
+    }
+}
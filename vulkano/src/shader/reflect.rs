@@ -10,6 +10,7 @@
 //! Extraction of information from SPIR-V modules, that is needed by the rest of Vulkano.
 
 use crate::descriptor_set::layout::DescriptorType;
+use crate::device::Features;
 use crate::image::view::ImageViewType;
 use crate::shader::ShaderScalarType;
 use crate::DeviceSize;
@@ -17,12 +18,13 @@ use crate::{
     pipeline::layout::PushConstantRange,
     shader::{
         spirv::{
-            Capability, Decoration, Dim, ExecutionMode, ExecutionModel, Id, Instruction, Spirv,
-            StorageClass,
+            BuiltIn, Capability, Decoration, Dim, ExecutionMode, ExecutionModel, Id, Instruction,
+            Spirv, StorageClass,
         },
-        DescriptorIdentifier, DescriptorRequirements, EntryPointInfo, GeometryShaderExecution,
-        GeometryShaderInput, ShaderExecution, ShaderInterface, ShaderInterfaceEntry,
-        ShaderInterfaceEntryType, ShaderStage, SpecializationConstantRequirements,
+        DescriptorIdentifier, DescriptorRequirements, EntryPointInfo, FragmentShaderExecution,
+        GeometryShaderExecution, GeometryShaderInput, ShaderExecution, ShaderInterface,
+        ShaderInterfaceEntry, ShaderInterfaceEntryType, ShaderStage,
+        SpecializationConstantRequirements,
     },
 };
 use std::borrow::Cow;
@@ -48,6 +50,173 @@ pub fn spirv_extensions<'a>(spirv: &'a Spirv) -> impl Iterator<Item = &'a str> {
         })
 }
 
+/// Returns the device features that are required by the atomic operations in `spirv`.
+///
+/// The SPIR-V capabilities for atomic operations only require one of several features to be
+/// enabled, while which one is actually needed depends on the storage class and the type that
+/// the operation is performed on. Some atomic operations on floats don't need a capability at
+/// all.
+pub fn atomic_features(spirv: &Spirv) -> Features {
+    #[derive(Clone, Copy)]
+    enum Storage {
+        Buffer,
+        Shared,
+        Image,
+    }
+
+    #[derive(Clone, Copy)]
+    enum Operation {
+        Add,
+        MinMax,
+        Other,
+    }
+
+    // Returns the storage class and pointee type of a pointer.
+    fn pointer_type(spirv: &Spirv, pointer: Id) -> Option<(StorageClass, Id)> {
+        let result_type_id = match *spirv.id(pointer).instruction() {
+            Instruction::Variable { result_type_id, .. }
+            | Instruction::AccessChain { result_type_id, .. }
+            | Instruction::InBoundsAccessChain { result_type_id, .. }
+            | Instruction::PtrAccessChain { result_type_id, .. }
+            | Instruction::ImageTexelPointer { result_type_id, .. }
+            | Instruction::FunctionParameter { result_type_id, .. }
+            | Instruction::CopyObject { result_type_id, .. } => result_type_id,
+            _ => return None,
+        };
+
+        match *spirv.id(result_type_id).instruction() {
+            Instruction::TypePointer {
+                ref storage_class,
+                ty,
+                ..
+            } => Some((storage_class.clone(), ty)),
+            _ => None,
+        }
+    }
+
+    let mut features = Features::none();
+
+    for instruction in spirv.instructions() {
+        let (pointer, operation) = match *instruction {
+            Instruction::AtomicLoad { pointer, .. }
+            | Instruction::AtomicStore { pointer, .. }
+            | Instruction::AtomicExchange { pointer, .. }
+            | Instruction::AtomicCompareExchange { pointer, .. }
+            | Instruction::AtomicCompareExchangeWeak { pointer, .. }
+            | Instruction::AtomicIIncrement { pointer, .. }
+            | Instruction::AtomicIDecrement { pointer, .. }
+            | Instruction::AtomicIAdd { pointer, .. }
+            | Instruction::AtomicISub { pointer, .. }
+            | Instruction::AtomicSMin { pointer, .. }
+            | Instruction::AtomicUMin { pointer, .. }
+            | Instruction::AtomicSMax { pointer, .. }
+            | Instruction::AtomicUMax { pointer, .. }
+            | Instruction::AtomicAnd { pointer, .. }
+            | Instruction::AtomicOr { pointer, .. }
+            | Instruction::AtomicXor { pointer, .. } => (pointer, Operation::Other),
+            Instruction::AtomicFAddEXT { pointer, .. } => (pointer, Operation::Add),
+            Instruction::AtomicFMinEXT { pointer, .. }
+            | Instruction::AtomicFMaxEXT { pointer, .. } => (pointer, Operation::MinMax),
+            _ => continue,
+        };
+
+        let (storage_class, pointee) = match pointer_type(spirv, pointer) {
+            Some(x) => x,
+            None => continue,
+        };
+
+        let storage = match storage_class {
+            StorageClass::StorageBuffer
+            | StorageClass::Uniform
+            | StorageClass::PhysicalStorageBuffer => Storage::Buffer,
+            StorageClass::Workgroup => Storage::Shared,
+            StorageClass::Image => Storage::Image,
+            _ => continue,
+        };
+
+        match *spirv.id(pointee).instruction() {
+            Instruction::TypeInt { width: 64, .. } => match storage {
+                Storage::Buffer => features.shader_buffer_int64_atomics = true,
+                Storage::Shared => features.shader_shared_int64_atomics = true,
+                Storage::Image => features.shader_image_int64_atomics = true,
+            },
+            Instruction::TypeFloat { width, .. } => {
+                let feature = match (storage, width, operation) {
+                    (Storage::Buffer, 16, Operation::Other) => {
+                        &mut features.shader_buffer_float16_atomics
+                    }
+                    (Storage::Buffer, 16, Operation::Add) => {
+                        &mut features.shader_buffer_float16_atomic_add
+                    }
+                    (Storage::Buffer, 16, Operation::MinMax) => {
+                        &mut features.shader_buffer_float16_atomic_min_max
+                    }
+                    (Storage::Buffer, 32, Operation::Other) => {
+                        &mut features.shader_buffer_float32_atomics
+                    }
+                    (Storage::Buffer, 32, Operation::Add) => {
+                        &mut features.shader_buffer_float32_atomic_add
+                    }
+                    (Storage::Buffer, 32, Operation::MinMax) => {
+                        &mut features.shader_buffer_float32_atomic_min_max
+                    }
+                    (Storage::Buffer, 64, Operation::Other) => {
+                        &mut features.shader_buffer_float64_atomics
+                    }
+                    (Storage::Buffer, 64, Operation::Add) => {
+                        &mut features.shader_buffer_float64_atomic_add
+                    }
+                    (Storage::Buffer, 64, Operation::MinMax) => {
+                        &mut features.shader_buffer_float64_atomic_min_max
+                    }
+                    (Storage::Shared, 16, Operation::Other) => {
+                        &mut features.shader_shared_float16_atomics
+                    }
+                    (Storage::Shared, 16, Operation::Add) => {
+                        &mut features.shader_shared_float16_atomic_add
+                    }
+                    (Storage::Shared, 16, Operation::MinMax) => {
+                        &mut features.shader_shared_float16_atomic_min_max
+                    }
+                    (Storage::Shared, 32, Operation::Other) => {
+                        &mut features.shader_shared_float32_atomics
+                    }
+                    (Storage::Shared, 32, Operation::Add) => {
+                        &mut features.shader_shared_float32_atomic_add
+                    }
+                    (Storage::Shared, 32, Operation::MinMax) => {
+                        &mut features.shader_shared_float32_atomic_min_max
+                    }
+                    (Storage::Shared, 64, Operation::Other) => {
+                        &mut features.shader_shared_float64_atomics
+                    }
+                    (Storage::Shared, 64, Operation::Add) => {
+                        &mut features.shader_shared_float64_atomic_add
+                    }
+                    (Storage::Shared, 64, Operation::MinMax) => {
+                        &mut features.shader_shared_float64_atomic_min_max
+                    }
+                    (Storage::Image, 32, Operation::Other) => {
+                        &mut features.shader_image_float32_atomics
+                    }
+                    (Storage::Image, 32, Operation::Add) => {
+                        &mut features.shader_image_float32_atomic_add
+                    }
+                    (Storage::Image, 32, Operation::MinMax) => {
+                        &mut features.shader_image_float32_atomic_min_max
+                    }
+                    _ => continue,
+                };
+
+                *feature = true;
+            }
+            _ => (),
+        }
+    }
+
+    features
+}
+
 /// Returns an iterator over all entry points in `spirv`, with information about the entry point.
 pub fn entry_points<'a>(
     spirv: &'a Spirv,
@@ -95,6 +264,7 @@ pub fn entry_points<'a>(
             StorageClass::Output,
             matches!(execution_model, ExecutionModel::TessellationControl),
         );
+        let uses_view_index = uses_built_in(&spirv, interface, BuiltIn::ViewIndex);
 
         Some((
             entry_point_name.clone(),
@@ -106,11 +276,27 @@ pub fn entry_points<'a>(
                 specialization_constant_requirements,
                 input_interface,
                 output_interface,
+                uses_view_index,
             },
         ))
     })
 }
 
+/// Returns true if one of the variables in `interface` is decorated with the built-in `built_in`.
+fn uses_built_in(spirv: &Spirv, interface: &[Id], built_in: BuiltIn) -> bool {
+    interface.iter().any(|&id| {
+        spirv.id(id).iter_decoration().any(|instruction| {
+            matches!(
+                instruction,
+                Instruction::Decorate {
+                    decoration: Decoration::BuiltIn { built_in: b },
+                    ..
+                } if *b == built_in
+            )
+        })
+    })
+}
+
 /// Extracts the `ShaderExecution` for the entry point `function_id` from `spirv`.
 fn shader_execution(
     spirv: &Spirv,
@@ -150,7 +336,20 @@ fn shader_execution(
             ShaderExecution::Geometry(GeometryShaderExecution { input })
         }
 
-        ExecutionModel::Fragment => ShaderExecution::Fragment,
+        ExecutionModel::Fragment => {
+            let depth_replacing = spirv.iter_execution_mode().into_iter().any(|instruction| {
+                matches!(
+                    instruction,
+                    Instruction::ExecutionMode {
+                        entry_point,
+                        mode: ExecutionMode::DepthReplacing,
+                        ..
+                    } if *entry_point == function_id
+                )
+            });
+
+            ShaderExecution::Fragment(FragmentShaderExecution { depth_replacing })
+        }
 
         ExecutionModel::GLCompute => ShaderExecution::Compute,
 
@@ -883,6 +1082,17 @@ fn descriptor_requirements_of(spirv: &Spirv, variable_id: Id) -> DescriptorVaria
         };
     }
 
+    reqs.input_attachment_index =
+        variable_id_info
+            .iter_decoration()
+            .find_map(|instruction| match instruction {
+                Instruction::Decorate {
+                    decoration: Decoration::InputAttachmentIndex { attachment_index },
+                    ..
+                } => Some(*attachment_index),
+                _ => None,
+            });
+
     DescriptorVariable {
         set: variable_id_info
             .iter_decoration()
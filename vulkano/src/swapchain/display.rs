@@ -9,8 +9,8 @@
 
 //! Allows you to create surfaces that fill a whole display, outside of the windowing system.
 //!
-//! **As far as the author knows, no existing device supports these features. Therefore the code
-//! here is mostly a draft and needs rework in both the API and the implementation.**
+//! This requires the `khr_display` instance extension, and is mostly useful for embedded
+//! applications that run without a window system.
 //!
 //! The purpose of the objects in this module is to let you create a `Surface` object that
 //! represents a location on the screen. This is done in four steps:
@@ -20,31 +20,37 @@
 //!   `Display::enumerate`.
 //! - Choose a `DisplayMode`, which is the combination of a display, a resolution and a refresh
 //!   rate. You can enumerate the modes available on a display with `Display::display_modes`, or
-//!   attempt to create your own mode with `TODO`.
-//! - Choose a `DisplayPlane`. A display can show multiple planes in a stacking fashion.
+//!   attempt to create your own mode with `DisplayMode::new`.
+//! - Choose a `DisplayPlane`. A display can show multiple planes in a stacking fashion. The
+//!   capabilities of a plane for a mode can be queried with `DisplayPlane::capabilities`.
 //! - Create a `Surface` object with `Surface::from_display_plane` and pass the chosen `DisplayMode`
-//!   and `DisplayPlane`.
-
-#![allow(dead_code)] // TODO: this module isn't finished
-#![allow(unused_variables)] // TODO: this module isn't finished
+//!   and `DisplayPlane`, along with a `DisplaySurfaceCreateInfo`.
+//!
+//! When a display is controlled by a window system, it must first be taken from it. With the
+//! `ext_acquire_xlib_display` extension, a display can be acquired from an X server with
+//! `Display::acquire_xlib`. With the `ext_direct_mode_display` extension, `Display::release`
+//! gives it back.
 
 use crate::check_errors;
 use crate::device::physical::PhysicalDevice;
 use crate::instance::Instance;
-use crate::swapchain::SupportedSurfaceTransforms;
+use crate::swapchain::{SupportedSurfaceTransforms, SurfaceTransform};
+use crate::Error;
 use crate::OomError;
 use crate::Success;
 use crate::VulkanObject;
+use std::error;
 use std::ffi::CStr;
 use std::fmt::Formatter;
+use std::mem::MaybeUninit;
+use std::os::raw::c_ulong;
 use std::sync::Arc;
 use std::vec::IntoIter;
 use std::{fmt, ptr};
 
-// TODO: extract this to a `display` module and solve the visibility problems
-
-/// ?
-// TODO: plane capabilities
+/// A plane of a physical device, on which the images of a display surface are shown.
+///
+/// A display can show multiple planes in a stacking fashion.
 // TODO: store properties in the instance?
 pub struct DisplayPlane {
     instance: Arc<Instance>,
@@ -56,10 +62,15 @@ pub struct DisplayPlane {
 
 impl DisplayPlane {
     /// See the docs of enumerate().
-    pub fn enumerate_raw(device: PhysicalDevice) -> Result<IntoIter<DisplayPlane>, OomError> {
-        let fns = device.instance().fns();
+    pub fn enumerate_raw(device: PhysicalDevice) -> Result<IntoIter<DisplayPlane>, DisplayError> {
+        if !device.instance().enabled_extensions().khr_display {
+            return Err(DisplayError::ExtensionNotEnabled {
+                extension: "khr_display",
+                reason: "enumerated the display planes of a physical device",
+            });
+        }
 
-        assert!(device.instance().enabled_extensions().khr_display); // TODO: return error instead
+        let fns = device.instance().fns();
 
         let display_plane_properties = unsafe {
             loop {
@@ -91,7 +102,7 @@ impl DisplayPlane {
         Ok(display_plane_properties
             .into_iter()
             .enumerate()
-            .map(|(index, prop)| {
+            .map(|(index, prop)| -> Result<DisplayPlane, DisplayError> {
                 let supported_displays = unsafe {
                     loop {
                         let mut count = 0;
@@ -100,8 +111,7 @@ impl DisplayPlane {
                             index as u32,
                             &mut count,
                             ptr::null_mut(),
-                        ))
-                        .unwrap(); // TODO: shouldn't unwrap
+                        ))?;
 
                         let mut displays = Vec::with_capacity(count as usize);
                         let result = check_errors((fns
@@ -111,8 +121,7 @@ impl DisplayPlane {
                             index as u32,
                             &mut count,
                             displays.as_mut_ptr(),
-                        ))
-                        .unwrap(); // TODO: shouldn't unwrap
+                        ))?;
 
                         if !matches!(result, Success::Incomplete) {
                             displays.set_len(count as usize);
@@ -121,15 +130,15 @@ impl DisplayPlane {
                     }
                 };
 
-                DisplayPlane {
+                Ok(DisplayPlane {
                     instance: device.instance().clone(),
                     physical_device: device.index(),
                     index: index as u32,
                     properties: prop,
                     supported_displays,
-                }
+                })
             })
-            .collect::<Vec<_>>()
+            .collect::<Result<Vec<_>, DisplayError>>()?
             .into_iter())
     }
 
@@ -137,6 +146,7 @@ impl DisplayPlane {
     ///
     /// # Panic
     ///
+    /// - Panics if the `khr_display` extension is not enabled on the instance.
     /// - Panics if the device or host ran out of memory.
     ///
     // TODO: move iterator creation here from raw constructor?
@@ -157,6 +167,13 @@ impl DisplayPlane {
         self.index
     }
 
+    /// Returns the position of the plane in the stack of planes of its current display, at the
+    /// time the plane was enumerated.
+    #[inline]
+    pub fn current_stack_index(&self) -> u32 {
+        self.properties.current_stack_index
+    }
+
     /// Returns true if this plane supports the given display.
     #[inline]
     pub fn supports(&self, display: &Display) -> bool {
@@ -170,6 +187,200 @@ impl DisplayPlane {
             .find(|&&d| d == display.internal_object())
             .is_some()
     }
+
+    /// Returns the capabilities of this plane when it is used with `display_mode`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `display_mode` and this plane don't belong to the same physical device.
+    pub fn capabilities(
+        &self,
+        display_mode: &DisplayMode,
+    ) -> Result<DisplayPlaneCapabilities, OomError> {
+        assert_eq!(
+            self.physical_device().internal_object(),
+            display_mode.display().physical_device().internal_object()
+        );
+
+        let capabilities = unsafe {
+            let fns = self.instance.fns();
+            let mut output = MaybeUninit::uninit();
+            check_errors((fns.khr_display.get_display_plane_capabilities_khr)(
+                self.physical_device().internal_object(),
+                display_mode.internal_object(),
+                self.index,
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(DisplayPlaneCapabilities {
+            supported_alpha: capabilities.supported_alpha.into(),
+            min_src_position: [
+                capabilities.min_src_position.x,
+                capabilities.min_src_position.y,
+            ],
+            max_src_position: [
+                capabilities.max_src_position.x,
+                capabilities.max_src_position.y,
+            ],
+            min_src_extent: [
+                capabilities.min_src_extent.width,
+                capabilities.min_src_extent.height,
+            ],
+            max_src_extent: [
+                capabilities.max_src_extent.width,
+                capabilities.max_src_extent.height,
+            ],
+            min_dst_position: [
+                capabilities.min_dst_position.x,
+                capabilities.min_dst_position.y,
+            ],
+            max_dst_position: [
+                capabilities.max_dst_position.x,
+                capabilities.max_dst_position.y,
+            ],
+            min_dst_extent: [
+                capabilities.min_dst_extent.width,
+                capabilities.min_dst_extent.height,
+            ],
+            max_dst_extent: [
+                capabilities.max_dst_extent.width,
+                capabilities.max_dst_extent.height,
+            ],
+        })
+    }
+}
+
+/// The capabilities of a display plane when it is used with a specific display mode.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct DisplayPlaneCapabilities {
+    /// The alpha modes that are supported when creating a surface on the plane.
+    pub supported_alpha: SupportedDisplayPlaneAlpha,
+
+    /// The minimum source rectangle offset supported by the plane.
+    pub min_src_position: [i32; 2],
+
+    /// The maximum source rectangle offset supported by the plane.
+    pub max_src_position: [i32; 2],
+
+    /// The minimum source rectangle size supported by the plane.
+    pub min_src_extent: [u32; 2],
+
+    /// The maximum source rectangle size supported by the plane.
+    pub max_src_extent: [u32; 2],
+
+    /// The minimum destination rectangle offset supported by the plane.
+    pub min_dst_position: [i32; 2],
+
+    /// The maximum destination rectangle offset supported by the plane.
+    pub max_dst_position: [i32; 2],
+
+    /// The minimum destination rectangle size supported by the plane.
+    pub min_dst_extent: [u32; 2],
+
+    /// The maximum destination rectangle size supported by the plane.
+    pub max_dst_extent: [u32; 2],
+}
+
+/// How the alpha values of the pixels of a display surface are treated.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+#[non_exhaustive]
+pub enum DisplayPlaneAlpha {
+    /// The alpha channel of the image is ignored. All the pixels are considered as if they have a
+    /// value of 1.0.
+    Opaque = ash::vk::DisplayPlaneAlphaFlagsKHR::OPAQUE.as_raw(),
+
+    /// The [`global_alpha`](DisplaySurfaceCreateInfo::global_alpha) value is used for all the
+    /// pixels, and the alpha channel of the image is ignored.
+    Global = ash::vk::DisplayPlaneAlphaFlagsKHR::GLOBAL.as_raw(),
+
+    /// The alpha channel of the image is respected. The color channels will be multiplied by the
+    /// alpha value before being added to what is behind.
+    PerPixel = ash::vk::DisplayPlaneAlphaFlagsKHR::PER_PIXEL.as_raw(),
+
+    /// The alpha channel of the image is respected. The color channels are expected to have
+    /// already been multiplied by the alpha value.
+    PerPixelPremultiplied = ash::vk::DisplayPlaneAlphaFlagsKHR::PER_PIXEL_PREMULTIPLIED.as_raw(),
+}
+
+impl From<DisplayPlaneAlpha> for ash::vk::DisplayPlaneAlphaFlagsKHR {
+    #[inline]
+    fn from(val: DisplayPlaneAlpha) -> Self {
+        Self::from_raw(val as u32)
+    }
+}
+
+/// List of supported display plane alpha modes.
+///
+/// See the docs of `DisplayPlaneAlpha`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SupportedDisplayPlaneAlpha {
+    pub opaque: bool,
+    pub global: bool,
+    pub per_pixel: bool,
+    pub per_pixel_premultiplied: bool,
+}
+
+impl From<ash::vk::DisplayPlaneAlphaFlagsKHR> for SupportedDisplayPlaneAlpha {
+    #[inline]
+    fn from(val: ash::vk::DisplayPlaneAlphaFlagsKHR) -> SupportedDisplayPlaneAlpha {
+        let mut result = SupportedDisplayPlaneAlpha::none();
+        if !(val & ash::vk::DisplayPlaneAlphaFlagsKHR::OPAQUE).is_empty() {
+            result.opaque = true;
+        }
+        if !(val & ash::vk::DisplayPlaneAlphaFlagsKHR::GLOBAL).is_empty() {
+            result.global = true;
+        }
+        if !(val & ash::vk::DisplayPlaneAlphaFlagsKHR::PER_PIXEL).is_empty() {
+            result.per_pixel = true;
+        }
+        if !(val & ash::vk::DisplayPlaneAlphaFlagsKHR::PER_PIXEL_PREMULTIPLIED).is_empty() {
+            result.per_pixel_premultiplied = true;
+        }
+        result
+    }
+}
+
+impl SupportedDisplayPlaneAlpha {
+    /// Builds a `SupportedDisplayPlaneAlpha` with all fields set to false.
+    #[inline]
+    pub fn none() -> SupportedDisplayPlaneAlpha {
+        SupportedDisplayPlaneAlpha {
+            opaque: false,
+            global: false,
+            per_pixel: false,
+            per_pixel_premultiplied: false,
+        }
+    }
+
+    /// Returns true if the given `DisplayPlaneAlpha` is in this list.
+    #[inline]
+    pub fn supports(&self, value: DisplayPlaneAlpha) -> bool {
+        match value {
+            DisplayPlaneAlpha::Opaque => self.opaque,
+            DisplayPlaneAlpha::Global => self.global,
+            DisplayPlaneAlpha::PerPixel => self.per_pixel,
+            DisplayPlaneAlpha::PerPixelPremultiplied => self.per_pixel_premultiplied,
+        }
+    }
+
+    /// Returns an iterator to the list of supported display plane alpha modes.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = DisplayPlaneAlpha> {
+        let moved = *self;
+        [
+            DisplayPlaneAlpha::Opaque,
+            DisplayPlaneAlpha::Global,
+            DisplayPlaneAlpha::PerPixel,
+            DisplayPlaneAlpha::PerPixelPremultiplied,
+        ]
+        .into_iter()
+        .filter(move |&mode| moved.supports(mode))
+    }
 }
 
 /// Represents a monitor connected to a physical device.
@@ -183,9 +394,15 @@ pub struct Display {
 
 impl Display {
     /// See the docs of enumerate().
-    pub fn enumerate_raw(device: PhysicalDevice) -> Result<IntoIter<Display>, OomError> {
+    pub fn enumerate_raw(device: PhysicalDevice) -> Result<IntoIter<Display>, DisplayError> {
+        if !device.instance().enabled_extensions().khr_display {
+            return Err(DisplayError::ExtensionNotEnabled {
+                extension: "khr_display",
+                reason: "enumerated the displays of a physical device",
+            });
+        }
+
         let fns = device.instance().fns();
-        assert!(device.instance().enabled_extensions().khr_display); // TODO: return error instead
 
         let display_properties = unsafe {
             loop {
@@ -229,6 +446,7 @@ impl Display {
     ///
     /// # Panic
     ///
+    /// - Panics if the `khr_display` extension is not enabled on the instance.
     /// - Panics if the device or host ran out of memory.
     ///
     // TODO: move iterator creation here from raw constructor?
@@ -237,6 +455,105 @@ impl Display {
         Display::enumerate_raw(device).unwrap()
     }
 
+    /// Returns the display that corresponds to the RandR output `rr_output` of the X server
+    /// connection `display`, or `None` if there is no such display on `device`.
+    ///
+    /// The `ext_acquire_xlib_display` extension must be enabled on the instance.
+    ///
+    /// # Safety
+    ///
+    /// - `display` must be a valid Xlib `Display` pointer.
+    pub unsafe fn from_xlib_randr_output<D>(
+        device: PhysicalDevice,
+        display: *const D,
+        rr_output: c_ulong,
+    ) -> Result<Option<Display>, DisplayError> {
+        if !device
+            .instance()
+            .enabled_extensions()
+            .ext_acquire_xlib_display
+        {
+            return Err(DisplayError::ExtensionNotEnabled {
+                extension: "ext_acquire_xlib_display",
+                reason: "looked up the display of a RandR output",
+            });
+        }
+
+        let handle = {
+            let fns = device.instance().fns();
+            let mut output = MaybeUninit::uninit();
+            check_errors(
+                (fns.ext_acquire_xlib_display.get_rand_r_output_display_ext)(
+                    device.internal_object(),
+                    display as *mut _,
+                    rr_output,
+                    output.as_mut_ptr(),
+                ),
+            )?;
+            output.assume_init()
+        };
+
+        if handle == ash::vk::DisplayKHR::null() {
+            return Ok(None);
+        }
+
+        Ok(Display::enumerate_raw(device)?.find(|display| display.internal_object() == handle))
+    }
+
+    /// Acquires exclusive control of this display from the X server that `display` is connected
+    /// to, so that surfaces can be created on it.
+    ///
+    /// Returns [`DisplayError::InitializationFailed`] if the X server can't give away the
+    /// display, for example because it is already in use.
+    ///
+    /// The `ext_acquire_xlib_display` extension must be enabled on the instance.
+    ///
+    /// # Safety
+    ///
+    /// - `display` must be a valid Xlib `Display` pointer.
+    pub unsafe fn acquire_xlib<D>(&self, display: *const D) -> Result<(), DisplayError> {
+        if !self.instance.enabled_extensions().ext_acquire_xlib_display {
+            return Err(DisplayError::ExtensionNotEnabled {
+                extension: "ext_acquire_xlib_display",
+                reason: "acquired a display from an X server",
+            });
+        }
+
+        let fns = self.instance.fns();
+        check_errors((fns.ext_acquire_xlib_display.acquire_xlib_display_ext)(
+            self.physical_device().internal_object(),
+            display as *mut _,
+            self.properties.display,
+        ))?;
+
+        Ok(())
+    }
+
+    /// Releases the control of this display that was acquired by the application, for example
+    /// with [`acquire_xlib`](Display::acquire_xlib).
+    ///
+    /// The `ext_direct_mode_display` extension must be enabled on the instance.
+    ///
+    /// # Safety
+    ///
+    /// - The swapchains that were created for surfaces on this display must not be used anymore.
+    pub unsafe fn release(&self) -> Result<(), DisplayError> {
+        if !self.instance.enabled_extensions().ext_direct_mode_display {
+            return Err(DisplayError::ExtensionNotEnabled {
+                extension: "ext_direct_mode_display",
+                reason: "released a display",
+            });
+        }
+
+        let fns = self.instance.fns();
+        check_errors((fns.ext_direct_mode_display.release_display_ext)(
+            self.physical_device().internal_object(),
+            self.properties.display,
+        ))?;
+
+        Ok(())
+    }
+
     /// Returns the name of the display.
     #[inline]
     pub fn name(&self) -> &str {
@@ -276,13 +593,15 @@ impl Display {
         self.properties.supported_transforms.into()
     }
 
-    /// Returns true if TODO.
+    /// Returns true if the planes of this display can be reordered, by giving a different
+    /// `plane_stack_index` when creating a surface.
     #[inline]
     pub fn plane_reorder_possible(&self) -> bool {
         self.properties.plane_reorder_possible != 0
     }
 
-    /// Returns true if TODO.
+    /// Returns true if the display keeps showing the last presented image when nothing new is
+    /// presented, so that presenting only the changed regions of an image is enough.
     #[inline]
     pub fn persistent_content(&self) -> bool {
         self.properties.persistent_content != 0
@@ -350,6 +669,16 @@ unsafe impl VulkanObject for Display {
     }
 }
 
+impl fmt::Debug for Display {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("Display")
+            .field("handle", &self.properties.display)
+            .field("name", &self.name())
+            .finish_non_exhaustive()
+    }
+}
+
 /// Represents a mode on a specific display.
 ///
 /// A display mode describes a supported display resolution and refresh rate.
@@ -360,35 +689,66 @@ pub struct DisplayMode {
 }
 
 impl DisplayMode {
-    /*pub fn new(display: &Display) -> Result<Arc<DisplayMode>, OomError> {
-        let fns = instance.fns();
-        assert!(device.instance().enabled_extensions().khr_display);     // TODO: return error instead
+    /// Creates a new display mode on `display`.
+    ///
+    /// Returns [`DisplayError::InitializationFailed`] if the display doesn't support the mode.
+    /// The mode is destroyed together with the display, so it doesn't need to be destroyed
+    /// explicitly.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if an element of `create_info.visible_region` is `0`.
+    /// - Panics if `create_info.refresh_rate` is `0`.
+    pub fn new(
+        display: &Display,
+        create_info: DisplayModeCreateInfo,
+    ) -> Result<DisplayMode, DisplayError> {
+        let DisplayModeCreateInfo {
+            visible_region,
+            refresh_rate,
+            _ne: _,
+        } = create_info;
+
+        // VUID-VkDisplayModeParametersKHR-width-01990
+        // VUID-VkDisplayModeParametersKHR-height-01991
+        assert!(visible_region[0] != 0 && visible_region[1] != 0);
+
+        // VUID-VkDisplayModeParametersKHR-refreshRate-01992
+        assert!(refresh_rate != 0);
 
         let parameters = ash::vk::DisplayModeParametersKHR {
-            visibleRegion: ash::vk::Extent2D { width: , height:  },
-            refreshRate: ,
+            visible_region: ash::vk::Extent2D {
+                width: visible_region[0],
+                height: visible_region[1],
+            },
+            refresh_rate,
         };
 
-        let display_mode = {
-            let infos = ash::vk::DisplayModeCreateInfoKHR {
-                flags: ash::vk::DisplayModeCreateFlags::empty(),
-                parameters: parameters,
-                ..Default::default()
-            };
-
-            let mut output = mem::uninitialized();
-            check_errors((fns.v1_0.CreateDisplayModeKHR)(display.device.internal_object(),
-                                                      display.display, &infos, ptr::null(),
-                                                      &mut output))?;
-            output
+        let create_info = ash::vk::DisplayModeCreateInfoKHR {
+            flags: ash::vk::DisplayModeCreateFlagsKHR::empty(),
+            parameters,
+            ..Default::default()
         };
 
-        Ok(Arc::new(DisplayMode {
-            instance: display.device.instance().clone(),
-            display_mode: display_mode,
-            parameters: ,
-        }))
-    }*/
+        let display_mode = unsafe {
+            let fns = display.instance.fns();
+            let mut output = MaybeUninit::uninit();
+            check_errors((fns.khr_display.create_display_mode_khr)(
+                display.physical_device().internal_object(),
+                display.internal_object(),
+                &create_info,
+                ptr::null(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(DisplayMode {
+            display: display.clone(),
+            display_mode,
+            parameters,
+        })
+    }
 
     /// Returns the display corresponding to this mode.
     #[inline]
@@ -437,3 +797,173 @@ unsafe impl VulkanObject for DisplayMode {
         self.display_mode
     }
 }
+
+/// Parameters to create a new `DisplayMode`.
+#[derive(Clone, Debug)]
+pub struct DisplayModeCreateInfo {
+    /// The dimensions of the region that is visible on the monitor.
+    ///
+    /// The default value is `[0; 2]`, which must be overridden.
+    pub visible_region: [u32; 2],
+
+    /// The refresh rate of the mode, in millihertz.
+    ///
+    /// The default value is `0`, which must be overridden.
+    pub refresh_rate: u32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for DisplayModeCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            visible_region: [0; 2],
+            refresh_rate: 0,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Parameters to create a surface with `Surface::from_display_plane`.
+#[derive(Clone, Debug)]
+pub struct DisplaySurfaceCreateInfo {
+    /// The position of the plane in the stack of planes of the display.
+    ///
+    /// If set to `None`, the [current stack index](DisplayPlane::current_stack_index) of the
+    /// plane is used. Other values can only be used if
+    /// [`plane_reorder_possible`](Display::plane_reorder_possible) returns `true`.
+    ///
+    /// The default value is `None`.
+    pub plane_stack_index: Option<u32>,
+
+    /// The transformation to apply to the images when they are shown on the display.
+    ///
+    /// The default value is [`SurfaceTransform::Identity`].
+    pub transform: SurfaceTransform,
+
+    /// How the alpha values of the images are treated.
+    ///
+    /// The value must be supported by the
+    /// [capabilities](DisplayPlane::capabilities) of the plane for the display mode.
+    ///
+    /// The default value is [`DisplayPlaneAlpha::Opaque`].
+    pub alpha_mode: DisplayPlaneAlpha,
+
+    /// The alpha value of all the pixels, if `alpha_mode` is [`DisplayPlaneAlpha::Global`].
+    ///
+    /// The value must be between 0.0 and 1.0 inclusive.
+    ///
+    /// The default value is `1.0`.
+    pub global_alpha: f32,
+
+    /// The size of the images of the surface.
+    ///
+    /// If set to `None`, the visible region of the display mode is used.
+    ///
+    /// The default value is `None`.
+    pub image_extent: Option<[u32; 2]>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for DisplaySurfaceCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            plane_stack_index: None,
+            transform: SurfaceTransform::Identity,
+            alpha_mode: DisplayPlaneAlpha::Opaque,
+            global_alpha: 1.0,
+            image_extent: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Error that can happen when using displays.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DisplayError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The display mode could not be created, or the display could not be acquired.
+    InitializationFailed,
+
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl error::Error for DisplayError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            DisplayError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DisplayError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            DisplayError::OomError(_) => write!(fmt, "not enough memory"),
+            DisplayError::InitializationFailed => write!(
+                fmt,
+                "the display mode could not be created, or the display could not be acquired"
+            ),
+            DisplayError::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+        }
+    }
+}
+
+impl From<OomError> for DisplayError {
+    #[inline]
+    fn from(err: OomError) -> DisplayError {
+        DisplayError::OomError(err)
+    }
+}
+
+impl From<Error> for DisplayError {
+    #[inline]
+    fn from(err: Error) -> DisplayError {
+        match err {
+            err @ Error::OutOfHostMemory => DisplayError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => DisplayError::OomError(OomError::from(err)),
+            Error::InitializationFailed => DisplayError::InitializationFailed,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Display, DisplayError, DisplayPlane};
+    use crate::device::physical::PhysicalDevice;
+
+    #[test]
+    fn khr_display_ext_missing() {
+        let instance = instance!();
+        let physical_device = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        match Display::enumerate_raw(physical_device) {
+            Err(DisplayError::ExtensionNotEnabled { .. }) => (),
+            _ => panic!(),
+        }
+
+        match DisplayPlane::enumerate_raw(physical_device) {
+            Err(DisplayError::ExtensionNotEnabled { .. }) => (),
+            _ => panic!(),
+        }
+    }
+}
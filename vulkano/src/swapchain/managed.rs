@@ -0,0 +1,750 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A swapchain that recreates itself when needed.
+//!
+//! A swapchain must be recreated when the surface changes, for example when the window is
+//! resized, and acquiring an image can fail or be suboptimal when this happens. A
+//! [`ManagedSwapchain`] keeps track of this, and recreates the swapchain and the views of its
+//! images before acquiring the next image. While the window is minimized, no image is acquired.
+//!
+//! ```no_run
+//! use vulkano::swapchain::managed::ManagedSwapchain;
+//! use vulkano::swapchain::SwapchainCreateInfo;
+//! use vulkano::sync::{FlushError, GpuFuture};
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+//! # let surface: std::sync::Arc<vulkano::swapchain::Surface<()>> = return;
+//! # let window_size: [u32; 2] = return;
+//!
+//! let mut swapchain = ManagedSwapchain::new(
+//!     device.clone(),
+//!     surface.clone(),
+//!     SwapchainCreateInfo {
+//!         image_extent: window_size,
+//!         ..Default::default()
+//!     },
+//! )
+//! .unwrap();
+//!
+//! // When the window is resized:
+//! swapchain.resize(window_size);
+//!
+//! // For each frame:
+//! if let Some(acquired) = swapchain.acquire(None).unwrap() {
+//!     if acquired.recreated {
+//!         // Recreate the framebuffers from `swapchain.image_views()`.
+//!     }
+//!
+//!     let result = acquired
+//!         .future
+//!         // .then_execute(...)
+//!         .then_swapchain_present(
+//!             queue.clone(),
+//!             swapchain.swapchain().clone(),
+//!             acquired.image_index,
+//!         )
+//!         .then_signal_fence_and_flush();
+//!
+//!     if let Err(FlushError::OutOfDate) = result {
+//!         swapchain.invalidate();
+//!     }
+//! }
+//! ```
+//!
+//! The present mode can be chosen with a [`PresentModePreference`], which is resolved against the
+//! present modes that the surface supports, and changed at runtime with
+//! [`set_present_mode_preference`](ManagedSwapchain::set_present_mode_preference).
+
+use super::{
+    acquire_next_image, AcquireError, PresentMode, PresentModePreference, Surface, SurfaceInfo,
+    Swapchain, SwapchainAcquireFuture, SwapchainCreateInfo, SwapchainCreationError,
+};
+use crate::{
+    device::{physical::SurfacePropertiesError, Device, DeviceOwned},
+    image::{view::ImageViewCreationError, ImageView, SwapchainImage},
+};
+use std::{error, fmt, mem, sync::Arc, time::Duration};
+
+/// A swapchain that is recreated, together with the views of its images, when the surface
+/// changes.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug)]
+pub struct ManagedSwapchain<W> {
+    swapchain: Arc<Swapchain<W>>,
+    images: Vec<Arc<SwapchainImage<W>>>,
+    image_views: Vec<Arc<ImageView<SwapchainImage<W>>>>,
+    // The size of the window, used when the surface doesn't determine the extent of the images.
+    window_extent: [u32; 2],
+    // The parameters to recreate the swapchain with, if they differ from the current ones.
+    pending_create_info: Option<SwapchainCreateInfo>,
+    needs_recreate: bool,
+    suboptimal_policy: SuboptimalPolicy,
+    // The number of consecutive images that were acquired as suboptimal.
+    suboptimal_count: u32,
+    // Whether the swapchain was recreated since an image was last returned by `acquire`.
+    recreated: bool,
+    present_mode_preference: Option<PresentModePreference>,
+    // The present mode to present the images with, which can differ from the present mode of the
+    // swapchain if it is one of its `present_modes`.
+    present_mode: PresentMode,
+}
+
+impl<W> ManagedSwapchain<W>
+where
+    W: Send + Sync,
+{
+    /// Creates a new swapchain for `surface`, and views of its images.
+    ///
+    /// `create_info.image_extent` must be the size of the window. It is only used on platforms
+    /// where the extent of the surface is determined by the swapchain, such as Wayland; on other
+    /// platforms, the current extent of the surface is used. The extent and
+    /// `create_info.min_image_count` are clamped to the capabilities of the surface.
+    ///
+    /// Returns [`ManagedSwapchainError::ZeroSizedExtent`] if the extent is zero-sized, for
+    /// example because the window is minimized. The swapchain must then be created once the
+    /// window has a size.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the device and the surface don't belong to the same instance.
+    /// - Panics if `create_info.usage` is empty.
+    pub fn new(
+        device: Arc<Device>,
+        surface: Arc<Surface<W>>,
+        mut create_info: SwapchainCreateInfo,
+    ) -> Result<ManagedSwapchain<W>, ManagedSwapchainError> {
+        let window_extent = create_info.image_extent;
+        create_info.image_extent =
+            clamped_image_extent(&device, &surface, &mut create_info, window_extent)?
+                .ok_or(ManagedSwapchainError::ZeroSizedExtent)?;
+
+        let (swapchain, images) = Swapchain::new(device, surface, create_info)?;
+        let image_views = create_image_views(&images)?;
+
+        Ok(ManagedSwapchain {
+            images,
+            image_views,
+            window_extent,
+            pending_create_info: None,
+            needs_recreate: false,
+            suboptimal_policy: SuboptimalPolicy::RecreateAtEndOfFrame,
+            suboptimal_count: 0,
+            recreated: false,
+            present_mode_preference: None,
+            present_mode: swapchain.present_mode(),
+            swapchain,
+        })
+    }
+
+    /// Returns the current swapchain.
+    ///
+    /// The swapchain changes when it is recreated by [`acquire`](Self::acquire).
+    #[inline]
+    pub fn swapchain(&self) -> &Arc<Swapchain<W>> {
+        &self.swapchain
+    }
+
+    /// Returns the images of the current swapchain.
+    #[inline]
+    pub fn images(&self) -> &[Arc<SwapchainImage<W>>] {
+        &self.images
+    }
+
+    /// Returns default views of the images of the current swapchain.
+    #[inline]
+    pub fn image_views(&self) -> &[Arc<ImageView<SwapchainImage<W>>>] {
+        &self.image_views
+    }
+
+    /// Sets the size of the window, and recreates the swapchain at the next
+    /// [`acquire`](Self::acquire).
+    ///
+    /// This must be called when the window is resized.
+    #[inline]
+    pub fn resize(&mut self, window_extent: [u32; 2]) {
+        self.window_extent = window_extent;
+        self.needs_recreate = true;
+    }
+
+    /// Recreates the swapchain at the next [`acquire`](Self::acquire).
+    ///
+    /// This must be called when presenting an image fails with `OutOfDate`, and can be called
+    /// when other parameters of the swapchain must change.
+    #[inline]
+    pub fn invalidate(&mut self) {
+        self.needs_recreate = true;
+    }
+
+    /// Recreates the swapchain with new parameters at the next [`acquire`](Self::acquire).
+    ///
+    /// `create_info.image_extent` is ignored; the extent is determined as in
+    /// [`new`](Self::new), with the size of the window given to [`resize`](Self::resize).
+    #[inline]
+    pub fn set_create_info(&mut self, create_info: SwapchainCreateInfo) {
+        self.pending_create_info = Some(create_info);
+        self.needs_recreate = true;
+    }
+
+    /// Returns how suboptimal swapchains are handled.
+    #[inline]
+    pub fn suboptimal_policy(&self) -> SuboptimalPolicy {
+        self.suboptimal_policy
+    }
+
+    /// Sets how suboptimal swapchains are handled.
+    ///
+    /// The default value is [`SuboptimalPolicy::RecreateAtEndOfFrame`].
+    #[inline]
+    pub fn set_suboptimal_policy(&mut self, suboptimal_policy: SuboptimalPolicy) {
+        self.suboptimal_policy = suboptimal_policy;
+    }
+
+    /// Returns the present mode that the images must be presented with.
+    ///
+    /// This is the present mode of the swapchain, unless a different present mode was chosen by
+    /// [`set_present_mode_preference`](Self::set_present_mode_preference) that the swapchain can
+    /// switch to without being recreated. The images must then be presented with
+    /// [`present_with_mode`](super::present_with_mode).
+    #[inline]
+    pub fn present_mode(&self) -> PresentMode {
+        self.present_mode
+    }
+
+    /// Returns the present mode preference that the present mode is chosen with, if any.
+    #[inline]
+    pub fn present_mode_preference(&self) -> Option<&PresentModePreference> {
+        self.present_mode_preference.as_ref()
+    }
+
+    /// Chooses the present mode with `preference` among the present modes supported by the
+    /// surface, and returns it. The choice is made again each time the swapchain is recreated,
+    /// replacing the `present_mode` of the create info.
+    ///
+    /// If the chosen present mode is one of the
+    /// [`present_modes`](SwapchainCreateInfo::present_modes) of the swapchain, it is used from
+    /// the next present on; see [`present_mode`](Self::present_mode). Otherwise, the swapchain is
+    /// recreated with it at the next [`acquire`](Self::acquire).
+    pub fn set_present_mode_preference(
+        &mut self,
+        preference: PresentModePreference,
+    ) -> Result<PresentMode, SurfacePropertiesError> {
+        let present_mode = self
+            .swapchain
+            .device()
+            .physical_device()
+            .choose_present_mode(self.swapchain.surface(), &preference)?;
+        self.present_mode_preference = Some(preference);
+
+        if present_mode == self.swapchain.present_mode()
+            || self.swapchain.present_modes().contains(&present_mode)
+        {
+            self.present_mode = present_mode;
+        } else {
+            self.needs_recreate = true;
+        }
+
+        Ok(present_mode)
+    }
+
+    /// Acquires the next image of the swapchain, recreating the swapchain first if needed.
+    ///
+    /// If acquiring the image fails because the swapchain is out of date, the swapchain is
+    /// recreated and the image is acquired again, once. If it succeeds but is suboptimal, the
+    /// swapchain is recreated according to the [`SuboptimalPolicy`].
+    ///
+    /// Returns `None` if the window is minimized, or otherwise has a zero-sized extent, or if the
+    /// surface changed while the swapchain was being recreated, so that it is still out of date.
+    /// No image can then be drawn to, and the swapchain is recreated again at the next call.
+    pub fn acquire(
+        &mut self,
+        timeout: Option<Duration>,
+    ) -> Result<Option<ManagedAcquiredImage<W>>, ManagedSwapchainError> {
+        // An image is acquired at most twice per call. A swapchain that is out of date or
+        // suboptimal right after being recreated means that the surface is still changing.
+        for attempt in 0..2 {
+            if self.needs_recreate && !self.recreate()? {
+                return Ok(None);
+            }
+
+            match acquire_next_image(self.swapchain.clone(), timeout) {
+                Ok((image_index, suboptimal, future)) => {
+                    if suboptimal {
+                        self.suboptimal_count += 1;
+                    } else {
+                        self.suboptimal_count = 0;
+                    }
+
+                    match self.suboptimal_policy.action(self.suboptimal_count) {
+                        SuboptimalAction::RecreateNow if attempt == 0 => {
+                            // The image is never presented. It is released when the swapchain
+                            // is destroyed after being recreated.
+                            drop(future);
+                            self.needs_recreate = true;
+                            continue;
+                        }
+                        SuboptimalAction::RecreateNow | SuboptimalAction::RecreateLater => {
+                            self.needs_recreate = true
+                        }
+                        SuboptimalAction::Keep => self.needs_recreate = false,
+                    }
+
+                    return Ok(Some(ManagedAcquiredImage {
+                        image_index,
+                        recreated: mem::take(&mut self.recreated),
+                        future,
+                    }));
+                }
+                Err(AcquireError::OutOfDate) => self.needs_recreate = true,
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Returns `false` if the swapchain can't be recreated at the moment.
+    fn recreate(&mut self) -> Result<bool, ManagedSwapchainError> {
+        let mut create_info = self
+            .pending_create_info
+            .take()
+            .unwrap_or_else(|| self.swapchain.create_info());
+
+        if let Some(preference) = &self.present_mode_preference {
+            create_info.present_mode = self
+                .swapchain
+                .device()
+                .physical_device()
+                .choose_present_mode(self.swapchain.surface(), preference)?;
+
+            // The present modes to switch between must include the present mode of the
+            // swapchain, and be compatible with it.
+            if !create_info
+                .present_modes
+                .contains(&create_info.present_mode)
+            {
+                create_info.present_modes.clear();
+            }
+        }
+
+        let image_extent = match clamped_image_extent(
+            self.swapchain.device(),
+            self.swapchain.surface(),
+            &mut create_info,
+            self.window_extent,
+        )? {
+            Some(image_extent) => image_extent,
+            None => {
+                self.pending_create_info = Some(create_info);
+                return Ok(false);
+            }
+        };
+
+        create_info.image_extent = image_extent;
+
+        let (swapchain, images) = match self.swapchain.recreate(create_info.clone()) {
+            Ok(r) => r,
+            // The surface can change between querying its capabilities and recreating the
+            // swapchain, for example while the window is being resized. The swapchain is then
+            // recreated at the next acquire.
+            Err(SwapchainCreationError::ImageExtentNotSupported { .. }) => {
+                self.pending_create_info = Some(create_info);
+                return Ok(false);
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        self.present_mode = swapchain.present_mode();
+        self.swapchain = swapchain;
+        self.images = images;
+        self.image_views = create_image_views(&self.images)?;
+        self.needs_recreate = false;
+        self.suboptimal_count = 0;
+        self.recreated = true;
+
+        Ok(true)
+    }
+}
+
+/// How a [`ManagedSwapchain`] handles images that are acquired as suboptimal.
+///
+/// A suboptimal swapchain can still be presented, but no longer matches the surface exactly, for
+/// example while the window is being resized. Recreating it for every frame of a live resize can
+/// cause visible hitches, on Windows in particular.
+///
+/// Except with `RecreateImmediately`, the suboptimal image is returned by
+/// [`ManagedSwapchain::acquire`] and must be presented; the swapchain is only recreated at a later
+/// call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SuboptimalPolicy {
+    /// The suboptimal image is discarded without being presented, and the swapchain is recreated
+    /// before an image is acquired from it again, in the same call to `acquire`. No suboptimal
+    /// image is drawn to, but the frame is delayed by the recreation.
+    ///
+    /// If the image that is acquired from the recreated swapchain is suboptimal too, it is
+    /// returned, and the swapchain is recreated at the next acquire.
+    RecreateImmediately,
+
+    /// The suboptimal image is returned, and the swapchain is recreated at the next acquire, after
+    /// the image has been presented.
+    RecreateAtEndOfFrame,
+
+    /// The given number of consecutive suboptimal images are ignored. If the image after them is
+    /// suboptimal too, the swapchain is recreated at the next acquire. A value of `0` is the same
+    /// as `RecreateAtEndOfFrame`.
+    IgnoreFor(u32),
+
+    /// The swapchain is not recreated because it is suboptimal. It is still recreated when it is
+    /// out of date, or after [`ManagedSwapchain::resize`] or [`ManagedSwapchain::invalidate`].
+    Ignore,
+}
+
+impl SuboptimalPolicy {
+    // Returns what to do with an acquired image, given the number of consecutive images that were
+    // acquired as suboptimal, including this one.
+    fn action(self, suboptimal_count: u32) -> SuboptimalAction {
+        if suboptimal_count == 0 {
+            return SuboptimalAction::Keep;
+        }
+
+        match self {
+            SuboptimalPolicy::RecreateImmediately => SuboptimalAction::RecreateNow,
+            SuboptimalPolicy::RecreateAtEndOfFrame => SuboptimalAction::RecreateLater,
+            SuboptimalPolicy::IgnoreFor(frames) if suboptimal_count > frames => {
+                SuboptimalAction::RecreateLater
+            }
+            SuboptimalPolicy::IgnoreFor(_) | SuboptimalPolicy::Ignore => SuboptimalAction::Keep,
+        }
+    }
+}
+
+impl Default for SuboptimalPolicy {
+    #[inline]
+    fn default() -> Self {
+        SuboptimalPolicy::RecreateAtEndOfFrame
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SuboptimalAction {
+    // The image is returned, and the swapchain is kept.
+    Keep,
+    // The image is returned, and the swapchain is recreated at the next acquire.
+    RecreateLater,
+    // The image is discarded, and the swapchain is recreated before acquiring another image.
+    RecreateNow,
+}
+
+/// An image that was acquired by [`ManagedSwapchain::acquire`].
+pub struct ManagedAcquiredImage<W> {
+    /// The index of the image in [`ManagedSwapchain::images`].
+    pub image_index: usize,
+
+    /// Whether the swapchain was recreated before the image was acquired. If so, any objects that
+    /// were created from the previous images, such as framebuffers, must be created again.
+    pub recreated: bool,
+
+    /// The future that represents the moment when the image is available.
+    pub future: SwapchainAcquireFuture<W>,
+}
+
+// Returns the extent of the images of a swapchain for the surface and the window, clamped to the
+// capabilities of the surface, or `None` if it is zero-sized. Also clamps the minimum image count.
+fn clamped_image_extent<W>(
+    device: &Device,
+    surface: &Surface<W>,
+    create_info: &mut SwapchainCreateInfo,
+    window_extent: [u32; 2],
+) -> Result<Option<[u32; 2]>, SurfacePropertiesError> {
+    let surface_capabilities = device.physical_device().surface_capabilities(
+        surface,
+        SurfaceInfo {
+            full_screen_exclusive: create_info.full_screen_exclusive,
+            win32_monitor: create_info.win32_monitor,
+            // The minimum image count can depend on the present mode.
+            present_mode: device
+                .instance()
+                .enabled_extensions()
+                .ext_surface_maintenance1
+                .then(|| create_info.present_mode),
+            ..Default::default()
+        },
+    )?;
+
+    create_info.min_image_count = create_info
+        .min_image_count
+        .max(surface_capabilities.min_image_count);
+
+    if let Some(max_image_count) = surface_capabilities.max_image_count {
+        create_info.min_image_count = create_info.min_image_count.min(max_image_count);
+    }
+
+    let extent = surface_capabilities.current_extent.unwrap_or(window_extent);
+
+    if extent.contains(&0) {
+        return Ok(None);
+    }
+
+    Ok(Some([
+        extent[0].clamp(
+            surface_capabilities.min_image_extent[0],
+            surface_capabilities.max_image_extent[0],
+        ),
+        extent[1].clamp(
+            surface_capabilities.min_image_extent[1],
+            surface_capabilities.max_image_extent[1],
+        ),
+    ]))
+}
+
+fn create_image_views<W>(
+    images: &[Arc<SwapchainImage<W>>],
+) -> Result<Vec<Arc<ImageView<SwapchainImage<W>>>>, ImageViewCreationError>
+where
+    W: Send + Sync,
+{
+    images
+        .iter()
+        .map(|image| ImageView::new_default(image.clone()))
+        .collect()
+}
+
+/// Error that can happen when creating or using a `ManagedSwapchain`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ManagedSwapchainError {
+    SurfacePropertiesError(SurfacePropertiesError),
+    SwapchainCreationError(SwapchainCreationError),
+    ImageViewCreationError(ImageViewCreationError),
+    AcquireError(AcquireError),
+
+    /// The extent of the swapchain images would be zero-sized, for example because the window is
+    /// minimized.
+    ZeroSizedExtent,
+}
+
+impl error::Error for ManagedSwapchainError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::SurfacePropertiesError(err) => Some(err),
+            Self::SwapchainCreationError(err) => Some(err),
+            Self::ImageViewCreationError(err) => Some(err),
+            Self::AcquireError(err) => Some(err),
+            Self::ZeroSizedExtent => None,
+        }
+    }
+}
+
+impl fmt::Display for ManagedSwapchainError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::SurfacePropertiesError(_) => {
+                write!(f, "querying the capabilities of the surface failed")
+            }
+            Self::SwapchainCreationError(_) => write!(f, "creating the swapchain failed"),
+            Self::ImageViewCreationError(_) => {
+                write!(f, "creating a view of a swapchain image failed")
+            }
+            Self::AcquireError(_) => write!(f, "acquiring a swapchain image failed"),
+            Self::ZeroSizedExtent => {
+                write!(f, "the extent of the swapchain images would be zero-sized",)
+            }
+        }
+    }
+}
+
+impl From<SurfacePropertiesError> for ManagedSwapchainError {
+    #[inline]
+    fn from(err: SurfacePropertiesError) -> Self {
+        Self::SurfacePropertiesError(err)
+    }
+}
+
+impl From<SwapchainCreationError> for ManagedSwapchainError {
+    #[inline]
+    fn from(err: SwapchainCreationError) -> Self {
+        Self::SwapchainCreationError(err)
+    }
+}
+
+impl From<ImageViewCreationError> for ManagedSwapchainError {
+    #[inline]
+    fn from(err: ImageViewCreationError) -> Self {
+        Self::ImageViewCreationError(err)
+    }
+}
+
+impl From<AcquireError> for ManagedSwapchainError {
+    #[inline]
+    fn from(err: AcquireError) -> Self {
+        Self::AcquireError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ManagedAcquiredImage, ManagedSwapchain, ManagedSwapchainError, SuboptimalAction,
+        SuboptimalPolicy,
+    };
+    use crate::{
+        device::Queue,
+        swapchain::{PresentMode, SurfaceInfo},
+        sync::GpuFuture,
+    };
+    use std::sync::Arc;
+
+    // Creates a device, a queue, a headless surface and the create info of a swapchain for it.
+    // Returns if the surface has a current extent, which would replace the extent of the window.
+    macro_rules! headless_surface {
+        () => {
+            headless_surface!([], [])
+        };
+        ($($args:tt)*) => {{
+            let (device, queue, swapchain, images) = headless_swapchain!($($args)*);
+            let surface = swapchain.surface().clone();
+            let create_info = swapchain.create_info();
+            drop((swapchain, images));
+
+            let capabilities = device
+                .physical_device()
+                .surface_capabilities(&surface, Default::default())
+                .unwrap();
+
+            if capabilities.current_extent.is_some() {
+                return;
+            }
+
+            (device, queue, surface, create_info)
+        }};
+    }
+
+    fn present(
+        queue: &Arc<Queue>,
+        swapchain: &ManagedSwapchain<()>,
+        image: ManagedAcquiredImage<()>,
+    ) {
+        image
+            .future
+            .then_swapchain_present(
+                queue.clone(),
+                swapchain.swapchain().clone(),
+                image.image_index,
+            )
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+    }
+
+    #[test]
+    fn new() {
+        let (device, _, surface, create_info) = headless_surface!();
+
+        let swapchain = ManagedSwapchain::new(device, surface, create_info).unwrap();
+        assert_eq!(swapchain.swapchain().image_extent(), [64, 64]);
+        assert_eq!(swapchain.images().len(), swapchain.image_views().len());
+    }
+
+    #[test]
+    fn new_min_image_count_for_present_mode() {
+        let (device, _, surface, mut create_info) = headless_surface!(
+            instance: [khr_get_surface_capabilities2, ext_surface_maintenance1],
+            [],
+            []
+        );
+        create_info.min_image_count = 1;
+        create_info.present_mode = PresentMode::Fifo;
+
+        let min_image_count = device
+            .physical_device()
+            .surface_capabilities(
+                &surface,
+                SurfaceInfo {
+                    present_mode: Some(PresentMode::Fifo),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .min_image_count;
+
+        let swapchain = ManagedSwapchain::new(device, surface, create_info).unwrap();
+        assert!(swapchain.swapchain().create_info().min_image_count >= min_image_count);
+    }
+
+    #[test]
+    fn new_zero_sized_extent() {
+        let (device, _, surface, mut create_info) = headless_surface!();
+        create_info.image_extent = [0, 64];
+
+        match ManagedSwapchain::new(device, surface, create_info) {
+            Err(ManagedSwapchainError::ZeroSizedExtent) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn acquire_recreates() {
+        let (device, queue, surface, create_info) = headless_surface!();
+        let mut swapchain = ManagedSwapchain::new(device, surface, create_info).unwrap();
+
+        let image = swapchain.acquire(None).unwrap().unwrap();
+        assert!(!image.recreated);
+        present(&queue, &swapchain, image);
+
+        // No image is acquired while the window is minimized.
+        swapchain.resize([0, 0]);
+        assert!(swapchain.acquire(None).unwrap().is_none());
+
+        swapchain.resize([32, 48]);
+        let image = swapchain.acquire(None).unwrap().unwrap();
+        assert!(image.recreated);
+        assert_eq!(swapchain.swapchain().image_extent(), [32, 48]);
+        assert_eq!(swapchain.images().len(), swapchain.image_views().len());
+        present(&queue, &swapchain, image);
+
+        swapchain.invalidate();
+        let image = swapchain.acquire(None).unwrap().unwrap();
+        assert!(image.recreated);
+        present(&queue, &swapchain, image);
+
+        let image = swapchain.acquire(None).unwrap().unwrap();
+        assert!(!image.recreated);
+        present(&queue, &swapchain, image);
+    }
+
+    #[test]
+    fn suboptimal_policy_action() {
+        use SuboptimalAction::{Keep, RecreateLater, RecreateNow};
+
+        let actions = |policy: SuboptimalPolicy| -> Vec<SuboptimalAction> {
+            (0..4).map(|count| policy.action(count)).collect()
+        };
+
+        assert_eq!(
+            actions(SuboptimalPolicy::RecreateImmediately),
+            [Keep, RecreateNow, RecreateNow, RecreateNow]
+        );
+        assert_eq!(
+            actions(SuboptimalPolicy::RecreateAtEndOfFrame),
+            [Keep, RecreateLater, RecreateLater, RecreateLater]
+        );
+        assert_eq!(
+            actions(SuboptimalPolicy::IgnoreFor(0)),
+            actions(SuboptimalPolicy::RecreateAtEndOfFrame)
+        );
+        assert_eq!(
+            actions(SuboptimalPolicy::IgnoreFor(2)),
+            [Keep, Keep, Keep, RecreateLater]
+        );
+        assert_eq!(actions(SuboptimalPolicy::Ignore), [Keep, Keep, Keep, Keep]);
+    }
+}
@@ -31,18 +31,31 @@
 //!
 //! ## Creating a surface from a window
 //!
-//! There are 5 extensions that each allow you to create a surface from a type of window:
-//!
-//! - `VK_KHR_xlib_surface`
-//! - `VK_KHR_xcb_surface`
-//! - `VK_KHR_wayland_surface`
-//! - `VK_KHR_android_surface`
-//! - `VK_KHR_win32_surface`
+//! There are several extensions that each allow you to create a surface from a type of window:
+//!
+//! - `VK_KHR_xlib_surface`, with `Surface::from_xlib`
+//! - `VK_KHR_xcb_surface`, with `Surface::from_xcb`
+//! - `VK_KHR_wayland_surface`, with `Surface::from_wayland`
+//! - `VK_KHR_android_surface`, with `Surface::from_android`
+//! - `VK_KHR_win32_surface`, with `Surface::from_win32`
+//! - `VK_EXT_metal_surface`, with `Surface::from_metal`
+//! - `VK_MVK_ios_surface` and `VK_MVK_macos_surface` (deprecated), with `Surface::from_ios` and
+//!   `Surface::from_mac_os`
+//! - `VK_NN_vi_surface`, with `Surface::from_vi`
 //!
 //! For example if you want to create a surface from an Android surface, you will have to enable
 //! the `VK_KHR_android_surface` extension and use `Surface::from_android`.
 //! See the documentation of `Surface` for all the possible constructors.
 //!
+//! With the Wayland, Win32, XCB and Xlib extensions, you can check whether a queue family can
+//! present on the platform before any surface is created, with
+//! `QueueFamily::supports_wayland_presentation` and the similar methods.
+//!
+//! If the windowing library implements the `HasRawWindowHandle` trait of the
+//! [raw-window-handle](https://crates.io/crates/raw-window-handle) crate, `Surface::from_window`
+//! picks the right constructor for the platform of the window, and is safe to call. It requires
+//! the `raw-window-handle` feature of vulkano.
+//!
 //! Trying to use one of these functions without enabling the proper extension will result in an
 //! error.
 //!
@@ -91,8 +104,14 @@
 //!
 //! ## Creating a surface from a monitor
 //!
-//! Currently no system provides the `VK_KHR_display` extension that contains this feature.
-//! This feature is still a work-in-progress in vulkano and will reside in the `display` module.
+//! With the `VK_KHR_display` extension, a surface can be created directly on a display, without
+//! going through a window system. See the [`display`] module for more information.
+//!
+//! ## Creating a headless surface
+//!
+//! With the `VK_EXT_headless_surface` extension, `Surface::headless` creates a surface that is
+//! not shown anywhere. It can be used to run the swapchain and present code on machines without
+//! a display server, such as CI machines.
 //!
 //! # Swapchains
 //!
@@ -138,8 +157,7 @@
 //! ```no_run
 //! # use std::sync::Arc;
 //! # use vulkano::device::Device;
-//! # use vulkano::swapchain::Surface;
-//! # use std::cmp::{max, min};
+//! # use vulkano::swapchain::{ImageBuffering, Surface, SurfaceFormatPreference};
 //! # fn choose_caps(device: Arc<Device>, surface: Arc<Surface<()>>) -> Result<(), Box<dyn std::error::Error>> {
 //! let surface_capabilities = device
 //!     .physical_device()
@@ -149,18 +167,16 @@
 //! let image_extent = surface_capabilities.current_extent.unwrap_or([640, 480]);
 //!
 //! // Try to use double-buffering.
-//! let min_image_count = match surface_capabilities.max_image_count {
-//!     None => max(2, surface_capabilities.min_image_count),
-//!     Some(limit) => min(max(2, surface_capabilities.min_image_count), limit)
-//! };
+//! let min_image_count = surface_capabilities.min_image_count_for(ImageBuffering::Double);
 //!
 //! // Preserve the current surface transform.
 //! let pre_transform = surface_capabilities.current_transform;
 //!
-//! // Use the first available format.
+//! // Use an sRGB format with the sRGB color space, or the closest supported format.
 //! let (image_format, color_space) = device
 //!     .physical_device()
-//!     .surface_formats(&surface, Default::default())?[0];
+//!     .choose_surface_format(&surface, Default::default(), &SurfaceFormatPreference::default())?
+//!     .unwrap();
 //! # Ok(())
 //! # }
 //! ```
@@ -310,29 +326,52 @@
 //! }
 //! ```
 //!
+//! The [`managed`] module provides a `ManagedSwapchain` that does this automatically, and also
+//! handles minimized windows and the views of the images.
+//!
 
 pub use self::present_region::PresentRegion;
 pub use self::present_region::RectangleLayer;
 pub use self::surface::ColorSpace;
 pub use self::surface::CompositeAlpha;
+pub use self::surface::DeviceGroupPresentCapabilities;
+pub use self::surface::DeviceGroupPresentMode;
+pub use self::surface::DeviceGroupPresentModes;
+pub use self::surface::ImageBuffering;
+pub use self::surface::PresentGravity;
 pub use self::surface::PresentMode;
+pub use self::surface::PresentModePreference;
+pub use self::surface::PresentScaling;
 pub use self::surface::SupportedCompositeAlpha;
+pub use self::surface::SupportedPresentGravity;
+pub use self::surface::SupportedPresentScaling;
 pub use self::surface::SupportedSurfaceTransforms;
 pub use self::surface::Surface;
 pub use self::surface::SurfaceApi;
 pub use self::surface::SurfaceCapabilities;
 pub use self::surface::SurfaceCreationError;
+pub use self::surface::SurfaceFormatPreference;
 pub use self::surface::SurfaceInfo;
 pub use self::surface::SurfaceTransform;
 pub use self::swapchain::acquire_next_image;
 pub use self::swapchain::acquire_next_image_raw;
 pub use self::swapchain::present;
 pub use self::swapchain::present_incremental;
+pub use self::swapchain::present_with_device_mask;
+pub use self::swapchain::present_with_id;
+pub use self::swapchain::present_with_mode;
+pub use self::swapchain::present_with_time;
 pub use self::swapchain::AcquireError;
 pub use self::swapchain::AcquiredImage;
+pub use self::swapchain::DisplayTimingError;
 pub use self::swapchain::FullScreenExclusive;
 pub use self::swapchain::FullScreenExclusiveError;
+pub use self::swapchain::HdrMetadata;
+pub use self::swapchain::HdrMetadataError;
+pub use self::swapchain::PastPresentationTiming;
 pub use self::swapchain::PresentFuture;
+pub use self::swapchain::PresentTime;
+pub use self::swapchain::PresentWaitError;
 pub use self::swapchain::Swapchain;
 pub use self::swapchain::SwapchainAcquireFuture;
 pub use self::swapchain::SwapchainCreateInfo;
@@ -341,7 +380,10 @@ pub use self::swapchain::Win32Monitor;
 use std::sync::atomic::AtomicBool;
 
 pub mod display;
+pub mod managed;
+pub mod pacing;
 mod present_region;
+pub mod statistics;
 mod surface;
 mod swapchain;
 
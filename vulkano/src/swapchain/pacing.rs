@@ -0,0 +1,279 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Presenting images at a steady rate.
+//!
+//! When an application renders slower than the refresh rate of the display, for example at 30
+//! frames per second on a 60 Hz display, the presentation engine shows each image as soon as it
+//! is ready. Some images then stay on the screen for one refresh cycle and others for two or
+//! three, which looks less smooth than a steady rate. The same happens at the full refresh rate
+//! when the rendering time of the frames varies.
+//!
+//! With the [`google_display_timing`](crate::device::DeviceExtensions::google_display_timing)
+//! extension, which is mostly available on Android, a [`FramePacer`] computes a desired present
+//! time for each image, so that consecutive images are presented a fixed number of refresh cycles
+//! apart.
+//!
+//! ```no_run
+//! use vulkano::swapchain::pacing::{FramePacer, FramePacerCreateInfo};
+//! use vulkano::sync::GpuFuture;
+//! # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+//! # let swapchain: std::sync::Arc<vulkano::swapchain::Swapchain<()>> = return;
+//! # let image_num: usize = return;
+//! # let render_future: Box<dyn GpuFuture> = return;
+//!
+//! // Present an image every other refresh cycle.
+//! let mut pacer = FramePacer::new(
+//!     swapchain.clone(),
+//!     FramePacerCreateInfo {
+//!         frame_interval: 2,
+//!         ..Default::default()
+//!     },
+//! )
+//! .unwrap();
+//!
+//! // For each frame:
+//! let present_time = pacer.next_present_time().unwrap();
+//! let future = render_future.then_swapchain_present_with_time(
+//!     queue.clone(),
+//!     swapchain.clone(),
+//!     image_num,
+//!     present_time,
+//! );
+//! ```
+
+use super::{DisplayTimingError, PastPresentationTiming, PresentTime, Swapchain};
+use std::{sync::Arc, time::Duration};
+
+/// Computes the present times of the images of a swapchain, so that they are presented at a
+/// steady rate.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug)]
+pub struct FramePacer<W> {
+    swapchain: Arc<Swapchain<W>>,
+    refresh_duration: u64,
+    frame_interval: u32,
+    next_present_id: u32,
+    // The ID and actual present time of the most recent present whose timing is known.
+    last_present: Option<(u32, u64)>,
+    missed_frames: u64,
+}
+
+impl<W> FramePacer<W> {
+    /// Creates a new `FramePacer` for the images of `swapchain`.
+    ///
+    /// The [`google_display_timing`](crate::device::DeviceExtensions::google_display_timing)
+    /// extension must be enabled on the device. When the swapchain is recreated, a new
+    /// `FramePacer` must be created for the new swapchain.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.frame_interval` is `0`.
+    pub fn new(
+        swapchain: Arc<Swapchain<W>>,
+        create_info: FramePacerCreateInfo,
+    ) -> Result<FramePacer<W>, DisplayTimingError> {
+        let FramePacerCreateInfo {
+            frame_interval,
+            _ne: _,
+        } = create_info;
+
+        assert!(frame_interval != 0);
+
+        let refresh_duration = swapchain.refresh_cycle_duration()?.as_nanos() as u64;
+
+        Ok(FramePacer {
+            swapchain,
+            refresh_duration,
+            frame_interval,
+            next_present_id: 0,
+            last_present: None,
+            missed_frames: 0,
+        })
+    }
+
+    /// Returns the swapchain whose images are paced.
+    #[inline]
+    pub fn swapchain(&self) -> &Arc<Swapchain<W>> {
+        &self.swapchain
+    }
+
+    /// Returns the duration of a refresh cycle of the display.
+    #[inline]
+    pub fn refresh_cycle_duration(&self) -> Duration {
+        Duration::from_nanos(self.refresh_duration)
+    }
+
+    /// Returns the number of refresh cycles between two presents.
+    #[inline]
+    pub fn frame_interval(&self) -> u32 {
+        self.frame_interval
+    }
+
+    /// Sets the number of refresh cycles between two presents.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `frame_interval` is `0`.
+    #[inline]
+    pub fn set_frame_interval(&mut self, frame_interval: u32) {
+        assert!(frame_interval != 0);
+        self.frame_interval = frame_interval;
+    }
+
+    /// Returns the number of images whose timing is known and that were presented at least one
+    /// refresh cycle after their desired present time.
+    #[inline]
+    pub fn missed_frames(&self) -> u64 {
+        self.missed_frames
+    }
+
+    /// Returns the timing to present the next image with.
+    ///
+    /// The timings of the previous presents are read back from the swapchain, so the images must
+    /// be presented with the returned timings in the order in which they were returned.
+    pub fn next_present_time(&mut self) -> Result<PresentTime, DisplayTimingError> {
+        let timings = self.swapchain.past_presentation_timings()?;
+
+        Ok(self.schedule(timings))
+    }
+
+    // Takes into account the timings of past presents, and returns the timing to present the next
+    // image with.
+    fn schedule(&mut self, timings: Vec<PastPresentationTiming>) -> PresentTime {
+        for timing in timings {
+            if timing.desired_present_time != 0
+                && timing.actual_present_time >= timing.desired_present_time + self.refresh_duration
+            {
+                self.missed_frames += 1;
+            }
+
+            self.last_present = Some((timing.present_id, timing.actual_present_time));
+        }
+
+        let present_id = self.next_present_id;
+        self.next_present_id = self.next_present_id.wrapping_add(1);
+
+        // Until the timing of a present is known, the images are presented as soon as possible.
+        let desired_present_time = match self.last_present {
+            Some((last_id, last_time)) => {
+                let cycles = present_id.wrapping_sub(last_id) as u64 * self.frame_interval as u64;
+
+                // Aim half a refresh cycle early, so that an image isn't pushed back by one cycle
+                // because of the imprecision of the timings.
+                (last_time + cycles * self.refresh_duration)
+                    .saturating_sub(self.refresh_duration / 2)
+            }
+            None => 0,
+        };
+
+        PresentTime {
+            present_id,
+            desired_present_time,
+        }
+    }
+}
+
+/// Parameters to create a new `FramePacer`.
+#[derive(Clone, Debug)]
+pub struct FramePacerCreateInfo {
+    /// The number of refresh cycles between two presents. For example, a value of 2 presents
+    /// images at half the refresh rate of the display.
+    ///
+    /// The default value is `1`.
+    pub frame_interval: u32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for FramePacerCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            frame_interval: 1,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FramePacer, FramePacerCreateInfo};
+    use crate::swapchain::{DisplayTimingError, PastPresentationTiming};
+
+    #[test]
+    fn google_display_timing_extension_not_enabled() {
+        let (_, _, swapchain, _) = headless_swapchain!([], []);
+
+        match FramePacer::new(swapchain, Default::default()) {
+            Err(DisplayTimingError::ExtensionNotEnabled {
+                extension: "google_display_timing",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn new() {
+        let (_, _, swapchain, _) = headless_swapchain!([google_display_timing], []);
+
+        let pacer = FramePacer::new(
+            swapchain,
+            FramePacerCreateInfo {
+                frame_interval: 2,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(pacer.frame_interval(), 2);
+        assert_eq!(pacer.missed_frames(), 0);
+    }
+
+    #[test]
+    fn schedule() {
+        let (_, _, swapchain, _) = headless_swapchain!([], []);
+
+        // A 16 ms refresh cycle, with an image every other cycle.
+        let mut pacer = FramePacer {
+            swapchain,
+            refresh_duration: 16_000_000,
+            frame_interval: 2,
+            next_present_id: 0,
+            last_present: None,
+            missed_frames: 0,
+        };
+        let timing =
+            |present_id, desired_present_time, actual_present_time| PastPresentationTiming {
+                present_id,
+                desired_present_time,
+                actual_present_time,
+                earliest_present_time: actual_present_time,
+                present_margin: 0,
+            };
+
+        // Without any known timing, the image is presented as soon as possible.
+        let present_time = pacer.schedule(vec![]);
+        assert_eq!(present_time.present_id, 0);
+        assert_eq!(present_time.desired_present_time, 0);
+
+        // Two cycles after the last known present, half a cycle early.
+        let present_time = pacer.schedule(vec![timing(0, 0, 1_000_000_000)]);
+        assert_eq!(present_time.present_id, 1);
+        assert_eq!(present_time.desired_present_time, 1_024_000_000);
+        assert_eq!(pacer.missed_frames(), 0);
+
+        // The image was presented a whole cycle late.
+        let present_time = pacer.schedule(vec![timing(1, 1_024_000_000, 1_040_000_000)]);
+        assert_eq!(present_time.present_id, 2);
+        assert_eq!(present_time.desired_present_time, 1_064_000_000);
+        assert_eq!(pacer.missed_frames(), 1);
+    }
+}
@@ -11,7 +11,9 @@ use crate::swapchain::Swapchain;
 
 /// Represents a region on an image.
 ///
-/// A region consists of an arbitrary amount of rectangles.
+/// A region consists of an arbitrary amount of rectangles. When presenting, the parts of the
+/// rectangles that are outside of the images of the swapchain are ignored, so that a region that
+/// was computed for the size of a window can be used while the swapchain is being recreated.
 #[derive(Debug, Clone)]
 pub struct PresentRegion {
     pub rectangles: Vec<RectangleLayer>,
@@ -49,6 +51,35 @@ impl RectangleLayer {
             && self.offset[1] as u32 + self.extent[1] <= swapchain.image_extent()[1]
             && self.layer < swapchain.image_array_layers()
     }
+
+    // Returns the part of the rectangle that is inside an image with the given extent and number
+    // of array layers, or `None` if it is empty.
+    pub(crate) fn clipped_to(&self, image_extent: [u32; 2], array_layers: u32) -> Option<Self> {
+        if self.layer >= array_layers {
+            return None;
+        }
+
+        let mut offset = [0; 2];
+        let mut extent = [0; 2];
+
+        for i in 0..2 {
+            let start = (self.offset[i] as i64).max(0);
+            let end = (self.offset[i] as i64 + self.extent[i] as i64).min(image_extent[i] as i64);
+
+            if end <= start {
+                return None;
+            }
+
+            offset[i] = start as i32;
+            extent[i] = (end - start) as u32;
+        }
+
+        Some(RectangleLayer {
+            offset,
+            extent,
+            layer: self.layer,
+        })
+    }
 }
 
 impl From<&RectangleLayer> for ash::vk::RectLayerKHR {
@@ -67,3 +98,28 @@ impl From<&RectangleLayer> for ash::vk::RectLayerKHR {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::RectangleLayer;
+
+    #[test]
+    fn clipped_to() {
+        let rectangle = RectangleLayer {
+            offset: [-10, 20],
+            extent: [50, 100],
+            layer: 0,
+        };
+
+        assert_eq!(
+            rectangle.clipped_to([100, 100], 1),
+            Some(RectangleLayer {
+                offset: [0, 20],
+                extent: [40, 80],
+                layer: 0,
+            })
+        );
+        assert_eq!(rectangle.clipped_to([100, 10], 1), None);
+        assert_eq!(rectangle.clipped_to([100, 100], 0), None);
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Measuring the time spent acquiring and presenting images.
+//!
+//! A [`PresentMonitor`] wraps the acquiring of the images of a swapchain, and keeps statistics
+//! over the most recent frames of:
+//!
+//! - The time that the CPU waited for an image to be acquired. This is high when the application
+//!   renders faster than the images are presented.
+//! - The time between submitting a present and the image being shown to the user. This requires
+//!   the [`present_id`](crate::device::Features::present_id) and
+//!   [`present_wait`](crate::device::Features::present_wait) features to be enabled on the
+//!   device.
+//!
+//! ```no_run
+//! use vulkano::swapchain::statistics::{PresentMonitor, PresentMonitorCreateInfo};
+//! use vulkano::sync::GpuFuture;
+//! # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+//! # let swapchain: std::sync::Arc<vulkano::swapchain::Swapchain<()>> = return;
+//!
+//! let mut monitor = PresentMonitor::new(swapchain.clone(), PresentMonitorCreateInfo::default());
+//!
+//! // For each frame:
+//! let (image_num, suboptimal, acquire_future) = monitor.acquire(None).unwrap();
+//! let present_future = match monitor.next_present_id() {
+//!     Some(present_id) => acquire_future
+//!         .then_swapchain_present_with_id(queue.clone(), swapchain.clone(), image_num, present_id)
+//!         .boxed(),
+//!     None => acquire_future
+//!         .then_swapchain_present(queue.clone(), swapchain.clone(), image_num)
+//!         .boxed(),
+//! };
+//!
+//! if let Some(latency) = monitor.statistics().present_latency {
+//!     println!("present latency: {:?}", latency.average);
+//! }
+//! ```
+
+use super::{acquire_next_image, AcquireError, Swapchain, SwapchainAcquireFuture};
+use crate::device::DeviceOwned;
+use std::{
+    collections::VecDeque,
+    num::NonZeroU64,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Acquires the images of a swapchain, and measures the time spent acquiring and presenting them.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug)]
+pub struct PresentMonitor<W> {
+    swapchain: Arc<Swapchain<W>>,
+    sample_count: usize,
+    acquire_waits: VecDeque<Duration>,
+    present_latencies: VecDeque<Duration>,
+    // The IDs of the presents whose completion hasn't been observed yet, and when they were
+    // submitted.
+    pending_presents: VecDeque<(NonZeroU64, Instant)>,
+    prev_present_id: u64,
+}
+
+impl<W> PresentMonitor<W> {
+    /// Creates a new `PresentMonitor` for the images of `swapchain`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.sample_count` is `0`.
+    pub fn new(
+        swapchain: Arc<Swapchain<W>>,
+        create_info: PresentMonitorCreateInfo,
+    ) -> PresentMonitor<W> {
+        let PresentMonitorCreateInfo {
+            sample_count,
+            _ne: _,
+        } = create_info;
+
+        assert!(sample_count != 0);
+
+        PresentMonitor {
+            swapchain,
+            sample_count,
+            acquire_waits: VecDeque::with_capacity(sample_count),
+            present_latencies: VecDeque::with_capacity(sample_count),
+            pending_presents: VecDeque::new(),
+            prev_present_id: 0,
+        }
+    }
+
+    /// Returns the swapchain whose images are acquired.
+    #[inline]
+    pub fn swapchain(&self) -> &Arc<Swapchain<W>> {
+        &self.swapchain
+    }
+
+    /// Replaces the swapchain whose images are acquired, after it has been recreated.
+    ///
+    /// The statistics are kept, but the latencies of the presents to the previous swapchain that
+    /// haven't been measured yet are lost.
+    #[inline]
+    pub fn set_swapchain(&mut self, swapchain: Arc<Swapchain<W>>) {
+        self.swapchain = swapchain;
+        self.pending_presents.clear();
+        self.prev_present_id = 0;
+    }
+
+    /// Acquires the next image of the swapchain, like [`acquire_next_image`], and measures how
+    /// long this took.
+    ///
+    /// This also measures the latency of the presents that have completed since the previous
+    /// call.
+    pub fn acquire(
+        &mut self,
+        timeout: Option<Duration>,
+    ) -> Result<(usize, bool, SwapchainAcquireFuture<W>), AcquireError> {
+        self.poll_presents();
+
+        let start = Instant::now();
+        let result = acquire_next_image(self.swapchain.clone(), timeout);
+        let acquire_wait = start.elapsed();
+
+        if result.is_ok() {
+            push_sample(&mut self.acquire_waits, self.sample_count, acquire_wait);
+        }
+
+        // Acquiring often waits for a present to complete, so check again.
+        self.poll_presents();
+
+        result
+    }
+
+    /// Returns the ID to present the next image with, using
+    /// [`present_with_id`](super::present_with_id), so that the latency of the present can be
+    /// measured. The present should be submitted right away.
+    ///
+    /// Returns `None` if the [`present_id`](crate::device::Features::present_id) or the
+    /// [`present_wait`](crate::device::Features::present_wait) feature is not enabled on the
+    /// device. The latency is then not measured.
+    pub fn next_present_id(&mut self) -> Option<NonZeroU64> {
+        let enabled_features = self.swapchain.device().enabled_features();
+
+        if !(enabled_features.present_id && enabled_features.present_wait) {
+            return None;
+        }
+
+        self.prev_present_id += 1;
+        let present_id = NonZeroU64::new(self.prev_present_id).unwrap();
+        self.pending_presents
+            .push_back((present_id, Instant::now()));
+
+        Some(present_id)
+    }
+
+    /// Returns the statistics over the most recent frames.
+    ///
+    /// The latencies of the presents are measured when [`acquire`](Self::acquire) is called, so
+    /// they are rounded up to the time of the next call after the image has been presented.
+    pub fn statistics(&self) -> PresentStatistics {
+        PresentStatistics {
+            acquire_wait: DurationStatistics::from_samples(&self.acquire_waits),
+            present_latency: DurationStatistics::from_samples(&self.present_latencies),
+        }
+    }
+
+    // Records the latency of the pending presents that have completed.
+    fn poll_presents(&mut self) {
+        while let Some(&(present_id, submitted)) = self.pending_presents.front() {
+            match self
+                .swapchain
+                .wait_for_present(present_id, Some(Duration::ZERO))
+            {
+                Ok(_) => {
+                    self.pending_presents.pop_front();
+                    push_sample(
+                        &mut self.present_latencies,
+                        self.sample_count,
+                        submitted.elapsed(),
+                    );
+                }
+                Err(_) => break,
+            }
+        }
+
+        // If the presents never complete, for example because they failed, don't keep them
+        // forever.
+        while self.pending_presents.len() > self.sample_count {
+            self.pending_presents.pop_front();
+        }
+    }
+}
+
+fn push_sample(samples: &mut VecDeque<Duration>, sample_count: usize, sample: Duration) {
+    if samples.len() == sample_count {
+        samples.pop_front();
+    }
+
+    samples.push_back(sample);
+}
+
+/// Parameters to create a new `PresentMonitor`.
+#[derive(Clone, Debug)]
+pub struct PresentMonitorCreateInfo {
+    /// The number of most recent frames that the statistics are computed over.
+    ///
+    /// The default value is `120`.
+    pub sample_count: usize,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for PresentMonitorCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            sample_count: 120,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Statistics over the most recent frames that were measured by a `PresentMonitor`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PresentStatistics {
+    /// The time that acquiring an image took.
+    ///
+    /// `None` if no image has been acquired yet.
+    pub acquire_wait: Option<DurationStatistics>,
+
+    /// The time between submitting a present and the image being shown to the user.
+    ///
+    /// `None` if no present has been measured yet.
+    pub present_latency: Option<DurationStatistics>,
+}
+
+/// Statistics over a number of measured durations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DurationStatistics {
+    /// The most recent duration.
+    pub last: Duration,
+
+    /// The shortest duration.
+    pub min: Duration,
+
+    /// The longest duration.
+    pub max: Duration,
+
+    /// The average of the durations.
+    pub average: Duration,
+
+    /// The number of durations that the statistics are computed over.
+    pub sample_count: usize,
+}
+
+impl DurationStatistics {
+    fn from_samples(samples: &VecDeque<Duration>) -> Option<Self> {
+        let &last = samples.back()?;
+
+        Some(DurationStatistics {
+            last,
+            min: samples.iter().copied().min().unwrap(),
+            max: samples.iter().copied().max().unwrap(),
+            average: samples.iter().sum::<Duration>() / samples.len() as u32,
+            sample_count: samples.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{push_sample, DurationStatistics};
+    use std::{collections::VecDeque, time::Duration};
+
+    #[test]
+    fn rolling_statistics() {
+        let mut samples = VecDeque::new();
+        assert_eq!(DurationStatistics::from_samples(&samples), None);
+
+        for millis in [4, 1, 3, 2] {
+            push_sample(&mut samples, 3, Duration::from_millis(millis));
+        }
+
+        assert_eq!(
+            DurationStatistics::from_samples(&samples),
+            Some(DurationStatistics {
+                last: Duration::from_millis(2),
+                min: Duration::from_millis(1),
+                max: Duration::from_millis(3),
+                average: Duration::from_millis(2),
+                sample_count: 3,
+            })
+        );
+    }
+}
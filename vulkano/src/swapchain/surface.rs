@@ -10,14 +10,22 @@
 use super::FullScreenExclusive;
 use super::Win32Monitor;
 use crate::check_errors;
+use crate::format::Format;
+use crate::format::NumericType;
 use crate::image::ImageUsage;
 use crate::instance::Instance;
 use crate::swapchain::display::DisplayMode;
 use crate::swapchain::display::DisplayPlane;
+use crate::swapchain::display::DisplayPlaneAlpha;
+use crate::swapchain::display::DisplaySurfaceCreateInfo;
+use crate::swapchain::display::SupportedDisplayPlaneAlpha;
 use crate::swapchain::SurfaceSwapchainLock;
 use crate::Error;
 use crate::OomError;
 use crate::VulkanObject;
+#[cfg(feature = "raw-window-handle")]
+use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+use smallvec::SmallVec;
 use std::error;
 use std::fmt;
 use std::hash::{Hash, Hasher};
@@ -72,10 +80,22 @@ impl<W> Surface<W> {
     ///
     /// - Panics if `display_mode` and `plane` don't belong to the same physical device.
     /// - Panics if `plane` doesn't support the display of `display_mode`.
+    /// - Panics if `create_info.alpha_mode` is [`DisplayPlaneAlpha::Global`] and
+    ///   `create_info.global_alpha` is not between 0.0 and 1.0 inclusive.
     pub fn from_display_plane(
         display_mode: &DisplayMode,
         plane: &DisplayPlane,
+        create_info: DisplaySurfaceCreateInfo,
     ) -> Result<Arc<Surface<()>>, SurfaceCreationError> {
+        let DisplaySurfaceCreateInfo {
+            plane_stack_index,
+            transform,
+            alpha_mode,
+            global_alpha,
+            image_extent,
+            _ne: _,
+        } = create_info;
+
         if !display_mode
             .display()
             .physical_device()
@@ -94,20 +114,49 @@ impl<W> Surface<W> {
         );
         assert!(plane.supports(display_mode.display()));
 
+        // VUID-VkDisplaySurfaceCreateInfoKHR-alphaMode-01255
+        let supported_alpha = plane.capabilities(display_mode)?.supported_alpha;
+
+        if !supported_alpha.supports(alpha_mode) {
+            return Err(SurfaceCreationError::DisplayPlaneAlphaNotSupported {
+                provided: alpha_mode,
+                supported: supported_alpha,
+            });
+        }
+
+        // VUID-VkDisplaySurfaceCreateInfoKHR-alphaMode-01254
+        if alpha_mode == DisplayPlaneAlpha::Global {
+            assert!((0.0..=1.0).contains(&global_alpha));
+        }
+
+        let image_extent = image_extent.unwrap_or_else(|| display_mode.visible_region());
+        let max_image_dimension = display_mode
+            .display()
+            .physical_device()
+            .properties()
+            .max_image_dimension2_d;
+
+        // VUID-VkDisplaySurfaceCreateInfoKHR-width-01256
+        if image_extent[0] > max_image_dimension || image_extent[1] > max_image_dimension {
+            return Err(SurfaceCreationError::ImageExtentExceedsLimit {
+                provided: image_extent,
+                max: max_image_dimension,
+            });
+        }
+
         let instance = display_mode.display().physical_device().instance();
 
         let create_info = ash::vk::DisplaySurfaceCreateInfoKHR {
             flags: ash::vk::DisplaySurfaceCreateFlagsKHR::empty(),
             display_mode: display_mode.internal_object(),
             plane_index: plane.index(),
-            plane_stack_index: 0, // FIXME: plane.properties.currentStackIndex,
-            transform: ash::vk::SurfaceTransformFlagsKHR::IDENTITY, // TODO: let user choose
-            global_alpha: 0.0,    // TODO: let user choose
-            alpha_mode: ash::vk::DisplayPlaneAlphaFlagsKHR::OPAQUE, // TODO: let user choose
+            plane_stack_index: plane_stack_index.unwrap_or_else(|| plane.current_stack_index()),
+            transform: transform.into(),
+            global_alpha,
+            alpha_mode: alpha_mode.into(),
             image_extent: ash::vk::Extent2D {
-                // TODO: let user choose
-                width: display_mode.visible_region()[0],
-                height: display_mode.visible_region()[1],
+                width: image_extent[0],
+                height: image_extent[1],
             },
             ..Default::default()
         };
@@ -134,6 +183,100 @@ impl<W> Surface<W> {
         }))
     }
 
+    /// Creates a headless `Surface`, which is not associated with any window or display.
+    ///
+    /// Swapchains can be created for a headless surface and images can be acquired and presented
+    /// as usual, but the presented images are not shown anywhere. This is useful to run the code
+    /// that renders to a swapchain on machines that have no display server, for example for
+    /// testing.
+    ///
+    /// A headless surface has no current extent, so the
+    /// [`image_extent`](crate::swapchain::SwapchainCreateInfo::image_extent) of a swapchain
+    /// created for it must be specified.
+    pub fn headless(
+        instance: Arc<Instance>,
+        win: W,
+    ) -> Result<Arc<Surface<W>>, SurfaceCreationError> {
+        if !instance.enabled_extensions().ext_headless_surface {
+            return Err(SurfaceCreationError::MissingExtension {
+                name: "VK_EXT_headless_surface",
+            });
+        }
+
+        let create_info = ash::vk::HeadlessSurfaceCreateInfoEXT {
+            flags: ash::vk::HeadlessSurfaceCreateFlagsEXT::empty(),
+            ..Default::default()
+        };
+
+        let handle = unsafe {
+            let fns = instance.fns();
+            let mut output = MaybeUninit::uninit();
+            check_errors((fns.ext_headless_surface.create_headless_surface_ext)(
+                instance.internal_object(),
+                &create_info,
+                ptr::null(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(Arc::new(Surface {
+            handle,
+            instance,
+            api: SurfaceApi::Headless,
+            window: win,
+
+            has_swapchain: AtomicBool::new(false),
+        }))
+    }
+
+    /// Creates a `Surface` from a window of any windowing library that implements
+    /// [`HasRawWindowHandle`], such as winit, sdl2 or glfw.
+    ///
+    /// The surface takes ownership of `window`, which ensures that the window outlives the
+    /// surface. `window` can also be a reference to the window, or an `Arc` of it.
+    ///
+    /// The instance extension that corresponds to the platform of the window must be enabled,
+    /// such as `khr_xlib_surface` for an Xlib window.
+    ///
+    /// On macOS and iOS, the view of the window must be backed by a `CAMetalLayer`.
+    ///
+    /// Returns [`SurfaceCreationError::UnsupportedWindowHandle`] if the handle of `window` is of
+    /// a platform that has no Vulkan surface, such as the web.
+    ///
+    /// This function is only available if the `raw-window-handle` feature is enabled.
+    #[cfg(feature = "raw-window-handle")]
+    pub fn from_window(
+        instance: Arc<Instance>,
+        window: W,
+    ) -> Result<Arc<Surface<W>>, SurfaceCreationError>
+    where
+        W: HasRawWindowHandle,
+    {
+        unsafe {
+            match window.raw_window_handle() {
+                RawWindowHandle::AndroidNdk(h) => {
+                    Surface::from_android(instance, h.a_native_window, window)
+                }
+                RawWindowHandle::UiKit(h) => Surface::from_ios(instance, h.ui_view, window),
+                RawWindowHandle::AppKit(h) => Surface::from_mac_os(instance, h.ns_view, window),
+                RawWindowHandle::Wayland(h) => {
+                    Surface::from_wayland(instance, h.display, h.surface, window)
+                }
+                RawWindowHandle::Win32(h) => {
+                    Surface::from_win32(instance, h.hinstance, h.hwnd, window)
+                }
+                RawWindowHandle::Xcb(h) => {
+                    Surface::from_xcb(instance, h.connection, h.window, window)
+                }
+                RawWindowHandle::Xlib(h) => {
+                    Surface::from_xlib(instance, h.display, h.window, window)
+                }
+                _ => Err(SurfaceCreationError::UnsupportedWindowHandle),
+            }
+        }
+    }
+
     /// Creates a `Surface` from an Android window.
     ///
     /// # Safety
@@ -665,6 +808,18 @@ pub enum SurfaceCreationError {
         /// Name of the missing extension.
         name: &'static str,
     },
+
+    /// The provided `alpha_mode` is not supported by the display plane for the display mode.
+    DisplayPlaneAlphaNotSupported {
+        provided: DisplayPlaneAlpha,
+        supported: SupportedDisplayPlaneAlpha,
+    },
+
+    /// The provided `image_extent` exceeds the `max_image_dimension2_d` limit.
+    ImageExtentExceedsLimit { provided: [u32; 2], max: u32 },
+
+    /// The window handle is of a platform that has no Vulkan surface.
+    UnsupportedWindowHandle,
 }
 
 impl error::Error for SurfaceCreationError {
@@ -688,6 +843,15 @@ impl fmt::Display for SurfaceCreationError {
                 SurfaceCreationError::MissingExtension { .. } => {
                     "the extension required for this function was not enabled"
                 }
+                SurfaceCreationError::DisplayPlaneAlphaNotSupported { .. } => {
+                    "the provided `alpha_mode` is not supported by the display plane for the display mode"
+                }
+                SurfaceCreationError::ImageExtentExceedsLimit { .. } => {
+                    "the provided `image_extent` exceeds the `max_image_dimension2_d` limit"
+                }
+                SurfaceCreationError::UnsupportedWindowHandle => {
+                    "the window handle is of a platform that has no Vulkan surface"
+                }
             }
         )
     }
@@ -716,6 +880,7 @@ impl From<Error> for SurfaceCreationError {
 #[non_exhaustive]
 pub enum SurfaceApi {
     DisplayPlane,
+    Headless,
 
     // Alphabetical order
     Android,
@@ -784,6 +949,101 @@ impl TryFrom<ash::vk::PresentModeKHR> for PresentMode {
     }
 }
 
+/// The present modes that are preferred, for
+/// [`PhysicalDevice::choose_present_mode`](crate::device::physical::PhysicalDevice::choose_present_mode).
+///
+/// The present modes that are supported depend on the platform and the driver. Only `Fifo` is
+/// always supported, so it is chosen if none of the preferred present modes are.
+///
+/// ```
+/// use vulkano::swapchain::{PresentMode, PresentModePreference};
+///
+/// let preference = PresentModePreference::low_latency();
+/// assert_eq!(
+///     preference.choose([PresentMode::Fifo, PresentMode::Mailbox]),
+///     PresentMode::Mailbox,
+/// );
+/// assert_eq!(preference.choose([PresentMode::Fifo]), PresentMode::Fifo);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PresentModePreference {
+    /// The present modes that can be chosen, from the most to the least preferred.
+    ///
+    /// The default value is empty, which always chooses `Fifo`.
+    pub present_modes: Vec<PresentMode>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for PresentModePreference {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            present_modes: Vec::new(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl PresentModePreference {
+    /// Returns a `PresentModePreference` for `present_modes`, from the most to the least
+    /// preferred.
+    #[inline]
+    pub fn new(present_modes: impl IntoIterator<Item = PresentMode>) -> Self {
+        Self {
+            present_modes: present_modes.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Vertical synchronization: `Fifo`.
+    #[inline]
+    pub fn vsync() -> Self {
+        Self::new([PresentMode::Fifo])
+    }
+
+    /// Vertical synchronization without waiting for the display when rendering faster than it:
+    /// `Mailbox`, then `Fifo`.
+    #[inline]
+    pub fn low_latency() -> Self {
+        Self::new([PresentMode::Mailbox, PresentMode::Fifo])
+    }
+
+    /// Vertical synchronization, except when a frame is late: `FifoRelaxed`, then `Fifo`.
+    #[inline]
+    pub fn adaptive() -> Self {
+        Self::new([PresentMode::FifoRelaxed, PresentMode::Fifo])
+    }
+
+    /// No vertical synchronization, which can cause tearing: `Immediate`, then `Mailbox`, then
+    /// `FifoRelaxed`, then `Fifo`.
+    #[inline]
+    pub fn no_vsync() -> Self {
+        Self::new([
+            PresentMode::Immediate,
+            PresentMode::Mailbox,
+            PresentMode::FifoRelaxed,
+            PresentMode::Fifo,
+        ])
+    }
+
+    /// Returns the most preferred present mode in `supported_present_modes`, or `Fifo` if none
+    /// of them are preferred.
+    pub fn choose(
+        &self,
+        supported_present_modes: impl IntoIterator<Item = PresentMode>,
+    ) -> PresentMode {
+        let supported_present_modes: SmallVec<[_; 4]> =
+            supported_present_modes.into_iter().collect();
+
+        self.present_modes
+            .iter()
+            .copied()
+            .find(|present_mode| supported_present_modes.contains(present_mode))
+            .unwrap_or(PresentMode::Fifo)
+    }
+}
+
 /// A transformation to apply to the image before showing it on the screen.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
@@ -1061,6 +1321,293 @@ impl SupportedCompositeAlpha {
     }
 }
 
+/// How an image is scaled when its dimensions don't match those of the surface.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+#[non_exhaustive]
+pub enum PresentScaling {
+    /// The image is not scaled. Pixels of the image map one-to-one to pixels of the surface.
+    OneToOne = ash::vk::PresentScalingFlagsEXT::ONE_TO_ONE.as_raw(),
+
+    /// The image is scaled up or down to fit the surface as much as possible while keeping its
+    /// aspect ratio.
+    AspectRatioStretch = ash::vk::PresentScalingFlagsEXT::ASPECT_RATIO_STRETCH.as_raw(),
+
+    /// The image is scaled up or down to exactly fill the surface.
+    Stretch = ash::vk::PresentScalingFlagsEXT::STRETCH.as_raw(),
+}
+
+impl From<PresentScaling> for ash::vk::PresentScalingFlagsEXT {
+    #[inline]
+    fn from(val: PresentScaling) -> Self {
+        Self::from_raw(val as u32)
+    }
+}
+
+/// List of supported present scaling behaviors.
+///
+/// See the docs of `PresentScaling`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SupportedPresentScaling {
+    pub one_to_one: bool,
+    pub aspect_ratio_stretch: bool,
+    pub stretch: bool,
+}
+
+impl From<ash::vk::PresentScalingFlagsEXT> for SupportedPresentScaling {
+    #[inline]
+    fn from(val: ash::vk::PresentScalingFlagsEXT) -> SupportedPresentScaling {
+        SupportedPresentScaling {
+            one_to_one: val.intersects(ash::vk::PresentScalingFlagsEXT::ONE_TO_ONE),
+            aspect_ratio_stretch: val
+                .intersects(ash::vk::PresentScalingFlagsEXT::ASPECT_RATIO_STRETCH),
+            stretch: val.intersects(ash::vk::PresentScalingFlagsEXT::STRETCH),
+        }
+    }
+}
+
+impl SupportedPresentScaling {
+    /// Builds a `SupportedPresentScaling` with all fields set to false.
+    #[inline]
+    pub fn none() -> SupportedPresentScaling {
+        SupportedPresentScaling {
+            one_to_one: false,
+            aspect_ratio_stretch: false,
+            stretch: false,
+        }
+    }
+
+    /// Returns true if the given `PresentScaling` is in this list.
+    #[inline]
+    pub fn supports(&self, value: PresentScaling) -> bool {
+        match value {
+            PresentScaling::OneToOne => self.one_to_one,
+            PresentScaling::AspectRatioStretch => self.aspect_ratio_stretch,
+            PresentScaling::Stretch => self.stretch,
+        }
+    }
+
+    /// Returns an iterator to the list of supported present scaling behaviors.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = PresentScaling> {
+        let moved = *self;
+        [
+            PresentScaling::OneToOne,
+            PresentScaling::AspectRatioStretch,
+            PresentScaling::Stretch,
+        ]
+        .into_iter()
+        .filter(move |&scaling| moved.supports(scaling))
+    }
+}
+
+/// Where an image is placed on the surface along one axis, when it doesn't fill the surface in
+/// that direction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+#[non_exhaustive]
+pub enum PresentGravity {
+    /// The image is placed at the left or top edge of the surface.
+    Min = ash::vk::PresentGravityFlagsEXT::MIN.as_raw(),
+
+    /// The image is placed at the right or bottom edge of the surface.
+    Max = ash::vk::PresentGravityFlagsEXT::MAX.as_raw(),
+
+    /// The image is placed in the center of the surface.
+    Centered = ash::vk::PresentGravityFlagsEXT::CENTERED.as_raw(),
+}
+
+impl From<PresentGravity> for ash::vk::PresentGravityFlagsEXT {
+    #[inline]
+    fn from(val: PresentGravity) -> Self {
+        Self::from_raw(val as u32)
+    }
+}
+
+/// List of supported present gravities.
+///
+/// See the docs of `PresentGravity`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SupportedPresentGravity {
+    pub min: bool,
+    pub max: bool,
+    pub centered: bool,
+}
+
+impl From<ash::vk::PresentGravityFlagsEXT> for SupportedPresentGravity {
+    #[inline]
+    fn from(val: ash::vk::PresentGravityFlagsEXT) -> SupportedPresentGravity {
+        SupportedPresentGravity {
+            min: val.intersects(ash::vk::PresentGravityFlagsEXT::MIN),
+            max: val.intersects(ash::vk::PresentGravityFlagsEXT::MAX),
+            centered: val.intersects(ash::vk::PresentGravityFlagsEXT::CENTERED),
+        }
+    }
+}
+
+impl SupportedPresentGravity {
+    /// Builds a `SupportedPresentGravity` with all fields set to false.
+    #[inline]
+    pub fn none() -> SupportedPresentGravity {
+        SupportedPresentGravity {
+            min: false,
+            max: false,
+            centered: false,
+        }
+    }
+
+    /// Returns true if the given `PresentGravity` is in this list.
+    #[inline]
+    pub fn supports(&self, value: PresentGravity) -> bool {
+        match value {
+            PresentGravity::Min => self.min,
+            PresentGravity::Max => self.max,
+            PresentGravity::Centered => self.centered,
+        }
+    }
+
+    /// Returns an iterator to the list of supported present gravities.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = PresentGravity> {
+        let moved = *self;
+        [
+            PresentGravity::Min,
+            PresentGravity::Max,
+            PresentGravity::Centered,
+        ]
+        .into_iter()
+        .filter(move |&gravity| moved.supports(gravity))
+    }
+}
+
+/// How the images of a device that was created from several physical devices are presented.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+#[non_exhaustive]
+pub enum DeviceGroupPresentMode {
+    /// A physical device with a presentation engine presents its own images.
+    Local = ash::vk::DeviceGroupPresentModeFlagsKHR::LOCAL.as_raw(),
+
+    /// A physical device with a presentation engine presents the images of another physical
+    /// device of the group.
+    Remote = ash::vk::DeviceGroupPresentModeFlagsKHR::REMOTE.as_raw(),
+
+    /// The sum of the images of several physical devices is presented. The images must use a
+    /// format that supports this.
+    Sum = ash::vk::DeviceGroupPresentModeFlagsKHR::SUM.as_raw(),
+
+    /// Several physical devices with a presentation engine each present their own images, for
+    /// example each to its own part of the surface.
+    LocalMultiDevice = ash::vk::DeviceGroupPresentModeFlagsKHR::LOCAL_MULTI_DEVICE.as_raw(),
+}
+
+impl From<DeviceGroupPresentMode> for ash::vk::DeviceGroupPresentModeFlagsKHR {
+    #[inline]
+    fn from(val: DeviceGroupPresentMode) -> Self {
+        Self::from_raw(val as u32)
+    }
+}
+
+/// List of device group present modes.
+///
+/// See the docs of `DeviceGroupPresentMode`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DeviceGroupPresentModes {
+    pub local: bool,
+    pub remote: bool,
+    pub sum: bool,
+    pub local_multi_device: bool,
+}
+
+impl From<ash::vk::DeviceGroupPresentModeFlagsKHR> for DeviceGroupPresentModes {
+    #[inline]
+    fn from(val: ash::vk::DeviceGroupPresentModeFlagsKHR) -> DeviceGroupPresentModes {
+        DeviceGroupPresentModes {
+            local: val.intersects(ash::vk::DeviceGroupPresentModeFlagsKHR::LOCAL),
+            remote: val.intersects(ash::vk::DeviceGroupPresentModeFlagsKHR::REMOTE),
+            sum: val.intersects(ash::vk::DeviceGroupPresentModeFlagsKHR::SUM),
+            local_multi_device: val
+                .intersects(ash::vk::DeviceGroupPresentModeFlagsKHR::LOCAL_MULTI_DEVICE),
+        }
+    }
+}
+
+impl From<DeviceGroupPresentModes> for ash::vk::DeviceGroupPresentModeFlagsKHR {
+    #[inline]
+    fn from(val: DeviceGroupPresentModes) -> Self {
+        val.iter()
+            .fold(Self::empty(), |flags, mode| flags | mode.into())
+    }
+}
+
+impl DeviceGroupPresentModes {
+    /// Builds a `DeviceGroupPresentModes` with all fields set to false.
+    #[inline]
+    pub fn none() -> DeviceGroupPresentModes {
+        DeviceGroupPresentModes {
+            local: false,
+            remote: false,
+            sum: false,
+            local_multi_device: false,
+        }
+    }
+
+    // The modes that a swapchain is created with if none are given.
+    #[inline]
+    pub(crate) fn local() -> DeviceGroupPresentModes {
+        DeviceGroupPresentModes {
+            local: true,
+            ..DeviceGroupPresentModes::none()
+        }
+    }
+
+    /// Returns true if the given `DeviceGroupPresentMode` is in this list.
+    #[inline]
+    pub fn supports(&self, value: DeviceGroupPresentMode) -> bool {
+        match value {
+            DeviceGroupPresentMode::Local => self.local,
+            DeviceGroupPresentMode::Remote => self.remote,
+            DeviceGroupPresentMode::Sum => self.sum,
+            DeviceGroupPresentMode::LocalMultiDevice => self.local_multi_device,
+        }
+    }
+
+    /// Returns true if all the modes in `other` are in this list.
+    #[inline]
+    pub fn is_superset_of(&self, other: &DeviceGroupPresentModes) -> bool {
+        other.iter().all(|mode| self.supports(mode))
+    }
+
+    /// Returns an iterator to the list of device group present modes.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = DeviceGroupPresentMode> {
+        let moved = *self;
+        [
+            DeviceGroupPresentMode::Local,
+            DeviceGroupPresentMode::Remote,
+            DeviceGroupPresentMode::Sum,
+            DeviceGroupPresentMode::LocalMultiDevice,
+        ]
+        .into_iter()
+        .filter(move |&mode| moved.supports(mode))
+    }
+}
+
+/// The capabilities of a device for presenting the images of its physical devices.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct DeviceGroupPresentCapabilities {
+    /// For each physical device of the device that has a presentation engine, at its device
+    /// index, a bit mask of the physical devices whose images it can present. The masks of the
+    /// physical devices without a presentation engine are zero.
+    pub present_mask: [u32; ash::vk::MAX_DEVICE_GROUP_SIZE],
+
+    /// The modes that the device supports.
+    pub modes: DeviceGroupPresentModes,
+}
+
 /// How the presentation engine should interpret the data.
 ///
 /// # A quick lesson about color spaces
@@ -1204,14 +1751,172 @@ impl From<ash::vk::ColorSpaceKHR> for ColorSpace {
     }
 }
 
+impl ColorSpace {
+    /// Returns whether the color space can represent colors that are brighter or more saturated
+    /// than what sRGB can represent, for high dynamic range output.
+    #[inline]
+    pub fn is_hdr(&self) -> bool {
+        matches!(
+            self,
+            ColorSpace::ExtendedSrgbLinear
+                | ColorSpace::ExtendedSrgbNonLinear
+                | ColorSpace::Bt2020Linear
+                | ColorSpace::Hdr10St2084
+                | ColorSpace::DolbyVision
+                | ColorSpace::Hdr10Hlg
+        )
+    }
+
+    /// Returns the formats that are usually used with the color space, from the most to the
+    /// least suitable.
+    ///
+    /// The extended sRGB color spaces, also known as scRGB, need a floating-point format to store
+    /// values outside of the `[0.0, 1.0]` range. The HDR10 color spaces are meant for 10-bit
+    /// formats, whose values are encoded with the transfer function of the color space.
+    pub fn preferred_formats(&self) -> &'static [Format] {
+        match self {
+            ColorSpace::SrgbNonLinear => &[
+                Format::B8G8R8A8_SRGB,
+                Format::R8G8B8A8_SRGB,
+                Format::A8B8G8R8_SRGB_PACK32,
+            ],
+            ColorSpace::ExtendedSrgbLinear | ColorSpace::Bt2020Linear => {
+                &[Format::R16G16B16A16_SFLOAT]
+            }
+            ColorSpace::ExtendedSrgbNonLinear => &[
+                Format::R16G16B16A16_SFLOAT,
+                Format::A2B10G10R10_UNORM_PACK32,
+            ],
+            ColorSpace::Hdr10St2084 | ColorSpace::Hdr10Hlg => &[
+                Format::A2B10G10R10_UNORM_PACK32,
+                Format::A2R10G10B10_UNORM_PACK32,
+            ],
+            _ => &[],
+        }
+    }
+}
+
+/// The properties of a surface format that are preferred, for
+/// [`PhysicalDevice::choose_surface_format`](crate::device::physical::PhysicalDevice::choose_surface_format).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SurfaceFormatPreference {
+    /// The color spaces that can be chosen, from the most to the least preferred.
+    ///
+    /// The color space is the first criterion: a format with a more preferred color space is
+    /// always chosen over one with a less preferred color space.
+    ///
+    /// The default value is `[ColorSpace::SrgbNonLinear]`.
+    pub color_spaces: Vec<ColorSpace>,
+
+    /// Whether formats that encode the color components to sRGB when they are written, such as
+    /// `B8G8R8A8_SRGB`, are preferred (`Some(true)`), or formats that store the values as they
+    /// are, such as `B8G8R8A8_UNORM` (`Some(false)`).
+    ///
+    /// With the `SrgbNonLinear` color space, an sRGB format lets shaders output linear colors,
+    /// while a `UNORM` format requires the shaders to encode the colors themselves, or else
+    /// the image looks too dark.
+    ///
+    /// The default value is `Some(true)`.
+    pub srgb: Option<bool>,
+
+    /// The preferred number of bits of the color components. Formats with at least this many
+    /// bits are preferred, and among them the ones with the fewest bits.
+    ///
+    /// The default value is `8`.
+    pub bits_per_component: u8,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for SurfaceFormatPreference {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            color_spaces: vec![ColorSpace::SrgbNonLinear],
+            srgb: Some(true),
+            bits_per_component: 8,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl SurfaceFormatPreference {
+    /// Returns the combination of format and color space in `surface_formats` that best matches
+    /// the preference, or `None` if none of them has one of the color spaces.
+    ///
+    /// The formats are ranked by, in order:
+    /// - the color space, as ordered in `color_spaces`;
+    /// - whether the format matches `srgb`;
+    /// - whether the format has at least `bits_per_component` bits, and then the number of bits
+    ///   that is closest to it;
+    /// - the position of the format in [`ColorSpace::preferred_formats`];
+    /// - the position of the format in `surface_formats`.
+    pub fn choose(&self, surface_formats: &[(Format, ColorSpace)]) -> Option<(Format, ColorSpace)> {
+        surface_formats
+            .iter()
+            .enumerate()
+            .filter_map(|(index, &(format, color_space))| {
+                let color_space_rank = self.color_spaces.iter().position(|&c| c == color_space)?;
+                let srgb_mismatch = self.srgb.map_or(false, |srgb| {
+                    srgb != (format.type_color() == Some(NumericType::SRGB))
+                });
+                let bits = format.components()[..3].iter().copied().max().unwrap();
+                let preferred_rank = color_space
+                    .preferred_formats()
+                    .iter()
+                    .position(|&f| f == format)
+                    .unwrap_or(usize::MAX);
+
+                Some((
+                    (
+                        color_space_rank,
+                        srgb_mismatch,
+                        bits < self.bits_per_component,
+                        (bits as i32 - self.bits_per_component as i32).abs(),
+                        preferred_rank,
+                        index,
+                    ),
+                    (format, color_space),
+                ))
+            })
+            .min_by_key(|&(key, _)| key)
+            .map(|(_, surface_format)| surface_format)
+    }
+}
+
 /// Parameters for
 /// [`PhysicalDevice::surface_capabilities`](crate::device::physical::PhysicalDevice::surface_capabilities)
 /// and
 /// [`PhysicalDevice::surface_formats`](crate::device::physical::PhysicalDevice::surface_formats).
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SurfaceInfo {
+    /// The full-screen exclusive mode to query the capabilities for.
+    ///
+    /// If not `FullScreenExclusive::Default`, the
+    /// [`khr_get_surface_capabilities2`](crate::instance::InstanceExtensions::khr_get_surface_capabilities2)
+    /// extension must be enabled on the instance.
+    ///
+    /// The default value is [`FullScreenExclusive::Default`].
     pub full_screen_exclusive: FullScreenExclusive,
+
+    /// The monitor to query the capabilities for, which must be `Some` if the surface is a Win32
+    /// surface and `full_screen_exclusive` is `FullScreenExclusive::ApplicationControlled`, and
+    /// `None` otherwise.
+    ///
+    /// The default value is `None`.
     pub win32_monitor: Option<Win32Monitor>,
+
+    /// The present mode to query the capabilities for. The
+    /// [`compatible_present_modes`](SurfaceCapabilities::compatible_present_modes) and the
+    /// present scaling capabilities are only returned if this is `Some`.
+    ///
+    /// If not `None`, the
+    /// [`ext_surface_maintenance1`](crate::instance::InstanceExtensions::ext_surface_maintenance1)
+    /// extension must be enabled on the instance.
+    ///
+    /// The default value is `None`.
+    pub present_mode: Option<PresentMode>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -1221,6 +1926,7 @@ impl Default for SurfaceInfo {
         Self {
             full_screen_exclusive: FullScreenExclusive::Default,
             win32_monitor: None,
+            present_mode: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1268,14 +1974,132 @@ pub struct SurfaceCapabilities {
 
     /// Whether full-screen exclusivity is supported.
     pub full_screen_exclusive_supported: bool,
+
+    /// The present modes that a swapchain created with the queried
+    /// [`present_mode`](SurfaceInfo::present_mode) can switch to without being recreated,
+    /// including the queried present mode itself.
+    ///
+    /// Empty if `present_mode` was `None`.
+    pub compatible_present_modes: SmallVec<[PresentMode; 4]>,
+
+    /// The scaling behaviors that are supported for the queried present mode.
+    pub supported_present_scaling: SupportedPresentScaling,
+
+    /// The gravities that are supported for the queried present mode, along the X and Y axes.
+    pub supported_present_gravity: [SupportedPresentGravity; 2],
+
+    /// Minimum width and height of a swapchain that uses this surface with a scaling behavior.
+    /// `None` if `present_mode` was `None`, or if the dimensions depend on the swapchain.
+    pub min_scaled_image_extent: Option<[u32; 2]>,
+
+    /// Maximum width and height of a swapchain that uses this surface with a scaling behavior.
+    /// `None` if `present_mode` was `None`, or if the dimensions depend on the swapchain.
+    pub max_scaled_image_extent: Option<[u32; 2]>,
+
+    /// Whether a protected swapchain can be created for this surface.
+    ///
+    /// Always `false` if the
+    /// [`khr_surface_protected_capabilities`](crate::instance::InstanceExtensions::khr_surface_protected_capabilities)
+    /// extension is not enabled on the instance.
+    pub supports_protected: bool,
+}
+
+impl SurfaceCapabilities {
+    /// Returns the minimum image count of a swapchain that gives the requested buffering, or the
+    /// closest count that the surface supports.
+    ///
+    /// If the surface requires more images, the minimum image count of the surface is returned.
+    /// The swapchain then adds latency, since more images can be queued for presentation. If the
+    /// surface supports fewer images, the maximum image count of the surface is returned.
+    #[inline]
+    pub fn min_image_count_for(&self, buffering: ImageBuffering) -> u32 {
+        let image_count = buffering.image_count().max(self.min_image_count);
+
+        match self.max_image_count {
+            Some(max_image_count) => image_count.min(max_image_count),
+            None => image_count,
+        }
+    }
+}
+
+/// The number of images of a swapchain that the application renders to in turn.
+///
+/// With more images, the application can render the next image while the previous ones are
+/// waiting to be presented, which avoids stalls but adds latency between rendering an image and
+/// showing it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ImageBuffering {
+    /// Two images: one that is shown and one that is rendered to. This has the lowest latency.
+    Double,
+
+    /// Three images, so that rendering doesn't have to wait while an image is waiting to be
+    /// shown. This is smoother when rendering takes close to a refresh cycle.
+    Triple,
+}
+
+impl ImageBuffering {
+    /// Returns the number of images.
+    #[inline]
+    pub fn image_count(&self) -> u32 {
+        match self {
+            ImageBuffering::Double => 2,
+            ImageBuffering::Triple => 3,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::device::physical::SurfacePropertiesError;
+    use crate::format::Format;
+    use crate::image::ImageUsage;
+    use crate::swapchain::ColorSpace;
+    use crate::swapchain::FullScreenExclusive;
+    use crate::swapchain::ImageBuffering;
+    use crate::swapchain::PresentMode;
+    use crate::swapchain::PresentModePreference;
+    use crate::swapchain::SupportedCompositeAlpha;
+    use crate::swapchain::SupportedPresentGravity;
+    use crate::swapchain::SupportedPresentScaling;
+    use crate::swapchain::SupportedSurfaceTransforms;
     use crate::swapchain::Surface;
+    use crate::swapchain::SurfaceCapabilities;
     use crate::swapchain::SurfaceCreationError;
+    use crate::swapchain::SurfaceFormatPreference;
+    use crate::swapchain::SurfaceInfo;
+    use crate::swapchain::SurfaceTransform;
+    use smallvec::SmallVec;
     use std::ptr;
 
+    #[cfg(feature = "raw-window-handle")]
+    #[test]
+    fn from_window_unsupported_handle() {
+        use raw_window_handle::{HasRawWindowHandle, RawWindowHandle, WebHandle};
+
+        struct WebWindow;
+
+        unsafe impl HasRawWindowHandle for WebWindow {
+            fn raw_window_handle(&self) -> RawWindowHandle {
+                RawWindowHandle::Web(WebHandle::empty())
+            }
+        }
+
+        let instance = instance!();
+        match Surface::from_window(instance, WebWindow) {
+            Err(SurfaceCreationError::UnsupportedWindowHandle) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn ext_headless_surface_ext_missing() {
+        let instance = instance!();
+        match Surface::headless(instance, ()) {
+            Err(SurfaceCreationError::MissingExtension { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn khr_win32_surface_ext_missing() {
         let instance = instance!();
@@ -1320,4 +2144,247 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn min_image_count_for_buffering() {
+        let mut capabilities = SurfaceCapabilities {
+            min_image_count: 2,
+            max_image_count: None,
+            current_extent: None,
+            min_image_extent: [1, 1],
+            max_image_extent: [4096, 4096],
+            max_image_array_layers: 1,
+            supported_transforms: SupportedSurfaceTransforms::none(),
+            current_transform: SurfaceTransform::Identity,
+            supported_composite_alpha: SupportedCompositeAlpha::none(),
+            supported_usage_flags: ImageUsage::none(),
+            full_screen_exclusive_supported: false,
+            compatible_present_modes: SmallVec::new(),
+            supported_present_scaling: SupportedPresentScaling::none(),
+            supported_present_gravity: [SupportedPresentGravity::none(); 2],
+            min_scaled_image_extent: None,
+            max_scaled_image_extent: None,
+            supports_protected: false,
+        };
+        assert_eq!(capabilities.min_image_count_for(ImageBuffering::Double), 2);
+        assert_eq!(capabilities.min_image_count_for(ImageBuffering::Triple), 3);
+
+        capabilities.min_image_count = 3;
+        assert_eq!(capabilities.min_image_count_for(ImageBuffering::Double), 3);
+
+        capabilities.min_image_count = 1;
+        capabilities.max_image_count = Some(2);
+        assert_eq!(capabilities.min_image_count_for(ImageBuffering::Triple), 2);
+    }
+
+    #[test]
+    fn choose_surface_format() {
+        let surface_formats = [
+            (Format::B8G8R8A8_UNORM, ColorSpace::SrgbNonLinear),
+            (Format::A2B10G10R10_UNORM_PACK32, ColorSpace::SrgbNonLinear),
+            (Format::B8G8R8A8_SRGB, ColorSpace::SrgbNonLinear),
+            (Format::A2B10G10R10_UNORM_PACK32, ColorSpace::Hdr10St2084),
+        ];
+
+        let preference = SurfaceFormatPreference::default();
+        assert_eq!(
+            preference.choose(&surface_formats),
+            Some((Format::B8G8R8A8_SRGB, ColorSpace::SrgbNonLinear))
+        );
+
+        let preference = SurfaceFormatPreference {
+            srgb: Some(false),
+            bits_per_component: 10,
+            ..Default::default()
+        };
+        assert_eq!(
+            preference.choose(&surface_formats),
+            Some((Format::A2B10G10R10_UNORM_PACK32, ColorSpace::SrgbNonLinear))
+        );
+
+        let preference = SurfaceFormatPreference {
+            color_spaces: vec![ColorSpace::Hdr10St2084, ColorSpace::SrgbNonLinear],
+            ..Default::default()
+        };
+        assert_eq!(
+            preference.choose(&surface_formats),
+            Some((Format::A2B10G10R10_UNORM_PACK32, ColorSpace::Hdr10St2084))
+        );
+
+        let preference = SurfaceFormatPreference {
+            color_spaces: vec![ColorSpace::ExtendedSrgbLinear],
+            ..Default::default()
+        };
+        assert_eq!(preference.choose(&surface_formats), None);
+    }
+
+    #[test]
+    fn choose_present_mode() {
+        let supported = [PresentMode::Fifo, PresentMode::Immediate];
+
+        assert_eq!(
+            PresentModePreference::low_latency().choose(supported),
+            PresentMode::Fifo
+        );
+        assert_eq!(
+            PresentModePreference::no_vsync().choose(supported),
+            PresentMode::Immediate
+        );
+        assert_eq!(
+            PresentModePreference::new([PresentMode::Mailbox]).choose(supported),
+            PresentMode::Fifo
+        );
+        assert_eq!(
+            PresentModePreference::default().choose(supported),
+            PresentMode::Fifo
+        );
+    }
+
+    #[test]
+    fn ext_surface_maintenance1_extension_not_enabled() {
+        let (device, _, swapchain, _) = headless_swapchain!([], []);
+
+        match device.physical_device().surface_capabilities(
+            swapchain.surface(),
+            SurfaceInfo {
+                present_mode: Some(PresentMode::Fifo),
+                ..Default::default()
+            },
+        ) {
+            Err(SurfacePropertiesError::ExtensionNotEnabled {
+                extension: "ext_surface_maintenance1",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn khr_get_surface_capabilities2_extension_not_enabled() {
+        let (device, _, swapchain, _) = headless_swapchain!([], []);
+
+        match device.physical_device().surface_capabilities(
+            swapchain.surface(),
+            SurfaceInfo {
+                full_screen_exclusive: FullScreenExclusive::Disallowed,
+                ..Default::default()
+            },
+        ) {
+            Err(SurfacePropertiesError::ExtensionNotEnabled {
+                extension: "khr_get_surface_capabilities2",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn surface_formats_for_present_mode() {
+        let (device, _, swapchain, _) = headless_swapchain!(
+            instance: [khr_get_surface_capabilities2, ext_surface_maintenance1],
+            [],
+            []
+        );
+        let physical_device = device.physical_device();
+
+        // The present mode doesn't affect the formats.
+        assert_eq!(
+            physical_device
+                .surface_formats(
+                    swapchain.surface(),
+                    SurfaceInfo {
+                        present_mode: Some(PresentMode::Fifo),
+                        ..Default::default()
+                    },
+                )
+                .unwrap(),
+            physical_device
+                .surface_formats(swapchain.surface(), Default::default())
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    fn surface_capabilities_for_present_mode() {
+        let (device, _, swapchain, _) = headless_swapchain!(
+            instance: [khr_get_surface_capabilities2, ext_surface_maintenance1],
+            [],
+            []
+        );
+
+        let capabilities = device
+            .physical_device()
+            .surface_capabilities(
+                swapchain.surface(),
+                SurfaceInfo {
+                    present_mode: Some(PresentMode::Fifo),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        // A present mode is always compatible with itself.
+        assert!(capabilities
+            .compatible_present_modes
+            .contains(&PresentMode::Fifo));
+    }
+
+    #[test]
+    fn color_space_preferred_formats() {
+        assert!(!ColorSpace::SrgbNonLinear.is_hdr());
+        assert!(ColorSpace::Hdr10St2084.is_hdr());
+        assert!(ColorSpace::ExtendedSrgbLinear.is_hdr());
+
+        for color_space in [
+            ColorSpace::SrgbNonLinear,
+            ColorSpace::ExtendedSrgbLinear,
+            ColorSpace::ExtendedSrgbNonLinear,
+            ColorSpace::Hdr10St2084,
+            ColorSpace::Hdr10Hlg,
+        ] {
+            assert!(!color_space.preferred_formats().is_empty());
+        }
+
+        assert_eq!(
+            ColorSpace::ExtendedSrgbLinear.preferred_formats()[0],
+            Format::R16G16B16A16_SFLOAT
+        );
+        assert_eq!(
+            ColorSpace::Hdr10St2084.preferred_formats()[0],
+            Format::A2B10G10R10_UNORM_PACK32
+        );
+    }
+
+    #[test]
+    fn select_surface_format() {
+        let (device, _, swapchain, _) = headless_swapchain!([], []);
+        let physical_device = device.physical_device();
+        let surface = swapchain.surface();
+
+        // Every surface supports sRGB.
+        let (_, color_space) = physical_device
+            .select_surface_format(
+                surface,
+                Default::default(),
+                &[ColorSpace::Hdr10St2084, ColorSpace::SrgbNonLinear],
+            )
+            .unwrap()
+            .unwrap();
+        let surface_formats = physical_device
+            .surface_formats(surface, Default::default())
+            .unwrap();
+        assert!(
+            color_space == ColorSpace::SrgbNonLinear
+                || surface_formats
+                    .iter()
+                    .any(|&(_, cs)| cs == ColorSpace::Hdr10St2084)
+        );
+
+        assert_eq!(
+            physical_device
+                .select_surface_format(surface, Default::default(), &[])
+                .unwrap(),
+            None
+        );
+    }
 }
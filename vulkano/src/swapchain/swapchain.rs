@@ -8,16 +8,26 @@
 // according to those terms.
 
 use super::{
-    ColorSpace, CompositeAlpha, PresentMode, PresentRegion, SupportedCompositeAlpha,
-    SupportedSurfaceTransforms, Surface, SurfaceTransform,
+    ColorSpace, CompositeAlpha, DeviceGroupPresentMode, DeviceGroupPresentModes, PresentGravity,
+    PresentMode, PresentRegion, PresentScaling, SupportedCompositeAlpha, SupportedPresentGravity,
+    SupportedPresentScaling, SupportedSurfaceTransforms, Surface, SurfaceTransform,
 };
 use crate::{
     buffer::sys::UnsafeBuffer,
     check_errors,
-    command_buffer::submit::{
-        SubmitAnyBuilder, SubmitPresentBuilder, SubmitPresentError, SubmitSemaphoresWaitBuilder,
+    command_buffer::{
+        pool::{standard::StandardCommandPoolAlloc, CommandPool, CommandPoolBuilderAlloc},
+        submit::{
+            SubmitAnyBuilder, SubmitCommandBufferBuilder, SubmitCommandBufferError,
+            SubmitPresentBuilder, SubmitPresentError, SubmitSemaphoresWaitBuilder,
+        },
+        sys::{CommandBufferBeginInfo, UnsafeCommandBuffer, UnsafeCommandBufferBuilder},
+        CommandBufferLevel, CommandBufferUsage,
+    },
+    device::{
+        physical::{QueueFamily, SurfacePropertiesError},
+        Device, DeviceOwned, Queue, WaitIdleError,
     },
-    device::{physical::SurfacePropertiesError, Device, DeviceOwned, Queue},
     format::Format,
     image::{
         sys::UnsafeImage, ImageCreateFlags, ImageDimensions, ImageFormatInfo, ImageInner,
@@ -25,10 +35,11 @@ use crate::{
     },
     swapchain::{SurfaceApi, SurfaceInfo, SurfaceSwapchainLock},
     sync::{
-        AccessCheckError, AccessError, AccessFlags, Fence, FlushError, GpuFuture, PipelineStages,
-        Semaphore, SemaphoreCreationError, Sharing,
+        AccessCheckError, AccessError, AccessFlags, DependencyInfo, Fence, FenceWaitError,
+        FlushError, GpuFuture, ImageMemoryBarrier, PipelineStages, QueueFamilyTransfer, Semaphore,
+        SemaphoreCreationError, Sharing,
     },
-    DeviceSize, Error, OomError, Success, VulkanObject,
+    DeviceSize, Error, OomError, Success, Version, VulkanObject,
 };
 use parking_lot::Mutex;
 use smallvec::SmallVec;
@@ -36,10 +47,11 @@ use std::{
     error, fmt,
     hash::{Hash, Hasher},
     mem::MaybeUninit,
+    num::NonZeroU64,
     ops::Range,
     ptr,
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
     time::Duration,
@@ -59,16 +71,26 @@ pub struct Swapchain<W> {
     image_array_layers: u32,
     image_usage: ImageUsage,
     image_sharing: Sharing<SmallVec<[u32; 4]>>,
+    image_view_formats: Vec<Format>,
+    protected: bool,
     pre_transform: SurfaceTransform,
     composite_alpha: CompositeAlpha,
     present_mode: PresentMode,
+    present_modes: SmallVec<[PresentMode; 4]>,
+    scaling_behavior: Option<PresentScaling>,
+    present_gravity: Option<[PresentGravity; 2]>,
     clipped: bool,
+    deferred_memory_allocation: bool,
     full_screen_exclusive: FullScreenExclusive,
     win32_monitor: Option<Win32Monitor>,
+    device_group_present_modes: DeviceGroupPresentModes,
 
     // Whether full-screen exclusive is currently held.
     full_screen_exclusive_held: AtomicBool,
 
+    // The greatest present ID that was provided when presenting an image, or 0.
+    prev_present_id: AtomicU64,
+
     // The images of this swapchain.
     images: Vec<ImageEntry>,
 
@@ -86,6 +108,10 @@ struct ImageEntry {
     image: Arc<UnsafeImage>,
     // If true, then the image is still in the undefined layout and must be transitioned.
     undefined_layout: AtomicBool,
+    // If the ownership of the image was transferred to the family of the queue that it was
+    // presented on, the queue that it was transferred from and the present queue. The ownership
+    // is transferred back when the image is acquired again.
+    ownership_transfer: Mutex<Option<(Arc<Queue>, Arc<Queue>)>>,
 }
 
 impl<W> Swapchain<W> {
@@ -99,6 +125,7 @@ impl<W> Swapchain<W> {
     ///
     /// - Panics if the device and the surface don't belong to the same instance.
     /// - Panics if `create_info.usage` is empty.
+    /// - Panics if `create_info.device_group_present_modes` is empty.
     ///
     // TODO: isn't it unsafe to take the surface through an Arc when it comes to vulkano-win?
     pub fn new(
@@ -139,12 +166,19 @@ impl<W> Swapchain<W> {
             image_array_layers,
             image_usage,
             image_sharing,
+            image_view_formats,
+            protected,
             pre_transform,
             composite_alpha,
             present_mode,
+            present_modes,
+            scaling_behavior,
+            present_gravity,
             clipped,
+            deferred_memory_allocation,
             full_screen_exclusive,
             win32_monitor,
+            device_group_present_modes,
             _ne: _,
         } = create_info;
 
@@ -160,14 +194,22 @@ impl<W> Swapchain<W> {
             image_array_layers,
             image_usage,
             image_sharing,
+            image_view_formats,
+            protected,
             pre_transform,
             composite_alpha,
             present_mode,
+            present_modes,
+            scaling_behavior,
+            present_gravity,
             clipped,
+            deferred_memory_allocation,
             full_screen_exclusive,
             win32_monitor,
+            device_group_present_modes,
 
             full_screen_exclusive_held: AtomicBool::new(false),
+            prev_present_id: AtomicU64::new(0),
             images,
             retired: Mutex::new(false),
         });
@@ -186,6 +228,7 @@ impl<W> Swapchain<W> {
     /// # Panics
     ///
     /// - Panics if `create_info.usage` is empty.
+    /// - Panics if `create_info.device_group_present_modes` is empty.
     #[inline]
     pub fn recreate(
         self: &Arc<Self>,
@@ -233,12 +276,19 @@ impl<W> Swapchain<W> {
             image_array_layers,
             image_usage,
             image_sharing,
+            image_view_formats,
+            protected,
             pre_transform,
             composite_alpha,
             present_mode,
+            present_modes,
+            scaling_behavior,
+            present_gravity,
             clipped,
+            deferred_memory_allocation,
             full_screen_exclusive,
             win32_monitor,
+            device_group_present_modes,
             _ne: _,
         } = create_info;
 
@@ -254,14 +304,22 @@ impl<W> Swapchain<W> {
             image_array_layers,
             image_usage,
             image_sharing,
+            image_view_formats,
+            protected,
             pre_transform,
             composite_alpha,
             present_mode,
+            present_modes,
+            scaling_behavior,
+            present_gravity,
             clipped,
+            deferred_memory_allocation,
             full_screen_exclusive,
             win32_monitor,
+            device_group_present_modes,
 
             full_screen_exclusive_held: AtomicBool::new(full_screen_exclusive_held),
+            prev_present_id: AtomicU64::new(0),
             images,
             retired: Mutex::new(false),
         });
@@ -286,18 +344,50 @@ impl<W> Swapchain<W> {
             image_array_layers,
             image_usage,
             ref mut image_sharing,
+            ref image_view_formats,
+            protected,
             pre_transform,
             composite_alpha,
             present_mode,
+            ref present_modes,
+            scaling_behavior,
+            present_gravity,
             clipped,
+            deferred_memory_allocation,
             full_screen_exclusive,
             win32_monitor,
+            device_group_present_modes,
             _ne: _,
         } = create_info;
 
         // VUID-VkSwapchainCreateInfoKHR-imageUsage-requiredbitmask
         assert!(image_usage != ImageUsage::none());
 
+        // VUID-VkDeviceGroupSwapchainCreateInfoKHR-modes-requiredbitmask
+        assert!(device_group_present_modes != DeviceGroupPresentModes::none());
+
+        if device_group_present_modes != DeviceGroupPresentModes::local() {
+            if !(device.api_version() >= Version::V1_1
+                || device.enabled_extensions().khr_device_group)
+            {
+                return Err(SwapchainCreationError::ExtensionNotEnabled {
+                    extension: "khr_device_group",
+                    reason: "`device_group_present_modes` was not only `local`",
+                });
+            }
+
+            let supported = device.surface_group_present_modes(surface)?;
+
+            if !supported.is_superset_of(&device_group_present_modes) {
+                return Err(
+                    SwapchainCreationError::DeviceGroupPresentModesNotSupported {
+                        provided: device_group_present_modes,
+                        supported,
+                    },
+                );
+            }
+        }
+
         if full_screen_exclusive != FullScreenExclusive::Default
             && !device.enabled_extensions().ext_full_screen_exclusive
         {
@@ -351,11 +441,98 @@ impl<W> Swapchain<W> {
             }
         });
 
+        if !image_view_formats.is_empty() {
+            if !device.enabled_extensions().khr_swapchain_mutable_format {
+                return Err(SwapchainCreationError::ExtensionNotEnabled {
+                    extension: "khr_swapchain_mutable_format",
+                    reason: "`image_view_formats` was not empty",
+                });
+            }
+
+            let image_format = image_format.unwrap();
+
+            // VUID-VkSwapchainCreateInfoKHR-flags-03168
+            if !image_view_formats.contains(&image_format) {
+                return Err(SwapchainCreationError::ImageViewFormatsMissingImageFormat);
+            }
+
+            // VUID-VkImageFormatListCreateInfo-pViewFormats-04059
+            for &view_format in image_view_formats {
+                if view_format.compatibility() != image_format.compatibility() {
+                    return Err(SwapchainCreationError::ImageViewFormatNotCompatible {
+                        view_format,
+                    });
+                }
+            }
+        }
+
+        if !present_modes.is_empty() {
+            if !device.enabled_features().swapchain_maintenance1 {
+                return Err(SwapchainCreationError::FeatureNotEnabled {
+                    feature: "swapchain_maintenance1",
+                    reason: "`present_modes` was not empty",
+                });
+            }
+
+            // VUID-VkSwapchainPresentModesCreateInfoEXT-presentMode-07764
+            if !present_modes.contains(&present_mode) {
+                return Err(SwapchainCreationError::PresentModesMissingPresentMode);
+            }
+        }
+
+        if scaling_behavior.is_some() && !device.enabled_features().swapchain_maintenance1 {
+            return Err(SwapchainCreationError::FeatureNotEnabled {
+                feature: "swapchain_maintenance1",
+                reason: "`scaling_behavior` was `Some`",
+            });
+        }
+
+        if present_gravity.is_some() && !device.enabled_features().swapchain_maintenance1 {
+            return Err(SwapchainCreationError::FeatureNotEnabled {
+                feature: "swapchain_maintenance1",
+                reason: "`present_gravity` was `Some`",
+            });
+        }
+
+        if deferred_memory_allocation && !device.enabled_features().swapchain_maintenance1 {
+            return Err(SwapchainCreationError::FeatureNotEnabled {
+                feature: "swapchain_maintenance1",
+                reason: "`deferred_memory_allocation` was `true`",
+            });
+        }
+
+        if protected {
+            if !device.enabled_features().protected_memory {
+                return Err(SwapchainCreationError::FeatureNotEnabled {
+                    feature: "protected_memory",
+                    reason: "`protected` was `true`",
+                });
+            }
+
+            // The protected capabilities of the surface can only be queried with this extension.
+            if !device
+                .instance()
+                .enabled_extensions()
+                .khr_surface_protected_capabilities
+            {
+                return Err(SwapchainCreationError::ExtensionNotEnabled {
+                    extension: "khr_surface_protected_capabilities",
+                    reason: "`protected` was `true`",
+                });
+            }
+        }
+
+        // The feature requires the `ext_surface_maintenance1` instance extension, which is needed
+        // to query the compatible present modes and the scaling capabilities.
         let surface_capabilities = device.physical_device().surface_capabilities(
             &surface,
             SurfaceInfo {
                 full_screen_exclusive,
                 win32_monitor,
+                present_mode: device
+                    .enabled_features()
+                    .swapchain_maintenance1
+                    .then(|| present_mode),
                 ..Default::default()
             },
         )?;
@@ -378,16 +555,35 @@ impl<W> Swapchain<W> {
             *image_extent = surface_capabilities.current_extent.unwrap();
         }
 
-        // VUID-VkSwapchainCreateInfoKHR-imageExtent-01274
-        if image_extent[0] < surface_capabilities.min_image_extent[0]
-            || image_extent[1] < surface_capabilities.min_image_extent[1]
-            || image_extent[0] > surface_capabilities.max_image_extent[0]
-            || image_extent[1] > surface_capabilities.max_image_extent[1]
+        // With a scaling behavior, the images are scaled to the surface, so they can have other
+        // dimensions than the surface supports.
+        let (min_image_extent, max_image_extent) = if scaling_behavior.is_some() {
+            (
+                surface_capabilities
+                    .min_scaled_image_extent
+                    .unwrap_or(surface_capabilities.min_image_extent),
+                surface_capabilities
+                    .max_scaled_image_extent
+                    .unwrap_or(surface_capabilities.max_image_extent),
+            )
+        } else {
+            (
+                surface_capabilities.min_image_extent,
+                surface_capabilities.max_image_extent,
+            )
+        };
+
+        // VUID-VkSwapchainCreateInfoKHR-pNext-07781
+        // VUID-VkSwapchainCreateInfoKHR-pNext-07782
+        if image_extent[0] < min_image_extent[0]
+            || image_extent[1] < min_image_extent[1]
+            || image_extent[0] > max_image_extent[0]
+            || image_extent[1] > max_image_extent[1]
         {
             return Err(SwapchainCreationError::ImageExtentNotSupported {
                 provided: *image_extent,
-                min_supported: surface_capabilities.min_image_extent,
-                max_supported: surface_capabilities.max_image_extent,
+                min_supported: min_image_extent,
+                max_supported: max_image_extent,
             });
         }
 
@@ -397,6 +593,11 @@ impl<W> Swapchain<W> {
             return Err(SwapchainCreationError::ImageExtentZeroLengthDimensions);
         }
 
+        // VUID-VkSwapchainCreateInfoKHR-flags-03187
+        if protected && !surface_capabilities.supports_protected {
+            return Err(SwapchainCreationError::ProtectedNotSupported);
+        }
+
         // VUID-VkSwapchainCreateInfoKHR-imageArrayLayers-01275
         if image_array_layers == 0
             || image_array_layers > surface_capabilities.max_image_array_layers
@@ -460,15 +661,62 @@ impl<W> Swapchain<W> {
             });
         }
 
-        // VUID-VkSwapchainCreateInfoKHR-presentMode-01281
-        if !device
+        let surface_present_modes: SmallVec<[_; 4]> = device
             .physical_device()
             .surface_present_modes(&surface)?
-            .any(|mode| mode == present_mode)
-        {
+            .collect();
+
+        // VUID-VkSwapchainCreateInfoKHR-presentMode-01281
+        if !surface_present_modes.contains(&present_mode) {
             return Err(SwapchainCreationError::PresentModeNotSupported);
         }
 
+        for &mode in present_modes {
+            // VUID-VkSwapchainPresentModesCreateInfoEXT-None-07762
+            if !surface_present_modes.contains(&mode) {
+                return Err(SwapchainCreationError::PresentModeNotSupported);
+            }
+
+            // VUID-VkSwapchainPresentModesCreateInfoEXT-pPresentModes-07763
+            if !surface_capabilities
+                .compatible_present_modes
+                .contains(&mode)
+            {
+                return Err(SwapchainCreationError::PresentModeNotCompatible {
+                    provided: mode,
+                    compatible: surface_capabilities.compatible_present_modes.clone(),
+                });
+            }
+        }
+
+        if let Some(scaling_behavior) = scaling_behavior {
+            // VUID-VkSwapchainPresentScalingCreateInfoEXT-scalingBehavior-07770
+            if !surface_capabilities
+                .supported_present_scaling
+                .supports(scaling_behavior)
+            {
+                return Err(SwapchainCreationError::PresentScalingNotSupported {
+                    provided: scaling_behavior,
+                    supported: surface_capabilities.supported_present_scaling,
+                });
+            }
+        }
+
+        if let Some(present_gravity) = present_gravity {
+            // VUID-VkSwapchainPresentScalingCreateInfoEXT-presentGravityX-07772
+            // VUID-VkSwapchainPresentScalingCreateInfoEXT-presentGravityY-07773
+            if !present_gravity
+                .iter()
+                .zip(surface_capabilities.supported_present_gravity.iter())
+                .all(|(&gravity, supported)| supported.supports(gravity))
+            {
+                return Err(SwapchainCreationError::PresentGravityNotSupported {
+                    provided: present_gravity,
+                    supported: surface_capabilities.supported_present_gravity,
+                });
+            }
+        }
+
         // VUID-VkSwapchainCreateInfoKHR-imageFormat-01778
         if device
             .physical_device()
@@ -477,6 +725,7 @@ impl<W> Swapchain<W> {
                 image_type: ImageType::Dim2d,
                 tiling: ImageTiling::Optimal,
                 usage: image_usage,
+                mutable_format: !image_view_formats.is_empty(),
                 ..Default::default()
             })?
             .is_none()
@@ -501,12 +750,19 @@ impl<W> Swapchain<W> {
             image_array_layers,
             image_usage,
             ref image_sharing,
+            ref image_view_formats,
+            protected,
             pre_transform,
             composite_alpha,
             present_mode,
+            ref present_modes,
+            scaling_behavior,
+            present_gravity,
             clipped,
+            deferred_memory_allocation,
             full_screen_exclusive,
             win32_monitor,
+            device_group_present_modes,
             _ne: _,
         } = create_info;
 
@@ -540,8 +796,79 @@ impl<W> Swapchain<W> {
                 None
             };
 
+        let image_view_formats_vk: SmallVec<[_; 4]> = image_view_formats
+            .iter()
+            .copied()
+            .map(ash::vk::Format::from)
+            .collect();
+        let mut image_format_list_info = if !image_view_formats_vk.is_empty() {
+            Some(ash::vk::ImageFormatListCreateInfo {
+                view_format_count: image_view_formats_vk.len() as u32,
+                p_view_formats: image_view_formats_vk.as_ptr(),
+                ..Default::default()
+            })
+        } else {
+            None
+        };
+
+        let present_modes_vk: SmallVec<[_; 4]> = present_modes
+            .iter()
+            .copied()
+            .map(ash::vk::PresentModeKHR::from)
+            .collect();
+        let mut present_modes_info = if !present_modes_vk.is_empty() {
+            Some(ash::vk::SwapchainPresentModesCreateInfoEXT {
+                present_mode_count: present_modes_vk.len() as u32,
+                p_present_modes: present_modes_vk.as_ptr(),
+                ..Default::default()
+            })
+        } else {
+            None
+        };
+
+        let mut present_scaling_info = if scaling_behavior.is_some() || present_gravity.is_some() {
+            let [present_gravity_x, present_gravity_y] = present_gravity
+                .map_or([Default::default(); 2], |gravity| {
+                    [gravity[0].into(), gravity[1].into()]
+                });
+
+            Some(ash::vk::SwapchainPresentScalingCreateInfoEXT {
+                scaling_behavior: scaling_behavior.map_or(Default::default(), Into::into),
+                present_gravity_x,
+                present_gravity_y,
+                ..Default::default()
+            })
+        } else {
+            None
+        };
+
+        // Without this structure, the images are only presented in the local mode.
+        let mut device_group_info =
+            if device_group_present_modes != DeviceGroupPresentModes::local() {
+                Some(ash::vk::DeviceGroupSwapchainCreateInfoKHR {
+                    modes: device_group_present_modes.into(),
+                    ..Default::default()
+                })
+            } else {
+                None
+            };
+
+        let mut flags = ash::vk::SwapchainCreateFlagsKHR::empty();
+
+        if image_format_list_info.is_some() {
+            flags |= ash::vk::SwapchainCreateFlagsKHR::MUTABLE_FORMAT;
+        }
+
+        if deferred_memory_allocation {
+            flags |= ash::vk::SwapchainCreateFlagsKHR::DEFERRED_MEMORY_ALLOCATION_EXT;
+        }
+
+        if protected {
+            flags |= ash::vk::SwapchainCreateFlagsKHR::PROTECTED;
+        }
+
         let mut create_info = ash::vk::SwapchainCreateInfoKHR {
-            flags: ash::vk::SwapchainCreateFlagsKHR::empty(),
+            flags,
             surface: surface.internal_object(),
             min_image_count,
             image_format: image_format.unwrap().into(),
@@ -577,6 +904,26 @@ impl<W> Swapchain<W> {
             create_info.p_next = surface_full_screen_exclusive_win32_info as *const _ as *const _;
         }
 
+        if let Some(image_format_list_info) = image_format_list_info.as_mut() {
+            image_format_list_info.p_next = create_info.p_next as *mut _;
+            create_info.p_next = image_format_list_info as *const _ as *const _;
+        }
+
+        if let Some(present_modes_info) = present_modes_info.as_mut() {
+            present_modes_info.p_next = create_info.p_next as *mut _;
+            create_info.p_next = present_modes_info as *const _ as *const _;
+        }
+
+        if let Some(present_scaling_info) = present_scaling_info.as_mut() {
+            present_scaling_info.p_next = create_info.p_next as *mut _;
+            create_info.p_next = present_scaling_info as *const _ as *const _;
+        }
+
+        if let Some(device_group_info) = device_group_info.as_mut() {
+            device_group_info.p_next = create_info.p_next as *mut _;
+            create_info.p_next = device_group_info as *const _ as *const _;
+        }
+
         let fns = device.fns();
 
         let handle = {
@@ -590,6 +937,8 @@ impl<W> Swapchain<W> {
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         let image_handles = loop {
             let mut count = 0;
             check_errors((fns.khr_swapchain.get_swapchain_images_khr)(
@@ -627,6 +976,8 @@ impl<W> Swapchain<W> {
             image_array_layers,
             image_usage,
             ref image_sharing, // TODO: put this in the image too
+            ref image_view_formats,
+            protected,
             ..
         } = create_info;
 
@@ -645,16 +996,22 @@ impl<W> Swapchain<W> {
                         handle,
                         image_usage,
                         image_format.unwrap(),
-                        ImageCreateFlags::none(),
+                        ImageCreateFlags {
+                            mutable_format: !image_view_formats.is_empty(),
+                            protected,
+                            ..ImageCreateFlags::none()
+                        },
                         dims,
                         SampleCount::Sample1,
                         1,
+                        image_view_formats.clone(),
                     )
                 };
 
                 ImageEntry {
                     image: img,
                     undefined_layout: AtomicBool::new(true),
+                    ownership_transfer: Mutex::new(None),
                 }
             })
             .collect()
@@ -671,12 +1028,19 @@ impl<W> Swapchain<W> {
             image_array_layers: self.image_array_layers,
             image_usage: self.image_usage,
             image_sharing: self.image_sharing.clone(),
+            image_view_formats: self.image_view_formats.clone(),
+            protected: self.protected,
             pre_transform: self.pre_transform,
             composite_alpha: self.composite_alpha,
             present_mode: self.present_mode,
+            present_modes: self.present_modes.clone(),
+            scaling_behavior: self.scaling_behavior,
+            present_gravity: self.present_gravity,
             clipped: self.clipped,
+            deferred_memory_allocation: self.deferred_memory_allocation,
             full_screen_exclusive: self.full_screen_exclusive,
             win32_monitor: self.win32_monitor,
+            device_group_present_modes: self.device_group_present_modes,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -729,6 +1093,19 @@ impl<W> Swapchain<W> {
         self.image_array_layers
     }
 
+    /// Returns the formats that image views of the images of the swapchain can have. If empty,
+    /// image views must have the same format as the images.
+    #[inline]
+    pub fn image_view_formats(&self) -> &[Format] {
+        &self.image_view_formats
+    }
+
+    /// Returns whether the images of the swapchain are protected images.
+    #[inline]
+    pub fn protected(&self) -> bool {
+        self.protected
+    }
+
     /// Returns the pre-transform that was passed when creating the swapchain.
     #[inline]
     pub fn pre_transform(&self) -> SurfaceTransform {
@@ -747,12 +1124,45 @@ impl<W> Swapchain<W> {
         self.present_mode
     }
 
+    /// Returns the present modes that the swapchain can switch to when presenting. If empty,
+    /// only `present_mode` can be used.
+    #[inline]
+    pub fn present_modes(&self) -> &[PresentMode] {
+        &self.present_modes
+    }
+
+    /// Returns the scaling behavior that was passed when creating the swapchain.
+    #[inline]
+    pub fn scaling_behavior(&self) -> Option<PresentScaling> {
+        self.scaling_behavior
+    }
+
+    /// Returns the present gravity that was passed when creating the swapchain.
+    #[inline]
+    pub fn present_gravity(&self) -> Option<[PresentGravity; 2]> {
+        self.present_gravity
+    }
+
     /// Returns the value of `clipped` that was passed when creating the swapchain.
     #[inline]
     pub fn clipped(&self) -> bool {
         self.clipped
     }
 
+    /// Returns the value of `deferred_memory_allocation` that was passed when creating the
+    /// swapchain.
+    #[inline]
+    pub fn deferred_memory_allocation(&self) -> bool {
+        self.deferred_memory_allocation
+    }
+
+    /// Returns the value of `device_group_present_modes` that was passed when creating the
+    /// swapchain.
+    #[inline]
+    pub fn device_group_present_modes(&self) -> DeviceGroupPresentModes {
+        self.device_group_present_modes
+    }
+
     /// Returns the value of 'full_screen_exclusive` that was passed when creating the swapchain.
     #[inline]
     pub fn full_screen_exclusive(&self) -> FullScreenExclusive {
@@ -828,6 +1238,198 @@ impl<W> Swapchain<W> {
         }
     }
 
+    /// Waits until the image that was presented with `present_id`, or an image that was presented
+    /// with a greater ID, has been presented to the user, or until `timeout` has elapsed.
+    ///
+    /// Returns whether the swapchain no longer matches the surface exactly, but can still be used
+    /// to present images, like [`AcquiredImage::suboptimal`]. A timeout of zero can be used to
+    /// check if the image has already been presented without blocking.
+    ///
+    /// The [`present_wait`](crate::device::Features::present_wait) feature must be enabled on the
+    /// device. Present IDs are provided with [`present_with_id`].
+    pub fn wait_for_present(
+        &self,
+        present_id: NonZeroU64,
+        timeout: Option<Duration>,
+    ) -> Result<bool, PresentWaitError> {
+        // VUID-vkWaitForPresentKHR-presentWait-06234
+        if !self.device.enabled_features().present_wait {
+            return Err(PresentWaitError::FeatureNotEnabled {
+                feature: "present_wait",
+                reason: "waited for a present",
+            });
+        }
+
+        let retired = self.retired.lock();
+
+        // VUID-vkWaitForPresentKHR-swapchain-04997
+        if *retired {
+            return Err(PresentWaitError::Retired);
+        }
+
+        let timeout_ns = if let Some(timeout) = timeout {
+            timeout
+                .as_secs()
+                .saturating_mul(1_000_000_000)
+                .saturating_add(timeout.subsec_nanos() as u64)
+        } else {
+            u64::MAX
+        };
+
+        let result = unsafe {
+            let fns = self.device.fns();
+            self.device
+                .check_errors((fns.khr_present_wait.wait_for_present_khr)(
+                    self.device.internal_object(),
+                    self.handle,
+                    present_id.get(),
+                    timeout_ns,
+                ))?
+        };
+
+        match result {
+            Success::Success => Ok(false),
+            Success::Suboptimal => Ok(true),
+            Success::Timeout => Err(PresentWaitError::Timeout),
+            s => panic!("unexpected success value: {:?}", s),
+        }
+    }
+
+    /// Sets the HDR metadata of the swapchain, which describes the display that the content was
+    /// mastered on and the luminance of the content.
+    ///
+    /// The metadata applies to the images that are presented after this call. It is meant for the
+    /// HDR color spaces, such as [`ColorSpace::Hdr10St2084`], and the presentation engine may
+    /// use it to map the content to the capabilities of the display.
+    ///
+    /// The [`ext_hdr_metadata`](crate::device::DeviceExtensions::ext_hdr_metadata) extension must
+    /// be enabled on the device.
+    pub fn set_hdr_metadata(&self, metadata: HdrMetadata) -> Result<(), HdrMetadataError> {
+        if !self.device.enabled_extensions().ext_hdr_metadata {
+            return Err(HdrMetadataError::ExtensionNotEnabled {
+                extension: "ext_hdr_metadata",
+                reason: "set the HDR metadata of a swapchain",
+            });
+        }
+
+        let HdrMetadata {
+            display_primary_red,
+            display_primary_green,
+            display_primary_blue,
+            white_point,
+            max_luminance,
+            min_luminance,
+            max_content_light_level,
+            max_frame_average_light_level,
+            _ne: _,
+        } = metadata;
+
+        let xy_color = |[x, y]: [f32; 2]| ash::vk::XYColorEXT { x, y };
+        let metadata = ash::vk::HdrMetadataEXT {
+            display_primary_red: xy_color(display_primary_red),
+            display_primary_green: xy_color(display_primary_green),
+            display_primary_blue: xy_color(display_primary_blue),
+            white_point: xy_color(white_point),
+            max_luminance,
+            min_luminance,
+            max_content_light_level,
+            max_frame_average_light_level,
+            ..Default::default()
+        };
+
+        unsafe {
+            let fns = self.device.fns();
+            (fns.ext_hdr_metadata.set_hdr_metadata_ext)(
+                self.device.internal_object(),
+                1,
+                &self.handle,
+                &metadata,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns the duration of a refresh cycle of the display that the swapchain presents to.
+    ///
+    /// The [`google_display_timing`](crate::device::DeviceExtensions::google_display_timing)
+    /// extension must be enabled on the device.
+    pub fn refresh_cycle_duration(&self) -> Result<Duration, DisplayTimingError> {
+        if !self.device.enabled_extensions().google_display_timing {
+            return Err(DisplayTimingError::ExtensionNotEnabled {
+                extension: "google_display_timing",
+                reason: "queried the refresh cycle duration",
+            });
+        }
+
+        let properties = unsafe {
+            let fns = self.device.fns();
+            let mut properties = MaybeUninit::uninit();
+            self.device.check_errors((fns
+                .google_display_timing
+                .get_refresh_cycle_duration_google)(
+                self.device.internal_object(),
+                self.handle,
+                properties.as_mut_ptr(),
+            ))?;
+            properties.assume_init()
+        };
+
+        Ok(Duration::from_nanos(properties.refresh_duration))
+    }
+
+    /// Returns the timings of the presents that have completed since the last call to this
+    /// function, in the order in which the images were presented.
+    ///
+    /// Only presents that were made with a [`PresentTime`] are reported. The implementation keeps
+    /// a limited number of timings, so this should be called regularly.
+    ///
+    /// The [`google_display_timing`](crate::device::DeviceExtensions::google_display_timing)
+    /// extension must be enabled on the device.
+    pub fn past_presentation_timings(
+        &self,
+    ) -> Result<Vec<PastPresentationTiming>, DisplayTimingError> {
+        if !self.device.enabled_extensions().google_display_timing {
+            return Err(DisplayTimingError::ExtensionNotEnabled {
+                extension: "google_display_timing",
+                reason: "queried the past presentation timings",
+            });
+        }
+
+        let timings = unsafe {
+            let fns = self.device.fns();
+
+            loop {
+                let mut count = 0;
+                self.device.check_errors((fns
+                    .google_display_timing
+                    .get_past_presentation_timing_google)(
+                    self.device.internal_object(),
+                    self.handle,
+                    &mut count,
+                    ptr::null_mut(),
+                ))?;
+
+                let mut timings = Vec::with_capacity(count as usize);
+                let result = self.device.check_errors((fns
+                    .google_display_timing
+                    .get_past_presentation_timing_google)(
+                    self.device.internal_object(),
+                    self.handle,
+                    &mut count,
+                    timings.as_mut_ptr(),
+                ))?;
+
+                if !matches!(result, Success::Incomplete) {
+                    timings.set_len(count as usize);
+                    break timings;
+                }
+            }
+        };
+
+        Ok(timings.into_iter().map(Into::into).collect())
+    }
+
     // This method is necessary to allow `SwapchainImage`s to signal when they have been
     // transitioned out of their initial `undefined` image layout.
     //
@@ -906,6 +1508,8 @@ pub struct SwapchainCreateInfo {
     /// The minimum number of images that will be created.
     ///
     /// The implementation is allowed to create more than this number, but never less.
+    /// [`SurfaceCapabilities::min_image_count_for`](crate::swapchain::SurfaceCapabilities::min_image_count_for)
+    /// returns a supported value for double or triple buffering.
     ///
     /// The default value is `2`.
     pub min_image_count: u32,
@@ -947,6 +1551,34 @@ pub struct SwapchainCreateInfo {
     /// The default value is [`Sharing::Exclusive`].
     pub image_sharing: Sharing<SmallVec<[u32; 4]>>,
 
+    /// The formats that image views created from the created images will have.
+    ///
+    /// If not empty, the images are created with the `mutable_format` flag, so that views with a
+    /// format other than `image_format` can be created from them, such as an sRGB view of a UNORM
+    /// swapchain. The list must contain `image_format` itself, every format must be compatible
+    /// with `image_format`, and the
+    /// [`khr_swapchain_mutable_format`](crate::device::DeviceExtensions::khr_swapchain_mutable_format)
+    /// extension must be enabled on the device.
+    ///
+    /// If a view format doesn't support all of `image_usage`, the views with that format must
+    /// restrict their [`usage`](crate::image::view::ImageViewCreateInfo::usage).
+    ///
+    /// The default value is empty.
+    pub image_view_formats: Vec<Format>,
+
+    /// Whether the created images are protected images, whose contents can only be accessed by
+    /// protected queue operations.
+    ///
+    /// If set to `true`, the
+    /// [`protected_memory`](crate::device::Features::protected_memory) feature must be enabled on
+    /// the device, the
+    /// [`khr_surface_protected_capabilities`](crate::instance::InstanceExtensions::khr_surface_protected_capabilities)
+    /// extension must be enabled on the instance, and the surface must
+    /// [support](crate::swapchain::SurfaceCapabilities::supports_protected) protected swapchains.
+    ///
+    /// The default value is `false`.
+    pub protected: bool,
+
     /// The transform that should be applied to an image before it is presented.
     ///
     /// The default value is [`SurfaceTransform::Identity`].
@@ -963,6 +1595,38 @@ pub struct SwapchainCreateInfo {
     /// The default is [`PresentMode::Fifo`].
     pub present_mode: PresentMode,
 
+    /// The present modes that the swapchain can switch to when presenting an image with
+    /// [`present_with_mode`], without being recreated.
+    ///
+    /// If not empty, the list must contain `present_mode`, every mode must be in the
+    /// [`compatible_present_modes`](crate::swapchain::SurfaceCapabilities::compatible_present_modes)
+    /// of the surface for `present_mode`, and the
+    /// [`swapchain_maintenance1`](crate::device::Features::swapchain_maintenance1) feature must
+    /// be enabled on the device.
+    ///
+    /// The default value is empty.
+    pub present_modes: SmallVec<[PresentMode; 4]>,
+
+    /// How the images are scaled when their dimensions don't match those of the surface. If
+    /// `None`, the behavior is platform-specific.
+    ///
+    /// If not `None`, the
+    /// [`swapchain_maintenance1`](crate::device::Features::swapchain_maintenance1) feature must
+    /// be enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub scaling_behavior: Option<PresentScaling>,
+
+    /// Where the images are placed on the surface along the X and Y axes, when they don't fill
+    /// the surface. If `None`, the behavior is platform-specific.
+    ///
+    /// If not `None`, the
+    /// [`swapchain_maintenance1`](crate::device::Features::swapchain_maintenance1) feature must
+    /// be enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub present_gravity: Option<[PresentGravity; 2]>,
+
     /// Whether the implementation is allowed to discard rendering operations that affect regions of
     /// the surface which aren't visible. This is important to take into account if your fragment
     /// shader has side-effects or if you want to read back the content of the image afterwards.
@@ -970,6 +1634,18 @@ pub struct SwapchainCreateInfo {
     /// The default value is `true`.
     pub clipped: bool,
 
+    /// Whether the implementation can wait until an image is acquired for the first time before
+    /// allocating its memory. This can reduce the amount of memory that is allocated when
+    /// swapchains are recreated often, for example when a window is being resized.
+    ///
+    /// If set to `true`, image views of an image must only be created after the image has been
+    /// acquired, and the
+    /// [`swapchain_maintenance1`](crate::device::Features::swapchain_maintenance1) feature must
+    /// be enabled on the device.
+    ///
+    /// The default value is `false`.
+    pub deferred_memory_allocation: bool,
+
     /// How full-screen exclusivity is to be handled.
     ///
     /// If set to anything other than [`FullScreenExclusive::Default`], then the
@@ -988,6 +1664,17 @@ pub struct SwapchainCreateInfo {
     /// The default value is `None`.
     pub win32_monitor: Option<Win32Monitor>,
 
+    /// For a device that was created from several physical devices, the modes in which the images
+    /// of the physical devices can be presented.
+    ///
+    /// If set to anything other than only `local`, then the device API version must be at least
+    /// 1.1, or the [`khr_device_group`](crate::device::DeviceExtensions::khr_device_group)
+    /// extension must be enabled on the device. The modes must be supported by the device for
+    /// the surface, see [`Device::surface_group_present_modes`]. Must not be empty.
+    ///
+    /// The default value is only `local`.
+    pub device_group_present_modes: DeviceGroupPresentModes,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -1002,12 +1689,19 @@ impl Default for SwapchainCreateInfo {
             image_array_layers: 1,
             image_usage: ImageUsage::none(),
             image_sharing: Sharing::Exclusive,
+            image_view_formats: Vec::new(),
+            protected: false,
             pre_transform: SurfaceTransform::Identity,
             composite_alpha: CompositeAlpha::Opaque,
             present_mode: PresentMode::Fifo,
+            present_modes: SmallVec::new(),
+            scaling_behavior: None,
+            present_gravity: None,
             clipped: true,
+            deferred_memory_allocation: false,
             full_screen_exclusive: FullScreenExclusive::Default,
             win32_monitor: None,
+            device_group_present_modes: DeviceGroupPresentModes::local(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1036,12 +1730,24 @@ pub enum SwapchainCreationError {
         reason: &'static str,
     },
 
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+
     /// The provided `composite_alpha` is not supported by the surface for this device.
     CompositeAlphaNotSupported {
         provided: CompositeAlpha,
         supported: SupportedCompositeAlpha,
     },
 
+    /// The provided `device_group_present_modes` are not supported by the surface for this
+    /// device.
+    DeviceGroupPresentModesNotSupported {
+        provided: DeviceGroupPresentModes,
+        supported: DeviceGroupPresentModes,
+    },
+
     /// The provided `format` and `color_space` are not supported by the surface for this device.
     FormatColorSpaceNotSupported,
 
@@ -1079,6 +1785,13 @@ pub enum SwapchainCreationError {
         supported: ImageUsage,
     },
 
+    /// One of the formats in the provided `image_view_formats` was not compatible with
+    /// `image_format`.
+    ImageViewFormatNotCompatible { view_format: Format },
+
+    /// The provided `image_view_formats` was not empty, but did not contain `image_format`.
+    ImageViewFormatsMissingImageFormat,
+
     /// The provided `min_image_count` is not within the range supported by the surface for this
     /// device.
     MinImageCountNotSupported {
@@ -1090,6 +1803,31 @@ pub enum SwapchainCreationError {
     /// The provided `present_mode` is not supported by the surface for this device.
     PresentModeNotSupported,
 
+    /// One of the provided `present_modes` is not compatible with `present_mode` for the surface.
+    PresentModeNotCompatible {
+        provided: PresentMode,
+        compatible: SmallVec<[PresentMode; 4]>,
+    },
+
+    /// The provided `present_modes` was not empty, but did not contain `present_mode`.
+    PresentModesMissingPresentMode,
+
+    /// The provided `present_gravity` is not supported by the surface for this device.
+    PresentGravityNotSupported {
+        provided: [PresentGravity; 2],
+        supported: [SupportedPresentGravity; 2],
+    },
+
+    /// The provided `scaling_behavior` is not supported by the surface for this device.
+    PresentScalingNotSupported {
+        provided: PresentScaling,
+        supported: SupportedPresentScaling,
+    },
+
+    /// The provided `protected` was `true`, but the surface does not support protected swapchains
+    /// for this device.
+    ProtectedNotSupported,
+
     /// The provided `pre_transform` is not supported by the surface for this device.
     PreTransformNotSupported {
         provided: SurfaceTransform,
@@ -1132,11 +1870,20 @@ impl fmt::Display for SwapchainCreationError {
                 "the extension {} must be enabled: {}",
                 extension, reason
             ),
+            Self::FeatureNotEnabled { feature, reason } => write!(
+                fmt,
+                "the feature {} must be enabled: {}",
+                feature, reason
+            ),
 
             Self::CompositeAlphaNotSupported { .. } => write!(
                 fmt,
                 "the provided `composite_alpha` is not supported by the surface for this device",
             ),
+            Self::DeviceGroupPresentModesNotSupported { .. } => write!(
+                fmt,
+                "the provided `device_group_present_modes` are not supported by the surface for this device",
+            ),
             Self::FormatColorSpaceNotSupported => write!(
                 fmt,
                 "the provided `format` and `color_space` are not supported by the surface for this device",
@@ -1168,6 +1915,15 @@ impl fmt::Display for SwapchainCreationError {
                 fmt,
                 "the provided `image_usage` has fields set that are not supported by the surface for this device",
             ),
+            Self::ImageViewFormatNotCompatible { view_format } => write!(
+                fmt,
+                "the format {:?} in the provided `image_view_formats` was not compatible with `image_format`",
+                view_format,
+            ),
+            Self::ImageViewFormatsMissingImageFormat => write!(
+                fmt,
+                "the provided `image_view_formats` was not empty, but did not contain `image_format`",
+            ),
             Self::MinImageCountNotSupported { provided, min_supported, max_supported } => write!(
                 fmt,
                 "the provided `min_image_count` ({}) is not within the range (min: {}, max: {:?}) supported by the surface for this device",
@@ -1177,10 +1933,31 @@ impl fmt::Display for SwapchainCreationError {
                 fmt,
                 "the provided `present_mode` is not supported by the surface for this device",
             ),
+            Self::PresentModeNotCompatible { provided, .. } => write!(
+                fmt,
+                "the present mode {:?} in the provided `present_modes` is not compatible with `present_mode` for the surface",
+                provided,
+            ),
+            Self::PresentModesMissingPresentMode => write!(
+                fmt,
+                "the provided `present_modes` was not empty, but did not contain `present_mode`",
+            ),
+            Self::PresentGravityNotSupported { .. } => write!(
+                fmt,
+                "the provided `present_gravity` is not supported by the surface for this device",
+            ),
+            Self::PresentScalingNotSupported { .. } => write!(
+                fmt,
+                "the provided `scaling_behavior` is not supported by the surface for this device",
+            ),
             Self::PreTransformNotSupported { .. } => write!(
                 fmt,
                 "the provided `pre_transform` is not supported by the surface for this device",
             ),
+            Self::ProtectedNotSupported => write!(
+                fmt,
+                "the surface does not support protected swapchains for this device",
+            ),
             Self::SwapchainAlreadyRetired => write!(
                 fmt,
                 "the swapchain has already been used to create a new one",
@@ -1221,6 +1998,9 @@ impl From<SurfacePropertiesError> for SwapchainCreationError {
             SurfacePropertiesError::OomError(err) => Self::OomError(err),
             SurfacePropertiesError::SurfaceLost => Self::SurfaceLost,
             SurfacePropertiesError::NotSupported => unreachable!(),
+            SurfacePropertiesError::ExtensionNotEnabled { extension, reason } => {
+                Self::ExtensionNotEnabled { extension, reason }
+            }
         }
     }
 }
@@ -1351,21 +2131,329 @@ impl From<Error> for FullScreenExclusiveError {
     }
 }
 
-impl From<OomError> for FullScreenExclusiveError {
-    #[inline]
-    fn from(err: OomError) -> FullScreenExclusiveError {
-        FullScreenExclusiveError::OomError(err)
-    }
-}
-
-/// Tries to take ownership of an image in order to draw on it.
-///
-/// The function returns the index of the image in the array of images that was returned
-/// when creating the swapchain, plus a future that represents the moment when the image will
-/// become available from the GPU (which may not be *immediately*).
+/// The HDR metadata of a swapchain, set with [`Swapchain::set_hdr_metadata`].
 ///
-/// If you try to draw on an image without acquiring it first, the execution will block. (TODO
-/// behavior may change).
+/// The chromaticity coordinates are in the CIE 1931 color space, and the luminances are in
+/// candelas per square meter (nits).
+#[derive(Clone, Debug, PartialEq)]
+pub struct HdrMetadata {
+    /// The chromaticity of the red primary of the mastering display.
+    ///
+    /// The default value is `[0.708, 0.292]`, the red primary of BT.2020.
+    pub display_primary_red: [f32; 2],
+
+    /// The chromaticity of the green primary of the mastering display.
+    ///
+    /// The default value is `[0.170, 0.797]`, the green primary of BT.2020.
+    pub display_primary_green: [f32; 2],
+
+    /// The chromaticity of the blue primary of the mastering display.
+    ///
+    /// The default value is `[0.131, 0.046]`, the blue primary of BT.2020.
+    pub display_primary_blue: [f32; 2],
+
+    /// The chromaticity of the white point of the mastering display.
+    ///
+    /// The default value is `[0.3127, 0.3290]`, the D65 white point.
+    pub white_point: [f32; 2],
+
+    /// The maximum luminance of the mastering display.
+    ///
+    /// The default value is `1000.0`.
+    pub max_luminance: f32,
+
+    /// The minimum luminance of the mastering display.
+    ///
+    /// The default value is `0.0001`.
+    pub min_luminance: f32,
+
+    /// The luminance of the brightest pixel of the content, or `0.0` if it is unknown.
+    ///
+    /// The default value is `0.0`.
+    pub max_content_light_level: f32,
+
+    /// The maximum average luminance of a frame of the content, or `0.0` if it is unknown.
+    ///
+    /// The default value is `0.0`.
+    pub max_frame_average_light_level: f32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for HdrMetadata {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            display_primary_red: [0.708, 0.292],
+            display_primary_green: [0.170, 0.797],
+            display_primary_blue: [0.131, 0.046],
+            white_point: [0.3127, 0.3290],
+            max_luminance: 1000.0,
+            min_luminance: 0.0001,
+            max_content_light_level: 0.0,
+            max_frame_average_light_level: 0.0,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Error that can happen when calling [`Swapchain::set_hdr_metadata`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HdrMetadataError {
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl error::Error for HdrMetadataError {}
+
+impl fmt::Display for HdrMetadataError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            HdrMetadataError::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+        }
+    }
+}
+
+/// Timing information to present an image with.
+///
+/// This is ignored if the
+/// [`google_display_timing`](crate::device::DeviceExtensions::google_display_timing) extension is
+/// not enabled on the device.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PresentTime {
+    /// A value chosen by the application, that identifies the present in the
+    /// [past presentation timings](Swapchain::past_presentation_timings).
+    pub present_id: u32,
+
+    /// The time in nanoseconds before which the image must not be presented, or `0` to present
+    /// the image as soon as possible.
+    ///
+    /// This is in the same time domain as [`PastPresentationTiming::actual_present_time`], which
+    /// is `CLOCK_MONOTONIC` on Android and Linux.
+    pub desired_present_time: u64,
+}
+
+impl From<PresentTime> for ash::vk::PresentTimeGOOGLE {
+    #[inline]
+    fn from(val: PresentTime) -> Self {
+        Self {
+            present_id: val.present_id,
+            desired_present_time: val.desired_present_time,
+        }
+    }
+}
+
+/// The timing of a present that has completed, as returned by
+/// [`Swapchain::past_presentation_timings`].
+///
+/// The times are in nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PastPresentationTiming {
+    /// The present ID that was provided in the [`PresentTime`] of the present.
+    pub present_id: u32,
+
+    /// The desired present time that was provided in the [`PresentTime`] of the present.
+    pub desired_present_time: u64,
+
+    /// The time when the image was presented.
+    pub actual_present_time: u64,
+
+    /// The earliest time when the image could have been presented, which can be earlier than
+    /// `actual_present_time` if `desired_present_time` was too late.
+    pub earliest_present_time: u64,
+
+    /// How early the processing of the present by the presentation engine completed, compared to
+    /// the deadline to present the image at `earliest_present_time`.
+    pub present_margin: u64,
+}
+
+impl From<ash::vk::PastPresentationTimingGOOGLE> for PastPresentationTiming {
+    #[inline]
+    fn from(val: ash::vk::PastPresentationTimingGOOGLE) -> Self {
+        Self {
+            present_id: val.present_id,
+            desired_present_time: val.desired_present_time,
+            actual_present_time: val.actual_present_time,
+            earliest_present_time: val.earliest_present_time,
+            present_margin: val.present_margin,
+        }
+    }
+}
+
+/// Error that can happen when querying the display timing of a swapchain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DisplayTimingError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The connection to the device has been lost.
+    DeviceLost,
+
+    /// The surface is no longer accessible and must be recreated.
+    SurfaceLost,
+
+    /// The surface has changed in a way that makes the swapchain unusable. You must query the
+    /// surface's new properties and recreate a new swapchain if you want to continue drawing.
+    OutOfDate,
+
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl error::Error for DisplayTimingError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            DisplayTimingError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DisplayTimingError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            DisplayTimingError::OomError(_) => write!(fmt, "not enough memory"),
+            DisplayTimingError::DeviceLost => {
+                write!(fmt, "the connection to the device has been lost")
+            }
+            DisplayTimingError::SurfaceLost => {
+                write!(fmt, "the surface of this swapchain is no longer valid")
+            }
+            DisplayTimingError::OutOfDate => write!(fmt, "the swapchain needs to be recreated"),
+            DisplayTimingError::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+        }
+    }
+}
+
+impl From<Error> for DisplayTimingError {
+    #[inline]
+    fn from(err: Error) -> DisplayTimingError {
+        match err {
+            err @ Error::OutOfHostMemory => DisplayTimingError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => DisplayTimingError::OomError(OomError::from(err)),
+            Error::DeviceLost => DisplayTimingError::DeviceLost,
+            Error::SurfaceLost => DisplayTimingError::SurfaceLost,
+            Error::OutOfDate => DisplayTimingError::OutOfDate,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+/// Error that can happen when calling [`Swapchain::wait_for_present`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PresentWaitError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The connection to the device has been lost.
+    DeviceLost,
+
+    /// The surface is no longer accessible and must be recreated.
+    SurfaceLost,
+
+    /// The swapchain has lost or doesn't have full-screen exclusivity possibly for
+    /// implementation-specific reasons outside of the application’s control.
+    FullScreenExclusiveLost,
+
+    /// The surface has changed in a way that makes the swapchain unusable. You must query the
+    /// surface's new properties and recreate a new swapchain if you want to continue drawing.
+    OutOfDate,
+
+    /// The timeout of the function has been reached before the image was presented.
+    Timeout,
+
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+
+    /// The swapchain has been used to create a new swapchain.
+    Retired,
+}
+
+impl error::Error for PresentWaitError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            PresentWaitError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PresentWaitError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            PresentWaitError::OomError(_) => write!(fmt, "not enough memory"),
+            PresentWaitError::DeviceLost => {
+                write!(fmt, "the connection to the device has been lost")
+            }
+            PresentWaitError::SurfaceLost => {
+                write!(fmt, "the surface of this swapchain is no longer valid")
+            }
+            PresentWaitError::FullScreenExclusiveLost => {
+                write!(fmt, "the swapchain no longer has full-screen exclusivity")
+            }
+            PresentWaitError::OutOfDate => write!(fmt, "the swapchain needs to be recreated"),
+            PresentWaitError::Timeout => {
+                write!(fmt, "the image has not been presented before the timeout")
+            }
+            PresentWaitError::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
+            PresentWaitError::Retired => {
+                write!(fmt, "the swapchain has been used to create a new swapchain")
+            }
+        }
+    }
+}
+
+impl From<Error> for PresentWaitError {
+    #[inline]
+    fn from(err: Error) -> PresentWaitError {
+        match err {
+            err @ Error::OutOfHostMemory => PresentWaitError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => PresentWaitError::OomError(OomError::from(err)),
+            Error::DeviceLost => PresentWaitError::DeviceLost,
+            Error::SurfaceLost => PresentWaitError::SurfaceLost,
+            Error::OutOfDate => PresentWaitError::OutOfDate,
+            Error::FullScreenExclusiveLost => PresentWaitError::FullScreenExclusiveLost,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<OomError> for FullScreenExclusiveError {
+    #[inline]
+    fn from(err: OomError) -> FullScreenExclusiveError {
+        FullScreenExclusiveError::OomError(err)
+    }
+}
+
+/// Tries to take ownership of an image in order to draw on it.
+///
+/// The function returns the index of the image in the array of images that was returned
+/// when creating the swapchain, plus a future that represents the moment when the image will
+/// become available from the GPU (which may not be *immediately*).
+///
+/// If you try to draw on an image without acquiring it first, the execution will block. (TODO
+/// behavior may change).
 ///
 /// The second field in the tuple in the Ok result is a bool represent if the acquisition was
 /// suboptimal. In this case the acquired image is still usable, but the swapchain should be
@@ -1398,6 +2486,16 @@ pub fn acquire_next_image<W>(
         acquire_result?
     };
 
+    let ownership_transfer = swapchain.images[id].ownership_transfer.lock().take();
+    let (semaphore, ownership_return) = match ownership_transfer {
+        Some((queue, present_queue)) => unsafe {
+            let (semaphore, ownership_return) =
+                OwnershipReturn::submit(&swapchain, id, queue, &present_queue, semaphore)?;
+            (semaphore, Some(ownership_return))
+        },
+        None => (semaphore, None),
+    };
+
     Ok((
         id,
         suboptimal,
@@ -1405,6 +2503,7 @@ pub fn acquire_next_image<W>(
             swapchain,
             semaphore: Some(semaphore),
             fence: Some(fence),
+            ownership_return,
             image_id: id,
             finished: AtomicBool::new(false),
         },
@@ -1418,6 +2517,12 @@ pub fn acquire_next_image<W>(
 ///
 /// The actual behavior depends on the present mode that you passed when creating the
 /// swapchain.
+///
+/// `queue` doesn't need to be the queue that `before` was submitted to. If it isn't, the present
+/// waits for `before` with a semaphore. If the queues are of different families and the swapchain
+/// was created with [`Sharing::Exclusive`], the ownership of the image is also transferred to the
+/// family of `queue`. It is transferred back to the family of the queue of `before` when the image
+/// is acquired again, so the image must then be used on a queue of that family.
 pub fn present<F, W>(
     swapchain: Arc<Swapchain<W>>,
     before: F,
@@ -1436,12 +2541,19 @@ where
     // always returns false anyway (by design), we don't need to do it.
     assert!(before.check_image_access(&swapchain_image, ImageLayout::PresentSrc, true, &queue).is_ok());         // TODO: return error instead*/
 
+    let queue_transfer = PresentQueueTransfer::new(&swapchain, before.queue(), &queue);
+
     PresentFuture {
         previous: before,
         queue,
+        queue_transfer,
         swapchain,
         image_id: index,
         present_region: None,
+        present_id: None,
+        present_time: None,
+        present_mode: None,
+        device_group_present: None,
         flushed: AtomicBool::new(false),
         finished: AtomicBool::new(false),
     }
@@ -1451,6 +2563,8 @@ where
 /// Areas outside the present region may be ignored by Vulkan in order to optimize presentation.
 ///
 /// This is just an optimization hint, as the Vulkan driver is free to ignore the given present region.
+/// The whole image must therefore still be up to date, not only the region. The parts of the
+/// region that are outside of the images of the swapchain are ignored.
 ///
 /// If `VK_KHR_incremental_present` is not enabled on the device, the parameter will be ignored.
 pub fn present_incremental<F, W>(
@@ -1472,12 +2586,164 @@ where
     // always returns false anyway (by design), we don't need to do it.
     assert!(before.check_image_access(&swapchain_image, ImageLayout::PresentSrc, true, &queue).is_ok());         // TODO: return error instead*/
 
+    let queue_transfer = PresentQueueTransfer::new(&swapchain, before.queue(), &queue);
+
     PresentFuture {
         previous: before,
         queue,
+        queue_transfer,
         swapchain,
         image_id: index,
         present_region: Some(present_region),
+        present_id: None,
+        present_time: None,
+        present_mode: None,
+        device_group_present: None,
+        flushed: AtomicBool::new(false),
+        finished: AtomicBool::new(false),
+    }
+}
+
+/// Same as `swapchain::present`, except it allows specifying a present ID.
+///
+/// The present ID identifies the present operation in
+/// [`Swapchain::wait_for_present`]. It must be greater than the IDs that were previously provided
+/// for the same swapchain, and the [`present_id`](crate::device::Features::present_id) feature
+/// must be enabled on the device. Otherwise, an error is returned when the future is flushed.
+pub fn present_with_id<F, W>(
+    swapchain: Arc<Swapchain<W>>,
+    before: F,
+    queue: Arc<Queue>,
+    index: usize,
+    present_id: NonZeroU64,
+) -> PresentFuture<F, W>
+where
+    F: GpuFuture,
+{
+    assert!(index < swapchain.images.len());
+
+    let queue_transfer = PresentQueueTransfer::new(&swapchain, before.queue(), &queue);
+
+    PresentFuture {
+        previous: before,
+        queue,
+        queue_transfer,
+        swapchain,
+        image_id: index,
+        present_region: None,
+        present_id: Some(present_id),
+        present_time: None,
+        present_mode: None,
+        device_group_present: None,
+        flushed: AtomicBool::new(false),
+        finished: AtomicBool::new(false),
+    }
+}
+
+/// Same as `swapchain::present`, except it allows specifying the timing of the present.
+///
+/// If `VK_GOOGLE_display_timing` is not enabled on the device, the parameter will be ignored.
+pub fn present_with_time<F, W>(
+    swapchain: Arc<Swapchain<W>>,
+    before: F,
+    queue: Arc<Queue>,
+    index: usize,
+    present_time: PresentTime,
+) -> PresentFuture<F, W>
+where
+    F: GpuFuture,
+{
+    assert!(index < swapchain.images.len());
+
+    let queue_transfer = PresentQueueTransfer::new(&swapchain, before.queue(), &queue);
+
+    PresentFuture {
+        previous: before,
+        queue,
+        queue_transfer,
+        swapchain,
+        image_id: index,
+        present_region: None,
+        present_id: None,
+        present_time: Some(present_time),
+        present_mode: None,
+        device_group_present: None,
+        flushed: AtomicBool::new(false),
+        finished: AtomicBool::new(false),
+    }
+}
+
+/// Same as `swapchain::present`, except it allows specifying the present mode to use for this
+/// present.
+///
+/// The present mode must be one of the [`present_modes`](Swapchain::present_modes) that the
+/// swapchain was created with. Otherwise, an error is returned when the future is flushed. The
+/// mode stays in use for the following presents, until another mode is specified.
+pub fn present_with_mode<F, W>(
+    swapchain: Arc<Swapchain<W>>,
+    before: F,
+    queue: Arc<Queue>,
+    index: usize,
+    present_mode: PresentMode,
+) -> PresentFuture<F, W>
+where
+    F: GpuFuture,
+{
+    assert!(index < swapchain.images.len());
+
+    let queue_transfer = PresentQueueTransfer::new(&swapchain, before.queue(), &queue);
+
+    PresentFuture {
+        previous: before,
+        queue,
+        queue_transfer,
+        swapchain,
+        image_id: index,
+        present_region: None,
+        present_id: None,
+        present_time: None,
+        present_mode: Some(present_mode),
+        device_group_present: None,
+        flushed: AtomicBool::new(false),
+        finished: AtomicBool::new(false),
+    }
+}
+
+/// Same as `swapchain::present`, except it allows specifying which physical devices present the
+/// image, for a device that was created from several physical devices.
+///
+/// `device_mask` contains the device indices of the physical devices that present the image, and
+/// `mode` must be one of the [`device_group_present_modes`](Swapchain::device_group_present_modes)
+/// that the swapchain was created with. In the `Local` and `Remote` modes, `device_mask` must
+/// contain exactly one physical device. The device API version must be at least 1.1, or the
+/// [`khr_device_group`](crate::device::DeviceExtensions::khr_device_group) extension must be
+/// enabled on the device. Otherwise, an error is returned when the future is flushed.
+pub fn present_with_device_mask<F, W>(
+    swapchain: Arc<Swapchain<W>>,
+    before: F,
+    queue: Arc<Queue>,
+    index: usize,
+    device_mask: u32,
+    mode: DeviceGroupPresentMode,
+) -> PresentFuture<F, W>
+where
+    F: GpuFuture,
+{
+    assert!(index < swapchain.images.len());
+
+    let queue_transfer = PresentQueueTransfer::new(&swapchain, before.queue(), &queue);
+
+    PresentFuture {
+        previous: before,
+        queue,
+        queue_transfer,
+        swapchain,
+        image_id: index,
+        present_region: None,
+        present_id: None,
+        present_time: None,
+        present_mode: None,
+        device_group_present: Some((device_mask, mode)),
         flushed: AtomicBool::new(false),
         finished: AtomicBool::new(false),
     }
@@ -1494,6 +2760,9 @@ pub struct SwapchainAcquireFuture<W> {
     // Fence that is signalled when the acquire is complete. Empty if the acquire has already
     // happened.
     fence: Option<Fence>,
+    // `Some` if the ownership of the image is transferred back from the family of the queue that
+    // it was last presented on.
+    ownership_return: Option<OwnershipReturn>,
     finished: AtomicBool,
 }
 
@@ -1596,8 +2865,33 @@ unsafe impl<W> GpuFuture for SwapchainAcquireFuture<W> {
 
 impl<W> Drop for SwapchainAcquireFuture<W> {
     fn drop(&mut self) {
+        if !*self.finished.get_mut() {
+            if let Some(ownership_return) = &self.ownership_return {
+                // Block until the ownership has been transferred back, so that the semaphores
+                // and command buffers are no longer in use.
+                match ownership_return.queue.wait() {
+                    Ok(()) | Err(WaitIdleError::DeviceLost) => (),
+                    Err(err) => panic!("{}", err),
+                }
+            }
+        }
+
         if let Some(ref fence) = self.fence {
-            fence.wait(None).unwrap(); // TODO: handle error?
+            // TODO: handle error?
+            match fence.wait(None) {
+                // If the device was lost, the fence will never be signaled.
+                Ok(()) | Err(FenceWaitError::DeviceLostError) => (),
+                Err(err) => panic!("{}", err),
+            }
+
+            if let Some(semaphore) = &mut self.semaphore {
+                // If the future was never submitted, the semaphore is signaled and nothing will
+                // wait on it.
+                if !*self.finished.get_mut() {
+                    semaphore.keep_out_of_pool();
+                }
+            }
+
             self.semaphore = None;
         }
 
@@ -1606,6 +2900,73 @@ impl<W> Drop for SwapchainAcquireFuture<W> {
     }
 }
 
+// The objects that transfer the ownership of an acquired image back to the family of the queue
+// that it was transferred from when it was last presented.
+struct OwnershipReturn {
+    // The queue that the ownership is transferred back to.
+    queue: Arc<Queue>,
+    // The semaphore of the acquire, and the semaphore that is signaled once the ownership has
+    // been released by the present queue.
+    semaphores: [Semaphore; 2],
+    // The command buffers that release and acquire the ownership of the image.
+    command_buffers: [(UnsafeCommandBuffer, StandardCommandPoolAlloc); 2],
+}
+
+impl OwnershipReturn {
+    // Transfers the ownership of an acquired image from the family of `present_queue` to the
+    // family of `queue`, after `acquire_semaphore` is signaled. Returns the semaphore that is
+    // signaled once the ownership has been acquired by `queue`.
+    unsafe fn submit<W>(
+        swapchain: &Swapchain<W>,
+        image_id: usize,
+        queue: Arc<Queue>,
+        present_queue: &Queue,
+        acquire_semaphore: Semaphore,
+    ) -> Result<(Semaphore, OwnershipReturn), AcquireError> {
+        let image = &swapchain.images[image_id].image;
+        let transfer = QueueFamilyTransfer {
+            source_index: present_queue.family().id(),
+            destination_index: queue.family().id(),
+        };
+        let release =
+            record_ownership_transfer(present_queue.family(), image.clone(), transfer, true)?;
+        let acquire = record_ownership_transfer(queue.family(), image.clone(), transfer, false)?;
+        let release_semaphore = Semaphore::from_pool(swapchain.device.clone())?;
+        let semaphore = Semaphore::from_pool(swapchain.device.clone())?;
+        let all_commands = PipelineStages {
+            all_commands: true,
+            ..PipelineStages::none()
+        };
+
+        let mut builder = SubmitCommandBufferBuilder::new();
+        builder.add_wait_semaphore(&acquire_semaphore, all_commands);
+        builder.add_command_buffer(&release.0);
+        builder.add_signal_semaphore(&release_semaphore);
+        builder.submit(present_queue)?;
+
+        let mut builder = SubmitCommandBufferBuilder::new();
+        builder.add_wait_semaphore(&release_semaphore, all_commands);
+        builder.add_command_buffer(&acquire.0);
+        builder.add_signal_semaphore(&semaphore);
+
+        if let Err(err) = builder.submit(&queue) {
+            // The release has been submitted, so its objects must be kept alive until it has
+            // completed.
+            present_queue.wait().ok();
+            return Err(err.into());
+        }
+
+        Ok((
+            semaphore,
+            OwnershipReturn {
+                queue,
+                semaphores: [acquire_semaphore, release_semaphore],
+                command_buffers: [release, acquire],
+            },
+        ))
+    }
+}
+
 unsafe impl<W> DeviceOwned for SwapchainAcquireFuture<W> {
     #[inline]
     fn device(&self) -> &Arc<Device> {
@@ -1672,6 +3033,16 @@ impl fmt::Display for AcquireError {
     }
 }
 
+impl From<SubmitCommandBufferError> for AcquireError {
+    #[inline]
+    fn from(err: SubmitCommandBufferError) -> AcquireError {
+        match err {
+            SubmitCommandBufferError::OomError(err) => AcquireError::OomError(err),
+            SubmitCommandBufferError::DeviceLost => AcquireError::DeviceLost,
+        }
+    }
+}
+
 impl From<SemaphoreCreationError> for AcquireError {
     fn from(err: SemaphoreCreationError) -> Self {
         AcquireError::SemaphoreError(err)
@@ -1708,9 +3079,16 @@ where
 {
     previous: P,
     queue: Arc<Queue>,
+    // `Some` if the previous future is submitted to a different queue than `queue`, or the error
+    // that happened while creating the semaphores of the transfer.
+    queue_transfer: Option<Result<PresentQueueTransfer, OomError>>,
     swapchain: Arc<Swapchain<W>>,
     image_id: usize,
     present_region: Option<PresentRegion>,
+    present_id: Option<NonZeroU64>,
+    present_time: Option<PresentTime>,
+    present_mode: Option<PresentMode>,
+    device_group_present: Option<(u32, DeviceGroupPresentMode)>,
     // True if `flush()` has been called on the future, which means that the present command has
     // been submitted.
     flushed: AtomicBool,
@@ -1734,6 +3112,281 @@ where
     pub fn swapchain(&self) -> &Arc<Swapchain<W>> {
         &self.swapchain
     }
+
+    /// Returns the present ID that the image is presented with, if any.
+    #[inline]
+    pub fn present_id(&self) -> Option<NonZeroU64> {
+        self.present_id
+    }
+
+    /// Returns the timing that the image is presented with, if any.
+    #[inline]
+    pub fn present_time(&self) -> Option<PresentTime> {
+        self.present_time
+    }
+
+    /// Returns the present mode that the image is presented with, if any.
+    #[inline]
+    pub fn present_mode(&self) -> Option<PresentMode> {
+        self.present_mode
+    }
+
+    /// Returns the device mask and the device group present mode that the image is presented
+    /// with, if any.
+    #[inline]
+    pub fn device_group_present(&self) -> Option<(u32, DeviceGroupPresentMode)> {
+        self.device_group_present
+    }
+
+    fn validate_device_group_present(
+        &self,
+        device_mask: u32,
+        mode: DeviceGroupPresentMode,
+    ) -> Result<(), FlushError> {
+        let device = &self.swapchain.device;
+
+        if !(device.api_version() >= Version::V1_1 || device.enabled_extensions().khr_device_group)
+        {
+            return Err(FlushError::DeviceGroupExtensionNotEnabled);
+        }
+
+        // VUID-VkDeviceGroupPresentInfoKHR-mode-01302
+        if !self.swapchain.device_group_present_modes.supports(mode)
+            || !device.is_valid_device_mask(device_mask)
+        {
+            return Err(FlushError::DeviceGroupPresentNotValid);
+        }
+
+        let present_mask = device
+            .group_present_capabilities()
+            .map_err(|err| match err {
+                SurfacePropertiesError::OomError(err) => FlushError::OomError(err),
+                SurfacePropertiesError::SurfaceLost => FlushError::SurfaceLost,
+                _ => FlushError::DeviceGroupPresentNotValid,
+            })?
+            .present_mask;
+
+        let valid = match mode {
+            // VUID-VkDeviceGroupPresentInfoKHR-mode-01298
+            DeviceGroupPresentMode::Local => {
+                device_mask.count_ones() == 1
+                    && present_mask[device_mask.trailing_zeros() as usize] != 0
+            }
+            // VUID-VkDeviceGroupPresentInfoKHR-mode-01299
+            DeviceGroupPresentMode::Remote => {
+                device_mask.count_ones() == 1
+                    && present_mask.iter().any(|&mask| mask & device_mask != 0)
+            }
+            // VUID-VkDeviceGroupPresentInfoKHR-mode-01300
+            DeviceGroupPresentMode::Sum => present_mask
+                .iter()
+                .any(|&mask| mask & device_mask == device_mask),
+            // VUID-VkDeviceGroupPresentInfoKHR-mode-01301
+            DeviceGroupPresentMode::LocalMultiDevice => (0..present_mask.len())
+                .filter(|&index| device_mask & (1 << index) != 0)
+                .all(|index| present_mask[index] != 0),
+        };
+
+        if !valid {
+            return Err(FlushError::DeviceGroupPresentNotValid);
+        }
+
+        Ok(())
+    }
+
+    // Submits the work of the previous future to its queue, followed by the release of the
+    // ownership of the image and the signal of `release_semaphore`, and then the acquire of the
+    // ownership on `self.queue`. Each submission is only done once.
+    unsafe fn submit_queue_transfer(
+        &self,
+        queue_transfer: &PresentQueueTransfer,
+    ) -> Result<(), FlushError> {
+        let mut state = queue_transfer.state.lock();
+        let image = &self.swapchain.images[self.image_id].image;
+        let transfer = QueueFamilyTransfer {
+            source_index: queue_transfer.source_queue.family().id(),
+            destination_index: self.queue.family().id(),
+        };
+
+        if !state.release_submitted {
+            let release = queue_transfer
+                .acquire_semaphore
+                .as_ref()
+                .map(|_| {
+                    record_ownership_transfer(
+                        queue_transfer.source_queue.family(),
+                        image.clone(),
+                        transfer,
+                        true,
+                    )
+                })
+                .transpose()
+                .map_err(FlushError::OomError)?;
+
+            let mut builder = match self.previous.build_submission()? {
+                SubmitAnyBuilder::Empty => SubmitCommandBufferBuilder::new(),
+                SubmitAnyBuilder::SemaphoresWait(sem) => sem.into(),
+                SubmitAnyBuilder::CommandBuffer(builder) => builder,
+                SubmitAnyBuilder::BindSparse(_) | SubmitAnyBuilder::QueuePresent(_) => {
+                    // Sparse bindings and presents can't be combined with a command buffer
+                    // submission. The semaphore is signaled after them instead, since they are
+                    // submitted earlier to the same queue.
+                    self.previous.flush()?;
+                    SubmitCommandBufferBuilder::new()
+                }
+            };
+
+            if let Some((command_buffer, _)) = &release {
+                builder.add_command_buffer(command_buffer);
+            }
+
+            builder.add_signal_semaphore(&queue_transfer.release_semaphore);
+            builder.submit(&queue_transfer.source_queue)?;
+            state.release_submitted = true;
+            state.command_buffers.extend(release);
+        }
+
+        if let Some(acquire_semaphore) = &queue_transfer.acquire_semaphore {
+            if !state.acquire_submitted {
+                let acquire =
+                    record_ownership_transfer(self.queue.family(), image.clone(), transfer, false)
+                        .map_err(FlushError::OomError)?;
+
+                let mut builder = SubmitCommandBufferBuilder::new();
+                builder.add_wait_semaphore(
+                    &queue_transfer.release_semaphore,
+                    PipelineStages {
+                        all_commands: true,
+                        ..PipelineStages::none()
+                    },
+                );
+                builder.add_command_buffer(&acquire.0);
+                builder.add_signal_semaphore(acquire_semaphore);
+                builder.submit(&self.queue)?;
+                state.acquire_submitted = true;
+                state.command_buffers.push(acquire);
+
+                *self.swapchain.images[self.image_id]
+                    .ownership_transfer
+                    .lock() = Some((queue_transfer.source_queue.clone(), self.queue.clone()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// The objects that synchronize a present with the previous future, when the previous future is
+// submitted to a different queue than the present.
+struct PresentQueueTransfer {
+    source_queue: Arc<Queue>,
+    // Signaled on `source_queue` once the work of the previous future has completed and the
+    // ownership of the image has been released.
+    release_semaphore: Semaphore,
+    // Signaled on the present queue once the ownership of the image has been acquired. `None` if
+    // the ownership doesn't need to be transferred.
+    acquire_semaphore: Option<Semaphore>,
+    state: Mutex<PresentQueueTransferState>,
+}
+
+#[derive(Default)]
+struct PresentQueueTransferState {
+    // Whether the work of the previous future and the release have been submitted.
+    release_submitted: bool,
+    // Whether the acquire has been submitted.
+    acquire_submitted: bool,
+    // The command buffers that have been submitted, kept alive until the present future is
+    // dropped.
+    command_buffers: SmallVec<[(UnsafeCommandBuffer, StandardCommandPoolAlloc); 2]>,
+}
+
+impl PresentQueueTransfer {
+    fn new<W>(
+        swapchain: &Swapchain<W>,
+        previous_queue: Option<Arc<Queue>>,
+        queue: &Queue,
+    ) -> Option<Result<PresentQueueTransfer, OomError>> {
+        let source_queue = previous_queue.filter(|previous_queue| **previous_queue != *queue)?;
+
+        // With concurrent sharing, the image can be used by both families without a transfer.
+        let ownership_transfer = source_queue.family().id() != queue.family().id()
+            && matches!(swapchain.image_sharing, Sharing::Exclusive);
+
+        // Semaphores from the pool have no export handle types, so only an OOM error can happen.
+        let semaphore = || {
+            Semaphore::from_pool(swapchain.device.clone()).map_err(|err| match err {
+                SemaphoreCreationError::OomError(err) => err,
+                SemaphoreCreationError::MissingExtension(_) => unreachable!(),
+            })
+        };
+
+        let semaphores = semaphore().and_then(|release_semaphore| {
+            Ok((
+                release_semaphore,
+                ownership_transfer.then(semaphore).transpose()?,
+            ))
+        });
+
+        Some(semaphores.map(
+            |(release_semaphore, acquire_semaphore)| PresentQueueTransfer {
+                source_queue,
+                release_semaphore,
+                acquire_semaphore,
+                state: Mutex::new(Default::default()),
+            },
+        ))
+    }
+}
+
+// Records a command buffer for `queue_family` that releases (if `release` is true) or acquires
+// the ownership of a swapchain image in the `PresentSrc` layout.
+unsafe fn record_ownership_transfer(
+    queue_family: QueueFamily,
+    image: Arc<UnsafeImage>,
+    transfer: QueueFamilyTransfer,
+    release: bool,
+) -> Result<(UnsafeCommandBuffer, StandardCommandPoolAlloc), OomError> {
+    let pool_builder_alloc = Device::standard_command_pool(image.device(), queue_family)
+        .allocate(CommandBufferLevel::Primary, 1)?
+        .next()
+        .expect("Requested one command buffer from the command pool, but got zero.");
+    let mut builder = UnsafeCommandBufferBuilder::new(
+        pool_builder_alloc.inner(),
+        CommandBufferBeginInfo {
+            usage: CommandBufferUsage::OneTimeSubmit,
+            ..Default::default()
+        },
+    )?;
+
+    let all_commands = PipelineStages {
+        all_commands: true,
+        ..PipelineStages::none()
+    };
+    let mut barrier = ImageMemoryBarrier {
+        old_layout: ImageLayout::PresentSrc,
+        new_layout: ImageLayout::PresentSrc,
+        queue_family_transfer: Some(transfer),
+        subresource_range: image.subresource_range(),
+        ..ImageMemoryBarrier::image(image)
+    };
+
+    // The destination scope of a release and the source scope of an acquire are ignored.
+    if release {
+        barrier.source_stages = all_commands;
+        barrier.source_access = AccessFlags {
+            memory_write: true,
+            ..AccessFlags::none()
+        };
+    } else {
+        barrier.destination_stages = all_commands;
+    }
+
+    builder.pipeline_barrier(&DependencyInfo {
+        image_memory_barriers: [barrier].into_iter().collect(),
+        ..Default::default()
+    });
+
+    Ok((builder.build()?, pool_builder_alloc.into_alloc()))
 }
 
 unsafe impl<P, W> GpuFuture for PresentFuture<P, W>
@@ -1753,9 +3406,62 @@ where
 
         let queue = self.previous.queue().map(|q| q.clone());
 
+        if let Some(present_id) = self.present_id {
+            // VUID-VkPresentInfoKHR-pNext-06235
+            if !self.swapchain.device.enabled_features().present_id {
+                return Err(FlushError::PresentIdFeatureNotEnabled);
+            }
+
+            // VUID-VkPresentIdKHR-presentIds-04999
+            if self
+                .swapchain
+                .prev_present_id
+                .fetch_max(present_id.get(), Ordering::SeqCst)
+                >= present_id.get()
+            {
+                return Err(FlushError::PresentIdLessThanOrEqual);
+            }
+        }
+
+        if let Some(present_mode) = self.present_mode {
+            // VUID-VkSwapchainPresentModeInfoEXT-pPresentModes-07761
+            if !self.swapchain.present_modes.contains(&present_mode) {
+                return Err(FlushError::PresentModeNotValid);
+            }
+        }
+
+        if let Some((device_mask, mode)) = self.device_group_present {
+            self.validate_device_group_present(device_mask, mode)?;
+        }
+
         // TODO: if the swapchain image layout is not PRESENT, should add a transition command
         // buffer
 
+        if let Some(queue_transfer) = &self.queue_transfer {
+            let queue_transfer = queue_transfer
+                .as_ref()
+                .map_err(|&err| FlushError::OomError(err))?;
+            self.submit_queue_transfer(queue_transfer)?;
+
+            let mut builder = SubmitPresentBuilder::new();
+            builder.add_wait_semaphore(
+                queue_transfer
+                    .acquire_semaphore
+                    .as_ref()
+                    .unwrap_or(&queue_transfer.release_semaphore),
+            );
+            builder.add_swapchain(
+                &self.swapchain,
+                self.image_id as u32,
+                self.present_region.as_ref(),
+                self.present_id,
+                self.present_time,
+                self.present_mode,
+                self.device_group_present,
+            );
+            return Ok(SubmitAnyBuilder::QueuePresent(builder));
+        }
+
         Ok(match self.previous.build_submission()? {
             SubmitAnyBuilder::Empty => {
                 let mut builder = SubmitPresentBuilder::new();
@@ -1763,6 +3469,10 @@ where
                     &self.swapchain,
                     self.image_id as u32,
                     self.present_region.as_ref(),
+                    self.present_id,
+                    self.present_time,
+                    self.present_mode,
+                    self.device_group_present,
                 );
                 SubmitAnyBuilder::QueuePresent(builder)
             }
@@ -1772,6 +3482,10 @@ where
                     &self.swapchain,
                     self.image_id as u32,
                     self.present_region.as_ref(),
+                    self.present_id,
+                    self.present_time,
+                    self.present_mode,
+                    self.device_group_present,
                 );
                 SubmitAnyBuilder::QueuePresent(builder)
             }
@@ -1785,6 +3499,10 @@ where
                     &self.swapchain,
                     self.image_id as u32,
                     self.present_region.as_ref(),
+                    self.present_id,
+                    self.present_time,
+                    self.present_mode,
+                    self.device_group_present,
                 );
                 SubmitAnyBuilder::QueuePresent(builder)
             }
@@ -1798,6 +3516,10 @@ where
                     &self.swapchain,
                     self.image_id as u32,
                     self.present_region.as_ref(),
+                    self.present_id,
+                    self.present_time,
+                    self.present_mode,
+                    self.device_group_present,
                 );
                 SubmitAnyBuilder::QueuePresent(builder)
             }
@@ -1859,11 +3581,6 @@ where
 
     #[inline]
     fn queue(&self) -> Option<Arc<Queue>> {
-        debug_assert!(match self.previous.queue() {
-            None => true,
-            Some(q) => q == self.queue,
-        });
-
         Some(self.queue.clone())
     }
 
@@ -1925,8 +3642,22 @@ where
             }
 
             if !*self.finished.get_mut() {
-                // Block until the queue finished.
-                self.queue().unwrap().wait().unwrap();
+                // Block until the queue finished. If the device was lost, its queues no longer
+                // execute anything, so the resources can be released without waiting.
+                match self.queue().unwrap().wait() {
+                    Ok(()) | Err(WaitIdleError::DeviceLost) => (),
+                    Err(err) => panic!("{}", err),
+                }
+
+                // If the acquire of the ownership couldn't be submitted, the present queue doesn't
+                // wait for the release, so its queue must be waited on as well.
+                if let Some(Ok(queue_transfer)) = &self.queue_transfer {
+                    match queue_transfer.source_queue.wait() {
+                        Ok(()) | Err(WaitIdleError::DeviceLost) => (),
+                        Err(err) => panic!("{}", err),
+                    }
+                }
+
                 self.previous.signal_finished();
             }
         }
@@ -1951,6 +3682,10 @@ pub unsafe fn acquire_next_image_raw<W>(
     semaphore: Option<&Semaphore>,
     fence: Option<&Fence>,
 ) -> Result<AcquiredImage, AcquireError> {
+    if swapchain.device.is_lost() {
+        return Err(AcquireError::DeviceLost);
+    }
+
     let fns = swapchain.device.fns();
 
     let timeout_ns = if let Some(timeout) = timeout {
@@ -1963,18 +3698,20 @@ pub unsafe fn acquire_next_image_raw<W>(
     };
 
     let mut out = MaybeUninit::uninit();
-    let r = check_errors((fns.khr_swapchain.acquire_next_image_khr)(
-        swapchain.device.internal_object(),
-        swapchain.handle,
-        timeout_ns,
-        semaphore
-            .map(|s| s.internal_object())
-            .unwrap_or(ash::vk::Semaphore::null()),
-        fence
-            .map(|f| f.internal_object())
-            .unwrap_or(ash::vk::Fence::null()),
-        out.as_mut_ptr(),
-    ))?;
+    let r = swapchain
+        .device
+        .check_errors((fns.khr_swapchain.acquire_next_image_khr)(
+            swapchain.device.internal_object(),
+            swapchain.handle,
+            timeout_ns,
+            semaphore
+                .map(|s| s.internal_object())
+                .unwrap_or(ash::vk::Semaphore::null()),
+            fence
+                .map(|f| f.internal_object())
+                .unwrap_or(ash::vk::Fence::null()),
+            out.as_mut_ptr(),
+        ))?;
 
     let out = out.assume_init();
     let (id, suboptimal) = match r {
@@ -1987,3 +3724,370 @@ pub unsafe fn acquire_next_image_raw<W>(
 
     Ok(AcquiredImage { id, suboptimal })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        acquire_next_image, DisplayTimingError, HdrMetadata, HdrMetadataError, PresentTime,
+        PresentWaitError, SwapchainCreateInfo, SwapchainCreationError,
+    };
+    use crate::command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage};
+    use crate::swapchain::PresentMode;
+    use crate::sync::{FlushError, GpuFuture};
+    use smallvec::smallvec;
+    use std::{num::NonZeroU64, time::Duration};
+
+    #[test]
+    fn google_display_timing_extension_not_enabled() {
+        let (_, _, swapchain, _) = headless_swapchain!([], []);
+
+        match swapchain.refresh_cycle_duration() {
+            Err(DisplayTimingError::ExtensionNotEnabled {
+                extension: "google_display_timing",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+
+        match swapchain.past_presentation_timings() {
+            Err(DisplayTimingError::ExtensionNotEnabled {
+                extension: "google_display_timing",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn present_with_time() {
+        let (_, queue, swapchain, _) = headless_swapchain!([google_display_timing], []);
+        assert!(swapchain.refresh_cycle_duration().unwrap() > Duration::ZERO);
+
+        let (index, _, acquire_future) = acquire_next_image(swapchain.clone(), None).unwrap();
+        acquire_future
+            .then_swapchain_present_with_time(
+                queue,
+                swapchain.clone(),
+                index,
+                PresentTime {
+                    present_id: 1,
+                    desired_present_time: 0,
+                },
+            )
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        // The timings are only returned once the presentation engine knows them.
+        for timing in swapchain.past_presentation_timings().unwrap() {
+            assert_eq!(timing.present_id, 1);
+        }
+    }
+
+    #[test]
+    fn present_with_time_extension_not_enabled() {
+        let (_, queue, swapchain, _) = headless_swapchain!([], []);
+
+        // Without the extension, the present time is ignored.
+        let (index, _, acquire_future) = acquire_next_image(swapchain.clone(), None).unwrap();
+        acquire_future
+            .then_swapchain_present_with_time(
+                queue,
+                swapchain,
+                index,
+                PresentTime {
+                    present_id: 1,
+                    desired_present_time: 0,
+                },
+            )
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+    }
+
+    #[test]
+    fn ext_hdr_metadata_extension_not_enabled() {
+        let (_, _, swapchain, _) = headless_swapchain!([], []);
+
+        match swapchain.set_hdr_metadata(Default::default()) {
+            Err(HdrMetadataError::ExtensionNotEnabled {
+                extension: "ext_hdr_metadata",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn set_hdr_metadata() {
+        let (_, _, swapchain, _) = headless_swapchain!([ext_hdr_metadata], []);
+
+        swapchain
+            .set_hdr_metadata(HdrMetadata {
+                max_content_light_level: 600.0,
+                max_frame_average_light_level: 400.0,
+                ..Default::default()
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn swapchain_maintenance1_feature_not_enabled() {
+        let (_, _, swapchain, _) = headless_swapchain!([], []);
+
+        match swapchain.recreate(SwapchainCreateInfo {
+            present_modes: smallvec![PresentMode::Fifo],
+            ..swapchain.create_info()
+        }) {
+            Err(SwapchainCreationError::FeatureNotEnabled {
+                feature: "swapchain_maintenance1",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+
+        match swapchain.recreate(SwapchainCreateInfo {
+            deferred_memory_allocation: true,
+            ..swapchain.create_info()
+        }) {
+            Err(SwapchainCreationError::FeatureNotEnabled {
+                feature: "swapchain_maintenance1",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn present_modes_missing_present_mode() {
+        let (_, _, swapchain, _) = headless_swapchain!(
+            instance: [khr_get_surface_capabilities2, ext_surface_maintenance1],
+            [ext_swapchain_maintenance1],
+            [swapchain_maintenance1]
+        );
+
+        match swapchain.recreate(SwapchainCreateInfo {
+            present_mode: PresentMode::Fifo,
+            present_modes: smallvec![PresentMode::Immediate],
+            ..swapchain.create_info()
+        }) {
+            Err(SwapchainCreationError::PresentModesMissingPresentMode) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn present_with_mode() {
+        let (_, queue, swapchain, _) = headless_swapchain!(
+            instance: [khr_get_surface_capabilities2, ext_surface_maintenance1],
+            [ext_swapchain_maintenance1],
+            [swapchain_maintenance1]
+        );
+
+        // The swapchain was created without a list of present modes.
+        let (index, _, acquire_future) = acquire_next_image(swapchain.clone(), None).unwrap();
+        match acquire_future
+            .then_swapchain_present_with_mode(
+                queue.clone(),
+                swapchain.clone(),
+                index,
+                PresentMode::Fifo,
+            )
+            .then_signal_fence_and_flush()
+        {
+            Err(FlushError::PresentModeNotValid) => (),
+            _ => panic!(),
+        }
+
+        let (swapchain, _) = swapchain
+            .recreate(SwapchainCreateInfo {
+                present_modes: smallvec![PresentMode::Fifo],
+                deferred_memory_allocation: true,
+                ..swapchain.create_info()
+            })
+            .unwrap();
+        assert_eq!(swapchain.present_modes(), &[PresentMode::Fifo]);
+        assert!(swapchain.deferred_memory_allocation());
+
+        let (index, _, acquire_future) = acquire_next_image(swapchain.clone(), None).unwrap();
+        let future = acquire_future.then_swapchain_present_with_mode(
+            queue,
+            swapchain,
+            index,
+            PresentMode::Fifo,
+        );
+        assert_eq!(future.present_mode(), Some(PresentMode::Fifo));
+        future
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+    }
+
+    #[test]
+    fn protected_memory_feature_not_enabled() {
+        let (_, _, swapchain, _) = headless_swapchain!([], []);
+
+        match swapchain.recreate(SwapchainCreateInfo {
+            protected: true,
+            ..swapchain.create_info()
+        }) {
+            Err(SwapchainCreationError::FeatureNotEnabled {
+                feature: "protected_memory",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn khr_surface_protected_capabilities_extension_not_enabled() {
+        let (_, _, swapchain, _) = headless_swapchain!([], [protected_memory]);
+
+        match swapchain.recreate(SwapchainCreateInfo {
+            protected: true,
+            ..swapchain.create_info()
+        }) {
+            Err(SwapchainCreationError::ExtensionNotEnabled {
+                extension: "khr_surface_protected_capabilities",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn protected() {
+        let (device, _, swapchain, _) = headless_swapchain!(
+            instance: [khr_get_surface_capabilities2, khr_surface_protected_capabilities],
+            [],
+            [protected_memory]
+        );
+        assert!(!swapchain.protected());
+
+        let supports_protected = device
+            .physical_device()
+            .surface_capabilities(swapchain.surface(), Default::default())
+            .unwrap()
+            .supports_protected;
+
+        match swapchain.recreate(SwapchainCreateInfo {
+            protected: true,
+            ..swapchain.create_info()
+        }) {
+            Ok((swapchain, _)) => {
+                assert!(supports_protected);
+                assert!(swapchain.protected());
+            }
+            Err(SwapchainCreationError::ProtectedNotSupported) => assert!(!supports_protected),
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    #[test]
+    fn present_from_another_queue() {
+        let (device, queues, swapchain, _) = headless_swapchain!(all_queues, instance: [], [], []);
+
+        // The second queue is either another queue of the same family, or a queue of another
+        // family, to which the ownership of the images is transferred.
+        let (render_queue, present_queue) = match &queues[..] {
+            [render_queue, present_queue, ..] => (render_queue.clone(), present_queue.clone()),
+            _ => return,
+        };
+        let ownership_transfer = render_queue.family() != present_queue.family();
+
+        // Each image is acquired at least twice, so the ownership is transferred back.
+        for _ in 0..swapchain.image_count() * 2 + 1 {
+            let (index, _, acquire_future) = acquire_next_image(swapchain.clone(), None).unwrap();
+            assert!(swapchain.images[index].ownership_transfer.lock().is_none());
+
+            let command_buffer = AutoCommandBufferBuilder::primary(
+                device.clone(),
+                render_queue.family(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+            acquire_future
+                .then_execute(render_queue.clone(), command_buffer)
+                .unwrap()
+                .then_swapchain_present(present_queue.clone(), swapchain.clone(), index)
+                .then_signal_fence_and_flush()
+                .unwrap()
+                .wait(None)
+                .unwrap();
+
+            assert_eq!(
+                swapchain.images[index].ownership_transfer.lock().is_some(),
+                ownership_transfer
+            );
+        }
+    }
+
+    #[test]
+    fn present_id_feature_not_enabled() {
+        let (_, queue, swapchain, _) = headless_swapchain!([], []);
+
+        let (index, _, acquire_future) = acquire_next_image(swapchain.clone(), None).unwrap();
+        match acquire_future
+            .then_swapchain_present_with_id(queue, swapchain, index, NonZeroU64::new(1).unwrap())
+            .then_signal_fence_and_flush()
+        {
+            Err(FlushError::PresentIdFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn present_wait_feature_not_enabled() {
+        let (_, _, swapchain, _) = headless_swapchain!([], []);
+
+        match swapchain.wait_for_present(NonZeroU64::new(1).unwrap(), Some(Duration::ZERO)) {
+            Err(PresentWaitError::FeatureNotEnabled {
+                feature: "present_wait",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn present_with_id_and_wait() {
+        let (_, queue, swapchain, _) = headless_swapchain!(
+            [khr_present_id, khr_present_wait],
+            [present_id, present_wait]
+        );
+        let present_id = NonZeroU64::new(1).unwrap();
+
+        let (index, _, acquire_future) = acquire_next_image(swapchain.clone(), None).unwrap();
+        let future = acquire_future.then_swapchain_present_with_id(
+            queue.clone(),
+            swapchain.clone(),
+            index,
+            present_id,
+        );
+        assert_eq!(future.present_id(), Some(present_id));
+        future
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+        swapchain
+            .wait_for_present(present_id, Some(Duration::from_secs(5)))
+            .unwrap();
+
+        // The IDs that an image is presented with must increase.
+        let (index, _, acquire_future) = acquire_next_image(swapchain.clone(), None).unwrap();
+        match acquire_future
+            .then_swapchain_present_with_id(queue, swapchain, index, present_id)
+            .then_signal_fence_and_flush()
+        {
+            Err(FlushError::PresentIdLessThanOrEqual) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -52,6 +52,8 @@ impl Event {
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         Ok(Event {
             device,
             handle,
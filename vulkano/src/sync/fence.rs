@@ -9,7 +9,10 @@
 
 use crate::{
     check_errors,
-    device::{Device, DeviceOwned},
+    device::{
+        object_tracker::{TrackedObject, TrackedObjectType},
+        Device, DeviceOwned,
+    },
     Error, OomError, Success, VulkanObject,
 };
 use smallvec::SmallVec;
@@ -43,6 +46,8 @@ pub struct Fence {
     // Indicates whether this fence was taken from the fence pool.
     // If true, will be put back into fence pool on drop.
     must_put_in_pool: bool,
+
+    tracked: TrackedObject,
 }
 
 impl Fence {
@@ -73,8 +78,11 @@ impl Fence {
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         Ok(Fence {
             handle,
+            tracked: device.track_object(TrackedObjectType::Fence),
             device,
             signaled: AtomicBool::new(signaled),
             must_put_in_pool: false,
@@ -103,6 +111,7 @@ impl Fence {
 
                 Fence {
                     handle,
+                    tracked: device.track_object(TrackedObjectType::Fence),
                     device,
                     signaled: AtomicBool::new(false),
                     must_put_in_pool: true,
@@ -121,14 +130,14 @@ impl Fence {
 
     /// Returns true if the fence is signaled.
     #[inline]
-    pub fn ready(&self) -> Result<bool, OomError> {
+    pub fn ready(&self) -> Result<bool, FenceWaitError> {
         unsafe {
             if self.signaled.load(Ordering::Relaxed) {
                 return Ok(true);
             }
 
             let fns = self.device.fns();
-            let result = check_errors((fns.v1_0.get_fence_status)(
+            let result = self.device.check_errors((fns.v1_0.get_fence_status)(
                 self.device.internal_object(),
                 self.handle,
             ))?;
@@ -164,7 +173,7 @@ impl Fence {
             };
 
             let fns = self.device.fns();
-            let r = check_errors((fns.v1_0.wait_for_fences)(
+            let r = self.device.check_errors((fns.v1_0.wait_for_fences)(
                 self.device.internal_object(),
                 1,
                 &self.handle,
@@ -227,7 +236,7 @@ impl Fence {
         let r = if let Some(device) = device {
             unsafe {
                 let fns = device.fns();
-                check_errors((fns.v1_0.wait_for_fences)(
+                device.check_errors((fns.v1_0.wait_for_fences)(
                     device.internal_object(),
                     fences.len() as u32,
                     fences.as_ptr(),
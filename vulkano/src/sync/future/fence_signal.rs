@@ -13,7 +13,7 @@ use crate::{
     command_buffer::submit::{SubmitAnyBuilder, SubmitCommandBufferBuilder},
     device::{Device, DeviceOwned, Queue},
     image::{sys::UnsafeImage, ImageLayout},
-    sync::{AccessFlags, Fence, PipelineStages},
+    sync::{AccessFlags, Fence, FenceWaitError, PipelineStages},
     DeviceSize,
 };
 use parking_lot::{Mutex, MutexGuard};
@@ -145,6 +145,34 @@ where
             _ => unreachable!(),
         }
     }
+
+    /// Returns whether the fence has been signaled by the GPU, without blocking. Performs a flush
+    /// if necessary.
+    ///
+    /// If the fence is signaled, this function also cleans any resource locked by previous
+    /// submissions, like `wait` does.
+    pub fn is_signaled(&self) -> Result<bool, FlushError> {
+        let mut state = self.state.lock();
+
+        self.flush_impl(&mut state)?;
+
+        match *state {
+            FenceSignalFutureState::Flushed(ref previous, ref fence) => {
+                if !fence.ready()? {
+                    return Ok(false);
+                }
+
+                unsafe {
+                    previous.signal_finished();
+                }
+            }
+            FenceSignalFutureState::Cleaned => return Ok(true),
+            _ => unreachable!(),
+        }
+
+        *state = FenceSignalFutureState::Cleaned;
+        Ok(true)
+    }
 }
 
 impl<F> FenceSignalFuture<F>
@@ -430,7 +458,12 @@ where
             FenceSignalFutureState::Flushed(previous, fence) => {
                 // This is a normal situation. Submitting worked.
                 // TODO: handle errors?
-                fence.wait(None).unwrap();
+                match fence.wait(None) {
+                    // If the device was lost, its queues no longer execute anything, so the
+                    // resources can be released without waiting.
+                    Ok(()) | Err(FenceWaitError::DeviceLostError) => (),
+                    Err(err) => panic!("{}", err),
+                }
                 unsafe {
                     previous.signal_finished();
                 }
@@ -11,6 +11,7 @@ pub use self::{
     fence_signal::{FenceSignalFuture, FenceSignalFutureBehavior},
     join::JoinFuture,
     now::{now, NowFuture},
+    readback::{read_back_buffer, read_back_image, ReadbackError, ReadbackFuture},
     semaphore_signal::SemaphoreSignalFuture,
 };
 use super::{AccessFlags, FenceWaitError, PipelineStages};
@@ -24,14 +25,18 @@ use crate::{
     },
     device::{DeviceOwned, Queue},
     image::{sys::UnsafeImage, ImageLayout},
-    swapchain::{self, PresentFuture, PresentRegion, Swapchain},
+    swapchain::{
+        self, DeviceGroupPresentMode, PresentFuture, PresentMode, PresentRegion, PresentTime,
+        Swapchain,
+    },
     DeviceSize, OomError,
 };
-use std::{error, fmt, ops::Range, sync::Arc};
+use std::{error, fmt, num::NonZeroU64, ops::Range, sync::Arc};
 
 mod fence_signal;
 mod join;
 mod now;
+mod readback;
 mod semaphore_signal;
 
 /// Represents an event that will happen on the GPU in the future.
@@ -278,6 +283,79 @@ pub unsafe trait GpuFuture: DeviceOwned {
         swapchain::present_incremental(swapchain, self, queue, image_index, present_region)
     }
 
+    /// Same as `then_swapchain_present`, except it allows specifying a present ID, that can be
+    /// waited for with [`Swapchain::wait_for_present`].
+    ///
+    /// > **Note**: This is just a shortcut for the `Swapchain::present_with_id()` function.
+    #[inline]
+    fn then_swapchain_present_with_id<W>(
+        self,
+        queue: Arc<Queue>,
+        swapchain: Arc<Swapchain<W>>,
+        image_index: usize,
+        present_id: NonZeroU64,
+    ) -> PresentFuture<Self, W>
+    where
+        Self: Sized,
+    {
+        swapchain::present_with_id(swapchain, self, queue, image_index, present_id)
+    }
+
+    /// Same as `then_swapchain_present`, except it allows specifying the timing of the present.
+    ///
+    /// > **Note**: This is just a shortcut for the `Swapchain::present_with_time()` function.
+    #[inline]
+    fn then_swapchain_present_with_time<W>(
+        self,
+        queue: Arc<Queue>,
+        swapchain: Arc<Swapchain<W>>,
+        image_index: usize,
+        present_time: PresentTime,
+    ) -> PresentFuture<Self, W>
+    where
+        Self: Sized,
+    {
+        swapchain::present_with_time(swapchain, self, queue, image_index, present_time)
+    }
+
+    /// Same as `then_swapchain_present`, except it allows specifying the present mode to use for
+    /// this present.
+    ///
+    /// > **Note**: This is just a shortcut for the `Swapchain::present_with_mode()` function.
+    #[inline]
+    fn then_swapchain_present_with_mode<W>(
+        self,
+        queue: Arc<Queue>,
+        swapchain: Arc<Swapchain<W>>,
+        image_index: usize,
+        present_mode: PresentMode,
+    ) -> PresentFuture<Self, W>
+    where
+        Self: Sized,
+    {
+        swapchain::present_with_mode(swapchain, self, queue, image_index, present_mode)
+    }
+
+    /// Same as `then_swapchain_present`, except it allows specifying which physical devices
+    /// present the image, for a device that was created from several physical devices.
+    ///
+    /// > **Note**: This is just a shortcut for the `Swapchain::present_with_device_mask()`
+    /// > function.
+    #[inline]
+    fn then_swapchain_present_with_device_mask<W>(
+        self,
+        queue: Arc<Queue>,
+        swapchain: Arc<Swapchain<W>>,
+        image_index: usize,
+        device_mask: u32,
+        mode: DeviceGroupPresentMode,
+    ) -> PresentFuture<Self, W>
+    where
+        Self: Sized,
+    {
+        swapchain::present_with_device_mask(swapchain, self, queue, image_index, device_mask, mode)
+    }
+
     /// Turn the current future into a `Box<dyn GpuFuture>`.
     ///
     /// This is a helper function that calls `Box::new(yourFuture) as Box<dyn GpuFuture>`.
@@ -494,6 +572,25 @@ pub enum FlushError {
 
     /// The flush operation needed to block, but the timeout has elapsed.
     Timeout,
+
+    /// A present ID was provided, but the `present_id` feature is not enabled on the device.
+    PresentIdFeatureNotEnabled,
+
+    /// A present ID was provided that is not greater than a present ID that was previously
+    /// provided for the same swapchain.
+    PresentIdLessThanOrEqual,
+
+    /// A present mode was provided that is not one of the present modes that the swapchain was
+    /// created with.
+    PresentModeNotValid,
+
+    /// A device mask was provided when presenting, but the device API version is less than 1.1
+    /// and the `khr_device_group` extension is not enabled on the device.
+    DeviceGroupExtensionNotEnabled,
+
+    /// A device mask and device group present mode were provided when presenting that are not
+    /// valid for the swapchain or the device.
+    DeviceGroupPresentNotValid,
 }
 
 impl error::Error for FlushError {
@@ -526,6 +623,25 @@ impl fmt::Display for FlushError {
                     "the flush operation needed to block, but the timeout has \
                                     elapsed"
                 }
+                FlushError::PresentIdFeatureNotEnabled => {
+                    "a present ID was provided, but the present_id feature is not enabled"
+                }
+                FlushError::PresentIdLessThanOrEqual => {
+                    "a present ID was provided that is not greater than a previous present ID \
+                    of the swapchain"
+                }
+                FlushError::PresentModeNotValid => {
+                    "a present mode was provided that is not one of the present modes that the \
+                    swapchain was created with"
+                }
+                FlushError::DeviceGroupExtensionNotEnabled => {
+                    "a device mask was provided when presenting, but the khr_device_group \
+                    extension is not enabled"
+                }
+                FlushError::DeviceGroupPresentNotValid => {
+                    "a device mask and device group present mode were provided that are not \
+                    valid for the swapchain or the device"
+                }
             }
         )
     }
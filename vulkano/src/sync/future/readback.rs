@@ -0,0 +1,376 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{AccessCheckError, FenceSignalFuture, FlushError, GpuFuture};
+use crate::{
+    buffer::{
+        sys::UnsafeBuffer, Buffer, BufferContents, BufferCreationError, BufferLocation,
+        BufferUsage, Subbuffer, TypedBufferAccess,
+    },
+    command_buffer::{
+        submit::SubmitAnyBuilder, AutoCommandBufferBuilder, BuildError, CommandBufferBeginError,
+        CommandBufferExecError, CommandBufferExecFuture, CommandBufferUsage, CopyBufferInfoTyped,
+        CopyImageToBufferInfo, PrimaryAutoCommandBuffer,
+    },
+    device::{Device, DeviceOwned, Queue},
+    image::{sys::UnsafeImage, ImageAccess, ImageLayout},
+    memory::DeviceMemoryAllocationError,
+    sync::{AccessFlags, PipelineStages},
+    DeviceSize, OomError,
+};
+use std::{error, fmt, ops::Range, sync::Arc, time::Duration};
+
+/// Copies the contents of `source` to host memory after `before`, without waiting for the copy
+/// to finish.
+///
+/// The copy is submitted to `queue` immediately. The returned future can be polled with
+/// [`try_get`](ReadbackFuture::try_get), for example once per frame, to retrieve the data
+/// without stalling the current thread.
+///
+/// # Panics
+///
+/// - Panics if `source` was not created with the `transfer_src` usage.
+/// - Panics if `source` is empty.
+pub fn read_back_buffer<S, T, F>(
+    queue: Arc<Queue>,
+    source: Arc<S>,
+    before: F,
+) -> Result<ReadbackFuture<F, T>, ReadbackError>
+where
+    S: TypedBufferAccess<Content = [T]> + 'static,
+    [T]: BufferContents,
+    T: Clone + Send + Sync + 'static,
+    F: GpuFuture + 'static,
+{
+    let device = source.device().clone();
+    let buffer = Buffer::new_slice::<T>(
+        device.clone(),
+        BufferUsage::transfer_dst(),
+        BufferLocation::Download,
+        source.len(),
+    )?;
+
+    let mut cbb = AutoCommandBufferBuilder::primary(
+        device,
+        queue.family(),
+        CommandBufferUsage::OneTimeSubmit,
+    )?;
+    cbb.copy_buffer(CopyBufferInfoTyped::buffers(source, buffer.clone()))
+        .unwrap();
+
+    ReadbackFuture::new(queue, cbb.build()?, buffer, before)
+}
+
+/// Copies the texels of the first mip level and array layer of `image` to host memory after
+/// `before`, without waiting for the copy to finish.
+///
+/// The texels are returned as bytes, tightly packed in the format of the image. The copy is
+/// submitted to `queue` immediately. The returned future can be polled with
+/// [`try_get`](ReadbackFuture::try_get), for example once per frame, to retrieve the data
+/// without stalling the current thread.
+///
+/// # Panics
+///
+/// - Panics if `image` was not created with the `transfer_src` usage.
+/// - Panics if the format of `image` is not a single-plane color format.
+pub fn read_back_image<I, F>(
+    queue: Arc<Queue>,
+    image: Arc<I>,
+    before: F,
+) -> Result<ReadbackFuture<F, u8>, ReadbackError>
+where
+    I: ImageAccess + 'static,
+    F: GpuFuture + 'static,
+{
+    let format = image.format();
+    assert!(
+        format.aspects().color && format.planes().is_empty(),
+        "the image must have a single-plane color format"
+    );
+
+    let device = image.device().clone();
+    let extent = image.dimensions().width_height_depth();
+    let block_extent = format.block_extent();
+    let num_blocks = (0..3)
+        .map(|i| ((extent[i] + block_extent[i] - 1) / block_extent[i]) as DeviceSize)
+        .product::<DeviceSize>();
+    let buffer = Buffer::new_slice::<u8>(
+        device.clone(),
+        BufferUsage::transfer_dst(),
+        BufferLocation::Download,
+        num_blocks * format.block_size().unwrap(),
+    )?;
+
+    let mut cbb = AutoCommandBufferBuilder::primary(
+        device,
+        queue.family(),
+        CommandBufferUsage::OneTimeSubmit,
+    )?;
+    let mut copy_info = CopyImageToBufferInfo::image_buffer(image, buffer.clone());
+    copy_info.regions[0].image_subresource.array_layers = 0..1;
+    cbb.copy_image_to_buffer(copy_info).unwrap();
+
+    ReadbackFuture::new(queue, cbb.build()?, buffer, before)
+}
+
+/// A future that represents a copy of GPU data to host memory, created with
+/// [`read_back_buffer`] or [`read_back_image`].
+///
+/// The data can be retrieved without blocking with [`try_get`](ReadbackFuture::try_get), or by
+/// blocking with [`get`](ReadbackFuture::get). The future can also be chained with other futures,
+/// like any other [`GpuFuture`].
+#[must_use = "Dropping this object will immediately block the thread until the GPU has finished \
+              processing the submission"]
+pub struct ReadbackFuture<F, T>
+where
+    F: GpuFuture,
+    [T]: BufferContents,
+{
+    future: FenceSignalFuture<CommandBufferExecFuture<F, PrimaryAutoCommandBuffer>>,
+    buffer: Arc<Subbuffer<[T]>>,
+}
+
+impl<F, T> ReadbackFuture<F, T>
+where
+    F: GpuFuture + 'static,
+    [T]: BufferContents,
+    T: Clone,
+{
+    fn new(
+        queue: Arc<Queue>,
+        command_buffer: PrimaryAutoCommandBuffer,
+        buffer: Arc<Subbuffer<[T]>>,
+        before: F,
+    ) -> Result<Self, ReadbackError> {
+        let future = before
+            .then_execute(queue, command_buffer)?
+            .then_signal_fence_and_flush()?;
+
+        Ok(ReadbackFuture { future, buffer })
+    }
+
+    /// Returns whether the copy has finished, without blocking.
+    #[inline]
+    pub fn is_ready(&self) -> Result<bool, FlushError> {
+        self.future.is_signaled()
+    }
+
+    /// Returns the data if the copy has finished, or `None` if it is still in progress. This
+    /// function never blocks.
+    #[inline]
+    pub fn try_get(&self) -> Result<Option<Vec<T>>, FlushError> {
+        if self.future.is_signaled()? {
+            Ok(Some(self.read()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Blocks the current thread until the copy has finished, and returns the data.
+    ///
+    /// If `timeout` is `None`, then the wait is infinite. Otherwise the thread will unblock after
+    /// the specified timeout has elapsed and an error will be returned.
+    #[inline]
+    pub fn get(&self, timeout: Option<Duration>) -> Result<Vec<T>, FlushError> {
+        self.future.wait(timeout)?;
+        Ok(self.read())
+    }
+
+    fn read(&self) -> Vec<T> {
+        // The GPU no longer holds a lock on the buffer once the fence is signaled.
+        self.buffer.read().unwrap().to_vec()
+    }
+}
+
+unsafe impl<F, T> GpuFuture for ReadbackFuture<F, T>
+where
+    F: GpuFuture,
+    [T]: BufferContents,
+{
+    #[inline]
+    fn cleanup_finished(&mut self) {
+        self.future.cleanup_finished()
+    }
+
+    #[inline]
+    unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, FlushError> {
+        self.future.build_submission()
+    }
+
+    #[inline]
+    fn flush(&self) -> Result<(), FlushError> {
+        self.future.flush()
+    }
+
+    #[inline]
+    unsafe fn signal_finished(&self) {
+        self.future.signal_finished()
+    }
+
+    #[inline]
+    fn queue_change_allowed(&self) -> bool {
+        self.future.queue_change_allowed()
+    }
+
+    #[inline]
+    fn queue(&self) -> Option<Arc<Queue>> {
+        self.future.queue()
+    }
+
+    #[inline]
+    fn check_buffer_access(
+        &self,
+        buffer: &UnsafeBuffer,
+        range: Range<DeviceSize>,
+        exclusive: bool,
+        queue: &Queue,
+    ) -> Result<Option<(PipelineStages, AccessFlags)>, AccessCheckError> {
+        self.future
+            .check_buffer_access(buffer, range, exclusive, queue)
+    }
+
+    #[inline]
+    fn check_image_access(
+        &self,
+        image: &UnsafeImage,
+        range: Range<DeviceSize>,
+        exclusive: bool,
+        expected_layout: ImageLayout,
+        queue: &Queue,
+    ) -> Result<Option<(PipelineStages, AccessFlags)>, AccessCheckError> {
+        self.future
+            .check_image_access(image, range, exclusive, expected_layout, queue)
+    }
+}
+
+unsafe impl<F, T> DeviceOwned for ReadbackFuture<F, T>
+where
+    F: GpuFuture,
+    [T]: BufferContents,
+{
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.future.device()
+    }
+}
+
+/// Error that can happen when starting a readback.
+#[derive(Clone, Debug)]
+pub enum ReadbackError {
+    BufferCreationError(BufferCreationError),
+    DeviceMemoryAllocationError(DeviceMemoryAllocationError),
+    CommandBufferBeginError(CommandBufferBeginError),
+    BuildError(BuildError),
+    CommandBufferExecError(CommandBufferExecError),
+    FlushError(FlushError),
+}
+
+impl error::Error for ReadbackError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::BufferCreationError(err) => Some(err),
+            Self::DeviceMemoryAllocationError(err) => Some(err),
+            Self::CommandBufferBeginError(err) => Some(err),
+            Self::BuildError(err) => Some(err),
+            Self::CommandBufferExecError(err) => Some(err),
+            Self::FlushError(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for ReadbackError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferCreationError(err) => err.fmt(f),
+            Self::DeviceMemoryAllocationError(err) => err.fmt(f),
+            Self::CommandBufferBeginError(err) => err.fmt(f),
+            Self::BuildError(err) => err.fmt(f),
+            Self::CommandBufferExecError(err) => err.fmt(f),
+            Self::FlushError(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<BufferCreationError> for ReadbackError {
+    #[inline]
+    fn from(err: BufferCreationError) -> Self {
+        Self::BufferCreationError(err)
+    }
+}
+
+impl From<DeviceMemoryAllocationError> for ReadbackError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocationError) -> Self {
+        Self::DeviceMemoryAllocationError(err)
+    }
+}
+
+impl From<OomError> for ReadbackError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::DeviceMemoryAllocationError(err.into())
+    }
+}
+
+impl From<CommandBufferBeginError> for ReadbackError {
+    #[inline]
+    fn from(err: CommandBufferBeginError) -> Self {
+        Self::CommandBufferBeginError(err)
+    }
+}
+
+impl From<BuildError> for ReadbackError {
+    #[inline]
+    fn from(err: BuildError) -> Self {
+        Self::BuildError(err)
+    }
+}
+
+impl From<CommandBufferExecError> for ReadbackError {
+    #[inline]
+    fn from(err: CommandBufferExecError) -> Self {
+        Self::CommandBufferExecError(err)
+    }
+}
+
+impl From<FlushError> for ReadbackError {
+    #[inline]
+    fn from(err: FlushError) -> Self {
+        Self::FlushError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_back_buffer;
+    use crate::{
+        buffer::{Buffer, BufferLocation, BufferUsage},
+        sync,
+    };
+
+    #[test]
+    fn buffer_round_trip() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let source = Buffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_src(),
+            BufferLocation::Upload,
+            0..64u32,
+        )
+        .unwrap();
+
+        let future = read_back_buffer(queue, source, sync::now(device)).unwrap();
+        let data = future.get(None).unwrap();
+        assert_eq!(data, (0..64u32).collect::<Vec<_>>());
+        assert_eq!(future.try_get().unwrap(), Some(data));
+    }
+}
@@ -13,7 +13,7 @@ use crate::{
     command_buffer::submit::{
         SubmitAnyBuilder, SubmitCommandBufferBuilder, SubmitSemaphoresWaitBuilder,
     },
-    device::{Device, DeviceOwned, Queue},
+    device::{Device, DeviceOwned, Queue, WaitIdleError},
     image::{sys::UnsafeImage, ImageLayout},
     sync::{AccessFlags, PipelineStages, Semaphore},
     DeviceSize,
@@ -189,9 +189,19 @@ where
         unsafe {
             if !*self.finished.get_mut() {
                 // TODO: handle errors?
-                self.flush().unwrap();
-                // Block until the queue finished.
-                self.queue().unwrap().wait().unwrap();
+                // If the device was lost, its queues no longer execute anything, so the resources
+                // can be released without waiting.
+                match self.flush() {
+                    Ok(()) => {
+                        // Block until the queue finished.
+                        match self.queue().unwrap().wait() {
+                            Ok(()) | Err(WaitIdleError::DeviceLost) => (),
+                            Err(err) => panic!("{}", err),
+                        }
+                    }
+                    Err(FlushError::DeviceLost) => (),
+                    Err(err) => panic!("{}", err),
+                }
                 self.previous.signal_finished();
             }
         }
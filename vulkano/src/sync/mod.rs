@@ -110,8 +110,9 @@ pub use self::{
     event::{Event, EventCreateInfo},
     fence::{Fence, FenceCreateInfo, FenceWaitError},
     future::{
-        now, AccessCheckError, AccessError, FenceSignalFuture, FlushError, GpuFuture, JoinFuture,
-        NowFuture, SemaphoreSignalFuture,
+        now, read_back_buffer, read_back_image, AccessCheckError, AccessError, FenceSignalFuture,
+        FlushError, GpuFuture, JoinFuture, NowFuture, ReadbackError, ReadbackFuture,
+        SemaphoreSignalFuture,
     },
     pipeline::{
         AccessFlags, BufferMemoryBarrier, DependencyInfo, ImageMemoryBarrier, MemoryBarrier,
@@ -120,6 +121,7 @@ pub use self::{
     semaphore::{
         ExternalSemaphoreHandleType, ExternalSemaphoreHandleTypes, ExternalSemaphoreInfo,
         ExternalSemaphoreProperties, Semaphore, SemaphoreCreateInfo, SemaphoreCreationError,
+        SemaphoreExportError, SemaphoreImportError, SemaphoreImportInfo,
     },
 };
 
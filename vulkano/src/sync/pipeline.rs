@@ -112,7 +112,12 @@ impl PipelineStages {
     #[inline]
     pub fn supported_access(&self) -> AccessFlags {
         if self.all_commands {
-            return AccessFlags::all();
+            // Access types that need an extension are only included if their stage is given
+            // explicitly, so that the stages of commands can be combined without enabling it.
+            return AccessFlags {
+                conditional_rendering_read: self.conditional_rendering,
+                ..AccessFlags::all()
+            };
         }
 
         let PipelineStages {
@@ -134,6 +139,7 @@ impl PipelineStages {
             all_graphics,
             all_commands,
             ray_tracing_shader,
+            mut conditional_rendering,
         } = *self;
 
         if all_graphics {
@@ -205,12 +211,12 @@ impl PipelineStages {
             host_write: host,
             memory_read: true,
             memory_write: true,
+            conditional_rendering_read: conditional_rendering,
 
             /*
             color_attachment_read_noncoherent: color_attachment_output,
             preprocess_read: command_preprocess,
             preprocess_write: command_preprocess,
-            conditional_rendering_read: conditional_rendering,
             fragment_shading_rate_attachment_read: fragment_shading_rate_attachment,
             invocation_mask_read: invocation_mask,
             transform_feedback_write: transform_feedback,
@@ -257,6 +263,7 @@ pipeline_stages! {
     all_graphics, AllGraphics => ALL_GRAPHICS, ash::vk::QueueFlags::GRAPHICS;
     all_commands, AllCommands => ALL_COMMANDS, ash::vk::QueueFlags::empty();
     ray_tracing_shader, RayTracingShader => RAY_TRACING_SHADER_KHR, ash::vk::QueueFlags::GRAPHICS | ash::vk::QueueFlags::COMPUTE | ash::vk::QueueFlags::TRANSFER;
+    conditional_rendering, ConditionalRendering => CONDITIONAL_RENDERING_EXT, ash::vk::QueueFlags::GRAPHICS | ash::vk::QueueFlags::COMPUTE;
 }
 
 macro_rules! access_flags {
@@ -360,6 +367,7 @@ access_flags! {
     host_write => HOST_WRITE,
     memory_read => MEMORY_READ,
     memory_write => MEMORY_WRITE,
+    conditional_rendering_read => CONDITIONAL_RENDERING_READ_EXT,
 }
 
 /// The full specification of memory access by the pipeline for a particular resource.
@@ -9,7 +9,10 @@
 
 use crate::{
     check_errors,
-    device::{Device, DeviceOwned},
+    device::{
+        object_tracker::{TrackedObject, TrackedObjectType},
+        Device, DeviceOwned,
+    },
     Error, OomError, Version, VulkanObject,
 };
 use std::{
@@ -33,6 +36,7 @@ pub struct Semaphore {
     must_put_in_pool: bool,
 
     export_handle_types: ExternalSemaphoreHandleTypes,
+    tracked: TrackedObject,
 }
 
 impl Semaphore {
@@ -104,7 +108,10 @@ impl Semaphore {
             output.assume_init()
         };
 
+        device.set_automatic_debug_name(handle);
+
         Ok(Semaphore {
+            tracked: device.track_object(TrackedObjectType::Semaphore),
             device,
             handle,
             must_put_in_pool: false,
@@ -123,6 +130,7 @@ impl Semaphore {
         let handle = device.semaphore_pool().lock().unwrap().pop();
         let semaphore = match handle {
             Some(handle) => Semaphore {
+                tracked: device.track_object(TrackedObjectType::Semaphore),
                 device,
                 handle,
                 must_put_in_pool: true,
@@ -140,25 +148,71 @@ impl Semaphore {
         Ok(semaphore)
     }
 
+    // Destroys the semaphore when it is dropped, instead of putting it back into the pool. This
+    // must be called if the semaphore is signaled and will not be waited on, because a semaphore
+    // from the pool is expected to be unsignaled.
+    #[inline]
+    pub(crate) fn keep_out_of_pool(&mut self) {
+        self.must_put_in_pool = false;
+    }
+
+    /// Exports the semaphore into a Unix file descriptor of the
+    /// [`OpaqueFd`](ExternalSemaphoreHandleType::OpaqueFd) handle type. The caller owns the
+    /// returned `File`.
+    ///
     /// # Safety
     ///
     /// - The semaphore must not be used, or have been used, to acquire a swapchain image.
+    #[inline]
     pub unsafe fn export_opaque_fd(&self) -> Result<File, SemaphoreExportError> {
+        self.export_fd(ExternalSemaphoreHandleType::OpaqueFd)
+    }
+
+    /// Exports the semaphore into a Unix file descriptor. The caller owns the returned `File`.
+    ///
+    /// `handle_type` must be [`ExternalSemaphoreHandleType::OpaqueFd`] or
+    /// [`ExternalSemaphoreHandleType::SyncFd`], and must have been provided in
+    /// [`SemaphoreCreateInfo::export_handle_types`]. The file can then be imported into a
+    /// semaphore of another device with [`import_fd`](Self::import_fd).
+    ///
+    /// # Safety
+    ///
+    /// - The semaphore must not be used, or have been used, to acquire a swapchain image.
+    /// - If `handle_type` is `SyncFd`, the semaphore must be signaled, or have a pending signal
+    ///   operation. Exporting it has the same effect on the semaphore as a wait operation.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the `khr_external_semaphore_fd` extension is not enabled on the device.
+    pub unsafe fn export_fd(
+        &self,
+        handle_type: ExternalSemaphoreHandleType,
+    ) -> Result<File, SemaphoreExportError> {
         let fns = self.device.fns();
 
+        // VUID-VkSemaphoreGetFdInfoKHR-handleType-01136
+        if !matches!(
+            handle_type,
+            ExternalSemaphoreHandleType::OpaqueFd | ExternalSemaphoreHandleType::SyncFd
+        ) {
+            return Err(SemaphoreExportError::HandleTypeNotSupported { handle_type });
+        }
+
         // VUID-VkSemaphoreGetFdInfoKHR-handleType-01132
-        if !self.export_handle_types.opaque_fd {
-            return Err(SemaphoreExportError::HandleTypeNotSupported {
-                handle_type: ExternalSemaphoreHandleType::OpaqueFd,
-            });
+        if !ash::vk::ExternalSemaphoreHandleTypeFlags::from(self.export_handle_types)
+            .intersects(handle_type.into())
+        {
+            return Err(SemaphoreExportError::HandleTypeNotSupported { handle_type });
         }
 
-        assert!(self.device.enabled_extensions().khr_external_semaphore);
         assert!(self.device.enabled_extensions().khr_external_semaphore_fd);
 
         // VUID-VkSemaphoreGetFdInfoKHR-semaphore-01133
         // Can't validate for swapchain.
 
+        // VUID-VkSemaphoreGetFdInfoKHR-handleType-03254
+        // Can't validate the pending signal operation, must be ensured by user.
+
         #[cfg(not(unix))]
         unreachable!("`khr_external_semaphore_fd` was somehow enabled on a non-Unix system");
 
@@ -169,7 +223,7 @@ impl Semaphore {
             let fd = {
                 let info = ash::vk::SemaphoreGetFdInfoKHR {
                     semaphore: self.handle,
-                    handle_type: ash::vk::ExternalSemaphoreHandleTypeFlagsKHR::OPAQUE_FD,
+                    handle_type: handle_type.into(),
                     ..Default::default()
                 };
 
@@ -185,6 +239,132 @@ impl Semaphore {
             Ok(file)
         }
     }
+
+    /// Imports a payload into the semaphore from a Unix file descriptor, for example one that was
+    /// exported from a semaphore of another device with [`export_fd`](Self::export_fd).
+    ///
+    /// An [`OpaqueFd`](ExternalSemaphoreHandleType::OpaqueFd) handle can only be imported if it
+    /// was exported from a device whose physical device is compatible with the physical device of
+    /// this semaphore, see
+    /// [`PhysicalDevice::is_opaque_handle_compatible`](crate::device::physical::PhysicalDevice::is_opaque_handle_compatible).
+    /// A [`SyncFd`](ExternalSemaphoreHandleType::SyncFd) handle can be imported from any device.
+    ///
+    /// # Safety
+    ///
+    /// - The semaphore must not be in use by the device, and no other thread must access it while
+    ///   the payload is being imported.
+    /// - If `import_info.file` is `Some`, it must be a valid file descriptor of
+    ///   `import_info.handle_type`, that was exported from a Vulkan semaphore or created in a way
+    ///   that is compatible with one. Vulkan takes ownership of the file descriptor once it is
+    ///   imported, so you must not perform any operations on it nor on any of its duplicates.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the semaphore was taken from the semaphore pool.
+    /// - Panics if `import_info.file` is `None` and `import_info.handle_type` is not `SyncFd`.
+    pub unsafe fn import_fd(
+        &self,
+        import_info: SemaphoreImportInfo,
+    ) -> Result<(), SemaphoreImportError> {
+        // A pooled semaphore is reused for other operations, so it must keep its own payload.
+        assert!(!self.must_put_in_pool);
+
+        self.validate_import_fd(&import_info)?;
+
+        let SemaphoreImportInfo {
+            handle_type,
+            file,
+            temporary,
+            _ne: _,
+        } = import_info;
+
+        #[cfg(not(unix))]
+        unreachable!("`khr_external_semaphore_fd` was somehow enabled on a non-Unix system");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::io::{FromRawFd, IntoRawFd};
+
+            // A file descriptor of -1 refers to a payload that is already signaled.
+            let fd = file.map_or(-1, IntoRawFd::into_raw_fd);
+
+            let info = ash::vk::ImportSemaphoreFdInfoKHR {
+                semaphore: self.handle,
+                flags: if temporary {
+                    ash::vk::SemaphoreImportFlags::TEMPORARY
+                } else {
+                    ash::vk::SemaphoreImportFlags::empty()
+                },
+                handle_type: handle_type.into(),
+                fd,
+                ..Default::default()
+            };
+
+            let fns = self.device.fns();
+            let result = check_errors((fns.khr_external_semaphore_fd.import_semaphore_fd_khr)(
+                self.device.internal_object(),
+                &info,
+            ));
+
+            // Vulkan only takes ownership of the file descriptor if the import succeeds.
+            if result.is_err() && fd != -1 {
+                drop(File::from_raw_fd(fd));
+            }
+
+            result?;
+            Ok(())
+        }
+    }
+
+    fn validate_import_fd(
+        &self,
+        import_info: &SemaphoreImportInfo,
+    ) -> Result<(), SemaphoreImportError> {
+        let &SemaphoreImportInfo {
+            handle_type,
+            ref file,
+            temporary,
+            _ne: _,
+        } = import_info;
+
+        if !self.device.enabled_extensions().khr_external_semaphore_fd {
+            return Err(SemaphoreImportError::ExtensionNotEnabled {
+                extension: "khr_external_semaphore_fd",
+                reason: "tried to import a semaphore payload from a file descriptor",
+            });
+        }
+
+        // VUID-VkImportSemaphoreFdInfoKHR-handleType-01143
+        if !matches!(
+            handle_type,
+            ExternalSemaphoreHandleType::OpaqueFd | ExternalSemaphoreHandleType::SyncFd
+        ) {
+            return Err(SemaphoreImportError::HandleTypeNotSupported { handle_type });
+        }
+
+        if let Some(properties) = self
+            .device
+            .physical_device()
+            .external_semaphore_properties(ExternalSemaphoreInfo::handle_type(handle_type))
+        {
+            if !properties.importable {
+                return Err(SemaphoreImportError::HandleTypeNotSupported { handle_type });
+            }
+        }
+
+        // Only a sync file descriptor has a special value for an already signaled payload.
+        assert!(file.is_some() || handle_type == ExternalSemaphoreHandleType::SyncFd);
+
+        // VUID-VkImportSemaphoreFdInfoKHR-handleType-07307
+        if handle_type == ExternalSemaphoreHandleType::SyncFd && !temporary {
+            return Err(SemaphoreImportError::HandleTypeRequiresTemporary { handle_type });
+        }
+
+        // VUID-VkImportSemaphoreFdInfoKHR-handleType-01543
+        // Can't validate, must be ensured by user.
+
+        Ok(())
+    }
 }
 
 impl Drop for Semaphore {
@@ -307,6 +487,48 @@ impl Default for SemaphoreCreateInfo {
     }
 }
 
+/// Parameters to import a payload into a semaphore from a Unix file descriptor.
+#[derive(Debug)]
+pub struct SemaphoreImportInfo {
+    /// The handle type of `file`. Must be [`ExternalSemaphoreHandleType::OpaqueFd`] or
+    /// [`ExternalSemaphoreHandleType::SyncFd`].
+    ///
+    /// There is no default value.
+    pub handle_type: ExternalSemaphoreHandleType,
+
+    /// The file descriptor to import.
+    ///
+    /// If `handle_type` is `SyncFd`, this can be `None` to import a payload that is already
+    /// signaled. Otherwise, it must be `Some`.
+    ///
+    /// There is no default value.
+    pub file: Option<File>,
+
+    /// Whether the payload is only imported temporarily. A temporary payload is removed by the
+    /// next wait operation on the semaphore, after which the semaphore has its previous payload
+    /// again.
+    ///
+    /// Must be `true` if `handle_type` is `SyncFd`.
+    ///
+    /// The default value is `true` if `handle_type` is `SyncFd`, and `false` otherwise.
+    pub temporary: bool,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl SemaphoreImportInfo {
+    /// Returns a `SemaphoreImportInfo` with the specified `handle_type` and `file`.
+    #[inline]
+    pub fn new(handle_type: ExternalSemaphoreHandleType, file: Option<File>) -> Self {
+        Self {
+            handle_type,
+            file,
+            temporary: handle_type == ExternalSemaphoreHandleType::SyncFd,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
 /// Describes the handle type used for Vulkan external semaphore APIs.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(u32)]
@@ -522,12 +744,96 @@ impl From<OomError> for SemaphoreExportError {
     }
 }
 
+/// Error that can happen when importing a payload into a semaphore.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SemaphoreImportError {
+    /// Not enough memory available.
+    OomError(OomError),
+
+    /// The file descriptor was not valid for the handle type.
+    InvalidExternalHandle,
+
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+
+    /// The handle type can't be imported into a semaphore of this device from a file
+    /// descriptor.
+    HandleTypeNotSupported {
+        handle_type: ExternalSemaphoreHandleType,
+    },
+
+    /// The handle type can only be imported temporarily, but `temporary` was `false`.
+    HandleTypeRequiresTemporary {
+        handle_type: ExternalSemaphoreHandleType,
+    },
+}
+
+impl fmt::Display for SemaphoreImportError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::OomError(_) => write!(fmt, "not enough memory available"),
+            Self::InvalidExternalHandle => write!(
+                fmt,
+                "the file descriptor was not valid for the handle type",
+            ),
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+            Self::HandleTypeNotSupported { handle_type } => write!(
+                fmt,
+                "the handle type ({:?}) can't be imported into a semaphore of this device from a file descriptor",
+                handle_type,
+            ),
+            Self::HandleTypeRequiresTemporary { handle_type } => write!(
+                fmt,
+                "the handle type ({:?}) can only be imported temporarily, but `temporary` was `false`",
+                handle_type,
+            ),
+        }
+    }
+}
+
+impl From<Error> for SemaphoreImportError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            e @ Error::OutOfHostMemory | e @ Error::OutOfDeviceMemory => Self::OomError(e.into()),
+            Error::InvalidExternalHandle => Self::InvalidExternalHandle,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl std::error::Error for SemaphoreImportError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            Self::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<OomError> for SemaphoreImportError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::device::physical::PhysicalDevice;
     use crate::device::{Device, DeviceCreateInfo, DeviceExtensions, QueueCreateInfo};
     use crate::instance::{Instance, InstanceCreateInfo, InstanceExtensions};
-    use crate::sync::{ExternalSemaphoreHandleTypes, Semaphore, SemaphoreCreateInfo};
+    use crate::sync::{
+        ExternalSemaphoreHandleType, ExternalSemaphoreHandleTypes, Semaphore, SemaphoreCreateInfo,
+        SemaphoreImportError, SemaphoreImportInfo,
+    };
     use crate::VulkanObject;
 
     #[test]
@@ -596,4 +902,25 @@ mod tests {
         .unwrap();
         let fd = unsafe { sem.export_opaque_fd().unwrap() };
     }
+
+    #[test]
+    fn semaphore_import_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device.enabled_extensions().khr_external_semaphore_fd {
+            return;
+        }
+
+        let sem = Semaphore::new(device, Default::default()).unwrap();
+
+        match unsafe {
+            sem.import_fd(SemaphoreImportInfo::new(
+                ExternalSemaphoreHandleType::SyncFd,
+                None,
+            ))
+        } {
+            Err(SemaphoreImportError::ExtensionNotEnabled { .. }) => (),
+            _ => panic!(),
+        }
+    }
 }
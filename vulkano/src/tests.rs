@@ -106,3 +106,146 @@ macro_rules! assert_should_panic {
         }
     }};
 }
+
+/// Creates a headless surface, a device and a queue that can present to it, and a swapchain for
+/// the surface, or returns if initialization fails.
+///
+/// The instance enables the `khr_surface` and `ext_headless_surface` extensions, and the device
+/// enables the `khr_swapchain` extension, in addition to the extensions and features that are
+/// listed.
+///
+/// With `all_queues`, a `Vec` of queues is returned instead of a single queue. It contains up to
+/// two queues of the family that is used to create the swapchain, followed by a queue of each
+/// other family that can present to the surface.
+macro_rules! headless_swapchain {
+    ([$($extension:ident),*], [$($feature:ident),*]) => (
+        headless_swapchain!(instance: [], [$($extension),*], [$($feature),*])
+    );
+    (
+        instance: [$($instance_extension:ident),*],
+        [$($extension:ident),*],
+        [$($feature:ident),*]
+    ) => ({
+        let (device, mut queues, swapchain, images) = headless_swapchain!(
+            all_queues,
+            instance: [$($instance_extension),*],
+            [$($extension),*],
+            [$($feature),*]
+        );
+
+        (device, queues.swap_remove(0), swapchain, images)
+    });
+    (
+        all_queues,
+        instance: [$($instance_extension:ident),*],
+        [$($extension:ident),*],
+        [$($feature:ident),*]
+    ) => ({
+        use crate::device::physical::PhysicalDevice;
+        use crate::device::{Device, DeviceCreateInfo, DeviceExtensions, Features, QueueCreateInfo};
+        use crate::image::ImageUsage;
+        use crate::instance::{Instance, InstanceCreateInfo, InstanceExtensions};
+        use crate::swapchain::{Surface, Swapchain, SwapchainCreateInfo};
+
+        let instance = match Instance::new(InstanceCreateInfo {
+            enabled_extensions: InstanceExtensions {
+                khr_surface: true,
+                ext_headless_surface: true,
+                $(
+                    $instance_extension: true,
+                )*
+                ..InstanceExtensions::none()
+            },
+            ..Default::default()
+        }) {
+            Ok(i) => i,
+            Err(_) => return,
+        };
+        let surface = match Surface::headless(instance.clone(), ()) {
+            Ok(s) => s,
+            Err(_) => return,
+        };
+
+        let enabled_extensions = DeviceExtensions {
+            khr_swapchain: true,
+            $(
+                $extension: true,
+            )*
+            .. DeviceExtensions::none()
+        };
+        let enabled_features = Features {
+            $(
+                $feature: true,
+            )*
+            .. Features::none()
+        };
+
+        let select = PhysicalDevice::enumerate(&instance)
+            .filter(|&p| {
+                p.supported_extensions().is_superset_of(&enabled_extensions) &&
+                p.supported_features().is_superset_of(&enabled_features)
+            })
+            .find_map(|p| {
+                p.queue_families()
+                    .find(|&q| q.supports_graphics() && q.supports_surface(&surface).unwrap_or(false))
+                    .map(|q| (p, q))
+            });
+
+        let (physical_device, queue_family) = match select {
+            Some(x) => x,
+            None => return,
+        };
+
+        let (device, queues) = match Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                queue_create_infos: physical_device
+                    .queue_families()
+                    .filter(|&q| q.supports_surface(&surface).unwrap_or(false))
+                    .map(|q| QueueCreateInfo {
+                        queues: if q == queue_family {
+                            vec![0.5; q.queues_count().min(2)]
+                        } else {
+                            vec![0.5]
+                        },
+                        ..QueueCreateInfo::family(q)
+                    })
+                    .collect(),
+                enabled_extensions,
+                enabled_features,
+                ..Default::default()
+            }
+        ) {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+
+        // A headless surface has no current extent, so any extent within the limits can be used.
+        let capabilities = physical_device
+            .surface_capabilities(&surface, Default::default())
+            .unwrap();
+        let image_format = physical_device
+            .surface_formats(&surface, Default::default())
+            .unwrap()[0]
+            .0;
+        let (swapchain, images) = Swapchain::new(
+            device.clone(),
+            surface,
+            SwapchainCreateInfo {
+                min_image_count: capabilities.min_image_count,
+                image_format: Some(image_format),
+                image_extent: [64, 64],
+                image_usage: ImageUsage::color_attachment(),
+                composite_alpha: capabilities.supported_composite_alpha.iter().next().unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // The queues of the family of the swapchain come first.
+        let mut queues: Vec<_> = queues.collect();
+        queues.sort_by_key(|queue| queue.family() != queue_family);
+
+        (device, queues, swapchain, images)
+    });
+}
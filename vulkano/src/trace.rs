@@ -0,0 +1,130 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+// Instrumentation of the operations of vulkano that can take a significant amount of time.
+//
+// When the `tracing` feature is enabled, `trace_span!` enters a span of the
+// [`tracing`](https://docs.rs/tracing) crate, with the `vulkano` target and the trace level, until
+// the end of the enclosing scope. The fields are given in the syntax of `tracing::trace_span!`.
+// When the feature is disabled, it expands to nothing and its arguments are not evaluated.
+//
+// Subscribers such as `tracing-tracy` can forward these spans to other profilers.
+macro_rules! trace_span {
+    ($name:expr $(, $($fields:tt)*)?) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!(target: "vulkano", $name $(, $($fields)*)?).entered();
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "tracing"))]
+    #[test]
+    fn disabled() {
+        // The fields are not evaluated.
+        trace_span!("disabled", value = unreachable!());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn spans() {
+        use crate::{
+            command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
+            pipeline::ComputePipeline,
+            shader::ShaderModule,
+            sync::{now, GpuFuture},
+        };
+        use std::sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        };
+        use tracing::{span, Event, Metadata, Subscriber};
+
+        // Records the names of the spans of vulkano.
+        #[derive(Default)]
+        struct Recorder {
+            next_id: AtomicU64,
+            names: Arc<Mutex<Vec<&'static str>>>,
+        }
+
+        impl Subscriber for Recorder {
+            fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+                metadata.target() == "vulkano"
+            }
+
+            fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+                self.names.lock().unwrap().push(span.metadata().name());
+                span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+            }
+
+            fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+            fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+            fn event(&self, _event: &Event<'_>) {}
+
+            fn enter(&self, _span: &span::Id) {}
+
+            fn exit(&self, _span: &span::Id) {}
+        }
+
+        let recorder = Recorder::default();
+        let names = recorder.names.clone();
+
+        tracing::subscriber::with_default(recorder, || {
+            let (device, queue) = gfx_dev_and_queue!();
+
+            // Empty compute shader.
+            const MODULE: [u32; 35] = [
+                0x07230203, 0x00010000, 0x00000000, 0x00000005, 0x00000000, 0x00020011, 0x00000001,
+                0x0003000e, 0x00000000, 0x00000001, 0x0005000f, 0x00000005, 0x00000001, 0x6e69616d,
+                0x00000000, 0x00060010, 0x00000001, 0x00000011, 0x00000001, 0x00000001, 0x00000001,
+                0x00020013, 0x00000002, 0x00030021, 0x00000003, 0x00000002, 0x00050036, 0x00000002,
+                0x00000001, 0x00000000, 0x00000003, 0x000200f8, 0x00000004, 0x000100fd, 0x00010038,
+            ];
+            let module = unsafe { ShaderModule::from_words(device.clone(), &MODULE).unwrap() };
+            let _pipeline = ComputePipeline::new(
+                device.clone(),
+                module.entry_point("main").unwrap(),
+                &(),
+                None,
+                |_| {},
+            )
+            .unwrap();
+
+            let command_buffer = AutoCommandBufferBuilder::primary(
+                device.clone(),
+                queue.family(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+            now(device.clone())
+                .then_execute(queue.clone(), command_buffer)
+                .unwrap()
+                .then_signal_fence_and_flush()
+                .unwrap()
+                .wait(None)
+                .unwrap();
+
+            let names = names.lock().unwrap();
+
+            for name in [
+                "create_compute_pipeline",
+                "begin_command_buffer",
+                "build_command_buffer",
+                "queue_submit",
+            ] {
+                assert!(names.contains(&name), "no `{}` span", name);
+            }
+        });
+    }
+}